@@ -0,0 +1,24 @@
+#!/bin/sh
+# Compile the engine shaders to the SPIR-V embedded by the crate (spirv/).
+# Run from any directory after editing a shader; commit the .spv files.
+# Needs glslangValidator (Vulkan SDK) on the PATH.
+set -e
+cd "$(dirname "$0")"
+mkdir -p spirv
+
+compile() { # <source> <output> [defines...]
+    src=$1; out=$2; shift 2
+    glslangValidator -V --target-env vulkan1.2 "$@" "$src" -o "spirv/$out" > /dev/null
+}
+
+compile standard_pbr.vert standard_pbr.vert.spv
+compile standard_pbr.frag standard_pbr.frag.spv
+for n in 1 2 3 4; do
+    compile standard_pbr.vert "standard_pbr_morph$n.vert.spv" -DMORPH_TARGET_COUNT=$n
+    compile standard_pbr.vert "standard_pbr_morph${n}_normals.vert.spv" -DMORPH_TARGET_COUNT=$n -DMORPH_NORMALS
+done
+
+compile post_fullscreen.vert post_fullscreen.vert.spv
+for effect in tonemap bloom_extract bloom_blur bloom_composite fxaa; do
+    compile "post_$effect.frag" "post_$effect.frag.spv"
+done
@@ -0,0 +1,33 @@
+#version 450
+
+// Galaxy3D bloom post effect, passes 1 and 2: separable Gaussian blur
+// (see galaxy_3d_engine/src/post/effects.rs).
+//
+// Interface:
+// - set 0, binding 0: source target (linear clamp)
+// - push constants: { vec2 direction; }, (1, 0) horizontal, (0, 1) vertical
+
+layout(set = 0, binding = 0) uniform sampler2D inputTexture;
+
+layout(push_constant) uniform Params {
+    vec2 direction;
+} params;
+
+layout(location = 0) in vec2 inUv;
+layout(location = 0) out vec4 outColor;
+
+// 9-tap Gaussian folded into 5 bilinear fetches
+const float OFFSETS[3] = float[](0.0, 1.3846153846, 3.2307692308);
+const float WEIGHTS[3] = float[](0.2270270270, 0.3162162162, 0.0702702703);
+
+void main() {
+    vec2 step = params.direction / vec2(textureSize(inputTexture, 0));
+
+    vec3 color = texture(inputTexture, inUv).rgb * WEIGHTS[0];
+    for (int i = 1; i < 3; ++i) {
+        color += texture(inputTexture, inUv + step * OFFSETS[i]).rgb * WEIGHTS[i];
+        color += texture(inputTexture, inUv - step * OFFSETS[i]).rgb * WEIGHTS[i];
+    }
+
+    outColor = vec4(color, 1.0);
+}
@@ -0,0 +1,25 @@
+#version 450
+
+// Galaxy3D bloom post effect, pass 3: additive composite
+// (see galaxy_3d_engine/src/post/effects.rs).
+//
+// Interface:
+// - set 0, binding 0: HDR input, binding 1: blurred bloom (linear clamp)
+// - push constants: { float intensity; }
+
+layout(set = 0, binding = 0) uniform sampler2D inputTexture;
+layout(set = 0, binding = 1) uniform sampler2D bloomTexture;
+
+layout(push_constant) uniform Params {
+    float intensity;
+} params;
+
+layout(location = 0) in vec2 inUv;
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    vec4 color = texture(inputTexture, inUv);
+    vec3 bloom = texture(bloomTexture, inUv).rgb;
+
+    outColor = vec4(color.rgb + bloom * params.intensity, color.a);
+}
@@ -0,0 +1,31 @@
+#version 450
+
+// Galaxy3D bloom post effect, pass 0: bright-pass extraction
+// (see galaxy_3d_engine/src/post/effects.rs).
+//
+// Interface:
+// - set 0, binding 0: HDR input (linear clamp)
+// - push constants: { float threshold; float knee; }
+
+layout(set = 0, binding = 0) uniform sampler2D inputTexture;
+
+layout(push_constant) uniform Params {
+    float threshold;
+    float knee;
+} params;
+
+layout(location = 0) in vec2 inUv;
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    vec3 color = texture(inputTexture, inUv).rgb;
+    float brightness = max(color.r, max(color.g, color.b));
+
+    // Quadratic soft knee over [threshold - knee, threshold + knee]
+    float knee = max(params.knee, 1e-5);
+    float soft = clamp(brightness - params.threshold + knee, 0.0, 2.0 * knee);
+    soft = soft * soft / (4.0 * knee);
+    float contribution = max(soft, brightness - params.threshold) / max(brightness, 1e-5);
+
+    outColor = vec4(color * contribution, 1.0);
+}
@@ -0,0 +1,14 @@
+#version 450
+
+// Galaxy3D post-processing vertex shader: one fullscreen triangle.
+//
+// Interface (see galaxy_3d_engine/src/post/post_effect.rs):
+// - drawn with draw(3, 0), no vertex buffer
+// - output: uv (location 0), (0, 0) at the top-left corner
+
+layout(location = 0) out vec2 outUv;
+
+void main() {
+    outUv = vec2((gl_VertexIndex << 1) & 2, gl_VertexIndex & 2);
+    gl_Position = vec4(outUv * 2.0 - 1.0, 0.0, 1.0);
+}
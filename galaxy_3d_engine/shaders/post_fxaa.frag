@@ -0,0 +1,124 @@
+#version 450
+
+// Galaxy3D FXAA post effect, after FXAA 3.11 (Timothy Lottes), quality
+// preset 12 (see galaxy_3d_engine/src/post/effects.rs).
+//
+// Interface:
+// - set 0, binding 0: LDR input (linear clamp), after tonemapping
+// - push constants: { float subpixel; float edgeThreshold; float edgeThresholdMin; }
+
+layout(set = 0, binding = 0) uniform sampler2D inputTexture;
+
+layout(push_constant) uniform Params {
+    float subpixel;
+    float edgeThreshold;
+    float edgeThresholdMin;
+} params;
+
+layout(location = 0) in vec2 inUv;
+layout(location = 0) out vec4 outColor;
+
+const int SEARCH_STEPS = 5;
+const float SEARCH_STEP_SIZES[SEARCH_STEPS] = float[](1.0, 1.5, 2.0, 4.0, 12.0);
+
+float luma(vec3 color) {
+    return dot(color, vec3(0.299, 0.587, 0.114));
+}
+
+float lumaAt(vec2 uv) {
+    return luma(texture(inputTexture, uv).rgb);
+}
+
+void main() {
+    vec2 texel = 1.0 / vec2(textureSize(inputTexture, 0));
+    vec4 center = texture(inputTexture, inUv);
+
+    float lumaM = luma(center.rgb);
+    float lumaN = lumaAt(inUv + vec2(0.0, -texel.y));
+    float lumaS = lumaAt(inUv + vec2(0.0, texel.y));
+    float lumaW = lumaAt(inUv + vec2(-texel.x, 0.0));
+    float lumaE = lumaAt(inUv + vec2(texel.x, 0.0));
+
+    float lumaMax = max(lumaM, max(max(lumaN, lumaS), max(lumaW, lumaE)));
+    float lumaMin = min(lumaM, min(min(lumaN, lumaS), min(lumaW, lumaE)));
+    float range = lumaMax - lumaMin;
+    if (range < max(params.edgeThresholdMin, lumaMax * params.edgeThreshold)) {
+        outColor = center;
+        return;
+    }
+
+    float lumaNW = lumaAt(inUv + vec2(-texel.x, -texel.y));
+    float lumaNE = lumaAt(inUv + vec2(texel.x, -texel.y));
+    float lumaSW = lumaAt(inUv + vec2(-texel.x, texel.y));
+    float lumaSE = lumaAt(inUv + vec2(texel.x, texel.y));
+
+    // Sub-pixel blend from the 3x3 neighborhood contrast
+    float average = (2.0 * (lumaN + lumaS + lumaW + lumaE) + lumaNW + lumaNE + lumaSW + lumaSE) / 12.0;
+    float subpixelBlend = clamp(abs(average - lumaM) / range, 0.0, 1.0);
+    subpixelBlend = smoothstep(0.0, 1.0, subpixelBlend);
+    subpixelBlend = subpixelBlend * subpixelBlend * params.subpixel;
+
+    // Edge orientation
+    float edgeHorizontal = abs(lumaNW + lumaNE - 2.0 * lumaN)
+        + 2.0 * abs(lumaW + lumaE - 2.0 * lumaM)
+        + abs(lumaSW + lumaSE - 2.0 * lumaS);
+    float edgeVertical = abs(lumaNW + lumaSW - 2.0 * lumaW)
+        + 2.0 * abs(lumaN + lumaS - 2.0 * lumaM)
+        + abs(lumaNE + lumaSE - 2.0 * lumaE);
+    bool horizontal = edgeHorizontal >= edgeVertical;
+
+    // Side of the edge with the strongest gradient
+    float lumaPositive = horizontal ? lumaS : lumaE;
+    float lumaNegative = horizontal ? lumaN : lumaW;
+    float gradientPositive = abs(lumaPositive - lumaM);
+    float gradientNegative = abs(lumaNegative - lumaM);
+    float stepLength = horizontal ? texel.y : texel.x;
+    float lumaEdge;
+    float gradient;
+    if (gradientPositive >= gradientNegative) {
+        lumaEdge = 0.5 * (lumaPositive + lumaM);
+        gradient = gradientPositive;
+    } else {
+        stepLength = -stepLength;
+        lumaEdge = 0.5 * (lumaNegative + lumaM);
+        gradient = gradientNegative;
+    }
+
+    // Walk along the edge in both directions until the luma leaves it
+    vec2 edgeUv = inUv + (horizontal ? vec2(0.0, 0.5 * stepLength) : vec2(0.5 * stepLength, 0.0));
+    vec2 edgeStep = horizontal ? vec2(texel.x, 0.0) : vec2(0.0, texel.y);
+    float gradientScaled = 0.25 * gradient;
+    vec2 uvPositive = edgeUv;
+    vec2 uvNegative = edgeUv;
+    float deltaPositive = 0.0;
+    float deltaNegative = 0.0;
+    bool donePositive = false;
+    bool doneNegative = false;
+    for (int i = 0; i < SEARCH_STEPS && !(donePositive && doneNegative); ++i) {
+        if (!donePositive) {
+            uvPositive += edgeStep * SEARCH_STEP_SIZES[i];
+            deltaPositive = lumaAt(uvPositive) - lumaEdge;
+            donePositive = abs(deltaPositive) >= gradientScaled;
+        }
+        if (!doneNegative) {
+            uvNegative -= edgeStep * SEARCH_STEP_SIZES[i];
+            deltaNegative = lumaAt(uvNegative) - lumaEdge;
+            doneNegative = abs(deltaNegative) >= gradientScaled;
+        }
+    }
+
+    float distancePositive = horizontal ? uvPositive.x - inUv.x : uvPositive.y - inUv.y;
+    float distanceNegative = horizontal ? inUv.x - uvNegative.x : inUv.y - uvNegative.y;
+    bool positiveCloser = distancePositive < distanceNegative;
+    float closest = min(distancePositive, distanceNegative);
+    float edgeLength = distancePositive + distanceNegative;
+
+    // Only blend when the end of the edge is on the other side of the center
+    bool centerBelowEdge = lumaM < lumaEdge;
+    bool endBelowEdge = (positiveCloser ? deltaPositive : deltaNegative) < 0.0;
+    float edgeBlend = centerBelowEdge != endBelowEdge ? 0.5 - closest / edgeLength : 0.0;
+
+    float blend = max(edgeBlend, subpixelBlend);
+    vec2 uv = inUv + (horizontal ? vec2(0.0, blend * stepLength) : vec2(blend * stepLength, 0.0));
+    outColor = vec4(texture(inputTexture, uv).rgb, center.a);
+}
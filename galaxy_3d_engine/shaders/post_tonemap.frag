@@ -0,0 +1,37 @@
+#version 450
+
+// Galaxy3D tonemap post effect (see galaxy_3d_engine/src/post/effects.rs).
+//
+// Interface:
+// - set 0, binding 0: HDR input (linear clamp)
+// - push constants: { float exposure; uint operator; float gamma; }
+
+// TonemapOperator
+const uint OPERATOR_REINHARD = 0u;
+const uint OPERATOR_ACES = 1u;
+
+layout(set = 0, binding = 0) uniform sampler2D inputTexture;
+
+layout(push_constant) uniform Params {
+    float exposure;
+    uint operator;
+    float gamma;
+} params;
+
+layout(location = 0) in vec2 inUv;
+layout(location = 0) out vec4 outColor;
+
+// ACES filmic curve fit (Krzysztof Narkowicz)
+vec3 aces(vec3 c) {
+    return clamp((c * (2.51 * c + 0.03)) / (c * (2.43 * c + 0.59) + 0.14), 0.0, 1.0);
+}
+
+void main() {
+    vec4 hdr = texture(inputTexture, inUv);
+    vec3 color = max(hdr.rgb * params.exposure, vec3(0.0));
+
+    color = params.operator == OPERATOR_REINHARD ? color / (1.0 + color) : aces(color);
+    color = pow(color, vec3(1.0 / params.gamma));
+
+    outColor = vec4(color, hdr.a);
+}
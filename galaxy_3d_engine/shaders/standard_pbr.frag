@@ -0,0 +1,387 @@
+#version 450
+#extension GL_EXT_nonuniform_qualifier : require
+
+// Galaxy3D standard PBR fragment shader (metallic-roughness, GGX).
+//
+// Interface (see galaxy_3d_engine/src/resource/standard_pbr.rs):
+// - set 0: bindless 2D textures (0) and samplers (4)
+// - set 1: frame UBO (0), instance SSBO (1), material SSBO (2), light SSBO (3),
+//   light cluster SSBO (4), light index SSBO (5)
+// - lights: the clustered lists when frame.lightClusterGrid is set
+//   (LightClusterGrid::upload), else the instance's top-8 lights
+// - output: linear HDR radiance (exposure and tone mapping are post effects)
+
+layout(constant_id = 0) const uint ENGINE_FEATURES = 0u;
+// EngineFeatures::FOG
+const uint FEATURE_FOG = 0x02u;
+// EngineFeatures::UNLIT
+const uint FEATURE_UNLIT = 0x10u;
+
+layout(set = 0, binding = 0) uniform texture2D textures2D[];
+layout(set = 0, binding = 4) uniform sampler samplers[6];
+
+layout(std140, set = 1, binding = 0) uniform Frame {
+    mat4 view;
+    mat4 projection;
+    mat4 viewProjection;
+    vec4 cameraPosition;
+    vec4 cameraDirection;
+    vec4 sunDirection;
+    vec4 sunColor;
+    vec4 ambientColor;
+    float time;
+    float deltaTime;
+    uint frameIndex;
+    float exposure;
+    float gamma;
+    float nearPlane;
+    float farPlane;
+    float ambientIntensity;
+    vec4 fogColor;
+    vec4 fogParams;
+    float fogBaseHeight;
+    uint fogMode;
+    mat4 previousViewProjection;
+    vec4 jitter;
+    uvec4 lightClusterGrid;
+    vec4 lightClusterDepth;
+} frame;
+
+struct Instance {
+    mat4 world;
+    mat4 previousWorld;
+    mat4 inverseWorld;
+    uint materialSlotId;
+    uint flags;
+    uint lightCount;
+    vec4 customData;
+    uvec4 lightIndices0;
+    uvec4 lightIndices1;
+    vec4 morphWeights;
+    vec4 colorTint;
+    vec4 emissiveTint;
+};
+
+struct Material {
+    vec4 baseColor;
+    vec4 emissiveColor;
+    float metallic;
+    float roughness;
+    float normalScale;
+    float ao;
+    float alphaCutoff;
+    float ior;
+    uint albedoTexture;
+    uint albedoSampler;
+    uint albedoLayer;
+    uint normalTexture;
+    uint normalSampler;
+    uint normalLayer;
+    uint metallicRoughnessTexture;
+    uint metallicRoughnessSampler;
+    uint metallicRoughnessLayer;
+    uint emissiveTexture;
+    uint emissiveSampler;
+    uint emissiveLayer;
+    uint aoTexture;
+    uint aoSampler;
+    uint aoLayer;
+    uint flags;
+    vec4 albedoUvRect;
+    vec4 emissiveUvRect;
+    vec2 uvScroll;
+    float uvRotationSpeed;
+};
+
+struct Light {
+    vec4 positionType;
+    vec4 directionRange;
+    vec4 colorIntensity;
+    vec4 spotParams;
+    vec4 attenuation;
+};
+
+layout(std430, set = 1, binding = 1) readonly buffer Instances {
+    Instance instances[];
+};
+
+layout(std430, set = 1, binding = 2) readonly buffer Materials {
+    Material materials[];
+};
+
+struct LightCluster {
+    uint lightOffset;
+    uint lightCount;
+};
+
+layout(std430, set = 1, binding = 3) readonly buffer Lights {
+    Light lights[];
+};
+
+layout(std430, set = 1, binding = 4) readonly buffer LightClusters {
+    LightCluster lightClusters[];
+};
+
+layout(std430, set = 1, binding = 5) readonly buffer LightIndices {
+    uint lightIndices[];
+};
+
+layout(location = 0) in vec3 inWorldPosition;
+layout(location = 1) in vec3 inWorldNormal;
+layout(location = 2) in vec2 inUv;
+layout(location = 3) flat in uint inDrawSlot;
+
+layout(location = 0) out vec4 outColor;
+
+const float PI = 3.14159265359;
+// Unbound texture slot (default material buffer value)
+const uint NO_TEXTURE = 0xFFFFFFFFu;
+// Unused light index (default instance buffer value)
+const uint NO_LIGHT = 0xFFFFFFFFu;
+const uint MAX_INSTANCE_LIGHTS = 8u;
+// Fragment outside the light cluster grid
+const uint NO_CLUSTER = 0xFFFFFFFFu;
+// LightType::Spot
+const uint LIGHT_TYPE_SPOT = 1u;
+// FogMode
+const uint FOG_LINEAR = 1u;
+const uint FOG_EXPONENTIAL = 2u;
+const uint FOG_EXPONENTIAL_SQUARED = 3u;
+// Reflectance of dielectrics at normal incidence
+const vec3 DIELECTRIC_F0 = vec3(0.04);
+// Avoids singular highlights on perfectly smooth surfaces
+const float MIN_ROUGHNESS = 0.045;
+const float EPSILON = 1e-4;
+// LodFade encoding (LOD_FADE_*)
+const uint DRAW_SLOT_MASK = 0x00FFFFFFu;
+const uint LOD_FADE_LEVEL_SHIFT = 24u;
+const uint LOD_FADE_LEVEL_MASK = 0x7Fu;
+const float LOD_FADE_LEVELS = 128.0;
+const uint LOD_FADE_OUT_BIT = 0x80000000u;
+
+// ===== TEXTURES =====
+
+vec4 sampleTexture(uint textureIndex, uint samplerIndex, vec2 uv, vec2 uvDx, vec2 uvDy, vec4 fallback) {
+    if (textureIndex == NO_TEXTURE) {
+        return fallback;
+    }
+    return textureGrad(
+        sampler2D(textures2D[nonuniformEXT(textureIndex)], samplers[samplerIndex]), uv, uvDx, uvDy);
+}
+
+// Slot with an atlas region (`{slot}UvRect`, flipbooks)
+vec4 sampleRegion(uint textureIndex, uint samplerIndex, vec2 uv, vec2 uvDx, vec2 uvDy, vec4 rect, vec4 fallback) {
+    return sampleTexture(textureIndex, samplerIndex,
+        rect.xy + fract(uv) * rect.zw, uvDx * rect.zw, uvDy * rect.zw, fallback);
+}
+
+// UV scrolling and rotation (material::animated_uv)
+vec2 animatedUv(vec2 uv, Material material) {
+    float angle = frame.time * material.uvRotationSpeed;
+    float s = sin(angle);
+    float c = cos(angle);
+    vec2 centered = uv - vec2(0.5);
+    return vec2(centered.x * c - centered.y * s, centered.x * s + centered.y * c)
+        + vec2(0.5) + frame.time * material.uvScroll;
+}
+
+// Tangent frame from screen derivatives (no tangent attribute needed)
+mat3 cotangentFrame(vec3 n, vec3 p, vec2 uv) {
+    vec3 dp1 = dFdx(p);
+    vec3 dp2 = dFdy(p);
+    vec2 duv1 = dFdx(uv);
+    vec2 duv2 = dFdy(uv);
+    vec3 dp2perp = cross(dp2, n);
+    vec3 dp1perp = cross(n, dp1);
+    vec3 t = dp2perp * duv1.x + dp1perp * duv2.x;
+    vec3 b = dp2perp * duv1.y + dp1perp * duv2.y;
+    float invMax = inversesqrt(max(max(dot(t, t), dot(b, b)), EPSILON * EPSILON));
+    return mat3(t * invMax, b * invMax, n);
+}
+
+// ===== BRDF =====
+
+float distributionGgx(float nDotH, float alpha) {
+    float alpha2 = alpha * alpha;
+    float d = nDotH * nDotH * (alpha2 - 1.0) + 1.0;
+    return alpha2 / (PI * d * d);
+}
+
+// Height-correlated Smith term, divided by 4 N.L N.V
+float visibilitySmithGgx(float nDotV, float nDotL, float alpha) {
+    float alpha2 = alpha * alpha;
+    float ggxV = nDotL * sqrt(nDotV * nDotV * (1.0 - alpha2) + alpha2);
+    float ggxL = nDotV * sqrt(nDotL * nDotL * (1.0 - alpha2) + alpha2);
+    return 0.5 / max(ggxV + ggxL, EPSILON);
+}
+
+vec3 fresnelSchlick(float vDotH, vec3 f0) {
+    return f0 + (1.0 - f0) * pow(1.0 - vDotH, 5.0);
+}
+
+vec3 shade(vec3 n, vec3 v, vec3 l, vec3 radiance, vec3 albedo, vec3 f0, float metallic, float alpha) {
+    float nDotL = clamp(dot(n, l), 0.0, 1.0);
+    if (nDotL <= 0.0) {
+        return vec3(0.0);
+    }
+    vec3 h = normalize(v + l);
+    float nDotV = clamp(abs(dot(n, v)), EPSILON, 1.0);
+    float nDotH = clamp(dot(n, h), 0.0, 1.0);
+    float vDotH = clamp(dot(v, h), 0.0, 1.0);
+
+    vec3 f = fresnelSchlick(vDotH, f0);
+    vec3 specular = f * distributionGgx(nDotH, alpha) * visibilitySmithGgx(nDotV, nDotL, alpha);
+    vec3 diffuse = (1.0 - f) * (1.0 - metallic) * albedo / PI;
+    return (diffuse + specular) * radiance * nDotL;
+}
+
+// ===== LIGHTS =====
+
+vec3 pointLight(Light light, vec3 position, out vec3 l) {
+    vec3 toLight = light.positionType.xyz - position;
+    float distance = length(toLight);
+    l = toLight / max(distance, EPSILON);
+
+    float range = light.directionRange.w;
+    float window = clamp(1.0 - pow(distance / max(range, EPSILON), 4.0), 0.0, 1.0);
+    vec3 k = light.attenuation.xyz;
+    float falloff = window * window / max(k.x + k.y * distance + k.z * distance * distance, EPSILON);
+
+    if (uint(light.positionType.w) == LIGHT_TYPE_SPOT) {
+        float cosOuter = cos(light.spotParams.y);
+        float cosInner = cos(light.spotParams.x);
+        float cosAngle = dot(-l, normalize(light.directionRange.xyz));
+        falloff *= smoothstep(cosOuter, max(cosInner, cosOuter + EPSILON), cosAngle);
+    }
+    return light.colorIntensity.rgb * light.colorIntensity.w * falloff;
+}
+
+// Cluster of a world position (LightClusterGrid::cluster_for_view_position)
+uint lightCluster(vec3 position) {
+    float depth = -(frame.view * vec4(position, 1.0)).z;
+    float near = frame.lightClusterDepth.x;
+    float far = frame.lightClusterDepth.y;
+    vec4 clip = frame.viewProjection * vec4(position, 1.0);
+    vec2 ndc = clip.xy / clip.w;
+    if (depth < near || depth > far || any(greaterThan(abs(ndc), vec2(1.0)))) {
+        return NO_CLUSTER;
+    }
+    uvec3 grid = frame.lightClusterGrid.xyz;
+    uint slice = min(uint(log(depth / near) / log(far / near) * float(grid.z)), grid.z - 1u);
+    uvec2 tile = min(uvec2((ndc * 0.5 + 0.5) * vec2(grid.xy)), grid.xy - 1u);
+    return tile.x + (tile.y + slice * grid.y) * grid.x;
+}
+
+// ===== FOG =====
+
+vec3 applyFog(vec3 color, vec3 position) {
+    if ((ENGINE_FEATURES & FEATURE_FOG) == 0u || frame.fogMode == 0u) {
+        return color;
+    }
+    float distance = length(position - frame.cameraPosition.xyz);
+    float amount = 0.0;
+    if (frame.fogMode == FOG_LINEAR) {
+        amount = clamp((distance - frame.fogParams.x)
+            / max(frame.fogParams.y - frame.fogParams.x, EPSILON), 0.0, 1.0);
+    } else if (frame.fogMode == FOG_EXPONENTIAL) {
+        amount = 1.0 - exp(-frame.fogParams.z * distance);
+    } else if (frame.fogMode == FOG_EXPONENTIAL_SQUARED) {
+        float density = frame.fogParams.z * distance;
+        amount = 1.0 - exp(-density * density);
+    }
+    amount *= min(exp(-frame.fogParams.w * (position.y - frame.fogBaseHeight)), 1.0);
+    return mix(color, frame.fogColor.rgb, clamp(amount, 0.0, 1.0));
+}
+
+// ===== LOD CROSS-FADE =====
+
+float dither(vec2 pixel) {
+    return fract(52.9829189 * fract(dot(pixel, vec2(0.06711056, 0.00583715))));
+}
+
+void main() {
+    uint level = (inDrawSlot >> LOD_FADE_LEVEL_SHIFT) & LOD_FADE_LEVEL_MASK;
+    if (level != 0u) {
+        bool fadingOut = (inDrawSlot & LOD_FADE_OUT_BIT) != 0u;
+        bool covered = dither(gl_FragCoord.xy) < float(level) / LOD_FADE_LEVELS;
+        if (covered == fadingOut) {
+            discard;
+        }
+    }
+
+    Instance instance = instances[inDrawSlot & DRAW_SLOT_MASK];
+    Material material = materials[instance.materialSlotId];
+
+    vec2 uv = animatedUv(inUv, material);
+    vec2 uvDx = dFdx(uv);
+    vec2 uvDy = dFdy(uv);
+    mat3 tbn = cotangentFrame(normalize(inWorldNormal), inWorldPosition, uv);
+
+    // Material inputs
+    vec4 baseColor = material.baseColor * instance.colorTint * sampleRegion(
+        material.albedoTexture, material.albedoSampler, uv, uvDx, uvDy, material.albedoUvRect, vec4(1.0));
+    vec4 metallicRoughness = sampleTexture(
+        material.metallicRoughnessTexture, material.metallicRoughnessSampler, uv, uvDx, uvDy, vec4(1.0));
+    float metallic = clamp(material.metallic * metallicRoughness.b, 0.0, 1.0);
+    float roughness = clamp(material.roughness * metallicRoughness.g, MIN_ROUGHNESS, 1.0);
+    float occlusion = material.ao * sampleTexture(
+        material.aoTexture, material.aoSampler, uv, uvDx, uvDy, vec4(1.0)).r;
+    vec3 emissive = material.emissiveColor.rgb * sampleRegion(
+        material.emissiveTexture, material.emissiveSampler, uv, uvDx, uvDy, material.emissiveUvRect, vec4(1.0)).rgb
+        + instance.emissiveTint.rgb;
+
+    if ((ENGINE_FEATURES & FEATURE_UNLIT) != 0u) {
+        outColor = vec4(baseColor.rgb + emissive, baseColor.a);
+        return;
+    }
+
+    vec3 n = tbn[2];
+    if (material.normalTexture != NO_TEXTURE) {
+        vec3 tangentNormal = sampleTexture(
+            material.normalTexture, material.normalSampler, uv, uvDx, uvDy, vec4(0.5, 0.5, 1.0, 1.0)).xyz * 2.0 - 1.0;
+        tangentNormal.xy *= material.normalScale;
+        n = normalize(tbn * tangentNormal);
+    }
+    vec3 v = normalize(frame.cameraPosition.xyz - inWorldPosition);
+    if (!gl_FrontFacing) {
+        n = -n;
+    }
+
+    vec3 albedo = baseColor.rgb;
+    vec3 f0 = mix(DIELECTRIC_F0, albedo, metallic);
+    float alpha = roughness * roughness;
+
+    // Sun
+    vec3 color = shade(n, v, normalize(-frame.sunDirection.xyz), frame.sunColor.rgb * frame.sunColor.a,
+        albedo, f0, metallic, alpha);
+
+    // Point and spot lights of the fragment's cluster
+    if (frame.lightClusterGrid.z != 0u) {
+        uint cluster = lightCluster(inWorldPosition);
+        LightCluster range = cluster == NO_CLUSTER ? LightCluster(0u, 0u) : lightClusters[cluster];
+        for (uint i = 0u; i < range.lightCount; i++) {
+            vec3 l;
+            vec3 radiance = pointLight(lights[lightIndices[range.lightOffset + i]], inWorldPosition, l);
+            color += shade(n, v, l, radiance, albedo, f0, metallic, alpha);
+        }
+    } else {
+        // Without a cluster grid, the lights assigned to the instance
+        uint lightCount = min(instance.lightCount, MAX_INSTANCE_LIGHTS);
+        for (uint i = 0u; i < lightCount; i++) {
+            uint lightIndex = i < 4u ? instance.lightIndices0[i] : instance.lightIndices1[i - 4u];
+            if (lightIndex == NO_LIGHT) {
+                continue;
+            }
+            vec3 l;
+            vec3 radiance = pointLight(lights[lightIndex], inWorldPosition, l);
+            color += shade(n, v, l, radiance, albedo, f0, metallic, alpha);
+        }
+    }
+
+    // Ambient
+    vec3 ambient = frame.ambientColor.rgb * frame.ambientIntensity;
+    color += ambient * albedo * (1.0 - metallic) * occlusion + ambient * f0 * occlusion;
+
+    color += emissive;
+    outColor = vec4(applyFog(color, inWorldPosition), baseColor.a);
+}
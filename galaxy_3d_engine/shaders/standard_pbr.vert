@@ -0,0 +1,105 @@
+#version 450
+
+// Galaxy3D standard PBR vertex shader.
+//
+// Interface (see galaxy_3d_engine/src/resource/standard_pbr.rs):
+// - vertex inputs: position (0), normal (1), uv (2)
+// - morph variants (MORPH_TARGET_COUNT defined, 1 to 4): position deltas at
+//   7.., normal deltas at 11.. with MORPH_NORMALS (geometry.rs
+//   MORPH_TARGET_FIRST_LOCATION), blended with Instance.morphWeights
+// - set 1: frame UBO (0), instance SSBO (1), material SSBO (2), light SSBO (3)
+// - push constant: draw slot (ForwardDrawer), LOD fade in the upper bits
+
+layout(location = 0) in vec3 inPosition;
+layout(location = 1) in vec3 inNormal;
+layout(location = 2) in vec2 inUv;
+
+#ifdef MORPH_TARGET_COUNT
+// MORPH_TARGET_FIRST_LOCATION, MORPH_TARGET_FIRST_LOCATION + MAX_MORPH_TARGETS
+layout(location = 7) in vec3 inMorphPosition[MORPH_TARGET_COUNT];
+#ifdef MORPH_NORMALS
+layout(location = 11) in vec3 inMorphNormal[MORPH_TARGET_COUNT];
+#endif
+#endif
+
+layout(std140, set = 1, binding = 0) uniform Frame {
+    mat4 view;
+    mat4 projection;
+    mat4 viewProjection;
+    vec4 cameraPosition;
+    vec4 cameraDirection;
+    vec4 sunDirection;
+    vec4 sunColor;
+    vec4 ambientColor;
+    float time;
+    float deltaTime;
+    uint frameIndex;
+    float exposure;
+    float gamma;
+    float nearPlane;
+    float farPlane;
+    float ambientIntensity;
+    vec4 fogColor;
+    vec4 fogParams;
+    float fogBaseHeight;
+    uint fogMode;
+    mat4 previousViewProjection;
+    vec4 jitter;
+    uvec4 lightClusterGrid;
+    vec4 lightClusterDepth;
+} frame;
+
+struct Instance {
+    mat4 world;
+    mat4 previousWorld;
+    mat4 inverseWorld;
+    uint materialSlotId;
+    uint flags;
+    uint lightCount;
+    vec4 customData;
+    uvec4 lightIndices0;
+    uvec4 lightIndices1;
+    vec4 morphWeights;
+    vec4 colorTint;
+    vec4 emissiveTint;
+};
+
+layout(std430, set = 1, binding = 1) readonly buffer Instances {
+    Instance instances[];
+};
+
+layout(push_constant) uniform Draw {
+    uint drawSlot;
+};
+
+// LOD_FADE_SLOT_MASK
+const uint DRAW_SLOT_MASK = 0x00FFFFFFu;
+
+layout(location = 0) out vec3 outWorldPosition;
+layout(location = 1) out vec3 outWorldNormal;
+layout(location = 2) out vec2 outUv;
+layout(location = 3) flat out uint outDrawSlot;
+
+// Same positions in the depth pre-pass and the shading pass
+invariant gl_Position;
+
+void main() {
+    Instance instance = instances[drawSlot & DRAW_SLOT_MASK];
+    vec3 position = inPosition;
+    vec3 normal = inNormal;
+#ifdef MORPH_TARGET_COUNT
+    for (int t = 0; t < MORPH_TARGET_COUNT; ++t) {
+        position += instance.morphWeights[t] * inMorphPosition[t];
+#ifdef MORPH_NORMALS
+        normal += instance.morphWeights[t] * inMorphNormal[t];
+#endif
+    }
+#endif
+    vec4 worldPosition = instance.world * vec4(position, 1.0);
+
+    outWorldPosition = worldPosition.xyz;
+    outWorldNormal = transpose(mat3(instance.inverseWorld)) * normal;
+    outUv = inUv;
+    outDrawSlot = drawSlot;
+    gl_Position = frame.viewProjection * worldPosition;
+}
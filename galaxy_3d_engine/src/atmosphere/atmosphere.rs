@@ -0,0 +1,173 @@
+/// Physical description of a planet atmosphere.
+///
+/// Distances are in kilometers and coefficients per kilometer, as in the
+/// Hillaire 2020 reference (defaults are the Earth values). The density of
+/// each medium depends on the altitude `h` above the ground:
+///
+/// - Rayleigh (air molecules): `exp(-h / rayleigh_scale_height)`
+/// - Mie (aerosols): `exp(-h / mie_scale_height)`
+/// - Ozone (absorption only): tent of `ozone_width` centered on
+///   `ozone_center`
+///
+/// `transmittance()` is the CPU version of the transmittance LUT, used to
+/// tint the sun light for the current time of day.
+
+use glam::Vec3;
+use crate::error::Result;
+use crate::engine_bail;
+
+/// Ray-march steps of the CPU transmittance
+const TRANSMITTANCE_STEPS: u32 = 40;
+
+/// Atmosphere parameters (see module docs).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Atmosphere {
+    /// Planet radius
+    pub bottom_radius: f32,
+    /// Radius of the top of the atmosphere
+    pub top_radius: f32,
+    pub rayleigh_scattering: Vec3,
+    pub rayleigh_scale_height: f32,
+    pub mie_scattering: Vec3,
+    /// Mie scattering plus absorption
+    pub mie_extinction: Vec3,
+    pub mie_scale_height: f32,
+    /// Anisotropy of the Mie phase function (Cornette-Shanks), in -1..1
+    pub mie_g: f32,
+    pub ozone_absorption: Vec3,
+    /// Altitude of the densest ozone
+    pub ozone_center: f32,
+    /// Thickness of the ozone layer
+    pub ozone_width: f32,
+    /// Albedo of the ground, bounced light of the multiple scattering LUT
+    pub ground_albedo: Vec3,
+}
+
+impl Default for Atmosphere {
+    fn default() -> Self {
+        Self::earth()
+    }
+}
+
+impl Atmosphere {
+    /// Earth atmosphere of the Hillaire 2020 reference
+    pub fn earth() -> Self {
+        Self {
+            bottom_radius: 6360.0,
+            top_radius: 6460.0,
+            rayleigh_scattering: Vec3::new(5.802e-3, 13.558e-3, 33.1e-3),
+            rayleigh_scale_height: 8.0,
+            mie_scattering: Vec3::splat(3.996e-3),
+            mie_extinction: Vec3::splat(4.40e-3),
+            mie_scale_height: 1.2,
+            mie_g: 0.8,
+            ozone_absorption: Vec3::new(0.650e-3, 1.881e-3, 0.085e-3),
+            ozone_center: 25.0,
+            ozone_width: 30.0,
+            ground_albedo: Vec3::splat(0.3),
+        }
+    }
+
+    /// Check the parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a radius or scale height is not positive, if the
+    /// top is not above the ground, if a coefficient is negative or not
+    /// finite, if the Mie extinction is below its scattering, or if `mie_g`
+    /// is outside -1..1.
+    pub fn validate(&self) -> Result<()> {
+        let positive = |value: f32| value > 0.0 && value.is_finite();
+        if !(positive(self.bottom_radius) && self.top_radius > self.bottom_radius && self.top_radius.is_finite()) {
+            engine_bail!("galaxy3d::Atmosphere",
+                "Radii must satisfy 0 < bottom < top, got {} and {}", self.bottom_radius, self.top_radius);
+        }
+        if !positive(self.rayleigh_scale_height) || !positive(self.mie_scale_height) || !positive(self.ozone_width) {
+            engine_bail!("galaxy3d::Atmosphere",
+                "Scale heights and ozone width must be positive, got {}, {} and {}",
+                self.rayleigh_scale_height, self.mie_scale_height, self.ozone_width);
+        }
+        let coefficients = [
+            ("rayleigh_scattering", self.rayleigh_scattering),
+            ("mie_scattering", self.mie_scattering),
+            ("mie_extinction", self.mie_extinction),
+            ("ozone_absorption", self.ozone_absorption),
+            ("ground_albedo", self.ground_albedo),
+        ];
+        for (name, value) in coefficients {
+            if !(value.min_element() >= 0.0 && value.is_finite()) {
+                engine_bail!("galaxy3d::Atmosphere", "{} must be non-negative, got {:?}", name, value);
+            }
+        }
+        if self.mie_extinction.cmplt(self.mie_scattering).any() {
+            engine_bail!("galaxy3d::Atmosphere",
+                "mie_extinction {:?} is below mie_scattering {:?}", self.mie_extinction, self.mie_scattering);
+        }
+        if !(self.mie_g > -1.0 && self.mie_g < 1.0) {
+            engine_bail!("galaxy3d::Atmosphere", "mie_g must be in -1..1, got {}", self.mie_g);
+        }
+        Ok(())
+    }
+
+    /// Extinction (scattering plus absorption) at `altitude`
+    pub fn extinction(&self, altitude: f32) -> Vec3 {
+        let rayleigh = (-altitude / self.rayleigh_scale_height).exp();
+        let mie = (-altitude / self.mie_scale_height).exp();
+        let ozone = (1.0 - (altitude - self.ozone_center).abs() / (self.ozone_width * 0.5)).max(0.0);
+        self.rayleigh_scattering * rayleigh + self.mie_extinction * mie + self.ozone_absorption * ozone
+    }
+
+    /// Fraction of the light reaching `altitude` from the direction at
+    /// `cos_zenith` (1 = straight up). Zero when the planet blocks the
+    /// direction.
+    pub fn transmittance(&self, altitude: f32, cos_zenith: f32) -> Vec3 {
+        let radius = (self.bottom_radius + altitude).clamp(self.bottom_radius, self.top_radius);
+        let cos_zenith = cos_zenith.clamp(-1.0, 1.0);
+        // Below the horizon the ray hits the ground
+        let cos_horizon = -(1.0 - (self.bottom_radius / radius).powi(2)).max(0.0).sqrt();
+        if cos_zenith < cos_horizon {
+            return Vec3::ZERO;
+        }
+        let Some(length) = ray_sphere_distance(radius, cos_zenith, self.top_radius) else {
+            return Vec3::ONE;
+        };
+
+        let step = length / TRANSMITTANCE_STEPS as f32;
+        let mut optical_depth = Vec3::ZERO;
+        for index in 0..TRANSMITTANCE_STEPS {
+            let t = (index as f32 + 0.5) * step;
+            // Distance to the planet center along the ray
+            let r = (radius * radius + t * t + 2.0 * radius * t * cos_zenith).sqrt();
+            optical_depth += self.extinction(r - self.bottom_radius) * step;
+        }
+        (-optical_depth).exp()
+    }
+
+    /// Push constant block of the atmosphere (std430, 96 bytes): `vec3` and
+    /// `float` pairs in the shader declaration order
+    pub(crate) fn to_bytes(self) -> Vec<u8> {
+        let pair = |v: Vec3, w: f32| [v.x, v.y, v.z, w];
+        let floats = [
+            pair(self.rayleigh_scattering, self.rayleigh_scale_height),
+            pair(self.mie_scattering, self.mie_scale_height),
+            pair(self.mie_extinction, self.mie_g),
+            pair(self.ozone_absorption, self.ozone_center),
+            pair(self.ground_albedo, self.ozone_width),
+            [self.bottom_radius, self.top_radius, 0.0, 0.0],
+        ];
+        bytemuck::cast_slice(&floats).to_vec()
+    }
+}
+
+/// Distance from a point at `radius` from the planet center, looking at
+/// `cos_zenith`, to the exit of the sphere `sphere_radius` around it
+fn ray_sphere_distance(radius: f32, cos_zenith: f32, sphere_radius: f32) -> Option<f32> {
+    let b = radius * cos_zenith;
+    let discriminant = b * b - radius * radius + sphere_radius * sphere_radius;
+    let exit = -b + discriminant.max(0.0).sqrt();
+    (exit > 0.0).then_some(exit)
+}
+
+#[cfg(test)]
+#[path = "atmosphere_tests.rs"]
+mod tests;
@@ -0,0 +1,375 @@
+/// Atmosphere lookup table baking.
+///
+/// `AtmosphereBaker` renders the three lookup tables of a Hillaire-style
+/// sky into `ResourceManager` textures:
+///
+/// - **Transmittance** (`<name>_transmittance`, 256x64): light reaching an
+///   altitude from a zenith angle. Depends on the atmosphere only.
+/// - **Multiple scattering** (`<name>_multi_scattering`, 32x32): isotropic
+///   contribution of the higher scattering orders, read from the
+///   transmittance LUT. Depends on the atmosphere only.
+/// - **Sky view** (`<name>_sky_view`, 192x108): sky luminance around the
+///   viewer, longitude relative to the sun and latitude remapped to put
+///   texels near the horizon. Depends on the sun direction and the viewer
+///   altitude: re-bake it with `bake_sky_view` when they change (time of
+///   day), the two other tables stay valid.
+///
+/// The engine has no compute path (§16.1), so each table is one fullscreen
+/// triangle drawn with an application pipeline (`AtmospherePipelines`), like
+/// the IBL bake. Shader interface of the three pipelines:
+///
+/// ```glsl
+/// layout(push_constant) uniform AtmosphereBake {  // fragment stage, offset 0
+///     vec3 rayleighScattering;  float rayleighScaleHeight;
+///     vec3 mieScattering;       float mieScaleHeight;
+///     vec3 mieExtinction;       float mieG;
+///     vec3 ozoneAbsorption;     float ozoneCenter;
+///     vec3 groundAlbedo;        float ozoneWidth;
+///     float bottomRadius;       float topRadius;  vec2 _pad;
+///     vec3 sunDirection;        float viewerAltitude;  // sky view only
+///     uint transmittanceIndex;      // bindless index (multi scattering, sky view)
+///     uint multiScatteringIndex;    // bindless index (sky view)
+/// };
+/// // vertex: fullscreen triangle from gl_VertexIndex (draw(3, 0), no vertex buffer)
+/// ```
+///
+/// Baking waits for the GPU (`wait_idle`): the sky view bake is a few
+/// thousand texels, cheap enough for a time-of-day change, not every frame.
+
+use std::sync::{Arc, Mutex};
+use glam::Vec3;
+use crate::error::Result;
+use crate::engine_bail;
+use crate::graphics_device::{
+    self, AccessType, AttachmentDesc, ClearValue, CommandList, FramebufferAttachment,
+    FramebufferDesc, GraphicsDevice, ImageAccess, LoadOp, MipmapMode, Rect2D, RenderPassDesc,
+    SampleCount, ShaderStageFlags, StoreOp, TextureFormat, TextureType, TextureUsage, Viewport,
+};
+use crate::resource::resource_manager::{ResourceManager, TextureKey};
+use crate::resource::texture::{LayerDesc, TextureDesc};
+use super::atmosphere::Atmosphere;
+
+/// Default size of the transmittance LUT (cos zenith, altitude)
+pub const DEFAULT_TRANSMITTANCE_LUT_SIZE: (u32, u32) = (256, 64);
+/// Default size of the multiple scattering LUT (cos sun zenith, altitude)
+pub const DEFAULT_MULTI_SCATTERING_LUT_SIZE: (u32, u32) = (32, 32);
+/// Default size of the sky view LUT (longitude, latitude)
+pub const DEFAULT_SKY_VIEW_LUT_SIZE: (u32, u32) = (192, 108);
+/// Format of the three LUTs
+pub const ATMOSPHERE_LUT_FORMAT: TextureFormat = TextureFormat::R16G16B16A16_SFLOAT;
+/// Layer name of the bake targets
+const BAKE_LAYER_NAME: &str = "baked";
+
+/// Pipelines drawing the bake passes (see module docs). All render
+/// `ATMOSPHERE_LUT_FORMAT`.
+#[derive(Clone)]
+pub struct AtmospherePipelines {
+    pub transmittance: Arc<dyn graphics_device::Pipeline>,
+    pub multi_scattering: Arc<dyn graphics_device::Pipeline>,
+    pub sky_view: Arc<dyn graphics_device::Pipeline>,
+}
+
+/// Sizes of the LUTs, `(width, height)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AtmosphereLutDesc {
+    pub transmittance_size: (u32, u32),
+    pub multi_scattering_size: (u32, u32),
+    pub sky_view_size: (u32, u32),
+}
+
+impl Default for AtmosphereLutDesc {
+    fn default() -> Self {
+        Self {
+            transmittance_size: DEFAULT_TRANSMITTANCE_LUT_SIZE,
+            multi_scattering_size: DEFAULT_MULTI_SCATTERING_LUT_SIZE,
+            sky_view_size: DEFAULT_SKY_VIEW_LUT_SIZE,
+        }
+    }
+}
+
+/// Baked lookup tables of one atmosphere
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AtmosphereLuts {
+    pub transmittance: TextureKey,
+    pub multi_scattering: TextureKey,
+    pub sky_view: TextureKey,
+}
+
+/// Where the sky view is seen from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SkyViewParams {
+    /// Unit direction toward the sun (world space, Y up)
+    pub sun_direction: Vec3,
+    /// Altitude of the viewer above the ground, in kilometers
+    pub viewer_altitude: f32,
+}
+
+impl Default for SkyViewParams {
+    fn default() -> Self {
+        Self { sun_direction: Vec3::Y, viewer_altitude: 0.0 }
+    }
+}
+
+/// One fullscreen draw into a LUT
+struct BakeDraw {
+    pipeline: Arc<dyn graphics_device::Pipeline>,
+    target: Arc<dyn graphics_device::Texture>,
+    framebuffer: Arc<dyn graphics_device::Framebuffer>,
+    /// The target was baked before and is being sampled
+    rebake: bool,
+}
+
+/// Bakes atmosphere LUTs with the application pipelines (see module docs).
+pub struct AtmosphereBaker {
+    pipelines: AtmospherePipelines,
+}
+
+impl AtmosphereBaker {
+    pub fn new(pipelines: AtmospherePipelines) -> Self {
+        Self { pipelines }
+    }
+
+    /// Create and bake the three LUTs of `atmosphere` as `<name>_transmittance`,
+    /// `<name>_multi_scattering` and `<name>_sky_view`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the atmosphere or sky view parameters are
+    /// invalid, if a size is zero, or if a texture or GPU operation fails.
+    pub fn bake(
+        &self,
+        rm: &mut ResourceManager,
+        graphics_device: &Arc<Mutex<dyn GraphicsDevice>>,
+        name: &str,
+        atmosphere: &Atmosphere,
+        sky_view: &SkyViewParams,
+        desc: &AtmosphereLutDesc,
+    ) -> Result<AtmosphereLuts> {
+        atmosphere.validate()?;
+        validate_sky_view(sky_view)?;
+        for (width, height) in [desc.transmittance_size, desc.multi_scattering_size, desc.sky_view_size] {
+            if width == 0 || height == 0 {
+                engine_bail!("galaxy3d::AtmosphereBaker", "LUT sizes must be non-zero: {:?}", desc);
+            }
+        }
+
+        let luts = AtmosphereLuts {
+            transmittance: create_target(rm, graphics_device,
+                format!("{}_transmittance", name), desc.transmittance_size)?,
+            multi_scattering: create_target(rm, graphics_device,
+                format!("{}_multi_scattering", name), desc.multi_scattering_size)?,
+            sky_view: create_target(rm, graphics_device,
+                format!("{}_sky_view", name), desc.sky_view_size)?,
+        };
+        let pipelines = [
+            (&self.pipelines.transmittance, luts.transmittance),
+            (&self.pipelines.multi_scattering, luts.multi_scattering),
+            (&self.pipelines.sky_view, luts.sky_view),
+        ];
+        record_and_submit(rm, graphics_device, &pipelines, &luts, atmosphere, sky_view, false)?;
+
+        crate::engine_info!("galaxy3d::AtmosphereBaker",
+            "Baked atmosphere '{}' (transmittance {:?}, multi scattering {:?}, sky view {:?})",
+            name, desc.transmittance_size, desc.multi_scattering_size, desc.sky_view_size);
+        Ok(luts)
+    }
+
+    /// Re-bake the sky view LUT of `luts` for a new sun direction or viewer
+    /// altitude.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the parameters are invalid, if a LUT is missing,
+    /// or if a GPU operation fails.
+    pub fn bake_sky_view(
+        &self,
+        rm: &ResourceManager,
+        graphics_device: &Arc<Mutex<dyn GraphicsDevice>>,
+        luts: &AtmosphereLuts,
+        atmosphere: &Atmosphere,
+        sky_view: &SkyViewParams,
+    ) -> Result<()> {
+        atmosphere.validate()?;
+        validate_sky_view(sky_view)?;
+        let pipelines = [(&self.pipelines.sky_view, luts.sky_view)];
+        record_and_submit(rm, graphics_device, &pipelines, luts, atmosphere, sky_view, true)
+    }
+}
+
+/// Push constants of a bake pass (see module docs)
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct BakeConstants {
+    atmosphere: Atmosphere,
+    sun_direction: Vec3,
+    viewer_altitude: f32,
+    transmittance_index: u32,
+    multi_scattering_index: u32,
+}
+
+impl BakeConstants {
+    /// Push constant block, in the shader declaration order
+    fn to_bytes(self) -> Vec<u8> {
+        let mut bytes = self.atmosphere.to_bytes();
+        bytes.extend_from_slice(bytemuck::cast_slice(&self.sun_direction.to_array()));
+        bytes.extend_from_slice(&self.viewer_altitude.to_ne_bytes());
+        bytes.extend_from_slice(&self.transmittance_index.to_ne_bytes());
+        bytes.extend_from_slice(&self.multi_scattering_index.to_ne_bytes());
+        bytes
+    }
+}
+
+/// Bake `pipelines` into their LUTs and wait for the device. `rebake`:
+/// the targets were baked before and are being sampled
+#[allow(clippy::too_many_arguments)]
+fn record_and_submit(
+    rm: &ResourceManager,
+    graphics_device: &Arc<Mutex<dyn GraphicsDevice>>,
+    pipelines: &[(&Arc<dyn graphics_device::Pipeline>, TextureKey)],
+    luts: &AtmosphereLuts,
+    atmosphere: &Atmosphere,
+    sky_view: &SkyViewParams,
+    rebake: bool,
+) -> Result<()> {
+    let texture = |key: TextureKey| -> Result<Arc<dyn graphics_device::Texture>> {
+        let Some(texture) = rm.texture(key) else {
+            engine_bail!("galaxy3d::AtmosphereBaker", "Atmosphere LUT texture not found");
+        };
+        Ok(texture.graphics_device_texture().clone())
+    };
+    let constants = BakeConstants {
+        atmosphere: *atmosphere,
+        sun_direction: sky_view.sun_direction.normalize(),
+        viewer_altitude: sky_view.viewer_altitude,
+        transmittance_index: texture(luts.transmittance)?.bindless_index(),
+        multi_scattering_index: texture(luts.multi_scattering)?.bindless_index(),
+    };
+
+    let gd = graphics_device.lock().unwrap();
+    let render_pass = create_render_pass(&*gd)?;
+    let draws = pipelines.iter()
+        .map(|&(pipeline, key)| -> Result<BakeDraw> {
+            let target = texture(key)?;
+            let framebuffer = create_framebuffer(&*gd, &render_pass, &target)?;
+            Ok(BakeDraw { pipeline: pipeline.clone(), target, framebuffer, rebake })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut cmd = gd.create_command_list()?;
+    cmd.begin()?;
+    record_draws(&mut *cmd, &render_pass, &draws, &constants.to_bytes())?;
+    cmd.end()?;
+    gd.submit(&[&*cmd])?;
+    gd.wait_idle()
+}
+
+fn validate_sky_view(sky_view: &SkyViewParams) -> Result<()> {
+    let length = sky_view.sun_direction.length();
+    if !(length > 0.0 && length.is_finite()) {
+        engine_bail!("galaxy3d::AtmosphereBaker",
+            "Sun direction must be non-zero and finite, got {:?}", sky_view.sun_direction);
+    }
+    if !(sky_view.viewer_altitude >= 0.0 && sky_view.viewer_altitude.is_finite()) {
+        engine_bail!("galaxy3d::AtmosphereBaker",
+            "Viewer altitude must be non-negative, got {}", sky_view.viewer_altitude);
+    }
+    Ok(())
+}
+
+/// Sampled render target without initial data (the bake writes every texel)
+fn create_target(
+    rm: &mut ResourceManager,
+    graphics_device: &Arc<Mutex<dyn GraphicsDevice>>,
+    name: String,
+    (width, height): (u32, u32),
+) -> Result<TextureKey> {
+    rm.create_texture(name.clone(), TextureDesc {
+        graphics_device: graphics_device.clone(),
+        texture: graphics_device::TextureDesc {
+            width,
+            height,
+            depth: 1,
+            format: ATMOSPHERE_LUT_FORMAT,
+            usage: TextureUsage::SampledAndRenderTarget,
+            texture_type: TextureType::Tex2D,
+            sample_count: SampleCount::S1,
+            array_layers: 1,
+            data: None,
+            mipmap: MipmapMode::None,
+            debug_name: Some(name),
+        },
+        layers: vec![LayerDesc { name: BAKE_LAYER_NAME.to_string(), layer_index: 0, data: None, regions: Vec::new() }],
+    })
+}
+
+fn create_render_pass(gd: &dyn GraphicsDevice) -> Result<Arc<dyn graphics_device::RenderPass>> {
+    gd.create_render_pass(&RenderPassDesc {
+        color_attachments: vec![AttachmentDesc {
+            format: ATMOSPHERE_LUT_FORMAT,
+            samples: SampleCount::S1,
+            // Every texel is written
+            load_op: LoadOp::DontCare,
+            store_op: StoreOp::Store,
+            stencil_load_op: LoadOp::DontCare,
+            stencil_store_op: StoreOp::DontCare,
+        }],
+        depth_stencil_attachment: None,
+        color_resolve_attachments: Vec::new(),
+    })
+}
+
+fn create_framebuffer(
+    gd: &dyn GraphicsDevice,
+    render_pass: &Arc<dyn graphics_device::RenderPass>,
+    target: &Arc<dyn graphics_device::Texture>,
+) -> Result<Arc<dyn graphics_device::Framebuffer>> {
+    gd.create_framebuffer(&FramebufferDesc {
+        render_pass,
+        color_attachments: vec![FramebufferAttachment::mip_layer(target.clone(), 0, 0)],
+        depth_stencil_attachment: None,
+        color_resolve_attachments: Vec::new(),
+        width: target.info().width,
+        height: target.info().height,
+    })
+}
+
+/// Record the draws in order, each LUT readable by the next ones
+fn record_draws(
+    cmd: &mut dyn CommandList,
+    render_pass: &Arc<dyn graphics_device::RenderPass>,
+    draws: &[BakeDraw],
+    constants: &[u8],
+) -> Result<()> {
+    for draw in draws {
+        let (width, height) = (draw.target.info().width, draw.target.info().height);
+        let access = ImageAccess {
+            texture: draw.target.clone(),
+            access_type: AccessType::ColorAttachmentWrite,
+            previous_access_type: draw.rebake.then_some(AccessType::FragmentShaderRead),
+        };
+        cmd.begin_render_pass(render_pass, &draw.framebuffer, &[ClearValue::Color([0.0; 4])], &[access], &[])?;
+        cmd.set_viewport(Viewport {
+            x: 0.0,
+            y: 0.0,
+            width: width as f32,
+            height: height as f32,
+            min_depth: 0.0,
+            max_depth: 1.0,
+        })?;
+        cmd.set_scissor(Rect2D { x: 0, y: 0, width, height })?;
+        cmd.bind_pipeline(&draw.pipeline)?;
+        cmd.bind_textures()?;
+        cmd.push_constants(ShaderStageFlags::FRAGMENT, 0, constants)?;
+        cmd.draw(3, 0)?;
+        cmd.end_render_pass()?;
+        cmd.declare_accesses(&[ImageAccess {
+            texture: draw.target.clone(),
+            access_type: AccessType::FragmentShaderRead,
+            previous_access_type: Some(AccessType::ColorAttachmentWrite),
+        }], &[])?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "atmosphere_baker_tests.rs"]
+mod tests;
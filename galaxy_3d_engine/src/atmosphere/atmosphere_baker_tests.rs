@@ -0,0 +1,107 @@
+use super::*;
+use crate::graphics_device::mock_graphics_device::{
+    MockCommandList, MockGraphicsDevice, MockPipeline, MockTexture,
+};
+
+fn make_baker() -> AtmosphereBaker {
+    let pipeline = |name: &str| -> Arc<dyn graphics_device::Pipeline> {
+        Arc::new(MockPipeline::new(name.to_string()))
+    };
+    AtmosphereBaker::new(AtmospherePipelines {
+        transmittance: pipeline("transmittance"),
+        multi_scattering: pipeline("multi_scattering"),
+        sky_view: pipeline("sky_view"),
+    })
+}
+
+fn make_device() -> Arc<Mutex<dyn GraphicsDevice>> {
+    Arc::new(Mutex::new(MockGraphicsDevice::new()))
+}
+
+fn info(rm: &ResourceManager, key: TextureKey) -> graphics_device::TextureInfo {
+    rm.texture(key).unwrap().graphics_device_texture().info().clone()
+}
+
+// ============================================================================
+// Bake
+// ============================================================================
+
+#[test]
+fn test_bake_creates_the_three_luts() {
+    let mut rm = ResourceManager::new();
+    let gd = make_device();
+    let luts = make_baker().bake(&mut rm, &gd, "earth", &Atmosphere::earth(),
+        &SkyViewParams::default(), &AtmosphereLutDesc::default()).unwrap();
+
+    assert_eq!(rm.texture_key("earth_transmittance"), Some(luts.transmittance));
+    assert_eq!(rm.texture_key("earth_multi_scattering"), Some(luts.multi_scattering));
+    assert_eq!(rm.texture_key("earth_sky_view"), Some(luts.sky_view));
+    let transmittance = info(&rm, luts.transmittance);
+    assert_eq!((transmittance.width, transmittance.height), DEFAULT_TRANSMITTANCE_LUT_SIZE);
+    assert_eq!(transmittance.format, ATMOSPHERE_LUT_FORMAT);
+    assert_eq!(transmittance.usage, TextureUsage::SampledAndRenderTarget);
+    let sky_view = info(&rm, luts.sky_view);
+    assert_eq!((sky_view.width, sky_view.height), DEFAULT_SKY_VIEW_LUT_SIZE);
+    assert_eq!(sky_view.texture_type, TextureType::Tex2D);
+
+    // Time of day: only the sky view is baked again
+    let sunset = SkyViewParams { sun_direction: Vec3::new(1.0, 0.05, 0.0), viewer_altitude: 0.0 };
+    assert!(make_baker().bake_sky_view(&rm, &gd, &luts, &Atmosphere::earth(), &sunset).is_ok());
+}
+
+#[test]
+fn test_bake_rejects_invalid_input() {
+    let mut rm = ResourceManager::new();
+    let gd = make_device();
+    let baker = make_baker();
+    let earth = Atmosphere::earth();
+    let desc = AtmosphereLutDesc::default();
+
+    let no_sun = SkyViewParams { sun_direction: Vec3::ZERO, viewer_altitude: 0.0 };
+    assert!(baker.bake(&mut rm, &gd, "a", &earth, &no_sun, &desc).is_err());
+    let underground = SkyViewParams { viewer_altitude: -1.0, ..Default::default() };
+    assert!(baker.bake(&mut rm, &gd, "b", &earth, &underground, &desc).is_err());
+    let empty = AtmosphereLutDesc { sky_view_size: (0, 108), ..desc };
+    assert!(baker.bake(&mut rm, &gd, "c", &earth, &SkyViewParams::default(), &empty).is_err());
+    let no_planet = Atmosphere { bottom_radius: 0.0, ..earth };
+    assert!(baker.bake(&mut rm, &gd, "d", &no_planet, &SkyViewParams::default(), &desc).is_err());
+    // Nothing was created by the failed bakes
+    assert_eq!(rm.texture_key("c_transmittance"), None);
+}
+
+// ============================================================================
+// Recording
+// ============================================================================
+
+#[test]
+fn test_record_draws_makes_each_lut_readable() {
+    let gd = MockGraphicsDevice::new();
+    let render_pass = create_render_pass(&gd).unwrap();
+    let draw = |name: &str, rebake| {
+        let target: Arc<dyn graphics_device::Texture> =
+            Arc::new(MockTexture::new(64, 32, 1, TextureType::Tex2D, name.to_string()));
+        BakeDraw {
+            pipeline: Arc::new(MockPipeline::new(name.to_string())),
+            framebuffer: create_framebuffer(&gd, &render_pass, &target).unwrap(),
+            target,
+            rebake,
+        }
+    };
+    let constants = BakeConstants {
+        atmosphere: Atmosphere::earth(),
+        sun_direction: Vec3::Y,
+        viewer_altitude: 0.0,
+        transmittance_index: 1,
+        multi_scattering_index: 2,
+    };
+    assert_eq!(constants.to_bytes().len(), 120);
+
+    let mut cmd = MockCommandList::new();
+    record_draws(&mut cmd, &render_pass, &[draw("a", false), draw("b", true)], &constants.to_bytes()).unwrap();
+    let pass = [
+        "begin_render_pass", "set_viewport", "set_scissor", "bind_pipeline", "bind_textures",
+        "push_constants", "draw", "end_render_pass", "declare_accesses 1",
+    ];
+    let expected: Vec<&str> = pass.iter().chain(pass.iter()).copied().collect();
+    assert_eq!(cmd.commands, expected);
+}
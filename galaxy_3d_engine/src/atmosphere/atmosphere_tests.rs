@@ -0,0 +1,67 @@
+use super::*;
+
+// ============================================================================
+// Validation
+// ============================================================================
+
+#[test]
+fn test_earth_is_valid_default() {
+    assert_eq!(Atmosphere::default(), Atmosphere::earth());
+    assert!(Atmosphere::earth().validate().is_ok());
+}
+
+#[test]
+fn test_validate_rejects_invalid_parameters() {
+    let earth = Atmosphere::earth();
+    assert!(Atmosphere { top_radius: earth.bottom_radius, ..earth }.validate().is_err());
+    assert!(Atmosphere { bottom_radius: 0.0, ..earth }.validate().is_err());
+    assert!(Atmosphere { mie_scale_height: 0.0, ..earth }.validate().is_err());
+    assert!(Atmosphere { rayleigh_scattering: Vec3::new(-1.0, 0.0, 0.0), ..earth }.validate().is_err());
+    assert!(Atmosphere { ozone_absorption: Vec3::splat(f32::NAN), ..earth }.validate().is_err());
+    assert!(Atmosphere { mie_extinction: Vec3::ZERO, ..earth }.validate().is_err());
+    assert!(Atmosphere { mie_g: 1.0, ..earth }.validate().is_err());
+}
+
+// ============================================================================
+// Transmittance
+// ============================================================================
+
+#[test]
+fn test_extinction_decreases_with_altitude() {
+    let earth = Atmosphere::earth();
+    let ground = earth.extinction(0.0);
+    assert!(ground.abs_diff_eq(
+        earth.rayleigh_scattering + earth.mie_extinction, 1e-9));
+    assert!(earth.extinction(50.0).max_element() < ground.max_element());
+    // Ozone is a tent around its center altitude
+    let no_ozone = Atmosphere { ozone_absorption: Vec3::ZERO, ..earth };
+    let ozone = |altitude| earth.extinction(altitude) - no_ozone.extinction(altitude);
+    assert!(ozone(25.0).abs_diff_eq(earth.ozone_absorption, 1e-8));
+    assert!(ozone(32.5).abs_diff_eq(earth.ozone_absorption * 0.5, 1e-8));
+    assert_eq!(ozone(45.0), Vec3::ZERO);
+}
+
+#[test]
+fn test_transmittance_by_sun_elevation() {
+    let earth = Atmosphere::earth();
+    let zenith = earth.transmittance(0.0, 1.0);
+    let horizon = earth.transmittance(0.0, 0.0);
+    assert!(zenith.min_element() > 0.5 && zenith.max_element() < 1.0);
+    // Longer path at sunset: darker, and red survives more than blue
+    assert!(horizon.max_element() < zenith.min_element());
+    assert!(horizon.x > horizon.z);
+
+    // The planet blocks the sun below the horizon
+    assert_eq!(earth.transmittance(0.0, -0.1), Vec3::ZERO);
+    // From higher up, the horizon dips below zero
+    assert!(earth.transmittance(10.0, -0.01).x > 0.0);
+    // Nothing is left to cross at the top
+    assert_eq!(earth.transmittance(earth.top_radius - earth.bottom_radius, 1.0), Vec3::ONE);
+}
+
+#[test]
+fn test_push_constant_block_size() {
+    let bytes = Atmosphere::earth().to_bytes();
+    assert_eq!(bytes.len(), 96);
+    assert_eq!(&bytes[12..16], &8.0f32.to_ne_bytes());
+}
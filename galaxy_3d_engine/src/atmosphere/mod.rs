@@ -0,0 +1,19 @@
+//! Physically-based sky and atmosphere.
+//!
+//! `Atmosphere` describes the planet atmosphere (Rayleigh, Mie, ozone).
+//! `AtmosphereBaker` renders its transmittance, multiple scattering and sky
+//! view lookup tables (Hillaire 2020), and `AtmosphereSkyDrawer` draws the
+//! sky view behind the scene. Distance fog stays a `Scene` setting
+//! (`Scene::set_fog`).
+
+mod atmosphere;
+mod atmosphere_baker;
+mod sky_drawer;
+
+pub use atmosphere::Atmosphere;
+pub use atmosphere_baker::{
+    AtmosphereBaker, AtmosphereLutDesc, AtmosphereLuts, AtmospherePipelines, SkyViewParams,
+    ATMOSPHERE_LUT_FORMAT, DEFAULT_MULTI_SCATTERING_LUT_SIZE, DEFAULT_SKY_VIEW_LUT_SIZE,
+    DEFAULT_TRANSMITTANCE_LUT_SIZE,
+};
+pub use sky_drawer::{AtmosphereSkyDrawer, DEFAULT_SUN_ANGULAR_RADIUS, DEFAULT_SUN_INTENSITY};
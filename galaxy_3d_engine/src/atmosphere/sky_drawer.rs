@@ -0,0 +1,138 @@
+/// Atmosphere sky drawing.
+///
+/// `AtmosphereSkyDrawer` is a `Drawer` that fills the background with the
+/// baked sky view LUT and a sun disc dimmed by the transmittance LUT. Like
+/// the `SkyboxDrawer`, it draws one fullscreen triangle at the far plane
+/// with an application pipeline (no vertex input, triangle list):
+///
+/// ```glsl
+/// layout(push_constant) uniform AtmosphereSky {
+///     mat4 inverseViewProjection;  // rotation-only view, jittered projection
+///     vec3 sunDirection;      float sunIntensity;
+///     uint skyViewIndex;      // bindless index, set 0 binding 0
+///     uint transmittanceIndex;
+///     float sunAngularRadius; // radians, 0 hides the disc
+///     float viewerAltitude;   // km, as baked in the sky view LUT
+///     float bottomRadius;     float topRadius;
+/// };
+/// // vertex: fullscreen triangle from gl_VertexIndex, z at the far plane
+/// // fragment: view direction -> sky view uv, plus the sun disc
+/// ```
+///
+/// Draw it after the opaque geometry so it only fills the empty pixels
+/// (same render state as the skybox).
+
+use std::sync::Arc;
+use glam::Vec4;
+use crate::error::Result;
+use crate::engine::Engine;
+use crate::engine_bail;
+use crate::graphics_device::{self, BindingGroup, CommandList, ShaderStageFlags};
+use crate::resource::resource_manager::PassInfo;
+use crate::scene::{Drawer, RenderView, Scene, SkyboxDrawer};
+use super::atmosphere::Atmosphere;
+use super::atmosphere_baker::{AtmosphereLuts, SkyViewParams};
+
+/// Vertices of the fullscreen triangle
+const SKY_VERTEX_COUNT: u32 = 3;
+
+/// Default sun illuminance multiplier
+pub const DEFAULT_SUN_INTENSITY: f32 = 20.0;
+/// Default angular radius of the sun disc (the real sun, in radians)
+pub const DEFAULT_SUN_ANGULAR_RADIUS: f32 = 0.004_65;
+
+/// Draws the atmosphere behind the scene (see module docs).
+pub struct AtmosphereSkyDrawer {
+    pipeline: Arc<dyn graphics_device::Pipeline>,
+    luts: AtmosphereLuts,
+    bottom_radius: f32,
+    top_radius: f32,
+    /// Sun and viewer of the sky view LUT: keep in sync with the last
+    /// `AtmosphereBaker::bake_sky_view`
+    pub sky_view: SkyViewParams,
+    pub sun_intensity: f32,
+    pub sun_angular_radius: f32,
+    /// Reused push constant block
+    push_constants: Vec<u8>,
+}
+
+impl AtmosphereSkyDrawer {
+    /// Create a sky drawing the `luts` baked for `atmosphere` with `pipeline`.
+    pub fn new(
+        pipeline: Arc<dyn graphics_device::Pipeline>,
+        luts: AtmosphereLuts,
+        atmosphere: &Atmosphere,
+    ) -> Self {
+        Self {
+            pipeline,
+            luts,
+            bottom_radius: atmosphere.bottom_radius,
+            top_radius: atmosphere.top_radius,
+            sky_view: SkyViewParams::default(),
+            sun_intensity: DEFAULT_SUN_INTENSITY,
+            sun_angular_radius: DEFAULT_SUN_ANGULAR_RADIUS,
+            push_constants: Vec::new(),
+        }
+    }
+
+    pub fn luts(&self) -> &AtmosphereLuts {
+        &self.luts
+    }
+}
+
+impl Drawer for AtmosphereSkyDrawer {
+    fn draw(
+        &mut self,
+        _scene: &mut Scene,
+        view: &RenderView,
+        cmd: &mut dyn CommandList,
+        _pass_info: &PassInfo,
+        _binding_group: &Arc<dyn BindingGroup>,
+        _bind_textures: bool,
+    ) -> Result<()> {
+        let camera = view.camera();
+        let (sky_view_index, transmittance_index) = {
+            let rm_arc = Engine::resource_manager()?;
+            let rm = rm_arc.lock().unwrap();
+            let (Some(sky_view), Some(transmittance)) =
+                (rm.texture(self.luts.sky_view), rm.texture(self.luts.transmittance))
+            else {
+                engine_bail!("galaxy3d::AtmosphereSkyDrawer", "Atmosphere LUT texture not found");
+            };
+            (
+                sky_view.graphics_device_texture().bindless_index(),
+                transmittance.graphics_device_texture().bindless_index(),
+            )
+        };
+
+        // Directions only: drop the camera translation from the view
+        let mut rotation = *camera.view_matrix();
+        rotation.w_axis = Vec4::W;
+        let inverse_view_projection = (camera.jittered_projection_matrix() * rotation).inverse();
+        let sun_direction = self.sky_view.sun_direction.normalize_or_zero();
+
+        self.push_constants.clear();
+        self.push_constants.extend_from_slice(bytemuck::cast_slice(&inverse_view_projection.to_cols_array()));
+        self.push_constants.extend_from_slice(bytemuck::cast_slice(&sun_direction.to_array()));
+        self.push_constants.extend_from_slice(&self.sun_intensity.to_ne_bytes());
+        self.push_constants.extend_from_slice(&sky_view_index.to_ne_bytes());
+        self.push_constants.extend_from_slice(&transmittance_index.to_ne_bytes());
+        self.push_constants.extend_from_slice(&self.sun_angular_radius.to_ne_bytes());
+        self.push_constants.extend_from_slice(&self.sky_view.viewer_altitude.to_ne_bytes());
+        self.push_constants.extend_from_slice(&self.bottom_radius.to_ne_bytes());
+        self.push_constants.extend_from_slice(&self.top_radius.to_ne_bytes());
+
+        cmd.set_viewport(*camera.viewport())?;
+        cmd.set_scissor(camera.effective_scissor())?;
+        cmd.bind_pipeline(&self.pipeline)?;
+        // The LUTs are sampled through the bindless set, whatever the pass asks
+        cmd.bind_textures()?;
+        cmd.set_dynamic_state(&SkyboxDrawer::render_state())?;
+        cmd.push_constants(ShaderStageFlags::VERTEX_FRAGMENT, 0, &self.push_constants)?;
+        cmd.draw(SKY_VERTEX_COUNT, 0)
+    }
+}
+
+#[cfg(test)]
+#[path = "sky_drawer_tests.rs"]
+mod tests;
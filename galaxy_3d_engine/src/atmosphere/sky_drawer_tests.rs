@@ -0,0 +1,69 @@
+use super::*;
+use serial_test::serial;
+use crate::graphics_device::{SampleCount, TextureFormat};
+use crate::graphics_device::mock_graphics_device::{
+    MockBindingGroup, MockCommandList, MockPipeline,
+};
+use crate::atmosphere::{AtmosphereBaker, AtmosphereLutDesc, AtmospherePipelines};
+use crate::render_graph::test_helpers::setup_engine;
+use crate::scene::scene_test_helpers::create_test_camera;
+
+fn bake_luts() -> AtmosphereLuts {
+    let pipeline = || -> Arc<dyn graphics_device::Pipeline> {
+        Arc::new(MockPipeline::new("bake".to_string()))
+    };
+    let baker = AtmosphereBaker::new(AtmospherePipelines {
+        transmittance: pipeline(),
+        multi_scattering: pipeline(),
+        sky_view: pipeline(),
+    });
+    let rm_arc = Engine::resource_manager().unwrap();
+    let mut rm = rm_arc.lock().unwrap();
+    baker.bake(&mut rm, &Engine::graphics_device("main").unwrap(), "earth",
+        &Atmosphere::earth(), &SkyViewParams::default(), &AtmosphereLutDesc::default()).unwrap()
+}
+
+fn draw(drawer: &mut AtmosphereSkyDrawer) -> Result<Vec<String>> {
+    let mut scene = Scene::new();
+    let view = RenderView::new(create_test_camera(), 0);
+    let pass_info = PassInfo::new(vec![TextureFormat::R8G8B8A8_UNORM], None, SampleCount::S1);
+    let binding_group: Arc<dyn BindingGroup> =
+        Arc::new(MockBindingGroup::new("pass_bg".to_string(), 1));
+    let mut cmd = MockCommandList::new();
+    drawer.draw(&mut scene, &view, &mut cmd, &pass_info, &binding_group, false)?;
+    Ok(cmd.commands)
+}
+
+// ============================================================================
+// Drawing
+// ============================================================================
+
+#[test]
+#[serial]
+fn test_sky_draws_fullscreen_triangle() {
+    setup_engine();
+    let luts = bake_luts();
+    let pipeline = Arc::new(MockPipeline::new("sky".to_string()));
+    let mut drawer = AtmosphereSkyDrawer::new(pipeline, luts, &Atmosphere::earth());
+    assert_eq!(drawer.sun_intensity, DEFAULT_SUN_INTENSITY);
+    assert_eq!(*drawer.luts(), luts);
+
+    assert_eq!(draw(&mut drawer).unwrap(), vec![
+        "set_viewport", "set_scissor", "bind_pipeline", "bind_textures",
+        "set_dynamic_state", "push_constants", "draw",
+    ]);
+    assert_eq!(drawer.push_constants.len(), 104);
+}
+
+#[test]
+#[serial]
+fn test_sky_rejects_missing_luts() {
+    setup_engine();
+    let luts = bake_luts();
+    let pipeline = Arc::new(MockPipeline::new("sky".to_string()));
+    let mut drawer = AtmosphereSkyDrawer::new(pipeline, luts, &Atmosphere::earth());
+
+    // The LUTs now belong to another resource manager
+    setup_engine();
+    assert!(draw(&mut drawer).is_err());
+}
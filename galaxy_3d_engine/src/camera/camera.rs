@@ -2,14 +2,15 @@
 ///
 /// The Camera computes nothing. The caller (game engine) is responsible
 /// for computing and setting all fields: view matrix, projection matrix,
-/// frustum, viewport, and scissor.
+/// frustum, viewport, scissor and TAA jitter.
 ///
 /// The engine does NOT store or manage cameras. They are tools provided
 /// by the engine, owned and driven by the caller.
 
-use glam::Mat4;
-use crate::graphics_device::command_list::{Viewport, Rect2D};
+use glam::{Mat4, Vec2, Vec4};
+use crate::graphics_device::viewport::{Viewport, Rect2D};
 use super::frustum::Frustum;
+use super::projection::{jitter_ndc_offset, jitter_projection, oblique_projection, plane_to_view_space};
 
 /// Low-level camera. A passive data container — computes nothing.
 ///
@@ -24,6 +25,8 @@ pub struct Camera {
     frustum: Frustum,
     viewport: Viewport,
     scissor: Option<Rect2D>,
+    /// Sub-pixel projection offset in pixels (TAA), zero by default
+    jitter: Vec2,
 }
 
 impl Camera {
@@ -37,6 +40,7 @@ impl Camera {
             frustum,
             viewport,
             scissor: None,
+            jitter: Vec2::ZERO,
         }
     }
 
@@ -72,6 +76,35 @@ impl Camera {
         self.scissor.as_ref()
     }
 
+    /// Sub-pixel projection offset in pixels (TAA).
+    pub fn jitter(&self) -> Vec2 {
+        self.jitter
+    }
+
+    /// Projection matrix shifted by the jitter (see `jitter_projection`).
+    ///
+    /// Used for rasterization; culling and LOD keep the unjittered matrix.
+    pub fn jittered_projection_matrix(&self) -> Mat4 {
+        jitter_projection(&self.projection_matrix, self.jitter,
+            Vec2::new(self.viewport.width, self.viewport.height))
+    }
+
+    /// NDC shift applied by the jitter (see `jitter_ndc_offset`)
+    pub fn jitter_ndc(&self) -> Vec2 {
+        jitter_ndc_offset(self.jitter, Vec2::new(self.viewport.width, self.viewport.height))
+    }
+
+    /// Combined view-projection matrix with the jitter.
+    pub fn jittered_view_projection_matrix(&self) -> Mat4 {
+        self.jittered_projection_matrix() * self.view_matrix
+    }
+
+    /// Projection clipped by a world-space plane instead of its near plane
+    /// (planar reflections, see `oblique_projection`).
+    pub fn oblique_projection_matrix(&self, world_plane: Vec4) -> Mat4 {
+        oblique_projection(&self.projection_matrix, plane_to_view_space(&self.view_matrix, world_plane))
+    }
+
     /// Effective scissor: explicit scissor or viewport bounds as Rect2D.
     pub fn effective_scissor(&self) -> Rect2D {
         self.scissor.unwrap_or(Rect2D {
@@ -108,6 +141,12 @@ impl Camera {
     pub fn set_scissor(&mut self, scissor: Option<Rect2D>) {
         self.scissor = scissor;
     }
+
+    /// Set the sub-pixel projection offset in pixels, typically
+    /// `taa_jitter(frame_index, sample_count)` each frame. Zero disables it.
+    pub fn set_jitter(&mut self, jitter: Vec2) {
+        self.jitter = jitter;
+    }
 }
 
 #[cfg(test)]
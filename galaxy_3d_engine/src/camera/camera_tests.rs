@@ -196,3 +196,46 @@ fn test_camera_clone() {
     assert_eq!(*cloned.projection_matrix(), proj);
     assert_eq!(cloned.viewport().width, 1920.0);
 }
+
+// ============================================================================
+// Jitter and oblique projection
+// ============================================================================
+
+#[test]
+fn test_jitter_defaults_to_zero() {
+    let proj = Mat4::perspective_rh(std::f32::consts::FRAC_PI_4, 16.0 / 9.0, 0.1, 100.0);
+    let camera = Camera::new(Mat4::IDENTITY, proj, create_test_frustum(), create_test_viewport());
+    assert_eq!(camera.jitter(), glam::Vec2::ZERO);
+    assert_eq!(camera.jittered_projection_matrix(), proj);
+    assert_eq!(camera.jittered_view_projection_matrix(), camera.view_projection_matrix());
+}
+
+#[test]
+fn test_jittered_projection_uses_viewport() {
+    let view = Mat4::look_at_rh(Vec3::new(0.0, 0.0, 5.0), Vec3::ZERO, Vec3::Y);
+    let proj = Mat4::perspective_rh(std::f32::consts::FRAC_PI_4, 16.0 / 9.0, 0.1, 100.0);
+    let mut camera = Camera::new(view, proj, create_test_frustum(), create_test_viewport());
+    let jitter = crate::camera::taa_jitter(1, crate::camera::DEFAULT_TAA_SAMPLE_COUNT);
+    camera.set_jitter(jitter);
+
+    let expected = crate::camera::jitter_projection(&proj, jitter, glam::Vec2::new(1920.0, 1080.0));
+    assert_eq!(camera.jitter(), jitter);
+    assert_eq!(camera.jittered_projection_matrix(), expected);
+    assert_eq!(camera.jittered_view_projection_matrix(), expected * view);
+    // The unjittered matrix stays available for culling
+    assert_eq!(*camera.projection_matrix(), proj);
+}
+
+#[test]
+fn test_oblique_projection_matrix_from_world_plane() {
+    let view = Mat4::look_at_rh(Vec3::new(0.0, 5.0, 0.0), Vec3::ZERO, Vec3::NEG_Z);
+    let proj = Mat4::perspective_rh(std::f32::consts::FRAC_PI_4, 16.0 / 9.0, 0.1, 100.0);
+    let camera = Camera::new(view, proj, create_test_frustum(), create_test_viewport());
+
+    // Water plane y = 1 seen from above, keeping what lies below it
+    let oblique = camera.oblique_projection_matrix(glam::Vec4::new(0.0, -1.0, 0.0, 1.0));
+    let on_plane = (oblique * view).project_point3(Vec3::new(0.5, 1.0, 0.2));
+    assert!(on_plane.z.abs() < 1e-4);
+    let above = (oblique * view).project_point3(Vec3::new(0.0, 3.0, 0.0));
+    assert!(above.z < 0.0);
+}
@@ -0,0 +1,158 @@
+//! Cascaded shadow map sub-frusta.
+//!
+//! The camera frustum is cut along its view depth into slices, and each
+//! slice is enclosed in an orthographic light frustum. No allocation: the
+//! split distances are written into a caller slice (one entry more than
+//! cascades) and each cascade is computed on its own.
+
+use glam::{Mat4, Vec3, Vec4};
+use super::camera::Camera;
+use super::frustum::Frustum;
+
+/// Weight of the logarithmic split scheme commonly used (`0` = uniform
+/// splits, `1` = logarithmic)
+pub const DEFAULT_CASCADE_SPLIT_LAMBDA: f32 = 0.75;
+
+/// Cascade split distances between `near` (> 0) and `far` (practical
+/// split scheme: `lambda` blends logarithmic and uniform splits).
+///
+/// `splits` receives `splits.len() - 1` cascades: `splits[0] == near`,
+/// the last entry is `far` and cascade `i` covers `splits[i]..splits[i + 1]`.
+/// Slices shorter than 2 entries are left untouched.
+pub fn cascade_split_distances(near: f32, far: f32, lambda: f32, splits: &mut [f32]) {
+    if splits.len() < 2 {
+        return;
+    }
+    let cascade_count = (splits.len() - 1) as f32;
+    for (i, split) in splits.iter_mut().enumerate() {
+        let p = i as f32 / cascade_count;
+        let uniform = near + (far - near) * p;
+        // near * (far / near)^p; glam maps powf to std or libm
+        let logarithmic = near * Vec3::splat(far / near).powf(p).x;
+        *split = lambda * logarithmic + (1.0 - lambda) * uniform;
+    }
+    // Exact bounds despite rounding
+    splits[0] = near;
+    splits[splits.len() - 1] = far;
+}
+
+/// World-space corners of the frustum of a view-projection matrix: near
+/// face then far face, each at NDC `(-1, -1)`, `(1, -1)`, `(-1, 1)`, `(1, 1)`.
+pub fn frustum_corners(view_projection: &Mat4) -> [Vec3; 8] {
+    let inverse = view_projection.inverse();
+    let mut corners = [Vec3::ZERO; 8];
+    for (i, corner) in corners.iter_mut().enumerate() {
+        let ndc = Vec4::new(
+            if i & 1 == 0 { -1.0 } else { 1.0 },
+            if i & 2 == 0 { -1.0 } else { 1.0 },
+            if i & 4 == 0 { 0.0 } else { 1.0 },
+            1.0,
+        );
+        let world = inverse * ndc;
+        *corner = world.truncate() / world.w;
+    }
+    corners
+}
+
+/// World-space corners of the part of the camera frustum between two view
+/// depths (distances along the camera forward axis), in the order of
+/// `frustum_corners`.
+///
+/// Depths outside the camera's near/far range extrapolate the frustum edges.
+pub fn sub_frustum_corners(camera: &Camera, near_depth: f32, far_depth: f32) -> [Vec3; 8] {
+    let corners = frustum_corners(&camera.view_projection_matrix());
+    let world = camera.view_matrix().inverse();
+    let position = world.w_axis.truncate();
+    let forward = -world.z_axis.truncate();
+    let camera_near = (corners[0] - position).dot(forward);
+    let camera_far = (corners[4] - position).dot(forward);
+    let range = camera_far - camera_near;
+
+    let mut result = [Vec3::ZERO; 8];
+    for i in 0..4 {
+        let (near_corner, far_corner) = (corners[i], corners[i + 4]);
+        result[i] = near_corner.lerp(far_corner, (near_depth - camera_near) / range);
+        result[i + 4] = near_corner.lerp(far_corner, (far_depth - camera_near) / range);
+    }
+    result
+}
+
+/// Orthographic light frustum enclosing one slice of the camera frustum.
+#[derive(Debug, Clone, Copy)]
+pub struct ShadowCascade {
+    /// Light view matrix (looking along the light direction)
+    pub view: Mat4,
+    /// Orthographic projection (`[0, 1]` depth)
+    pub projection: Mat4,
+    /// View depth where the cascade starts
+    pub split_near: f32,
+    /// View depth where the cascade ends
+    pub split_far: f32,
+}
+
+impl ShadowCascade {
+    /// Fit a cascade to the camera frustum between `split_near` and
+    /// `split_far`, for a directional light shining along `light_direction`.
+    ///
+    /// The light frustum is built around the bounding sphere of the slice,
+    /// so its size does not change when the camera rotates, and its center
+    /// is snapped to the shadow map texels (`resolution` texels wide) so
+    /// shadow edges do not shimmer when the camera moves.
+    ///
+    /// The depth range covers the sphere only: casters farther toward the
+    /// light need a longer range (e.g. depth clamping in the shadow pipeline).
+    pub fn fit(
+        camera: &Camera,
+        light_direction: Vec3,
+        split_near: f32,
+        split_far: f32,
+        resolution: u32,
+    ) -> Self {
+        let corners = sub_frustum_corners(camera, split_near, split_far);
+        let mut center = Vec3::ZERO;
+        for corner in &corners {
+            center += *corner;
+        }
+        center /= corners.len() as f32;
+        let mut radius: f32 = 0.0;
+        for corner in &corners {
+            radius = radius.max(corner.distance(center));
+        }
+
+        let direction = light_direction.normalize_or_zero();
+        let up = if direction.cross(Vec3::Y).length_squared() > 1e-6 { Vec3::Y } else { Vec3::Z };
+
+        // Snap the center to whole texels in light space
+        let texel = 2.0 * radius / resolution.max(1) as f32;
+        let light_rotation = Mat4::look_at_rh(Vec3::ZERO, direction, up);
+        let light_center = light_rotation.transform_point3(center);
+        let center = if texel > 0.0 {
+            let snapped = (light_center / texel).floor() * texel;
+            light_rotation.inverse().transform_point3(Vec3::new(snapped.x, snapped.y, light_center.z))
+        } else {
+            center
+        };
+
+        let eye = center - direction * radius;
+        Self {
+            view: Mat4::look_at_rh(eye, center, up),
+            projection: Mat4::orthographic_rh(-radius, radius, -radius, radius, 0.0, 2.0 * radius),
+            split_near,
+            split_far,
+        }
+    }
+
+    /// Combined view-projection matrix (projection * view)
+    pub fn view_projection(&self) -> Mat4 {
+        self.projection * self.view
+    }
+
+    /// Culling frustum of the cascade
+    pub fn frustum(&self) -> Frustum {
+        Frustum::from_view_projection(&self.view_projection())
+    }
+}
+
+#[cfg(test)]
+#[path = "cascade_tests.rs"]
+mod tests;
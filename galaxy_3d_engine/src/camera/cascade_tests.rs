@@ -0,0 +1,130 @@
+use super::*;
+use crate::graphics_device::viewport::Viewport;
+
+fn make_camera() -> Camera {
+    let view = Mat4::look_at_rh(Vec3::new(2.0, 3.0, 10.0), Vec3::new(2.0, 0.0, 0.0), Vec3::Y);
+    let projection = Mat4::perspective_rh(std::f32::consts::FRAC_PI_3, 16.0 / 9.0, 0.5, 200.0);
+    let viewport = Viewport { x: 0.0, y: 0.0, width: 1920.0, height: 1080.0, min_depth: 0.0, max_depth: 1.0 };
+    Camera::new(view, projection, Frustum::from_view_projection(&(projection * view)), viewport)
+}
+
+fn view_depth(camera: &Camera, point: Vec3) -> f32 {
+    -camera.view_matrix().transform_point3(point).z
+}
+
+// ============================================================================
+// Split distances
+// ============================================================================
+
+#[test]
+fn test_split_distances_uniform_and_logarithmic() {
+    let mut splits = [0.0; 5];
+    cascade_split_distances(1.0, 16.0, 0.0, &mut splits);
+    assert_eq!(splits, [1.0, 4.75, 8.5, 12.25, 16.0]);
+
+    cascade_split_distances(1.0, 16.0, 1.0, &mut splits);
+    for (split, expected) in splits.iter().zip([1.0, 2.0, 4.0, 8.0, 16.0]) {
+        assert!((split - expected).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn test_split_distances_increase() {
+    let mut splits = [0.0; 4];
+    cascade_split_distances(0.1, 500.0, DEFAULT_CASCADE_SPLIT_LAMBDA, &mut splits);
+    assert_eq!(splits[0], 0.1);
+    assert_eq!(splits[3], 500.0);
+    assert!(splits.windows(2).all(|pair| pair[0] < pair[1]));
+
+    // Too short: untouched
+    let mut single = [7.0];
+    cascade_split_distances(0.1, 500.0, 0.5, &mut single);
+    assert_eq!(single, [7.0]);
+}
+
+// ============================================================================
+// Frustum corners
+// ============================================================================
+
+#[test]
+fn test_frustum_corners_of_camera() {
+    let camera = make_camera();
+    let corners = frustum_corners(&camera.view_projection_matrix());
+    for corner in &corners[..4] {
+        assert!((view_depth(&camera, *corner) - 0.5).abs() < 1e-3);
+    }
+    for corner in &corners[4..] {
+        assert!((view_depth(&camera, *corner) - 200.0).abs() < 0.1);
+    }
+    // Corners reproject to the NDC corners
+    let ndc = camera.view_projection_matrix().project_point3(corners[3]);
+    assert!(ndc.abs_diff_eq(Vec3::new(1.0, 1.0, 0.0), 1e-4));
+}
+
+#[test]
+fn test_sub_frustum_corners_at_split_depths() {
+    let camera = make_camera();
+    let corners = sub_frustum_corners(&camera, 10.0, 30.0);
+    let vp = camera.view_projection_matrix();
+    for (i, corner) in corners.iter().enumerate() {
+        let expected_depth = if i < 4 { 10.0 } else { 30.0 };
+        assert!((view_depth(&camera, *corner) - expected_depth).abs() < 1e-3);
+        // Still on the frustum edges: NDC x and y are +-1
+        let ndc = vp.project_point3(*corner);
+        assert!((ndc.x.abs() - 1.0).abs() < 1e-3 && (ndc.y.abs() - 1.0).abs() < 1e-3);
+    }
+}
+
+// ============================================================================
+// Cascade fitting
+// ============================================================================
+
+#[test]
+fn test_cascade_encloses_its_slice() {
+    let camera = make_camera();
+    let light = Vec3::new(-0.3, -1.0, 0.2);
+    let cascade = ShadowCascade::fit(&camera, light, 5.0, 25.0, 2048);
+    assert_eq!((cascade.split_near, cascade.split_far), (5.0, 25.0));
+
+    let vp = cascade.view_projection();
+    for corner in sub_frustum_corners(&camera, 5.0, 25.0) {
+        let ndc = vp.project_point3(corner);
+        assert!(ndc.x.abs() <= 1.0 + 1e-3 && ndc.y.abs() <= 1.0 + 1e-3);
+        assert!((-1e-3..=1.0 + 1e-3).contains(&ndc.z));
+    }
+    // The light looks along its direction
+    let forward = -cascade.view.inverse().z_axis.truncate();
+    assert!(forward.abs_diff_eq(light.normalize(), 1e-5));
+}
+
+#[test]
+fn test_cascade_stable_under_rotation_and_snapped() {
+    let camera = make_camera();
+    let mut turned = camera.clone();
+    turned.set_view(Mat4::look_at_rh(Vec3::new(2.0, 3.0, 10.0), Vec3::new(-5.0, 1.0, 0.0), Vec3::Y));
+
+    let light = Vec3::NEG_Y;
+    let a = ShadowCascade::fit(&camera, light, 1.0, 20.0, 1024);
+    let b = ShadowCascade::fit(&turned, light, 1.0, 20.0, 1024);
+    // Bounding sphere: same extent whatever the camera orientation
+    assert!((a.projection.x_axis.x - b.projection.x_axis.x).abs() < 1e-4);
+
+    // Straight-down light: the center is snapped to whole texels
+    let radius = 1.0 / a.projection.x_axis.x;
+    let texel = 2.0 * radius / 1024.0;
+    let center = a.view.inverse().w_axis.truncate();
+    for coordinate in [center.x / texel, center.z / texel] {
+        assert!((coordinate - coordinate.round()).abs() < 1e-2);
+    }
+}
+
+#[test]
+fn test_cascade_frustum_culls_outside() {
+    let camera = make_camera();
+    let cascade = ShadowCascade::fit(&camera, Vec3::new(0.2, -1.0, 0.1), 1.0, 10.0, 1024);
+    let frustum = cascade.frustum();
+    let inside = crate::scene::AABB { min: Vec3::new(1.5, -0.5, 4.5), max: Vec3::new(2.5, 0.5, 5.5) };
+    let far_away = crate::scene::AABB { min: Vec3::splat(900.0), max: Vec3::splat(901.0) };
+    assert!(frustum.intersects_aabb(&inside));
+    assert!(!frustum.intersects_aabb(&far_away));
+}
@@ -0,0 +1,155 @@
+/// DepthPyramid — hierarchical-Z (Hi-Z) buffer for occlusion tests.
+///
+/// Level 0 is a depth image (standard depth: 0 at the near plane, 1 at the
+/// far plane). Each following level halves the resolution (rounding up) and
+/// keeps the farthest depth of the texels it covers, so one texel of level
+/// N bounds the depth of a `2^N x 2^N` block of level 0.
+///
+/// An AABB is occluded when its nearest projected depth is farther than
+/// the farthest depth under its screen rectangle. The test reads the level
+/// where that rectangle spans at most two texels per axis: a handful of
+/// reads per instance, whatever its size on screen.
+///
+/// The pyramid is built on the CPU (`from_depth`) from the previous frame's
+/// depth target, read back without stalling through a `ReadbackManager`.
+/// This is a reduced scope: there is no compute-pass build, because the
+/// backends do not expose compute dispatch. Since `OcclusionCuller` tests
+/// on the CPU, a GPU build would still read the levels back; it would only
+/// move the downsampling off the main thread.
+
+use glam::{Mat4, Vec3};
+use crate::scene::AABB;
+
+/// Largest span, in texels per axis, read by one occlusion test
+const MAX_TEST_SPAN: u32 = 2;
+
+/// Clip-space `w` under which a corner is considered at or behind the eye
+const MIN_CLIP_W: f32 = 1e-5;
+
+/// Hierarchical depth buffer (see module docs).
+#[derive(Debug, Clone)]
+pub struct DepthPyramid {
+    /// `(width, height, texels)` per level, level 0 first
+    levels: Vec<(u32, u32, Vec<f32>)>,
+}
+
+impl DepthPyramid {
+    /// Build the pyramid from a row-major depth image (row 0 at the top,
+    /// as in a Vulkan framebuffer).
+    ///
+    /// Returns None when the size is zero or `depth` does not hold
+    /// `width * height` values.
+    pub fn from_depth(width: u32, height: u32, depth: &[f32]) -> Option<Self> {
+        if width == 0 || height == 0 || depth.len() != width as usize * height as usize {
+            return None;
+        }
+        let mut levels = vec![(width, height, depth.to_vec())];
+        while let Some(&(w, h, ref texels)) = levels.last() {
+            if w == 1 && h == 1 {
+                break;
+            }
+            let (next_w, next_h) = (w.div_ceil(2), h.div_ceil(2));
+            let mut next = Vec::with_capacity(next_w as usize * next_h as usize);
+            for y in 0..next_h {
+                for x in 0..next_w {
+                    let mut farthest = f32::MIN;
+                    for sy in (y * 2)..(y * 2 + 2).min(h) {
+                        for sx in (x * 2)..(x * 2 + 2).min(w) {
+                            farthest = farthest.max(texels[(sy * w + sx) as usize]);
+                        }
+                    }
+                    next.push(farthest);
+                }
+            }
+            levels.push((next_w, next_h, next));
+        }
+        Some(Self { levels })
+    }
+
+    /// Width of level 0
+    pub fn width(&self) -> u32 {
+        self.levels[0].0
+    }
+
+    /// Height of level 0
+    pub fn height(&self) -> u32 {
+        self.levels[0].1
+    }
+
+    /// Number of levels (down to 1x1)
+    pub fn level_count(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Farthest depth stored in a texel of a level (None when out of range)
+    pub fn texel(&self, level: usize, x: u32, y: u32) -> Option<f32> {
+        let (w, h, texels) = self.levels.get(level)?;
+        if x >= *w || y >= *h {
+            return None;
+        }
+        Some(texels[(y * w + x) as usize])
+    }
+
+    /// Whether a world-space AABB is hidden behind the depth stored in the
+    /// pyramid, seen through `view_projection` (the matrix the depth was
+    /// rendered with).
+    ///
+    /// Conservative: boxes crossing the near plane or lying outside the
+    /// screen are never occluded (frustum culling handles the latter).
+    pub fn is_occluded(&self, aabb: &AABB, view_projection: &Mat4) -> bool {
+        let mut ndc_min = Vec3::splat(f32::MAX);
+        let mut ndc_max = Vec3::splat(f32::MIN);
+        for i in 0..8 {
+            let corner = Vec3::new(
+                if i & 1 == 0 { aabb.min.x } else { aabb.max.x },
+                if i & 2 == 0 { aabb.min.y } else { aabb.max.y },
+                if i & 4 == 0 { aabb.min.z } else { aabb.max.z },
+            );
+            let clip = *view_projection * corner.extend(1.0);
+            if clip.w <= MIN_CLIP_W {
+                return false;
+            }
+            let ndc = clip.truncate() / clip.w;
+            ndc_min = ndc_min.min(ndc);
+            ndc_max = ndc_max.max(ndc);
+        }
+        if !(ndc_min.is_finite() && ndc_max.is_finite()) {
+            return false;
+        }
+
+        // NDC [-1, 1] -> level 0 pixels, clamped to the screen
+        let (width, height) = (self.width() as f32, self.height() as f32);
+        let to_px = |ndc: f32, size: f32| (ndc * 0.5 + 0.5) * size;
+        let (x_min, x_max) = (to_px(ndc_min.x, width), to_px(ndc_max.x, width));
+        let (y_min, y_max) = (to_px(ndc_min.y, height), to_px(ndc_max.y, height));
+        if x_max <= 0.0 || y_max <= 0.0 || x_min >= width || y_min >= height {
+            return false;
+        }
+        let x0 = x_min.max(0.0) as u32;
+        let y0 = y_min.max(0.0) as u32;
+        let x1 = (x_max.ceil() as u32).clamp(x0 + 1, self.width()) - 1;
+        let y1 = (y_max.ceil() as u32).clamp(y0 + 1, self.height()) - 1;
+
+        // Coarsest useful level: the rectangle spans at most MAX_TEST_SPAN texels
+        let mut level = 0;
+        while level + 1 < self.levels.len()
+            && ((x1 >> level) - (x0 >> level) + 1 > MAX_TEST_SPAN
+                || (y1 >> level) - (y0 >> level) + 1 > MAX_TEST_SPAN)
+        {
+            level += 1;
+        }
+
+        let (w, _, texels) = &self.levels[level];
+        let mut farthest = f32::MIN;
+        for y in (y0 >> level)..=(y1 >> level) {
+            for x in (x0 >> level)..=(x1 >> level) {
+                farthest = farthest.max(texels[(y * w + x) as usize]);
+            }
+        }
+        ndc_min.z > farthest
+    }
+}
+
+#[cfg(test)]
+#[path = "depth_pyramid_tests.rs"]
+mod tests;
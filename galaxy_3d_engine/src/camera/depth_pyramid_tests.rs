@@ -0,0 +1,103 @@
+use super::*;
+
+const WIDTH: u32 = 64;
+const HEIGHT: u32 = 32;
+
+/// Camera at the origin looking down -Z (depth 0 at near, 1 at far)
+fn view_projection() -> Mat4 {
+    Mat4::perspective_rh(std::f32::consts::FRAC_PI_2, WIDTH as f32 / HEIGHT as f32, 0.1, 100.0)
+}
+
+/// Depth of a view-space point seen through `view_projection()`
+fn depth_at(z: f32) -> f32 {
+    let clip = view_projection() * Vec3::new(0.0, 0.0, z).extend(1.0);
+    clip.z / clip.w
+}
+
+fn unit_box_at(z: f32) -> AABB {
+    AABB { min: Vec3::new(-0.5, -0.5, z - 0.5), max: Vec3::new(0.5, 0.5, z + 0.5) }
+}
+
+/// Full-screen wall at view-space `z`
+fn wall_pyramid(z: f32) -> DepthPyramid {
+    let depth = vec![depth_at(z); (WIDTH * HEIGHT) as usize];
+    DepthPyramid::from_depth(WIDTH, HEIGHT, &depth).unwrap()
+}
+
+// ============================================================================
+// Construction
+// ============================================================================
+
+#[test]
+fn test_from_depth_rejects_invalid_sizes() {
+    assert!(DepthPyramid::from_depth(0, 4, &[]).is_none());
+    assert!(DepthPyramid::from_depth(2, 2, &[0.0; 3]).is_none());
+}
+
+#[test]
+fn test_levels_halve_down_to_one_texel_with_odd_sizes() {
+    // 5x3 -> 3x2 -> 2x1 -> 1x1
+    let depth: Vec<f32> = (0..15).map(|i| i as f32).collect();
+    let pyramid = DepthPyramid::from_depth(5, 3, &depth).unwrap();
+    assert_eq!(pyramid.level_count(), 4);
+    assert_eq!((pyramid.width(), pyramid.height()), (5, 3));
+    assert_eq!(pyramid.texel(1, 2, 1), Some(14.0));
+    assert_eq!(pyramid.texel(1, 3, 0), None);
+    assert_eq!(pyramid.texel(3, 0, 0), Some(14.0));
+}
+
+#[test]
+fn test_levels_keep_farthest_depth() {
+    let mut depth = vec![0.25; 16];
+    depth[5] = 0.75;
+    let pyramid = DepthPyramid::from_depth(4, 4, &depth).unwrap();
+    assert_eq!(pyramid.texel(1, 0, 0), Some(0.75));
+    assert_eq!(pyramid.texel(1, 1, 1), Some(0.25));
+    assert_eq!(pyramid.texel(2, 0, 0), Some(0.75));
+}
+
+// ============================================================================
+// Occlusion test
+// ============================================================================
+
+#[test]
+fn test_box_behind_wall_is_occluded() {
+    let pyramid = wall_pyramid(-10.0);
+    assert!(pyramid.is_occluded(&unit_box_at(-20.0), &view_projection()));
+}
+
+#[test]
+fn test_box_in_front_of_wall_is_visible() {
+    let pyramid = wall_pyramid(-10.0);
+    assert!(!pyramid.is_occluded(&unit_box_at(-5.0), &view_projection()));
+}
+
+#[test]
+fn test_box_crossing_wall_is_visible() {
+    let pyramid = wall_pyramid(-10.0);
+    let aabb = AABB { min: Vec3::new(-0.5, -0.5, -12.0), max: Vec3::new(0.5, 0.5, -8.0) };
+    assert!(!pyramid.is_occluded(&aabb, &view_projection()));
+}
+
+#[test]
+fn test_hole_in_wall_keeps_box_visible() {
+    let mut depth = vec![depth_at(-10.0); (WIDTH * HEIGHT) as usize];
+    // Far texel at the screen center, under the box
+    depth[((HEIGHT / 2) * WIDTH + WIDTH / 2) as usize] = 1.0;
+    let pyramid = DepthPyramid::from_depth(WIDTH, HEIGHT, &depth).unwrap();
+    assert!(!pyramid.is_occluded(&unit_box_at(-20.0), &view_projection()));
+}
+
+#[test]
+fn test_box_crossing_near_plane_is_never_occluded() {
+    let pyramid = wall_pyramid(-0.2);
+    let aabb = AABB { min: Vec3::new(-0.5, -0.5, -1.0), max: Vec3::new(0.5, 0.5, 1.0) };
+    assert!(!pyramid.is_occluded(&aabb, &view_projection()));
+}
+
+#[test]
+fn test_box_off_screen_is_not_occluded() {
+    let pyramid = wall_pyramid(-10.0);
+    let aabb = AABB { min: Vec3::new(500.0, -0.5, -20.5), max: Vec3::new(501.0, 0.5, -19.5) };
+    assert!(!pyramid.is_occluded(&aabb, &view_projection()));
+}
@@ -1,4 +1,4 @@
-use glam::{Mat4, Vec3};
+use glam::{Mat4, Vec3, Vec4};
 use crate::scene::AABB;
 use super::*;
 
@@ -197,3 +197,97 @@ fn test_plane_constants() {
     assert_eq!(PLANE_NEAR, 4);
     assert_eq!(PLANE_FAR, 5);
 }
+
+// ============================================================================
+// Reference snapshots
+// ============================================================================
+
+#[test]
+fn test_orthographic_plane_snapshot() {
+    // Unit box in x/y, depth 0..10 along -Z
+    let frustum = Frustum::from_view_projection(&Mat4::orthographic_rh(-1.0, 1.0, -1.0, 1.0, 0.0, 10.0));
+    let expected = [
+        Vec4::new(1.0, 0.0, 0.0, 1.0),   // left:   x >= -1
+        Vec4::new(-1.0, 0.0, 0.0, 1.0),  // right:  x <= 1
+        Vec4::new(0.0, 1.0, 0.0, 1.0),   // bottom: y >= -1
+        Vec4::new(0.0, -1.0, 0.0, 1.0),  // top:    y <= 1
+        // near: extracted with the -w..w depth convention, so with a 0..1
+        // depth projection it sits one depth range behind the camera
+        // (conservative, never culls visible objects)
+        Vec4::new(0.0, 0.0, -1.0, 10.0),
+        Vec4::new(0.0, 0.0, 1.0, 10.0),  // far:    z >= -10
+    ];
+    for (i, (plane, want)) in frustum.planes.iter().zip(expected).enumerate() {
+        assert!((*plane - want).abs().max_element() < 1e-5, "plane {}: {:?} != {:?}", i, plane, want);
+    }
+}
+
+#[test]
+fn test_points_exactly_on_planes_are_inside() {
+    let frustum = Frustum::from_view_projection(&Mat4::orthographic_rh(-1.0, 1.0, -1.0, 1.0, 0.0, 10.0));
+    let on_planes = [
+        Vec3::new(-1.0, 0.0, -5.0),
+        Vec3::new(1.0, 0.0, -5.0),
+        Vec3::new(0.0, -1.0, -5.0),
+        Vec3::new(0.0, 1.0, -5.0),
+        Vec3::new(0.0, 0.0, -10.0),
+        Vec3::new(1.0, 1.0, -10.0), // corner: three planes at once
+    ];
+    for p in on_planes {
+        let point = AABB { min: p, max: p };
+        assert!(frustum.intersects_aabb(&point), "{:?}", p);
+        assert_ne!(frustum.classify_aabb(&point), FrustumTest::Outside, "{:?}", p);
+    }
+
+    // Just past a plane is outside
+    let past = Vec3::new(1.0 + 1e-3, 0.0, -5.0);
+    assert!(!frustum.intersects_aabb(&AABB { min: past, max: past }));
+}
+
+#[test]
+fn test_zero_size_aabb_classification() {
+    let frustum = Frustum::from_view_projection(&Mat4::orthographic_rh(-1.0, 1.0, -1.0, 1.0, 0.0, 10.0));
+    let inside = AABB { min: Vec3::new(0.5, 0.5, -5.0), max: Vec3::new(0.5, 0.5, -5.0) };
+    let outside = AABB { min: Vec3::new(3.0, 0.0, -5.0), max: Vec3::new(3.0, 0.0, -5.0) };
+    assert_eq!(frustum.classify_aabb(&inside), FrustumTest::Inside);
+    assert_eq!(frustum.classify_aabb(&outside), FrustumTest::Outside);
+    assert!(!frustum.intersects_aabb(&outside));
+}
+
+#[test]
+fn test_perspective_reference_grid_matches_clip_space() {
+    let vp = Mat4::perspective_rh(std::f32::consts::FRAC_PI_3, 1.5, 1.0, 20.0)
+        * Mat4::look_at_rh(Vec3::new(2.0, 1.0, 5.0), Vec3::new(0.0, 0.0, -3.0), Vec3::Y);
+    let frustum = Frustum::from_view_projection(&vp);
+
+    // Points clearly inside the clip volume (0..w depth) must never be
+    // culled; points clearly outside the side or far planes must be.
+    // Points within the margin of a plane are not checked.
+    let mut checked = 0;
+    for xi in -12..=12 {
+        for yi in -12..=12 {
+            for zi in -12..=12 {
+                let p = Vec3::new(xi as f32, yi as f32, zi as f32) * 2.0;
+                let c = vp * p.extend(1.0);
+                let margin = 1e-3 * (1.0 + c.w.abs());
+                let inside = c.x.abs() < c.w - margin
+                    && c.y.abs() < c.w - margin
+                    && c.z > margin
+                    && c.z < c.w - margin;
+                let outside = c.w < -margin
+                    || c.x.abs() > c.w + margin
+                    || c.y.abs() > c.w + margin
+                    || c.z > c.w + margin;
+                let visible = frustum.intersects_aabb(&AABB { min: p, max: p });
+                if inside {
+                    assert!(visible, "{:?} inside clip volume was culled", p);
+                    checked += 1;
+                } else if outside {
+                    assert!(!visible, "{:?} outside clip volume was kept", p);
+                    checked += 1;
+                }
+            }
+        }
+    }
+    assert!(checked > 10_000);
+}
@@ -5,6 +5,10 @@
 //! systems (Unity `relativeHeight`, Simplygon "on-screen-size", Nanite
 //! per-cluster screen-space error) — invariant to viewport resolution and
 //! field of view.
+//!
+//! `reference_sphere_diameter` evaluates the same metric through a fixed
+//! reference projection instead of the camera's: the result then depends on
+//! the view distance only (distance-based LOD selection).
 
 use glam::Vec3;
 use super::camera::Camera;
@@ -29,19 +33,44 @@ pub fn project_sphere_diameter(
     radius_ws: f32,
     camera: &Camera,
 ) -> f32 {
-    let world = camera.view_matrix().inverse();
-    let cam_pos = world.w_axis.truncate();
-    let cam_forward = -world.z_axis.truncate();
-
-    let view_depth = (center_ws - cam_pos).dot(cam_forward).max(1e-4);
-
     // projection[1][1] == 1 / tan(fov_y / 2) for a standard perspective matrix
     let p11 = camera.projection_matrix().y_axis.y;
     // Protect against orthographic / degenerate matrices (p11 == 0).
     let fov_y_factor = if p11.abs() > 1e-6 { 2.0 / p11 } else { 0.0 };
 
-    let viewport_h = camera.viewport().height as f32;
+    let viewport_h = camera.viewport().height;
+
+    sphere_diameter(view_depth(center_ws, camera), radius_ws, fov_y_factor, viewport_h)
+}
+
+/// Projected diameter, in pixels, of a world-space bounding sphere seen
+/// through a reference projection, at the camera's forward-axis depth.
+///
+/// `pixels_per_unit` is the reference focal length: the pixels covered by
+/// one world unit at a depth of one unit (`viewport_height / (2 *
+/// tan(fov_y / 2))`). Unlike `project_sphere_diameter`, camera zoom and
+/// render resolution do not change the result: only the object's size and
+/// view distance do.
+pub fn reference_sphere_diameter(
+    center_ws: Vec3,
+    radius_ws: f32,
+    camera: &Camera,
+    pixels_per_unit: f32,
+) -> f32 {
+    sphere_diameter(view_depth(center_ws, camera), radius_ws, 1.0, pixels_per_unit)
+}
+
+/// Forward-axis depth of a point, clamped to a small epsilon
+fn view_depth(center_ws: Vec3, camera: &Camera) -> f32 {
+    let world = camera.view_matrix().inverse();
+    let cam_pos = world.w_axis.truncate();
+    let cam_forward = -world.z_axis.truncate();
+
+    (center_ws - cam_pos).dot(cam_forward).max(1e-4)
+}
 
+/// Diameter in pixels from depth, radius, `2 * tan(fov_y / 2)` and viewport height
+fn sphere_diameter(view_depth: f32, radius_ws: f32, fov_y_factor: f32, viewport_h: f32) -> f32 {
     (2.0 * radius_ws * viewport_h) / (view_depth * fov_y_factor.max(1e-6))
 }
 
@@ -80,3 +80,41 @@ fn test_zero_radius_is_zero() {
     let size = project_sphere_diameter(Vec3::new(0.0, 0.0, -10.0), 0.0, &camera);
     assert_eq!(size, 0.0);
 }
+
+#[test]
+fn test_distance_sweep_is_monotonic_and_finite() {
+    let camera = make_camera(std::f32::consts::FRAC_PI_3, 1280.0, 720.0);
+    let mut previous = f32::INFINITY;
+    for step in 0..200 {
+        let z = -0.05 - step as f32 * 2.5;
+        let size = project_sphere_diameter(Vec3::new(0.0, 0.0, z), 1.0, &camera);
+        assert!(size.is_finite() && size >= 0.0, "z={} size={}", z, size);
+        assert!(size <= previous, "z={} size={} > {}", z, size, previous);
+        previous = size;
+    }
+}
+
+#[test]
+fn test_reference_diameter_matches_camera_with_same_projection() {
+    let fov_y = std::f32::consts::FRAC_PI_3;
+    let cam = make_camera(fov_y, 1920.0, 1080.0);
+    let pixels_per_unit = 1080.0 / (2.0 * (fov_y * 0.5).tan());
+    let center = Vec3::new(0.0, 0.0, -25.0);
+    let from_camera = project_sphere_diameter(center, 2.0, &cam);
+    let from_reference = reference_sphere_diameter(center, 2.0, &cam, pixels_per_unit);
+    assert!((from_camera - from_reference).abs() < 1e-2);
+}
+
+#[test]
+fn test_reference_diameter_ignores_camera_zoom_and_resolution() {
+    let wide = make_camera(std::f32::consts::FRAC_PI_2, 1920.0, 1080.0);
+    let zoomed = make_camera(0.2, 640.0, 360.0);
+    let center = Vec3::new(0.0, 0.0, -40.0);
+    assert_eq!(
+        reference_sphere_diameter(center, 1.0, &wide, 900.0),
+        reference_sphere_diameter(center, 1.0, &zoomed, 900.0),
+    );
+    // Halving the distance doubles the diameter
+    let near = reference_sphere_diameter(Vec3::new(0.0, 0.0, -20.0), 1.0, &wide, 900.0);
+    assert!((near - 2.0 * reference_sphere_diameter(center, 1.0, &wide, 900.0)).abs() < 1e-3);
+}
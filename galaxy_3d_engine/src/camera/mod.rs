@@ -3,16 +3,37 @@
 //! Provides passive data containers for the rendering pipeline.
 //! The engine does NOT store or manage cameras — they are tools
 //! provided by the engine, owned and driven by the caller.
+//!
+//! Camera, frustum, projection utilities (TAA jitter, oblique clipping, mirroring),
+//! shadow cascades and LOD metric are part of the core built without the
+//! `renderer` feature; visibility results need it.
 
 mod camera;
 mod frustum;
 mod lod;
-mod visible_instances;
+mod projection;
+mod cascade;
 
 pub use camera::Camera;
 pub use frustum::{
     Frustum, FrustumTest,
     PLANE_LEFT, PLANE_RIGHT, PLANE_BOTTOM, PLANE_TOP, PLANE_NEAR, PLANE_FAR,
 };
-pub use lod::project_sphere_diameter;
-pub use visible_instances::{VisibleInstances, VisibleInstance};
+pub use lod::{project_sphere_diameter, reference_sphere_diameter};
+pub use projection::{
+    halton, taa_jitter, jitter_ndc_offset, jitter_projection, oblique_projection, plane_to_view_space,
+    reflection_matrix,
+    DEFAULT_TAA_SAMPLE_COUNT,
+};
+pub use cascade::{
+    cascade_split_distances, frustum_corners, sub_frustum_corners, ShadowCascade,
+    DEFAULT_CASCADE_SPLIT_LAMBDA,
+};
+
+cfg_renderer! {
+    mod visible_instances;
+    mod depth_pyramid;
+
+    pub use visible_instances::{VisibleInstances, VisibleInstance};
+    pub use depth_pyramid::DepthPyramid;
+}
@@ -0,0 +1,118 @@
+//! Projection utilities: TAA jitter, oblique near-plane clipping and
+//! planar mirroring.
+//!
+//! Projections follow `Mat4::perspective_rh` / `Mat4::orthographic_rh`:
+//! right-handed view space (camera looking down -Z) and a `[0, 1]` clip
+//! depth, as Vulkan expects.
+
+use glam::{Mat4, Vec2, Vec3, Vec4};
+
+/// Typical length of a TAA jitter sequence (`taa_jitter`)
+pub const DEFAULT_TAA_SAMPLE_COUNT: u32 = 8;
+
+/// Element `index` of the Halton low-discrepancy sequence in `base`, in
+/// `[0, 1)`. Index 0 returns 0: TAA sequences usually start at 1.
+pub fn halton(index: u32, base: u32) -> f32 {
+    let mut index = index;
+    let mut fraction = 1.0;
+    let mut result = 0.0;
+    while index > 0 {
+        fraction /= base as f32;
+        result += fraction * (index % base) as f32;
+        index /= base;
+    }
+    result
+}
+
+/// Sub-pixel TAA jitter of a frame, in pixels, in `[-0.5, 0.5)`.
+///
+/// Halton (2, 3) sequence of `sample_count` samples (at least 1), cycling
+/// with the frame index. Pass it to `Camera::set_jitter`.
+pub fn taa_jitter(frame_index: u64, sample_count: u32) -> Vec2 {
+    let index = (frame_index % sample_count.max(1) as u64) as u32 + 1;
+    Vec2::new(halton(index, 2), halton(index, 3)) - 0.5
+}
+
+/// NDC shift of a jitter in pixels on a viewport of `viewport_size`
+/// (zero for an empty viewport).
+pub fn jitter_ndc_offset(jitter: Vec2, viewport_size: Vec2) -> Vec2 {
+    if viewport_size.cmple(Vec2::ZERO).any() {
+        return Vec2::ZERO;
+    }
+    jitter * 2.0 / viewport_size
+}
+
+/// Projection shifted by a jitter in pixels on a viewport of `viewport_size`.
+///
+/// The offset is applied in clip space (`x += dx * w`), so it is a constant
+/// NDC shift (`jitter_ndc_offset`) for perspective and orthographic
+/// projections alike. Positive values move the image toward +x / +y in
+/// framebuffer coordinates.
+pub fn jitter_projection(projection: &Mat4, jitter: Vec2, viewport_size: Vec2) -> Mat4 {
+    let offset = jitter_ndc_offset(jitter, viewport_size);
+    if offset == Vec2::ZERO {
+        return *projection;
+    }
+    Mat4::from_translation(Vec3::new(offset.x, offset.y, 0.0)) * *projection
+}
+
+/// Projection whose near plane is replaced by a view-space clip plane
+/// (Lengyel, "Oblique View Frustum Depth Projection and Clipping").
+///
+/// Used for planar reflections: geometry behind the mirror is clipped by
+/// the depth test instead of a clip distance. `clip_plane` is
+/// `(normal, d)` with `dot(normal, p) + d >= 0` on the visible side; the
+/// camera must be on the clipped side (`d < 0`), otherwise the projection
+/// is returned unchanged. The far plane becomes oblique too, so depth
+/// precision is reduced: use it for reflection passes only.
+pub fn oblique_projection(projection: &Mat4, clip_plane: Vec4) -> Mat4 {
+    if clip_plane.w >= 0.0 {
+        return *projection;
+    }
+    // Clip-space corner of the frustum opposite to the plane, brought back
+    // to view space
+    let corner = Vec4::new(sign(clip_plane.x), sign(clip_plane.y), 1.0, 1.0);
+    let q = projection.inverse() * corner;
+    let scaled = clip_plane / clip_plane.dot(q);
+
+    // Replace the third row (clip z) with the scaled plane
+    let mut result = *projection;
+    result.x_axis.z = scaled.x;
+    result.y_axis.z = scaled.y;
+    result.z_axis.z = scaled.z;
+    result.w_axis.z = scaled.w;
+    result
+}
+
+/// Plane `(normal, d)` moved from world space to view space
+pub fn plane_to_view_space(view: &Mat4, world_plane: Vec4) -> Vec4 {
+    view.inverse().transpose() * world_plane
+}
+
+/// Mirror transform about a world-space plane `(normal, d)` (the normal
+/// need not be unit length). Applied after the view matrix
+/// (`view * reflection_matrix(plane)`), it gives the camera seeing the
+/// reflection; the mirror flips the triangle winding.
+pub fn reflection_matrix(plane: Vec4) -> Mat4 {
+    let length = plane.truncate().length();
+    if length == 0.0 {
+        return Mat4::IDENTITY;
+    }
+    let normal = plane.truncate() / length;
+    let d = plane.w / length;
+    Mat4::from_cols(
+        (Vec3::X - 2.0 * normal.x * normal).extend(0.0),
+        (Vec3::Y - 2.0 * normal.y * normal).extend(0.0),
+        (Vec3::Z - 2.0 * normal.z * normal).extend(0.0),
+        (-2.0 * d * normal).extend(1.0),
+    )
+}
+
+/// Sign of `value`, 0 counting as positive
+fn sign(value: f32) -> f32 {
+    if value < 0.0 { -1.0 } else { 1.0 }
+}
+
+#[cfg(test)]
+#[path = "projection_tests.rs"]
+mod tests;
@@ -0,0 +1,144 @@
+use super::*;
+
+fn perspective() -> Mat4 {
+    Mat4::perspective_rh(std::f32::consts::FRAC_PI_3, 16.0 / 9.0, 0.1, 100.0)
+}
+
+/// NDC position of a view-space point
+fn project(projection: &Mat4, point: Vec3) -> Vec3 {
+    projection.project_point3(point)
+}
+
+// ============================================================================
+// Halton / TAA jitter
+// ============================================================================
+
+#[test]
+fn test_halton_reference_values() {
+    assert_eq!(halton(0, 2), 0.0);
+    assert_eq!(halton(1, 2), 0.5);
+    assert_eq!(halton(2, 2), 0.25);
+    assert_eq!(halton(3, 2), 0.75);
+    assert!((halton(1, 3) - 1.0 / 3.0).abs() < 1e-6);
+    assert!((halton(2, 3) - 2.0 / 3.0).abs() < 1e-6);
+    assert!((halton(4, 3) - 4.0 / 9.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_taa_jitter_cycles_within_half_pixel() {
+    let first = taa_jitter(0, DEFAULT_TAA_SAMPLE_COUNT);
+    assert_eq!(first, Vec2::new(0.0, 1.0 / 3.0 - 0.5));
+    for frame in 0..DEFAULT_TAA_SAMPLE_COUNT as u64 {
+        let jitter = taa_jitter(frame, DEFAULT_TAA_SAMPLE_COUNT);
+        assert!(jitter.cmpge(Vec2::splat(-0.5)).all() && jitter.cmplt(Vec2::splat(0.5)).all());
+    }
+    assert_eq!(taa_jitter(DEFAULT_TAA_SAMPLE_COUNT as u64 + 3, DEFAULT_TAA_SAMPLE_COUNT),
+        taa_jitter(3, DEFAULT_TAA_SAMPLE_COUNT));
+    // A zero sample count behaves as one sample
+    assert_eq!(taa_jitter(5, 0), taa_jitter(0, 1));
+}
+
+#[test]
+fn test_jitter_projection_shifts_ndc_by_pixels() {
+    let projection = perspective();
+    let viewport = Vec2::new(1920.0, 1080.0);
+    let jitter = Vec2::new(0.5, -0.25);
+    let jittered = jitter_projection(&projection, jitter, viewport);
+
+    for point in [Vec3::new(0.0, 0.0, -1.0), Vec3::new(3.0, -2.0, -40.0)] {
+        let shift = project(&jittered, point) - project(&projection, point);
+        assert!(shift.truncate().abs_diff_eq(jitter * 2.0 / viewport, 1e-5));
+        assert!(shift.z.abs() < 1e-6);
+    }
+    // Same result for an orthographic projection
+    let ortho = Mat4::orthographic_rh(-10.0, 10.0, -5.0, 5.0, 0.1, 50.0);
+    let shift = project(&jitter_projection(&ortho, jitter, viewport), Vec3::new(1.0, 1.0, -5.0))
+        - project(&ortho, Vec3::new(1.0, 1.0, -5.0));
+    assert!(shift.truncate().abs_diff_eq(jitter * 2.0 / viewport, 1e-5));
+}
+
+#[test]
+fn test_jitter_projection_identity_cases() {
+    let projection = perspective();
+    assert_eq!(jitter_projection(&projection, Vec2::ZERO, Vec2::new(800.0, 600.0)), projection);
+    assert_eq!(jitter_projection(&projection, Vec2::ONE, Vec2::ZERO), projection);
+}
+
+#[test]
+fn test_jitter_ndc_offset() {
+    let offset = jitter_ndc_offset(Vec2::new(0.5, -0.25), Vec2::new(100.0, 50.0));
+    assert!(offset.abs_diff_eq(Vec2::new(0.01, -0.01), 1e-7));
+    assert_eq!(jitter_ndc_offset(Vec2::ONE, Vec2::new(100.0, 0.0)), Vec2::ZERO);
+}
+
+// ============================================================================
+// Oblique clipping
+// ============================================================================
+
+#[test]
+fn test_oblique_projection_maps_clip_plane_to_near_depth() {
+    let projection = perspective();
+    // Mirror plane z = -5 facing away from the camera: visible side is z < -5
+    let plane = Vec4::new(0.0, 0.0, -1.0, -5.0);
+    let oblique = oblique_projection(&projection, plane);
+
+    // Points on the plane land on the near depth, in front of it inside
+    let on_plane = project(&oblique, Vec3::new(0.3, -0.2, -5.0));
+    assert!(on_plane.z.abs() < 1e-4);
+    let beyond = project(&oblique, Vec3::new(0.0, 0.0, -20.0));
+    assert!(beyond.z > 0.0 && beyond.z <= 1.0);
+    // Points between the camera and the plane are clipped
+    assert!(project(&oblique, Vec3::new(0.0, 0.0, -2.0)).z < 0.0);
+
+    // x and y are untouched
+    let point = Vec3::new(1.0, 2.0, -30.0);
+    assert!(project(&oblique, point).truncate().abs_diff_eq(project(&projection, point).truncate(), 1e-5));
+}
+
+#[test]
+fn test_oblique_projection_tilted_plane() {
+    let projection = perspective();
+    let normal = Vec3::new(0.0, 0.6, -0.8);
+    let origin = Vec3::new(0.0, -1.0, -6.0);
+    let plane = normal.extend(-normal.dot(origin));
+    let oblique = oblique_projection(&projection, plane);
+    let on_plane = origin + normal.any_orthonormal_vector() * 0.5;
+    assert!(project(&oblique, on_plane).z.abs() < 1e-4);
+}
+
+#[test]
+fn test_oblique_projection_ignores_plane_facing_camera() {
+    let projection = perspective();
+    assert_eq!(oblique_projection(&projection, Vec4::new(0.0, 0.0, 1.0, 5.0)), projection);
+}
+
+#[test]
+fn test_plane_to_view_space() {
+    let view = Mat4::look_at_rh(Vec3::new(0.0, 3.0, 10.0), Vec3::new(0.0, 3.0, 0.0), Vec3::Y);
+    // World plane y = 1 (normal +Y)
+    let view_plane = plane_to_view_space(&view, Vec4::new(0.0, 1.0, 0.0, -1.0));
+    // A world point on the plane is on the view-space plane
+    let point = view.transform_point3(Vec3::new(4.0, 1.0, -7.0));
+    assert!(view_plane.dot(point.extend(1.0)).abs() < 1e-5);
+    assert!(view_plane.truncate().abs_diff_eq(Vec3::Y, 1e-6));
+}
+
+// ============================================================================
+// Reflection
+// ============================================================================
+
+#[test]
+fn test_reflection_matrix_mirrors_about_plane() {
+    // Plane y = 2, given with a non-unit normal
+    let mirror = reflection_matrix(Vec4::new(0.0, 2.0, 0.0, -4.0));
+    assert!(mirror.transform_point3(Vec3::new(1.0, 5.0, -3.0)).abs_diff_eq(Vec3::new(1.0, -1.0, -3.0), 1e-6));
+    assert!(mirror.transform_point3(Vec3::new(7.0, 2.0, 4.0)).abs_diff_eq(Vec3::new(7.0, 2.0, 4.0), 1e-6));
+    assert!((mirror * mirror).abs_diff_eq(Mat4::IDENTITY, 1e-6));
+    assert!(mirror.determinant() < 0.0);
+
+    // Tilted plane through the origin: points swap sides along the normal
+    let normal = Vec3::new(1.0, 1.0, 0.0).normalize();
+    let tilted = reflection_matrix(normal.extend(0.0));
+    assert!(tilted.transform_point3(normal * 3.0).abs_diff_eq(normal * -3.0, 1e-5));
+    assert_eq!(reflection_matrix(Vec4::new(0.0, 0.0, 0.0, 1.0)), Mat4::IDENTITY);
+}
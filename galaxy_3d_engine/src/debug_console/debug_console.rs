@@ -0,0 +1,478 @@
+//! Debug console model: log view, command registry, input line and watch
+//! variables.
+//!
+//! ```ignore
+//! let log = RingBufferLogger::new(512);
+//! fan_out.add_sink("console", log.clone())?;
+//!
+//! let mut console = DebugConsole::new();
+//! console.attach_log(log);
+//! console.register("reload_shaders", |_args| {
+//!     reload_shaders()?;
+//!     Ok("shaders reloaded".to_string())
+//! })?;
+//! console.watch_fn("fps", move || format!("{:.1}", fps.load()));
+//!
+//! // each frame
+//! console.watch("camera", format!("{:?}", camera.position()));
+//! if console.is_visible() {
+//!     for line in console.lines(30) { text.draw(&line.text, line.color()); }
+//!     for (name, value) in console.watch_values() { ... }
+//! }
+//! ```
+//!
+//! Built-in commands: `help` (lists the commands) and `clear` (clears the
+//! command output). Arguments are separated by whitespace; double quotes
+//! group words into one argument.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::time::SystemTime;
+use glam::Vec4;
+use crate::error::Result;
+use crate::engine_bail;
+use crate::log::{LogEntry, LogSeverity, RingBufferLogger};
+
+/// Command output lines kept
+pub const DEFAULT_CONSOLE_OUTPUT_LINES: usize = 256;
+
+/// Submitted input lines kept for `history_previous()` / `history_next()`
+pub const DEFAULT_CONSOLE_HISTORY: usize = 64;
+
+/// Built-in commands, which cannot be registered
+const BUILTIN_COMMANDS: [&str; 2] = ["help", "clear"];
+
+/// Command handler: receives the arguments (command name excluded) and
+/// returns the text to print
+pub type ConsoleCommand = Box<dyn FnMut(&[&str]) -> Result<String> + Send>;
+
+/// Watch variable evaluated each time the watches are listed
+pub type ConsoleWatch = Box<dyn Fn() -> String + Send>;
+
+/// Kind of a console line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsoleLineKind {
+    /// Log entry of the given severity
+    Log(LogSeverity),
+    /// Echo of a submitted input line
+    Input,
+    /// Output of a command
+    Output,
+    /// Failure of a command
+    Error,
+}
+
+/// One line of the console
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsoleLine {
+    pub kind: ConsoleLineKind,
+    pub text: String,
+    pub timestamp: SystemTime,
+}
+
+impl ConsoleLine {
+    /// Suggested text color (linear RGBA)
+    pub fn color(&self) -> Vec4 {
+        match self.kind {
+            ConsoleLineKind::Log(LogSeverity::Trace) => Vec4::new(0.5, 0.5, 0.5, 1.0),
+            ConsoleLineKind::Log(LogSeverity::Debug) => Vec4::new(0.3, 0.8, 0.8, 1.0),
+            ConsoleLineKind::Log(LogSeverity::Info) => Vec4::new(0.8, 0.8, 0.8, 1.0),
+            ConsoleLineKind::Log(LogSeverity::Warn) => Vec4::new(1.0, 0.8, 0.2, 1.0),
+            ConsoleLineKind::Log(LogSeverity::Error) | ConsoleLineKind::Error => Vec4::new(1.0, 0.3, 0.3, 1.0),
+            ConsoleLineKind::Input => Vec4::new(0.4, 0.7, 1.0, 1.0),
+            ConsoleLineKind::Output => Vec4::ONE,
+        }
+    }
+
+    fn from_log(entry: &LogEntry) -> Self {
+        Self {
+            kind: ConsoleLineKind::Log(entry.severity),
+            text: format!("[{:?}] [{}] {}", entry.severity, entry.source, entry.message),
+            timestamp: entry.timestamp,
+        }
+    }
+}
+
+/// Registered command
+struct RegisteredCommand {
+    help: String,
+    handler: ConsoleCommand,
+}
+
+/// Value of a watch variable
+enum WatchValue {
+    /// Set with `watch()`
+    Text(String),
+    /// Evaluated when listed
+    Callback(ConsoleWatch),
+}
+
+/// Debug console (see module docs).
+pub struct DebugConsole {
+    visible: bool,
+    /// Source of the log lines
+    log: Option<RingBufferLogger>,
+    /// Least severity shown from the log
+    log_level: LogSeverity,
+    commands: BTreeMap<String, RegisteredCommand>,
+    watches: BTreeMap<String, WatchValue>,
+    /// Input echoes and command results, oldest first
+    output: VecDeque<ConsoleLine>,
+    max_output_lines: usize,
+    /// Current input line
+    input: String,
+    /// Submitted lines, oldest first
+    history: VecDeque<String>,
+    /// Position in `history` while browsing it (None: editing a new line)
+    history_cursor: Option<usize>,
+}
+
+impl Default for DebugConsole {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DebugConsole {
+    /// Hidden console without log source, showing Info and above
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            log: None,
+            log_level: LogSeverity::Info,
+            commands: BTreeMap::new(),
+            watches: BTreeMap::new(),
+            output: VecDeque::new(),
+            max_output_lines: DEFAULT_CONSOLE_OUTPUT_LINES,
+            input: String::new(),
+            history: VecDeque::new(),
+            history_cursor: None,
+        }
+    }
+
+    // ===== VISIBILITY =====
+
+    /// Show a hidden console, hide a visible one (bind it to a key)
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    // ===== LOG =====
+
+    /// Show the entries of `log`, a sink of the engine logger (typically a
+    /// clone added to a `LogFanOut`)
+    pub fn attach_log(&mut self, log: RingBufferLogger) {
+        self.log = Some(log);
+    }
+
+    pub fn detach_log(&mut self) {
+        self.log = None;
+    }
+
+    pub fn log_level(&self) -> LogSeverity {
+        self.log_level
+    }
+
+    /// Least severity of the log entries shown
+    pub fn set_log_level(&mut self, level: LogSeverity) {
+        self.log_level = level;
+    }
+
+    /// The last `count` lines, oldest first: log entries of at least
+    /// `log_level()` merged by time with the command input and output
+    pub fn lines(&self, count: usize) -> Vec<ConsoleLine> {
+        let mut lines: Vec<ConsoleLine> = self.log.as_ref()
+            .map(|log| log.entries().iter()
+                .filter(|entry| entry.severity >= self.log_level)
+                .map(ConsoleLine::from_log)
+                .collect())
+            .unwrap_or_default();
+        lines.extend(self.output.iter().cloned());
+        // Stable: output lines logged at the same instant stay after the log
+        lines.sort_by_key(|line| line.timestamp);
+        let skip = lines.len().saturating_sub(count);
+        lines.split_off(skip)
+    }
+
+    /// Clear the command output (log entries stay in their ring buffer)
+    pub fn clear_output(&mut self) {
+        self.output.clear();
+    }
+
+    fn push_output(&mut self, kind: ConsoleLineKind, text: &str) {
+        for line in text.lines() {
+            if self.output.len() == self.max_output_lines {
+                self.output.pop_front();
+            }
+            self.output.push_back(ConsoleLine { kind, text: line.to_string(), timestamp: SystemTime::now() });
+        }
+    }
+
+    // ===== COMMANDS =====
+
+    /// Register a command without help text
+    ///
+    /// # Errors
+    ///
+    /// See `register_with_help`.
+    pub fn register<F>(&mut self, name: &str, handler: F) -> Result<()>
+    where
+        F: FnMut(&[&str]) -> Result<String> + Send + 'static,
+    {
+        self.register_with_help(name, "", handler)
+    }
+
+    /// Register a command, listed by `help` with `help`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `name` is empty, contains whitespace, is a
+    /// built-in command or is already registered.
+    pub fn register_with_help<F>(&mut self, name: &str, help: &str, handler: F) -> Result<()>
+    where
+        F: FnMut(&[&str]) -> Result<String> + Send + 'static,
+    {
+        if name.is_empty() || name.contains(char::is_whitespace) || name.contains('"') {
+            engine_bail!("galaxy3d::DebugConsole", "Invalid console command name '{}'", name);
+        }
+        if BUILTIN_COMMANDS.contains(&name) || self.commands.contains_key(name) {
+            engine_bail!("galaxy3d::DebugConsole", "Console command '{}' already exists", name);
+        }
+        self.commands.insert(name.to_string(), RegisteredCommand {
+            help: help.to_string(),
+            handler: Box::new(handler),
+        });
+        Ok(())
+    }
+
+    /// Remove a command. Returns false if it was not registered.
+    pub fn unregister(&mut self, name: &str) -> bool {
+        self.commands.remove(name).is_some()
+    }
+
+    /// Registered and built-in command names, sorted
+    pub fn command_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.commands.keys().map(String::as_str)
+            .chain(BUILTIN_COMMANDS)
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Run a command line, echoing it and printing its result to the
+    /// console. An empty line does nothing.
+    ///
+    /// # Returns
+    ///
+    /// The text printed by the command
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the command is unknown or fails (the error is
+    /// printed too).
+    pub fn execute(&mut self, line: &str) -> Result<String> {
+        let tokens = tokenize(line);
+        let Some((name, args)) = tokens.split_first() else {
+            return Ok(String::new());
+        };
+        self.push_output(ConsoleLineKind::Input, &format!("> {}", line.trim()));
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+        let result = match name.as_str() {
+            "help" => Ok(self.help_text()),
+            "clear" => {
+                self.clear_output();
+                return Ok(String::new());
+            }
+            _ => match self.commands.get_mut(name.as_str()) {
+                Some(command) => (command.handler)(&args),
+                None => Err(crate::engine_warn_err!("galaxy3d::DebugConsole",
+                    "Unknown console command '{}' (type 'help')", name)),
+            },
+        };
+        match &result {
+            Ok(text) => self.push_output(ConsoleLineKind::Output, text),
+            Err(e) => self.push_output(ConsoleLineKind::Error, &e.to_string()),
+        }
+        result
+    }
+
+    fn help_text(&self) -> String {
+        let mut text = String::from("help - list the commands\nclear - clear the console output");
+        for (name, command) in &self.commands {
+            text.push('\n');
+            text.push_str(name);
+            if !command.help.is_empty() {
+                text.push_str(" - ");
+                text.push_str(&command.help);
+            }
+        }
+        text
+    }
+
+    // ===== INPUT LINE =====
+
+    /// Current input line
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Replace the input line
+    pub fn set_input(&mut self, input: &str) {
+        self.input = input.to_string();
+        self.history_cursor = None;
+    }
+
+    /// Append typed text (control characters are ignored)
+    pub fn type_text(&mut self, text: &str) {
+        self.input.extend(text.chars().filter(|c| !c.is_control()));
+        self.history_cursor = None;
+    }
+
+    /// Delete the last character of the input line
+    pub fn backspace(&mut self) {
+        self.input.pop();
+    }
+
+    /// Execute the input line, add it to the history and clear it. Returns
+    /// None when the line is blank.
+    pub fn submit(&mut self) -> Option<Result<String>> {
+        let line = std::mem::take(&mut self.input);
+        self.history_cursor = None;
+        if line.trim().is_empty() {
+            return None;
+        }
+        if self.history.back() != Some(&line) {
+            if self.history.len() == DEFAULT_CONSOLE_HISTORY {
+                self.history.pop_front();
+            }
+            self.history.push_back(line.clone());
+        }
+        Some(self.execute(&line))
+    }
+
+    /// Complete the input line to the longest prefix shared by the command
+    /// names it starts. Returns the matching names.
+    pub fn complete(&mut self) -> Vec<String> {
+        if self.input.contains(char::is_whitespace) {
+            return Vec::new();
+        }
+        let matches: Vec<String> = self.command_names().into_iter()
+            .filter(|name| name.starts_with(self.input.as_str()))
+            .map(str::to_string)
+            .collect();
+        if let Some(first) = matches.first() {
+            let common = matches.iter()
+                .map(|name| first.char_indices().zip(name.chars())
+                    .find(|((_, a), b)| a != b)
+                    .map_or(first.len().min(name.len()), |((index, _), _)| index))
+                .min()
+                .unwrap_or(0);
+            self.input = first[..common].to_string();
+            if matches.len() == 1 {
+                self.input.push(' ');
+            }
+        }
+        matches
+    }
+
+    /// Replace the input line with the previous history line
+    pub fn history_previous(&mut self) {
+        if self.history.is_empty() {
+            return;
+        }
+        let index = match self.history_cursor {
+            Some(index) => index.saturating_sub(1),
+            None => self.history.len() - 1,
+        };
+        self.history_cursor = Some(index);
+        self.input = self.history[index].clone();
+    }
+
+    /// Replace the input line with the next history line, or clear it past
+    /// the most recent one
+    pub fn history_next(&mut self) {
+        match self.history_cursor {
+            Some(index) if index + 1 < self.history.len() => {
+                self.history_cursor = Some(index + 1);
+                self.input = self.history[index + 1].clone();
+            }
+            Some(_) => {
+                self.history_cursor = None;
+                self.input.clear();
+            }
+            None => {}
+        }
+    }
+
+    /// Submitted lines, oldest first
+    pub fn history(&self) -> impl Iterator<Item = &str> {
+        self.history.iter().map(String::as_str)
+    }
+
+    // ===== WATCHES =====
+
+    /// Set the value shown for the watch variable `name` (call it whenever
+    /// the value changes, e.g. every frame)
+    pub fn watch(&mut self, name: &str, value: impl std::fmt::Display) {
+        self.watches.insert(name.to_string(), WatchValue::Text(value.to_string()));
+    }
+
+    /// Show the watch variable `name`, evaluated by `value` each time the
+    /// watches are listed
+    pub fn watch_fn<F>(&mut self, name: &str, value: F)
+    where
+        F: Fn() -> String + Send + 'static,
+    {
+        self.watches.insert(name.to_string(), WatchValue::Callback(Box::new(value)));
+    }
+
+    /// Stop showing a watch variable. Returns false if there was none.
+    pub fn unwatch(&mut self, name: &str) -> bool {
+        self.watches.remove(name).is_some()
+    }
+
+    /// Watch variables and their current values, sorted by name
+    pub fn watch_values(&self) -> Vec<(&str, String)> {
+        self.watches.iter()
+            .map(|(name, value)| (name.as_str(), match value {
+                WatchValue::Text(text) => text.clone(),
+                WatchValue::Callback(callback) => callback(),
+            }))
+            .collect()
+    }
+}
+
+/// Split a command line on whitespace, double quotes grouping words
+fn tokenize(line: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current: Option<String> = None;
+    let mut quoted = false;
+    for c in line.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                current.get_or_insert_with(String::new);
+            }
+            c if c.is_whitespace() && !quoted => {
+                if let Some(token) = current.take() {
+                    tokens.push(token);
+                }
+            }
+            c => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    tokens.extend(current);
+    tokens
+}
+
+#[cfg(test)]
+#[path = "debug_console_tests.rs"]
+mod tests;
@@ -0,0 +1,216 @@
+use super::*;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use crate::log::Logger;
+
+fn entry(severity: LogSeverity, message: &str, timestamp: SystemTime) -> LogEntry {
+    LogEntry {
+        severity,
+        timestamp,
+        source: "test".to_string(),
+        message: message.to_string(),
+        file: None,
+        line: None,
+    }
+}
+
+fn texts(lines: &[ConsoleLine]) -> Vec<&str> {
+    lines.iter().map(|line| line.text.as_str()).collect()
+}
+
+// ============================================================================
+// Visibility
+// ============================================================================
+
+#[test]
+fn test_toggle_visibility() {
+    let mut console = DebugConsole::new();
+    assert!(!console.is_visible());
+    console.toggle();
+    assert!(console.is_visible());
+    console.toggle();
+    assert!(!console.is_visible());
+    console.set_visible(true);
+    assert!(console.is_visible());
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+#[test]
+fn test_register_and_execute_command() {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let recorded = calls.clone();
+    let mut console = DebugConsole::new();
+    console.register("reload_shaders", move |args| {
+        recorded.lock().unwrap().push(args.join("|"));
+        Ok(format!("{} args", args.len()))
+    }).unwrap();
+
+    assert_eq!(console.execute("reload_shaders a \"b c\"").unwrap(), "2 args");
+    assert_eq!(*calls.lock().unwrap(), vec!["a|b c".to_string()]);
+    let lines = console.lines(10);
+    assert_eq!(texts(&lines), vec!["> reload_shaders a \"b c\"", "2 args"]);
+    assert_eq!(lines[0].kind, ConsoleLineKind::Input);
+    assert_eq!(lines[1].kind, ConsoleLineKind::Output);
+}
+
+#[test]
+fn test_register_rejects_invalid_and_duplicate_names() {
+    let mut console = DebugConsole::new();
+    console.register("stats", |_| Ok(String::new())).unwrap();
+    assert!(console.register("stats", |_| Ok(String::new())).is_err());
+    assert!(console.register("help", |_| Ok(String::new())).is_err());
+    assert!(console.register("two words", |_| Ok(String::new())).is_err());
+    assert!(console.register("", |_| Ok(String::new())).is_err());
+    assert_eq!(console.command_names(), vec!["clear", "help", "stats"]);
+
+    assert!(console.unregister("stats"));
+    assert!(!console.unregister("stats"));
+}
+
+#[test]
+fn test_unknown_and_failing_commands_print_errors() {
+    let mut console = DebugConsole::new();
+    console.register("fail", |_| Err(crate::galaxy3d::Error::BackendError("boom".to_string()))).unwrap();
+    assert!(console.execute("nope").is_err());
+    assert!(console.execute("fail").is_err());
+    let lines = console.lines(10);
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[1].kind, ConsoleLineKind::Error);
+    assert!(lines[3].text.contains("boom"));
+}
+
+#[test]
+fn test_builtin_help_and_clear() {
+    let mut console = DebugConsole::new();
+    console.register_with_help("stats", "print the frame statistics", |_| Ok(String::new())).unwrap();
+    let help = console.execute("help").unwrap();
+    assert!(help.contains("stats - print the frame statistics"));
+    // Multi-line output is split into lines
+    assert_eq!(console.lines(usize::MAX).len(), 1 + help.lines().count());
+
+    console.execute("clear").unwrap();
+    assert!(console.lines(10).is_empty());
+    // Blank lines do nothing
+    assert_eq!(console.execute("   ").unwrap(), "");
+    assert!(console.lines(10).is_empty());
+}
+
+#[test]
+fn test_output_is_capped() {
+    let mut console = DebugConsole::new();
+    console.register("echo", |args| Ok(args.join(" "))).unwrap();
+    for i in 0..DEFAULT_CONSOLE_OUTPUT_LINES {
+        console.execute(&format!("echo {}", i)).unwrap();
+    }
+    let lines = console.lines(usize::MAX);
+    assert_eq!(lines.len(), DEFAULT_CONSOLE_OUTPUT_LINES);
+    assert_eq!(lines.last().unwrap().text, format!("{}", DEFAULT_CONSOLE_OUTPUT_LINES - 1));
+}
+
+// ============================================================================
+// Log view
+// ============================================================================
+
+#[test]
+fn test_lines_merge_log_and_output_by_time() {
+    let log = RingBufferLogger::new(16);
+    let past = SystemTime::now() - Duration::from_secs(10);
+    log.log(&entry(LogSeverity::Debug, "hidden", past));
+    log.log(&entry(LogSeverity::Warn, "before", past));
+
+    let mut console = DebugConsole::new();
+    console.attach_log(log.clone());
+    console.register("ping", |_| Ok("pong".to_string())).unwrap();
+    console.execute("ping").unwrap();
+    log.log(&entry(LogSeverity::Error, "after", SystemTime::now() + Duration::from_secs(10)));
+
+    let lines = console.lines(10);
+    assert_eq!(texts(&lines), vec!["[Warn] [test] before", "> ping", "pong", "[Error] [test] after"]);
+    assert_eq!(lines[0].kind, ConsoleLineKind::Log(LogSeverity::Warn));
+    assert_eq!(lines[0].color(), Vec4::new(1.0, 0.8, 0.2, 1.0));
+    assert_eq!(texts(&console.lines(2)), vec!["pong", "[Error] [test] after"]);
+
+    console.set_log_level(LogSeverity::Trace);
+    assert_eq!(console.lines(10).len(), 5);
+    console.detach_log();
+    assert_eq!(console.lines(10).len(), 2);
+}
+
+// ============================================================================
+// Input line
+// ============================================================================
+
+#[test]
+fn test_submit_runs_input_and_records_history() {
+    let mut console = DebugConsole::new();
+    console.register("echo", |args| Ok(args.join(" "))).unwrap();
+    console.type_text("echo hi\n");
+    console.type_text("x");
+    console.backspace();
+    assert_eq!(console.input(), "echo hi");
+    assert_eq!(console.submit().unwrap().unwrap(), "hi");
+    assert_eq!(console.input(), "");
+    assert!(console.submit().is_none());
+
+    console.set_input("echo there");
+    console.submit();
+    console.set_input("echo there");
+    console.submit();
+    assert_eq!(console.history().collect::<Vec<_>>(), vec!["echo hi", "echo there"]);
+}
+
+#[test]
+fn test_history_navigation() {
+    let mut console = DebugConsole::new();
+    for line in ["a", "b"] {
+        console.set_input(line);
+        let _ = console.submit();
+    }
+    console.history_previous();
+    assert_eq!(console.input(), "b");
+    console.history_previous();
+    console.history_previous();
+    assert_eq!(console.input(), "a");
+    console.history_next();
+    assert_eq!(console.input(), "b");
+    console.history_next();
+    assert_eq!(console.input(), "");
+}
+
+#[test]
+fn test_complete_command_names() {
+    let mut console = DebugConsole::new();
+    console.register("reload_shaders", |_| Ok(String::new())).unwrap();
+    console.register("reload_textures", |_| Ok(String::new())).unwrap();
+    console.set_input("rel");
+    assert_eq!(console.complete().len(), 2);
+    assert_eq!(console.input(), "reload_");
+    console.type_text("s");
+    assert_eq!(console.complete(), vec!["reload_shaders".to_string()]);
+    assert_eq!(console.input(), "reload_shaders ");
+}
+
+// ============================================================================
+// Watches
+// ============================================================================
+
+#[test]
+fn test_watch_values() {
+    let counter = Arc::new(Mutex::new(1));
+    let watched = counter.clone();
+    let mut console = DebugConsole::new();
+    console.watch("name", "level1");
+    console.watch_fn("counter", move || watched.lock().unwrap().to_string());
+    assert_eq!(console.watch_values(), vec![("counter", "1".to_string()), ("name", "level1".to_string())]);
+
+    *counter.lock().unwrap() = 2;
+    console.watch("name", 42);
+    assert_eq!(console.watch_values(), vec![("counter", "2".to_string()), ("name", "42".to_string())]);
+
+    assert!(console.unwatch("counter"));
+    assert!(!console.unwatch("counter"));
+    assert_eq!(console.watch_values().len(), 1);
+}
@@ -0,0 +1,16 @@
+//! In-engine debug console.
+//!
+//! `DebugConsole` is the model of a toggleable console overlay: it merges
+//! the recent log entries (read from a `RingBufferLogger` sink) with the
+//! echo and output of the commands typed in it, runs commands registered
+//! by the application (`console.register("reload_shaders", ...)`), and
+//! lists watch variables. It produces colored text lines
+//! (`ConsoleLine`); drawing them is left to the application's text or UI
+//! renderer, the engine having none.
+
+mod debug_console;
+
+pub use debug_console::{
+    DebugConsole, ConsoleLine, ConsoleLineKind, ConsoleCommand, ConsoleWatch,
+    DEFAULT_CONSOLE_OUTPUT_LINES, DEFAULT_CONSOLE_HISTORY,
+};
@@ -0,0 +1,217 @@
+//! Debug line batch.
+//!
+//! Every shape is expanded into line segments when it is submitted and
+//! stored as two vertices (position + color) per segment, ready to be
+//! uploaded as-is. Segments live for `duration` seconds of `advance()`
+//! calls, and are always drawn at least once: a duration of 0 draws the
+//! shape for the current frame only.
+
+use glam::{Mat4, Vec3, Vec4};
+use crate::camera::Camera;
+use crate::graphics_device::{BufferFormat, VertexAttribute, VertexBinding, VertexInputRate, VertexLayout};
+use crate::scene::AABB;
+
+/// Floats per vertex: position (3) + color (4)
+const DEBUG_VERTEX_FLOATS: usize = 7;
+
+/// Size in bytes of one debug vertex
+pub const DEBUG_VERTEX_STRIDE: u32 = (DEBUG_VERTEX_FLOATS * std::mem::size_of::<f32>()) as u32;
+
+/// Segments per circle of `DebugDraw::sphere`
+pub const SPHERE_SEGMENTS: usize = 24;
+
+/// Vertex layout of the debug vertex buffer.
+///
+/// location 0: position (vec3), location 1: color (vec4, linear RGBA).
+pub fn debug_vertex_layout() -> VertexLayout {
+    VertexLayout {
+        bindings: vec![VertexBinding { binding: 0, stride: DEBUG_VERTEX_STRIDE, input_rate: VertexInputRate::Vertex }],
+        attributes: vec![
+            VertexAttribute { location: 0, binding: 0, format: BufferFormat::R32G32B32_SFLOAT, offset: 0 },
+            VertexAttribute { location: 1, binding: 0, format: BufferFormat::R32G32B32A32_SFLOAT, offset: 12 },
+        ],
+    }
+}
+
+/// Batch of debug line segments.
+///
+/// Shared between the game code (submission) and `DebugDrawAction`
+/// (rendering) through an `Arc<Mutex<DebugDraw>>`. Call `advance(dt)` once
+/// per frame, after the render graph has executed.
+pub struct DebugDraw {
+    /// Two vertices per segment
+    vertices: Vec<[f32; DEBUG_VERTEX_FLOATS]>,
+    /// Remaining lifetime in seconds, one per segment
+    lifetimes: Vec<f32>,
+    /// Camera transform pushed to the debug shader
+    view_projection: Mat4,
+    enabled: bool,
+}
+
+impl DebugDraw {
+    pub fn new() -> Self {
+        Self {
+            vertices: Vec::new(),
+            lifetimes: Vec::new(),
+            view_projection: Mat4::IDENTITY,
+            enabled: true,
+        }
+    }
+
+    /// Enable or disable submission. While disabled, shape calls are
+    /// ignored; segments already submitted keep expiring normally.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Whether submission is enabled
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Set the view-projection matrix the segments are drawn with (usually
+    /// the one of the camera rendering the debug pass), once per frame
+    pub fn set_view_projection(&mut self, view_projection: Mat4) {
+        self.view_projection = view_projection;
+    }
+
+    /// View-projection matrix the segments are drawn with
+    pub fn view_projection(&self) -> &Mat4 {
+        &self.view_projection
+    }
+
+    // ===== SHAPES =====
+
+    /// Line segment from `a` to `b`
+    pub fn line(&mut self, a: Vec3, b: Vec3, color: Vec4, duration: f32) {
+        if !self.enabled {
+            return;
+        }
+        self.vertices.push([a.x, a.y, a.z, color.x, color.y, color.z, color.w]);
+        self.vertices.push([b.x, b.y, b.z, color.x, color.y, color.z, color.w]);
+        self.lifetimes.push(duration);
+    }
+
+    /// The 12 edges of an axis-aligned box
+    pub fn aabb(&mut self, aabb: &AABB, color: Vec4, duration: f32) {
+        let corners = [
+            Vec3::new(aabb.min.x, aabb.min.y, aabb.min.z),
+            Vec3::new(aabb.max.x, aabb.min.y, aabb.min.z),
+            Vec3::new(aabb.max.x, aabb.max.y, aabb.min.z),
+            Vec3::new(aabb.min.x, aabb.max.y, aabb.min.z),
+            Vec3::new(aabb.min.x, aabb.min.y, aabb.max.z),
+            Vec3::new(aabb.max.x, aabb.min.y, aabb.max.z),
+            Vec3::new(aabb.max.x, aabb.max.y, aabb.max.z),
+            Vec3::new(aabb.min.x, aabb.max.y, aabb.max.z),
+        ];
+        self.box_edges(&corners, color, duration);
+    }
+
+    /// Wire sphere: three great circles around the X, Y and Z axes
+    pub fn sphere(&mut self, center: Vec3, radius: f32, color: Vec4, duration: f32) {
+        let step = std::f32::consts::TAU / SPHERE_SEGMENTS as f32;
+        for i in 0..SPHERE_SEGMENTS {
+            let (s0, c0) = (i as f32 * step).sin_cos();
+            let (s1, c1) = ((i + 1) as f32 * step).sin_cos();
+            let (s0, c0, s1, c1) = (s0 * radius, c0 * radius, s1 * radius, c1 * radius);
+            self.line(center + Vec3::new(0.0, c0, s0), center + Vec3::new(0.0, c1, s1), color, duration);
+            self.line(center + Vec3::new(c0, 0.0, s0), center + Vec3::new(c1, 0.0, s1), color, duration);
+            self.line(center + Vec3::new(c0, s0, 0.0), center + Vec3::new(c1, s1, 0.0), color, duration);
+        }
+    }
+
+    /// The 12 edges of a camera frustum.
+    ///
+    /// Corners are unprojected from the clip-space volume of the camera
+    /// (x, y in [-1, 1], depth in [0, 1]).
+    pub fn frustum(&mut self, camera: &Camera, color: Vec4, duration: f32) {
+        let inverse = (*camera.projection_matrix() * *camera.view_matrix()).inverse();
+        let corner = |x: f32, y: f32, z: f32| inverse.project_point3(Vec3::new(x, y, z));
+        let corners = [
+            corner(-1.0, -1.0, 0.0),
+            corner(1.0, -1.0, 0.0),
+            corner(1.0, 1.0, 0.0),
+            corner(-1.0, 1.0, 0.0),
+            corner(-1.0, -1.0, 1.0),
+            corner(1.0, -1.0, 1.0),
+            corner(1.0, 1.0, 1.0),
+            corner(-1.0, 1.0, 1.0),
+        ];
+        self.box_edges(&corners, color, duration);
+    }
+
+    /// Local axes of a transform, `size` units long: X in red, Y in green,
+    /// Z in blue
+    pub fn axis(&mut self, transform: &Mat4, size: f32, duration: f32) {
+        let origin = transform.transform_point3(Vec3::ZERO);
+        let axes = [(Vec3::X, Vec4::new(1.0, 0.0, 0.0, 1.0)),
+                    (Vec3::Y, Vec4::new(0.0, 1.0, 0.0, 1.0)),
+                    (Vec3::Z, Vec4::new(0.0, 0.0, 1.0, 1.0))];
+        for (axis, color) in axes {
+            self.line(origin, transform.transform_point3(axis * size), color, duration);
+        }
+    }
+
+    /// Edges of a hexahedron given as near face (4 corners) then far face
+    /// (4 corners), each in winding order
+    fn box_edges(&mut self, corners: &[Vec3; 8], color: Vec4, duration: f32) {
+        for i in 0..4 {
+            let j = (i + 1) % 4;
+            self.line(corners[i], corners[j], color, duration);
+            self.line(corners[i + 4], corners[j + 4], color, duration);
+            self.line(corners[i], corners[i + 4], color, duration);
+        }
+    }
+
+    // ===== FRAME =====
+
+    /// Age every segment by `dt` seconds and drop the expired ones.
+    ///
+    /// Call once per frame, after rendering: a segment submitted with a
+    /// duration of 0 is drawn by the frame it was submitted in, then removed.
+    pub fn advance(&mut self, dt: f32) {
+        let mut write = 0;
+        for read in 0..self.lifetimes.len() {
+            let remaining = self.lifetimes[read] - dt;
+            if remaining > 0.0 {
+                self.lifetimes[write] = remaining;
+                self.vertices[write * 2] = self.vertices[read * 2];
+                self.vertices[write * 2 + 1] = self.vertices[read * 2 + 1];
+                write += 1;
+            }
+        }
+        self.lifetimes.truncate(write);
+        self.vertices.truncate(write * 2);
+    }
+
+    /// Remove every segment
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+        self.lifetimes.clear();
+    }
+
+    /// Number of line segments
+    pub fn line_count(&self) -> usize {
+        self.lifetimes.len()
+    }
+
+    /// Number of vertices (two per segment)
+    pub fn vertex_count(&self) -> u32 {
+        self.vertices.len() as u32
+    }
+
+    /// Vertex data laid out as `debug_vertex_layout()`
+    pub fn vertex_data(&self) -> &[u8] {
+        bytemuck::cast_slice(&self.vertices)
+    }
+}
+
+impl Default for DebugDraw {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+#[path = "debug_draw_tests.rs"]
+mod tests;
@@ -0,0 +1,99 @@
+//! Pass action rendering a `DebugDraw` batch.
+//!
+//! Shader interface of the debug pipeline:
+//! - vertex input: `debug_vertex_layout()` (position vec3, color vec4)
+//! - push constants: vertex stage, offset 0, the view-projection matrix
+//!   (`mat4`, 64 bytes)
+//! - topology: `PrimitiveTopology::LineList`
+//!
+//! The vertices are uploaded every frame into a host-visible vertex buffer.
+//! One buffer is kept per frame in flight so the upload never overwrites
+//! data a previous frame's command list is still reading; buffers grow
+//! (to the next power of two) when the batch outgrows them.
+
+use std::sync::{Arc, Mutex};
+use crate::error::Result;
+use crate::engine::Engine;
+use crate::graphics_device::{self, BufferDesc, BufferUsage, CommandList, ShaderStageFlags};
+use crate::render_graph::PassAction;
+use crate::resource::resource_manager::PassInfo;
+use super::debug_draw::{DebugDraw, DEBUG_VERTEX_STRIDE};
+
+/// Initial capacity of each vertex buffer, in vertices
+const INITIAL_VERTEX_CAPACITY: u32 = 1024;
+
+/// Pass action drawing the segments of a `DebugDraw`.
+pub struct DebugDrawAction {
+    debug_draw: Arc<Mutex<DebugDraw>>,
+    pipeline: Arc<dyn graphics_device::Pipeline>,
+    /// One vertex buffer per frame in flight, with its capacity in vertices
+    vertex_buffers: Vec<Option<(Arc<dyn graphics_device::Buffer>, u32)>>,
+    /// Index of the buffer used by the next frame
+    frame: usize,
+}
+
+impl DebugDrawAction {
+    /// Create the action for a render graph with `frames_in_flight` command
+    /// lists. `pipeline` must follow the debug shader interface (see module
+    /// docs). Vertex buffers are created on first use.
+    pub fn new(
+        debug_draw: Arc<Mutex<DebugDraw>>,
+        pipeline: Arc<dyn graphics_device::Pipeline>,
+        frames_in_flight: usize,
+    ) -> Result<Self> {
+        if frames_in_flight == 0 {
+            crate::engine_bail!("galaxy3d::DebugDrawAction", "frames_in_flight must be at least 1");
+        }
+        Ok(Self {
+            debug_draw,
+            pipeline,
+            vertex_buffers: (0..frames_in_flight).map(|_| None).collect(),
+            frame: 0,
+        })
+    }
+
+    /// Return the vertex buffer of the current frame, (re)created when
+    /// smaller than `vertex_count`
+    fn frame_buffer(&mut self, vertex_count: u32) -> Result<Arc<dyn graphics_device::Buffer>> {
+        let slot = &mut self.vertex_buffers[self.frame];
+        if let Some((buffer, capacity)) = slot {
+            if *capacity >= vertex_count {
+                return Ok(buffer.clone());
+            }
+        }
+        let capacity = vertex_count.next_power_of_two().max(INITIAL_VERTEX_CAPACITY);
+        let gd_arc = Engine::graphics_device("main")?;
+        let buffer = gd_arc.lock().unwrap().create_buffer(BufferDesc {
+            size: capacity as u64 * DEBUG_VERTEX_STRIDE as u64,
+            usage: BufferUsage::Vertex,
+            debug_name: Some("DebugDraw vertices".to_string()),
+        })?;
+        *slot = Some((buffer.clone(), capacity));
+        Ok(buffer)
+    }
+}
+
+impl PassAction for DebugDrawAction {
+    fn execute(&mut self, cmd: &mut dyn CommandList, _pass_info: &PassInfo) -> Result<()> {
+        let debug_draw = self.debug_draw.clone();
+        let debug_draw = debug_draw.lock().unwrap();
+        let vertex_count = debug_draw.vertex_count();
+        if vertex_count == 0 {
+            return Ok(());
+        }
+
+        let buffer = self.frame_buffer(vertex_count)?;
+        self.frame = (self.frame + 1) % self.vertex_buffers.len();
+        buffer.update(0, debug_draw.vertex_data())?;
+
+        cmd.bind_pipeline(&self.pipeline)?;
+        cmd.push_constants(ShaderStageFlags::VERTEX, 0,
+            bytemuck::bytes_of(debug_draw.view_projection()))?;
+        cmd.bind_vertex_buffer(&buffer, 0)?;
+        cmd.draw(vertex_count, 0)
+    }
+}
+
+#[cfg(test)]
+#[path = "debug_draw_action_tests.rs"]
+mod tests;
@@ -0,0 +1,78 @@
+use super::*;
+use glam::Vec3;
+use glam::Vec4;
+use serial_test::serial;
+use crate::graphics_device::{SampleCount, TextureFormat};
+use crate::graphics_device::mock_graphics_device::{MockCommandList, MockPipeline};
+use crate::render_graph::test_helpers::setup_engine;
+
+fn make_pass_info() -> PassInfo {
+    PassInfo::new(vec![TextureFormat::R8G8B8A8_UNORM], None, SampleCount::S1)
+}
+
+fn make_action(debug_draw: &Arc<Mutex<DebugDraw>>) -> DebugDrawAction {
+    let pipeline: Arc<dyn graphics_device::Pipeline> =
+        Arc::new(MockPipeline::new("debug_pipeline".to_string()));
+    DebugDrawAction::new(debug_draw.clone(), pipeline, 2).unwrap()
+}
+
+#[test]
+fn test_new_rejects_zero_frames_in_flight() {
+    let debug_draw = Arc::new(Mutex::new(DebugDraw::new()));
+    let pipeline: Arc<dyn graphics_device::Pipeline> =
+        Arc::new(MockPipeline::new("debug_pipeline".to_string()));
+    assert!(DebugDrawAction::new(debug_draw, pipeline, 0).is_err());
+}
+
+#[test]
+#[serial]
+fn test_execute_empty_batch_records_nothing() {
+    setup_engine();
+    let debug_draw = Arc::new(Mutex::new(DebugDraw::new()));
+    let mut action = make_action(&debug_draw);
+    let mut cmd = MockCommandList::new();
+    action.execute(&mut cmd, &make_pass_info()).unwrap();
+    assert!(cmd.commands.is_empty());
+}
+
+#[test]
+#[serial]
+fn test_execute_draws_batch() {
+    setup_engine();
+    let debug_draw = Arc::new(Mutex::new(DebugDraw::new()));
+    debug_draw.lock().unwrap().line(Vec3::ZERO, Vec3::ONE, Vec4::ONE, 0.0);
+    let mut action = make_action(&debug_draw);
+    let mut cmd = MockCommandList::new();
+    action.execute(&mut cmd, &make_pass_info()).unwrap();
+    assert_eq!(cmd.commands, vec!["bind_pipeline", "push_constants", "bind_vertex_buffer", "draw"]);
+}
+
+#[test]
+#[serial]
+fn test_vertex_buffers_rotate_and_grow() {
+    setup_engine();
+    let debug_draw = Arc::new(Mutex::new(DebugDraw::new()));
+    debug_draw.lock().unwrap().line(Vec3::ZERO, Vec3::ONE, Vec4::ONE, 10.0);
+    let mut action = make_action(&debug_draw);
+    let mut cmd = MockCommandList::new();
+
+    action.execute(&mut cmd, &make_pass_info()).unwrap();
+    action.execute(&mut cmd, &make_pass_info()).unwrap();
+    let first = action.vertex_buffers[0].as_ref().unwrap().0.clone();
+    assert!(action.vertex_buffers[1].is_some());
+    assert!(!Arc::ptr_eq(&first, &action.vertex_buffers[1].as_ref().unwrap().0));
+
+    // Same size: buffer reused
+    action.execute(&mut cmd, &make_pass_info()).unwrap();
+    assert!(Arc::ptr_eq(&first, &action.vertex_buffers[0].as_ref().unwrap().0));
+
+    // Outgrown: buffer recreated at the next power of two
+    {
+        let mut draw = debug_draw.lock().unwrap();
+        for _ in 0..INITIAL_VERTEX_CAPACITY {
+            draw.line(Vec3::ZERO, Vec3::ONE, Vec4::ONE, 10.0);
+        }
+    }
+    action.execute(&mut cmd, &make_pass_info()).unwrap();
+    assert_eq!(action.vertex_buffers[1].as_ref().unwrap().1, 4096);
+}
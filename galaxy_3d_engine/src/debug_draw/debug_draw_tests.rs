@@ -0,0 +1,113 @@
+use super::*;
+use crate::graphics_device::Viewport;
+use crate::camera::Frustum;
+
+const RED: Vec4 = Vec4::new(1.0, 0.0, 0.0, 1.0);
+
+fn vertex(draw: &DebugDraw, index: usize) -> [f32; DEBUG_VERTEX_FLOATS] {
+    let floats: &[f32] = bytemuck::cast_slice(draw.vertex_data());
+    floats[index * DEBUG_VERTEX_FLOATS..(index + 1) * DEBUG_VERTEX_FLOATS].try_into().unwrap()
+}
+
+// ============================================================================
+// Shapes
+// ============================================================================
+
+#[test]
+fn test_line_stores_two_vertices() {
+    let mut draw = DebugDraw::new();
+    draw.line(Vec3::ZERO, Vec3::new(1.0, 2.0, 3.0), RED, 0.0);
+    assert_eq!(draw.line_count(), 1);
+    assert_eq!(draw.vertex_count(), 2);
+    assert_eq!(draw.vertex_data().len(), 2 * DEBUG_VERTEX_STRIDE as usize);
+    assert_eq!(vertex(&draw, 1), [1.0, 2.0, 3.0, 1.0, 0.0, 0.0, 1.0]);
+}
+
+#[test]
+fn test_shape_segment_counts() {
+    let mut draw = DebugDraw::new();
+    draw.aabb(&AABB { min: Vec3::splat(-1.0), max: Vec3::ONE }, RED, 0.0);
+    assert_eq!(draw.line_count(), 12);
+
+    draw.clear();
+    draw.sphere(Vec3::ZERO, 2.0, RED, 0.0);
+    assert_eq!(draw.line_count(), 3 * SPHERE_SEGMENTS);
+
+    draw.clear();
+    draw.axis(&Mat4::IDENTITY, 1.0, 0.0);
+    assert_eq!(draw.line_count(), 3);
+}
+
+#[test]
+fn test_sphere_vertices_lie_on_sphere() {
+    let mut draw = DebugDraw::new();
+    let center = Vec3::new(5.0, -1.0, 2.0);
+    draw.sphere(center, 2.0, RED, 0.0);
+    for i in 0..draw.vertex_count() as usize {
+        let v = vertex(&draw, i);
+        let distance = (Vec3::new(v[0], v[1], v[2]) - center).length();
+        assert!((distance - 2.0).abs() < 1e-5, "vertex {} at distance {}", i, distance);
+    }
+}
+
+#[test]
+fn test_axis_follows_transform() {
+    let mut draw = DebugDraw::new();
+    draw.axis(&Mat4::from_translation(Vec3::new(1.0, 0.0, 0.0)), 2.0, 0.0);
+    // X axis: red, from (1,0,0) to (3,0,0)
+    assert_eq!(vertex(&draw, 0)[..3], [1.0, 0.0, 0.0]);
+    assert_eq!(vertex(&draw, 1), [3.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0]);
+}
+
+#[test]
+fn test_frustum_corners_of_orthographic_camera() {
+    let projection = Mat4::orthographic_rh(-2.0, 2.0, -1.0, 1.0, 1.0, 10.0);
+    let frustum = Frustum::from_view_projection(&projection);
+    let viewport = Viewport { x: 0.0, y: 0.0, width: 4.0, height: 2.0, min_depth: 0.0, max_depth: 1.0 };
+    let camera = Camera::new(Mat4::IDENTITY, projection, frustum, viewport);
+
+    let mut draw = DebugDraw::new();
+    draw.frustum(&camera, RED, 0.0);
+    assert_eq!(draw.line_count(), 12);
+    for i in 0..draw.vertex_count() as usize {
+        let v = vertex(&draw, i);
+        assert!((v[0].abs() - 2.0).abs() < 1e-5, "{:?}", v);
+        assert!((v[1].abs() - 1.0).abs() < 1e-5, "{:?}", v);
+        assert!((v[2] + 1.0).abs() < 1e-5 || (v[2] + 10.0).abs() < 1e-5, "{:?}", v);
+    }
+}
+
+#[test]
+fn test_disabled_draw_ignores_shapes() {
+    let mut draw = DebugDraw::new();
+    draw.set_enabled(false);
+    draw.line(Vec3::ZERO, Vec3::ONE, RED, 1.0);
+    draw.sphere(Vec3::ZERO, 1.0, RED, 1.0);
+    assert_eq!(draw.line_count(), 0);
+    assert!(!draw.is_enabled());
+}
+
+// ============================================================================
+// Lifetimes
+// ============================================================================
+
+#[test]
+fn test_advance_expires_segments() {
+    let mut draw = DebugDraw::new();
+    draw.line(Vec3::ZERO, Vec3::X, RED, 0.0);
+    draw.line(Vec3::ZERO, Vec3::Y, RED, 0.05);
+    draw.line(Vec3::ZERO, Vec3::Z, RED, 1.0);
+
+    draw.advance(0.016);
+    assert_eq!(draw.line_count(), 2);
+    // Remaining segments are compacted in submission order
+    assert_eq!(vertex(&draw, 1)[..3], [0.0, 1.0, 0.0]);
+    assert_eq!(vertex(&draw, 3)[..3], [0.0, 0.0, 1.0]);
+
+    draw.advance(0.1);
+    assert_eq!(draw.line_count(), 1);
+    assert_eq!(vertex(&draw, 1)[..3], [0.0, 0.0, 1.0]);
+
+    draw.advance(1.0);
+    assert_eq!(draw.vertex_count(), 0);
+}
@@ -0,0 +1,13 @@
+//! Immediate-mode debug drawing.
+//!
+//! `DebugDraw` collects colored line segments (lines, boxes, spheres,
+//! frusta, axes) from anywhere in the frame, each with a lifetime, and
+//! `DebugDrawAction` renders them in a dedicated render-graph pass from a
+//! per-frame dynamic vertex buffer. Used to visualize physics shapes,
+//! octree cells, light ranges or camera frusta.
+
+mod debug_draw;
+mod debug_draw_action;
+
+pub use debug_draw::{DebugDraw, debug_vertex_layout, DEBUG_VERTEX_STRIDE, SPHERE_SEGMENTS};
+pub use debug_draw_action::DebugDrawAction;
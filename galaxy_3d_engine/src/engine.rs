@@ -5,14 +5,24 @@
 /// concurrent access.
 
 use rustc_hash::FxHashMap;
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::path::Path;
 use std::sync::{OnceLock, RwLock, Arc, Mutex};
 use std::time::SystemTime;
-use crate::graphics_device::GraphicsDevice;
+use winit::window::Window;
+use crate::camera::Camera;
+use crate::graphics_device::{GraphicsDevice, GraphicsDeviceStats, Config, graphics_device_plugin_registry};
+use crate::post::ViewTargetManager;
+use crate::profiler::{Profiler, FrameProfile};
 use crate::resource::ResourceManager;
+use crate::resource::resource_manager::{TextureKey, BufferKey, MaterialKey, PipelineKey, ResourceStats};
 use crate::scene::SceneManager;
-use crate::render_graph::RenderGraphManager;
+use crate::render_graph::{RenderGraphManager, GraphResource};
 use crate::error::{Result, Error};
-use crate::log::{Logger, LogEntry, LogSeverity, DefaultLogger};
+use crate::event::ProgressBus;
+use crate::log::{Logger, LogEntry, LogSeverity, LogFilter, DefaultLogger};
+use crate::utils::{CoordinateSystem, Handedness};
 
 // ===== INTERNAL STATE =====
 
@@ -22,6 +32,21 @@ static ENGINE_STATE: OnceLock<EngineState> = OnceLock::new();
 /// Global logger (initialized with DefaultLogger)
 static LOGGER: OnceLock<RwLock<Box<dyn Logger>>> = OnceLock::new();
 
+/// Severity filter applied before the logger (everything enabled by
+/// default)
+static LOG_FILTER: RwLock<LogFilter> = RwLock::new(LogFilter::new(Some(LogSeverity::Trace)));
+
+/// Most recent Warn / Error log entries, oldest first (see
+/// `Engine::recent_errors()`)
+static RECENT_ERRORS: Mutex<VecDeque<LogEntry>> = Mutex::new(VecDeque::new());
+
+/// Engine-wide bus of background work progress (see `Engine::progress_bus()`)
+static PROGRESS_BUS: OnceLock<ProgressBus> = OnceLock::new();
+
+/// Convention of the engine world space (right-handed Y-up by default)
+static WORLD_COORDINATE_SYSTEM: RwLock<CoordinateSystem> =
+    RwLock::new(CoordinateSystem::Y_UP_RIGHT_HANDED);
+
 /// Internal state structure holding all engine singletons
 struct EngineState {
     /// Named graphics devices (multiple devices supported, keyed by name)
@@ -32,6 +57,19 @@ struct EngineState {
     scene_manager: RwLock<Option<Arc<Mutex<SceneManager>>>>,
     /// Render graph manager singleton
     render_graph_manager: RwLock<Option<Arc<Mutex<RenderGraphManager>>>>,
+    /// View target manager singleton
+    view_target_manager: RwLock<Option<Arc<Mutex<ViewTargetManager>>>>,
+    /// Frame counter of `Engine::begin_frame()` / `end_frame()`
+    frame: Mutex<FrameState>,
+}
+
+/// Frame counter of the engine
+#[derive(Default)]
+struct FrameState {
+    /// Number of frames ended
+    index: u64,
+    /// A frame is begun and not ended
+    in_progress: bool,
 }
 
 impl EngineState {
@@ -42,10 +80,23 @@ impl EngineState {
             resource_manager: RwLock::new(None),
             scene_manager: RwLock::new(None),
             render_graph_manager: RwLock::new(None),
+            view_target_manager: RwLock::new(None),
+            frame: Mutex::new(FrameState::default()),
         }
     }
 }
 
+/// Summary of a frame, returned by `Engine::end_frame()`
+#[derive(Debug, Clone)]
+pub struct FrameSummary {
+    /// Index of the frame (0 for the first one)
+    pub frame_index: u64,
+    /// Counters of each graphics device over the frame, sorted by name
+    pub device_stats: Vec<(String, GraphicsDeviceStats)>,
+    /// Profiler zones of the frame (None when the profiler is disabled)
+    pub profile: Option<FrameProfile>,
+}
+
 // ===== PUBLIC API =====
 
 /// Main engine singleton manager
@@ -55,6 +106,16 @@ impl EngineState {
 pub struct Engine;
 
 impl Engine {
+    /// Number of Warn / Error log entries kept for `recent_errors()`
+    pub const RECENT_ERROR_CAPACITY: usize = 32;
+
+    /// Name of the graphics device created by `initialize_with_config()`
+    pub const MAIN_GRAPHICS_DEVICE: &'static str = "main";
+
+    /// Name of the view target manager created by `initialize_with_config()`
+    /// (prefixes its textures and graph resources)
+    pub const VIEW_TARGET_MANAGER_NAME: &'static str = "views";
+
     /// Helper to log errors before returning them (internal use)
     ///
     /// This ensures all Engine errors are automatically logged with proper severity
@@ -86,31 +147,175 @@ impl Engine {
         Ok(())
     }
 
+    /// Initialize the engine and create every subsystem from `config`
+    ///
+    /// In order:
+    /// 1. The graphics device `MAIN_GRAPHICS_DEVICE`, from the plugin named
+    ///    by `config.backend` (or the only registered plugin when None).
+    ///    The backend crate must have registered its plugin (e.g.
+    ///    `galaxy_3d_engine_renderer_vulkan::register()`).
+    /// 2. The resource manager, with `config.platform_profile`
+    /// 3. The scene manager
+    /// 4. The view target manager (`VIEW_TARGET_MANAGER_NAME`)
+    /// 5. The render graph manager
+    ///
+    /// On failure, everything created so far is destroyed (`shutdown()`).
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - Window the graphics device renders to
+    /// * `config` - Graphics device configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - No plugin matches `config.backend`, or it is None and zero or
+    ///   several plugins are registered
+    /// - The backend fails to create its graphics device
+    /// - A subsystem already exists
+    ///
+    pub fn initialize_with_config(window: &Window, config: Config) -> Result<()> {
+        Self::initialize()?;
+        let result = Self::create_subsystems(window, config);
+        if result.is_err() {
+            Self::shutdown();
+        }
+        result
+    }
+
+    /// Create the subsystems of `initialize_with_config()` (internal use)
+    fn create_subsystems(window: &Window, config: Config) -> Result<()> {
+        let platform_profile = config.platform_profile;
+        let device = {
+            let registry = graphics_device_plugin_registry().lock()
+                .map_err(|_| Self::log_and_return_error(
+                    Error::BackendError("GraphicsDevice plugin registry lock poisoned".to_string())
+                ))?;
+            let registry = registry.as_ref()
+                .expect("the plugin registry is created on first access");
+            let backend = match config.backend.as_deref() {
+                Some(backend) => backend.to_string(),
+                None => match registry.plugin_names().as_slice() {
+                    [backend] => backend.to_string(),
+                    [] => return Err(Self::log_and_return_error(Error::InitializationFailed(
+                        "No GraphicsDevice plugin registered. Register a backend first (e.g. galaxy_3d_engine_renderer_vulkan::register()).".to_string()
+                    ))),
+                    names => return Err(Self::log_and_return_error(Error::InitializationFailed(format!(
+                        "Several GraphicsDevice plugins registered ({}). Set Config::backend.", names.join(", ")
+                    )))),
+                },
+            };
+            registry.create_graphics_device(&backend, window, config)
+                .map_err(Self::log_and_return_error)?
+        };
+        Self::register_graphics_device(Self::MAIN_GRAPHICS_DEVICE, device)?;
+        crate::engine_info!("galaxy3d::Engine", "GraphicsDevice '{}' created successfully", Self::MAIN_GRAPHICS_DEVICE);
+
+        Self::create_resource_manager()?;
+        Self::resource_manager()?.lock()
+            .map_err(|_| Self::log_and_return_error(
+                Error::BackendError("ResourceManager lock poisoned".to_string())
+            ))?
+            .set_platform_profile(platform_profile);
+        Self::create_scene_manager()?;
+        Self::create_view_target_manager()?;
+        Self::create_render_graph_manager()?;
+        Ok(())
+    }
+
     /// Shutdown the entire engine and destroy all singletons
     ///
     /// This should be called at application shutdown to properly cleanup all subsystems.
     /// After calling this, you must call `initialize()` again before creating new subsystems.
+    ///
+    /// Teardown order is deterministic:
+    /// 1. Wait for every graphics device to be idle (no GPU work in flight)
+    /// 2. Render graph manager (command lists, framebuffers, graph resources)
+    /// 3. View target manager (keys of graph resources and textures)
+    /// 4. Scene manager (scenes reference resources)
+    /// 5. Resource manager (resources reference GPU objects)
+    /// 6. Graphics devices
+    ///
+    /// The frame counter of `begin_frame()` / `end_frame()` restarts at 0.
+    ///
+    /// The render graph goes first, ahead of scenes and resources: it holds
+    /// resource keys and recorded command lists, so it must not outlive the
+    /// resources those keys point to.
+    ///
+    /// Anything still referenced outside the engine at that point is logged
+    /// as a warning (type, name, reference count) so that drop-order issues
+    /// can be tracked down.
     pub fn shutdown() {
         if let Some(state) = ENGINE_STATE.get() {
+            // Drain GPU work BEFORE dropping anything that may be in use
+            if let Ok(graphics_devices) = state.graphics_devices.read() {
+                for (name, device) in graphics_devices.iter() {
+                    match device.lock() {
+                        Ok(device) => {
+                            if let Err(e) = device.wait_idle() {
+                                crate::engine_warn!("galaxy3d::Engine",
+                                    "wait_idle failed on GraphicsDevice '{}' during shutdown: {}",
+                                    name, e);
+                            }
+                        }
+                        Err(_) => {
+                            crate::engine_warn!("galaxy3d::Engine",
+                                "GraphicsDevice '{}' lock poisoned during shutdown", name);
+                        }
+                    }
+                }
+            }
+
             // Clear render graph manager BEFORE scene manager
             if let Ok(mut rgm) = state.render_graph_manager.write() {
-                *rgm = None;
+                if let Some(rgm) = rgm.take() {
+                    Self::report_leak("RenderGraphManager", "render_graph_manager", Arc::strong_count(&rgm));
+                }
+            }
+            if let Ok(mut vtm) = state.view_target_manager.write() {
+                if let Some(vtm) = vtm.take() {
+                    Self::report_leak("ViewTargetManager", "view_target_manager", Arc::strong_count(&vtm));
+                }
             }
             // Clear scene manager BEFORE resource manager (scenes reference resources)
             if let Ok(mut sm) = state.scene_manager.write() {
-                *sm = None;
+                if let Some(sm) = sm.take() {
+                    Self::report_leak("SceneManager", "scene_manager", Arc::strong_count(&sm));
+                }
             }
             // Clear resource manager BEFORE graphics_devices (resources reference GPU objects)
             if let Ok(mut rm) = state.resource_manager.write() {
-                *rm = None;
+                if let Some(rm) = rm.take() {
+                    if let Ok(rm_guard) = rm.lock() {
+                        for leak in rm_guard.external_references() {
+                            Self::report_leak(leak.kind, &leak.name, leak.ref_count);
+                        }
+                    }
+                    Self::report_leak("ResourceManager", "resource_manager", Arc::strong_count(&rm));
+                }
             }
             // Clear all graphics devices
             if let Ok(mut graphics_devices) = state.graphics_devices.write() {
-                graphics_devices.clear();
+                for (name, device) in graphics_devices.drain() {
+                    Self::report_leak("GraphicsDevice", &name, Arc::strong_count(&device));
+                }
+            }
+            if let Ok(mut frame) = state.frame.lock() {
+                *frame = FrameState::default();
             }
         }
     }
 
+    /// Log a warning if a subsystem or resource is still referenced elsewhere
+    /// at shutdown (`ref_count` includes the engine's own reference).
+    fn report_leak(kind: &str, name: &str, ref_count: usize) {
+        if ref_count > 1 {
+            crate::engine_warn!("galaxy3d::Engine",
+                "Leak at shutdown: {} '{}' still has {} outstanding reference(s)",
+                kind, name, ref_count - 1);
+        }
+    }
+
     /// Create and register a named graphics device
     ///
     /// Wraps the graphics device in Arc and registers it in the global graphics_device map.
@@ -523,6 +728,233 @@ impl Engine {
         Ok(())
     }
 
+    // ===== VIEW TARGET MANAGER API =====
+
+    /// Create and register the view target manager singleton
+    ///
+    /// Creates a new ViewTargetManager named `VIEW_TARGET_MANAGER_NAME` and
+    /// registers it as a global singleton.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The engine is not initialized
+    /// - A view target manager already exists
+    ///
+    pub fn create_view_target_manager() -> Result<()> {
+        let state = ENGINE_STATE.get()
+            .ok_or_else(|| Self::log_and_return_error(
+                Error::InitializationFailed("Engine not initialized. Call Engine::initialize() first.".to_string())
+            ))?;
+
+        let mut lock = state.view_target_manager.write()
+            .map_err(|_| Self::log_and_return_error(
+                Error::BackendError("ViewTargetManager lock poisoned".to_string())
+            ))?;
+
+        if lock.is_some() {
+            return Err(Self::log_and_return_error(
+                Error::InitializationFailed("ViewTargetManager already exists. Call Engine::destroy_view_target_manager() first.".to_string())
+            ));
+        }
+
+        *lock = Some(Arc::new(Mutex::new(ViewTargetManager::new(Self::VIEW_TARGET_MANAGER_NAME))));
+
+        crate::engine_info!("galaxy3d::Engine", "ViewTargetManager singleton created successfully");
+
+        Ok(())
+    }
+
+    /// Get the view target manager singleton
+    ///
+    /// Provides global access to the view target manager after it has been created.
+    ///
+    /// # Returns
+    ///
+    /// A shared pointer to the ViewTargetManager wrapped in a Mutex for thread-safe access
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The engine is not initialized
+    /// - The view target manager has not been created
+    ///
+    pub fn view_target_manager() -> Result<Arc<Mutex<ViewTargetManager>>> {
+        let state = ENGINE_STATE.get()
+            .ok_or_else(|| Self::log_and_return_error(
+                Error::InitializationFailed("Engine not initialized. Call Engine::initialize() first.".to_string())
+            ))?;
+
+        let lock = state.view_target_manager.read()
+            .map_err(|_| Self::log_and_return_error(
+                Error::BackendError("ViewTargetManager lock poisoned".to_string())
+            ))?;
+
+        lock.clone()
+            .ok_or_else(|| Self::log_and_return_error(
+                Error::InitializationFailed("ViewTargetManager not created. Call Engine::create_view_target_manager() first.".to_string())
+            ))
+    }
+
+    /// Destroy the view target manager singleton
+    ///
+    /// Removes the view target manager singleton, allowing a new one to be
+    /// created. The textures and graph resources of its targets are not
+    /// removed: call `ViewTargetManager::remove_view_target` first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the engine is not initialized
+    ///
+    pub fn destroy_view_target_manager() -> Result<()> {
+        let state = ENGINE_STATE.get()
+            .ok_or_else(|| Self::log_and_return_error(
+                Error::InitializationFailed("Engine not initialized".to_string())
+            ))?;
+
+        let mut lock = state.view_target_manager.write()
+            .map_err(|_| Self::log_and_return_error(
+                Error::BackendError("ViewTargetManager lock poisoned".to_string())
+            ))?;
+
+        *lock = None;
+
+        crate::engine_info!("galaxy3d::Engine", "ViewTargetManager singleton destroyed");
+
+        Ok(())
+    }
+
+    // ===== FRAME API =====
+
+    /// Begin a frame
+    ///
+    /// 1. Starts a profiler frame (`Profiler::begin_frame()`)
+    /// 2. Waits for the previous submit of every graphics device, then
+    ///    recycles its transient descriptor pools and resets its per-frame
+    ///    stats
+    /// 3. Releases the resources retired by the resource manager (the GPU
+    ///    no longer uses them) and advances its texture usage frame
+    ///
+    /// Call it once per frame, before updating scenes and recording
+    /// commands, and pair it with `end_frame()`.
+    ///
+    /// # Returns
+    ///
+    /// The index of the frame (0 for the first one)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The engine is not initialized
+    /// - A frame is already begun
+    /// - Waiting for a graphics device or resetting its transient pools
+    ///   fails
+    ///
+    pub fn begin_frame() -> Result<u64> {
+        let state = ENGINE_STATE.get()
+            .ok_or_else(|| Self::log_and_return_error(
+                Error::InitializationFailed("Engine not initialized. Call Engine::initialize() first.".to_string())
+            ))?;
+
+        let mut frame = state.frame.lock()
+            .map_err(|_| Self::log_and_return_error(
+                Error::BackendError("Frame state lock poisoned".to_string())
+            ))?;
+        if frame.in_progress {
+            return Err(Self::log_and_return_error(
+                Error::BackendError(format!("Frame {} already begun. Call Engine::end_frame() first.", frame.index))
+            ));
+        }
+
+        Profiler::begin_frame();
+
+        let devices: Vec<(String, Arc<Mutex<dyn GraphicsDevice>>)> = state.graphics_devices.read()
+            .map_err(|_| Self::log_and_return_error(
+                Error::BackendError("GraphicsDevices lock poisoned".to_string())
+            ))?
+            .iter().map(|(name, device)| (name.clone(), Arc::clone(device))).collect();
+        for (name, device) in &devices {
+            let device = device.lock()
+                .map_err(|_| Self::log_and_return_error(
+                    Error::BackendError(format!("GraphicsDevice '{}' lock poisoned", name))
+                ))?;
+            device.wait_for_previous_submit()?;
+            device.reset_transient_pools()?;
+            device.reset_stats();
+        }
+
+        let rm = state.resource_manager.read()
+            .map_err(|_| Self::log_and_return_error(
+                Error::BackendError("ResourceManager lock poisoned".to_string())
+            ))?
+            .clone();
+        if let Some(rm) = rm {
+            let mut rm = rm.lock()
+                .map_err(|_| Self::log_and_return_error(
+                    Error::BackendError("ResourceManager lock poisoned".to_string())
+                ))?;
+            rm.release_retired_resources();
+            rm.begin_texture_usage_frame();
+        }
+
+        frame.in_progress = true;
+        Ok(frame.index)
+    }
+
+    /// End the frame started by `begin_frame()`
+    ///
+    /// Call it after the last submit of the frame.
+    ///
+    /// # Returns
+    ///
+    /// The stats of every graphics device over the frame and its profiler
+    /// zones
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the engine is not initialized or no frame is
+    /// begun.
+    ///
+    pub fn end_frame() -> Result<FrameSummary> {
+        let state = ENGINE_STATE.get()
+            .ok_or_else(|| Self::log_and_return_error(
+                Error::InitializationFailed("Engine not initialized. Call Engine::initialize() first.".to_string())
+            ))?;
+
+        let mut frame = state.frame.lock()
+            .map_err(|_| Self::log_and_return_error(
+                Error::BackendError("Frame state lock poisoned".to_string())
+            ))?;
+        if !frame.in_progress {
+            return Err(Self::log_and_return_error(
+                Error::BackendError("No frame begun. Call Engine::begin_frame() first.".to_string())
+            ));
+        }
+
+        let mut device_stats: Vec<(String, GraphicsDeviceStats)> = state.graphics_devices.read()
+            .map(|lock| lock.iter()
+                .filter_map(|(name, device)| device.lock().ok().map(|device| (name.clone(), device.stats())))
+                .collect())
+            .unwrap_or_default();
+        device_stats.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let summary = FrameSummary {
+            frame_index: frame.index,
+            device_stats,
+            profile: Profiler::end_frame(),
+        };
+        frame.index += 1;
+        frame.in_progress = false;
+        Ok(summary)
+    }
+
+    /// Number of frames ended since the engine was initialized
+    pub fn frame_count() -> u64 {
+        ENGINE_STATE.get()
+            .and_then(|state| state.frame.lock().ok().map(|frame| frame.index))
+            .unwrap_or(0)
+    }
+
     /// Reset all singletons for testing (only available in test builds)
     #[cfg(test)]
     pub fn reset_for_testing() {
@@ -530,6 +962,9 @@ impl Engine {
             if let Ok(mut rgm) = state.render_graph_manager.write() {
                 *rgm = None;
             }
+            if let Ok(mut vtm) = state.view_target_manager.write() {
+                *vtm = None;
+            }
             if let Ok(mut sm) = state.scene_manager.write() {
                 *sm = None;
             }
@@ -539,7 +974,65 @@ impl Engine {
             if let Ok(mut graphics_devices) = state.graphics_devices.write() {
                 graphics_devices.clear();
             }
+            if let Ok(mut frame) = state.frame.lock() {
+                *frame = FrameState::default();
+            }
         }
+        if let Ok(mut cs) = WORLD_COORDINATE_SYSTEM.write() {
+            *cs = CoordinateSystem::Y_UP_RIGHT_HANDED;
+        }
+        if let Ok(mut recent) = RECENT_ERRORS.lock() {
+            recent.clear();
+        }
+        if let Ok(mut filter) = LOG_FILTER.write() {
+            *filter = LogFilter::default();
+        }
+    }
+
+    // ===== COORDINATE SYSTEM API =====
+
+    /// Set the convention of the engine world space
+    ///
+    /// Only the up axis is configurable: the world is always right-handed
+    /// (projection, frustum and winding conventions assume it). Assets and
+    /// physics data in other conventions are converted with
+    /// `CoordinateSystem::conversion_to(&Engine::world_coordinate_system())`.
+    /// Set it once at startup, before creating scenes and cameras.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `coordinate_system` is left-handed.
+    ///
+    pub fn set_world_coordinate_system(coordinate_system: CoordinateSystem) -> Result<()> {
+        if coordinate_system.handedness != Handedness::Right {
+            crate::engine_bail!("galaxy3d::Engine",
+                "The engine world must be right-handed, got {:?}", coordinate_system);
+        }
+        let mut lock = WORLD_COORDINATE_SYSTEM.write()
+            .map_err(|_| Self::log_and_return_error(
+                Error::BackendError("World coordinate system lock poisoned".to_string())
+            ))?;
+        *lock = coordinate_system;
+        Ok(())
+    }
+
+    /// Convention of the engine world space
+    pub fn world_coordinate_system() -> CoordinateSystem {
+        WORLD_COORDINATE_SYSTEM.read()
+            .map(|lock| *lock)
+            .unwrap_or(CoordinateSystem::Y_UP_RIGHT_HANDED)
+    }
+
+    // ===== EVENT API =====
+
+    /// Engine-wide bus of background work progress
+    ///
+    /// Background producers publish `ProgressEvent`s on it; loading screens
+    /// `subscribe()` and feed the events to a `LoadProgress`. The returned
+    /// handle shares the subscribers of every other handle. It does not
+    /// need `initialize()` and survives `shutdown()`.
+    pub fn progress_bus() -> ProgressBus {
+        PROGRESS_BUS.get_or_init(ProgressBus::new).clone()
     }
 
     // ===== LOGGING API =====
@@ -568,6 +1061,40 @@ impl Engine {
         }
     }
 
+    /// Replace the severity filter applied to every entry before the logger
+    ///
+    /// `recent_errors()` keeps warnings and errors whatever the filter.
+    /// Sinks of a `LogFanOut` can filter further with their own filter.
+    ///
+    pub fn set_log_filter(filter: LogFilter) {
+        if let Ok(mut lock) = LOG_FILTER.write() {
+            *lock = filter;
+        }
+    }
+
+    /// Current severity filter (see `set_log_filter`)
+    pub fn log_filter() -> LogFilter {
+        LOG_FILTER.read()
+            .map(|lock| lock.clone())
+            .unwrap_or_default()
+    }
+
+    /// Set the minimum severity logged for `module` and the modules below
+    /// it (None: silenced), e.g. `("galaxy3d::vulkan", Some(LogSeverity::Debug))`
+    ///
+    pub fn set_module_log_level(module: &str, level: Option<LogSeverity>) {
+        if let Ok(mut lock) = LOG_FILTER.write() {
+            lock.set_module_level(module, level);
+        }
+    }
+
+    /// Whether an entry of `severity` from `source` reaches the logger
+    pub fn is_log_enabled(severity: LogSeverity, source: &str) -> bool {
+        LOG_FILTER.read()
+            .map(|lock| lock.enabled(severity, source))
+            .unwrap_or(true)
+    }
+
     /// Internal logging method (for simple logs without file:line)
     ///
     /// Used by macros like engine_info!, engine_warn!, etc.
@@ -578,17 +1105,14 @@ impl Engine {
     /// * `source` - Source module (e.g., "galaxy3d::Engine")
     /// * `message` - Log message
     pub fn log(severity: LogSeverity, source: &str, message: String) {
-        let logger_lock = LOGGER.get_or_init(|| RwLock::new(Box::new(DefaultLogger)));
-        if let Ok(lock) = logger_lock.read() {
-            lock.log(&LogEntry {
-                severity,
-                timestamp: SystemTime::now(),
-                source: source.to_string(),
-                message,
-                file: None,
-                line: None,
-            });
-        }
+        Self::dispatch(LogEntry {
+            severity,
+            timestamp: SystemTime::now(),
+            source: source.to_string(),
+            message,
+            file: None,
+            line: None,
+        });
     }
 
     /// Internal logging method with file:line information (for ERROR logs)
@@ -609,18 +1133,273 @@ impl Engine {
         file: &'static str,
         line: u32,
     ) {
-        let logger_lock = LOGGER.get_or_init(|| RwLock::new(Box::new(DefaultLogger)));
-        if let Ok(lock) = logger_lock.read() {
-            lock.log(&LogEntry {
-                severity,
-                timestamp: SystemTime::now(),
-                source: source.to_string(),
-                message,
-                file: Some(file),
-                line: Some(line),
-            });
+        Self::dispatch(LogEntry {
+            severity,
+            timestamp: SystemTime::now(),
+            source: source.to_string(),
+            message,
+            file: Some(file),
+            line: Some(line),
+        });
+    }
+
+    /// The last `RECENT_ERROR_CAPACITY` Warn / Error log entries, oldest first
+    pub fn recent_errors() -> Vec<LogEntry> {
+        RECENT_ERRORS.lock()
+            .map(|recent| recent.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Send an entry to the logger if the log filter lets it through,
+    /// keeping warnings and errors for `recent_errors()`
+    fn dispatch(entry: LogEntry) {
+        if Self::is_log_enabled(entry.severity, &entry.source) {
+            let logger_lock = LOGGER.get_or_init(|| RwLock::new(Box::new(DefaultLogger)));
+            if let Ok(lock) = logger_lock.read() {
+                lock.log(&entry);
+            }
+        }
+        if entry.severity >= LogSeverity::Warn {
+            if let Ok(mut recent) = RECENT_ERRORS.lock() {
+                if recent.len() == Self::RECENT_ERROR_CAPACITY {
+                    recent.pop_front();
+                }
+                recent.push_back(entry);
+            }
         }
     }
+
+    // ===== FRAME STATE DUMP =====
+
+    /// Write `frame_state_report()` to `path` (atomically, see
+    /// `utils::write_atomic`), for attaching to bug reports.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the engine is not initialized or the file cannot
+    /// be written.
+    pub fn dump_frame_state(path: impl AsRef<Path>) -> Result<()> {
+        let report = Self::frame_state_report()?;
+        crate::utils::write_atomic(path.as_ref(), report.as_bytes())?;
+        crate::engine_info!("galaxy3d::Engine",
+            "Frame state written to '{}'", path.as_ref().display());
+        Ok(())
+    }
+
+    /// Human-readable report of the current frame: graphics devices,
+    /// resource counts, scenes and their instance counts, render graphs
+    /// with the passes of their last execution, every render pass with its
+    /// targets, bound pipelines / materials and camera, and the recent
+    /// warnings and errors.
+    ///
+    /// Managers that have not been created are reported as such. Locks the
+    /// `ResourceManager`, then the `RenderGraphManager` and the scenes drawn
+    /// by its passes: call it outside of render graph execution.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the engine is not initialized.
+    pub fn frame_state_report() -> Result<String> {
+        let state = ENGINE_STATE.get()
+            .ok_or_else(|| Self::log_and_return_error(
+                Error::InitializationFailed("Engine not initialized. Call Engine::initialize() first.".to_string())
+            ))?;
+        let mut report = String::new();
+        write_frame_state(&mut report, state)
+            .expect("writing to a String cannot fail");
+        Ok(report)
+    }
+}
+
+// ===== FRAME STATE REPORT =====
+
+/// Names of the resources a frame-state report refers to by key, copied so
+/// that the `ResourceManager` lock is released before the render graph is
+/// inspected.
+#[derive(Default)]
+struct ResourceNames {
+    stats: Option<ResourceStats>,
+    textures: FxHashMap<TextureKey, String>,
+    buffers: FxHashMap<BufferKey, String>,
+    materials: FxHashMap<MaterialKey, String>,
+    pipelines: FxHashMap<PipelineKey, String>,
+}
+
+impl ResourceNames {
+    fn collect(rm: &ResourceManager) -> Self {
+        Self {
+            stats: Some(rm.stats()),
+            textures: rm.textures().map(|e| (e.key, e.name.to_string())).collect(),
+            buffers: rm.buffers().map(|e| (e.key, e.name.to_string())).collect(),
+            materials: rm.materials().map(|e| (e.key, e.name.to_string())).collect(),
+            pipelines: rm.pipelines().map(|e| (e.key, e.name.to_string())).collect(),
+        }
+    }
+}
+
+/// Name of a keyed resource, or a placeholder for unnamed / removed ones
+fn name_of<'a, K: std::hash::Hash + Eq>(names: &'a FxHashMap<K, String>, key: &K) -> &'a str {
+    names.get(key).map(String::as_str).unwrap_or("<unnamed>")
+}
+
+fn write_frame_state(out: &mut String, state: &EngineState) -> std::fmt::Result {
+    writeln!(out, "Galaxy3D frame state")?;
+    writeln!(out, "world: {:?}", Engine::world_coordinate_system())?;
+
+    // Graphics devices
+    let mut devices: Vec<(String, Arc<Mutex<dyn GraphicsDevice>>)> = state.graphics_devices.read()
+        .map(|lock| lock.iter().map(|(name, gd)| (name.clone(), gd.clone())).collect())
+        .unwrap_or_default();
+    devices.sort_by(|a, b| a.0.cmp(&b.0));
+    writeln!(out, "\n== Graphics devices ({}) ==", devices.len())?;
+    for (name, gd) in &devices {
+        match gd.lock() {
+            Ok(gd) => {
+                let stats = gd.stats();
+                writeln!(out, "{}: {} draw calls, {} triangles, {} pipeline binds, {} submits, {} bytes GPU memory",
+                    name, stats.draw_calls, stats.triangles, stats.pipeline_binds,
+                    stats.submits, stats.gpu_memory_used)?;
+            }
+            Err(_) => writeln!(out, "{}: lock poisoned", name)?,
+        }
+    }
+
+    // Resources
+    let rm = state.resource_manager.read().ok().and_then(|lock| lock.clone());
+    let names = rm.as_ref()
+        .and_then(|rm| rm.lock().ok().map(|rm| ResourceNames::collect(&rm)))
+        .unwrap_or_default();
+    writeln!(out, "\n== Resources ==")?;
+    match &names.stats {
+        Some(stats) => {
+            writeln!(out, "{} textures, {} geometries, {} shaders, {} pipelines, {} materials, {} meshes, {} buffers",
+                stats.textures, stats.geometries, stats.shaders, stats.pipelines,
+                stats.materials, stats.meshes, stats.buffers)?;
+            writeln!(out, "{} retired, {} pending removal, {} externally referenced",
+                stats.retired_resources, stats.pending_removals, stats.externally_referenced)?;
+        }
+        None => writeln!(out, "ResourceManager not created")?,
+    }
+
+    // Scenes
+    writeln!(out, "\n== Scenes ==")?;
+    let sm = state.scene_manager.read().ok().and_then(|lock| lock.clone());
+    match sm.as_ref().map(|sm| sm.lock()) {
+        Some(Ok(sm)) => {
+            let mut scene_names = sm.scene_names();
+            scene_names.sort_unstable();
+            for name in scene_names {
+                let Some(scene) = sm.scene(name) else { continue };
+                let Ok(scene) = scene.lock() else {
+                    writeln!(out, "{}: lock poisoned", name)?;
+                    continue;
+                };
+                writeln!(out, "{}: {} instances ({} new, {} dirty transforms), {} lights, {} billboards, {} nodes",
+                    name, scene.render_instance_count(), scene.new_instance_count(),
+                    scene.dirty_instance_transform_count(), scene.light_count(),
+                    scene.billboard_count(), scene.node_count())?;
+            }
+        }
+        Some(Err(_)) => writeln!(out, "SceneManager lock poisoned")?,
+        None => writeln!(out, "SceneManager not created")?,
+    }
+
+    // Render graphs and passes
+    match state.render_graph_manager.read().ok().and_then(|lock| lock.clone()) {
+        Some(rgm) => match rgm.lock() {
+            Ok(rgm) => write_render_graphs(out, &rgm, &names)?,
+            Err(_) => writeln!(out, "\n== Render graphs ==\nRenderGraphManager lock poisoned")?,
+        },
+        None => writeln!(out, "\n== Render graphs ==\nRenderGraphManager not created")?,
+    }
+
+    // Recent errors
+    let recent = Engine::recent_errors();
+    let now = SystemTime::now();
+    writeln!(out, "\n== Recent warnings and errors ({}) ==", recent.len())?;
+    for entry in &recent {
+        let age = now.duration_since(entry.timestamp).unwrap_or_default().as_secs_f32();
+        write!(out, "[{:?}] {:.1}s ago {}: {}", entry.severity, age, entry.source, entry.message)?;
+        match (entry.file, entry.line) {
+            (Some(file), Some(line)) => writeln!(out, " ({}:{})", file, line)?,
+            _ => writeln!(out)?,
+        }
+    }
+    Ok(())
+}
+
+fn write_render_graphs(out: &mut String, rgm: &RenderGraphManager, names: &ResourceNames) -> std::fmt::Result {
+    let mut graphs: Vec<_> = rgm.render_graphs().map(|(_, graph)| graph).collect();
+    graphs.sort_by(|a, b| a.name().cmp(b.name()));
+    writeln!(out, "\n== Render graphs ({}) ==", graphs.len())?;
+    for graph in graphs {
+        let passes: Vec<&str> = graph.executed_passes().iter()
+            .filter_map(|&key| rgm.render_pass(key).map(|pass| pass.name()))
+            .collect();
+        if passes.is_empty() {
+            writeln!(out, "{}: not executed", graph.name())?;
+        } else {
+            writeln!(out, "{}: {}", graph.name(), passes.join(" -> "))?;
+        }
+    }
+
+    let mut passes: Vec<_> = rgm.render_passes().map(|(_, pass)| pass).collect();
+    passes.sort_by(|a, b| a.name().cmp(b.name()));
+    writeln!(out, "\n== Render passes ({}) ==", passes.len())?;
+    for pass in passes {
+        writeln!(out, "{}", pass.name())?;
+        if let Some(info) = pass.pass_info() {
+            writeln!(out, "  formats: color {:?}, depth {:?}, {:?}",
+                info.color_formats, info.depth_format, info.sample_count)?;
+        }
+        for access in pass.accesses() {
+            let resource_name = rgm.graph_resource_name(access.graph_resource_key).unwrap_or("<removed>");
+            let target = match rgm.graph_resource(access.graph_resource_key) {
+                Some(GraphResource::Texture { texture_key, base_mip_level, base_array_layer, layer_count }) =>
+                    format!("texture '{}' mip {} layers {}..{}", name_of(&names.textures, &texture_key),
+                        base_mip_level, base_array_layer, base_array_layer + layer_count),
+                Some(GraphResource::Buffer(buffer_key)) =>
+                    format!("buffer '{}'", name_of(&names.buffers, &buffer_key)),
+                None => "missing".to_string(),
+            };
+            writeln!(out, "  {:?} '{}' -> {}", access.access_type, resource_name, target)?;
+        }
+        let summary = pass.action_summary();
+        writeln!(out, "  action: {}, {} visible submeshes", summary.kind, summary.visible_submeshes)?;
+        if !summary.pipelines.is_empty() {
+            let list: Vec<&str> = summary.pipelines.iter().map(|k| name_of(&names.pipelines, k)).collect();
+            writeln!(out, "  pipelines: {}", list.join(", "))?;
+        }
+        if !summary.materials.is_empty() {
+            let list: Vec<&str> = summary.materials.iter().map(|k| name_of(&names.materials, k)).collect();
+            writeln!(out, "  materials: {}", list.join(", "))?;
+        }
+        if let Some(camera) = &summary.camera {
+            write_camera(out, camera)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_camera(out: &mut String, camera: &Camera) -> std::fmt::Result {
+    let world = camera.view_matrix().inverse();
+    let position = world.w_axis.truncate();
+    let forward = -world.z_axis.truncate().normalize_or_zero();
+    writeln!(out, "  camera: position ({:.3}, {:.3}, {:.3}), forward ({:.3}, {:.3}, {:.3})",
+        position.x, position.y, position.z, forward.x, forward.y, forward.z)?;
+    let projection = camera.projection_matrix();
+    if projection.w_axis.w == 0.0 {
+        let fov_y = 2.0 * (1.0 / projection.y_axis.y).atan();
+        writeln!(out, "  projection: perspective, vertical fov {:.1} deg, aspect {:.3}",
+            fov_y.to_degrees(), projection.y_axis.y / projection.x_axis.x)?;
+    } else {
+        writeln!(out, "  projection: orthographic")?;
+    }
+    let viewport = camera.viewport();
+    let jitter = camera.jitter();
+    writeln!(out, "  viewport: {}x{} at ({}, {}), depth {}..{}, jitter ({:.3}, {:.3})",
+        viewport.width, viewport.height, viewport.x, viewport.y,
+        viewport.min_depth, viewport.max_depth, jitter.x, jitter.y)
 }
 
 #[cfg(test)]
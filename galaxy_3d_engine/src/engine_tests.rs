@@ -190,6 +190,49 @@ fn test_reset_for_testing() {
     assert_eq!(Engine::graphics_device_count(), 0);
 }
 
+#[test]
+#[serial]
+fn test_shutdown_reports_outstanding_references() {
+    setup();
+
+    let test_logger = TestLogger::new();
+    let entries_ref = test_logger.entries.clone();
+    Engine::set_logger(test_logger);
+
+    // Keep a clone of the device alive across shutdown
+    let _held = Engine::create_graphics_device("test_shutdown_leak", MockGraphicsDevice::new()).unwrap();
+    Engine::shutdown();
+
+    {
+        let entries = entries_ref.lock().unwrap();
+        assert!(entries.iter().any(|e| e.starts_with("Warn")
+            && e.contains("GraphicsDevice 'test_shutdown_leak'")
+            && e.contains("1 outstanding reference")));
+    }
+
+    Engine::reset_logger();
+    Engine::initialize().unwrap();
+}
+
+#[test]
+#[serial]
+fn test_shutdown_without_leaks_logs_nothing() {
+    setup();
+    Engine::create_graphics_device("test_shutdown_no_leak", MockGraphicsDevice::new()).unwrap();
+    Engine::create_resource_manager().unwrap();
+
+    let test_logger = TestLogger::new();
+    let entries_ref = test_logger.entries.clone();
+    Engine::set_logger(test_logger);
+
+    Engine::shutdown();
+
+    assert!(!entries_ref.lock().unwrap().iter().any(|e| e.contains("Leak at shutdown")));
+
+    Engine::reset_logger();
+    Engine::initialize().unwrap();
+}
+
 // ============================================================================
 // RENDERER API TESTS
 // ============================================================================
@@ -616,6 +659,25 @@ fn test_custom_logger_receives_logs() {
     assert_eq!(entries.len(), 5);
 }
 
+// ============================================================================
+// EVENT API TESTS
+// ============================================================================
+
+#[test]
+fn test_progress_bus_handles_share_subscribers() {
+    use crate::event::{ProgressEvent, ProgressState};
+
+    let subscriber = Engine::progress_bus().subscribe();
+    Engine::progress_bus().publish(ProgressEvent {
+        source: "galaxy3d::Engine",
+        name: "engine_tests_progress".to_string(),
+        state: ProgressState::Queued,
+    });
+    // Other tests may publish on the engine-wide bus concurrently
+    let events = subscriber.drain();
+    assert!(events.iter().any(|e| e.name == "engine_tests_progress" && e.state == ProgressState::Queued));
+}
+
 // ============================================================================
 // INTEGRATION TESTS
 // ============================================================================
@@ -1330,3 +1392,239 @@ fn test_log_and_return_error_initialization_failed_path() {
     assert!(entries.iter().any(|e| e.contains("Initialization failed")));
     assert!(entries.iter().any(|e| e.contains("test init failed")));
 }
+
+// ============================================================================
+// COORDINATE SYSTEM TESTS
+// ============================================================================
+
+#[test]
+#[serial]
+fn test_world_coordinate_system_defaults_to_y_up_and_resets() {
+    use crate::utils::CoordinateSystem;
+    setup();
+    assert_eq!(Engine::world_coordinate_system(), CoordinateSystem::Y_UP_RIGHT_HANDED);
+
+    Engine::set_world_coordinate_system(CoordinateSystem::Z_UP_RIGHT_HANDED).unwrap();
+    assert_eq!(Engine::world_coordinate_system(), CoordinateSystem::Z_UP_RIGHT_HANDED);
+
+    Engine::reset_for_testing();
+    assert_eq!(Engine::world_coordinate_system(), CoordinateSystem::Y_UP_RIGHT_HANDED);
+}
+
+#[test]
+#[serial]
+fn test_left_handed_world_coordinate_system_rejected() {
+    use crate::utils::CoordinateSystem;
+    setup();
+    assert!(Engine::set_world_coordinate_system(CoordinateSystem::Y_UP_LEFT_HANDED).is_err());
+    assert_eq!(Engine::world_coordinate_system(), CoordinateSystem::Y_UP_RIGHT_HANDED);
+}
+
+// ============================================================================
+// FRAME STATE DUMP TESTS
+// ============================================================================
+
+#[test]
+#[serial]
+fn test_recent_errors_keep_last_warnings_and_errors() {
+    setup();
+    Engine::log(LogSeverity::Info, "galaxy3d::test", "ignored".to_string());
+    for i in 0..Engine::RECENT_ERROR_CAPACITY + 2 {
+        Engine::log(LogSeverity::Warn, "galaxy3d::test", format!("warning {}", i));
+    }
+    Engine::log_detailed(LogSeverity::Error, "galaxy3d::test", "failure".to_string(), "engine.rs", 7);
+
+    let recent = Engine::recent_errors();
+    assert_eq!(recent.len(), Engine::RECENT_ERROR_CAPACITY);
+    assert_eq!(recent[0].message, "warning 3");
+    assert_eq!(recent.last().unwrap().message, "failure");
+
+    Engine::reset_for_testing();
+    assert!(Engine::recent_errors().is_empty());
+}
+
+#[test]
+#[serial]
+fn test_frame_state_report_lists_scenes_passes_and_errors() {
+    use crate::render_graph::{AccessType, GraphResource, ResourceAccess};
+    use crate::render_graph::test_helpers::{
+        setup_engine_for_render_graph, default_color_ops, make_recording_pass,
+    };
+
+    let env = setup_engine_for_render_graph();
+    Engine::create_scene_manager().unwrap();
+    Engine::create_render_graph_manager().unwrap();
+    Engine::scene_manager().unwrap().lock().unwrap().create_scene("level").unwrap();
+    {
+        let rgm_arc = Engine::render_graph_manager().unwrap();
+        let mut rgm = rgm_arc.lock().unwrap();
+        let color = rgm.create_graph_resource("hdr", GraphResource::Texture {
+            texture_key: env.color_texture, base_mip_level: 0, base_array_layer: 0, layer_count: 1,
+        }).unwrap();
+        let (action, _) = make_recording_pass();
+        let pass = rgm.create_render_pass("opaque", vec![ResourceAccess {
+            graph_resource_key: color,
+            access_type: AccessType::ColorAttachmentWrite,
+            target_ops: Some(default_color_ops()),
+        }], action).unwrap();
+        let graph = rgm.create_render_graph("frame", 1).unwrap();
+        rgm.create_render_graph("idle", 1).unwrap();
+        rgm.execute_render_graph(graph, &[pass], |_cmd| Ok(())).unwrap();
+    }
+    Engine::log(LogSeverity::Error, "galaxy3d::test", "swapchain lost".to_string());
+
+    let report = Engine::frame_state_report().unwrap();
+    assert!(report.contains("level: 0 instances"));
+    assert!(report.contains("frame: opaque"));
+    assert!(report.contains("idle: not executed"));
+    assert!(report.contains("ColorAttachmentWrite 'hdr' -> texture 'color' mip 0 layers 0..1"));
+    assert!(report.contains("action: RecordingPassAction"));
+    assert!(report.contains("galaxy3d::test: swapchain lost"));
+
+    let path = std::env::temp_dir()
+        .join(format!("galaxy3d_frame_state_{}.txt", std::process::id()));
+    Engine::dump_frame_state(&path).unwrap();
+    let written = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert!(written.contains("frame: opaque"));
+}
+
+#[test]
+#[serial]
+fn test_frame_state_report_without_managers() {
+    setup();
+    let report = Engine::frame_state_report().unwrap();
+    assert!(report.contains("ResourceManager not created"));
+    assert!(report.contains("SceneManager not created"));
+    assert!(report.contains("RenderGraphManager not created"));
+}
+
+// ============================================================================
+// VIEW TARGET MANAGER TESTS
+// ============================================================================
+
+#[test]
+#[serial]
+fn test_view_target_manager_create_get_destroy() {
+    setup();
+    assert!(Engine::view_target_manager().is_err());
+
+    Engine::create_view_target_manager().unwrap();
+    assert!(Engine::create_view_target_manager().is_err());
+    let vtm = Engine::view_target_manager().unwrap();
+    assert_eq!(vtm.lock().unwrap().name(), Engine::VIEW_TARGET_MANAGER_NAME);
+    drop(vtm);
+
+    Engine::destroy_view_target_manager().unwrap();
+    assert!(Engine::view_target_manager().is_err());
+}
+
+#[test]
+#[serial]
+fn test_shutdown_destroys_view_target_manager() {
+    setup();
+    Engine::create_view_target_manager().unwrap();
+    Engine::shutdown();
+    assert!(Engine::view_target_manager().is_err());
+}
+
+// ============================================================================
+// FRAME API TESTS
+// ============================================================================
+
+#[test]
+#[serial]
+fn test_begin_end_frame_pairing_and_index() {
+    setup();
+    Engine::create_graphics_device("main", MockGraphicsDevice::new()).unwrap();
+    Engine::create_resource_manager().unwrap();
+
+    assert!(Engine::end_frame().is_err());
+    assert_eq!(Engine::begin_frame().unwrap(), 0);
+    assert!(Engine::begin_frame().is_err());
+
+    let summary = Engine::end_frame().unwrap();
+    assert_eq!(summary.frame_index, 0);
+    assert_eq!(summary.device_stats.len(), 1);
+    assert_eq!(summary.device_stats[0].0, "main");
+    assert_eq!(Engine::frame_count(), 1);
+
+    assert_eq!(Engine::begin_frame().unwrap(), 1);
+    assert_eq!(Engine::end_frame().unwrap().frame_index, 1);
+}
+
+#[test]
+#[serial]
+fn test_begin_frame_resets_transient_pools() {
+    setup();
+    let device = MockGraphicsDevice::new();
+    let groups = device.transient_binding_groups.clone();
+    let resets = device.transient_pool_resets.clone();
+    Engine::create_graphics_device("main", device).unwrap();
+
+    *groups.lock().unwrap() = 3;
+    Engine::begin_frame().unwrap();
+    assert_eq!(*resets.lock().unwrap(), 1);
+    *groups.lock().unwrap() = 2;
+    let summary = Engine::end_frame().unwrap();
+    assert_eq!(summary.device_stats[0].1.transient_descriptor_sets, 2);
+
+    Engine::begin_frame().unwrap();
+    assert_eq!(*groups.lock().unwrap(), 0);
+    Engine::end_frame().unwrap();
+}
+
+#[test]
+#[serial]
+fn test_begin_frame_without_subsystems() {
+    setup();
+    assert_eq!(Engine::begin_frame().unwrap(), 0);
+    assert!(Engine::end_frame().unwrap().device_stats.is_empty());
+}
+
+#[test]
+#[serial]
+fn test_shutdown_restarts_frame_count() {
+    setup();
+    Engine::begin_frame().unwrap();
+    Engine::end_frame().unwrap();
+    Engine::begin_frame().unwrap();
+    Engine::shutdown();
+
+    assert_eq!(Engine::frame_count(), 0);
+    assert_eq!(Engine::begin_frame().unwrap(), 0);
+    Engine::end_frame().unwrap();
+}
+
+// ============================================================================
+// LOG FILTER TESTS
+// ============================================================================
+
+#[test]
+#[serial]
+fn test_log_filter_gates_logger_but_not_recent_errors() {
+    setup();
+    let test_logger = TestLogger::new();
+    let entries_ref = test_logger.entries.clone();
+    Engine::set_logger(test_logger);
+
+    Engine::set_log_filter(crate::galaxy3d::log::LogFilter::parse("info,galaxy3d::noisy=off").unwrap());
+    Engine::set_module_log_level("galaxy3d::chatty", Some(LogSeverity::Trace));
+    assert!(!Engine::is_log_enabled(LogSeverity::Debug, "galaxy3d::Engine"));
+    assert!(Engine::is_log_enabled(LogSeverity::Trace, "galaxy3d::chatty::Sub"));
+
+    Engine::log(LogSeverity::Debug, "galaxy3d::Engine", "hidden".to_string());
+    Engine::log(LogSeverity::Error, "galaxy3d::noisy", "silenced".to_string());
+    Engine::log(LogSeverity::Trace, "galaxy3d::chatty", "shown".to_string());
+    {
+        let entries = entries_ref.lock().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].contains("shown"));
+    }
+    assert!(Engine::recent_errors().iter().any(|e| e.message == "silenced"));
+    assert_eq!(Engine::log_filter().level_for("galaxy3d::noisy"), None);
+
+    Engine::reset_logger();
+    Engine::reset_for_testing();
+    assert_eq!(Engine::log_filter(), crate::galaxy3d::log::LogFilter::default());
+}
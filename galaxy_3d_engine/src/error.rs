@@ -2,6 +2,15 @@
 //!
 //! This module defines the error types used throughout the engine,
 //! including rendering, initialization, and resource management.
+//!
+//! Host applications can branch on failures without string matching:
+//! - match the structured variants (`ResourceNotFound`, `Incompatible`,
+//!   `Backend`, ...);
+//! - or compare `Error::code()`, a stable `ErrorCode`.
+//!
+//! `ResultExt::context()` wraps an error with higher-level context. The
+//! wrapped error stays reachable through `std::error::Error::source()` and
+//! `Error::root_cause()`; `{:#}` formats the whole chain.
 
 use std::fmt;
 
@@ -22,20 +31,158 @@ pub enum Error {
 
     /// Initialization failed (engine, graphics_device, subsystems)
     InitializationFailed(String),
+
+    /// A resource looked up by key or name does not exist
+    ResourceNotFound {
+        /// Resource type (e.g. "Texture", "Geometry")
+        kind: &'static str,
+        /// Name or key of the missing resource
+        name: String,
+    },
+
+    /// A value does not match what it is combined with (formats, layouts, ...)
+    Incompatible {
+        expected: String,
+        found: String,
+    },
+
+    /// A backend API call failed with a native result code (e.g. a `VkResult`)
+    Backend {
+        code: i32,
+        message: String,
+    },
+
+    /// An error wrapped with higher-level context (see `ResultExt`)
+    Context {
+        context: String,
+        source: Box<Error>,
+    },
+}
+
+/// Stable error codes, one per `Error` variant.
+///
+/// The numeric values never change between releases; new variants get new
+/// values. A `Context` error reports the code of its root cause.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum ErrorCode {
+    BackendError = 1,
+    OutOfMemory = 2,
+    InvalidResource = 3,
+    InitializationFailed = 4,
+    ResourceNotFound = 5,
+    Incompatible = 6,
+    Backend = 7,
+}
+
+impl ErrorCode {
+    /// Numeric value of the code
+    pub fn as_u32(self) -> u32 {
+        self as u32
+    }
+
+    /// Stable identifier of the code (e.g. "GALAXY3D_RESOURCE_NOT_FOUND")
+    pub fn name(self) -> &'static str {
+        match self {
+            ErrorCode::BackendError => "GALAXY3D_BACKEND_ERROR",
+            ErrorCode::OutOfMemory => "GALAXY3D_OUT_OF_MEMORY",
+            ErrorCode::InvalidResource => "GALAXY3D_INVALID_RESOURCE",
+            ErrorCode::InitializationFailed => "GALAXY3D_INITIALIZATION_FAILED",
+            ErrorCode::ResourceNotFound => "GALAXY3D_RESOURCE_NOT_FOUND",
+            ErrorCode::Incompatible => "GALAXY3D_INCOMPATIBLE",
+            ErrorCode::Backend => "GALAXY3D_BACKEND",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Error {
+    /// Stable code of this error (of the root cause for `Context`)
+    pub fn code(&self) -> ErrorCode {
+        match self.root_cause() {
+            Error::BackendError(_) => ErrorCode::BackendError,
+            Error::OutOfMemory => ErrorCode::OutOfMemory,
+            Error::InvalidResource(_) => ErrorCode::InvalidResource,
+            Error::InitializationFailed(_) => ErrorCode::InitializationFailed,
+            Error::ResourceNotFound { .. } => ErrorCode::ResourceNotFound,
+            Error::Incompatible { .. } => ErrorCode::Incompatible,
+            Error::Backend { .. } => ErrorCode::Backend,
+            Error::Context { .. } => unreachable!("root_cause() never returns a Context"),
+        }
+    }
+
+    /// Wrap this error with higher-level context
+    pub fn context(self, context: impl Into<String>) -> Self {
+        Error::Context { context: context.into(), source: Box::new(self) }
+    }
+
+    /// Innermost error of a context chain (self when not a `Context`)
+    pub fn root_cause(&self) -> &Error {
+        let mut error = self;
+        while let Error::Context { source, .. } = error {
+            error = source;
+        }
+        error
+    }
 }
 
 impl fmt::Display for Error {
+    /// `{}` shows this error only; `{:#}` appends the whole source chain
+    /// (`context: cause: root cause`).
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Error::BackendError(msg) => write!(f, "Backend error: {}", msg),
             Error::OutOfMemory => write!(f, "Out of GPU memory"),
             Error::InvalidResource(msg) => write!(f, "Invalid resource: {}", msg),
             Error::InitializationFailed(msg) => write!(f, "Initialization failed: {}", msg),
+            Error::ResourceNotFound { kind, name } => write!(f, "{} '{}' not found", kind, name),
+            Error::Incompatible { expected, found } => {
+                write!(f, "Incompatible: expected {}, found {}", expected, found)
+            }
+            Error::Backend { code, message } => write!(f, "Backend error {}: {}", code, message),
+            Error::Context { context, source } => {
+                if f.alternate() {
+                    write!(f, "{}: {:#}", context, source)
+                } else {
+                    write!(f, "{}", context)
+                }
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Context { source, .. } => Some(source.as_ref()),
+            _ => None,
         }
     }
 }
 
-impl std::error::Error for Error {}
+/// Context helpers on `Result`
+pub trait ResultExt<T> {
+    /// Wrap the error with context
+    fn context(self, context: impl Into<String>) -> Result<T>;
+
+    /// Wrap the error with context built only on failure
+    fn with_context<C: Into<String>>(self, context: impl FnOnce() -> C) -> Result<T>;
+}
+
+impl<T> ResultExt<T> for Result<T> {
+    fn context(self, context: impl Into<String>) -> Result<T> {
+        self.map_err(|e| e.context(context))
+    }
+
+    fn with_context<C: Into<String>>(self, context: impl FnOnce() -> C) -> Result<T> {
+        self.map_err(|e| e.context(context()))
+    }
+}
 
 #[cfg(test)]
 #[path = "error_tests.rs"]
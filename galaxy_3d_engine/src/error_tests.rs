@@ -2,7 +2,7 @@
 //!
 //! Tests all Error variants and their implementations (Display, Debug, Clone, std::error::Error).
 
-use crate::error::{Error, Result};
+use crate::error::{Error, ErrorCode, Result, ResultExt};
 
 // ============================================================================
 // ERROR DISPLAY TESTS
@@ -172,3 +172,64 @@ fn test_error_message_content() {
     let err3 = Error::InitializationFailed("Failed to load vulkan-1.dll".to_string());
     assert!(format!("{}", err3).contains("vulkan-1.dll"));
 }
+
+// ============================================================================
+// STRUCTURED VARIANTS AND ERROR CODES
+// ============================================================================
+
+#[test]
+fn test_structured_variants_display() {
+    let not_found = Error::ResourceNotFound { kind: "Texture", name: "albedo".to_string() };
+    assert_eq!(not_found.to_string(), "Texture 'albedo' not found");
+
+    let incompatible = Error::Incompatible { expected: "R32_UINT".to_string(), found: "RGBA8_UNORM".to_string() };
+    assert_eq!(incompatible.to_string(), "Incompatible: expected R32_UINT, found RGBA8_UNORM");
+
+    let backend = Error::Backend { code: -2, message: "vkAllocateMemory failed".to_string() };
+    assert_eq!(backend.to_string(), "Backend error -2: vkAllocateMemory failed");
+}
+
+#[test]
+fn test_error_codes_are_stable() {
+    let cases = [
+        (Error::BackendError(String::new()), ErrorCode::BackendError, 1),
+        (Error::OutOfMemory, ErrorCode::OutOfMemory, 2),
+        (Error::InvalidResource(String::new()), ErrorCode::InvalidResource, 3),
+        (Error::InitializationFailed(String::new()), ErrorCode::InitializationFailed, 4),
+        (Error::ResourceNotFound { kind: "Mesh", name: String::new() }, ErrorCode::ResourceNotFound, 5),
+        (Error::Incompatible { expected: String::new(), found: String::new() }, ErrorCode::Incompatible, 6),
+        (Error::Backend { code: -4, message: String::new() }, ErrorCode::Backend, 7),
+    ];
+    for (err, code, value) in cases {
+        assert_eq!(err.code(), code);
+        assert_eq!(code.as_u32(), value);
+    }
+    assert_eq!(ErrorCode::ResourceNotFound.to_string(), "GALAXY3D_RESOURCE_NOT_FOUND");
+}
+
+// ============================================================================
+// CONTEXT CHAINS
+// ============================================================================
+
+#[test]
+fn test_context_chain() {
+    use std::error::Error as _;
+
+    fn load() -> Result<()> {
+        Err(Error::ResourceNotFound { kind: "Geometry", name: "hull".to_string() })
+    }
+    let err = load()
+        .context("Creating mesh 'ship'")
+        .with_context(|| format!("Loading scene '{}'", "harbor"))
+        .unwrap_err();
+
+    assert_eq!(err.to_string(), "Loading scene 'harbor'");
+    assert_eq!(format!("{:#}", err), "Loading scene 'harbor': Creating mesh 'ship': Geometry 'hull' not found");
+    assert_eq!(err.code(), ErrorCode::ResourceNotFound);
+    assert!(matches!(err.root_cause(), Error::ResourceNotFound { kind: "Geometry", .. }));
+
+    let middle = err.source().unwrap();
+    assert_eq!(middle.to_string(), "Creating mesh 'ship'");
+    assert_eq!(middle.source().unwrap().to_string(), "Geometry 'hull' not found");
+    assert!(middle.source().unwrap().source().is_none());
+}
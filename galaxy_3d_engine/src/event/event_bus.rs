@@ -0,0 +1,76 @@
+/// Multi-producer, multi-subscriber event bus.
+///
+/// Every subscriber owns an unbounded channel. `publish()` clones the event
+/// into each of them; subscribers whose `EventSubscriber` was dropped are
+/// removed on the next publish. A cloned `EventBus` shares the subscriber
+/// list, so producers keep their own handle.
+
+use std::sync::{mpsc, Arc, Mutex};
+
+/// Cloneable handle to an event bus
+pub struct EventBus<E> {
+    subscribers: Arc<Mutex<Vec<mpsc::Sender<E>>>>,
+}
+
+/// Receiving end of one subscription
+pub struct EventSubscriber<E> {
+    receiver: mpsc::Receiver<E>,
+}
+
+impl<E> Clone for EventBus<E> {
+    fn clone(&self) -> Self {
+        Self { subscribers: Arc::clone(&self.subscribers) }
+    }
+}
+
+impl<E: Clone + Send> Default for EventBus<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Clone + Send> EventBus<E> {
+    /// Create a bus without subscribers
+    pub fn new() -> Self {
+        Self { subscribers: Arc::new(Mutex::new(Vec::new())) }
+    }
+
+    /// Subscribe: every event published from now on is queued for the
+    /// returned subscriber
+    pub fn subscribe(&self) -> EventSubscriber<E> {
+        let (sender, receiver) = mpsc::channel();
+        if let Ok(mut subscribers) = self.subscribers.lock() {
+            subscribers.push(sender);
+        }
+        EventSubscriber { receiver }
+    }
+
+    /// Queue `event` for every live subscriber. Without subscribers the
+    /// event is dropped.
+    pub fn publish(&self, event: E) {
+        if let Ok(mut subscribers) = self.subscribers.lock() {
+            subscribers.retain(|sender| sender.send(event.clone()).is_ok());
+        }
+    }
+
+    /// Number of subscribers (dropped ones count until the next publish)
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.lock().map_or(0, |subscribers| subscribers.len())
+    }
+}
+
+impl<E> EventSubscriber<E> {
+    /// Next queued event, if any
+    pub fn try_next(&self) -> Option<E> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// Take every queued event, oldest first
+    pub fn drain(&self) -> Vec<E> {
+        self.receiver.try_iter().collect()
+    }
+}
+
+#[cfg(test)]
+#[path = "event_bus_tests.rs"]
+mod tests;
@@ -0,0 +1,56 @@
+use super::*;
+use std::thread;
+
+// ============================================================================
+// EventBus
+// ============================================================================
+
+#[test]
+fn test_publish_without_subscribers_is_dropped() {
+    let bus = EventBus::<u32>::new();
+    bus.publish(1);
+    let subscriber = bus.subscribe();
+    assert!(subscriber.try_next().is_none());
+}
+
+#[test]
+fn test_every_subscriber_receives_every_event_in_order() {
+    let bus = EventBus::new();
+    let a = bus.subscribe();
+    let b = bus.subscribe();
+    bus.publish(1);
+    bus.publish(2);
+    assert_eq!(a.drain(), vec![1, 2]);
+    assert_eq!(b.try_next(), Some(1));
+    assert_eq!(b.try_next(), Some(2));
+    assert_eq!(b.try_next(), None);
+}
+
+#[test]
+fn test_dropped_subscriber_is_removed_on_publish() {
+    let bus = EventBus::new();
+    let kept = bus.subscribe();
+    drop(bus.subscribe());
+    assert_eq!(bus.subscriber_count(), 2);
+    bus.publish(7);
+    assert_eq!(bus.subscriber_count(), 1);
+    assert_eq!(kept.drain(), vec![7]);
+}
+
+#[test]
+fn test_clones_share_subscribers_across_threads() {
+    let bus = EventBus::new();
+    let subscriber = bus.subscribe();
+    let producers: Vec<_> = (0..4)
+        .map(|i| {
+            let bus = bus.clone();
+            thread::spawn(move || bus.publish(i))
+        })
+        .collect();
+    for producer in producers {
+        producer.join().unwrap();
+    }
+    let mut events = subscriber.drain();
+    events.sort();
+    assert_eq!(events, vec![0, 1, 2, 3]);
+}
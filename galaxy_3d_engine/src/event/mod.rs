@@ -0,0 +1,17 @@
+//! Engine event bus.
+//!
+//! `EventBus` fans out published events to every subscriber through
+//! channels: producers publish from any thread, each subscriber drains its
+//! own queue when it wants (typically once per frame on the main thread),
+//! so listeners never run on worker threads.
+//!
+//! `ProgressEvent` reports background work (queued / started / completed /
+//! failed / cancelled, with durations) on the engine-wide `ProgressBus`
+//! returned by `Engine::progress_bus()`. `LoadProgress` sums those events
+//! into the fraction a loading screen shows.
+
+mod event_bus;
+mod progress;
+
+pub use event_bus::{EventBus, EventSubscriber};
+pub use progress::{LoadProgress, ProgressBus, ProgressEvent, ProgressState};
@@ -0,0 +1,134 @@
+/// Progress events of background work.
+///
+/// Producers publish one `ProgressEvent` per state change of a job on a
+/// `ProgressBus` (the engine-wide one is `Engine::progress_bus()`).
+/// A job is identified by its source (the producer, named like log sources,
+/// e.g. `"galaxy3d::StreamingManager"`) and its name: re-requesting a name
+/// after a failure or a cancellation starts it over.
+
+use std::time::Duration;
+use rustc_hash::FxHashMap;
+use super::event_bus::{EventBus, EventSubscriber};
+
+/// Event bus of background work progress
+pub type ProgressBus = EventBus<ProgressEvent>;
+
+/// State change of a job
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProgressState {
+    /// Accepted, waiting for a worker thread
+    Queued,
+    /// Taken by a worker thread after `waited` in the queue
+    Started { waited: Duration },
+    /// Done. `work` is the time spent on the worker thread, `total` the
+    /// time since the job was queued.
+    Completed { work: Duration, total: Duration },
+    /// Failed on the worker thread or at upload
+    Failed { error: String, total: Duration },
+    /// Removed before completion
+    Cancelled,
+}
+
+/// Progress event of one job
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgressEvent {
+    pub source: &'static str,
+    pub name: String,
+    pub state: ProgressState,
+}
+
+/// Last known state of a tracked job
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum JobStatus {
+    Pending,
+    Completed,
+    Failed,
+}
+
+/// Sums progress events into loading screen counters.
+///
+/// Feed it every event of a subscriber (`record_all()`); cancelled jobs
+/// stop counting, failed jobs count as finished.
+#[derive(Debug, Clone, Default)]
+pub struct LoadProgress {
+    jobs: FxHashMap<(&'static str, String), JobStatus>,
+}
+
+impl LoadProgress {
+    /// Create an empty tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply one event
+    pub fn record(&mut self, event: &ProgressEvent) {
+        let key = (event.source, event.name.clone());
+        match event.state {
+            ProgressState::Queued | ProgressState::Started { .. } => {
+                self.jobs.insert(key, JobStatus::Pending);
+            }
+            ProgressState::Completed { .. } => {
+                self.jobs.insert(key, JobStatus::Completed);
+            }
+            ProgressState::Failed { .. } => {
+                self.jobs.insert(key, JobStatus::Failed);
+            }
+            ProgressState::Cancelled => {
+                self.jobs.remove(&key);
+            }
+        }
+    }
+
+    /// Apply every event queued for `subscriber`
+    pub fn record_all(&mut self, subscriber: &EventSubscriber<ProgressEvent>) {
+        while let Some(event) = subscriber.try_next() {
+            self.record(&event);
+        }
+    }
+
+    /// Tracked jobs (cancelled ones excluded)
+    pub fn total(&self) -> usize {
+        self.jobs.len()
+    }
+
+    /// Jobs completed
+    pub fn completed(&self) -> usize {
+        self.count(JobStatus::Completed)
+    }
+
+    /// Jobs failed
+    pub fn failed(&self) -> usize {
+        self.count(JobStatus::Failed)
+    }
+
+    /// Jobs neither completed nor failed
+    pub fn pending(&self) -> usize {
+        self.count(JobStatus::Pending)
+    }
+
+    /// Finished jobs over tracked jobs, in [0, 1] (1 without jobs)
+    pub fn fraction(&self) -> f32 {
+        if self.jobs.is_empty() {
+            return 1.0;
+        }
+        (self.total() - self.pending()) as f32 / self.total() as f32
+    }
+
+    /// Every tracked job is finished
+    pub fn is_done(&self) -> bool {
+        self.pending() == 0
+    }
+
+    /// Forget every job (e.g. at the start of the next level load)
+    pub fn clear(&mut self) {
+        self.jobs.clear();
+    }
+
+    fn count(&self, status: JobStatus) -> usize {
+        self.jobs.values().filter(|s| **s == status).count()
+    }
+}
+
+#[cfg(test)]
+#[path = "progress_tests.rs"]
+mod tests;
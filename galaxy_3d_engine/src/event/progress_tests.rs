@@ -0,0 +1,91 @@
+use super::*;
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn event(name: &str, state: ProgressState) -> ProgressEvent {
+    ProgressEvent { source: "galaxy3d::Test", name: name.to_string(), state }
+}
+
+fn completed() -> ProgressState {
+    ProgressState::Completed { work: Duration::from_millis(2), total: Duration::from_millis(5) }
+}
+
+fn failed() -> ProgressState {
+    ProgressState::Failed { error: "bad".to_string(), total: Duration::from_millis(1) }
+}
+
+// ============================================================================
+// LoadProgress
+// ============================================================================
+
+#[test]
+fn test_empty_progress_is_done() {
+    let progress = LoadProgress::new();
+    assert_eq!(progress.total(), 0);
+    assert_eq!(progress.fraction(), 1.0);
+    assert!(progress.is_done());
+}
+
+#[test]
+fn test_fraction_counts_completed_and_failed_jobs() {
+    let mut progress = LoadProgress::new();
+    for name in ["a", "b", "c", "d"] {
+        progress.record(&event(name, ProgressState::Queued));
+    }
+    progress.record(&event("a", ProgressState::Started { waited: Duration::ZERO }));
+    progress.record(&event("a", completed()));
+    progress.record(&event("b", failed()));
+    assert_eq!(progress.total(), 4);
+    assert_eq!(progress.completed(), 1);
+    assert_eq!(progress.failed(), 1);
+    assert_eq!(progress.pending(), 2);
+    assert_eq!(progress.fraction(), 0.5);
+    assert!(!progress.is_done());
+}
+
+#[test]
+fn test_cancelled_job_stops_counting() {
+    let mut progress = LoadProgress::new();
+    progress.record(&event("a", ProgressState::Queued));
+    progress.record(&event("b", ProgressState::Queued));
+    progress.record(&event("b", ProgressState::Cancelled));
+    progress.record(&event("a", completed()));
+    assert_eq!(progress.total(), 1);
+    assert!(progress.is_done());
+}
+
+#[test]
+fn test_requeued_job_is_pending_again() {
+    let mut progress = LoadProgress::new();
+    progress.record(&event("a", ProgressState::Queued));
+    progress.record(&event("a", failed()));
+    progress.record(&event("a", ProgressState::Queued));
+    assert_eq!(progress.failed(), 0);
+    assert_eq!(progress.pending(), 1);
+}
+
+#[test]
+fn test_same_name_of_another_source_is_another_job() {
+    let mut progress = LoadProgress::new();
+    progress.record(&event("rock", ProgressState::Queued));
+    progress.record(&ProgressEvent {
+        source: "galaxy3d::Other",
+        name: "rock".to_string(),
+        state: ProgressState::Queued,
+    });
+    assert_eq!(progress.total(), 2);
+}
+
+#[test]
+fn test_record_all_drains_the_subscriber() {
+    let bus = ProgressBus::new();
+    let subscriber = bus.subscribe();
+    bus.publish(event("a", ProgressState::Queued));
+    bus.publish(event("a", completed()));
+    let mut progress = LoadProgress::new();
+    progress.record_all(&subscriber);
+    assert_eq!(progress.completed(), 1);
+    assert!(subscriber.try_next().is_none());
+}
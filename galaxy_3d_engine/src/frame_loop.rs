@@ -0,0 +1,350 @@
+//! Frame pacing and fixed-timestep update loop for Galaxy3D Engine
+//!
+//! `FrameLoop` measures the wall-clock time between frames and splits it
+//! into fixed simulation steps, carrying the remainder over to the next
+//! frame. The fraction of a step left over is the interpolation factor
+//! (`FrameTick::alpha`): set on the scene with `FrameTick::apply_to_scene`,
+//! it makes `DefaultUpdater::update_instances` draw every instance between
+//! its previous and current step transforms.
+//!
+//! ```ignore
+//! let mut frame_loop = FrameLoop::new(1.0 / 60.0)?;
+//! frame_loop.set_fps_cap(Some(144.0))?;
+//! loop {
+//!     let tick = frame_loop.begin_frame();
+//!     for _ in 0..tick.steps {
+//!         scene.begin_transform_step();
+//!         simulate(&mut scene, tick.step);
+//!     }
+//!     tick.apply_to_scene(&mut scene);
+//!     clock.advance(tick.frame_delta);
+//!     render(&mut scene, &mut updater)?;
+//!     frame_loop.end_frame(); // sleeps up to the FPS cap
+//!     hud_text = frame_loop.stats().to_string();
+//! }
+//! ```
+//!
+//! Safeguards:
+//! - a frame longer than `MAX_FRAME_DELTA` (breakpoint, window drag) only
+//!   accounts for `MAX_FRAME_DELTA`
+//! - at most `max_steps_per_frame()` steps run per frame; the time beyond
+//!   is dropped (`FrameTick::dropped_time`) so a slow simulation cannot
+//!   fall further behind every frame
+//!
+//! Frame times (begin to begin, sleep included) are kept for the last
+//! `history_len()` frames; `stats()` gives their average and percentiles.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::{Duration, Instant};
+use crate::error::Result;
+use crate::engine_bail;
+use crate::scene::{Scene, MAX_FRAME_DELTA};
+
+/// Simulation step of a 60 Hz update rate, in seconds
+pub const DEFAULT_FIXED_STEP: f32 = 1.0 / 60.0;
+
+/// Most fixed steps run in one frame
+pub const DEFAULT_MAX_STEPS_PER_FRAME: u32 = 8;
+
+/// Frames kept for `FrameLoop::stats()`
+pub const DEFAULT_FRAME_TIME_HISTORY: usize = 240;
+
+/// Time left before the FPS cap deadline under which `end_frame()` yields
+/// instead of sleeping (sleep granularity is about a millisecond)
+const SPIN_THRESHOLD: Duration = Duration::from_millis(1);
+
+/// Simulation work of one frame, returned by `FrameLoop::begin_frame()`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameTick {
+    /// Wall-clock time since the previous frame, clamped to
+    /// `MAX_FRAME_DELTA` (0 for the first frame), in seconds
+    pub frame_delta: f32,
+    /// Number of fixed steps to simulate this frame
+    pub steps: u32,
+    /// Duration of one step, in seconds
+    pub step: f32,
+    /// Fraction of a step elapsed after the last one, in [0, 1)
+    pub alpha: f32,
+    /// Time dropped because more than `max_steps_per_frame()` steps were
+    /// due, in seconds
+    pub dropped_time: f32,
+}
+
+impl FrameTick {
+    /// Set `alpha` as the transform interpolation of `scene`, read by the
+    /// updater when it composes the instance matrices
+    pub fn apply_to_scene(&self, scene: &mut Scene) {
+        scene.set_transform_interpolation(self.alpha);
+    }
+}
+
+/// Frame time statistics over the recent frames, in milliseconds
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FrameTimeStats {
+    /// Number of frames measured
+    pub frames: usize,
+    pub average_ms: f32,
+    pub min_ms: f32,
+    pub max_ms: f32,
+    /// Median frame time
+    pub p50_ms: f32,
+    pub p95_ms: f32,
+    /// 1% of the frames are slower than this (stutter)
+    pub p99_ms: f32,
+    /// Frames per second from `average_ms`
+    pub fps: f32,
+}
+
+impl FrameTimeStats {
+    /// Statistics of `frame_times_ms` (all zero when empty)
+    pub fn from_frame_times(frame_times_ms: &[f32]) -> Self {
+        if frame_times_ms.is_empty() {
+            return Self::default();
+        }
+        let mut sorted = frame_times_ms.to_vec();
+        sorted.sort_by(f32::total_cmp);
+        let average_ms = sorted.iter().sum::<f32>() / sorted.len() as f32;
+        Self {
+            frames: sorted.len(),
+            average_ms,
+            min_ms: sorted[0],
+            max_ms: sorted[sorted.len() - 1],
+            p50_ms: percentile(&sorted, 50.0),
+            p95_ms: percentile(&sorted, 95.0),
+            p99_ms: percentile(&sorted, 99.0),
+            fps: if average_ms > 0.0 { 1000.0 / average_ms } else { 0.0 },
+        }
+    }
+}
+
+impl fmt::Display for FrameTimeStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.1} fps | {:.2} ms avg | p50 {:.2} | p95 {:.2} | p99 {:.2} | max {:.2}",
+            self.fps, self.average_ms, self.p50_ms, self.p95_ms, self.p99_ms, self.max_ms)
+    }
+}
+
+/// Nearest-rank percentile of sorted, non-empty values
+fn percentile(sorted: &[f32], percent: f32) -> f32 {
+    let rank = (percent / 100.0 * sorted.len() as f32).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Fixed-timestep frame loop with an optional FPS cap (see module docs).
+pub struct FrameLoop {
+    fixed_step: f32,
+    max_steps_per_frame: u32,
+    /// Frames per second `end_frame()` waits for (None: uncapped)
+    fps_cap: Option<f32>,
+    /// Simulation time not consumed by a step yet
+    accumulator: f32,
+    /// Start of the current frame (None before the first one)
+    frame_start: Option<Instant>,
+    /// Recent frame times in milliseconds, oldest first
+    frame_times: VecDeque<f32>,
+    history_len: usize,
+    /// Frames begun since creation or `reset()`
+    frame_index: u64,
+}
+
+impl Default for FrameLoop {
+    fn default() -> Self {
+        Self {
+            fixed_step: DEFAULT_FIXED_STEP,
+            max_steps_per_frame: DEFAULT_MAX_STEPS_PER_FRAME,
+            fps_cap: None,
+            accumulator: 0.0,
+            frame_start: None,
+            frame_times: VecDeque::with_capacity(DEFAULT_FRAME_TIME_HISTORY),
+            history_len: DEFAULT_FRAME_TIME_HISTORY,
+            frame_index: 0,
+        }
+    }
+}
+
+impl FrameLoop {
+    /// Uncapped loop simulating steps of `fixed_step` seconds
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `fixed_step` is not finite and positive.
+    pub fn new(fixed_step: f32) -> Result<Self> {
+        let mut frame_loop = Self::default();
+        frame_loop.set_fixed_step(fixed_step)?;
+        Ok(frame_loop)
+    }
+
+    // ===== FRAME =====
+
+    /// Start a frame: measure the time since the previous one and return
+    /// the steps to simulate
+    pub fn begin_frame(&mut self) -> FrameTick {
+        let now = Instant::now();
+        match self.frame_start.replace(now) {
+            Some(previous) => self.advance(now.duration_since(previous).as_secs_f32()),
+            None => {
+                self.frame_index += 1;
+                self.split_steps(0.0)
+            }
+        }
+    }
+
+    /// Account for a frame of `wall_delta` seconds measured by the caller
+    /// (replay, tests) and return the steps to simulate. `begin_frame()`
+    /// calls it with the measured time.
+    pub fn advance(&mut self, wall_delta: f32) -> FrameTick {
+        let wall_delta = if wall_delta.is_finite() { wall_delta.max(0.0) } else { 0.0 };
+        if self.frame_times.len() == self.history_len {
+            self.frame_times.pop_front();
+        }
+        if self.history_len > 0 {
+            self.frame_times.push_back(wall_delta * 1000.0);
+        }
+        self.frame_index += 1;
+        self.split_steps(wall_delta.min(MAX_FRAME_DELTA))
+    }
+
+    /// Add `frame_delta` to the accumulator and take whole steps out of it
+    fn split_steps(&mut self, frame_delta: f32) -> FrameTick {
+        self.accumulator += frame_delta;
+        let due = (self.accumulator / self.fixed_step).floor();
+        let steps = (due as u32).min(self.max_steps_per_frame);
+        self.accumulator -= steps as f32 * self.fixed_step;
+        let mut dropped_time = 0.0;
+        if steps == self.max_steps_per_frame && self.accumulator >= self.fixed_step {
+            // Keep the fraction only: the simulation runs slower than real time
+            let kept = self.accumulator % self.fixed_step;
+            dropped_time = self.accumulator - kept;
+            self.accumulator = kept;
+        }
+        FrameTick {
+            frame_delta,
+            steps,
+            step: self.fixed_step,
+            alpha: (self.accumulator / self.fixed_step).clamp(0.0, 1.0 - f32::EPSILON),
+            dropped_time,
+        }
+    }
+
+    /// End the frame started by `begin_frame()`, waiting until the FPS
+    /// cap allows the next one. No-op when uncapped.
+    pub fn end_frame(&mut self) {
+        let Some(frame_start) = self.frame_start else { return };
+        let deadline = Instant::now() + self.cap_wait(frame_start.elapsed());
+        loop {
+            let now = Instant::now();
+            if now >= deadline {
+                break;
+            }
+            let remaining = deadline - now;
+            if remaining > SPIN_THRESHOLD {
+                std::thread::sleep(remaining - SPIN_THRESHOLD);
+            } else {
+                std::thread::yield_now();
+            }
+        }
+    }
+
+    /// Time to wait after a frame that took `elapsed` so that frames last
+    /// at least `1 / fps_cap` (zero when uncapped or already late)
+    pub fn cap_wait(&self, elapsed: Duration) -> Duration {
+        match self.fps_cap {
+            Some(fps) => Duration::from_secs_f32(1.0 / fps).saturating_sub(elapsed),
+            None => Duration::ZERO,
+        }
+    }
+
+    /// Forget the timing and the accumulated time (after loading, or when
+    /// the window was minimized). Settings are kept.
+    pub fn reset(&mut self) {
+        self.accumulator = 0.0;
+        self.frame_start = None;
+        self.frame_times.clear();
+        self.frame_index = 0;
+    }
+
+    // ===== READINGS =====
+
+    /// Frames begun since creation or `reset()`
+    pub fn frame_index(&self) -> u64 {
+        self.frame_index
+    }
+
+    /// Current interpolation factor (the last `FrameTick::alpha`)
+    pub fn alpha(&self) -> f32 {
+        (self.accumulator / self.fixed_step).clamp(0.0, 1.0 - f32::EPSILON)
+    }
+
+    /// Frame time statistics over the last `history_len()` frames
+    pub fn stats(&self) -> FrameTimeStats {
+        let frame_times: Vec<f32> = self.frame_times.iter().copied().collect();
+        FrameTimeStats::from_frame_times(&frame_times)
+    }
+
+    // ===== SETTINGS =====
+
+    pub fn fixed_step(&self) -> f32 {
+        self.fixed_step
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if `step` is not finite and positive.
+    pub fn set_fixed_step(&mut self, step: f32) -> Result<()> {
+        if !(step.is_finite() && step > 0.0) {
+            engine_bail!("galaxy3d::FrameLoop", "Fixed step must be positive, got {}", step);
+        }
+        self.fixed_step = step;
+        Ok(())
+    }
+
+    pub fn max_steps_per_frame(&self) -> u32 {
+        self.max_steps_per_frame
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if `max_steps` is 0.
+    pub fn set_max_steps_per_frame(&mut self, max_steps: u32) -> Result<()> {
+        if max_steps == 0 {
+            engine_bail!("galaxy3d::FrameLoop", "At least one step per frame is needed");
+        }
+        self.max_steps_per_frame = max_steps;
+        Ok(())
+    }
+
+    pub fn fps_cap(&self) -> Option<f32> {
+        self.fps_cap
+    }
+
+    /// Frames per second `end_frame()` limits the loop to (None:
+    /// uncapped)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `fps` is not finite and positive.
+    pub fn set_fps_cap(&mut self, fps: Option<f32>) -> Result<()> {
+        if let Some(fps) = fps.filter(|fps| !(fps.is_finite() && *fps > 0.0)) {
+            engine_bail!("galaxy3d::FrameLoop", "FPS cap must be positive, got {}", fps);
+        }
+        self.fps_cap = fps;
+        Ok(())
+    }
+
+    pub fn history_len(&self) -> usize {
+        self.history_len
+    }
+
+    /// Number of frames `stats()` covers (oldest frames are dropped)
+    pub fn set_history_len(&mut self, frames: usize) {
+        self.history_len = frames;
+        while self.frame_times.len() > frames {
+            self.frame_times.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "frame_loop_tests.rs"]
+mod tests;
@@ -0,0 +1,143 @@
+use super::*;
+
+/// Step used by the tests: 0.25 s is exact in binary floating point
+const STEP: f32 = 0.25;
+
+fn frame_loop() -> FrameLoop {
+    FrameLoop::new(STEP).unwrap()
+}
+
+// ============================================================================
+// Fixed-step accumulation
+// ============================================================================
+
+#[test]
+fn test_advance_splits_frame_into_steps_with_alpha() {
+    let mut frame_loop = frame_loop();
+    let tick = frame_loop.advance(0.125);
+    assert_eq!(tick.steps, 0);
+    assert_eq!(tick.alpha, 0.5);
+
+    let tick = frame_loop.advance(0.1875);
+    assert_eq!(tick.steps, 1);
+    assert_eq!(tick.step, STEP);
+    assert_eq!(tick.alpha, 0.25);
+    assert_eq!(frame_loop.alpha(), 0.25);
+    assert_eq!(frame_loop.frame_index(), 2);
+}
+
+#[test]
+fn test_advance_clamps_long_frames() {
+    let mut frame_loop = FrameLoop::new(MAX_FRAME_DELTA / 4.0).unwrap();
+    let tick = frame_loop.advance(10.0);
+    assert_eq!(tick.frame_delta, MAX_FRAME_DELTA);
+    assert_eq!(tick.steps, 4);
+    assert_eq!(tick.dropped_time, 0.0);
+    // Invalid deltas count as no time
+    assert_eq!(frame_loop.advance(f32::NAN).steps, 0);
+    assert_eq!(frame_loop.advance(-1.0).frame_delta, 0.0);
+}
+
+#[test]
+fn test_advance_drops_time_beyond_max_steps() {
+    let mut frame_loop = FrameLoop::new(0.015625).unwrap();
+    frame_loop.set_max_steps_per_frame(2).unwrap();
+    let tick = frame_loop.advance(0.0625);
+    assert_eq!(tick.steps, 2);
+    assert_eq!(tick.dropped_time, 0.03125);
+    assert_eq!(tick.alpha, 0.0);
+}
+
+#[test]
+fn test_first_begin_frame_has_no_time() {
+    let mut frame_loop = frame_loop();
+    let tick = frame_loop.begin_frame();
+    assert_eq!(tick.frame_delta, 0.0);
+    assert_eq!(tick.steps, 0);
+    assert_eq!(frame_loop.stats().frames, 0);
+    frame_loop.begin_frame();
+    assert_eq!(frame_loop.stats().frames, 1);
+}
+
+#[test]
+fn test_reset_forgets_time_and_history() {
+    let mut frame_loop = frame_loop();
+    frame_loop.advance(0.125);
+    frame_loop.reset();
+    assert_eq!(frame_loop.alpha(), 0.0);
+    assert_eq!(frame_loop.frame_index(), 0);
+    assert_eq!(frame_loop.stats().frames, 0);
+}
+
+#[test]
+fn test_apply_to_scene_sets_interpolation() {
+    let mut scene = Scene::new();
+    frame_loop().advance(0.125).apply_to_scene(&mut scene);
+    assert_eq!(scene.transform_interpolation(), 0.5);
+}
+
+// ============================================================================
+// Settings
+// ============================================================================
+
+#[test]
+fn test_settings_reject_invalid_values() {
+    assert!(FrameLoop::new(0.0).is_err());
+    assert!(FrameLoop::new(f32::INFINITY).is_err());
+    let mut frame_loop = frame_loop();
+    assert!(frame_loop.set_max_steps_per_frame(0).is_err());
+    assert!(frame_loop.set_fps_cap(Some(0.0)).is_err());
+    assert!(frame_loop.set_fps_cap(Some(f32::NAN)).is_err());
+    assert!(frame_loop.set_fps_cap(None).is_ok());
+}
+
+#[test]
+fn test_cap_wait_fills_frame_to_cap() {
+    let mut frame_loop = frame_loop();
+    assert_eq!(frame_loop.cap_wait(Duration::ZERO), Duration::ZERO);
+    frame_loop.set_fps_cap(Some(4.0)).unwrap();
+    assert_eq!(frame_loop.cap_wait(Duration::from_millis(100)), Duration::from_millis(150));
+    assert_eq!(frame_loop.cap_wait(Duration::from_millis(400)), Duration::ZERO);
+}
+
+#[test]
+fn test_end_frame_waits_for_cap() {
+    let mut frame_loop = frame_loop();
+    frame_loop.set_fps_cap(Some(100.0)).unwrap();
+    frame_loop.begin_frame();
+    let start = Instant::now();
+    frame_loop.end_frame();
+    assert!(start.elapsed() >= Duration::from_millis(5));
+}
+
+// ============================================================================
+// Frame time statistics
+// ============================================================================
+
+#[test]
+fn test_stats_percentiles() {
+    let times: Vec<f32> = (1..=100).map(|ms| ms as f32).collect();
+    let stats = FrameTimeStats::from_frame_times(&times);
+    assert_eq!(stats.frames, 100);
+    assert_eq!(stats.min_ms, 1.0);
+    assert_eq!(stats.max_ms, 100.0);
+    assert_eq!(stats.p50_ms, 50.0);
+    assert_eq!(stats.p95_ms, 95.0);
+    assert_eq!(stats.p99_ms, 99.0);
+    assert_eq!(stats.average_ms, 50.5);
+    assert_eq!(FrameTimeStats::from_frame_times(&[]), FrameTimeStats::default());
+}
+
+#[test]
+fn test_stats_keep_history_len_frames() {
+    let mut frame_loop = frame_loop();
+    frame_loop.set_history_len(2);
+    frame_loop.advance(0.010);
+    frame_loop.advance(0.020);
+    frame_loop.advance(0.030);
+    let stats = frame_loop.stats();
+    assert_eq!(stats.frames, 2);
+    assert_eq!(stats.min_ms, 20.0);
+    assert!((stats.fps - 40.0).abs() < 1e-3);
+    assert!(stats.to_string().starts_with("40.0 fps"));
+}
@@ -35,8 +35,11 @@ pub enum AccessType {
     TransferRead,
     /// Transfer destination (copy, blit)
     TransferWrite,
-    /// Ray tracing acceleration structure read
+    /// Ray tracing shader read (acceleration structure, storage buffer /
+    /// image, sampled texture)
     RayTracingRead,
+    /// Ray tracing shader write (storage buffer / image)
+    RayTracingWrite,
 }
 
 impl AccessType {
@@ -48,6 +51,7 @@ impl AccessType {
                 | Self::DepthStencilWrite
                 | Self::ComputeWrite
                 | Self::TransferWrite
+                | Self::RayTracingWrite
         )
     }
 
@@ -71,7 +75,9 @@ impl AccessType {
 ///
 /// `previous_access_type` is precalculated by the render graph during
 /// `compile()` — the backend uses it to determine the source layout
-/// and pipeline stage for barrier emission.
+/// and pipeline stage for barrier emission, until the command list has
+/// accessed the texture itself: from then on, the command list's tracked
+/// state wins (`Config::validate_barriers` reports disagreements).
 pub struct ImageAccess {
     /// The GPU texture being accessed
     pub texture: Arc<dyn Texture>,
@@ -94,7 +100,8 @@ pub struct BufferAccess {
     pub buffer: Arc<dyn Buffer>,
     /// How this buffer is accessed in the pass
     pub access_type: AccessType,
-    /// How this buffer was accessed previously (None = first use)
+    /// How this buffer was accessed previously (None = first use), used
+    /// like `ImageAccess::previous_access_type`
     pub previous_access_type: Option<AccessType>,
 }
 
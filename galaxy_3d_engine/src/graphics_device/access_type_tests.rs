@@ -55,6 +55,12 @@ fn test_is_write_ray_tracing_read() {
     assert!(!AccessType::RayTracingRead.is_write());
 }
 
+#[test]
+fn test_is_write_ray_tracing_write() {
+    assert!(AccessType::RayTracingWrite.is_write());
+    assert!(!AccessType::RayTracingWrite.is_attachment());
+}
+
 #[test]
 fn test_is_attachment_color_write() {
     assert!(AccessType::ColorAttachmentWrite.is_attachment());
@@ -8,7 +8,11 @@
 /// - Layout deduced from the Pipeline (user never manipulates layouts directly)
 /// - Pool managed internally by the graphics_device
 
-use crate::graphics_device::{Texture, Buffer, SamplerType, ShaderStage};
+use crate::error::Result;
+use crate::engine_bail;
+use crate::graphics_device::{
+    Texture, TextureUsage, Buffer, SamplerType, SamplerDesc, ShaderStage, AccelerationStructure,
+};
 
 // ============================================================================
 // Binding types and layout description
@@ -19,10 +23,23 @@ use crate::graphics_device::{Texture, Buffer, SamplerType, ShaderStage};
 pub enum BindingType {
     /// Uniform buffer (read-only structured data)
     UniformBuffer,
+    /// Uniform buffer bound with a dynamic offset
+    /// (`CommandList::bind_binding_group_dynamic`), declared by the pipeline
+    /// in `PipelineDesc::dynamic_uniform_buffers`
+    UniformBufferDynamic,
     /// Combined image sampler (texture + sampler in one binding)
     CombinedImageSampler,
+    /// Depth texture with a comparison sampler (`sampler2DShadow`, hardware
+    /// PCF). Its resource must be a depth-format texture and a sampler with
+    /// `SamplerDesc::compare` set.
+    SampledDepthTexture,
     /// Storage buffer (read/write for compute shaders)
     StorageBuffer,
+    /// Storage image (`image2D`, read/write for compute and ray tracing
+    /// shaders): one mip level of a `TextureUsage::Storage` texture
+    StorageImage,
+    /// Top-level acceleration structure (ray tracing)
+    AccelerationStructure,
 }
 
 /// Shader stage visibility flags
@@ -33,8 +50,18 @@ impl ShaderStageFlags {
     pub const VERTEX: Self = Self(0x01);
     pub const FRAGMENT: Self = Self(0x02);
     pub const COMPUTE: Self = Self(0x04);
+    pub const TASK: Self = Self(0x08);
+    pub const MESH: Self = Self(0x10);
     pub const VERTEX_FRAGMENT: Self = Self(0x03);
-    pub const ALL: Self = Self(0x07);
+    /// Stages of a mesh shader pipeline
+    pub const TASK_MESH_FRAGMENT: Self = Self(0x1A);
+    pub const RAYGEN: Self = Self(0x20);
+    pub const MISS: Self = Self(0x40);
+    pub const CLOSEST_HIT: Self = Self(0x80);
+    pub const ANY_HIT: Self = Self(0x100);
+    /// Stages of a ray tracing pipeline
+    pub const RAY_TRACING: Self = Self(0x1E0);
+    pub const ALL: Self = Self(0x1FF);
 
     /// Create from a slice of ShaderStage
     pub fn from_stages(stages: &[ShaderStage]) -> Self {
@@ -44,6 +71,12 @@ impl ShaderStageFlags {
                 ShaderStage::Vertex => 0x01,
                 ShaderStage::Fragment => 0x02,
                 ShaderStage::Compute => 0x04,
+                ShaderStage::Task => 0x08,
+                ShaderStage::Mesh => 0x10,
+                ShaderStage::RayGen => 0x20,
+                ShaderStage::Miss => 0x40,
+                ShaderStage::ClosestHit => 0x80,
+                ShaderStage::AnyHit => 0x100,
             };
         }
         Self(flags)
@@ -55,6 +88,12 @@ impl ShaderStageFlags {
     pub fn contains_vertex(&self) -> bool { self.0 & 0x01 != 0 }
     pub fn contains_fragment(&self) -> bool { self.0 & 0x02 != 0 }
     pub fn contains_compute(&self) -> bool { self.0 & 0x04 != 0 }
+    pub fn contains_task(&self) -> bool { self.0 & 0x08 != 0 }
+    pub fn contains_mesh(&self) -> bool { self.0 & 0x10 != 0 }
+    pub fn contains_raygen(&self) -> bool { self.0 & 0x20 != 0 }
+    pub fn contains_miss(&self) -> bool { self.0 & 0x40 != 0 }
+    pub fn contains_closest_hit(&self) -> bool { self.0 & 0x80 != 0 }
+    pub fn contains_any_hit(&self) -> bool { self.0 & 0x100 != 0 }
     pub fn bits(&self) -> u32 { self.0 }
 }
 
@@ -89,10 +128,111 @@ pub struct BindingGroupLayoutDesc {
 pub enum BindingResource<'a> {
     /// Uniform buffer binding
     UniformBuffer(&'a dyn Buffer),
+    /// Dynamic-offset uniform buffer binding: the shader sees `range` bytes
+    /// starting at the offset given when the group is bound
+    UniformBufferDynamic(&'a dyn Buffer, u64),
     /// Sampled texture (the backend resolves the actual GPU sampler from the type)
     SampledTexture(&'a dyn Texture, SamplerType),
+    /// Sampled texture with a fully described sampler (cached by the backend)
+    SampledTextureDesc(&'a dyn Texture, SamplerDesc),
     /// Storage buffer binding
     StorageBuffer(&'a dyn Buffer),
+    /// Storage image binding: one mip level (all layers) of a
+    /// `TextureUsage::Storage` texture. The texture must be in a
+    /// `ComputeWrite` / `RayTracingWrite` access when the shader runs.
+    StorageImage(&'a dyn Texture, u32),
+    /// Top-level acceleration structure (`GraphicsDevice::create_tlas`)
+    AccelerationStructure(&'a dyn AccelerationStructure),
+}
+
+impl BindingResource<'_> {
+    /// Sampled texture and whether its sampler compares depth
+    fn sampled_texture(&self) -> Option<(&dyn Texture, bool)> {
+        match self {
+            BindingResource::SampledTexture(texture, sampler_type) =>
+                Some((*texture, SamplerDesc::from(*sampler_type).compare.is_some())),
+            BindingResource::SampledTextureDesc(texture, desc) =>
+                Some((*texture, desc.compare.is_some())),
+            _ => None,
+        }
+    }
+}
+
+/// Check the depth sampling of `resources` (indexed by binding number).
+///
+/// A comparison sampler must sample a depth-format texture, and every
+/// `BindingType::SampledDepthTexture` slot of `layout` must receive a
+/// depth texture with a comparison sampler. Backends call it when a
+/// binding group is created.
+///
+/// # Errors
+///
+/// Returns an error naming the first binding that breaks a rule.
+pub fn validate_depth_sampling(
+    layout: Option<&BindingGroupLayoutDesc>,
+    resources: &[BindingResource],
+) -> Result<()> {
+    for (binding, resource) in resources.iter().enumerate() {
+        if let Some((texture, true)) = resource.sampled_texture() {
+            if !texture.info().format.is_depth() {
+                engine_bail!("galaxy3d::BindingGroup",
+                    "binding {}: comparison sampler used with non-depth format {:?}",
+                    binding, texture.info().format);
+            }
+        }
+    }
+
+    let Some(layout) = layout else { return Ok(()) };
+    for entry in layout.entries.iter().filter(|e| e.binding_type == BindingType::SampledDepthTexture) {
+        match resources.get(entry.binding as usize).and_then(|r| r.sampled_texture()) {
+            Some((_, true)) => {}
+            Some((_, false)) => engine_bail!("galaxy3d::BindingGroup",
+                "binding {}: SampledDepthTexture needs a comparison sampler", entry.binding),
+            None => engine_bail!("galaxy3d::BindingGroup",
+                "binding {}: SampledDepthTexture needs a sampled depth texture", entry.binding),
+        }
+    }
+    Ok(())
+}
+
+/// Check the storage images of `resources` (indexed by binding number).
+///
+/// A storage image must be a `TextureUsage::Storage` texture and its mip
+/// level must exist, and every `BindingType::StorageImage` slot of
+/// `layout` must receive a storage image. Backends call it when a binding
+/// group is created.
+///
+/// # Errors
+///
+/// Returns an error naming the first binding that breaks a rule.
+pub fn validate_storage_images(
+    layout: Option<&BindingGroupLayoutDesc>,
+    resources: &[BindingResource],
+) -> Result<()> {
+    for (binding, resource) in resources.iter().enumerate() {
+        if let BindingResource::StorageImage(texture, mip_level) = resource {
+            let info = texture.info();
+            if info.usage != TextureUsage::Storage {
+                engine_bail!("galaxy3d::BindingGroup",
+                    "binding {}: storage image needs a TextureUsage::Storage texture (got {:?})",
+                    binding, info.usage);
+            }
+            if *mip_level >= info.mip_levels {
+                engine_bail!("galaxy3d::BindingGroup",
+                    "binding {}: storage image mip level {} out of range (mip_levels = {})",
+                    binding, mip_level, info.mip_levels);
+            }
+        }
+    }
+
+    let Some(layout) = layout else { return Ok(()) };
+    for entry in layout.entries.iter().filter(|e| e.binding_type == BindingType::StorageImage) {
+        if !matches!(resources.get(entry.binding as usize), Some(BindingResource::StorageImage(..))) {
+            engine_bail!("galaxy3d::BindingGroup",
+                "binding {}: StorageImage slot needs a storage image resource", entry.binding);
+        }
+    }
+    Ok(())
 }
 
 // ============================================================================
@@ -24,9 +24,25 @@ fn test_constants_vertex_fragment() {
     assert_eq!(ShaderStageFlags::VERTEX_FRAGMENT.bits(), 0x03);
 }
 
+#[test]
+fn test_constants_task_mesh() {
+    assert_eq!(ShaderStageFlags::TASK.bits(), 0x08);
+    assert_eq!(ShaderStageFlags::MESH.bits(), 0x10);
+    assert_eq!(ShaderStageFlags::TASK_MESH_FRAGMENT.bits(), 0x1A);
+}
+
+#[test]
+fn test_constants_ray_tracing() {
+    assert_eq!(ShaderStageFlags::RAYGEN.bits(), 0x20);
+    assert_eq!(ShaderStageFlags::MISS.bits(), 0x40);
+    assert_eq!(ShaderStageFlags::CLOSEST_HIT.bits(), 0x80);
+    assert_eq!(ShaderStageFlags::ANY_HIT.bits(), 0x100);
+    assert_eq!(ShaderStageFlags::RAY_TRACING.bits(), 0x1E0);
+}
+
 #[test]
 fn test_constants_all() {
-    assert_eq!(ShaderStageFlags::ALL.bits(), 0x07);
+    assert_eq!(ShaderStageFlags::ALL.bits(), 0x1FF);
 }
 
 // ============================================================================
@@ -71,6 +87,22 @@ fn test_from_stages_all() {
     assert_eq!(f.bits(), 0x07);
 }
 
+#[test]
+fn test_from_stages_mesh_pipeline() {
+    let f = ShaderStageFlags::from_stages(&[
+        ShaderStage::Task, ShaderStage::Mesh, ShaderStage::Fragment,
+    ]);
+    assert_eq!(f, ShaderStageFlags::TASK_MESH_FRAGMENT);
+}
+
+#[test]
+fn test_from_stages_ray_tracing_pipeline() {
+    let f = ShaderStageFlags::from_stages(&[
+        ShaderStage::RayGen, ShaderStage::Miss, ShaderStage::ClosestHit, ShaderStage::AnyHit,
+    ]);
+    assert_eq!(f, ShaderStageFlags::RAY_TRACING);
+}
+
 #[test]
 fn test_from_stages_dedup_idempotent() {
     let f = ShaderStageFlags::from_stages(&[ShaderStage::Vertex, ShaderStage::Vertex]);
@@ -142,6 +174,25 @@ fn test_contains_compute_when_absent() {
     assert!(!ShaderStageFlags::VERTEX_FRAGMENT.contains_compute());
 }
 
+#[test]
+fn test_contains_task_mesh() {
+    assert!(ShaderStageFlags::TASK_MESH_FRAGMENT.contains_task());
+    assert!(ShaderStageFlags::TASK_MESH_FRAGMENT.contains_mesh());
+    assert!(ShaderStageFlags::ALL.contains_mesh());
+    assert!(!ShaderStageFlags::MESH.contains_task());
+    assert!(!ShaderStageFlags::VERTEX_FRAGMENT.contains_mesh());
+}
+
+#[test]
+fn test_contains_ray_tracing_stages() {
+    let flags = ShaderStageFlags::RAY_TRACING;
+    assert!(flags.contains_raygen() && flags.contains_miss());
+    assert!(flags.contains_closest_hit() && flags.contains_any_hit());
+    assert!(!flags.contains_fragment());
+    assert!(ShaderStageFlags::ALL.contains_any_hit());
+    assert!(!ShaderStageFlags::TASK_MESH_FRAGMENT.contains_raygen());
+}
+
 // ============================================================================
 // Equality / Hash / Clone
 // ============================================================================
@@ -189,7 +240,10 @@ fn test_binding_type_equality_and_hash() {
     set.insert(BindingType::UniformBuffer);
     set.insert(BindingType::CombinedImageSampler);
     set.insert(BindingType::StorageBuffer);
-    assert_eq!(set.len(), 3);
+    set.insert(BindingType::UniformBufferDynamic);
+    set.insert(BindingType::SampledDepthTexture);
+    set.insert(BindingType::StorageImage);
+    assert_eq!(set.len(), 6);
 }
 
 #[test]
@@ -230,3 +284,103 @@ fn test_binding_group_layout_desc_clone_preserves_entries() {
     assert_eq!(cloned.entries[0].binding, 0);
     assert_eq!(cloned.entries[1].count, 4);
 }
+
+// ============================================================================
+// validate_depth_sampling
+// ============================================================================
+
+fn texture(format: crate::graphics_device::TextureFormat) -> crate::graphics_device::mock_graphics_device::MockTexture {
+    let mut texture = crate::graphics_device::mock_graphics_device::MockTexture::new(
+        64, 64, 1, crate::graphics_device::TextureType::Tex2D, "shadow_map".to_string());
+    texture.info.format = format;
+    texture
+}
+
+fn depth_slot(binding: u32) -> BindingGroupLayoutDesc {
+    BindingGroupLayoutDesc {
+        entries: vec![BindingSlotDesc {
+            binding,
+            binding_type: BindingType::SampledDepthTexture,
+            count: 1,
+            stage_flags: ShaderStageFlags::FRAGMENT,
+        }],
+    }
+}
+
+#[test]
+fn test_depth_sampling_accepts_compare_sampler_on_depth_texture() {
+    use crate::graphics_device::{CompareOp, TextureFormat};
+    let depth = texture(TextureFormat::D32_FLOAT);
+    let preset = [BindingResource::SampledTexture(&depth, SamplerType::Shadow)];
+    assert!(validate_depth_sampling(Some(&depth_slot(0)), &preset).is_ok());
+    let custom = [BindingResource::SampledTextureDesc(&depth, SamplerDesc::depth_compare(CompareOp::Greater))];
+    assert!(validate_depth_sampling(Some(&depth_slot(0)), &custom).is_ok());
+}
+
+#[test]
+fn test_depth_sampling_rejects_compare_sampler_on_color_texture() {
+    let color = texture(crate::graphics_device::TextureFormat::R8G8B8A8_UNORM);
+    let resources = [BindingResource::SampledTexture(&color, SamplerType::Shadow)];
+    assert!(validate_depth_sampling(None, &resources).is_err());
+}
+
+#[test]
+fn test_depth_sampling_slot_requires_compare_sampler() {
+    let depth = texture(crate::graphics_device::TextureFormat::D16_UNORM);
+    let resources = [BindingResource::SampledTexture(&depth, SamplerType::LinearClamp)];
+    assert!(validate_depth_sampling(None, &resources).is_ok());
+    assert!(validate_depth_sampling(Some(&depth_slot(0)), &resources).is_err());
+    // Slot without a resource
+    assert!(validate_depth_sampling(Some(&depth_slot(1)), &resources).is_err());
+}
+
+// ============================================================================
+// validate_storage_images
+// ============================================================================
+
+fn storage_texture(mip_levels: u32) -> crate::graphics_device::mock_graphics_device::MockTexture {
+    let mut texture = crate::graphics_device::mock_graphics_device::MockTexture::new(
+        64, 64, 1, crate::graphics_device::TextureType::Tex2D, "depth_pyramid".to_string());
+    texture.info.usage = TextureUsage::Storage;
+    texture.info.mip_levels = mip_levels;
+    texture
+}
+
+fn storage_slot(binding: u32) -> BindingGroupLayoutDesc {
+    BindingGroupLayoutDesc {
+        entries: vec![BindingSlotDesc {
+            binding,
+            binding_type: BindingType::StorageImage,
+            count: 1,
+            stage_flags: ShaderStageFlags::COMPUTE,
+        }],
+    }
+}
+
+#[test]
+fn test_storage_images_accept_each_mip_level() {
+    let pyramid = storage_texture(4);
+    for mip_level in 0..4 {
+        let resources = [BindingResource::StorageImage(&pyramid, mip_level)];
+        assert!(validate_storage_images(Some(&storage_slot(0)), &resources).is_ok());
+    }
+}
+
+#[test]
+fn test_storage_images_reject_invalid_textures() {
+    let pyramid = storage_texture(4);
+    assert!(validate_storage_images(None, &[BindingResource::StorageImage(&pyramid, 4)]).is_err());
+
+    let sampled = texture(crate::graphics_device::TextureFormat::R8G8B8A8_UNORM);
+    assert!(validate_storage_images(None, &[BindingResource::StorageImage(&sampled, 0)]).is_err());
+}
+
+#[test]
+fn test_storage_image_slot_requires_storage_image() {
+    let pyramid = storage_texture(1);
+    let resources = [BindingResource::SampledTexture(&pyramid, SamplerType::NearestClamp)];
+    assert!(validate_storage_images(None, &resources).is_ok());
+    assert!(validate_storage_images(Some(&storage_slot(0)), &resources).is_err());
+    // Slot without a resource
+    assert!(validate_storage_images(Some(&storage_slot(1)), &resources).is_err());
+}
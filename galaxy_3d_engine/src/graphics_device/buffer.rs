@@ -1,11 +1,13 @@
 /// Buffer trait and buffer descriptor
 
 use crate::error::Result;
+use crate::graphics_device::ScalarKind;
 
 /// Buffer usage flags
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BufferUsage {
-    /// Vertex buffer
+    /// Vertex buffer (also readable as a storage buffer, for the vertex
+    /// pulling of mesh shaders)
     Vertex,
     /// Index buffer
     Index,
@@ -13,6 +15,9 @@ pub enum BufferUsage {
     Uniform,
     /// Storage buffer
     Storage,
+    /// CPU readback target of `CommandList::copy_texture_to_buffer` and
+    /// `CommandList::copy_buffer_to_buffer`
+    Readback,
 }
 
 /// Descriptor for creating a buffer
@@ -22,6 +27,8 @@ pub struct BufferDesc {
     pub size: u64,
     /// Buffer usage
     pub usage: BufferUsage,
+    /// Name shown by graphics debuggers (RenderDoc, validation messages)
+    pub debug_name: Option<String>,
 }
 
 /// Buffer data format for vertex attributes and indices
@@ -30,6 +37,7 @@ pub struct BufferDesc {
 /// Used for vertex attributes (position, normal, UV, etc.) and index types.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[allow(non_camel_case_types)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub enum BufferFormat {
     // Float formats (vertex attributes)
     R32_SFLOAT,         // float (4 bytes)
@@ -68,6 +76,31 @@ pub enum BufferFormat {
     R8_UINT,
     R8G8_UINT,
     R8G8B8A8_UINT,
+
+    // Half float formats (read as float)
+    R16_SFLOAT,
+    R16G16_SFLOAT,          // vec2 (4 bytes)
+    R16G16B16A16_SFLOAT,    // vec4 (8 bytes), vec3 data padded with w
+
+    // Normalized short formats (read as float, 0..1 / -1..1)
+    R16_UNORM,
+    R16G16_UNORM,
+    R16G16B16A16_UNORM,
+    R16_SNORM,
+    R16G16_SNORM,
+    R16G16B16A16_SNORM,
+
+    // Normalized byte formats (read as float, 0..1 / -1..1)
+    R8_UNORM,
+    R8G8_UNORM,
+    R8G8B8A8_UNORM,         // colors
+    R8_SNORM,
+    R8G8_SNORM,
+    R8G8B8A8_SNORM,         // normals, tangents
+
+    // Packed 10:10:10:2 formats (read as float vec4, R in the low bits)
+    A2B10G10R10_UNORM_PACK32,
+    A2B10G10R10_SNORM_PACK32,   // optional as Vulkan vertex input
 }
 
 impl BufferFormat {
@@ -89,6 +122,71 @@ impl BufferFormat {
             BufferFormat::R8_SINT | BufferFormat::R8_UINT => 1,
             BufferFormat::R8G8_SINT | BufferFormat::R8G8_UINT => 2,
             BufferFormat::R8G8B8A8_SINT | BufferFormat::R8G8B8A8_UINT => 4,
+
+            // Half float and normalized short formats
+            BufferFormat::R16_SFLOAT | BufferFormat::R16_UNORM | BufferFormat::R16_SNORM => 2,
+            BufferFormat::R16G16_SFLOAT | BufferFormat::R16G16_UNORM | BufferFormat::R16G16_SNORM => 4,
+            BufferFormat::R16G16B16A16_SFLOAT | BufferFormat::R16G16B16A16_UNORM
+            | BufferFormat::R16G16B16A16_SNORM => 8,
+
+            // Normalized byte formats
+            BufferFormat::R8_UNORM | BufferFormat::R8_SNORM => 1,
+            BufferFormat::R8G8_UNORM | BufferFormat::R8G8_SNORM => 2,
+            BufferFormat::R8G8B8A8_UNORM | BufferFormat::R8G8B8A8_SNORM => 4,
+
+            // Packed formats
+            BufferFormat::A2B10G10R10_UNORM_PACK32 | BufferFormat::A2B10G10R10_SNORM_PACK32 => 4,
+        }
+    }
+
+    /// Numeric type a shader reads from an attribute of this format
+    pub fn scalar_kind(&self) -> ScalarKind {
+        match self {
+            BufferFormat::R32_SFLOAT | BufferFormat::R32G32_SFLOAT
+            | BufferFormat::R32G32B32_SFLOAT | BufferFormat::R32G32B32A32_SFLOAT
+            | BufferFormat::R16_SFLOAT | BufferFormat::R16G16_SFLOAT | BufferFormat::R16G16B16A16_SFLOAT
+            | BufferFormat::R16_UNORM | BufferFormat::R16G16_UNORM | BufferFormat::R16G16B16A16_UNORM
+            | BufferFormat::R16_SNORM | BufferFormat::R16G16_SNORM | BufferFormat::R16G16B16A16_SNORM
+            | BufferFormat::R8_UNORM | BufferFormat::R8G8_UNORM | BufferFormat::R8G8B8A8_UNORM
+            | BufferFormat::R8_SNORM | BufferFormat::R8G8_SNORM | BufferFormat::R8G8B8A8_SNORM
+            | BufferFormat::A2B10G10R10_UNORM_PACK32
+            | BufferFormat::A2B10G10R10_SNORM_PACK32 => ScalarKind::Float32,
+
+            BufferFormat::R32_SINT | BufferFormat::R32G32_SINT
+            | BufferFormat::R32G32B32_SINT | BufferFormat::R32G32B32A32_SINT
+            | BufferFormat::R16_SINT | BufferFormat::R16G16_SINT | BufferFormat::R16G16B16A16_SINT
+            | BufferFormat::R8_SINT | BufferFormat::R8G8_SINT | BufferFormat::R8G8B8A8_SINT => ScalarKind::Int32,
+
+            BufferFormat::R32_UINT | BufferFormat::R32G32_UINT
+            | BufferFormat::R32G32B32_UINT | BufferFormat::R32G32B32A32_UINT
+            | BufferFormat::R16_UINT | BufferFormat::R16G16_UINT | BufferFormat::R16G16B16A16_UINT
+            | BufferFormat::R8_UINT | BufferFormat::R8G8_UINT | BufferFormat::R8G8B8A8_UINT => ScalarKind::UInt32,
+        }
+    }
+
+    /// Number of components of this format
+    pub fn component_count(&self) -> u32 {
+        match self {
+            BufferFormat::R32_SFLOAT | BufferFormat::R32_SINT | BufferFormat::R32_UINT
+            | BufferFormat::R16_SINT | BufferFormat::R16_UINT
+            | BufferFormat::R8_SINT | BufferFormat::R8_UINT
+            | BufferFormat::R16_SFLOAT | BufferFormat::R16_UNORM | BufferFormat::R16_SNORM
+            | BufferFormat::R8_UNORM | BufferFormat::R8_SNORM => 1,
+
+            BufferFormat::R32G32_SFLOAT | BufferFormat::R32G32_SINT | BufferFormat::R32G32_UINT
+            | BufferFormat::R16G16_SINT | BufferFormat::R16G16_UINT
+            | BufferFormat::R8G8_SINT | BufferFormat::R8G8_UINT
+            | BufferFormat::R16G16_SFLOAT | BufferFormat::R16G16_UNORM | BufferFormat::R16G16_SNORM
+            | BufferFormat::R8G8_UNORM | BufferFormat::R8G8_SNORM => 2,
+
+            BufferFormat::R32G32B32_SFLOAT | BufferFormat::R32G32B32_SINT | BufferFormat::R32G32B32_UINT => 3,
+
+            BufferFormat::R32G32B32A32_SFLOAT | BufferFormat::R32G32B32A32_SINT | BufferFormat::R32G32B32A32_UINT
+            | BufferFormat::R16G16B16A16_SINT | BufferFormat::R16G16B16A16_UINT
+            | BufferFormat::R8G8B8A8_SINT | BufferFormat::R8G8B8A8_UINT
+            | BufferFormat::R16G16B16A16_SFLOAT | BufferFormat::R16G16B16A16_UNORM
+            | BufferFormat::R16G16B16A16_SNORM | BufferFormat::R8G8B8A8_UNORM | BufferFormat::R8G8B8A8_SNORM
+            | BufferFormat::A2B10G10R10_UNORM_PACK32 | BufferFormat::A2B10G10R10_SNORM_PACK32 => 4,
         }
     }
 }
@@ -106,6 +204,12 @@ pub trait Buffer: Send + Sync {
     /// * `data` - Data to write
     fn update(&self, offset: u64, data: &[u8]) -> Result<()>;
 
+    /// Read `data.len()` bytes starting at `offset` from a CPU-accessible buffer.
+    ///
+    /// The caller must make sure the GPU has finished writing the range
+    /// (e.g. the command list that wrote it has completed).
+    fn read(&self, offset: u64, data: &mut [u8]) -> Result<()>;
+
     /// Raw pointer to persistently mapped memory
     ///
     /// Returns None if the buffer is not CPU-accessible (device-local only).
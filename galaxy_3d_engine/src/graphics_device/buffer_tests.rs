@@ -4,7 +4,7 @@
 //! for all vertex attribute and index buffer formats.
 
 #[cfg(test)]
-use crate::graphics_device::BufferFormat;
+use crate::graphics_device::{BufferFormat, ScalarKind};
 
 // ============================================================================
 // FLOAT FORMATS
@@ -65,6 +65,29 @@ fn test_buffer_format_size_bytes_unsigned_int_formats() {
     assert_eq!(BufferFormat::R8G8B8A8_UINT.size_bytes(), 4);
 }
 
+// ============================================================================
+// COMPRESSED FORMATS (HALF FLOAT, NORMALIZED, PACKED)
+// ============================================================================
+
+#[test]
+fn test_buffer_format_size_bytes_compressed_formats() {
+    // Half floats halve the size of the 32-bit formats
+    assert_eq!(BufferFormat::R16_SFLOAT.size_bytes(), 2);
+    assert_eq!(BufferFormat::R16G16_SFLOAT.size_bytes(), 4);
+    assert_eq!(BufferFormat::R16G16B16A16_SFLOAT.size_bytes(), 8);
+
+    // Normalized shorts and bytes
+    assert_eq!(BufferFormat::R16G16_UNORM.size_bytes(), 4);
+    assert_eq!(BufferFormat::R16G16B16A16_SNORM.size_bytes(), 8);
+    assert_eq!(BufferFormat::R8_UNORM.size_bytes(), 1);
+    assert_eq!(BufferFormat::R8G8_SNORM.size_bytes(), 2);
+    assert_eq!(BufferFormat::R8G8B8A8_UNORM.size_bytes(), 4);
+
+    // Four components in one word
+    assert_eq!(BufferFormat::A2B10G10R10_UNORM_PACK32.size_bytes(), 4);
+    assert_eq!(BufferFormat::A2B10G10R10_SNORM_PACK32.size_bytes(), 4);
+}
+
 // ============================================================================
 // COMPREHENSIVE TEST
 // ============================================================================
@@ -122,3 +145,28 @@ fn test_buffer_format_size_bytes_all_variants() {
                    "Unsigned int format size mismatch for {:?}", format);
     }
 }
+
+// ============================================================================
+// BufferFormat::scalar_kind / component_count
+// ============================================================================
+
+#[test]
+fn test_buffer_format_shader_type() {
+    let cases = [
+        (BufferFormat::R32_SFLOAT, ScalarKind::Float32, 1),
+        (BufferFormat::R32G32B32_SFLOAT, ScalarKind::Float32, 3),
+        (BufferFormat::R16G16_SINT, ScalarKind::Int32, 2),
+        (BufferFormat::R32G32B32_SINT, ScalarKind::Int32, 3),
+        (BufferFormat::R8G8B8A8_UINT, ScalarKind::UInt32, 4),
+        (BufferFormat::R16_UINT, ScalarKind::UInt32, 1),
+        // Compressed formats are read as floats
+        (BufferFormat::R16G16_SFLOAT, ScalarKind::Float32, 2),
+        (BufferFormat::R16G16B16A16_UNORM, ScalarKind::Float32, 4),
+        (BufferFormat::R8_SNORM, ScalarKind::Float32, 1),
+        (BufferFormat::A2B10G10R10_SNORM_PACK32, ScalarKind::Float32, 4),
+    ];
+    for (format, kind, count) in cases {
+        assert_eq!(format.scalar_kind(), kind, "{:?}", format);
+        assert_eq!(format.component_count(), count, "{:?}", format);
+    }
+}
@@ -5,9 +5,11 @@ use crate::error::Result;
 use crate::graphics_device::{
     RenderPass, Framebuffer, Pipeline, Buffer,
     BindingGroup, IndexType, ShaderStageFlags, ImageAccess, BufferAccess,
-    DynamicRenderState,
+    DynamicRenderState, AccelerationStructure, TlasInstance, QueryPool,
 };
 
+pub use super::viewport::{Viewport, Rect2D};
+
 /// Command list for recording rendering commands
 ///
 /// Commands are recorded and later submitted to the GPU via RendererDevice::submit()
@@ -44,6 +46,24 @@ pub trait CommandList: Send + Sync {
     /// End the current render pass
     fn end_render_pass(&mut self) -> Result<()>;
 
+    /// Emit the barriers of access declarations outside a render pass
+    ///
+    /// `begin_render_pass` emits the barriers of the accesses it is given;
+    /// this does the same without starting a pass. Used to leave textures
+    /// rendered outside the render graph ready for their next use (e.g.
+    /// baked targets moved to `AccessType::FragmentShaderRead` so materials
+    /// can sample them).
+    ///
+    /// # Arguments
+    ///
+    /// * `image_accesses` - Per-image access declarations
+    /// * `buffer_accesses` - Per-buffer access declarations
+    fn declare_accesses(
+        &mut self,
+        image_accesses: &[ImageAccess],
+        buffer_accesses: &[BufferAccess],
+    ) -> Result<()>;
+
     /// Set the viewport
     ///
     /// # Arguments
@@ -82,6 +102,27 @@ pub trait CommandList: Send + Sync {
         binding_group: &Arc<dyn BindingGroup>,
     ) -> Result<()>;
 
+    /// Bind a binding group holding dynamic-offset uniform buffers
+    ///
+    /// `dynamic_offsets` gives one byte offset per
+    /// `BindingType::UniformBufferDynamic` binding of the group, in binding
+    /// order. Each offset must be a multiple of
+    /// `GraphicsDevice::uniform_buffer_alignment()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `pipeline` - Pipeline to bind the group to (needed to extract pipeline layout)
+    /// * `set_index` - Set index
+    /// * `binding_group` - The binding group to bind
+    /// * `dynamic_offsets` - Offsets of the dynamic uniform buffers
+    fn bind_binding_group_dynamic(
+        &mut self,
+        pipeline: &Arc<dyn Pipeline>,
+        set_index: u32,
+        binding_group: &Arc<dyn BindingGroup>,
+        dynamic_offsets: &[u32],
+    ) -> Result<()>;
+
     /// Bind the bindless texture descriptor set (set 0).
     ///
     /// Must be called after `bind_pipeline`. Uses the currently bound pipeline
@@ -106,6 +147,18 @@ pub trait CommandList: Send + Sync {
     /// * `offset` - Offset into the buffer in bytes
     fn bind_vertex_buffer(&mut self, buffer: &Arc<dyn Buffer>, offset: u64) -> Result<()>;
 
+    /// Bind a vertex buffer to a specific vertex input binding
+    ///
+    /// `bind_vertex_buffer` binds binding 0; this binds any other binding
+    /// declared by the pipeline vertex layout (e.g. per-instance data).
+    ///
+    /// # Arguments
+    ///
+    /// * `binding` - Vertex input binding index
+    /// * `buffer` - Buffer to bind
+    /// * `offset` - Offset into the buffer in bytes
+    fn bind_vertex_buffer_at(&mut self, binding: u32, buffer: &Arc<dyn Buffer>, offset: u64) -> Result<()>;
+
     /// Bind an index buffer
     ///
     /// # Arguments
@@ -132,6 +185,117 @@ pub trait CommandList: Send + Sync {
     /// * `vertex_offset` - Value added to vertex index before indexing into the vertex buffer
     fn draw_indexed(&mut self, index_count: u32, first_index: u32, vertex_offset: i32) -> Result<()>;
 
+    /// Draw several instances of vertices
+    ///
+    /// # Arguments
+    ///
+    /// * `vertex_count` - Number of vertices to draw
+    /// * `first_vertex` - Index of first vertex
+    /// * `instance_count` - Number of instances to draw
+    /// * `first_instance` - Index of the first instance (offsets instance-rate attributes)
+    fn draw_instanced(
+        &mut self,
+        vertex_count: u32,
+        first_vertex: u32,
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<()>;
+
+    /// Draw several instances of indexed vertices
+    ///
+    /// # Arguments
+    ///
+    /// * `index_count` - Number of indices to draw
+    /// * `first_index` - Index of first index
+    /// * `vertex_offset` - Value added to vertex index before indexing into the vertex buffer
+    /// * `instance_count` - Number of instances to draw
+    /// * `first_instance` - Index of the first instance (offsets instance-rate attributes)
+    fn draw_indexed_instanced(
+        &mut self,
+        index_count: u32,
+        first_index: u32,
+        vertex_offset: i32,
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<()>;
+
+    /// Launch task (or mesh, without task shader) workgroups of the bound
+    /// mesh shader pipeline
+    ///
+    /// Only available when `GraphicsDevice::supports_mesh_shaders()` is true.
+    ///
+    /// # Arguments
+    ///
+    /// * `group_count_x` - Number of workgroups in X
+    /// * `group_count_y` - Number of workgroups in Y
+    /// * `group_count_z` - Number of workgroups in Z
+    fn draw_mesh_tasks(&mut self, group_count_x: u32, group_count_y: u32, group_count_z: u32) -> Result<()>;
+
+    /// Rebuild a top-level acceleration structure from its instances
+    ///
+    /// Only available when `GraphicsDevice::supports_ray_tracing()` is true.
+    /// Must be recorded outside a render pass. The instances are written to
+    /// the TLAS instance buffer at record time, so call it after
+    /// `GraphicsDevice::wait_for_previous_submit()` (as for instance
+    /// buffers), at most once per TLAS per command list. The backend emits
+    /// the barriers making the build visible to later shader reads.
+    ///
+    /// # Arguments
+    ///
+    /// * `tlas` - Top-level structure (`GraphicsDevice::create_tlas`)
+    /// * `instances` - Instances, at most the TLAS `max_instances`
+    fn build_tlas(
+        &mut self,
+        tlas: &Arc<dyn AccelerationStructure>,
+        instances: &[TlasInstance],
+    ) -> Result<()>;
+
+    /// Launch rays with the bound ray tracing pipeline
+    ///
+    /// Only available when `GraphicsDevice::supports_ray_tracing()` is true.
+    /// Must be recorded outside a render pass. One ray generation
+    /// invocation runs per launch element (`gl_LaunchIDEXT`).
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Launch width (typically the render target width)
+    /// * `height` - Launch height
+    /// * `depth` - Launch depth (1 for images)
+    fn trace_rays(&mut self, width: u32, height: u32, depth: u32) -> Result<()>;
+
+    /// Reset queries before they are recorded again
+    ///
+    /// Must be recorded outside a render pass, before the `begin_query` of
+    /// each query (a query that was not reset has no result).
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Query pool (`GraphicsDevice::create_query_pool`)
+    /// * `first_query` - First query to reset
+    /// * `query_count` - Number of queries to reset
+    fn reset_queries(&mut self, pool: &Arc<dyn QueryPool>, first_query: u32, query_count: u32) -> Result<()>;
+
+    /// Start a query: the commands recorded until `end_query` are counted
+    ///
+    /// A query begun inside a render pass must end inside it, one begun
+    /// outside must end outside. Only one query of each `QueryType` may be
+    /// active at a time. Occlusion queries count the samples of the draws
+    /// of a render pass.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Query pool
+    /// * `query` - Index of the query in the pool
+    fn begin_query(&mut self, pool: &Arc<dyn QueryPool>, query: u32) -> Result<()>;
+
+    /// End the query started by `begin_query`
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Query pool
+    /// * `query` - Index of the query in the pool
+    fn end_query(&mut self, pool: &Arc<dyn QueryPool>, query: u32) -> Result<()>;
+
     /// Set all dynamic pipeline states for the next draw call
     ///
     /// The backend translates this into the appropriate vkCmdSet* calls.
@@ -142,26 +306,89 @@ pub trait CommandList: Send + Sync {
     /// * `state` - The resolved dynamic render state for this draw call
     fn set_dynamic_state(&mut self, state: &DynamicRenderState) -> Result<()>;
 
-}
+    /// Resolve a multisampled color texture into a single-sampled one
+    ///
+    /// Must be recorded outside a render pass. Prefer a resolve attachment
+    /// (`RenderPassDesc::color_resolve_attachments`) when the resolve can
+    /// happen at the end of the pass that renders the MSAA texture; this
+    /// explicit resolve is for MSAA textures resolved later or partially.
+    ///
+    /// `src.access_type` must be `AccessType::TransferRead` and
+    /// `dst.access_type` `AccessType::TransferWrite`; the backend emits the
+    /// barriers from their `previous_access_type`, as `begin_render_pass`
+    /// does. The textures must satisfy `TextureInfo::validate_resolve_into`.
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - Multisampled source texture access
+    /// * `dst` - Single-sampled destination texture access
+    fn resolve_texture(&mut self, src: &ImageAccess, dst: &ImageAccess) -> Result<()>;
+
+    /// Copy texel regions of a color texture into a buffer
+    ///
+    /// Must be recorded outside a render pass. Reads mip 0, layer 0 of
+    /// `src`; each region is written tightly packed (row after row) at its
+    /// `buffer_offset`. `src.access_type` must be `AccessType::TransferRead`.
+    /// The backend makes the written range visible to the host, so `dst`
+    /// (created with `BufferUsage::Readback`) can be read with
+    /// `Buffer::read` once the command list has completed.
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - Source texture access
+    /// * `dst` - Destination buffer
+    /// * `regions` - Texel rectangles to copy and where to put them
+    fn copy_texture_to_buffer(
+        &mut self,
+        src: &ImageAccess,
+        dst: &Arc<dyn Buffer>,
+        regions: &[TextureCopyRegion],
+    ) -> Result<()>;
+
+    /// Copy a byte range of a buffer into another buffer
+    ///
+    /// Must be recorded outside a render pass. `src.access_type` must be
+    /// `AccessType::TransferRead`; the backend waits for
+    /// `src.previous_access_type` (typically `ComputeWrite`) before copying.
+    /// As with `copy_texture_to_buffer`, the written range is made visible
+    /// to the host, so a `BufferUsage::Readback` destination can be read
+    /// with `Buffer::read` once the command list has completed.
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - Source buffer access
+    /// * `src_offset` - Byte offset of the range in the source buffer
+    /// * `dst` - Destination buffer
+    /// * `dst_offset` - Byte offset of the range in the destination buffer
+    /// * `size` - Number of bytes to copy
+    fn copy_buffer_to_buffer(
+        &mut self,
+        src: &BufferAccess,
+        src_offset: u64,
+        dst: &Arc<dyn Buffer>,
+        dst_offset: u64,
+        size: u64,
+    ) -> Result<()>;
+
+    /// Open a named region of commands, shown by graphics debuggers
+    /// (RenderDoc, Nsight)
+    ///
+    /// Regions nest and are closed by `end_debug_label` in the same command
+    /// list. Backends without debug support enabled ignore them.
+    fn begin_debug_label(&mut self, name: &str) -> Result<()>;
+
+    /// Close the region opened by the last `begin_debug_label`
+    fn end_debug_label(&mut self) -> Result<()>;
 
-/// Viewport dimensions and depth range
-#[derive(Debug, Clone, Copy)]
-pub struct Viewport {
-    pub x: f32,
-    pub y: f32,
-    pub width: f32,
-    pub height: f32,
-    pub min_depth: f32,
-    pub max_depth: f32,
 }
 
-/// 2D rectangle
+/// Texel rectangle copied by `CommandList::copy_texture_to_buffer`
 #[derive(Debug, Clone, Copy)]
-pub struct Rect2D {
-    pub x: i32,
-    pub y: i32,
-    pub width: u32,
-    pub height: u32,
+pub struct TextureCopyRegion {
+    /// Texel rectangle in the source texture
+    pub rect: Rect2D,
+    /// Byte offset of the first texel in the destination buffer
+    pub buffer_offset: u64,
 }
 
 /// Clear value for an attachment
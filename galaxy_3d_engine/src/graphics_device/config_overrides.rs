@@ -0,0 +1,242 @@
+/// Environment variable and command-line overrides of `Config`.
+///
+/// QA and automated test matrices change the engine setup without
+/// rebuilding. Precedence, from lowest to highest:
+///
+/// 1. `Config::default()`
+/// 2. The values set by the application code
+/// 3. `GALAXY3D_*` environment variables (`Config::apply_env`)
+/// 4. Command-line arguments (`Config::apply_args`)
+///
+/// | Field | Environment | Argument | Values |
+/// |---|---|---|---|
+/// | `enable_validation` | `GALAXY3D_VALIDATION` | `--validation[=v]`, `--no-validation` | `on`/`off`, `true`/`false`, `yes`/`no`, `1`/`0` |
+/// | `backend` | `GALAXY3D_BACKEND` | `--backend <name>` | backend name (`vulkan`) |
+/// | `adapter_index` | `GALAXY3D_ADAPTER` | `--adapter <n>` | GPU index, in enumeration order |
+/// | `window_size` | `GALAXY3D_WINDOW_SIZE` | `--window-size <w>x<h>` | e.g. `1920x1080` |
+/// | `quality_preset` | `GALAXY3D_QUALITY` | `--quality <p>` | `low`, `medium`, `high`, `ultra` |
+/// | `platform_profile` | `GALAXY3D_PLATFORM` | `--platform <p>` | `desktop`, `steamdeck`, `mobile` |
+///
+/// Arguments are written `--name value` or `--name=value`. Values are case
+/// insensitive. `apply_args` returns the arguments it does not know, so the
+/// application can parse its own; everything after `--` is passed through.
+///
+/// ```ignore
+/// let mut config = Config { app_name: "Demo".to_string(), ..Default::default() };
+/// let app_args = config.apply_overrides()?;
+/// ```
+
+use super::{Config, PlatformProfile};
+use crate::error::Result;
+use crate::engine_bail;
+use crate::perf_advisor::QualityPreset;
+
+/// Prefix of the environment variables read by `Config::apply_env`
+pub const CONFIG_ENV_PREFIX: &str = "GALAXY3D_";
+
+/// Prefix of the command-line arguments read by `Config::apply_args`
+const ARG_PREFIX: &str = "--";
+
+/// Argument turning validation off
+const NO_VALIDATION_ARG: &str = "no-validation";
+
+/// One overridable `Config` field
+struct ConfigOverride {
+    /// Environment variable name, without `CONFIG_ENV_PREFIX`
+    env: &'static str,
+    /// Argument name, without `ARG_PREFIX`
+    arg: &'static str,
+    /// Value of the argument given without one (flags)
+    flag_value: Option<&'static str>,
+    /// Accepted values, for the error message
+    expected: &'static str,
+    /// Parse `value` into the config, false if it is invalid
+    apply: fn(&mut Config, &str) -> bool,
+}
+
+const OVERRIDES: &[ConfigOverride] = &[
+    ConfigOverride {
+        env: "VALIDATION",
+        arg: "validation",
+        flag_value: Some("on"),
+        expected: "on or off",
+        apply: |config, value| parse_bool(value).map(|v| config.enable_validation = v).is_some(),
+    },
+    ConfigOverride {
+        env: "BACKEND",
+        arg: "backend",
+        flag_value: None,
+        expected: "a backend name",
+        apply: |config, value| {
+            !value.is_empty() && {
+                config.backend = Some(value.to_ascii_lowercase());
+                true
+            }
+        },
+    },
+    ConfigOverride {
+        env: "ADAPTER",
+        arg: "adapter",
+        flag_value: None,
+        expected: "a GPU index",
+        apply: |config, value| value.parse().map(|v| config.adapter_index = Some(v)).is_ok(),
+    },
+    ConfigOverride {
+        env: "WINDOW_SIZE",
+        arg: "window-size",
+        flag_value: None,
+        expected: "<width>x<height>",
+        apply: |config, value| parse_size(value).map(|v| config.window_size = Some(v)).is_some(),
+    },
+    ConfigOverride {
+        env: "QUALITY",
+        arg: "quality",
+        flag_value: None,
+        expected: "low, medium, high or ultra",
+        apply: |config, value| parse_quality(value).map(|v| config.quality_preset = Some(v)).is_some(),
+    },
+    ConfigOverride {
+        env: "PLATFORM",
+        arg: "platform",
+        flag_value: None,
+        expected: "desktop, steamdeck or mobile",
+        apply: |config, value| parse_platform(value).map(|v| config.platform_profile = v).is_some(),
+    },
+];
+
+impl ConfigOverride {
+    fn set(&self, config: &mut Config, value: &str, source: &str) -> Result<()> {
+        if !(self.apply)(config, value.trim()) {
+            engine_bail!("galaxy3d::Config",
+                "Invalid value '{}' for {}: expected {}", value, source, self.expected);
+        }
+        Ok(())
+    }
+}
+
+impl Config {
+    /// Apply the environment and then the command line of the process
+    /// (see module docs). Returns the arguments left to the application.
+    ///
+    /// # Errors
+    ///
+    /// Same as `apply_env_with()` and `apply_args()`.
+    pub fn apply_overrides(&mut self) -> Result<Vec<String>> {
+        self.apply_env()?;
+        self.apply_args(std::env::args().skip(1))
+    }
+
+    /// Apply the `GALAXY3D_*` variables of the process environment.
+    ///
+    /// # Errors
+    ///
+    /// Same as `apply_env_with()`.
+    pub fn apply_env(&mut self) -> Result<()> {
+        self.apply_env_with(|name| std::env::var(name).ok())
+    }
+
+    /// Apply the `GALAXY3D_*` variables read through `env` (empty values
+    /// count as unset).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a variable has an invalid value.
+    pub fn apply_env_with(&mut self, env: impl Fn(&str) -> Option<String>) -> Result<()> {
+        for entry in OVERRIDES {
+            let name = format!("{}{}", CONFIG_ENV_PREFIX, entry.env);
+            if let Some(value) = env(&name).filter(|value| !value.trim().is_empty()) {
+                entry.set(self, &value, &name)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply the command-line arguments (program name excluded). Returns
+    /// the arguments it does not know, in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an argument has an invalid or missing value.
+    pub fn apply_args<I, S>(&mut self, args: I) -> Result<Vec<String>>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut args = args.into_iter().map(Into::into);
+        let mut unknown = Vec::new();
+        while let Some(arg) = args.next() {
+            let Some(option) = arg.strip_prefix(ARG_PREFIX) else {
+                unknown.push(arg);
+                continue;
+            };
+            if option.is_empty() {
+                unknown.push(arg);
+                unknown.extend(args);
+                break;
+            }
+            let (name, inline_value) = match option.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (option, None),
+            };
+            if name == NO_VALIDATION_ARG && inline_value.is_none() {
+                self.enable_validation = false;
+                continue;
+            }
+            let Some(entry) = OVERRIDES.iter().find(|entry| entry.arg == name) else {
+                unknown.push(arg);
+                continue;
+            };
+            let value = match (inline_value, entry.flag_value) {
+                (Some(value), _) => value,
+                (None, Some(flag_value)) => flag_value.to_string(),
+                (None, None) => match args.next() {
+                    Some(value) => value,
+                    None => engine_bail!("galaxy3d::Config",
+                        "Missing value for {}{}: expected {}", ARG_PREFIX, name, entry.expected),
+                },
+            };
+            entry.set(self, &value, &format!("{}{}", ARG_PREFIX, name))?;
+        }
+        Ok(unknown)
+    }
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "on" | "true" | "yes" | "1" => Some(true),
+        "off" | "false" | "no" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+fn parse_size(value: &str) -> Option<(u32, u32)> {
+    let (width, height) = value.to_ascii_lowercase().split_once('x')
+        .map(|(w, h)| (w.trim().parse::<u32>(), h.trim().parse::<u32>()))?;
+    match (width, height) {
+        (Ok(width), Ok(height)) if width > 0 && height > 0 => Some((width, height)),
+        _ => None,
+    }
+}
+
+fn parse_quality(value: &str) -> Option<QualityPreset> {
+    match value.to_ascii_lowercase().as_str() {
+        "low" => Some(QualityPreset::Low),
+        "medium" => Some(QualityPreset::Medium),
+        "high" => Some(QualityPreset::High),
+        "ultra" => Some(QualityPreset::Ultra),
+        _ => None,
+    }
+}
+
+fn parse_platform(value: &str) -> Option<PlatformProfile> {
+    match value.to_ascii_lowercase().as_str() {
+        "desktop" => Some(PlatformProfile::Desktop),
+        "steamdeck" | "steam-deck" => Some(PlatformProfile::SteamDeck),
+        "mobile" => Some(PlatformProfile::Mobile),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+#[path = "config_overrides_tests.rs"]
+mod tests;
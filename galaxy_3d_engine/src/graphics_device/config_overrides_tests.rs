@@ -0,0 +1,119 @@
+use super::*;
+use std::collections::HashMap;
+
+fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+    let vars: HashMap<String, String> =
+        vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+    move |name| vars.get(name).cloned()
+}
+
+// ============================================================================
+// Environment
+// ============================================================================
+
+#[test]
+fn test_env_overrides_every_field() {
+    let mut config = Config { enable_validation: true, ..Default::default() };
+    config.apply_env_with(env(&[
+        ("GALAXY3D_VALIDATION", "off"),
+        ("GALAXY3D_BACKEND", "Vulkan"),
+        ("GALAXY3D_ADAPTER", "1"),
+        ("GALAXY3D_WINDOW_SIZE", "1280x720"),
+        ("GALAXY3D_QUALITY", "ULTRA"),
+        ("GALAXY3D_PLATFORM", "SteamDeck"),
+    ])).unwrap();
+
+    assert!(!config.enable_validation);
+    assert_eq!(config.backend.as_deref(), Some("vulkan"));
+    assert_eq!(config.adapter_index, Some(1));
+    assert_eq!(config.window_size, Some((1280, 720)));
+    assert_eq!(config.quality_preset, Some(QualityPreset::Ultra));
+    assert_eq!(config.platform_profile, PlatformProfile::SteamDeck);
+}
+
+#[test]
+fn test_env_unset_or_empty_keeps_application_values() {
+    let mut config = Config { adapter_index: Some(2), ..Default::default() };
+    config.apply_env_with(env(&[("GALAXY3D_ADAPTER", " ")])).unwrap();
+    assert_eq!(config.adapter_index, Some(2));
+    assert!(config.window_size.is_none());
+}
+
+#[test]
+fn test_env_invalid_values_are_errors() {
+    for (name, value) in [
+        ("GALAXY3D_VALIDATION", "maybe"),
+        ("GALAXY3D_ADAPTER", "-1"),
+        ("GALAXY3D_WINDOW_SIZE", "1280"),
+        ("GALAXY3D_WINDOW_SIZE", "0x720"),
+        ("GALAXY3D_QUALITY", "extreme"),
+        ("GALAXY3D_PLATFORM", "console"),
+    ] {
+        let mut config = Config::default();
+        assert!(config.apply_env_with(env(&[(name, value)])).is_err(), "{}={}", name, value);
+    }
+}
+
+// ============================================================================
+// Command line
+// ============================================================================
+
+#[test]
+fn test_args_both_value_forms() {
+    let mut config = Config::default();
+    let rest = config.apply_args([
+        "--adapter", "3", "--window-size=800X600", "--quality", "low", "--backend=vulkan",
+        "--platform", "mobile",
+    ]).unwrap();
+
+    assert!(rest.is_empty());
+    assert_eq!(config.adapter_index, Some(3));
+    assert_eq!(config.window_size, Some((800, 600)));
+    assert_eq!(config.quality_preset, Some(QualityPreset::Low));
+    assert_eq!(config.backend.as_deref(), Some("vulkan"));
+    assert_eq!(config.platform_profile, PlatformProfile::Mobile);
+}
+
+#[test]
+fn test_args_validation_flags() {
+    let mut config = Config { enable_validation: false, ..Default::default() };
+    config.apply_args(["--validation"]).unwrap();
+    assert!(config.enable_validation);
+    config.apply_args(["--no-validation"]).unwrap();
+    assert!(!config.enable_validation);
+    config.apply_args(["--validation=yes"]).unwrap();
+    assert!(config.enable_validation);
+}
+
+#[test]
+fn test_args_unknown_are_returned_in_order() {
+    let mut config = Config::default();
+    let rest = config.apply_args([
+        "level1", "--fullscreen", "--adapter=1", "--", "--adapter=2",
+    ]).unwrap();
+
+    assert_eq!(rest, vec!["level1", "--fullscreen", "--", "--adapter=2"]);
+    assert_eq!(config.adapter_index, Some(1));
+}
+
+#[test]
+fn test_args_missing_or_invalid_value_is_error() {
+    let mut config = Config::default();
+    assert!(config.apply_args(["--quality"]).is_err());
+    assert!(config.apply_args(["--adapter", "first"]).is_err());
+    assert!(config.apply_args(["--backend="]).is_err());
+    assert!(config.backend.is_none());
+}
+
+// ============================================================================
+// Precedence
+// ============================================================================
+
+#[test]
+fn test_args_override_env_override_application() {
+    let mut config = Config { quality_preset: Some(QualityPreset::High), ..Default::default() };
+    config.apply_env_with(env(&[("GALAXY3D_QUALITY", "medium")])).unwrap();
+    assert_eq!(config.quality_preset, Some(QualityPreset::Medium));
+    config.apply_args(["--quality=low"]).unwrap();
+    assert_eq!(config.quality_preset, Some(QualityPreset::Low));
+}
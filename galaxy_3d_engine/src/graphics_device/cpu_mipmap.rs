@@ -0,0 +1,233 @@
+//! CPU mipmap generation for `MipmapMode::GenerateCpu`.
+//!
+//! Each level is downsampled from the previous one (kept in f32 between
+//! levels) with a separable filter. Filtering happens in linear space (sRGB
+//! formats are decoded first) on premultiplied alpha, so transparent texels
+//! do not bleed their color into their neighbours. Output rows of each pass
+//! are split across scoped threads.
+
+use crate::error::Result;
+use crate::engine_bail;
+use super::texture::{MipFilter, TextureFormat};
+
+/// Half-width of the Kaiser-windowed sinc, in destination texels
+const KAISER_RADIUS: f32 = 2.0;
+/// Kaiser window shape parameter (higher = smoother, less ringing)
+const KAISER_ALPHA: f32 = 4.0;
+/// Below this number of output texels a pass runs on the calling thread
+const PARALLEL_MIN_TEXELS: usize = 64 * 64;
+
+type Texel = [f32; 4];
+
+/// Generate mip levels 1..`mip_levels` of one RGBA8 image.
+///
+/// Returns one byte buffer per generated level (level 1 first), each of
+/// size `(width >> level).max(1) * (height >> level).max(1) * 4`.
+///
+/// # Errors
+///
+/// Returns an error if the format is not an 8-bit RGBA/BGRA format or if
+/// `data` does not match the dimensions.
+pub(crate) fn generate_mip_chain(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    format: TextureFormat,
+    filter: MipFilter,
+    mip_levels: u32,
+) -> Result<Vec<Vec<u8>>> {
+    let srgb = match format {
+        TextureFormat::R8G8B8A8_UNORM | TextureFormat::B8G8R8A8_UNORM => false,
+        TextureFormat::R8G8B8A8_SRGB | TextureFormat::B8G8R8A8_SRGB => true,
+        _ => engine_bail!("galaxy3d::Texture",
+            "CPU mipmap generation does not support {:?} (8-bit RGBA/BGRA only)", format),
+    };
+    let expected = width as usize * height as usize * 4;
+    if data.len() != expected {
+        engine_bail!("galaxy3d::Texture",
+            "CPU mipmap generation: expected {} bytes for {}x{}, got {}",
+            expected, width, height, data.len());
+    }
+
+    let mut level: Vec<Texel> = data.chunks_exact(4).map(|p| decode(p, srgb)).collect();
+    let (mut w, mut h) = (width as usize, height as usize);
+    let mut mips = Vec::with_capacity(mip_levels.saturating_sub(1) as usize);
+    for mip in 1..mip_levels {
+        let dst_w = (width >> mip).max(1) as usize;
+        let dst_h = (height >> mip).max(1) as usize;
+        level = downsample(&level, w, h, dst_w, dst_h, filter);
+        mips.push(level.iter().flat_map(|t| encode(t, srgb)).collect());
+        (w, h) = (dst_w, dst_h);
+    }
+    Ok(mips)
+}
+
+// ===== COLOR CONVERSION =====
+
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+}
+
+fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.0031308 { c * 12.92 } else { 1.055 * c.powf(1.0 / 2.4) - 0.055 }
+}
+
+/// Bytes to linear premultiplied texel
+fn decode(p: &[u8], srgb: bool) -> Texel {
+    let a = p[3] as f32 / 255.0;
+    let channel = |b: u8| {
+        let c = b as f32 / 255.0;
+        if srgb { srgb_to_linear(c) * a } else { c * a }
+    };
+    [channel(p[0]), channel(p[1]), channel(p[2]), a]
+}
+
+/// Linear premultiplied texel to bytes (clamped: Kaiser lobes overshoot)
+fn encode(t: &Texel, srgb: bool) -> [u8; 4] {
+    let a = t[3].clamp(0.0, 1.0);
+    let channel = |c: f32| {
+        let c = if a > 0.0 { (c / a).clamp(0.0, 1.0) } else { 0.0 };
+        let c = if srgb { linear_to_srgb(c) } else { c };
+        (c * 255.0).round() as u8
+    };
+    [channel(t[0]), channel(t[1]), channel(t[2]), (a * 255.0).round() as u8]
+}
+
+// ===== FILTERING =====
+
+/// Zeroth-order modified Bessel function of the first kind (series)
+fn bessel_i0(x: f32) -> f32 {
+    let mut sum = 1.0;
+    let mut term = 1.0;
+    let half_sq = x * x / 4.0;
+    for k in 1..32 {
+        term *= half_sq / (k * k) as f32;
+        sum += term;
+        if term < sum * 1e-7 {
+            break;
+        }
+    }
+    sum
+}
+
+/// Filter weight at distance `d`, in destination texels
+fn filter_weight(filter: MipFilter, d: f32) -> f32 {
+    match filter {
+        MipFilter::Box => if d.abs() < 0.5 { 1.0 } else { 0.0 },
+        MipFilter::Kaiser => {
+            let d = d.abs();
+            if d >= KAISER_RADIUS {
+                return 0.0;
+            }
+            let sinc = if d < 1e-6 {
+                1.0
+            } else {
+                let x = std::f32::consts::PI * d;
+                x.sin() / x
+            };
+            let r = d / KAISER_RADIUS;
+            sinc * bessel_i0(KAISER_ALPHA * (1.0 - r * r).sqrt()) / bessel_i0(KAISER_ALPHA)
+        }
+    }
+}
+
+fn filter_support(filter: MipFilter) -> f32 {
+    match filter {
+        MipFilter::Box => 0.5,
+        MipFilter::Kaiser => KAISER_RADIUS,
+    }
+}
+
+/// Normalized `(source index, weight)` taps of every destination texel along
+/// one axis, with edge clamping.
+fn axis_taps(src_len: usize, dst_len: usize, filter: MipFilter) -> Vec<Vec<(usize, f32)>> {
+    let scale = src_len as f32 / dst_len as f32;
+    let reach = filter_support(filter) * scale;
+    (0..dst_len).map(|x| {
+        let center = (x as f32 + 0.5) * scale;
+        let first = (center - reach).floor() as i64;
+        let last = (center + reach).ceil() as i64;
+        let mut taps: Vec<(usize, f32)> = (first..=last).filter_map(|i| {
+            let w = filter_weight(filter, (i as f32 + 0.5 - center) / scale);
+            (w != 0.0).then(|| (i.clamp(0, src_len as i64 - 1) as usize, w))
+        }).collect();
+        let sum: f32 = taps.iter().map(|t| t.1).sum();
+        if sum.abs() < 1e-6 {
+            taps = vec![((center as usize).min(src_len - 1), 1.0)];
+        } else {
+            taps.iter_mut().for_each(|t| t.1 /= sum);
+        }
+        taps
+    }).collect()
+}
+
+/// Separable resize: horizontal pass, then vertical pass.
+fn downsample(
+    src: &[Texel],
+    src_w: usize,
+    src_h: usize,
+    dst_w: usize,
+    dst_h: usize,
+    filter: MipFilter,
+) -> Vec<Texel> {
+    let x_taps = axis_taps(src_w, dst_w, filter);
+    let y_taps = axis_taps(src_h, dst_h, filter);
+
+    let mut tmp = vec![[0.0; 4]; dst_w * src_h];
+    for_each_row(&mut tmp, dst_w, |y, row| {
+        let src_row = &src[y * src_w..(y + 1) * src_w];
+        for (out, taps) in row.iter_mut().zip(&x_taps) {
+            *out = accumulate(taps.iter().map(|&(i, w)| (&src_row[i], w)));
+        }
+    });
+
+    let mut dst = vec![[0.0; 4]; dst_w * dst_h];
+    for_each_row(&mut dst, dst_w, |y, row| {
+        for (x, out) in row.iter_mut().enumerate() {
+            *out = accumulate(y_taps[y].iter().map(|&(j, w)| (&tmp[j * dst_w + x], w)));
+        }
+    });
+    dst
+}
+
+fn accumulate<'a>(taps: impl Iterator<Item = (&'a Texel, f32)>) -> Texel {
+    let mut acc = [0.0; 4];
+    for (t, w) in taps {
+        for c in 0..4 {
+            acc[c] += t[c] * w;
+        }
+    }
+    acc
+}
+
+/// Run `f(row_index, row)` over every row of `out`, split across threads
+/// for large images.
+fn for_each_row<F>(out: &mut [Texel], row_len: usize, f: F)
+where
+    F: Fn(usize, &mut [Texel]) + Sync,
+{
+    let rows = out.len() / row_len;
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get()).min(rows);
+    if out.len() < PARALLEL_MIN_TEXELS || threads <= 1 {
+        for (y, row) in out.chunks_mut(row_len).enumerate() {
+            f(y, row);
+        }
+        return;
+    }
+
+    let rows_per_thread = rows.div_ceil(threads);
+    std::thread::scope(|scope| {
+        for (chunk_index, chunk) in out.chunks_mut(rows_per_thread * row_len).enumerate() {
+            let f = &f;
+            scope.spawn(move || {
+                for (i, row) in chunk.chunks_mut(row_len).enumerate() {
+                    f(chunk_index * rows_per_thread + i, row);
+                }
+            });
+        }
+    });
+}
+
+#[cfg(test)]
+#[path = "cpu_mipmap_tests.rs"]
+mod tests;
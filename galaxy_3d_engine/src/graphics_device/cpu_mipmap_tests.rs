@@ -0,0 +1,93 @@
+//! Unit tests for CPU mipmap generation.
+
+use super::*;
+
+fn solid(width: u32, height: u32, rgba: [u8; 4]) -> Vec<u8> {
+    (0..width * height).flat_map(|_| rgba).collect()
+}
+
+// ============================================================================
+// CHAIN SHAPE
+// ============================================================================
+
+#[test]
+fn test_chain_level_sizes_non_square() {
+    let data = solid(8, 2, [10, 20, 30, 255]);
+    let mips = generate_mip_chain(&data, 8, 2, TextureFormat::R8G8B8A8_UNORM, MipFilter::Box, 4).unwrap();
+    // 4x1, 2x1, 1x1
+    let sizes: Vec<usize> = mips.iter().map(|m| m.len()).collect();
+    assert_eq!(sizes, vec![4 * 4, 2 * 4, 4]);
+}
+
+#[test]
+fn test_single_level_generates_nothing() {
+    let data = solid(4, 4, [0, 0, 0, 255]);
+    let mips = generate_mip_chain(&data, 4, 4, TextureFormat::R8G8B8A8_UNORM, MipFilter::Kaiser, 1).unwrap();
+    assert!(mips.is_empty());
+}
+
+#[test]
+fn test_solid_color_preserved_by_both_filters() {
+    for filter in [MipFilter::Box, MipFilter::Kaiser] {
+        let data = solid(16, 16, [200, 100, 50, 255]);
+        let mips = generate_mip_chain(&data, 16, 16, TextureFormat::R8G8B8A8_SRGB, filter, 5).unwrap();
+        for mip in &mips {
+            for texel in mip.chunks_exact(4) {
+                assert_eq!(texel, &[200, 100, 50, 255], "{:?}", filter);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_large_image_parallel_path_matches_solid_color() {
+    let data = solid(256, 256, [64, 128, 192, 255]);
+    let mips = generate_mip_chain(&data, 256, 256, TextureFormat::B8G8R8A8_UNORM, MipFilter::Kaiser, 2).unwrap();
+    assert_eq!(mips[0].len(), 128 * 128 * 4);
+    assert!(mips[0].chunks_exact(4).all(|t| t == [64, 128, 192, 255]));
+}
+
+// ============================================================================
+// COLOR SPACE AND ALPHA
+// ============================================================================
+
+#[test]
+fn test_box_unorm_averages_bytes() {
+    // Black and white columns -> mid grey in UNORM
+    let data: Vec<u8> = [[0, 0, 0, 255], [255, 255, 255, 255]].concat();
+    let mips = generate_mip_chain(&data, 2, 1, TextureFormat::R8G8B8A8_UNORM, MipFilter::Box, 2).unwrap();
+    assert_eq!(mips[0], vec![128, 128, 128, 255]);
+}
+
+#[test]
+fn test_box_srgb_averages_in_linear_space() {
+    // 50% linear coverage encodes to ~188 in sRGB, not 128
+    let data: Vec<u8> = [[0, 0, 0, 255], [255, 255, 255, 255]].concat();
+    let mips = generate_mip_chain(&data, 2, 1, TextureFormat::R8G8B8A8_SRGB, MipFilter::Box, 2).unwrap();
+    assert_eq!(mips[0][0], 188);
+    assert_eq!(mips[0][3], 255);
+}
+
+#[test]
+fn test_alpha_weighted_transparent_texels_do_not_bleed() {
+    // Opaque red next to fully transparent green: color stays red
+    let data: Vec<u8> = [[255, 0, 0, 255], [0, 255, 0, 0]].concat();
+    let mips = generate_mip_chain(&data, 2, 1, TextureFormat::R8G8B8A8_UNORM, MipFilter::Box, 2).unwrap();
+    assert_eq!(mips[0], vec![255, 0, 0, 128]);
+}
+
+// ============================================================================
+// ERRORS
+// ============================================================================
+
+#[test]
+fn test_unsupported_format_fails() {
+    let data = vec![0u8; 4 * 4 * 8];
+    assert!(generate_mip_chain(&data, 4, 4, TextureFormat::R16G16B16A16_SFLOAT, MipFilter::Box, 3).is_err());
+}
+
+#[test]
+fn test_data_size_mismatch_fails() {
+    let data = vec![0u8; 10];
+    assert!(generate_mip_chain(&data, 4, 4, TextureFormat::R8G8B8A8_UNORM, MipFilter::Box, 3).is_err());
+}
@@ -12,10 +12,14 @@ use crate::graphics_device::{
     CommandList, RenderPass, Swapchain,
     RenderPassDesc,
     Framebuffer, FramebufferDesc,
+    PlatformProfile,
+    AccelerationStructure, BlasDesc, TlasDesc, RayTracingPipelineDesc,
+    QueryPool, QueryPoolDesc,
 };
 
 // Import error types from crate root
 use crate::error::{Error, Result};
+use crate::perf_advisor::QualityPreset;
 
 /// Debug severity level for validation messages
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -120,6 +124,8 @@ pub struct BindlessConfig {
     pub max_texture_3d: u32,
     /// Maximum number of 2D array textures (default: 64)
     pub max_texture_2d_array: u32,
+    /// Maximum number of cubemap array textures (default: 16)
+    pub max_texture_cube_array: u32,
 }
 
 impl Default for BindlessConfig {
@@ -129,6 +135,45 @@ impl Default for BindlessConfig {
             max_texture_cube: 64,
             max_texture_3d: 16,
             max_texture_2d_array: 64,
+            max_texture_cube_array: 16,
+        }
+    }
+}
+
+/// Descriptor pool sizing
+///
+/// Binding groups are allocated from pools of this size. A new pool is
+/// created when the current one is exhausted, so the sizes trade memory
+/// for fewer pools. Zero descriptor counts leave the type out of the pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DescriptorPoolConfig {
+    /// Maximum number of descriptor sets per pool (default: 1024)
+    pub max_sets: u32,
+    /// Combined image samplers per pool (default: 2048)
+    pub combined_image_samplers: u32,
+    /// Uniform buffers per pool (default: 1024)
+    pub uniform_buffers: u32,
+    /// Dynamic uniform buffers per pool (default: 256)
+    pub uniform_buffers_dynamic: u32,
+    /// Storage buffers per pool (default: 1024)
+    pub storage_buffers: u32,
+    /// Storage images per pool (default: 256)
+    pub storage_images: u32,
+    /// Acceleration structures per pool, only with `Config::ray_tracing`
+    /// (default: 256)
+    pub acceleration_structures: u32,
+}
+
+impl Default for DescriptorPoolConfig {
+    fn default() -> Self {
+        Self {
+            max_sets: 1024,
+            combined_image_samplers: 2048,
+            uniform_buffers: 1024,
+            uniform_buffers_dynamic: 256,
+            storage_buffers: 1024,
+            storage_images: 256,
+            acceleration_structures: 256,
         }
     }
 }
@@ -156,6 +201,35 @@ pub struct Config {
     pub enable_validation_stats: bool,
     /// Bindless texture table sizes
     pub bindless: BindlessConfig,
+    /// Requested backend name (e.g. "vulkan"). A backend refuses a config
+    /// naming another one; None accepts any.
+    pub backend: Option<String>,
+    /// GPU to use, in the backend enumeration order (None: the first one)
+    pub adapter_index: Option<usize>,
+    /// Requested window size in pixels. The engine does not own the window:
+    /// the application reads it when creating its window.
+    pub window_size: Option<(u32, u32)>,
+    /// Requested quality preset, applied by the application (see
+    /// `PerformanceAdvisor`)
+    pub quality_preset: Option<QualityPreset>,
+    /// Target device class. The backend clamps sampler anisotropy with it;
+    /// pass it to `ResourceManager::set_platform_profile()` to clamp
+    /// textures and shadow maps.
+    pub platform_profile: PlatformProfile,
+    /// Enable hardware ray tracing (acceleration structures, ray tracing
+    /// pipelines) when the device supports it. Off by default: it makes
+    /// vertex and index buffers addressable by the GPU.
+    pub ray_tracing: bool,
+    /// Report barriers the backend finds redundant (two reads in the same
+    /// layout) and declared previous accesses that disagree with the
+    /// command list's tracked state. Off by default: one warning per case.
+    pub validate_barriers: bool,
+    /// Sizes of the pools of `create_binding_group` and
+    /// `create_binding_group_from_layout`
+    pub descriptor_pools: DescriptorPoolConfig,
+    /// Sizes of the pools of `create_transient_binding_group`, recycled by
+    /// `reset_transient_pools`
+    pub transient_descriptor_pools: DescriptorPoolConfig,
 }
 
 impl Default for Config {
@@ -171,19 +245,86 @@ impl Default for Config {
             panic_on_error: false,
             enable_validation_stats: cfg!(debug_assertions),
             bindless: BindlessConfig::default(),
+            backend: None,
+            adapter_index: None,
+            window_size: None,
+            quality_preset: None,
+            platform_profile: PlatformProfile::Desktop,
+            ray_tracing: false,
+            validate_barriers: false,
+            descriptor_pools: DescriptorPoolConfig::default(),
+            transient_descriptor_pools: DescriptorPoolConfig {
+                max_sets: 256,
+                combined_image_samplers: 512,
+                uniform_buffers: 256,
+                uniform_buffers_dynamic: 64,
+                storage_buffers: 256,
+                storage_images: 64,
+                acceleration_structures: 64,
+            },
         }
     }
 }
 
 /// Graphics device statistics
+///
+/// Per-frame counters accumulate from the last `GraphicsDevice::reset_stats()`;
+/// memory figures describe the live resources.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct GraphicsDeviceStats {
     /// Number of draw calls this frame
     pub draw_calls: u32,
-    /// Number of triangles drawn this frame
+    /// Number of triangles drawn this frame (all instances included)
     pub triangles: u32,
-    /// GPU memory used (bytes)
+    /// Number of pipeline binds this frame
+    pub pipeline_binds: u32,
+    /// Number of descriptor sets allocated this frame
+    pub descriptor_sets_allocated: u32,
+    /// Number of queue submits this frame
+    pub submits: u32,
+    /// GPU memory used by allocations (bytes)
     pub gpu_memory_used: u64,
+    /// GPU memory reserved by the allocator, including free space in its
+    /// memory blocks (bytes)
+    pub gpu_memory_reserved: u64,
+    /// Memory of the live buffers (bytes)
+    pub buffer_memory: u64,
+    /// Memory of the live textures (bytes)
+    pub texture_memory: u64,
+    /// Number of descriptor pools, transient ones included
+    pub descriptor_pools: u32,
+    /// Descriptor sets held by all the pools (see `DescriptorPoolUsage`)
+    pub descriptor_sets_in_use: u32,
+    /// Descriptor set capacity of all the pools
+    pub descriptor_set_capacity: u32,
+    /// Descriptor sets held by the transient pools
+    pub transient_descriptor_sets: u32,
+}
+
+/// Usage of one descriptor pool (`GraphicsDevice::descriptor_pool_usage`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DescriptorPoolUsage {
+    /// Pool of `create_transient_binding_group`
+    pub transient: bool,
+    /// Descriptor sets allocated from the pool and not reclaimed yet. The
+    /// set of a dropped binding group stays allocated until its pool is
+    /// reset (transient pools) or destroyed with the device.
+    pub allocated_sets: u32,
+    /// Descriptor set capacity of the pool
+    pub max_sets: u32,
+}
+
+impl GraphicsDeviceStats {
+    /// Fill the descriptor pool counters from per-pool usage
+    pub fn set_descriptor_pool_usage(&mut self, pools: &[DescriptorPoolUsage]) {
+        self.descriptor_pools = pools.len() as u32;
+        self.descriptor_sets_in_use = pools.iter().map(|pool| pool.allocated_sets).sum();
+        self.descriptor_set_capacity = pools.iter().map(|pool| pool.max_sets).sum();
+        self.transient_descriptor_sets = pools.iter()
+            .filter(|pool| pool.transient)
+            .map(|pool| pool.allocated_sets)
+            .sum();
+    }
 }
 
 // ============================================================================
@@ -246,6 +387,96 @@ pub trait GraphicsDevice: Send + Sync {
         fragment_shader: &Arc<dyn Shader>,
     ) -> Result<Arc<dyn Pipeline>>;
 
+    /// Whether the device runs mesh shader pipelines (`create_mesh_pipeline`,
+    /// `CommandList::draw_mesh_tasks`)
+    fn supports_mesh_shaders(&self) -> bool;
+
+    /// Create a mesh shader pipeline
+    ///
+    /// The task and mesh stages replace the vertex input and the vertex
+    /// stage: `desc.vertex_layout` is ignored and `desc.topology` only
+    /// feeds the triangle statistics. Draw it with
+    /// `CommandList::draw_mesh_tasks`.
+    ///
+    /// # Arguments
+    ///
+    /// * `desc` - Pipeline descriptor (without shaders)
+    /// * `task_shader` - Optional task shader (e.g. meshlet culling)
+    /// * `mesh_shader` - Mesh shader
+    /// * `fragment_shader` - Fragment shader
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `supports_mesh_shaders()` is false.
+    fn create_mesh_pipeline(
+        &mut self,
+        desc: PipelineDesc,
+        task_shader: Option<&Arc<dyn Shader>>,
+        mesh_shader: &Arc<dyn Shader>,
+        fragment_shader: &Arc<dyn Shader>,
+    ) -> Result<Arc<dyn Pipeline>>;
+
+    /// Whether hardware ray tracing is enabled (`Config::ray_tracing` and
+    /// device support): acceleration structures, `create_ray_tracing_pipeline`,
+    /// `CommandList::trace_rays`
+    fn supports_ray_tracing(&self) -> bool;
+
+    /// Alignment of the dynamic offsets of uniform buffers, in bytes
+    /// (`minUniformBufferOffsetAlignment` in Vulkan, a power of two)
+    fn uniform_buffer_alignment(&self) -> u64;
+
+    /// Whether pipeline statistics queries are available
+    /// (`QueryType::PipelineStatistics`). Occlusion queries always are.
+    fn supports_pipeline_statistics_queries(&self) -> bool;
+
+    /// Create a pool of `desc.count` queries of one type
+    ///
+    /// Record the queries with `CommandList::reset_queries`, `begin_query`
+    /// and `end_query`, then read them from the pool.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `desc.count` is 0, or for pipeline statistics
+    /// queries when `supports_pipeline_statistics_queries()` is false.
+    fn create_query_pool(&mut self, desc: &QueryPoolDesc) -> Result<Arc<dyn QueryPool>>;
+
+    /// Create and build a bottom-level acceleration structure
+    ///
+    /// The build is submitted and waited for before returning (like texture
+    /// uploads): create BLASes at load time, not per frame. The vertex and
+    /// index buffers are only read during the build.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `supports_ray_tracing()` is false or `desc` has
+    /// no geometry.
+    fn create_blas(&mut self, desc: &BlasDesc) -> Result<Arc<dyn AccelerationStructure>>;
+
+    /// Create a top-level acceleration structure for up to
+    /// `desc.max_instances` instances
+    ///
+    /// The structure is empty until built with `CommandList::build_tlas`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `supports_ray_tracing()` is false.
+    fn create_tlas(&mut self, desc: &TlasDesc) -> Result<Arc<dyn AccelerationStructure>>;
+
+    /// Create a ray tracing pipeline and its shader binding table
+    ///
+    /// Bind it with `CommandList::bind_pipeline` and launch it with
+    /// `CommandList::trace_rays`. Binding groups are created from it as for
+    /// graphics pipelines.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `supports_ray_tracing()` is false or a shader
+    /// does not match its slot (ray generation, miss, closest hit, any hit).
+    fn create_ray_tracing_pipeline(
+        &mut self,
+        desc: &RayTracingPipelineDesc,
+    ) -> Result<Arc<dyn Pipeline>>;
+
     /// Create a command list for recording rendering commands
     ///
     /// # Returns
@@ -344,6 +575,28 @@ pub trait GraphicsDevice: Send + Sync {
         resources: &[BindingResource],
     ) -> Result<Arc<dyn BindingGroup>>;
 
+    /// Create a binding group valid for the current frame only
+    ///
+    /// Same as `create_binding_group`, but allocated from the transient
+    /// pools (`Config::transient_descriptor_pools`), which
+    /// `reset_transient_pools()` recycles as a whole. The binding group must
+    /// not be bound after that reset, even if it is still referenced.
+    fn create_transient_binding_group(
+        &self,
+        pipeline: &Arc<dyn Pipeline>,
+        set_index: u32,
+        resources: &[BindingResource],
+    ) -> Result<Arc<dyn BindingGroup>>;
+
+    /// Recycle every descriptor set of the transient binding groups
+    ///
+    /// Waits for the previous submit first, so the GPU no longer reads
+    /// them. `Engine::begin_frame()` calls it for every device.
+    fn reset_transient_pools(&self) -> Result<()>;
+
+    /// Usage of each descriptor pool, persistent pools first
+    fn descriptor_pool_usage(&self) -> Vec<DescriptorPoolUsage>;
+
     /// Wait for all GPU operations to complete
     fn wait_idle(&self) -> Result<()>;
 
@@ -357,6 +610,12 @@ pub trait GraphicsDevice: Send + Sync {
     /// Get statistics about the graphics device
     fn stats(&self) -> GraphicsDeviceStats;
 
+    /// Reset the per-frame counters of `stats()` (draws, triangles,
+    /// pipeline binds, descriptor set allocations, submits).
+    ///
+    /// Call once per frame, typically right after reading `stats()`.
+    fn reset_stats(&self);
+
     /// Notify graphics device that the window has been resized
     ///
     /// # Arguments
@@ -399,6 +658,13 @@ impl GraphicsDevicePluginRegistry {
         self.plugins.insert(name, Box::new(factory));
     }
 
+    /// Names of the registered plugins, sorted
+    pub fn plugin_names(&self) -> Vec<&'static str> {
+        let mut names: Vec<&'static str> = self.plugins.keys().copied().collect();
+        names.sort_unstable();
+        names
+    }
+
     /// Create a graphics device using a registered plugin
     ///
     /// # Arguments
@@ -150,6 +150,7 @@ fn test_bindless_config_default_values() {
     assert_eq!(c.max_texture_cube, 64);
     assert_eq!(c.max_texture_3d, 16);
     assert_eq!(c.max_texture_2d_array, 64);
+    assert_eq!(c.max_texture_cube_array, 16);
 }
 
 #[test]
@@ -159,12 +160,37 @@ fn test_bindless_config_clone_preserves_values() {
         max_texture_cube: 32,
         max_texture_3d: 8,
         max_texture_2d_array: 128,
+        max_texture_cube_array: 4,
     };
     let cloned = c.clone();
     assert_eq!(cloned.max_texture_2d, 1024);
     assert_eq!(cloned.max_texture_cube, 32);
     assert_eq!(cloned.max_texture_3d, 8);
     assert_eq!(cloned.max_texture_2d_array, 128);
+    assert_eq!(cloned.max_texture_cube_array, 4);
+}
+
+// ============================================================================
+// DescriptorPoolConfig
+// ============================================================================
+
+#[test]
+fn test_descriptor_pool_config_default_values() {
+    let c = DescriptorPoolConfig::default();
+    assert_eq!(c.max_sets, 1024);
+    assert_eq!(c.combined_image_samplers, 2048);
+    assert_eq!(c.uniform_buffers, 1024);
+    assert_eq!(c.uniform_buffers_dynamic, 256);
+    assert_eq!(c.storage_buffers, 1024);
+    assert_eq!(c.storage_images, 256);
+    assert_eq!(c.acceleration_structures, 256);
+}
+
+#[test]
+fn test_config_default_descriptor_pools() {
+    let c = Config::default();
+    assert_eq!(c.descriptor_pools, DescriptorPoolConfig::default());
+    assert!(c.transient_descriptor_pools.max_sets < c.descriptor_pools.max_sets);
 }
 
 // ============================================================================
@@ -205,6 +231,18 @@ fn test_config_default_bindless_matches_default() {
     assert_eq!(c.bindless.max_texture_cube, b.max_texture_cube);
 }
 
+#[test]
+fn test_config_default_has_no_overrides() {
+    let c = Config::default();
+    assert!(c.backend.is_none());
+    assert!(c.adapter_index.is_none());
+    assert!(c.window_size.is_none());
+    assert!(c.quality_preset.is_none());
+    assert_eq!(c.platform_profile, PlatformProfile::Desktop);
+    assert!(!c.ray_tracing);
+    assert!(!c.validate_barriers);
+}
+
 #[test]
 fn test_config_clone_preserves_fields() {
     let mut c = Config::default();
@@ -225,11 +263,17 @@ fn test_graphics_device_stats_default_zero() {
     assert_eq!(s.draw_calls, 0);
     assert_eq!(s.triangles, 0);
     assert_eq!(s.gpu_memory_used, 0);
+    assert_eq!(s.pipeline_binds, 0);
+    assert_eq!(s.descriptor_sets_allocated, 0);
+    assert_eq!(s.submits, 0);
+    assert_eq!(s.gpu_memory_reserved, 0);
+    assert_eq!(s.buffer_memory, 0);
+    assert_eq!(s.texture_memory, 0);
 }
 
 #[test]
 fn test_graphics_device_stats_clone_copy() {
-    let s = GraphicsDeviceStats { draw_calls: 100, triangles: 500_000, gpu_memory_used: 1_000_000 };
+    let s = GraphicsDeviceStats { draw_calls: 100, triangles: 500_000, gpu_memory_used: 1_000_000, ..Default::default() };
     let t = s;
     let u = s.clone();
     assert_eq!(s.draw_calls, t.draw_calls);
@@ -237,6 +281,20 @@ fn test_graphics_device_stats_clone_copy() {
     assert_eq!(s.gpu_memory_used, t.gpu_memory_used);
 }
 
+#[test]
+fn test_graphics_device_stats_descriptor_pool_usage() {
+    let mut s = GraphicsDeviceStats::default();
+    s.set_descriptor_pool_usage(&[
+        DescriptorPoolUsage { transient: false, allocated_sets: 1024, max_sets: 1024 },
+        DescriptorPoolUsage { transient: false, allocated_sets: 10, max_sets: 1024 },
+        DescriptorPoolUsage { transient: true, allocated_sets: 7, max_sets: 256 },
+    ]);
+    assert_eq!(s.descriptor_pools, 3);
+    assert_eq!(s.descriptor_sets_in_use, 1041);
+    assert_eq!(s.descriptor_set_capacity, 2304);
+    assert_eq!(s.transient_descriptor_sets, 7);
+}
+
 #[test]
 fn test_validation_stats_total_zero_after_reset() {
     let zero = ValidationStats::default();
@@ -257,7 +315,7 @@ fn test_debug_severity_default_is_set() {
 
 mod plugin_registry {
     use serial_test::serial;
-    use crate::graphics_device::register_graphics_device_plugin;
+    use crate::graphics_device::{register_graphics_device_plugin, graphics_device_plugin_registry};
 
     #[test]
     #[serial]
@@ -281,4 +339,20 @@ mod plugin_registry {
             Err(crate::error::Error::InitializationFailed("c".to_string()))
         });
     }
+
+    #[test]
+    #[serial]
+    fn test_plugin_names_lists_registered_plugins_sorted() {
+        register_graphics_device_plugin("test_plugin_unique_e", |_w, _c| {
+            Err(crate::error::Error::InitializationFailed("e".to_string()))
+        });
+        register_graphics_device_plugin("test_plugin_unique_d", |_w, _c| {
+            Err(crate::error::Error::InitializationFailed("d".to_string()))
+        });
+        let names = graphics_device_plugin_registry().lock().unwrap()
+            .as_ref().unwrap().plugin_names();
+        let d = names.iter().position(|n| *n == "test_plugin_unique_d").unwrap();
+        let e = names.iter().position(|n| *n == "test_plugin_unique_e").unwrap();
+        assert!(d < e);
+    }
 }
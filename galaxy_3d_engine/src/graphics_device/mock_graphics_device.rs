@@ -16,7 +16,10 @@ use crate::graphics_device::{
     BindingResource, BindingGroupLayoutDesc,
     RenderPassDesc, FramebufferDesc, Viewport, Rect2D,
     ClearValue, IndexType, TextureInfo, ImageAccess, BufferAccess,
-    PipelineReflection, DynamicRenderState, ShaderStageFlags,
+    PipelineReflection, DynamicRenderState, ShaderStageFlags, AccessType, TextureCopyRegion, ShaderStage,
+    AccelerationStructure, AccelerationStructureKind, BlasDesc, TlasDesc, TlasInstance,
+    RayTracingPipelineDesc, QueryPool, QueryPoolDesc, QueryType, PipelineStatistics,
+    validate_query_range,
 };
 #[cfg(test)]
 use crate::error::Result;
@@ -45,6 +48,14 @@ impl Buffer for MockBuffer {
         Ok(())
     }
 
+    fn read(&self, offset: u64, data: &mut [u8]) -> Result<()> {
+        if offset + data.len() as u64 > self.size {
+            crate::engine_bail!("galaxy3d::MockBuffer", "read: range out of bounds");
+        }
+        data.fill(0);
+        Ok(())
+    }
+
     fn mapped_ptr(&self) -> Option<*mut u8> {
         None
     }
@@ -60,6 +71,8 @@ pub struct MockTexture {
     pub info: TextureInfo,
     pub name: String,
     pub bindless_index: u32,
+    /// `(layer, mip_level)` of every `update()` call
+    pub updates: Mutex<Vec<(u32, u32)>>,
 }
 
 #[cfg(test)]
@@ -71,6 +84,7 @@ impl MockTexture {
             info: TextureInfo {
                 width,
                 height,
+                depth: 1,
                 format: crate::graphics_device::TextureFormat::R8G8B8A8_UNORM,
                 usage: crate::graphics_device::TextureUsage::Sampled,
                 array_layers,
@@ -80,6 +94,7 @@ impl MockTexture {
             },
             name,
             bindless_index: NEXT_BINDLESS_INDEX.fetch_add(1, Ordering::Relaxed),
+            updates: Mutex::new(Vec::new()),
         }
     }
 }
@@ -93,6 +108,16 @@ impl Texture for MockTexture {
     fn bindless_index(&self) -> u32 {
         self.bindless_index
     }
+
+    fn update(&self, layer: u32, mip_level: u32, data: &[u8]) -> Result<()> {
+        let expected = self.info.mip_byte_size(mip_level);
+        if layer >= self.info.array_layers || expected != Some(data.len()) {
+            crate::engine_bail!("galaxy3d::MockTexture",
+                "update: layer {} / mip {} / {} bytes do not match the texture", layer, mip_level, data.len());
+        }
+        self.updates.lock().unwrap().push((layer, mip_level));
+        Ok(())
+    }
 }
 
 // ============================================================================
@@ -103,12 +128,13 @@ impl Texture for MockTexture {
 #[derive(Debug)]
 pub struct MockShader {
     pub name: String,
+    pub vertex_inputs: Vec<crate::graphics_device::ReflectedVertexInput>,
 }
 
 #[cfg(test)]
 impl MockShader {
     pub fn new(name: String) -> Self {
-        Self { name }
+        Self { name, vertex_inputs: Vec::new() }
     }
 }
 
@@ -120,6 +146,9 @@ impl Shader for MockShader {
     fn reflected_push_constants(&self) -> &[crate::graphics_device::ReflectedPushConstant] {
         &[]
     }
+    fn reflected_vertex_inputs(&self) -> &[crate::graphics_device::ReflectedVertexInput] {
+        &self.vertex_inputs
+    }
 }
 
 // ============================================================================
@@ -149,6 +178,66 @@ impl Pipeline for MockPipeline {
     }
 }
 
+// ============================================================================
+// Mock AccelerationStructure
+// ============================================================================
+
+#[cfg(test)]
+#[derive(Debug)]
+pub struct MockAccelerationStructure {
+    pub kind: AccelerationStructureKind,
+    pub size: u64,
+    pub device_address: u64,
+}
+
+#[cfg(test)]
+impl MockAccelerationStructure {
+    pub fn new(kind: AccelerationStructureKind, size: u64, device_address: u64) -> Self {
+        Self { kind, size, device_address }
+    }
+}
+
+#[cfg(test)]
+impl AccelerationStructure for MockAccelerationStructure {
+    fn kind(&self) -> AccelerationStructureKind { self.kind }
+    fn size(&self) -> u64 { self.size }
+    fn device_address(&self) -> u64 { self.device_address }
+}
+
+// ============================================================================
+// Mock QueryPool
+// ============================================================================
+
+#[cfg(test)]
+#[derive(Debug)]
+pub struct MockQueryPool {
+    pub query_type: QueryType,
+    pub count: u32,
+}
+
+#[cfg(test)]
+impl MockQueryPool {
+    pub fn new(query_type: QueryType, count: u32) -> Self {
+        Self { query_type, count }
+    }
+}
+
+#[cfg(test)]
+impl QueryPool for MockQueryPool {
+    fn query_type(&self) -> QueryType { self.query_type }
+    fn count(&self) -> u32 { self.count }
+
+    fn occlusion_results(&self, first_query: u32, query_count: u32) -> Result<Vec<Option<u64>>> {
+        validate_query_range(self, QueryType::Occlusion, first_query, query_count)?;
+        Ok(vec![Some(0); query_count as usize])
+    }
+
+    fn pipeline_statistics(&self, first_query: u32, query_count: u32) -> Result<Vec<Option<PipelineStatistics>>> {
+        validate_query_range(self, QueryType::PipelineStatistics, first_query, query_count)?;
+        Ok(vec![Some(PipelineStatistics::default()); query_count as usize])
+    }
+}
+
 // ============================================================================
 // Mock CommandList
 // ============================================================================
@@ -195,6 +284,11 @@ impl CommandList for MockCommandList {
         Ok(())
     }
 
+    fn declare_accesses(&mut self, image_accesses: &[ImageAccess], _buffer_accesses: &[BufferAccess]) -> Result<()> {
+        self.commands.push(format!("declare_accesses {}", image_accesses.len()));
+        Ok(())
+    }
+
     fn bind_pipeline(&mut self, _pipeline: &Arc<dyn Pipeline>) -> Result<()> {
         self.commands.push("bind_pipeline".to_string());
         Ok(())
@@ -205,6 +299,11 @@ impl CommandList for MockCommandList {
         Ok(())
     }
 
+    fn bind_vertex_buffer_at(&mut self, binding: u32, _buffer: &Arc<dyn Buffer>, _offset: u64) -> Result<()> {
+        self.commands.push(format!("bind_vertex_buffer_at {}", binding));
+        Ok(())
+    }
+
     fn bind_index_buffer(&mut self, _buffer: &Arc<dyn Buffer>, _offset: u64, _index_type: IndexType) -> Result<()> {
         self.commands.push("bind_index_buffer".to_string());
         Ok(())
@@ -225,6 +324,17 @@ impl CommandList for MockCommandList {
         Ok(())
     }
 
+    fn bind_binding_group_dynamic(
+        &mut self,
+        _pipeline: &Arc<dyn Pipeline>,
+        _set_index: u32,
+        _binding_group: &Arc<dyn BindingGroup>,
+        dynamic_offsets: &[u32],
+    ) -> Result<()> {
+        self.commands.push(format!("bind_binding_group_dynamic {:?}", dynamic_offsets));
+        Ok(())
+    }
+
     fn draw(&mut self, _vertex_count: u32, _first_vertex: u32) -> Result<()> {
         self.commands.push("draw".to_string());
         Ok(())
@@ -235,6 +345,59 @@ impl CommandList for MockCommandList {
         Ok(())
     }
 
+    fn draw_instanced(
+        &mut self,
+        _vertex_count: u32,
+        _first_vertex: u32,
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<()> {
+        self.commands.push(format!("draw_instanced {}@{}", instance_count, first_instance));
+        Ok(())
+    }
+
+    fn draw_indexed_instanced(
+        &mut self,
+        _index_count: u32,
+        _first_index: u32,
+        _vertex_offset: i32,
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<()> {
+        self.commands.push(format!("draw_indexed_instanced {}@{}", instance_count, first_instance));
+        Ok(())
+    }
+
+    fn draw_mesh_tasks(&mut self, group_count_x: u32, _group_count_y: u32, _group_count_z: u32) -> Result<()> {
+        self.commands.push(format!("draw_mesh_tasks {}", group_count_x));
+        Ok(())
+    }
+
+    fn build_tlas(&mut self, _tlas: &Arc<dyn AccelerationStructure>, instances: &[TlasInstance]) -> Result<()> {
+        self.commands.push(format!("build_tlas {}", instances.len()));
+        Ok(())
+    }
+
+    fn trace_rays(&mut self, width: u32, height: u32, _depth: u32) -> Result<()> {
+        self.commands.push(format!("trace_rays {}x{}", width, height));
+        Ok(())
+    }
+
+    fn reset_queries(&mut self, _pool: &Arc<dyn QueryPool>, first_query: u32, query_count: u32) -> Result<()> {
+        self.commands.push(format!("reset_queries {}+{}", first_query, query_count));
+        Ok(())
+    }
+
+    fn begin_query(&mut self, _pool: &Arc<dyn QueryPool>, query: u32) -> Result<()> {
+        self.commands.push(format!("begin_query {}", query));
+        Ok(())
+    }
+
+    fn end_query(&mut self, _pool: &Arc<dyn QueryPool>, query: u32) -> Result<()> {
+        self.commands.push(format!("end_query {}", query));
+        Ok(())
+    }
+
     fn set_viewport(&mut self, _viewport: Viewport) -> Result<()> {
         self.commands.push("set_viewport".to_string());
         Ok(())
@@ -255,6 +418,56 @@ impl CommandList for MockCommandList {
         Ok(())
     }
 
+    fn resolve_texture(&mut self, src: &ImageAccess, dst: &ImageAccess) -> Result<()> {
+        if src.access_type != AccessType::TransferRead || dst.access_type != AccessType::TransferWrite {
+            crate::engine_bail!("galaxy3d::MockCommandList",
+                "resolve_texture: expected TransferRead -> TransferWrite accesses");
+        }
+        src.texture.info().validate_resolve_into(dst.texture.info())?;
+        self.commands.push("resolve_texture".to_string());
+        Ok(())
+    }
+
+    fn copy_texture_to_buffer(
+        &mut self,
+        src: &ImageAccess,
+        _dst: &Arc<dyn Buffer>,
+        regions: &[TextureCopyRegion],
+    ) -> Result<()> {
+        if src.access_type != AccessType::TransferRead {
+            crate::engine_bail!("galaxy3d::MockCommandList",
+                "copy_texture_to_buffer: expected TransferRead source access");
+        }
+        self.commands.push(format!("copy_texture_to_buffer {}", regions.len()));
+        Ok(())
+    }
+
+    fn copy_buffer_to_buffer(
+        &mut self,
+        src: &BufferAccess,
+        _src_offset: u64,
+        _dst: &Arc<dyn Buffer>,
+        _dst_offset: u64,
+        size: u64,
+    ) -> Result<()> {
+        if src.access_type != AccessType::TransferRead {
+            crate::engine_bail!("galaxy3d::MockCommandList",
+                "copy_buffer_to_buffer: expected TransferRead source access");
+        }
+        self.commands.push(format!("copy_buffer_to_buffer {}", size));
+        Ok(())
+    }
+
+    fn begin_debug_label(&mut self, name: &str) -> Result<()> {
+        self.commands.push(format!("begin_debug_label {}", name));
+        Ok(())
+    }
+
+    fn end_debug_label(&mut self) -> Result<()> {
+        self.commands.push("end_debug_label".to_string());
+        Ok(())
+    }
+
 }
 
 // ============================================================================
@@ -400,6 +613,24 @@ pub struct MockGraphicsDevice {
     pub created_shaders: Arc<Mutex<Vec<String>>>,
     /// Track created pipelines
     pub created_pipelines: Arc<Mutex<Vec<String>>>,
+    /// Reflected inputs given to the vertex shaders created from now on
+    pub vertex_shader_inputs: Vec<crate::graphics_device::ReflectedVertexInput>,
+    /// Answer of `supports_mesh_shaders` (false by default)
+    pub mesh_shaders: bool,
+    /// Answer of `supports_ray_tracing` (false by default)
+    pub ray_tracing: bool,
+    /// Answer of `uniform_buffer_alignment` (256 by default)
+    pub uniform_buffer_alignment: u64,
+    /// Answer of `supports_pipeline_statistics_queries` (false by default)
+    pub pipeline_statistics_queries: bool,
+    /// Number of acceleration structures created, also used to give each
+    /// one a distinct device address
+    pub acceleration_structure_count: u64,
+    /// Transient binding groups created since the last
+    /// `reset_transient_pools`
+    pub transient_binding_groups: Arc<Mutex<u32>>,
+    /// Number of `reset_transient_pools` calls
+    pub transient_pool_resets: Arc<Mutex<u32>>,
 }
 
 #[cfg(test)]
@@ -411,6 +642,14 @@ impl MockGraphicsDevice {
             created_textures: Arc::new(Mutex::new(Vec::new())),
             created_shaders: Arc::new(Mutex::new(Vec::new())),
             created_pipelines: Arc::new(Mutex::new(Vec::new())),
+            vertex_shader_inputs: Vec::new(),
+            mesh_shaders: false,
+            ray_tracing: false,
+            uniform_buffer_alignment: 256,
+            pipeline_statistics_queries: false,
+            acceleration_structure_count: 0,
+            transient_binding_groups: Arc::new(Mutex::new(0)),
+            transient_pool_resets: Arc::new(Mutex::new(0)),
         }
     }
 
@@ -438,30 +677,108 @@ impl MockGraphicsDevice {
 #[cfg(test)]
 impl GraphicsDevice for MockGraphicsDevice {
     fn create_texture(&mut self, desc: TextureDesc) -> Result<Arc<dyn Texture>> {
-        let name = format!("texture_{}x{}", desc.width, desc.height);
+        let name = desc.debug_name.clone()
+            .unwrap_or_else(|| format!("texture_{}x{}", desc.width, desc.height));
         self.created_textures.lock().unwrap().push(name.clone());
-        Ok(Arc::new(MockTexture::new(desc.width, desc.height, desc.array_layers, desc.texture_type, name)))
+        let mut texture = MockTexture::new(desc.width, desc.height, desc.array_layers, desc.texture_type, name);
+        texture.info.depth = desc.depth.max(1);
+        texture.info.format = desc.format;
+        texture.info.usage = desc.usage;
+        texture.info.sample_count = desc.sample_count;
+        texture.info.mip_levels = desc.mipmap.mip_levels(desc.width, desc.height);
+        Ok(Arc::new(texture))
     }
 
     fn create_buffer(&mut self, desc: BufferDesc) -> Result<Arc<dyn Buffer>> {
-        let name = format!("buffer_{}", desc.size);
+        let name = desc.debug_name.clone().unwrap_or_else(|| format!("buffer_{}", desc.size));
         self.created_buffers.lock().unwrap().push(name.clone());
         Ok(Arc::new(MockBuffer::new(desc.size, name)))
     }
 
     fn create_shader(&mut self, desc: ShaderDesc) -> Result<Arc<dyn Shader>> {
-        let name = format!("shader_{:?}", desc.stage);
+        let name = desc.debug_name.clone().unwrap_or_else(|| format!("shader_{:?}", desc.stage));
         self.created_shaders.lock().unwrap().push(name.clone());
-        Ok(Arc::new(MockShader::new(name)))
+        let mut shader = MockShader::new(name);
+        if desc.stage == ShaderStage::Vertex {
+            shader.vertex_inputs = self.vertex_shader_inputs.clone();
+        }
+        Ok(Arc::new(shader))
     }
 
     fn create_pipeline(
         &mut self,
-        _desc: PipelineDesc,
+        desc: PipelineDesc,
         _vertex_shader: &Arc<dyn Shader>,
         _fragment_shader: &Arc<dyn Shader>,
     ) -> Result<Arc<dyn Pipeline>> {
-        let name = "pipeline".to_string();
+        let name = desc.debug_name.unwrap_or_else(|| "pipeline".to_string());
+        self.created_pipelines.lock().unwrap().push(name.clone());
+        Ok(Arc::new(MockPipeline::new(name)))
+    }
+
+    fn supports_mesh_shaders(&self) -> bool {
+        self.mesh_shaders
+    }
+
+    fn create_mesh_pipeline(
+        &mut self,
+        desc: PipelineDesc,
+        _task_shader: Option<&Arc<dyn Shader>>,
+        _mesh_shader: &Arc<dyn Shader>,
+        _fragment_shader: &Arc<dyn Shader>,
+    ) -> Result<Arc<dyn Pipeline>> {
+        if !self.mesh_shaders {
+            crate::engine_bail!("galaxy3d::MockGraphicsDevice", "Mesh shaders are not supported");
+        }
+        let name = desc.debug_name.unwrap_or_else(|| "mesh_pipeline".to_string());
+        self.created_pipelines.lock().unwrap().push(name.clone());
+        Ok(Arc::new(MockPipeline::new(name)))
+    }
+
+    fn supports_ray_tracing(&self) -> bool {
+        self.ray_tracing
+    }
+
+    fn uniform_buffer_alignment(&self) -> u64 {
+        self.uniform_buffer_alignment
+    }
+
+    fn supports_pipeline_statistics_queries(&self) -> bool {
+        self.pipeline_statistics_queries
+    }
+
+    fn create_query_pool(&mut self, desc: &QueryPoolDesc) -> Result<Arc<dyn QueryPool>> {
+        desc.validate()?;
+        if desc.query_type == QueryType::PipelineStatistics && !self.pipeline_statistics_queries {
+            crate::engine_bail!("galaxy3d::MockGraphicsDevice", "Pipeline statistics queries are not supported");
+        }
+        Ok(Arc::new(MockQueryPool::new(desc.query_type, desc.count)))
+    }
+
+    fn create_blas(&mut self, desc: &BlasDesc) -> Result<Arc<dyn AccelerationStructure>> {
+        if !self.ray_tracing || desc.geometries.is_empty() {
+            crate::engine_bail!("galaxy3d::MockGraphicsDevice", "Cannot create BLAS");
+        }
+        let triangles: u64 = desc.geometries.iter().map(|g| g.triangle_count as u64).sum();
+        self.acceleration_structure_count += 1;
+        Ok(Arc::new(MockAccelerationStructure::new(
+            AccelerationStructureKind::BottomLevel, triangles * 64, self.acceleration_structure_count << 16)))
+    }
+
+    fn create_tlas(&mut self, desc: &TlasDesc) -> Result<Arc<dyn AccelerationStructure>> {
+        if !self.ray_tracing {
+            crate::engine_bail!("galaxy3d::MockGraphicsDevice", "Ray tracing is not supported");
+        }
+        self.acceleration_structure_count += 1;
+        Ok(Arc::new(MockAccelerationStructure::new(
+            AccelerationStructureKind::TopLevel, desc.max_instances as u64 * 128, self.acceleration_structure_count << 16)))
+    }
+
+    fn create_ray_tracing_pipeline(&mut self, desc: &RayTracingPipelineDesc) -> Result<Arc<dyn Pipeline>> {
+        if !self.ray_tracing {
+            crate::engine_bail!("galaxy3d::MockGraphicsDevice", "Ray tracing is not supported");
+        }
+        let name = desc.debug_name.clone().unwrap_or_else(|| "ray_tracing_pipeline".to_string());
         self.created_pipelines.lock().unwrap().push(name.clone());
         Ok(Arc::new(MockPipeline::new(name)))
     }
@@ -502,6 +819,33 @@ impl GraphicsDevice for MockGraphicsDevice {
         )))
     }
 
+    fn create_transient_binding_group(
+        &self,
+        _pipeline: &Arc<dyn Pipeline>,
+        set_index: u32,
+        _resources: &[BindingResource],
+    ) -> Result<Arc<dyn BindingGroup>> {
+        *self.transient_binding_groups.lock().unwrap() += 1;
+        Ok(Arc::new(MockBindingGroup::new(
+            format!("transient_binding_group_set{}", set_index),
+            set_index,
+        )))
+    }
+
+    fn reset_transient_pools(&self) -> Result<()> {
+        *self.transient_binding_groups.lock().unwrap() = 0;
+        *self.transient_pool_resets.lock().unwrap() += 1;
+        Ok(())
+    }
+
+    fn descriptor_pool_usage(&self) -> Vec<crate::graphics_device::DescriptorPoolUsage> {
+        vec![crate::graphics_device::DescriptorPoolUsage {
+            transient: true,
+            allocated_sets: *self.transient_binding_groups.lock().unwrap(),
+            max_sets: 256,
+        }]
+    }
+
     fn create_swapchain(&self, _window: &Window) -> Result<Box<dyn Swapchain>> {
         Ok(Box::new(MockSwapchain::new(3)))
     }
@@ -528,7 +872,13 @@ impl GraphicsDevice for MockGraphicsDevice {
     }
 
     fn stats(&self) -> crate::graphics_device::GraphicsDeviceStats {
-        crate::graphics_device::GraphicsDeviceStats::default()
+        let mut stats = crate::graphics_device::GraphicsDeviceStats::default();
+        stats.set_descriptor_pool_usage(&self.descriptor_pool_usage());
+        stats
+    }
+
+    fn reset_stats(&self) {
+        // No counters in the mock
     }
 
     fn resize(&mut self, _width: u32, _height: u32) {
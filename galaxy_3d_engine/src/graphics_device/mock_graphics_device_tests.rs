@@ -177,6 +177,14 @@ fn test_mock_command_list_draw_indexed() {
     assert_eq!(cmd_list.commands[0], "draw_indexed");
 }
 
+#[test]
+fn test_mock_command_list_draw_mesh_tasks() {
+    let mut cmd_list = MockCommandList::new();
+
+    cmd_list.draw_mesh_tasks(4, 1, 1).unwrap();
+    assert_eq!(cmd_list.commands, vec!["draw_mesh_tasks 4"]);
+}
+
 #[test]
 fn test_mock_command_list_set_viewport() {
     let mut cmd_list = MockCommandList::new();
@@ -241,6 +249,36 @@ fn test_mock_command_list_complete_workflow() {
     assert_eq!(cmd_list.commands[6], "end");
 }
 
+fn resolve_access(
+    sample_count: SampleCount,
+    access_type: crate::graphics_device::AccessType,
+) -> crate::graphics_device::ImageAccess {
+    let mut texture = MockTexture::new(64, 64, 1, TextureType::Tex2D, "msaa".to_string());
+    texture.info.sample_count = sample_count;
+    crate::graphics_device::ImageAccess {
+        texture: Arc::new(texture),
+        access_type,
+        previous_access_type: Some(crate::graphics_device::AccessType::ColorAttachmentWrite),
+    }
+}
+
+#[test]
+fn test_mock_command_list_resolve_texture() {
+    use crate::graphics_device::AccessType;
+    let mut cmd_list = MockCommandList::new();
+    let src = resolve_access(SampleCount::S4, AccessType::TransferRead);
+    let dst = resolve_access(SampleCount::S1, AccessType::TransferWrite);
+
+    cmd_list.resolve_texture(&src, &dst).unwrap();
+    assert_eq!(cmd_list.commands, vec!["resolve_texture"]);
+
+    // Swapped accesses and single-sampled sources are rejected
+    assert!(cmd_list.resolve_texture(&dst, &src).is_err());
+    let single = resolve_access(SampleCount::S1, AccessType::TransferRead);
+    assert!(cmd_list.resolve_texture(&single, &dst).is_err());
+    assert_eq!(cmd_list.commands.len(), 1);
+}
+
 // ============================================================================
 // MockRenderPass Tests
 // ============================================================================
@@ -334,6 +372,7 @@ fn test_mock_graphics_device_create_texture() {
     let desc = TextureDesc {
         width: 256,
         height: 256,
+        depth: 1,
         format: TextureFormat::R8G8B8A8_UNORM,
         usage: TextureUsage::Sampled,
         array_layers: 1,
@@ -341,6 +380,7 @@ fn test_mock_graphics_device_create_texture() {
         data: None,
         texture_type: TextureType::Tex2D,
         sample_count: SampleCount::S1,
+        debug_name: None,
     };
 
     let _texture = graphics_device.create_texture(desc).unwrap();
@@ -357,6 +397,7 @@ fn test_mock_graphics_device_create_buffer() {
     let desc = BufferDesc {
         size: 1024,
         usage: BufferUsage::Vertex,
+        debug_name: None,
     };
 
     let _buffer = graphics_device.create_buffer(desc).unwrap();
@@ -374,6 +415,7 @@ fn test_mock_graphics_device_create_shader() {
         stage: ShaderStage::Vertex,
         code: &[1, 2, 3, 4],
         entry_point: "main".to_string(),
+        debug_name: None,
     };
 
     let _shader = graphics_device.create_shader(desc).unwrap();
@@ -391,6 +433,7 @@ fn test_mock_graphics_device_create_shader_fragment() {
         stage: ShaderStage::Fragment,
         code: &[1, 2, 3, 4],
         entry_point: "main".to_string(),
+        debug_name: None,
     };
 
     let _shader = graphics_device.create_shader(desc).unwrap();
@@ -408,12 +451,14 @@ fn test_mock_graphics_device_create_pipeline() {
         stage: ShaderStage::Vertex,
         code: &[],
         entry_point: "main".to_string(),
+        debug_name: None,
     }).unwrap();
 
     let fragment_shader = graphics_device.create_shader(ShaderDesc {
         stage: ShaderStage::Fragment,
         code: &[],
         entry_point: "main".to_string(),
+        debug_name: None,
     }).unwrap();
 
     let vertex_layout = VertexLayout {
@@ -442,6 +487,9 @@ fn test_mock_graphics_device_create_pipeline() {
         multisample: Default::default(),
         color_formats: vec![],
         depth_format: None,
+        engine_features: Default::default(),
+        debug_name: None,
+        dynamic_uniform_buffers: Vec::new(),
     };
 
     let _pipeline = graphics_device.create_pipeline(desc, &vertex_shader, &fragment_shader).unwrap();
@@ -451,6 +499,44 @@ fn test_mock_graphics_device_create_pipeline() {
     assert_eq!(created_pipelines[0], "pipeline");
 }
 
+#[test]
+fn test_mock_graphics_device_create_mesh_pipeline() {
+    let mut graphics_device = MockGraphicsDevice::new();
+    let mut shader = |stage| graphics_device.create_shader(ShaderDesc {
+        stage,
+        code: &[],
+        entry_point: "main".to_string(),
+        debug_name: None,
+    }).unwrap();
+    let task_shader = shader(ShaderStage::Task);
+    let mesh_shader = shader(ShaderStage::Mesh);
+    let fragment_shader = shader(ShaderStage::Fragment);
+    let desc = || PipelineDesc {
+        vertex_layout: VertexLayout { bindings: vec![], attributes: vec![] },
+        topology: PrimitiveTopology::TriangleList,
+        rasterization: Default::default(),
+        color_blend: Default::default(),
+        multisample: Default::default(),
+        color_formats: vec![],
+        depth_format: None,
+        engine_features: Default::default(),
+        debug_name: Some("meshlets".to_string()),
+        dynamic_uniform_buffers: Vec::new(),
+    };
+
+    // Unsupported by default
+    assert!(!graphics_device.supports_mesh_shaders());
+    assert!(graphics_device.create_mesh_pipeline(
+        desc(), Some(&task_shader), &mesh_shader, &fragment_shader,
+    ).is_err());
+
+    graphics_device.mesh_shaders = true;
+    assert!(graphics_device.supports_mesh_shaders());
+    graphics_device.create_mesh_pipeline(desc(), Some(&task_shader), &mesh_shader, &fragment_shader).unwrap();
+    graphics_device.create_mesh_pipeline(desc(), None, &mesh_shader, &fragment_shader).unwrap();
+    assert_eq!(graphics_device.get_created_pipelines(), vec!["meshlets", "meshlets"]);
+}
+
 #[test]
 fn test_mock_graphics_device_create_command_list() {
     let graphics_device = MockGraphicsDevice::new();
@@ -641,12 +727,14 @@ fn test_mock_graphics_device_multiple_resources() {
         let buffer_desc = BufferDesc {
             size: 1024 * (i + 1) as u64,
             usage: BufferUsage::Vertex,
+            debug_name: None,
         };
         graphics_device.create_buffer(buffer_desc).unwrap();
 
         let texture_desc = TextureDesc {
             width: 256,
             height: 256,
+            depth: 1,
             format: TextureFormat::R8G8B8A8_UNORM,
             usage: TextureUsage::Sampled,
             array_layers: 1,
@@ -654,6 +742,7 @@ fn test_mock_graphics_device_multiple_resources() {
             data: None,
             texture_type: TextureType::Tex2D,
             sample_count: SampleCount::S1,
+            debug_name: None,
         };
         graphics_device.create_texture(texture_desc).unwrap();
     }
@@ -673,6 +762,7 @@ fn test_mock_graphics_device_tracking_persistence() {
         let desc = BufferDesc {
             size: 2048,
             usage: BufferUsage::Index,
+            debug_name: None,
         };
         r.create_buffer(desc).unwrap();
     }
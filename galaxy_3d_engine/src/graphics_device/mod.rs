@@ -1,36 +1,57 @@
 /// Graphics device module - all rendering-related types and traits
+///
+/// Without the `renderer` feature only the plain viewport types are built.
 
-// Module declarations
-pub mod graphics_device;
-pub mod texture;
-pub mod buffer;
-pub mod shader;
-pub mod pipeline;
+// Core module (always built)
+pub mod viewport;
+pub use viewport::*;
 
-// New architecture modules
-pub mod command_list;
-pub mod render_pass;
-pub mod swapchain;
-pub mod access_type;
-pub mod binding_group;
-pub mod frame_buffer;
+cfg_renderer! {
+    // Module declarations
+    pub mod graphics_device;
+    pub mod config_overrides;
+    pub mod platform_profile;
+    pub mod texture;
+    pub mod sampler;
+    pub mod buffer;
+    pub mod vertex_packing;
+    pub mod shader;
+    pub mod pipeline;
 
-// Re-export everything from graphics_device.rs
-pub use graphics_device::*;
+    // New architecture modules
+    pub mod command_list;
+    pub mod render_pass;
+    pub mod swapchain;
+    pub mod access_type;
+    pub mod binding_group;
+    pub mod frame_buffer;
+    pub mod ray_tracing;
+    pub mod query;
+    mod cpu_mipmap;
 
-// Re-export from other modules
-pub use texture::*;
-pub use buffer::*;
-pub use shader::*;
-pub use pipeline::*;
+    // Re-export everything from graphics_device.rs
+    pub use graphics_device::*;
+    pub use config_overrides::CONFIG_ENV_PREFIX;
+    pub use platform_profile::{PlatformProfile, PlatformLimits};
 
-// Re-export new architecture types
-pub use access_type::*;
-pub use command_list::*;
-pub use render_pass::*;
-pub use swapchain::*;
-pub use binding_group::*;
-pub use frame_buffer::*;
+    // Re-export from other modules
+    pub use texture::*;
+    pub use sampler::*;
+    pub use buffer::*;
+    pub use vertex_packing::*;
+    pub use shader::*;
+    pub use pipeline::*;
+
+    // Re-export new architecture types
+    pub use access_type::*;
+    pub use command_list::*;
+    pub use render_pass::*;
+    pub use swapchain::*;
+    pub use binding_group::*;
+    pub use frame_buffer::*;
+    pub use ray_tracing::*;
+    pub use query::*;
+}
 
 // Mock graphics device for tests (no GPU required)
 #[cfg(test)]
@@ -5,6 +5,7 @@ use crate::graphics_device::{BufferFormat, ShaderStage, BindingType, ShaderStage
 
 /// Primitive topology
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub enum PrimitiveTopology {
     /// Triangle list
     TriangleList,
@@ -16,8 +17,21 @@ pub enum PrimitiveTopology {
     PointList,
 }
 
+impl PrimitiveTopology {
+    /// Number of triangles assembled from `vertex_count` vertices (or
+    /// indices); 0 for line and point topologies
+    pub fn triangle_count(&self, vertex_count: u32) -> u32 {
+        match self {
+            PrimitiveTopology::TriangleList => vertex_count / 3,
+            PrimitiveTopology::TriangleStrip => vertex_count.saturating_sub(2),
+            PrimitiveTopology::LineList | PrimitiveTopology::PointList => 0,
+        }
+    }
+}
+
 /// Index buffer element type
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub enum IndexType {
     /// 16-bit indices (max 65535 vertices)
     U16,
@@ -37,6 +51,7 @@ impl IndexType {
 
 /// Vertex input rate
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub enum VertexInputRate {
     /// Data is per-vertex
     Vertex,
@@ -46,6 +61,7 @@ pub enum VertexInputRate {
 
 /// Vertex attribute description
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub struct VertexAttribute {
     /// Attribute location in shader
     pub location: u32,
@@ -59,6 +75,7 @@ pub struct VertexAttribute {
 
 /// Vertex binding description
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub struct VertexBinding {
     /// Binding index
     pub binding: u32,
@@ -70,6 +87,7 @@ pub struct VertexBinding {
 
 /// Vertex input layout
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub struct VertexLayout {
     /// Vertex bindings
     pub bindings: Vec<VertexBinding>,
@@ -101,6 +119,7 @@ pub struct PushConstantRange {
 
 /// Face culling mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub enum CullMode {
     /// No culling
     None,
@@ -112,6 +131,7 @@ pub enum CullMode {
 
 /// Front face winding order
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub enum FrontFace {
     /// Counter-clockwise vertices define front face
     CounterClockwise,
@@ -121,6 +141,7 @@ pub enum FrontFace {
 
 /// Polygon rendering mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub enum PolygonMode {
     /// Fill polygons
     Fill,
@@ -134,6 +155,7 @@ pub enum PolygonMode {
 
 /// Comparison operator for depth and stencil tests
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub enum CompareOp {
     /// Never pass
     Never,
@@ -155,6 +177,7 @@ pub enum CompareOp {
 
 /// Stencil operation
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub enum StencilOp {
     /// Keep current value
     Keep,
@@ -178,6 +201,7 @@ pub enum StencilOp {
 
 /// Blend factor for color blending equations
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub enum BlendFactor {
     Zero,
     One,
@@ -196,6 +220,7 @@ pub enum BlendFactor {
 
 /// Blend operation
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub enum BlendOp {
     /// result = src * srcFactor + dst * dstFactor
     Add,
@@ -213,6 +238,7 @@ pub enum BlendOp {
 
 /// Multisample count
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub enum SampleCount {
     /// 1 sample (no multisampling)
     S1,
@@ -228,6 +254,7 @@ pub enum SampleCount {
 
 /// Depth bias parameters
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub struct DepthBias {
     /// Constant depth offset
     pub constant_factor: f32,
@@ -241,6 +268,7 @@ pub struct DepthBias {
 ///
 /// Fields that moved to DynamicRenderState: cull_mode, front_face, depth_bias
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub struct RasterizationState {
     /// Polygon rendering mode
     pub polygon_mode: PolygonMode,
@@ -264,6 +292,7 @@ impl Default for RasterizationState {
 
 /// Stencil operation state (per-face)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub struct StencilOpState {
     /// Action on stencil test fail
     pub fail_op: StencilOp,
@@ -301,6 +330,7 @@ impl Default for StencilOpState {
 
 /// Color write mask
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub struct ColorWriteMask {
     pub r: bool,
     pub g: bool,
@@ -326,6 +356,7 @@ impl Default for ColorWriteMask {
 /// Blend mode is baked into the pipeline because changing it dynamically
 /// causes shader recompilation on tile-based GPUs (ARM Mali, Adreno).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub struct ColorBlendState {
     /// Enable blending
     pub blend_enable: bool,
@@ -363,10 +394,22 @@ impl Default for ColorBlendState {
     }
 }
 
+impl ColorBlendState {
+    /// No color output (depth-only rendering: shadows, depth pre-pass)
+    pub fn depth_only() -> Self {
+        Self {
+            color_write_mask: ColorWriteMask::NONE,
+            color_write_enable: false,
+            ..Default::default()
+        }
+    }
+}
+
 // ===== MULTISAMPLE STATE =====
 
 /// Multisampling state
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub struct MultisampleState {
     /// Number of samples per pixel
     pub sample_count: SampleCount,
@@ -383,6 +426,120 @@ impl Default for MultisampleState {
     }
 }
 
+// ===== BLEND PRESETS =====
+
+/// Ready-made color blend and multisample configurations.
+///
+/// `color_blend()` and `multisample()` build the matching `PipelineDesc`
+/// state blocks. Blended presets expect a pass with depth write disabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlendPreset {
+    /// No blending (default state)
+    Opaque,
+    /// Straight alpha: `src * a + dst * (1 - a)`
+    AlphaBlend,
+    /// Premultiplied alpha: `src + dst * (1 - a)`
+    PremultipliedAlpha,
+    /// Additive: `src * a + dst` (particles, glows)
+    Additive,
+    /// No blending, coverage mask from the fragment alpha (foliage, fences).
+    /// Requires a multisampled target.
+    AlphaToCoverage,
+}
+
+impl BlendPreset {
+    /// Color blend state of the preset
+    pub fn color_blend(self) -> ColorBlendState {
+        let blend = |src_color, dst_color, src_alpha, dst_alpha| ColorBlendState {
+            blend_enable: true,
+            src_color_factor: src_color,
+            dst_color_factor: dst_color,
+            src_alpha_factor: src_alpha,
+            dst_alpha_factor: dst_alpha,
+            ..Default::default()
+        };
+        match self {
+            BlendPreset::Opaque | BlendPreset::AlphaToCoverage => ColorBlendState::default(),
+            BlendPreset::AlphaBlend => blend(
+                BlendFactor::SrcAlpha, BlendFactor::OneMinusSrcAlpha,
+                BlendFactor::One, BlendFactor::OneMinusSrcAlpha,
+            ),
+            BlendPreset::PremultipliedAlpha => blend(
+                BlendFactor::One, BlendFactor::OneMinusSrcAlpha,
+                BlendFactor::One, BlendFactor::OneMinusSrcAlpha,
+            ),
+            BlendPreset::Additive => blend(
+                BlendFactor::SrcAlpha, BlendFactor::One,
+                BlendFactor::Zero, BlendFactor::One,
+            ),
+        }
+    }
+
+    /// Multisample state of the preset for the given sample count
+    pub fn multisample(self, sample_count: SampleCount) -> MultisampleState {
+        MultisampleState {
+            sample_count,
+            alpha_to_coverage_enable: self == BlendPreset::AlphaToCoverage,
+        }
+    }
+
+    /// True if the preset blends with the destination (transparent pass)
+    pub fn is_blended(self) -> bool {
+        self.color_blend().blend_enable
+    }
+}
+
+/// Common blend setup mistake reported by `blend_warnings()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlendWarning {
+    /// Blending with depth write on: blended surfaces occlude what is drawn
+    /// behind them afterwards
+    BlendWithDepthWrite,
+    /// Alpha-to-coverage on a single-sampled target has no effect
+    AlphaToCoverageWithoutMsaa,
+    /// Alpha-to-coverage combined with blending applies the alpha twice
+    AlphaToCoverageWithBlend,
+}
+
+impl std::fmt::Display for BlendWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            BlendWarning::BlendWithDepthWrite =>
+                "blending is enabled with depth write on (disable depth write for transparent passes)",
+            BlendWarning::AlphaToCoverageWithoutMsaa =>
+                "alpha-to-coverage has no effect without multisampling",
+            BlendWarning::AlphaToCoverageWithBlend =>
+                "alpha-to-coverage is combined with blending (alpha applied twice)",
+        })
+    }
+}
+
+/// Check a blend setup for common mistakes. `multisample` and `render_state`
+/// are optional so pipelines and material passes can be checked separately.
+pub fn blend_warnings(
+    color_blend: &ColorBlendState,
+    multisample: Option<&MultisampleState>,
+    render_state: Option<&DynamicRenderState>,
+) -> Vec<BlendWarning> {
+    let mut warnings = Vec::new();
+    if let Some(rs) = render_state {
+        if color_blend.blend_enable && rs.depth_test_enable && rs.depth_write_enable {
+            warnings.push(BlendWarning::BlendWithDepthWrite);
+        }
+    }
+    if let Some(ms) = multisample {
+        if ms.alpha_to_coverage_enable {
+            if ms.sample_count == SampleCount::S1 {
+                warnings.push(BlendWarning::AlphaToCoverageWithoutMsaa);
+            }
+            if color_blend.blend_enable {
+                warnings.push(BlendWarning::AlphaToCoverageWithBlend);
+            }
+        }
+    }
+    warnings
+}
+
 // ===== STENCIL FACE FLAGS =====
 
 /// Which stencil face(s) to target
@@ -405,6 +562,7 @@ pub enum StencilFaceFlags {
 /// Built by the Material (one per pass) and passed to the backend
 /// as a single unit via CommandList::set_dynamic_state().
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub struct DynamicRenderState {
     // Rasterization
     /// Face culling mode
@@ -516,6 +674,185 @@ impl From<&DynamicRenderState> for DynamicRenderStateKey {
     }
 }
 
+// ===== ENGINE FEATURES =====
+
+/// Engine-wide feature mask (shadows, fog, IBL, ...) baked into pipelines.
+///
+/// The mask is passed to both shader stages as the `u32` specialization
+/// constant `EngineFeatures::SPECIALIZATION_CONSTANT_ID`, so shaders branch
+/// on constants folded at pipeline creation instead of runtime uniforms:
+///
+/// ```glsl
+/// layout(constant_id = 0) const uint ENGINE_FEATURES = 0;
+/// if ((ENGINE_FEATURES & 1u) != 0u) { /* shadows */ }
+/// ```
+///
+/// Shaders that do not declare the constant are unaffected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
+pub struct EngineFeatures(u32);
+
+impl EngineFeatures {
+    pub const NONE: Self = Self(0x00);
+    pub const SHADOWS: Self = Self(0x01);
+    pub const FOG: Self = Self(0x02);
+    pub const IBL: Self = Self(0x04);
+    /// Global features (the mask given to `ResourceManager::set_engine_features`)
+    pub const ALL: Self = Self(0x07);
+    /// The pass has a motion-vector color attachment. Not a global feature:
+    /// set by the drawer of such a pass (`ForwardDrawerConfig::motion_vectors`).
+    pub const MOTION_VECTORS: Self = Self(0x08);
+    /// Unlit shading (base color and emission only). Not a global feature:
+    /// set by the drawer of such a pass (`ForwardDrawerConfig::unlit`).
+    pub const UNLIT: Self = Self(0x10);
+    /// Debug view outputs (see `DebugViewMode`). Not global features: set
+    /// by the drawers while the matching debug view is active.
+    pub const DEBUG_NORMALS: Self = Self(0x20);
+    pub const DEBUG_OVERDRAW: Self = Self(0x40);
+    pub const DEBUG_LOD: Self = Self(0x80);
+    pub const DEBUG_CASCADES: Self = Self(0x100);
+    /// All debug view outputs
+    pub const DEBUG_VIEWS: Self = Self(0x1E0);
+
+    /// Specialization constant id carrying the mask
+    pub const SPECIALIZATION_CONSTANT_ID: u32 = 0;
+
+    /// Create from raw bits
+    pub fn from_bits(bits: u32) -> Self { Self(bits) }
+
+    /// Look up a single feature by its symbolic name
+    /// (`"shadows"`, `"fog"`, `"ibl"`), case-insensitive.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "shadows" => Some(Self::SHADOWS),
+            "fog" => Some(Self::FOG),
+            "ibl" => Some(Self::IBL),
+            _ => None,
+        }
+    }
+
+    /// Combine several symbolic names into one mask. Returns None if any
+    /// name is unknown.
+    pub fn from_names(names: &[&str]) -> Option<Self> {
+        names.iter().try_fold(Self::NONE, |acc, name| Some(acc | Self::from_name(name)?))
+    }
+
+    pub fn bits(&self) -> u32 { self.0 }
+    pub fn is_empty(&self) -> bool { self.0 == 0 }
+    pub fn contains(&self, other: Self) -> bool { self.0 & other.0 == other.0 }
+}
+
+impl std::ops::BitOr for EngineFeatures {
+    type Output = Self;
+    fn bitor(self, rhs: Self) -> Self { Self(self.0 | rhs.0) }
+}
+
+impl std::ops::BitAnd for EngineFeatures {
+    type Output = Self;
+    fn bitand(self, rhs: Self) -> Self { Self(self.0 & rhs.0) }
+}
+
+// ===== DEBUG VIEW MODE =====
+
+/// Engine-wide debug visualization (`ResourceManager::set_debug_view`).
+///
+/// The drawers derive a variant of every pipeline they resolve: wireframe
+/// swaps the polygon mode, the other modes set one `EngineFeatures::DEBUG_*`
+/// bit for the shaders to output the visualization instead of their
+/// shading, and overdraw also blends additively with depth test off:
+///
+/// ```glsl
+/// if ((ENGINE_FEATURES & 0x20u) != 0u) { color = vec4(N * 0.5 + 0.5, 1.0); }  // normals
+/// if ((ENGINE_FEATURES & 0x40u) != 0u) { color = vec4(0.1, 0.05, 0.0, 1.0); } // overdraw
+/// if ((ENGINE_FEATURES & 0x80u) != 0u) { color = lodColor((drawSlot >> 24) & 0x7Fu); }
+/// if ((ENGINE_FEATURES & 0x100u) != 0u) { color = cascadeColor(cascadeIndex); }
+/// ```
+///
+/// With `LodColoring`, the upper bits of the draw slot carry the LOD index
+/// instead of the cross-fade level (`encode_debug_lod`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
+pub enum DebugViewMode {
+    /// Normal shading
+    #[default]
+    None,
+    /// Triangle edges (`PolygonMode::Line`), shading kept
+    Wireframe,
+    /// World-space normals as colors
+    Normals,
+    /// Fragments per pixel, accumulated additively (heatmap)
+    Overdraw,
+    /// One color per LOD index
+    LodColoring,
+    /// One color per shadow cascade
+    ShadowCascades,
+}
+
+impl DebugViewMode {
+    /// Every mode, `None` first
+    pub const ALL: [Self; 6] = [
+        Self::None, Self::Wireframe, Self::Normals, Self::Overdraw, Self::LodColoring, Self::ShadowCascades,
+    ];
+
+    /// Look up a mode by its symbolic name (`"none"`, `"wireframe"`,
+    /// `"normals"`, `"overdraw"`, `"lod"`, `"cascades"`), case-insensitive
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "none" => Some(Self::None),
+            "wireframe" => Some(Self::Wireframe),
+            "normals" => Some(Self::Normals),
+            "overdraw" => Some(Self::Overdraw),
+            "lod" => Some(Self::LodColoring),
+            "cascades" => Some(Self::ShadowCascades),
+            _ => None,
+        }
+    }
+
+    /// Symbolic name (see `from_name`)
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Wireframe => "wireframe",
+            Self::Normals => "normals",
+            Self::Overdraw => "overdraw",
+            Self::LodColoring => "lod",
+            Self::ShadowCascades => "cascades",
+        }
+    }
+
+    /// Feature bit added to every pipeline drawn in this mode
+    pub fn engine_features(self) -> EngineFeatures {
+        match self {
+            Self::None | Self::Wireframe => EngineFeatures::NONE,
+            Self::Normals => EngineFeatures::DEBUG_NORMALS,
+            Self::Overdraw => EngineFeatures::DEBUG_OVERDRAW,
+            Self::LodColoring => EngineFeatures::DEBUG_LOD,
+            Self::ShadowCascades => EngineFeatures::DEBUG_CASCADES,
+        }
+    }
+
+    /// Polygon mode of a material pass drawn in this mode
+    pub fn polygon_mode(self, material: PolygonMode) -> PolygonMode {
+        if self == Self::Wireframe { PolygonMode::Line } else { material }
+    }
+
+    /// Color blend replacing the material's in this mode, if any
+    pub fn color_blend(self) -> Option<ColorBlendState> {
+        (self == Self::Overdraw).then(|| BlendPreset::Additive.color_blend())
+    }
+
+    /// Render state replacing the material's in this mode, if any: overdraw
+    /// counts every fragment, hidden or back-facing
+    pub fn render_state(self, material: &DynamicRenderState) -> Option<DynamicRenderState> {
+        (self == Self::Overdraw).then_some(DynamicRenderState {
+            cull_mode: CullMode::None,
+            depth_test_enable: false,
+            depth_write_enable: false,
+            ..*material
+        })
+    }
+}
+
 // ===== PIPELINE DESCRIPTOR =====
 
 /// Descriptor for creating a graphics pipeline
@@ -539,6 +876,30 @@ pub struct PipelineDesc {
     pub color_formats: Vec<TextureFormat>,
     /// Depth/stencil attachment format (None if no depth/stencil)
     pub depth_format: Option<TextureFormat>,
+    /// Engine features folded into the shaders as a specialization constant
+    pub engine_features: EngineFeatures,
+    /// Name shown by graphics debuggers (RenderDoc, validation messages)
+    pub debug_name: Option<String>,
+    /// `(set, binding)` of the uniform buffers bound with a dynamic offset
+    /// (`BindingType::UniformBufferDynamic`, e.g. a `UniformRing`). The
+    /// reflection cannot tell them apart from regular uniform buffers.
+    pub dynamic_uniform_buffers: Vec<(u32, u32)>,
+}
+
+impl PipelineDesc {
+    /// Depth-only variant of this descriptor, for shadow and depth passes.
+    ///
+    /// Color attachments and color writes are stripped; everything that
+    /// affects depth (vertex input, rasterization, multisampling, depth
+    /// format) is kept. The fragment shader is still bound by the caller so
+    /// alpha-tested materials keep discarding.
+    pub fn depth_only(&self) -> Self {
+        Self {
+            color_blend: ColorBlendState::depth_only(),
+            color_formats: Vec::new(),
+            ..self.clone()
+        }
+    }
 }
 
 // ============================================================================
@@ -621,6 +982,20 @@ pub struct ReflectedPushConstant {
     pub members: Vec<ReflectedMember>,
 }
 
+/// A vertex shader input (`layout(location = N) in ...`) extracted from
+/// compiled shader bytecode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReflectedVertexInput {
+    /// Input name from shader debug info (e.g., "inNormal")
+    pub name: String,
+    /// Vertex attribute location
+    pub location: u32,
+    /// Numeric type the shader reads
+    pub scalar_kind: ScalarKind,
+    /// Number of components (1 for scalars, 2-4 for vectors)
+    pub component_count: u32,
+}
+
 // ============================================================================
 // Pipeline reflection — merged data
 // ============================================================================
@@ -4,9 +4,179 @@
 
 use crate::graphics_device::{
     IndexType, PrimitiveTopology, VertexInputRate, VertexLayout,
-    VertexBinding, VertexAttribute, BufferFormat,
+    VertexBinding, VertexAttribute, BufferFormat, EngineFeatures,
+    BlendPreset, BlendWarning, BlendFactor, ColorBlendState, DynamicRenderState,
+    MultisampleState, SampleCount, blend_warnings, ColorWriteMask, PipelineDesc,
+    TextureFormat, DebugViewMode, PolygonMode, CullMode,
 };
 
+// ============================================================================
+// ENGINE FEATURES TESTS
+// ============================================================================
+
+#[test]
+fn test_engine_features_bits() {
+    assert_eq!(EngineFeatures::default(), EngineFeatures::NONE);
+    assert_eq!(EngineFeatures::SHADOWS.bits(), 0x01);
+    assert_eq!(EngineFeatures::FOG.bits(), 0x02);
+    assert_eq!(EngineFeatures::IBL.bits(), 0x04);
+    assert_eq!(EngineFeatures::SHADOWS | EngineFeatures::FOG | EngineFeatures::IBL, EngineFeatures::ALL);
+    assert_eq!(EngineFeatures::MOTION_VECTORS.bits(), 0x08);
+    assert!(!EngineFeatures::ALL.contains(EngineFeatures::MOTION_VECTORS));
+}
+
+#[test]
+fn test_engine_features_contains_and_mask() {
+    let active = EngineFeatures::SHADOWS | EngineFeatures::IBL;
+    assert!(active.contains(EngineFeatures::SHADOWS));
+    assert!(!active.contains(EngineFeatures::FOG));
+    assert_eq!(active & EngineFeatures::FOG, EngineFeatures::NONE);
+    assert!((active & EngineFeatures::FOG).is_empty());
+    assert_eq!(active & (EngineFeatures::SHADOWS | EngineFeatures::FOG), EngineFeatures::SHADOWS);
+}
+
+#[test]
+fn test_engine_features_from_names() {
+    assert_eq!(EngineFeatures::from_name("Shadows"), Some(EngineFeatures::SHADOWS));
+    assert_eq!(EngineFeatures::from_name("fog"), Some(EngineFeatures::FOG));
+    assert_eq!(EngineFeatures::from_name("ibl"), Some(EngineFeatures::IBL));
+    assert_eq!(EngineFeatures::from_name("ssao"), None);
+    assert_eq!(
+        EngineFeatures::from_names(&["shadows", "ibl"]),
+        Some(EngineFeatures::SHADOWS | EngineFeatures::IBL),
+    );
+    assert_eq!(EngineFeatures::from_names(&[]), Some(EngineFeatures::NONE));
+    assert_eq!(EngineFeatures::from_names(&["fog", "ssao"]), None);
+}
+
+// ============================================================================
+// DEBUG VIEW MODE TESTS
+// ============================================================================
+
+#[test]
+fn test_debug_view_names_round_trip() {
+    for mode in DebugViewMode::ALL {
+        assert_eq!(DebugViewMode::from_name(mode.name()), Some(mode));
+    }
+    assert_eq!(DebugViewMode::from_name("Wireframe"), Some(DebugViewMode::Wireframe));
+    assert_eq!(DebugViewMode::from_name("albedo"), None);
+    assert_eq!(DebugViewMode::default(), DebugViewMode::None);
+}
+
+#[test]
+fn test_debug_view_features_are_drawer_bits() {
+    let mut all = EngineFeatures::NONE;
+    for mode in DebugViewMode::ALL {
+        let features = mode.engine_features();
+        assert!(EngineFeatures::DEBUG_VIEWS.contains(features));
+        assert!((features & all).is_empty());
+        all = all | features;
+    }
+    assert_eq!(all, EngineFeatures::DEBUG_VIEWS);
+    assert!((EngineFeatures::DEBUG_VIEWS & (EngineFeatures::ALL | EngineFeatures::UNLIT)).is_empty());
+}
+
+#[test]
+fn test_debug_view_pipeline_overrides() {
+    assert_eq!(DebugViewMode::Wireframe.polygon_mode(PolygonMode::Fill), PolygonMode::Line);
+    assert_eq!(DebugViewMode::Normals.polygon_mode(PolygonMode::Fill), PolygonMode::Fill);
+
+    assert_eq!(DebugViewMode::Overdraw.color_blend(), Some(BlendPreset::Additive.color_blend()));
+    assert_eq!(DebugViewMode::Wireframe.color_blend(), None);
+
+    let material = DynamicRenderState::default();
+    let overdraw = DebugViewMode::Overdraw.render_state(&material).unwrap();
+    assert!(!overdraw.depth_test_enable && !overdraw.depth_write_enable);
+    assert_eq!(overdraw.cull_mode, CullMode::None);
+    assert!(DebugViewMode::LodColoring.render_state(&material).is_none());
+}
+
+// ============================================================================
+// BLEND PRESET TESTS
+// ============================================================================
+
+#[test]
+fn test_blend_preset_factors() {
+    assert_eq!(BlendPreset::Opaque.color_blend(), ColorBlendState::default());
+
+    let premul = BlendPreset::PremultipliedAlpha.color_blend();
+    assert!(premul.blend_enable);
+    assert_eq!(premul.src_color_factor, BlendFactor::One);
+    assert_eq!(premul.dst_color_factor, BlendFactor::OneMinusSrcAlpha);
+
+    let additive = BlendPreset::Additive.color_blend();
+    assert_eq!(additive.src_color_factor, BlendFactor::SrcAlpha);
+    assert_eq!(additive.dst_color_factor, BlendFactor::One);
+
+    assert!(BlendPreset::AlphaBlend.is_blended());
+    assert!(!BlendPreset::AlphaToCoverage.is_blended());
+}
+
+#[test]
+fn test_blend_preset_multisample() {
+    let ms = BlendPreset::AlphaToCoverage.multisample(SampleCount::S4);
+    assert!(ms.alpha_to_coverage_enable);
+    assert_eq!(ms.sample_count, SampleCount::S4);
+    assert!(!BlendPreset::PremultipliedAlpha.multisample(SampleCount::S4).alpha_to_coverage_enable);
+}
+
+#[test]
+fn test_blend_warnings_depth_write() {
+    let blended = BlendPreset::PremultipliedAlpha.color_blend();
+    let depth_write = DynamicRenderState::default();
+    assert_eq!(
+        blend_warnings(&blended, None, Some(&depth_write)),
+        vec![BlendWarning::BlendWithDepthWrite],
+    );
+
+    let no_write = DynamicRenderState { depth_write_enable: false, ..Default::default() };
+    assert!(blend_warnings(&blended, None, Some(&no_write)).is_empty());
+    assert!(blend_warnings(&ColorBlendState::default(), None, Some(&depth_write)).is_empty());
+}
+
+#[test]
+fn test_blend_warnings_alpha_to_coverage() {
+    let opaque = BlendPreset::AlphaToCoverage.color_blend();
+    let single = BlendPreset::AlphaToCoverage.multisample(SampleCount::S1);
+    assert_eq!(
+        blend_warnings(&opaque, Some(&single), None),
+        vec![BlendWarning::AlphaToCoverageWithoutMsaa],
+    );
+
+    let msaa = BlendPreset::AlphaToCoverage.multisample(SampleCount::S4);
+    assert!(blend_warnings(&opaque, Some(&msaa), None).is_empty());
+
+    let blended = BlendPreset::AlphaBlend.color_blend();
+    assert_eq!(
+        blend_warnings(&blended, Some(&msaa), None),
+        vec![BlendWarning::AlphaToCoverageWithBlend],
+    );
+    assert!(blend_warnings(&blended, Some(&MultisampleState::default()), None).is_empty());
+}
+
+#[test]
+fn test_pipeline_desc_depth_only() {
+    let desc = PipelineDesc {
+        vertex_layout: VertexLayout { bindings: vec![], attributes: vec![] },
+        topology: PrimitiveTopology::TriangleList,
+        rasterization: Default::default(),
+        color_blend: BlendPreset::AlphaBlend.color_blend(),
+        multisample: BlendPreset::AlphaToCoverage.multisample(SampleCount::S4),
+        color_formats: vec![TextureFormat::R8G8B8A8_UNORM],
+        depth_format: Some(TextureFormat::D32_FLOAT),
+        engine_features: EngineFeatures::SHADOWS,
+        debug_name: None,
+        dynamic_uniform_buffers: Vec::new(),
+    };
+    let depth = desc.depth_only();
+    assert!(depth.color_formats.is_empty());
+    assert_eq!(depth.color_blend, ColorBlendState::depth_only());
+    assert_eq!(depth.color_blend.color_write_mask, ColorWriteMask::NONE);
+    assert_eq!(depth.depth_format, Some(TextureFormat::D32_FLOAT));
+    assert_eq!(depth.multisample, desc.multisample);
+    assert_eq!(depth.engine_features, EngineFeatures::SHADOWS);
+}
+
 // ============================================================================
 // INDEX TYPE TESTS
 // ============================================================================
@@ -89,6 +259,16 @@ fn test_primitive_topology_debug() {
     assert!(format!("{:?}", PrimitiveTopology::PointList).contains("PointList"));
 }
 
+#[test]
+fn test_primitive_topology_triangle_count() {
+    assert_eq!(PrimitiveTopology::TriangleList.triangle_count(36), 12);
+    assert_eq!(PrimitiveTopology::TriangleList.triangle_count(2), 0);
+    assert_eq!(PrimitiveTopology::TriangleStrip.triangle_count(6), 4);
+    assert_eq!(PrimitiveTopology::TriangleStrip.triangle_count(1), 0);
+    assert_eq!(PrimitiveTopology::LineList.triangle_count(6), 0);
+    assert_eq!(PrimitiveTopology::PointList.triangle_count(6), 0);
+}
+
 #[test]
 fn test_primitive_topology_clone() {
     let topo1 = PrimitiveTopology::TriangleList;
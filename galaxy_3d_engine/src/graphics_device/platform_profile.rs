@@ -0,0 +1,95 @@
+/// Platform profiles: per-device clamps of texture resolution, anisotropy
+/// and shadow map resolution.
+///
+/// One asset set ships on every device; the profile selected in
+/// `Config::platform_profile` lowers what the device cannot afford:
+///
+/// | Profile | Max texture size | Max anisotropy | Max shadow resolution |
+/// |---|---|---|---|
+/// | `Desktop` | unlimited | 16 | 8192 |
+/// | `SteamDeck` | 2048 | 8 | 2048 |
+/// | `Mobile` | 1024 | 4 | 1024 |
+///
+/// The backend clamps its samplers with `max_anisotropy`. Textures and
+/// shadow maps are clamped by the `ResourceManager`
+/// (`set_platform_profile()`): oversized textures lose their top mip
+/// levels at load (see `TextureDesc::drop_top_mips()`).
+
+/// Target device class (see module docs)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PlatformProfile {
+    #[default]
+    Desktop,
+    SteamDeck,
+    Mobile,
+}
+
+impl PlatformProfile {
+    /// Clamps of this profile
+    pub fn limits(self) -> PlatformLimits {
+        match self {
+            Self::Desktop => PlatformLimits {
+                max_texture_size: None,
+                max_anisotropy: 16.0,
+                max_shadow_resolution: 8192,
+            },
+            Self::SteamDeck => PlatformLimits {
+                max_texture_size: Some(2048),
+                max_anisotropy: 8.0,
+                max_shadow_resolution: 2048,
+            },
+            Self::Mobile => PlatformLimits {
+                max_texture_size: Some(1024),
+                max_anisotropy: 4.0,
+                max_shadow_resolution: 1024,
+            },
+        }
+    }
+}
+
+/// Resolution and filtering clamps applied on a platform
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlatformLimits {
+    /// Largest width or height of a loaded texture (None: unlimited)
+    pub max_texture_size: Option<u32>,
+    /// Highest sampler anisotropy (1.0 disables anisotropic filtering)
+    pub max_anisotropy: f32,
+    /// Largest shadow map width or height
+    pub max_shadow_resolution: u32,
+}
+
+impl Default for PlatformLimits {
+    fn default() -> Self {
+        PlatformProfile::Desktop.limits()
+    }
+}
+
+impl PlatformLimits {
+    /// Number of top mip levels to drop so that a `width` x `height`
+    /// texture fits in `max_texture_size` (0 if it already fits)
+    pub fn texture_mip_drop(&self, width: u32, height: u32) -> u32 {
+        let Some(max_size) = self.max_texture_size else {
+            return 0;
+        };
+        let max_size = max_size.max(1);
+        let mut levels = 0;
+        while (width.max(height) >> levels) > max_size {
+            levels += 1;
+        }
+        levels
+    }
+
+    /// `requested` anisotropy, clamped to `max_anisotropy`
+    pub fn clamp_anisotropy(&self, requested: f32) -> f32 {
+        requested.min(self.max_anisotropy.max(1.0))
+    }
+
+    /// `requested` shadow map resolution, clamped to `max_shadow_resolution`
+    pub fn clamp_shadow_resolution(&self, requested: u32) -> u32 {
+        requested.min(self.max_shadow_resolution.max(1))
+    }
+}
+
+#[cfg(test)]
+#[path = "platform_profile_tests.rs"]
+mod tests;
@@ -0,0 +1,33 @@
+/// Unit tests for platform_profile.rs
+
+use super::*;
+
+#[test]
+fn test_default_is_unclamped_desktop() {
+    let limits = PlatformLimits::default();
+    assert_eq!(limits, PlatformProfile::Desktop.limits());
+    assert_eq!(limits.texture_mip_drop(16384, 16384), 0);
+    assert_eq!(limits.clamp_anisotropy(16.0), 16.0);
+}
+
+#[test]
+fn test_texture_mip_drop_fits_largest_side() {
+    let limits = PlatformProfile::Mobile.limits();
+    assert_eq!(limits.texture_mip_drop(1024, 1024), 0);
+    assert_eq!(limits.texture_mip_drop(2048, 512), 1);
+    // 4097 >> 2 = 1024 fits; 8200 >> 3 = 1025 does not, 8200 >> 4 does
+    assert_eq!(limits.texture_mip_drop(1000, 4097), 2);
+    assert_eq!(limits.texture_mip_drop(8200, 1000), 4);
+}
+
+#[test]
+fn test_clamps_follow_profile() {
+    let deck = PlatformProfile::SteamDeck.limits();
+    assert_eq!(deck.clamp_anisotropy(16.0), 8.0);
+    assert_eq!(deck.clamp_anisotropy(2.0), 2.0);
+    assert_eq!(deck.clamp_shadow_resolution(4096), 2048);
+    assert_eq!(deck.clamp_shadow_resolution(1024), 1024);
+
+    let disabled = PlatformLimits { max_anisotropy: 0.0, ..deck };
+    assert_eq!(disabled.clamp_anisotropy(16.0), 1.0);
+}
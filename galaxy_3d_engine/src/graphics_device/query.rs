@@ -0,0 +1,160 @@
+/// GPU queries: occlusion and pipeline statistics.
+///
+/// A `QueryPool` (`GraphicsDevice::create_query_pool`) holds `count`
+/// queries of one `QueryType`. A query is reset with
+/// `CommandList::reset_queries` (outside a render pass), then counts the
+/// commands recorded between `CommandList::begin_query` and `end_query`.
+/// Once the command list has completed, `QueryPool::occlusion_results` or
+/// `QueryPool::pipeline_statistics` read the results. They never wait: a
+/// query whose result is not available yet reads `None`, so results can be
+/// polled a frame or two later without stalling the CPU.
+///
+/// ```ignore
+/// cmd.reset_queries(&pool, 0, 1)?;
+/// cmd.begin_render_pass(...)?;
+/// cmd.begin_query(&pool, 0)?;
+/// cmd.draw_indexed(bounding_box_indices, 0, 0)?;
+/// cmd.end_query(&pool, 0)?;
+/// cmd.end_render_pass()?;
+/// // ...frames later
+/// let visible = pool.occlusion_results(0, 1)?[0].map(|samples| samples > 0);
+/// ```
+
+use crate::error::Result;
+use crate::engine_bail;
+
+/// Kind of the queries of a pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QueryType {
+    /// Number of samples passing the depth and stencil tests. Exact when the
+    /// device supports precise occlusion queries, otherwise only zero /
+    /// non-zero is meaningful.
+    Occlusion,
+    /// Shader invocation and primitive counters (`PipelineStatistics`).
+    /// Only available when
+    /// `GraphicsDevice::supports_pipeline_statistics_queries()` is true.
+    PipelineStatistics,
+}
+
+/// Descriptor for creating a query pool
+#[derive(Debug, Clone)]
+pub struct QueryPoolDesc {
+    /// Kind of every query of the pool
+    pub query_type: QueryType,
+    /// Number of queries
+    pub count: u32,
+    /// Name shown by graphics debuggers
+    pub debug_name: Option<String>,
+}
+
+impl QueryPoolDesc {
+    /// Check the query count.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `count` is 0.
+    pub fn validate(&self) -> Result<()> {
+        if self.count == 0 {
+            engine_bail!("galaxy3d::QueryPool", "{:?} query pool needs at least one query", self.query_type);
+        }
+        Ok(())
+    }
+}
+
+/// Number of counters of a pipeline statistics query
+pub const PIPELINE_STATISTICS_COUNTER_COUNT: usize = 7;
+
+/// Result of a pipeline statistics query
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PipelineStatistics {
+    /// Vertices read by the input assembler
+    pub input_assembly_vertices: u64,
+    /// Primitives read by the input assembler
+    pub input_assembly_primitives: u64,
+    /// Vertex shader invocations
+    pub vertex_shader_invocations: u64,
+    /// Primitives reaching the clipping stage
+    pub clipping_invocations: u64,
+    /// Primitives output by the clipping stage
+    pub clipping_primitives: u64,
+    /// Fragment shader invocations
+    pub fragment_shader_invocations: u64,
+    /// Compute shader invocations
+    pub compute_shader_invocations: u64,
+}
+
+impl PipelineStatistics {
+    /// Statistics from the raw counters of a query, in field order
+    pub fn from_counters(counters: &[u64; PIPELINE_STATISTICS_COUNTER_COUNT]) -> Self {
+        Self {
+            input_assembly_vertices: counters[0],
+            input_assembly_primitives: counters[1],
+            vertex_shader_invocations: counters[2],
+            clipping_invocations: counters[3],
+            clipping_primitives: counters[4],
+            fragment_shader_invocations: counters[5],
+            compute_shader_invocations: counters[6],
+        }
+    }
+}
+
+/// Query pool resource trait
+///
+/// Implemented by backend-specific query pools. Destroyed when dropped
+/// (after the GPU is done with it).
+pub trait QueryPool: Send + Sync {
+    /// Kind of the queries
+    fn query_type(&self) -> QueryType;
+
+    /// Number of queries
+    fn count(&self) -> u32;
+
+    /// Sample counts of `query_count` occlusion queries from `first_query`
+    /// (`None`: result not available yet)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pool does not hold occlusion queries or the
+    /// range is out of the pool.
+    fn occlusion_results(&self, first_query: u32, query_count: u32) -> Result<Vec<Option<u64>>>;
+
+    /// Counters of `query_count` pipeline statistics queries from
+    /// `first_query` (`None`: result not available yet)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pool does not hold pipeline statistics
+    /// queries or the range is out of the pool.
+    fn pipeline_statistics(&self, first_query: u32, query_count: u32) -> Result<Vec<Option<PipelineStatistics>>>;
+}
+
+/// Check that `query_count` queries from `first_query` exist in `pool` and
+/// are of `query_type`. Backends call it before recording or reading
+/// queries.
+///
+/// # Errors
+///
+/// Returns an error if the type does not match or the range is empty or
+/// out of the pool.
+pub fn validate_query_range(
+    pool: &dyn QueryPool,
+    query_type: QueryType,
+    first_query: u32,
+    query_count: u32,
+) -> Result<()> {
+    if pool.query_type() != query_type {
+        engine_bail!("galaxy3d::QueryPool",
+            "{:?} query pool used for {:?} queries", pool.query_type(), query_type);
+    }
+    let in_range = first_query.checked_add(query_count).is_some_and(|end| end <= pool.count());
+    if query_count == 0 || !in_range {
+        engine_bail!("galaxy3d::QueryPool",
+            "queries {}..{} out of range (pool of {})",
+            first_query, first_query as u64 + query_count as u64, pool.count());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "query_tests.rs"]
+mod tests;
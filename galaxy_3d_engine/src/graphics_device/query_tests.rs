@@ -0,0 +1,69 @@
+use super::*;
+use crate::graphics_device::mock_graphics_device::{MockGraphicsDevice, MockQueryPool};
+use crate::graphics_device::GraphicsDevice;
+
+fn desc(query_type: QueryType, count: u32) -> QueryPoolDesc {
+    QueryPoolDesc { query_type, count, debug_name: None }
+}
+
+// ============================================================================
+// QueryPoolDesc
+// ============================================================================
+
+#[test]
+fn test_desc_validate_rejects_empty_pool() {
+    assert!(desc(QueryType::Occlusion, 0).validate().is_err());
+    assert!(desc(QueryType::Occlusion, 1).validate().is_ok());
+}
+
+#[test]
+fn test_create_pipeline_statistics_pool_needs_support() {
+    let mut gd = MockGraphicsDevice::new();
+    assert!(gd.create_query_pool(&desc(QueryType::PipelineStatistics, 4)).is_err());
+    assert!(gd.create_query_pool(&desc(QueryType::Occlusion, 4)).is_ok());
+
+    gd.pipeline_statistics_queries = true;
+    let pool = gd.create_query_pool(&desc(QueryType::PipelineStatistics, 4)).unwrap();
+    assert_eq!(pool.query_type(), QueryType::PipelineStatistics);
+    assert_eq!(pool.count(), 4);
+}
+
+// ============================================================================
+// validate_query_range
+// ============================================================================
+
+#[test]
+fn test_query_range_in_pool() {
+    let pool = MockQueryPool::new(QueryType::Occlusion, 8);
+    assert!(validate_query_range(&pool, QueryType::Occlusion, 0, 8).is_ok());
+    assert!(validate_query_range(&pool, QueryType::Occlusion, 7, 1).is_ok());
+    assert!(validate_query_range(&pool, QueryType::Occlusion, 7, 2).is_err());
+    assert!(validate_query_range(&pool, QueryType::Occlusion, 8, 0).is_err());
+    assert!(validate_query_range(&pool, QueryType::Occlusion, u32::MAX, 2).is_err());
+}
+
+#[test]
+fn test_query_range_rejects_other_type() {
+    let pool = MockQueryPool::new(QueryType::Occlusion, 8);
+    assert!(validate_query_range(&pool, QueryType::PipelineStatistics, 0, 1).is_err());
+    assert!(pool.pipeline_statistics(0, 1).is_err());
+    assert_eq!(pool.occlusion_results(2, 3).unwrap().len(), 3);
+}
+
+// ============================================================================
+// PipelineStatistics
+// ============================================================================
+
+#[test]
+fn test_pipeline_statistics_from_counters() {
+    let stats = PipelineStatistics::from_counters(&[1, 2, 3, 4, 5, 6, 7]);
+    assert_eq!(stats, PipelineStatistics {
+        input_assembly_vertices: 1,
+        input_assembly_primitives: 2,
+        vertex_shader_invocations: 3,
+        clipping_invocations: 4,
+        clipping_primitives: 5,
+        fragment_shader_invocations: 6,
+        compute_shader_invocations: 7,
+    });
+}
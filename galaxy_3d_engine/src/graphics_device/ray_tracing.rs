@@ -0,0 +1,283 @@
+/// Hardware ray tracing types: acceleration structures, ray tracing
+/// pipelines and their shader binding table.
+///
+/// Ray tracing is opt-in (`Config::ray_tracing`) and only available when
+/// `GraphicsDevice::supports_ray_tracing()` is true:
+///
+/// - a bottom-level acceleration structure (BLAS) holds the triangles of
+///   one or more geometries, in local space. It is built once by
+///   `GraphicsDevice::create_blas`.
+/// - a top-level acceleration structure (TLAS) places BLAS instances in the
+///   world. `GraphicsDevice::create_tlas` allocates it for up to
+///   `max_instances` instances; `CommandList::build_tlas` rebuilds it from
+///   `TlasInstance`s, typically once per frame.
+/// - a ray tracing pipeline (`GraphicsDevice::create_ray_tracing_pipeline`)
+///   groups a ray generation shader, miss shaders and hit groups. The
+///   backend creates its shader binding table (`ShaderBindingTableLayout`);
+///   `CommandList::trace_rays` launches the bound pipeline.
+
+use std::sync::Arc;
+use glam::Mat4;
+use crate::error::Result;
+use crate::engine_bail;
+use crate::graphics_device::{Buffer, BufferFormat, IndexType, Shader};
+
+/// Size of one `TlasInstance` in the instance buffer of a TLAS
+/// (`VkAccelerationStructureInstanceKHR`, `D3D12_RAYTRACING_INSTANCE_DESC`)
+pub const TLAS_INSTANCE_SIZE: usize = 64;
+/// Largest `TlasInstance::custom_index` / `hit_group_offset` (24 bits)
+pub const TLAS_INSTANCE_MAX_INDEX: u32 = 0x00FF_FFFF;
+
+/// Level of an acceleration structure
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccelerationStructureKind {
+    /// Triangles of geometries (local space)
+    BottomLevel,
+    /// Instances of bottom-level structures (world space)
+    TopLevel,
+}
+
+/// Acceleration structure resource trait
+///
+/// Implemented by backend-specific acceleration structures. Destroyed when
+/// dropped (after the GPU is done with it).
+pub trait AccelerationStructure: Send + Sync {
+    /// Bottom or top level
+    fn kind(&self) -> AccelerationStructureKind;
+    /// Size of the acceleration structure storage (bytes)
+    fn size(&self) -> u64;
+    /// GPU address, referenced by `TlasInstance::blas_address`
+    fn device_address(&self) -> u64;
+}
+
+/// Triangles of one geometry of a BLAS, read from vertex and index buffers
+#[derive(Clone)]
+pub struct BlasGeometry {
+    /// Vertex buffer holding the positions
+    pub vertex_buffer: Arc<dyn Buffer>,
+    /// Position format (`R32G32B32_SFLOAT`, `R32G32_SFLOAT`,
+    /// `R16G16B16A16_SFLOAT` or `R16G16_SFLOAT`)
+    pub vertex_format: BufferFormat,
+    /// Bytes between two vertices
+    pub vertex_stride: u32,
+    /// Byte offset of the position inside a vertex
+    pub position_offset: u32,
+    /// Vertex added to every index (base vertex)
+    pub first_vertex: u32,
+    /// Number of vertices from `first_vertex`
+    pub vertex_count: u32,
+    /// Index buffer (None: non-indexed triangle list)
+    pub index_buffer: Option<Arc<dyn Buffer>>,
+    /// Index element type (ignored without index buffer)
+    pub index_type: IndexType,
+    /// First index in the index buffer (ignored without index buffer)
+    pub first_index: u32,
+    /// Number of triangles (triangle list)
+    pub triangle_count: u32,
+    /// No any hit shader runs on these triangles
+    pub opaque: bool,
+}
+
+/// Descriptor for creating a bottom-level acceleration structure
+#[derive(Clone)]
+pub struct BlasDesc {
+    /// Geometries of the BLAS (`gl_GeometryIndexEXT` in hit shaders)
+    pub geometries: Vec<BlasGeometry>,
+    /// Name shown by graphics debuggers
+    pub debug_name: Option<String>,
+}
+
+/// Descriptor for creating a top-level acceleration structure
+#[derive(Debug, Clone)]
+pub struct TlasDesc {
+    /// Largest instance count given to `CommandList::build_tlas`
+    pub max_instances: u32,
+    /// Name shown by graphics debuggers
+    pub debug_name: Option<String>,
+}
+
+/// An instance of a BLAS in a TLAS
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TlasInstance {
+    /// Local to world transform (the last row must be 0, 0, 0, 1)
+    pub transform: Mat4,
+    /// Value of `gl_InstanceCustomIndexEXT` (24 bits), e.g. a draw slot
+    pub custom_index: u32,
+    /// Visibility mask, ANDed with the cull mask of `traceRayEXT`
+    pub mask: u8,
+    /// Hit group of the instance in the shader binding table (24 bits)
+    pub hit_group_offset: u32,
+    /// Skip back face culling (two-sided materials)
+    pub double_sided: bool,
+    /// Treat every triangle as opaque (no any hit shader)
+    pub force_opaque: bool,
+    /// `AccelerationStructure::device_address` of the instanced BLAS
+    pub blas_address: u64,
+}
+
+impl TlasInstance {
+    /// Instance buffer layout (`TLAS_INSTANCE_SIZE` bytes):
+    ///
+    /// ```text
+    /// float transform[3][4];                   // row-major 3x4
+    /// uint  customIndex : 24, mask : 8;
+    /// uint  hitGroupOffset : 24, flags : 8;    // 0x1 cull disable, 0x4 opaque
+    /// u64   blasAddress;
+    /// ```
+    pub fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        let rows = self.transform.transpose().to_cols_array();
+        bytes.extend_from_slice(bytemuck::cast_slice(&rows[..12]));
+        let mut flags = 0u32;
+        if self.double_sided {
+            flags |= 0x1;
+        }
+        if self.force_opaque {
+            flags |= 0x4;
+        }
+        let words = [
+            (self.custom_index & TLAS_INSTANCE_MAX_INDEX) | ((self.mask as u32) << 24),
+            (self.hit_group_offset & TLAS_INSTANCE_MAX_INDEX) | (flags << 24),
+        ];
+        bytes.extend_from_slice(bytemuck::cast_slice(&words));
+        bytes.extend_from_slice(&self.blas_address.to_ne_bytes());
+    }
+}
+
+/// Hit group of a ray tracing pipeline (triangles)
+#[derive(Clone, Default)]
+pub struct RayTracingHitGroup {
+    /// Closest hit shader
+    pub closest_hit: Option<Arc<dyn Shader>>,
+    /// Any hit shader (alpha testing)
+    pub any_hit: Option<Arc<dyn Shader>>,
+}
+
+/// Descriptor for creating a ray tracing pipeline
+///
+/// Descriptor set layouts and push constants are deduced from the shaders,
+/// as for graphics pipelines (set 0 is the bindless set).
+#[derive(Clone)]
+pub struct RayTracingPipelineDesc {
+    /// Ray generation shader
+    pub raygen: Arc<dyn Shader>,
+    /// Miss shaders (miss index of `traceRayEXT`)
+    pub miss: Vec<Arc<dyn Shader>>,
+    /// Hit groups (instance `hit_group_offset` + SBT offset of `traceRayEXT`)
+    pub hit_groups: Vec<RayTracingHitGroup>,
+    /// Deepest `traceRayEXT` nesting (1: no ray traced from hit or miss shaders)
+    pub max_recursion_depth: u32,
+    /// Name shown by graphics debuggers
+    pub debug_name: Option<String>,
+}
+
+/// Region of a shader binding table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ShaderBindingTableRegion {
+    /// Byte offset of the region in the table
+    pub offset: u64,
+    /// Bytes between two records
+    pub stride: u64,
+    /// Region size (0 for an empty region)
+    pub size: u64,
+}
+
+/// Layout of a shader binding table: one ray generation record, then the
+/// miss records, then the hit group records.
+///
+/// Records are shader group handles padded to `handle_alignment`; each
+/// region starts on `base_alignment`. The ray generation region holds a
+/// single record whose stride equals its size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShaderBindingTableLayout {
+    /// Ray generation region
+    pub raygen: ShaderBindingTableRegion,
+    /// Miss region
+    pub miss: ShaderBindingTableRegion,
+    /// Hit group region
+    pub hit: ShaderBindingTableRegion,
+    /// Size of the whole table
+    pub size: u64,
+    /// Size of a shader group handle
+    pub handle_size: u32,
+    /// Number of miss records
+    pub miss_count: u32,
+    /// Number of hit group records
+    pub hit_count: u32,
+}
+
+impl ShaderBindingTableLayout {
+    /// Compute the layout from the device limits and the group counts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an alignment is not a power of two.
+    pub fn new(
+        handle_size: u32,
+        handle_alignment: u32,
+        base_alignment: u32,
+        miss_count: u32,
+        hit_count: u32,
+    ) -> Result<Self> {
+        if !handle_alignment.is_power_of_two() || !base_alignment.is_power_of_two() {
+            engine_bail!("galaxy3d::ShaderBindingTable",
+                "Alignments must be powers of two (handle {}, base {})", handle_alignment, base_alignment);
+        }
+        let align_up = |value: u64, alignment: u32| {
+            let mask = alignment as u64 - 1;
+            (value + mask) & !mask
+        };
+        let record = align_up(handle_size as u64, handle_alignment);
+        let raygen_size = align_up(record, base_alignment);
+        let raygen = ShaderBindingTableRegion { offset: 0, stride: raygen_size, size: raygen_size };
+        let region = |offset: u64, count: u32| ShaderBindingTableRegion {
+            offset,
+            stride: record,
+            size: align_up(record * count as u64, base_alignment),
+        };
+        let miss = region(raygen.size, miss_count);
+        let hit = region(miss.offset + miss.size, hit_count);
+        Ok(Self {
+            raygen,
+            miss,
+            hit,
+            size: hit.offset + hit.size,
+            handle_size,
+            miss_count,
+            hit_count,
+        })
+    }
+
+    /// Number of shader groups (ray generation, miss and hit groups)
+    pub fn group_count(&self) -> u32 {
+        1 + self.miss_count + self.hit_count
+    }
+
+    /// Place the shader group handles (`handle_size` bytes each, in group
+    /// order: ray generation, miss, hit groups) into a table of `size`
+    /// bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `handles` does not hold one handle per group.
+    pub fn write_table(&self, handles: &[u8]) -> Result<Vec<u8>> {
+        let handle_size = self.handle_size as usize;
+        if handles.len() != self.group_count() as usize * handle_size {
+            engine_bail!("galaxy3d::ShaderBindingTable",
+                "Expected {} handles of {} bytes, got {} bytes",
+                self.group_count(), handle_size, handles.len());
+        }
+        let records = std::iter::once(self.raygen.offset)
+            .chain((0..self.miss_count as u64).map(|i| self.miss.offset + i * self.miss.stride))
+            .chain((0..self.hit_count as u64).map(|i| self.hit.offset + i * self.hit.stride));
+        let mut table = vec![0u8; self.size as usize];
+        for (offset, handle) in records.zip(handles.chunks_exact(handle_size)) {
+            let offset = offset as usize;
+            table[offset..offset + handle_size].copy_from_slice(handle);
+        }
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+#[path = "ray_tracing_tests.rs"]
+mod tests;
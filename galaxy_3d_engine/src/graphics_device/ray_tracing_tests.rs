@@ -0,0 +1,123 @@
+use super::*;
+use glam::Vec3;
+
+fn instance() -> TlasInstance {
+    TlasInstance {
+        transform: Mat4::from_translation(Vec3::new(1.0, 2.0, 3.0)),
+        custom_index: 7,
+        mask: 0xFF,
+        hit_group_offset: 0,
+        double_sided: false,
+        force_opaque: false,
+        blas_address: 0x1234_5678_9ABC,
+    }
+}
+
+fn words(bytes: &[u8]) -> Vec<u32> {
+    bytes.chunks_exact(4).map(|c| u32::from_ne_bytes(c.try_into().unwrap())).collect()
+}
+
+// ============================================================================
+// TlasInstance
+// ============================================================================
+
+#[test]
+fn test_instance_write_bytes_size() {
+    let mut bytes = Vec::new();
+    instance().write_bytes(&mut bytes);
+    instance().write_bytes(&mut bytes);
+    assert_eq!(bytes.len(), 2 * TLAS_INSTANCE_SIZE);
+}
+
+#[test]
+fn test_instance_transform_is_row_major_3x4() {
+    let mut bytes = Vec::new();
+    instance().write_bytes(&mut bytes);
+    let floats: &[f32] = bytemuck::cast_slice(&bytes[..48]);
+    assert_eq!(floats, [
+        1.0, 0.0, 0.0, 1.0,
+        0.0, 1.0, 0.0, 2.0,
+        0.0, 0.0, 1.0, 3.0,
+    ]);
+}
+
+#[test]
+fn test_instance_packs_index_mask_and_flags() {
+    let mut inst = instance();
+    inst.custom_index = 0x0ABCDE;
+    inst.mask = 0x03;
+    inst.hit_group_offset = 2;
+    inst.double_sided = true;
+    inst.force_opaque = true;
+    let mut bytes = Vec::new();
+    inst.write_bytes(&mut bytes);
+    let w = words(&bytes);
+    assert_eq!(w[12], 0x030A_BCDE);
+    assert_eq!(w[13], 0x0500_0002);
+    assert_eq!(u64::from_ne_bytes(bytes[56..64].try_into().unwrap()), 0x1234_5678_9ABC);
+}
+
+#[test]
+fn test_instance_index_truncated_to_24_bits() {
+    let mut inst = instance();
+    inst.custom_index = 0x1FF_FFFF;
+    inst.mask = 0;
+    let mut bytes = Vec::new();
+    inst.write_bytes(&mut bytes);
+    assert_eq!(words(&bytes)[12], TLAS_INSTANCE_MAX_INDEX);
+}
+
+// ============================================================================
+// ShaderBindingTableLayout
+// ============================================================================
+
+#[test]
+fn test_sbt_layout_regions() {
+    // Common desktop limits: 32-byte handles, 32-byte records, 64-byte regions
+    let layout = ShaderBindingTableLayout::new(32, 32, 64, 2, 3).unwrap();
+    assert_eq!(layout.raygen, ShaderBindingTableRegion { offset: 0, stride: 64, size: 64 });
+    assert_eq!(layout.miss, ShaderBindingTableRegion { offset: 64, stride: 32, size: 64 });
+    assert_eq!(layout.hit, ShaderBindingTableRegion { offset: 128, stride: 32, size: 128 });
+    assert_eq!(layout.size, 256);
+    assert_eq!(layout.group_count(), 6);
+}
+
+#[test]
+fn test_sbt_layout_pads_records_to_handle_alignment() {
+    let layout = ShaderBindingTableLayout::new(24, 32, 32, 1, 1).unwrap();
+    assert_eq!(layout.miss.stride, 32);
+    assert_eq!(layout.hit.offset, 64);
+    assert_eq!(layout.size, 96);
+}
+
+#[test]
+fn test_sbt_layout_empty_regions() {
+    let layout = ShaderBindingTableLayout::new(32, 32, 64, 0, 0).unwrap();
+    assert_eq!(layout.miss.size, 0);
+    assert_eq!(layout.hit.size, 0);
+    assert_eq!(layout.size, 64);
+}
+
+#[test]
+fn test_sbt_layout_rejects_bad_alignment() {
+    assert!(ShaderBindingTableLayout::new(32, 24, 64, 1, 1).is_err());
+    assert!(ShaderBindingTableLayout::new(32, 32, 0, 1, 1).is_err());
+}
+
+#[test]
+fn test_sbt_write_table_places_handles() {
+    let layout = ShaderBindingTableLayout::new(4, 8, 16, 2, 1).unwrap();
+    let handles: Vec<u8> = (1..=4u8).flat_map(|g| [g; 4]).collect();
+    let table = layout.write_table(&handles).unwrap();
+    assert_eq!(table.len(), layout.size as usize);
+    assert_eq!(&table[0..8], [1, 1, 1, 1, 0, 0, 0, 0]);
+    assert_eq!(&table[16..20], [2; 4]);
+    assert_eq!(&table[24..28], [3; 4]);
+    assert_eq!(&table[32..36], [4; 4]);
+}
+
+#[test]
+fn test_sbt_write_table_rejects_wrong_handle_count() {
+    let layout = ShaderBindingTableLayout::new(32, 32, 64, 1, 1).unwrap();
+    assert!(layout.write_table(&[0u8; 64]).is_err());
+}
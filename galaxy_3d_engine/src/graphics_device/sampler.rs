@@ -0,0 +1,234 @@
+/// Sampler description: how a texture is filtered and addressed.
+///
+/// `SamplerType` names the presets of the bindless sampler table;
+/// `SamplerDesc` describes any sampler in full. It is bound with
+/// `BindingResource::SampledTextureDesc`, and the backend creates and
+/// caches one GPU sampler per distinct description.
+///
+/// Shadow maps are sampled with hardware PCF: a `SamplerDesc::depth_compare`
+/// sampler on a depth texture, bound to a `BindingType::SampledDepthTexture`
+/// slot. Each fetch returns the filtered result of comparing the 4 nearest
+/// depths with the reference value; `pcf_grid_offsets` spreads more fetches
+/// over a wider kernel:
+///
+/// ```glsl
+/// layout(set = 1, binding = 0) uniform sampler2DShadow shadowMap;
+/// layout(set = 1, binding = 1) uniform Pcf { vec4 offsets[49]; int count; vec2 texel; };
+///
+/// float lit = 0.0;
+/// for (int i = 0; i < count; i++)
+///     lit += texture(shadowMap, vec3(uv + offsets[i].xy * texel, depth));
+/// lit /= float(count);
+/// ```
+
+use crate::error::Result;
+use crate::engine_bail;
+use crate::graphics_device::{CompareOp, SamplerType};
+
+/// Texel filter used for magnification and minification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
+pub enum Filter {
+    /// Closest texel
+    Nearest,
+    /// Bilinear blend of the 4 closest texels
+    Linear,
+}
+
+/// Filter between mip levels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
+pub enum SamplerMipmapMode {
+    /// Closest mip level
+    Nearest,
+    /// Blend of the 2 closest mip levels (trilinear filtering)
+    Linear,
+}
+
+/// Behavior of texture coordinates outside [0, 1]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
+pub enum AddressMode {
+    /// Tile the texture
+    Repeat,
+    /// Tile the texture, mirrored every other tile
+    MirroredRepeat,
+    /// Clamp to the edge texels
+    ClampToEdge,
+    /// Return `SamplerDesc::border_color`
+    ClampToBorder,
+}
+
+/// Color returned by `AddressMode::ClampToBorder`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
+pub enum BorderColor {
+    /// (0, 0, 0, 0)
+    TransparentBlack,
+    /// (0, 0, 0, 1)
+    OpaqueBlack,
+    /// (1, 1, 1, 1)
+    OpaqueWhite,
+}
+
+/// Full sampler description
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
+pub struct SamplerDesc {
+    /// Filter when a texel covers several pixels
+    pub mag_filter: Filter,
+    /// Filter when a pixel covers several texels
+    pub min_filter: Filter,
+    /// Filter between mip levels
+    pub mipmap_mode: SamplerMipmapMode,
+    /// Addressing of the U coordinate
+    pub address_mode_u: AddressMode,
+    /// Addressing of the V coordinate
+    pub address_mode_v: AddressMode,
+    /// Addressing of the W coordinate (3D textures)
+    pub address_mode_w: AddressMode,
+    /// Anisotropic filtering level (`None`: disabled). Clamped to the
+    /// platform profile's `max_anisotropy`.
+    pub max_anisotropy: Option<f32>,
+    /// Depth comparison against the reference value (shadow samplers)
+    pub compare: Option<CompareOp>,
+    /// Color outside [0, 1] with `AddressMode::ClampToBorder`
+    pub border_color: BorderColor,
+    /// Bias added to the computed mip level
+    pub mip_lod_bias: f32,
+    /// Most detailed mip level used
+    pub min_lod: f32,
+    /// Least detailed mip level used (`None`: no clamp)
+    pub max_lod: Option<f32>,
+}
+
+impl Default for SamplerDesc {
+    /// Trilinear filtering, repeat addressing, no anisotropy
+    fn default() -> Self {
+        Self {
+            mag_filter: Filter::Linear,
+            min_filter: Filter::Linear,
+            mipmap_mode: SamplerMipmapMode::Linear,
+            address_mode_u: AddressMode::Repeat,
+            address_mode_v: AddressMode::Repeat,
+            address_mode_w: AddressMode::Repeat,
+            max_anisotropy: None,
+            compare: None,
+            border_color: BorderColor::OpaqueBlack,
+            mip_lod_bias: 0.0,
+            min_lod: 0.0,
+            max_lod: None,
+        }
+    }
+}
+
+impl SamplerDesc {
+    /// Same address mode on all three coordinates
+    pub fn with_address_mode(mut self, mode: AddressMode) -> Self {
+        self.address_mode_u = mode;
+        self.address_mode_v = mode;
+        self.address_mode_w = mode;
+        self
+    }
+
+    /// Same filter for magnification, minification and mip levels
+    pub fn with_filter(mut self, filter: Filter) -> Self {
+        self.mag_filter = filter;
+        self.min_filter = filter;
+        self.mipmap_mode = match filter {
+            Filter::Nearest => SamplerMipmapMode::Nearest,
+            Filter::Linear => SamplerMipmapMode::Linear,
+        };
+        self
+    }
+
+    /// Hardware PCF sampler: bilinear depth comparison with `op`, clamped
+    /// to an opaque white border (outside the shadow map is lit with
+    /// `Less` / `LessOrEqual`)
+    pub fn depth_compare(op: CompareOp) -> Self {
+        Self {
+            mipmap_mode: SamplerMipmapMode::Nearest,
+            compare: Some(op),
+            border_color: BorderColor::OpaqueWhite,
+            ..Self::default().with_address_mode(AddressMode::ClampToBorder)
+        }
+    }
+
+    /// Check the numeric fields.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a value is NaN, `max_anisotropy` is below 1,
+    /// a LOD is negative or `max_lod` is below `min_lod`.
+    pub fn validate(&self) -> Result<()> {
+        if let Some(anisotropy) = self.max_anisotropy {
+            if anisotropy.is_nan() || anisotropy < 1.0 {
+                engine_bail!("galaxy3d::SamplerDesc", "max_anisotropy must be at least 1 (got {})", anisotropy);
+            }
+        }
+        if self.mip_lod_bias.is_nan() || self.min_lod.is_nan() || self.min_lod < 0.0 {
+            engine_bail!("galaxy3d::SamplerDesc",
+                "invalid LOD settings (mip_lod_bias {}, min_lod {})", self.mip_lod_bias, self.min_lod);
+        }
+        if let Some(max_lod) = self.max_lod {
+            if max_lod.is_nan() || max_lod < self.min_lod {
+                engine_bail!("galaxy3d::SamplerDesc",
+                    "max_lod ({}) must not be below min_lod ({})", max_lod, self.min_lod);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<SamplerType> for SamplerDesc {
+    /// Description of a preset of the bindless sampler table
+    fn from(sampler_type: SamplerType) -> Self {
+        match sampler_type {
+            SamplerType::LinearRepeat | SamplerType::Anisotropic => Self {
+                max_anisotropy: Some(16.0),
+                ..Self::default()
+            },
+            SamplerType::LinearClamp => Self {
+                max_anisotropy: Some(16.0),
+                ..Self::default().with_address_mode(AddressMode::ClampToEdge)
+            },
+            SamplerType::NearestRepeat => Self::default().with_filter(Filter::Nearest),
+            SamplerType::NearestClamp => Self::default()
+                .with_filter(Filter::Nearest)
+                .with_address_mode(AddressMode::ClampToEdge),
+            SamplerType::Shadow => Self::depth_compare(CompareOp::LessOrEqual),
+        }
+    }
+}
+
+/// Largest `pcf_grid_offsets` kernel
+pub const PCF_MAX_KERNEL_SIZE: u32 = 7;
+
+/// Texel offsets of a `kernel_size` x `kernel_size` PCF kernel, centered
+/// on the sample (row by row).
+///
+/// Odd sizes land on texel centers. Even sizes are offset by half a texel,
+/// so that each bilinear comparison fetch covers 2x2 texels: a 2x2 kernel
+/// then filters 3x3 texels, a 4x4 kernel 5x5.
+///
+/// # Errors
+///
+/// Returns an error if `kernel_size` is 0 or above `PCF_MAX_KERNEL_SIZE`.
+pub fn pcf_grid_offsets(kernel_size: u32) -> Result<Vec<[f32; 2]>> {
+    if kernel_size == 0 || kernel_size > PCF_MAX_KERNEL_SIZE {
+        engine_bail!("galaxy3d::pcf_grid_offsets",
+            "kernel_size must be in 1..={} (got {})", PCF_MAX_KERNEL_SIZE, kernel_size);
+    }
+    let half = (kernel_size - 1) as f32 * 0.5;
+    let mut offsets = Vec::with_capacity((kernel_size * kernel_size) as usize);
+    for y in 0..kernel_size {
+        for x in 0..kernel_size {
+            offsets.push([x as f32 - half, y as f32 - half]);
+        }
+    }
+    Ok(offsets)
+}
+
+#[cfg(test)]
+#[path = "sampler_tests.rs"]
+mod tests;
@@ -0,0 +1,120 @@
+//! Unit tests for Sampler module
+//!
+//! Tests SamplerDesc presets, builders and validation.
+
+use crate::graphics_device::{
+    AddressMode, BorderColor, CompareOp, Filter, SamplerDesc, SamplerMipmapMode, SamplerType,
+};
+
+// ============================================================================
+// PRESETS
+// ============================================================================
+
+#[test]
+fn test_default_is_trilinear_repeat() {
+    let desc = SamplerDesc::default();
+    assert_eq!(desc.min_filter, Filter::Linear);
+    assert_eq!(desc.mipmap_mode, SamplerMipmapMode::Linear);
+    assert_eq!(desc.address_mode_w, AddressMode::Repeat);
+    assert_eq!(desc.max_anisotropy, None);
+    assert_eq!(desc.compare, None);
+    assert_eq!(desc.max_lod, None);
+}
+
+#[test]
+fn test_sampler_type_presets() {
+    let linear_clamp = SamplerDesc::from(SamplerType::LinearClamp);
+    assert_eq!(linear_clamp.address_mode_u, AddressMode::ClampToEdge);
+    assert_eq!(linear_clamp.max_anisotropy, Some(16.0));
+
+    let nearest = SamplerDesc::from(SamplerType::NearestRepeat);
+    assert_eq!(nearest.mag_filter, Filter::Nearest);
+    assert_eq!(nearest.mipmap_mode, SamplerMipmapMode::Nearest);
+    assert_eq!(nearest.max_anisotropy, None);
+
+    let shadow = SamplerDesc::from(SamplerType::Shadow);
+    assert_eq!(shadow.compare, Some(CompareOp::LessOrEqual));
+    assert_eq!(shadow.address_mode_v, AddressMode::ClampToBorder);
+    assert_eq!(shadow.border_color, BorderColor::OpaqueWhite);
+    assert_eq!(shadow.mag_filter, Filter::Linear);
+}
+
+#[test]
+fn test_builders_set_every_coordinate() {
+    let desc = SamplerDesc::default()
+        .with_address_mode(AddressMode::MirroredRepeat)
+        .with_filter(Filter::Nearest);
+    assert_eq!(desc.address_mode_u, AddressMode::MirroredRepeat);
+    assert_eq!(desc.address_mode_v, AddressMode::MirroredRepeat);
+    assert_eq!(desc.address_mode_w, AddressMode::MirroredRepeat);
+    assert_eq!(desc.min_filter, Filter::Nearest);
+    assert_eq!(desc.mipmap_mode, SamplerMipmapMode::Nearest);
+}
+
+// ============================================================================
+// VALIDATION
+// ============================================================================
+
+#[test]
+fn test_validate_accepts_presets() {
+    for sampler_type in [
+        SamplerType::LinearRepeat, SamplerType::LinearClamp, SamplerType::NearestRepeat,
+        SamplerType::NearestClamp, SamplerType::Shadow, SamplerType::Anisotropic,
+    ] {
+        assert!(SamplerDesc::from(sampler_type).validate().is_ok());
+    }
+}
+
+#[test]
+fn test_validate_rejects_invalid_values() {
+    let anisotropy = SamplerDesc { max_anisotropy: Some(0.5), ..Default::default() };
+    assert!(anisotropy.validate().is_err());
+
+    let lod_range = SamplerDesc { min_lod: 4.0, max_lod: Some(2.0), ..Default::default() };
+    assert!(lod_range.validate().is_err());
+
+    let negative_lod = SamplerDesc { min_lod: -1.0, ..Default::default() };
+    assert!(negative_lod.validate().is_err());
+
+    let nan_bias = SamplerDesc { mip_lod_bias: f32::NAN, ..Default::default() };
+    assert!(nan_bias.validate().is_err());
+}
+
+// ============================================================================
+// PCF
+// ============================================================================
+
+#[test]
+fn test_depth_compare_sampler() {
+    let desc = SamplerDesc::depth_compare(CompareOp::Greater);
+    assert_eq!(desc.compare, Some(CompareOp::Greater));
+    assert_eq!(desc.mag_filter, Filter::Linear);
+    assert_eq!(desc.address_mode_u, AddressMode::ClampToBorder);
+    assert_eq!(desc.border_color, BorderColor::OpaqueWhite);
+    assert_eq!(SamplerDesc::from(SamplerType::Shadow), SamplerDesc::depth_compare(CompareOp::LessOrEqual));
+}
+
+#[test]
+fn test_pcf_grid_offsets_odd_kernel_is_centered_on_texels() {
+    let offsets = crate::graphics_device::pcf_grid_offsets(3).unwrap();
+    assert_eq!(offsets.len(), 9);
+    assert_eq!(offsets[0], [-1.0, -1.0]);
+    assert_eq!(offsets[4], [0.0, 0.0]);
+    assert_eq!(offsets[8], [1.0, 1.0]);
+}
+
+#[test]
+fn test_pcf_grid_offsets_even_kernel_is_offset_by_half_texel() {
+    let offsets = crate::graphics_device::pcf_grid_offsets(2).unwrap();
+    assert_eq!(offsets, vec![[-0.5, -0.5], [0.5, -0.5], [-0.5, 0.5], [0.5, 0.5]]);
+    let sum = offsets.iter().fold([0.0, 0.0], |acc, o| [acc[0] + o[0], acc[1] + o[1]]);
+    assert_eq!(sum, [0.0, 0.0]);
+}
+
+#[test]
+fn test_pcf_grid_offsets_rejects_invalid_size() {
+    use crate::graphics_device::{pcf_grid_offsets, PCF_MAX_KERNEL_SIZE};
+    assert!(pcf_grid_offsets(0).is_err());
+    assert!(pcf_grid_offsets(PCF_MAX_KERNEL_SIZE + 1).is_err());
+    assert_eq!(pcf_grid_offsets(1).unwrap(), vec![[0.0, 0.0]]);
+}
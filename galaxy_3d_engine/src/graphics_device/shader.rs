@@ -2,6 +2,7 @@
 
 /// Shader stage
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub enum ShaderStage {
     /// Vertex shader
     Vertex,
@@ -9,6 +10,18 @@ pub enum ShaderStage {
     Fragment,
     /// Compute shader
     Compute,
+    /// Task shader (mesh shader pipelines, optional stage before `Mesh`)
+    Task,
+    /// Mesh shader (replaces the vertex stage of mesh shader pipelines)
+    Mesh,
+    /// Ray generation shader (ray tracing pipelines, launched by `trace_rays`)
+    RayGen,
+    /// Miss shader (ray tracing pipelines, run when a ray hits nothing)
+    Miss,
+    /// Closest hit shader (ray tracing pipelines, run on the nearest hit)
+    ClosestHit,
+    /// Any hit shader (ray tracing pipelines, run on every candidate hit)
+    AnyHit,
 }
 
 /// Descriptor for creating a shader
@@ -20,9 +33,11 @@ pub struct ShaderDesc<'a> {
     pub stage: ShaderStage,
     /// Entry point function name
     pub entry_point: String,
+    /// Name shown by graphics debuggers (RenderDoc, validation messages)
+    pub debug_name: Option<String>,
 }
 
-use crate::graphics_device::pipeline::{ReflectedBinding, ReflectedPushConstant};
+use crate::graphics_device::pipeline::{ReflectedBinding, ReflectedPushConstant, ReflectedVertexInput};
 
 /// Shader resource trait
 ///
@@ -35,4 +50,6 @@ pub trait Shader: Send + Sync {
     fn reflected_bindings(&self) -> &[ReflectedBinding];
     /// Reflected push constant blocks from compiled shader bytecode
     fn reflected_push_constants(&self) -> &[ReflectedPushConstant];
+    /// Reflected vertex inputs (empty for non-vertex stages)
+    fn reflected_vertex_inputs(&self) -> &[ReflectedVertexInput];
 }
@@ -17,6 +17,10 @@ pub trait Swapchain: Send + Sync {
     /// handling layout transitions and format conversion.
     /// Must be called while the command list is recording and outside a render pass.
     ///
+    /// HDR sources (`TextureFormat::is_hdr()`) are converted to the surface
+    /// format with values clamped to [0, 1]: tonemap them first (see
+    /// `post::Tonemap`) to keep highlights. Depth formats are rejected.
+    ///
     /// # Arguments
     ///
     /// * `cmd` - Command list to record the blit into
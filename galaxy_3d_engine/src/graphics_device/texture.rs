@@ -1,7 +1,7 @@
 /// Texture trait, texture descriptor, texture info, and mipmap types
 
 use crate::error::Result;
-use crate::engine_err;
+use crate::{engine_bail, engine_err};
 use super::pipeline::SampleCount;
 
 /// Texture format enumeration
@@ -10,6 +10,7 @@ use super::pipeline::SampleCount;
 /// For vertex attribute formats, see `BufferFormat` in buffer.rs.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[allow(non_camel_case_types)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextureFormat {
     // Color texture formats (8-bit)
     R8G8B8A8_SRGB,
@@ -19,6 +20,14 @@ pub enum TextureFormat {
 
     // Color texture formats (HDR)
     R16G16B16A16_SFLOAT,
+    /// Packed unsigned float RGB (11/11/10 bits, no alpha)
+    R11G11B10_UFLOAT,
+    /// Two 16-bit float channels (motion vectors)
+    R16G16_SFLOAT,
+
+    // Integer color formats
+    /// Single 32-bit unsigned integer channel (object IDs, picking)
+    R32_UINT,
 
     // Depth/stencil formats
     D16_UNORM,
@@ -35,8 +44,13 @@ impl TextureFormat {
             TextureFormat::R8G8B8A8_SRGB | TextureFormat::R8G8B8A8_UNORM |
             TextureFormat::B8G8R8A8_SRGB | TextureFormat::B8G8R8A8_UNORM => 4,
 
-            // HDR color formats (8 bytes per pixel)
+            // HDR color formats
             TextureFormat::R16G16B16A16_SFLOAT => 8,
+            TextureFormat::R11G11B10_UFLOAT => 4,
+            TextureFormat::R16G16_SFLOAT => 4,
+
+            // Integer color formats
+            TextureFormat::R32_UINT => 4,
 
             // Depth/stencil formats
             TextureFormat::D16_UNORM => 2,
@@ -45,10 +59,30 @@ impl TextureFormat {
             TextureFormat::D32_FLOAT_S8_UINT => 8,
         }
     }
+
+    /// Returns true for floating-point color formats (values not clamped to [0, 1])
+    pub fn is_hdr(&self) -> bool {
+        matches!(self,
+            TextureFormat::R16G16B16A16_SFLOAT
+            | TextureFormat::R11G11B10_UFLOAT
+            | TextureFormat::R16G16_SFLOAT
+        )
+    }
+
+    /// Returns true for depth and depth/stencil formats
+    pub fn is_depth(&self) -> bool {
+        matches!(self,
+            TextureFormat::D16_UNORM
+            | TextureFormat::D32_FLOAT
+            | TextureFormat::D24_UNORM_S8_UINT
+            | TextureFormat::D32_FLOAT_S8_UINT
+        )
+    }
 }
 
 /// Texture usage flags
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextureUsage {
     /// Texture can be sampled in shaders
     Sampled,
@@ -58,15 +92,38 @@ pub enum TextureUsage {
     SampledAndRenderTarget,
     /// Texture can be used as depth/stencil attachment
     DepthStencil,
+    /// Texture can be written by shaders as a storage image (compute and
+    /// ray tracing output), one mip level per binding, and sampled
+    Storage,
 }
 
 /// Texture dimensionality and view type
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextureType {
     /// Standard 2D texture (sampler2D)
     Tex2D,
     /// 2D texture array with one or more layers (sampler2DArray)
     Array2D,
+    /// Cube map: `CUBE_FACE_COUNT` square layers in +X, -X, +Y, -Y, +Z, -Z
+    /// order (samplerCube)
+    Cube,
+    /// Array of cube maps, `CUBE_FACE_COUNT` layers per cube
+    /// (samplerCubeArray)
+    CubeArray,
+    /// Volume texture of `depth` slices (sampler3D): fog and LUT volumes,
+    /// light clusters, noise
+    Tex3D,
+}
+
+/// Number of layers of one cube map
+pub const CUBE_FACE_COUNT: u32 = 6;
+
+impl TextureType {
+    /// True for `Cube` and `CubeArray`
+    pub fn is_cube(self) -> bool {
+        matches!(self, Self::Cube | Self::CubeArray)
+    }
 }
 
 // ===== TEXTURE DATA =====
@@ -110,12 +167,34 @@ pub enum MipmapMode {
         max_levels: Option<u32>,
     },
 
+    /// Generate mipmaps on the CPU before upload, from the initial data
+    /// - sRGB-correct (filters in linear space) and alpha-weighted
+    /// - Rows are filtered in parallel across threads
+    /// - Use when blit quality is not enough; 8-bit RGBA/BGRA formats only
+    /// - Converted to `Manual` by `TextureDesc::resolve_cpu_mipmaps()`
+    GenerateCpu {
+        /// Downsampling filter
+        filter: MipFilter,
+        /// Maximum mip levels to generate. None = full chain to 1x1
+        max_levels: Option<u32>,
+    },
+
     /// Manually provided mipmap data (levels 1+)
     /// Level 0 comes from TextureData
     /// Use for: pre-processed assets with high-quality mipmaps (Lanczos, Kaiser)
     Manual(ManualMipmapData),
 }
 
+/// Downsampling filter used by `MipmapMode::GenerateCpu`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
+pub enum MipFilter {
+    /// 2x2 average (fast, slightly blurry)
+    Box,
+    /// Kaiser-windowed sinc (sharper, used by offline texture tools)
+    Kaiser,
+}
+
 /// Manual mipmap data (levels 1, 2, 3, ...)
 /// Level 0 is provided via TextureData
 #[derive(Debug, Clone)]
@@ -144,7 +223,7 @@ impl MipmapMode {
     pub fn mip_levels(&self, width: u32, height: u32) -> u32 {
         match self {
             MipmapMode::None => 1,
-            MipmapMode::Generate { max_levels } => {
+            MipmapMode::Generate { max_levels } | MipmapMode::GenerateCpu { max_levels, .. } => {
                 let full_chain = Self::max_mip_levels(width, height);
                 max_levels.map(|m| m.min(full_chain)).unwrap_or(full_chain)
             }
@@ -182,6 +261,8 @@ pub struct TextureDesc {
     pub width: u32,
     /// Height in pixels
     pub height: u32,
+    /// Depth in pixels (1 for all types but `TextureType::Tex3D`)
+    pub depth: u32,
     /// Pixel format
     pub format: TextureFormat,
     /// Usage flags
@@ -196,6 +277,162 @@ pub struct TextureDesc {
     pub texture_type: TextureType,
     /// Number of samples per pixel (default: S1 = no multisampling)
     pub sample_count: SampleCount,
+    /// Name shown by graphics debuggers (RenderDoc, validation messages)
+    pub debug_name: Option<String>,
+}
+
+impl TextureDesc {
+    /// Check the depth extent against the texture type: `Tex3D` textures
+    /// are volumes of one layer and one mip level (sampled, or written as
+    /// storage images), whose initial data (`Single`, or `Layers` of
+    /// layer 0) holds every slice, slice after slice. Other types have a
+    /// depth of 1.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if one of these rules is broken, or if the initial
+    /// data of a `Tex3D` texture does not hold `width * height * depth`
+    /// texels.
+    pub fn validate_volume(&self) -> Result<()> {
+        if self.texture_type != TextureType::Tex3D {
+            if self.depth > 1 {
+                engine_bail!("galaxy3d::Texture",
+                    "{:?} texture cannot have depth > 1 (got {}). Use TextureType::Tex3D instead.",
+                    self.texture_type, self.depth);
+            }
+            return Ok(());
+        }
+        if self.depth == 0 || self.array_layers > 1 {
+            engine_bail!("galaxy3d::Texture",
+                "Tex3D texture needs depth >= 1 and a single layer (got depth {}, {} layers)",
+                self.depth, self.array_layers);
+        }
+        if !matches!(self.usage, TextureUsage::Sampled | TextureUsage::Storage)
+            || self.sample_count != SampleCount::S1
+        {
+            engine_bail!("galaxy3d::Texture",
+                "Tex3D texture must be single-sampled with TextureUsage::Sampled or Storage (got {:?}, {:?})",
+                self.usage, self.sample_count);
+        }
+        if !matches!(self.mipmap, MipmapMode::None) {
+            engine_bail!("galaxy3d::Texture", "Tex3D texture cannot have mipmaps");
+        }
+        let images: Vec<&[u8]> = match &self.data {
+            None => Vec::new(),
+            Some(TextureData::Single(data)) => vec![data.as_slice()],
+            Some(TextureData::Layers(layers)) => {
+                if let Some(layer) = layers.iter().find(|l| l.layer != 0) {
+                    engine_bail!("galaxy3d::Texture",
+                        "Tex3D texture has a single layer (data given for layer {})", layer.layer);
+                }
+                layers.iter().map(|l| l.data.as_slice()).collect()
+            }
+        };
+        let expected = self.width as usize * self.height as usize * self.depth as usize
+            * self.format.bytes_per_pixel() as usize;
+        if let Some(data) = images.iter().find(|data| data.len() != expected) {
+            engine_bail!("galaxy3d::Texture",
+                "Tex3D data size mismatch: expected {} bytes ({}x{}x{}), got {}",
+                expected, self.width, self.height, self.depth, data.len());
+        }
+        Ok(())
+    }
+
+    /// Replace `MipmapMode::GenerateCpu` by `MipmapMode::Manual` holding the
+    /// generated levels. Other modes are returned unchanged. Backends call
+    /// this before creating the texture.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no initial data to generate from, or if
+    /// the format or data size is not supported by the CPU generator.
+    pub fn resolve_cpu_mipmaps(mut self) -> Result<Self> {
+        let MipmapMode::GenerateCpu { filter, .. } = self.mipmap else {
+            return Ok(self);
+        };
+        let mip_levels = self.mipmap.mip_levels(self.width, self.height);
+        let generate = |data: &[u8]| super::cpu_mipmap::generate_mip_chain(
+            data, self.width, self.height, self.format, filter, mip_levels,
+        );
+        let manual = match &self.data {
+            Some(TextureData::Single(data)) => ManualMipmapData::Single(generate(data)?),
+            Some(TextureData::Layers(layers)) => ManualMipmapData::Layers(
+                layers.iter()
+                    .map(|l| Ok(LayerMipmapData { layer: l.layer, mips: generate(&l.data)? }))
+                    .collect::<Result<_>>()?,
+            ),
+            None => engine_bail!("galaxy3d::Texture",
+                "MipmapMode::GenerateCpu requires initial texture data"),
+        };
+        self.mipmap = MipmapMode::Manual(manual);
+        Ok(self)
+    }
+
+    /// Halve the texture `levels` times, dropping its top mip levels (see
+    /// `PlatformLimits::texture_mip_drop()`).
+    ///
+    /// Each image takes its manual mip of the new size when
+    /// `MipmapMode::Manual` provides it, and is otherwise downsampled on the
+    /// CPU (box filter). Generated chains lose `levels` levels.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, leaving the descriptor unchanged, if an image has
+    /// to be downsampled on the CPU and its format or data size is not
+    /// supported by the CPU generator.
+    pub fn drop_top_mips(&mut self, levels: u32) -> Result<()> {
+        if levels == 0 {
+            return Ok(());
+        }
+        let (width, height, format) = (self.width, self.height, self.format);
+        let shrink = |data: &[u8], manual: Option<&Vec<Vec<u8>>>| -> Result<Vec<u8>> {
+            match manual.and_then(|mips| mips.get(levels as usize - 1)) {
+                Some(level) => Ok(level.clone()),
+                None => super::cpu_mipmap::generate_mip_chain(
+                    data, width, height, format, MipFilter::Box, levels + 1,
+                ).map(|mut mips| mips.pop().unwrap_or_default()),
+            }
+        };
+        let manual_mips = |layer: u32| match &self.mipmap {
+            MipmapMode::Manual(ManualMipmapData::Single(mips)) if layer == 0 => Some(mips),
+            MipmapMode::Manual(ManualMipmapData::Layers(layers)) => {
+                layers.iter().find(|l| l.layer == layer).map(|l| &l.mips)
+            }
+            _ => None,
+        };
+
+        // Build every image first so that a failure leaves self untouched
+        let data = match &self.data {
+            Some(TextureData::Single(data)) => Some(TextureData::Single(shrink(data, manual_mips(0))?)),
+            Some(TextureData::Layers(layers)) => Some(TextureData::Layers(
+                layers.iter()
+                    .map(|l| Ok(TextureLayerData { layer: l.layer, data: shrink(&l.data, manual_mips(l.layer))? }))
+                    .collect::<Result<_>>()?,
+            )),
+            None => None,
+        };
+        let skip = |mips: &Vec<Vec<u8>>| mips.iter().skip(levels as usize).cloned().collect::<Vec<_>>();
+        self.mipmap = match &self.mipmap {
+            MipmapMode::None => MipmapMode::None,
+            MipmapMode::Generate { max_levels } => MipmapMode::Generate {
+                max_levels: max_levels.map(|m| m.saturating_sub(levels).max(1)),
+            },
+            MipmapMode::GenerateCpu { filter, max_levels } => MipmapMode::GenerateCpu {
+                filter: *filter,
+                max_levels: max_levels.map(|m| m.saturating_sub(levels).max(1)),
+            },
+            MipmapMode::Manual(ManualMipmapData::Single(mips)) => {
+                MipmapMode::Manual(ManualMipmapData::Single(skip(mips)))
+            }
+            MipmapMode::Manual(ManualMipmapData::Layers(layers)) => MipmapMode::Manual(ManualMipmapData::Layers(
+                layers.iter().map(|l| LayerMipmapData { layer: l.layer, mips: skip(&l.mips) }).collect(),
+            )),
+        };
+        self.data = data;
+        self.width = (width >> levels).max(1);
+        self.height = (height >> levels).max(1);
+        Ok(())
+    }
 }
 
 // ===== TEXTURE INFO =====
@@ -210,6 +447,8 @@ pub struct TextureInfo {
     pub width: u32,
     /// Height in pixels
     pub height: u32,
+    /// Depth in pixels (1 for all types but `TextureType::Tex3D`)
+    pub depth: u32,
     /// Pixel format
     pub format: TextureFormat,
     /// Usage flags
@@ -229,6 +468,7 @@ impl TextureInfo {
     pub fn new(
         width: u32,
         height: u32,
+        depth: u32,
         format: TextureFormat,
         usage: TextureUsage,
         array_layers: u32,
@@ -236,7 +476,7 @@ impl TextureInfo {
         texture_type: TextureType,
         sample_count: SampleCount,
     ) -> Self {
-        Self { width, height, format, usage, array_layers, mip_levels, texture_type, sample_count }
+        Self { width, height, depth, format, usage, array_layers, mip_levels, texture_type, sample_count }
     }
 
     /// Returns true if this texture has mipmaps (mip_levels > 1)
@@ -255,13 +495,46 @@ impl TextureInfo {
         Some((w, h))
     }
 
-    /// Calculate expected byte size for a specific mip level
+    /// Calculate expected byte size for a specific mip level (one layer,
+    /// all depth slices)
     /// Returns None if mip_level >= mip_levels
     pub fn mip_byte_size(&self, mip_level: u32) -> Option<usize> {
         self.mip_dimensions(mip_level).map(|(w, h)| {
-            (w * h * self.format.bytes_per_pixel()) as usize
+            let d = (self.depth >> mip_level).max(1);
+            w as usize * h as usize * d as usize * self.format.bytes_per_pixel() as usize
         })
     }
+
+    /// Check that this multisampled color texture can be resolved into `dst`
+    /// with `CommandList::resolve_texture()`: `dst` must be single-sampled,
+    /// with the same color format, extent and layer count.
+    pub fn validate_resolve_into(&self, dst: &TextureInfo) -> Result<()> {
+        if self.sample_count == SampleCount::S1 {
+            engine_bail!("galaxy3d::Texture", "Resolve source must be multisampled");
+        }
+        if dst.sample_count != SampleCount::S1 {
+            engine_bail!("galaxy3d::Texture",
+                "Resolve destination must be single-sampled (got {:?})", dst.sample_count);
+        }
+        if self.format.is_depth() {
+            engine_bail!("galaxy3d::Texture",
+                "Cannot resolve depth format {:?} (use a depth resolve attachment)", self.format);
+        }
+        if self.format != dst.format {
+            engine_bail!("galaxy3d::Texture",
+                "Resolve format mismatch: {:?} -> {:?}", self.format, dst.format);
+        }
+        if (self.width, self.height) != (dst.width, dst.height) {
+            engine_bail!("galaxy3d::Texture",
+                "Resolve extent mismatch: {}x{} -> {}x{}",
+                self.width, self.height, dst.width, dst.height);
+        }
+        if self.array_layers != dst.array_layers {
+            engine_bail!("galaxy3d::Texture",
+                "Resolve layer count mismatch: {} -> {}", self.array_layers, dst.array_layers);
+        }
+        Ok(())
+    }
 }
 
 // ===== TEXTURE TRAIT =====
@@ -312,6 +585,7 @@ pub trait Texture: Send + Sync {
 /// Describes HOW a texture is sampled by the GPU (filtering, addressing, mipmapping).
 /// The backend creates and caches the actual GPU sampler objects internally.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub enum SamplerType {
     /// Bilinear filtering, repeat addressing, trilinear mipmapping, anisotropy x16
     /// Best for: most 3D textures (diffuse, normal, etc.)
@@ -19,6 +19,28 @@ fn test_texture_format_bytes_per_pixel_color_formats() {
     assert_eq!(TextureFormat::B8G8R8A8_UNORM.bytes_per_pixel(), 4);
 }
 
+// ============================================================================
+// HDR FORMATS
+// ============================================================================
+
+#[test]
+fn test_texture_format_bytes_per_pixel_hdr_formats() {
+    // RGBA16F = 4 x 16-bit float, R11G11B10 = packed 32-bit
+    assert_eq!(TextureFormat::R16G16B16A16_SFLOAT.bytes_per_pixel(), 8);
+    assert_eq!(TextureFormat::R11G11B10_UFLOAT.bytes_per_pixel(), 4);
+    assert_eq!(TextureFormat::R16G16_SFLOAT.bytes_per_pixel(), 4);
+}
+
+#[test]
+fn test_texture_format_is_hdr() {
+    assert!(TextureFormat::R16G16B16A16_SFLOAT.is_hdr());
+    assert!(TextureFormat::R11G11B10_UFLOAT.is_hdr());
+    assert!(TextureFormat::R16G16_SFLOAT.is_hdr());
+    assert!(!TextureFormat::R8G8B8A8_UNORM.is_hdr());
+    assert!(!TextureFormat::B8G8R8A8_SRGB.is_hdr());
+    assert!(!TextureFormat::D32_FLOAT.is_hdr());
+}
+
 // ============================================================================
 // DEPTH/STENCIL FORMATS
 // ============================================================================
@@ -41,7 +63,7 @@ fn test_texture_format_bytes_per_pixel_depth_formats() {
 
 #[test]
 fn test_texture_format_bytes_per_pixel_all_variants() {
-    // Verify all 7 variants have correct sizes
+    // Verify all 9 variants have correct sizes
     // This test ensures no variant was missed or misconfigured
 
     let formats_with_sizes = [
@@ -51,6 +73,11 @@ fn test_texture_format_bytes_per_pixel_all_variants() {
         (TextureFormat::B8G8R8A8_SRGB, 4),
         (TextureFormat::B8G8R8A8_UNORM, 4),
 
+        // HDR color formats
+        (TextureFormat::R16G16B16A16_SFLOAT, 8),
+        (TextureFormat::R11G11B10_UFLOAT, 4),
+        (TextureFormat::R16G16_SFLOAT, 4),
+
         // Depth/stencil formats
         (TextureFormat::D16_UNORM, 2),
         (TextureFormat::D32_FLOAT, 4),
@@ -142,6 +169,104 @@ fn test_mipmap_mode_generate_with_max_levels() {
     assert_eq!(mode.mip_levels(4, 4), 3);
 }
 
+#[test]
+fn test_mipmap_mode_generate_cpu_levels() {
+    use crate::graphics_device::MipFilter;
+    let mode = MipmapMode::GenerateCpu { filter: MipFilter::Kaiser, max_levels: None };
+    assert_eq!(mode.mip_levels(512, 256), 10);
+    let mode = MipmapMode::GenerateCpu { filter: MipFilter::Box, max_levels: Some(3) };
+    assert_eq!(mode.mip_levels(512, 256), 3);
+}
+
+fn cpu_mip_desc(data: Option<crate::graphics_device::TextureData>, array_layers: u32) -> crate::graphics_device::TextureDesc {
+    crate::graphics_device::TextureDesc {
+        width: 4,
+        height: 4,
+        depth: 1,
+        format: TextureFormat::R8G8B8A8_UNORM,
+        usage: crate::graphics_device::TextureUsage::Sampled,
+        array_layers,
+        data,
+        mipmap: MipmapMode::GenerateCpu { filter: crate::graphics_device::MipFilter::Box, max_levels: None },
+        texture_type: crate::graphics_device::TextureType::Tex2D,
+        sample_count: crate::graphics_device::SampleCount::S1,
+        debug_name: None,
+    }
+}
+
+#[test]
+fn test_resolve_cpu_mipmaps_single() {
+    use crate::graphics_device::TextureData;
+    let desc = cpu_mip_desc(Some(TextureData::Single(vec![255; 4 * 4 * 4])), 1)
+        .resolve_cpu_mipmaps().unwrap();
+    match desc.mipmap {
+        MipmapMode::Manual(ManualMipmapData::Single(mips)) => {
+            assert_eq!(mips.len(), 2);
+            assert_eq!(mips[0].len(), 2 * 2 * 4);
+            assert_eq!(mips[1].len(), 4);
+        }
+        other => panic!("expected manual single mips, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_resolve_cpu_mipmaps_layers() {
+    use crate::graphics_device::{TextureData, TextureLayerData};
+    let data = TextureData::Layers(vec![TextureLayerData { layer: 2, data: vec![0; 4 * 4 * 4] }]);
+    let desc = cpu_mip_desc(Some(data), 3).resolve_cpu_mipmaps().unwrap();
+    match desc.mipmap {
+        MipmapMode::Manual(ManualMipmapData::Layers(layers)) => {
+            assert_eq!(layers.len(), 1);
+            assert_eq!(layers[0].layer, 2);
+            assert_eq!(layers[0].mips.len(), 2);
+        }
+        other => panic!("expected manual layer mips, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_resolve_cpu_mipmaps_requires_data() {
+    assert!(cpu_mip_desc(None, 1).resolve_cpu_mipmaps().is_err());
+}
+
+#[test]
+fn test_resolve_cpu_mipmaps_keeps_other_modes() {
+    let mut desc = cpu_mip_desc(None, 1);
+    desc.mipmap = MipmapMode::Generate { max_levels: None };
+    let desc = desc.resolve_cpu_mipmaps().unwrap();
+    assert!(matches!(desc.mipmap, MipmapMode::Generate { max_levels: None }));
+}
+
+#[test]
+fn test_drop_top_mips_downsamples_on_cpu() {
+    use crate::graphics_device::TextureData;
+    let mut desc = cpu_mip_desc(Some(TextureData::Single(vec![255; 4 * 4 * 4])), 1);
+    desc.mipmap = MipmapMode::Generate { max_levels: Some(3) };
+    desc.drop_top_mips(1).unwrap();
+    assert_eq!((desc.width, desc.height), (2, 2));
+    assert!(matches!(desc.data, Some(TextureData::Single(ref d)) if d.len() == 2 * 2 * 4));
+    assert!(matches!(desc.mipmap, MipmapMode::Generate { max_levels: Some(2) }));
+}
+
+#[test]
+fn test_drop_top_mips_uses_manual_levels() {
+    use crate::graphics_device::TextureData;
+    let mut desc = cpu_mip_desc(Some(TextureData::Single(vec![0; 4 * 4 * 4])), 1);
+    desc.mipmap = MipmapMode::Manual(ManualMipmapData::Single(vec![vec![1; 2 * 2 * 4], vec![2; 4]]));
+    desc.drop_top_mips(1).unwrap();
+    assert!(matches!(desc.data, Some(TextureData::Single(ref d)) if *d == vec![1; 2 * 2 * 4]));
+    assert!(matches!(desc.mipmap, MipmapMode::Manual(ManualMipmapData::Single(ref m)) if *m == vec![vec![2; 4]]));
+}
+
+#[test]
+fn test_drop_top_mips_unsupported_format_is_unchanged() {
+    use crate::graphics_device::TextureData;
+    let mut desc = cpu_mip_desc(Some(TextureData::Single(vec![0; 4 * 4 * 4])), 1);
+    desc.format = TextureFormat::R16G16B16A16_SFLOAT;
+    assert!(desc.drop_top_mips(1).is_err());
+    assert_eq!((desc.width, desc.height), (4, 4));
+}
+
 #[test]
 fn test_mipmap_mode_manual_single() {
     // Provide 3 manual mip levels (levels 1, 2, 3)
@@ -216,6 +341,7 @@ fn test_texture_info_texture_type() {
     let info_2d = TextureInfo {
         width: 256,
         height: 256,
+        depth: 1,
         format: TextureFormat::R8G8B8A8_UNORM,
         usage: TextureUsage::Sampled,
         array_layers: 1,
@@ -228,6 +354,7 @@ fn test_texture_info_texture_type() {
     let info_array = TextureInfo {
         width: 256,
         height: 256,
+        depth: 1,
         format: TextureFormat::R8G8B8A8_UNORM,
         usage: TextureUsage::Sampled,
         array_layers: 4,
@@ -243,6 +370,7 @@ fn test_texture_info_has_mipmaps() {
     let info_no_mips = TextureInfo {
         width: 256,
         height: 256,
+        depth: 1,
         format: TextureFormat::R8G8B8A8_UNORM,
         usage: TextureUsage::Sampled,
         array_layers: 1,
@@ -255,6 +383,7 @@ fn test_texture_info_has_mipmaps() {
     let info_with_mips = TextureInfo {
         width: 256,
         height: 256,
+        depth: 1,
         format: TextureFormat::R8G8B8A8_UNORM,
         usage: TextureUsage::Sampled,
         array_layers: 1,
@@ -270,6 +399,7 @@ fn test_texture_info_mip_dimensions() {
     let info = TextureInfo {
         width: 256,
         height: 256,
+        depth: 1,
         format: TextureFormat::R8G8B8A8_UNORM,
         usage: TextureUsage::Sampled,
         array_layers: 1,
@@ -302,6 +432,7 @@ fn test_texture_info_mip_dimensions_non_square() {
     let info = TextureInfo {
         width: 512,
         height: 256,
+        depth: 1,
         format: TextureFormat::R8G8B8A8_UNORM,
         usage: TextureUsage::Sampled,
         array_layers: 1,
@@ -321,6 +452,7 @@ fn test_texture_info_mip_dimensions_min_size() {
     let info = TextureInfo {
         width: 4,
         height: 4,
+        depth: 1,
         format: TextureFormat::R8G8B8A8_UNORM,
         usage: TextureUsage::Sampled,
         array_layers: 1,
@@ -342,6 +474,7 @@ fn test_texture_info_mip_byte_size() {
     let info = TextureInfo {
         width: 256,
         height: 256,
+        depth: 1,
         format: TextureFormat::R8G8B8A8_UNORM, // 4 bytes per pixel
         usage: TextureUsage::Sampled,
         array_layers: 1,
@@ -371,6 +504,7 @@ fn test_texture_info_mip_byte_size_depth_format() {
     let info = TextureInfo {
         width: 512,
         height: 512,
+        depth: 1,
         format: TextureFormat::D16_UNORM, // 2 bytes per pixel
         usage: TextureUsage::DepthStencil,
         array_layers: 1,
@@ -389,6 +523,129 @@ fn test_texture_info_mip_byte_size_depth_format() {
     assert_eq!(info.mip_byte_size(2), Some(32_768));
 }
 
+#[test]
+fn test_texture_info_mip_byte_size_volume() {
+    let info = TextureInfo::new(
+        16, 8, 4, TextureFormat::R8G8B8A8_UNORM,
+        TextureUsage::Sampled, 1, 3, TextureType::Tex3D, SampleCount::S1,
+    );
+    assert_eq!(info.mip_byte_size(0), Some(16 * 8 * 4 * 4));
+    assert_eq!(info.mip_byte_size(1), Some(8 * 4 * 2 * 4));
+    assert_eq!(info.mip_byte_size(2), Some(4 * 2 * 4));
+}
+
+// ============================================================================
+// VOLUME VALIDATION
+// ============================================================================
+
+fn volume_desc(depth: u32, data: Option<crate::graphics_device::TextureData>) -> crate::graphics_device::TextureDesc {
+    crate::graphics_device::TextureDesc {
+        width: 4,
+        height: 2,
+        depth,
+        format: TextureFormat::R8G8B8A8_UNORM,
+        usage: TextureUsage::Sampled,
+        array_layers: 1,
+        data,
+        mipmap: MipmapMode::None,
+        texture_type: TextureType::Tex3D,
+        sample_count: SampleCount::S1,
+        debug_name: None,
+    }
+}
+
+#[test]
+fn test_validate_volume_accepts_tex3d() {
+    use crate::graphics_device::{TextureData, TextureLayerData};
+    assert!(volume_desc(8, None).validate_volume().is_ok());
+    assert!(volume_desc(8, Some(TextureData::Single(vec![0; 4 * 2 * 8 * 4]))).validate_volume().is_ok());
+    let layers = TextureData::Layers(vec![TextureLayerData { layer: 0, data: vec![0; 4 * 2 * 8 * 4] }]);
+    assert!(volume_desc(8, Some(layers)).validate_volume().is_ok());
+}
+
+#[test]
+fn test_validate_volume_rejects_invalid_tex3d() {
+    use crate::graphics_device::{TextureData, TextureLayerData};
+    assert!(volume_desc(0, None).validate_volume().is_err());
+    assert!(volume_desc(8, Some(TextureData::Single(vec![0; 4 * 2 * 4]))).validate_volume().is_err());
+    let layers = TextureData::Layers(vec![TextureLayerData { layer: 1, data: vec![0; 4 * 2 * 8 * 4] }]);
+    assert!(volume_desc(8, Some(layers)).validate_volume().is_err());
+
+    let mut desc = volume_desc(8, None);
+    desc.array_layers = 2;
+    assert!(desc.validate_volume().is_err());
+    let mut desc = volume_desc(8, None);
+    desc.mipmap = MipmapMode::Generate { max_levels: None };
+    assert!(desc.validate_volume().is_err());
+    let mut desc = volume_desc(8, None);
+    desc.usage = TextureUsage::RenderTarget;
+    assert!(desc.validate_volume().is_err());
+}
+
+#[test]
+fn test_validate_volume_rejects_depth_on_2d_types() {
+    let mut desc = volume_desc(1, None);
+    for texture_type in [TextureType::Tex2D, TextureType::Array2D, TextureType::Cube] {
+        desc.texture_type = texture_type;
+        desc.depth = 1;
+        assert!(desc.validate_volume().is_ok());
+        desc.depth = 4;
+        assert!(desc.validate_volume().is_err());
+    }
+}
+
+// ============================================================================
+// MSAA RESOLVE VALIDATION
+// ============================================================================
+
+fn msaa_info(format: TextureFormat, sample_count: SampleCount) -> TextureInfo {
+    TextureInfo::new(
+        640, 480, 1, format, TextureUsage::RenderTarget, 1, 1, TextureType::Tex2D, sample_count,
+    )
+}
+
+#[test]
+fn test_texture_format_is_depth() {
+    assert!(TextureFormat::D16_UNORM.is_depth());
+    assert!(TextureFormat::D32_FLOAT.is_depth());
+    assert!(TextureFormat::D24_UNORM_S8_UINT.is_depth());
+    assert!(TextureFormat::D32_FLOAT_S8_UINT.is_depth());
+    assert!(!TextureFormat::R8G8B8A8_UNORM.is_depth());
+    assert!(!TextureFormat::R16G16B16A16_SFLOAT.is_depth());
+}
+
+#[test]
+fn test_validate_resolve_into_accepts_msaa_to_single_sample() {
+    for samples in [SampleCount::S2, SampleCount::S4, SampleCount::S8] {
+        let src = msaa_info(TextureFormat::R16G16B16A16_SFLOAT, samples);
+        let dst = msaa_info(TextureFormat::R16G16B16A16_SFLOAT, SampleCount::S1);
+        assert!(src.validate_resolve_into(&dst).is_ok());
+    }
+}
+
+#[test]
+fn test_validate_resolve_into_rejects_invalid_pairs() {
+    let src = msaa_info(TextureFormat::R8G8B8A8_UNORM, SampleCount::S4);
+    let dst = msaa_info(TextureFormat::R8G8B8A8_UNORM, SampleCount::S1);
+
+    // Single-sampled source / multisampled destination
+    assert!(dst.validate_resolve_into(&dst).is_err());
+    assert!(src.validate_resolve_into(&src).is_err());
+    // Format mismatch
+    assert!(src.validate_resolve_into(&msaa_info(TextureFormat::B8G8R8A8_UNORM, SampleCount::S1)).is_err());
+    // Extent mismatch
+    let mut small = dst.clone();
+    small.width = 320;
+    assert!(src.validate_resolve_into(&small).is_err());
+    // Layer count mismatch
+    let mut layered = dst.clone();
+    layered.array_layers = 2;
+    assert!(src.validate_resolve_into(&layered).is_err());
+    // Depth formats resolve through attachments only
+    let depth = msaa_info(TextureFormat::D32_FLOAT, SampleCount::S4);
+    assert!(depth.validate_resolve_into(&msaa_info(TextureFormat::D32_FLOAT, SampleCount::S1)).is_err());
+}
+
 // ============================================================================
 // SamplerType / TextureType / TextureUsage / TextureInfo extras
 // ============================================================================
@@ -438,7 +695,7 @@ fn test_texture_usage_clone_copy_eq() {
 #[test]
 fn test_texture_info_new_constructor() {
     let info = TextureInfo::new(
-        128, 64,
+        128, 64, 1,
         TextureFormat::R8G8B8A8_UNORM,
         TextureUsage::SampledAndRenderTarget,
         2, 3,
@@ -458,7 +715,7 @@ fn test_texture_info_new_constructor() {
 #[test]
 fn test_texture_info_clone() {
     let info = TextureInfo::new(
-        16, 16, TextureFormat::R8G8B8A8_UNORM,
+        16, 16, 1, TextureFormat::R8G8B8A8_UNORM,
         TextureUsage::Sampled, 1, 1, TextureType::Tex2D, SampleCount::S1,
     );
     let cloned = info.clone();
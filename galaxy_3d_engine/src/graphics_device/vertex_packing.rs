@@ -0,0 +1,111 @@
+/// Encoding of compressed vertex attributes
+///
+/// Helpers to fill vertex buffers with the half float, normalized and
+/// packed `BufferFormat`s. The GPU decodes them to floats in the vertex
+/// shader, so the attributes stay `vec2` / `vec4` in GLSL.
+///
+/// Typical layouts: positions in `R16G16B16A16_SFLOAT`, normals in
+/// `A2B10G10R10_SNORM_PACK32` or `R8G8B8A8_SNORM`, UVs in `R16G16_SFLOAT`,
+/// colors in `R8G8B8A8_UNORM`.
+
+/// Nearest IEEE half float (round to nearest even, overflow to infinity)
+pub fn f32_to_f16(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xFF) as i32;
+    let mantissa = bits & 0x007F_FFFF;
+    if exponent == 0xFF {
+        // Infinity, or a quiet NaN
+        return sign | 0x7C00 | if mantissa != 0 { 0x0200 } else { 0 };
+    }
+
+    let exponent = exponent - 127 + 15;
+    if exponent >= 0x1F {
+        return sign | 0x7C00;
+    }
+    if exponent <= 0 {
+        // Subnormal half (or zero): shift the mantissa with its implicit bit
+        if exponent < -10 {
+            return sign;
+        }
+        let mantissa = mantissa | 0x0080_0000;
+        let shift = (14 - exponent) as u32;
+        return sign | round_shifted(mantissa, shift) as u16;
+    }
+    // A carry out of the mantissa bumps the exponent, up to infinity
+    let half = ((exponent as u32) << 10) + round_shifted(mantissa, 13);
+    sign | half as u16
+}
+
+/// Float value of an IEEE half float
+pub fn f16_to_f32(bits: u16) -> f32 {
+    let sign = ((bits & 0x8000) as u32) << 16;
+    let exponent = ((bits >> 10) & 0x1F) as u32;
+    let mantissa = (bits & 0x03FF) as u32;
+    match exponent {
+        0 if mantissa == 0 => f32::from_bits(sign),
+        0 => {
+            let magnitude = mantissa as f32 * (-24.0f32).exp2();
+            if sign != 0 { -magnitude } else { magnitude }
+        }
+        0x1F => f32::from_bits(sign | 0x7F80_0000 | (mantissa << 13)),
+        _ => f32::from_bits(sign | ((exponent + 127 - 15) << 23) | (mantissa << 13)),
+    }
+}
+
+/// `value` (clamped to 0..1) as an 8-bit UNORM component
+pub fn pack_unorm8(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// `value` (clamped to -1..1) as an 8-bit SNORM component
+pub fn pack_snorm8(value: f32) -> i8 {
+    (value.clamp(-1.0, 1.0) * 127.0).round() as i8
+}
+
+/// `value` (clamped to 0..1) as a 16-bit UNORM component
+pub fn pack_unorm16(value: f32) -> u16 {
+    (value.clamp(0.0, 1.0) * 65535.0).round() as u16
+}
+
+/// `value` (clamped to -1..1) as a 16-bit SNORM component
+pub fn pack_snorm16(value: f32) -> i16 {
+    (value.clamp(-1.0, 1.0) * 32767.0).round() as i16
+}
+
+/// `[r, g, b, a]` (clamped to 0..1) as an `A2B10G10R10_UNORM_PACK32` word
+pub fn pack_a2b10g10r10_unorm(value: [f32; 4]) -> u32 {
+    let component = |v: f32, max: f32| (v.clamp(0.0, 1.0) * max).round() as u32;
+    component(value[0], 1023.0)
+        | (component(value[1], 1023.0) << 10)
+        | (component(value[2], 1023.0) << 20)
+        | (component(value[3], 3.0) << 30)
+}
+
+/// `[r, g, b, a]` (clamped to -1..1) as an `A2B10G10R10_SNORM_PACK32`
+/// word. The 2-bit alpha only holds -1, 0 and 1 (e.g. a tangent sign).
+pub fn pack_a2b10g10r10_snorm(value: [f32; 4]) -> u32 {
+    let component = |v: f32, max: f32, mask: u32| {
+        ((v.clamp(-1.0, 1.0) * max).round() as i32) as u32 & mask
+    };
+    component(value[0], 511.0, 0x3FF)
+        | (component(value[1], 511.0, 0x3FF) << 10)
+        | (component(value[2], 511.0, 0x3FF) << 20)
+        | (component(value[3], 1.0, 0x3) << 30)
+}
+
+/// `mantissa >> shift`, rounded to nearest even
+fn round_shifted(mantissa: u32, shift: u32) -> u32 {
+    let truncated = mantissa >> shift;
+    let remainder = mantissa & ((1 << shift) - 1);
+    let halfway = 1 << (shift - 1);
+    if remainder > halfway || (remainder == halfway && truncated & 1 == 1) {
+        truncated + 1
+    } else {
+        truncated
+    }
+}
+
+#[cfg(test)]
+#[path = "vertex_packing_tests.rs"]
+mod tests;
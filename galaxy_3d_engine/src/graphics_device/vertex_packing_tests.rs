@@ -0,0 +1,73 @@
+use super::*;
+
+// ============================================================================
+// Half floats
+// ============================================================================
+
+#[test]
+fn test_f16_round_trips_exact_values() {
+    for value in [0.0f32, -0.0, 1.0, -2.5, 0.5, 1024.0, 65504.0, (-14.0f32).exp2(), (-24.0f32).exp2()] {
+        let bits = f32_to_f16(value);
+        assert_eq!(f16_to_f32(bits).to_bits(), value.to_bits(), "{}", value);
+    }
+    assert_eq!(f32_to_f16(1.0), 0x3C00);
+    assert_eq!(f32_to_f16(-2.0), 0xC000);
+    assert_eq!(f32_to_f16(65504.0), 0x7BFF);
+}
+
+#[test]
+fn test_f16_rounds_to_nearest_even() {
+    // 1 + 2^-11 is halfway between 1 and the next half: ties to the even 1
+    assert_eq!(f32_to_f16(1.0 + (-11.0f32).exp2()), 0x3C00);
+    // 1 + 3 * 2^-11 ties up to the even 1 + 2^-9
+    assert_eq!(f32_to_f16(1.0 + 3.0 * (-11.0f32).exp2()), 0x3C02);
+    assert_eq!(f32_to_f16(1.0 + 1.5 * (-11.0f32).exp2()), 0x3C01);
+    // Mantissa carry into the exponent
+    assert_eq!(f32_to_f16(2.0 - (-12.0f32).exp2()), 0x4000);
+    // Values near the quantization step
+    let value = 0.1234f32;
+    assert!((f16_to_f32(f32_to_f16(value)) - value).abs() < 1e-4);
+}
+
+#[test]
+fn test_f16_special_values() {
+    assert_eq!(f32_to_f16(f32::INFINITY), 0x7C00);
+    assert_eq!(f32_to_f16(f32::NEG_INFINITY), 0xFC00);
+    assert_eq!(f32_to_f16(1.0e6), 0x7C00);
+    assert_eq!(f32_to_f16(65520.0), 0x7C00);
+    assert!(f16_to_f32(f32_to_f16(f32::NAN)).is_nan());
+    assert_eq!(f16_to_f32(0x7C00), f32::INFINITY);
+    // Below half the smallest subnormal: zero, keeping the sign
+    assert_eq!(f32_to_f16(-1.0e-9), 0x8000);
+    assert_eq!(f32_to_f16((-25.0f32).exp2() * 1.5), 0x0001);
+}
+
+// ============================================================================
+// Normalized components
+// ============================================================================
+
+#[test]
+fn test_normalized_components() {
+    assert_eq!(pack_unorm8(1.0), 255);
+    assert_eq!(pack_unorm8(0.5), 128);
+    assert_eq!(pack_unorm8(-3.0), 0);
+    assert_eq!(pack_snorm8(-1.0), -127);
+    assert_eq!(pack_snorm8(2.0), 127);
+    assert_eq!(pack_unorm16(1.0), 65535);
+    assert_eq!(pack_snorm16(-0.5), -16384);
+    assert_eq!(pack_snorm16(0.0), 0);
+}
+
+#[test]
+fn test_pack_a2b10g10r10() {
+    assert_eq!(pack_a2b10g10r10_unorm([1.0, 0.0, 0.0, 0.0]), 0x3FF);
+    assert_eq!(pack_a2b10g10r10_unorm([0.0, 1.0, 0.0, 1.0]), (0x3FF << 10) | (0x3 << 30));
+    assert_eq!(pack_a2b10g10r10_unorm([0.0, 0.0, 2.0, 0.0]), 0x3FF << 20);
+
+    // -1 is 0x201 in 10-bit two's complement, 0x3 in 2-bit
+    let word = pack_a2b10g10r10_snorm([-1.0, 1.0, 0.0, -1.0]);
+    assert_eq!(word & 0x3FF, 0x201);
+    assert_eq!((word >> 10) & 0x3FF, 0x1FF);
+    assert_eq!((word >> 20) & 0x3FF, 0);
+    assert_eq!(word >> 30, 0x3);
+}
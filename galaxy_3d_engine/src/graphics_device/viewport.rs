@@ -0,0 +1,24 @@
+/// Viewport and 2D rectangle types.
+///
+/// Plain data shared by the camera and the command lists; part of the core
+/// built without the `renderer` feature.
+
+/// Viewport dimensions and depth range
+#[derive(Debug, Clone, Copy)]
+pub struct Viewport {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub min_depth: f32,
+    pub max_depth: f32,
+}
+
+/// 2D rectangle
+#[derive(Debug, Clone, Copy)]
+pub struct Rect2D {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
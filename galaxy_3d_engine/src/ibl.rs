@@ -0,0 +1,395 @@
+/// Image-based lighting (IBL) baking.
+///
+/// `IblBaker` turns an environment cube map into the textures PBR materials
+/// sample for ambient lighting:
+///
+/// - **Irradiance**: a small cube map of the cosine-weighted diffuse light.
+/// - **Specular**: a cube map whose mips hold the environment prefiltered
+///   for increasing roughness (mip `m` of `n` is roughness `m / (n - 1)`).
+/// - **BRDF LUT**: a 2D table of the split-sum scale and bias, indexed by
+///   `(N.V, roughness)`. It does not depend on the environment, so it is
+///   baked once (`bake_brdf_lut`).
+///
+/// Every texel is rendered by a fragment pass: one fullscreen triangle per
+/// cube face and mip, drawn with an application pipeline (`IblPipelines`).
+/// The results are `ResourceManager` textures in the fragment-shader-read
+/// state, registered in the bindless table like any other texture.
+///
+/// Shader interface of the three pipelines:
+///
+/// ```glsl
+/// layout(push_constant) uniform IblBake {  // fragment stage, offset 0
+///     uint face;              // cube face, +X -X +Y -Y +Z -Z (0 for the LUT)
+///     float roughness;        // specular mip roughness (0 otherwise)
+///     uint environmentIndex;  // bindless index of the source cube (set 0 binding 2)
+///     uint sampleCount;       // importance samples per texel
+///     float environmentSize;  // source face size, for mip-filtered sampling
+/// };
+/// // vertex: fullscreen triangle from gl_VertexIndex (draw(3, 0), no vertex buffer)
+/// // fragment: direction from face and uv, then integrate the environment
+/// ```
+///
+/// Baking waits for the GPU (`wait_idle`): do it at load time, not per frame.
+
+use std::sync::{Arc, Mutex};
+use crate::error::Result;
+use crate::engine_bail;
+use crate::graphics_device::{
+    self, AccessType, AttachmentDesc, ClearValue, CommandList, FramebufferAttachment,
+    FramebufferDesc, GraphicsDevice, ImageAccess, LoadOp, MipmapMode, Rect2D, RenderPassDesc,
+    SampleCount, ShaderStageFlags, StoreOp, TextureFormat, TextureType, TextureUsage, Viewport,
+    CUBE_FACE_COUNT,
+};
+use crate::resource::resource_manager::{ResourceManager, TextureKey};
+use crate::resource::texture::{LayerDesc, TextureDesc};
+
+/// Default face size of the irradiance cube map
+pub const DEFAULT_IRRADIANCE_SIZE: u32 = 32;
+/// Default face size of the specular cube map (mip 0)
+pub const DEFAULT_SPECULAR_SIZE: u32 = 128;
+/// Default number of specular mips (roughness steps)
+pub const DEFAULT_SPECULAR_MIP_LEVELS: u32 = 5;
+/// Default importance samples per texel
+pub const DEFAULT_IBL_SAMPLE_COUNT: u32 = 1024;
+/// Default size of the BRDF LUT
+pub const DEFAULT_BRDF_LUT_SIZE: u32 = 512;
+/// Format of the irradiance and specular cube maps
+pub const IBL_CUBE_FORMAT: TextureFormat = TextureFormat::R16G16B16A16_SFLOAT;
+/// Format of the BRDF LUT (scale, bias)
+pub const BRDF_LUT_FORMAT: TextureFormat = TextureFormat::R16G16_SFLOAT;
+/// Layer name of the single-layer bake targets
+const BAKE_LAYER_NAME: &str = "baked";
+
+/// Pipelines drawing the bake passes (see module docs). The irradiance and
+/// specular pipelines render `IBL_CUBE_FORMAT`, the LUT one `BRDF_LUT_FORMAT`.
+#[derive(Clone)]
+pub struct IblPipelines {
+    pub irradiance: Arc<dyn graphics_device::Pipeline>,
+    pub specular: Arc<dyn graphics_device::Pipeline>,
+    pub brdf_lut: Arc<dyn graphics_device::Pipeline>,
+}
+
+/// Sizes and quality of an environment bake
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IblBakeDesc {
+    /// Face size of the irradiance cube map
+    pub irradiance_size: u32,
+    /// Face size of the specular cube map (mip 0)
+    pub specular_size: u32,
+    /// Number of specular mips, at most the full chain of `specular_size`
+    pub specular_mip_levels: u32,
+    /// Importance samples per texel
+    pub sample_count: u32,
+}
+
+impl Default for IblBakeDesc {
+    fn default() -> Self {
+        Self {
+            irradiance_size: DEFAULT_IRRADIANCE_SIZE,
+            specular_size: DEFAULT_SPECULAR_SIZE,
+            specular_mip_levels: DEFAULT_SPECULAR_MIP_LEVELS,
+            sample_count: DEFAULT_IBL_SAMPLE_COUNT,
+        }
+    }
+}
+
+/// Baked lighting of one environment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IblEnvironment {
+    /// Irradiance cube map (`<name>_irradiance`)
+    pub irradiance: TextureKey,
+    /// Prefiltered specular cube map (`<name>_specular`)
+    pub specular: TextureKey,
+    /// Mips of `specular`, for the roughness to LOD mapping in shaders
+    pub specular_mip_levels: u32,
+}
+
+/// Push constants of a bake pass (see module docs)
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct IblBakeConstants {
+    face: u32,
+    roughness: f32,
+    environment_index: u32,
+    sample_count: u32,
+    environment_size: f32,
+}
+
+impl IblBakeConstants {
+    /// Push constant block, in the shader declaration order
+    fn to_bytes(self) -> Vec<u8> {
+        [
+            self.face.to_ne_bytes(),
+            self.roughness.to_ne_bytes(),
+            self.environment_index.to_ne_bytes(),
+            self.sample_count.to_ne_bytes(),
+            self.environment_size.to_ne_bytes(),
+        ].concat()
+    }
+}
+
+/// One fullscreen draw into a face and mip of the target
+struct BakeDraw {
+    framebuffer: Arc<dyn graphics_device::Framebuffer>,
+    size: u32,
+    constants: IblBakeConstants,
+}
+
+/// Bakes IBL textures with the application pipelines (see module docs).
+pub struct IblBaker {
+    pipelines: IblPipelines,
+}
+
+impl IblBaker {
+    pub fn new(pipelines: IblPipelines) -> Self {
+        Self { pipelines }
+    }
+
+    /// Bake the irradiance and specular cube maps of `environment` (a
+    /// `TextureType::Cube` texture) into `<name>_irradiance` and
+    /// `<name>_specular`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `environment` is missing or not a cube, if a size
+    /// is zero or the mip count exceeds the chain, or if a texture or GPU
+    /// operation fails.
+    pub fn bake_environment(
+        &self,
+        rm: &mut ResourceManager,
+        graphics_device: &Arc<Mutex<dyn GraphicsDevice>>,
+        name: &str,
+        environment: TextureKey,
+        desc: &IblBakeDesc,
+    ) -> Result<IblEnvironment> {
+        if desc.irradiance_size == 0 || desc.specular_size == 0 || desc.sample_count == 0 {
+            engine_bail!("galaxy3d::IblBaker", "Bake sizes and sample count must be non-zero: {:?}", desc);
+        }
+        let max_mips = MipmapMode::max_mip_levels(desc.specular_size, desc.specular_size);
+        if desc.specular_mip_levels == 0 || desc.specular_mip_levels > max_mips {
+            engine_bail!("galaxy3d::IblBaker",
+                "specular_mip_levels must be in 1..={} for size {} (got {})",
+                max_mips, desc.specular_size, desc.specular_mip_levels);
+        }
+        let (environment_index, environment_size) = {
+            let Some(texture) = rm.texture(environment) else {
+                engine_bail!("galaxy3d::IblBaker", "Environment texture not found");
+            };
+            let texture = texture.graphics_device_texture();
+            if texture.info().texture_type != TextureType::Cube {
+                engine_bail!("galaxy3d::IblBaker",
+                    "Environment texture has type {:?}, expected Cube", texture.info().texture_type);
+            }
+            (texture.bindless_index(), texture.info().width as f32)
+        };
+        let constants = |face, roughness| IblBakeConstants {
+            face,
+            roughness,
+            environment_index,
+            sample_count: desc.sample_count,
+            environment_size,
+        };
+
+        let irradiance = create_target(rm, graphics_device, format!("{}_irradiance", name),
+            desc.irradiance_size, IBL_CUBE_FORMAT, TextureType::Cube, 1)?;
+        let specular = create_target(rm, graphics_device, format!("{}_specular", name),
+            desc.specular_size, IBL_CUBE_FORMAT, TextureType::Cube, desc.specular_mip_levels)?;
+        let irradiance_texture = rm.texture(irradiance).unwrap().graphics_device_texture().clone();
+        let specular_texture = rm.texture(specular).unwrap().graphics_device_texture().clone();
+
+        let gd = graphics_device.lock().unwrap();
+        let render_pass = create_render_pass(&*gd, IBL_CUBE_FORMAT)?;
+        let irradiance_draws = (0..CUBE_FACE_COUNT)
+            .map(|face| {
+                bake_draw(&*gd, &render_pass, &irradiance_texture, 0, face, constants(face, 0.0))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let mut specular_draws = Vec::new();
+        for mip in 0..desc.specular_mip_levels {
+            let roughness = specular_roughness(mip, desc.specular_mip_levels);
+            for face in 0..CUBE_FACE_COUNT {
+                specular_draws.push(bake_draw(&*gd, &render_pass, &specular_texture, mip, face,
+                    constants(face, roughness))?);
+            }
+        }
+
+        let mut cmd = gd.create_command_list()?;
+        cmd.begin()?;
+        record_target(&mut *cmd, &render_pass, &self.pipelines.irradiance, &irradiance_texture, &irradiance_draws)?;
+        record_target(&mut *cmd, &render_pass, &self.pipelines.specular, &specular_texture, &specular_draws)?;
+        cmd.end()?;
+        gd.submit(&[&*cmd])?;
+        gd.wait_idle()?;
+
+        crate::engine_info!("galaxy3d::IblBaker",
+            "Baked IBL '{}' (irradiance {}, specular {} x {} mips)",
+            name, desc.irradiance_size, desc.specular_size, desc.specular_mip_levels);
+        Ok(IblEnvironment { irradiance, specular, specular_mip_levels: desc.specular_mip_levels })
+    }
+
+    /// Bake the split-sum BRDF LUT into a `size` x `size` texture `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `size` or `sample_count` is zero, or if the
+    /// texture or a GPU operation fails.
+    pub fn bake_brdf_lut(
+        &self,
+        rm: &mut ResourceManager,
+        graphics_device: &Arc<Mutex<dyn GraphicsDevice>>,
+        name: &str,
+        size: u32,
+        sample_count: u32,
+    ) -> Result<TextureKey> {
+        if size == 0 || sample_count == 0 {
+            engine_bail!("galaxy3d::IblBaker", "BRDF LUT size and sample count must be non-zero");
+        }
+        let lut = create_target(rm, graphics_device, name.to_string(), size, BRDF_LUT_FORMAT, TextureType::Tex2D, 1)?;
+        let lut_texture = rm.texture(lut).unwrap().graphics_device_texture().clone();
+
+        let gd = graphics_device.lock().unwrap();
+        let render_pass = create_render_pass(&*gd, BRDF_LUT_FORMAT)?;
+        let draws = [bake_draw(&*gd, &render_pass, &lut_texture, 0, 0, IblBakeConstants {
+            face: 0,
+            roughness: 0.0,
+            environment_index: 0,
+            sample_count,
+            environment_size: 0.0,
+        })?];
+
+        let mut cmd = gd.create_command_list()?;
+        cmd.begin()?;
+        record_target(&mut *cmd, &render_pass, &self.pipelines.brdf_lut, &lut_texture, &draws)?;
+        cmd.end()?;
+        gd.submit(&[&*cmd])?;
+        gd.wait_idle()?;
+        Ok(lut)
+    }
+}
+
+/// Roughness prefiltered into specular mip `mip` of `mip_levels`
+fn specular_roughness(mip: u32, mip_levels: u32) -> f32 {
+    if mip_levels <= 1 {
+        0.0
+    } else {
+        mip as f32 / (mip_levels - 1) as f32
+    }
+}
+
+/// Sampled render target without initial data (the bake writes every texel)
+fn create_target(
+    rm: &mut ResourceManager,
+    graphics_device: &Arc<Mutex<dyn GraphicsDevice>>,
+    name: String,
+    size: u32,
+    format: TextureFormat,
+    texture_type: TextureType,
+    mip_levels: u32,
+) -> Result<TextureKey> {
+    let mipmap = if mip_levels > 1 {
+        MipmapMode::Generate { max_levels: Some(mip_levels) }
+    } else {
+        MipmapMode::None
+    };
+    let array_layers = if texture_type.is_cube() { CUBE_FACE_COUNT } else { 1 };
+    rm.create_texture(name.clone(), TextureDesc {
+        graphics_device: graphics_device.clone(),
+        texture: graphics_device::TextureDesc {
+            width: size,
+            height: size,
+            depth: 1,
+            format,
+            usage: TextureUsage::SampledAndRenderTarget,
+            texture_type,
+            sample_count: SampleCount::S1,
+            array_layers,
+            data: None,
+            mipmap,
+            debug_name: Some(name),
+        },
+        layers: if array_layers == 1 {
+            vec![LayerDesc { name: BAKE_LAYER_NAME.to_string(), layer_index: 0, data: None, regions: Vec::new() }]
+        } else {
+            Vec::new()
+        },
+    })
+}
+
+fn create_render_pass(
+    gd: &dyn GraphicsDevice,
+    format: TextureFormat,
+) -> Result<Arc<dyn graphics_device::RenderPass>> {
+    gd.create_render_pass(&RenderPassDesc {
+        color_attachments: vec![AttachmentDesc {
+            format,
+            samples: SampleCount::S1,
+            // Every texel is written
+            load_op: LoadOp::DontCare,
+            store_op: StoreOp::Store,
+            stencil_load_op: LoadOp::DontCare,
+            stencil_store_op: StoreOp::DontCare,
+        }],
+        depth_stencil_attachment: None,
+        color_resolve_attachments: Vec::new(),
+    })
+}
+
+fn bake_draw(
+    gd: &dyn GraphicsDevice,
+    render_pass: &Arc<dyn graphics_device::RenderPass>,
+    texture: &Arc<dyn graphics_device::Texture>,
+    mip: u32,
+    face: u32,
+    constants: IblBakeConstants,
+) -> Result<BakeDraw> {
+    let size = (texture.info().width >> mip).max(1);
+    let framebuffer = gd.create_framebuffer(&FramebufferDesc {
+        render_pass,
+        color_attachments: vec![FramebufferAttachment::mip_layer(texture.clone(), mip, face)],
+        depth_stencil_attachment: None,
+        color_resolve_attachments: Vec::new(),
+        width: size,
+        height: size,
+    })?;
+    Ok(BakeDraw { framebuffer, size, constants })
+}
+
+/// Record the draws writing `target`, then leave it ready for sampling
+fn record_target(
+    cmd: &mut dyn CommandList,
+    render_pass: &Arc<dyn graphics_device::RenderPass>,
+    pipeline: &Arc<dyn graphics_device::Pipeline>,
+    target: &Arc<dyn graphics_device::Texture>,
+    draws: &[BakeDraw],
+) -> Result<()> {
+    for (index, draw) in draws.iter().enumerate() {
+        let access = ImageAccess {
+            texture: target.clone(),
+            access_type: AccessType::ColorAttachmentWrite,
+            previous_access_type: (index > 0).then_some(AccessType::ColorAttachmentWrite),
+        };
+        cmd.begin_render_pass(render_pass, &draw.framebuffer, &[ClearValue::Color([0.0; 4])], &[access], &[])?;
+        cmd.set_viewport(Viewport {
+            x: 0.0,
+            y: 0.0,
+            width: draw.size as f32,
+            height: draw.size as f32,
+            min_depth: 0.0,
+            max_depth: 1.0,
+        })?;
+        cmd.set_scissor(Rect2D { x: 0, y: 0, width: draw.size, height: draw.size })?;
+        cmd.bind_pipeline(pipeline)?;
+        cmd.bind_textures()?;
+        cmd.push_constants(ShaderStageFlags::FRAGMENT, 0, &draw.constants.to_bytes())?;
+        cmd.draw(3, 0)?;
+        cmd.end_render_pass()?;
+    }
+    cmd.declare_accesses(&[ImageAccess {
+        texture: target.clone(),
+        access_type: AccessType::FragmentShaderRead,
+        previous_access_type: Some(AccessType::ColorAttachmentWrite),
+    }], &[])
+}
+
+#[cfg(test)]
+#[path = "ibl_tests.rs"]
+mod tests;
@@ -0,0 +1,148 @@
+use super::*;
+use crate::graphics_device::mock_graphics_device::{
+    MockCommandList, MockGraphicsDevice, MockPipeline, MockTexture,
+};
+
+fn make_baker() -> IblBaker {
+    let pipeline = |name: &str| -> Arc<dyn graphics_device::Pipeline> {
+        Arc::new(MockPipeline::new(name.to_string()))
+    };
+    IblBaker::new(IblPipelines {
+        irradiance: pipeline("irradiance"),
+        specular: pipeline("specular"),
+        brdf_lut: pipeline("brdf_lut"),
+    })
+}
+
+fn make_device() -> Arc<Mutex<dyn GraphicsDevice>> {
+    Arc::new(Mutex::new(MockGraphicsDevice::new()))
+}
+
+fn create_environment(
+    rm: &mut ResourceManager,
+    gd: &Arc<Mutex<dyn GraphicsDevice>>,
+    texture_type: TextureType,
+) -> TextureKey {
+    rm.create_texture("sky".to_string(), TextureDesc {
+        graphics_device: gd.clone(),
+        texture: graphics_device::TextureDesc {
+            width: 256, height: 256, depth: 1,
+            format: TextureFormat::R16G16B16A16_SFLOAT,
+            usage: TextureUsage::Sampled,
+            texture_type,
+            sample_count: SampleCount::S1,
+            array_layers: CUBE_FACE_COUNT, data: None, mipmap: MipmapMode::None,
+            debug_name: None,
+        },
+        layers: Vec::new(),
+    }).unwrap()
+}
+
+fn info(rm: &ResourceManager, key: TextureKey) -> graphics_device::TextureInfo {
+    rm.texture(key).unwrap().graphics_device_texture().info().clone()
+}
+
+// ============================================================================
+// Environment bake
+// ============================================================================
+
+#[test]
+fn test_bake_environment_creates_cube_targets() {
+    let mut rm = ResourceManager::new();
+    let gd = make_device();
+    let sky = create_environment(&mut rm, &gd, TextureType::Cube);
+
+    let baked = make_baker().bake_environment(&mut rm, &gd, "sky", sky, &IblBakeDesc::default()).unwrap();
+
+    assert_eq!(rm.texture_key("sky_irradiance"), Some(baked.irradiance));
+    assert_eq!(rm.texture_key("sky_specular"), Some(baked.specular));
+    let irradiance = info(&rm, baked.irradiance);
+    assert_eq!(irradiance.texture_type, TextureType::Cube);
+    assert_eq!(irradiance.array_layers, CUBE_FACE_COUNT);
+    assert_eq!((irradiance.width, irradiance.format), (DEFAULT_IRRADIANCE_SIZE, IBL_CUBE_FORMAT));
+    let specular = info(&rm, baked.specular);
+    assert_eq!(specular.width, DEFAULT_SPECULAR_SIZE);
+    assert_eq!(specular.mip_levels, DEFAULT_SPECULAR_MIP_LEVELS);
+    assert_eq!(baked.specular_mip_levels, DEFAULT_SPECULAR_MIP_LEVELS);
+    assert_eq!(specular.usage, TextureUsage::SampledAndRenderTarget);
+}
+
+#[test]
+fn test_bake_environment_rejects_invalid_input() {
+    let mut rm = ResourceManager::new();
+    let gd = make_device();
+    let baker = make_baker();
+    let sky = create_environment(&mut rm, &gd, TextureType::Cube);
+
+    let too_many_mips = IblBakeDesc { specular_size: 16, specular_mip_levels: 6, ..Default::default() };
+    assert!(baker.bake_environment(&mut rm, &gd, "a", sky, &too_many_mips).is_err());
+    let no_samples = IblBakeDesc { sample_count: 0, ..Default::default() };
+    assert!(baker.bake_environment(&mut rm, &gd, "b", sky, &no_samples).is_err());
+
+    let mut other = ResourceManager::new();
+    let array = create_environment(&mut other, &gd, TextureType::Array2D);
+    assert!(baker.bake_environment(&mut other, &gd, "c", array, &IblBakeDesc::default()).is_err());
+    // Nothing was created by the failed bakes
+    assert_eq!(rm.texture_key("a_irradiance"), None);
+    assert_eq!(other.texture_key("c_irradiance"), None);
+}
+
+#[test]
+fn test_specular_roughness_spans_mips() {
+    assert_eq!(specular_roughness(0, 1), 0.0);
+    assert_eq!(specular_roughness(0, 5), 0.0);
+    assert_eq!(specular_roughness(2, 5), 0.5);
+    assert_eq!(specular_roughness(4, 5), 1.0);
+}
+
+// ============================================================================
+// BRDF LUT
+// ============================================================================
+
+#[test]
+fn test_bake_brdf_lut() {
+    let mut rm = ResourceManager::new();
+    let gd = make_device();
+    let baker = make_baker();
+
+    let lut = baker.bake_brdf_lut(&mut rm, &gd, "brdf_lut", DEFAULT_BRDF_LUT_SIZE, DEFAULT_IBL_SAMPLE_COUNT).unwrap();
+    let lut = info(&rm, lut);
+    assert_eq!(lut.texture_type, TextureType::Tex2D);
+    assert_eq!((lut.width, lut.height), (DEFAULT_BRDF_LUT_SIZE, DEFAULT_BRDF_LUT_SIZE));
+    assert_eq!(lut.format, BRDF_LUT_FORMAT);
+
+    assert!(baker.bake_brdf_lut(&mut rm, &gd, "empty", 0, DEFAULT_IBL_SAMPLE_COUNT).is_err());
+}
+
+// ============================================================================
+// Recording
+// ============================================================================
+
+#[test]
+fn test_record_target_draws_then_makes_target_readable() {
+    let gd = MockGraphicsDevice::new();
+    let render_pass = create_render_pass(&gd, IBL_CUBE_FORMAT).unwrap();
+    let target: Arc<dyn graphics_device::Texture> =
+        Arc::new(MockTexture::new(64, 64, CUBE_FACE_COUNT, TextureType::Cube, "target".to_string()));
+    let constants = IblBakeConstants {
+        face: 0, roughness: 0.0, environment_index: 0, sample_count: 1, environment_size: 64.0,
+    };
+    let draws = [
+        bake_draw(&gd, &render_pass, &target, 0, 0, constants).unwrap(),
+        bake_draw(&gd, &render_pass, &target, 1, 5, constants).unwrap(),
+    ];
+    assert_eq!(draws[1].size, 32);
+
+    let mut cmd = MockCommandList::new();
+    let pipeline: Arc<dyn graphics_device::Pipeline> = Arc::new(MockPipeline::new("bake".to_string()));
+    record_target(&mut cmd, &render_pass, &pipeline, &target, &draws).unwrap();
+
+    let pass = [
+        "begin_render_pass", "set_viewport", "set_scissor", "bind_pipeline", "bind_textures",
+        "push_constants", "draw", "end_render_pass",
+    ];
+    let mut expected: Vec<&str> = pass.iter().chain(pass.iter()).copied().collect();
+    expected.push("declare_accesses 1");
+    assert_eq!(cmd.commands, expected);
+    assert_eq!(constants.to_bytes().len(), 20);
+}
@@ -17,46 +17,94 @@ dynamic polymorphism (similar to C++ virtual inheritance). Backend implementatio
 - **RendererFrame**: Frame recording trait
 
 Backend implementations provide concrete types that implement these traits.
+
+## Features
+
+- **renderer** (default): the full engine. Implies `std`.
+- **manifest**: serializable resource manifests (`resource::manifest`) and scene
+  files (`scene::SceneFile`), saved and loaded as RON or JSON. Implies `renderer`.
+- **archive**: packed asset archives (`resource::asset_archive`), zstd-compressed
+  and memory-mapped, read by the streaming workers. Implies `renderer`.
+- **log**: `log::LogCrateLogger`, forwarding engine log entries to the `log` crate.
+  Implies `renderer`.
+- **tracing**: `log::TracingLogger`, forwarding engine log entries as `tracing` events.
+  Implies `renderer`.
+- **std** / **libm**: math backend of `glam`; one of them is required.
+
+With default features disabled, only the math and scene-description core
+is built (`Camera`, `Frustum`, `project_sphere_diameter`, `AABB`,
+`apply_hysteresis`, `Viewport`, `Rect2D`). It depends on `glam` alone and
+is `no_std` without the `std` feature, so simulation binaries can share
+the culling and LOD logic without linking the renderer dependencies:
+
+```toml
+galaxy_3d_engine = { version = "0.1", default-features = false, features = ["libm"] }
+```
 */
 
-// Internal modules
-mod error;
-mod engine;
-pub mod log;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Items built only with the `renderer` feature
+macro_rules! cfg_renderer {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "renderer")]
+            $item
+        )*
+    };
+}
+
+/// Embed a SPIR-V file of `shaders/spirv/` as a `&'static [u8]`, 4-byte
+/// aligned as `GraphicsDevice::create_shader` reads it as words
+#[cfg(feature = "renderer")]
+macro_rules! include_spirv {
+    ($file:literal) => {{
+        #[repr(C, align(4))]
+        struct Aligned<T: ?Sized>(T);
+        static ALIGNED: &Aligned<[u8]> = &Aligned(*include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/shaders/spirv/", $file)));
+        &ALIGNED.0
+    }};
+}
+
+// Core modules (always built)
 pub mod graphics_device;
-pub mod resource;
 pub mod scene;
 pub mod camera;
-pub mod render_graph;
-pub mod utils;
-
-// Main galaxy3d namespace module
-pub mod galaxy3d {
-    // Error types
-    pub use crate::error::{Error, Result};
 
-    // Engine singleton
-    pub use crate::engine::Engine;
-
-    // GraphicsDevice factory trait
-    pub use crate::graphics_device::GraphicsDevice;
+// Modules exporting macros cannot be macro-expanded (engine_* macros are
+// referred to by absolute paths)
+#[cfg(feature = "renderer")]
+pub mod log;
+#[cfg(feature = "renderer")]
+pub mod profiler;
 
-    // Logging sub-module (types only, NOT macros)
-    pub mod log {
-        pub use crate::log::{Logger, LogEntry, LogSeverity, DefaultLogger};
-        // Note: engine_* macros are NOT re-exported here - they are internal only
-    }
+// Internal modules
+cfg_renderer! {
+    mod error;
+    mod engine;
+    pub mod resource;
+    pub mod event;
+    pub mod render_graph;
+    pub mod post;
+    pub mod debug_draw;
+    pub mod minimap;
+    pub mod terrain;
+    pub mod planar_reflection;
+    pub mod perf_advisor;
+    pub mod frame_loop;
+    pub mod debug_console;
+    pub mod ibl;
+    pub mod atmosphere;
+    pub mod utils;
+}
 
+// Main galaxy3d namespace module
+pub mod galaxy3d {
     // Render sub-module with all rendering types
     pub mod render {
         pub use crate::graphics_device::*;
     }
 
-    // Resource sub-module
-    pub mod resource {
-        pub use crate::resource::*;
-    }
-
     // Scene sub-module
     pub mod scene {
         pub use crate::scene::*;
@@ -67,14 +115,103 @@ pub mod galaxy3d {
         pub use crate::camera::*;
     }
 
-    // Render graph sub-module
-    pub mod render_graph {
-        pub use crate::render_graph::*;
-    }
-
-    // Utils sub-module
-    pub mod utils {
-        pub use crate::utils::*;
+    cfg_renderer! {
+        // Error types
+        pub use crate::error::{Error, ErrorCode, Result, ResultExt};
+
+        // Engine singleton
+        pub use crate::engine::{Engine, FrameSummary};
+
+        // GraphicsDevice factory trait
+        pub use crate::graphics_device::GraphicsDevice;
+
+        // Logging sub-module (types only, NOT macros)
+        pub mod log {
+            pub use crate::log::{
+                Logger, LogEntry, LogSeverity, DefaultLogger, LogFilter, LogFanOut,
+                FileLogger, FileRotation, RingBufferLogger, format_log_entry,
+            };
+            #[cfg(feature = "log")]
+            pub use crate::log::LogCrateLogger;
+            #[cfg(feature = "tracing")]
+            pub use crate::log::TracingLogger;
+            // Note: engine_* macros are NOT re-exported here - they are internal only
+        }
+
+        // Resource sub-module
+        pub mod resource {
+            pub use crate::resource::*;
+        }
+
+        // Event bus sub-module
+        pub mod event {
+            pub use crate::event::*;
+        }
+
+        // Render graph sub-module
+        pub mod render_graph {
+            pub use crate::render_graph::*;
+        }
+
+        // Post-processing sub-module
+        pub mod post {
+            pub use crate::post::*;
+        }
+
+        // Debug drawing sub-module
+        pub mod debug_draw {
+            pub use crate::debug_draw::*;
+        }
+
+        // Minimap sub-module
+        pub mod minimap {
+            pub use crate::minimap::*;
+        }
+
+        // Terrain sub-module
+        pub mod terrain {
+            pub use crate::terrain::*;
+        }
+
+        // Planar reflection sub-module
+        pub mod planar_reflection {
+            pub use crate::planar_reflection::*;
+        }
+
+        // CPU profiler sub-module (the profile_scope! macro is exported at the crate root)
+        pub mod profiler {
+            pub use crate::profiler::*;
+        }
+
+        // Performance scaling advisor sub-module
+        pub mod perf_advisor {
+            pub use crate::perf_advisor::*;
+        }
+
+        // Frame pacing and fixed-timestep loop sub-module
+        pub mod frame_loop {
+            pub use crate::frame_loop::*;
+        }
+
+        // In-engine debug console sub-module
+        pub mod debug_console {
+            pub use crate::debug_console::*;
+        }
+
+        // Image-based lighting baking sub-module
+        pub mod ibl {
+            pub use crate::ibl::*;
+        }
+
+        // Sky and atmosphere sub-module
+        pub mod atmosphere {
+            pub use crate::atmosphere::*;
+        }
+
+        // Utils sub-module
+        pub mod utils {
+            pub use crate::utils::*;
+        }
     }
 }
 
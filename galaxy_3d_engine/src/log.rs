@@ -6,10 +6,23 @@
 //! - Colored console output by default
 //! - Thread-safe logging with RwLock
 //! - File and line information for detailed ERROR logs
+//! - Per-module severity filters (`LogFilter`), global
+//!   (`Engine::set_log_filter`) or per sink, changeable at runtime
+//! - Sinks: `LogFanOut` (several sinks, each with its own filter),
+//!   `FileLogger` (size-based rotation), `RingBufferLogger` (last entries
+//!   in memory, for an in-app console), and forwarding to the `log` crate
+//!   (`LogCrateLogger`, feature `log`) or to `tracing` (`TracingLogger`,
+//!   feature `tracing`)
 
 use colored::*;
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::SystemTime;
 use chrono::{DateTime, Local};
+use crate::error::Result;
 
 /// Logger trait for custom logging implementations
 ///
@@ -120,6 +133,539 @@ impl Logger for DefaultLogger {
     }
 }
 
+impl LogSeverity {
+    /// Severity from its name, case-insensitive ("trace", "debug", "info",
+    /// "warn" / "warning", "error")
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "trace" => Some(Self::Trace),
+            "debug" => Some(Self::Debug),
+            "info" => Some(Self::Info),
+            "warn" | "warning" => Some(Self::Warn),
+            "error" => Some(Self::Error),
+            _ => None,
+        }
+    }
+}
+
+/// Plain-text line of an entry, without colors:
+/// `[timestamp] [SEVERITY] [source] message (file:line)`
+pub fn format_log_entry(entry: &LogEntry) -> String {
+    let datetime: DateTime<Local> = entry.timestamp.into();
+    let severity = match entry.severity {
+        LogSeverity::Trace => "TRACE",
+        LogSeverity::Debug => "DEBUG",
+        LogSeverity::Info => "INFO ",
+        LogSeverity::Warn => "WARN ",
+        LogSeverity::Error => "ERROR",
+    };
+    let mut line = format!("[{}] [{}] [{}] {}",
+        datetime.format("%Y-%m-%d %H:%M:%S%.3f"), severity, entry.source, entry.message);
+    if let (Some(file), Some(line_number)) = (entry.file, entry.line) {
+        line.push_str(&format!(" ({}:{})", file, line_number));
+    }
+    line
+}
+
+// ===== LOG FILTER =====
+
+/// Minimum severity per source module
+///
+/// A module rule applies to the sources equal to the module or below it
+/// (`galaxy3d::vulkan` covers `galaxy3d::vulkan::Texture`, not
+/// `galaxy3d::vulkanx`). The longest matching module wins; sources matching
+/// no rule use the default level. A level of None silences the module.
+///
+/// ```ignore
+/// let filter = LogFilter::parse("warn,galaxy3d::vulkan=debug,galaxy3d::Engine=off")?;
+/// Engine::set_log_filter(filter);
+/// Engine::set_module_log_level("galaxy3d::ResourceManager", Some(LogSeverity::Trace));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogFilter {
+    default_level: Option<LogSeverity>,
+    /// (module, minimum severity), in insertion order
+    modules: Vec<(String, Option<LogSeverity>)>,
+}
+
+impl Default for LogFilter {
+    /// Everything enabled
+    fn default() -> Self {
+        Self::new(Some(LogSeverity::Trace))
+    }
+}
+
+impl LogFilter {
+    /// Filter without module rules (None: everything silenced)
+    pub const fn new(default_level: Option<LogSeverity>) -> Self {
+        Self { default_level, modules: Vec::new() }
+    }
+
+    /// Parse a comma-separated spec: an optional default level, then
+    /// `module=level` rules. Levels are severity names or `off`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a level is unknown or a module is empty.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let mut filter = Self::default();
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            match directive.split_once('=') {
+                Some((module, level)) => {
+                    let module = module.trim();
+                    if module.is_empty() {
+                        crate::engine_bail!("galaxy3d::LogFilter", "Empty module in log filter directive '{}'", directive);
+                    }
+                    filter.set_module_level(module, Self::parse_level(level.trim())?);
+                }
+                None => filter.default_level = Self::parse_level(directive)?,
+            }
+        }
+        Ok(filter)
+    }
+
+    fn parse_level(name: &str) -> Result<Option<LogSeverity>> {
+        if name.eq_ignore_ascii_case("off") {
+            return Ok(None);
+        }
+        match LogSeverity::from_name(name) {
+            Some(level) => Ok(Some(level)),
+            None => crate::engine_bail!("galaxy3d::LogFilter", "Unknown log level '{}'", name),
+        }
+    }
+
+    pub fn default_level(&self) -> Option<LogSeverity> {
+        self.default_level
+    }
+
+    pub fn set_default_level(&mut self, level: Option<LogSeverity>) {
+        self.default_level = level;
+    }
+
+    /// Set the minimum severity of `module` and the modules below it
+    /// (None: silenced), replacing its previous rule
+    pub fn set_module_level(&mut self, module: &str, level: Option<LogSeverity>) {
+        match self.modules.iter_mut().find(|(name, _)| name == module) {
+            Some(rule) => rule.1 = level,
+            None => self.modules.push((module.to_string(), level)),
+        }
+    }
+
+    /// Remove the rule of `module`. Returns false if it had none.
+    pub fn clear_module_level(&mut self, module: &str) -> bool {
+        let count = self.modules.len();
+        self.modules.retain(|(name, _)| name != module);
+        self.modules.len() != count
+    }
+
+    /// Module rules, in insertion order
+    pub fn module_levels(&self) -> impl Iterator<Item = (&str, Option<LogSeverity>)> {
+        self.modules.iter().map(|(name, level)| (name.as_str(), *level))
+    }
+
+    /// Minimum severity logged for `source` (None: silenced)
+    pub fn level_for(&self, source: &str) -> Option<LogSeverity> {
+        self.modules.iter()
+            .filter(|(module, _)| source.strip_prefix(module.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with("::")))
+            .max_by_key(|(module, _)| module.len())
+            .map_or(self.default_level, |(_, level)| *level)
+    }
+
+    /// Whether an entry of `severity` from `source` passes the filter
+    pub fn enabled(&self, severity: LogSeverity, source: &str) -> bool {
+        self.level_for(source).is_some_and(|level| severity >= level)
+    }
+}
+
+// ===== SINKS =====
+
+/// One logger of a `LogFanOut`
+struct LogSink {
+    name: String,
+    logger: Box<dyn Logger>,
+    filter: LogFilter,
+}
+
+/// Logger sending every entry to several sinks, each behind its own
+/// `LogFilter`
+///
+/// Clones share the sinks: keep a clone after `Engine::set_logger()` to
+/// add, remove or re-filter sinks at runtime. Sinks are called in the
+/// order they were added, from the logging thread, under a read lock: a
+/// sink must not add or remove sinks from `log()`.
+///
+/// ```ignore
+/// let fan_out = LogFanOut::new();
+/// fan_out.add_sink("console", DefaultLogger)?;
+/// fan_out.add_filtered_sink("file", FileLogger::new("logs/engine.log", FileRotation::default())?,
+///     LogFilter::new(Some(LogSeverity::Debug)))?;
+/// fan_out.add_sink("console_overlay", ring_buffer.clone())?;
+/// Engine::set_logger(fan_out.clone());
+/// ```
+#[derive(Clone, Default)]
+pub struct LogFanOut {
+    sinks: Arc<RwLock<Vec<LogSink>>>,
+}
+
+impl LogFanOut {
+    /// Fan-out without sinks
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a sink receiving every entry
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a sink named `name` already exists.
+    pub fn add_sink<L: Logger + 'static>(&self, name: &str, logger: L) -> Result<()> {
+        self.add_filtered_sink(name, logger, LogFilter::default())
+    }
+
+    /// Add a sink receiving the entries passing `filter`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a sink named `name` already exists.
+    pub fn add_filtered_sink<L: Logger + 'static>(&self, name: &str, logger: L, filter: LogFilter) -> Result<()> {
+        let added = match self.sinks.write() {
+            Ok(mut sinks) if !sinks.iter().any(|sink| sink.name == name) => {
+                sinks.push(LogSink { name: name.to_string(), logger: Box::new(logger), filter });
+                true
+            }
+            _ => false,
+        };
+        // Logged once the lock is released: this fan-out may be the engine logger
+        if !added {
+            crate::engine_bail!("galaxy3d::LogFanOut", "Log sink '{}' already exists", name);
+        }
+        Ok(())
+    }
+
+    /// Remove the sink named `name`. Returns false if there is none.
+    pub fn remove_sink(&self, name: &str) -> bool {
+        self.sinks.write().is_ok_and(|mut sinks| {
+            let count = sinks.len();
+            sinks.retain(|sink| sink.name != name);
+            sinks.len() != count
+        })
+    }
+
+    /// Replace the filter of the sink named `name`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no sink named `name`.
+    pub fn set_sink_filter(&self, name: &str, filter: LogFilter) -> Result<()> {
+        let found = self.sinks.write().is_ok_and(|mut sinks| {
+            match sinks.iter_mut().find(|sink| sink.name == name) {
+                Some(sink) => {
+                    sink.filter = filter;
+                    true
+                }
+                None => false,
+            }
+        });
+        if !found {
+            crate::engine_bail!("galaxy3d::LogFanOut", "No log sink named '{}'", name);
+        }
+        Ok(())
+    }
+
+    /// Filter of the sink named `name`
+    pub fn sink_filter(&self, name: &str) -> Option<LogFilter> {
+        self.sinks.read().ok()?
+            .iter().find(|sink| sink.name == name)
+            .map(|sink| sink.filter.clone())
+    }
+
+    /// Names of the sinks, in order
+    pub fn sink_names(&self) -> Vec<String> {
+        self.sinks.read()
+            .map(|sinks| sinks.iter().map(|sink| sink.name.clone()).collect())
+            .unwrap_or_default()
+    }
+}
+
+impl Logger for LogFanOut {
+    fn log(&self, entry: &LogEntry) {
+        if let Ok(sinks) = self.sinks.read() {
+            for sink in sinks.iter().filter(|sink| sink.filter.enabled(entry.severity, &entry.source)) {
+                sink.logger.log(entry);
+            }
+        }
+    }
+}
+
+/// Rotation of a `FileLogger`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileRotation {
+    /// Size above which the file is rotated, in bytes
+    pub max_bytes: u64,
+    /// Rotated files kept (`engine.log.1` is the most recent). 0 truncates
+    /// the file instead.
+    pub max_files: usize,
+}
+
+impl Default for FileRotation {
+    /// 10 MiB, 5 rotated files
+    fn default() -> Self {
+        Self { max_bytes: 10 * 1024 * 1024, max_files: 5 }
+    }
+}
+
+/// Open log file and its size
+struct LogFile {
+    file: File,
+    size: u64,
+}
+
+/// Logger appending plain-text lines (`format_log_entry`) to a file,
+/// rotated by size
+///
+/// Before a line would take the file over `max_bytes`, `engine.log.N-1`
+/// becomes `engine.log.N` (the oldest is deleted), ..., `engine.log`
+/// becomes `engine.log.1`, and a new `engine.log` is started. Each line is
+/// written with one call, so a crash loses at most the line being written.
+/// Write failures go to stderr (logging them would recurse).
+pub struct FileLogger {
+    path: PathBuf,
+    rotation: FileRotation,
+    file: Mutex<Option<LogFile>>,
+}
+
+impl FileLogger {
+    /// Append to `path`, creating it and its parent directories if needed
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened.
+    pub fn new(path: impl AsRef<Path>, rotation: FileRotation) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = Self::open(&path)
+            .map_err(|e| crate::engine_err!("galaxy3d::FileLogger",
+                "Failed to open log file '{}': {}", path.display(), e))?;
+        Ok(Self { path, rotation, file: Mutex::new(Some(file)) })
+    }
+
+    fn open(path: &Path) -> std::io::Result<LogFile> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let size = file.metadata()?.len();
+        Ok(LogFile { file, size })
+    }
+
+    /// Path of the log file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Path of the `index`-th rotated file (`engine.log.1`, ...)
+    pub fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut name = self.path.as_os_str().to_os_string();
+        name.push(format!(".{}", index));
+        PathBuf::from(name)
+    }
+
+    /// Shift the rotated files and start a new log file
+    fn rotate(&self) -> std::io::Result<LogFile> {
+        if self.rotation.max_files > 0 {
+            let _ = fs::remove_file(self.rotated_path(self.rotation.max_files));
+            for index in (1..self.rotation.max_files).rev() {
+                let from = self.rotated_path(index);
+                if from.exists() {
+                    fs::rename(&from, self.rotated_path(index + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.rotated_path(1))?;
+        } else {
+            fs::remove_file(&self.path)?;
+        }
+        Self::open(&self.path)
+    }
+}
+
+impl Logger for FileLogger {
+    fn log(&self, entry: &LogEntry) {
+        let mut line = format_log_entry(entry);
+        line.push('\n');
+        let Ok(mut state) = self.file.lock() else { return };
+        let size = state.as_ref().map_or(0, |file| file.size);
+        if size > 0 && size + line.len() as u64 > self.rotation.max_bytes {
+            // Close the file before renaming it (required on Windows)
+            *state = None;
+            match self.rotate() {
+                Ok(file) => *state = Some(file),
+                Err(e) => eprintln!("galaxy3d::FileLogger: failed to rotate '{}': {}", self.path.display(), e),
+            }
+        }
+        if state.is_none() {
+            match Self::open(&self.path) {
+                Ok(file) => *state = Some(file),
+                Err(e) => {
+                    eprintln!("galaxy3d::FileLogger: failed to open '{}': {}", self.path.display(), e);
+                    return;
+                }
+            }
+        }
+        if let Some(file) = state.as_mut() {
+            match file.file.write_all(line.as_bytes()) {
+                Ok(()) => file.size += line.len() as u64,
+                Err(e) => eprintln!("galaxy3d::FileLogger: failed to write '{}': {}", self.path.display(), e),
+            }
+        }
+    }
+}
+
+/// Entries kept by a `RingBufferLogger`
+struct LogRing {
+    entries: VecDeque<LogEntry>,
+    capacity: usize,
+    /// Entries logged since creation (or `clear()`), dropped ones included
+    total: u64,
+}
+
+/// Logger keeping the last `capacity` entries in memory, for an in-app
+/// console
+///
+/// Clones share the entries: install one clone (in a `LogFanOut`) and read
+/// the other.
+#[derive(Clone)]
+pub struct RingBufferLogger {
+    ring: Arc<Mutex<LogRing>>,
+}
+
+impl RingBufferLogger {
+    /// Empty buffer keeping `capacity` entries
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            ring: Arc::new(Mutex::new(LogRing {
+                entries: VecDeque::with_capacity(capacity),
+                capacity,
+                total: 0,
+            })),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.ring.lock().map_or(0, |ring| ring.capacity)
+    }
+
+    /// Number of entries kept
+    pub fn len(&self) -> usize {
+        self.ring.lock().map_or(0, |ring| ring.entries.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Entries logged since creation or `clear()`, including those dropped
+    /// from the buffer. A reader compares it with its last value to know
+    /// whether there is anything new.
+    pub fn total_logged(&self) -> u64 {
+        self.ring.lock().map_or(0, |ring| ring.total)
+    }
+
+    /// The kept entries, oldest first
+    pub fn entries(&self) -> Vec<LogEntry> {
+        self.recent(usize::MAX)
+    }
+
+    /// The last `count` kept entries, oldest first
+    pub fn recent(&self, count: usize) -> Vec<LogEntry> {
+        self.ring.lock()
+            .map(|ring| {
+                let skip = ring.entries.len().saturating_sub(count);
+                ring.entries.iter().skip(skip).cloned().collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Drop every entry and restart `total_logged()` at 0
+    pub fn clear(&self) {
+        if let Ok(mut ring) = self.ring.lock() {
+            ring.entries.clear();
+            ring.total = 0;
+        }
+    }
+}
+
+impl Logger for RingBufferLogger {
+    fn log(&self, entry: &LogEntry) {
+        if let Ok(mut ring) = self.ring.lock() {
+            ring.total += 1;
+            if ring.capacity == 0 {
+                return;
+            }
+            if ring.entries.len() == ring.capacity {
+                ring.entries.pop_front();
+            }
+            ring.entries.push_back(entry.clone());
+        }
+    }
+}
+
+/// Logger forwarding entries to the `log` crate facade (feature `log`)
+///
+/// The source becomes the record target; file and line are forwarded when
+/// present.
+#[cfg(feature = "log")]
+pub struct LogCrateLogger;
+
+#[cfg(feature = "log")]
+impl Logger for LogCrateLogger {
+    fn log(&self, entry: &LogEntry) {
+        let level = match entry.severity {
+            LogSeverity::Trace => ::log::Level::Trace,
+            LogSeverity::Debug => ::log::Level::Debug,
+            LogSeverity::Info => ::log::Level::Info,
+            LogSeverity::Warn => ::log::Level::Warn,
+            LogSeverity::Error => ::log::Level::Error,
+        };
+        let logger = ::log::logger();
+        let metadata = ::log::Metadata::builder().level(level).target(&entry.source).build();
+        if logger.enabled(&metadata) {
+            logger.log(&::log::Record::builder()
+                .metadata(metadata)
+                .args(format_args!("{}", entry.message))
+                .file(entry.file)
+                .line(entry.line)
+                .build());
+        }
+    }
+}
+
+/// Logger forwarding entries as `tracing` events (feature `tracing`)
+///
+/// Events have the target `galaxy3d`; the source, file and line are event
+/// fields.
+#[cfg(feature = "tracing")]
+pub struct TracingLogger;
+
+#[cfg(feature = "tracing")]
+impl Logger for TracingLogger {
+    fn log(&self, entry: &LogEntry) {
+        macro_rules! forward {
+            ($level:expr) => {
+                ::tracing::event!(target: "galaxy3d", $level,
+                    source = %entry.source, file = entry.file, line = entry.line,
+                    "{}", entry.message)
+            };
+        }
+        match entry.severity {
+            LogSeverity::Trace => forward!(::tracing::Level::TRACE),
+            LogSeverity::Debug => forward!(::tracing::Level::DEBUG),
+            LogSeverity::Info => forward!(::tracing::Level::INFO),
+            LogSeverity::Warn => forward!(::tracing::Level::WARN),
+            LogSeverity::Error => forward!(::tracing::Level::ERROR),
+        }
+    }
+}
+
 // ===== LOGGING MACROS (INTERNAL USE ONLY) =====
 
 /// Log a TRACE message (very verbose, typically disabled)
@@ -211,6 +757,8 @@ macro_rules! engine_error {
 // - engine_bail_warn! → engine_warn!  + return Err(BackendError)
 // - engine_err!       → engine_error! + Error::BackendError (value, for closures)
 // - engine_warn_err!  → engine_warn!  + Error::BackendError (value, for closures)
+// - engine_not_found! → engine_error! + Error::ResourceNotFound (value, for closures)
+// - engine_backend_err! → engine_error! + Error::Backend with a native code (value)
 
 /// Log an ERROR and immediately return Err(BackendError)
 ///
@@ -261,6 +809,33 @@ macro_rules! engine_warn_err {
     }};
 }
 
+/// Log an ERROR and return Error::ResourceNotFound as a value (for closures)
+///
+/// Use in `.ok_or_else(|| engine_not_found!(source, "Texture", name))`.
+/// `$name` is anything `Display` (use `format!("{:?}", key)` for keys).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! engine_not_found {
+    ($source:expr, $kind:expr, $name:expr) => {{
+        let name = $name.to_string();
+        $crate::engine_error!($source, "{} '{}' not found", $kind, name);
+        $crate::galaxy3d::Error::ResourceNotFound { kind: $kind, name }
+    }};
+}
+
+/// Log an ERROR and return Error::Backend as a value (for closures)
+///
+/// `$code` is the native result code (e.g. `vk::Result::as_raw()`).
+/// Use in `.map_err(|e| engine_backend_err!(source, e.as_raw(), "...: {:?}", e))`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! engine_backend_err {
+    ($source:expr, $code:expr, $($arg:tt)*) => {{
+        $crate::engine_error!($source, $($arg)*);
+        $crate::galaxy3d::Error::Backend { code: $code, message: format!($($arg)*) }
+    }};
+}
+
 #[cfg(test)]
 #[path = "log_tests.rs"]
 mod tests;
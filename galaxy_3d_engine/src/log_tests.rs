@@ -1,8 +1,12 @@
 //! Unit tests for log.rs
 //!
-//! Tests Logger trait, LogEntry, LogSeverity, and DefaultLogger.
+//! Tests Logger trait, LogEntry, LogSeverity, DefaultLogger, LogFilter and
+//! the sinks.
 
-use crate::log::{Logger, LogEntry, LogSeverity, DefaultLogger};
+use crate::log::{
+    Logger, LogEntry, LogSeverity, DefaultLogger, LogFilter, LogFanOut,
+    FileLogger, FileRotation, RingBufferLogger, format_log_entry,
+};
 use std::time::SystemTime;
 
 // ============================================================================
@@ -362,3 +366,155 @@ fn test_log_entry_with_different_timestamps() {
     // time2 should be after time1
     assert!(entry2.timestamp > entry1.timestamp);
 }
+
+fn entry(severity: LogSeverity, source: &str, message: &str) -> LogEntry {
+    LogEntry {
+        severity,
+        timestamp: SystemTime::now(),
+        source: source.to_string(),
+        message: message.to_string(),
+        file: None,
+        line: None,
+    }
+}
+
+// ============================================================================
+// LOG FILTER TESTS
+// ============================================================================
+
+#[test]
+fn test_log_severity_from_name() {
+    assert_eq!(LogSeverity::from_name("WARN"), Some(LogSeverity::Warn));
+    assert_eq!(LogSeverity::from_name("warning"), Some(LogSeverity::Warn));
+    assert_eq!(LogSeverity::from_name("trace"), Some(LogSeverity::Trace));
+    assert_eq!(LogSeverity::from_name("verbose"), None);
+}
+
+#[test]
+fn test_log_filter_longest_module_wins() {
+    let mut filter = LogFilter::new(Some(LogSeverity::Warn));
+    filter.set_module_level("galaxy3d::vulkan", Some(LogSeverity::Debug));
+    filter.set_module_level("galaxy3d::vulkan::Texture", None);
+
+    assert!(!filter.enabled(LogSeverity::Info, "galaxy3d::Engine"));
+    assert!(filter.enabled(LogSeverity::Warn, "galaxy3d::Engine"));
+    assert!(filter.enabled(LogSeverity::Debug, "galaxy3d::vulkan"));
+    assert!(filter.enabled(LogSeverity::Debug, "galaxy3d::vulkan::Buffer"));
+    assert!(!filter.enabled(LogSeverity::Error, "galaxy3d::vulkan::Texture"));
+    // Only whole module names match
+    assert_eq!(filter.level_for("galaxy3d::vulkanx"), Some(LogSeverity::Warn));
+
+    assert!(filter.clear_module_level("galaxy3d::vulkan::Texture"));
+    assert!(!filter.clear_module_level("galaxy3d::vulkan::Texture"));
+    assert!(filter.enabled(LogSeverity::Error, "galaxy3d::vulkan::Texture"));
+}
+
+#[test]
+fn test_log_filter_parse() {
+    let filter = LogFilter::parse(" warn , galaxy3d::vulkan=debug,galaxy3d::Engine=off ").unwrap();
+    assert_eq!(filter.default_level(), Some(LogSeverity::Warn));
+    assert_eq!(filter.level_for("galaxy3d::vulkan::Buffer"), Some(LogSeverity::Debug));
+    assert_eq!(filter.level_for("galaxy3d::Engine"), None);
+    assert_eq!(filter.module_levels().count(), 2);
+
+    assert_eq!(LogFilter::parse("").unwrap(), LogFilter::default());
+    assert!(LogFilter::parse("galaxy3d=loud").is_err());
+    assert!(LogFilter::parse("=info").is_err());
+}
+
+// ============================================================================
+// SINK TESTS
+// ============================================================================
+
+#[test]
+fn test_fan_out_filters_each_sink() {
+    let all = RingBufferLogger::new(8);
+    let errors = RingBufferLogger::new(8);
+    let fan_out = LogFanOut::new();
+    fan_out.add_sink("all", all.clone()).unwrap();
+    fan_out.add_filtered_sink("errors", errors.clone(), LogFilter::new(Some(LogSeverity::Error))).unwrap();
+    assert!(fan_out.add_sink("all", DefaultLogger).is_err());
+    assert_eq!(fan_out.sink_names(), vec!["all".to_string(), "errors".to_string()]);
+
+    fan_out.log(&entry(LogSeverity::Info, "test", "info"));
+    fan_out.log(&entry(LogSeverity::Error, "test", "error"));
+    assert_eq!(all.len(), 2);
+    assert_eq!(errors.len(), 1);
+
+    fan_out.set_sink_filter("errors", LogFilter::default()).unwrap();
+    assert!(fan_out.set_sink_filter("missing", LogFilter::default()).is_err());
+    fan_out.log(&entry(LogSeverity::Info, "test", "info"));
+    assert_eq!(errors.len(), 2);
+
+    assert!(fan_out.remove_sink("all"));
+    assert!(!fan_out.remove_sink("all"));
+    fan_out.log(&entry(LogSeverity::Info, "test", "info"));
+    assert_eq!(all.len(), 3);
+}
+
+#[test]
+fn test_fan_out_is_shared_between_clones_and_threads() {
+    let ring = RingBufferLogger::new(100);
+    let fan_out = LogFanOut::new();
+    fan_out.add_sink("ring", ring.clone()).unwrap();
+    let handles: Vec<_> = (0..4).map(|_| {
+        let fan_out = fan_out.clone();
+        std::thread::spawn(move || {
+            for _ in 0..10 {
+                fan_out.log(&entry(LogSeverity::Info, "test", "threaded"));
+            }
+        })
+    }).collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(ring.total_logged(), 40);
+}
+
+#[test]
+fn test_ring_buffer_keeps_last_entries() {
+    let ring = RingBufferLogger::new(3);
+    for i in 0..5 {
+        ring.log(&entry(LogSeverity::Info, "test", &format!("message {}", i)));
+    }
+    assert_eq!(ring.capacity(), 3);
+    assert_eq!(ring.total_logged(), 5);
+    let messages: Vec<String> = ring.entries().into_iter().map(|e| e.message).collect();
+    assert_eq!(messages, vec!["message 2", "message 3", "message 4"]);
+    assert_eq!(ring.recent(1)[0].message, "message 4");
+
+    ring.clear();
+    assert!(ring.is_empty());
+    assert_eq!(ring.total_logged(), 0);
+}
+
+#[test]
+fn test_format_log_entry_plain_text() {
+    let mut error = entry(LogSeverity::Error, "galaxy3d::test", "failed");
+    error.file = Some("engine.rs");
+    error.line = Some(12);
+    let line = format_log_entry(&error);
+    assert!(line.ends_with("[ERROR] [galaxy3d::test] failed (engine.rs:12)"));
+    assert!(format_log_entry(&entry(LogSeverity::Info, "a", "b")).ends_with("[INFO ] [a] b"));
+}
+
+#[test]
+fn test_file_logger_rotates_by_size() {
+    let dir = std::env::temp_dir()
+        .join(format!("galaxy3d_file_logger_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let line_len = format_log_entry(&entry(LogSeverity::Info, "test", "0123456789")).len() as u64 + 1;
+    let logger = FileLogger::new(dir.join("engine.log"),
+        FileRotation { max_bytes: line_len * 2, max_files: 2 }).unwrap();
+
+    for _ in 0..7 {
+        logger.log(&entry(LogSeverity::Info, "test", "0123456789"));
+    }
+    let read = |path: std::path::PathBuf| std::fs::read_to_string(path).unwrap_or_default().lines().count();
+    assert_eq!(read(logger.path().to_path_buf()), 1);
+    assert_eq!(read(logger.rotated_path(1)), 2);
+    assert_eq!(read(logger.rotated_path(2)), 2);
+    assert!(!logger.rotated_path(3).exists());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,392 @@
+//! Overhead view of a scene with icon markers.
+//!
+//! `Minimap` owns an orthographic camera looking straight down the world
+//! up axis at a followed point, and the `RenderView` of its own pass type
+//! (only the materials with a pass of that type appear on the map). The
+//! scene is drawn into the minimap targets by a regular `ScenePassAction`
+//! with a `ForwardDrawer` using `Minimap::drawer_config()` (unlit, no
+//! shadows, no fog), then `MinimapAction` draws the markers on top.
+//!
+//! Markers (player, objectives, pings) are submitted in world space every
+//! frame and projected on the CPU into the map; out-of-range markers are
+//! dropped or pinned to the map edge.
+
+use glam::{Mat4, Quat, Vec2, Vec3, Vec4};
+use std::sync::{Arc, Mutex};
+use crate::error::Result;
+use crate::{engine_bail, engine_not_found};
+use crate::engine::Engine;
+use crate::camera::{Camera, Frustum, VisibleInstances};
+use crate::graphics_device::{
+    BufferFormat, SamplerType, TextureFormat, VertexAttribute, VertexBinding, VertexInputRate,
+    VertexLayout, Viewport,
+};
+use crate::post::{ViewTarget, ViewTargetDesc, ViewTargetManager};
+use crate::render_graph::RenderGraphManager;
+use crate::resource::material::{LayerRef, RegionRef};
+use crate::resource::resource_manager::{ResourceManager, TextureKey};
+use crate::scene::{resolve_layer_region, ForwardDrawerConfig, RenderView, Scene, ViewDispatcher};
+use crate::utils::{CoordinateSystem, Handedness};
+
+// ===== MARKER LAYOUT =====
+
+/// Size in bytes of one marker instance in the instance vertex buffer
+pub const MINIMAP_MARKER_STRIDE: u32 = 64;
+
+/// Vertices per marker (one triangle strip quad)
+pub const MINIMAP_MARKER_VERTEX_COUNT: u32 = 4;
+
+/// Vertex layout of the marker instance buffer (one binding, per instance).
+///
+/// - location 0: center (vec2, NDC), size (pixels), rotation (radians,
+///   counterclockwise on the map)
+/// - location 1: UV rectangle (vec4, `offset.xy, scale.xy`)
+/// - location 2: color (vec4, linear RGBA)
+/// - location 3: bindless texture index, sampler index, layer, pinned to
+///   the edge (uvec4)
+pub fn minimap_marker_layout() -> VertexLayout {
+    let attribute = |location: u32, format: BufferFormat, offset: u32| VertexAttribute {
+        location, binding: 0, format, offset,
+    };
+    VertexLayout {
+        bindings: vec![VertexBinding {
+            binding: 0,
+            stride: MINIMAP_MARKER_STRIDE,
+            input_rate: VertexInputRate::Instance,
+        }],
+        attributes: vec![
+            attribute(0, BufferFormat::R32G32B32A32_SFLOAT, 0),
+            attribute(1, BufferFormat::R32G32B32A32_SFLOAT, 16),
+            attribute(2, BufferFormat::R32G32B32A32_SFLOAT, 32),
+            attribute(3, BufferFormat::R32G32B32A32_UINT, 48),
+        ],
+    }
+}
+
+// ===== MINIMAP DESC =====
+
+/// Descriptor for creating a Minimap.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MinimapDesc {
+    /// Size of the minimap texture, in pixels
+    pub width: u32,
+    pub height: u32,
+    /// World distance from the followed point to the top and bottom edges
+    /// (the zoom). The horizontal extent follows the aspect ratio.
+    pub half_extent: f32,
+    /// Height of the camera above the followed point
+    pub camera_height: f32,
+    /// Depth of the visible slab below the camera (far plane). Must exceed
+    /// `camera_height` to see below the followed point.
+    pub depth_range: f32,
+    /// Turn the map with the followed heading (false: the top of the map
+    /// always faces the world forward axis)
+    pub rotate_with_heading: bool,
+    /// Pass type of the minimap view
+    pub pass_type: u8,
+}
+
+// ===== MARKERS =====
+
+/// Icon of a marker: a texture region resolved once, drawn many times.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MinimapIcon {
+    /// Index in the bindless texture table
+    bindless_index: u32,
+    /// Index in the bindless sampler table (= SamplerType as u32)
+    sampler_index: u32,
+    layer: u32,
+    /// UV rectangle `(offset.xy, scale.xy)` of the region
+    uv_rect: [f32; 4],
+}
+
+impl MinimapIcon {
+    /// Resolve a texture, layer and atlas region (same rules as
+    /// `BillboardDesc`: a region requires a layer).
+    pub fn new(
+        texture: TextureKey,
+        layer: Option<LayerRef>,
+        region: Option<RegionRef>,
+        sampler_type: SamplerType,
+        resource_manager: &ResourceManager,
+    ) -> Result<Self> {
+        let texture_ref = resource_manager.texture(texture)
+            .ok_or_else(|| engine_not_found!("galaxy3d::Minimap", "Texture", format!("{:?}", texture)))?;
+        let (layer, uv_rect) = resolve_layer_region(texture_ref, layer, region, "galaxy3d::Minimap")?;
+        Ok(Self {
+            bindless_index: texture_ref.graphics_device_texture().bindless_index(),
+            sampler_index: sampler_type as u32,
+            layer,
+            uv_rect,
+        })
+    }
+
+    /// Get the UV rectangle `(offset.xy, scale.xy)` of the region
+    pub fn uv_rect(&self) -> [f32; 4] { self.uv_rect }
+
+    /// Get the resolved layer index
+    pub fn layer(&self) -> u32 { self.layer }
+}
+
+/// A marker submitted for the current frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MinimapMarker {
+    /// World-space position
+    pub position: Vec3,
+    pub icon: MinimapIcon,
+    /// Color multiplied with the icon (linear RGBA)
+    pub color: Vec4,
+    /// Icon size in pixels of the minimap texture
+    pub size: f32,
+    /// World heading of the icon (radians, counterclockwise around the up
+    /// axis, 0 = world forward); turns with the map
+    pub rotation: f32,
+    /// Pin the marker to the map edge when it is out of range (objectives)
+    /// instead of dropping it
+    pub pin_to_edge: bool,
+}
+
+// ===== MINIMAP =====
+
+/// Orthographic top-down view following a point, with its markers.
+pub struct Minimap {
+    desc: MinimapDesc,
+    center: Vec3,
+    heading: f32,
+    camera: Camera,
+    render_view: Arc<Mutex<Option<RenderView>>>,
+    markers: Vec<MinimapMarker>,
+}
+
+impl Minimap {
+    /// Create a minimap centered on the world origin.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the size is zero or if the extent, height or
+    /// depth range is not positive (or the depth range does not exceed the
+    /// camera height).
+    pub fn new(desc: MinimapDesc) -> Result<Self> {
+        if desc.width == 0 || desc.height == 0 {
+            engine_bail!("galaxy3d::Minimap", "size must be non-zero, got {}x{}", desc.width, desc.height);
+        }
+        if !(desc.half_extent > 0.0 && desc.half_extent.is_finite()) {
+            engine_bail!("galaxy3d::Minimap", "half_extent must be positive, got {}", desc.half_extent);
+        }
+        if !(desc.camera_height > 0.0 && desc.depth_range > desc.camera_height && desc.depth_range.is_finite()) {
+            engine_bail!("galaxy3d::Minimap",
+                "need 0 < camera_height < depth_range, got {} and {}", desc.camera_height, desc.depth_range);
+        }
+        let camera = Self::build_camera(&desc, Vec3::ZERO, 0.0);
+        let render_view = RenderView::new(camera.clone(), desc.pass_type);
+        Ok(Self {
+            desc,
+            center: Vec3::ZERO,
+            heading: 0.0,
+            camera,
+            render_view: Arc::new(Mutex::new(Some(render_view))),
+            markers: Vec::new(),
+        })
+    }
+
+    // ===== ACCESSORS =====
+
+    pub fn desc(&self) -> &MinimapDesc {
+        &self.desc
+    }
+
+    /// Followed point
+    pub fn center(&self) -> Vec3 {
+        self.center
+    }
+
+    /// Followed heading (radians)
+    pub fn heading(&self) -> f32 {
+        self.heading
+    }
+
+    /// Camera to cull the scene with
+    pub fn camera(&self) -> &Camera {
+        &self.camera
+    }
+
+    /// View drawn by the minimap passes (`ScenePassAction`, `MinimapAction`)
+    pub fn render_view(&self) -> Arc<Mutex<Option<RenderView>>> {
+        self.render_view.clone()
+    }
+
+    /// Drawer options of the minimap scene pass: unlit, no shadows, no fog
+    pub fn drawer_config() -> ForwardDrawerConfig {
+        ForwardDrawerConfig { unlit: true, ..Default::default() }
+    }
+
+    pub fn markers(&self) -> &[MinimapMarker] {
+        &self.markers
+    }
+
+    // ===== VIEW =====
+
+    /// Center the map on `center`. `heading` (radians, counterclockwise
+    /// around the up axis, 0 = world forward) turns the map when
+    /// `rotate_with_heading` is set.
+    pub fn follow(&mut self, center: Vec3, heading: f32) {
+        self.center = center;
+        self.heading = heading;
+        self.camera = Self::build_camera(&self.desc, center, self.map_rotation());
+        if let Some(view) = self.render_view.lock().unwrap().as_mut() {
+            view.set_camera(self.camera.clone());
+        }
+    }
+
+    /// Change the zoom (world distance from the center to the top edge).
+    pub fn set_half_extent(&mut self, half_extent: f32) -> Result<()> {
+        if !(half_extent > 0.0 && half_extent.is_finite()) {
+            engine_bail!("galaxy3d::Minimap", "half_extent must be positive, got {}", half_extent);
+        }
+        self.desc.half_extent = half_extent;
+        self.follow(self.center, self.heading);
+        Ok(())
+    }
+
+    /// Fill the minimap view from the instances culled with `camera()`.
+    pub fn dispatch(&self, visible: &VisibleInstances, scene: &mut Scene, rm: &ResourceManager) {
+        let mut view = self.render_view.lock().unwrap();
+        if let Some(view) = view.as_mut() {
+            ViewDispatcher::dispatch(visible, scene, rm, std::slice::from_mut(view));
+        }
+    }
+
+    /// Allocate the color (and depth) target of the map, named after
+    /// `view_name` in `targets`. The color target is sampled by the HUD.
+    pub fn create_target(
+        &self,
+        targets: &mut ViewTargetManager,
+        graph_manager: &mut RenderGraphManager,
+        view_name: &str,
+        color_format: TextureFormat,
+        depth_format: Option<TextureFormat>,
+    ) -> Result<ViewTarget> {
+        let view = self.render_view.lock().unwrap();
+        let Some(ref view) = *view else {
+            engine_bail!("galaxy3d::Minimap", "render view was taken");
+        };
+        targets.create_view_target(graph_manager, view_name, view, &ViewTargetDesc {
+            output_width: self.desc.width,
+            output_height: self.desc.height,
+            color_format,
+            depth_format,
+        })
+    }
+
+    /// Texture coordinates of a world position on the map, None when out of
+    /// range.
+    pub fn world_to_uv(&self, position: Vec3) -> Option<Vec2> {
+        let ndc = self.world_to_ndc(position);
+        (ndc.abs().max_element() <= 1.0).then(|| ndc_to_uv(ndc))
+    }
+
+    /// World position under texture coordinates of the map, at the height
+    /// of the followed point (click-to-move on the HUD).
+    pub fn uv_to_world(&self, uv: Vec2) -> Vec3 {
+        let view_projection = self.camera.view_projection_matrix();
+        let depth = view_projection.project_point3(self.center).z;
+        let ndc = Vec2::new(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0);
+        view_projection.inverse().project_point3(ndc.extend(depth))
+    }
+
+    // ===== MARKERS =====
+
+    /// Add a marker to the current frame. Markers stay until
+    /// `clear_markers()`: clear and resubmit them every frame.
+    pub fn push_marker(&mut self, marker: MinimapMarker) {
+        self.markers.push(marker);
+    }
+
+    pub fn clear_markers(&mut self) {
+        self.markers.clear();
+    }
+
+    /// Project the markers into the map and append their instance data
+    /// (`minimap_marker_layout()`) to `out`. Returns the number of markers
+    /// written.
+    pub(crate) fn write_markers(&self, out: &mut Vec<u8>) -> u32 {
+        let map_rotation = self.map_rotation();
+        let mut count = 0;
+        for marker in &self.markers {
+            let mut ndc = self.world_to_ndc(marker.position);
+            let reach = ndc.abs().max_element();
+            let pinned = reach > 1.0;
+            if pinned {
+                if !marker.pin_to_edge || !reach.is_finite() {
+                    continue;
+                }
+                ndc /= reach;
+            }
+            let floats = [
+                ndc.x, ndc.y, marker.size, marker.rotation - map_rotation,
+                marker.icon.uv_rect[0], marker.icon.uv_rect[1], marker.icon.uv_rect[2], marker.icon.uv_rect[3],
+                marker.color.x, marker.color.y, marker.color.z, marker.color.w,
+            ];
+            out.extend_from_slice(bytemuck::cast_slice(&floats));
+            let ids = [
+                marker.icon.bindless_index, marker.icon.sampler_index, marker.icon.layer, pinned as u32,
+            ];
+            out.extend_from_slice(bytemuck::cast_slice(&ids));
+            count += 1;
+        }
+        count
+    }
+
+    // ===== PRIVATE HELPERS =====
+
+    /// Angle the map is turned by
+    fn map_rotation(&self) -> f32 {
+        if self.desc.rotate_with_heading { self.heading } else { 0.0 }
+    }
+
+    fn world_to_ndc(&self, position: Vec3) -> Vec2 {
+        self.camera.view_projection_matrix().project_point3(position).truncate()
+    }
+
+    /// Top-down orthographic camera above `center`, the top of the image
+    /// facing the world forward axis turned by `rotation`
+    fn build_camera(desc: &MinimapDesc, center: Vec3, rotation: f32) -> Camera {
+        let world = Engine::world_coordinate_system();
+        let up = world.up_vector();
+        let forward = CoordinateSystem::Y_UP_RIGHT_HANDED.conversion_to(&world)
+            .transform_vector3(Vec3::NEG_Z);
+        let top = Quat::from_axis_angle(up, rotation) * forward;
+
+        let eye = center + up * desc.camera_height;
+        let half_height = desc.half_extent;
+        let half_width = desc.half_extent * desc.width as f32 / desc.height as f32;
+        let (view, projection) = match world.handedness {
+            Handedness::Right => (
+                Mat4::look_at_rh(eye, center, top),
+                Mat4::orthographic_rh(-half_width, half_width, -half_height, half_height, 0.0, desc.depth_range),
+            ),
+            Handedness::Left => (
+                Mat4::look_at_lh(eye, center, top),
+                Mat4::orthographic_lh(-half_width, half_width, -half_height, half_height, 0.0, desc.depth_range),
+            ),
+        };
+        let viewport = Viewport {
+            x: 0.0,
+            y: 0.0,
+            width: desc.width as f32,
+            height: desc.height as f32,
+            min_depth: 0.0,
+            max_depth: 1.0,
+        };
+        Camera::new(view, projection, Frustum::from_view_projection(&(projection * view)), viewport)
+    }
+}
+
+/// Texture coordinates (v down) of a point in NDC (y up)
+fn ndc_to_uv(ndc: Vec2) -> Vec2 {
+    Vec2::new((ndc.x + 1.0) * 0.5, (1.0 - ndc.y) * 0.5)
+}
+
+#[cfg(test)]
+#[path = "minimap_tests.rs"]
+mod tests;
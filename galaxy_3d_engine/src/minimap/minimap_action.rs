@@ -0,0 +1,126 @@
+//! Pass action drawing the markers of a `Minimap`.
+//!
+//! Shader interface of the marker pipeline:
+//! - vertex input: `minimap_marker_layout()`, per instance; no vertex
+//!   buffer, the shader builds the quad from `gl_VertexIndex` (0..4:
+//!   bottom-left, bottom-right, top-left, top-right), turning the corner
+//!   offsets `(±size / 2)` by the rotation in pixels before scaling them
+//!   to NDC
+//! - push constants: vertex stage, offset 0, `vec4 { 2 / width, 2 / height,
+//!   0, 0 }`, the pixel to NDC scale of the minimap target (16 bytes)
+//! - set 0: bindless textures, sampled with the instance's texture and
+//!   sampler indices, at the instance's layer and UV rectangle
+//! - topology: `PrimitiveTopology::TriangleStrip`
+//!
+//! Run the pass after the minimap scene pass, on the same color target
+//! (`LoadOp::Load`), without depth test: markers are always on top.
+//!
+//! The instances are uploaded every frame into a host-visible vertex
+//! buffer, one per frame in flight, grown to the next power of two like
+//! `BillboardAction`.
+
+use std::sync::{Arc, Mutex};
+use crate::error::Result;
+use crate::engine::Engine;
+use crate::graphics_device::{
+    self, BufferDesc, BufferUsage, CommandList, Rect2D, ShaderStageFlags, Viewport,
+};
+use crate::render_graph::PassAction;
+use crate::resource::resource_manager::PassInfo;
+use super::minimap::{Minimap, MINIMAP_MARKER_STRIDE, MINIMAP_MARKER_VERTEX_COUNT};
+
+/// Initial capacity of each instance buffer, in markers
+const INITIAL_INSTANCE_CAPACITY: u32 = 64;
+
+/// Pass action drawing the markers of a `Minimap`.
+pub struct MinimapAction {
+    minimap: Arc<Mutex<Minimap>>,
+    pipeline: Arc<dyn graphics_device::Pipeline>,
+    /// One instance buffer per frame in flight, with its capacity in markers
+    instance_buffers: Vec<Option<(Arc<dyn graphics_device::Buffer>, u32)>>,
+    /// Index of the buffer used by the next frame
+    frame: usize,
+    /// Packed instance data, reused across frames
+    instance_data: Vec<u8>,
+}
+
+impl MinimapAction {
+    /// Create the action for a render graph with `frames_in_flight` command
+    /// lists. `pipeline` must follow the marker shader interface (see module
+    /// docs). Instance buffers are created on first use.
+    pub fn new(
+        minimap: Arc<Mutex<Minimap>>,
+        pipeline: Arc<dyn graphics_device::Pipeline>,
+        frames_in_flight: usize,
+    ) -> Result<Self> {
+        if frames_in_flight == 0 {
+            crate::engine_bail!("galaxy3d::MinimapAction", "frames_in_flight must be at least 1");
+        }
+        Ok(Self {
+            minimap,
+            pipeline,
+            instance_buffers: (0..frames_in_flight).map(|_| None).collect(),
+            frame: 0,
+            instance_data: Vec::new(),
+        })
+    }
+
+    /// Return the instance buffer of the current frame, (re)created when
+    /// smaller than `instance_count`
+    fn frame_buffer(&mut self, instance_count: u32) -> Result<Arc<dyn graphics_device::Buffer>> {
+        let slot = &mut self.instance_buffers[self.frame];
+        if let Some((buffer, capacity)) = slot {
+            if *capacity >= instance_count {
+                return Ok(buffer.clone());
+            }
+        }
+        let capacity = instance_count.next_power_of_two().max(INITIAL_INSTANCE_CAPACITY);
+        let gd_arc = Engine::graphics_device("main")?;
+        let buffer = gd_arc.lock().unwrap().create_buffer(BufferDesc {
+            size: capacity as u64 * MINIMAP_MARKER_STRIDE as u64,
+            usage: BufferUsage::Vertex,
+            debug_name: Some("Minimap markers".to_string()),
+        })?;
+        *slot = Some((buffer.clone(), capacity));
+        Ok(buffer)
+    }
+}
+
+impl PassAction for MinimapAction {
+    fn execute(&mut self, cmd: &mut dyn CommandList, _pass_info: &PassInfo) -> Result<()> {
+        let (instance_count, width, height) = {
+            let minimap = self.minimap.clone();
+            let minimap = minimap.lock().unwrap();
+            self.instance_data.clear();
+            let count = minimap.write_markers(&mut self.instance_data);
+            (count, minimap.desc().width, minimap.desc().height)
+        };
+        if instance_count == 0 {
+            return Ok(());
+        }
+
+        let buffer = self.frame_buffer(instance_count)?;
+        self.frame = (self.frame + 1) % self.instance_buffers.len();
+        buffer.update(0, &self.instance_data)?;
+
+        let pixel_to_ndc = [2.0 / width as f32, 2.0 / height as f32, 0.0, 0.0];
+        cmd.set_viewport(Viewport {
+            x: 0.0,
+            y: 0.0,
+            width: width as f32,
+            height: height as f32,
+            min_depth: 0.0,
+            max_depth: 1.0,
+        })?;
+        cmd.set_scissor(Rect2D { x: 0, y: 0, width, height })?;
+        cmd.bind_pipeline(&self.pipeline)?;
+        cmd.bind_textures()?;
+        cmd.push_constants(ShaderStageFlags::VERTEX, 0, bytemuck::cast_slice(&pixel_to_ndc))?;
+        cmd.bind_vertex_buffer(&buffer, 0)?;
+        cmd.draw_instanced(MINIMAP_MARKER_VERTEX_COUNT, 0, instance_count, 0)
+    }
+}
+
+#[cfg(test)]
+#[path = "minimap_action_tests.rs"]
+mod tests;
@@ -0,0 +1,80 @@
+use super::*;
+use glam::{Vec3, Vec4};
+use serial_test::serial;
+use crate::graphics_device::{SampleCount, SamplerType, TextureFormat};
+use crate::graphics_device::mock_graphics_device::{MockCommandList, MockPipeline};
+use crate::minimap::{MinimapDesc, MinimapIcon, MinimapMarker};
+use crate::render_graph::test_helpers::setup_engine;
+use crate::resource::resource_manager::ResourceManager;
+use crate::scene::scene_test_helpers::create_atlas_texture;
+
+fn make_pass_info() -> PassInfo {
+    PassInfo::new(vec![TextureFormat::R8G8B8A8_UNORM], None, SampleCount::S1)
+}
+
+fn make_pipeline() -> Arc<dyn graphics_device::Pipeline> {
+    Arc::new(MockPipeline::new("minimap_marker_pipeline".to_string()))
+}
+
+fn make_minimap() -> Arc<Mutex<Minimap>> {
+    Arc::new(Mutex::new(Minimap::new(MinimapDesc {
+        width: 256,
+        height: 256,
+        half_extent: 50.0,
+        camera_height: 100.0,
+        depth_range: 200.0,
+        rotate_with_heading: false,
+        pass_type: 1,
+    }).unwrap()))
+}
+
+fn push_marker(minimap: &Mutex<Minimap>, rm: &mut ResourceManager, position: Vec3) {
+    let texture = rm.texture_key("atlas").unwrap_or_else(|| create_atlas_texture(rm));
+    let icon = MinimapIcon::new(texture, None, None, SamplerType::LinearClamp, rm).unwrap();
+    minimap.lock().unwrap().push_marker(MinimapMarker {
+        position,
+        icon,
+        color: Vec4::ONE,
+        size: 12.0,
+        rotation: 0.0,
+        pin_to_edge: false,
+    });
+}
+
+#[test]
+fn test_new_rejects_zero_frames_in_flight() {
+    assert!(MinimapAction::new(make_minimap(), make_pipeline(), 0).is_err());
+}
+
+#[test]
+#[serial]
+fn test_execute_without_markers_records_nothing() {
+    setup_engine();
+    let minimap = make_minimap();
+    let mut rm = ResourceManager::new();
+    push_marker(&minimap, &mut rm, Vec3::new(500.0, 0.0, 0.0));
+    let mut action = MinimapAction::new(minimap, make_pipeline(), 2).unwrap();
+
+    let mut cmd = MockCommandList::new();
+    action.execute(&mut cmd, &make_pass_info()).unwrap();
+    assert!(cmd.commands.is_empty());
+}
+
+#[test]
+#[serial]
+fn test_execute_draws_markers_instanced() {
+    setup_engine();
+    let minimap = make_minimap();
+    let mut rm = ResourceManager::new();
+    push_marker(&minimap, &mut rm, Vec3::ZERO);
+    push_marker(&minimap, &mut rm, Vec3::new(10.0, 0.0, 10.0));
+    let mut action = MinimapAction::new(minimap, make_pipeline(), 2).unwrap();
+
+    let mut cmd = MockCommandList::new();
+    action.execute(&mut cmd, &make_pass_info()).unwrap();
+    assert_eq!(cmd.commands, vec![
+        "set_viewport", "set_scissor", "bind_pipeline", "bind_textures",
+        "push_constants", "bind_vertex_buffer", "draw_instanced 2@0",
+    ]);
+    assert_eq!(action.instance_data.len(), 2 * MINIMAP_MARKER_STRIDE as usize);
+}
@@ -0,0 +1,186 @@
+use super::*;
+use serial_test::serial;
+use crate::scene::scene_test_helpers::create_atlas_texture;
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// 200x100 map, 10 units to the top edge (20 to the side edges)
+fn make_desc() -> MinimapDesc {
+    MinimapDesc {
+        width: 200,
+        height: 100,
+        half_extent: 10.0,
+        camera_height: 50.0,
+        depth_range: 100.0,
+        rotate_with_heading: false,
+        pass_type: 3,
+    }
+}
+
+fn make_icon(rm: &mut ResourceManager) -> MinimapIcon {
+    let texture = create_atlas_texture(rm);
+    MinimapIcon::new(
+        texture, Some(LayerRef::Index(0)), Some(RegionRef::Name("smoke".to_string())),
+        SamplerType::LinearClamp, rm,
+    ).unwrap()
+}
+
+fn make_marker(icon: MinimapIcon, position: Vec3, pin_to_edge: bool) -> MinimapMarker {
+    MinimapMarker {
+        position,
+        icon,
+        color: Vec4::ONE,
+        size: 16.0,
+        rotation: 0.0,
+        pin_to_edge,
+    }
+}
+
+/// (center NDC, rotation, pinned flag) of each packed marker
+fn packed_markers(minimap: &Minimap) -> Vec<(Vec2, f32, u32)> {
+    let mut data = Vec::new();
+    minimap.write_markers(&mut data);
+    data.chunks(MINIMAP_MARKER_STRIDE as usize)
+        .map(|instance| {
+            let floats: &[f32] = bytemuck::cast_slice(&instance[..16]);
+            let ids: &[u32] = bytemuck::cast_slice(&instance[48..]);
+            (Vec2::new(floats[0], floats[1]), floats[3], ids[3])
+        })
+        .collect()
+}
+
+fn assert_uv(actual: Option<Vec2>, expected: Vec2) {
+    let actual = actual.expect("position should be on the map");
+    assert!(actual.abs_diff_eq(expected, 1e-5), "{:?} != {:?}", actual, expected);
+}
+
+// ============================================================================
+// Tests: creation
+// ============================================================================
+
+#[test]
+fn test_new_rejects_invalid_desc() {
+    let desc = make_desc();
+    assert!(Minimap::new(MinimapDesc { width: 0, ..desc }).is_err());
+    assert!(Minimap::new(MinimapDesc { half_extent: 0.0, ..desc }).is_err());
+    assert!(Minimap::new(MinimapDesc { camera_height: -1.0, ..desc }).is_err());
+    assert!(Minimap::new(MinimapDesc { depth_range: 40.0, ..desc }).is_err());
+    assert!(Minimap::new(desc).is_ok());
+}
+
+#[test]
+#[serial]
+fn test_view_uses_pass_type_and_target_size() {
+    let minimap = Minimap::new(make_desc()).unwrap();
+    let view = minimap.render_view();
+    let view = view.lock().unwrap();
+    let view = view.as_ref().unwrap();
+
+    assert_eq!(view.pass_type(), 3);
+    assert_eq!(view.camera().viewport().width, 200.0);
+    assert_eq!(view.camera().viewport().height, 100.0);
+    assert!(Minimap::drawer_config().unlit);
+}
+
+// ============================================================================
+// Tests: projection
+// ============================================================================
+
+#[test]
+#[serial]
+fn test_follow_centers_map_north_up() {
+    let mut minimap = Minimap::new(make_desc()).unwrap();
+    let center = Vec3::new(100.0, 5.0, -40.0);
+    minimap.follow(center, 1.0);
+
+    assert_uv(minimap.world_to_uv(center), Vec2::new(0.5, 0.5));
+    // World forward (-Z) at the top, +X on the right, height ignored
+    assert_uv(minimap.world_to_uv(center + Vec3::new(0.0, -3.0, -10.0)), Vec2::new(0.5, 0.0));
+    assert_uv(minimap.world_to_uv(center + Vec3::new(20.0, 0.0, 0.0)), Vec2::new(1.0, 0.5));
+    assert!(minimap.world_to_uv(center + Vec3::new(0.0, 0.0, 11.0)).is_none());
+}
+
+#[test]
+#[serial]
+fn test_rotate_with_heading_turns_map() {
+    let mut minimap = Minimap::new(MinimapDesc { rotate_with_heading: true, ..make_desc() }).unwrap();
+    // Heading a quarter turn counterclockwise: facing -X
+    minimap.follow(Vec3::ZERO, std::f32::consts::FRAC_PI_2);
+
+    assert_uv(minimap.world_to_uv(Vec3::new(-10.0, 0.0, 0.0)), Vec2::new(0.5, 0.0));
+    assert_uv(minimap.world_to_uv(Vec3::new(0.0, 0.0, -20.0)), Vec2::new(1.0, 0.5));
+}
+
+#[test]
+#[serial]
+fn test_uv_to_world_inverts_world_to_uv() {
+    let mut minimap = Minimap::new(MinimapDesc { rotate_with_heading: true, ..make_desc() }).unwrap();
+    minimap.follow(Vec3::new(3.0, 2.0, 1.0), 0.7);
+    let position = Vec3::new(8.0, 2.0, -4.0);
+
+    let uv = minimap.world_to_uv(position).unwrap();
+    assert!(minimap.uv_to_world(uv).abs_diff_eq(position, 1e-3));
+}
+
+#[test]
+#[serial]
+fn test_set_half_extent_zooms() {
+    let mut minimap = Minimap::new(make_desc()).unwrap();
+    assert!(minimap.set_half_extent(-1.0).is_err());
+    minimap.set_half_extent(20.0).unwrap();
+    assert_uv(minimap.world_to_uv(Vec3::new(0.0, 0.0, -10.0)), Vec2::new(0.5, 0.25));
+}
+
+// ============================================================================
+// Tests: markers
+// ============================================================================
+
+#[test]
+fn test_icon_resolves_region() {
+    let mut rm = ResourceManager::new();
+    let icon = make_icon(&mut rm);
+    assert_eq!(icon.uv_rect(), [0.0, 0.0, 0.25, 0.25]);
+    assert_eq!(icon.layer(), 0);
+
+    let texture = rm.texture_key("atlas").unwrap();
+    assert!(MinimapIcon::new(
+        texture, None, Some(RegionRef::Index(0)), SamplerType::LinearClamp, &rm,
+    ).is_err());
+}
+
+#[test]
+#[serial]
+fn test_markers_out_of_range_dropped_or_pinned() {
+    let mut rm = ResourceManager::new();
+    let icon = make_icon(&mut rm);
+    let mut minimap = Minimap::new(make_desc()).unwrap();
+    minimap.push_marker(make_marker(icon, Vec3::new(10.0, 0.0, 0.0), false));
+    minimap.push_marker(make_marker(icon, Vec3::new(0.0, 0.0, -40.0), false));
+    minimap.push_marker(make_marker(icon, Vec3::new(0.0, 0.0, -40.0), true));
+
+    let packed = packed_markers(&minimap);
+    assert_eq!(packed.len(), 2);
+    assert!(packed[0].0.abs_diff_eq(Vec2::new(0.5, 0.0), 1e-5));
+    assert_eq!(packed[0].2, 0);
+    assert!(packed[1].0.abs_diff_eq(Vec2::new(0.0, 1.0), 1e-5));
+    assert_eq!(packed[1].2, 1);
+
+    minimap.clear_markers();
+    assert!(packed_markers(&minimap).is_empty());
+}
+
+#[test]
+#[serial]
+fn test_marker_rotation_relative_to_map() {
+    let mut rm = ResourceManager::new();
+    let icon = make_icon(&mut rm);
+    let mut minimap = Minimap::new(MinimapDesc { rotate_with_heading: true, ..make_desc() }).unwrap();
+    minimap.follow(Vec3::ZERO, 0.5);
+    minimap.push_marker(MinimapMarker { rotation: 0.5, ..make_marker(icon, Vec3::ZERO, false) });
+    minimap.push_marker(MinimapMarker { rotation: 2.0, ..make_marker(icon, Vec3::ZERO, false) });
+
+    let rotations: Vec<f32> = packed_markers(&minimap).iter().map(|m| m.1).collect();
+    assert_eq!(rotations, vec![0.0, 1.5]);
+}
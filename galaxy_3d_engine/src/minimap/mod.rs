@@ -0,0 +1,15 @@
+//! Minimap / overhead view rendering.
+//!
+//! `Minimap` follows a point with an orthographic top-down `RenderView` of
+//! its own pass type, drawn unlit into a render target the HUD samples.
+//! Icon markers submitted in world space are projected into the map and
+//! drawn on top by `MinimapAction`.
+
+mod minimap;
+mod minimap_action;
+
+pub use minimap::{
+    Minimap, MinimapDesc, MinimapIcon, MinimapMarker, minimap_marker_layout,
+    MINIMAP_MARKER_STRIDE, MINIMAP_MARKER_VERTEX_COUNT,
+};
+pub use minimap_action::MinimapAction;
@@ -0,0 +1,324 @@
+//! Performance scaling advisor for Galaxy3D Engine
+//!
+//! `PerformanceAdvisor` watches the frame time against a budget and
+//! suggests a lower `QualityPreset` when the budget has been exceeded for a
+//! while, or a higher one when there is headroom again. With `auto_apply`,
+//! the advisor switches its current preset itself; otherwise the
+//! application decides and calls `set_preset()`.
+//!
+//! Inputs, once per frame (`PerformanceSample`):
+//! - CPU frame time (e.g. `FrameProfile::duration`)
+//! - GPU frame time, when the application measures it (timestamp queries
+//!   read back with `ReadbackManager`); the engine does not record GPU
+//!   timestamps itself
+//! - `GraphicsDeviceStats`: draw calls and triangles (reported with the
+//!   advice) and GPU memory (checked against an optional memory budget)
+//!
+//! Hysteresis:
+//! - the frame time is smoothed (exponential moving average over a quarter
+//!   of a second)
+//! - a downgrade needs the budget to be exceeded for `downgrade_after`
+//!   seconds without interruption
+//! - an upgrade needs the frame time to stay under `upgrade_headroom` ×
+//!   budget for the longer `upgrade_after` window
+//! - no advice is given during `cooldown` seconds after the previous one
+//!
+//! Notifications: every advice is returned by `update()`, logged, and
+//! passed to the listener set with `set_listener()`.
+
+use crate::graphics_device::GraphicsDeviceStats;
+
+/// Frame time budget of a 60 Hz target, in milliseconds
+pub const DEFAULT_FRAME_BUDGET_MS: f32 = 1000.0 / 60.0;
+
+/// Seconds over budget before a downgrade is advised
+pub const DEFAULT_DOWNGRADE_AFTER: f32 = 2.0;
+
+/// Seconds with headroom before an upgrade is advised
+pub const DEFAULT_UPGRADE_AFTER: f32 = 10.0;
+
+/// Fraction of the budget the frame time must stay under to upgrade
+pub const DEFAULT_UPGRADE_HEADROOM: f32 = 0.7;
+
+/// Seconds without advice after an advice
+pub const DEFAULT_ADVICE_COOLDOWN: f32 = 3.0;
+
+/// Time constant of the frame time smoothing, in seconds
+const FRAME_TIME_SMOOTHING: f32 = 0.25;
+
+/// Callback receiving each advice
+pub type PerformanceListener = Box<dyn FnMut(&QualityAdvice) + Send>;
+
+// ===== QUALITY PRESET =====
+
+/// Quality level the application maps to its own settings (resolution
+/// scale, shadow resolution, LOD bias, post effects, ...).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum QualityPreset {
+    Low,
+    Medium,
+    High,
+    Ultra,
+}
+
+impl QualityPreset {
+    /// Next lower preset, None at `Low`
+    pub fn lower(self) -> Option<Self> {
+        match self {
+            Self::Low => None,
+            Self::Medium => Some(Self::Low),
+            Self::High => Some(Self::Medium),
+            Self::Ultra => Some(Self::High),
+        }
+    }
+
+    /// Next higher preset, None at `Ultra`
+    pub fn higher(self) -> Option<Self> {
+        match self {
+            Self::Low => Some(Self::Medium),
+            Self::Medium => Some(Self::High),
+            Self::High => Some(Self::Ultra),
+            Self::Ultra => None,
+        }
+    }
+}
+
+// ===== CONFIG / SAMPLES =====
+
+/// Budgets and hysteresis windows of a `PerformanceAdvisor`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PerformanceAdvisorConfig {
+    /// Frame time budget, in milliseconds
+    pub frame_budget_ms: f32,
+    /// GPU memory budget in bytes (None = memory not checked)
+    pub memory_budget: Option<u64>,
+    /// Seconds over budget before a downgrade is advised
+    pub downgrade_after: f32,
+    /// Seconds with headroom before an upgrade is advised
+    pub upgrade_after: f32,
+    /// Fraction of the budgets the frame time and memory must stay under
+    /// to upgrade
+    pub upgrade_headroom: f32,
+    /// Seconds without advice after an advice
+    pub cooldown: f32,
+    /// Switch the current preset automatically (off: suggestions only)
+    pub auto_apply: bool,
+}
+
+impl Default for PerformanceAdvisorConfig {
+    fn default() -> Self {
+        Self {
+            frame_budget_ms: DEFAULT_FRAME_BUDGET_MS,
+            memory_budget: None,
+            downgrade_after: DEFAULT_DOWNGRADE_AFTER,
+            upgrade_after: DEFAULT_UPGRADE_AFTER,
+            upgrade_headroom: DEFAULT_UPGRADE_HEADROOM,
+            cooldown: DEFAULT_ADVICE_COOLDOWN,
+            auto_apply: false,
+        }
+    }
+}
+
+/// Measurements of one frame
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PerformanceSample {
+    /// CPU frame time, in milliseconds
+    pub cpu_frame_ms: f32,
+    /// GPU frame time in milliseconds, when measured
+    pub gpu_frame_ms: Option<f32>,
+    /// Device counters of the frame (`GraphicsDevice::stats()`)
+    pub stats: GraphicsDeviceStats,
+}
+
+impl PerformanceSample {
+    /// Frame time: the slower of the CPU and the GPU
+    pub fn frame_ms(&self) -> f32 {
+        self.cpu_frame_ms.max(self.gpu_frame_ms.unwrap_or(0.0))
+    }
+}
+
+// ===== ADVICE =====
+
+/// Why an advice was given
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdviceReason {
+    /// Over the frame budget, the GPU being the slower side
+    GpuBound,
+    /// Over the frame budget, the CPU being the slower side (see the draw
+    /// call count)
+    CpuBound,
+    /// Over the GPU memory budget
+    MemoryBound,
+    /// Frame time and memory under the upgrade headroom
+    Headroom,
+}
+
+/// Preset change advised by a `PerformanceAdvisor`
+#[derive(Debug, Clone, Copy)]
+pub struct QualityAdvice {
+    pub from: QualityPreset,
+    pub to: QualityPreset,
+    pub reason: AdviceReason,
+    /// Smoothed frame time when the advice was given, in milliseconds
+    pub frame_ms: f32,
+    /// Device counters of the last frame
+    pub stats: GraphicsDeviceStats,
+    /// Whether the advisor switched to `to` itself (`auto_apply`)
+    pub applied: bool,
+}
+
+// ===== ADVISOR =====
+
+/// Suggests quality preset changes from per-frame measurements (see
+/// module docs).
+pub struct PerformanceAdvisor {
+    config: PerformanceAdvisorConfig,
+    preset: QualityPreset,
+    /// Smoothed frame time (None before the first sample)
+    frame_ms: Option<f32>,
+    /// Uninterrupted time over budget, in seconds
+    over_budget: f32,
+    /// Uninterrupted time under the upgrade headroom, in seconds
+    headroom: f32,
+    /// Remaining time without advice, in seconds
+    cooldown: f32,
+    listener: Option<PerformanceListener>,
+}
+
+impl PerformanceAdvisor {
+    pub fn new(config: PerformanceAdvisorConfig, preset: QualityPreset) -> Self {
+        Self {
+            config,
+            preset,
+            frame_ms: None,
+            over_budget: 0.0,
+            headroom: 0.0,
+            cooldown: 0.0,
+            listener: None,
+        }
+    }
+
+    // ===== ACCESSORS =====
+
+    pub fn config(&self) -> &PerformanceAdvisorConfig {
+        &self.config
+    }
+
+    pub fn set_config(&mut self, config: PerformanceAdvisorConfig) {
+        self.config = config;
+    }
+
+    /// Preset the advisor reasons from
+    pub fn preset(&self) -> QualityPreset {
+        self.preset
+    }
+
+    /// Set the preset in use (e.g. after accepting a suggestion). Restarts
+    /// the hysteresis windows and the cooldown.
+    pub fn set_preset(&mut self, preset: QualityPreset) {
+        self.preset = preset;
+        self.restart();
+    }
+
+    /// Smoothed frame time, in milliseconds
+    pub fn frame_ms(&self) -> Option<f32> {
+        self.frame_ms
+    }
+
+    /// Set the callback receiving each advice (None to remove it)
+    pub fn set_listener(&mut self, listener: Option<PerformanceListener>) {
+        self.listener = listener;
+    }
+
+    // ===== PER FRAME =====
+
+    /// Feed the measurements of a frame lasting `dt` seconds. Returns the
+    /// advice given this frame, if any.
+    pub fn update(&mut self, sample: &PerformanceSample, dt: f32) -> Option<QualityAdvice> {
+        let frame_ms = match self.frame_ms {
+            Some(smoothed) => {
+                let weight = 1.0 - (-dt.max(0.0) / FRAME_TIME_SMOOTHING).exp();
+                smoothed + (sample.frame_ms() - smoothed) * weight
+            }
+            None => sample.frame_ms(),
+        };
+        self.frame_ms = Some(frame_ms);
+        self.cooldown = (self.cooldown - dt).max(0.0);
+
+        let memory = sample.stats.gpu_memory_used;
+        let over_memory = self.config.memory_budget.is_some_and(|budget| memory > budget);
+        let over_time = frame_ms > self.config.frame_budget_ms;
+        let has_headroom = frame_ms < self.config.frame_budget_ms * self.config.upgrade_headroom
+            && self.config.memory_budget
+                .is_none_or(|budget| (memory as f64) < budget as f64 * self.config.upgrade_headroom as f64);
+
+        if over_time || over_memory {
+            self.over_budget += dt;
+            self.headroom = 0.0;
+        } else if has_headroom {
+            self.headroom += dt;
+            self.over_budget = 0.0;
+        } else {
+            self.over_budget = 0.0;
+            self.headroom = 0.0;
+        }
+        if self.cooldown > 0.0 {
+            return None;
+        }
+
+        let (to, reason) = if self.over_budget >= self.config.downgrade_after {
+            let reason = if over_memory {
+                AdviceReason::MemoryBound
+            } else if sample.gpu_frame_ms.is_some_and(|gpu| gpu > sample.cpu_frame_ms) {
+                AdviceReason::GpuBound
+            } else {
+                AdviceReason::CpuBound
+            };
+            (self.preset.lower()?, reason)
+        } else if self.headroom >= self.config.upgrade_after {
+            (self.preset.higher()?, AdviceReason::Headroom)
+        } else {
+            return None;
+        };
+
+        let advice = QualityAdvice {
+            from: self.preset,
+            to,
+            reason,
+            frame_ms,
+            stats: sample.stats,
+            applied: self.config.auto_apply,
+        };
+        if advice.applied {
+            self.preset = to;
+        }
+        // Suggestions are repeated at most once per window
+        self.restart();
+        self.notify(&advice);
+        Some(advice)
+    }
+
+    // ===== PRIVATE HELPERS =====
+
+    /// Restart the hysteresis windows and the cooldown
+    fn restart(&mut self) {
+        self.over_budget = 0.0;
+        self.headroom = 0.0;
+        self.cooldown = self.config.cooldown;
+    }
+
+    fn notify(&mut self, advice: &QualityAdvice) {
+        let verb = if advice.applied { "Switched" } else { "Suggest switching" };
+        crate::engine_info!("galaxy3d::PerformanceAdvisor",
+            "{} quality preset {:?} -> {:?} ({:?}: {:.2} ms for a {:.2} ms budget, {} draw calls, {} triangles, {} bytes of GPU memory)",
+            verb, advice.from, advice.to, advice.reason, advice.frame_ms, self.config.frame_budget_ms,
+            advice.stats.draw_calls, advice.stats.triangles, advice.stats.gpu_memory_used);
+        if let Some(listener) = self.listener.as_mut() {
+            listener(advice);
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "perf_advisor_tests.rs"]
+mod tests;
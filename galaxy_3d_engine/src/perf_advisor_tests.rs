@@ -0,0 +1,147 @@
+use super::*;
+use std::sync::{Arc, Mutex};
+
+/// Frame duration fed to the advisor, in seconds
+const DT: f32 = 0.1;
+
+fn sample(cpu_frame_ms: f32, gpu_frame_ms: Option<f32>) -> PerformanceSample {
+    PerformanceSample { cpu_frame_ms, gpu_frame_ms, stats: GraphicsDeviceStats::default() }
+}
+
+/// Feed `seconds` worth of identical frames, returning every advice
+fn run(advisor: &mut PerformanceAdvisor, sample: &PerformanceSample, seconds: f32) -> Vec<QualityAdvice> {
+    let frames = (seconds / DT).round() as usize;
+    (0..frames).filter_map(|_| advisor.update(sample, DT)).collect()
+}
+
+// ============================================================================
+// QualityPreset
+// ============================================================================
+
+#[test]
+fn test_quality_preset_steps() {
+    assert_eq!(QualityPreset::Low.lower(), None);
+    assert_eq!(QualityPreset::High.lower(), Some(QualityPreset::Medium));
+    assert_eq!(QualityPreset::Medium.higher(), Some(QualityPreset::High));
+    assert_eq!(QualityPreset::Ultra.higher(), None);
+    assert!(QualityPreset::Low < QualityPreset::Ultra);
+}
+
+#[test]
+fn test_sample_frame_time_is_slower_side() {
+    assert_eq!(sample(10.0, None).frame_ms(), 10.0);
+    assert_eq!(sample(10.0, Some(14.0)).frame_ms(), 14.0);
+    assert_eq!(sample(12.0, Some(8.0)).frame_ms(), 12.0);
+}
+
+// ============================================================================
+// Downgrade
+// ============================================================================
+
+#[test]
+fn test_downgrade_after_sustained_overrun() {
+    let mut advisor = PerformanceAdvisor::new(PerformanceAdvisorConfig::default(), QualityPreset::High);
+    let slow = sample(10.0, Some(25.0));
+
+    // Just under the window: nothing yet
+    assert!(run(&mut advisor, &slow, DEFAULT_DOWNGRADE_AFTER - 2.0 * DT).is_empty());
+    let advice = run(&mut advisor, &slow, 2.0 * DT);
+    assert_eq!(advice.len(), 1);
+    assert_eq!(advice[0].from, QualityPreset::High);
+    assert_eq!(advice[0].to, QualityPreset::Medium);
+    assert_eq!(advice[0].reason, AdviceReason::GpuBound);
+    // Suggestion only: the preset is left to the application
+    assert!(!advice[0].applied);
+    assert_eq!(advisor.preset(), QualityPreset::High);
+}
+
+#[test]
+fn test_short_spike_does_not_downgrade() {
+    let mut advisor = PerformanceAdvisor::new(PerformanceAdvisorConfig::default(), QualityPreset::High);
+    let slow = sample(25.0, None);
+    let fast = sample(14.0, None);
+    for _ in 0..5 {
+        assert!(run(&mut advisor, &slow, 1.0).is_empty());
+        // Smoothed frame time falls back under budget: the window restarts
+        assert!(run(&mut advisor, &fast, 1.0).is_empty());
+    }
+}
+
+#[test]
+fn test_auto_apply_switches_preset_and_cools_down() {
+    let config = PerformanceAdvisorConfig { auto_apply: true, ..Default::default() };
+    let mut advisor = PerformanceAdvisor::new(config, QualityPreset::Ultra);
+    let slow = sample(30.0, Some(20.0));
+
+    let advice = run(&mut advisor, &slow, DEFAULT_DOWNGRADE_AFTER);
+    assert_eq!(advice.len(), 1);
+    assert!(advice[0].applied);
+    assert_eq!(advice[0].reason, AdviceReason::CpuBound);
+    assert_eq!(advisor.preset(), QualityPreset::High);
+
+    // Still over budget: next step only after cooldown + window
+    assert!(run(&mut advisor, &slow, DEFAULT_ADVICE_COOLDOWN).is_empty());
+    let advice = run(&mut advisor, &slow, DEFAULT_DOWNGRADE_AFTER);
+    assert_eq!(advice.len(), 1);
+    assert_eq!(advisor.preset(), QualityPreset::Medium);
+
+    // Nothing below Low
+    advisor.set_preset(QualityPreset::Low);
+    assert!(run(&mut advisor, &slow, 20.0).is_empty());
+}
+
+#[test]
+fn test_memory_budget_downgrades() {
+    let config = PerformanceAdvisorConfig { memory_budget: Some(1000), ..Default::default() };
+    let mut advisor = PerformanceAdvisor::new(config, QualityPreset::High);
+    let mut heavy = sample(5.0, Some(5.0));
+    heavy.stats.gpu_memory_used = 1500;
+    let advice = run(&mut advisor, &heavy, DEFAULT_DOWNGRADE_AFTER);
+    assert_eq!(advice.len(), 1);
+    assert_eq!(advice[0].reason, AdviceReason::MemoryBound);
+    assert_eq!(advice[0].stats.gpu_memory_used, 1500);
+}
+
+// ============================================================================
+// Upgrade
+// ============================================================================
+
+#[test]
+fn test_upgrade_needs_headroom_for_longer_window() {
+    let mut advisor = PerformanceAdvisor::new(PerformanceAdvisorConfig::default(), QualityPreset::Medium);
+    // Under budget but above the headroom: stays put
+    assert!(run(&mut advisor, &sample(14.0, None), 30.0).is_empty());
+
+    let fast = sample(5.0, None);
+    assert!(run(&mut advisor, &fast, DEFAULT_DOWNGRADE_AFTER).is_empty());
+    let advice = run(&mut advisor, &fast, DEFAULT_UPGRADE_AFTER);
+    assert_eq!(advice.len(), 1);
+    assert_eq!(advice[0].to, QualityPreset::High);
+    assert_eq!(advice[0].reason, AdviceReason::Headroom);
+}
+
+#[test]
+fn test_memory_near_budget_blocks_upgrade() {
+    let config = PerformanceAdvisorConfig { memory_budget: Some(1000), ..Default::default() };
+    let mut advisor = PerformanceAdvisor::new(config, QualityPreset::Medium);
+    let mut fast = sample(5.0, None);
+    fast.stats.gpu_memory_used = 900;
+    assert!(run(&mut advisor, &fast, 30.0).is_empty());
+}
+
+// ============================================================================
+// Notifications
+// ============================================================================
+
+#[test]
+fn test_listener_receives_advice() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let sink = received.clone();
+    let mut advisor = PerformanceAdvisor::new(PerformanceAdvisorConfig::default(), QualityPreset::High);
+    advisor.set_listener(Some(Box::new(move |advice: &QualityAdvice| {
+        sink.lock().unwrap().push((advice.from, advice.to));
+    })));
+
+    run(&mut advisor, &sample(40.0, None), DEFAULT_DOWNGRADE_AFTER);
+    assert_eq!(*received.lock().unwrap(), vec![(QualityPreset::High, QualityPreset::Medium)]);
+}
@@ -0,0 +1,10 @@
+//! Planar reflections (water, mirrors).
+//!
+//! `PlanarReflection` mirrors a camera about a world plane and clips the
+//! geometry behind it with an oblique near plane. The mirrored view is
+//! drawn by a regular `ScenePassAction` into a view target that water and
+//! mirror materials sample.
+
+mod planar_reflection;
+
+pub use planar_reflection::{PlanarReflection, PlanarReflectionDesc, reflection_uv};
@@ -0,0 +1,251 @@
+//! Mirrored view of a scene about a world plane.
+//!
+//! `PlanarReflection` follows the main camera: every frame, `update()`
+//! builds the camera seeing the reflection (`view * reflection_matrix`),
+//! with an oblique near plane on the mirror so the geometry behind it is
+//! clipped by the depth range instead of a clip distance. The view has its
+//! own pass type, so only the materials with a pass of that type are
+//! reflected (skip the water itself, particles, ...).
+//!
+//! The mirror flips the triangle winding. To keep the materials' cull
+//! mode, the reflection projection also flips X: the image is stored
+//! mirrored horizontally, and water shaders sample it at
+//! `reflection_uv(screen_uv)` = `(1 - u, v)` of their own screen position
+//! (plus any ripple distortion).
+//!
+//! The reflection pass is optional: add its key to the executed passes
+//! only while `is_active()` (the main camera is in front of the mirror).
+
+use glam::{Mat4, Vec2, Vec3, Vec4};
+use std::sync::{Arc, Mutex};
+use crate::error::Result;
+use crate::engine_bail;
+use crate::camera::{reflection_matrix, Camera, Frustum, VisibleInstances};
+use crate::graphics_device::{self, TextureFormat, Viewport};
+use crate::post::{ViewTarget, ViewTargetDesc, ViewTargetManager};
+use crate::render_graph::{AccessType, RenderGraphManager, ResourceAccess, TargetOps};
+use crate::resource::resource_manager::ResourceManager;
+use crate::scene::{RenderView, Scene, ViewDispatcher};
+
+// ===== PLANAR REFLECTION DESC =====
+
+/// Descriptor for creating a PlanarReflection.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlanarReflectionDesc {
+    /// Size of the reflection texture, in pixels (often half the screen)
+    pub width: u32,
+    pub height: u32,
+    /// Pass type of the reflection view
+    pub pass_type: u8,
+    /// Distance the clip plane is lowered below the mirror, so the
+    /// reflection still covers the surface where ripples push it down
+    pub clip_offset: f32,
+}
+
+// ===== PLANAR REFLECTION =====
+
+/// Camera and view rendering the reflection of a scene in a plane.
+pub struct PlanarReflection {
+    desc: PlanarReflectionDesc,
+    /// World plane `(normal, d)`, unit normal toward the reflected side
+    plane: Vec4,
+    camera: Camera,
+    render_view: Arc<Mutex<Option<RenderView>>>,
+    active: bool,
+}
+
+impl PlanarReflection {
+    /// Create a reflection in the world plane `(normal, d)` (points `p`
+    /// with `dot(normal, p) + d > 0` are reflected). The camera stays
+    /// inactive until the first `update()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the size is zero, the plane normal is zero or
+    /// not finite, or the clip offset is negative.
+    pub fn new(plane: Vec4, desc: PlanarReflectionDesc) -> Result<Self> {
+        if desc.width == 0 || desc.height == 0 {
+            engine_bail!("galaxy3d::PlanarReflection",
+                "size must be non-zero, got {}x{}", desc.width, desc.height);
+        }
+        if !(desc.clip_offset >= 0.0 && desc.clip_offset.is_finite()) {
+            engine_bail!("galaxy3d::PlanarReflection",
+                "clip_offset must be non-negative, got {}", desc.clip_offset);
+        }
+        let plane = Self::normalize_plane(plane)?;
+        let camera = Camera::new(
+            Mat4::IDENTITY,
+            Mat4::IDENTITY,
+            Frustum::from_view_projection(&Mat4::IDENTITY),
+            Self::viewport(&desc),
+        );
+        let render_view = RenderView::new(camera.clone(), desc.pass_type);
+        Ok(Self {
+            desc,
+            plane,
+            camera,
+            render_view: Arc::new(Mutex::new(Some(render_view))),
+            active: false,
+        })
+    }
+
+    // ===== ACCESSORS =====
+
+    pub fn desc(&self) -> &PlanarReflectionDesc {
+        &self.desc
+    }
+
+    /// Mirror plane `(normal, d)`, unit normal
+    pub fn plane(&self) -> Vec4 {
+        self.plane
+    }
+
+    /// Move the mirror (the water level changed). Takes effect on the next
+    /// `update()`.
+    pub fn set_plane(&mut self, plane: Vec4) -> Result<()> {
+        self.plane = Self::normalize_plane(plane)?;
+        Ok(())
+    }
+
+    /// Camera to cull the scene with
+    pub fn camera(&self) -> &Camera {
+        &self.camera
+    }
+
+    /// View drawn by the reflection pass (`ScenePassAction`)
+    pub fn render_view(&self) -> Arc<Mutex<Option<RenderView>>> {
+        self.render_view.clone()
+    }
+
+    /// The main camera was in front of the mirror at the last `update()`:
+    /// the reflection pass needs to run this frame
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    // ===== VIEW =====
+
+    /// Mirror `main_camera` about the plane. Returns `is_active()`: behind
+    /// the mirror (under water) nothing is reflected and the camera is left
+    /// unchanged.
+    pub fn update(&mut self, main_camera: &Camera) -> bool {
+        let eye = main_camera.view_matrix().inverse().w_axis.truncate();
+        self.active = self.plane.truncate().dot(eye) + self.plane.w > 0.0;
+        if !self.active {
+            return false;
+        }
+
+        let view = *main_camera.view_matrix() * reflection_matrix(self.plane);
+        let mut camera = Camera::new(
+            view,
+            *main_camera.projection_matrix(),
+            Frustum::from_view_projection(&Mat4::IDENTITY),
+            Self::viewport(&self.desc),
+        );
+        let clip_plane = self.plane + Vec4::new(0.0, 0.0, 0.0, self.desc.clip_offset);
+        let projection = Mat4::from_scale(Vec3::new(-1.0, 1.0, 1.0))
+            * camera.oblique_projection_matrix(clip_plane);
+        camera.set_projection(projection);
+        camera.set_frustum(Frustum::from_view_projection(&(projection * view)));
+        self.camera = camera;
+
+        if let Some(view) = self.render_view.lock().unwrap().as_mut() {
+            view.set_camera(self.camera.clone());
+        }
+        true
+    }
+
+    /// Fill the reflection view from the instances culled with `camera()`.
+    pub fn dispatch(&self, visible: &VisibleInstances, scene: &mut Scene, rm: &ResourceManager) {
+        let mut view = self.render_view.lock().unwrap();
+        if let Some(view) = view.as_mut() {
+            ViewDispatcher::dispatch(visible, scene, rm, std::slice::from_mut(view));
+        }
+    }
+
+    /// Allocate the color (and depth) target of the reflection, named after
+    /// `view_name` in `targets`. Bind `ViewTarget::color_texture` in the
+    /// water materials and declare the read with
+    /// `ViewTargetManager::bind_sampled_targets`.
+    pub fn create_target(
+        &self,
+        targets: &mut ViewTargetManager,
+        graph_manager: &mut RenderGraphManager,
+        view_name: &str,
+        color_format: TextureFormat,
+        depth_format: Option<TextureFormat>,
+    ) -> Result<ViewTarget> {
+        let view = self.render_view.lock().unwrap();
+        let Some(ref view) = *view else {
+            engine_bail!("galaxy3d::PlanarReflection", "render view was taken");
+        };
+        targets.create_view_target(graph_manager, view_name, view, &ViewTargetDesc {
+            output_width: self.desc.width,
+            output_height: self.desc.height,
+            color_format,
+            depth_format,
+        })
+    }
+
+    /// Accesses of the reflection pass: clear and write the color (to
+    /// `clear_color`) and depth of `target`
+    pub fn pass_accesses(&self, target: &ViewTarget, clear_color: [f32; 4]) -> Vec<ResourceAccess> {
+        let mut accesses = vec![ResourceAccess {
+            graph_resource_key: target.color,
+            access_type: AccessType::ColorAttachmentWrite,
+            target_ops: Some(TargetOps::Color {
+                clear_color,
+                load_op: graphics_device::LoadOp::Clear,
+                store_op: graphics_device::StoreOp::Store,
+                resolve_target: None,
+            }),
+        }];
+        if let Some(depth) = target.depth {
+            accesses.push(ResourceAccess {
+                graph_resource_key: depth,
+                access_type: AccessType::DepthStencilWrite,
+                target_ops: Some(TargetOps::DepthStencil {
+                    depth_clear: 1.0,
+                    stencil_clear: 0,
+                    depth_load_op: graphics_device::LoadOp::Clear,
+                    depth_store_op: graphics_device::StoreOp::DontCare,
+                    stencil_load_op: graphics_device::LoadOp::DontCare,
+                    stencil_store_op: graphics_device::StoreOp::DontCare,
+                }),
+            });
+        }
+        accesses
+    }
+
+    // ===== PRIVATE HELPERS =====
+
+    fn normalize_plane(plane: Vec4) -> Result<Vec4> {
+        let length = plane.truncate().length();
+        if !(length > 0.0 && length.is_finite() && plane.w.is_finite()) {
+            engine_bail!("galaxy3d::PlanarReflection",
+                "plane normal must be non-zero and finite, got {:?}", plane);
+        }
+        Ok(plane / length)
+    }
+
+    fn viewport(desc: &PlanarReflectionDesc) -> Viewport {
+        Viewport {
+            x: 0.0,
+            y: 0.0,
+            width: desc.width as f32,
+            height: desc.height as f32,
+            min_depth: 0.0,
+            max_depth: 1.0,
+        }
+    }
+}
+
+/// Texture coordinates of the reflection seen at `screen_uv` of the main
+/// view (the reflection is stored mirrored horizontally)
+pub fn reflection_uv(screen_uv: Vec2) -> Vec2 {
+    Vec2::new(1.0 - screen_uv.x, screen_uv.y)
+}
+
+#[cfg(test)]
+#[path = "planar_reflection_tests.rs"]
+mod tests;
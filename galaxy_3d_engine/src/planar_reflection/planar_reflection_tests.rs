@@ -0,0 +1,147 @@
+use super::*;
+use serial_test::serial;
+use crate::render_graph::test_helpers::{setup_engine_for_render_graph, make_recording_pass};
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn make_desc() -> PlanarReflectionDesc {
+    PlanarReflectionDesc { width: 320, height: 180, pass_type: 2, clip_offset: 0.0 }
+}
+
+/// Water plane y = 0, reflecting what is above it
+fn water() -> Vec4 {
+    Vec4::new(0.0, 1.0, 0.0, 0.0)
+}
+
+/// Perspective camera at `eye` looking at the origin
+fn main_camera(eye: Vec3) -> Camera {
+    let view = Mat4::look_at_rh(eye, Vec3::ZERO, Vec3::Y);
+    let projection = Mat4::perspective_rh(std::f32::consts::FRAC_PI_3, 16.0 / 9.0, 0.1, 100.0);
+    let viewport = Viewport { x: 0.0, y: 0.0, width: 1280.0, height: 720.0, min_depth: 0.0, max_depth: 1.0 };
+    Camera::new(view, projection, Frustum::from_view_projection(&(projection * view)), viewport)
+}
+
+/// NDC position of a world point
+fn project(camera: &Camera, point: Vec3) -> Vec3 {
+    camera.view_projection_matrix().project_point3(point)
+}
+
+fn ndc_to_uv(ndc: Vec3) -> Vec2 {
+    Vec2::new((ndc.x + 1.0) * 0.5, (1.0 - ndc.y) * 0.5)
+}
+
+// ============================================================================
+// Tests: creation
+// ============================================================================
+
+#[test]
+fn test_new_validates_desc_and_plane() {
+    assert!(PlanarReflection::new(water(), PlanarReflectionDesc { width: 0, ..make_desc() }).is_err());
+    assert!(PlanarReflection::new(water(), PlanarReflectionDesc { clip_offset: -1.0, ..make_desc() }).is_err());
+    assert!(PlanarReflection::new(Vec4::new(0.0, 0.0, 0.0, 1.0), make_desc()).is_err());
+    assert!(PlanarReflection::new(Vec4::new(0.0, f32::NAN, 0.0, 1.0), make_desc()).is_err());
+
+    let reflection = PlanarReflection::new(Vec4::new(0.0, 2.0, 0.0, -4.0), make_desc()).unwrap();
+    assert_eq!(reflection.plane(), Vec4::new(0.0, 1.0, 0.0, -2.0));
+    assert!(!reflection.is_active());
+    let view = reflection.render_view();
+    assert_eq!(view.lock().unwrap().as_ref().unwrap().pass_type(), 2);
+}
+
+// ============================================================================
+// Tests: mirrored camera
+// ============================================================================
+
+#[test]
+fn test_update_mirrors_the_camera_below_the_plane() {
+    let mut reflection = PlanarReflection::new(water(), make_desc()).unwrap();
+    assert!(reflection.update(&main_camera(Vec3::new(0.0, 5.0, 10.0))));
+    assert!(reflection.is_active());
+
+    let camera = reflection.camera();
+    let eye = camera.view_matrix().inverse().w_axis.truncate();
+    assert!(eye.abs_diff_eq(Vec3::new(0.0, -5.0, 10.0), 1e-4));
+    assert_eq!(camera.viewport().width, 320.0);
+
+    let view = reflection.render_view();
+    let view = view.lock().unwrap();
+    assert_eq!(*view.as_ref().unwrap().camera().view_matrix(), *camera.view_matrix());
+}
+
+#[test]
+fn test_reflection_lands_at_mirrored_screen_uv() {
+    let main = main_camera(Vec3::new(0.0, 5.0, 10.0));
+    let mut reflection = PlanarReflection::new(water(), make_desc()).unwrap();
+    reflection.update(&main);
+
+    // The mirror image of a point is seen by the main camera where its
+    // reflection must be sampled
+    for point in [Vec3::new(1.0, 2.0, -3.0), Vec3::new(-2.5, 0.5, 1.0)] {
+        let mirrored = Vec3::new(point.x, -point.y, point.z);
+        let screen_uv = ndc_to_uv(project(&main, mirrored));
+        let stored_uv = ndc_to_uv(project(reflection.camera(), point));
+        assert!(stored_uv.abs_diff_eq(reflection_uv(screen_uv), 1e-4), "{:?}", point);
+    }
+}
+
+#[test]
+fn test_geometry_below_the_plane_is_clipped() {
+    let mut reflection = PlanarReflection::new(water(), make_desc()).unwrap();
+    reflection.update(&main_camera(Vec3::new(0.0, 5.0, 10.0)));
+    let depth = |y: f32| project(reflection.camera(), Vec3::new(0.0, y, 0.0)).z;
+    assert!(depth(1.0) > 0.0 && depth(1.0) < 1.0);
+    assert!(depth(0.0).abs() < 1e-4);
+    assert!(depth(-0.25) < 0.0);
+
+    // The clip offset keeps a band below the surface
+    let mut offset = PlanarReflection::new(water(), PlanarReflectionDesc { clip_offset: 0.5, ..make_desc() }).unwrap();
+    offset.update(&main_camera(Vec3::new(0.0, 5.0, 10.0)));
+    assert!(project(offset.camera(), Vec3::new(0.0, -0.25, 0.0)).z > 0.0);
+}
+
+#[test]
+fn test_camera_behind_the_plane_is_inactive() {
+    let mut reflection = PlanarReflection::new(water(), make_desc()).unwrap();
+    reflection.update(&main_camera(Vec3::new(0.0, 5.0, 10.0)));
+    let above = *reflection.camera().view_matrix();
+
+    assert!(!reflection.update(&main_camera(Vec3::new(0.0, -5.0, 10.0))));
+    assert!(!reflection.is_active());
+    assert_eq!(*reflection.camera().view_matrix(), above);
+
+    // Moving the plane above the camera deactivates it too
+    reflection.set_plane(Vec4::new(0.0, 1.0, 0.0, -10.0)).unwrap();
+    assert!(!reflection.update(&main_camera(Vec3::new(0.0, 5.0, 10.0))));
+}
+
+// ============================================================================
+// Tests: render graph
+// ============================================================================
+
+#[test]
+#[serial]
+fn test_pass_writes_the_reflection_target() {
+    setup_engine_for_render_graph();
+    let mut rgm = RenderGraphManager::new();
+    let mut targets = ViewTargetManager::new("views");
+    let reflection = PlanarReflection::new(water(), make_desc()).unwrap();
+
+    let target = reflection.create_target(
+        &mut targets, &mut rgm, "water", TextureFormat::R16G16B16A16_SFLOAT, Some(TextureFormat::D32_FLOAT),
+    ).unwrap();
+    assert_eq!((target.width, target.height), (320, 180));
+
+    let accesses = reflection.pass_accesses(&target, [0.0; 4]);
+    assert_eq!(accesses.len(), 2);
+    assert_eq!(accesses[0].graph_resource_key, target.color);
+    assert_eq!(accesses[0].access_type, AccessType::ColorAttachmentWrite);
+    assert_eq!(accesses[1].access_type, AccessType::DepthStencilWrite);
+
+    let (action, counter) = make_recording_pass();
+    let pass = rgm.create_render_pass("reflection", accesses, action).unwrap();
+    let graph = rgm.create_render_graph("frame", 1).unwrap();
+    rgm.execute_render_graph(graph, &[pass], |_| Ok(())).unwrap();
+    assert_eq!(counter.load(std::sync::atomic::Ordering::Relaxed), 1);
+}
@@ -0,0 +1,349 @@
+//! Built-in post effects: HDR tonemapping, bloom, FXAA and the upscaling
+//! composite of scaled views.
+//!
+//! Each effect receives its pipelines and documents the push constant
+//! layout their shaders must declare (std430, fragment stage, offset 0).
+//! The engine ships those shaders for tonemapping, bloom and FXAA in
+//! `galaxy_3d_engine/shaders/post_*`, embedded below as GLSL and SPIR-V
+//! (`shaders/compile.sh`): build each pipeline from `POST_FULLSCREEN_VERTEX_SPIRV`
+//! and the effect's fragment shader. `Upscale` takes the application's
+//! shader. Parameters are public fields and are read every frame.
+
+use std::sync::Arc;
+use crate::graphics_device::{self, Viewport};
+use crate::scene::{FoveationMask, RenderView};
+use super::post_effect::{PostEffect, PostPass, PostSlot, PostTargetDesc};
+
+/// Append a `f32` push constant field.
+fn push_f32(out: &mut Vec<u8>, value: f32) {
+    out.extend_from_slice(bytemuck::bytes_of(&value));
+}
+
+/// Append a `u32` push constant field.
+fn push_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(bytemuck::bytes_of(&value));
+}
+
+// ===== SHADERS =====
+
+/// GLSL source of the fullscreen triangle vertex shader shared by the post passes
+pub const POST_FULLSCREEN_VERTEX_GLSL: &str = include_str!("../../shaders/post_fullscreen.vert");
+/// GLSL source of the `Tonemap` fragment shader
+pub const TONEMAP_FRAGMENT_GLSL: &str = include_str!("../../shaders/post_tonemap.frag");
+/// GLSL source of the `Bloom` extract pass fragment shader
+pub const BLOOM_EXTRACT_FRAGMENT_GLSL: &str = include_str!("../../shaders/post_bloom_extract.frag");
+/// GLSL source of the `Bloom` blur passes fragment shader
+pub const BLOOM_BLUR_FRAGMENT_GLSL: &str = include_str!("../../shaders/post_bloom_blur.frag");
+/// GLSL source of the `Bloom` composite pass fragment shader
+pub const BLOOM_COMPOSITE_FRAGMENT_GLSL: &str = include_str!("../../shaders/post_bloom_composite.frag");
+/// GLSL source of the `Fxaa` fragment shader
+pub const FXAA_FRAGMENT_GLSL: &str = include_str!("../../shaders/post_fxaa.frag");
+
+/// SPIR-V of the fullscreen triangle vertex shader
+pub static POST_FULLSCREEN_VERTEX_SPIRV: &[u8] = include_spirv!("post_fullscreen.vert.spv");
+/// SPIR-V of the `Tonemap` fragment shader
+pub static TONEMAP_FRAGMENT_SPIRV: &[u8] = include_spirv!("post_tonemap.frag.spv");
+/// SPIR-V of the `Bloom` extract pass fragment shader
+pub static BLOOM_EXTRACT_FRAGMENT_SPIRV: &[u8] = include_spirv!("post_bloom_extract.frag.spv");
+/// SPIR-V of the `Bloom` blur passes fragment shader
+pub static BLOOM_BLUR_FRAGMENT_SPIRV: &[u8] = include_spirv!("post_bloom_blur.frag.spv");
+/// SPIR-V of the `Bloom` composite pass fragment shader
+pub static BLOOM_COMPOSITE_FRAGMENT_SPIRV: &[u8] = include_spirv!("post_bloom_composite.frag.spv");
+/// SPIR-V of the `Fxaa` fragment shader
+pub static FXAA_FRAGMENT_SPIRV: &[u8] = include_spirv!("post_fxaa.frag.spv");
+
+// ===== TONEMAP =====
+
+/// Tonemapping curve applied by `Tonemap`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TonemapOperator {
+    /// `c / (1 + c)`
+    Reinhard = 0,
+    /// ACES filmic fit (Narkowicz)
+    Aces = 1,
+}
+
+/// HDR to LDR tonemapping with exposure and gamma.
+///
+/// Push constants: `{ float exposure; uint operator; float gamma; }`
+pub struct Tonemap {
+    pipeline: Arc<dyn graphics_device::Pipeline>,
+    /// Linear exposure multiplier applied before the curve
+    pub exposure: f32,
+    /// Tonemapping curve
+    pub operator: TonemapOperator,
+    /// Output gamma (1.0 when writing to an sRGB target)
+    pub gamma: f32,
+}
+
+impl Tonemap {
+    /// Create a tonemap effect (exposure 1.0, ACES, gamma 1.0).
+    pub fn new(pipeline: Arc<dyn graphics_device::Pipeline>) -> Self {
+        Self {
+            pipeline,
+            exposure: 1.0,
+            operator: TonemapOperator::Aces,
+            gamma: 1.0,
+        }
+    }
+}
+
+impl PostEffect for Tonemap {
+    fn name(&self) -> &str {
+        "tonemap"
+    }
+
+    fn passes(&self) -> Vec<PostPass> {
+        vec![PostPass {
+            pipeline: self.pipeline.clone(),
+            inputs: vec![PostSlot::Input],
+            output: PostSlot::Output,
+        }]
+    }
+
+    fn write_push_constants(&self, _pass_index: usize, out: &mut Vec<u8>) {
+        push_f32(out, self.exposure);
+        push_u32(out, self.operator as u32);
+        push_f32(out, self.gamma);
+    }
+}
+
+// ===== BLOOM =====
+
+/// Bloom: bright-pass extraction, separable blur at reduced resolution,
+/// then additive composite over the input.
+///
+/// Passes and push constants:
+/// 0. extract   `Input -> Target(0)`           `{ float threshold; float knee; }`
+/// 1. blur H    `Target(0) -> Target(1)`       `{ vec2 direction; }` = (1, 0)
+/// 2. blur V    `Target(1) -> Target(2)`       `{ vec2 direction; }` = (0, 1)
+/// 3. composite `Input, Target(2) -> Output`   `{ float intensity; }`
+///
+/// Each target is written by a single pass, as required by the render
+/// graph's writer-before-reader ordering.
+pub struct Bloom {
+    extract_pipeline: Arc<dyn graphics_device::Pipeline>,
+    blur_pipeline: Arc<dyn graphics_device::Pipeline>,
+    composite_pipeline: Arc<dyn graphics_device::Pipeline>,
+    /// Luminance above which pixels contribute to the bloom
+    pub threshold: f32,
+    /// Width of the soft transition below `threshold`
+    pub knee: f32,
+    /// Strength of the bloom added over the input
+    pub intensity: f32,
+    /// Resolution divisor of the blur targets (fixed once the stack is built)
+    pub downscale: u32,
+}
+
+impl Bloom {
+    /// Create a bloom effect (threshold 1.0, knee 0.5, intensity 0.05,
+    /// half-resolution blur).
+    pub fn new(
+        extract_pipeline: Arc<dyn graphics_device::Pipeline>,
+        blur_pipeline: Arc<dyn graphics_device::Pipeline>,
+        composite_pipeline: Arc<dyn graphics_device::Pipeline>,
+    ) -> Self {
+        Self {
+            extract_pipeline,
+            blur_pipeline,
+            composite_pipeline,
+            threshold: 1.0,
+            knee: 0.5,
+            intensity: 0.05,
+            downscale: 2,
+        }
+    }
+}
+
+impl PostEffect for Bloom {
+    fn name(&self) -> &str {
+        "bloom"
+    }
+
+    fn targets(&self) -> Vec<PostTargetDesc> {
+        let target = PostTargetDesc { scale_divisor: self.downscale, format: None };
+        vec![target; 3]
+    }
+
+    fn passes(&self) -> Vec<PostPass> {
+        vec![
+            PostPass {
+                pipeline: self.extract_pipeline.clone(),
+                inputs: vec![PostSlot::Input],
+                output: PostSlot::Target(0),
+            },
+            PostPass {
+                pipeline: self.blur_pipeline.clone(),
+                inputs: vec![PostSlot::Target(0)],
+                output: PostSlot::Target(1),
+            },
+            PostPass {
+                pipeline: self.blur_pipeline.clone(),
+                inputs: vec![PostSlot::Target(1)],
+                output: PostSlot::Target(2),
+            },
+            PostPass {
+                pipeline: self.composite_pipeline.clone(),
+                inputs: vec![PostSlot::Input, PostSlot::Target(2)],
+                output: PostSlot::Output,
+            },
+        ]
+    }
+
+    fn write_push_constants(&self, pass_index: usize, out: &mut Vec<u8>) {
+        match pass_index {
+            0 => {
+                push_f32(out, self.threshold);
+                push_f32(out, self.knee);
+            }
+            1 => {
+                push_f32(out, 1.0);
+                push_f32(out, 0.0);
+            }
+            2 => {
+                push_f32(out, 0.0);
+                push_f32(out, 1.0);
+            }
+            _ => push_f32(out, self.intensity),
+        }
+    }
+}
+
+// ===== FXAA =====
+
+/// Fast approximate anti-aliasing (FXAA 3.11 quality parameters).
+///
+/// Push constants: `{ float subpixel; float edge_threshold; float edge_threshold_min; }`
+pub struct Fxaa {
+    pipeline: Arc<dyn graphics_device::Pipeline>,
+    /// Sub-pixel aliasing removal amount (0.0 = off, 1.0 = softest)
+    pub subpixel: f32,
+    /// Minimum local contrast required to process an edge
+    pub edge_threshold: f32,
+    /// Contrast below which dark areas are skipped
+    pub edge_threshold_min: f32,
+}
+
+impl Fxaa {
+    /// Create an FXAA effect with the FXAA 3.11 default quality settings.
+    pub fn new(pipeline: Arc<dyn graphics_device::Pipeline>) -> Self {
+        Self {
+            pipeline,
+            subpixel: 0.75,
+            edge_threshold: 0.166,
+            edge_threshold_min: 0.0833,
+        }
+    }
+}
+
+impl PostEffect for Fxaa {
+    fn name(&self) -> &str {
+        "fxaa"
+    }
+
+    fn passes(&self) -> Vec<PostPass> {
+        vec![PostPass {
+            pipeline: self.pipeline.clone(),
+            inputs: vec![PostSlot::Input],
+            output: PostSlot::Output,
+        }]
+    }
+
+    fn write_push_constants(&self, _pass_index: usize, out: &mut Vec<u8>) {
+        push_f32(out, self.subpixel);
+        push_f32(out, self.edge_threshold);
+        push_f32(out, self.edge_threshold_min);
+    }
+}
+
+// ===== UPSCALE =====
+
+/// Composite of a view rendered at its own render scale: samples the
+/// scaled color (linear clamp) into the view's rectangle of the output.
+/// Use it as the last effect of the view's stack, whose input is the
+/// view's `ViewTarget::color`.
+///
+/// Push constants:
+/// `{ float renderScale; float sharpness; uint foveated; float outerDensity;
+///    vec4 foveation; }`, with `foveation = (center.xy, innerRadius,
+/// outerRadius)` in view UV (zero when `foveated` is 0).
+///
+/// Split-screen views compositing into one output: the stack output is
+/// cleared by each composite (`LoadOp::DontCare`), so set the output access
+/// of all but the first view to `LoadOp::Load`
+/// (`RenderGraphManager::set_pass_access_target_ops`).
+pub struct Upscale {
+    pipeline: Arc<dyn graphics_device::Pipeline>,
+    /// Render scale of the input (informs the filter footprint)
+    pub render_scale: f32,
+    /// Sharpening strength after upscaling (0.0 = off)
+    pub sharpness: f32,
+    /// Density mask of the view, forwarded to the shader
+    pub foveation: Option<FoveationMask>,
+    /// Rectangle of the output covered by the view (None = whole output)
+    pub output_viewport: Option<Viewport>,
+}
+
+impl Upscale {
+    /// Create an upscale effect (scale 1.0, no sharpening, no foveation,
+    /// whole output).
+    pub fn new(pipeline: Arc<dyn graphics_device::Pipeline>) -> Self {
+        Self {
+            pipeline,
+            render_scale: 1.0,
+            sharpness: 0.0,
+            foveation: None,
+            output_viewport: None,
+        }
+    }
+
+    /// Copy the render scale, density mask and output viewport of a view.
+    /// Call it when those change.
+    pub fn sync_view(&mut self, view: &RenderView) {
+        self.render_scale = view.render_scale();
+        self.foveation = view.foveation().copied();
+        self.output_viewport = Some(*view.output_viewport());
+    }
+}
+
+impl PostEffect for Upscale {
+    fn name(&self) -> &str {
+        "upscale"
+    }
+
+    fn passes(&self) -> Vec<PostPass> {
+        vec![PostPass {
+            pipeline: self.pipeline.clone(),
+            inputs: vec![PostSlot::Input],
+            output: PostSlot::Output,
+        }]
+    }
+
+    fn write_push_constants(&self, _pass_index: usize, out: &mut Vec<u8>) {
+        push_f32(out, self.render_scale);
+        push_f32(out, self.sharpness);
+        push_u32(out, self.foveation.is_some() as u32);
+        match &self.foveation {
+            Some(mask) => {
+                push_f32(out, mask.outer_density);
+                for value in [mask.center[0], mask.center[1], mask.inner_radius, mask.outer_radius] {
+                    push_f32(out, value);
+                }
+            }
+            None => {
+                push_f32(out, 1.0);
+                for _ in 0..4 {
+                    push_f32(out, 0.0);
+                }
+            }
+        }
+    }
+
+    fn viewport(&self, _pass_index: usize) -> Option<Viewport> {
+        self.output_viewport
+    }
+}
+
+#[cfg(test)]
+#[path = "effects_tests.rs"]
+mod tests;
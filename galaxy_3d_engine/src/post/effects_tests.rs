@@ -0,0 +1,196 @@
+use super::*;
+use crate::graphics_device::mock_graphics_device::MockPipeline;
+
+fn make_pipeline(name: &str) -> Arc<dyn graphics_device::Pipeline> {
+    Arc::new(MockPipeline::new(name.to_string()))
+}
+
+fn read_f32(bytes: &[u8], index: usize) -> f32 {
+    f32::from_ne_bytes(bytes[index * 4..index * 4 + 4].try_into().unwrap())
+}
+
+fn read_u32(bytes: &[u8], index: usize) -> u32 {
+    u32::from_ne_bytes(bytes[index * 4..index * 4 + 4].try_into().unwrap())
+}
+
+// ============================================================================
+// Shaders
+// ============================================================================
+
+#[test]
+fn test_embedded_spirv_is_aligned_spirv() {
+    for code in [
+        POST_FULLSCREEN_VERTEX_SPIRV, TONEMAP_FRAGMENT_SPIRV, BLOOM_EXTRACT_FRAGMENT_SPIRV,
+        BLOOM_BLUR_FRAGMENT_SPIRV, BLOOM_COMPOSITE_FRAGMENT_SPIRV, FXAA_FRAGMENT_SPIRV,
+    ] {
+        assert_eq!(code.as_ptr() as usize % 4, 0);
+        assert_eq!(code.len() % 4, 0);
+        assert_eq!(code[..4], 0x0723_0203u32.to_le_bytes());
+    }
+}
+
+#[test]
+fn test_glsl_push_constants_match_effects() {
+    for (source, fields) in [
+        (TONEMAP_FRAGMENT_GLSL, &["float exposure;", "uint operator;", "float gamma;"][..]),
+        (BLOOM_EXTRACT_FRAGMENT_GLSL, &["float threshold;", "float knee;"]),
+        (BLOOM_BLUR_FRAGMENT_GLSL, &["vec2 direction;"]),
+        (BLOOM_COMPOSITE_FRAGMENT_GLSL, &["float intensity;"]),
+        (FXAA_FRAGMENT_GLSL, &["float subpixel;", "float edgeThreshold;", "float edgeThresholdMin;"]),
+    ] {
+        let block = &source[source.find("layout(push_constant)").unwrap()..];
+        let declared: Vec<&str> = block.lines().skip(1).map(str::trim)
+            .take_while(|line| !line.starts_with('}')).collect();
+        assert_eq!(declared, fields);
+    }
+    for operator in [TonemapOperator::Reinhard, TonemapOperator::Aces] {
+        let name = format!("{:?}", operator).to_uppercase();
+        let constant = format!("OPERATOR_{} = {}u;", name, operator as u32);
+        assert!(TONEMAP_FRAGMENT_GLSL.contains(&constant), "{}", constant);
+    }
+}
+
+// ============================================================================
+// Tonemap
+// ============================================================================
+
+#[test]
+fn test_tonemap_single_pass_input_to_output() {
+    let tonemap = Tonemap::new(make_pipeline("tonemap"));
+    let passes = tonemap.passes();
+    assert_eq!(passes.len(), 1);
+    assert_eq!(passes[0].inputs, vec![PostSlot::Input]);
+    assert_eq!(passes[0].output, PostSlot::Output);
+    assert!(tonemap.targets().is_empty());
+}
+
+#[test]
+fn test_tonemap_push_constants() {
+    let mut tonemap = Tonemap::new(make_pipeline("tonemap"));
+    tonemap.exposure = 2.5;
+    tonemap.operator = TonemapOperator::Reinhard;
+    tonemap.gamma = 2.2;
+    let mut out = Vec::new();
+    tonemap.write_push_constants(0, &mut out);
+    assert_eq!(out.len(), 12);
+    assert_eq!(read_f32(&out, 0), 2.5);
+    assert_eq!(read_u32(&out, 1), 0);
+    assert_eq!(read_f32(&out, 2), 2.2);
+}
+
+// ============================================================================
+// Bloom
+// ============================================================================
+
+#[test]
+fn test_bloom_passes_and_targets() {
+    let bloom = Bloom::new(
+        make_pipeline("extract"), make_pipeline("blur"), make_pipeline("composite"),
+    );
+    let targets = bloom.targets();
+    assert_eq!(targets.len(), 3);
+    assert!(targets.iter().all(|t| t.scale_divisor == 2 && t.format.is_none()));
+
+    let passes = bloom.passes();
+    assert_eq!(passes.len(), 4);
+    assert_eq!(passes[0].output, PostSlot::Target(0));
+    assert_eq!(passes[1].inputs, vec![PostSlot::Target(0)]);
+    assert_eq!(passes[2].output, PostSlot::Target(2));
+    assert_eq!(passes[3].inputs, vec![PostSlot::Input, PostSlot::Target(2)]);
+    assert_eq!(passes[3].output, PostSlot::Output);
+}
+
+#[test]
+fn test_bloom_push_constants_per_pass() {
+    let mut bloom = Bloom::new(
+        make_pipeline("extract"), make_pipeline("blur"), make_pipeline("composite"),
+    );
+    bloom.threshold = 1.5;
+    bloom.intensity = 0.2;
+
+    let mut out = Vec::new();
+    bloom.write_push_constants(0, &mut out);
+    assert_eq!(read_f32(&out, 0), 1.5);
+
+    out.clear();
+    bloom.write_push_constants(1, &mut out);
+    assert_eq!((read_f32(&out, 0), read_f32(&out, 1)), (1.0, 0.0));
+
+    out.clear();
+    bloom.write_push_constants(2, &mut out);
+    assert_eq!((read_f32(&out, 0), read_f32(&out, 1)), (0.0, 1.0));
+
+    out.clear();
+    bloom.write_push_constants(3, &mut out);
+    assert_eq!(out.len(), 4);
+    assert_eq!(read_f32(&out, 0), 0.2);
+}
+
+// ============================================================================
+// FXAA
+// ============================================================================
+
+#[test]
+fn test_fxaa_push_constants() {
+    let fxaa = Fxaa::new(make_pipeline("fxaa"));
+    assert_eq!(fxaa.passes().len(), 1);
+    let mut out = Vec::new();
+    fxaa.write_push_constants(0, &mut out);
+    assert_eq!(out.len(), 12);
+    assert_eq!(read_f32(&out, 0), fxaa.subpixel);
+    assert_eq!(read_f32(&out, 1), fxaa.edge_threshold);
+    assert_eq!(read_f32(&out, 2), fxaa.edge_threshold_min);
+}
+
+// ============================================================================
+// Upscale
+// ============================================================================
+
+fn make_view(render_scale: f32) -> RenderView {
+    use crate::camera::{Camera, Frustum};
+    let viewport = Viewport { x: 960.0, y: 0.0, width: 960.0, height: 1080.0, min_depth: 0.0, max_depth: 1.0 };
+    let camera = Camera::new(
+        glam::Mat4::IDENTITY, glam::Mat4::IDENTITY,
+        Frustum::from_view_projection(&glam::Mat4::IDENTITY), viewport,
+    );
+    let mut view = RenderView::new(camera, 0);
+    view.set_render_scale(render_scale).unwrap();
+    view
+}
+
+#[test]
+fn test_upscale_push_constants_without_foveation() {
+    let mut upscale = Upscale::new(make_pipeline("upscale"));
+    upscale.sharpness = 0.2;
+    assert_eq!(upscale.passes().len(), 1);
+    assert!(upscale.viewport(0).is_none());
+
+    let mut out = Vec::new();
+    upscale.write_push_constants(0, &mut out);
+    assert_eq!(out.len(), 32);
+    assert_eq!(read_f32(&out, 0), 1.0);
+    assert_eq!(read_f32(&out, 1), 0.2);
+    assert_eq!(read_u32(&out, 2), 0);
+    assert_eq!(read_f32(&out, 3), 1.0);
+}
+
+#[test]
+fn test_upscale_sync_view_copies_scale_mask_and_rect() {
+    let mut view = make_view(0.5);
+    view.set_foveation(Some(FoveationMask {
+        center: [0.5, 0.4], inner_radius: 0.2, outer_radius: 0.6, outer_density: 0.25,
+    })).unwrap();
+    let mut upscale = Upscale::new(make_pipeline("upscale"));
+    upscale.sync_view(&view);
+
+    // Output rectangle at full resolution, not the scaled camera viewport
+    let viewport = upscale.viewport(0).unwrap();
+    assert_eq!((viewport.x, viewport.width), (960.0, 960.0));
+
+    let mut out = Vec::new();
+    upscale.write_push_constants(0, &mut out);
+    assert_eq!(read_f32(&out, 0), 0.5);
+    assert_eq!(read_u32(&out, 2), 1);
+    assert_eq!(read_f32(&out, 3), 0.25);
+    assert_eq!([read_f32(&out, 4), read_f32(&out, 5), read_f32(&out, 6), read_f32(&out, 7)], [0.5, 0.4, 0.2, 0.6]);
+}
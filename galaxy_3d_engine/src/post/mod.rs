@@ -0,0 +1,36 @@
+//! Post-processing module.
+//!
+//! A `PostStack` chains `PostEffect`s (tonemapping, bloom, FXAA, or any
+//! user effect) into fullscreen render passes of the render graph. Each
+//! stack is built for one (input, output) pair of render-graph targets,
+//! so every `RenderView` / render target can have its own stack.
+//!
+//! Effects only describe their passes (pipeline, sampled inputs, output
+//! and push constants); the stack allocates the ping-pong and
+//! intermediate textures and creates the render passes. The built-in
+//! effects ship their GLSL and SPIR-V (`effects`).
+//!
+//! Views rendered at their own render scale get their targets from a
+//! `ViewTargetManager` and are brought back to output resolution by an
+//! `Upscale` effect.
+//!
+//! `TemporalAa` is built separately, on the main scene pass: it adds the
+//! motion vectors to that pass and keeps history textures across frames.
+
+mod effects;
+mod post_effect;
+mod post_stack;
+mod taa;
+mod view_targets;
+
+pub use effects::{Tonemap, TonemapOperator, Bloom, Fxaa, Upscale};
+pub use effects::{
+    POST_FULLSCREEN_VERTEX_GLSL, TONEMAP_FRAGMENT_GLSL, BLOOM_EXTRACT_FRAGMENT_GLSL,
+    BLOOM_BLUR_FRAGMENT_GLSL, BLOOM_COMPOSITE_FRAGMENT_GLSL, FXAA_FRAGMENT_GLSL,
+    POST_FULLSCREEN_VERTEX_SPIRV, TONEMAP_FRAGMENT_SPIRV, BLOOM_EXTRACT_FRAGMENT_SPIRV,
+    BLOOM_BLUR_FRAGMENT_SPIRV, BLOOM_COMPOSITE_FRAGMENT_SPIRV, FXAA_FRAGMENT_SPIRV,
+};
+pub use post_effect::{PostEffect, PostPass, PostSlot, PostTargetDesc};
+pub use post_stack::PostStack;
+pub use taa::{TemporalAa, DEFAULT_TAA_FEEDBACK, MOTION_VECTOR_FORMAT};
+pub use view_targets::{ViewTargetManager, ViewTargetDesc, ViewTarget};
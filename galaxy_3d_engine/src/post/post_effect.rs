@@ -0,0 +1,136 @@
+//! Post effect trait and the fullscreen pass action that runs it.
+//!
+//! A `PostEffect` is a small, chainable unit of post-processing. It declares
+//! the fullscreen passes it needs (`passes()`), the intermediate targets
+//! those passes render into (`targets()`), and fills the push constants of
+//! each pass every frame (`write_push_constants()`), so parameter changes
+//! take effect without rebuilding the stack.
+//!
+//! Shader interface of every post pass:
+//! - set 0, binding N: combined image sampler (linear clamp) for `inputs[N]`
+//! - push constants: fragment stage, offset 0, bytes from `write_push_constants`
+//! - drawn as a single fullscreen triangle (`draw(3, 0)`, no vertex buffer),
+//!   over the whole output or the pass's `viewport()` rectangle
+
+use std::sync::{Arc, Mutex};
+use crate::error::Result;
+use crate::graphics_device::{self, CommandList, Rect2D, ShaderStageFlags, TextureFormat, Viewport};
+use crate::render_graph::PassAction;
+use crate::resource::resource_manager::PassInfo;
+
+// ===== PASS DESCRIPTION =====
+
+/// Texture a post pass samples from or renders into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PostSlot {
+    /// Output of the previous effect (or the stack input for the first one)
+    Input,
+    /// Input of the next effect (or the stack output for the last one)
+    Output,
+    /// One of the effect's own targets, indexing `PostEffect::targets()`
+    Target(usize),
+}
+
+/// Intermediate render target owned by an effect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PostTargetDesc {
+    /// Resolution divisor relative to the stack input (1 = full, 2 = half, ...)
+    pub scale_divisor: u32,
+    /// Pixel format (None = same format as the stack input)
+    pub format: Option<TextureFormat>,
+}
+
+/// One fullscreen pass of a post effect.
+#[derive(Clone)]
+pub struct PostPass {
+    /// Pipeline drawing the fullscreen triangle
+    pub pipeline: Arc<dyn graphics_device::Pipeline>,
+    /// Sampled textures, bound at set 0 in declaration order
+    pub inputs: Vec<PostSlot>,
+    /// Color attachment written by the pass
+    pub output: PostSlot,
+}
+
+// ===== POST EFFECT TRAIT =====
+
+/// A chainable post-processing effect.
+pub trait PostEffect: Send + Sync {
+    /// Effect name, used to name the render passes and textures it creates.
+    fn name(&self) -> &str;
+
+    /// Intermediate targets used by `passes()` through `PostSlot::Target`.
+    fn targets(&self) -> Vec<PostTargetDesc> {
+        Vec::new()
+    }
+
+    /// Fullscreen passes of the effect, in execution order. The last pass
+    /// must write `PostSlot::Output`.
+    fn passes(&self) -> Vec<PostPass>;
+
+    /// Append the push constants of pass `pass_index` to `out` (left empty
+    /// when the pass has no push constants). Called every frame.
+    fn write_push_constants(&self, pass_index: usize, out: &mut Vec<u8>) {
+        let _ = (pass_index, out);
+    }
+
+    /// Rectangle of the output written by pass `pass_index` (None = the
+    /// whole output). Called every frame.
+    fn viewport(&self, pass_index: usize) -> Option<Viewport> {
+        let _ = pass_index;
+        None
+    }
+}
+
+// ===== POST PASS ACTION =====
+
+/// Pass action recording one pass of a `PostEffect`.
+pub(crate) struct PostPassAction {
+    effect: Arc<Mutex<dyn PostEffect>>,
+    pass_index: usize,
+    pipeline: Arc<dyn graphics_device::Pipeline>,
+    binding_group: Arc<dyn graphics_device::BindingGroup>,
+    /// Push constant bytes, reused across frames
+    push_constants: Vec<u8>,
+}
+
+impl PostPassAction {
+    pub(crate) fn new(
+        effect: Arc<Mutex<dyn PostEffect>>,
+        pass_index: usize,
+        pipeline: Arc<dyn graphics_device::Pipeline>,
+        binding_group: Arc<dyn graphics_device::BindingGroup>,
+    ) -> Self {
+        Self { effect, pass_index, pipeline, binding_group, push_constants: Vec::new() }
+    }
+}
+
+impl PassAction for PostPassAction {
+    fn execute(&mut self, cmd: &mut dyn CommandList, _pass_info: &PassInfo) -> Result<()> {
+        self.push_constants.clear();
+        let viewport = {
+            let effect = self.effect.lock().unwrap();
+            effect.write_push_constants(self.pass_index, &mut self.push_constants);
+            effect.viewport(self.pass_index)
+        };
+
+        if let Some(viewport) = viewport {
+            cmd.set_viewport(viewport)?;
+            cmd.set_scissor(Rect2D {
+                x: viewport.x as i32,
+                y: viewport.y as i32,
+                width: viewport.width as u32,
+                height: viewport.height as u32,
+            })?;
+        }
+        cmd.bind_pipeline(&self.pipeline)?;
+        cmd.bind_binding_group(&self.pipeline, 0, &self.binding_group)?;
+        if !self.push_constants.is_empty() {
+            cmd.push_constants(ShaderStageFlags::FRAGMENT, 0, &self.push_constants)?;
+        }
+        cmd.draw(3, 0)
+    }
+}
+
+#[cfg(test)]
+#[path = "post_effect_tests.rs"]
+mod tests;
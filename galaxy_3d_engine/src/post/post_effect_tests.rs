@@ -0,0 +1,102 @@
+use super::*;
+use crate::graphics_device::mock_graphics_device::{MockCommandList, MockPipeline, MockBindingGroup};
+use crate::graphics_device::SampleCount;
+
+fn make_pass_info() -> PassInfo {
+    PassInfo::new(vec![TextureFormat::R8G8B8A8_UNORM], None, SampleCount::S1)
+}
+
+fn make_pipeline() -> Arc<dyn graphics_device::Pipeline> {
+    Arc::new(MockPipeline::new("post_pipeline".to_string()))
+}
+
+/// Effect with one pass and a configurable number of push constant bytes.
+struct TestEffect {
+    pipeline: Arc<dyn graphics_device::Pipeline>,
+    push_size: usize,
+}
+
+impl PostEffect for TestEffect {
+    fn name(&self) -> &str {
+        "test"
+    }
+
+    fn passes(&self) -> Vec<PostPass> {
+        vec![PostPass {
+            pipeline: self.pipeline.clone(),
+            inputs: vec![PostSlot::Input],
+            output: PostSlot::Output,
+        }]
+    }
+
+    fn write_push_constants(&self, _pass_index: usize, out: &mut Vec<u8>) {
+        out.resize(out.len() + self.push_size, 0);
+    }
+}
+
+fn make_action(push_size: usize) -> PostPassAction {
+    let effect: Arc<Mutex<dyn PostEffect>> =
+        Arc::new(Mutex::new(TestEffect { pipeline: make_pipeline(), push_size }));
+    let binding_group: Arc<dyn graphics_device::BindingGroup> =
+        Arc::new(MockBindingGroup::new("post_bg".to_string(), 0));
+    PostPassAction::new(effect, 0, make_pipeline(), binding_group)
+}
+
+// ============================================================================
+// PostEffect defaults
+// ============================================================================
+
+#[test]
+fn test_post_effect_default_targets_empty() {
+    let effect = TestEffect { pipeline: make_pipeline(), push_size: 0 };
+    assert!(effect.targets().is_empty());
+}
+
+// ============================================================================
+// PostPassAction
+// ============================================================================
+
+#[test]
+fn test_post_pass_action_with_push_constants() {
+    let mut action = make_action(12);
+    let mut cmd = MockCommandList::new();
+    action.execute(&mut cmd, &make_pass_info()).unwrap();
+    assert_eq!(cmd.commands, vec!["bind_pipeline", "bind_binding_group", "push_constants", "draw"]);
+}
+
+#[test]
+fn test_post_pass_action_without_push_constants() {
+    let mut action = make_action(0);
+    let mut cmd = MockCommandList::new();
+    action.execute(&mut cmd, &make_pass_info()).unwrap();
+    assert_eq!(cmd.commands, vec!["bind_pipeline", "bind_binding_group", "draw"]);
+}
+
+#[test]
+fn test_post_pass_action_sets_effect_viewport() {
+    let mut upscale = crate::post::Upscale::new(make_pipeline());
+    upscale.output_viewport = Some(graphics_device::Viewport {
+        x: 960.0, y: 0.0, width: 960.0, height: 1080.0, min_depth: 0.0, max_depth: 1.0,
+    });
+    let effect: Arc<Mutex<dyn PostEffect>> = Arc::new(Mutex::new(upscale));
+    let binding_group: Arc<dyn graphics_device::BindingGroup> =
+        Arc::new(MockBindingGroup::new("post_bg".to_string(), 0));
+    let mut action = PostPassAction::new(effect, 0, make_pipeline(), binding_group);
+
+    let mut cmd = MockCommandList::new();
+    action.execute(&mut cmd, &make_pass_info()).unwrap();
+    assert_eq!(cmd.commands, vec![
+        "set_viewport", "set_scissor", "bind_pipeline", "bind_binding_group", "push_constants", "draw",
+    ]);
+}
+
+#[test]
+fn test_post_pass_action_reuses_push_constant_buffer() {
+    let mut action = make_action(8);
+    let mut cmd = MockCommandList::new();
+    action.execute(&mut cmd, &make_pass_info()).unwrap();
+    action.execute(&mut cmd, &make_pass_info()).unwrap();
+    // Cleared every frame, not accumulated
+    assert_eq!(action.push_constants.len(), 8);
+    assert_eq!(cmd.commands.len(), 8);
+}
@@ -0,0 +1,328 @@
+//! Post-processing stack — turns a chain of `PostEffect`s into render passes.
+//!
+//! `build()` runs once per stack: it allocates the link textures that
+//! connect consecutive effects and the intermediate targets each effect
+//! declares, registers them as `GraphResource`s, and creates one fullscreen
+//! `RenderPass` per effect pass. The resulting pass keys are appended to the
+//! pass list given to `RenderGraphManager::execute_render_graph`.
+//!
+//! Effect parameters are read every frame through the shared
+//! `Arc<Mutex<E>>` returned by `push_effect()`; the effect list and target
+//! sizes are fixed once the stack is built.
+//!
+//! The render graph orders passes by writer-before-reader on each graph
+//! resource, so every texture created here has exactly one writer: links
+//! are not ping-ponged and effect targets must not be written twice.
+
+use std::sync::{Arc, Mutex};
+use crate::error::Result;
+use crate::engine_bail;
+use crate::engine::Engine;
+use crate::graphics_device::{
+    self, AccessType, BindingGroupLayoutDesc, BindingResource, BindingSlotDesc, BindingType,
+    LoadOp, MipmapMode, SamplerType, ShaderStageFlags, StoreOp, TextureUsage,
+};
+use crate::render_graph::{
+    GraphResource, GraphResourceKey, RenderGraphManager, RenderPassKey, ResourceAccess, TargetOps,
+};
+use crate::resource::resource_manager::TextureKey;
+use crate::resource::texture::{LayerDesc, TextureDesc};
+use super::post_effect::{PostEffect, PostPassAction, PostSlot};
+
+/// Ordered chain of post effects rendering from one render-graph target
+/// into another.
+pub struct PostStack {
+    name: String,
+    effects: Vec<Arc<Mutex<dyn PostEffect>>>,
+    /// Render passes in execution order (empty until built)
+    passes: Vec<RenderPassKey>,
+    /// Textures created by the stack (links + effect targets)
+    textures: Vec<TextureKey>,
+}
+
+impl PostStack {
+    /// Create an empty stack. `name` prefixes every pass and texture the
+    /// stack creates, so it must be unique per render target.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            effects: Vec::new(),
+            passes: Vec::new(),
+            textures: Vec::new(),
+        }
+    }
+
+    /// Append an effect at the end of the chain. Returns a shared handle
+    /// used to tweak its parameters at runtime.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stack has already been built.
+    pub fn push_effect<E: PostEffect + 'static>(&mut self, effect: E) -> Result<Arc<Mutex<E>>> {
+        if self.is_built() {
+            engine_bail!("galaxy3d::PostStack",
+                "Cannot add effect '{}' to post stack '{}': already built",
+                effect.name(), self.name);
+        }
+        let effect = Arc::new(Mutex::new(effect));
+        self.effects.push(effect.clone());
+        Ok(effect)
+    }
+
+    // ===== ACCESSORS =====
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn effect_count(&self) -> usize {
+        self.effects.len()
+    }
+
+    /// True once `build()` has succeeded
+    pub fn is_built(&self) -> bool {
+        !self.passes.is_empty()
+    }
+
+    /// Render passes of the stack, in execution order
+    pub fn passes(&self) -> &[RenderPassKey] {
+        &self.passes
+    }
+
+    /// Textures allocated by the stack (links and effect targets)
+    pub fn textures(&self) -> &[TextureKey] {
+        &self.textures
+    }
+
+    // ===== BUILD =====
+
+    /// Create the render passes reading `input` and writing `output`.
+    ///
+    /// Intermediate textures use the input resolution (divided by each
+    /// target's `scale_divisor`) and the input format unless overridden.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stack is empty or already built, if `input`
+    /// or `output` is not a texture graph resource, or if an effect
+    /// declares an invalid pass (unknown target, sampling its own output,
+    /// slot written twice, last pass not writing `PostSlot::Output`).
+    pub fn build(
+        &mut self,
+        graph_manager: &mut RenderGraphManager,
+        input: GraphResourceKey,
+        output: GraphResourceKey,
+    ) -> Result<&[RenderPassKey]> {
+        if self.is_built() {
+            engine_bail!("galaxy3d::PostStack", "Post stack '{}' is already built", self.name);
+        }
+        if self.effects.is_empty() {
+            engine_bail!("galaxy3d::PostStack", "Post stack '{}' has no effect", self.name);
+        }
+        let input_texture = Self::texture_key(graph_manager, input, "input")?;
+        Self::texture_key(graph_manager, output, "output")?;
+
+        let rm_arc = Engine::resource_manager()?;
+        let gd_arc = Engine::graphics_device("main")?;
+        let input_info = {
+            let rm = rm_arc.lock().unwrap();
+            let texture = rm.texture(input_texture).ok_or_else(|| {
+                crate::engine_err!("galaxy3d::PostStack",
+                    "Post stack '{}': input texture not found", self.name)
+            })?;
+            texture.graphics_device_texture().info().clone()
+        };
+
+        // One link texture between each pair of consecutive effects
+        let mut links = Vec::with_capacity(self.effects.len() - 1);
+        for i in 0..self.effects.len() - 1 {
+            let name = format!("{}/link_{}", self.name, i);
+            links.push(self.create_target(graph_manager, &name, &input_info, 1, None)?);
+        }
+
+        let effect_count = self.effects.len();
+        let mut passes = Vec::new();
+        for (effect_index, effect) in self.effects.clone().into_iter().enumerate() {
+            let (effect_name, targets, effect_passes) = {
+                let e = effect.lock().unwrap();
+                (e.name().to_string(), e.targets(), e.passes())
+            };
+            let prefix = format!("{}/{}_{}", self.name, effect_index, effect_name);
+
+            let effect_input = if effect_index == 0 {
+                input
+            } else {
+                links[effect_index - 1]
+            };
+            let effect_output = if effect_index + 1 == effect_count {
+                output
+            } else {
+                links[effect_index]
+            };
+
+            if effect_passes.last().map(|p| p.output) != Some(PostSlot::Output) {
+                engine_bail!("galaxy3d::PostStack",
+                    "Post effect '{}': last pass must write PostSlot::Output", prefix);
+            }
+            for (i, pass) in effect_passes.iter().enumerate() {
+                if effect_passes[..i].iter().any(|p| p.output == pass.output) {
+                    engine_bail!("galaxy3d::PostStack",
+                        "Post effect '{}': {:?} is written by more than one pass", prefix, pass.output);
+                }
+            }
+
+            let mut target_keys = Vec::with_capacity(targets.len());
+            for (i, target) in targets.iter().enumerate() {
+                let name = format!("{}/target_{}", prefix, i);
+                target_keys.push(self.create_target(
+                    graph_manager, &name, &input_info, target.scale_divisor, target.format,
+                )?);
+            }
+
+            let resolve = |slot: PostSlot| -> Result<GraphResourceKey> {
+                match slot {
+                    PostSlot::Input => Ok(effect_input),
+                    PostSlot::Output => Ok(effect_output),
+                    PostSlot::Target(i) => target_keys.get(i).copied().ok_or_else(|| {
+                        crate::engine_err!("galaxy3d::PostStack",
+                            "Post effect '{}': target {} out of bounds (count: {})",
+                            prefix, i, target_keys.len())
+                    }),
+                }
+            };
+
+            for (pass_index, pass) in effect_passes.into_iter().enumerate() {
+                if pass.inputs.contains(&pass.output) {
+                    engine_bail!("galaxy3d::PostStack",
+                        "Post effect '{}': pass {} samples its own output", prefix, pass_index);
+                }
+
+                let output_key = resolve(pass.output)?;
+                let mut accesses = Vec::with_capacity(pass.inputs.len() + 1);
+                let mut input_textures = Vec::with_capacity(pass.inputs.len());
+                for slot in &pass.inputs {
+                    let key = resolve(*slot)?;
+                    input_textures.push(Self::texture_key(graph_manager, key, "pass input")?);
+                    accesses.push(ResourceAccess {
+                        graph_resource_key: key,
+                        access_type: AccessType::FragmentShaderRead,
+                        target_ops: None,
+                    });
+                }
+                accesses.push(ResourceAccess {
+                    graph_resource_key: output_key,
+                    access_type: AccessType::ColorAttachmentWrite,
+                    target_ops: Some(TargetOps::Color {
+                        clear_color: [0.0, 0.0, 0.0, 0.0],
+                        load_op: LoadOp::DontCare,
+                        store_op: StoreOp::Store,
+                        resolve_target: None,
+                    }),
+                });
+
+                let binding_group = {
+                    let rm = rm_arc.lock().unwrap();
+                    let gd = gd_arc.lock().unwrap();
+                    let layout = BindingGroupLayoutDesc {
+                        entries: (0..input_textures.len()).map(|i| BindingSlotDesc {
+                            binding: i as u32,
+                            binding_type: BindingType::CombinedImageSampler,
+                            count: 1,
+                            stage_flags: ShaderStageFlags::FRAGMENT,
+                        }).collect(),
+                    };
+                    let mut resources = Vec::with_capacity(input_textures.len());
+                    for key in &input_textures {
+                        let texture = rm.texture(*key).ok_or_else(|| {
+                            crate::engine_err!("galaxy3d::PostStack",
+                                "Post effect '{}': input texture not found", prefix)
+                        })?;
+                        resources.push(BindingResource::SampledTexture(
+                            texture.graphics_device_texture().as_ref(),
+                            SamplerType::LinearClamp,
+                        ));
+                    }
+                    gd.create_binding_group_from_layout(&layout, 0, &resources)?
+                };
+
+                let action = PostPassAction::new(
+                    effect.clone(), pass_index, pass.pipeline, binding_group,
+                );
+                let pass_name = format!("{}/pass_{}", prefix, pass_index);
+                passes.push(graph_manager.create_render_pass(
+                    &pass_name, accesses, Box::new(action),
+                )?);
+            }
+        }
+
+        self.passes = passes;
+        Ok(&self.passes)
+    }
+
+    // ===== PRIVATE HELPERS =====
+
+    /// Texture key behind a graph resource, or an error if it is a buffer.
+    fn texture_key(
+        graph_manager: &RenderGraphManager,
+        key: GraphResourceKey,
+        what: &str,
+    ) -> Result<TextureKey> {
+        match graph_manager.graph_resource(key) {
+            Some(GraphResource::Texture { texture_key, .. }) => Ok(texture_key),
+            Some(GraphResource::Buffer(_)) => {
+                engine_bail!("galaxy3d::PostStack", "Post stack {} must be a texture, got a buffer", what)
+            }
+            None => engine_bail!("galaxy3d::PostStack", "Post stack {}: GraphResourceKey not found", what),
+        }
+    }
+
+    /// Create a sampled render target and register it as a graph resource.
+    fn create_target(
+        &mut self,
+        graph_manager: &mut RenderGraphManager,
+        name: &str,
+        input_info: &graphics_device::TextureInfo,
+        scale_divisor: u32,
+        format: Option<graphics_device::TextureFormat>,
+    ) -> Result<GraphResourceKey> {
+        if scale_divisor == 0 {
+            engine_bail!("galaxy3d::PostStack", "Post target '{}': scale_divisor must be non-zero", name);
+        }
+        let rm_arc = Engine::resource_manager()?;
+        let gd_arc = Engine::graphics_device("main")?;
+        let texture_key = rm_arc.lock().unwrap().create_texture(name.to_string(), TextureDesc {
+            graphics_device: gd_arc,
+            texture: graphics_device::TextureDesc {
+                width: (input_info.width / scale_divisor).max(1),
+                height: (input_info.height / scale_divisor).max(1),
+                depth: 1,
+                format: format.unwrap_or(input_info.format),
+                usage: TextureUsage::SampledAndRenderTarget,
+                texture_type: graphics_device::TextureType::Tex2D,
+                sample_count: graphics_device::SampleCount::S1,
+                array_layers: 1,
+                data: None,
+                mipmap: MipmapMode::None,
+                debug_name: None,
+            },
+            layers: vec![LayerDesc {
+                name: "default".to_string(),
+                layer_index: 0,
+                data: None,
+                regions: vec![],
+            }],
+        })?;
+        self.textures.push(texture_key);
+
+        graph_manager.create_graph_resource(name, GraphResource::Texture {
+            texture_key,
+            base_mip_level: 0,
+            base_array_layer: 0,
+            layer_count: 1,
+        })
+    }
+}
+
+#[cfg(test)]
+#[path = "post_stack_tests.rs"]
+mod tests;
@@ -0,0 +1,295 @@
+//! Tests for PostStack
+//!
+//! Build tests rely on the global Engine + a `MockGraphicsDevice` and are
+//! `#[serial]` because they share global state.
+
+use super::*;
+use crate::graphics_device::mock_graphics_device::MockPipeline;
+use crate::graphics_device::{TextureFormat, TextureType, SampleCount};
+use crate::render_graph::test_helpers::{setup_engine_for_render_graph, make_recording_pass};
+use crate::post::{Tonemap, Bloom, Fxaa, PostPass, PostTargetDesc};
+use serial_test::serial;
+
+fn make_pipeline(name: &str) -> Arc<dyn graphics_device::Pipeline> {
+    Arc::new(MockPipeline::new(name.to_string()))
+}
+
+fn make_bloom() -> Bloom {
+    Bloom::new(make_pipeline("extract"), make_pipeline("blur"), make_pipeline("composite"))
+}
+
+/// Register a 64x64 HDR color texture and wrap it in a graph resource.
+fn make_target(rgm: &mut RenderGraphManager, name: &str) -> GraphResourceKey {
+    let rm_arc = Engine::resource_manager().unwrap();
+    let gd_arc = Engine::graphics_device("main").unwrap();
+    let texture_key = rm_arc.lock().unwrap().create_texture(name.to_string(), TextureDesc {
+        graphics_device: gd_arc,
+        texture: graphics_device::TextureDesc {
+            width: 64, height: 64, depth: 1,
+            format: TextureFormat::R16G16B16A16_SFLOAT,
+            usage: TextureUsage::SampledAndRenderTarget,
+            texture_type: TextureType::Tex2D,
+            sample_count: SampleCount::S1,
+            array_layers: 1,
+            data: None,
+            mipmap: MipmapMode::None,
+            debug_name: None,
+        },
+        layers: vec![LayerDesc { name: "default".to_string(), layer_index: 0, data: None, regions: vec![] }],
+    }).unwrap();
+    rgm.create_graph_resource(name, GraphResource::Texture {
+        texture_key, base_mip_level: 0, base_array_layer: 0, layer_count: 1,
+    }).unwrap()
+}
+
+fn texture_size(key: TextureKey) -> (u32, u32) {
+    let rm_arc = Engine::resource_manager().unwrap();
+    let rm = rm_arc.lock().unwrap();
+    let info = rm.texture(key).unwrap().graphics_device_texture().info().clone();
+    (info.width, info.height)
+}
+
+// ============================================================================
+// Construction (no Engine)
+// ============================================================================
+
+#[test]
+fn test_new_stack_is_empty() {
+    let stack = PostStack::new("main_view");
+    assert_eq!(stack.name(), "main_view");
+    assert_eq!(stack.effect_count(), 0);
+    assert!(!stack.is_built());
+    assert!(stack.passes().is_empty());
+}
+
+#[test]
+fn test_push_effect_returns_shared_handle() {
+    let mut stack = PostStack::new("main_view");
+    let tonemap = stack.push_effect(Tonemap::new(make_pipeline("tonemap"))).unwrap();
+    tonemap.lock().unwrap().exposure = 3.0;
+    assert_eq!(stack.effect_count(), 1);
+    let mut out = Vec::new();
+    stack.effects[0].lock().unwrap().write_push_constants(0, &mut out);
+    assert_eq!(f32::from_ne_bytes(out[0..4].try_into().unwrap()), 3.0);
+}
+
+// ============================================================================
+// Build (Engine-backed)
+// ============================================================================
+
+#[test]
+#[serial]
+fn test_build_single_effect_writes_output_directly() {
+    setup_engine_for_render_graph();
+    let mut rgm = RenderGraphManager::new();
+    let input = make_target(&mut rgm, "hdr");
+    let output = make_target(&mut rgm, "ldr");
+
+    let mut stack = PostStack::new("view");
+    stack.push_effect(Tonemap::new(make_pipeline("tonemap"))).unwrap();
+    let passes = stack.build(&mut rgm, input, output).unwrap().to_vec();
+
+    assert_eq!(passes.len(), 1);
+    assert!(stack.textures().is_empty());
+    let pass = rgm.render_pass(passes[0]).unwrap();
+    assert_eq!(pass.name(), "view/0_tonemap/pass_0");
+    assert_eq!(pass.accesses().len(), 2);
+    assert_eq!(pass.accesses()[0].graph_resource_key, input);
+    assert_eq!(pass.accesses()[0].access_type, AccessType::FragmentShaderRead);
+    assert_eq!(pass.accesses()[1].graph_resource_key, output);
+    assert_eq!(pass.accesses()[1].access_type, AccessType::ColorAttachmentWrite);
+}
+
+#[test]
+#[serial]
+fn test_build_chain_uses_link_targets() {
+    setup_engine_for_render_graph();
+    let mut rgm = RenderGraphManager::new();
+    let input = make_target(&mut rgm, "hdr");
+    let output = make_target(&mut rgm, "ldr");
+
+    let mut stack = PostStack::new("view");
+    stack.push_effect(make_bloom()).unwrap();
+    stack.push_effect(Tonemap::new(make_pipeline("tonemap"))).unwrap();
+    stack.push_effect(Fxaa::new(make_pipeline("fxaa"))).unwrap();
+    let passes = stack.build(&mut rgm, input, output).unwrap().to_vec();
+
+    // bloom (4) + tonemap (1) + fxaa (1)
+    assert_eq!(passes.len(), 6);
+    // 2 links + 3 bloom targets
+    assert_eq!(stack.textures().len(), 5);
+
+    let link_0 = rgm.graph_resource_id("view/link_0").unwrap();
+    let link_1 = rgm.graph_resource_id("view/link_1").unwrap();
+
+    // Bloom composite writes link 0
+    let composite = rgm.render_pass(passes[3]).unwrap();
+    assert_eq!(composite.accesses()[0].graph_resource_key, input);
+    assert_eq!(composite.accesses()[2].graph_resource_key, link_0);
+    // Tonemap reads link 0 and writes link 1
+    let tonemap = rgm.render_pass(passes[4]).unwrap();
+    assert_eq!(tonemap.accesses()[0].graph_resource_key, link_0);
+    assert_eq!(tonemap.accesses()[1].graph_resource_key, link_1);
+    // FXAA reads link 1 and writes the stack output
+    let fxaa = rgm.render_pass(passes[5]).unwrap();
+    assert_eq!(fxaa.accesses()[0].graph_resource_key, link_1);
+    assert_eq!(fxaa.accesses()[1].graph_resource_key, output);
+}
+
+#[test]
+#[serial]
+fn test_build_effect_targets_are_downscaled() {
+    setup_engine_for_render_graph();
+    let mut rgm = RenderGraphManager::new();
+    let input = make_target(&mut rgm, "hdr");
+    let output = make_target(&mut rgm, "ldr");
+
+    let mut stack = PostStack::new("view");
+    let mut bloom = make_bloom();
+    bloom.downscale = 4;
+    stack.push_effect(bloom).unwrap();
+    stack.build(&mut rgm, input, output).unwrap();
+
+    assert_eq!(stack.textures().len(), 3);
+    for key in stack.textures() {
+        assert_eq!(texture_size(*key), (16, 16));
+    }
+}
+
+#[test]
+#[serial]
+fn test_build_twice_fails() {
+    setup_engine_for_render_graph();
+    let mut rgm = RenderGraphManager::new();
+    let input = make_target(&mut rgm, "hdr");
+    let output = make_target(&mut rgm, "ldr");
+
+    let mut stack = PostStack::new("view");
+    stack.push_effect(Fxaa::new(make_pipeline("fxaa"))).unwrap();
+    stack.build(&mut rgm, input, output).unwrap();
+    assert!(stack.build(&mut rgm, input, output).is_err());
+    assert!(stack.push_effect(Fxaa::new(make_pipeline("fxaa"))).is_err());
+}
+
+#[test]
+#[serial]
+fn test_build_empty_stack_fails() {
+    setup_engine_for_render_graph();
+    let mut rgm = RenderGraphManager::new();
+    let input = make_target(&mut rgm, "hdr");
+    let output = make_target(&mut rgm, "ldr");
+
+    let mut stack = PostStack::new("view");
+    assert!(stack.build(&mut rgm, input, output).is_err());
+    assert!(!stack.is_built());
+}
+
+#[test]
+#[serial]
+fn test_build_buffer_input_fails() {
+    setup_engine_for_render_graph();
+    let mut rgm = RenderGraphManager::new();
+    let input = rgm.create_graph_resource("buffer",
+        GraphResource::Buffer(crate::resource::resource_manager::BufferKey::default()),
+    ).unwrap();
+    let output = make_target(&mut rgm, "ldr");
+
+    let mut stack = PostStack::new("view");
+    stack.push_effect(Fxaa::new(make_pipeline("fxaa"))).unwrap();
+    assert!(stack.build(&mut rgm, input, output).is_err());
+}
+
+/// Effect whose single pass does not write `PostSlot::Output`.
+struct DanglingEffect;
+
+impl PostEffect for DanglingEffect {
+    fn name(&self) -> &str {
+        "dangling"
+    }
+
+    fn passes(&self) -> Vec<PostPass> {
+        vec![PostPass {
+            pipeline: make_pipeline("dangling"),
+            inputs: vec![PostSlot::Input],
+            output: PostSlot::Target(0),
+        }]
+    }
+}
+
+#[test]
+#[serial]
+fn test_build_effect_not_writing_output_fails() {
+    setup_engine_for_render_graph();
+    let mut rgm = RenderGraphManager::new();
+    let input = make_target(&mut rgm, "hdr");
+    let output = make_target(&mut rgm, "ldr");
+
+    let mut stack = PostStack::new("view");
+    stack.push_effect(DanglingEffect).unwrap();
+    assert!(stack.build(&mut rgm, input, output).is_err());
+}
+
+/// Effect writing the same target from two passes.
+struct DoubleWriteEffect;
+
+impl PostEffect for DoubleWriteEffect {
+    fn name(&self) -> &str {
+        "double_write"
+    }
+
+    fn targets(&self) -> Vec<PostTargetDesc> {
+        vec![PostTargetDesc { scale_divisor: 1, format: None }]
+    }
+
+    fn passes(&self) -> Vec<PostPass> {
+        vec![
+            PostPass { pipeline: make_pipeline("a"), inputs: vec![PostSlot::Input], output: PostSlot::Target(0) },
+            PostPass { pipeline: make_pipeline("b"), inputs: vec![PostSlot::Input], output: PostSlot::Target(0) },
+            PostPass { pipeline: make_pipeline("c"), inputs: vec![PostSlot::Target(0)], output: PostSlot::Output },
+        ]
+    }
+}
+
+#[test]
+#[serial]
+fn test_build_slot_written_twice_fails() {
+    setup_engine_for_render_graph();
+    let mut rgm = RenderGraphManager::new();
+    let input = make_target(&mut rgm, "hdr");
+    let output = make_target(&mut rgm, "ldr");
+
+    let mut stack = PostStack::new("view");
+    stack.push_effect(DoubleWriteEffect).unwrap();
+    assert!(stack.build(&mut rgm, input, output).is_err());
+}
+
+#[test]
+#[serial]
+fn test_built_passes_execute_in_render_graph() {
+    setup_engine_for_render_graph();
+    Engine::create_render_graph_manager().unwrap();
+    let rgm_arc = Engine::render_graph_manager().unwrap();
+    let mut rgm = rgm_arc.lock().unwrap();
+    let graph_key = rgm.create_render_graph("main", 1).unwrap();
+    let input = make_target(&mut rgm, "hdr");
+    let output = make_target(&mut rgm, "ldr");
+
+    // Scene pass writing the HDR target, followed by the post stack
+    let (action, counter) = make_recording_pass();
+    let scene_pass = rgm.create_render_pass("scene", vec![ResourceAccess {
+        graph_resource_key: input,
+        access_type: AccessType::ColorAttachmentWrite,
+        target_ops: Some(crate::render_graph::test_helpers::default_color_ops()),
+    }], action).unwrap();
+
+    let mut stack = PostStack::new("view");
+    stack.push_effect(make_bloom()).unwrap();
+    stack.push_effect(Tonemap::new(make_pipeline("tonemap"))).unwrap();
+    stack.push_effect(Fxaa::new(make_pipeline("fxaa"))).unwrap();
+    stack.push_effect(Fxaa::new(make_pipeline("fxaa"))).unwrap();
+    let mut passes = vec![scene_pass];
+    passes.extend_from_slice(stack.build(&mut rgm, input, output).unwrap());
+
+    rgm.execute_render_graph(graph_key, &passes, |_cmd| Ok(())).unwrap();
+    assert_eq!(counter.load(std::sync::atomic::Ordering::SeqCst), 1);
+}
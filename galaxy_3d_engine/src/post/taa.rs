@@ -0,0 +1,434 @@
+//! Temporal anti-aliasing: camera jitter, motion vectors and a history
+//! resolve pass.
+//!
+//! `build()` runs once, after the main scene pass exists:
+//! - a motion-vector target (`R16G16_SFLOAT`, cleared to 0) is appended to
+//!   the scene pass as an extra color attachment, at location
+//!   `motion_vector_location()`
+//! - two history textures (input format) are marked persistent in the
+//!   render graph, so their content survives from one frame to the next
+//! - two resolve passes are created, one per frame parity: each reads the
+//!   scene color, the motion vectors and one history, and writes the output
+//!   and the other history. The render graph orders them after the scene
+//!   pass (writer before reader).
+//!
+//! Every frame, `begin_frame()` jitters the camera (Halton sequence, see
+//! `taa_jitter`) and returns the resolve pass to execute with the scene
+//! passes. Call it before `Updater::update_frame` so the frame uniform gets
+//! the jittered matrices.
+//!
+//! Shader contract of the scene pass, drawn by a `ForwardDrawer` with
+//! `ForwardDrawerConfig::motion_vectors` (its pipelines then get
+//! `EngineFeatures::MOTION_VECTORS`): write the screen-space motion of the
+//! fragment at `motion_vector_location()`, from the frame uniform
+//! (`viewProjection` is jittered, `previousViewProjection` is not) and the
+//! instance `world` / `previousWorld` matrices:
+//!
+//! ```glsl
+//! // vertex
+//! currentClip  = frame.viewProjection * world * pos;                 // jittered
+//! previousClip = frame.previousViewProjection * previousWorld * pos; // unjittered
+//! // fragment: UV delta from the previous frame to this one, jitter removed
+//! motion = (currentClip.xy / currentClip.w - frame.jitter.xy
+//!         - previousClip.xy / previousClip.w) * 0.5;
+//! ```
+//!
+//! Blended (transparent) shaders write the motion with alpha 0, so the
+//! blend keeps the motion of the opaque surface behind them.
+//!
+//! Shader contract of the resolve pass:
+//! - set 0, bindings 0..3: scene color, motion vectors, history (linear clamp)
+//! - color attachments: 0 = output, 1 = new history
+//! - push constants (fragment stage, offset 0):
+//!   `{ vec2 jitterUv; float feedback; uint historyValid; }`
+//!
+//! ```glsl
+//! vec3 current = texture(color, uv - jitterUv).rgb;       // unjittered
+//! vec2 previousUv = uv - texture(motion, uv).xy;
+//! vec3 history = texture(history, previousUv).rgb;
+//! // clamp the history to the min / max of the 3x3 color neighborhood
+//! history = clamp(history, neighborhoodMin, neighborhoodMax);
+//! bool valid = historyValid != 0 && all(equal(previousUv, clamp(previousUv, 0.0, 1.0)));
+//! result = valid ? mix(current, history, feedback) : current;
+//! ```
+//!
+//! The scene pass must be single-sampled (resolve MSAA before TAA, or use
+//! TAA instead of MSAA).
+
+use std::sync::{Arc, Mutex};
+use glam::Vec2;
+use crate::error::Result;
+use crate::engine_bail;
+use crate::engine::Engine;
+use crate::camera::{taa_jitter, Camera, DEFAULT_TAA_SAMPLE_COUNT};
+use crate::graphics_device::{
+    self, AccessType, BindingGroupLayoutDesc, BindingResource, BindingSlotDesc, BindingType,
+    CommandList, LoadOp, MipmapMode, SampleCount, SamplerType, ShaderStageFlags, StoreOp,
+    TextureFormat, TextureUsage,
+};
+use crate::render_graph::{
+    GraphResource, GraphResourceKey, PassAction, RenderGraphManager, RenderPassKey,
+    ResourceAccess, TargetOps,
+};
+use crate::resource::resource_manager::{PassInfo, TextureKey};
+use crate::resource::texture::{LayerDesc, TextureDesc};
+
+/// Default weight of the history in the resolve
+pub const DEFAULT_TAA_FEEDBACK: f32 = 0.9;
+
+/// Format of the motion-vector target (screen-space UV delta)
+pub const MOTION_VECTOR_FORMAT: TextureFormat = TextureFormat::R16G16_SFLOAT;
+
+/// Number of history textures (read one, write the other)
+const HISTORY_COUNT: usize = 2;
+
+/// Per-frame values read by the resolve passes
+#[derive(Debug, Clone, Copy)]
+struct TaaFrame {
+    jitter_uv: Vec2,
+    feedback: f32,
+    history_valid: bool,
+}
+
+/// Temporal anti-aliasing of one scene pass.
+pub struct TemporalAa {
+    name: String,
+    pipeline: Arc<dyn graphics_device::Pipeline>,
+    /// Weight of the history in the resolve (0 = no accumulation)
+    pub feedback: f32,
+    /// Length of the jitter sequence
+    pub sample_count: u32,
+    /// Frames started since the build or the last `reset()`
+    frame_index: u64,
+    frame: Arc<Mutex<TaaFrame>>,
+    /// Resolve passes, indexed by frame parity (empty until built)
+    passes: Vec<RenderPassKey>,
+    motion_vectors: Option<GraphResourceKey>,
+    motion_vector_location: u32,
+    history: Vec<GraphResourceKey>,
+    /// Textures created by the build (motion vectors, then histories)
+    textures: Vec<TextureKey>,
+}
+
+impl TemporalAa {
+    /// Create the effect. `name` prefixes every pass and texture it creates.
+    /// `pipeline` follows the resolve shader contract (see module docs).
+    pub fn new(name: &str, pipeline: Arc<dyn graphics_device::Pipeline>) -> Self {
+        Self {
+            name: name.to_string(),
+            pipeline,
+            feedback: DEFAULT_TAA_FEEDBACK,
+            sample_count: DEFAULT_TAA_SAMPLE_COUNT,
+            frame_index: 0,
+            frame: Arc::new(Mutex::new(TaaFrame {
+                jitter_uv: Vec2::ZERO,
+                feedback: DEFAULT_TAA_FEEDBACK,
+                history_valid: false,
+            })),
+            passes: Vec::new(),
+            motion_vectors: None,
+            motion_vector_location: 0,
+            history: Vec::new(),
+            textures: Vec::new(),
+        }
+    }
+
+    // ===== ACCESSORS =====
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// True once `build()` has succeeded
+    pub fn is_built(&self) -> bool {
+        !self.passes.is_empty()
+    }
+
+    /// Resolve passes, indexed by frame parity
+    pub fn passes(&self) -> &[RenderPassKey] {
+        &self.passes
+    }
+
+    /// Motion-vector graph resource written by the scene pass
+    pub fn motion_vectors(&self) -> Option<GraphResourceKey> {
+        self.motion_vectors
+    }
+
+    /// Color attachment location of the motion vectors in the scene pass
+    pub fn motion_vector_location(&self) -> u32 {
+        self.motion_vector_location
+    }
+
+    /// History graph resources, indexed by the parity of the frame that
+    /// writes them
+    pub fn history(&self) -> &[GraphResourceKey] {
+        &self.history
+    }
+
+    /// Textures allocated by the build (motion vectors, then histories)
+    pub fn textures(&self) -> &[TextureKey] {
+        &self.textures
+    }
+
+    // ===== BUILD =====
+
+    /// Add the motion vectors to `scene_pass` and create the resolve passes
+    /// reading `color` (written by `scene_pass`) and writing `output`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if already built, if `scene_pass` is unknown or
+    /// multisampled, or if `color` or `output` is not a texture graph
+    /// resource.
+    pub fn build(
+        &mut self,
+        graph_manager: &mut RenderGraphManager,
+        scene_pass: RenderPassKey,
+        color: GraphResourceKey,
+        output: GraphResourceKey,
+    ) -> Result<&[RenderPassKey]> {
+        if self.is_built() {
+            engine_bail!("galaxy3d::TemporalAa", "TAA '{}' is already built", self.name);
+        }
+        let color_texture = Self::texture_key(graph_manager, color, "color")?;
+        Self::texture_key(graph_manager, output, "output")?;
+        let (mut scene_accesses, motion_location) = {
+            let pass = graph_manager.render_pass(scene_pass).ok_or_else(|| {
+                crate::engine_err!("galaxy3d::TemporalAa",
+                    "TAA '{}': scene RenderPassKey not found", self.name)
+            })?;
+            let pass_info = pass.pass_info().ok_or_else(|| {
+                crate::engine_err!("galaxy3d::TemporalAa",
+                    "TAA '{}': scene pass '{}' has no attachment", self.name, pass.name())
+            })?;
+            if pass_info.sample_count != SampleCount::S1 {
+                engine_bail!("galaxy3d::TemporalAa",
+                    "TAA '{}': scene pass '{}' must be single-sampled", self.name, pass.name());
+            }
+            (pass.accesses().to_vec(), pass_info.color_formats.len() as u32)
+        };
+
+        let rm_arc = Engine::resource_manager()?;
+        let gd_arc = Engine::graphics_device("main")?;
+        let color_info = {
+            let rm = rm_arc.lock().unwrap();
+            let texture = rm.texture(color_texture).ok_or_else(|| {
+                crate::engine_err!("galaxy3d::TemporalAa",
+                    "TAA '{}': color texture not found", self.name)
+            })?;
+            texture.graphics_device_texture().info().clone()
+        };
+
+        // Motion vectors: extra color attachment of the scene pass
+        let motion_name = format!("{}/motion_vectors", self.name);
+        let motion = self.create_target(graph_manager, &motion_name, &color_info, MOTION_VECTOR_FORMAT)?;
+        scene_accesses.push(ResourceAccess {
+            graph_resource_key: motion,
+            access_type: AccessType::ColorAttachmentWrite,
+            target_ops: Some(TargetOps::Color {
+                clear_color: [0.0, 0.0, 0.0, 0.0],
+                load_op: LoadOp::Clear,
+                store_op: StoreOp::Store,
+                resolve_target: None,
+            }),
+        });
+        graph_manager.replace_pass_accesses(scene_pass, scene_accesses)?;
+
+        let mut history = Vec::with_capacity(HISTORY_COUNT);
+        for i in 0..HISTORY_COUNT {
+            let name = format!("{}/history_{}", self.name, i);
+            let key = self.create_target(graph_manager, &name, &color_info, color_info.format)?;
+            graph_manager.set_graph_resource_persistent(key, true)?;
+            history.push(key);
+        }
+
+        // One resolve pass per parity: read history[1 - i], write history[i]
+        let mut passes = Vec::with_capacity(HISTORY_COUNT);
+        for (i, &written) in history.iter().enumerate() {
+            let read = history[(i + 1) % HISTORY_COUNT];
+            let inputs = [color, motion, read];
+            let mut accesses = Vec::with_capacity(inputs.len() + 2);
+            for key in inputs {
+                accesses.push(ResourceAccess {
+                    graph_resource_key: key,
+                    access_type: AccessType::FragmentShaderRead,
+                    target_ops: None,
+                });
+            }
+            for key in [output, written] {
+                accesses.push(ResourceAccess {
+                    graph_resource_key: key,
+                    access_type: AccessType::ColorAttachmentWrite,
+                    target_ops: Some(TargetOps::Color {
+                        clear_color: [0.0, 0.0, 0.0, 0.0],
+                        load_op: LoadOp::DontCare,
+                        store_op: StoreOp::Store,
+                        resolve_target: None,
+                    }),
+                });
+            }
+
+            let binding_group = {
+                let rm = rm_arc.lock().unwrap();
+                let gd = gd_arc.lock().unwrap();
+                let layout = BindingGroupLayoutDesc {
+                    entries: (0..inputs.len()).map(|binding| BindingSlotDesc {
+                        binding: binding as u32,
+                        binding_type: BindingType::CombinedImageSampler,
+                        count: 1,
+                        stage_flags: ShaderStageFlags::FRAGMENT,
+                    }).collect(),
+                };
+                let mut resources = Vec::with_capacity(inputs.len());
+                for key in inputs {
+                    let texture_key = Self::texture_key(graph_manager, key, "resolve input")?;
+                    let texture = rm.texture(texture_key).ok_or_else(|| {
+                        crate::engine_err!("galaxy3d::TemporalAa",
+                            "TAA '{}': resolve input texture not found", self.name)
+                    })?;
+                    resources.push(BindingResource::SampledTexture(
+                        texture.graphics_device_texture().as_ref(),
+                        SamplerType::LinearClamp,
+                    ));
+                }
+                gd.create_binding_group_from_layout(&layout, 0, &resources)?
+            };
+
+            let action = TaaResolveAction {
+                frame: self.frame.clone(),
+                pipeline: self.pipeline.clone(),
+                binding_group,
+                push_constants: Vec::new(),
+            };
+            let pass_name = format!("{}/resolve_{}", self.name, i);
+            passes.push(graph_manager.create_render_pass(&pass_name, accesses, Box::new(action))?);
+        }
+
+        self.motion_vectors = Some(motion);
+        self.motion_vector_location = motion_location;
+        self.history = history;
+        self.frame_index = 0;
+        self.passes = passes;
+        Ok(&self.passes)
+    }
+
+    // ===== PER FRAME =====
+
+    /// Start a frame: jitter `camera` and return the resolve pass to
+    /// execute after the scene pass. Call it before `update_frame`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the effect is not built.
+    pub fn begin_frame(&mut self, camera: &mut Camera) -> Result<RenderPassKey> {
+        if !self.is_built() {
+            engine_bail!("galaxy3d::TemporalAa", "TAA '{}' is not built", self.name);
+        }
+        camera.set_jitter(taa_jitter(self.frame_index, self.sample_count));
+        *self.frame.lock().unwrap() = TaaFrame {
+            // NDC [-1, 1] to UV [0, 1]
+            jitter_uv: camera.jitter_ndc() * 0.5,
+            feedback: self.feedback,
+            history_valid: self.frame_index > 0,
+        };
+        let pass = self.passes[(self.frame_index % HISTORY_COUNT as u64) as usize];
+        self.frame_index += 1;
+        Ok(pass)
+    }
+
+    /// Discard the history (camera cut, teleport): the next frame shows
+    /// the current image only.
+    pub fn reset(&mut self) {
+        self.frame_index = 0;
+    }
+
+    // ===== PRIVATE HELPERS =====
+
+    /// Texture key behind a graph resource, or an error if it is a buffer.
+    fn texture_key(
+        graph_manager: &RenderGraphManager,
+        key: GraphResourceKey,
+        what: &str,
+    ) -> Result<TextureKey> {
+        match graph_manager.graph_resource(key) {
+            Some(GraphResource::Texture { texture_key, .. }) => Ok(texture_key),
+            Some(GraphResource::Buffer(_)) => {
+                engine_bail!("galaxy3d::TemporalAa", "TAA {} must be a texture, got a buffer", what)
+            }
+            None => engine_bail!("galaxy3d::TemporalAa", "TAA {}: GraphResourceKey not found", what),
+        }
+    }
+
+    /// Create a full-resolution sampled render target and register it as a
+    /// graph resource.
+    fn create_target(
+        &mut self,
+        graph_manager: &mut RenderGraphManager,
+        name: &str,
+        color_info: &graphics_device::TextureInfo,
+        format: TextureFormat,
+    ) -> Result<GraphResourceKey> {
+        let rm_arc = Engine::resource_manager()?;
+        let gd_arc = Engine::graphics_device("main")?;
+        let texture_key = rm_arc.lock().unwrap().create_texture(name.to_string(), TextureDesc {
+            graphics_device: gd_arc,
+            texture: graphics_device::TextureDesc {
+                width: color_info.width,
+                height: color_info.height,
+                depth: 1,
+                format,
+                usage: TextureUsage::SampledAndRenderTarget,
+                texture_type: graphics_device::TextureType::Tex2D,
+                sample_count: SampleCount::S1,
+                array_layers: 1,
+                data: None,
+                mipmap: MipmapMode::None,
+                debug_name: None,
+            },
+            layers: vec![LayerDesc {
+                name: "default".to_string(),
+                layer_index: 0,
+                data: None,
+                regions: vec![],
+            }],
+        })?;
+        self.textures.push(texture_key);
+
+        graph_manager.create_graph_resource(name, GraphResource::Texture {
+            texture_key,
+            base_mip_level: 0,
+            base_array_layer: 0,
+            layer_count: 1,
+        })
+    }
+}
+
+// ===== RESOLVE PASS ACTION =====
+
+/// Pass action recording one TAA resolve pass.
+struct TaaResolveAction {
+    frame: Arc<Mutex<TaaFrame>>,
+    pipeline: Arc<dyn graphics_device::Pipeline>,
+    binding_group: Arc<dyn graphics_device::BindingGroup>,
+    /// Push constant bytes, reused across frames
+    push_constants: Vec<u8>,
+}
+
+impl PassAction for TaaResolveAction {
+    fn execute(&mut self, cmd: &mut dyn CommandList, _pass_info: &PassInfo) -> Result<()> {
+        let frame = *self.frame.lock().unwrap();
+        self.push_constants.clear();
+        self.push_constants.extend_from_slice(bytemuck::bytes_of(&frame.jitter_uv));
+        self.push_constants.extend_from_slice(bytemuck::bytes_of(&frame.feedback));
+        self.push_constants.extend_from_slice(bytemuck::bytes_of(&(frame.history_valid as u32)));
+
+        cmd.bind_pipeline(&self.pipeline)?;
+        cmd.bind_binding_group(&self.pipeline, 0, &self.binding_group)?;
+        cmd.push_constants(ShaderStageFlags::FRAGMENT, 0, &self.push_constants)?;
+        cmd.draw(3, 0)
+    }
+}
+
+#[cfg(test)]
+#[path = "taa_tests.rs"]
+mod tests;
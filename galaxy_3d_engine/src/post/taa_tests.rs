@@ -0,0 +1,236 @@
+//! Tests for TemporalAa
+//!
+//! Build tests rely on the global Engine + a `MockGraphicsDevice` and are
+//! `#[serial]` because they share global state.
+
+use super::*;
+use glam::Mat4;
+use crate::camera::Frustum;
+use crate::graphics_device::mock_graphics_device::{MockBindingGroup, MockCommandList, MockPipeline};
+use crate::graphics_device::{TextureType, Viewport};
+use crate::render_graph::test_helpers::{
+    default_color_ops, make_recording_pass, setup_engine_for_render_graph,
+};
+use serial_test::serial;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+fn make_pipeline(name: &str) -> Arc<dyn graphics_device::Pipeline> {
+    Arc::new(MockPipeline::new(name.to_string()))
+}
+
+fn make_camera() -> Camera {
+    let viewport = Viewport { x: 0.0, y: 0.0, width: 64.0, height: 64.0, min_depth: 0.0, max_depth: 1.0 };
+    Camera::new(Mat4::IDENTITY, Mat4::IDENTITY, Frustum::from_view_projection(&Mat4::IDENTITY), viewport)
+}
+
+/// Register a 64x64 HDR color texture and wrap it in a graph resource.
+fn make_target(rgm: &mut RenderGraphManager, name: &str, sample_count: SampleCount) -> GraphResourceKey {
+    let rm_arc = Engine::resource_manager().unwrap();
+    let gd_arc = Engine::graphics_device("main").unwrap();
+    let texture_key = rm_arc.lock().unwrap().create_texture(name.to_string(), TextureDesc {
+        graphics_device: gd_arc,
+        texture: graphics_device::TextureDesc {
+            width: 64, height: 64, depth: 1,
+            format: TextureFormat::R16G16B16A16_SFLOAT,
+            usage: TextureUsage::SampledAndRenderTarget,
+            texture_type: TextureType::Tex2D,
+            sample_count,
+            array_layers: 1,
+            data: None,
+            mipmap: MipmapMode::None,
+            debug_name: None,
+        },
+        layers: vec![LayerDesc { name: "default".to_string(), layer_index: 0, data: None, regions: vec![] }],
+    }).unwrap();
+    rgm.create_graph_resource(name, GraphResource::Texture {
+        texture_key, base_mip_level: 0, base_array_layer: 0, layer_count: 1,
+    }).unwrap()
+}
+
+struct Fixture {
+    color: GraphResourceKey,
+    output: GraphResourceKey,
+    scene_pass: RenderPassKey,
+    scene_counter: Arc<AtomicU32>,
+}
+
+/// Scene pass writing an HDR color target, plus the TAA output target
+fn make_fixture(rgm: &mut RenderGraphManager) -> Fixture {
+    let color = make_target(rgm, "hdr", SampleCount::S1);
+    let output = make_target(rgm, "taa_output", SampleCount::S1);
+    let (action, scene_counter) = make_recording_pass();
+    let scene_pass = rgm.create_render_pass("scene", vec![ResourceAccess {
+        graph_resource_key: color,
+        access_type: AccessType::ColorAttachmentWrite,
+        target_ops: Some(default_color_ops()),
+    }], action).unwrap();
+    Fixture { color, output, scene_pass, scene_counter }
+}
+
+// ============================================================================
+// Construction (no Engine)
+// ============================================================================
+
+#[test]
+fn test_new_taa_is_not_built() {
+    let mut taa = TemporalAa::new("view_taa", make_pipeline("resolve"));
+    assert_eq!(taa.name(), "view_taa");
+    assert_eq!(taa.feedback, DEFAULT_TAA_FEEDBACK);
+    assert_eq!(taa.sample_count, DEFAULT_TAA_SAMPLE_COUNT);
+    assert!(!taa.is_built());
+    assert!(taa.motion_vectors().is_none());
+    assert!(taa.begin_frame(&mut make_camera()).is_err());
+}
+
+#[test]
+fn test_resolve_action_push_constants() {
+    let frame = Arc::new(Mutex::new(TaaFrame {
+        jitter_uv: Vec2::new(0.25, -0.5),
+        feedback: 0.8,
+        history_valid: true,
+    }));
+    let mut action = TaaResolveAction {
+        frame,
+        pipeline: make_pipeline("resolve"),
+        binding_group: Arc::new(MockBindingGroup::new("taa_bg".to_string(), 0)),
+        push_constants: Vec::new(),
+    };
+    let mut cmd = MockCommandList::new();
+    let pass_info = PassInfo::new(vec![TextureFormat::R16G16B16A16_SFLOAT; 2], None, SampleCount::S1);
+    action.execute(&mut cmd, &pass_info).unwrap();
+    action.execute(&mut cmd, &pass_info).unwrap();
+
+    assert_eq!(&cmd.commands[..4], &["bind_pipeline", "bind_binding_group", "push_constants", "draw"]);
+    // Cleared every frame, not accumulated
+    assert_eq!(action.push_constants.len(), 16);
+    let floats: &[f32] = bytemuck::cast_slice(&action.push_constants[..12]);
+    assert_eq!(floats, &[0.25, -0.5, 0.8]);
+    assert_eq!(u32::from_ne_bytes(action.push_constants[12..16].try_into().unwrap()), 1);
+}
+
+// ============================================================================
+// Build (Engine-backed)
+// ============================================================================
+
+#[test]
+#[serial]
+fn test_build_adds_motion_vectors_to_scene_pass() {
+    setup_engine_for_render_graph();
+    let mut rgm = RenderGraphManager::new();
+    let fixture = make_fixture(&mut rgm);
+
+    let mut taa = TemporalAa::new("view_taa", make_pipeline("resolve"));
+    taa.build(&mut rgm, fixture.scene_pass, fixture.color, fixture.output).unwrap();
+
+    let motion = taa.motion_vectors().unwrap();
+    assert_eq!(taa.motion_vector_location(), 1);
+    let scene = rgm.render_pass(fixture.scene_pass).unwrap();
+    assert_eq!(scene.accesses().len(), 2);
+    assert_eq!(scene.accesses()[1].graph_resource_key, motion);
+    assert_eq!(scene.accesses()[1].access_type, AccessType::ColorAttachmentWrite);
+    assert_eq!(scene.pass_info().unwrap().color_formats,
+        vec![TextureFormat::R16G16B16A16_SFLOAT, MOTION_VECTOR_FORMAT]);
+}
+
+#[test]
+#[serial]
+fn test_build_creates_persistent_history_and_resolve_passes() {
+    setup_engine_for_render_graph();
+    let mut rgm = RenderGraphManager::new();
+    let fixture = make_fixture(&mut rgm);
+
+    let mut taa = TemporalAa::new("view_taa", make_pipeline("resolve"));
+    let passes = taa.build(&mut rgm, fixture.scene_pass, fixture.color, fixture.output).unwrap().to_vec();
+    assert!(taa.is_built());
+    assert_eq!(passes.len(), 2);
+    // Motion vectors + 2 histories
+    assert_eq!(taa.textures().len(), 3);
+
+    let history = taa.history().to_vec();
+    assert!(history.iter().all(|key| rgm.is_graph_resource_persistent(*key)));
+    assert!(!rgm.is_graph_resource_persistent(taa.motion_vectors().unwrap()));
+
+    for (i, pass_key) in passes.iter().enumerate() {
+        let pass = rgm.render_pass(*pass_key).unwrap();
+        assert_eq!(pass.name(), format!("view_taa/resolve_{}", i));
+        let keys: Vec<_> = pass.accesses().iter().map(|a| a.graph_resource_key).collect();
+        assert_eq!(keys, vec![
+            fixture.color, taa.motion_vectors().unwrap(), history[1 - i], fixture.output, history[i],
+        ]);
+    }
+
+    assert!(taa.build(&mut rgm, fixture.scene_pass, fixture.color, fixture.output).is_err());
+}
+
+#[test]
+#[serial]
+fn test_build_rejects_multisampled_scene_pass() {
+    setup_engine_for_render_graph();
+    let mut rgm = RenderGraphManager::new();
+    let color = make_target(&mut rgm, "hdr_msaa", SampleCount::S4);
+    let output = make_target(&mut rgm, "taa_output", SampleCount::S1);
+    let (action, _) = make_recording_pass();
+    let scene_pass = rgm.create_render_pass("scene", vec![ResourceAccess {
+        graph_resource_key: color,
+        access_type: AccessType::ColorAttachmentWrite,
+        target_ops: Some(default_color_ops()),
+    }], action).unwrap();
+
+    let mut taa = TemporalAa::new("view_taa", make_pipeline("resolve"));
+    assert!(taa.build(&mut rgm, scene_pass, color, output).is_err());
+    assert!(!taa.is_built());
+}
+
+// ============================================================================
+// Per frame (Engine-backed)
+// ============================================================================
+
+#[test]
+#[serial]
+fn test_begin_frame_alternates_passes_and_jitters_camera() {
+    setup_engine_for_render_graph();
+    let mut rgm = RenderGraphManager::new();
+    let fixture = make_fixture(&mut rgm);
+    let mut taa = TemporalAa::new("view_taa", make_pipeline("resolve"));
+    let passes = taa.build(&mut rgm, fixture.scene_pass, fixture.color, fixture.output).unwrap().to_vec();
+
+    let mut camera = make_camera();
+    assert_eq!(taa.begin_frame(&mut camera).unwrap(), passes[0]);
+    assert_eq!(camera.jitter(), taa_jitter(0, DEFAULT_TAA_SAMPLE_COUNT));
+    assert!(!taa.frame.lock().unwrap().history_valid);
+
+    assert_eq!(taa.begin_frame(&mut camera).unwrap(), passes[1]);
+    assert_eq!(camera.jitter(), taa_jitter(1, DEFAULT_TAA_SAMPLE_COUNT));
+    let frame = *taa.frame.lock().unwrap();
+    assert!(frame.history_valid);
+    assert_eq!(frame.jitter_uv, camera.jitter_ndc() * 0.5);
+
+    taa.reset();
+    assert_eq!(taa.begin_frame(&mut camera).unwrap(), passes[0]);
+    assert!(!taa.frame.lock().unwrap().history_valid);
+}
+
+#[test]
+#[serial]
+fn test_resolve_runs_after_scene_pass_and_carries_history() {
+    setup_engine_for_render_graph();
+    Engine::create_render_graph_manager().unwrap();
+    let rgm_arc = Engine::render_graph_manager().unwrap();
+    let mut rgm = rgm_arc.lock().unwrap();
+    let graph_key = rgm.create_render_graph("main", 1).unwrap();
+    let fixture = make_fixture(&mut rgm);
+    let mut taa = TemporalAa::new("view_taa", make_pipeline("resolve"));
+    taa.build(&mut rgm, fixture.scene_pass, fixture.color, fixture.output).unwrap();
+
+    let mut camera = make_camera();
+    for frame in 0..2 {
+        // Listed in any order: the graph sorts the resolve after the scene pass
+        let resolve = taa.begin_frame(&mut camera).unwrap();
+        rgm.execute_render_graph(graph_key, &[resolve, fixture.scene_pass], |_cmd| Ok(())).unwrap();
+        assert_eq!(fixture.scene_counter.load(Ordering::SeqCst), frame + 1);
+
+        let written = taa.history()[frame as usize];
+        assert_eq!(rgm.render_graph(graph_key).unwrap().persistent_access(written),
+            Some(AccessType::ColorAttachmentWrite));
+    }
+}
@@ -0,0 +1,339 @@
+//! Per-view render targets sized by the view's render scale.
+//!
+//! Each `RenderView` can render at its own resolution
+//! (`RenderView::set_render_scale`). `ViewTargetManager` allocates the color
+//! (and optional depth) texture of a view at `RenderView::scaled_extent()` of
+//! the output size, and registers them as `GraphResource`s for the view's
+//! scene pass. The scaled color is then brought back to output resolution
+//! by a `PostStack` ending with an `Upscale` effect (the composite pass).
+//!
+//! Target sizes are fixed once created, as the framebuffers and binding
+//! groups of the passes using them are. When the render scale of a view
+//! changes (`is_stale()`), remove its targets, create them again and
+//! rebuild the passes that used them.
+//!
+//! Render to texture: the color target of a view (security camera, portal,
+//! mirror) can be bound in a material texture slot through
+//! `ViewTarget::color_texture`. A pass drawing such materials must read the
+//! target so the graph orders it after the view's pass and transitions the
+//! texture to a sampled layout: `bind_sampled_targets()` adds those reads
+//! to the pass from the materials it draws (`sampled_accesses()` builds
+//! them for `create_render_pass`).
+
+use rustc_hash::FxHashMap;
+use crate::error::Result;
+use crate::engine_bail;
+use crate::engine::Engine;
+use crate::graphics_device::{self, MipmapMode, TextureFormat, TextureUsage};
+use crate::render_graph::{AccessType, GraphResource, GraphResourceKey, RenderGraphManager, RenderPassKey, ResourceAccess};
+use crate::resource::resource_manager::{MaterialKey, ResourceManager, TextureKey};
+use crate::resource::texture::{LayerDesc, TextureDesc};
+use crate::scene::RenderView;
+
+/// Formats and output size of a view's targets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ViewTargetDesc {
+    /// Output resolution the view is composited to
+    pub output_width: u32,
+    pub output_height: u32,
+    /// Scene color format (sampled by the composite pass)
+    pub color_format: TextureFormat,
+    /// Depth format (None = no depth target)
+    pub depth_format: Option<TextureFormat>,
+}
+
+/// Targets allocated for one view.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViewTarget {
+    /// Scene color, at the scaled resolution
+    pub color: GraphResourceKey,
+    /// Texture behind `color`, to bind in a material texture slot
+    pub color_texture: TextureKey,
+    /// Scene depth, at the scaled resolution
+    pub depth: Option<GraphResourceKey>,
+    /// Scaled resolution
+    pub width: u32,
+    pub height: u32,
+    /// Render scale the targets were sized for
+    pub render_scale: f32,
+}
+
+/// Textures behind a view target, for removal
+struct ViewTargetEntry {
+    target: ViewTarget,
+    textures: Vec<TextureKey>,
+}
+
+/// Allocates and tracks the render targets of each view.
+pub struct ViewTargetManager {
+    name: String,
+    targets: FxHashMap<String, ViewTargetEntry>,
+}
+
+impl ViewTargetManager {
+    /// Create an empty manager. `name` prefixes every texture and graph
+    /// resource it creates.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            targets: FxHashMap::default(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Targets of a view, if created
+    pub fn view_target(&self, view_name: &str) -> Option<&ViewTarget> {
+        self.targets.get(view_name).map(|entry| &entry.target)
+    }
+
+    pub fn view_target_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Whether the targets of a view no longer match its render scale
+    /// (false for a view without targets)
+    pub fn is_stale(&self, view_name: &str, view: &RenderView) -> bool {
+        self.view_target(view_name)
+            .is_some_and(|target| target.render_scale != view.render_scale())
+    }
+
+    /// Allocate the targets of a view at its scaled resolution.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the view already has targets, if the output
+    /// size is zero or if the depth format is not a depth format.
+    pub fn create_view_target(
+        &mut self,
+        graph_manager: &mut RenderGraphManager,
+        view_name: &str,
+        view: &RenderView,
+        desc: &ViewTargetDesc,
+    ) -> Result<ViewTarget> {
+        if self.targets.contains_key(view_name) {
+            engine_bail!("galaxy3d::ViewTargetManager",
+                "View '{}' of '{}' already has targets", view_name, self.name);
+        }
+        if desc.output_width == 0 || desc.output_height == 0 {
+            engine_bail!("galaxy3d::ViewTargetManager",
+                "View '{}': output size must be non-zero, got {}x{}",
+                view_name, desc.output_width, desc.output_height);
+        }
+        if let Some(format) = desc.depth_format {
+            if !format.is_depth() {
+                engine_bail!("galaxy3d::ViewTargetManager",
+                    "View '{}': {:?} is not a depth format", view_name, format);
+            }
+        }
+
+        let (width, height) = view.scaled_extent(desc.output_width, desc.output_height);
+        let prefix = format!("{}/{}", self.name, view_name);
+        let mut textures = Vec::with_capacity(2);
+        let (color, depth) = match Self::create_targets(
+            graph_manager, &prefix, width, height, desc, &mut textures,
+        ) {
+            Ok(keys) => keys,
+            Err(err) => {
+                Self::release(graph_manager, &prefix, &textures);
+                return Err(err);
+            }
+        };
+
+        let target = ViewTarget {
+            color,
+            color_texture: textures[0],
+            depth,
+            width,
+            height,
+            render_scale: view.render_scale(),
+        };
+        self.targets.insert(view_name.to_string(), ViewTargetEntry { target, textures });
+        Ok(target)
+    }
+
+    /// Remove the targets of a view (graph resources and textures).
+    /// Returns false if the view has none.
+    ///
+    /// Passes still referencing the graph resources must be rebuilt or
+    /// removed before the next graph execution.
+    pub fn remove_view_target(
+        &mut self,
+        graph_manager: &mut RenderGraphManager,
+        view_name: &str,
+    ) -> bool {
+        let Some(entry) = self.targets.remove(view_name) else {
+            return false;
+        };
+        let prefix = format!("{}/{}", self.name, view_name);
+        Self::release(graph_manager, &prefix, &entry.textures);
+        true
+    }
+
+    // ===== SAMPLED TARGETS =====
+
+    /// Fragment-shader reads of the view color targets sampled by
+    /// `materials`, in first-use order (one per target).
+    pub fn sampled_accesses(&self, rm: &ResourceManager, materials: &[MaterialKey]) -> Vec<ResourceAccess> {
+        let mut accesses: Vec<ResourceAccess> = Vec::new();
+        let slots = materials.iter()
+            .filter_map(|key| rm.material(*key))
+            .flat_map(|material| material.iter_all_texture_slots());
+        for slot in slots {
+            let Some(target) = self.target_of_texture(slot.texture()) else {
+                continue;
+            };
+            if accesses.iter().any(|a| a.graph_resource_key == target.color) {
+                continue;
+            }
+            accesses.push(ResourceAccess {
+                graph_resource_key: target.color,
+                access_type: AccessType::FragmentShaderRead,
+                target_ops: None,
+            });
+        }
+        accesses
+    }
+
+    /// Replace the view target reads of a pass with the ones its
+    /// `materials` need (see `sampled_accesses()`), keeping its other
+    /// accesses. Returns the number of sampled targets.
+    ///
+    /// Call it again when the materials drawn by the pass change. Locks
+    /// `Engine::resource_manager()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pass does not exist or writes a target it
+    /// would sample (a feedback loop).
+    pub fn bind_sampled_targets(
+        &self,
+        graph_manager: &mut RenderGraphManager,
+        pass_key: RenderPassKey,
+        materials: &[MaterialKey],
+    ) -> Result<usize> {
+        let sampled = {
+            let rm_arc = Engine::resource_manager()?;
+            let rm = rm_arc.lock().unwrap();
+            self.sampled_accesses(&rm, materials)
+        };
+        let Some(pass) = graph_manager.render_pass(pass_key) else {
+            engine_bail!("galaxy3d::ViewTargetManager",
+                "bind_sampled_targets: RenderPassKey not found");
+        };
+        let is_target_read = |access: &ResourceAccess| {
+            access.access_type == AccessType::FragmentShaderRead
+                && self.targets.values().any(|entry| entry.target.color == access.graph_resource_key)
+        };
+        let mut accesses: Vec<ResourceAccess> = pass.accesses().iter()
+            .filter(|access| !is_target_read(access))
+            .copied()
+            .collect();
+        for read in &sampled {
+            let written = accesses.iter().any(|access| {
+                access.graph_resource_key == read.graph_resource_key && access.access_type.is_write()
+            });
+            if written {
+                engine_bail!("galaxy3d::ViewTargetManager",
+                    "Pass '{}' samples the view target '{}' it renders to",
+                    pass.name(),
+                    graph_manager.graph_resource_name(read.graph_resource_key).unwrap_or("?"));
+            }
+        }
+        accesses.extend_from_slice(&sampled);
+        graph_manager.replace_pass_accesses(pass_key, accesses)?;
+        Ok(sampled.len())
+    }
+
+    // ===== PRIVATE HELPERS =====
+
+    /// Target whose color texture is `texture`
+    fn target_of_texture(&self, texture: TextureKey) -> Option<&ViewTarget> {
+        self.targets.values()
+            .map(|entry| &entry.target)
+            .find(|target| target.color_texture == texture)
+    }
+
+    /// Create the color and depth targets of a view
+    fn create_targets(
+        graph_manager: &mut RenderGraphManager,
+        prefix: &str,
+        width: u32,
+        height: u32,
+        desc: &ViewTargetDesc,
+        textures: &mut Vec<TextureKey>,
+    ) -> Result<(GraphResourceKey, Option<GraphResourceKey>)> {
+        let color = Self::create_texture(
+            graph_manager, &format!("{}/color", prefix), width, height,
+            desc.color_format, TextureUsage::SampledAndRenderTarget, textures,
+        )?;
+        let depth = desc.depth_format.map(|format| Self::create_texture(
+            graph_manager, &format!("{}/depth", prefix), width, height,
+            format, TextureUsage::DepthStencil, textures,
+        )).transpose()?;
+        Ok((color, depth))
+    }
+
+    /// Create a render target texture and register it as a graph resource.
+    fn create_texture(
+        graph_manager: &mut RenderGraphManager,
+        name: &str,
+        width: u32,
+        height: u32,
+        format: TextureFormat,
+        usage: TextureUsage,
+        textures: &mut Vec<TextureKey>,
+    ) -> Result<GraphResourceKey> {
+        let rm_arc = Engine::resource_manager()?;
+        let gd_arc = Engine::graphics_device("main")?;
+        let texture_key = rm_arc.lock().unwrap().create_texture(name.to_string(), TextureDesc {
+            graphics_device: gd_arc,
+            texture: graphics_device::TextureDesc {
+                width,
+                height,
+                depth: 1,
+                format,
+                usage,
+                texture_type: graphics_device::TextureType::Tex2D,
+                sample_count: graphics_device::SampleCount::S1,
+                array_layers: 1,
+                data: None,
+                mipmap: MipmapMode::None,
+                debug_name: None,
+            },
+            layers: vec![LayerDesc {
+                name: "default".to_string(),
+                layer_index: 0,
+                data: None,
+                regions: vec![],
+            }],
+        })?;
+        textures.push(texture_key);
+
+        graph_manager.create_graph_resource(name, GraphResource::Texture {
+            texture_key,
+            base_mip_level: 0,
+            base_array_layer: 0,
+            layer_count: 1,
+        })
+    }
+
+    /// Drop the graph resources and textures of a view.
+    fn release(graph_manager: &mut RenderGraphManager, prefix: &str, textures: &[TextureKey]) {
+        for suffix in ["color", "depth"] {
+            graph_manager.remove_graph_resource_by_name(&format!("{}/{}", prefix, suffix));
+        }
+        if let Ok(rm_arc) = Engine::resource_manager() {
+            let mut rm = rm_arc.lock().unwrap();
+            for key in textures {
+                rm.remove_texture(*key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "view_targets_tests.rs"]
+mod tests;
@@ -0,0 +1,229 @@
+//! Tests for ViewTargetManager
+//!
+//! Tests rely on the global Engine + a `MockGraphicsDevice` and are
+//! `#[serial]` because they share global state.
+
+use super::*;
+use crate::camera::{Camera, Frustum};
+use crate::graphics_device::Viewport;
+use crate::render_graph::test_helpers::setup_engine_for_render_graph;
+use glam::Mat4;
+use serial_test::serial;
+
+fn make_view(render_scale: f32) -> RenderView {
+    let viewport = Viewport { x: 0.0, y: 0.0, width: 1920.0, height: 1080.0, min_depth: 0.0, max_depth: 1.0 };
+    let camera = Camera::new(Mat4::IDENTITY, Mat4::IDENTITY, Frustum::from_view_projection(&Mat4::IDENTITY), viewport);
+    let mut view = RenderView::new(camera, 0);
+    view.set_render_scale(render_scale).unwrap();
+    view
+}
+
+fn make_desc(depth_format: Option<TextureFormat>) -> ViewTargetDesc {
+    ViewTargetDesc {
+        output_width: 1920,
+        output_height: 1080,
+        color_format: TextureFormat::R16G16B16A16_SFLOAT,
+        depth_format,
+    }
+}
+
+fn texture_size(graph_manager: &RenderGraphManager, key: GraphResourceKey) -> (u32, u32) {
+    let Some(GraphResource::Texture { texture_key, .. }) = graph_manager.graph_resource(key) else {
+        panic!("not a texture graph resource");
+    };
+    let rm_arc = Engine::resource_manager().unwrap();
+    let rm = rm_arc.lock().unwrap();
+    let info = rm.texture(texture_key).unwrap().graphics_device_texture().info().clone();
+    (info.width, info.height)
+}
+
+// ============================================================================
+// Allocation
+// ============================================================================
+
+#[test]
+#[serial]
+fn test_targets_use_the_scaled_extent() {
+    setup_engine_for_render_graph();
+    let mut rgm = RenderGraphManager::new();
+    let mut manager = ViewTargetManager::new("views");
+
+    let target = manager.create_view_target(
+        &mut rgm, "left", &make_view(0.5), &make_desc(Some(TextureFormat::D32_FLOAT)),
+    ).unwrap();
+
+    assert_eq!((target.width, target.height), (960, 540));
+    assert_eq!(texture_size(&rgm, target.color), (960, 540));
+    assert_eq!(texture_size(&rgm, target.depth.unwrap()), (960, 540));
+    assert_eq!(rgm.graph_resource_id("views/left/color"), Some(target.color));
+    assert_eq!(manager.view_target("left"), Some(&target));
+}
+
+#[test]
+#[serial]
+fn test_each_view_gets_its_own_resolution() {
+    setup_engine_for_render_graph();
+    let mut rgm = RenderGraphManager::new();
+    let mut manager = ViewTargetManager::new("views");
+
+    let left = manager.create_view_target(&mut rgm, "left", &make_view(1.0), &make_desc(None)).unwrap();
+    let right = manager.create_view_target(&mut rgm, "right", &make_view(0.75), &make_desc(None)).unwrap();
+
+    assert_eq!(texture_size(&rgm, left.color), (1920, 1080));
+    assert_eq!(texture_size(&rgm, right.color), (1440, 810));
+    assert!(right.depth.is_none());
+    assert_eq!(manager.view_target_count(), 2);
+}
+
+#[test]
+#[serial]
+fn test_invalid_requests_are_rejected() {
+    setup_engine_for_render_graph();
+    let mut rgm = RenderGraphManager::new();
+    let mut manager = ViewTargetManager::new("views");
+    let view = make_view(1.0);
+
+    let zero = ViewTargetDesc { output_width: 0, ..make_desc(None) };
+    assert!(manager.create_view_target(&mut rgm, "main", &view, &zero).is_err());
+    let bad_depth = make_desc(Some(TextureFormat::R8G8B8A8_UNORM));
+    assert!(manager.create_view_target(&mut rgm, "main", &view, &bad_depth).is_err());
+
+    manager.create_view_target(&mut rgm, "main", &view, &make_desc(None)).unwrap();
+    assert!(manager.create_view_target(&mut rgm, "main", &view, &make_desc(None)).is_err());
+}
+
+// ============================================================================
+// Reallocation
+// ============================================================================
+
+#[test]
+#[serial]
+fn test_scale_change_marks_stale_and_reallocates() {
+    setup_engine_for_render_graph();
+    let mut rgm = RenderGraphManager::new();
+    let mut manager = ViewTargetManager::new("views");
+    let mut view = make_view(1.0);
+    manager.create_view_target(&mut rgm, "main", &view, &make_desc(Some(TextureFormat::D32_FLOAT))).unwrap();
+    let texture_count = Engine::resource_manager().unwrap().lock().unwrap().texture_count();
+
+    assert!(!manager.is_stale("main", &view));
+    view.set_render_scale(0.5).unwrap();
+    assert!(manager.is_stale("main", &view));
+    assert!(!manager.is_stale("other", &view));
+
+    assert!(manager.remove_view_target(&mut rgm, "main"));
+    assert!(!manager.remove_view_target(&mut rgm, "main"));
+    assert!(rgm.graph_resource_id("views/main/color").is_none());
+    assert!(rgm.graph_resource_id("views/main/depth").is_none());
+    assert_eq!(Engine::resource_manager().unwrap().lock().unwrap().texture_count(), texture_count - 2);
+
+    let target = manager.create_view_target(&mut rgm, "main", &view, &make_desc(None)).unwrap();
+    assert_eq!(texture_size(&rgm, target.color), (960, 540));
+    assert!(!manager.is_stale("main", &view));
+}
+
+// ============================================================================
+// Sampled targets
+// ============================================================================
+
+/// Material of the Engine ResourceManager sampling `texture` in pass 0
+fn make_sampling_material(name: &str, texture: TextureKey) -> MaterialKey {
+    use crate::graphics_device::{PolygonMode, SamplerType, ShaderStage};
+    use crate::resource::material::{MaterialDesc, MaterialPassDesc, MaterialTextureSlotDesc};
+    use crate::resource::shader::ShaderDesc;
+    let rm_arc = Engine::resource_manager().unwrap();
+    let gd_arc = Engine::graphics_device("main").unwrap();
+    let mut rm = rm_arc.lock().unwrap();
+    let fragment_shader = rm.create_shader(
+        format!("{}_frag", name),
+        ShaderDesc { code: &[], stage: ShaderStage::Fragment, entry_point: "main".to_string() },
+        &mut *gd_arc.lock().unwrap(),
+    ).unwrap();
+    let material = rm.create_material(name.to_string(), MaterialDesc {
+        passes: vec![MaterialPassDesc {
+            pass_type: 0,
+            fragment_shader,
+            color_blend: Default::default(),
+            polygon_mode: PolygonMode::Fill,
+            textures: vec![MaterialTextureSlotDesc {
+                name: "screen".to_string(),
+                texture,
+                layer: None,
+                region: None,
+                sampler_type: SamplerType::LinearClamp,
+                flipbook: None,
+            }],
+            params: vec![],
+            render_state: None,
+            engine_features: Default::default(),
+            render_queue: None,
+        }],
+    }, &*gd_arc.lock().unwrap()).unwrap();
+    material
+}
+
+fn write_access(key: GraphResourceKey) -> ResourceAccess {
+    ResourceAccess {
+        graph_resource_key: key,
+        access_type: AccessType::ColorAttachmentWrite,
+        target_ops: Some(crate::render_graph::test_helpers::default_color_ops()),
+    }
+}
+
+#[test]
+#[serial]
+fn test_sampled_accesses_follow_material_textures() {
+    let env = setup_engine_for_render_graph();
+    let mut rgm = RenderGraphManager::new();
+    let mut manager = ViewTargetManager::new("views");
+    let camera = manager.create_view_target(&mut rgm, "camera", &make_view(1.0), &make_desc(None)).unwrap();
+
+    let monitor = make_sampling_material("monitor", camera.color_texture);
+    let monitor_copy = make_sampling_material("monitor_copy", camera.color_texture);
+    let plain = make_sampling_material("plain", env.color_texture);
+
+    let rm_arc = Engine::resource_manager().unwrap();
+    let rm = rm_arc.lock().unwrap();
+    let accesses = manager.sampled_accesses(&rm, &[plain, monitor, monitor_copy]);
+    assert_eq!(accesses.len(), 1);
+    assert_eq!(accesses[0].graph_resource_key, camera.color);
+    assert_eq!(accesses[0].access_type, AccessType::FragmentShaderRead);
+    assert!(accesses[0].target_ops.is_none());
+    assert!(manager.sampled_accesses(&rm, &[plain]).is_empty());
+}
+
+#[test]
+#[serial]
+fn test_bind_sampled_targets_orders_passes_and_rejects_feedback() {
+    use crate::render_graph::test_helpers::make_recording_pass;
+    let env = setup_engine_for_render_graph();
+    let mut rgm = RenderGraphManager::new();
+    let mut manager = ViewTargetManager::new("views");
+    let camera = manager.create_view_target(&mut rgm, "camera", &make_view(0.5), &make_desc(None)).unwrap();
+    let monitor = make_sampling_material("monitor", camera.color_texture);
+    let main_color = rgm.create_graph_resource("main_color", GraphResource::Texture {
+        texture_key: env.color_texture, base_mip_level: 0, base_array_layer: 0, layer_count: 1,
+    }).unwrap();
+
+    let (action, _) = make_recording_pass();
+    let camera_pass = rgm.create_render_pass("camera", vec![write_access(camera.color)], action).unwrap();
+    let (action, _) = make_recording_pass();
+    let main_pass = rgm.create_render_pass("main", vec![write_access(main_color)], action).unwrap();
+
+    assert_eq!(manager.bind_sampled_targets(&mut rgm, main_pass, &[monitor]).unwrap(), 1);
+    // Binding again replaces the read instead of stacking it
+    assert_eq!(manager.bind_sampled_targets(&mut rgm, main_pass, &[monitor]).unwrap(), 1);
+    assert_eq!(rgm.render_pass(main_pass).unwrap().accesses().len(), 2);
+
+    // The reader runs after the writer whatever the submission order
+    let graph = rgm.create_render_graph("frame", 1).unwrap();
+    rgm.execute_render_graph(graph, &[main_pass, camera_pass], |_| Ok(())).unwrap();
+    assert_eq!(rgm.render_graph(graph).unwrap().executed_passes(), &[camera_pass, main_pass]);
+
+    // Unbinding drops the read
+    assert_eq!(manager.bind_sampled_targets(&mut rgm, main_pass, &[]).unwrap(), 0);
+    assert_eq!(rgm.render_pass(main_pass).unwrap().accesses().len(), 1);
+
+    assert!(manager.bind_sampled_targets(&mut rgm, camera_pass, &[monitor]).is_err());
+    assert_eq!(rgm.render_pass(camera_pass).unwrap().accesses().len(), 1);
+}
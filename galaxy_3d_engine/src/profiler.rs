@@ -0,0 +1,367 @@
+//! CPU frame profiler for Galaxy3D Engine
+//!
+//! This module measures where CPU frame time goes:
+//! - RAII zones opened with `profile_scope!("culling")`, closed at end of scope
+//! - Nested zones and zones from any thread
+//! - Per-frame aggregation (calls, total and max time per zone name)
+//! - Export hook called with each finished frame (overlay, Tracy bridge, ...)
+//! - `ChromeTrace` writer producing chrome://tracing / Perfetto JSON
+//!
+//! The profiler is disabled by default: a disabled zone costs one relaxed
+//! atomic load. Zones closed outside `Profiler::begin_frame()` /
+//! `Profiler::end_frame()` are ignored.
+
+use std::cell::Cell;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use crate::error::Result;
+
+/// Maximum number of zones recorded per frame; extra zones are counted in
+/// `FrameProfile::dropped_zones`
+pub const MAX_ZONES_PER_FRAME: usize = 65536;
+
+/// Chrome trace thread ID of the per-frame events
+const FRAME_TRACK_ID: u32 = 0;
+
+/// Chrome trace process ID of every event
+const TRACE_PROCESS_ID: u32 = 1;
+
+/// Callback receiving each finished frame
+pub type ProfilerHook = Box<dyn FnMut(&FrameProfile) + Send>;
+
+/// Fast path: checked when a zone opens
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Time origin of every timestamp
+static EPOCH: OnceLock<Instant> = OnceLock::new();
+
+/// Next profiler thread ID (0 is the frame track)
+static NEXT_THREAD_ID: AtomicU32 = AtomicU32::new(FRAME_TRACK_ID + 1);
+
+/// Global profiler state
+static STATE: Mutex<ProfilerState> = Mutex::new(ProfilerState {
+    frame_start: None,
+    frame_index: 0,
+    zones: Vec::new(),
+    dropped_zones: 0,
+    hook: None,
+});
+
+thread_local! {
+    /// Profiler ID of the current thread (assigned on first zone)
+    static THREAD_ID: Cell<u32> = const { Cell::new(0) };
+    /// Number of zones currently open on this thread
+    static DEPTH: Cell<u32> = const { Cell::new(0) };
+}
+
+struct ProfilerState {
+    /// Start of the current frame, relative to `EPOCH` (None outside a frame)
+    frame_start: Option<Duration>,
+    frame_index: u64,
+    zones: Vec<ZoneRecord>,
+    dropped_zones: u32,
+    hook: Option<ProfilerHook>,
+}
+
+fn now() -> Duration {
+    EPOCH.get_or_init(Instant::now).elapsed()
+}
+
+fn current_thread_id() -> u32 {
+    THREAD_ID.with(|id| {
+        if id.get() == FRAME_TRACK_ID {
+            id.set(NEXT_THREAD_ID.fetch_add(1, Ordering::Relaxed));
+        }
+        id.get()
+    })
+}
+
+// ===== ZONES =====
+
+/// One closed zone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZoneRecord {
+    /// Name given to `profile_scope!`
+    pub name: &'static str,
+    /// Profiler thread ID (1 for the first thread that opened a zone, ...)
+    pub thread: u32,
+    /// Number of enclosing zones open on the same thread
+    pub depth: u32,
+    /// Start time, relative to the profiler epoch
+    pub start: Duration,
+    pub duration: Duration,
+}
+
+/// RAII zone guard created by `profile_scope!`
+///
+/// The zone is measured from creation to drop.
+pub struct ProfileScope {
+    name: &'static str,
+    /// None when the profiler was disabled at creation
+    start: Option<Duration>,
+    depth: u32,
+}
+
+impl ProfileScope {
+    /// Open a zone (prefer the `profile_scope!` macro)
+    #[inline]
+    pub fn new(name: &'static str) -> Self {
+        if !ENABLED.load(Ordering::Relaxed) {
+            return Self { name, start: None, depth: 0 };
+        }
+        let depth = DEPTH.with(|d| {
+            let depth = d.get();
+            d.set(depth + 1);
+            depth
+        });
+        Self { name, start: Some(now()), depth }
+    }
+}
+
+impl Drop for ProfileScope {
+    fn drop(&mut self) {
+        let Some(start) = self.start else {
+            return;
+        };
+        let end = now();
+        DEPTH.with(|d| d.set(self.depth));
+        let record = ZoneRecord {
+            name: self.name,
+            thread: current_thread_id(),
+            depth: self.depth,
+            start,
+            duration: end.saturating_sub(start),
+        };
+        let mut state = STATE.lock().unwrap();
+        if state.frame_start.is_none() {
+            return;
+        }
+        if state.zones.len() < MAX_ZONES_PER_FRAME {
+            state.zones.push(record);
+        } else {
+            state.dropped_zones += 1;
+        }
+    }
+}
+
+/// Measure the rest of the enclosing scope as a named profiler zone
+///
+/// ```ignore
+/// {
+///     galaxy_3d_engine::profile_scope!("culling");
+///     culler.cull_into(&scene, &camera, index, &mut visible);
+/// }
+/// ```
+#[macro_export]
+macro_rules! profile_scope {
+    ($name:expr) => {
+        let _profile_scope = $crate::profiler::ProfileScope::new($name);
+    };
+}
+
+// ===== FRAME PROFILE =====
+
+/// Aggregated timings of one zone name over a frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZoneStats {
+    pub name: &'static str,
+    /// Number of zones closed with this name
+    pub calls: u32,
+    /// Sum of their durations (nested zones of the same name count twice)
+    pub total: Duration,
+    pub max: Duration,
+}
+
+/// Zones recorded between `Profiler::begin_frame()` and `Profiler::end_frame()`
+#[derive(Debug, Clone, Default)]
+pub struct FrameProfile {
+    /// Frame number (incremented by each `begin_frame()`)
+    pub index: u64,
+    /// Frame start, relative to the profiler epoch
+    pub start: Duration,
+    pub duration: Duration,
+    /// Zones in closing order
+    pub zones: Vec<ZoneRecord>,
+    /// Zones ignored because the frame already held `MAX_ZONES_PER_FRAME`
+    pub dropped_zones: u32,
+    /// Per-name aggregation, sorted by decreasing total time
+    pub stats: Vec<ZoneStats>,
+}
+
+impl FrameProfile {
+    fn new(index: u64, start: Duration, end: Duration, zones: Vec<ZoneRecord>, dropped_zones: u32) -> Self {
+        let mut stats: Vec<ZoneStats> = Vec::new();
+        for zone in &zones {
+            match stats.iter_mut().find(|s| s.name == zone.name) {
+                Some(s) => {
+                    s.calls += 1;
+                    s.total += zone.duration;
+                    s.max = s.max.max(zone.duration);
+                }
+                None => stats.push(ZoneStats {
+                    name: zone.name,
+                    calls: 1,
+                    total: zone.duration,
+                    max: zone.duration,
+                }),
+            }
+        }
+        stats.sort_by(|a, b| b.total.cmp(&a.total).then(a.name.cmp(b.name)));
+        Self {
+            index,
+            start,
+            duration: end.saturating_sub(start),
+            zones,
+            dropped_zones,
+            stats,
+        }
+    }
+
+    /// Aggregated timings of a zone name
+    pub fn stat(&self, name: &str) -> Option<&ZoneStats> {
+        self.stats.iter().find(|s| s.name == name)
+    }
+}
+
+// ===== PROFILER =====
+
+/// Global CPU frame profiler
+///
+/// Per frame:
+/// 1. `Profiler::begin_frame()` at the top of the frame loop;
+/// 2. `profile_scope!(name)` in the code to measure (the engine already
+///    measures culling, drawing, updaters and render graph execution);
+/// 3. `Profiler::end_frame()` at the bottom. It returns the frame profile
+///    and passes it to the export hook.
+pub struct Profiler;
+
+impl Profiler {
+    /// Enable or disable zone recording (disabled by default)
+    pub fn set_enabled(enabled: bool) {
+        if enabled {
+            EPOCH.get_or_init(Instant::now);
+        }
+        ENABLED.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn is_enabled() -> bool {
+        ENABLED.load(Ordering::Relaxed)
+    }
+
+    /// Start a frame, discarding the zones of a frame never ended
+    pub fn begin_frame() {
+        let mut state = STATE.lock().unwrap();
+        state.frame_start = Some(now());
+        state.frame_index += 1;
+        state.zones.clear();
+        state.dropped_zones = 0;
+    }
+
+    /// End the frame started by `begin_frame()`.
+    ///
+    /// Returns None when the profiler is disabled or no frame was started.
+    /// Zones still open (e.g. around the frame loop) are not part of it.
+    pub fn end_frame() -> Option<FrameProfile> {
+        let mut state = STATE.lock().unwrap();
+        let start = state.frame_start.take()?;
+        let zones = std::mem::take(&mut state.zones);
+        if !Self::is_enabled() {
+            return None;
+        }
+        let frame = FrameProfile::new(state.frame_index, start, now(), zones, state.dropped_zones);
+        if let Some(hook) = state.hook.as_mut() {
+            hook(&frame);
+        }
+        Some(frame)
+    }
+
+    /// Set the callback receiving each frame from `end_frame()` (None to
+    /// remove it).
+    ///
+    /// The hook runs with the profiler locked: it must not open zones.
+    pub fn set_export_hook(hook: Option<ProfilerHook>) {
+        STATE.lock().unwrap().hook = hook;
+    }
+}
+
+// ===== CHROME TRACE EXPORT =====
+
+/// Accumulates frames as Chrome trace events (JSON Object Format), loadable
+/// in chrome://tracing or Perfetto
+///
+/// Each frame is one event on track 0; zones go to the track of their
+/// thread.
+#[derive(Debug, Default)]
+pub struct ChromeTrace {
+    /// Comma-separated event objects
+    events: String,
+    event_count: usize,
+}
+
+impl ChromeTrace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of events added so far
+    pub fn event_count(&self) -> usize {
+        self.event_count
+    }
+
+    /// Append the frame and its zones
+    pub fn add_frame(&mut self, frame: &FrameProfile) {
+        let name = format!("Frame {}", frame.index);
+        self.push_event(&name, FRAME_TRACK_ID, frame.start, frame.duration);
+        for zone in &frame.zones {
+            self.push_event(zone.name, zone.thread, zone.start, zone.duration);
+        }
+    }
+
+    /// Complete trace document
+    pub fn to_json(&self) -> String {
+        format!("{{\"traceEvents\":[{}],\"displayTimeUnit\":\"ms\"}}", self.events)
+    }
+
+    /// Write the trace document to a file
+    pub fn save(&self, path: &str) -> Result<()> {
+        std::fs::write(path, self.to_json()).map_err(|e| {
+            crate::engine_err!("galaxy3d::ChromeTrace", "Failed to write '{}': {}", path, e)
+        })
+    }
+
+    fn push_event(&mut self, name: &str, thread: u32, start: Duration, duration: Duration) {
+        if self.event_count > 0 {
+            self.events.push(',');
+        }
+        self.events.push_str("{\"name\":\"");
+        push_json_escaped(&mut self.events, name);
+        let _ = write!(
+            self.events,
+            "\",\"ph\":\"X\",\"ts\":{:.3},\"dur\":{:.3},\"pid\":{},\"tid\":{}}}",
+            start.as_secs_f64() * 1e6,
+            duration.as_secs_f64() * 1e6,
+            TRACE_PROCESS_ID,
+            thread,
+        );
+        self.event_count += 1;
+    }
+}
+
+fn push_json_escaped(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "profiler_tests.rs"]
+mod tests;
@@ -0,0 +1,141 @@
+use super::*;
+use serial_test::serial;
+use std::sync::Arc;
+
+/// Enable the profiler with no hook and no open frame
+fn setup_profiler() {
+    Profiler::set_export_hook(None);
+    Profiler::end_frame();
+    Profiler::set_enabled(true);
+}
+
+// ============================================================================
+// Zones and frames
+// ============================================================================
+
+#[test]
+#[serial]
+fn test_zones_aggregated_per_frame() {
+    setup_profiler();
+    Profiler::begin_frame();
+    {
+        crate::profile_scope!("test_outer");
+        for _ in 0..3 {
+            crate::profile_scope!("test_inner");
+        }
+    }
+    let frame = Profiler::end_frame().unwrap();
+
+    let outer = frame.stat("test_outer").unwrap();
+    let inner = frame.stat("test_inner").unwrap();
+    assert_eq!(outer.calls, 1);
+    assert_eq!(inner.calls, 3);
+    assert!(inner.max <= inner.total);
+    assert!(outer.total >= inner.total);
+    assert!(frame.duration >= outer.total);
+
+    // Inner zones close first, one level deeper, on the same thread
+    let zones: Vec<_> = frame.zones.iter().filter(|z| z.name.starts_with("test_")).collect();
+    assert_eq!(zones.len(), 4);
+    assert_eq!(zones[3].name, "test_outer");
+    assert_eq!(zones[3].depth + 1, zones[0].depth);
+    assert!(zones.iter().all(|z| z.thread == zones[0].thread));
+    Profiler::set_enabled(false);
+}
+
+#[test]
+#[serial]
+fn test_zones_outside_frame_or_disabled_are_ignored() {
+    setup_profiler();
+    {
+        crate::profile_scope!("test_before_frame");
+    }
+    Profiler::begin_frame();
+    Profiler::set_enabled(false);
+    {
+        crate::profile_scope!("test_disabled");
+    }
+    assert!(Profiler::end_frame().is_none());
+
+    Profiler::set_enabled(true);
+    Profiler::begin_frame();
+    let frame = Profiler::end_frame().unwrap();
+    assert!(frame.stat("test_before_frame").is_none());
+    assert!(frame.stat("test_disabled").is_none());
+    // A second end_frame() has no frame to end
+    assert!(Profiler::end_frame().is_none());
+    Profiler::set_enabled(false);
+}
+
+#[test]
+#[serial]
+fn test_zones_from_other_threads_get_their_own_track() {
+    setup_profiler();
+    Profiler::begin_frame();
+    {
+        crate::profile_scope!("test_main_thread");
+    }
+    std::thread::spawn(|| {
+        crate::profile_scope!("test_worker_thread");
+    }).join().unwrap();
+    let frame = Profiler::end_frame().unwrap();
+
+    let thread_of = |name: &str| frame.zones.iter().find(|z| z.name == name).unwrap().thread;
+    assert_ne!(thread_of("test_main_thread"), thread_of("test_worker_thread"));
+    assert_ne!(thread_of("test_worker_thread"), FRAME_TRACK_ID);
+    Profiler::set_enabled(false);
+}
+
+#[test]
+#[serial]
+fn test_export_hook_receives_each_frame() {
+    setup_profiler();
+    let frames = Arc::new(Mutex::new(Vec::new()));
+    let sink = frames.clone();
+    Profiler::set_export_hook(Some(Box::new(move |frame: &FrameProfile| {
+        sink.lock().unwrap().push(frame.index);
+    })));
+
+    Profiler::begin_frame();
+    let first = Profiler::end_frame().unwrap();
+    Profiler::begin_frame();
+    let second = Profiler::end_frame().unwrap();
+    Profiler::set_export_hook(None);
+    Profiler::begin_frame();
+    Profiler::end_frame();
+
+    assert_eq!(second.index, first.index + 1);
+    assert_eq!(*frames.lock().unwrap(), vec![first.index, second.index]);
+    Profiler::set_enabled(false);
+}
+
+// ============================================================================
+// Chrome trace export
+// ============================================================================
+
+#[test]
+fn test_chrome_trace_json() {
+    let zone = ZoneRecord {
+        name: "say \"hi\"",
+        thread: 3,
+        depth: 0,
+        start: Duration::from_micros(1500),
+        duration: Duration::from_micros(250),
+    };
+    let frame = FrameProfile::new(7, Duration::from_millis(1), Duration::from_millis(3), vec![zone], 0);
+    assert_eq!(frame.stats, vec![ZoneStats {
+        name: "say \"hi\"", calls: 1, total: zone.duration, max: zone.duration,
+    }]);
+
+    let mut trace = ChromeTrace::new();
+    assert_eq!(trace.to_json(), "{\"traceEvents\":[],\"displayTimeUnit\":\"ms\"}");
+    trace.add_frame(&frame);
+    assert_eq!(trace.event_count(), 2);
+    assert_eq!(
+        trace.to_json(),
+        "{\"traceEvents\":[\
+         {\"name\":\"Frame 7\",\"ph\":\"X\",\"ts\":1000.000,\"dur\":2000.000,\"pid\":1,\"tid\":0},\
+         {\"name\":\"say \\\"hi\\\"\",\"ph\":\"X\",\"ts\":1500.000,\"dur\":250.000,\"pid\":1,\"tid\":3}\
+         ],\"displayTimeUnit\":\"ms\"}"
+    );
+}
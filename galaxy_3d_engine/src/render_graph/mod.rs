@@ -14,17 +14,24 @@ mod access_type;
 mod frame_buffer;
 mod graph_resource;
 mod pass_action;
+mod readback_manager;
 mod render_graph;
 mod render_graph_manager;
 mod render_pass;
+mod uniform_ring;
 
 #[cfg(test)]
-mod test_helpers;
+pub(crate) mod test_helpers;
 
 pub use access_type::{AccessType, ResourceAccess, TargetOps};
 pub use frame_buffer::{ColorAttachmentSlot, Framebuffer, FramebufferKey};
 pub use graph_resource::{GraphResource, GraphResourceKey};
-pub use pass_action::{PassAction, FullscreenAction, CustomAction, ScenePassAction, SceneBinding};
+pub use pass_action::{
+    PassAction, PassActionSummary, FullscreenAction, CustomAction, ScenePassAction, SceneBinding,
+    RayTracedShadowAction,
+};
+pub use readback_manager::{ReadbackManager, ReadbackTicket, ReadbackCallback};
 pub use render_graph::{RenderGraph, RenderGraphKey};
 pub use render_graph_manager::RenderGraphManager;
 pub use render_pass::{RenderPass, RenderPassKey};
+pub use uniform_ring::{UniformRing, UniformRingDesc};
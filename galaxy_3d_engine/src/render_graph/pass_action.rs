@@ -1,22 +1,53 @@
 /// Pass action trait and implementations.
 ///
 /// Defines how a render pass records its draw commands between
-/// begin_render_pass() and end_render_pass().
+/// begin_render_pass() and end_render_pass(), and what it reports about
+/// them in `Engine::dump_frame_state()` (`PassActionSummary`).
 
 use std::sync::{Arc, Mutex};
 use crate::error::Result;
 use crate::engine::Engine;
 use crate::graphics_device::{self, CommandList, BindingGroup, BindingResource, BindingGroupLayoutDesc, BindingSlotDesc, BindingType, ShaderStageFlags, SamplerType};
-use crate::resource::resource_manager::PassInfo;
+use crate::camera::Camera;
+use crate::resource::resource_manager::{PassInfo, MaterialKey, PipelineKey};
 use crate::resource::buffer::Buffer;
 use crate::resource::texture::Texture;
 use crate::scene::RenderView;
-use crate::scene::{Scene, Drawer};
+use crate::scene::{Scene, Drawer, RayTracingScene};
 
 /// Action executed by a render pass.
 pub trait PassAction: Send + Sync {
     /// Record draw commands into the command list.
     fn execute(&mut self, cmd: &mut dyn CommandList, pass_info: &PassInfo) -> Result<()>;
+
+    /// What the action draws, for frame-state reports. Defaults to the
+    /// action type name alone.
+    fn summary(&self) -> PassActionSummary {
+        let type_name = std::any::type_name::<Self>();
+        PassActionSummary::new(type_name.rsplit("::").next().unwrap_or(type_name))
+    }
+}
+
+/// Description of a pass action in `Engine::dump_frame_state()`.
+#[derive(Debug, Clone, Default)]
+pub struct PassActionSummary {
+    /// Action type (e.g. "ScenePassAction")
+    pub kind: &'static str,
+    /// Camera of the drawn render view
+    pub camera: Option<Camera>,
+    /// Submeshes in the drawn render view
+    pub visible_submeshes: usize,
+    /// Distinct pipelines cached by the drawn submeshes (first-draw order)
+    pub pipelines: Vec<PipelineKey>,
+    /// Distinct materials of the drawn submeshes (first-draw order)
+    pub materials: Vec<MaterialKey>,
+}
+
+impl PassActionSummary {
+    /// Summary with a kind and nothing drawn
+    pub fn new(kind: &'static str) -> Self {
+        Self { kind, ..Default::default() }
+    }
 }
 
 /// Fullscreen pass action (data-driven, no closure)
@@ -157,6 +188,122 @@ impl PassAction for ScenePassAction {
         }
         Ok(())
     }
+
+    fn summary(&self) -> PassActionSummary {
+        let mut summary = PassActionSummary::new("ScenePassAction");
+        let scene = self.scene.lock().unwrap();
+        let view = self.render_view.lock().unwrap();
+        let Some(ref view) = *view else {
+            return summary;
+        };
+        summary.camera = Some(view.camera().clone());
+        summary.visible_submeshes = view.len();
+        for item in view.iter() {
+            let pass = scene.render_instance(item.key)
+                .and_then(|instance| instance.sub_mesh(item.submesh_index as usize))
+                .and_then(|sub_mesh| sub_mesh.pass_by_index(item.pass_index as usize));
+            let Some(pass) = pass else {
+                continue;
+            };
+            if !summary.materials.contains(&pass.material()) {
+                summary.materials.push(pass.material());
+            }
+            if let Some(pipeline) = pass.cached_pipeline_key() {
+                if !summary.pipelines.contains(&pipeline) {
+                    summary.pipelines.push(pipeline);
+                }
+            }
+        }
+        summary
+    }
+}
+
+// ===== RAY TRACED SHADOW ACTION =====
+
+/// Ray-traced shadow pass action — one shadow ray per pixel.
+///
+/// Rebuilds the TLAS of a `RayTracingScene` (its `update()` must have run
+/// this frame), then launches a ray tracing pipeline over `extent`. The
+/// pass has no attachment: it runs outside any render pass, after the
+/// barriers of its accesses (depth as `AccessType::RayTracingRead`, the
+/// shadow mask as `AccessType::RayTracingWrite`).
+///
+/// The binding group (set 1) is built by the caller from the pipeline;
+/// binding 0 is the TLAS (`RayTracingScene::tlas`). Shader contract:
+///
+/// ```glsl
+/// layout(set = 1, binding = 0) uniform accelerationStructureEXT tlas;
+/// layout(push_constant) uniform Shadow {
+///     vec4 lightDirection;   // xyz: towards the light, w: max ray distance
+///     uvec2 extent;
+/// };
+/// // raygen: world position of the pixel from the depth, then
+/// traceRayEXT(tlas, gl_RayFlagsTerminateOnFirstHitEXT | gl_RayFlagsSkipClosestHitShaderEXT,
+///             RT_MASK_SHADOW_CASTER, 0, 0, 0, position + normal * bias, 0.0,
+///             lightDirection.xyz, lightDirection.w, 0);
+/// // miss 0: the payload is set to 1.0 (lit); it starts at 0.0 (shadowed)
+/// ```
+pub struct RayTracedShadowAction {
+    ray_tracing_scene: Arc<Mutex<RayTracingScene>>,
+    pipeline: Arc<dyn graphics_device::Pipeline>,
+    binding_group: Arc<dyn BindingGroup>,
+    light_direction: glam::Vec3,
+    max_distance: f32,
+    extent: (u32, u32),
+}
+
+impl RayTracedShadowAction {
+    /// Create the action for a shadow mask of `extent` pixels.
+    ///
+    /// `light_direction` points towards the light (normalized here).
+    pub fn new(
+        ray_tracing_scene: Arc<Mutex<RayTracingScene>>,
+        pipeline: Arc<dyn graphics_device::Pipeline>,
+        binding_group: Arc<dyn BindingGroup>,
+        light_direction: glam::Vec3,
+        max_distance: f32,
+        extent: (u32, u32),
+    ) -> Self {
+        Self {
+            ray_tracing_scene,
+            pipeline,
+            binding_group,
+            light_direction: light_direction.normalize_or_zero(),
+            max_distance,
+            extent,
+        }
+    }
+
+    /// Set the direction towards the light (normalized here)
+    pub fn set_light_direction(&mut self, light_direction: glam::Vec3) {
+        self.light_direction = light_direction.normalize_or_zero();
+    }
+
+    /// Set the shadow mask size (e.g. after a resize)
+    pub fn set_extent(&mut self, width: u32, height: u32) {
+        self.extent = (width, height);
+    }
+
+    /// Push constant block of the shader contract
+    fn push_constant_bytes(&self) -> [u8; 24] {
+        let mut bytes = [0u8; 24];
+        let floats = [
+            self.light_direction.x, self.light_direction.y, self.light_direction.z, self.max_distance,
+        ];
+        bytes[..16].copy_from_slice(bytemuck::cast_slice(&floats));
+        bytes[16..].copy_from_slice(bytemuck::cast_slice(&[self.extent.0, self.extent.1]));
+        bytes
+    }
+}
+
+impl PassAction for RayTracedShadowAction {
+    fn execute(&mut self, cmd: &mut dyn CommandList, _pass_info: &PassInfo) -> Result<()> {
+        self.ray_tracing_scene.lock().unwrap().record_build(cmd)?;
+        cmd.bind_pipeline(&self.pipeline)?;
+        cmd.bind_binding_group(&self.pipeline, 1, &self.binding_group)?;
+        cmd.push_constants(ShaderStageFlags::RAYGEN, 0, &self.push_constant_bytes())?;
+        cmd.trace_rays(self.extent.0, self.extent.1, 1)
+    }
 }
 
 #[cfg(test)]
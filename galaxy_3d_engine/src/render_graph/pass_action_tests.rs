@@ -182,3 +182,58 @@ mod scene_pass_action {
         assert!(cmd.commands.iter().any(|c| c == "set_viewport"));
     }
 }
+
+// ============================================================================
+// RayTracedShadowAction
+// ============================================================================
+
+mod ray_traced_shadow_action {
+    use super::*;
+    use crate::graphics_device::mock_graphics_device::MockGraphicsDevice;
+    use crate::scene::RayTracingScene;
+    use std::sync::Mutex;
+
+    fn make_action() -> RayTracedShadowAction {
+        let mut gd = MockGraphicsDevice::new();
+        gd.ray_tracing = true;
+        let rt_scene = Arc::new(Mutex::new(RayTracingScene::new(&mut gd, 8).unwrap()));
+        let pipeline: Arc<dyn crate::graphics_device::Pipeline> =
+            Arc::new(MockPipeline::new("rt_shadow".to_string()));
+        let binding_group: Arc<dyn crate::graphics_device::BindingGroup> =
+            Arc::new(MockBindingGroup::new("rt_shadow_bg".to_string(), 1));
+        RayTracedShadowAction::new(
+            rt_scene, pipeline, binding_group, glam::Vec3::new(0.0, 2.0, 0.0), 100.0, (64, 32),
+        )
+    }
+
+    #[test]
+    fn test_execute_builds_tlas_then_traces() {
+        let mut action = make_action();
+        let mut cmd = MockCommandList::new();
+        action.execute(&mut cmd, &make_pass_info()).unwrap();
+        assert_eq!(cmd.commands, vec![
+            "build_tlas 0", "bind_pipeline", "bind_binding_group", "push_constants", "trace_rays 64x32",
+        ]);
+    }
+
+    #[test]
+    fn test_set_extent() {
+        let mut action = make_action();
+        action.set_extent(128, 96);
+        let mut cmd = MockCommandList::new();
+        action.execute(&mut cmd, &make_pass_info()).unwrap();
+        assert_eq!(cmd.commands.last().unwrap(), "trace_rays 128x96");
+    }
+
+    #[test]
+    fn test_push_constants_layout() {
+        let mut action = make_action();
+        action.set_light_direction(glam::Vec3::new(3.0, 0.0, 4.0));
+        let bytes = action.push_constant_bytes();
+        let words: Vec<[u8; 4]> = bytes.chunks_exact(4).map(|c| c.try_into().unwrap()).collect();
+        let floats: Vec<f32> = words[..4].iter().map(|w| f32::from_ne_bytes(*w)).collect();
+        assert_eq!(floats, [0.6, 0.0, 0.8, 100.0]);
+        assert_eq!(u32::from_ne_bytes(words[4]), 64);
+        assert_eq!(u32::from_ne_bytes(words[5]), 32);
+    }
+}
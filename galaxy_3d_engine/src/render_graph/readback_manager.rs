@@ -0,0 +1,278 @@
+/// Asynchronous GPU → CPU readbacks.
+///
+/// `ReadbackManager` schedules copies of texture regions or buffer ranges
+/// into host-visible staging buffers and hands the bytes back once the
+/// command list that recorded them has completed, without ever waiting on
+/// the GPU. Typical users: entity picking (`Picker`), luminance histograms
+/// for auto-exposure, GPU culling statistics, screenshots.
+///
+/// Completion is tracked by frame count, like the command list ring of a
+/// `RenderGraph`: a copy recorded in frame N is read after frame
+/// N + `frames_in_flight`, whose command list reuse waited for frame N's
+/// fence. Per frame:
+/// 1. `read_texture()` / `read_buffer()` record copies, outside a render pass
+///    (typically in the `post_passes` callback of
+///    `RenderGraphManager::execute_render_graph`), and return a ticket;
+///    `on_complete()` optionally attaches a callback to it;
+/// 2. `poll()` once after the render graph has executed. It fires the
+///    callbacks of completed readbacks and keeps the other results until
+///    `take(ticket)`.
+///
+/// Staging buffers are created through the "main" graphics device on first
+/// use and recycled once their readback has completed.
+
+use std::sync::Arc;
+use rustc_hash::FxHashMap;
+use crate::error::Result;
+use crate::engine::Engine;
+use crate::graphics_device::{
+    Buffer, BufferAccess, BufferDesc, BufferUsage, CommandList, ImageAccess, Rect2D,
+    TextureCopyRegion,
+};
+
+/// Smallest staging buffer created, in bytes
+const MIN_STAGING_SIZE: u64 = 256;
+
+/// Maximum number of idle staging buffers kept for reuse
+const MAX_FREE_STAGING_BUFFERS: usize = 16;
+
+/// Handle of a scheduled readback
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ReadbackTicket(u64);
+
+/// Callback receiving the bytes of a completed readback
+pub type ReadbackCallback = Box<dyn FnOnce(&[u8]) + Send>;
+
+/// Copy recorded in a frame, waiting for its command list
+struct PendingReadback {
+    ticket: ReadbackTicket,
+    staging: Arc<dyn Buffer>,
+    /// Capacity of `staging`, in bytes
+    capacity: u64,
+    /// Number of bytes copied at the start of `staging`
+    size: u64,
+    /// Remaining `poll()` calls before the copy is guaranteed complete
+    frames_remaining: usize,
+    callback: Option<ReadbackCallback>,
+}
+
+/// Scheduler of asynchronous readbacks (see module docs).
+pub struct ReadbackManager {
+    frames_in_flight: usize,
+    next_ticket: u64,
+    pending: Vec<PendingReadback>,
+    /// Idle staging buffers with their capacity
+    free_staging: Vec<(Arc<dyn Buffer>, u64)>,
+    /// Completed readbacks without callback, until `take()`
+    completed: FxHashMap<ReadbackTicket, Vec<u8>>,
+    /// Reused scratch of `read_texture()`
+    regions: Vec<TextureCopyRegion>,
+}
+
+impl ReadbackManager {
+    /// Create a manager for a render graph with `frames_in_flight` command
+    /// lists.
+    pub fn new(frames_in_flight: usize) -> Result<Self> {
+        if frames_in_flight == 0 {
+            crate::engine_bail!("galaxy3d::ReadbackManager", "frames_in_flight must be at least 1");
+        }
+        Ok(Self {
+            frames_in_flight,
+            next_ticket: 0,
+            pending: Vec::new(),
+            free_staging: Vec::new(),
+            completed: FxHashMap::default(),
+            regions: Vec::new(),
+        })
+    }
+
+    /// Number of command lists a readback waits for
+    pub fn frames_in_flight(&self) -> usize {
+        self.frames_in_flight
+    }
+
+    /// Record the copy of texel rectangles of a color texture (mip 0,
+    /// layer 0).
+    ///
+    /// The result holds the rectangles one after the other, each tightly
+    /// packed row after row, with `format.bytes_per_pixel()` bytes per texel.
+    /// `src.access_type` must be `AccessType::TransferRead`, with
+    /// `previous_access_type` set to how the texture was last written.
+    pub fn read_texture(
+        &mut self,
+        cmd: &mut dyn CommandList,
+        src: &ImageAccess,
+        rects: &[Rect2D],
+    ) -> Result<ReadbackTicket> {
+        let info = src.texture.info();
+        if info.format.is_depth() {
+            crate::engine_bail!("galaxy3d::ReadbackManager",
+                "read_texture: depth format {:?} cannot be read back", info.format);
+        }
+        if rects.is_empty() {
+            crate::engine_bail!("galaxy3d::ReadbackManager", "read_texture: no rectangle to read");
+        }
+        let texel_size = info.format.bytes_per_pixel() as u64;
+        self.regions.clear();
+        let mut size = 0;
+        for rect in rects {
+            let inside = rect.x >= 0 && rect.y >= 0
+                && rect.x as u64 + rect.width as u64 <= info.width as u64
+                && rect.y as u64 + rect.height as u64 <= info.height as u64;
+            if !inside || rect.width == 0 || rect.height == 0 {
+                crate::engine_bail!("galaxy3d::ReadbackManager",
+                    "read_texture: rectangle {:?} outside the {}x{} texture",
+                    rect, info.width, info.height);
+            }
+            self.regions.push(TextureCopyRegion { rect: *rect, buffer_offset: size });
+            size += rect.width as u64 * rect.height as u64 * texel_size;
+        }
+
+        let (staging, capacity) = self.acquire_staging(size)?;
+        if let Err(e) = cmd.copy_texture_to_buffer(src, &staging, &self.regions) {
+            self.release_staging(staging, capacity);
+            return Err(e);
+        }
+        Ok(self.push_pending(staging, capacity, size))
+    }
+
+    /// Record the copy of `size` bytes of a buffer, starting at `offset`.
+    ///
+    /// `src.access_type` must be `AccessType::TransferRead`, with
+    /// `previous_access_type` set to how the buffer was last written
+    /// (e.g. `ComputeWrite` for GPU culling counters).
+    pub fn read_buffer(
+        &mut self,
+        cmd: &mut dyn CommandList,
+        src: &BufferAccess,
+        offset: u64,
+        size: u64,
+    ) -> Result<ReadbackTicket> {
+        if size == 0 {
+            crate::engine_bail!("galaxy3d::ReadbackManager", "read_buffer: size must not be 0");
+        }
+        let (staging, capacity) = self.acquire_staging(size)?;
+        if let Err(e) = cmd.copy_buffer_to_buffer(src, offset, &staging, 0, size) {
+            self.release_staging(staging, capacity);
+            return Err(e);
+        }
+        Ok(self.push_pending(staging, capacity, size))
+    }
+
+    /// Deliver the result of a pending readback to `callback` (from
+    /// `poll()`) instead of keeping it for `take()`.
+    ///
+    /// Returns false if the ticket is not pending (unknown or already
+    /// completed).
+    pub fn on_complete<F>(&mut self, ticket: ReadbackTicket, callback: F) -> bool
+    where
+        F: FnOnce(&[u8]) + Send + 'static,
+    {
+        match self.pending.iter_mut().find(|p| p.ticket == ticket) {
+            Some(pending) => {
+                pending.callback = Some(Box::new(callback));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Number of readbacks whose copy may still be in flight
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// True if the result of `ticket` is waiting for `take()`
+    pub fn is_ready(&self, ticket: ReadbackTicket) -> bool {
+        self.completed.contains_key(&ticket)
+    }
+
+    /// Remove and return the result of a completed readback
+    pub fn take(&mut self, ticket: ReadbackTicket) -> Option<Vec<u8>> {
+        self.completed.remove(&ticket)
+    }
+
+    /// Collect the readbacks whose copy has completed.
+    ///
+    /// Call once per frame, after the render graph has executed. Returns
+    /// the number of readbacks completed by this call.
+    pub fn poll(&mut self) -> Result<usize> {
+        let mut count = 0;
+        let mut i = 0;
+        while i < self.pending.len() {
+            if self.pending[i].frames_remaining > 0 {
+                self.pending[i].frames_remaining -= 1;
+                i += 1;
+                continue;
+            }
+            let pending = self.pending.remove(i);
+            let mut data = vec![0u8; pending.size as usize];
+            let read = pending.staging.read(0, &mut data);
+            self.release_staging(pending.staging, pending.capacity);
+            read?;
+            match pending.callback {
+                Some(callback) => callback(&data),
+                None => {
+                    self.completed.insert(pending.ticket, data);
+                }
+            }
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Drop pending readbacks, unclaimed results and idle staging buffers
+    pub fn clear(&mut self) {
+        self.pending.clear();
+        self.completed.clear();
+        self.free_staging.clear();
+    }
+
+    // ===== STAGING BUFFERS =====
+
+    /// Take the smallest idle staging buffer holding `size` bytes, or
+    /// create one
+    fn acquire_staging(&mut self, size: u64) -> Result<(Arc<dyn Buffer>, u64)> {
+        let best = self.free_staging.iter()
+            .enumerate()
+            .filter(|(_, (_, capacity))| *capacity >= size)
+            .min_by_key(|(_, (_, capacity))| *capacity)
+            .map(|(i, _)| i);
+        if let Some(i) = best {
+            return Ok(self.free_staging.swap_remove(i));
+        }
+        let capacity = size.next_power_of_two().max(MIN_STAGING_SIZE);
+        let gd_arc = Engine::graphics_device("main")?;
+        let staging = gd_arc.lock().unwrap()
+            .create_buffer(BufferDesc {
+                size: capacity,
+                usage: BufferUsage::Readback,
+                debug_name: Some("ReadbackManager staging".to_string()),
+            })?;
+        Ok((staging, capacity))
+    }
+
+    fn release_staging(&mut self, staging: Arc<dyn Buffer>, capacity: u64) {
+        if self.free_staging.len() < MAX_FREE_STAGING_BUFFERS {
+            self.free_staging.push((staging, capacity));
+        }
+    }
+
+    fn push_pending(&mut self, staging: Arc<dyn Buffer>, capacity: u64, size: u64) -> ReadbackTicket {
+        let ticket = ReadbackTicket(self.next_ticket);
+        self.next_ticket += 1;
+        self.pending.push(PendingReadback {
+            ticket,
+            staging,
+            capacity,
+            size,
+            frames_remaining: self.frames_in_flight,
+            callback: None,
+        });
+        ticket
+    }
+}
+
+#[cfg(test)]
+#[path = "readback_manager_tests.rs"]
+mod tests;
@@ -0,0 +1,151 @@
+use super::*;
+use std::sync::Mutex;
+use crate::graphics_device::{AccessType, TextureFormat, TextureType};
+use crate::graphics_device::mock_graphics_device::{MockBuffer, MockCommandList, MockGraphicsDevice, MockTexture};
+use super::super::test_helpers::setup_engine_with_device;
+use serial_test::serial;
+
+/// Set up the "main" mock device and return its created-buffer log
+fn setup_engine() -> Arc<Mutex<Vec<String>>> {
+    let device = MockGraphicsDevice::new();
+    let created_buffers = device.created_buffers.clone();
+    setup_engine_with_device(device);
+    created_buffers
+}
+
+fn texture_access(format: TextureFormat) -> ImageAccess {
+    let mut texture = MockTexture::new(64, 32, 1, TextureType::Tex2D, "source".to_string());
+    texture.info.format = format;
+    ImageAccess {
+        texture: Arc::new(texture),
+        access_type: AccessType::TransferRead,
+        previous_access_type: Some(AccessType::ColorAttachmentWrite),
+    }
+}
+
+fn buffer_access() -> BufferAccess {
+    BufferAccess {
+        buffer: Arc::new(MockBuffer::new(1024, "stats".to_string())),
+        access_type: AccessType::TransferRead,
+        previous_access_type: Some(AccessType::ComputeWrite),
+    }
+}
+
+fn rect(x: i32, y: i32, width: u32, height: u32) -> Rect2D {
+    Rect2D { x, y, width, height }
+}
+
+// ============================================================================
+// Recording
+// ============================================================================
+
+#[test]
+fn test_rejects_zero_frames_in_flight() {
+    assert!(ReadbackManager::new(0).is_err());
+}
+
+#[test]
+#[serial]
+fn test_read_texture_records_one_copy_for_all_rects() {
+    setup_engine();
+    let mut readbacks = ReadbackManager::new(2).unwrap();
+    let mut cmd = MockCommandList::new();
+    readbacks.read_texture(&mut cmd, &texture_access(TextureFormat::R8G8B8A8_UNORM),
+        &[rect(0, 0, 4, 4), rect(10, 10, 1, 1)]).unwrap();
+    assert_eq!(cmd.commands, vec!["copy_texture_to_buffer 2".to_string()]);
+    assert_eq!(readbacks.pending_count(), 1);
+}
+
+#[test]
+#[serial]
+fn test_read_texture_rejects_invalid_requests() {
+    setup_engine();
+    let mut readbacks = ReadbackManager::new(1).unwrap();
+    let mut cmd = MockCommandList::new();
+    let color = texture_access(TextureFormat::R8G8B8A8_UNORM);
+    assert!(readbacks.read_texture(&mut cmd, &color, &[]).is_err());
+    assert!(readbacks.read_texture(&mut cmd, &color, &[rect(60, 0, 8, 1)]).is_err());
+    assert!(readbacks.read_texture(&mut cmd, &color, &[rect(-1, 0, 1, 1)]).is_err());
+    assert!(readbacks.read_texture(&mut cmd, &color, &[rect(0, 0, 0, 1)]).is_err());
+    assert!(readbacks.read_texture(&mut cmd,
+        &texture_access(TextureFormat::D32_FLOAT), &[rect(0, 0, 1, 1)]).is_err());
+    assert!(cmd.commands.is_empty());
+    assert_eq!(readbacks.pending_count(), 0);
+}
+
+#[test]
+#[serial]
+fn test_failed_copy_is_not_pending() {
+    setup_engine();
+    let mut readbacks = ReadbackManager::new(1).unwrap();
+    let mut cmd = MockCommandList::new();
+    let mut src = buffer_access();
+    src.access_type = AccessType::ComputeRead;
+    assert!(readbacks.read_buffer(&mut cmd, &src, 0, 16).is_err());
+    assert!(readbacks.read_buffer(&mut cmd, &buffer_access(), 0, 0).is_err());
+    assert_eq!(readbacks.pending_count(), 0);
+}
+
+// ============================================================================
+// Completion
+// ============================================================================
+
+#[test]
+#[serial]
+fn test_polled_ticket_ready_after_frames_in_flight() {
+    setup_engine();
+    let mut readbacks = ReadbackManager::new(2).unwrap();
+    let mut cmd = MockCommandList::new();
+    let ticket = readbacks.read_buffer(&mut cmd, &buffer_access(), 64, 12).unwrap();
+    assert_eq!(cmd.commands, vec!["copy_buffer_to_buffer 12".to_string()]);
+
+    // Frames N and N+1 may still be in flight
+    assert_eq!(readbacks.poll().unwrap(), 0);
+    assert_eq!(readbacks.poll().unwrap(), 0);
+    assert!(!readbacks.is_ready(ticket));
+    assert_eq!(readbacks.poll().unwrap(), 1);
+    assert!(readbacks.is_ready(ticket));
+    assert_eq!(readbacks.take(ticket), Some(vec![0u8; 12]));
+    assert_eq!(readbacks.take(ticket), None);
+    assert_eq!(readbacks.pending_count(), 0);
+}
+
+#[test]
+#[serial]
+fn test_callback_receives_packed_rects() {
+    setup_engine();
+    let mut readbacks = ReadbackManager::new(1).unwrap();
+    let mut cmd = MockCommandList::new();
+    let ticket = readbacks.read_texture(&mut cmd, &texture_access(TextureFormat::R16G16B16A16_SFLOAT),
+        &[rect(0, 0, 2, 3), rect(5, 5, 1, 1)]).unwrap();
+
+    let received = Arc::new(Mutex::new(None));
+    let sink = received.clone();
+    assert!(readbacks.on_complete(ticket, move |data| *sink.lock().unwrap() = Some(data.len())));
+    readbacks.poll().unwrap();
+    readbacks.poll().unwrap();
+    // (2 * 3 + 1) texels of 8 bytes; delivered to the callback, not kept
+    assert_eq!(*received.lock().unwrap(), Some(56));
+    assert!(!readbacks.is_ready(ticket));
+    assert!(!readbacks.on_complete(ticket, |_| {}));
+}
+
+#[test]
+#[serial]
+fn test_staging_buffers_are_recycled() {
+    let created_buffers = setup_engine();
+    let mut readbacks = ReadbackManager::new(1).unwrap();
+    let mut cmd = MockCommandList::new();
+    readbacks.read_buffer(&mut cmd, &buffer_access(), 0, 100).unwrap();
+    readbacks.read_buffer(&mut cmd, &buffer_access(), 0, 300).unwrap();
+    assert_eq!(created_buffers.lock().unwrap().len(), 2);
+    readbacks.poll().unwrap();
+    readbacks.poll().unwrap();
+
+    // Both fit the idle buffers: the smallest fitting one is reused each time
+    readbacks.read_buffer(&mut cmd, &buffer_access(), 0, 200).unwrap();
+    readbacks.read_buffer(&mut cmd, &buffer_access(), 0, 16).unwrap();
+    assert_eq!(created_buffers.lock().unwrap().len(), 2);
+    readbacks.read_buffer(&mut cmd, &buffer_access(), 0, 16).unwrap();
+    assert_eq!(created_buffers.lock().unwrap().len(), 3);
+}
@@ -5,14 +5,21 @@
 /// resolution, and per-pass image-access lists. All scratch is `Vec` /
 /// `FxHashMap` reused across frames via `clear()` — zero heap allocation
 /// in steady state.
+///
+/// Resources start each frame with no previous access (their content is
+/// discarded by the first layout transition), except persistent ones
+/// (`RenderGraphManager::set_graph_resource_persistent`): the graph carries
+/// their last access over to the next frame, so history textures keep
+/// their content.
 
 use std::collections::VecDeque;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use slotmap::SlotMap;
 use crate::error::Result;
 use crate::engine_bail;
 use crate::engine::Engine;
 use crate::graphics_device;
+use crate::resource::resource_manager::PassInfo;
 use super::access_type::{AccessType, TargetOps};
 use super::frame_buffer::{Framebuffer, FramebufferKey};
 use super::graph_resource::{GraphResource, GraphResourceKey};
@@ -33,6 +40,10 @@ pub struct RenderGraph {
     prev_access: FxHashMap<GraphResourceKey, AccessType>,
     image_accesses: Vec<graphics_device::ImageAccess>,
     buffer_accesses: Vec<graphics_device::BufferAccess>,
+    /// Last access of each persistent resource, carried across frames
+    persistent_access: FxHashMap<GraphResourceKey, AccessType>,
+    /// PassInfo given to the actions of passes without attachments
+    no_attachment_pass_info: PassInfo,
 
     // Topological sort scratch
     in_degree: FxHashMap<RenderPassKey, u32>,
@@ -64,6 +75,8 @@ impl RenderGraph {
             prev_access: FxHashMap::default(),
             image_accesses: Vec::new(),
             buffer_accesses: Vec::new(),
+            persistent_access: FxHashMap::default(),
+            no_attachment_pass_info: PassInfo::new(Vec::new(), None, graphics_device::SampleCount::S1),
             in_degree: FxHashMap::default(),
             successors: FxHashMap::default(),
             writers: FxHashMap::default(),
@@ -75,6 +88,22 @@ impl RenderGraph {
         &self.name
     }
 
+    /// Drop the access carried over for a persistent resource: its next
+    /// use starts from an undefined content.
+    pub(crate) fn forget_persistent_access(&mut self, key: GraphResourceKey) {
+        self.persistent_access.remove(&key);
+    }
+
+    /// Access carried over to the next frame for a persistent resource
+    pub fn persistent_access(&self, key: GraphResourceKey) -> Option<AccessType> {
+        self.persistent_access.get(&key).copied()
+    }
+
+    /// Passes run by the most recent `execute()` call, in execution order
+    pub fn executed_passes(&self) -> &[RenderPassKey] {
+        &self.sorted_passes
+    }
+
     /// Borrow the command list recorded by the most recent `execute()` call.
     pub fn command_list(&self) -> Result<&dyn graphics_device::CommandList> {
         if self.command_lists.is_empty() {
@@ -103,12 +132,14 @@ impl RenderGraph {
         passes_map: &mut SlotMap<RenderPassKey, RenderPass>,
         graph_resources: &SlotMap<GraphResourceKey, GraphResource>,
         framebuffers: &SlotMap<FramebufferKey, Framebuffer>,
+        persistent: &FxHashSet<GraphResourceKey>,
         passes: &[RenderPassKey],
         post_passes: F,
     ) -> Result<()>
     where
         F: FnOnce(&mut dyn graphics_device::CommandList) -> Result<()>,
     {
+        crate::profile_scope!("render_graph");
         // The ResourceManager is locked only briefly to materialise each
         // pass's image/buffer accesses (Arc clones are stashed in the
         // scratch lists), and the lock is released before any user
@@ -142,6 +173,7 @@ impl RenderGraph {
         // command list, even on error — otherwise the next frame's
         // begin() would fail on a still-recording list.
         self.prev_access.clear();
+        self.prev_access.extend(self.persistent_access.iter().map(|(k, a)| (*k, *a)));
         let result = (|| -> Result<()> {
             for i in 0..self.sorted_passes.len() {
                 let pass_key = self.sorted_passes[i];
@@ -198,18 +230,32 @@ impl RenderGraph {
                     }
                 }
 
-                // Begin → action → end. Skip passes without attachments
-                // (compute-only paths are not yet recorded here).
+                // Begin → action → end. Passes without attachments (ray
+                // tracing, transfers) record their action outside any
+                // render pass, after the barriers of their accesses.
                 let pass = passes_map.get_mut(pass_key).unwrap();
                 let (rp, fb_key) = match (pass.gd_render_pass(), pass.framebuffer_key()) {
                     (Some(rp), Some(fb_key)) => (rp.clone(), fb_key),
-                    _ => continue,
+                    _ => {
+                        self.command_lists[frame].begin_debug_label(pass.name())?;
+                        self.command_lists[frame].declare_accesses(
+                            &self.image_accesses,
+                            &self.buffer_accesses,
+                        )?;
+                        pass.action_mut().execute(
+                            &mut *self.command_lists[frame],
+                            &self.no_attachment_pass_info,
+                        )?;
+                        self.command_lists[frame].end_debug_label()?;
+                        continue;
+                    }
                 };
                 let fb = framebuffers.get(fb_key).ok_or_else(|| {
                     crate::engine_err!("galaxy3d::RenderGraph",
                         "Pass '{}': framebuffer_key not found in manager", pass.name())
                 })?;
                 let gd_fb = fb.gd_framebuffer().clone();
+                self.command_lists[frame].begin_debug_label(pass.name())?;
                 self.command_lists[frame].begin_render_pass(
                     &rp,
                     &gd_fb,
@@ -226,6 +272,13 @@ impl RenderGraph {
                     &pass_info_clone,
                 )?;
                 self.command_lists[frame].end_render_pass()?;
+                self.command_lists[frame].end_debug_label()?;
+            }
+
+            for key in persistent {
+                if let Some(access) = self.prev_access.get(key) {
+                    self.persistent_access.insert(*key, *access);
+                }
             }
 
             // 5. Post-passes hook (e.g. swapchain blit).
@@ -12,13 +12,14 @@
 /// surface at call time rather than at the next frame.
 
 use std::sync::Arc;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use slotmap::SlotMap;
 use crate::error::Result;
 use crate::engine_bail;
 use crate::engine::Engine;
 use crate::graphics_device;
 use crate::resource::resource_manager::{PassInfo, ResourceManager};
+use crate::scene::{instance_pass_name, SceneView, ViewList};
 use super::access_type::{AccessType, ResourceAccess, TargetOps};
 use super::frame_buffer::{ColorAttachmentSlot, Framebuffer, FramebufferKey, FramebufferLookupKey};
 use super::graph_resource::{GraphResource, GraphResourceKey};
@@ -35,6 +36,8 @@ pub struct RenderGraphManager {
 
     graph_resources: SlotMap<GraphResourceKey, GraphResource>,
     graph_resource_names: FxHashMap<String, GraphResourceKey>,
+    /// Graph resources whose content is kept from one frame to the next
+    persistent_resources: FxHashSet<GraphResourceKey>,
 
     framebuffers: SlotMap<FramebufferKey, Framebuffer>,
     framebuffer_lookup: FxHashMap<FramebufferLookupKey, FramebufferKey>,
@@ -58,6 +61,7 @@ impl RenderGraphManager {
             pass_names: FxHashMap::default(),
             graph_resources: SlotMap::with_key(),
             graph_resource_names: FxHashMap::default(),
+            persistent_resources: FxHashSet::default(),
             framebuffers: SlotMap::with_key(),
             framebuffer_lookup: FxHashMap::default(),
         }
@@ -103,6 +107,10 @@ impl RenderGraphManager {
         self.graphs.len()
     }
 
+    pub fn render_graphs(&self) -> impl Iterator<Item = (RenderGraphKey, &RenderGraph)> {
+        self.graphs.iter()
+    }
+
     // ===== RENDER PASS =====
 
     /// Create a render pass and immediately compute its full cache
@@ -167,6 +175,74 @@ impl RenderGraphManager {
         self.passes.len()
     }
 
+    pub fn render_passes(&self) -> impl Iterator<Item = (RenderPassKey, &RenderPass)> {
+        self.passes.iter()
+    }
+
+    /// Remove a render pass. Its framebuffer stays cached.
+    pub fn remove_render_pass(&mut self, key: RenderPassKey) -> bool {
+        let removed = self.passes.remove(key).is_some();
+        if removed {
+            self.pass_names.retain(|_, v| *v != key);
+        }
+        removed
+    }
+
+    // ===== RENDER PASS — VIEW INSTANCES =====
+
+    /// Create one render pass per view of `views`, in render order, named
+    /// `instance_pass_name(base_name, view)`. `make_pass` returns the
+    /// accesses and action of a view (typically a `ScenePassAction` on
+    /// `view.render_view(pass_type)`, writing the view's own target).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an instance name is taken, or if `make_pass` or
+    /// a pass cache fails; the instances already created are removed.
+    pub fn create_render_pass_instances<F>(
+        &mut self,
+        base_name: &str,
+        views: &ViewList,
+        mut make_pass: F,
+    ) -> Result<Vec<RenderPassKey>>
+    where
+        F: FnMut(&SceneView) -> Result<(Vec<ResourceAccess>, Box<dyn PassAction>)>,
+    {
+        for view in views.iter() {
+            let name = instance_pass_name(base_name, view.name());
+            if self.pass_names.contains_key(&name) {
+                engine_bail!("galaxy3d::RenderGraphManager",
+                    "RenderPass '{}' already exists", name);
+            }
+        }
+        let mut keys = Vec::with_capacity(views.len());
+        for view in views.iter() {
+            let created = make_pass(view).and_then(|(accesses, action)| {
+                self.create_render_pass(&instance_pass_name(base_name, view.name()), accesses, action)
+            });
+            match created {
+                Ok(key) => keys.push(key),
+                Err(e) => {
+                    for key in keys {
+                        self.remove_render_pass(key);
+                    }
+                    return Err(e);
+                }
+            }
+        }
+        Ok(keys)
+    }
+
+    /// Keys of the instances of `base_name` for the enabled views of
+    /// `views`, in render order — the pass list of `execute_render_graph()`.
+    /// Views without an instance are skipped.
+    pub fn render_pass_instance_keys(&self, base_name: &str, views: &ViewList) -> Vec<RenderPassKey> {
+        views.iter()
+            .filter(|view| view.is_enabled())
+            .filter_map(|view| self.render_pass_id(&instance_pass_name(base_name, view.name())))
+            .collect()
+    }
+
     // ===== RENDER PASS — EAGER SETTERS =====
 
     /// Replace the `GraphResource` of access #`access_idx`. Rebuilds the
@@ -341,10 +417,18 @@ impl RenderGraphManager {
         self.graph_resources.len()
     }
 
+    /// Name of a graph resource (linear search, for diagnostics)
+    pub fn graph_resource_name(&self, key: GraphResourceKey) -> Option<&str> {
+        self.graph_resource_names.iter()
+            .find(|(_, &k)| k == key)
+            .map(|(name, _)| name.as_str())
+    }
+
     pub fn remove_graph_resource(&mut self, key: GraphResourceKey) -> bool {
         let removed = self.graph_resources.remove(key).is_some();
         if removed {
             self.graph_resource_names.retain(|_, v| *v != key);
+            self.forget_persistent(key);
         }
         removed
     }
@@ -353,12 +437,40 @@ impl RenderGraphManager {
         match self.graph_resource_names.remove(name) {
             Some(key) => {
                 self.graph_resources.remove(key);
+                self.forget_persistent(key);
                 true
             }
             None => false,
         }
     }
 
+    /// Keep the content of a graph resource from one frame to the next
+    /// (history textures). By default, the first access of a frame
+    /// discards the content (transition from an undefined layout).
+    ///
+    /// Every graph forgets the access it carried over for the resource, so
+    /// call it again after swapping the texture behind the resource.
+    pub fn set_graph_resource_persistent(
+        &mut self,
+        key: GraphResourceKey,
+        persistent: bool,
+    ) -> Result<()> {
+        if !self.graph_resources.contains_key(key) {
+            engine_bail!("galaxy3d::RenderGraphManager",
+                "set_graph_resource_persistent: GraphResourceKey not found");
+        }
+        self.forget_persistent(key);
+        if persistent {
+            self.persistent_resources.insert(key);
+        }
+        Ok(())
+    }
+
+    /// Whether the content of a graph resource is kept across frames
+    pub fn is_graph_resource_persistent(&self, key: GraphResourceKey) -> bool {
+        self.persistent_resources.contains(&key)
+    }
+
     // ===== FRAMEBUFFER =====
 
     /// Get or create a `Framebuffer` matching the given attachment set.
@@ -432,13 +544,13 @@ impl RenderGraphManager {
         F: FnOnce(&mut dyn graphics_device::CommandList) -> Result<()>,
     {
         let graph = self.graphs.get_mut(graph_key).ok_or_else(|| {
-            crate::engine_err!("galaxy3d::RenderGraphManager",
-                "execute_render_graph: RenderGraphKey not found")
+            crate::engine_not_found!("galaxy3d::RenderGraphManager", "RenderGraph", format!("{:?}", graph_key))
         })?;
         graph.execute(
             &mut self.passes,
             &self.graph_resources,
             &self.framebuffers,
+            &self.persistent_resources,
             passes,
             post_passes,
         )
@@ -451,12 +563,21 @@ impl RenderGraphManager {
         self.pass_names.clear();
         self.graph_resources.clear();
         self.graph_resource_names.clear();
+        self.persistent_resources.clear();
         self.framebuffers.clear();
         self.framebuffer_lookup.clear();
     }
 
     // ===== PRIVATE HELPERS =====
 
+    /// Unmark a persistent resource and drop its carried access in every graph
+    fn forget_persistent(&mut self, key: GraphResourceKey) {
+        self.persistent_resources.remove(&key);
+        for graph in self.graphs.values_mut() {
+            graph.forget_persistent_access(key);
+        }
+    }
+
     /// Walk a slice of `ResourceAccess` and build everything a `RenderPass`
     /// needs to execute: framebuffer (via the manager's cache), render-pass
     /// descriptor (formats / load-store ops), `PassInfo` (with generation
@@ -593,7 +714,7 @@ impl RenderGraphManager {
                 // DepthStencilReadOnly = sampling the depth, NOT a
                 // framebuffer attachment for our engine semantics.
                 // Likewise for *ShaderRead, Compute*, Transfer*,
-                // RayTracingRead, and buffer accesses — none of them
+                // RayTracing*, and buffer accesses — none of them
                 // contribute to the framebuffer.
                 _ => {}
             }
@@ -601,3 +601,133 @@ fn test_clear_drops_engine_backed_passes() {
     assert_eq!(rgm.graph_resource_count(), 0);
     assert_eq!(rgm.framebuffer_count(), 0);
 }
+
+// ============================================================================
+// Persistent graph resources
+// ============================================================================
+
+#[test]
+#[serial]
+fn test_set_graph_resource_persistent() {
+    let env = setup_engine_for_render_graph();
+    let mut rgm = RenderGraphManager::new();
+    let history = rgm.create_graph_resource("history", GraphResource::Texture {
+        texture_key: env.color_texture, base_mip_level: 0, base_array_layer: 0, layer_count: 1,
+    }).unwrap();
+    assert!(!rgm.is_graph_resource_persistent(history));
+
+    rgm.set_graph_resource_persistent(history, true).unwrap();
+    assert!(rgm.is_graph_resource_persistent(history));
+    rgm.set_graph_resource_persistent(history, false).unwrap();
+    assert!(!rgm.is_graph_resource_persistent(history));
+
+    rgm.set_graph_resource_persistent(history, true).unwrap();
+    rgm.remove_graph_resource(history);
+    assert!(!rgm.is_graph_resource_persistent(history));
+    assert!(rgm.set_graph_resource_persistent(history, true).is_err());
+}
+
+#[test]
+#[serial]
+fn test_persistent_resource_access_carried_across_frames() {
+    let env = setup_engine_for_render_graph();
+    let rgm_arc = {
+        Engine::create_render_graph_manager().unwrap();
+        Engine::render_graph_manager().unwrap()
+    };
+    let mut rgm = rgm_arc.lock().unwrap();
+    let graph_key = rgm.create_render_graph("main", 1).unwrap();
+    let history = rgm.create_graph_resource("history", GraphResource::Texture {
+        texture_key: env.color_texture, base_mip_level: 0, base_array_layer: 0, layer_count: 1,
+    }).unwrap();
+    let (action, _) = make_recording_pass();
+    let pass_key = rgm.create_render_pass("resolve", vec![ResourceAccess {
+        graph_resource_key: history,
+        access_type: AccessType::ColorAttachmentWrite,
+        target_ops: Some(default_color_ops()),
+    }], action).unwrap();
+
+    // Not persistent: nothing carried over
+    rgm.execute_render_graph(graph_key, &[pass_key], |_cmd| Ok(())).unwrap();
+    assert_eq!(rgm.render_graph(graph_key).unwrap().persistent_access(history), None);
+
+    rgm.set_graph_resource_persistent(history, true).unwrap();
+    rgm.execute_render_graph(graph_key, &[pass_key], |_cmd| Ok(())).unwrap();
+    assert_eq!(
+        rgm.render_graph(graph_key).unwrap().persistent_access(history),
+        Some(AccessType::ColorAttachmentWrite),
+    );
+
+    // Re-marking forgets the carried access
+    rgm.set_graph_resource_persistent(history, true).unwrap();
+    assert_eq!(rgm.render_graph(graph_key).unwrap().persistent_access(history), None);
+}
+
+// ============================================================================
+// Per-view pass instances
+// ============================================================================
+
+fn two_views() -> crate::scene::ViewList {
+    use crate::scene::{SceneView, ViewList};
+    use crate::scene::scene_test_helpers::create_test_camera;
+    let mut views = ViewList::new();
+    views.add(SceneView::new("main", "world", create_test_camera(), &[0]).unwrap()).unwrap();
+    views.add(SceneView::new("minimap", "world", create_test_camera(), &[0]).unwrap().with_order(-1)).unwrap();
+    views
+}
+
+#[test]
+#[serial]
+fn test_render_pass_instances_follow_view_order() {
+    let env = setup_engine_for_render_graph();
+    let mut rgm = RenderGraphManager::new();
+    let color_gr = rgm.create_graph_resource("c", GraphResource::Texture {
+        texture_key: env.color_texture, base_mip_level: 0, base_array_layer: 0, layer_count: 1,
+    }).unwrap();
+    let mut views = two_views();
+
+    let keys = rgm.create_render_pass_instances("opaque", &views, |_view| {
+        let (action, _) = make_recording_pass();
+        Ok((vec![ResourceAccess {
+            graph_resource_key: color_gr,
+            access_type: AccessType::ColorAttachmentWrite,
+            target_ops: Some(default_color_ops()),
+        }], action as Box<dyn PassAction>))
+    }).unwrap();
+    assert_eq!(keys.len(), 2);
+    assert_eq!(rgm.render_pass(keys[0]).unwrap().name(), "opaque@minimap");
+    assert_eq!(rgm.render_pass(keys[1]).unwrap().name(), "opaque@main");
+    assert_eq!(rgm.render_pass_instance_keys("opaque", &views), keys);
+
+    views.view_mut("minimap").unwrap().set_enabled(false);
+    assert_eq!(rgm.render_pass_instance_keys("opaque", &views), vec![keys[1]]);
+
+    // Instancing twice collides on the names
+    assert!(rgm.create_render_pass_instances("opaque", &views, |_| unreachable!()).is_err());
+}
+
+#[test]
+#[serial]
+fn test_render_pass_instances_roll_back_on_error() {
+    let env = setup_engine_for_render_graph();
+    let mut rgm = RenderGraphManager::new();
+    let color_gr = rgm.create_graph_resource("c", GraphResource::Texture {
+        texture_key: env.color_texture, base_mip_level: 0, base_array_layer: 0, layer_count: 1,
+    }).unwrap();
+    let views = two_views();
+
+    let result = rgm.create_render_pass_instances("opaque", &views, |view| {
+        if view.name() == "main" {
+            crate::engine_bail!("galaxy3d::test", "no target for '{}'", view.name());
+        }
+        let (action, _) = make_recording_pass();
+        Ok((vec![ResourceAccess {
+            graph_resource_key: color_gr,
+            access_type: AccessType::ColorAttachmentWrite,
+            target_ops: Some(default_color_ops()),
+        }], action as Box<dyn PassAction>))
+    });
+    assert!(result.is_err());
+    assert_eq!(rgm.render_pass_count(), 0);
+    assert!(rgm.render_pass_id("opaque@minimap").is_none());
+}
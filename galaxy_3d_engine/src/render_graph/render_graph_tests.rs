@@ -342,6 +342,36 @@ fn test_render_graph_execute_writer_equals_reader_self_loop() {
     assert!(result.is_ok(), "self read+write should sort cleanly: {:?}", result);
 }
 
+#[test]
+#[serial]
+fn test_render_graph_execute_runs_pass_without_attachments() {
+    // A pass with no attachment (e.g. a ray tracing pass writing a
+    // storage image) still runs its action, outside any render pass.
+    let env = setup_engine_for_render_graph();
+    Engine::create_render_graph_manager().unwrap();
+    let rgm_arc = Engine::render_graph_manager().unwrap();
+
+    let (graph_key, pass_key, counter) = {
+        let mut rgm = rgm_arc.lock().unwrap();
+        let graph_key = rgm.create_render_graph("main", 1).unwrap();
+        let shadow_gr = rgm.create_graph_resource("shadow", GraphResource::Texture {
+            texture_key: env.color_texture, base_mip_level: 0, base_array_layer: 0, layer_count: 1,
+        }).unwrap();
+        let (action, counter) = make_recording_pass();
+        let pass_key = rgm.create_render_pass("rt_shadow", vec![ResourceAccess {
+            graph_resource_key: shadow_gr,
+            access_type: AccessType::RayTracingWrite,
+            target_ops: None,
+        }], action).unwrap();
+        (graph_key, pass_key, counter)
+    };
+
+    let mut rgm = rgm_arc.lock().unwrap();
+    assert!(rgm.render_pass(pass_key).unwrap().framebuffer_key().is_none());
+    rgm.execute_render_graph(graph_key, &[pass_key], |_| Ok(())).unwrap();
+    assert_eq!(counter.load(std::sync::atomic::Ordering::SeqCst), 1);
+}
+
 #[test]
 fn test_graph_resource_with_buffer_does_not_panic_on_construction() {
     // Constructing a GraphResource::Buffer is allowed even with an
@@ -14,7 +14,7 @@ use crate::graphics_device;
 use crate::resource::resource_manager::PassInfo;
 use super::access_type::ResourceAccess;
 use super::frame_buffer::FramebufferKey;
-use super::pass_action::PassAction;
+use super::pass_action::{PassAction, PassActionSummary};
 
 slotmap::new_key_type! {
     /// Stable key for a `RenderPass` in the `RenderGraphManager`.
@@ -76,6 +76,11 @@ impl RenderPass {
         self.framebuffer_key
     }
 
+    /// What the pass action draws (see `PassAction::summary()`)
+    pub fn action_summary(&self) -> PassActionSummary {
+        self.action.summary()
+    }
+
     pub(crate) fn gd_render_pass(&self) -> Option<&Arc<dyn graphics_device::RenderPass>> {
         self.gd_render_pass.as_ref()
     }
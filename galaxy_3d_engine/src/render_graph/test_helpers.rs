@@ -2,8 +2,10 @@
 //!
 //! Provides a one-call `setup_engine_for_render_graph()` that initializes
 //! the global Engine with a `MockGraphicsDevice`, an empty `ResourceManager`,
-//! and registers a couple of pre-made attachments. All tests using these
-//! helpers must be `#[serial]` because the Engine state is global.
+//! and registers a couple of pre-made attachments. `setup_engine()` does
+//! the same without the attachments, for tests outside the render graph.
+//! All tests using these helpers must be `#[serial]` because the Engine
+//! state is global.
 
 use std::sync::Arc;
 use crate::engine::Engine;
@@ -19,14 +21,25 @@ pub(crate) struct GraphTestEnv {
     pub depth_texture: TextureKey,
 }
 
+/// Bring the global Engine to a clean state with `device` registered as
+/// "main" and an empty ResourceManager.
+pub(crate) fn setup_engine_with_device(device: MockGraphicsDevice) {
+    Engine::initialize().unwrap();
+    Engine::reset_for_testing();
+    Engine::create_graphics_device("main", device).unwrap();
+    Engine::create_resource_manager().unwrap();
+}
+
+/// `setup_engine_with_device()` with a fresh MockGraphicsDevice.
+pub(crate) fn setup_engine() {
+    setup_engine_with_device(MockGraphicsDevice::new());
+}
+
 /// Bring the global Engine to a clean state with a MockGraphicsDevice and an
 /// empty ResourceManager. Returns texture keys for a 64x64 R8G8B8A8 color
 /// and a 64x64 D32 depth attachment, both registered in the manager.
 pub(crate) fn setup_engine_for_render_graph() -> GraphTestEnv {
-    Engine::initialize().unwrap();
-    Engine::reset_for_testing();
-    Engine::create_graphics_device("main", MockGraphicsDevice::new()).unwrap();
-    Engine::create_resource_manager().unwrap();
+    setup_engine();
 
     let rm_arc = Engine::resource_manager().unwrap();
     let gd_arc = Engine::graphics_device("main").unwrap();
@@ -35,7 +48,7 @@ pub(crate) fn setup_engine_for_render_graph() -> GraphTestEnv {
     let color_texture = rm.create_texture("color".to_string(), TextureDesc {
         graphics_device: gd_arc.clone(),
         texture: graphics_device::TextureDesc {
-            width: 64, height: 64,
+            width: 64, height: 64, depth: 1,
             format: TextureFormat::R8G8B8A8_UNORM,
             usage: TextureUsage::RenderTarget,
             texture_type: TextureType::Tex2D,
@@ -43,6 +56,7 @@ pub(crate) fn setup_engine_for_render_graph() -> GraphTestEnv {
             array_layers: 1,
             data: None,
             mipmap: MipmapMode::None,
+            debug_name: None,
         },
         layers: vec![LayerDesc { name: "default".to_string(), layer_index: 0, data: None, regions: vec![] }],
     }).unwrap();
@@ -50,7 +64,7 @@ pub(crate) fn setup_engine_for_render_graph() -> GraphTestEnv {
     let depth_texture = rm.create_texture("depth".to_string(), TextureDesc {
         graphics_device: gd_arc.clone(),
         texture: graphics_device::TextureDesc {
-            width: 64, height: 64,
+            width: 64, height: 64, depth: 1,
             format: TextureFormat::D32_FLOAT,
             usage: TextureUsage::DepthStencil,
             texture_type: TextureType::Tex2D,
@@ -58,6 +72,7 @@ pub(crate) fn setup_engine_for_render_graph() -> GraphTestEnv {
             array_layers: 1,
             data: None,
             mipmap: MipmapMode::None,
+            debug_name: None,
         },
         layers: vec![LayerDesc { name: "default".to_string(), layer_index: 0, data: None, regions: vec![] }],
     }).unwrap();
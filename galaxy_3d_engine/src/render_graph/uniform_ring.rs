@@ -0,0 +1,176 @@
+/// Per-frame uniform allocator for per-draw constants.
+///
+/// `UniformRing` owns one persistently mapped uniform buffer per frame in
+/// flight, each bound by a binding group holding a single dynamic-offset
+/// uniform buffer (binding 0 of `UniformRingDesc::set_index`). Per-draw
+/// constants are appended to the current frame's buffer instead of living
+/// in buffers of their own:
+///
+/// 1. `begin_frame()` once per frame, before recording; it moves to the
+///    next buffer, whose previous contents were read by the command list
+///    of frame N - `frames_in_flight` (already completed, like the command
+///    list ring of a `RenderGraph`);
+/// 2. `alloc_uniform(&value)` per draw returns the binding group and the
+///    dynamic offset to pass to `CommandList::bind_binding_group_dynamic`.
+///
+/// Pipelines drawn with it list `(set_index, 0)` in
+/// `PipelineDesc::dynamic_uniform_buffers`. The shader block may not be
+/// larger than `UniformRingDesc::max_block_size`.
+
+use std::sync::Arc;
+use crate::error::Result;
+use crate::graphics_device::{
+    BindingGroup, BindingGroupLayoutDesc, BindingResource, BindingSlotDesc, BindingType, Buffer,
+    BufferDesc, BufferUsage, GraphicsDevice, ShaderStageFlags,
+};
+
+/// Descriptor of a `UniformRing`
+#[derive(Debug, Clone)]
+pub struct UniformRingDesc {
+    /// Number of command lists of the render graph (one buffer each)
+    pub frames_in_flight: usize,
+    /// Bytes available per frame
+    pub frame_size: u64,
+    /// Largest value allocated, in bytes (range seen by the shader)
+    pub max_block_size: u64,
+    /// Set index of the binding groups
+    pub set_index: u32,
+    /// Shader stages reading the constants (must match the pipeline
+    /// layouts, `ShaderStageFlags::VERTEX_FRAGMENT` for graphics pipelines)
+    pub stage_flags: ShaderStageFlags,
+}
+
+/// Uniform buffer of a frame and the binding group bound to it
+struct RingFrame {
+    buffer: Arc<dyn Buffer>,
+    binding_group: Arc<dyn BindingGroup>,
+}
+
+/// Per-frame uniform allocator (see module docs).
+pub struct UniformRing {
+    frames: Vec<RingFrame>,
+    frame_size: u64,
+    max_block_size: u64,
+    /// `GraphicsDevice::uniform_buffer_alignment`
+    alignment: u64,
+    current_frame: usize,
+    /// First free byte of the current frame's buffer
+    head: u64,
+}
+
+impl UniformRing {
+    /// Create the buffers and binding groups of every frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `frames_in_flight` or `max_block_size` is 0, if
+    /// `frame_size` is smaller than `max_block_size` or does not fit a
+    /// 32-bit dynamic offset, or if a buffer or binding group cannot be
+    /// created.
+    pub fn new(graphics_device: &mut dyn GraphicsDevice, desc: &UniformRingDesc) -> Result<Self> {
+        if desc.frames_in_flight == 0 {
+            crate::engine_bail!("galaxy3d::UniformRing", "frames_in_flight must be at least 1");
+        }
+        if desc.max_block_size == 0 || desc.frame_size < desc.max_block_size {
+            crate::engine_bail!("galaxy3d::UniformRing",
+                "frame_size ({}) must hold at least one block of max_block_size ({}) bytes, and blocks cannot be empty",
+                desc.frame_size, desc.max_block_size);
+        }
+        if desc.frame_size > u32::MAX as u64 {
+            crate::engine_bail!("galaxy3d::UniformRing",
+                "frame_size ({}) does not fit a 32-bit dynamic offset", desc.frame_size);
+        }
+
+        let layout = BindingGroupLayoutDesc {
+            entries: vec![BindingSlotDesc {
+                binding: 0,
+                binding_type: BindingType::UniformBufferDynamic,
+                count: 1,
+                stage_flags: desc.stage_flags,
+            }],
+        };
+        let mut frames = Vec::with_capacity(desc.frames_in_flight);
+        for frame in 0..desc.frames_in_flight {
+            let buffer = graphics_device.create_buffer(BufferDesc {
+                size: desc.frame_size,
+                usage: BufferUsage::Uniform,
+                debug_name: Some(format!("UniformRing frame {}", frame)),
+            })?;
+            let binding_group = graphics_device.create_binding_group_from_layout(
+                &layout,
+                desc.set_index,
+                &[BindingResource::UniformBufferDynamic(buffer.as_ref(), desc.max_block_size)],
+            )?;
+            frames.push(RingFrame { buffer, binding_group });
+        }
+
+        Ok(Self {
+            frames,
+            frame_size: desc.frame_size,
+            max_block_size: desc.max_block_size,
+            alignment: graphics_device.uniform_buffer_alignment().max(1),
+            current_frame: desc.frames_in_flight - 1,
+            head: 0,
+        })
+    }
+
+    /// Number of per-frame buffers
+    pub fn frames_in_flight(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Index of the buffer allocations go to
+    pub fn current_frame(&self) -> usize {
+        self.current_frame
+    }
+
+    /// Bytes used in the current frame's buffer (alignment padding included)
+    pub fn used_bytes(&self) -> u64 {
+        self.head
+    }
+
+    /// Binding group of the current frame
+    pub fn binding_group(&self) -> &Arc<dyn BindingGroup> {
+        &self.frames[self.current_frame].binding_group
+    }
+
+    /// Move to the next frame's buffer and free all its allocations.
+    pub fn begin_frame(&mut self) {
+        self.current_frame = (self.current_frame + 1) % self.frames.len();
+        self.head = 0;
+    }
+
+    /// Copy `value` into the current frame's buffer.
+    ///
+    /// Returns the binding group and the dynamic offset to bind it with.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `T` is larger than `max_block_size` or the
+    /// frame's buffer is full.
+    pub fn alloc_uniform<T: bytemuck::Pod>(&mut self, value: &T) -> Result<(Arc<dyn BindingGroup>, u32)> {
+        self.alloc_bytes(bytemuck::bytes_of(value))
+    }
+
+    /// Copy raw bytes into the current frame's buffer (see `alloc_uniform`).
+    pub fn alloc_bytes(&mut self, data: &[u8]) -> Result<(Arc<dyn BindingGroup>, u32)> {
+        if data.len() as u64 > self.max_block_size {
+            crate::engine_bail!("galaxy3d::UniformRing",
+                "{} bytes allocated, max_block_size is {}", data.len(), self.max_block_size);
+        }
+        let offset = self.head.next_multiple_of(self.alignment);
+        // The shader sees max_block_size bytes from the offset
+        if offset + self.max_block_size > self.frame_size {
+            crate::engine_bail!("galaxy3d::UniformRing",
+                "frame buffer full ({} of {} bytes used)", self.head, self.frame_size);
+        }
+        let frame = &self.frames[self.current_frame];
+        frame.buffer.update(offset, data)?;
+        self.head = offset + data.len() as u64;
+        Ok((Arc::clone(&frame.binding_group), offset as u32))
+    }
+}
+
+#[cfg(test)]
+#[path = "uniform_ring_tests.rs"]
+mod tests;
@@ -0,0 +1,104 @@
+use super::*;
+use crate::graphics_device::mock_graphics_device::MockGraphicsDevice;
+
+fn desc(frames_in_flight: usize, frame_size: u64, max_block_size: u64) -> UniformRingDesc {
+    UniformRingDesc {
+        frames_in_flight,
+        frame_size,
+        max_block_size,
+        set_index: 1,
+        stage_flags: ShaderStageFlags::VERTEX_FRAGMENT,
+    }
+}
+
+// ============================================================================
+// Creation
+// ============================================================================
+
+#[test]
+fn test_new_creates_one_buffer_per_frame() {
+    let mut gd = MockGraphicsDevice::new();
+    let ring = UniformRing::new(&mut gd, &desc(3, 4096, 64)).unwrap();
+    assert_eq!(ring.frames_in_flight(), 3);
+    assert_eq!(gd.get_created_buffers().len(), 3);
+    assert_eq!(ring.binding_group().set_index(), 1);
+}
+
+#[test]
+fn test_new_rejects_invalid_desc() {
+    let mut gd = MockGraphicsDevice::new();
+    assert!(UniformRing::new(&mut gd, &desc(0, 4096, 64)).is_err());
+    assert!(UniformRing::new(&mut gd, &desc(2, 4096, 0)).is_err());
+    assert!(UniformRing::new(&mut gd, &desc(2, 32, 64)).is_err());
+    assert!(UniformRing::new(&mut gd, &desc(2, u32::MAX as u64 + 1, 64)).is_err());
+}
+
+// ============================================================================
+// Allocation
+// ============================================================================
+
+#[test]
+fn test_alloc_offsets_are_aligned() {
+    let mut gd = MockGraphicsDevice::new();
+    let mut ring = UniformRing::new(&mut gd, &desc(2, 4096, 64)).unwrap();
+    ring.begin_frame();
+    let (_, first) = ring.alloc_uniform(&[1.0f32; 4]).unwrap();
+    let (_, second) = ring.alloc_uniform(&[2.0f32; 4]).unwrap();
+    let (_, third) = ring.alloc_uniform(&7u32).unwrap();
+    assert_eq!((first, second, third), (0, 256, 512));
+    assert_eq!(ring.used_bytes(), 516);
+}
+
+#[test]
+fn test_alloc_follows_device_alignment() {
+    let mut gd = MockGraphicsDevice::new();
+    gd.uniform_buffer_alignment = 64;
+    let mut ring = UniformRing::new(&mut gd, &desc(1, 1024, 64)).unwrap();
+    ring.alloc_bytes(&[0u8; 20]).unwrap();
+    let (_, offset) = ring.alloc_bytes(&[0u8; 20]).unwrap();
+    assert_eq!(offset, 64);
+}
+
+#[test]
+fn test_alloc_rejects_block_larger_than_max() {
+    let mut gd = MockGraphicsDevice::new();
+    let mut ring = UniformRing::new(&mut gd, &desc(1, 4096, 16)).unwrap();
+    assert!(ring.alloc_uniform(&[0.0f32; 8]).is_err());
+}
+
+#[test]
+fn test_alloc_fails_when_frame_is_full() {
+    let mut gd = MockGraphicsDevice::new();
+    // Blocks start on the mock's 256-byte alignment: 0, 256, then 512
+    let mut ring = UniformRing::new(&mut gd, &desc(1, 560, 64)).unwrap();
+    ring.alloc_bytes(&[0u8; 64]).unwrap();
+    ring.alloc_bytes(&[0u8; 64]).unwrap();
+    // Offset 512 + 64-byte range = 576 would overflow the 560-byte buffer
+    assert!(ring.alloc_bytes(&[0u8; 4]).is_err());
+}
+
+// ============================================================================
+// Frames
+// ============================================================================
+
+#[test]
+fn test_begin_frame_cycles_buffers_and_resets_offsets() {
+    let mut gd = MockGraphicsDevice::new();
+    let mut ring = UniformRing::new(&mut gd, &desc(2, 4096, 64)).unwrap();
+
+    ring.begin_frame();
+    assert_eq!(ring.current_frame(), 0);
+    let (group_0, _) = ring.alloc_bytes(&[0u8; 16]).unwrap();
+    ring.alloc_bytes(&[0u8; 16]).unwrap();
+
+    ring.begin_frame();
+    assert_eq!(ring.current_frame(), 1);
+    assert_eq!(ring.used_bytes(), 0);
+    let (group_1, offset) = ring.alloc_bytes(&[0u8; 16]).unwrap();
+    assert_eq!(offset, 0);
+    assert!(!Arc::ptr_eq(&group_0, &group_1));
+
+    ring.begin_frame();
+    assert_eq!(ring.current_frame(), 0);
+    assert!(Arc::ptr_eq(ring.binding_group(), &group_0));
+}
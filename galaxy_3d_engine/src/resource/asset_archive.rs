@@ -0,0 +1,356 @@
+/// Packed asset archives (`archive` feature).
+///
+/// An archive packs many asset files into one, so a shipped game opens a
+/// single file instead of hundreds of loose ones. Layout (little endian):
+///
+/// | Part | Content |
+/// |---|---|
+/// | Header (32 bytes) | magic `G3DPACK\0`, version `u32`, entry count `u32`, TOC offset `u64`, TOC size `u64` |
+/// | Blobs | entry data, stored raw or zstd-compressed, back to back |
+/// | TOC | per entry: name length `u16`, UTF-8 name, offset `u64`, stored size `u64`, size `u64`, compression `u8` |
+///
+/// `AssetArchiveWriter` is the packing tool API. `AssetArchive` memory-maps
+/// an archive and reads entries from the mapping: raw entries are borrowed
+/// without a copy, compressed entries are decompressed on read. It is
+/// `Send + Sync`: share it in an `Arc` with the streaming workers
+/// (`StreamingManager::request_from_archive()`).
+
+use std::borrow::Cow;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use rustc_hash::FxHashMap;
+use crate::error::Result;
+use crate::{engine_bail, engine_err};
+use crate::utils::write_atomic;
+
+/// First bytes of an archive file
+pub const ARCHIVE_MAGIC: [u8; 8] = *b"G3DPACK\0";
+/// Format version written by `AssetArchiveWriter`
+pub const ARCHIVE_VERSION: u32 = 1;
+/// Header size in bytes
+const HEADER_SIZE: usize = 32;
+/// Size of a TOC entry without its name
+const TOC_ENTRY_FIXED_SIZE: usize = 2 + 8 + 8 + 8 + 1;
+
+// ===== COMPRESSION =====
+
+/// Storage of an archive entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveCompression {
+    /// Raw bytes, read without a copy
+    None,
+    /// zstd stream at the given level (1-22, 3 is zstd's default)
+    Zstd(i32),
+}
+
+impl ArchiveCompression {
+    fn tag(self) -> u8 {
+        match self {
+            Self::None => 0,
+            Self::Zstd(_) => 1,
+        }
+    }
+}
+
+/// An entry of the table of contents
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveEntry {
+    /// Offset of the stored bytes from the start of the file
+    pub offset: u64,
+    /// Bytes stored in the archive
+    pub stored_size: u64,
+    /// Bytes once decompressed
+    pub size: u64,
+    /// True for zstd entries
+    pub compressed: bool,
+}
+
+// ===== WRITER =====
+
+/// Builds an archive in memory and writes it (see module docs).
+#[derive(Default)]
+pub struct AssetArchiveWriter {
+    names: Vec<String>,
+    entries: Vec<ArchiveEntry>,
+    blobs: Vec<u8>,
+}
+
+impl AssetArchiveWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of entries added
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Add an entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `name` is empty, longer than 65535 bytes or
+    /// already added, or if compression fails.
+    pub fn add(&mut self, name: impl Into<String>, data: &[u8], compression: ArchiveCompression) -> Result<()> {
+        let name = name.into();
+        if name.is_empty() || name.len() > u16::MAX as usize {
+            engine_bail!("galaxy3d::AssetArchive", "Invalid entry name length {}", name.len());
+        }
+        if self.names.contains(&name) {
+            engine_bail!("galaxy3d::AssetArchive", "Entry '{}' already added", name);
+        }
+        let stored: Cow<[u8]> = match compression {
+            ArchiveCompression::None => Cow::Borrowed(data),
+            ArchiveCompression::Zstd(level) => Cow::Owned(zstd::bulk::compress(data, level)
+                .map_err(|e| engine_err!("galaxy3d::AssetArchive", "Cannot compress '{}': {}", name, e))?),
+        };
+        self.entries.push(ArchiveEntry {
+            offset: (HEADER_SIZE + self.blobs.len()) as u64,
+            stored_size: stored.len() as u64,
+            size: data.len() as u64,
+            compressed: compression.tag() != 0,
+        });
+        self.blobs.extend_from_slice(&stored);
+        self.names.push(name);
+        Ok(())
+    }
+
+    /// Add the content of a file
+    pub fn add_file(
+        &mut self,
+        name: impl Into<String>,
+        path: impl AsRef<Path>,
+        compression: ArchiveCompression,
+    ) -> Result<()> {
+        let path = path.as_ref();
+        let data = std::fs::read(path).map_err(|e| engine_err!("galaxy3d::AssetArchive",
+            "Cannot read '{}': {}", path.display(), e))?;
+        self.add(name, &data, compression)
+    }
+
+    /// Archive bytes (header, blobs, TOC)
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut toc = Vec::new();
+        for (name, entry) in self.names.iter().zip(&self.entries) {
+            toc.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            toc.extend_from_slice(name.as_bytes());
+            toc.extend_from_slice(&entry.offset.to_le_bytes());
+            toc.extend_from_slice(&entry.stored_size.to_le_bytes());
+            toc.extend_from_slice(&entry.size.to_le_bytes());
+            toc.push(entry.compressed as u8);
+        }
+        let toc_offset = (HEADER_SIZE + self.blobs.len()) as u64;
+
+        let mut bytes = Vec::with_capacity(HEADER_SIZE + self.blobs.len() + toc.len());
+        bytes.extend_from_slice(&ARCHIVE_MAGIC);
+        bytes.extend_from_slice(&ARCHIVE_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&(self.entries.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&toc_offset.to_le_bytes());
+        bytes.extend_from_slice(&(toc.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&self.blobs);
+        bytes.extend_from_slice(&toc);
+        bytes
+    }
+
+    /// Write the archive to `path` (atomically, see `write_atomic`)
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<()> {
+        write_atomic(path, &self.to_bytes())
+    }
+}
+
+// ===== READER =====
+
+/// Bytes of an open archive
+enum ArchiveBytes {
+    Mapped(memmap2::Mmap),
+    Owned(Vec<u8>),
+}
+
+impl ArchiveBytes {
+    fn as_slice(&self) -> &[u8] {
+        match self {
+            Self::Mapped(map) => map,
+            Self::Owned(bytes) => bytes,
+        }
+    }
+}
+
+/// A read-only archive (see module docs).
+pub struct AssetArchive {
+    path: Option<PathBuf>,
+    bytes: ArchiveBytes,
+    entries: FxHashMap<String, ArchiveEntry>,
+}
+
+impl AssetArchive {
+    /// Memory-map the archive at `path` and read its TOC.
+    ///
+    /// The file must not be modified while the archive is open.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or mapped, or is not a
+    /// valid archive.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|e| engine_err!("galaxy3d::AssetArchive",
+            "Cannot open '{}': {}", path.display(), e))?;
+        // SAFETY: the mapping is read-only; the caller keeps the file
+        // unmodified while the archive is open (see above).
+        let map = unsafe { memmap2::Mmap::map(&file) }.map_err(|e| engine_err!("galaxy3d::AssetArchive",
+            "Cannot map '{}': {}", path.display(), e))?;
+        let mut archive = Self::from_archive_bytes(ArchiveBytes::Mapped(map))?;
+        archive.path = Some(path.to_path_buf());
+        Ok(archive)
+    }
+
+    /// Read an archive held in memory
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self> {
+        Self::from_archive_bytes(ArchiveBytes::Owned(bytes))
+    }
+
+    /// File of the archive (None when read from memory)
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.contains_key(name)
+    }
+
+    /// TOC entry of `name`
+    pub fn entry(&self, name: &str) -> Option<&ArchiveEntry> {
+        self.entries.get(name)
+    }
+
+    /// Entry names (unordered)
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    /// Content of `name`: borrowed from the mapping for raw entries,
+    /// decompressed for zstd entries.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `name` is unknown or its data is corrupt.
+    pub fn read(&self, name: &str) -> Result<Cow<'_, [u8]>> {
+        let Some(entry) = self.entries.get(name) else {
+            engine_bail!("galaxy3d::AssetArchive", "Archive has no entry '{}'", name);
+        };
+        // Range checked in from_archive_bytes()
+        let start = entry.offset as usize;
+        let stored = &self.bytes.as_slice()[start..start + entry.stored_size as usize];
+        if !entry.compressed {
+            return Ok(Cow::Borrowed(stored));
+        }
+        let data = zstd::bulk::decompress(stored, entry.size as usize)
+            .map_err(|e| engine_err!("galaxy3d::AssetArchive", "Cannot decompress '{}': {}", name, e))?;
+        if data.len() as u64 != entry.size {
+            engine_bail!("galaxy3d::AssetArchive",
+                "Entry '{}' decompressed to {} bytes, expected {}", name, data.len(), entry.size);
+        }
+        Ok(Cow::Owned(data))
+    }
+
+    // ===== PRIVATE HELPERS =====
+
+    fn from_archive_bytes(bytes: ArchiveBytes) -> Result<Self> {
+        let entries = parse_toc(bytes.as_slice())?;
+        Ok(Self { path: None, bytes, entries })
+    }
+}
+
+/// Cursor over little-endian fields
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let Some(slice) = self.bytes.get(self.pos..self.pos + len) else {
+            engine_bail!("galaxy3d::AssetArchive", "Truncated archive");
+        };
+        self.pos += len;
+        Ok(slice)
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+}
+
+/// Validate the header and read the TOC
+fn parse_toc(bytes: &[u8]) -> Result<FxHashMap<String, ArchiveEntry>> {
+    let mut header = Reader { bytes, pos: 0 };
+    if header.take(ARCHIVE_MAGIC.len())? != ARCHIVE_MAGIC {
+        engine_bail!("galaxy3d::AssetArchive", "Not an asset archive (bad magic)");
+    }
+    let version = header.u32()?;
+    if version != ARCHIVE_VERSION {
+        engine_bail!("galaxy3d::AssetArchive",
+            "Unsupported archive version {} (expected {})", version, ARCHIVE_VERSION);
+    }
+    let count = header.u32()? as usize;
+    let toc_offset = header.u64()?;
+    let toc_size = header.u64()?;
+    let Some(toc) = toc_offset.checked_add(toc_size)
+        .filter(|&end| end <= bytes.len() as u64)
+        .map(|end| &bytes[toc_offset as usize..end as usize])
+    else {
+        engine_bail!("galaxy3d::AssetArchive", "TOC out of the archive bounds");
+    };
+
+    let mut reader = Reader { bytes: toc, pos: 0 };
+    let mut entries = FxHashMap::default();
+    entries.reserve(count.min(toc.len() / TOC_ENTRY_FIXED_SIZE));
+    for _ in 0..count {
+        let name_len = reader.u16()? as usize;
+        let name = std::str::from_utf8(reader.take(name_len)?)
+            .map_err(|_| engine_err!("galaxy3d::AssetArchive", "Entry name is not UTF-8"))?
+            .to_string();
+        let entry = ArchiveEntry {
+            offset: reader.u64()?,
+            stored_size: reader.u64()?,
+            size: reader.u64()?,
+            compressed: match reader.take(1)?[0] {
+                0 => false,
+                1 => true,
+                tag => engine_bail!("galaxy3d::AssetArchive", "Entry '{}' has unknown compression {}", name, tag),
+            },
+        };
+        let in_bounds = entry.offset.checked_add(entry.stored_size).is_some_and(|end| end <= toc_offset);
+        if !in_bounds || (!entry.compressed && entry.stored_size != entry.size) {
+            engine_bail!("galaxy3d::AssetArchive", "Entry '{}' has an invalid range", name);
+        }
+        if entries.insert(name.clone(), entry).is_some() {
+            engine_bail!("galaxy3d::AssetArchive", "Duplicate entry '{}'", name);
+        }
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+#[path = "asset_archive_tests.rs"]
+mod tests;
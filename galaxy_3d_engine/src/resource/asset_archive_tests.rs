@@ -0,0 +1,136 @@
+/// Unit tests for asset_archive.rs
+
+use super::*;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use crate::graphics_device::{self, GraphicsDevice};
+use crate::graphics_device::mock_graphics_device::MockGraphicsDevice;
+use crate::resource::resource_manager::ResourceManager;
+use crate::resource::streaming::{LoadState, StreamedAsset, StreamingBudget, StreamingManager};
+use crate::resource::texture::{LayerDesc, TextureDesc};
+
+/// Compressible payload of `len` bytes
+fn payload(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i / 64) as u8).collect()
+}
+
+fn sample_writer() -> AssetArchiveWriter {
+    let mut writer = AssetArchiveWriter::new();
+    writer.add("raw.bin", &payload(1000), ArchiveCompression::None).unwrap();
+    writer.add("packed.bin", &payload(8192), ArchiveCompression::Zstd(3)).unwrap();
+    writer.add("empty.bin", &[], ArchiveCompression::Zstd(3)).unwrap();
+    writer
+}
+
+// ============================================================================
+// Format
+// ============================================================================
+
+#[test]
+fn test_round_trip_raw_and_compressed_entries() {
+    let archive = AssetArchive::from_bytes(sample_writer().to_bytes()).unwrap();
+    assert_eq!(archive.len(), 3);
+
+    let raw = archive.read("raw.bin").unwrap();
+    assert!(matches!(raw, Cow::Borrowed(_)));
+    assert_eq!(&*raw, payload(1000).as_slice());
+
+    let packed = archive.entry("packed.bin").unwrap();
+    assert!(packed.compressed);
+    assert_eq!(packed.size, 8192);
+    assert!(packed.stored_size < packed.size);
+    assert_eq!(&*archive.read("packed.bin").unwrap(), payload(8192).as_slice());
+    assert!(archive.read("empty.bin").unwrap().is_empty());
+
+    assert!(!archive.contains("missing.bin"));
+    assert!(archive.read("missing.bin").is_err());
+}
+
+#[test]
+fn test_writer_rejects_invalid_names() {
+    let mut writer = AssetArchiveWriter::new();
+    writer.add("a", &[1], ArchiveCompression::None).unwrap();
+    assert!(writer.add("a", &[2], ArchiveCompression::None).is_err());
+    assert!(writer.add("", &[2], ArchiveCompression::None).is_err());
+    assert_eq!(writer.len(), 1);
+}
+
+#[test]
+fn test_rejects_corrupt_archives() {
+    let bytes = sample_writer().to_bytes();
+
+    let mut bad_magic = bytes.clone();
+    bad_magic[0] = b'X';
+    assert!(AssetArchive::from_bytes(bad_magic).is_err());
+
+    let mut bad_version = bytes.clone();
+    bad_version[8] = 99;
+    assert!(AssetArchive::from_bytes(bad_version).is_err());
+
+    assert!(AssetArchive::from_bytes(bytes[..bytes.len() - 1].to_vec()).is_err());
+    assert!(AssetArchive::from_bytes(bytes[..HEADER_SIZE - 1].to_vec()).is_err());
+}
+
+#[test]
+fn test_open_maps_written_file() {
+    let path = std::env::temp_dir()
+        .join(format!("galaxy3d_archive_{}_open.pak", std::process::id()));
+    sample_writer().write(&path).unwrap();
+
+    let archive = AssetArchive::open(&path).unwrap();
+    assert_eq!(archive.path(), Some(path.as_path()));
+    assert_eq!(&*archive.read("packed.bin").unwrap(), payload(8192).as_slice());
+    drop(archive);
+
+    let _ = std::fs::remove_file(&path);
+    assert!(AssetArchive::open(&path).is_err());
+}
+
+// ============================================================================
+// Streaming
+// ============================================================================
+
+#[test]
+fn test_streaming_decodes_archive_entries() {
+    let mut writer = AssetArchiveWriter::new();
+    writer.add("textures/checker.rgba", &[255u8; 16], ArchiveCompression::Zstd(3)).unwrap();
+    let archive = Arc::new(AssetArchive::from_bytes(writer.to_bytes()).unwrap());
+
+    let gd: Arc<Mutex<dyn GraphicsDevice>> = Arc::new(Mutex::new(MockGraphicsDevice::new()));
+    let mut rm = ResourceManager::new();
+    let mut streaming = StreamingManager::new(1, StreamingBudget::default()).unwrap();
+
+    let device = gd.clone();
+    assert!(streaming.request_from_archive("checker", 0, &archive, "textures/checker.rgba", move |bytes| {
+        Ok(StreamedAsset::Texture(TextureDesc {
+            graphics_device: device,
+            texture: graphics_device::TextureDesc {
+                width: 2,
+                height: 2,
+                depth: 1,
+                format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
+                usage: graphics_device::TextureUsage::Sampled,
+                array_layers: 1,
+                data: Some(graphics_device::TextureData::Single(bytes.to_vec())),
+                mipmap: graphics_device::MipmapMode::None,
+                texture_type: graphics_device::TextureType::Tex2D,
+                sample_count: graphics_device::SampleCount::S1,
+                debug_name: None,
+            },
+            layers: vec![LayerDesc { name: "default".to_string(), layer_index: 0, data: None, regions: vec![] }],
+        }))
+    }));
+    assert!(streaming.request_from_archive("missing", 0, &archive, "textures/missing.rgba", |_| {
+        unreachable!("decode must not run for a missing entry")
+    }));
+
+    let start = Instant::now();
+    while streaming.in_flight_count() > 0 {
+        assert!(start.elapsed() < Duration::from_secs(5), "streaming did not settle");
+        streaming.update(&mut rm);
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    assert!(streaming.is_resident("checker"));
+    assert!(rm.texture_key("checker").is_some());
+    assert!(matches!(streaming.load_state("missing"), Some(LoadState::Failed(_))));
+}
@@ -0,0 +1,219 @@
+/// Rectangle packing inside an atlas layer.
+///
+/// `AtlasPacker` tracks the free space of a texture layer as a set of
+/// maximal free rectangles (MaxRects): every free rectangle is as large as
+/// possible, so they overlap. A new rectangle goes to the free rectangle
+/// that leaves the shortest leftover side (best short side fit), then every
+/// free rectangle it overlaps is split around it.
+///
+/// Regions declared by hand (`AtlasRegionDesc`) are reserved: allocations
+/// never overlap them. Freeing an allocation rebuilds the free rectangles
+/// from the remaining regions, so the free space never stays split by
+/// rectangles that are gone. `AtlasStats::fragmentation` tells when the
+/// free space is scattered enough to be worth repacking the atlas.
+
+use super::texture::AtlasRegion;
+
+/// Occupancy of an atlas layer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AtlasStats {
+    /// Layer area, in pixels
+    pub total_area: u64,
+    /// Regions handed out by `allocate_region()` and not freed yet
+    pub allocated_regions: usize,
+    /// Area of the allocated and declared regions
+    pub used_area: u64,
+    pub free_area: u64,
+    /// Area of the largest rectangle that can still be allocated
+    pub largest_free_area: u64,
+    /// Number of maximal free rectangles
+    pub free_rect_count: usize,
+    /// `1 - largest_free_area / free_area`: 0 when the free space is one
+    /// rectangle, close to 1 when it is scattered in small pieces
+    pub fragmentation: f32,
+}
+
+/// MaxRects allocator of one atlas layer.
+pub(crate) struct AtlasPacker {
+    width: u32,
+    height: u32,
+    /// Regions declared by hand (never freed, may overlap)
+    reserved: Vec<AtlasRegion>,
+    /// Regions handed out by `allocate()`
+    allocated: Vec<AtlasRegion>,
+    /// Maximal free rectangles
+    free: Vec<AtlasRegion>,
+}
+
+impl AtlasPacker {
+    /// Create the packer of a `width` x `height` layer whose `reserved`
+    /// regions are already used.
+    pub(crate) fn new(width: u32, height: u32, reserved: &[AtlasRegion]) -> Self {
+        let mut packer = Self {
+            width,
+            height,
+            reserved: reserved.to_vec(),
+            allocated: Vec::new(),
+            free: Vec::new(),
+        };
+        packer.rebuild_free();
+        packer
+    }
+
+    /// Mark a region declared by hand as used
+    pub(crate) fn reserve(&mut self, region: &AtlasRegion) {
+        self.reserved.push(region.clone());
+        self.occupy(region);
+    }
+
+    /// Place a `width` x `height` rectangle, or None if it fits nowhere.
+    pub(crate) fn allocate(&mut self, width: u32, height: u32) -> Option<AtlasRegion> {
+        let best = self.free.iter()
+            .filter(|rect| rect.width >= width && rect.height >= height)
+            .min_by_key(|rect| {
+                let (dx, dy) = (rect.width - width, rect.height - height);
+                (dx.min(dy), dx.max(dy))
+            })?;
+        let region = AtlasRegion { x: best.x, y: best.y, width, height };
+        self.occupy(&region);
+        self.allocated.push(region.clone());
+        Some(region)
+    }
+
+    /// Give back an allocated region. Returns false if `region` was not
+    /// allocated by this packer.
+    pub(crate) fn free(&mut self, region: &AtlasRegion) -> bool {
+        let Some(index) = self.allocated.iter().position(|r| r == region) else {
+            return false;
+        };
+        self.allocated.swap_remove(index);
+        self.rebuild_free();
+        true
+    }
+
+    pub(crate) fn stats(&self) -> AtlasStats {
+        let total_area = self.width as u64 * self.height as u64;
+        let used: Vec<&AtlasRegion> = self.reserved.iter().chain(&self.allocated).collect();
+        let free_area = total_area - union_area(&used);
+        let largest_free_area = self.free.iter().map(area).max().unwrap_or(0);
+        let fragmentation = if free_area == 0 {
+            0.0
+        } else {
+            1.0 - largest_free_area as f32 / free_area as f32
+        };
+        AtlasStats {
+            total_area,
+            allocated_regions: self.allocated.len(),
+            used_area: total_area - free_area,
+            free_area,
+            largest_free_area,
+            free_rect_count: self.free.len(),
+            fragmentation,
+        }
+    }
+
+    // ===== PRIVATE HELPERS =====
+
+    /// Recompute the free rectangles from the used regions
+    fn rebuild_free(&mut self) {
+        self.free.clear();
+        if self.width > 0 && self.height > 0 {
+            self.free.push(AtlasRegion { x: 0, y: 0, width: self.width, height: self.height });
+        }
+        let used: Vec<AtlasRegion> = self.reserved.iter().chain(&self.allocated).cloned().collect();
+        for region in &used {
+            self.occupy(region);
+        }
+    }
+
+    /// Split every free rectangle overlapping `used` into the (up to four)
+    /// maximal rectangles around it, then drop the rectangles contained in
+    /// another one.
+    fn occupy(&mut self, used: &AtlasRegion) {
+        let mut split = Vec::with_capacity(self.free.len());
+        for rect in self.free.drain(..) {
+            if !overlaps(&rect, used) {
+                split.push(rect);
+                continue;
+            }
+            let (rect_right, rect_bottom) = (rect.x + rect.width, rect.y + rect.height);
+            let (used_right, used_bottom) = (used.x + used.width, used.y + used.height);
+            if used.x > rect.x {
+                split.push(AtlasRegion { width: used.x - rect.x, ..rect.clone() });
+            }
+            if used_right < rect_right {
+                split.push(AtlasRegion { x: used_right, width: rect_right - used_right, ..rect.clone() });
+            }
+            if used.y > rect.y {
+                split.push(AtlasRegion { height: used.y - rect.y, ..rect.clone() });
+            }
+            if used_bottom < rect_bottom {
+                split.push(AtlasRegion { y: used_bottom, height: rect_bottom - used_bottom, ..rect });
+            }
+        }
+
+        for (i, rect) in split.iter().enumerate() {
+            let redundant = split.iter().enumerate().any(|(j, other)| {
+                i != j && contains(other, rect) && (rect != other || j < i)
+            });
+            if !redundant {
+                self.free.push(rect.clone());
+            }
+        }
+    }
+}
+
+fn area(region: &AtlasRegion) -> u64 {
+    region.width as u64 * region.height as u64
+}
+
+fn overlaps(a: &AtlasRegion, b: &AtlasRegion) -> bool {
+    a.x < b.x + b.width && b.x < a.x + a.width && a.y < b.y + b.height && b.y < a.y + a.height
+}
+
+fn contains(outer: &AtlasRegion, inner: &AtlasRegion) -> bool {
+    inner.x >= outer.x && inner.y >= outer.y
+        && inner.x + inner.width <= outer.x + outer.width
+        && inner.y + inner.height <= outer.y + outer.height
+}
+
+/// Area covered by possibly overlapping regions (sweep over the x edges)
+fn union_area(regions: &[&AtlasRegion]) -> u64 {
+    let mut edges: Vec<u32> = regions.iter().flat_map(|r| [r.x, r.x + r.width]).collect();
+    edges.sort_unstable();
+    edges.dedup();
+
+    let mut covered = 0;
+    let mut spans = Vec::new();
+    for slab in edges.windows(2) {
+        let (left, right) = (slab[0], slab[1]);
+        spans.clear();
+        spans.extend(regions.iter()
+            .filter(|r| r.x <= left && r.x + r.width >= right)
+            .map(|r| (r.y, r.y + r.height)));
+        spans.sort_unstable();
+
+        let mut height = 0;
+        let mut current: Option<(u32, u32)> = None;
+        for &(top, bottom) in &spans {
+            match current {
+                Some((start, end)) if top <= end => current = Some((start, end.max(bottom))),
+                _ => {
+                    if let Some((start, end)) = current {
+                        height += (end - start) as u64;
+                    }
+                    current = Some((top, bottom));
+                }
+            }
+        }
+        if let Some((start, end)) = current {
+            height += (end - start) as u64;
+        }
+        covered += height * (right - left) as u64;
+    }
+    covered
+}
+
+#[cfg(test)]
+#[path = "atlas_packer_tests.rs"]
+mod tests;
@@ -0,0 +1,117 @@
+/// Unit tests for atlas_packer.rs
+
+use super::*;
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+fn region(x: u32, y: u32, width: u32, height: u32) -> AtlasRegion {
+    AtlasRegion { x, y, width, height }
+}
+
+fn assert_disjoint(regions: &[AtlasRegion]) {
+    for (i, a) in regions.iter().enumerate() {
+        for b in &regions[i + 1..] {
+            assert!(!overlaps(a, b), "{:?} overlaps {:?}", a, b);
+        }
+    }
+}
+
+// ============================================================================
+// ALLOCATION TESTS
+// ============================================================================
+
+#[test]
+fn test_allocate_fills_layer_without_overlap() {
+    let mut packer = AtlasPacker::new(64, 64, &[]);
+    let regions: Vec<AtlasRegion> = (0..16).map(|_| packer.allocate(16, 16).unwrap()).collect();
+
+    assert_disjoint(&regions);
+    assert!(regions.iter().all(|r| r.x + r.width <= 64 && r.y + r.height <= 64));
+    assert!(packer.allocate(1, 1).is_none());
+    assert_eq!(packer.stats().free_area, 0);
+    assert_eq!(packer.stats().allocated_regions, 16);
+}
+
+#[test]
+fn test_allocate_mixed_sizes() {
+    let mut packer = AtlasPacker::new(128, 128, &[]);
+    let mut regions = Vec::new();
+    for (width, height) in [(64, 32), (32, 64), (50, 20), (10, 90), (64, 64), (7, 7)] {
+        regions.push(packer.allocate(width, height).unwrap());
+    }
+    assert_disjoint(&regions);
+    assert!(packer.allocate(129, 1).is_none());
+}
+
+#[test]
+fn test_allocate_avoids_reserved_regions() {
+    let reserved = [region(0, 0, 48, 48), region(16, 16, 48, 48)];
+    let mut packer = AtlasPacker::new(64, 64, &reserved);
+    assert_eq!(packer.stats().used_area, 48 * 48 * 2 - 32 * 32);
+
+    let allocated = packer.allocate(16, 16).unwrap();
+    assert!(reserved.iter().all(|r| !overlaps(r, &allocated)));
+
+    packer.reserve(&region(48, 0, 16, 16));
+    while let Some(next) = packer.allocate(16, 16) {
+        assert!(!overlaps(&next, &region(48, 0, 16, 16)));
+    }
+    assert_eq!(packer.stats().free_area, 0);
+}
+
+// ============================================================================
+// FREE TESTS
+// ============================================================================
+
+#[test]
+fn test_free_merges_space_back() {
+    let mut packer = AtlasPacker::new(64, 64, &[]);
+    let regions: Vec<AtlasRegion> = (0..4).map(|_| packer.allocate(32, 32).unwrap()).collect();
+    assert!(packer.allocate(64, 64).is_none());
+
+    for r in &regions {
+        assert!(packer.free(r));
+    }
+    assert!(!packer.free(&regions[0]));
+    assert_eq!(packer.stats().free_rect_count, 1);
+    assert_eq!(packer.allocate(64, 64), Some(region(0, 0, 64, 64)));
+}
+
+#[test]
+fn test_free_unknown_region_is_ignored() {
+    let reserved = region(0, 0, 8, 8);
+    let mut packer = AtlasPacker::new(64, 64, std::slice::from_ref(&reserved));
+    assert!(!packer.free(&reserved));
+    assert_eq!(packer.stats().used_area, 64);
+}
+
+// ============================================================================
+// STATS TESTS
+// ============================================================================
+
+#[test]
+fn test_stats_report_fragmentation() {
+    let mut packer = AtlasPacker::new(64, 64, &[]);
+    let empty = packer.stats();
+    assert_eq!(empty.total_area, 4096);
+    assert_eq!(empty.free_area, 4096);
+    assert_eq!(empty.largest_free_area, 4096);
+    assert_eq!(empty.fragmentation, 0.0);
+
+    // Checkerboard of 32x32 quadrants: two free squares that cannot merge
+    let regions: Vec<AtlasRegion> = (0..4).map(|_| packer.allocate(32, 32).unwrap()).collect();
+    let diagonal = regions.iter()
+        .find(|r| r.x == 0 && r.y == 0).into_iter()
+        .chain(regions.iter().find(|r| r.x == 32 && r.y == 32));
+    for r in diagonal.cloned().collect::<Vec<_>>() {
+        packer.free(&r);
+    }
+
+    let stats = packer.stats();
+    assert_eq!(stats.free_area, 2048);
+    assert_eq!(stats.largest_free_area, 1024);
+    assert_eq!(stats.fragmentation, 0.5);
+    assert!(packer.allocate(64, 32).is_none());
+}
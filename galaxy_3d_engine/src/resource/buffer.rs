@@ -111,7 +111,7 @@ pub struct Buffer {
 }
 
 impl Buffer {
-    pub(crate) fn from_desc(desc: BufferDesc) -> Result<Self> {
+    pub(crate) fn from_desc(desc: BufferDesc, name: &str) -> Result<Self> {
         // ========== VALIDATION ==========
         if desc.fields.is_empty() {
             engine_bail!("galaxy3d::Buffer", "Buffer must have at least one field");
@@ -165,7 +165,9 @@ impl Buffer {
         };
 
         let graphics_device_buffer = desc.graphics_device.lock().unwrap()
-            .create_buffer(graphics_device::BufferDesc { size, usage })?;
+            .create_buffer(graphics_device::BufferDesc {
+                size, usage, debug_name: Some(name.to_string()),
+            })?;
 
         Ok(Self {
             graphics_device_buffer,
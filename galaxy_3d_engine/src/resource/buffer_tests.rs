@@ -24,7 +24,7 @@ fn create_test_buffer(kind: BufferKind, fields: &[(&str, FieldType)], count: u32
         kind,
         fields: make_fields(fields),
         count,
-    }).unwrap()
+    }, "test").unwrap()
 }
 
 // ============================================================================
@@ -67,7 +67,7 @@ fn test_empty_fields_fails() {
         kind: BufferKind::Storage,
         fields: vec![],
         count: 10,
-    });
+    }, "test");
     assert!(result.is_err());
 }
 
@@ -79,7 +79,7 @@ fn test_zero_count_fails() {
         kind: BufferKind::Storage,
         fields: make_fields(&[("world", FieldType::Mat4)]),
         count: 0,
-    });
+    }, "test");
     assert!(result.is_err());
 }
 
@@ -94,7 +94,7 @@ fn test_duplicate_field_names_fails() {
             ("world", FieldType::Mat4),
         ]),
         count: 10,
-    });
+    }, "test");
     assert!(result.is_err());
 }
 
@@ -0,0 +1,165 @@
+/// Engine-created fallback resources.
+///
+/// `ResourceManager::create_default_resources()` registers:
+/// - 1x1 white, black and flat-normal textures;
+/// - a magenta "error" material;
+/// - an error cube geometry and mesh using that material.
+///
+/// Once created, they replace missing references instead of failing:
+/// a material texture slot whose texture does not exist binds the fallback
+/// texture matching the slot name (`fallback_texture_for_slot`), and a mesh
+/// submesh whose material does not exist uses the error material. Broken
+/// content then renders visibly wrong instead of aborting resource creation.
+/// Asset loaders bind the same resources when a load fails.
+///
+/// The error material's fragment shaders are supplied by the application
+/// (`DefaultResourcesDesc::error_passes`). The standard PBR fragment shader
+/// fits (`resource::standard_pbr`): the error cube has its vertex layout.
+
+use std::sync::{Arc, Mutex};
+use crate::graphics_device;
+use crate::resource::resource_manager::{TextureKey, MaterialKey, MeshKey, GeometryKey, ShaderKey};
+
+/// Name of the 1x1 opaque white texture
+pub const DEFAULT_WHITE_TEXTURE: &str = "galaxy3d::white";
+/// Name of the 1x1 opaque black texture
+pub const DEFAULT_BLACK_TEXTURE: &str = "galaxy3d::black";
+/// Name of the 1x1 flat tangent-space normal texture (0.5, 0.5, 1.0)
+pub const DEFAULT_NORMAL_TEXTURE: &str = "galaxy3d::flat_normal";
+/// Name of the error material
+pub const ERROR_MATERIAL: &str = "galaxy3d::error";
+/// Name of the error cube geometry
+pub const ERROR_CUBE_GEOMETRY: &str = "galaxy3d::error_cube";
+/// Name of the error cube mesh
+pub const ERROR_CUBE_MESH: &str = "galaxy3d::error_cube";
+
+/// Color of the error material (magenta)
+pub const ERROR_COLOR: [f32; 4] = [1.0, 0.0, 1.0, 1.0];
+/// Material parameter receiving `ERROR_COLOR` (field of the default material buffer)
+pub const ERROR_COLOR_PARAM: &str = "baseColor";
+/// Texture slot of the error material, bound to the white texture
+pub const ERROR_ALBEDO_SLOT: &str = "albedo";
+
+/// Texel of the white texture (RGBA8)
+const WHITE_TEXEL: [u8; 4] = [255, 255, 255, 255];
+/// Texel of the black texture (RGBA8)
+const BLACK_TEXEL: [u8; 4] = [0, 0, 0, 255];
+/// Texel of the flat normal texture (RGBA8, +Z in tangent space)
+const FLAT_NORMAL_TEXEL: [u8; 4] = [128, 128, 255, 255];
+
+/// Vertex attribute location of the error cube normal
+pub const ERROR_CUBE_NORMAL_LOCATION: u32 = 1;
+/// Vertex attribute location of the error cube texture coordinates
+pub const ERROR_CUBE_UV_LOCATION: u32 = 2;
+/// Half extent of the error cube (unit cube centered on the origin)
+const ERROR_CUBE_HALF_EXTENT: f32 = 0.5;
+/// Floats per error cube vertex: position (3) + normal (3) + uv (2)
+pub(crate) const ERROR_CUBE_VERTEX_FLOATS: usize = 8;
+/// Vertices per cube face (no shared vertices, so normals stay flat)
+const ERROR_CUBE_FACE_VERTICES: u16 = 4;
+
+/// Descriptor of `ResourceManager::create_default_resources()`
+pub struct DefaultResourcesDesc {
+    /// Graphics device creating the textures and the cube buffers
+    pub graphics_device: Arc<Mutex<dyn graphics_device::GraphicsDevice>>,
+    /// One `(pass_type, fragment_shader)` per pass of the error material.
+    /// Each pass binds the white texture to `ERROR_ALBEDO_SLOT` and sets
+    /// `ERROR_COLOR_PARAM` to `ERROR_COLOR`.
+    pub error_passes: Vec<(u8, ShaderKey)>,
+}
+
+/// Keys of the fallback resources (see module docs)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DefaultResources {
+    pub white_texture: TextureKey,
+    pub black_texture: TextureKey,
+    pub normal_texture: TextureKey,
+    pub error_material: MaterialKey,
+    pub error_cube_geometry: GeometryKey,
+    pub error_cube_mesh: MeshKey,
+}
+
+impl DefaultResources {
+    /// Fallback texture of a material texture slot, chosen by slot name:
+    /// flat normal for normal maps, black for emissive maps, white otherwise
+    /// (neutral for albedo, occlusion and metallic/roughness factors).
+    pub fn fallback_texture_for_slot(&self, slot_name: &str) -> TextureKey {
+        let slot_name = slot_name.to_ascii_lowercase();
+        if slot_name.contains("normal") {
+            self.normal_texture
+        } else if slot_name.contains("emissive") {
+            self.black_texture
+        } else {
+            self.white_texture
+        }
+    }
+}
+
+/// The three 1x1 fallback textures, as `(name, texel)`
+pub(crate) fn default_texture_texels() -> [(&'static str, [u8; 4]); 3] {
+    [
+        (DEFAULT_WHITE_TEXTURE, WHITE_TEXEL),
+        (DEFAULT_BLACK_TEXTURE, BLACK_TEXEL),
+        (DEFAULT_NORMAL_TEXTURE, FLAT_NORMAL_TEXEL),
+    ]
+}
+
+/// Vertex layout of the error cube: position, normal and uv in one binding
+pub(crate) fn error_cube_vertex_layout() -> graphics_device::VertexLayout {
+    const FLOAT_SIZE: u32 = 4;
+    let attribute = |location, format, floats_before: u32| graphics_device::VertexAttribute {
+        location,
+        binding: 0,
+        format,
+        offset: floats_before * FLOAT_SIZE,
+    };
+    graphics_device::VertexLayout {
+        bindings: vec![graphics_device::VertexBinding {
+            binding: 0,
+            stride: ERROR_CUBE_VERTEX_FLOATS as u32 * FLOAT_SIZE,
+            input_rate: graphics_device::VertexInputRate::Vertex,
+        }],
+        attributes: vec![
+            attribute(crate::resource::geometry::GEOMETRY_POSITION_LOCATION,
+                graphics_device::BufferFormat::R32G32B32_SFLOAT, 0),
+            attribute(ERROR_CUBE_NORMAL_LOCATION, graphics_device::BufferFormat::R32G32B32_SFLOAT, 3),
+            attribute(ERROR_CUBE_UV_LOCATION, graphics_device::BufferFormat::R32G32_SFLOAT, 6),
+        ],
+    }
+}
+
+/// Vertices (interleaved f32) and u16 indices of the error cube:
+/// 6 faces of 4 vertices, counter-clockwise seen from outside.
+pub(crate) fn error_cube_data() -> (Vec<f32>, Vec<u16>) {
+    // (normal, tangent u axis, tangent v axis); u x v == normal
+    const FACES: [([f32; 3], [f32; 3], [f32; 3]); 6] = [
+        ([ 1.0, 0.0, 0.0], [0.0, 0.0, -1.0], [0.0, 1.0, 0.0]),
+        ([-1.0, 0.0, 0.0], [0.0, 0.0,  1.0], [0.0, 1.0, 0.0]),
+        ([0.0,  1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, -1.0]),
+        ([0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0,  1.0]),
+        ([0.0, 0.0,  1.0], [ 1.0, 0.0, 0.0], [0.0, 1.0, 0.0]),
+        ([0.0, 0.0, -1.0], [-1.0, 0.0, 0.0], [0.0, 1.0, 0.0]),
+    ];
+    const CORNERS: [[f32; 2]; 4] = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+
+    let mut vertices = Vec::with_capacity(FACES.len() * CORNERS.len() * ERROR_CUBE_VERTEX_FLOATS);
+    let mut indices = Vec::with_capacity(FACES.len() * 6);
+    for (face, (normal, u_axis, v_axis)) in FACES.iter().enumerate() {
+        for [u, v] in CORNERS {
+            // Corner in [-1, 1] along the face tangents
+            let (su, sv) = (u * 2.0 - 1.0, v * 2.0 - 1.0);
+            for axis in 0..3 {
+                vertices.push((normal[axis] + su * u_axis[axis] + sv * v_axis[axis]) * ERROR_CUBE_HALF_EXTENT);
+            }
+            vertices.extend_from_slice(normal);
+            vertices.extend_from_slice(&[u, v]);
+        }
+        let base = face as u16 * ERROR_CUBE_FACE_VERTICES;
+        indices.extend_from_slice(&[base, base + 1, base + 2, base + 2, base + 3, base]);
+    }
+    (vertices, indices)
+}
+
+#[cfg(test)]
+#[path = "default_resources_tests.rs"]
+mod tests;
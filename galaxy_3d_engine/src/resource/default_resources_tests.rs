@@ -0,0 +1,89 @@
+/// Tests for the default (fallback) resources data
+
+use super::*;
+use glam::Vec3;
+use slotmap::SlotMap;
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Build a DefaultResources with distinct texture keys
+fn create_test_defaults() -> DefaultResources {
+    let mut textures: SlotMap<TextureKey, ()> = SlotMap::with_key();
+    DefaultResources {
+        white_texture: textures.insert(()),
+        black_texture: textures.insert(()),
+        normal_texture: textures.insert(()),
+        error_material: MaterialKey::default(),
+        error_cube_geometry: GeometryKey::default(),
+        error_cube_mesh: MeshKey::default(),
+    }
+}
+
+/// Read vertex `i` of the error cube as (position, normal, uv)
+fn cube_vertex(vertices: &[f32], i: usize) -> (Vec3, Vec3, [f32; 2]) {
+    let v = &vertices[i * ERROR_CUBE_VERTEX_FLOATS..(i + 1) * ERROR_CUBE_VERTEX_FLOATS];
+    (Vec3::new(v[0], v[1], v[2]), Vec3::new(v[3], v[4], v[5]), [v[6], v[7]])
+}
+
+// ============================================================================
+// Tests: fallback texture selection
+// ============================================================================
+
+#[test]
+fn test_fallback_texture_for_slot() {
+    let defaults = create_test_defaults();
+
+    assert_eq!(defaults.fallback_texture_for_slot("albedo"), defaults.white_texture);
+    assert_eq!(defaults.fallback_texture_for_slot("ao"), defaults.white_texture);
+    assert_eq!(defaults.fallback_texture_for_slot("normal"), defaults.normal_texture);
+    assert_eq!(defaults.fallback_texture_for_slot("detailNormalMap"), defaults.normal_texture);
+    assert_eq!(defaults.fallback_texture_for_slot("Emissive"), defaults.black_texture);
+}
+
+// ============================================================================
+// Tests: error cube
+// ============================================================================
+
+#[test]
+fn test_error_cube_counts() {
+    let (vertices, indices) = error_cube_data();
+
+    assert_eq!(vertices.len(), 24 * ERROR_CUBE_VERTEX_FLOATS);
+    assert_eq!(indices.len(), 36);
+    assert!(indices.iter().all(|&i| (i as usize) < 24));
+}
+
+#[test]
+fn test_error_cube_vertices_lie_on_their_face() {
+    let (vertices, _) = error_cube_data();
+
+    for i in 0..24 {
+        let (position, normal, [u, v]) = cube_vertex(&vertices, i);
+        assert_eq!(position.dot(normal), ERROR_CUBE_HALF_EXTENT);
+        assert_eq!(position.abs().max_element(), ERROR_CUBE_HALF_EXTENT);
+        assert!((0.0..=1.0).contains(&u) && (0.0..=1.0).contains(&v));
+    }
+}
+
+#[test]
+fn test_error_cube_triangles_face_outward() {
+    let (vertices, indices) = error_cube_data();
+
+    for triangle in indices.chunks(3) {
+        let (p0, normal, _) = cube_vertex(&vertices, triangle[0] as usize);
+        let (p1, _, _) = cube_vertex(&vertices, triangle[1] as usize);
+        let (p2, _, _) = cube_vertex(&vertices, triangle[2] as usize);
+        assert!((p1 - p0).cross(p2 - p0).dot(normal) > 0.0);
+    }
+}
+
+#[test]
+fn test_error_cube_vertex_layout_stride() {
+    let layout = error_cube_vertex_layout();
+
+    assert_eq!(layout.bindings[0].stride as usize, ERROR_CUBE_VERTEX_FLOATS * 4);
+    let locations: Vec<u32> = layout.attributes.iter().map(|a| a.location).collect();
+    assert_eq!(locations, vec![0, ERROR_CUBE_NORMAL_LOCATION, ERROR_CUBE_UV_LOCATION]);
+}
@@ -31,11 +31,30 @@
 //!         └── ...
 //! ```
 
+use std::borrow::Cow;
 use rustc_hash::FxHashMap;
 use std::sync::{Arc, Mutex};
-use crate::error::Result;
+use crate::error::{Result, ResultExt};
 use crate::{engine_bail, engine_err};
 use crate::graphics_device;
+use crate::scene::{AABB, Ray};
+use super::meshlet::{build_meshlets, Meshlet, MeshletBuffers, MeshletConfig, MeshletData, MESHLET_GPU_SIZE};
+use glam::Vec3;
+
+/// Maximum number of morph targets per Geometry (one per component of the
+/// per-instance `morphWeights` Vec4)
+pub const MAX_MORPH_TARGETS: usize = 4;
+/// Vertex input binding of the morph target delta stream
+pub const MORPH_TARGET_BUFFER_BINDING: u32 = 2;
+/// Vertex attribute location of the position delta of morph target 0.
+/// Target `i` reads its position delta at `MORPH_TARGET_FIRST_LOCATION + i`
+/// and its normal delta (if any) at `MORPH_TARGET_FIRST_LOCATION + MAX_MORPH_TARGETS + i`.
+pub const MORPH_TARGET_FIRST_LOCATION: u32 = 7;
+/// Size of one delta (vec3 of f32) in the morph target stream
+const MORPH_DELTA_SIZE: u32 = 12;
+/// Vertex attribute location of the position, read to compute `Geometry::bounds()`
+/// and kept on the CPU for `Geometry::raycast_lod()`
+pub const GEOMETRY_POSITION_LOCATION: u32 = 0;
 
 // ============================================================================
 // GEOMETRY SUBMESH LOD
@@ -58,6 +77,11 @@ pub struct GeometrySubMeshLOD {
 
     /// Primitive topology for this LOD
     topology: graphics_device::PrimitiveTopology,
+
+    /// First meshlet of this LOD in `Geometry::meshlets()`
+    meshlet_offset: u32,
+    /// Number of meshlets (0 until `Geometry::build_meshlets`)
+    meshlet_count: u32,
 }
 
 impl GeometrySubMeshLOD {
@@ -85,6 +109,16 @@ impl GeometrySubMeshLOD {
     pub fn topology(&self) -> graphics_device::PrimitiveTopology {
         self.topology
     }
+
+    /// Get the first meshlet of this LOD in `Geometry::meshlets()`
+    pub fn meshlet_offset(&self) -> u32 {
+        self.meshlet_offset
+    }
+
+    /// Get the number of meshlets (0 without meshlets)
+    pub fn meshlet_count(&self) -> u32 {
+        self.meshlet_count
+    }
 }
 
 // ============================================================================
@@ -267,6 +301,33 @@ pub struct Geometry {
     /// Unique sort id assigned at creation; used as a sort-key component to
     /// group consecutive draw calls that share the same vertex/index buffer.
     sort_id: u16,
+
+    /// Morph target deltas, bound at `MORPH_TARGET_BUFFER_BINDING` (None
+    /// without morph targets)
+    morph_target_buffer: Option<Arc<dyn graphics_device::Buffer>>,
+
+    /// Local-space bounds of all vertices (None without a float position
+    /// attribute at `GEOMETRY_POSITION_LOCATION`)
+    bounds: Option<AABB>,
+
+    /// CPU copy of the vertex positions, for raycasts (empty without a
+    /// float position attribute at `GEOMETRY_POSITION_LOCATION`)
+    positions: Vec<Vec3>,
+
+    /// CPU copy of the indices, widened to u32 (empty if non-indexed)
+    indices: Vec<u32>,
+
+    /// Morph target names, in weight order
+    morph_target_names: Vec<String>,
+
+    /// The morph targets have normal deltas
+    morph_target_normals: bool,
+
+    /// Meshlets of all LODs (empty until `build_meshlets`)
+    meshlets: Vec<Meshlet>,
+
+    /// GPU copies of the meshlet data (None until `build_meshlets`)
+    meshlet_buffers: Option<MeshletBuffers>,
 }
 
 impl Geometry {
@@ -294,13 +355,21 @@ impl Geometry {
             meshes: Vec::new(),
             mesh_names: FxHashMap::default(),
             sort_id,
+            morph_target_buffer: None,
+            morph_target_names: Vec::new(),
+            morph_target_normals: false,
+            bounds: None,
+            positions: Vec::new(),
+            indices: Vec::new(),
+            meshlets: Vec::new(),
+            meshlet_buffers: None,
         }
     }
 
     /// Create a Geometry from a descriptor
     ///
     /// Creates the GPU buffers and populates meshes from the descriptor.
-    pub(crate) fn from_desc(desc: GeometryDesc, sort_id: u16) -> Result<Self> {
+    pub(crate) fn from_desc(desc: GeometryDesc<'_>, sort_id: u16) -> Result<Self> {
         // Get stride from first binding (binding 0)
         let vertex_stride = desc.vertex_layout.bindings
             .first()
@@ -320,18 +389,30 @@ impl Geometry {
 
         let vertex_count = desc.vertex_data.len() / vertex_stride;
 
+        // Morph targets: validate and interleave the deltas per vertex
+        let mut vertex_layout = desc.vertex_layout;
+        let morph_target_data = if desc.morph_targets.is_empty() {
+            None
+        } else {
+            Some(build_morph_target_stream(&desc.morph_targets, vertex_count, &mut vertex_layout)?)
+        };
+        let positions = read_positions(&desc.vertex_data, &vertex_layout);
+        let bounds = positions.as_deref().and_then(|positions| compute_position_bounds(positions, &desc.morph_targets));
+
         // Create vertex buffer
         let vertex_buffer = {
             let mut graphics_device = desc.graphics_device.lock().unwrap();
             let buffer = graphics_device.create_buffer(graphics_device::BufferDesc {
                 size: desc.vertex_data.len() as u64,
                 usage: graphics_device::BufferUsage::Vertex,
+                debug_name: Some(format!("{} vertices", desc.name)),
             })?;
             buffer.update(0, &desc.vertex_data)?;
             buffer
         };
 
         // Create index buffer (if provided)
+        let mut indices = Vec::new();
         let (index_buffer, index_count) = if let Some(ref index_data) = desc.index_data {
             let index_size = desc.index_type.size_bytes() as usize;
 
@@ -342,13 +423,18 @@ impl Geometry {
             }
 
             let count = index_data.len() / index_size;
+            indices = index_data.chunks_exact(index_size).map(|bytes| match desc.index_type {
+                graphics_device::IndexType::U16 => u16::from_ne_bytes([bytes[0], bytes[1]]) as u32,
+                graphics_device::IndexType::U32 => u32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            }).collect();
             let buffer = {
                 let mut graphics_device = desc.graphics_device.lock().unwrap();
                 let buf = graphics_device.create_buffer(graphics_device::BufferDesc {
                     size: index_data.len() as u64,
                     usage: graphics_device::BufferUsage::Index,
+                    debug_name: Some(format!("{} indices", desc.name)),
                 })?;
-                buf.update(0, index_data)?;
+                buf.update(0, index_data.as_ref())?;
                 buf
             };
             (Some(buffer), count as u32)
@@ -356,18 +442,37 @@ impl Geometry {
             (None, 0)
         };
 
+        let morph_target_buffer = if let Some(data) = morph_target_data {
+            let mut graphics_device = desc.graphics_device.lock().unwrap();
+            let buffer = graphics_device.create_buffer(graphics_device::BufferDesc {
+                size: data.len() as u64,
+                usage: graphics_device::BufferUsage::Vertex,
+                debug_name: Some(format!("{} morph targets", desc.name)),
+            })?;
+            buffer.update(0, &data)?;
+            Some(buffer)
+        } else {
+            None
+        };
+
         // Build Geometry
         let mut geometry = Self::new(
             desc.name,
             desc.graphics_device,
             vertex_buffer,
             index_buffer,
-            desc.vertex_layout,
+            vertex_layout,
             desc.index_type,
             vertex_count as u32,
             index_count,
             sort_id,
         );
+        geometry.morph_target_buffer = morph_target_buffer;
+        geometry.bounds = bounds;
+        geometry.positions = positions.unwrap_or_default();
+        geometry.indices = indices;
+        geometry.morph_target_normals = desc.morph_targets.first().is_some_and(|t| t.normal_deltas.is_some());
+        geometry.morph_target_names = desc.morph_targets.into_iter().map(|t| t.name).collect();
 
         // Add meshes from descriptor
         for mesh_desc in desc.meshes {
@@ -417,6 +522,159 @@ impl Geometry {
         &self.vertex_layout
     }
 
+    /// Local-space bounds of every vertex, widened by the morph target
+    /// deltas (for weights in [0, 1]).
+    ///
+    /// Computed at creation from the float position attribute at
+    /// `GEOMETRY_POSITION_LOCATION` (vec2 positions get z = 0). None when
+    /// there is no such attribute or no vertex.
+    pub fn bounds(&self) -> Option<&AABB> {
+        self.bounds.as_ref()
+    }
+
+    /// CPU copy of the local-space vertex positions (empty without a float
+    /// position attribute at `GEOMETRY_POSITION_LOCATION`)
+    pub fn positions(&self) -> &[Vec3] {
+        &self.positions
+    }
+
+    /// CPU copy of the indices (empty if non-indexed)
+    pub fn indices(&self) -> &[u32] {
+        &self.indices
+    }
+
+    /// Number of triangles drawn by a LOD (0 for non-triangle topologies)
+    pub fn lod_triangle_count(&self, lod: &GeometrySubMeshLOD) -> u32 {
+        let element_count = if self.is_indexed() { lod.index_count } else { lod.vertex_count };
+        match lod.topology {
+            graphics_device::PrimitiveTopology::TriangleList => element_count / 3,
+            graphics_device::PrimitiveTopology::TriangleStrip => element_count.saturating_sub(2),
+            _ => 0,
+        }
+    }
+
+    /// Local-space corners of a triangle of a LOD, from the CPU positions
+    /// (None when out of range or without CPU positions)
+    pub fn lod_triangle(&self, lod: &GeometrySubMeshLOD, triangle: u32) -> Option<[Vec3; 3]> {
+        if triangle >= self.lod_triangle_count(lod) {
+            return None;
+        }
+        let [a, b, c] = self.lod_triangle_vertices(lod, triangle)?;
+        let position = |index: u32| self.positions.get(index as usize).copied();
+        Some([position(a)?, position(b)?, position(c)?])
+    }
+
+    /// Vertex buffer indices (base vertex applied) of a triangle of a LOD,
+    /// in element order (odd strip triangles are not rewound)
+    fn lod_triangle_vertices(&self, lod: &GeometrySubMeshLOD, triangle: u32) -> Option<[u32; 3]> {
+        if triangle >= self.lod_triangle_count(lod) {
+            return None;
+        }
+        let first = match lod.topology {
+            graphics_device::PrimitiveTopology::TriangleList => triangle * 3,
+            _ => triangle,
+        };
+        // Index of the element-th vertex of the LOD
+        let vertex = |element: u32| -> Option<u32> {
+            if self.is_indexed() {
+                Some(*self.indices.get((lod.index_offset + element) as usize)? + lod.vertex_offset)
+            } else {
+                Some(lod.vertex_offset + element)
+            }
+        };
+        Some([vertex(first)?, vertex(first + 1)?, vertex(first + 2)?])
+    }
+
+    /// Nearest triangle of a LOD hit by a local-space ray, as
+    /// `(distance, triangle index within the LOD)`.
+    ///
+    /// Uses the CPU positions in their base pose (morph targets are
+    /// ignored). Triangles are tested from both sides. Only triangle
+    /// topologies can be hit; None as well without CPU positions.
+    pub fn raycast_lod(&self, lod: &GeometrySubMeshLOD, ray: &Ray, max_distance: f32) -> Option<(f32, u32)> {
+        let mut nearest: Option<(f32, u32)> = None;
+        for triangle in 0..self.lod_triangle_count(lod) {
+            let Some([a, b, c]) = self.lod_triangle(lod, triangle) else {
+                continue;
+            };
+            if let Some(distance) = ray.intersect_triangle(a, b, c) {
+                if distance <= max_distance && nearest.is_none_or(|(best, _)| distance < best) {
+                    nearest = Some((distance, triangle));
+                }
+            }
+        }
+        nearest
+    }
+
+    /// Triangles of a LOD as a BLAS geometry, for
+    /// `GraphicsDevice::create_blas` (morph targets are ignored).
+    ///
+    /// None unless the LOD is a triangle list and the position attribute
+    /// at `GEOMETRY_POSITION_LOCATION` is a vec2/vec3 float or a vec2/vec4
+    /// half float of the vertex buffer binding (0).
+    pub fn lod_blas_geometry(&self, lod: &GeometrySubMeshLOD, opaque: bool) -> Option<graphics_device::BlasGeometry> {
+        if lod.topology != graphics_device::PrimitiveTopology::TriangleList {
+            return None;
+        }
+        let attribute = self.vertex_layout.attributes.iter()
+            .find(|a| a.location == GEOMETRY_POSITION_LOCATION && a.binding == 0)?;
+        if !matches!(attribute.format,
+            graphics_device::BufferFormat::R32G32B32_SFLOAT
+            | graphics_device::BufferFormat::R32G32_SFLOAT
+            | graphics_device::BufferFormat::R16G16B16A16_SFLOAT
+            | graphics_device::BufferFormat::R16G16_SFLOAT)
+        {
+            return None;
+        }
+        let binding = self.vertex_layout.bindings.iter().find(|b| b.binding == 0)?;
+        Some(graphics_device::BlasGeometry {
+            vertex_buffer: Arc::clone(&self.vertex_buffer),
+            vertex_format: attribute.format,
+            vertex_stride: binding.stride,
+            position_offset: attribute.offset,
+            first_vertex: lod.vertex_offset,
+            vertex_count: lod.vertex_count,
+            index_buffer: self.index_buffer.clone(),
+            index_type: self.index_type,
+            first_index: lod.index_offset,
+            triangle_count: self.lod_triangle_count(lod),
+            opaque,
+        })
+    }
+
+    /// Meshlets of all LODs, each LOD owning the range
+    /// `meshlet_offset()..meshlet_offset() + meshlet_count()` (empty until
+    /// `build_meshlets`)
+    pub fn meshlets(&self) -> &[Meshlet] {
+        &self.meshlets
+    }
+
+    /// GPU copies of the meshlet data (None until `build_meshlets`)
+    pub fn meshlet_buffers(&self) -> Option<&MeshletBuffers> {
+        self.meshlet_buffers.as_ref()
+    }
+
+    /// Get the morph target delta buffer (None without morph targets)
+    pub fn morph_target_buffer(&self) -> Option<&Arc<dyn graphics_device::Buffer>> {
+        self.morph_target_buffer.as_ref()
+    }
+
+    /// Get the number of morph targets
+    pub fn morph_target_count(&self) -> usize {
+        self.morph_target_names.len()
+    }
+
+    /// Whether the morph targets have normal deltas (false without morph
+    /// targets)
+    pub fn morph_target_has_normals(&self) -> bool {
+        self.morph_target_normals
+    }
+
+    /// Get the weight index of a morph target by name
+    pub fn morph_target_id(&self, name: &str) -> Option<usize> {
+        self.morph_target_names.iter().position(|n| n == name)
+    }
+
     /// Get the index type (only meaningful if indexed)
     pub fn index_type(&self) -> graphics_device::IndexType {
         self.index_type
@@ -483,6 +741,90 @@ impl Geometry {
 
     // ===== MODIFICATION =====
 
+    /// Split every triangle LOD into meshlets for the mesh shader path and
+    /// upload them to storage buffers (see `MeshletBuffers`), replacing
+    /// previous meshlets. Returns the number of meshlets.
+    ///
+    /// Meant for import time: LODs added afterwards have no meshlets until
+    /// the next call. Non-triangle LODs get none. Triangle strips are
+    /// rewound to counter-clockwise triangles.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config is invalid, if the geometry has no
+    /// CPU positions (float position attribute at
+    /// `GEOMETRY_POSITION_LOCATION`), or if a buffer cannot be created.
+    pub fn build_meshlets(&mut self, config: &MeshletConfig) -> Result<u32> {
+        config.validate()?;
+        if self.positions.is_empty() && self.total_vertex_count > 0 {
+            engine_bail!("galaxy3d::Geometry",
+                "Geometry '{}' has no CPU positions to build meshlets from", self.name);
+        }
+
+        // Meshlet range of each LOD, in mesh / submesh / LOD order
+        let mut data = MeshletData::default();
+        let mut ranges = Vec::new();
+        for mesh in &self.meshes {
+            for submesh in &mesh.submeshes {
+                for lod in &submesh.lods {
+                    let strip = lod.topology == graphics_device::PrimitiveTopology::TriangleStrip;
+                    let triangles: Vec<[u32; 3]> = (0..self.lod_triangle_count(lod))
+                        .filter_map(|triangle| {
+                            let [a, b, c] = self.lod_triangle_vertices(lod, triangle)?;
+                            Some(if strip && triangle % 2 == 1 { [a, c, b] } else { [a, b, c] })
+                        })
+                        .collect();
+                    let lod_data = build_meshlets(&self.positions, &triangles, config)
+                        .with_context(|| format!("Building meshlets of geometry '{}'", self.name))?;
+                    ranges.push((data.meshlets.len() as u32, lod_data.meshlets.len() as u32));
+                    data.append(lod_data);
+                }
+            }
+        }
+
+        self.meshlet_buffers = if data.meshlets.is_empty() {
+            None
+        } else {
+            Some(self.create_meshlet_buffers(&data)?)
+        };
+        let mut ranges = ranges.into_iter();
+        for lod in self.meshes.iter_mut()
+            .flat_map(|mesh| mesh.submeshes.iter_mut())
+            .flat_map(|submesh| submesh.lods.iter_mut())
+        {
+            (lod.meshlet_offset, lod.meshlet_count) = ranges.next().unwrap_or_default();
+        }
+        self.meshlets = data.meshlets;
+        Ok(self.meshlets.len() as u32)
+    }
+
+    /// Upload meshlet data to storage buffers
+    fn create_meshlet_buffers(&self, data: &MeshletData) -> Result<MeshletBuffers> {
+        let mut meshlet_bytes = Vec::with_capacity(data.meshlets.len() * MESHLET_GPU_SIZE);
+        for meshlet in &data.meshlets {
+            meshlet.write_bytes(&mut meshlet_bytes);
+        }
+        // The shaders read the triangle bytes 4 by 4
+        let mut triangle_bytes = data.triangles.clone();
+        triangle_bytes.resize(data.triangles.len().next_multiple_of(4), 0);
+
+        let mut graphics_device = self.graphics_device.lock().unwrap();
+        let mut storage = |bytes: &[u8], what: &str| -> Result<Arc<dyn graphics_device::Buffer>> {
+            let buffer = graphics_device.create_buffer(graphics_device::BufferDesc {
+                size: bytes.len() as u64,
+                usage: graphics_device::BufferUsage::Storage,
+                debug_name: Some(format!("{} {}", self.name, what)),
+            })?;
+            buffer.update(0, bytes)?;
+            Ok(buffer)
+        };
+        Ok(MeshletBuffers {
+            meshlets: storage(&meshlet_bytes, "meshlets")?,
+            vertices: storage(bytemuck::cast_slice(&data.vertices), "meshlet vertices")?,
+            triangles: storage(&triangle_bytes, "meshlet triangles")?,
+        })
+    }
+
     /// Add a mesh, returns its id (index).
     ///
     /// Validates all submesh LOD offsets against buffer sizes.
@@ -589,6 +931,8 @@ impl Geometry {
             index_offset: desc.index_offset,
             index_count: desc.index_count,
             topology: desc.topology,
+            meshlet_offset: 0,
+            meshlet_count: 0,
         });
         if let Some(t) = threshold {
             submesh.lod_thresholds.push(t);
@@ -692,6 +1036,8 @@ impl Geometry {
                 index_offset: lod_desc.index_offset,
                 index_count: lod_desc.index_count,
                 topology: lod_desc.topology,
+                meshlet_offset: 0,
+                meshlet_count: 0,
             });
         }
         submesh.lod_thresholds = desc.lod_thresholds;
@@ -705,6 +1051,7 @@ impl Geometry {
 
 /// Descriptor for creating a single LOD variant of a GeometrySubMesh.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub struct GeometrySubMeshLODDesc {
     /// First vertex offset
     pub vertex_offset: u32,
@@ -720,6 +1067,7 @@ pub struct GeometrySubMeshLODDesc {
 
 /// Descriptor for creating a GeometrySubMesh (with all its LOD variants).
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub struct GeometrySubMeshDesc {
     /// SubMesh name (unique within its parent GeometryMesh)
     pub name: String,
@@ -740,6 +1088,7 @@ pub struct GeometrySubMeshDesc {
 
 /// Descriptor for creating a GeometryMesh
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub struct GeometryMeshDesc {
     /// Mesh name (unique within the group)
     pub name: String,
@@ -747,23 +1096,179 @@ pub struct GeometryMeshDesc {
     pub submeshes: Vec<GeometrySubMeshDesc>,
 }
 
+/// Descriptor for a morph target (blend shape) of a Geometry.
+///
+/// Deltas are per vertex of the whole Geometry vertex buffer (index `i` is
+/// vertex `i`, regardless of meshes). The vertex shader blends
+/// `position + sum(weight[t] * position_delta[t])` (same for normals), with
+/// the weights read from the per-instance `morphWeights` field.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
+pub struct MorphTargetDesc {
+    /// Target name (e.g. "smile"), used to look up its weight index
+    pub name: String,
+    /// Position delta of every vertex
+    pub position_deltas: Vec<[f32; 3]>,
+    /// Normal delta of every vertex (all targets of a Geometry must either
+    /// have normal deltas or not)
+    pub normal_deltas: Option<Vec<[f32; 3]>>,
+}
+
+/// Decode the float or half float position attribute of binding 0 (vec2
+/// positions get z = 0); None without such an attribute
+fn read_positions(vertex_data: &[u8], vertex_layout: &graphics_device::VertexLayout) -> Option<Vec<Vec3>> {
+    let attribute = vertex_layout.attributes.iter()
+        .find(|a| a.location == GEOMETRY_POSITION_LOCATION && a.binding == 0)?;
+    let (components, component_size) = match attribute.format {
+        graphics_device::BufferFormat::R32G32_SFLOAT => (2, 4),
+        graphics_device::BufferFormat::R32G32B32_SFLOAT
+        | graphics_device::BufferFormat::R32G32B32A32_SFLOAT => (3, 4),
+        graphics_device::BufferFormat::R16G16_SFLOAT => (2, 2),
+        graphics_device::BufferFormat::R16G16B16A16_SFLOAT => (3, 2),
+        _ => return None,
+    };
+    let stride = vertex_layout.bindings.iter().find(|b| b.binding == 0)?.stride as usize;
+    let offset = attribute.offset as usize;
+    if stride == 0 || offset + components * component_size > stride {
+        return None;
+    }
+
+    Some(vertex_data.chunks_exact(stride).map(|vertex| {
+        let mut position = Vec3::ZERO;
+        for c in 0..components {
+            let start = offset + c * component_size;
+            position[c] = if component_size == 2 {
+                graphics_device::f16_to_f32(u16::from_ne_bytes([vertex[start], vertex[start + 1]]))
+            } else {
+                f32::from_ne_bytes(vertex[start..start + 4].try_into().unwrap())
+            };
+        }
+        position
+    }).collect())
+}
+
+/// Bounds of the positions, widened by the morph target deltas (None
+/// without vertex or with non-finite positions)
+fn compute_position_bounds(positions: &[Vec3], morph_targets: &[MorphTargetDesc]) -> Option<AABB> {
+    let mut min = Vec3::splat(f32::INFINITY);
+    let mut max = Vec3::splat(f32::NEG_INFINITY);
+    for &position in positions {
+        min = min.min(position);
+        max = max.max(position);
+    }
+    for target in morph_targets {
+        let mut delta_min = Vec3::ZERO;
+        let mut delta_max = Vec3::ZERO;
+        for delta in &target.position_deltas {
+            delta_min = delta_min.min(Vec3::from(*delta));
+            delta_max = delta_max.max(Vec3::from(*delta));
+        }
+        min += delta_min;
+        max += delta_max;
+    }
+    let bounds = AABB { min, max };
+    bounds.is_finite().then_some(bounds)
+}
+
+/// Validate morph targets and build their interleaved vertex stream.
+///
+/// Appends the morph binding (`MORPH_TARGET_BUFFER_BINDING`) and attributes
+/// to `vertex_layout`. Per vertex, the stream holds every target's position
+/// delta, then every target's normal delta (if present).
+fn build_morph_target_stream(
+    targets: &[MorphTargetDesc],
+    vertex_count: usize,
+    vertex_layout: &mut graphics_device::VertexLayout,
+) -> Result<Vec<u8>> {
+    if targets.len() > MAX_MORPH_TARGETS {
+        engine_bail!("galaxy3d::Geometry", "{} morph targets exceed the maximum of {}",
+            targets.len(), MAX_MORPH_TARGETS);
+    }
+    let with_normals = targets[0].normal_deltas.is_some();
+    for target in targets {
+        if target.normal_deltas.is_some() != with_normals {
+            engine_bail!("galaxy3d::Geometry",
+                "Morph target '{}': all targets must either have normal deltas or not", target.name);
+        }
+        let normal_count = target.normal_deltas.as_ref().map_or(vertex_count, |n| n.len());
+        if target.position_deltas.len() != vertex_count || normal_count != vertex_count {
+            engine_bail!("galaxy3d::Geometry",
+                "Morph target '{}': expected {} deltas (one per vertex)", target.name, vertex_count);
+        }
+    }
+
+    let first_normal_location = MORPH_TARGET_FIRST_LOCATION + MAX_MORPH_TARGETS as u32;
+    let last_location = first_normal_location + targets.len() as u32;
+    if vertex_layout.bindings.iter().any(|b| b.binding == MORPH_TARGET_BUFFER_BINDING)
+        || vertex_layout.attributes.iter().any(|a| (MORPH_TARGET_FIRST_LOCATION..last_location).contains(&a.location))
+    {
+        engine_bail!("galaxy3d::Geometry",
+            "Morph targets: vertex layout already uses binding {} or locations {}..{}",
+            MORPH_TARGET_BUFFER_BINDING, MORPH_TARGET_FIRST_LOCATION, last_location);
+    }
+
+    let streams_per_target = if with_normals { 2 } else { 1 };
+    let stride = MORPH_DELTA_SIZE * (targets.len() * streams_per_target) as u32;
+    vertex_layout.bindings.push(graphics_device::VertexBinding {
+        binding: MORPH_TARGET_BUFFER_BINDING,
+        stride,
+        input_rate: graphics_device::VertexInputRate::Vertex,
+    });
+    for t in 0..targets.len() as u32 {
+        vertex_layout.attributes.push(graphics_device::VertexAttribute {
+            location: MORPH_TARGET_FIRST_LOCATION + t,
+            binding: MORPH_TARGET_BUFFER_BINDING,
+            format: graphics_device::BufferFormat::R32G32B32_SFLOAT,
+            offset: t * MORPH_DELTA_SIZE,
+        });
+        if with_normals {
+            vertex_layout.attributes.push(graphics_device::VertexAttribute {
+                location: first_normal_location + t,
+                binding: MORPH_TARGET_BUFFER_BINDING,
+                format: graphics_device::BufferFormat::R32G32B32_SFLOAT,
+                offset: (targets.len() as u32 + t) * MORPH_DELTA_SIZE,
+            });
+        }
+    }
+
+    let mut data = Vec::with_capacity(stride as usize * vertex_count);
+    for v in 0..vertex_count {
+        for target in targets {
+            data.extend_from_slice(bytemuck::cast_slice(&target.position_deltas[v]));
+        }
+        for target in targets {
+            if let Some(normals) = &target.normal_deltas {
+                data.extend_from_slice(bytemuck::cast_slice(&normals[v]));
+            }
+        }
+    }
+    Ok(data)
+}
+
 /// Descriptor for creating a Geometry resource
 ///
 /// The ResourceManager will create the GPU buffers from the provided data.
 /// Vertex and index counts are computed automatically from data length and layout.
-pub struct GeometryDesc {
+///
+/// Vertex and index data are `Cow`s: pass owned `Vec`s (`vec.into()`) or
+/// borrow them (`Cow::Borrowed(&mapped[range])`), e.g. straight from a
+/// memory-mapped pack file. Borrowed bytes are copied once, directly into
+/// the GPU buffers.
+pub struct GeometryDesc<'a> {
     /// Geometry group name
     pub name: String,
     /// Graphics device to use for GPU buffer creation
     pub graphics_device: Arc<Mutex<dyn graphics_device::GraphicsDevice>>,
     /// Raw vertex data (bytes, interleaved according to vertex_layout)
-    pub vertex_data: Vec<u8>,
+    pub vertex_data: Cow<'a, [u8]>,
     /// Raw index data (optional, None for non-indexed geometries)
-    pub index_data: Option<Vec<u8>>,
+    pub index_data: Option<Cow<'a, [u8]>>,
     /// Vertex layout description (defines stride for vertex count calculation)
     pub vertex_layout: graphics_device::VertexLayout,
     /// Index type (U16 or U32, defines stride for index count calculation)
     pub index_type: graphics_device::IndexType,
+    /// Morph targets (empty for none, at most `MAX_MORPH_TARGETS`)
+    pub morph_targets: Vec<MorphTargetDesc>,
     /// Initial meshes (can be empty, add later via add_mesh)
     pub meshes: Vec<GeometryMeshDesc>,
 }
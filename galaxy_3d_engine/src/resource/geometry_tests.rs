@@ -3,6 +3,8 @@
 /// Tests the Geometry, GeometryMesh, GeometrySubMesh, and GeometrySubMeshLOD
 /// hierarchy without requiring GPU. Uses MockGraphicsDevice for testing.
 
+#[cfg(test)]
+use std::borrow::Cow;
 #[cfg(test)]
 use std::sync::{Arc, Mutex};
 #[cfg(test)]
@@ -10,7 +12,8 @@ use crate::graphics_device;
 #[cfg(test)]
 use crate::resource::{
     Geometry, GeometryDesc, GeometryMeshDesc, GeometrySubMeshDesc, GeometrySubMeshLODDesc,
-    GeometrySubMesh,
+    GeometrySubMesh, MorphTargetDesc, MAX_MORPH_TARGETS,
+    MORPH_TARGET_BUFFER_BINDING, MORPH_TARGET_FIRST_LOCATION, MeshletConfig,
 };
 
 // ============================================================================
@@ -91,10 +94,11 @@ fn test_create_geometry_indexed() {
     let desc = GeometryDesc {
         name: "test_geom".to_string(),
         graphics_device: graphics_device.clone(),
-        vertex_data: create_quad_vertex_data(),
-        index_data: Some(create_quad_index_data_u16()),
+        vertex_data: create_quad_vertex_data().into(),
+        index_data: Some(create_quad_index_data_u16().into()),
         vertex_layout: create_simple_vertex_layout(),
         index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![],
     };
 
@@ -113,10 +117,11 @@ fn test_create_geometry_non_indexed() {
     let desc = GeometryDesc {
         name: "test_geom".to_string(),
         graphics_device: graphics_device.clone(),
-        vertex_data: create_quad_vertex_data(),
+        vertex_data: create_quad_vertex_data().into(),
         index_data: None,
         vertex_layout: create_simple_vertex_layout(),
         index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![],
     };
 
@@ -129,16 +134,43 @@ fn test_create_geometry_non_indexed() {
     assert!(geom.index_buffer().is_none());
 }
 
+#[test]
+fn test_create_geometry_from_borrowed_data() {
+    // Simulates a mapped pack file: header, then vertices, then indices
+    let mut pack = vec![0xAAu8; 16];
+    pack.extend(create_quad_vertex_data());
+    pack.extend(create_quad_index_data_u16());
+    let vertex_end = 16 + 32;
+
+    let graphics_device = create_mock_graphics_device();
+    let desc = GeometryDesc {
+        name: "test_geom".to_string(),
+        graphics_device: graphics_device.clone(),
+        vertex_data: Cow::Borrowed(&pack[16..vertex_end]),
+        index_data: Some(Cow::Borrowed(&pack[vertex_end..])),
+        vertex_layout: create_simple_vertex_layout(),
+        index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
+        meshes: vec![],
+    };
+
+    let geom = Geometry::from_desc(desc, 0).unwrap();
+
+    assert_eq!(geom.total_vertex_count(), 4);
+    assert_eq!(geom.total_index_count(), 6);
+}
+
 #[test]
 fn test_create_geometry_with_mesh() {
     let graphics_device = create_mock_graphics_device();
     let desc = GeometryDesc {
         name: "test_geom".to_string(),
         graphics_device: graphics_device.clone(),
-        vertex_data: create_quad_vertex_data(),
-        index_data: Some(create_quad_index_data_u16()),
+        vertex_data: create_quad_vertex_data().into(),
+        index_data: Some(create_quad_index_data_u16().into()),
         vertex_layout: create_simple_vertex_layout(),
         index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![
             GeometryMeshDesc {
                 name: "hero".to_string(),
@@ -159,10 +191,11 @@ fn test_create_geometry_invalid_vertex_stride() {
     let desc = GeometryDesc {
         name: "test_geom".to_string(),
         graphics_device: graphics_device.clone(),
-        vertex_data: vec![1, 2, 3],
+        vertex_data: vec![1, 2, 3].into(),
         index_data: None,
         vertex_layout: create_simple_vertex_layout(),
         index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![],
     };
 
@@ -176,10 +209,11 @@ fn test_create_geometry_invalid_index_stride() {
     let desc = GeometryDesc {
         name: "test_geom".to_string(),
         graphics_device: graphics_device.clone(),
-        vertex_data: create_quad_vertex_data(),
-        index_data: Some(vec![1, 2, 3]),
+        vertex_data: create_quad_vertex_data().into(),
+        index_data: Some(vec![1, 2, 3].into()),
         vertex_layout: create_simple_vertex_layout(),
         index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![],
     };
 
@@ -197,10 +231,11 @@ fn test_add_mesh() {
     let desc = GeometryDesc {
         name: "test_geom".to_string(),
         graphics_device: graphics_device.clone(),
-        vertex_data: create_quad_vertex_data(),
-        index_data: Some(create_quad_index_data_u16()),
+        vertex_data: create_quad_vertex_data().into(),
+        index_data: Some(create_quad_index_data_u16().into()),
         vertex_layout: create_simple_vertex_layout(),
         index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![],
     };
 
@@ -224,10 +259,11 @@ fn test_get_mesh_by_name() {
     let desc = GeometryDesc {
         name: "test_geom".to_string(),
         graphics_device: graphics_device.clone(),
-        vertex_data: create_quad_vertex_data(),
-        index_data: Some(create_quad_index_data_u16()),
+        vertex_data: create_quad_vertex_data().into(),
+        index_data: Some(create_quad_index_data_u16().into()),
         vertex_layout: create_simple_vertex_layout(),
         index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![
             GeometryMeshDesc {
                 name: "hero".to_string(),
@@ -251,10 +287,11 @@ fn test_get_mesh_by_id() {
     let desc = GeometryDesc {
         name: "test_geom".to_string(),
         graphics_device: graphics_device.clone(),
-        vertex_data: create_quad_vertex_data(),
-        index_data: Some(create_quad_index_data_u16()),
+        vertex_data: create_quad_vertex_data().into(),
+        index_data: Some(create_quad_index_data_u16().into()),
         vertex_layout: create_simple_vertex_layout(),
         index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![
             GeometryMeshDesc {
                 name: "hero".to_string(),
@@ -278,10 +315,11 @@ fn test_add_duplicate_mesh() {
     let desc = GeometryDesc {
         name: "test_geom".to_string(),
         graphics_device: graphics_device.clone(),
-        vertex_data: create_quad_vertex_data(),
-        index_data: Some(create_quad_index_data_u16()),
+        vertex_data: create_quad_vertex_data().into(),
+        index_data: Some(create_quad_index_data_u16().into()),
         vertex_layout: create_simple_vertex_layout(),
         index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![
             GeometryMeshDesc {
                 name: "hero".to_string(),
@@ -311,10 +349,11 @@ fn test_add_submesh_to_mesh() {
     let desc = GeometryDesc {
         name: "test_geom".to_string(),
         graphics_device: graphics_device.clone(),
-        vertex_data: create_quad_vertex_data(),
-        index_data: Some(create_quad_index_data_u16()),
+        vertex_data: create_quad_vertex_data().into(),
+        index_data: Some(create_quad_index_data_u16().into()),
         vertex_layout: create_simple_vertex_layout(),
         index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![
             GeometryMeshDesc {
                 name: "hero".to_string(),
@@ -340,10 +379,11 @@ fn test_add_submesh_lod_to_existing_submesh() {
     let desc = GeometryDesc {
         name: "test_geom".to_string(),
         graphics_device: graphics_device.clone(),
-        vertex_data: create_quad_vertex_data(),
-        index_data: Some(create_quad_index_data_u16()),
+        vertex_data: create_quad_vertex_data().into(),
+        index_data: Some(create_quad_index_data_u16().into()),
         vertex_layout: create_simple_vertex_layout(),
         index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![
             GeometryMeshDesc {
                 name: "hero".to_string(),
@@ -383,10 +423,11 @@ fn test_submesh_lod_accessors() {
     let desc = GeometryDesc {
         name: "test_geom".to_string(),
         graphics_device: graphics_device.clone(),
-        vertex_data,
-        index_data: Some(index_data),
+        vertex_data: vertex_data.into(),
+        index_data: Some(index_data.into()),
         vertex_layout: create_simple_vertex_layout(),
         index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![
             GeometryMeshDesc {
                 name: "hero".to_string(),
@@ -417,10 +458,11 @@ fn test_submesh_validation_vertex_overflow() {
     let desc = GeometryDesc {
         name: "test_geom".to_string(),
         graphics_device: graphics_device.clone(),
-        vertex_data: create_quad_vertex_data(),
-        index_data: Some(create_quad_index_data_u16()),
+        vertex_data: create_quad_vertex_data().into(),
+        index_data: Some(create_quad_index_data_u16().into()),
         vertex_layout: create_simple_vertex_layout(),
         index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![],
     };
 
@@ -451,10 +493,11 @@ fn test_submesh_validation_index_overflow() {
     let desc = GeometryDesc {
         name: "test_geom".to_string(),
         graphics_device: graphics_device.clone(),
-        vertex_data: create_quad_vertex_data(),
-        index_data: Some(create_quad_index_data_u16()),
+        vertex_data: create_quad_vertex_data().into(),
+        index_data: Some(create_quad_index_data_u16().into()),
         vertex_layout: create_simple_vertex_layout(),
         index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![],
     };
 
@@ -489,10 +532,11 @@ fn test_submesh_lookup_by_name() {
     let desc = GeometryDesc {
         name: "test_geom".to_string(),
         graphics_device: graphics_device.clone(),
-        vertex_data: create_quad_vertex_data(),
-        index_data: Some(create_quad_index_data_u16()),
+        vertex_data: create_quad_vertex_data().into(),
+        index_data: Some(create_quad_index_data_u16().into()),
         vertex_layout: create_simple_vertex_layout(),
         index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![
             GeometryMeshDesc {
                 name: "hero".to_string(),
@@ -521,10 +565,11 @@ fn test_multiple_submeshes() {
     let desc = GeometryDesc {
         name: "test_geom".to_string(),
         graphics_device: graphics_device.clone(),
-        vertex_data: create_quad_vertex_data(),
-        index_data: Some(create_quad_index_data_u16()),
+        vertex_data: create_quad_vertex_data().into(),
+        index_data: Some(create_quad_index_data_u16().into()),
         vertex_layout: create_simple_vertex_layout(),
         index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![
             GeometryMeshDesc {
                 name: "hero".to_string(),
@@ -575,10 +620,11 @@ fn test_complex_geometry_hierarchy() {
     let desc = GeometryDesc {
         name: "characters".to_string(),
         graphics_device: graphics_device.clone(),
-        vertex_data,
-        index_data: Some(index_data),
+        vertex_data: vertex_data.into(),
+        index_data: Some(index_data.into()),
         vertex_layout: create_simple_vertex_layout(),
         index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![
             GeometryMeshDesc {
                 name: "hero".to_string(),
@@ -656,10 +702,11 @@ fn test_geometry_getters() {
     let desc = GeometryDesc {
         name: "test_geom".to_string(),
         graphics_device: graphics_device.clone(),
-        vertex_data: create_quad_vertex_data(),
-        index_data: Some(create_quad_index_data_u16()),
+        vertex_data: create_quad_vertex_data().into(),
+        index_data: Some(create_quad_index_data_u16().into()),
         vertex_layout: create_simple_vertex_layout(),
         index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![],
     };
 
@@ -683,10 +730,11 @@ fn test_submesh_by_id_path() {
     let desc = GeometryDesc {
         name: "test_geom".to_string(),
         graphics_device: graphics_device.clone(),
-        vertex_data: create_quad_vertex_data(),
-        index_data: Some(create_quad_index_data_u16()),
+        vertex_data: create_quad_vertex_data().into(),
+        index_data: Some(create_quad_index_data_u16().into()),
         vertex_layout: create_simple_vertex_layout(),
         index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![
             GeometryMeshDesc {
                 name: "hero".to_string(),
@@ -743,10 +791,11 @@ fn test_submeshes_iterator() {
     let desc = GeometryDesc {
         name: "test_geom".to_string(),
         graphics_device: graphics_device.clone(),
-        vertex_data: create_quad_vertex_data(),
-        index_data: Some(create_quad_index_data_u16()),
+        vertex_data: create_quad_vertex_data().into(),
+        index_data: Some(create_quad_index_data_u16().into()),
         vertex_layout: create_simple_vertex_layout(),
         index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![
             GeometryMeshDesc {
                 name: "hero".to_string(),
@@ -778,3 +827,217 @@ fn test_submeshes_iterator() {
         assert_eq!(lod0.index_count(), 6);
     }
 }
+
+// ============================================================================
+// MORPH TARGET TESTS
+// ============================================================================
+
+fn make_morph_target(name: &str, vertex_count: usize, with_normals: bool) -> MorphTargetDesc {
+    MorphTargetDesc {
+        name: name.to_string(),
+        position_deltas: vec![[0.0, 0.1, 0.0]; vertex_count],
+        normal_deltas: with_normals.then(|| vec![[0.0, 0.0, 1.0]; vertex_count]),
+    }
+}
+
+fn make_morph_geometry_desc(morph_targets: Vec<MorphTargetDesc>) -> GeometryDesc<'static> {
+    GeometryDesc {
+        name: "morph_geom".to_string(),
+        graphics_device: create_mock_graphics_device(),
+        vertex_data: create_quad_vertex_data().into(),
+        index_data: Some(create_quad_index_data_u16().into()),
+        vertex_layout: create_simple_vertex_layout(),
+        index_type: graphics_device::IndexType::U16,
+        morph_targets,
+        meshes: vec![],
+    }
+}
+
+#[test]
+fn test_geometry_without_morph_targets() {
+    let geom = Geometry::from_desc(make_morph_geometry_desc(Vec::new()), 0).unwrap();
+    assert_eq!(geom.morph_target_count(), 0);
+    assert!(!geom.morph_target_has_normals());
+    assert!(geom.morph_target_buffer().is_none());
+    assert_eq!(geom.vertex_layout().bindings.len(), 1);
+}
+
+#[test]
+fn test_geometry_morph_targets_extend_vertex_layout() {
+    let geom = Geometry::from_desc(make_morph_geometry_desc(vec![
+        make_morph_target("smile", 4, true),
+        make_morph_target("blink", 4, true),
+    ]), 0).unwrap();
+
+    assert_eq!(geom.morph_target_count(), 2);
+    assert!(geom.morph_target_has_normals());
+    assert_eq!(geom.morph_target_id("blink"), Some(1));
+    assert_eq!(geom.morph_target_id("frown"), None);
+    assert!(geom.morph_target_buffer().is_some());
+
+    let layout = geom.vertex_layout();
+    let binding = layout.bindings.iter().find(|b| b.binding == MORPH_TARGET_BUFFER_BINDING).unwrap();
+    // 2 targets x (position + normal) x 12 bytes
+    assert_eq!(binding.stride, 2 * 2 * 12);
+    let locations: Vec<u32> = layout.attributes.iter()
+        .filter(|a| a.binding == MORPH_TARGET_BUFFER_BINDING)
+        .map(|a| a.location)
+        .collect();
+    let normals = MORPH_TARGET_FIRST_LOCATION + MAX_MORPH_TARGETS as u32;
+    assert_eq!(locations, vec![
+        MORPH_TARGET_FIRST_LOCATION, normals, MORPH_TARGET_FIRST_LOCATION + 1, normals + 1,
+    ]);
+}
+
+#[test]
+fn test_geometry_bounds() {
+    let geom = Geometry::from_desc(make_morph_geometry_desc(Vec::new()), 0).unwrap();
+    let bounds = geom.bounds().unwrap();
+    assert_eq!(bounds.min, glam::Vec3::ZERO);
+    assert_eq!(bounds.max, glam::Vec3::new(1.0, 1.0, 0.0));
+
+    // Morph deltas widen the bounds (weights up to 1 per target)
+    let geom = Geometry::from_desc(make_morph_geometry_desc(vec![
+        make_morph_target("smile", 4, false),
+        make_morph_target("blink", 4, false),
+    ]), 0).unwrap();
+    assert!(!geom.morph_target_has_normals());
+    assert_eq!(geom.bounds().unwrap().max, glam::Vec3::new(1.0, 1.2, 0.0));
+
+    // Half float positions
+    let mut desc = make_morph_geometry_desc(Vec::new());
+    let halves = [[-1.0f32, 0.5, 2.0, 1.0], [3.0, -0.25, 0.0, 1.0], [0.0; 4], [1.0; 4]];
+    desc.vertex_data = halves.iter()
+        .flat_map(|p| p.iter().flat_map(|&v| graphics_device::f32_to_f16(v).to_ne_bytes()))
+        .collect::<Vec<u8>>()
+        .into();
+    desc.vertex_layout.attributes[0].format = graphics_device::BufferFormat::R16G16B16A16_SFLOAT;
+    let geom = Geometry::from_desc(desc, 0).unwrap();
+    let bounds = geom.bounds().unwrap();
+    assert_eq!(bounds.min, glam::Vec3::new(-1.0, -0.25, 0.0));
+    assert_eq!(bounds.max, glam::Vec3::new(3.0, 1.0, 2.0));
+
+    // No float position attribute
+    let mut desc = make_morph_geometry_desc(Vec::new());
+    desc.vertex_layout.attributes[0].format = graphics_device::BufferFormat::R32G32_SINT;
+    assert!(Geometry::from_desc(desc, 0).unwrap().bounds().is_none());
+}
+
+#[test]
+fn test_geometry_morph_target_validation() {
+    // Wrong delta count
+    assert!(Geometry::from_desc(make_morph_geometry_desc(vec![
+        make_morph_target("short", 3, false),
+    ]), 0).is_err());
+
+    // Mixed normal deltas
+    assert!(Geometry::from_desc(make_morph_geometry_desc(vec![
+        make_morph_target("a", 4, true),
+        make_morph_target("b", 4, false),
+    ]), 0).is_err());
+
+    // Too many targets
+    let targets = (0..=MAX_MORPH_TARGETS).map(|i| make_morph_target(&format!("t{}", i), 4, false)).collect();
+    assert!(Geometry::from_desc(make_morph_geometry_desc(targets), 0).is_err());
+}
+
+// ============================================================================
+// MESHLET TESTS
+// ============================================================================
+
+/// Quad with a triangle list LOD and a triangle strip LOD
+fn make_meshlet_geometry_desc() -> GeometryDesc<'static> {
+    let indices: Vec<u16> = vec![0, 1, 2, 2, 3, 0, 0, 1, 3, 2];
+    let mut strip_lod = make_quad_lod_desc();
+    strip_lod.index_offset = 6;
+    strip_lod.index_count = 4;
+    strip_lod.topology = graphics_device::PrimitiveTopology::TriangleStrip;
+    GeometryDesc {
+        name: "meshlet_geom".to_string(),
+        graphics_device: create_mock_graphics_device(),
+        vertex_data: create_quad_vertex_data().into(),
+        index_data: Some(indices.iter().flat_map(|&i| i.to_le_bytes()).collect::<Vec<u8>>().into()),
+        vertex_layout: create_simple_vertex_layout(),
+        index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
+        meshes: vec![GeometryMeshDesc {
+            name: "quad".to_string(),
+            submeshes: vec![GeometrySubMeshDesc {
+                name: "quad".to_string(),
+                lods: vec![make_quad_lod_desc(), strip_lod],
+                lod_thresholds: vec![(10.0, 12.0)],
+            }],
+        }],
+    }
+}
+
+#[test]
+fn test_geometry_without_meshlets() {
+    let geom = Geometry::from_desc(make_meshlet_geometry_desc(), 0).unwrap();
+    assert!(geom.meshlets().is_empty());
+    assert!(geom.meshlet_buffers().is_none());
+    assert_eq!(geom.mesh(0).unwrap().submesh(0).unwrap().lod(0).unwrap().meshlet_count(), 0);
+}
+
+#[test]
+fn test_geometry_build_meshlets_per_lod() {
+    let mut geom = Geometry::from_desc(make_meshlet_geometry_desc(), 0).unwrap();
+    assert_eq!(geom.build_meshlets(&MeshletConfig::default()).unwrap(), 2);
+    assert_eq!(geom.meshlets().len(), 2);
+    assert!(geom.meshlet_buffers().is_some());
+
+    let submesh = geom.mesh(0).unwrap().submesh(0).unwrap();
+    let (lod0, lod1) = (submesh.lod(0).unwrap(), submesh.lod(1).unwrap());
+    assert_eq!((lod0.meshlet_offset(), lod0.meshlet_count()), (0, 1));
+    assert_eq!((lod1.meshlet_offset(), lod1.meshlet_count()), (1, 1));
+    assert_eq!(geom.meshlets()[1].triangle_count, 2);
+
+    // Odd strip triangles are rewound: both LODs face +Z
+    for meshlet in geom.meshlets() {
+        assert!((meshlet.cone_axis - glam::Vec3::Z).length() < 1e-5);
+    }
+}
+
+#[test]
+fn test_geometry_build_meshlets_errors() {
+    let mut geom = Geometry::from_desc(make_meshlet_geometry_desc(), 0).unwrap();
+    let config = MeshletConfig { max_vertices: 300, max_triangles: 4 };
+    assert!(geom.build_meshlets(&config).is_err());
+
+    // No float position attribute
+    let mut desc = make_meshlet_geometry_desc();
+    desc.vertex_layout.attributes[0].format = graphics_device::BufferFormat::R32G32_SINT;
+    let mut geom = Geometry::from_desc(desc, 0).unwrap();
+    assert!(geom.build_meshlets(&MeshletConfig::default()).is_err());
+}
+
+// ============================================================================
+// RAY TRACING TESTS
+// ============================================================================
+
+#[test]
+fn test_geometry_lod_blas_geometry() {
+    let geom = Geometry::from_desc(make_meshlet_geometry_desc(), 0).unwrap();
+    let submesh = geom.mesh(0).unwrap().submesh(0).unwrap();
+
+    let blas = geom.lod_blas_geometry(submesh.lod(0).unwrap(), true).unwrap();
+    assert_eq!(blas.vertex_format, graphics_device::BufferFormat::R32G32_SFLOAT);
+    assert_eq!((blas.vertex_stride, blas.position_offset), (8, 0));
+    assert_eq!((blas.first_vertex, blas.vertex_count), (0, 4));
+    assert_eq!((blas.first_index, blas.triangle_count), (0, 2));
+    assert!(blas.index_buffer.is_some());
+    assert_eq!(blas.index_type, graphics_device::IndexType::U16);
+    assert!(blas.opaque);
+
+    // Triangle strips are not accepted by BLAS builds
+    assert!(geom.lod_blas_geometry(submesh.lod(1).unwrap(), true).is_none());
+}
+
+#[test]
+fn test_geometry_lod_blas_geometry_unsupported_position() {
+    let mut desc = make_meshlet_geometry_desc();
+    desc.vertex_layout.attributes[0].format = graphics_device::BufferFormat::R32G32_SINT;
+    let geom = Geometry::from_desc(desc, 0).unwrap();
+    let lod = geom.submesh_lod(0, 0, 0).unwrap();
+    assert!(geom.lod_blas_geometry(lod, true).is_none());
+}
@@ -0,0 +1,568 @@
+/// Serializable resource manifest (`manifest` feature).
+///
+/// A `ResourceManifest` describes a set of textures, geometries, shaders,
+/// pipelines, materials and meshes. Resources reference each other by name
+/// and bulk data (pixels, vertices, indices, shader code) lives in separate
+/// files, referenced by path relative to the manifest file:
+///
+/// ```ron
+/// (
+///     textures: [(name: "rock_albedo", width: 256, height: 256,
+///                 format: R8G8B8A8_SRGB, data: Some("rock_albedo.rgba"), ...)],
+///     shaders: [(name: "pbr_frag", code: "pbr.frag.spv", stage: Fragment, entry_point: "main")],
+///     materials: [(name: "rock", passes: [(fragment_shader: "pbr_frag",
+///                  textures: [(name: "albedo", texture: "rock_albedo", ...)], ...)])],
+///     ...
+/// )
+/// ```
+///
+/// The format follows the file extension: `.ron` or `.json`.
+///
+/// `ResourceManager::load_manifest()` creates the resources in dependency
+/// order (textures, geometries, shaders, pipelines, materials, meshes) and
+/// remembers their entries; `ResourceManager::save_manifest()` writes back
+/// the entries of the resources still registered. Resources created by the
+/// `create_*` methods have no manifest entry (their data source is unknown).
+
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use serde::{Serialize, Deserialize};
+use crate::error::Result;
+use crate::{engine_bail, engine_err};
+use crate::graphics_device::{self, GraphicsDevice};
+use crate::utils::write_atomic;
+use super::resource_manager::{
+    ResourceManager, ResourceKind,
+    TextureKey, GeometryKey, ShaderKey, PipelineKey, MaterialKey, MeshKey,
+};
+use super::texture::{AtlasRegion, AtlasRegionDesc, LayerDesc, TextureDesc};
+use super::geometry::{GeometryDesc, GeometryMeshDesc, MorphTargetDesc};
+use super::shader::ShaderDesc;
+use super::pipeline::PipelineDesc;
+use super::material::{
+    FlipbookDesc, LayerRef, MaterialDesc, MaterialPassDesc, MaterialTextureSlotDesc,
+    ParamValue, RegionRef, RenderQueueClass,
+};
+use super::mesh::{GeometryMeshRef, GeometrySubMeshRef, MeshDesc, MeshSubMeshDesc};
+
+// ===== FORMAT =====
+
+/// Text format of a manifest file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestFormat {
+    Ron,
+    Json,
+}
+
+impl ManifestFormat {
+    /// Format matching the extension of `path` (`.ron` or `.json`)
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "ron" => Some(Self::Ron),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+// ===== ENTRIES =====
+
+/// Mipmap generation of a manifest texture (manual mips are bulk data and
+/// not supported)
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum ManifestMipmap {
+    #[default]
+    None,
+    Generate { max_levels: Option<u32> },
+    GenerateCpu { filter: graphics_device::MipFilter, max_levels: Option<u32> },
+}
+
+impl ManifestMipmap {
+    fn to_mode(self) -> graphics_device::MipmapMode {
+        match self {
+            Self::None => graphics_device::MipmapMode::None,
+            Self::Generate { max_levels } => graphics_device::MipmapMode::Generate { max_levels },
+            Self::GenerateCpu { filter, max_levels } =>
+                graphics_device::MipmapMode::GenerateCpu { filter, max_levels },
+        }
+    }
+}
+
+/// Texture entry. `data` holds the raw bytes of the whole texture, each
+/// layer's `data` the raw bytes of that layer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextureManifest {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    /// Depth of `Tex3D` textures
+    #[serde(default = "default_depth")]
+    pub depth: u32,
+    pub format: graphics_device::TextureFormat,
+    pub usage: graphics_device::TextureUsage,
+    pub texture_type: graphics_device::TextureType,
+    pub array_layers: u32,
+    pub sample_count: graphics_device::SampleCount,
+    #[serde(default)]
+    pub mipmap: ManifestMipmap,
+    #[serde(default)]
+    pub data: Option<PathBuf>,
+    /// Layers of the texture. Left empty, a simple texture (`array_layers`
+    /// 1) gets a single layer named "default".
+    #[serde(default)]
+    pub layers: Vec<LayerManifest>,
+}
+
+fn default_depth() -> u32 {
+    1
+}
+
+/// Texture layer entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayerManifest {
+    pub name: String,
+    pub layer_index: u32,
+    #[serde(default)]
+    pub data: Option<PathBuf>,
+    #[serde(default)]
+    pub regions: Vec<AtlasRegionManifest>,
+}
+
+/// Named atlas region of a layer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AtlasRegionManifest {
+    pub name: String,
+    pub region: AtlasRegion,
+}
+
+/// Geometry entry. `vertex_data` and `index_data` hold the raw buffer bytes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeometryManifest {
+    pub name: String,
+    pub vertex_data: PathBuf,
+    #[serde(default)]
+    pub index_data: Option<PathBuf>,
+    pub vertex_layout: graphics_device::VertexLayout,
+    pub index_type: graphics_device::IndexType,
+    #[serde(default)]
+    pub morph_targets: Vec<MorphTargetDesc>,
+    pub meshes: Vec<GeometryMeshDesc>,
+}
+
+/// Shader entry. `code` holds the compiled bytecode (SPIR-V).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShaderManifest {
+    pub name: String,
+    pub code: PathBuf,
+    pub stage: graphics_device::ShaderStage,
+    pub entry_point: String,
+}
+
+/// Pipeline entry (shaders by name)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineManifest {
+    pub name: String,
+    pub vertex_shader: String,
+    pub fragment_shader: String,
+    pub vertex_layout: graphics_device::VertexLayout,
+    pub topology: graphics_device::PrimitiveTopology,
+    #[serde(default)]
+    pub rasterization: graphics_device::RasterizationState,
+    #[serde(default)]
+    pub color_blend: graphics_device::ColorBlendState,
+    #[serde(default)]
+    pub multisample: graphics_device::MultisampleState,
+    pub color_formats: Vec<graphics_device::TextureFormat>,
+    #[serde(default)]
+    pub depth_format: Option<graphics_device::TextureFormat>,
+    #[serde(default)]
+    pub engine_features: graphics_device::EngineFeatures,
+}
+
+/// Material entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaterialManifest {
+    pub name: String,
+    pub passes: Vec<MaterialPassManifest>,
+}
+
+/// Material pass entry (fragment shader by name)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaterialPassManifest {
+    pub pass_type: u8,
+    pub fragment_shader: String,
+    #[serde(default)]
+    pub color_blend: graphics_device::ColorBlendState,
+    pub polygon_mode: graphics_device::PolygonMode,
+    #[serde(default)]
+    pub textures: Vec<TextureSlotManifest>,
+    #[serde(default)]
+    pub params: Vec<(String, ParamValue)>,
+    #[serde(default)]
+    pub render_state: Option<graphics_device::DynamicRenderState>,
+    #[serde(default)]
+    pub engine_features: graphics_device::EngineFeatures,
+    #[serde(default)]
+    pub render_queue: Option<RenderQueueClass>,
+}
+
+/// Material texture slot entry (texture by name)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextureSlotManifest {
+    pub name: String,
+    pub texture: String,
+    #[serde(default)]
+    pub layer: Option<LayerRef>,
+    #[serde(default)]
+    pub region: Option<RegionRef>,
+    pub sampler_type: graphics_device::SamplerType,
+    #[serde(default)]
+    pub flipbook: Option<FlipbookDesc>,
+}
+
+/// Mesh entry (geometry and materials by name)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeshManifest {
+    pub name: String,
+    pub geometry: String,
+    pub geometry_mesh: GeometryMeshRef,
+    pub submeshes: Vec<MeshSubMeshManifest>,
+}
+
+/// Mesh submesh entry (material by name)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeshSubMeshManifest {
+    pub submesh: GeometrySubMeshRef,
+    pub material: String,
+}
+
+// ===== MANIFEST =====
+
+/// A set of resource entries (see module docs)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResourceManifest {
+    #[serde(default)]
+    pub textures: Vec<TextureManifest>,
+    #[serde(default)]
+    pub geometries: Vec<GeometryManifest>,
+    #[serde(default)]
+    pub shaders: Vec<ShaderManifest>,
+    #[serde(default)]
+    pub pipelines: Vec<PipelineManifest>,
+    #[serde(default)]
+    pub materials: Vec<MaterialManifest>,
+    #[serde(default)]
+    pub meshes: Vec<MeshManifest>,
+}
+
+impl ResourceManifest {
+    /// Number of entries of every kind
+    pub fn len(&self) -> usize {
+        self.textures.len() + self.geometries.len() + self.shaders.len()
+            + self.pipelines.len() + self.materials.len() + self.meshes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Parse a manifest text
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text is not a valid manifest.
+    pub fn parse(text: &str, format: ManifestFormat) -> Result<Self> {
+        match format {
+            ManifestFormat::Ron => ron::from_str(text)
+                .map_err(|e| engine_err!("galaxy3d::ResourceManifest", "Invalid RON manifest: {}", e)),
+            ManifestFormat::Json => serde_json::from_str(text)
+                .map_err(|e| engine_err!("galaxy3d::ResourceManifest", "Invalid JSON manifest: {}", e)),
+        }
+    }
+
+    /// Print the manifest (pretty-printed)
+    pub fn to_text(&self, format: ManifestFormat) -> Result<String> {
+        match format {
+            ManifestFormat::Ron => ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+                .map_err(|e| engine_err!("galaxy3d::ResourceManifest", "Cannot write RON manifest: {}", e)),
+            ManifestFormat::Json => serde_json::to_string_pretty(self)
+                .map_err(|e| engine_err!("galaxy3d::ResourceManifest", "Cannot write JSON manifest: {}", e)),
+        }
+    }
+
+    /// Read a manifest file, in the format of its extension
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the extension is not `.ron` / `.json`, or the
+    /// file cannot be read or parsed.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let format = format_of(path)?;
+        let text = std::fs::read_to_string(path).map_err(|e| engine_err!("galaxy3d::ResourceManifest",
+            "Cannot read manifest '{}': {}", path.display(), e))?;
+        Self::parse(&text, format)
+    }
+
+    /// Write the manifest to `path` (atomically, see `write_atomic`), in
+    /// the format of its extension
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let text = self.to_text(format_of(path)?)?;
+        write_atomic(path, text.as_bytes())
+    }
+
+    /// Create the resources of the manifest in `rm`. Data paths are
+    /// relative to `base_dir`. On error, the resources already created are
+    /// removed.
+    pub(crate) fn create_resources(
+        &self,
+        rm: &mut ResourceManager,
+        base_dir: &Path,
+        graphics_device: &Arc<Mutex<dyn GraphicsDevice>>,
+    ) -> Result<ManifestRecords> {
+        let mut records = ManifestRecords::default();
+        match self.create_into(rm, base_dir, graphics_device, &mut records) {
+            Ok(()) => Ok(records),
+            Err(error) => {
+                records.remove_all(rm);
+                Err(error)
+            }
+        }
+    }
+
+    fn create_into(
+        &self,
+        rm: &mut ResourceManager,
+        base_dir: &Path,
+        graphics_device: &Arc<Mutex<dyn GraphicsDevice>>,
+        records: &mut ManifestRecords,
+    ) -> Result<()> {
+        for entry in &self.textures {
+            let key = rm.create_texture(entry.name.clone(), texture_desc(entry, base_dir, graphics_device)?)?;
+            records.textures.push((key, entry.clone()));
+        }
+        for entry in &self.geometries {
+            let desc = GeometryDesc {
+                name: entry.name.clone(),
+                graphics_device: graphics_device.clone(),
+                vertex_data: Cow::Owned(read_data(base_dir, &entry.vertex_data)?),
+                index_data: entry.index_data.as_ref()
+                    .map(|path| read_data(base_dir, path).map(Cow::Owned))
+                    .transpose()?,
+                vertex_layout: entry.vertex_layout.clone(),
+                index_type: entry.index_type,
+                morph_targets: entry.morph_targets.clone(),
+                meshes: entry.meshes.clone(),
+            };
+            let key = rm.create_geometry(entry.name.clone(), desc)?;
+            records.geometries.push((key, entry.clone()));
+        }
+        for entry in &self.shaders {
+            let code = read_data(base_dir, &entry.code)?;
+            let desc = ShaderDesc { code: &code, stage: entry.stage, entry_point: entry.entry_point.clone() };
+            let mut gd = graphics_device.lock().unwrap();
+            let key = rm.create_shader(entry.name.clone(), desc, &mut *gd)?;
+            records.shaders.push((key, entry.clone()));
+        }
+        for entry in &self.pipelines {
+            let desc = PipelineDesc {
+                vertex_shader: shader_key(rm, &entry.name, &entry.vertex_shader)?,
+                fragment_shader: shader_key(rm, &entry.name, &entry.fragment_shader)?,
+                vertex_layout: entry.vertex_layout.clone(),
+                topology: entry.topology,
+                rasterization: entry.rasterization,
+                color_blend: entry.color_blend,
+                multisample: entry.multisample,
+                color_formats: entry.color_formats.clone(),
+                depth_format: entry.depth_format,
+                engine_features: entry.engine_features,
+            };
+            let mut gd = graphics_device.lock().unwrap();
+            let key = rm.create_pipeline(entry.name.clone(), desc, &mut *gd)?;
+            records.pipelines.push((key, entry.clone()));
+        }
+        for entry in &self.materials {
+            let desc = material_desc(rm, entry)?;
+            let gd = graphics_device.lock().unwrap();
+            let key = rm.create_material(entry.name.clone(), desc, &*gd)?;
+            records.materials.push((key, entry.clone()));
+        }
+        for entry in &self.meshes {
+            let geometry = rm.geometry_key(&entry.geometry).ok_or_else(|| engine_err!("galaxy3d::ResourceManifest",
+                "Mesh '{}' references unknown geometry '{}'", entry.name, entry.geometry))?;
+            let submeshes = entry.submeshes.iter()
+                .map(|submesh| Ok(MeshSubMeshDesc {
+                    submesh: submesh.submesh.clone(),
+                    material: rm.material_key(&submesh.material).ok_or_else(|| engine_err!("galaxy3d::ResourceManifest",
+                        "Mesh '{}' references unknown material '{}'", entry.name, submesh.material))?,
+                }))
+                .collect::<Result<Vec<_>>>()?;
+            let desc = MeshDesc { geometry, geometry_mesh: entry.geometry_mesh.clone(), submeshes };
+            let key = rm.create_mesh(entry.name.clone(), desc)?;
+            records.meshes.push((key, entry.clone()));
+        }
+        Ok(())
+    }
+}
+
+// ===== RECORDS =====
+
+/// Manifest entries of the resources created from manifests, with their
+/// keys (kept by the `ResourceManager` for `save_manifest()`)
+#[derive(Default)]
+pub(crate) struct ManifestRecords {
+    textures: Vec<(TextureKey, TextureManifest)>,
+    geometries: Vec<(GeometryKey, GeometryManifest)>,
+    shaders: Vec<(ShaderKey, ShaderManifest)>,
+    pipelines: Vec<(PipelineKey, PipelineManifest)>,
+    materials: Vec<(MaterialKey, MaterialManifest)>,
+    meshes: Vec<(MeshKey, MeshManifest)>,
+}
+
+impl ManifestRecords {
+    pub(crate) fn extend(&mut self, other: ManifestRecords) {
+        self.textures.extend(other.textures);
+        self.geometries.extend(other.geometries);
+        self.shaders.extend(other.shaders);
+        self.pipelines.extend(other.pipelines);
+        self.materials.extend(other.materials);
+        self.meshes.extend(other.meshes);
+    }
+
+    /// Entries whose resource is still registered under the same name
+    pub(crate) fn live(&self, rm: &ResourceManager) -> ResourceManifest {
+        fn keep<K: Copy + PartialEq, E: Clone>(
+            records: &[(K, E)],
+            name: impl Fn(&E) -> &str,
+            key_of: impl Fn(&str) -> Option<K>,
+        ) -> Vec<E> {
+            records.iter()
+                .filter(|(key, entry)| key_of(name(entry)) == Some(*key))
+                .map(|(_, entry)| entry.clone())
+                .collect()
+        }
+        ResourceManifest {
+            textures: keep(&self.textures, |e| &e.name, |n| rm.texture_key(n)),
+            geometries: keep(&self.geometries, |e| &e.name, |n| rm.geometry_key(n)),
+            shaders: keep(&self.shaders, |e| &e.name, |n| rm.shader_key(n)),
+            pipelines: keep(&self.pipelines, |e| &e.name, |n| rm.pipeline_key(n)),
+            materials: keep(&self.materials, |e| &e.name, |n| rm.material_key(n)),
+            meshes: keep(&self.meshes, |e| &e.name, |n| rm.mesh_key(n)),
+        }
+    }
+
+    /// Remove the recorded resources, dependents first
+    fn remove_all(&self, rm: &mut ResourceManager) {
+        fn names<K, E>(records: &[(K, E)], name: impl Fn(&E) -> &str) -> Vec<String> {
+            records.iter().map(|(_, entry)| name(entry).to_string()).collect()
+        }
+        rm.remove_many(ResourceKind::Mesh, &names(&self.meshes, |e| &e.name));
+        rm.remove_many(ResourceKind::Material, &names(&self.materials, |e| &e.name));
+        rm.remove_many(ResourceKind::Pipeline, &names(&self.pipelines, |e| &e.name));
+        rm.remove_many(ResourceKind::Shader, &names(&self.shaders, |e| &e.name));
+        rm.remove_many(ResourceKind::Geometry, &names(&self.geometries, |e| &e.name));
+        rm.remove_many(ResourceKind::Texture, &names(&self.textures, |e| &e.name));
+    }
+}
+
+// ===== PRIVATE HELPERS =====
+
+fn format_of(path: &Path) -> Result<ManifestFormat> {
+    match ManifestFormat::from_path(path) {
+        Some(format) => Ok(format),
+        None => engine_bail!("galaxy3d::ResourceManifest",
+            "Manifest '{}' must have a .ron or .json extension", path.display()),
+    }
+}
+
+fn read_data(base_dir: &Path, path: &Path) -> Result<Vec<u8>> {
+    let full_path = base_dir.join(path);
+    std::fs::read(&full_path).map_err(|e| engine_err!("galaxy3d::ResourceManifest",
+        "Cannot read '{}': {}", full_path.display(), e))
+}
+
+fn shader_key(rm: &ResourceManager, owner: &str, name: &str) -> Result<ShaderKey> {
+    rm.shader_key(name).ok_or_else(|| engine_err!("galaxy3d::ResourceManifest",
+        "'{}' references unknown shader '{}'", owner, name))
+}
+
+fn texture_desc(
+    entry: &TextureManifest,
+    base_dir: &Path,
+    graphics_device: &Arc<Mutex<dyn GraphicsDevice>>,
+) -> Result<TextureDesc> {
+    let data = entry.data.as_ref()
+        .map(|path| read_data(base_dir, path).map(graphics_device::TextureData::Single))
+        .transpose()?;
+    let mut layers = entry.layers.iter()
+        .map(|layer| Ok(LayerDesc {
+            name: layer.name.clone(),
+            layer_index: layer.layer_index,
+            data: layer.data.as_ref().map(|path| read_data(base_dir, path)).transpose()?,
+            regions: layer.regions.iter()
+                .map(|r| AtlasRegionDesc { name: r.name.clone(), region: r.region.clone() })
+                .collect(),
+        }))
+        .collect::<Result<Vec<_>>>()?;
+    if layers.is_empty() && entry.array_layers == 1 {
+        layers.push(LayerDesc {
+            name: "default".to_string(),
+            layer_index: 0,
+            data: None,
+            regions: vec![],
+        });
+    }
+    Ok(TextureDesc {
+        graphics_device: graphics_device.clone(),
+        texture: graphics_device::TextureDesc {
+            width: entry.width,
+            height: entry.height,
+            depth: entry.depth,
+            format: entry.format,
+            usage: entry.usage,
+            array_layers: entry.array_layers,
+            data,
+            mipmap: entry.mipmap.to_mode(),
+            texture_type: entry.texture_type,
+            sample_count: entry.sample_count,
+            debug_name: None,
+        },
+        layers,
+    })
+}
+
+fn material_desc(rm: &ResourceManager, entry: &MaterialManifest) -> Result<MaterialDesc> {
+    let passes = entry.passes.iter()
+        .map(|pass| {
+            let textures = pass.textures.iter()
+                .map(|slot| Ok(MaterialTextureSlotDesc {
+                    name: slot.name.clone(),
+                    texture: rm.texture_key(&slot.texture).ok_or_else(|| engine_err!("galaxy3d::ResourceManifest",
+                        "Material '{}' references unknown texture '{}'", entry.name, slot.texture))?,
+                    layer: slot.layer.clone(),
+                    region: slot.region.clone(),
+                    sampler_type: slot.sampler_type,
+                    flipbook: slot.flipbook.clone(),
+                }))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(MaterialPassDesc {
+                pass_type: pass.pass_type,
+                fragment_shader: shader_key(rm, &entry.name, &pass.fragment_shader)?,
+                color_blend: pass.color_blend,
+                polygon_mode: pass.polygon_mode,
+                textures,
+                params: pass.params.clone(),
+                render_state: pass.render_state,
+                engine_features: pass.engine_features,
+                render_queue: pass.render_queue,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(MaterialDesc { passes })
+}
+
+#[cfg(test)]
+#[path = "manifest_tests.rs"]
+mod tests;
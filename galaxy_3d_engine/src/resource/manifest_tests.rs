@@ -0,0 +1,273 @@
+/// Unit tests for manifest.rs
+///
+/// Resources are created on a MockGraphicsDevice from data files written
+/// in a scratch directory.
+
+use super::*;
+use crate::graphics_device::mock_graphics_device::MockGraphicsDevice;
+use crate::resource::geometry::{GeometrySubMeshDesc, GeometrySubMeshLODDesc};
+use crate::utils::test_helpers::scratch_dir;
+use std::fs;
+
+fn create_mock_graphics_device() -> Arc<Mutex<dyn GraphicsDevice>> {
+    Arc::new(Mutex::new(MockGraphicsDevice::new()))
+}
+
+fn vertex_layout() -> graphics_device::VertexLayout {
+    graphics_device::VertexLayout {
+        bindings: vec![graphics_device::VertexBinding {
+            binding: 0, stride: 8, input_rate: graphics_device::VertexInputRate::Vertex,
+        }],
+        attributes: vec![graphics_device::VertexAttribute {
+            location: 0, binding: 0, format: graphics_device::BufferFormat::R32G32_SFLOAT, offset: 0,
+        }],
+    }
+}
+
+/// One resource of every kind: texture, quad geometry, two shaders,
+/// pipeline, material sampling the texture, mesh drawing the quad
+fn sample_manifest() -> ResourceManifest {
+    ResourceManifest {
+        textures: vec![TextureManifest {
+            name: "checker".to_string(),
+            width: 2,
+            height: 2,
+            depth: 1,
+            format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
+            usage: graphics_device::TextureUsage::Sampled,
+            texture_type: graphics_device::TextureType::Tex2D,
+            array_layers: 1,
+            sample_count: graphics_device::SampleCount::S1,
+            mipmap: ManifestMipmap::None,
+            data: Some(PathBuf::from("checker.rgba")),
+            layers: vec![LayerManifest {
+                name: "default".to_string(),
+                layer_index: 0,
+                data: None,
+                regions: vec![],
+            }],
+        }],
+        geometries: vec![GeometryManifest {
+            name: "quad".to_string(),
+            vertex_data: PathBuf::from("quad.vtx"),
+            index_data: Some(PathBuf::from("quad.idx")),
+            vertex_layout: vertex_layout(),
+            index_type: graphics_device::IndexType::U16,
+            morph_targets: vec![],
+            meshes: vec![GeometryMeshDesc {
+                name: "quad".to_string(),
+                submeshes: vec![GeometrySubMeshDesc {
+                    name: "face".to_string(),
+                    lods: vec![GeometrySubMeshLODDesc {
+                        vertex_offset: 0, vertex_count: 4, index_offset: 0, index_count: 6,
+                        topology: graphics_device::PrimitiveTopology::TriangleList,
+                    }],
+                    lod_thresholds: vec![],
+                }],
+            }],
+        }],
+        shaders: vec![
+            ShaderManifest {
+                name: "vert".to_string(), code: PathBuf::from("shader.spv"),
+                stage: graphics_device::ShaderStage::Vertex, entry_point: "main".to_string(),
+            },
+            ShaderManifest {
+                name: "frag".to_string(), code: PathBuf::from("shader.spv"),
+                stage: graphics_device::ShaderStage::Fragment, entry_point: "main".to_string(),
+            },
+        ],
+        pipelines: vec![PipelineManifest {
+            name: "opaque".to_string(),
+            vertex_shader: "vert".to_string(),
+            fragment_shader: "frag".to_string(),
+            vertex_layout: vertex_layout(),
+            topology: graphics_device::PrimitiveTopology::TriangleList,
+            rasterization: Default::default(),
+            color_blend: Default::default(),
+            multisample: Default::default(),
+            color_formats: vec![],
+            depth_format: None,
+            engine_features: Default::default(),
+        }],
+        materials: vec![MaterialManifest {
+            name: "checker".to_string(),
+            passes: vec![MaterialPassManifest {
+                pass_type: 0,
+                fragment_shader: "frag".to_string(),
+                color_blend: Default::default(),
+                polygon_mode: graphics_device::PolygonMode::Fill,
+                textures: vec![TextureSlotManifest {
+                    name: "albedo".to_string(),
+                    texture: "checker".to_string(),
+                    layer: None,
+                    region: None,
+                    sampler_type: graphics_device::SamplerType::NearestClamp,
+                    flipbook: None,
+                }],
+                params: vec![("tint".to_string(), ParamValue::Vec4([1.0, 0.5, 0.25, 1.0]))],
+                render_state: None,
+                engine_features: Default::default(),
+                render_queue: None,
+            }],
+        }],
+        meshes: vec![MeshManifest {
+            name: "quad".to_string(),
+            geometry: "quad".to_string(),
+            geometry_mesh: GeometryMeshRef::Name("quad".to_string()),
+            submeshes: vec![MeshSubMeshManifest {
+                submesh: GeometrySubMeshRef::Name("face".to_string()),
+                material: "checker".to_string(),
+            }],
+        }],
+    }
+}
+
+/// Write the data files of `sample_manifest()` into `dir`
+fn write_sample_data(dir: &Path) {
+    fs::write(dir.join("checker.rgba"), [255u8; 16]).unwrap();
+    fs::write(dir.join("quad.vtx"), [0u8; 32]).unwrap();
+    fs::write(dir.join("quad.idx"), [0u8; 12]).unwrap();
+    fs::write(dir.join("shader.spv"), b"").unwrap();
+}
+
+// ============================================================================
+// Text format
+// ============================================================================
+
+#[test]
+fn test_format_from_extension() {
+    assert_eq!(ManifestFormat::from_path(Path::new("level.ron")), Some(ManifestFormat::Ron));
+    assert_eq!(ManifestFormat::from_path(Path::new("a/level.JSON")), Some(ManifestFormat::Json));
+    assert_eq!(ManifestFormat::from_path(Path::new("level.toml")), None);
+    assert_eq!(ManifestFormat::from_path(Path::new("level")), None);
+}
+
+#[test]
+fn test_text_round_trip_ron_and_json() {
+    let manifest = sample_manifest();
+    for format in [ManifestFormat::Ron, ManifestFormat::Json] {
+        let text = manifest.to_text(format).unwrap();
+        let parsed = ResourceManifest::parse(&text, format).unwrap();
+        assert_eq!(parsed.len(), manifest.len());
+        assert_eq!(parsed.to_text(format).unwrap(), text);
+    }
+}
+
+#[test]
+fn test_parse_fills_defaults_and_rejects_invalid_text() {
+    let manifest = ResourceManifest::parse(
+        r#"(shaders: [(name: "frag", code: "frag.spv", stage: Fragment, entry_point: "main")])"#,
+        ManifestFormat::Ron,
+    ).unwrap();
+    assert_eq!(manifest.len(), 1);
+    assert!(manifest.textures.is_empty());
+
+    assert!(ResourceManifest::parse("{ \"shaders\": 3 }", ManifestFormat::Json).is_err());
+    assert!(ResourceManifest::load("level.toml").is_err());
+}
+
+// ============================================================================
+// Resource creation
+// ============================================================================
+
+#[test]
+fn test_load_manifest_creates_resources_and_saves_them_back() {
+    let dir = scratch_dir("manifest_load_save");
+    write_sample_data(&dir);
+    sample_manifest().save(dir.join("level.ron")).unwrap();
+
+    let gd = create_mock_graphics_device();
+    let mut rm = ResourceManager::new();
+    rm.load_manifest(dir.join("level.ron"), &gd).unwrap();
+    assert!(rm.texture_key("checker").is_some());
+    assert!(rm.geometry_key("quad").is_some());
+    assert!(rm.pipeline_key("opaque").is_some());
+    assert!(rm.material_key("checker").is_some());
+    assert!(rm.mesh_key("quad").is_some());
+
+    // Saved as JSON next to the RON source: same entries, same data paths
+    rm.save_manifest(dir.join("level.json")).unwrap();
+    let saved = ResourceManifest::load(dir.join("level.json")).unwrap();
+    assert_eq!(saved.len(), sample_manifest().len());
+    assert_eq!(saved.textures[0].data, Some(PathBuf::from("checker.rgba")));
+
+    // A second manager loads the saved manifest
+    let mut reloaded = ResourceManager::new();
+    reloaded.load_manifest(dir.join("level.json"), &gd).unwrap();
+    assert!(reloaded.mesh_key("quad").is_some());
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_simple_texture_without_layers_gets_default_layer() {
+    let dir = scratch_dir("manifest_default_layer");
+    write_sample_data(&dir);
+    let mut manifest = sample_manifest();
+    manifest.textures[0].layers.clear();
+
+    let gd = create_mock_graphics_device();
+    let mut rm = ResourceManager::new();
+    rm.create_from_manifest(&manifest, &dir, &gd).unwrap();
+    let texture = rm.texture_by_name("checker").unwrap();
+    assert_eq!(texture.layer_count(), 1);
+    assert!(texture.layer_by_name("default").is_some());
+
+    // Array textures get no default layer: theirs are added later
+    let mut array = sample_manifest().textures.remove(0);
+    array.name = "array".to_string();
+    array.texture_type = graphics_device::TextureType::Array2D;
+    array.array_layers = 2;
+    array.data = None;
+    array.layers.clear();
+    let manifest = ResourceManifest { textures: vec![array], ..Default::default() };
+    rm.create_from_manifest(&manifest, &dir, &gd).unwrap();
+    assert_eq!(rm.texture_by_name("array").unwrap().layer_count(), 0);
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_manifest_skips_removed_resources() {
+    let dir = scratch_dir("manifest_removed");
+    write_sample_data(&dir);
+
+    let gd = create_mock_graphics_device();
+    let mut rm = ResourceManager::new();
+    rm.create_from_manifest(&sample_manifest(), &dir, &gd).unwrap();
+    assert_eq!(rm.manifest().len(), sample_manifest().len());
+
+    rm.remove_many(ResourceKind::Mesh, &["quad"]);
+    let manifest = rm.manifest();
+    assert!(manifest.meshes.is_empty());
+    assert_eq!(manifest.materials.len(), 1);
+
+    rm.clear();
+    assert!(rm.manifest().is_empty());
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_failed_manifest_removes_created_resources() {
+    let dir = scratch_dir("manifest_rollback");
+    write_sample_data(&dir);
+
+    let mut manifest = sample_manifest();
+    manifest.meshes[0].submeshes[0].material = "missing".to_string();
+
+    let gd = create_mock_graphics_device();
+    let mut rm = ResourceManager::new();
+    assert!(rm.create_from_manifest(&manifest, &dir, &gd).is_err());
+    assert!(rm.texture_key("checker").is_none());
+    assert!(rm.shader_key("vert").is_none());
+    assert!(rm.material_key("checker").is_none());
+    assert!(rm.manifest().is_empty());
+
+    // Missing data file
+    fs::remove_file(dir.join("quad.vtx")).unwrap();
+    assert!(rm.create_from_manifest(&sample_manifest(), &dir, &gd).is_err());
+    assert!(rm.texture_key("checker").is_none());
+
+    let _ = fs::remove_dir_all(&dir);
+}
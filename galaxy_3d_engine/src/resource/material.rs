@@ -16,12 +16,22 @@
 ///
 /// At creation time, the Material resolves keys via the ResourceManager and
 /// resolves layer/region references for each texture slot.
-
+///
+/// Texture animation (standard material features):
+/// - Flipbook: a slot can play a sequence of atlas regions of its layer
+///   (`MaterialTextureSlotDesc::flipbook`). The current region is written
+///   each frame by `ResourceManager::sync_material_animations()` as the UV
+///   rectangle `{slot}UvRect`.
+/// - UV scrolling/rotation: the `UV_SCROLL_PARAM` and
+///   `UV_ROTATION_SPEED_PARAM` params are synced like any other param and
+///   applied by the shader with the frame `time` (see `animated_uv`).
+
+use std::fmt;
 use rustc_hash::{FxHashMap, FxHashSet};
 use crate::error::Result;
-use crate::{engine_bail, engine_err};
+use crate::{engine_bail, engine_err, engine_warn};
 use crate::resource::resource_manager::{ResourceManager, ShaderKey, TextureKey};
-use crate::graphics_device::{self, SamplerType, ColorBlendState, PolygonMode, DynamicRenderState};
+use crate::graphics_device::{self, SamplerType, ColorBlendState, PolygonMode, DynamicRenderState, EngineFeatures};
 
 // ===== REFERENCE TYPES =====
 
@@ -29,6 +39,8 @@ use crate::graphics_device::{self, SamplerType, ColorBlendState, PolygonMode, Dy
 ///
 /// Used in descriptors to let the user choose the most convenient way
 /// to reference a layer. Resolved to a u32 index at creation time.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub enum LayerRef {
     Index(u32),
     Name(String),
@@ -38,15 +50,109 @@ pub enum LayerRef {
 ///
 /// Used in descriptors to let the user choose the most convenient way
 /// to reference a region. Resolved to a u32 index at creation time.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub enum RegionRef {
     Index(u32),
     Name(String),
 }
 
+// ===== TEXTURE ANIMATION =====
+
+/// Param (Vec2) holding the UV scroll speed, in UV units per second
+pub const UV_SCROLL_PARAM: &str = "uvScroll";
+/// Param (Float) holding the UV rotation speed around (0.5, 0.5), in radians per second
+pub const UV_ROTATION_SPEED_PARAM: &str = "uvRotationSpeed";
+/// Suffix of the Vec4 field receiving the UV rectangle of a slot's region
+/// (`"albedo"` -> `"albedoUvRect"`)
+pub const UV_RECT_FIELD_SUFFIX: &str = "UvRect";
+/// UV rectangle `(offset.xy, scale.xy)` covering the whole layer
+pub const FULL_UV_RECT: [f32; 4] = [0.0, 0.0, 1.0, 1.0];
+/// Center of the UV rotation
+const UV_ROTATION_CENTER: [f32; 2] = [0.5, 0.5];
+
+/// Flipbook descriptor: atlas regions of the slot's layer played in order
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlipbookDesc {
+    /// Regions shown one after the other (at least one)
+    pub frames: Vec<RegionRef>,
+    /// Playback rate (finite, > 0)
+    pub frames_per_second: f32,
+    /// Restart after the last frame (otherwise hold it)
+    pub looping: bool,
+}
+
+/// Resolved flipbook of a texture slot (region indices of the slot's layer)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Flipbook {
+    frames: Vec<u32>,
+    frames_per_second: f32,
+    looping: bool,
+}
+
+impl Flipbook {
+    /// Region indices, in playback order
+    pub fn frames(&self) -> &[u32] {
+        &self.frames
+    }
+
+    /// Playback rate
+    pub fn frames_per_second(&self) -> f32 {
+        self.frames_per_second
+    }
+
+    /// Whether playback restarts after the last frame
+    pub fn looping(&self) -> bool {
+        self.looping
+    }
+
+    /// Duration of one playback, in seconds
+    pub fn duration(&self) -> f32 {
+        self.frames.len() as f32 / self.frames_per_second
+    }
+
+    /// Position in `frames()` shown at `time` (seconds since playback
+    /// start; negative times show the first frame)
+    pub fn frame_at(&self, time: f32) -> usize {
+        if time.is_nan() || time <= 0.0 {
+            return 0;
+        }
+        let frame = (time * self.frames_per_second) as u64;
+        let count = self.frames.len() as u64;
+        if self.looping {
+            (frame % count) as usize
+        } else {
+            frame.min(count - 1) as usize
+        }
+    }
+
+    /// Region index shown at `time`
+    pub fn region_at(&self, time: f32) -> u32 {
+        self.frames[self.frame_at(time)]
+    }
+}
+
+/// UV scrolling and rotation as applied by shaders: rotate `uv` around
+/// (0.5, 0.5) by `time * rotation_speed`, then offset it by
+/// `time * scroll`. Shaders then map the result into the slot's region
+/// with `{slot}UvRect.xy + fract(uv) * {slot}UvRect.zw`.
+///
+/// CPU reference of the shader-side contract.
+pub fn animated_uv(uv: [f32; 2], scroll: [f32; 2], rotation_speed: f32, time: f32) -> [f32; 2] {
+    let (sin, cos) = (time * rotation_speed).sin_cos();
+    let (x, y) = (uv[0] - UV_ROTATION_CENTER[0], uv[1] - UV_ROTATION_CENTER[1]);
+    [
+        x * cos - y * sin + UV_ROTATION_CENTER[0] + time * scroll[0],
+        x * sin + y * cos + UV_ROTATION_CENTER[1] + time * scroll[1],
+    ]
+}
+
 // ===== PARAMETER VALUES =====
 
 /// A typed parameter value for the material
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub enum ParamValue {
     Float(f32),
     Vec2([f32; 2]),
@@ -98,9 +204,29 @@ pub struct MaterialTextureSlot {
     sampler_index: u32,
     layer: Option<u32>,
     region: Option<u32>,
+    flipbook: Option<Flipbook>,
     sampler_type: SamplerType,
 }
 
+// ===== RENDER QUEUE =====
+
+/// Render queue a material pass is drawn in.
+///
+/// The drawer emits the queues in declaration order: opaque surfaces sorted
+/// for state changes, then alpha-tested ones, then transparent ones sorted
+/// back-to-front.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
+pub enum RenderQueueClass {
+    /// Solid surfaces
+    #[default]
+    Opaque,
+    /// Solid surfaces with discarded fragments (foliage, fences)
+    AlphaTest,
+    /// Blended surfaces
+    Transparent,
+}
+
 // ===== MATERIAL PASS =====
 
 /// A single rendering pass within a Material.
@@ -114,6 +240,9 @@ pub struct MaterialPass {
     color_blend: ColorBlendState,
     polygon_mode: PolygonMode,
     render_state: DynamicRenderState,
+    /// Engine features the pass's shaders react to
+    engine_features: EngineFeatures,
+    render_queue: RenderQueueClass,
     /// Stable u16 id identifying this pass's `DynamicRenderState`. Assigned by
     /// `ResourceManager::get_or_assign_material_render_state_signature_id()`
     /// after the Material is built. Two passes with identical render states
@@ -138,6 +267,8 @@ pub struct Material {
     passes: Vec<MaterialPass>,
     /// Generation counter for pipeline cache invalidation
     generation: u64,
+    /// Params or texture slots changed since the last buffer sync
+    dirty: bool,
 }
 
 // ===== DESCRIPTORS =====
@@ -153,6 +284,14 @@ pub struct MaterialPassDesc {
     /// Dynamic render state for this pass.
     /// If None, uses DynamicRenderState::default().
     pub render_state: Option<DynamicRenderState>,
+    /// Engine features the pass's shaders react to (e.g.
+    /// `EngineFeatures::from_names(&["shadows", "fog"])`). Only these bits
+    /// of the active engine mask select the pipeline variant, so passes
+    /// ignoring a feature are not rebuilt when it is toggled.
+    pub engine_features: EngineFeatures,
+    /// Render queue of the pass.
+    /// If None, Transparent when blending is enabled, Opaque otherwise.
+    pub render_queue: Option<RenderQueueClass>,
 }
 
 /// Material creation descriptor
@@ -167,6 +306,96 @@ pub struct MaterialTextureSlotDesc {
     pub layer: Option<LayerRef>,
     pub region: Option<RegionRef>,
     pub sampler_type: SamplerType,
+    /// Atlas region sequence to play instead of a fixed region
+    /// (requires `layer`, exclusive with `region`)
+    pub flipbook: Option<FlipbookDesc>,
+}
+
+// ===== INSPECTION REPORT =====
+
+/// Snapshot of a resolved texture slot, produced by `Material::describe()`.
+#[derive(Debug, Clone)]
+pub struct TextureSlotReport {
+    /// Slot name
+    pub name: String,
+    /// Texture bound to the slot
+    pub texture: TextureKey,
+    /// Bindless index read from the GPU texture at creation time
+    pub bindless_index: u32,
+    /// Sampler type requested by the slot
+    pub sampler_type: SamplerType,
+    /// Sampler index in the bindless sampler table
+    pub sampler_index: u32,
+    /// Resolved layer index (None = whole texture / layer 0)
+    pub layer: Option<u32>,
+    /// Resolved region index (None = whole layer)
+    pub region: Option<u32>,
+    /// Resolved flipbook (None = not animated)
+    pub flipbook: Option<Flipbook>,
+}
+
+/// Snapshot of a single MaterialPass, produced by `Material::describe()`.
+#[derive(Debug, Clone)]
+pub struct MaterialPassReport {
+    pub pass_type: u8,
+    pub fragment_shader: ShaderKey,
+    pub polygon_mode: PolygonMode,
+    pub color_blend: ColorBlendState,
+    pub render_state: DynamicRenderState,
+    pub render_state_signature_id: u16,
+    pub engine_features: EngineFeatures,
+    pub render_queue: RenderQueueClass,
+    /// Texture slots in declaration order
+    pub textures: Vec<TextureSlotReport>,
+    /// Parameters `(name, current value)` in declaration order
+    pub params: Vec<(String, ParamValue)>,
+}
+
+/// Structured dump of a Material, for editor property panels and debugging.
+///
+/// The `Display` implementation pretty-prints the whole report (one section
+/// per pass), e.g. to answer "why is this texture not showing".
+#[derive(Debug, Clone)]
+pub struct MaterialReport {
+    pub slot_id: u32,
+    pub generation: u64,
+    pub passes: Vec<MaterialPassReport>,
+}
+
+impl fmt::Display for MaterialReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Material (slot {}, generation {}, {} pass(es))",
+            self.slot_id, self.generation, self.passes.len())?;
+        for pass in &self.passes {
+            writeln!(f, "  Pass type {}:", pass.pass_type)?;
+            writeln!(f, "    fragment shader: {:?}", pass.fragment_shader)?;
+            writeln!(f, "    polygon mode: {:?}", pass.polygon_mode)?;
+            writeln!(f, "    blend: {}", if pass.color_blend.blend_enable { "enabled" } else { "disabled" })?;
+            writeln!(f, "    render state signature: {}", pass.render_state_signature_id)?;
+            writeln!(f, "    engine features: {:#x}", pass.engine_features.bits())?;
+            writeln!(f, "    render queue: {:?}", pass.render_queue)?;
+            writeln!(f, "    textures ({}):", pass.textures.len())?;
+            for tex in &pass.textures {
+                write!(f, "      {} -> {:?} (bindless {}, sampler {:?})",
+                    tex.name, tex.texture, tex.bindless_index, tex.sampler_type)?;
+                if let Some(layer) = tex.layer {
+                    write!(f, " layer {}", layer)?;
+                }
+                if let Some(region) = tex.region {
+                    write!(f, " region {}", region)?;
+                }
+                if let Some(flipbook) = &tex.flipbook {
+                    write!(f, " flipbook {:?} at {} fps", flipbook.frames, flipbook.frames_per_second)?;
+                }
+                writeln!(f)?;
+            }
+            writeln!(f, "    params ({}):", pass.params.len())?;
+            for (name, value) in &pass.params {
+                writeln!(f, "      {} = {:?}", name, value)?;
+            }
+        }
+        Ok(())
+    }
 }
 
 // ===== MATERIAL IMPLEMENTATION =====
@@ -258,81 +487,8 @@ impl Material {
             let mut texture_names = FxHashMap::default();
 
             for (vec_index, slot_desc) in pass_desc.textures.into_iter().enumerate() {
-                // Resolve texture key
-                let texture_arc = resource_manager.texture(slot_desc.texture)
-                    .ok_or_else(|| engine_err!("galaxy3d::Material",
-                        "Texture slot '{}' (pass_type {}): texture key not found in ResourceManager",
-                        slot_desc.name, pass_type))?;
-
-                // Resolve layer reference
-                let resolved_layer = match slot_desc.layer {
-                    None => None,
-                    Some(LayerRef::Index(i)) => {
-                        if texture_arc.layer(i).is_none() {
-                            engine_bail!("galaxy3d::Material",
-                                "Texture slot '{}' (pass_type {}): layer index {} does not exist",
-                                slot_desc.name, pass_type, i);
-                        }
-                        Some(i)
-                    }
-                    Some(LayerRef::Name(ref name)) => {
-                        let idx = texture_arc.layer_index_by_name(name)
-                            .ok_or_else(|| engine_err!("galaxy3d::Material",
-                                "Texture slot '{}' (pass_type {}): layer '{}' not found",
-                                slot_desc.name, pass_type, name))?;
-                        Some(idx)
-                    }
-                };
-
-                // Resolve region reference (requires a resolved layer)
-                let resolved_region = match slot_desc.region {
-                    None => None,
-                    Some(region_ref) => {
-                        let layer_idx = resolved_layer
-                            .ok_or_else(|| engine_err!("galaxy3d::Material",
-                                "Texture slot '{}' (pass_type {}): region specified without a layer",
-                                slot_desc.name, pass_type))?;
-
-                        let layer = texture_arc.layer(layer_idx)
-                            .ok_or_else(|| engine_err!("galaxy3d::Material",
-                                "Texture slot '{}' (pass_type {}): layer {} not found during region resolution",
-                                slot_desc.name, pass_type, layer_idx))?;
-
-                        match region_ref {
-                            RegionRef::Index(i) => {
-                                if layer.region(i).is_none() {
-                                    engine_bail!("galaxy3d::Material",
-                                        "Texture slot '{}' (pass_type {}): region index {} does not exist in layer {}",
-                                        slot_desc.name, pass_type, i, layer_idx);
-                                }
-                                Some(i)
-                            }
-                            RegionRef::Name(ref name) => {
-                                let idx = layer.region_index_by_name(name)
-                                    .ok_or_else(|| engine_err!("galaxy3d::Material",
-                                        "Texture slot '{}' (pass_type {}): region '{}' not found in layer {}",
-                                        slot_desc.name, pass_type, name, layer_idx))?;
-                                Some(idx)
-                            }
-                        }
-                    }
-                };
-
-                // Read bindless index from the GPU texture
-                let gd_texture = texture_arc.graphics_device_texture();
-                let bindless_index = gd_texture.bindless_index();
-                let sampler_index = slot_desc.sampler_type as u32;
-
                 texture_names.insert(slot_desc.name.clone(), vec_index);
-                textures.push(MaterialTextureSlot {
-                    name: slot_desc.name,
-                    texture: slot_desc.texture,
-                    bindless_index,
-                    sampler_index,
-                    layer: resolved_layer,
-                    region: resolved_region,
-                    sampler_type: slot_desc.sampler_type,
-                });
+                textures.push(MaterialTextureSlot::from_desc(slot_desc, pass_type, resource_manager)?);
             }
 
             // ===== Build params for this pass =====
@@ -345,6 +501,13 @@ impl Material {
             }
 
             let render_state = pass_desc.render_state.unwrap_or_default();
+            let render_queue = pass_desc.render_queue.unwrap_or(
+                if pass_desc.color_blend.blend_enable {
+                    RenderQueueClass::Transparent
+                } else {
+                    RenderQueueClass::Opaque
+                }
+            );
 
             passes.push(MaterialPass {
                 pass_type,
@@ -352,6 +515,8 @@ impl Material {
                 color_blend: pass_desc.color_blend,
                 polygon_mode: pass_desc.polygon_mode,
                 render_state,
+                engine_features: pass_desc.engine_features,
+                render_queue,
                 // Assigned after `from_desc` by ResourceManager::create_material().
                 render_state_signature_id: 0,
                 textures,
@@ -365,6 +530,8 @@ impl Material {
             slot_id,
             passes,
             generation: 0,
+            // Never written to a material buffer yet
+            dirty: true,
         })
     }
 
@@ -380,6 +547,62 @@ impl Material {
         self.generation
     }
 
+    // ===== RUNTIME UPDATES =====
+
+    /// Whether params or texture slots changed since the last
+    /// `ResourceManager::sync_dirty_materials()` (true for new materials)
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Mark the material as written to the material buffer
+    pub(crate) fn clear_dirty(&mut self) {
+        self.dirty = false;
+    }
+
+    /// Change the value of a param and mark the material dirty.
+    ///
+    /// The param must exist (in any pass) and keep its type, as the material
+    /// buffer layout is fixed.
+    pub fn set_param(&mut self, name: &str, value: ParamValue) -> Result<()> {
+        let param = self.passes.iter_mut()
+            .find_map(|pass| {
+                let index = *pass.param_names.get(name)?;
+                pass.params.get_mut(index)
+            })
+            .ok_or_else(|| engine_err!("galaxy3d::Material", "Unknown param '{}'", name))?;
+
+        if std::mem::discriminant(&param.value) != std::mem::discriminant(&value) {
+            engine_bail!("galaxy3d::Material",
+                "Param '{}' type mismatch (current: {:?}, new: {:?})", name, param.value, value);
+        }
+        param.value = value;
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Replace a resolved texture slot (same name) and mark the material
+    /// dirty. Use `ResourceManager::set_material_texture_slot()`.
+    pub(crate) fn set_texture_slot(&mut self, slot: MaterialTextureSlot) -> Result<()> {
+        let current = self.passes.iter_mut()
+            .find_map(|pass| {
+                let index = *pass.texture_names.get(&slot.name)?;
+                pass.textures.get_mut(index)
+            })
+            .ok_or_else(|| engine_err!("galaxy3d::Material", "Unknown texture slot '{}'", slot.name))?;
+
+        *current = slot;
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Pass type of the pass owning a texture slot
+    pub(crate) fn texture_slot_pass_type(&self, name: &str) -> Option<u8> {
+        self.passes.iter()
+            .find(|pass| pass.texture_names.contains_key(name))
+            .map(|pass| pass.pass_type)
+    }
+
     // ===== PASS ACCESS =====
 
     /// Get the number of passes in this material
@@ -439,6 +662,176 @@ impl Material {
     pub fn total_texture_slot_count(&self) -> usize {
         self.passes.iter().map(|p| p.textures.len()).sum()
     }
+
+    // ===== INSPECTION =====
+
+    /// Build a structured snapshot of every pass, texture slot and parameter
+    /// with its current value.
+    ///
+    /// Use `to_string()` on the result for a pretty-printed dump.
+    pub fn describe(&self) -> MaterialReport {
+        let passes = self.passes.iter().map(|pass| MaterialPassReport {
+            pass_type: pass.pass_type,
+            fragment_shader: pass.fragment_shader,
+            polygon_mode: pass.polygon_mode,
+            color_blend: pass.color_blend,
+            render_state: pass.render_state,
+            render_state_signature_id: pass.render_state_signature_id,
+            engine_features: pass.engine_features,
+            render_queue: pass.render_queue,
+            textures: pass.textures.iter().map(|slot| TextureSlotReport {
+                name: slot.name.clone(),
+                texture: slot.texture,
+                bindless_index: slot.bindless_index,
+                sampler_type: slot.sampler_type,
+                sampler_index: slot.sampler_index,
+                layer: slot.layer,
+                region: slot.region,
+                flipbook: slot.flipbook.clone(),
+            }).collect(),
+            params: pass.params.iter()
+                .map(|p| (p.name.clone(), p.value.clone()))
+                .collect(),
+        }).collect();
+
+        MaterialReport {
+            slot_id: self.slot_id,
+            generation: self.generation,
+            passes,
+        }
+    }
+}
+
+// ===== TEXTURE SLOT RESOLUTION =====
+
+impl MaterialTextureSlot {
+    /// Resolve a texture slot descriptor (internal use by Material and ResourceManager)
+    ///
+    /// A missing texture falls back to the default texture of the slot,
+    /// without layer, region or flipbook. `pass_type` is only used in
+    /// error messages.
+    pub(crate) fn from_desc(
+        slot_desc: MaterialTextureSlotDesc,
+        pass_type: u8,
+        resource_manager: &ResourceManager,
+    ) -> Result<Self> {
+        // Resolve texture key (missing texture: fallback texture of the slot)
+        let (texture_key, texture_arc, layer_ref, region_ref, flipbook_desc) =
+            match resource_manager.texture(slot_desc.texture) {
+                Some(texture) => (slot_desc.texture, texture, slot_desc.layer, slot_desc.region, slot_desc.flipbook),
+                None => {
+                    let (key, texture) = resource_manager.default_resources()
+                        .map(|defaults| defaults.fallback_texture_for_slot(&slot_desc.name))
+                        .and_then(|key| resource_manager.texture(key).map(|texture| (key, texture)))
+                        .ok_or_else(|| engine_err!("galaxy3d::Material",
+                            "Texture slot '{}' (pass_type {}): texture key not found in ResourceManager",
+                            slot_desc.name, pass_type))?;
+                    engine_warn!("galaxy3d::Material",
+                        "Texture slot '{}' (pass_type {}): texture key not found, using fallback texture {:?}",
+                        slot_desc.name, pass_type, key);
+                    // Layer/region/flipbook refs target the missing texture
+                    (key, texture, None, None, None)
+                }
+            };
+
+        // Resolve layer reference
+        let resolved_layer = match layer_ref {
+            None => None,
+            Some(LayerRef::Index(i)) => {
+                if texture_arc.layer(i).is_none() {
+                    engine_bail!("galaxy3d::Material",
+                        "Texture slot '{}' (pass_type {}): layer index {} does not exist",
+                        slot_desc.name, pass_type, i);
+                }
+                Some(i)
+            }
+            Some(LayerRef::Name(ref name)) => {
+                let idx = texture_arc.layer_index_by_name(name)
+                    .ok_or_else(|| engine_err!("galaxy3d::Material",
+                        "Texture slot '{}' (pass_type {}): layer '{}' not found",
+                        slot_desc.name, pass_type, name))?;
+                Some(idx)
+            }
+        };
+
+        // Resolve a region reference (requires a resolved layer)
+        let resolve_region = |region_ref: RegionRef| -> Result<u32> {
+            let layer_idx = resolved_layer
+                .ok_or_else(|| engine_err!("galaxy3d::Material",
+                    "Texture slot '{}' (pass_type {}): region specified without a layer",
+                    slot_desc.name, pass_type))?;
+
+            let layer = texture_arc.layer(layer_idx)
+                .ok_or_else(|| engine_err!("galaxy3d::Material",
+                    "Texture slot '{}' (pass_type {}): layer {} not found during region resolution",
+                    slot_desc.name, pass_type, layer_idx))?;
+
+            match region_ref {
+                RegionRef::Index(i) => {
+                    if layer.region(i).is_none() {
+                        engine_bail!("galaxy3d::Material",
+                            "Texture slot '{}' (pass_type {}): region index {} does not exist in layer {}",
+                            slot_desc.name, pass_type, i, layer_idx);
+                    }
+                    Ok(i)
+                }
+                RegionRef::Name(ref name) => {
+                    layer.region_index_by_name(name)
+                        .ok_or_else(|| engine_err!("galaxy3d::Material",
+                            "Texture slot '{}' (pass_type {}): region '{}' not found in layer {}",
+                            slot_desc.name, pass_type, name, layer_idx))
+                }
+            }
+        };
+
+        let resolved_region = region_ref.map(resolve_region).transpose()?;
+
+        // Resolve flipbook frames (regions of the same layer)
+        let resolved_flipbook = match flipbook_desc {
+            None => None,
+            Some(flipbook_desc) => {
+                if resolved_region.is_some() {
+                    engine_bail!("galaxy3d::Material",
+                        "Texture slot '{}' (pass_type {}): region and flipbook are mutually exclusive",
+                        slot_desc.name, pass_type);
+                }
+                if flipbook_desc.frames.is_empty() {
+                    engine_bail!("galaxy3d::Material",
+                        "Texture slot '{}' (pass_type {}): flipbook has no frame",
+                        slot_desc.name, pass_type);
+                }
+                if !flipbook_desc.frames_per_second.is_finite() || flipbook_desc.frames_per_second <= 0.0 {
+                    engine_bail!("galaxy3d::Material",
+                        "Texture slot '{}' (pass_type {}): flipbook frame rate must be finite and positive, got {}",
+                        slot_desc.name, pass_type, flipbook_desc.frames_per_second);
+                }
+                let frames = flipbook_desc.frames.into_iter()
+                    .map(resolve_region)
+                    .collect::<Result<Vec<u32>>>()?;
+                Some(Flipbook {
+                    frames,
+                    frames_per_second: flipbook_desc.frames_per_second,
+                    looping: flipbook_desc.looping,
+                })
+            }
+        };
+
+        // Read bindless index from the GPU texture
+        let gd_texture = texture_arc.graphics_device_texture();
+        let bindless_index = gd_texture.bindless_index();
+        let sampler_index = slot_desc.sampler_type as u32;
+
+        Ok(MaterialTextureSlot {
+            name: slot_desc.name,
+            texture: texture_key,
+            bindless_index,
+            sampler_index,
+            layer: resolved_layer,
+            region: resolved_region,
+            flipbook: resolved_flipbook,
+            sampler_type: slot_desc.sampler_type,
+        })
+    }
 }
 
 // ===== MATERIAL PASS ACCESSORS =====
@@ -469,6 +862,16 @@ impl MaterialPass {
         &self.render_state
     }
 
+    /// Get the engine features this pass reacts to
+    pub fn engine_features(&self) -> EngineFeatures {
+        self.engine_features
+    }
+
+    /// Get the render queue this pass is drawn in
+    pub fn render_queue(&self) -> RenderQueueClass {
+        self.render_queue
+    }
+
     /// Get the stable u16 id identifying this pass's render state.
     ///
     /// Two passes with identical `DynamicRenderState` values share the same id.
@@ -659,6 +1062,20 @@ impl MaterialTextureSlot {
         self.region
     }
 
+    /// Get the resolved flipbook (None = not animated)
+    pub fn flipbook(&self) -> Option<&Flipbook> {
+        self.flipbook.as_ref()
+    }
+
+    /// Region shown at `time`: the flipbook frame when animated, the
+    /// fixed region otherwise
+    pub fn region_at(&self, time: f32) -> Option<u32> {
+        match &self.flipbook {
+            Some(flipbook) => Some(flipbook.region_at(time)),
+            None => self.region,
+        }
+    }
+
     /// Get the sampler type for this texture slot
     pub fn sampler_type(&self) -> SamplerType {
         self.sampler_type
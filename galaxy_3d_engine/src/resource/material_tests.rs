@@ -28,7 +28,7 @@ fn create_simple_texture(rm: &mut ResourceManager, gd: &Arc<Mutex<dyn graphics_d
     rm.create_texture(name.to_string(), TextureDesc {
         graphics_device: gd.clone(),
         texture: graphics_device::TextureDesc {
-            width: 256, height: 256,
+            width: 256, height: 256, depth: 1,
             format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
             usage: graphics_device::TextureUsage::Sampled,
             texture_type: graphics_device::TextureType::Tex2D,
@@ -36,6 +36,7 @@ fn create_simple_texture(rm: &mut ResourceManager, gd: &Arc<Mutex<dyn graphics_d
             array_layers: 1,
             data: Some(graphics_device::TextureData::Single(vec![255u8; 256 * 256 * 4])),
             mipmap: graphics_device::MipmapMode::None,
+            debug_name: None,
         },
         layers: vec![LayerDesc { name: "default".to_string(), layer_index: 0, data: None, regions: vec![] }],
     }).unwrap()
@@ -45,12 +46,13 @@ fn create_indexed_texture_with_regions(rm: &mut ResourceManager, gd: &Arc<Mutex<
     rm.create_texture(name.to_string(), TextureDesc {
         graphics_device: gd.clone(),
         texture: graphics_device::TextureDesc {
-            width: 256, height: 256,
+            width: 256, height: 256, depth: 1,
             format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
             usage: graphics_device::TextureUsage::Sampled,
             texture_type: graphics_device::TextureType::Array2D,
             sample_count: graphics_device::SampleCount::S1,
             array_layers: 4, data: None, mipmap: graphics_device::MipmapMode::None,
+            debug_name: None,
         },
         layers: vec![
             LayerDesc { name: "diffuse".to_string(), layer_index: 0, data: None, regions: vec![
@@ -82,7 +84,7 @@ fn create_test_pipeline(rm: &mut ResourceManager, gd: &Arc<Mutex<dyn graphics_de
         vertex_shader: vk, fragment_shader: fk,
         vertex_layout, topology: graphics_device::PrimitiveTopology::TriangleList,
         rasterization: Default::default(), color_blend: Default::default(),
-        multisample: Default::default(), color_formats: vec![], depth_format: None,
+        multisample: Default::default(), color_formats: vec![], depth_format: None, engine_features: Default::default(),
     }, &mut *gd.lock().unwrap()).unwrap();
     (pk, fk)
 }
@@ -103,6 +105,8 @@ fn single_pass_desc(
             textures,
             params,
             render_state: None,
+            engine_features: Default::default(),
+            render_queue: None,
         }],
     }
 }
@@ -111,6 +115,31 @@ fn single_pass_desc(
 // Tests: Basic Material Creation
 // ============================================================================
 
+#[test]
+fn test_render_queue_derived_from_blend() {
+    let (mut rm, gd) = create_test_context();
+    let (_pk, fk) = create_test_pipeline(&mut rm, &gd, "p");
+    let mat = Material::from_desc(0, single_pass_desc(fk, vec![], vec![]), &rm, &*gd.lock().unwrap()).unwrap();
+    assert_eq!(mat.pass(0).unwrap().render_queue(), RenderQueueClass::Opaque);
+
+    let mut desc = single_pass_desc(fk, vec![], vec![]);
+    desc.passes[0].color_blend = crate::graphics_device::BlendPreset::AlphaBlend.color_blend();
+    let mat = Material::from_desc(1, desc, &rm, &*gd.lock().unwrap()).unwrap();
+    assert_eq!(mat.pass(0).unwrap().render_queue(), RenderQueueClass::Transparent);
+}
+
+#[test]
+fn test_render_queue_explicit_override() {
+    let (mut rm, gd) = create_test_context();
+    let (_pk, fk) = create_test_pipeline(&mut rm, &gd, "p");
+    let mut desc = single_pass_desc(fk, vec![], vec![]);
+    desc.passes[0].render_queue = Some(RenderQueueClass::AlphaTest);
+    let mat = Material::from_desc(0, desc, &rm, &*gd.lock().unwrap()).unwrap();
+    assert_eq!(mat.pass(0).unwrap().render_queue(), RenderQueueClass::AlphaTest);
+    assert_eq!(mat.describe().passes[0].render_queue, RenderQueueClass::AlphaTest);
+    assert!(mat.describe().to_string().contains("render queue: AlphaTest"));
+}
+
 #[test]
 fn test_create_material_minimal() {
     let (mut rm, gd) = create_test_context();
@@ -130,7 +159,7 @@ fn test_create_material_with_simple_texture() {
     let (_pk, fk) = create_test_pipeline(&mut rm, &gd, "p");
     let tk = create_simple_texture(&mut rm, &gd, "tex");
     let mat = Material::from_desc(0, single_pass_desc(fk, vec![MaterialTextureSlotDesc {
-        name: "albedo".to_string(), texture: tk, layer: None, region: None, sampler_type: SamplerType::LinearRepeat,
+        name: "albedo".to_string(), texture: tk, layer: None, region: None, sampler_type: SamplerType::LinearRepeat, flipbook: None,
     }], vec![]), &rm, &*gd.lock().unwrap()).unwrap();
     let pass = mat.pass(0).unwrap();
     assert_eq!(pass.texture_slot_count(), 1);
@@ -192,7 +221,7 @@ fn test_layer_ref_by_index() {
     let (_pk, fk) = create_test_pipeline(&mut rm, &gd, "p");
     let tk = create_indexed_texture_with_regions(&mut rm, &gd, "itex");
     let mat = Material::from_desc(0, single_pass_desc(fk, vec![MaterialTextureSlotDesc {
-        name: "d".to_string(), texture: tk, layer: Some(LayerRef::Index(1)), region: None, sampler_type: SamplerType::LinearRepeat,
+        name: "d".to_string(), texture: tk, layer: Some(LayerRef::Index(1)), region: None, sampler_type: SamplerType::LinearRepeat, flipbook: None,
     }], vec![]), &rm, &*gd.lock().unwrap()).unwrap();
     assert_eq!(mat.pass(0).unwrap().texture_slot_by_name("d").unwrap().layer(), Some(1));
 }
@@ -203,7 +232,7 @@ fn test_layer_ref_by_name() {
     let (_pk, fk) = create_test_pipeline(&mut rm, &gd, "p");
     let tk = create_indexed_texture_with_regions(&mut rm, &gd, "itex");
     let mat = Material::from_desc(0, single_pass_desc(fk, vec![MaterialTextureSlotDesc {
-        name: "n".to_string(), texture: tk, layer: Some(LayerRef::Name("normal".to_string())), region: None, sampler_type: SamplerType::LinearRepeat,
+        name: "n".to_string(), texture: tk, layer: Some(LayerRef::Name("normal".to_string())), region: None, sampler_type: SamplerType::LinearRepeat, flipbook: None,
     }], vec![]), &rm, &*gd.lock().unwrap()).unwrap();
     assert_eq!(mat.pass(0).unwrap().texture_slot_by_name("n").unwrap().layer(), Some(1));
 }
@@ -214,7 +243,7 @@ fn test_layer_ref_invalid_index() {
     let (_pk, fk) = create_test_pipeline(&mut rm, &gd, "p");
     let tk = create_indexed_texture_with_regions(&mut rm, &gd, "itex");
     assert!(Material::from_desc(0, single_pass_desc(fk, vec![MaterialTextureSlotDesc {
-        name: "x".to_string(), texture: tk, layer: Some(LayerRef::Index(99)), region: None, sampler_type: SamplerType::LinearRepeat,
+        name: "x".to_string(), texture: tk, layer: Some(LayerRef::Index(99)), region: None, sampler_type: SamplerType::LinearRepeat, flipbook: None,
     }], vec![]), &rm, &*gd.lock().unwrap()).is_err());
 }
 
@@ -224,7 +253,7 @@ fn test_layer_ref_invalid_name() {
     let (_pk, fk) = create_test_pipeline(&mut rm, &gd, "p");
     let tk = create_indexed_texture_with_regions(&mut rm, &gd, "itex");
     assert!(Material::from_desc(0, single_pass_desc(fk, vec![MaterialTextureSlotDesc {
-        name: "x".to_string(), texture: tk, layer: Some(LayerRef::Name("nonexistent".to_string())), region: None, sampler_type: SamplerType::LinearRepeat,
+        name: "x".to_string(), texture: tk, layer: Some(LayerRef::Name("nonexistent".to_string())), region: None, sampler_type: SamplerType::LinearRepeat, flipbook: None,
     }], vec![]), &rm, &*gd.lock().unwrap()).is_err());
 }
 
@@ -239,7 +268,7 @@ fn test_region_ref_by_index() {
     let tk = create_indexed_texture_with_regions(&mut rm, &gd, "itex");
     let mat = Material::from_desc(0, single_pass_desc(fk, vec![MaterialTextureSlotDesc {
         name: "t".to_string(), texture: tk, layer: Some(LayerRef::Name("diffuse".to_string())),
-        region: Some(RegionRef::Index(0)), sampler_type: SamplerType::LinearRepeat,
+        region: Some(RegionRef::Index(0)), sampler_type: SamplerType::LinearRepeat, flipbook: None,
     }], vec![]), &rm, &*gd.lock().unwrap()).unwrap();
     let slot = mat.pass(0).unwrap().texture_slot_by_name("t").unwrap();
     assert_eq!(slot.layer(), Some(0));
@@ -253,7 +282,7 @@ fn test_region_ref_by_name() {
     let tk = create_indexed_texture_with_regions(&mut rm, &gd, "itex");
     let mat = Material::from_desc(0, single_pass_desc(fk, vec![MaterialTextureSlotDesc {
         name: "t".to_string(), texture: tk, layer: Some(LayerRef::Name("diffuse".to_string())),
-        region: Some(RegionRef::Name("stone".to_string())), sampler_type: SamplerType::LinearRepeat,
+        region: Some(RegionRef::Name("stone".to_string())), sampler_type: SamplerType::LinearRepeat, flipbook: None,
     }], vec![]), &rm, &*gd.lock().unwrap()).unwrap();
     let slot = mat.pass(0).unwrap().texture_slot_by_name("t").unwrap();
     assert_eq!(slot.layer(), Some(0));
@@ -267,7 +296,7 @@ fn test_region_ref_invalid_index() {
     let tk = create_indexed_texture_with_regions(&mut rm, &gd, "itex");
     assert!(Material::from_desc(0, single_pass_desc(fk, vec![MaterialTextureSlotDesc {
         name: "x".to_string(), texture: tk, layer: Some(LayerRef::Index(0)),
-        region: Some(RegionRef::Index(99)), sampler_type: SamplerType::LinearRepeat,
+        region: Some(RegionRef::Index(99)), sampler_type: SamplerType::LinearRepeat, flipbook: None,
     }], vec![]), &rm, &*gd.lock().unwrap()).is_err());
 }
 
@@ -278,7 +307,7 @@ fn test_region_ref_invalid_name() {
     let tk = create_indexed_texture_with_regions(&mut rm, &gd, "itex");
     assert!(Material::from_desc(0, single_pass_desc(fk, vec![MaterialTextureSlotDesc {
         name: "x".to_string(), texture: tk, layer: Some(LayerRef::Index(0)),
-        region: Some(RegionRef::Name("nonexistent".to_string())), sampler_type: SamplerType::LinearRepeat,
+        region: Some(RegionRef::Name("nonexistent".to_string())), sampler_type: SamplerType::LinearRepeat, flipbook: None,
     }], vec![]), &rm, &*gd.lock().unwrap()).is_err());
 }
 
@@ -289,10 +318,103 @@ fn test_region_without_layer_fails() {
     let tk = create_indexed_texture_with_regions(&mut rm, &gd, "itex");
     assert!(Material::from_desc(0, single_pass_desc(fk, vec![MaterialTextureSlotDesc {
         name: "x".to_string(), texture: tk, layer: None,
-        region: Some(RegionRef::Index(0)), sampler_type: SamplerType::LinearRepeat,
+        region: Some(RegionRef::Index(0)), sampler_type: SamplerType::LinearRepeat, flipbook: None,
     }], vec![]), &rm, &*gd.lock().unwrap()).is_err());
 }
 
+// ============================================================================
+// Tests: Flipbook / UV animation
+// ============================================================================
+
+fn flipbook_slot(tk: TextureKey, layer: Option<LayerRef>, region: Option<RegionRef>, flipbook: FlipbookDesc) -> MaterialTextureSlotDesc {
+    MaterialTextureSlotDesc {
+        name: "t".to_string(), texture: tk, layer, region,
+        sampler_type: SamplerType::LinearRepeat, flipbook: Some(flipbook),
+    }
+}
+
+fn grass_stone_flipbook(frames_per_second: f32, looping: bool) -> FlipbookDesc {
+    FlipbookDesc {
+        frames: vec![RegionRef::Name("grass".to_string()), RegionRef::Index(1), RegionRef::Index(0)],
+        frames_per_second,
+        looping,
+    }
+}
+
+#[test]
+fn test_flipbook_resolves_frames() {
+    let (mut rm, gd) = create_test_context();
+    let (_pk, fk) = create_test_pipeline(&mut rm, &gd, "p");
+    let tk = create_indexed_texture_with_regions(&mut rm, &gd, "itex");
+    let mat = Material::from_desc(0, single_pass_desc(fk, vec![
+        flipbook_slot(tk, Some(LayerRef::Index(0)), None, grass_stone_flipbook(10.0, true)),
+    ], vec![]), &rm, &*gd.lock().unwrap()).unwrap();
+    let slot = mat.pass(0).unwrap().texture_slot_by_name("t").unwrap();
+    let flipbook = slot.flipbook().unwrap();
+    assert_eq!(flipbook.frames(), &[0, 1, 0]);
+    assert_eq!(flipbook.frames_per_second(), 10.0);
+    assert!(flipbook.looping());
+    assert_eq!(slot.region(), None);
+    assert_eq!(slot.region_at(0.15), Some(1));
+    assert_eq!(mat.describe().passes[0].textures[0].flipbook.as_ref(), Some(flipbook));
+}
+
+#[test]
+fn test_flipbook_validation_errors() {
+    let (mut rm, gd) = create_test_context();
+    let (_pk, fk) = create_test_pipeline(&mut rm, &gd, "p");
+    let tk = create_indexed_texture_with_regions(&mut rm, &gd, "itex");
+    let layer = || Some(LayerRef::Index(0));
+    let invalid = [
+        // No layer
+        flipbook_slot(tk, None, None, grass_stone_flipbook(10.0, true)),
+        // Exclusive with a fixed region
+        flipbook_slot(tk, layer(), Some(RegionRef::Index(0)), grass_stone_flipbook(10.0, true)),
+        // No frame
+        flipbook_slot(tk, layer(), None, FlipbookDesc { frames: vec![], frames_per_second: 10.0, looping: true }),
+        // Invalid frame rates
+        flipbook_slot(tk, layer(), None, grass_stone_flipbook(0.0, true)),
+        flipbook_slot(tk, layer(), None, grass_stone_flipbook(f32::NAN, true)),
+        // Unknown region
+        flipbook_slot(tk, layer(), None, FlipbookDesc {
+            frames: vec![RegionRef::Index(99)], frames_per_second: 10.0, looping: true,
+        }),
+    ];
+    for slot in invalid {
+        assert!(Material::from_desc(0, single_pass_desc(fk, vec![slot], vec![]), &rm, &*gd.lock().unwrap()).is_err());
+    }
+}
+
+#[test]
+fn test_flipbook_frame_at_looping_and_clamped() {
+    let looping = Flipbook { frames: vec![4, 5, 6], frames_per_second: 2.0, looping: true };
+    assert_eq!(looping.duration(), 1.5);
+    assert_eq!(looping.frame_at(-1.0), 0);
+    assert_eq!(looping.frame_at(f32::NAN), 0);
+    assert_eq!(looping.frame_at(0.4), 0);
+    assert_eq!(looping.frame_at(0.6), 1);
+    assert_eq!(looping.frame_at(1.2), 2);
+    assert_eq!(looping.frame_at(1.6), 0);
+    assert_eq!(looping.region_at(2.1), 5);
+
+    let once = Flipbook { looping: false, ..looping };
+    assert_eq!(once.frame_at(1.2), 2);
+    assert_eq!(once.frame_at(100.0), 2);
+}
+
+#[test]
+fn test_animated_uv_scroll_and_rotation() {
+    let close = |a: [f32; 2], b: [f32; 2]| (a[0] - b[0]).abs() < 1e-5 && (a[1] - b[1]).abs() < 1e-5;
+    // No animation at time 0
+    assert!(close(animated_uv([0.2, 0.7], [1.0, 2.0], 3.0, 0.0), [0.2, 0.7]));
+    // Scroll only
+    assert!(close(animated_uv([0.2, 0.7], [0.5, -0.25], 0.0, 2.0), [1.2, 0.2]));
+    // Quarter turn around the center
+    let quarter = std::f32::consts::FRAC_PI_2;
+    assert!(close(animated_uv([1.0, 0.5], [0.0, 0.0], quarter, 1.0), [0.5, 1.0]));
+    assert!(close(animated_uv([0.5, 0.5], [0.0, 0.0], quarter, 1.0), [0.5, 0.5]));
+}
+
 // ============================================================================
 // Tests: Validation Errors
 // ============================================================================
@@ -303,8 +425,8 @@ fn test_duplicate_texture_slot_name_within_pass_fails() {
     let (_pk, fk) = create_test_pipeline(&mut rm, &gd, "p");
     let tk = create_simple_texture(&mut rm, &gd, "tex");
     assert!(Material::from_desc(0, single_pass_desc(fk, vec![
-        MaterialTextureSlotDesc { name: "albedo".to_string(), texture: tk, layer: None, region: None, sampler_type: SamplerType::LinearRepeat },
-        MaterialTextureSlotDesc { name: "albedo".to_string(), texture: tk, layer: None, region: None, sampler_type: SamplerType::LinearRepeat },
+        MaterialTextureSlotDesc { name: "albedo".to_string(), texture: tk, layer: None, region: None, sampler_type: SamplerType::LinearRepeat, flipbook: None },
+        MaterialTextureSlotDesc { name: "albedo".to_string(), texture: tk, layer: None, region: None, sampler_type: SamplerType::LinearRepeat, flipbook: None },
     ], vec![]), &rm, &*gd.lock().unwrap()).is_err());
 }
 
@@ -332,8 +454,8 @@ fn test_duplicate_pass_type_fails() {
     let (_pk, fk) = create_test_pipeline(&mut rm, &gd, "p");
     let desc = MaterialDesc {
         passes: vec![
-            MaterialPassDesc { pass_type: 0, fragment_shader: fk, color_blend: Default::default(), polygon_mode: PolygonMode::Fill, textures: vec![], params: vec![], render_state: None },
-            MaterialPassDesc { pass_type: 0, fragment_shader: fk, color_blend: Default::default(), polygon_mode: PolygonMode::Fill, textures: vec![], params: vec![], render_state: None },
+            MaterialPassDesc { pass_type: 0, fragment_shader: fk, color_blend: Default::default(), polygon_mode: PolygonMode::Fill, textures: vec![], params: vec![], render_state: None, engine_features: Default::default(), render_queue: None },
+            MaterialPassDesc { pass_type: 0, fragment_shader: fk, color_blend: Default::default(), polygon_mode: PolygonMode::Fill, textures: vec![], params: vec![], render_state: None, engine_features: Default::default(), render_queue: None },
         ],
     };
     assert!(Material::from_desc(0, desc, &rm, &*gd.lock().unwrap()).is_err());
@@ -347,11 +469,11 @@ fn test_duplicate_texture_name_across_passes_fails() {
     let desc = MaterialDesc {
         passes: vec![
             MaterialPassDesc { pass_type: 0, fragment_shader: fk, color_blend: Default::default(), polygon_mode: PolygonMode::Fill, textures: vec![
-                MaterialTextureSlotDesc { name: "albedo".to_string(), texture: tk, layer: None, region: None, sampler_type: SamplerType::LinearRepeat },
-            ], params: vec![], render_state: None },
+                MaterialTextureSlotDesc { name: "albedo".to_string(), texture: tk, layer: None, region: None, sampler_type: SamplerType::LinearRepeat, flipbook: None },
+            ], params: vec![], render_state: None, engine_features: Default::default(), render_queue: None },
             MaterialPassDesc { pass_type: 1, fragment_shader: fk, color_blend: Default::default(), polygon_mode: PolygonMode::Fill, textures: vec![
-                MaterialTextureSlotDesc { name: "albedo".to_string(), texture: tk, layer: None, region: None, sampler_type: SamplerType::LinearRepeat },
-            ], params: vec![], render_state: None },
+                MaterialTextureSlotDesc { name: "albedo".to_string(), texture: tk, layer: None, region: None, sampler_type: SamplerType::LinearRepeat, flipbook: None },
+            ], params: vec![], render_state: None, engine_features: Default::default(), render_queue: None },
         ],
     };
     assert!(Material::from_desc(0, desc, &rm, &*gd.lock().unwrap()).is_err());
@@ -365,10 +487,10 @@ fn test_duplicate_param_name_across_passes_fails() {
         passes: vec![
             MaterialPassDesc { pass_type: 0, fragment_shader: fk, color_blend: Default::default(), polygon_mode: PolygonMode::Fill, textures: vec![], params: vec![
                 ("roughness".to_string(), ParamValue::Float(0.5)),
-            ], render_state: None },
+            ], render_state: None, engine_features: Default::default(), render_queue: None },
             MaterialPassDesc { pass_type: 1, fragment_shader: fk, color_blend: Default::default(), polygon_mode: PolygonMode::Fill, textures: vec![], params: vec![
                 ("roughness".to_string(), ParamValue::Float(0.8)),
-            ], render_state: None },
+            ], render_state: None, engine_features: Default::default(), render_queue: None },
         ],
     };
     assert!(Material::from_desc(0, desc, &rm, &*gd.lock().unwrap()).is_err());
@@ -389,18 +511,22 @@ fn test_multi_pass_material() {
             MaterialPassDesc {
                 pass_type: 0, fragment_shader: fk, color_blend: Default::default(), polygon_mode: PolygonMode::Fill,
                 textures: vec![
-                    MaterialTextureSlotDesc { name: "albedo".to_string(), texture: tk1, layer: None, region: None, sampler_type: SamplerType::LinearRepeat },
+                    MaterialTextureSlotDesc { name: "albedo".to_string(), texture: tk1, layer: None, region: None, sampler_type: SamplerType::LinearRepeat, flipbook: None },
                 ],
                 params: vec![("roughness".to_string(), ParamValue::Float(0.5))],
                 render_state: None,
+                engine_features: Default::default(),
+                render_queue: None,
             },
             MaterialPassDesc {
                 pass_type: 42, fragment_shader: fk, color_blend: Default::default(), polygon_mode: PolygonMode::Fill,
                 textures: vec![
-                    MaterialTextureSlotDesc { name: "shadow_alpha".to_string(), texture: tk2, layer: None, region: None, sampler_type: SamplerType::NearestClamp },
+                    MaterialTextureSlotDesc { name: "shadow_alpha".to_string(), texture: tk2, layer: None, region: None, sampler_type: SamplerType::NearestClamp, flipbook: None },
                 ],
                 params: vec![("alpha_cutoff".to_string(), ParamValue::Float(0.5))],
                 render_state: None,
+                engine_features: Default::default(),
+                render_queue: None,
             },
         ],
     };
@@ -443,8 +569,8 @@ fn test_multiple_texture_slots() {
     let tk1 = create_simple_texture(&mut rm, &gd, "tex1");
     let tk2 = create_simple_texture(&mut rm, &gd, "tex2");
     let mat = Material::from_desc(0, single_pass_desc(fk, vec![
-        MaterialTextureSlotDesc { name: "albedo".to_string(), texture: tk1, layer: None, region: None, sampler_type: SamplerType::LinearRepeat },
-        MaterialTextureSlotDesc { name: "normal".to_string(), texture: tk2, layer: None, region: None, sampler_type: SamplerType::LinearRepeat },
+        MaterialTextureSlotDesc { name: "albedo".to_string(), texture: tk1, layer: None, region: None, sampler_type: SamplerType::LinearRepeat, flipbook: None },
+        MaterialTextureSlotDesc { name: "normal".to_string(), texture: tk2, layer: None, region: None, sampler_type: SamplerType::LinearRepeat, flipbook: None },
     ], vec![]), &rm, &*gd.lock().unwrap()).unwrap();
     let pass = mat.pass(0).unwrap();
     assert_eq!(pass.texture_slot_count(), 2);
@@ -466,11 +592,11 @@ fn test_full_pbr_material() {
     let indexed_k = create_indexed_texture_with_regions(&mut rm, &gd, "indexed_tex");
 
     let mat = Material::from_desc(0, single_pass_desc(fk, vec![
-        MaterialTextureSlotDesc { name: "albedo".to_string(), texture: albedo_k, layer: None, region: None, sampler_type: SamplerType::LinearRepeat },
-        MaterialTextureSlotDesc { name: "normal".to_string(), texture: normal_k, layer: None, region: None, sampler_type: SamplerType::LinearRepeat },
+        MaterialTextureSlotDesc { name: "albedo".to_string(), texture: albedo_k, layer: None, region: None, sampler_type: SamplerType::LinearRepeat, flipbook: None },
+        MaterialTextureSlotDesc { name: "normal".to_string(), texture: normal_k, layer: None, region: None, sampler_type: SamplerType::LinearRepeat, flipbook: None },
         MaterialTextureSlotDesc { name: "detail".to_string(), texture: indexed_k,
             layer: Some(LayerRef::Name("diffuse".to_string())), region: Some(RegionRef::Name("grass".to_string())),
-            sampler_type: SamplerType::LinearRepeat },
+            sampler_type: SamplerType::LinearRepeat, flipbook: None },
     ], vec![
         ("roughness".to_string(), ParamValue::Float(0.5)),
         ("metallic".to_string(), ParamValue::Float(0.0)),
@@ -546,8 +672,8 @@ fn test_texture_slots_slice() {
     let (_pk, fk) = create_test_pipeline(&mut rm, &gd, "p");
     let tk = create_simple_texture(&mut rm, &gd, "tex");
     let mat = Material::from_desc(0, single_pass_desc(fk, vec![
-        MaterialTextureSlotDesc { name: "a".to_string(), texture: tk, layer: None, region: None, sampler_type: SamplerType::LinearRepeat },
-        MaterialTextureSlotDesc { name: "b".to_string(), texture: tk, layer: None, region: None, sampler_type: SamplerType::LinearRepeat },
+        MaterialTextureSlotDesc { name: "a".to_string(), texture: tk, layer: None, region: None, sampler_type: SamplerType::LinearRepeat, flipbook: None },
+        MaterialTextureSlotDesc { name: "b".to_string(), texture: tk, layer: None, region: None, sampler_type: SamplerType::LinearRepeat, flipbook: None },
     ], vec![]), &rm, &*gd.lock().unwrap()).unwrap();
     let pass = mat.pass(0).unwrap();
     assert_eq!(pass.texture_slots().len(), 2);
@@ -593,7 +719,7 @@ fn test_texture_slot_id() {
     let (_pk, fk) = create_test_pipeline(&mut rm, &gd, "p");
     let tk = create_simple_texture(&mut rm, &gd, "tex");
     let mat = Material::from_desc(0, single_pass_desc(fk, vec![MaterialTextureSlotDesc {
-        name: "diffuse".to_string(), texture: tk, layer: None, region: None, sampler_type: SamplerType::LinearRepeat,
+        name: "diffuse".to_string(), texture: tk, layer: None, region: None, sampler_type: SamplerType::LinearRepeat, flipbook: None,
     }], vec![]), &rm, &*gd.lock().unwrap()).unwrap();
     let pass = mat.pass(0).unwrap();
     assert_eq!(pass.texture_slot_id("diffuse"), Some(0));
@@ -612,3 +738,137 @@ fn test_slot_id() {
         &rm, &*gd.lock().unwrap()).unwrap();
     assert_eq!(mat.slot_id(), 42);
 }
+
+// ============================================================================
+// Tests: describe()
+// ============================================================================
+
+#[test]
+fn test_describe_reports_slots_and_params() {
+    let (mut rm, gd) = create_test_context();
+    let (_pk, fk) = create_test_pipeline(&mut rm, &gd, "p");
+    let tk = create_indexed_texture_with_regions(&mut rm, &gd, "atlas");
+    let mat = Material::from_desc(7, single_pass_desc(fk, vec![MaterialTextureSlotDesc {
+        name: "albedo".to_string(), texture: tk,
+        layer: Some(LayerRef::Name("diffuse".to_string())),
+        region: Some(RegionRef::Name("stone".to_string())),
+        sampler_type: SamplerType::NearestClamp,
+        flipbook: None,
+    }], vec![
+        ("roughness".to_string(), ParamValue::Float(0.25)),
+    ]), &rm, &*gd.lock().unwrap()).unwrap();
+
+    let report = mat.describe();
+    assert_eq!(report.slot_id, 7);
+    assert_eq!(report.passes.len(), 1);
+
+    let pass = &report.passes[0];
+    assert_eq!(pass.pass_type, 0);
+    assert_eq!(pass.fragment_shader, fk);
+    assert_eq!(pass.textures.len(), 1);
+    let tex = &pass.textures[0];
+    assert_eq!(tex.name, "albedo");
+    assert_eq!(tex.texture, tk);
+    assert_eq!(tex.layer, Some(0));
+    assert_eq!(tex.region, Some(1));
+    assert_eq!(tex.sampler_type, SamplerType::NearestClamp);
+    assert_eq!(tex.sampler_index, SamplerType::NearestClamp as u32);
+
+    assert_eq!(pass.params.len(), 1);
+    assert_eq!(pass.params[0].0, "roughness");
+    assert!(matches!(pass.params[0].1, ParamValue::Float(v) if v == 0.25));
+}
+
+#[test]
+fn test_describe_display_lists_everything() {
+    let (mut rm, gd) = create_test_context();
+    let (_pk, fk) = create_test_pipeline(&mut rm, &gd, "p");
+    let tk = create_simple_texture(&mut rm, &gd, "tex");
+    let mat = Material::from_desc(0, single_pass_desc(fk, vec![MaterialTextureSlotDesc {
+        name: "albedo".to_string(), texture: tk, layer: None, region: None, sampler_type: SamplerType::LinearRepeat, flipbook: None,
+    }], vec![
+        ("tint".to_string(), ParamValue::Vec3([1.0, 0.5, 0.0])),
+    ]), &rm, &*gd.lock().unwrap()).unwrap();
+
+    let text = mat.describe().to_string();
+    assert!(text.contains("Pass type 0"));
+    assert!(text.contains("albedo"));
+    assert!(text.contains("LinearRepeat"));
+    assert!(text.contains("tint = Vec3([1.0, 0.5, 0.0])"));
+}
+
+#[test]
+fn test_pass_engine_features_stored_and_reported() {
+    let (mut rm, gd) = create_test_context();
+    let (_pk, fk) = create_test_pipeline(&mut rm, &gd, "p");
+    let mut desc = single_pass_desc(fk, vec![], vec![]);
+    desc.passes[0].engine_features = EngineFeatures::from_names(&["shadows", "fog"]).unwrap();
+    let mat = Material::from_desc(0, desc, &rm, &*gd.lock().unwrap()).unwrap();
+
+    let expected = EngineFeatures::SHADOWS | EngineFeatures::FOG;
+    assert_eq!(mat.pass(0).unwrap().engine_features(), expected);
+    let report = mat.describe();
+    assert_eq!(report.passes[0].engine_features, expected);
+    assert!(report.to_string().contains("engine features: 0x3"));
+}
+
+// ============================================================================
+// Tests: runtime updates
+// ============================================================================
+
+#[test]
+fn test_set_param_updates_value_and_marks_dirty() {
+    let (mut rm, gd) = create_test_context();
+    let (_pk, fk) = create_test_pipeline(&mut rm, &gd, "p");
+    let mut mat = Material::from_desc(0, single_pass_desc(fk, vec![], vec![
+        ("roughness".to_string(), ParamValue::Float(0.8)),
+    ]), &rm, &*gd.lock().unwrap()).unwrap();
+    assert!(mat.is_dirty());
+    mat.clear_dirty();
+
+    mat.set_param("roughness", ParamValue::Float(0.2)).unwrap();
+    assert!(mat.is_dirty());
+    assert_eq!(mat.pass(0).unwrap().param_by_name("roughness").unwrap().as_float(), Some(0.2));
+    assert_eq!(mat.generation(), 0);
+}
+
+#[test]
+fn test_set_param_rejects_unknown_name_and_type_change() {
+    let (mut rm, gd) = create_test_context();
+    let (_pk, fk) = create_test_pipeline(&mut rm, &gd, "p");
+    let mut mat = Material::from_desc(0, single_pass_desc(fk, vec![], vec![
+        ("roughness".to_string(), ParamValue::Float(0.8)),
+    ]), &rm, &*gd.lock().unwrap()).unwrap();
+    mat.clear_dirty();
+
+    assert!(mat.set_param("metallic", ParamValue::Float(1.0)).is_err());
+    assert!(mat.set_param("roughness", ParamValue::UInt(1)).is_err());
+    assert!(!mat.is_dirty());
+    assert_eq!(mat.pass(0).unwrap().param_by_name("roughness").unwrap().as_float(), Some(0.8));
+}
+
+#[test]
+fn test_set_texture_slot_replaces_resolved_slot() {
+    let (mut rm, gd) = create_test_context();
+    let (_pk, fk) = create_test_pipeline(&mut rm, &gd, "p");
+    let simple = create_simple_texture(&mut rm, &gd, "simple");
+    let atlas = create_indexed_texture_with_regions(&mut rm, &gd, "atlas");
+    let mut mat = Material::from_desc(0, single_pass_desc(fk, vec![MaterialTextureSlotDesc {
+        name: "albedo".to_string(), texture: simple, layer: None, region: None, sampler_type: SamplerType::LinearRepeat, flipbook: None,
+    }], vec![]), &rm, &*gd.lock().unwrap()).unwrap();
+    mat.clear_dirty();
+
+    let slot = MaterialTextureSlot::from_desc(MaterialTextureSlotDesc {
+        name: "albedo".to_string(), texture: atlas, layer: Some(LayerRef::Name("normal".to_string())),
+        region: None, sampler_type: SamplerType::NearestClamp, flipbook: None,
+    }, 0, &rm).unwrap();
+    mat.set_texture_slot(slot).unwrap();
+
+    let slot = mat.pass(0).unwrap().texture_slot_by_name("albedo").unwrap();
+    assert_eq!(slot.texture(), atlas);
+    assert_eq!(slot.layer(), Some(1));
+    assert_eq!(slot.sampler_type(), SamplerType::NearestClamp);
+    assert!(mat.is_dirty());
+    assert_eq!(mat.texture_slot_pass_type("albedo"), Some(0));
+    assert_eq!(mat.texture_slot_pass_type("normal"), None);
+}
@@ -15,7 +15,7 @@
 
 use rustc_hash::FxHashMap;
 use crate::error::Result;
-use crate::{engine_bail, engine_err};
+use crate::{engine_bail, engine_err, engine_not_found, engine_warn};
 use crate::resource::resource_manager::{ResourceManager, GeometryKey, MaterialKey};
 
 // ===== REFERENCE TYPES =====
@@ -24,6 +24,8 @@ use crate::resource::resource_manager::{ResourceManager, GeometryKey, MaterialKe
 ///
 /// Used in descriptors to let the user choose the most convenient way
 /// to reference a GeometryMesh. Resolved to a usize id at creation time.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub enum GeometryMeshRef {
     Index(usize),
     Name(String),
@@ -33,6 +35,8 @@ pub enum GeometryMeshRef {
 ///
 /// Used in descriptors to let the user choose the most convenient way
 /// to reference a GeometrySubMesh. Resolved to a usize id at creation time.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub enum GeometrySubMeshRef {
     Index(usize),
     Name(String),
@@ -93,8 +97,7 @@ impl Mesh {
 
         // ========== RESOLVE GEOMETRY ==========
         let geometry_arc = resource_manager.geometry(geometry)
-            .ok_or_else(|| engine_err!("galaxy3d::Mesh",
-                "Geometry key not found in ResourceManager"))?;
+            .ok_or_else(|| engine_not_found!("galaxy3d::Mesh", "Geometry", format!("{:?}", geometry)))?;
 
         // ========== RESOLVE GEOMETRY MESH ==========
         let geometry_mesh_id = match &geometry_mesh {
@@ -107,8 +110,7 @@ impl Mesh {
             }
             GeometryMeshRef::Name(name) => {
                 geometry_arc.mesh_id(name)
-                    .ok_or_else(|| engine_err!("galaxy3d::Mesh",
-                        "GeometryMesh '{}' not found", name))?
+                    .ok_or_else(|| engine_not_found!("galaxy3d::Mesh", "GeometryMesh", name))?
             }
         };
 
@@ -131,8 +133,7 @@ impl Mesh {
                 }
                 GeometrySubMeshRef::Name(name) => {
                     geom_mesh.submesh_id(name)
-                        .ok_or_else(|| engine_err!("galaxy3d::Mesh",
-                            "GeometrySubMesh '{}' not found", name))?
+                        .ok_or_else(|| engine_not_found!("galaxy3d::Mesh", "GeometrySubMesh", name))?
                 }
             };
 
@@ -141,7 +142,7 @@ impl Mesh {
                     "Duplicate submesh assignment (resolved id {})", submesh_id);
             }
 
-            submesh_map.insert(submesh_id, submesh_desc.material);
+            submesh_map.insert(submesh_id, resolve_material(submesh_desc.material, resource_manager));
         }
 
         // Complete submesh coverage
@@ -193,6 +194,23 @@ impl Mesh {
     }
 }
 
+/// Material of a submesh: the error material replaces a missing material
+/// once the default resources exist.
+fn resolve_material(material: MaterialKey, resource_manager: &ResourceManager) -> MaterialKey {
+    if resource_manager.material(material).is_some() {
+        return material;
+    }
+    match resource_manager.default_resources() {
+        Some(defaults) => {
+            engine_warn!("galaxy3d::Mesh",
+                "Material {:?} not found, using the error material", material);
+            defaults.error_material
+        }
+        // Validated when the mesh is instantiated
+        None => material,
+    }
+}
+
 // ===== MESH SUBMESH ACCESSORS =====
 
 impl MeshSubMesh {
@@ -58,10 +58,11 @@ fn create_test_resources() -> (
     let desc = GeometryDesc {
         name: "characters".to_string(),
         graphics_device: graphics_device.clone(),
-        vertex_data: vec![0u8; 160],
-        index_data: Some(vec![0u8; 48]),
+        vertex_data: vec![0u8; 160].into(),
+        index_data: Some(vec![0u8; 48].into()),
         vertex_layout,
         index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![
             GeometryMeshDesc {
                 name: "body".to_string(),
@@ -132,7 +133,7 @@ fn create_test_pipeline(rm: &mut ResourceManager, gd: &Arc<Mutex<dyn graphics_de
         vertex_shader: vk, fragment_shader: fk,
         vertex_layout, topology: graphics_device::PrimitiveTopology::TriangleList,
         rasterization: Default::default(), color_blend: Default::default(),
-        multisample: Default::default(), color_formats: vec![], depth_format: None,
+        multisample: Default::default(), color_formats: vec![], depth_format: None, engine_features: Default::default(),
     };
     let pk = rm.create_pipeline(name.to_string(), desc, &mut *gd.lock().unwrap()).unwrap();
     (pk, fk)
@@ -145,6 +146,8 @@ fn create_test_material(rm: &mut ResourceManager, gd: &Arc<Mutex<dyn graphics_de
             fragment_shader, color_blend: Default::default(), polygon_mode: PolygonMode::Fill, textures: vec![],
             params: vec![("value".to_string(), ParamValue::Float(value))],
             render_state: None,
+            engine_features: Default::default(),
+            render_queue: None,
         }],
     }, &*gd.lock().unwrap()).unwrap()
 }
@@ -0,0 +1,249 @@
+/// Meshlets: small clusters of triangles for the mesh shader path.
+///
+/// `build_meshlets` splits a triangle list greedily, in index order, into
+/// meshlets of at most `max_vertices` vertices and `max_triangles`
+/// triangles. Each meshlet references its vertices through `vertices`
+/// (indices into the geometry vertex buffer, base vertex applied) and its
+/// triangles through `triangles` (3 local vertex indices per triangle, one
+/// byte each).
+///
+/// Each meshlet also carries a bounding sphere and a normal cone, so the
+/// task shader can drop meshlets outside the frustum or facing away from
+/// the camera (`Meshlet::is_backfacing`) before any vertex is processed.
+
+use std::sync::Arc;
+use glam::Vec3;
+use rustc_hash::FxHashMap;
+use crate::error::Result;
+use crate::engine_bail;
+use crate::graphics_device;
+
+/// Default maximum number of vertices per meshlet
+pub const DEFAULT_MESHLET_MAX_VERTICES: u32 = 64;
+/// Default maximum number of triangles per meshlet (keeps the local
+/// triangle data of a meshlet within 3 * 124 = 372 bytes)
+pub const DEFAULT_MESHLET_MAX_TRIANGLES: u32 = 124;
+/// Size of one meshlet in the meshlet storage buffer (std430)
+pub const MESHLET_GPU_SIZE: usize = 48;
+
+/// Meshlet size limits. They must not exceed the `max_vertices` /
+/// `max_primitives` declared by the mesh shader.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MeshletConfig {
+    /// Maximum vertices per meshlet, in 3..=256 (local indices are bytes)
+    pub max_vertices: u32,
+    /// Maximum triangles per meshlet, in 1..=256
+    pub max_triangles: u32,
+}
+
+impl Default for MeshletConfig {
+    fn default() -> Self {
+        Self {
+            max_vertices: DEFAULT_MESHLET_MAX_VERTICES,
+            max_triangles: DEFAULT_MESHLET_MAX_TRIANGLES,
+        }
+    }
+}
+
+impl MeshletConfig {
+    /// Check the limits.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `max_vertices` is outside 3..=256 or
+    /// `max_triangles` outside 1..=256.
+    pub fn validate(&self) -> Result<()> {
+        if !(3..=256).contains(&self.max_vertices) {
+            engine_bail!("galaxy3d::Meshlet", "max_vertices must be in 3..=256, got {}", self.max_vertices);
+        }
+        if !(1..=256).contains(&self.max_triangles) {
+            engine_bail!("galaxy3d::Meshlet", "max_triangles must be in 1..=256, got {}", self.max_triangles);
+        }
+        Ok(())
+    }
+}
+
+/// A cluster of triangles (see module docs).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Meshlet {
+    /// First entry of the meshlet in `MeshletData::vertices`
+    pub vertex_offset: u32,
+    pub vertex_count: u32,
+    /// First byte of the meshlet in `MeshletData::triangles`
+    pub triangle_offset: u32,
+    pub triangle_count: u32,
+    /// Bounding sphere, local space
+    pub center: Vec3,
+    pub radius: f32,
+    /// Average direction of the triangle normals
+    pub cone_axis: Vec3,
+    /// Sine of the largest angle between `cone_axis` and a triangle normal;
+    /// 1 when the normals spread too much for the meshlet to be culled
+    pub cone_cutoff: f32,
+}
+
+impl Meshlet {
+    /// Whether every triangle of the meshlet faces away from a camera at
+    /// `camera_position` (same space as the meshlet). CPU reference of the
+    /// task shader cone test.
+    pub fn is_backfacing(&self, camera_position: Vec3) -> bool {
+        let to_center = self.center - camera_position;
+        to_center.dot(self.cone_axis) >= self.cone_cutoff * to_center.length() + self.radius
+    }
+
+    /// Storage buffer layout (std430, `MESHLET_GPU_SIZE` bytes):
+    ///
+    /// ```glsl
+    /// struct Meshlet {
+    ///     vec3 center;   float radius;
+    ///     vec3 coneAxis; float coneCutoff;
+    ///     uint vertexOffset; uint vertexCount;
+    ///     uint triangleOffset; uint triangleCount;
+    /// };
+    /// ```
+    pub(crate) fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        let floats = [
+            self.center.x, self.center.y, self.center.z, self.radius,
+            self.cone_axis.x, self.cone_axis.y, self.cone_axis.z, self.cone_cutoff,
+        ];
+        bytes.extend_from_slice(bytemuck::cast_slice(&floats));
+        let counts = [self.vertex_offset, self.vertex_count, self.triangle_offset, self.triangle_count];
+        bytes.extend_from_slice(bytemuck::cast_slice(&counts));
+    }
+}
+
+/// Meshlets of a triangle list, with their vertex and triangle data.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MeshletData {
+    pub meshlets: Vec<Meshlet>,
+    /// Vertex buffer indices referenced by the meshlets
+    pub vertices: Vec<u32>,
+    /// Local vertex indices, 3 per triangle
+    pub triangles: Vec<u8>,
+}
+
+impl MeshletData {
+    /// Append `other`, shifting its offsets past the current data
+    pub fn append(&mut self, other: MeshletData) {
+        let vertex_base = self.vertices.len() as u32;
+        let triangle_base = self.triangles.len() as u32;
+        self.meshlets.extend(other.meshlets.into_iter().map(|meshlet| Meshlet {
+            vertex_offset: meshlet.vertex_offset + vertex_base,
+            triangle_offset: meshlet.triangle_offset + triangle_base,
+            ..meshlet
+        }));
+        self.vertices.extend(other.vertices);
+        self.triangles.extend(other.triangles);
+    }
+}
+
+/// GPU copies of the meshlet data of a `Geometry` (storage buffers)
+pub struct MeshletBuffers {
+    /// `Meshlet` array (`MESHLET_GPU_SIZE` bytes each)
+    pub meshlets: Arc<dyn graphics_device::Buffer>,
+    /// `uint` vertex buffer indices
+    pub vertices: Arc<dyn graphics_device::Buffer>,
+    /// Local vertex indices, 4 per `uint` (little end first), padded
+    pub triangles: Arc<dyn graphics_device::Buffer>,
+}
+
+/// Split `triangles` (counter-clockwise front faces, indices into
+/// `positions`) into meshlets.
+///
+/// Degenerate triangles (repeated index) are skipped.
+///
+/// # Errors
+///
+/// Returns an error if the config is invalid or an index is out of
+/// `positions`.
+pub fn build_meshlets(positions: &[Vec3], triangles: &[[u32; 3]], config: &MeshletConfig) -> Result<MeshletData> {
+    config.validate()?;
+    let mut data = MeshletData::default();
+    // Vertex buffer index -> local index in the open meshlet
+    let mut local: FxHashMap<u32, u8> = FxHashMap::default();
+    let mut open = Meshlet {
+        vertex_offset: 0,
+        vertex_count: 0,
+        triangle_offset: 0,
+        triangle_count: 0,
+        center: Vec3::ZERO,
+        radius: 0.0,
+        cone_axis: Vec3::ZERO,
+        cone_cutoff: 1.0,
+    };
+
+    for triangle in triangles {
+        if let Some(&index) = triangle.iter().find(|&&index| index as usize >= positions.len()) {
+            engine_bail!("galaxy3d::Meshlet",
+                "Index {} is out of the {} positions", index, positions.len());
+        }
+        let [a, b, c] = *triangle;
+        if a == b || b == c || a == c {
+            continue;
+        }
+
+        let new_vertices = triangle.iter().filter(|index| !local.contains_key(index)).count() as u32;
+        if open.vertex_count + new_vertices > config.max_vertices || open.triangle_count == config.max_triangles {
+            close_meshlet(&mut data, &mut open, positions);
+            local.clear();
+        }
+
+        for &index in triangle {
+            let local_index = *local.entry(index).or_insert_with(|| {
+                data.vertices.push(index);
+                open.vertex_count += 1;
+                (open.vertex_count - 1) as u8
+            });
+            data.triangles.push(local_index);
+        }
+        open.triangle_count += 1;
+    }
+    close_meshlet(&mut data, &mut open, positions);
+    Ok(data)
+}
+
+/// Compute the bounds of the open meshlet, push it and open the next one
+fn close_meshlet(data: &mut MeshletData, open: &mut Meshlet, positions: &[Vec3]) {
+    if open.triangle_count == 0 {
+        return;
+    }
+    let vertices = &data.vertices[open.vertex_offset as usize..][..open.vertex_count as usize];
+    let position = |local: u8| positions[vertices[local as usize] as usize];
+
+    // Sphere around the box of the vertices
+    let (min, max) = vertices.iter().fold((Vec3::splat(f32::MAX), Vec3::splat(f32::MIN)), |(min, max), &index| {
+        (min.min(positions[index as usize]), max.max(positions[index as usize]))
+    });
+    open.center = (min + max) * 0.5;
+    open.radius = vertices.iter()
+        .map(|&index| positions[index as usize].distance(open.center))
+        .fold(0.0, f32::max);
+
+    // Normal cone: average normal, then the widest deviation from it
+    let triangle_bytes = &data.triangles[open.triangle_offset as usize..][..open.triangle_count as usize * 3];
+    let normals: Vec<Vec3> = triangle_bytes.chunks_exact(3).map(|corners| {
+        let (a, b, c) = (position(corners[0]), position(corners[1]), position(corners[2]));
+        (b - a).cross(c - a).normalize_or_zero()
+    }).collect();
+    open.cone_axis = normals.iter().copied().sum::<Vec3>().normalize_or_zero();
+    let min_dot = normals.iter().map(|normal| normal.dot(open.cone_axis)).fold(1.0, f32::min);
+    // Past ~84 degrees the cone test almost never succeeds
+    open.cone_cutoff = if open.cone_axis == Vec3::ZERO || min_dot <= 0.1 {
+        1.0
+    } else {
+        (1.0 - min_dot * min_dot).sqrt()
+    };
+
+    data.meshlets.push(*open);
+    *open = Meshlet {
+        vertex_offset: data.vertices.len() as u32,
+        vertex_count: 0,
+        triangle_offset: data.triangles.len() as u32,
+        triangle_count: 0,
+        ..*open
+    };
+}
+
+#[cfg(test)]
+#[path = "meshlet_tests.rs"]
+mod tests;
@@ -0,0 +1,202 @@
+use super::*;
+
+/// Flat grid of `size` x `size` quads in the XY plane, facing +Z
+fn grid(size: u32) -> (Vec<Vec3>, Vec<[u32; 3]>) {
+    let row = size + 1;
+    let positions = (0..row * row)
+        .map(|i| Vec3::new((i % row) as f32, (i / row) as f32, 0.0))
+        .collect();
+    let mut triangles = Vec::new();
+    for y in 0..size {
+        for x in 0..size {
+            let corner = y * row + x;
+            triangles.push([corner, corner + 1, corner + row + 1]);
+            triangles.push([corner, corner + row + 1, corner + row]);
+        }
+    }
+    (positions, triangles)
+}
+
+/// Triangles of the meshlets, back in vertex buffer indices
+fn rebuild_triangles(data: &MeshletData) -> Vec<[u32; 3]> {
+    let mut triangles = Vec::new();
+    for meshlet in &data.meshlets {
+        let vertices = &data.vertices[meshlet.vertex_offset as usize..][..meshlet.vertex_count as usize];
+        let bytes = &data.triangles[meshlet.triangle_offset as usize..][..meshlet.triangle_count as usize * 3];
+        for corners in bytes.chunks_exact(3) {
+            triangles.push([
+                vertices[corners[0] as usize],
+                vertices[corners[1] as usize],
+                vertices[corners[2] as usize],
+            ]);
+        }
+    }
+    triangles
+}
+
+// ============================================================================
+// MeshletConfig
+// ============================================================================
+
+#[test]
+fn test_config_default() {
+    let config = MeshletConfig::default();
+    assert_eq!(config.max_vertices, DEFAULT_MESHLET_MAX_VERTICES);
+    assert_eq!(config.max_triangles, DEFAULT_MESHLET_MAX_TRIANGLES);
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn test_config_validate_limits() {
+    assert!(MeshletConfig { max_vertices: 2, max_triangles: 1 }.validate().is_err());
+    assert!(MeshletConfig { max_vertices: 257, max_triangles: 1 }.validate().is_err());
+    assert!(MeshletConfig { max_vertices: 3, max_triangles: 0 }.validate().is_err());
+    assert!(MeshletConfig { max_vertices: 3, max_triangles: 257 }.validate().is_err());
+    assert!(MeshletConfig { max_vertices: 256, max_triangles: 256 }.validate().is_ok());
+}
+
+// ============================================================================
+// build_meshlets
+// ============================================================================
+
+#[test]
+fn test_build_keeps_every_triangle_in_order() {
+    let (positions, triangles) = grid(8);
+    let data = build_meshlets(&positions, &triangles, &MeshletConfig::default()).unwrap();
+    assert_eq!(rebuild_triangles(&data), triangles);
+}
+
+#[test]
+fn test_build_respects_limits() {
+    let (positions, triangles) = grid(8);
+    let config = MeshletConfig { max_vertices: 16, max_triangles: 10 };
+    let data = build_meshlets(&positions, &triangles, &config).unwrap();
+
+    assert!(data.meshlets.len() >= triangles.len().div_ceil(10));
+    for meshlet in &data.meshlets {
+        assert!(meshlet.vertex_count <= 16);
+        assert!(meshlet.triangle_count >= 1 && meshlet.triangle_count <= 10);
+    }
+    assert_eq!(rebuild_triangles(&data), triangles);
+}
+
+#[test]
+fn test_build_shares_vertices_within_meshlet() {
+    // Two triangles of one quad share two vertices
+    let (positions, triangles) = grid(1);
+    let data = build_meshlets(&positions, &triangles, &MeshletConfig::default()).unwrap();
+    assert_eq!(data.meshlets.len(), 1);
+    assert_eq!(data.meshlets[0].vertex_count, 4);
+    assert_eq!(data.vertices.len(), 4);
+    assert_eq!(data.triangles.len(), 6);
+}
+
+#[test]
+fn test_build_skips_degenerate_triangles() {
+    let (positions, mut triangles) = grid(1);
+    triangles.push([0, 0, 1]);
+    let data = build_meshlets(&positions, &triangles, &MeshletConfig::default()).unwrap();
+    assert_eq!(rebuild_triangles(&data), triangles[..2]);
+}
+
+#[test]
+fn test_build_empty() {
+    let data = build_meshlets(&[], &[], &MeshletConfig::default()).unwrap();
+    assert_eq!(data, MeshletData::default());
+}
+
+#[test]
+fn test_build_index_out_of_range() {
+    let (positions, _) = grid(1);
+    assert!(build_meshlets(&positions, &[[0, 1, 9]], &MeshletConfig::default()).is_err());
+}
+
+#[test]
+fn test_build_invalid_config() {
+    let (positions, triangles) = grid(1);
+    let config = MeshletConfig { max_vertices: 0, max_triangles: 4 };
+    assert!(build_meshlets(&positions, &triangles, &config).is_err());
+}
+
+// ============================================================================
+// Bounds and normal cone
+// ============================================================================
+
+#[test]
+fn test_bounding_sphere_contains_vertices() {
+    let (positions, triangles) = grid(4);
+    let data = build_meshlets(&positions, &triangles, &MeshletConfig::default()).unwrap();
+    let meshlet = data.meshlets[0];
+    assert!((meshlet.center - Vec3::new(2.0, 2.0, 0.0)).length() < 1e-5);
+    for &index in &data.vertices {
+        assert!(positions[index as usize].distance(meshlet.center) <= meshlet.radius + 1e-5);
+    }
+}
+
+#[test]
+fn test_flat_meshlet_cone() {
+    let (positions, triangles) = grid(2);
+    let meshlet = build_meshlets(&positions, &triangles, &MeshletConfig::default()).unwrap().meshlets[0];
+    assert!((meshlet.cone_axis - Vec3::Z).length() < 1e-5);
+    assert!(meshlet.cone_cutoff < 1e-3);
+
+    // Seen from behind the plane: culled; from the front: kept
+    assert!(meshlet.is_backfacing(Vec3::new(1.0, 1.0, -10.0)));
+    assert!(!meshlet.is_backfacing(Vec3::new(1.0, 1.0, 10.0)));
+    // Grazing view from the side of the bounding sphere: kept
+    assert!(!meshlet.is_backfacing(Vec3::new(20.0, 1.0, -0.5)));
+}
+
+#[test]
+fn test_opposite_normals_never_culled() {
+    // Two triangles back to back
+    let positions = vec![Vec3::ZERO, Vec3::X, Vec3::Y];
+    let triangles = [[0, 1, 2], [0, 2, 1]];
+    let meshlet = build_meshlets(&positions, &triangles, &MeshletConfig::default()).unwrap().meshlets[0];
+    assert_eq!(meshlet.cone_cutoff, 1.0);
+    for camera in [Vec3::Z * 5.0, -Vec3::Z * 5.0, Vec3::X * 5.0] {
+        assert!(!meshlet.is_backfacing(camera));
+    }
+}
+
+// ============================================================================
+// MeshletData / GPU layout
+// ============================================================================
+
+#[test]
+fn test_append_shifts_offsets() {
+    let (positions, triangles) = grid(1);
+    let config = MeshletConfig::default();
+    let mut data = build_meshlets(&positions, &triangles, &config).unwrap();
+    data.append(build_meshlets(&positions, &triangles, &config).unwrap());
+
+    assert_eq!(data.meshlets.len(), 2);
+    assert_eq!(data.meshlets[1].vertex_offset, 4);
+    assert_eq!(data.meshlets[1].triangle_offset, 6);
+    let mut expected = triangles.clone();
+    expected.extend_from_slice(&triangles);
+    assert_eq!(rebuild_triangles(&data), expected);
+}
+
+#[test]
+fn test_write_bytes_layout() {
+    let meshlet = Meshlet {
+        vertex_offset: 1,
+        vertex_count: 2,
+        triangle_offset: 3,
+        triangle_count: 4,
+        center: Vec3::new(5.0, 6.0, 7.0),
+        radius: 8.0,
+        cone_axis: Vec3::Z,
+        cone_cutoff: 0.5,
+    };
+    let mut bytes = Vec::new();
+    meshlet.write_bytes(&mut bytes);
+    assert_eq!(bytes.len(), MESHLET_GPU_SIZE);
+
+    let words: Vec<[u8; 4]> = bytes.chunks_exact(4).map(|word| word.try_into().unwrap()).collect();
+    let floats: Vec<f32> = words[..8].iter().map(|&word| f32::from_ne_bytes(word)).collect();
+    assert_eq!(floats, [5.0, 6.0, 7.0, 8.0, 0.0, 0.0, 1.0, 0.5]);
+    let counts: Vec<u32> = words[8..].iter().map(|&word| u32::from_ne_bytes(word)).collect();
+    assert_eq!(counts, [1, 2, 3, 4]);
+}
@@ -4,16 +4,32 @@
 
 pub mod resource_manager;
 pub mod texture;
+pub mod atlas_packer;
 pub mod geometry;
+pub mod meshlet;
 pub mod shader;
 pub mod pipeline;
 pub mod material;
 pub mod mesh;
 pub mod buffer;
+pub mod texture_usage;
+pub mod virtual_texture;
+pub mod streaming;
+pub mod default_resources;
+pub mod standard_pbr;
+#[cfg(feature = "manifest")]
+pub mod manifest;
+#[cfg(feature = "archive")]
+pub mod asset_archive;
 
-pub use resource_manager::ResourceManager;
+pub use resource_manager::{
+    ResourceManager, ResourceLeak, ResourceKind, ResourceEntry, ResourceStats,
+    ShaderReloadReport,
+};
 pub use resource_manager::{
     TextureKey, GeometryKey, ShaderKey, PipelineKey, MaterialKey, MeshKey, BufferKey,
+    TextureHandle, GeometryHandle, ShaderHandle, PipelineHandle, MaterialHandle, MeshHandle,
+    BufferHandle,
 };
 pub use shader::{
     Shader, ShaderDesc,
@@ -23,17 +39,27 @@ pub use texture::{
     AtlasRegion, AtlasRegionDesc,
     TextureDesc, LayerDesc,
 };
+pub use atlas_packer::AtlasStats;
 pub use geometry::{
     Geometry, GeometryMesh, GeometrySubMesh, GeometrySubMeshLOD,
     GeometryDesc, GeometryMeshDesc, GeometrySubMeshDesc, GeometrySubMeshLODDesc,
+    MorphTargetDesc, MAX_MORPH_TARGETS, MORPH_TARGET_BUFFER_BINDING, MORPH_TARGET_FIRST_LOCATION,
+    GEOMETRY_POSITION_LOCATION,
+};
+pub use meshlet::{
+    Meshlet, MeshletData, MeshletConfig, MeshletBuffers, build_meshlets,
+    DEFAULT_MESHLET_MAX_VERTICES, DEFAULT_MESHLET_MAX_TRIANGLES, MESHLET_GPU_SIZE,
 };
 pub use pipeline::{
-    Pipeline, PipelineDesc,
+    Pipeline, PipelineDesc, PipelineReflectionReport,
 };
 pub use material::{
     Material, MaterialPass, MaterialTextureSlot, MaterialParam,
     MaterialDesc, MaterialPassDesc, MaterialTextureSlotDesc,
-    LayerRef, RegionRef, ParamValue,
+    LayerRef, RegionRef, ParamValue, RenderQueueClass,
+    MaterialReport, MaterialPassReport, TextureSlotReport,
+    FlipbookDesc, Flipbook, animated_uv,
+    UV_SCROLL_PARAM, UV_ROTATION_SPEED_PARAM, UV_RECT_FIELD_SUFFIX, FULL_UV_RECT,
 };
 pub use mesh::{
     Mesh, MeshSubMesh,
@@ -44,3 +70,47 @@ pub use mesh::{
 pub use buffer::{
     Buffer, BufferDesc, BufferKind, FieldType, FieldDesc,
 };
+pub use default_resources::{
+    DefaultResources, DefaultResourcesDesc,
+    DEFAULT_WHITE_TEXTURE, DEFAULT_BLACK_TEXTURE, DEFAULT_NORMAL_TEXTURE,
+    ERROR_MATERIAL, ERROR_CUBE_GEOMETRY, ERROR_CUBE_MESH,
+    ERROR_COLOR, ERROR_COLOR_PARAM, ERROR_ALBEDO_SLOT,
+    ERROR_CUBE_NORMAL_LOCATION, ERROR_CUBE_UV_LOCATION,
+};
+pub use standard_pbr::{
+    StandardPbrDesc, StandardPbrShaders, StandardPbrMaterialDesc, standard_pbr_vertex_layout,
+    STANDARD_PBR_VERTEX_GLSL, STANDARD_PBR_FRAGMENT_GLSL,
+    STANDARD_PBR_VERTEX_SPIRV, STANDARD_PBR_FRAGMENT_SPIRV, STANDARD_PBR_MORPH_VERTEX_SPIRV,
+    STANDARD_PBR_VERTEX_SHADER, STANDARD_PBR_FRAGMENT_SHADER, standard_pbr_morph_vertex_shader_name,
+    STANDARD_PBR_POSITION_LOCATION, STANDARD_PBR_NORMAL_LOCATION, STANDARD_PBR_UV_LOCATION,
+    STANDARD_PBR_FRAME_BINDING, STANDARD_PBR_INSTANCE_BINDING,
+    STANDARD_PBR_MATERIAL_BINDING, STANDARD_PBR_LIGHT_BINDING,
+    STANDARD_PBR_LIGHT_CLUSTER_BINDING, STANDARD_PBR_LIGHT_INDEX_BINDING,
+    PBR_ALBEDO_SLOT, PBR_NORMAL_SLOT, PBR_METALLIC_ROUGHNESS_SLOT, PBR_EMISSIVE_SLOT, PBR_AO_SLOT,
+    PBR_BASE_COLOR_PARAM, PBR_EMISSIVE_COLOR_PARAM, PBR_METALLIC_PARAM, PBR_ROUGHNESS_PARAM,
+    PBR_NORMAL_SCALE_PARAM, PBR_AO_PARAM,
+};
+pub use texture_usage::{
+    TextureUsageTracker, TextureUsageStats, TextureUsageRecord, TextureUsageReport,
+};
+pub use virtual_texture::{
+    VirtualTexture, VirtualTextureDesc, VirtualTextureFeedback, VirtualTextureUpdate,
+    VirtualPage, VirtualPageLoader,
+    VIRTUAL_TEXTURE_FEEDBACK_FORMAT, VT_PAGE_TABLE_FORMAT, VT_FEEDBACK_NONE, VT_PAGE_TABLE_EMPTY,
+    MAX_VIRTUAL_PAGES_PER_SIDE, DEFAULT_VT_PAGE_SIZE, DEFAULT_VT_PAGE_BORDER, DEFAULT_VT_PHYSICAL_PAGES,
+};
+pub use streaming::{
+    StreamingManager, StreamingBudget, StreamingFrameStats, StreamedAsset, StreamingJob, LoadState,
+    STREAMING_PROGRESS_SOURCE, DEFAULT_MAX_UPLOADS_PER_FRAME, DEFAULT_MAX_UPLOAD_BYTES_PER_FRAME,
+};
+#[cfg(feature = "manifest")]
+pub use manifest::{
+    ResourceManifest, ManifestFormat, ManifestMipmap,
+    TextureManifest, LayerManifest, AtlasRegionManifest, GeometryManifest, ShaderManifest,
+    PipelineManifest, MaterialManifest, MaterialPassManifest, TextureSlotManifest,
+    MeshManifest, MeshSubMeshManifest,
+};
+#[cfg(feature = "archive")]
+pub use asset_archive::{
+    AssetArchive, AssetArchiveWriter, ArchiveEntry, ArchiveCompression, ARCHIVE_MAGIC, ARCHIVE_VERSION,
+};
@@ -4,6 +4,7 @@
 /// and exposes its reflection data. Created by the ResourceManager which
 /// handles the GPU pipeline creation via the GraphicsDevice.
 
+use std::fmt;
 use std::sync::Arc;
 use crate::graphics_device;
 use crate::resource::resource_manager::ShaderKey;
@@ -15,6 +16,8 @@ pub struct Pipeline {
     graphics_device_pipeline: Arc<dyn graphics_device::Pipeline>,
     vertex_shader: ShaderKey,
     fragment_shader: ShaderKey,
+    /// Descriptor the GPU pipeline was created from (used to derive variants)
+    desc: graphics_device::PipelineDesc,
     /// Pipeline signature id: pipelines sharing this id have compatible layouts
     /// (same descriptor set layouts + push constant ranges). Used for sorting
     /// draw calls to preserve descriptor set binds when switching pipelines.
@@ -51,6 +54,67 @@ pub struct PipelineDesc {
     pub color_formats: Vec<graphics_device::TextureFormat>,
     /// Depth/stencil attachment format (None if no depth/stencil)
     pub depth_format: Option<graphics_device::TextureFormat>,
+    /// Engine features folded into the shaders as a specialization constant
+    pub engine_features: graphics_device::EngineFeatures,
+}
+
+// ===== REFLECTION REPORT =====
+
+/// Structured dump of a Pipeline's shader reflection, produced by
+/// `Pipeline::reflection_report()`.
+///
+/// Bindings are sorted by `(set, binding)`. The `Display` implementation
+/// pretty-prints every binding, push constant block and their members.
+#[derive(Debug, Clone)]
+pub struct PipelineReflectionReport {
+    pub signature_id: u16,
+    pub sort_id: u16,
+    pub binding_group_layout_count: u32,
+    pub bindings: Vec<graphics_device::ReflectedBinding>,
+    pub push_constants: Vec<graphics_device::ReflectedPushConstant>,
+}
+
+impl fmt::Display for PipelineReflectionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Pipeline (signature {}, sort id {}, {} binding group layout(s))",
+            self.signature_id, self.sort_id, self.binding_group_layout_count)?;
+        writeln!(f, "  bindings ({}):", self.bindings.len())?;
+        for b in &self.bindings {
+            writeln!(f, "    set {} binding {}: {} {:?} stages 0x{:x}",
+                b.set, b.binding, b.name, b.binding_type, b.stage_flags.bits())?;
+            write_members(f, &b.members, 3)?;
+        }
+        writeln!(f, "  push constants ({}):", self.push_constants.len())?;
+        for pc in &self.push_constants {
+            match pc.size {
+                Some(size) => writeln!(f, "    {} ({} bytes) stages 0x{:x}",
+                    pc.name, size, pc.stage_flags.bits())?,
+                None => writeln!(f, "    {} (runtime-sized) stages 0x{:x}",
+                    pc.name, pc.stage_flags.bits())?,
+            }
+            write_members(f, &pc.members, 3)?;
+        }
+        Ok(())
+    }
+}
+
+/// Write reflected block members, one per line, recursing into nested structs.
+fn write_members(
+    f: &mut fmt::Formatter<'_>,
+    members: &[graphics_device::ReflectedMember],
+    depth: usize,
+) -> fmt::Result {
+    let indent = "  ".repeat(depth);
+    for m in members {
+        match &m.member_type {
+            graphics_device::ReflectedMemberType::Struct(children) => {
+                writeln!(f, "{}+{} {}: struct", indent, m.offset, m.name)?;
+                write_members(f, children, depth + 1)?;
+            }
+            other => writeln!(f, "{}+{} {}: {:?}", indent, m.offset, m.name, other)?,
+        }
+    }
+    Ok(())
 }
 
 // ===== PIPELINE IMPLEMENTATION =====
@@ -61,10 +125,11 @@ impl Pipeline {
         graphics_device_pipeline: Arc<dyn graphics_device::Pipeline>,
         vertex_shader: ShaderKey,
         fragment_shader: ShaderKey,
+        desc: graphics_device::PipelineDesc,
         signature_id: u16,
         sort_id: u16,
     ) -> Self {
-        Self { graphics_device_pipeline, vertex_shader, fragment_shader, signature_id, sort_id }
+        Self { graphics_device_pipeline, vertex_shader, fragment_shader, desc, signature_id, sort_id }
     }
 
     /// Get the pipeline signature id
@@ -87,6 +152,11 @@ impl Pipeline {
         self.fragment_shader
     }
 
+    /// Get the descriptor the GPU pipeline was created from
+    pub fn desc(&self) -> &graphics_device::PipelineDesc {
+        &self.desc
+    }
+
     /// Get the underlying graphics device pipeline
     pub fn graphics_device_pipeline(&self) -> &Arc<dyn graphics_device::Pipeline> {
         &self.graphics_device_pipeline
@@ -101,6 +171,22 @@ impl Pipeline {
     pub fn binding_group_layout_count(&self) -> u32 {
         self.graphics_device_pipeline.binding_group_layout_count()
     }
+
+    /// Build a structured dump of all reflected bindings and push constants.
+    ///
+    /// Use `to_string()` on the result for a pretty-printed dump.
+    pub fn reflection_report(&self) -> PipelineReflectionReport {
+        let reflection = self.reflection();
+        let mut bindings = reflection.bindings().to_vec();
+        bindings.sort_by_key(|b| (b.set, b.binding));
+        PipelineReflectionReport {
+            signature_id: self.signature_id,
+            sort_id: self.sort_id,
+            binding_group_layout_count: self.binding_group_layout_count(),
+            bindings,
+            push_constants: reflection.push_constants().to_vec(),
+        }
+    }
 }
 
 #[cfg(test)]
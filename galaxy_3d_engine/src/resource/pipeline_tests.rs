@@ -52,12 +52,14 @@ fn create_test_pipeline() -> crate::resource::Pipeline {
         stage: graphics_device::ShaderStage::Vertex,
         entry_point: "main".to_string(),
         code: &[],
+        debug_name: None,
     }).unwrap();
 
     let fragment_shader = gd_lock.create_shader(graphics_device::ShaderDesc {
         stage: graphics_device::ShaderStage::Fragment,
         entry_point: "main".to_string(),
         code: &[],
+        debug_name: None,
     }).unwrap();
 
     let desc = graphics_device::PipelineDesc {
@@ -68,10 +70,13 @@ fn create_test_pipeline() -> crate::resource::Pipeline {
         multisample: Default::default(),
         color_formats: vec![],
         depth_format: None,
+        engine_features: Default::default(),
+        debug_name: None,
+        dynamic_uniform_buffers: Vec::new(),
     };
 
-    let gd_pipeline = gd_lock.create_pipeline(desc, &vertex_shader, &fragment_shader).unwrap();
-    crate::resource::Pipeline::from_gpu_pipeline(gd_pipeline, vk, fk, 0, 0)
+    let gd_pipeline = gd_lock.create_pipeline(desc.clone(), &vertex_shader, &fragment_shader).unwrap();
+    crate::resource::Pipeline::from_gpu_pipeline(gd_pipeline, vk, fk, desc, 0, 0)
 }
 
 // ============================================================================
@@ -143,11 +148,13 @@ fn test_pipeline_from_gpu_pipeline_with_explicit_ids() {
         stage: graphics_device::ShaderStage::Vertex,
         entry_point: "main".to_string(),
         code: &[],
+        debug_name: None,
     }).unwrap();
     let fs = gd_lock.create_shader(graphics_device::ShaderDesc {
         stage: graphics_device::ShaderStage::Fragment,
         entry_point: "main".to_string(),
         code: &[],
+        debug_name: None,
     }).unwrap();
     let desc = graphics_device::PipelineDesc {
         vertex_layout: create_simple_vertex_layout(),
@@ -157,15 +164,122 @@ fn test_pipeline_from_gpu_pipeline_with_explicit_ids() {
         multisample: Default::default(),
         color_formats: vec![],
         depth_format: None,
+        engine_features: Default::default(),
+        debug_name: None,
+        dynamic_uniform_buffers: Vec::new(),
     };
-    let gd_pipeline = gd_lock.create_pipeline(desc, &vs, &fs).unwrap();
+    let gd_pipeline = gd_lock.create_pipeline(desc.clone(), &vs, &fs).unwrap();
     let pipeline = crate::resource::Pipeline::from_gpu_pipeline(
         gd_pipeline,
         ShaderKey::default(),
         ShaderKey::default(),
+        desc,
         42,
         99,
     );
     assert_eq!(pipeline.signature_id(), 42);
     assert_eq!(pipeline.sort_id(), 99);
 }
+
+// ============================================================================
+// REFLECTION REPORT TESTS
+// ============================================================================
+
+/// Pipeline stub exposing a non-empty reflection (the mock one is empty)
+struct ReflectedStubPipeline {
+    reflection: graphics_device::PipelineReflection,
+}
+
+impl graphics_device::Pipeline for ReflectedStubPipeline {
+    fn binding_group_layout_count(&self) -> u32 { 2 }
+    fn reflection(&self) -> &graphics_device::PipelineReflection {
+        &self.reflection
+    }
+}
+
+fn create_reflected_pipeline() -> crate::resource::Pipeline {
+    use crate::resource::resource_manager::ShaderKey;
+    use graphics_device::{
+        ReflectedBinding, ReflectedMember, ReflectedMemberType, ReflectedPushConstant,
+        ScalarKind, BindingType, ShaderStageFlags,
+    };
+
+    let bindings = vec![
+        ReflectedBinding {
+            name: "Frame".to_string(), set: 1, binding: 0,
+            binding_type: BindingType::UniformBuffer,
+            stage_flags: ShaderStageFlags::VERTEX_FRAGMENT,
+            members: vec![ReflectedMember {
+                name: "view".to_string(), offset: 0, size: Some(64),
+                member_type: ReflectedMemberType::Matrix(ScalarKind::Float32, 4, 4),
+            }],
+        },
+        ReflectedBinding {
+            name: "albedoTex".to_string(), set: 0, binding: 0,
+            binding_type: BindingType::CombinedImageSampler,
+            stage_flags: ShaderStageFlags::FRAGMENT,
+            members: vec![],
+        },
+    ];
+    let push_constants = vec![ReflectedPushConstant {
+        name: "DrawData".to_string(),
+        stage_flags: ShaderStageFlags::VERTEX,
+        size: Some(4),
+        members: vec![ReflectedMember {
+            name: "drawSlot".to_string(), offset: 0, size: Some(4),
+            member_type: ReflectedMemberType::Scalar(ScalarKind::UInt32),
+        }],
+    }];
+
+    let gd_pipeline: Arc<dyn graphics_device::Pipeline> = Arc::new(ReflectedStubPipeline {
+        reflection: graphics_device::PipelineReflection::new(bindings, push_constants),
+    });
+    let desc = graphics_device::PipelineDesc {
+        vertex_layout: create_simple_vertex_layout(),
+        topology: graphics_device::PrimitiveTopology::TriangleList,
+        rasterization: Default::default(),
+        color_blend: Default::default(),
+        multisample: Default::default(),
+        color_formats: vec![],
+        depth_format: None,
+        engine_features: Default::default(),
+        debug_name: None,
+        dynamic_uniform_buffers: Vec::new(),
+    };
+    crate::resource::Pipeline::from_gpu_pipeline(
+        gd_pipeline, ShaderKey::default(), ShaderKey::default(), desc, 3, 5,
+    )
+}
+
+#[test]
+fn test_reflection_report_empty_for_mock() {
+    let pipeline = create_test_pipeline();
+    let report = pipeline.reflection_report();
+    assert!(report.bindings.is_empty());
+    assert!(report.push_constants.is_empty());
+    assert_eq!(report.binding_group_layout_count, 0);
+}
+
+#[test]
+fn test_reflection_report_sorts_bindings_by_set() {
+    let pipeline = create_reflected_pipeline();
+    let report = pipeline.reflection_report();
+    assert_eq!(report.signature_id, 3);
+    assert_eq!(report.sort_id, 5);
+    assert_eq!(report.binding_group_layout_count, 2);
+    assert_eq!(report.bindings.len(), 2);
+    assert_eq!(report.bindings[0].name, "albedoTex");
+    assert_eq!(report.bindings[1].name, "Frame");
+    assert_eq!(report.push_constants.len(), 1);
+}
+
+#[test]
+fn test_reflection_report_display() {
+    let pipeline = create_reflected_pipeline();
+    let text = pipeline.reflection_report().to_string();
+    assert!(text.contains("set 0 binding 0: albedoTex CombinedImageSampler"));
+    assert!(text.contains("set 1 binding 0: Frame UniformBuffer"));
+    assert!(text.contains("view: Matrix(Float32, 4, 4)"));
+    assert!(text.contains("DrawData (4 bytes)"));
+    assert!(text.contains("drawSlot: Scalar(UInt32)"));
+}
@@ -3,25 +3,29 @@
 //! Stores and provides access to all engine resources (textures, geometries, etc.).
 //! Uses SlotMaps for O(1) key-based access with stable keys, plus name-based lookup.
 
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use slotmap::SlotMap;
+use std::any::Any;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
-use crate::error::Result;
+use std::time::{Duration, Instant};
+use crate::error::{Error, Result, ResultExt};
 use crate::graphics_device;
 use crate::resource::texture::{
     Texture,
-    TextureDesc, LayerDesc, AtlasRegionDesc,
+    TextureDesc, LayerDesc, AtlasRegion, AtlasRegionDesc,
 };
 use crate::resource::geometry::{
     Geometry, GeometryDesc, GeometryMeshDesc, GeometrySubMeshDesc, GeometrySubMeshLODDesc,
+    MAX_MORPH_TARGETS,
 };
 use crate::resource::shader::Shader;
 use crate::resource::pipeline::{
     Pipeline, PipelineDesc,
 };
 use crate::resource::material::{
-    Material, MaterialDesc,
+    Material, MaterialDesc, MaterialTextureSlot,
+    FULL_UV_RECT, UV_RECT_FIELD_SUFFIX, UV_SCROLL_PARAM, UV_ROTATION_SPEED_PARAM,
 };
 use crate::resource::mesh::{
     Mesh, MeshDesc,
@@ -30,6 +34,20 @@ use crate::resource::buffer::{
     Buffer, BufferDesc, BufferKind, FieldDesc, FieldType,
 };
 use crate::resource::material::ParamValue;
+use crate::resource::default_resources::{
+    self, DefaultResources, DefaultResourcesDesc,
+    ERROR_MATERIAL, ERROR_CUBE_GEOMETRY, ERROR_CUBE_MESH, ERROR_COLOR, ERROR_COLOR_PARAM,
+    ERROR_ALBEDO_SLOT,
+};
+use crate::resource::standard_pbr::{
+    StandardPbrDesc, StandardPbrShaders, StandardPbrMaterialDesc,
+    STANDARD_PBR_VERTEX_SHADER, STANDARD_PBR_FRAGMENT_SHADER, standard_pbr_morph_vertex_shader_name,
+    PBR_BASE_COLOR_PARAM, PBR_EMISSIVE_COLOR_PARAM, PBR_METALLIC_PARAM, PBR_ROUGHNESS_PARAM,
+    PBR_NORMAL_SCALE_PARAM, PBR_AO_PARAM,
+};
+use crate::resource::mesh::{GeometryMeshRef, GeometrySubMeshRef, MeshSubMeshDesc};
+use crate::resource::material::{MaterialPassDesc, MaterialTextureSlotDesc};
+use crate::resource::texture_usage::{TextureUsageTracker, TextureUsageStats, TextureUsageRecord, TextureUsageReport};
 use crate::utils::SlotAllocator;
 
 // ===== RESOURCE KEYS =====
@@ -51,6 +69,27 @@ slotmap::new_key_type! {
     pub struct BufferKey;
 }
 
+// ===== RESOURCE HANDLES =====
+
+// The keys are the generational handles returned by the `create_*`
+// methods: O(1) slot lookup, and a stale handle resolves to None even after
+// its slot is reused. Name lookups are the secondary index.
+
+/// Generational handle of a Texture (see `TextureKey`)
+pub type TextureHandle = TextureKey;
+/// Generational handle of a Geometry (see `GeometryKey`)
+pub type GeometryHandle = GeometryKey;
+/// Generational handle of a Shader (see `ShaderKey`)
+pub type ShaderHandle = ShaderKey;
+/// Generational handle of a Pipeline (see `PipelineKey`)
+pub type PipelineHandle = PipelineKey;
+/// Generational handle of a Material (see `MaterialKey`)
+pub type MaterialHandle = MaterialKey;
+/// Generational handle of a Mesh (see `MeshKey`)
+pub type MeshHandle = MeshKey;
+/// Generational handle of a Buffer (see `BufferKey`)
+pub type BufferHandle = BufferKey;
+
 // ===== PIPELINE CACHE KEY =====
 
 /// Composite key for the pipeline cache.
@@ -72,6 +111,8 @@ pub struct PipelineCacheKey {
     pub color_formats: Vec<graphics_device::TextureFormat>,
     pub depth_format: Option<graphics_device::TextureFormat>,
     pub sample_count: graphics_device::SampleCount,
+    /// Active engine features masked by the material pass's features
+    pub engine_features: graphics_device::EngineFeatures,
 }
 
 /// Render pass attachment information needed for pipeline creation.
@@ -114,8 +155,239 @@ impl PassInfo {
     }
 }
 
+// ===== LEAK REPORT =====
+
+/// A resource still referenced outside the ResourceManager.
+///
+/// Produced by `ResourceManager::external_references()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceLeak {
+    /// Resource type ("Texture", "Geometry", ...)
+    pub kind: &'static str,
+    /// Registered resource name
+    pub name: String,
+    /// Current `Arc` strong count (the ResourceManager holds 1 of them)
+    pub ref_count: usize,
+}
+
+// ===== ENUMERATION =====
+
+/// A registered resource, yielded by `ResourceManager::textures()`,
+/// `materials()`, `meshes()`, ...
+///
+/// The metadata (dimensions, passes, submeshes, ...) is read from the
+/// resource itself.
+pub struct ResourceEntry<'a, K, T> {
+    /// Registered resource name
+    pub name: &'a str,
+    /// Key of the resource
+    pub key: K,
+    /// The resource
+    pub resource: &'a Arc<T>,
+}
+
+impl<K: Copy, T> Clone for ResourceEntry<'_, K, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K: Copy, T> Copy for ResourceEntry<'_, K, T> {}
+
+impl<K: std::fmt::Debug, T> std::fmt::Debug for ResourceEntry<'_, K, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResourceEntry")
+            .field("name", &self.name)
+            .field("key", &self.key)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Summary statistics of a ResourceManager, returned by `ResourceManager::stats()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ResourceStats {
+    pub textures: usize,
+    pub geometries: usize,
+    pub shaders: usize,
+    pub pipelines: usize,
+    pub materials: usize,
+    pub meshes: usize,
+    pub buffers: usize,
+    /// Pipelines registered by `resolve_pipeline()` (included in `pipelines`)
+    pub cached_pipeline_variants: usize,
+    /// Material slots in use in the material SSBO
+    pub material_slots: u32,
+    /// Total size in bytes of the registered buffers
+    pub buffer_bytes: u64,
+    /// Resources retired and not released yet
+    pub retired_resources: usize,
+    /// Resources still referenced outside the ResourceManager
+    pub externally_referenced: usize,
+    /// Textures pinned resident (see `pin_texture()`)
+    pub pinned_textures: usize,
+    /// Buffers pinned resident (see `pin_buffer()`)
+    pub pinned_buffers: usize,
+    /// Total size in bytes of the pinned buffers (included in `buffer_bytes`)
+    pub pinned_buffer_bytes: u64,
+    /// Resources removed while still referenced, kept until their last
+    /// dependent is removed (included in the counts above)
+    pub pending_removals: usize,
+}
+
+impl ResourceStats {
+    /// Number of registered resources of every type
+    pub fn total(&self) -> usize {
+        self.textures + self.geometries + self.shaders + self.pipelines
+            + self.materials + self.meshes + self.buffers
+    }
+}
+
+// ===== SHADER RELOAD REPORT =====
+
+/// Diagnostics of `ResourceManager::reload_shader()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShaderReloadReport {
+    /// Name of the reloaded shader
+    pub shader: String,
+    /// Pipelines recreated with the new shader (named pipelines and
+    /// `resolve_pipeline()` variants), sorted by name
+    pub recreated_pipelines: Vec<String>,
+    /// Time spent compiling the shader and recreating the pipelines
+    pub duration: Duration,
+}
+
+impl std::fmt::Display for ShaderReloadReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Reloaded shader '{}' in {:.2} ms, recreated {} pipeline(s)",
+            self.shader, self.duration.as_secs_f64() * 1000.0, self.recreated_pipelines.len())?;
+        if !self.recreated_pipelines.is_empty() {
+            write!(f, ": {}", self.recreated_pipelines.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+// ===== RESOURCE KIND =====
+
+/// Resource type selector for bulk operations (`ResourceManager::remove_many`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResourceKind {
+    Texture,
+    Geometry,
+    Shader,
+    Pipeline,
+    Material,
+    Mesh,
+    Buffer,
+}
+
 // ===== PRIVATE HELPERS =====
 
+/// A resource removed in bulk, kept alive until `release_retired_resources()`.
+type RetiredResource = Arc<dyn Any + Send + Sync>;
+
+/// Remove the named entries of one resource type (unknown names are
+/// skipped) and return them with their keys.
+fn take_named<K: slotmap::Key, T, S: AsRef<str>>(
+    names: &mut FxHashMap<String, K>,
+    storage: &mut SlotMap<K, Arc<T>>,
+    to_remove: &[S],
+) -> Vec<(K, Arc<T>)> {
+    to_remove.iter()
+        .filter_map(|name| names.remove(name.as_ref()))
+        .filter_map(|key| storage.remove(key).map(|res| (key, res)))
+        .collect()
+}
+
+/// Iterate the registered resources of one type.
+fn entries<'a, K: slotmap::Key, T>(
+    names: &'a FxHashMap<String, K>,
+    storage: &'a SlotMap<K, Arc<T>>,
+) -> impl Iterator<Item = ResourceEntry<'a, K, T>> {
+    names.iter().filter_map(|(name, &key)| {
+        storage.get(key).map(|resource| ResourceEntry { name, key, resource })
+    })
+}
+
+/// Move removed resources to the retired list.
+fn retire<K, T: Send + Sync + 'static>(removed: Vec<(K, Arc<T>)>, retired: &mut Vec<RetiredResource>) {
+    retired.extend(removed.into_iter().map(|(_, res)| res as RetiredResource));
+}
+
+/// Release one count of a counter table (residency pins, dependency
+/// counts), dropping the entry at zero. Returns false if the key was not
+/// counted.
+fn release_count<K: std::hash::Hash + Eq>(counts: &mut FxHashMap<K, u32>, key: K) -> bool {
+    let Some(count) = counts.get_mut(&key) else {
+        return false;
+    };
+    *count -= 1;
+    if *count == 0 {
+        counts.remove(&key);
+    }
+    true
+}
+
+/// A resource of any type, key of the dependency counts
+type ResourceId = (ResourceKind, slotmap::KeyData);
+
+fn resource_id<K: slotmap::Key>(kind: ResourceKind, key: K) -> ResourceId {
+    (kind, key.data())
+}
+
+/// Resources referenced by a material: the texture of every slot and the
+/// fragment shader of every pass
+fn material_dependencies(material: &Material) -> Vec<ResourceId> {
+    material.passes().iter()
+        .flat_map(|pass| {
+            std::iter::once(resource_id(ResourceKind::Shader, pass.fragment_shader()))
+                .chain(pass.texture_slots().iter().map(|slot| resource_id(ResourceKind::Texture, slot.texture())))
+        })
+        .collect()
+}
+
+/// Resources referenced by a mesh: its geometry and submesh materials
+fn mesh_dependencies(mesh: &Mesh) -> Vec<ResourceId> {
+    std::iter::once(resource_id(ResourceKind::Geometry, mesh.geometry()))
+        .chain((0..mesh.submesh_count())
+            .filter_map(|id| mesh.submesh(id))
+            .map(|submesh| resource_id(ResourceKind::Material, submesh.material())))
+        .collect()
+}
+
+/// Resources referenced by a pipeline: its shaders
+fn pipeline_dependencies(pipeline: &Pipeline) -> Vec<ResourceId> {
+    vec![
+        resource_id(ResourceKind::Shader, pipeline.vertex_shader()),
+        resource_id(ResourceKind::Shader, pipeline.fragment_shader()),
+    ]
+}
+
+/// Unregister the names of `to_remove` whose resource is still referenced,
+/// marking it pending removal. Returns the other names (unknown ones
+/// included) and the number of deferred removals.
+fn defer_referenced<'n, K: slotmap::Key, S: AsRef<str>>(
+    kind: ResourceKind,
+    names: &mut FxHashMap<String, K>,
+    to_remove: &'n [S],
+    dependency_counts: &FxHashMap<ResourceId, u32>,
+    pending_removals: &mut FxHashSet<ResourceId>,
+) -> (Vec<&'n str>, usize) {
+    let mut remaining = Vec::with_capacity(to_remove.len());
+    let mut deferred = 0;
+    for name in to_remove.iter().map(AsRef::as_ref) {
+        match names.get(name).map(|&key| resource_id(kind, key)) {
+            Some(id) if dependency_counts.contains_key(&id) => {
+                names.remove(name);
+                pending_removals.insert(id);
+                deferred += 1;
+            }
+            _ => remaining.push(name),
+        }
+    }
+    (remaining, deferred)
+}
+
 /// Map a ParamValue to its compatible FieldType.
 /// Bool maps to UInt (GLSL convention: bools are u32 in GPU buffers).
 fn compatible_field_type(value: &ParamValue) -> FieldType {
@@ -178,6 +450,8 @@ pub struct ResourceManager {
     buffer_names: FxHashMap<String, BufferKey>,
 
     material_slot_allocator: SlotAllocator,
+    /// Materials created or changed since the last `sync_dirty_materials()`
+    dirty_materials: Vec<MaterialKey>,
 
     /// Pipeline cache: maps composite pipeline parameters to an existing PipelineKey.
     /// Pipelines created by the cache are stored in the same `pipelines` SlotMap
@@ -202,6 +476,48 @@ pub struct ResourceManager {
     next_pipeline_sort_id: u16,
     /// Counter for the next per-Geometry sort id (unique id per resource::Geometry).
     next_geometry_sort_id: u16,
+
+    /// Per-frame record of the textures drawn with (fed by the drawer).
+    texture_usage: TextureUsageTracker,
+
+    /// Pin counts of the textures and buffers that must stay resident
+    /// (absent = not pinned).
+    texture_pins: FxHashMap<TextureKey, u32>,
+    buffer_pins: FxHashMap<BufferKey, u32>,
+
+    /// Number of resources referencing each resource (mesh → geometry and
+    /// materials, material → textures and fragment shaders, pipeline →
+    /// shaders)
+    dependency_counts: FxHashMap<ResourceId, u32>,
+    /// Resources removed while referenced: nameless, retired with their
+    /// last dependent
+    pending_removals: FxHashSet<ResourceId>,
+
+    /// Active engine features, folded into cached pipelines.
+    engine_features: graphics_device::EngineFeatures,
+    /// Bumped on every `engine_features` or `debug_view` change;
+    /// invalidates the pipelines cached on render instances.
+    engine_features_generation: u64,
+    /// Active debug visualization, applied by the drawers
+    debug_view: graphics_device::DebugViewMode,
+
+    /// Platform profile clamps applied to loaded textures and shadow maps
+    platform_limits: graphics_device::PlatformLimits,
+
+    /// Resources removed by `clear()` / `remove_many()`. Their GPU objects
+    /// may still be used by frames in flight, so they are only dropped by
+    /// `release_retired_resources()`.
+    retired_resources: Vec<RetiredResource>,
+
+    /// Fallback resources (None until `create_default_resources()`)
+    default_resources: Option<DefaultResources>,
+
+    /// Built-in PBR shaders (None until `create_standard_pbr_shaders()`)
+    standard_pbr: Option<StandardPbrShaders>,
+
+    /// Manifest entries of the resources created by `load_manifest()`
+    #[cfg(feature = "manifest")]
+    manifest_records: super::manifest::ManifestRecords,
 }
 
 impl ResourceManager {
@@ -225,6 +541,7 @@ impl ResourceManager {
             buffer_names: FxHashMap::default(),
 
             material_slot_allocator: SlotAllocator::new(),
+            dirty_materials: Vec::new(),
 
             pipeline_cache: HashMap::new(),
 
@@ -236,6 +553,28 @@ impl ResourceManager {
 
             next_pipeline_sort_id: 0,
             next_geometry_sort_id: 0,
+
+            texture_usage: TextureUsageTracker::new(),
+
+            texture_pins: FxHashMap::default(),
+            buffer_pins: FxHashMap::default(),
+
+            dependency_counts: FxHashMap::default(),
+            pending_removals: FxHashSet::default(),
+
+            engine_features: graphics_device::EngineFeatures::NONE,
+            engine_features_generation: 0,
+            debug_view: graphics_device::DebugViewMode::None,
+
+            platform_limits: graphics_device::PlatformLimits::default(),
+
+            retired_resources: Vec::new(),
+
+            default_resources: None,
+            standard_pbr: None,
+
+            #[cfg(feature = "manifest")]
+            manifest_records: Default::default(),
         }
     }
 
@@ -283,7 +622,7 @@ impl ResourceManager {
     /// Get or assign a u16 id for a Material pass render state.
     /// Two passes with equal `DynamicRenderState` values return the same id,
     /// allowing the drawer to skip redundant `set_dynamic_state` calls.
-    fn get_or_assign_material_render_state_signature_id(
+    pub(crate) fn get_or_assign_material_render_state_signature_id(
         &mut self,
         state: &graphics_device::DynamicRenderState,
     ) -> Result<u16> {
@@ -312,6 +651,18 @@ impl ResourceManager {
             crate::engine_bail_warn!("galaxy3d::ResourceManager", "Texture '{}' already exists", name);
         }
 
+        let mut desc = desc;
+        desc.texture.debug_name.get_or_insert_with(|| name.clone());
+        let dropped = self.platform_limits.texture_mip_drop(desc.texture.width, desc.texture.height);
+        if dropped > 0 && desc.is_downscalable() {
+            match desc.drop_top_mips(dropped) {
+                Ok(()) => crate::engine_info!("galaxy3d::ResourceManager",
+                    "Texture '{}' downscaled to {}x{} by the platform profile",
+                    name, desc.texture.width, desc.texture.height),
+                Err(e) => crate::engine_warn!("galaxy3d::ResourceManager",
+                    "Texture '{}' kept at full size: {}", name, e),
+            }
+        }
         let texture = Texture::from_desc(desc)?;
         let is_simple = texture.is_simple();
         let layer_count = texture.layer_count();
@@ -346,20 +697,32 @@ impl ResourceManager {
     }
 
     /// Remove a texture by key
+    ///
+    /// A texture still used by a material is only unregistered: it is
+    /// retired when its last material is removed (see `dependent_count()`).
     pub fn remove_texture(&mut self, key: TextureKey) -> bool {
-        if let Some(_) = self.textures.remove(key) {
-            self.texture_names.retain(|_, v| *v != key);
-            crate::engine_info!("galaxy3d::ResourceManager", "Removed Texture resource");
-            true
-        } else {
-            false
+        let id = resource_id(ResourceKind::Texture, key);
+        if !self.textures.contains_key(key) || self.pending_removals.contains(&id) {
+            return false;
+        }
+        self.texture_names.retain(|_, v| *v != key);
+        if !self.defer_removal(id) {
+            self.textures.remove(key);
+            self.texture_usage.remove(key);
+            self.texture_pins.remove(&key);
         }
+        crate::engine_info!("galaxy3d::ResourceManager", "Removed Texture resource");
+        true
     }
 
-    /// Remove a texture by name
+    /// Remove a texture by name (see `remove_texture()`)
     pub fn remove_texture_by_name(&mut self, name: &str) -> bool {
         if let Some(key) = self.texture_names.remove(name) {
-            self.textures.remove(key);
+            if !self.defer_removal(resource_id(ResourceKind::Texture, key)) {
+                self.textures.remove(key);
+                self.texture_usage.remove(key);
+                self.texture_pins.remove(&key);
+            }
             crate::engine_info!("galaxy3d::ResourceManager", "Removed Texture resource '{}'", name);
             true
         } else {
@@ -372,6 +735,54 @@ impl ResourceManager {
         self.textures.len()
     }
 
+    // ===== TEXTURE USAGE =====
+
+    /// Advance the texture usage frame counter. Call once per frame, before
+    /// drawing.
+    pub fn begin_texture_usage_frame(&mut self) {
+        self.texture_usage.begin_frame();
+    }
+
+    /// Record one draw with every texture slot of a material pass.
+    ///
+    /// Called by the drawer for each emitted submesh; custom drawers should
+    /// call it too so the statistics stay meaningful. Unknown material or
+    /// pass index is ignored.
+    pub fn record_material_texture_usage(&mut self, material: MaterialKey, pass_index: usize) {
+        let pass = match self.materials.get(material).and_then(|m| m.pass(pass_index)) {
+            Some(p) => p,
+            None => return,
+        };
+        for slot in pass.texture_slots() {
+            self.texture_usage.record(slot.texture(), slot.layer());
+        }
+    }
+
+    /// Usage statistics of a texture, or None if it was never drawn with
+    pub fn texture_usage(&self, key: TextureKey) -> Option<&TextureUsageStats> {
+        self.texture_usage.stats(key)
+    }
+
+    /// Current texture usage frame index
+    pub fn texture_usage_frame(&self) -> u64 {
+        self.texture_usage.frame()
+    }
+
+    /// Snapshot of the usage of every registered texture, sorted by name.
+    pub fn texture_usage_report(&self) -> TextureUsageReport {
+        let mut textures: Vec<TextureUsageRecord> = self.texture_names.iter()
+            .map(|(name, &key)| TextureUsageRecord {
+                texture: key,
+                name: name.clone(),
+                stats: self.texture_usage.stats(key).cloned(),
+                frames_idle: self.texture_usage.frames_idle(key),
+                pin_count: self.texture_pin_count(key),
+            })
+            .collect();
+        textures.sort_by(|a, b| a.name.cmp(&b.name));
+        TextureUsageReport { frame: self.texture_usage.frame(), textures }
+    }
+
     // ===== TEXTURE MODIFICATION =====
 
     /// Add a layer to an existing indexed texture
@@ -380,13 +791,7 @@ impl ResourceManager {
         texture_key: TextureKey,
         desc: LayerDesc,
     ) -> Result<u32> {
-        let arc = self.textures.get_mut(texture_key)
-            .ok_or_else(|| crate::engine_warn_err!("galaxy3d::ResourceManager", "Texture not found"))?;
-
-        let texture = Arc::get_mut(arc)
-            .ok_or_else(|| crate::engine_warn_err!("galaxy3d::ResourceManager", "Cannot mutate texture: other references exist"))?;
-
-        texture.add_layer(desc)
+        self.texture_mut(texture_key)?.add_layer(desc)
     }
 
     /// Add a region to an existing texture layer
@@ -396,25 +801,51 @@ impl ResourceManager {
         layer_name: &str,
         desc: AtlasRegionDesc,
     ) -> Result<u32> {
-        let arc = self.textures.get_mut(texture_key)
-            .ok_or_else(|| crate::engine_warn_err!("galaxy3d::ResourceManager", "Texture not found"))?;
+        self.texture_mut(texture_key)?.add_region(layer_name, desc)
+    }
 
-        let texture = Arc::get_mut(arc)
-            .ok_or_else(|| crate::engine_warn_err!("galaxy3d::ResourceManager", "Cannot mutate texture: other references exist"))?;
+    /// Pack a `width` x `height` region into a texture layer at runtime
+    /// (see `TextureLayer::allocate_region`)
+    pub fn allocate_texture_region(
+        &mut self,
+        texture_key: TextureKey,
+        layer_name: &str,
+        width: u32,
+        height: u32,
+    ) -> Result<AtlasRegion> {
+        self.texture_mut(texture_key)?.allocate_region(layer_name, width, height)
+    }
 
-        texture.add_region(layer_name, desc)
+    /// Give back a region packed by `allocate_texture_region()`. Returns
+    /// false if the layer did not allocate it.
+    pub fn free_texture_region(
+        &mut self,
+        texture_key: TextureKey,
+        layer_name: &str,
+        region: &AtlasRegion,
+    ) -> Result<bool> {
+        self.texture_mut(texture_key)?.free_region(layer_name, region)
+    }
+
+    fn texture_mut(&mut self, key: TextureKey) -> Result<&mut Texture> {
+        let arc = self.textures.get_mut(key)
+            .ok_or_else(|| crate::engine_not_found!("galaxy3d::ResourceManager", "Texture", format!("{:?}", key)))?;
+
+        Arc::get_mut(arc)
+            .ok_or_else(|| crate::engine_warn_err!("galaxy3d::ResourceManager", "Cannot mutate texture: other references exist"))
     }
 
     // ===== GEOMETRY CREATION =====
 
     /// Create a geometry resource and register it
-    pub fn create_geometry(&mut self, name: String, desc: GeometryDesc) -> Result<GeometryKey> {
+    pub fn create_geometry(&mut self, name: String, desc: GeometryDesc<'_>) -> Result<GeometryKey> {
         if self.geometry_names.contains_key(&name) {
             crate::engine_bail_warn!("galaxy3d::ResourceManager", "Geometry '{}' already exists", name);
         }
 
         let sort_id = self.assign_geometry_sort_id()?;
-        let geometry = Geometry::from_desc(desc, sort_id)?;
+        let geometry = Geometry::from_desc(desc, sort_id)
+            .with_context(|| format!("Creating geometry '{}'", name))?;
         let mesh_count = geometry.mesh_count();
         let total_vertex_count = geometry.total_vertex_count();
         let total_index_count = geometry.total_index_count();
@@ -448,9 +879,14 @@ impl ResourceManager {
     }
 
     /// Remove a geometry by name
+    ///
+    /// A geometry still used by a mesh is only unregistered: it is retired
+    /// when its last mesh is removed (see `dependent_count()`).
     pub fn remove_geometry(&mut self, name: &str) -> bool {
         if let Some(key) = self.geometry_names.remove(name) {
-            self.geometries.remove(key);
+            if !self.defer_removal(resource_id(ResourceKind::Geometry, key)) {
+                self.geometries.remove(key);
+            }
             crate::engine_info!("galaxy3d::ResourceManager", "Removed Geometry resource '{}'", name);
             true
         } else {
@@ -468,7 +904,7 @@ impl ResourceManager {
     /// Add a mesh to an existing geometry resource
     pub fn add_geometry_mesh(&mut self, geom_key: GeometryKey, desc: GeometryMeshDesc) -> Result<usize> {
         let arc = self.geometries.get_mut(geom_key)
-            .ok_or_else(|| crate::engine_warn_err!("galaxy3d::ResourceManager", "Geometry not found"))?;
+            .ok_or_else(|| crate::engine_not_found!("galaxy3d::ResourceManager", "Geometry", format!("{:?}", geom_key)))?;
 
         let geometry = Arc::get_mut(arc)
             .ok_or_else(|| crate::engine_warn_err!("galaxy3d::ResourceManager", "Cannot mutate Geometry: other references exist"))?;
@@ -484,7 +920,7 @@ impl ResourceManager {
         desc: GeometrySubMeshDesc,
     ) -> Result<usize> {
         let arc = self.geometries.get_mut(geom_key)
-            .ok_or_else(|| crate::engine_warn_err!("galaxy3d::ResourceManager", "Geometry not found"))?;
+            .ok_or_else(|| crate::engine_not_found!("galaxy3d::ResourceManager", "Geometry", format!("{:?}", geom_key)))?;
 
         let geometry = Arc::get_mut(arc)
             .ok_or_else(|| crate::engine_warn_err!("galaxy3d::ResourceManager", "Cannot mutate Geometry: other references exist"))?;
@@ -505,7 +941,7 @@ impl ResourceManager {
         threshold: Option<(f32, f32)>,
     ) -> Result<usize> {
         let arc = self.geometries.get_mut(geom_key)
-            .ok_or_else(|| crate::engine_warn_err!("galaxy3d::ResourceManager", "Geometry not found"))?;
+            .ok_or_else(|| crate::engine_not_found!("galaxy3d::ResourceManager", "Geometry", format!("{:?}", geom_key)))?;
 
         let geometry = Arc::get_mut(arc)
             .ok_or_else(|| crate::engine_warn_err!("galaxy3d::ResourceManager", "Cannot mutate Geometry: other references exist"))?;
@@ -513,6 +949,22 @@ impl ResourceManager {
         geometry.add_submesh_lod(mesh_id, submesh_id, desc, threshold)
     }
 
+    /// Build the meshlets of an existing geometry for the mesh shader path
+    /// (see `Geometry::build_meshlets`). Returns the number of meshlets.
+    pub fn build_geometry_meshlets(
+        &mut self,
+        geom_key: GeometryKey,
+        config: &crate::resource::MeshletConfig,
+    ) -> Result<u32> {
+        let arc = self.geometries.get_mut(geom_key)
+            .ok_or_else(|| crate::engine_not_found!("galaxy3d::ResourceManager", "Geometry", format!("{:?}", geom_key)))?;
+
+        let geometry = Arc::get_mut(arc)
+            .ok_or_else(|| crate::engine_warn_err!("galaxy3d::ResourceManager", "Cannot mutate Geometry: other references exist"))?;
+
+        geometry.build_meshlets(config)
+    }
+
     // ===== SHADER CREATION =====
 
     /// Create a shader resource
@@ -530,6 +982,7 @@ impl ResourceManager {
             code: desc.code,
             stage: desc.stage,
             entry_point: desc.entry_point,
+            debug_name: Some(name.clone()),
         })?;
         let shader = Shader::from_gpu_shader(gd_shader, desc.stage);
 
@@ -565,6 +1018,95 @@ impl ResourceManager {
         self.shaders.len()
     }
 
+    // ===== SHADER HOT-RELOAD =====
+
+    /// Replace the code of a shader and recreate every pipeline using it.
+    ///
+    /// Keys are kept: the shader and the recreated pipelines are swapped in
+    /// place, so materials, render instance pipeline caches and the drawer
+    /// (which looks pipelines up by key every frame) pick them up on their
+    /// next use. Objects holding a GPU pipeline directly (fullscreen
+    /// actions, post effects, debug draw) must be rebuilt by the caller from
+    /// `ShaderReloadReport::recreated_pipelines`. Binding groups are created
+    /// from layouts and stay valid.
+    ///
+    /// Nothing is replaced when the shader or any pipeline fails to build:
+    /// the previous version keeps running.
+    pub fn reload_shader(
+        &mut self,
+        key: ShaderKey,
+        desc: crate::resource::shader::ShaderDesc,
+        graphics_device: &mut dyn graphics_device::GraphicsDevice,
+    ) -> Result<ShaderReloadReport> {
+        let start = Instant::now();
+        let name = self.shader_names.iter()
+            .find(|(_, k)| **k == key)
+            .map(|(name, _)| name.clone())
+            .ok_or_else(|| crate::engine_not_found!("galaxy3d::ResourceManager",
+                "Shader", format!("{:?}", key)))?;
+        let old_stage = self.shaders[key].stage();
+        if desc.stage != old_stage {
+            crate::engine_error!("galaxy3d::ResourceManager",
+                "Reloading shader '{}': stage {:?} does not match {:?}", name, desc.stage, old_stage);
+            return Err(Error::Incompatible {
+                expected: format!("{:?} shader", old_stage),
+                found: format!("{:?} shader", desc.stage),
+            });
+        }
+
+        let gd_shader = graphics_device.create_shader(graphics_device::ShaderDesc {
+            code: desc.code,
+            stage: desc.stage,
+            entry_point: desc.entry_point,
+            debug_name: Some(name.clone()),
+        }).with_context(|| format!("Reloading shader '{}'", name))?;
+        let shader = Arc::new(Shader::from_gpu_shader(gd_shader, desc.stage));
+
+        // Build every dependent pipeline before committing anything
+        let mut rebuilt = Vec::new();
+        for (pipeline_name, &pipeline_key) in &self.pipeline_names {
+            let old = &self.pipelines[pipeline_key];
+            if old.vertex_shader() != key && old.fragment_shader() != key {
+                continue;
+            }
+            let shader_of = |stage_key: ShaderKey| {
+                if stage_key == key { &shader } else { &self.shaders[stage_key] }
+            };
+            let gd_pipeline = graphics_device.create_pipeline(
+                old.desc().clone(),
+                shader_of(old.vertex_shader()).graphics_device_shader(),
+                shader_of(old.fragment_shader()).graphics_device_shader(),
+            ).with_context(|| format!("Reloading shader '{}': recreating pipeline '{}'", name, pipeline_name))?;
+            rebuilt.push((pipeline_name.clone(), pipeline_key, gd_pipeline));
+        }
+
+        // Commit
+        self.shaders[key] = shader;
+        let mut recreated_pipelines = Vec::with_capacity(rebuilt.len());
+        for (pipeline_name, pipeline_key, gd_pipeline) in rebuilt {
+            let signature_key = graphics_device::PipelineSignatureKey::from_reflection(
+                gd_pipeline.reflection(),
+            );
+            let signature_id = self.get_or_assign_pipeline_signature_id(signature_key)?;
+            let old = &self.pipelines[pipeline_key];
+            let pipeline = Pipeline::from_gpu_pipeline(
+                gd_pipeline,
+                old.vertex_shader(),
+                old.fragment_shader(),
+                old.desc().clone(),
+                signature_id,
+                old.sort_id(),
+            );
+            self.pipelines[pipeline_key] = Arc::new(pipeline);
+            recreated_pipelines.push(pipeline_name);
+        }
+        recreated_pipelines.sort();
+
+        let report = ShaderReloadReport { shader: name, recreated_pipelines, duration: start.elapsed() };
+        crate::engine_info!("galaxy3d::ResourceManager", "{}", report);
+        Ok(report)
+    }
+
     // ===== PIPELINE CREATION =====
 
     /// Create a pipeline resource
@@ -580,13 +1122,18 @@ impl ResourceManager {
         if self.pipeline_names.contains_key(&name) {
             crate::engine_bail_warn!("galaxy3d::ResourceManager", "Pipeline '{}' already exists", name);
         }
+        for warning in graphics_device::blend_warnings(&desc.color_blend, Some(&desc.multisample), None) {
+            crate::engine_warn!("galaxy3d::ResourceManager", "Pipeline '{}': {}", name, warning);
+        }
 
         let vert = self.shaders.get(desc.vertex_shader)
-            .ok_or_else(|| crate::engine_err!("galaxy3d::ResourceManager",
-                "Pipeline '{}': vertex shader not found", name))?;
+            .ok_or_else(|| crate::engine_not_found!("galaxy3d::ResourceManager",
+                "Vertex shader", format!("{:?}", desc.vertex_shader)))
+            .with_context(|| format!("Creating pipeline '{}'", name))?;
         let frag = self.shaders.get(desc.fragment_shader)
-            .ok_or_else(|| crate::engine_err!("galaxy3d::ResourceManager",
-                "Pipeline '{}': fragment shader not found", name))?;
+            .ok_or_else(|| crate::engine_not_found!("galaxy3d::ResourceManager",
+                "Fragment shader", format!("{:?}", desc.fragment_shader)))
+            .with_context(|| format!("Creating pipeline '{}'", name))?;
 
         let gd_desc = graphics_device::PipelineDesc {
             vertex_layout: desc.vertex_layout,
@@ -596,10 +1143,13 @@ impl ResourceManager {
             multisample: desc.multisample,
             color_formats: desc.color_formats,
             depth_format: desc.depth_format,
+            engine_features: desc.engine_features,
+            debug_name: Some(name.clone()),
+            dynamic_uniform_buffers: Vec::new(),
         };
 
         let gd_pipeline = graphics_device.create_pipeline(
-            gd_desc,
+            gd_desc.clone(),
             vert.graphics_device_shader(),
             frag.graphics_device_shader(),
         )?;
@@ -614,10 +1164,12 @@ impl ResourceManager {
             gd_pipeline,
             desc.vertex_shader,
             desc.fragment_shader,
+            gd_desc,
             signature_id,
             sort_id,
         );
 
+        self.add_dependencies(&pipeline_dependencies(&pipeline));
         let key = self.pipelines.insert(Arc::new(pipeline));
         self.pipeline_names.insert(name.clone(), key);
 
@@ -627,6 +1179,43 @@ impl ResourceManager {
         Ok(key)
     }
 
+    /// Create a depth-only variant of an existing pipeline.
+    ///
+    /// Same shaders (an alpha-testing fragment shader keeps discarding) and
+    /// depth state, no color attachments and no color writes. Use it for
+    /// shadow maps or a depth pre-pass render pass without maintaining a
+    /// parallel pipeline definition.
+    pub fn create_depth_only_pipeline(
+        &mut self,
+        name: String,
+        source: PipelineKey,
+        graphics_device: &mut dyn graphics_device::GraphicsDevice,
+    ) -> Result<PipelineKey> {
+        let source = self.pipelines.get(source)
+            .ok_or_else(|| crate::engine_not_found!("galaxy3d::ResourceManager",
+                "Pipeline", format!("{:?}", source)))
+            .with_context(|| format!("Creating depth-only pipeline '{}'", name))?;
+        let depth_desc = source.desc().depth_only();
+        if depth_desc.depth_format.is_none() {
+            crate::engine_bail!("galaxy3d::ResourceManager",
+                "Depth-only pipeline '{}': source pipeline has no depth format", name);
+        }
+
+        let desc = PipelineDesc {
+            vertex_shader: source.vertex_shader(),
+            fragment_shader: source.fragment_shader(),
+            vertex_layout: depth_desc.vertex_layout,
+            topology: depth_desc.topology,
+            rasterization: depth_desc.rasterization,
+            color_blend: depth_desc.color_blend,
+            multisample: depth_desc.multisample,
+            color_formats: depth_desc.color_formats,
+            depth_format: depth_desc.depth_format,
+            engine_features: depth_desc.engine_features,
+        };
+        self.create_pipeline(name, desc, graphics_device)
+    }
+
     // ===== PIPELINE ACCESS =====
 
     /// Get a pipeline by key
@@ -648,7 +1237,9 @@ impl ResourceManager {
     /// Remove a pipeline by name
     pub fn remove_pipeline(&mut self, name: &str) -> bool {
         if let Some(key) = self.pipeline_names.remove(name) {
-            self.pipelines.remove(key);
+            if let Some(pipeline) = self.pipelines.remove(key) {
+                self.release_dependencies(pipeline_dependencies(&pipeline));
+            }
             crate::engine_info!("galaxy3d::ResourceManager", "Removed Pipeline resource '{}'", name);
             true
         } else {
@@ -669,6 +1260,9 @@ impl ResourceManager {
     /// with this exact combination already exists in the cache, returns its key.
     /// Otherwise, creates a new `resource::Pipeline` (stored in the same SlotMap
     /// as manual pipelines) and caches the mapping.
+    ///
+    /// `engine_features` selects the pipeline variant: pass the active mask
+    /// restricted to the features the material pass declares.
     pub fn resolve_pipeline(
         &mut self,
         vertex_shader: ShaderKey,
@@ -678,6 +1272,7 @@ impl ResourceManager {
         color_blend: &graphics_device::ColorBlendState,
         polygon_mode: graphics_device::PolygonMode,
         pass_info: &PassInfo,
+        engine_features: graphics_device::EngineFeatures,
         graphics_device: &mut dyn graphics_device::GraphicsDevice,
     ) -> Result<PipelineKey> {
         let cache_key = PipelineCacheKey {
@@ -690,6 +1285,7 @@ impl ResourceManager {
             color_formats: pass_info.color_formats.clone(),
             depth_format: pass_info.depth_format,
             sample_count: pass_info.sample_count,
+            engine_features,
         };
 
         // Cache hit — pipeline already exists
@@ -725,6 +1321,7 @@ impl ResourceManager {
                 },
                 color_formats: cache_key.color_formats.clone(),
                 depth_format: cache_key.depth_format,
+                engine_features: cache_key.engine_features,
             },
             graphics_device,
         )?;
@@ -733,6 +1330,84 @@ impl ResourceManager {
         Ok(pipeline_key)
     }
 
+    // ===== ENGINE FEATURES =====
+
+    /// Set the active engine feature mask.
+    ///
+    /// Pipelines are not rebuilt here: the generation bump makes the drawer
+    /// re-resolve every cached pipeline on its next draw, creating only the
+    /// variants missing from the pipeline cache (previous variants are kept,
+    /// so toggling a feature back is free). Returns false if unchanged.
+    pub fn set_engine_features(&mut self, features: graphics_device::EngineFeatures) -> bool {
+        if features == self.engine_features {
+            return false;
+        }
+        crate::engine_info!("galaxy3d::ResourceManager",
+            "Engine features changed {:#x} -> {:#x}, pipelines will be re-resolved",
+            self.engine_features.bits(), features.bits());
+        self.engine_features = features;
+        self.engine_features_generation += 1;
+        true
+    }
+
+    /// Active engine feature mask
+    pub fn engine_features(&self) -> graphics_device::EngineFeatures {
+        self.engine_features
+    }
+
+    /// Generation of the engine feature mask and debug view (0 until the
+    /// first change)
+    pub fn engine_features_generation(&self) -> u64 {
+        self.engine_features_generation
+    }
+
+    /// Switch the debug visualization of every drawer.
+    ///
+    /// Like `set_engine_features()`, the generation bump makes the drawers
+    /// re-resolve their pipelines into the debug variants (created once,
+    /// then kept in the pipeline cache). Returns false if unchanged.
+    pub fn set_debug_view(&mut self, mode: graphics_device::DebugViewMode) -> bool {
+        if mode == self.debug_view {
+            return false;
+        }
+        crate::engine_info!("galaxy3d::ResourceManager",
+            "Debug view changed {} -> {}, pipelines will be re-resolved",
+            self.debug_view.name(), mode.name());
+        self.debug_view = mode;
+        self.engine_features_generation += 1;
+        true
+    }
+
+    /// Active debug visualization
+    pub fn debug_view(&self) -> graphics_device::DebugViewMode {
+        self.debug_view
+    }
+
+    // ===== PLATFORM PROFILE =====
+
+    /// Apply the clamps of a platform profile (usually
+    /// `Config::platform_profile`) to the textures and shadow maps created
+    /// from now on. Existing textures keep their size.
+    pub fn set_platform_profile(&mut self, profile: graphics_device::PlatformProfile) {
+        self.set_platform_limits(profile.limits());
+    }
+
+    /// Same as `set_platform_profile()` with custom clamps
+    pub fn set_platform_limits(&mut self, limits: graphics_device::PlatformLimits) {
+        crate::engine_info!("galaxy3d::ResourceManager", "Platform limits set to {:?}", limits);
+        self.platform_limits = limits;
+    }
+
+    /// Active platform clamps (Desktop until set)
+    pub fn platform_limits(&self) -> &graphics_device::PlatformLimits {
+        &self.platform_limits
+    }
+
+    /// Shadow map resolution to allocate for a `requested` one
+    pub fn shadow_resolution(&self, requested: u32) -> u32 {
+        self.platform_limits.clamp_shadow_resolution(requested)
+    }
+
     // ===== MATERIAL CREATION =====
 
     /// Create a material resource and register it
@@ -748,6 +1423,12 @@ impl ResourceManager {
 
         let slot_id = self.material_slot_allocator.alloc();
         let mut material = Material::from_desc(slot_id, desc, &*self, graphics_device)?;
+        for pass in material.passes() {
+            for warning in graphics_device::blend_warnings(pass.color_blend(), None, Some(pass.render_state())) {
+                crate::engine_warn!("galaxy3d::ResourceManager",
+                    "Material '{}' pass {}: {}", name, pass.pass_type(), warning);
+            }
+        }
 
         // Assign a stable signature id to every pass's render state.
         // Deduplicated across Materials via the ResourceManager-wide registry.
@@ -763,8 +1444,10 @@ impl ResourceManager {
         let texture_count = material.total_texture_slot_count();
         let param_count = material.total_param_count();
 
+        self.add_dependencies(&material_dependencies(&material));
         let key = self.materials.insert(Arc::new(material));
         self.material_names.insert(name.clone(), key);
+        self.dirty_materials.push(key);
 
         crate::engine_info!("galaxy3d::ResourceManager",
             "Created Material resource '{}' slot {} ({} texture slot{}, {} param{})",
@@ -794,14 +1477,23 @@ impl ResourceManager {
     }
 
     /// Remove a material by name
+    ///
+    /// A material still used by a mesh is only unregistered: it keeps its
+    /// slot and is retired when its last mesh is removed (see
+    /// `dependent_count()`).
     pub fn remove_material(&mut self, name: &str) -> bool {
-        if let Some(key) = self.material_names.remove(name) {
-            if let Some(material) = self.materials.remove(key) {
-                self.material_slot_allocator.free(material.slot_id());
-                crate::engine_info!("galaxy3d::ResourceManager",
-                    "Removed Material resource '{}' (freed slot {})", name, material.slot_id());
-                return true;
-            }
+        let Some(key) = self.material_names.remove(name) else {
+            return false;
+        };
+        if self.defer_removal(resource_id(ResourceKind::Material, key)) {
+            return true;
+        }
+        if let Some(material) = self.materials.remove(key) {
+            self.material_slot_allocator.free(material.slot_id());
+            self.release_dependencies(material_dependencies(&material));
+            crate::engine_info!("galaxy3d::ResourceManager",
+                "Removed Material resource '{}' (freed slot {})", name, material.slot_id());
+            return true;
         }
         false
     }
@@ -821,6 +1513,60 @@ impl ResourceManager {
         self.material_slot_allocator.len()
     }
 
+    // ===== MATERIAL MODIFICATION =====
+
+    /// Change a material param (see `Material::set_param()`).
+    ///
+    /// The new value reaches the GPU with the next `sync_dirty_materials()`.
+    pub fn set_material_param(&mut self, key: MaterialKey, name: &str, value: ParamValue) -> Result<()> {
+        let material = self.material_mut(key)?;
+        let was_dirty = material.is_dirty();
+        material.set_param(name, value)?;
+        if !was_dirty {
+            self.dirty_materials.push(key);
+        }
+        Ok(())
+    }
+
+    /// Rebind an existing texture slot of a material (`desc.name`).
+    ///
+    /// The descriptor is resolved as at creation (layer, region, flipbook,
+    /// fallback texture). The new binding reaches the GPU with the next
+    /// `sync_dirty_materials()`.
+    pub fn set_material_texture_slot(&mut self, key: MaterialKey, desc: MaterialTextureSlotDesc) -> Result<()> {
+        let material = self.materials.get(key)
+            .ok_or_else(|| crate::engine_not_found!("galaxy3d::ResourceManager", "Material", format!("{:?}", key)))?;
+        let pass_type = material.texture_slot_pass_type(&desc.name)
+            .ok_or_else(|| crate::engine_err!("galaxy3d::ResourceManager",
+                "Material has no texture slot '{}'", desc.name))?;
+        let slot = MaterialTextureSlot::from_desc(desc, pass_type, &*self)?;
+
+        let material = self.material_mut(key)?;
+        let was_dirty = material.is_dirty();
+        let previous = material_dependencies(material);
+        material.set_texture_slot(slot)?;
+        let current = material_dependencies(material);
+        if !was_dirty {
+            self.dirty_materials.push(key);
+        }
+        self.add_dependencies(&current);
+        self.release_dependencies(previous);
+        Ok(())
+    }
+
+    /// Number of materials waiting for `sync_dirty_materials()`
+    pub fn dirty_material_count(&self) -> usize {
+        self.dirty_materials.len()
+    }
+
+    fn material_mut(&mut self, key: MaterialKey) -> Result<&mut Material> {
+        let arc = self.materials.get_mut(key)
+            .ok_or_else(|| crate::engine_not_found!("galaxy3d::ResourceManager", "Material", format!("{:?}", key)))?;
+
+        Arc::get_mut(arc)
+            .ok_or_else(|| crate::engine_warn_err!("galaxy3d::ResourceManager", "Cannot mutate material: other references exist"))
+    }
+
     // ===== MATERIAL SYNC =====
 
     /// Sync all material parameters into a GPU buffer.
@@ -828,83 +1574,167 @@ impl ResourceManager {
     /// For each material, matches params by name against buffer fields.
     /// Copies values only when name AND type match. Non-blocking warnings
     /// for mismatches (the function never fails on a mismatch).
+    ///
+    /// Use it to fill a new buffer. Per frame, `sync_dirty_materials()`
+    /// only writes the materials that changed.
     pub fn sync_materials_to_buffer(&self, buffer: &Buffer) -> Result<()> {
         for (_, material) in &self.materials {
-            let slot_id = material.slot_id();
+            self.write_material(buffer, material)?;
+        }
+        Ok(())
+    }
 
-            if slot_id >= buffer.count() {
-                crate::engine_warn!("galaxy3d::ResourceManager",
-                    "sync_materials: material slot_id {} exceeds buffer count {}",
-                    slot_id, buffer.count());
+    /// Write the materials created or changed since the last call into a
+    /// GPU buffer (same matching rules as `sync_materials_to_buffer()`).
+    ///
+    /// Returns the number of materials written. Call once per frame, before
+    /// the buffer is read by the GPU. A buffer owned by the manager is
+    /// passed as a clone of its `Arc` (`rm.buffer(key).cloned()`).
+    pub fn sync_dirty_materials(&mut self, buffer: &Buffer) -> Result<usize> {
+        let keys = std::mem::take(&mut self.dirty_materials);
+        let mut written = 0;
+        for (index, &key) in keys.iter().enumerate() {
+            // Removed since it was changed
+            let Some(material) = self.materials.get(key) else {
                 continue;
+            };
+            if let Err(err) = self.write_material(buffer, material) {
+                // Retry the unwritten materials on the next sync
+                self.dirty_materials.extend_from_slice(&keys[index..]);
+                return Err(err);
+            }
+            match self.materials.get_mut(key).and_then(Arc::get_mut) {
+                Some(material) => material.clear_dirty(),
+                // Shared outside the manager: written again on the next sync
+                None => self.dirty_materials.push(key),
             }
+            written += 1;
+        }
+        Ok(written)
+    }
 
-            for param in material.iter_all_params() {
-                // 1. Find field by name
-                let field_index = match buffer.field_id(param.name()) {
-                    Some(idx) => idx,
-                    None => {
-                        crate::engine_warn!("galaxy3d::ResourceManager",
-                            "sync_materials: param '{}' not found in buffer layout",
-                            param.name());
-                        continue;
-                    }
-                };
+    /// Write the params and texture slots of one material into its slot
+    fn write_material(&self, buffer: &Buffer, material: &Material) -> Result<()> {
+        let slot_id = material.slot_id();
 
-                // 2. Check type compatibility
-                let field_type = buffer.fields()[field_index].field_type;
-                let param_type = compatible_field_type(param.value());
+        if slot_id >= buffer.count() {
+            crate::engine_warn!("galaxy3d::ResourceManager",
+                "sync_materials: material slot_id {} exceeds buffer count {}",
+                slot_id, buffer.count());
+            return Ok(());
+        }
 
-                if param_type != field_type {
+        for param in material.iter_all_params() {
+            // 1. Find field by name
+            let field_index = match buffer.field_id(param.name()) {
+                Some(idx) => idx,
+                None => {
                     crate::engine_warn!("galaxy3d::ResourceManager",
-                        "sync_materials: param '{}' type mismatch (param: {:?}, field: {:?})",
-                        param.name(), param_type, field_type);
+                        "sync_materials: param '{}' not found in buffer layout",
+                        param.name());
                     continue;
                 }
+            };
 
-                // 3. Specific Bool→UInt info warning
-                if matches!(param.value(), ParamValue::Bool(_)) {
-                    crate::engine_warn!("galaxy3d::ResourceManager",
-                        "sync_materials: param '{}' is Bool, \
-                         mapped to UInt field (GLSL convention)",
-                        param.name());
-                }
+            // 2. Check type compatibility
+            let field_type = buffer.fields()[field_index].field_type;
+            let param_type = compatible_field_type(param.value());
 
-                // 4. Convert to padded bytes and write
-                let bytes = param_to_padded_bytes(param.value());
-                buffer.update_field(slot_id, field_index, &bytes)?;
+            if param_type != field_type {
+                crate::engine_warn!("galaxy3d::ResourceManager",
+                    "sync_materials: param '{}' type mismatch (param: {:?}, field: {:?})",
+                    param.name(), param_type, field_type);
+                continue;
             }
 
-            // ===== TEXTURE SLOTS → BUFFER FIELDS (bindless index, sampler index, layer) =====
-            // Convention: slot name "albedo" maps to fields "albedoTexture", "albedoSampler", "albedoLayer"
-            for slot in material.iter_all_texture_slots() {
-                let slot_name = slot.name();
+            // 3. Specific Bool→UInt info warning
+            if matches!(param.value(), ParamValue::Bool(_)) {
+                crate::engine_warn!("galaxy3d::ResourceManager",
+                    "sync_materials: param '{}' is Bool, \
+                     mapped to UInt field (GLSL convention)",
+                    param.name());
+            }
 
-                // Write bindless texture index → "{name}Texture"
-                let tex_field_name = format!("{}Texture", slot_name);
-                if let Some(field_index) = buffer.field_id(&tex_field_name) {
-                    let value = slot.bindless_index();
-                    buffer.update_field(slot_id, field_index, &value.to_ne_bytes())?;
-                }
+            // 4. Convert to padded bytes and write
+            let bytes = param_to_padded_bytes(param.value());
+            buffer.update_field(slot_id, field_index, &bytes)?;
+        }
 
-                // Write sampler index → "{name}Sampler"
-                let sampler_field_name = format!("{}Sampler", slot_name);
-                if let Some(field_index) = buffer.field_id(&sampler_field_name) {
-                    let value = slot.sampler_index();
-                    buffer.update_field(slot_id, field_index, &value.to_ne_bytes())?;
-                }
+        // ===== TEXTURE SLOTS → BUFFER FIELDS (bindless index, sampler index, layer) =====
+        // Convention: slot name "albedo" maps to fields "albedoTexture", "albedoSampler", "albedoLayer"
+        for slot in material.iter_all_texture_slots() {
+            let slot_name = slot.name();
 
-                // Write layer index → "{name}Layer"
-                let layer_field_name = format!("{}Layer", slot_name);
-                if let Some(field_index) = buffer.field_id(&layer_field_name) {
-                    let value: u32 = slot.layer().unwrap_or(0);
-                    buffer.update_field(slot_id, field_index, &value.to_ne_bytes())?;
-                }
+            // Write bindless texture index → "{name}Texture"
+            let tex_field_name = format!("{}Texture", slot_name);
+            if let Some(field_index) = buffer.field_id(&tex_field_name) {
+                let value = slot.bindless_index();
+                buffer.update_field(slot_id, field_index, &value.to_ne_bytes())?;
+            }
+
+            // Write sampler index → "{name}Sampler"
+            let sampler_field_name = format!("{}Sampler", slot_name);
+            if let Some(field_index) = buffer.field_id(&sampler_field_name) {
+                let value = slot.sampler_index();
+                buffer.update_field(slot_id, field_index, &value.to_ne_bytes())?;
+            }
+
+            // Write layer index → "{name}Layer"
+            let layer_field_name = format!("{}Layer", slot_name);
+            if let Some(field_index) = buffer.field_id(&layer_field_name) {
+                let value: u32 = slot.layer().unwrap_or(0);
+                buffer.update_field(slot_id, field_index, &value.to_ne_bytes())?;
+            }
+
+            // Write region UV rectangle → "{name}UvRect" (first flipbook frame)
+            self.write_slot_uv_rect(buffer, slot_id, slot, 0.0)?;
+        }
+        Ok(())
+    }
+
+    /// Write the current flipbook frame of every animated texture slot
+    /// into a GPU buffer, as the `{name}UvRect` UV rectangle.
+    ///
+    /// Call once per frame after `sync_materials_to_buffer()`, with the
+    /// time written to the frame uniform `time` field. Slots without a
+    /// flipbook are left untouched.
+    pub fn sync_material_animations(&self, buffer: &Buffer, time: f32) -> Result<()> {
+        for (_, material) in &self.materials {
+            // Out-of-range slots are reported by sync_materials_to_buffer
+            if material.slot_id() >= buffer.count() {
+                continue;
+            }
+            for slot in material.iter_all_texture_slots().filter(|slot| slot.flipbook().is_some()) {
+                self.write_slot_uv_rect(buffer, material.slot_id(), slot, time)?;
             }
         }
         Ok(())
     }
 
+    /// Write the UV rectangle of the region a slot shows at `time` into
+    /// its `{name}UvRect` Vec4 field, if the buffer has one. Slots without
+    /// region cover the whole layer.
+    fn write_slot_uv_rect(&self, buffer: &Buffer, slot_id: u32, slot: &MaterialTextureSlot, time: f32) -> Result<()> {
+        let field_name = format!("{}{}", slot.name(), UV_RECT_FIELD_SUFFIX);
+        let Some(field_index) = buffer.field_id(&field_name) else {
+            return Ok(());
+        };
+        if buffer.fields()[field_index].field_type != FieldType::Vec4 {
+            return Ok(());
+        }
+
+        let uv_rect = slot.region_at(time)
+            .and_then(|region| {
+                let texture = self.texture(slot.texture())?;
+                let info = texture.graphics_device_texture().info();
+                let region = texture.layer(slot.layer().unwrap_or(0))?.region(region)?;
+                Some(region.uv_rect(info.width, info.height))
+            })
+            .unwrap_or(FULL_UV_RECT);
+        let bytes: Vec<u8> = uv_rect.iter().flat_map(|v| v.to_ne_bytes()).collect();
+        buffer.update_field(slot_id, field_index, &bytes)
+    }
+
     // ===== MESH CREATION =====
 
     /// Create a mesh resource and register it
@@ -915,6 +1745,7 @@ impl ResourceManager {
 
         let mesh = Mesh::from_desc(desc, &*self)?;
         let submesh_count = mesh.submesh_count();
+        self.add_dependencies(&mesh_dependencies(&mesh));
 
         let key = self.meshes.insert(Arc::new(mesh));
         self.mesh_names.insert(name.clone(), key);
@@ -947,7 +1778,9 @@ impl ResourceManager {
     /// Remove a mesh by name
     pub fn remove_mesh(&mut self, name: &str) -> bool {
         if let Some(key) = self.mesh_names.remove(name) {
-            self.meshes.remove(key);
+            if let Some(mesh) = self.meshes.remove(key) {
+                self.release_dependencies(mesh_dependencies(&mesh));
+            }
             crate::engine_info!("galaxy3d::ResourceManager", "Removed Mesh resource '{}'", name);
             true
         } else {
@@ -968,7 +1801,7 @@ impl ResourceManager {
             crate::engine_bail_warn!("galaxy3d::ResourceManager", "Buffer '{}' already exists", name);
         }
 
-        let buffer = Buffer::from_desc(desc)?;
+        let buffer = Buffer::from_desc(desc, &name)?;
         let kind = buffer.kind();
         let count = buffer.count();
         let stride = buffer.stride();
@@ -1006,6 +1839,7 @@ impl ResourceManager {
     pub fn remove_buffer(&mut self, name: &str) -> bool {
         if let Some(key) = self.buffer_names.remove(name) {
             self.buffers.remove(key);
+            self.buffer_pins.remove(&key);
             crate::engine_info!("galaxy3d::ResourceManager", "Removed Buffer resource '{}'", name);
             true
         } else {
@@ -1018,15 +1852,706 @@ impl ResourceManager {
         self.buffers.len()
     }
 
+    // ===== BULK REMOVAL =====
+
+    /// Remove several resources of one type by name in a single pass.
+    ///
+    /// Unknown names are skipped. Unlike the single-item `remove_*` methods,
+    /// the removed resources are retired instead of dropped: their GPU
+    /// objects are destroyed by `release_retired_resources()`. Referenced
+    /// resources are deferred as with `remove_geometry()`.
+    /// Returns the number of resources removed.
+    pub fn remove_many<S: AsRef<str>>(&mut self, kind: ResourceKind, names: &[S]) -> usize {
+        let retired = &mut self.retired_resources;
+        let counts = &self.dependency_counts;
+        let pending = &mut self.pending_removals;
+        let mut released = Vec::new();
+        let count = match kind {
+            ResourceKind::Texture => {
+                let (remaining, deferred) = defer_referenced(kind, &mut self.texture_names, names, counts, pending);
+                let removed = take_named(&mut self.texture_names, &mut self.textures, &remaining);
+                for (key, _) in &removed {
+                    self.texture_usage.remove(*key);
+                    self.texture_pins.remove(key);
+                }
+                let count = removed.len() + deferred;
+                retire(removed, retired);
+                count
+            }
+            ResourceKind::Geometry => {
+                let (remaining, deferred) = defer_referenced(kind, &mut self.geometry_names, names, counts, pending);
+                let removed = take_named(&mut self.geometry_names, &mut self.geometries, &remaining);
+                let count = removed.len() + deferred;
+                retire(removed, retired);
+                count
+            }
+            ResourceKind::Shader => {
+                let (remaining, deferred) = defer_referenced(kind, &mut self.shader_names, names, counts, pending);
+                let removed = take_named(&mut self.shader_names, &mut self.shaders, &remaining);
+                let count = removed.len() + deferred;
+                retire(removed, retired);
+                count
+            }
+            ResourceKind::Pipeline => {
+                let removed = take_named(&mut self.pipeline_names, &mut self.pipelines, names);
+                if !removed.is_empty() {
+                    // Drop cache entries pointing at the removed pipelines
+                    let pipelines = &self.pipelines;
+                    self.pipeline_cache.retain(|_, key| pipelines.contains_key(*key));
+                }
+                released.extend(removed.iter().flat_map(|(_, pipeline)| pipeline_dependencies(pipeline)));
+                let count = removed.len();
+                retire(removed, retired);
+                count
+            }
+            ResourceKind::Material => {
+                let (remaining, deferred) = defer_referenced(kind, &mut self.material_names, names, counts, pending);
+                let removed = take_named(&mut self.material_names, &mut self.materials, &remaining);
+                for (_, material) in &removed {
+                    self.material_slot_allocator.free(material.slot_id());
+                    released.extend(material_dependencies(material));
+                }
+                let count = removed.len() + deferred;
+                retire(removed, retired);
+                count
+            }
+            ResourceKind::Mesh => {
+                let removed = take_named(&mut self.mesh_names, &mut self.meshes, names);
+                released.extend(removed.iter().flat_map(|(_, mesh)| mesh_dependencies(mesh)));
+                let count = removed.len();
+                retire(removed, retired);
+                count
+            }
+            ResourceKind::Buffer => {
+                let removed = take_named(&mut self.buffer_names, &mut self.buffers, names);
+                for (key, _) in &removed {
+                    self.buffer_pins.remove(key);
+                }
+                let count = removed.len();
+                retire(removed, retired);
+                count
+            }
+        };
+        self.release_dependencies(released);
+        crate::engine_info!("galaxy3d::ResourceManager",
+            "Removed {} {:?} resources ({} requested)", count, kind, names.len());
+        count
+    }
+
+    /// Remove every resource (level unload).
+    ///
+    /// The removed resources are retired (see `release_retired_resources()`).
+    /// Material slots, the pipeline cache, texture usage records, pins,
+    /// dependency counts and the pipeline/geometry sort ids are reset. Engine features and signature
+    /// ids are kept.
+    pub fn clear(&mut self) {
+        fn drain<K: slotmap::Key, T: Send + Sync + 'static>(
+            names: &mut FxHashMap<String, K>,
+            storage: &mut SlotMap<K, Arc<T>>,
+            retired: &mut Vec<RetiredResource>,
+        ) -> usize {
+            names.clear();
+            let count = storage.len();
+            retired.extend(storage.drain().map(|(_, res)| res as RetiredResource));
+            count
+        }
+
+        let retired = &mut self.retired_resources;
+        let count = drain(&mut self.texture_names, &mut self.textures, retired)
+            + drain(&mut self.geometry_names, &mut self.geometries, retired)
+            + drain(&mut self.shader_names, &mut self.shaders, retired)
+            + drain(&mut self.pipeline_names, &mut self.pipelines, retired)
+            + drain(&mut self.material_names, &mut self.materials, retired)
+            + drain(&mut self.mesh_names, &mut self.meshes, retired)
+            + drain(&mut self.buffer_names, &mut self.buffers, retired);
+
+        self.material_slot_allocator = SlotAllocator::new();
+        self.dirty_materials.clear();
+        self.pipeline_cache.clear();
+        self.texture_usage.clear();
+        self.texture_pins.clear();
+        self.buffer_pins.clear();
+        self.dependency_counts.clear();
+        self.pending_removals.clear();
+        self.next_pipeline_sort_id = 0;
+        self.next_geometry_sort_id = 0;
+        self.default_resources = None;
+        self.standard_pbr = None;
+        #[cfg(feature = "manifest")]
+        {
+            self.manifest_records = Default::default();
+        }
+
+        crate::engine_info!("galaxy3d::ResourceManager", "Cleared {} resources", count);
+    }
+
+    /// Number of resources retired by `clear()` / `remove_many()` and not
+    /// released yet.
+    pub fn retired_resource_count(&self) -> usize {
+        self.retired_resources.len()
+    }
+
+    /// Drop the retired resources, destroying their GPU objects if nothing
+    /// else references them.
+    ///
+    /// Call once the GPU no longer uses them, e.g. after the next
+    /// `wait_for_previous_submit()` or `wait_idle()`.
+    /// Returns the number of resources released.
+    pub fn release_retired_resources(&mut self) -> usize {
+        let count = self.retired_resources.len();
+        self.retired_resources.clear();
+        count
+    }
+
+    // ===== RESIDENCY PINNING =====
+
+    /// Pin a texture resident: eviction must never pick it, whatever the
+    /// memory pressure (UI atlas, assets of the current cutscene).
+    ///
+    /// Pins are counted, each `pin_texture()` needs its `unpin_texture()`.
+    /// Pinned textures are left out of `TextureUsageReport::idle_for()`.
+    /// Returns the new pin count.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the texture does not exist.
+    pub fn pin_texture(&mut self, key: TextureKey) -> Result<u32> {
+        if !self.textures.contains_key(key) {
+            crate::engine_bail_warn!("galaxy3d::ResourceManager", "Cannot pin texture {:?}: not found", key);
+        }
+        let count = self.texture_pins.entry(key).or_insert(0);
+        *count += 1;
+        Ok(*count)
+    }
+
+    /// Release one pin of a texture. Returns false if it was not pinned.
+    pub fn unpin_texture(&mut self, key: TextureKey) -> bool {
+        release_count(&mut self.texture_pins, key)
+    }
+
+    /// Number of pins held on a texture (0 = evictable)
+    pub fn texture_pin_count(&self, key: TextureKey) -> u32 {
+        self.texture_pins.get(&key).copied().unwrap_or(0)
+    }
+
+    /// Pin a buffer resident (see `pin_texture()`). Returns the new pin count.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer does not exist.
+    pub fn pin_buffer(&mut self, key: BufferKey) -> Result<u32> {
+        if !self.buffers.contains_key(key) {
+            crate::engine_bail_warn!("galaxy3d::ResourceManager", "Cannot pin buffer {:?}: not found", key);
+        }
+        let count = self.buffer_pins.entry(key).or_insert(0);
+        *count += 1;
+        Ok(*count)
+    }
+
+    /// Release one pin of a buffer. Returns false if it was not pinned.
+    pub fn unpin_buffer(&mut self, key: BufferKey) -> bool {
+        release_count(&mut self.buffer_pins, key)
+    }
+
+    /// Number of pins held on a buffer (0 = evictable)
+    pub fn buffer_pin_count(&self, key: BufferKey) -> u32 {
+        self.buffer_pins.get(&key).copied().unwrap_or(0)
+    }
+
+    // ===== DEPENDENCIES =====
+
+    /// Number of resources referencing a registered resource: meshes for a
+    /// geometry or a material, materials for a texture, materials and
+    /// pipelines for a shader. 0 for unknown names and for the resource
+    /// types nothing references (meshes, pipelines, buffers).
+    ///
+    /// A referenced resource removed by name stays alive, without a name,
+    /// until its last dependent is removed; it is then retired (see
+    /// `release_retired_resources()`).
+    pub fn dependent_count(&self, kind: ResourceKind, name: &str) -> u32 {
+        let key = match kind {
+            ResourceKind::Texture => self.texture_names.get(name).map(|&k| resource_id(kind, k)),
+            ResourceKind::Geometry => self.geometry_names.get(name).map(|&k| resource_id(kind, k)),
+            ResourceKind::Shader => self.shader_names.get(name).map(|&k| resource_id(kind, k)),
+            ResourceKind::Material => self.material_names.get(name).map(|&k| resource_id(kind, k)),
+            ResourceKind::Pipeline | ResourceKind::Mesh | ResourceKind::Buffer => None,
+        };
+        key.and_then(|id| self.dependency_counts.get(&id).copied()).unwrap_or(0)
+    }
+
+    /// Number of resources removed while referenced and not retired yet
+    pub fn pending_removal_count(&self) -> usize {
+        self.pending_removals.len()
+    }
+
+    fn add_dependencies(&mut self, dependencies: &[ResourceId]) {
+        for &id in dependencies {
+            *self.dependency_counts.entry(id).or_insert(0) += 1;
+        }
+    }
+
+    /// Mark a resource removed by name pending if something references it.
+    /// Returns false if it can be dropped now.
+    fn defer_removal(&mut self, id: ResourceId) -> bool {
+        let Some(&count) = self.dependency_counts.get(&id) else {
+            return false;
+        };
+        self.pending_removals.insert(id);
+        crate::engine_info!("galaxy3d::ResourceManager",
+            "{:?} resource still referenced by {} resource(s), removal deferred", id.0, count);
+        true
+    }
+
+    /// Release the references of a removed resource, retiring the pending
+    /// removals left without dependents (and, in turn, their own
+    /// references).
+    fn release_dependencies(&mut self, dependencies: Vec<ResourceId>) {
+        let mut released = dependencies;
+        while let Some(id) = released.pop() {
+            release_count(&mut self.dependency_counts, id);
+            if !self.dependency_counts.contains_key(&id) && self.pending_removals.remove(&id) {
+                released.extend(self.retire_pending(id));
+            }
+        }
+    }
+
+    /// Retire a pending removal. Returns its own references.
+    fn retire_pending(&mut self, (kind, data): ResourceId) -> Vec<ResourceId> {
+        let retired = &mut self.retired_resources;
+        let mut dependencies = Vec::new();
+        match kind {
+            ResourceKind::Texture => {
+                let key = TextureKey::from(data);
+                if let Some(texture) = self.textures.remove(key) {
+                    self.texture_usage.remove(key);
+                    self.texture_pins.remove(&key);
+                    retired.push(texture);
+                }
+            }
+            ResourceKind::Geometry => {
+                if let Some(geometry) = self.geometries.remove(GeometryKey::from(data)) {
+                    retired.push(geometry);
+                }
+            }
+            ResourceKind::Shader => {
+                if let Some(shader) = self.shaders.remove(ShaderKey::from(data)) {
+                    retired.push(shader);
+                }
+            }
+            ResourceKind::Material => {
+                if let Some(material) = self.materials.remove(MaterialKey::from(data)) {
+                    self.material_slot_allocator.free(material.slot_id());
+                    dependencies = material_dependencies(&material);
+                    retired.push(material);
+                }
+            }
+            // Never referenced
+            ResourceKind::Pipeline | ResourceKind::Mesh | ResourceKind::Buffer => {}
+        }
+        crate::engine_info!("galaxy3d::ResourceManager", "Retired {:?} resource after its last dependent", kind);
+        dependencies
+    }
+
+    // ===== ENUMERATION =====
+
+    /// Iterate all textures (in no particular order)
+    pub fn textures(&self) -> impl Iterator<Item = ResourceEntry<'_, TextureKey, Texture>> {
+        entries(&self.texture_names, &self.textures)
+    }
+
+    /// Iterate all geometries (in no particular order)
+    pub fn geometries(&self) -> impl Iterator<Item = ResourceEntry<'_, GeometryKey, Geometry>> {
+        entries(&self.geometry_names, &self.geometries)
+    }
+
+    /// Iterate all shaders (in no particular order)
+    pub fn shaders(&self) -> impl Iterator<Item = ResourceEntry<'_, ShaderKey, Shader>> {
+        entries(&self.shader_names, &self.shaders)
+    }
+
+    /// Iterate all pipelines, including the variants created by
+    /// `resolve_pipeline()` (in no particular order)
+    pub fn pipelines(&self) -> impl Iterator<Item = ResourceEntry<'_, PipelineKey, Pipeline>> {
+        entries(&self.pipeline_names, &self.pipelines)
+    }
+
+    /// Iterate all materials (in no particular order)
+    pub fn materials(&self) -> impl Iterator<Item = ResourceEntry<'_, MaterialKey, Material>> {
+        entries(&self.material_names, &self.materials)
+    }
+
+    /// Iterate all meshes (in no particular order)
+    pub fn meshes(&self) -> impl Iterator<Item = ResourceEntry<'_, MeshKey, Mesh>> {
+        entries(&self.mesh_names, &self.meshes)
+    }
+
+    /// Iterate all buffers (in no particular order)
+    pub fn buffers(&self) -> impl Iterator<Item = ResourceEntry<'_, BufferKey, Buffer>> {
+        entries(&self.buffer_names, &self.buffers)
+    }
+
+    /// Summary statistics (counts, cache sizes, buffer memory)
+    pub fn stats(&self) -> ResourceStats {
+        ResourceStats {
+            textures: self.textures.len(),
+            geometries: self.geometries.len(),
+            shaders: self.shaders.len(),
+            pipelines: self.pipelines.len(),
+            materials: self.materials.len(),
+            meshes: self.meshes.len(),
+            buffers: self.buffers.len(),
+            cached_pipeline_variants: self.pipeline_cache.len(),
+            material_slots: self.material_slot_count(),
+            buffer_bytes: self.buffers.values().map(|buffer| buffer.size()).sum(),
+            retired_resources: self.retired_resources.len(),
+            externally_referenced: self.external_references().len(),
+            pinned_textures: self.texture_pins.len(),
+            pinned_buffers: self.buffer_pins.len(),
+            pinned_buffer_bytes: self.buffer_pins.keys()
+                .filter_map(|&key| self.buffers.get(key))
+                .map(|buffer| buffer.size())
+                .sum(),
+            pending_removals: self.pending_removals.len(),
+        }
+    }
+
+    // ===== DEFAULT RESOURCES =====
+
+    /// Create the fallback textures, error material and error cube
+    /// (see `resource::default_resources`).
+    ///
+    /// From then on, a missing material texture is replaced by the fallback
+    /// texture of its slot and a missing mesh material by the error material.
+    /// `clear()` removes them; call again after a clear.
+    pub fn create_default_resources(&mut self, desc: DefaultResourcesDesc) -> Result<DefaultResources> {
+        if self.default_resources.is_some() {
+            crate::engine_bail_warn!("galaxy3d::ResourceManager", "Default resources already exist");
+        }
+        let DefaultResourcesDesc { graphics_device, error_passes } = desc;
+
+        // 1x1 textures
+        let mut textures = Vec::with_capacity(3);
+        for (name, texel) in default_resources::default_texture_texels() {
+            textures.push(self.create_texture(name.to_string(), TextureDesc {
+                graphics_device: graphics_device.clone(),
+                texture: graphics_device::TextureDesc {
+                    width: 1,
+                    height: 1,
+                    depth: 1,
+                    format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
+                    usage: graphics_device::TextureUsage::Sampled,
+                    array_layers: 1,
+                    data: Some(graphics_device::TextureData::Single(texel.to_vec())),
+                    mipmap: graphics_device::MipmapMode::None,
+                    texture_type: graphics_device::TextureType::Tex2D,
+                    sample_count: graphics_device::SampleCount::S1,
+                    debug_name: Some(name.to_string()),
+                },
+                layers: vec![LayerDesc {
+                    name: "default".to_string(),
+                    layer_index: 0,
+                    data: None,
+                    regions: Vec::new(),
+                }],
+            })?);
+        }
+        let (white_texture, black_texture, normal_texture) = (textures[0], textures[1], textures[2]);
+
+        // Error material: magenta base color over the white texture
+        let passes = error_passes.into_iter()
+            .map(|(pass_type, fragment_shader)| MaterialPassDesc {
+                pass_type,
+                fragment_shader,
+                color_blend: graphics_device::ColorBlendState::default(),
+                polygon_mode: graphics_device::PolygonMode::Fill,
+                textures: vec![MaterialTextureSlotDesc {
+                    name: ERROR_ALBEDO_SLOT.to_string(),
+                    texture: white_texture,
+                    layer: None,
+                    region: None,
+                    sampler_type: graphics_device::SamplerType::NearestRepeat,
+                    flipbook: None,
+                }],
+                params: vec![(ERROR_COLOR_PARAM.to_string(), ParamValue::Vec4(ERROR_COLOR))],
+                render_state: None,
+                engine_features: graphics_device::EngineFeatures::NONE,
+                render_queue: None,
+            })
+            .collect();
+        let error_material = {
+            let device = graphics_device.lock().unwrap();
+            self.create_material(ERROR_MATERIAL.to_string(), MaterialDesc { passes }, &*device)?
+        };
+
+        // Error cube
+        let (vertices, indices) = default_resources::error_cube_data();
+        let error_cube_geometry = self.create_geometry(ERROR_CUBE_GEOMETRY.to_string(), GeometryDesc {
+            name: ERROR_CUBE_GEOMETRY.to_string(),
+            graphics_device,
+            vertex_data: bytemuck::cast_slice(&vertices).into(),
+            index_data: Some(bytemuck::cast_slice(&indices).into()),
+            vertex_layout: default_resources::error_cube_vertex_layout(),
+            index_type: graphics_device::IndexType::U16,
+            morph_targets: Vec::new(),
+            meshes: vec![GeometryMeshDesc {
+                name: ERROR_CUBE_MESH.to_string(),
+                submeshes: vec![GeometrySubMeshDesc {
+                    name: "default".to_string(),
+                    lods: vec![GeometrySubMeshLODDesc {
+                        vertex_offset: 0,
+                        vertex_count: (vertices.len() / default_resources::ERROR_CUBE_VERTEX_FLOATS) as u32,
+                        index_offset: 0,
+                        index_count: indices.len() as u32,
+                        topology: graphics_device::PrimitiveTopology::TriangleList,
+                    }],
+                    lod_thresholds: Vec::new(),
+                }],
+            }],
+        })?;
+        let error_cube_mesh = self.create_mesh(ERROR_CUBE_MESH.to_string(), MeshDesc {
+            geometry: error_cube_geometry,
+            geometry_mesh: GeometryMeshRef::Index(0),
+            submeshes: vec![MeshSubMeshDesc {
+                submesh: GeometrySubMeshRef::Index(0),
+                material: error_material,
+            }],
+        })?;
+
+        let defaults = DefaultResources {
+            white_texture,
+            black_texture,
+            normal_texture,
+            error_material,
+            error_cube_geometry,
+            error_cube_mesh,
+        };
+        self.default_resources = Some(defaults);
+        Ok(defaults)
+    }
+
+    /// Fallback resources, if `create_default_resources()` was called
+    pub fn default_resources(&self) -> Option<&DefaultResources> {
+        self.default_resources.as_ref()
+    }
+
+    // ===== STANDARD PBR =====
+
+    /// Create the built-in PBR shaders from their SPIR-V (see
+    /// `resource::standard_pbr`). `clear()` removes them; call again after
+    /// a clear.
+    pub fn create_standard_pbr_shaders(
+        &mut self,
+        desc: StandardPbrDesc,
+        graphics_device: &mut dyn graphics_device::GraphicsDevice,
+    ) -> Result<StandardPbrShaders> {
+        if self.standard_pbr.is_some() {
+            crate::engine_bail_warn!("galaxy3d::ResourceManager", "Standard PBR shaders already exist");
+        }
+        let mut created = Vec::new();
+        let result = self.create_standard_pbr_shader_set(&desc, graphics_device, &mut created);
+        match result {
+            Ok(shaders) => {
+                self.standard_pbr = Some(shaders);
+                Ok(shaders)
+            }
+            Err(e) => {
+                self.remove_many(ResourceKind::Shader, &created);
+                Err(e)
+            }
+        }
+    }
+
+    /// Create every standard shader, pushing the name of each one created
+    /// to `created`
+    fn create_standard_pbr_shader_set(
+        &mut self,
+        desc: &StandardPbrDesc,
+        graphics_device: &mut dyn graphics_device::GraphicsDevice,
+        created: &mut Vec<String>,
+    ) -> Result<StandardPbrShaders> {
+        let mut create = |rm: &mut Self, name: String, code: &[u8], stage| {
+            let key = rm.create_shader(name.clone(), crate::resource::shader::ShaderDesc {
+                code,
+                stage,
+                entry_point: "main".to_string(),
+            }, &mut *graphics_device)?;
+            created.push(name);
+            Ok::<_, Error>(key)
+        };
+        let vertex_shader = create(self, STANDARD_PBR_VERTEX_SHADER.to_string(),
+            desc.vertex_spirv, graphics_device::ShaderStage::Vertex)?;
+        let mut morph_vertex_shaders = [[vertex_shader; 2]; MAX_MORPH_TARGETS];
+        for (index, variants) in desc.morph_vertex_spirv.iter().enumerate() {
+            for (normals, code) in variants.iter().enumerate() {
+                morph_vertex_shaders[index][normals] = create(self,
+                    standard_pbr_morph_vertex_shader_name(index + 1, normals == 1),
+                    code, graphics_device::ShaderStage::Vertex)?;
+            }
+        }
+        let fragment_shader = create(self, STANDARD_PBR_FRAGMENT_SHADER.to_string(),
+            desc.fragment_spirv, graphics_device::ShaderStage::Fragment)?;
+        Ok(StandardPbrShaders { vertex_shader, morph_vertex_shaders, fragment_shader })
+    }
+
+    /// Built-in PBR shaders, if `create_standard_pbr_shaders()` was called
+    pub fn standard_pbr_shaders(&self) -> Option<&StandardPbrShaders> {
+        self.standard_pbr.as_ref()
+    }
+
+    /// Create a one-pass material drawn by the built-in PBR fragment shader.
+    /// Its params and texture slots are the default material buffer fields.
+    pub fn create_standard_pbr_material(
+        &mut self,
+        name: String,
+        desc: StandardPbrMaterialDesc,
+        graphics_device: &dyn graphics_device::GraphicsDevice,
+    ) -> Result<MaterialKey> {
+        let Some(shaders) = self.standard_pbr else {
+            crate::engine_bail!("galaxy3d::ResourceManager",
+                "Material '{}': standard PBR shaders not created", name);
+        };
+
+        let mut textures = Vec::new();
+        for (slot, texture_key) in desc.texture_slots() {
+            let Some(texture) = self.textures.get(texture_key) else {
+                return Err(crate::engine_not_found!("galaxy3d::ResourceManager", "Texture", format!("{:?}", texture_key)));
+            };
+            let texture_type = texture.graphics_device_texture().info().texture_type;
+            if texture_type != graphics_device::TextureType::Tex2D {
+                crate::engine_bail!("galaxy3d::ResourceManager",
+                    "Material '{}': slot '{}' texture has type {:?}, expected Tex2D", name, slot, texture_type);
+            }
+            textures.push(MaterialTextureSlotDesc {
+                name: slot.to_string(),
+                texture: texture_key,
+                layer: None,
+                region: None,
+                sampler_type: desc.sampler_type,
+                flipbook: None,
+            });
+        }
+
+        let [r, g, b] = desc.emissive_color;
+        let pass = MaterialPassDesc {
+            pass_type: desc.pass_type,
+            fragment_shader: shaders.fragment_shader,
+            color_blend: graphics_device::ColorBlendState::default(),
+            polygon_mode: graphics_device::PolygonMode::Fill,
+            textures,
+            params: vec![
+                (PBR_BASE_COLOR_PARAM.to_string(), ParamValue::Vec4(desc.base_color)),
+                (PBR_EMISSIVE_COLOR_PARAM.to_string(), ParamValue::Vec4([r, g, b, 1.0])),
+                (PBR_METALLIC_PARAM.to_string(), ParamValue::Float(desc.metallic)),
+                (PBR_ROUGHNESS_PARAM.to_string(), ParamValue::Float(desc.roughness)),
+                (PBR_NORMAL_SCALE_PARAM.to_string(), ParamValue::Float(desc.normal_scale)),
+                (PBR_AO_PARAM.to_string(), ParamValue::Float(desc.ao)),
+            ],
+            render_state: desc.render_state,
+            engine_features: graphics_device::EngineFeatures::FOG,
+            render_queue: None,
+        };
+        self.create_material(name, MaterialDesc { passes: vec![pass] }, graphics_device)
+    }
+
+    // ===== MANIFEST =====
+
+    /// Create the resources described by `manifest` (see
+    /// `resource::manifest`). Data paths are relative to `base_dir`. All
+    /// or nothing: on error, the resources already created are removed.
+    #[cfg(feature = "manifest")]
+    pub fn create_from_manifest(
+        &mut self,
+        manifest: &super::manifest::ResourceManifest,
+        base_dir: &std::path::Path,
+        graphics_device: &Arc<Mutex<dyn graphics_device::GraphicsDevice>>,
+    ) -> Result<()> {
+        let records = manifest.create_resources(self, base_dir, graphics_device)?;
+        self.manifest_records.extend(records);
+        crate::engine_info!("galaxy3d::ResourceManager",
+            "Created {} resources from manifest", manifest.len());
+        Ok(())
+    }
+
+    /// Read a `.ron` / `.json` manifest file and create its resources.
+    /// Data paths are relative to the manifest's directory.
+    #[cfg(feature = "manifest")]
+    pub fn load_manifest(
+        &mut self,
+        path: impl AsRef<std::path::Path>,
+        graphics_device: &Arc<Mutex<dyn graphics_device::GraphicsDevice>>,
+    ) -> Result<()> {
+        let path = path.as_ref();
+        let manifest = super::manifest::ResourceManifest::load(path)?;
+        let base_dir = path.parent().unwrap_or(std::path::Path::new(""));
+        self.create_from_manifest(&manifest, base_dir, graphics_device)
+    }
+
+    /// Manifest of the resources created from manifests and still
+    /// registered under their name
+    #[cfg(feature = "manifest")]
+    pub fn manifest(&self) -> super::manifest::ResourceManifest {
+        self.manifest_records.live(self)
+    }
+
+    /// Write `manifest()` to `path` (`.ron` / `.json`). Data paths are
+    /// written as loaded: save next to the source manifest to keep them valid.
+    #[cfg(feature = "manifest")]
+    pub fn save_manifest(&self, path: impl AsRef<std::path::Path>) -> Result<()> {
+        self.manifest().save(path)
+    }
+
+    // ===== LEAK REPORT =====
+
+    /// List every resource still referenced outside the ResourceManager.
+    ///
+    /// A resource is reported when its `Arc` strong count is greater than 1,
+    /// i.e. someone (scene, render graph, user code) still holds a clone.
+    /// Called by `Engine::shutdown()` to log leaks before teardown.
+    pub fn external_references(&self) -> Vec<ResourceLeak> {
+        fn collect<K: slotmap::Key, T>(
+            kind: &'static str,
+            names: &FxHashMap<String, K>,
+            storage: &SlotMap<K, Arc<T>>,
+            out: &mut Vec<ResourceLeak>,
+        ) {
+            for (name, key) in names {
+                if let Some(res) = storage.get(*key) {
+                    let ref_count = Arc::strong_count(res);
+                    if ref_count > 1 {
+                        out.push(ResourceLeak { kind, name: name.clone(), ref_count });
+                    }
+                }
+            }
+        }
+
+        let mut leaks = Vec::new();
+        collect("Texture", &self.texture_names, &self.textures, &mut leaks);
+        collect("Geometry", &self.geometry_names, &self.geometries, &mut leaks);
+        collect("Shader", &self.shader_names, &self.shaders, &mut leaks);
+        collect("Pipeline", &self.pipeline_names, &self.pipelines, &mut leaks);
+        collect("Material", &self.material_names, &self.materials, &mut leaks);
+        collect("Mesh", &self.mesh_names, &self.meshes, &mut leaks);
+        collect("Buffer", &self.buffer_names, &self.buffers, &mut leaks);
+        leaks.sort_by(|a, b| (a.kind, &a.name).cmp(&(b.kind, &b.name)));
+        leaks
+    }
+
     /// Create a default per-frame uniform buffer (UBO) with standard engine fields.
     ///
-    /// Layout (std140, 304 bytes):
+    /// Layout (std140, 432 bytes):
     /// - Camera: view, projection, viewProjection (Mat4), cameraPosition, cameraDirection (Vec4)
     /// - Lighting: sunDirection, sunColor, ambientColor (Vec4)
     /// - Time: time, deltaTime (Float), frameIndex (UInt)
     /// - Post-process: exposure, gamma (Float)
     /// - Depth: nearPlane, farPlane (Float)
     /// - Ambient: ambientIntensity (Float)
+    /// - Fog: fogColor, fogParams (start, end, density, heightFalloff) (Vec4),
+    ///   fogBaseHeight (Float), fogMode (UInt, `FogMode` value)
+    /// - Motion vectors: previousViewProjection (Mat4, unjittered), jitter
+    ///   (Vec4, current NDC jitter in `.xy`, previous in `.zw`)
+    /// - Clustered lights: lightClusterGrid (UVec4, tiles x / y, depth slices,
+    ///   0 = off), lightClusterDepth (Vec4, near and far of the grid), written
+    ///   by `LightClusterGrid::upload`
     ///
     /// Fields that would cause artifacts or crashes at zero are initialized
     /// with safe defaults.
@@ -1055,6 +2580,14 @@ impl ResourceManager {
                 FieldDesc { name: "nearPlane".to_string(),        field_type: FieldType::Float },
                 FieldDesc { name: "farPlane".to_string(),         field_type: FieldType::Float },
                 FieldDesc { name: "ambientIntensity".to_string(), field_type: FieldType::Float },
+                FieldDesc { name: "fogColor".to_string(),         field_type: FieldType::Vec4 },
+                FieldDesc { name: "fogParams".to_string(),        field_type: FieldType::Vec4 },
+                FieldDesc { name: "fogBaseHeight".to_string(),    field_type: FieldType::Float },
+                FieldDesc { name: "fogMode".to_string(),          field_type: FieldType::UInt },
+                FieldDesc { name: "previousViewProjection".to_string(), field_type: FieldType::Mat4 },
+                FieldDesc { name: "jitter".to_string(),           field_type: FieldType::Vec4 },
+                FieldDesc { name: "lightClusterGrid".to_string(), field_type: FieldType::UVec4 },
+                FieldDesc { name: "lightClusterDepth".to_string(), field_type: FieldType::Vec4 },
             ],
             count: 1,
         })?;
@@ -1096,23 +2629,33 @@ impl ResourceManager {
                 FieldDesc { name: "customData".to_string(),     field_type: FieldType::Vec4 },
                 FieldDesc { name: "lightIndices0".to_string(),  field_type: FieldType::UVec4 },
                 FieldDesc { name: "lightIndices1".to_string(),  field_type: FieldType::UVec4 },
+                FieldDesc { name: "morphWeights".to_string(),   field_type: FieldType::Vec4 },
+                FieldDesc { name: "colorTint".to_string(),      field_type: FieldType::Vec4 },
+                FieldDesc { name: "emissiveTint".to_string(),   field_type: FieldType::Vec4 },
             ],
             count,
         })?;
 
         let buffer = self.buffer(key).unwrap();
 
-        // Safe defaults: lightCount = 0 (zero-initialized), no lights assigned (sentinel 0xFFFFFFFF)
+        // Safe defaults: lightCount = 0 (zero-initialized), no lights assigned (sentinel 0xFFFFFFFF),
+        // no color tint (multiplier 1; emissiveTint stays zero-initialized)
         let no_light = [0xFFFFFFFFu32; 4];
+        let no_tint = [1.0f32; 4];
         for i in 0..count {
             buffer.update_field(i, 7, bytemuck::bytes_of(&no_light))?; // lightIndices0
             buffer.update_field(i, 8, bytemuck::bytes_of(&no_light))?; // lightIndices1
+            buffer.update_field(i, 10, bytemuck::bytes_of(&no_tint))?; // colorTint
         }
 
         Ok(key)
     }
 
     /// Create a default material storage buffer (SSBO) with standard PBR fields.
+    ///
+    /// Texture animation fields: `albedoUvRect` and `emissiveUvRect` (region
+    /// UV rectangles, see `sync_material_animations()`), `uvScroll` and
+    /// `uvRotationSpeed` (see `material::animated_uv`).
     pub fn create_default_material_buffer(
         &mut self,
         name: String,
@@ -1147,6 +2690,10 @@ impl ResourceManager {
                 FieldDesc { name: "aoSampler".to_string(),                    field_type: FieldType::UInt },
                 FieldDesc { name: "aoLayer".to_string(),                      field_type: FieldType::UInt },
                 FieldDesc { name: "flags".to_string(),                        field_type: FieldType::UInt },
+                FieldDesc { name: "albedoUvRect".to_string(),                 field_type: FieldType::Vec4 },
+                FieldDesc { name: "emissiveUvRect".to_string(),               field_type: FieldType::Vec4 },
+                FieldDesc { name: UV_SCROLL_PARAM.to_string(),                field_type: FieldType::Vec2 },
+                FieldDesc { name: UV_ROTATION_SPEED_PARAM.to_string(),        field_type: FieldType::Float },
             ],
             count,
         })?;
@@ -1156,6 +2703,7 @@ impl ResourceManager {
         // Safe defaults for all slots
         let f = |name: &str| buffer.field_id(name).unwrap();
         let no_texture = u32::MAX.to_ne_bytes();
+        let full_uv_rect = FULL_UV_RECT.map(|v| v.to_ne_bytes()).concat();
 
         for i in 0..count {
             buffer.update_field(i, f("baseColor"),   &[1.0f32, 1.0, 1.0, 1.0].map(|v| v.to_ne_bytes()).concat())?;
@@ -1179,6 +2727,8 @@ impl ResourceManager {
             buffer.update_field(i, f("aoTexture"),                    &no_texture)?;
             buffer.update_field(i, f("aoSampler"),                    &0u32.to_ne_bytes())?;
             buffer.update_field(i, f("aoLayer"),                      &0u32.to_ne_bytes())?;
+            buffer.update_field(i, f("albedoUvRect"),                 &full_uv_rect)?;
+            buffer.update_field(i, f("emissiveUvRect"),               &full_uv_rect)?;
         }
 
         Ok(key)
@@ -1216,6 +2766,50 @@ impl ResourceManager {
 
         Ok(key)
     }
+
+    /// Create a default light cluster storage buffer (SSBO): one
+    /// `lightOffset` / `lightCount` pair (UInt) per cluster of a
+    /// `LightClusterGrid`, indexing the light index buffer.
+    ///
+    /// `count` is the grid's `cluster_count()`. Zero-initialized: every
+    /// cluster starts empty.
+    pub fn create_default_light_cluster_buffer(
+        &mut self,
+        name: String,
+        graphics_device: Arc<Mutex<dyn graphics_device::GraphicsDevice>>,
+        count: u32,
+    ) -> Result<BufferKey> {
+        self.create_buffer(name, BufferDesc {
+            graphics_device,
+            kind: BufferKind::Storage,
+            fields: vec![
+                FieldDesc { name: "lightOffset".to_string(), field_type: FieldType::UInt },
+                FieldDesc { name: "lightCount".to_string(),  field_type: FieldType::UInt },
+            ],
+            count,
+        })
+    }
+
+    /// Create a default light index storage buffer (SSBO): the light SSBO
+    /// slots of all clusters of a `LightClusterGrid`, concatenated.
+    ///
+    /// `count` bounds the lights listed per frame; the grid's
+    /// `max_light_index_count()` can never overflow.
+    pub fn create_default_light_index_buffer(
+        &mut self,
+        name: String,
+        graphics_device: Arc<Mutex<dyn graphics_device::GraphicsDevice>>,
+        count: u32,
+    ) -> Result<BufferKey> {
+        self.create_buffer(name, BufferDesc {
+            graphics_device,
+            kind: BufferKind::Storage,
+            fields: vec![
+                FieldDesc { name: "lightIndex".to_string(), field_type: FieldType::UInt },
+            ],
+            count,
+        })
+    }
 }
 
 // ============================================================================
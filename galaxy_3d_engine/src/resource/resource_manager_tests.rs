@@ -9,8 +9,13 @@ use crate::resource::{
     MeshSubMeshDesc, GeometryMeshRef, GeometrySubMeshRef,
     GeometrySubMeshDesc, GeometrySubMeshLODDesc,
     BufferKind, FieldDesc,
-    MaterialPassDesc, MaterialTextureSlotDesc, LayerRef,
+    MaterialPassDesc, MaterialTextureSlotDesc, LayerRef, RegionRef, FlipbookDesc,
     ShaderDesc,
+    DefaultResources, DefaultResourcesDesc,
+    StandardPbrDesc, StandardPbrShaders, StandardPbrMaterialDesc, MorphTargetDesc,
+    standard_pbr_morph_vertex_shader_name, STANDARD_PBR_VERTEX_SHADER, STANDARD_PBR_FRAGMENT_SHADER, PBR_ALBEDO_SLOT, PBR_METALLIC_PARAM,
+    DEFAULT_WHITE_TEXTURE, DEFAULT_BLACK_TEXTURE, DEFAULT_NORMAL_TEXTURE,
+    ERROR_MATERIAL, ERROR_CUBE_MESH, ERROR_ALBEDO_SLOT,
 };
 use std::sync::{Arc, Mutex};
 
@@ -35,6 +40,7 @@ fn create_test_texture_desc(
         texture: graphics_device::TextureDesc {
             width,
             height,
+            depth: 1,
             format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
             usage: graphics_device::TextureUsage::Sampled,
             array_layers: 1,
@@ -42,6 +48,7 @@ fn create_test_texture_desc(
             mipmap: graphics_device::MipmapMode::None,
             texture_type: graphics_device::TextureType::Tex2D,
             sample_count: graphics_device::SampleCount::S1,
+            debug_name: None,
         },
         layers: vec![LayerDesc {
             name: "default".to_string(),
@@ -56,7 +63,7 @@ fn create_test_texture_desc(
 fn create_test_geometry_desc(
     graphics_device: Arc<Mutex<dyn graphics_device::GraphicsDevice>>,
     name: &str,
-) -> GeometryDesc {
+) -> GeometryDesc<'static> {
     // Simple quad: 4 vertices (Position2D + UV), 6 indices
     let vertex_data = vec![
         // Position (x, y) + UV (u, v)
@@ -105,10 +112,11 @@ fn create_test_geometry_desc(
     GeometryDesc {
         name: name.to_string(),
         graphics_device,
-        vertex_data: vertex_bytes,
-        index_data: Some(index_bytes),
+        vertex_data: vertex_bytes.into(),
+        index_data: Some(index_bytes.into()),
         vertex_layout,
         index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![GeometryMeshDesc {
             name: name.to_string(),
             submeshes: vec![GeometrySubMeshDesc {
@@ -175,6 +183,7 @@ fn create_test_pipeline_desc(vertex_shader: ShaderKey, fragment_shader: ShaderKe
         multisample: Default::default(),
         color_formats: vec![],
         depth_format: None,
+        engine_features: Default::default(),
     }
 }
 
@@ -189,6 +198,8 @@ fn create_test_material_desc(fragment_shader: ShaderKey) -> MaterialDesc {
             textures: vec![],
             params: vec![],
             render_state: None,
+            engine_features: Default::default(),
+            render_queue: None,
         }],
     }
 }
@@ -303,6 +314,38 @@ fn test_remove_texture_not_found() {
     assert!(!removed);
 }
 
+#[test]
+fn test_removed_texture_key_is_stale_after_slot_reuse() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+
+    let old_key = rm.create_texture("old".to_string(),
+        create_test_texture_desc(graphics_device.clone(), "old", 4, 4)).unwrap();
+    assert!(rm.remove_texture(old_key));
+
+    // The new texture takes the freed slot with a new version
+    let new_key = rm.create_texture("new".to_string(),
+        create_test_texture_desc(graphics_device.clone(), "new", 4, 4)).unwrap();
+    assert_ne!(old_key, new_key);
+    assert!(rm.texture(old_key).is_none());
+    assert!(!rm.remove_texture(old_key));
+    assert!(rm.texture(new_key).is_some());
+}
+
+#[test]
+fn test_texture_handle_lookup_matches_name_index() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+
+    let handle: TextureHandle = rm.create_texture("albedo".to_string(),
+        create_test_texture_desc(graphics_device.clone(), "albedo", 4, 4)).unwrap();
+    assert_eq!(rm.texture_key("albedo"), Some(handle));
+    assert!(Arc::ptr_eq(rm.texture(handle).unwrap(), rm.texture_by_name("albedo").unwrap()));
+
+    assert!(rm.remove_texture_by_name("albedo"));
+    assert!(rm.texture(handle).is_none());
+}
+
 #[test]
 fn test_duplicate_texture_fails() {
     let mut rm = ResourceManager::new();
@@ -359,6 +402,36 @@ fn test_texture_count() {
     assert_eq!(rm.texture_count(), 0);
 }
 
+#[test]
+fn test_platform_profile_downscales_loaded_textures() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+    rm.set_platform_limits(graphics_device::PlatformLimits {
+        max_texture_size: Some(64),
+        ..graphics_device::PlatformProfile::Mobile.limits()
+    });
+
+    let mut desc = create_test_texture_desc(graphics_device.clone(), "atlas", 256, 128);
+    desc.layers[0].regions.push(AtlasRegionDesc {
+        name: "icon".to_string(),
+        region: AtlasRegion { x: 128, y: 64, width: 64, height: 32 },
+    });
+    let key = rm.create_texture("atlas".to_string(), desc).unwrap();
+    let texture = rm.texture(key).unwrap();
+    let info = texture.graphics_device_texture().info();
+    assert_eq!((info.width, info.height), (64, 32));
+    let region = texture.layer(0).unwrap().region_by_name("icon").unwrap();
+    assert_eq!(*region, AtlasRegion { x: 32, y: 16, width: 16, height: 8 });
+
+    // Render targets are never downscaled
+    let mut target = create_test_texture_desc(graphics_device.clone(), "target", 256, 256);
+    target.texture.usage = graphics_device::TextureUsage::SampledAndRenderTarget;
+    let key = rm.create_texture("target".to_string(), target).unwrap();
+    assert_eq!(rm.texture(key).unwrap().graphics_device_texture().info().width, 256);
+
+    assert_eq!(rm.shadow_resolution(4096), 1024);
+}
+
 // ============================================================================
 // Tests: Geometry Management
 // ============================================================================
@@ -585,6 +658,45 @@ fn test_pipeline_count() {
     assert_eq!(rm.pipeline_count(), 0);
 }
 
+#[test]
+fn test_create_depth_only_pipeline() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+
+    let (vk, fk) = create_test_shaders(&mut rm, &graphics_device);
+    let desc = PipelineDesc {
+        color_formats: vec![graphics_device::TextureFormat::R8G8B8A8_UNORM],
+        depth_format: Some(graphics_device::TextureFormat::D32_FLOAT),
+        ..create_test_pipeline_desc(vk, fk)
+    };
+    let source = rm.create_pipeline("lit".to_string(), desc, &mut *graphics_device.lock().unwrap()).unwrap();
+    let depth = rm.create_depth_only_pipeline(
+        "lit_depth".to_string(), source, &mut *graphics_device.lock().unwrap(),
+    ).unwrap();
+
+    let depth = rm.pipeline(depth).unwrap();
+    assert_eq!(depth.vertex_shader(), vk);
+    assert_eq!(depth.fragment_shader(), fk);
+    assert!(depth.desc().color_formats.is_empty());
+    assert!(!depth.desc().color_blend.color_write_enable);
+    assert_eq!(depth.desc().depth_format, Some(graphics_device::TextureFormat::D32_FLOAT));
+    assert_eq!(rm.pipeline_count(), 2);
+}
+
+#[test]
+fn test_create_depth_only_pipeline_requires_depth_format() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+
+    let desc = { let (vk, fk) = create_test_shaders(&mut rm, &graphics_device); create_test_pipeline_desc(vk, fk) };
+    let source = rm.create_pipeline("flat".to_string(), desc, &mut *graphics_device.lock().unwrap()).unwrap();
+    let result = rm.create_depth_only_pipeline(
+        "flat_depth".to_string(), source, &mut *graphics_device.lock().unwrap(),
+    );
+    assert!(result.is_err());
+    assert_eq!(rm.pipeline_count(), 1);
+}
+
 // ============================================================================
 // Tests: Material Management
 // ============================================================================
@@ -889,6 +1001,146 @@ fn test_clear_all_resources() {
     assert_eq!(rm.mesh_count(), 0);
 }
 
+#[test]
+fn test_remove_many_textures_retires_them() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+    for i in 0..4 {
+        let desc = create_test_texture_desc(graphics_device.clone(), &format!("texture{}", i), 16, 16);
+        rm.create_texture(format!("texture{}", i), desc).unwrap();
+    }
+    let kept = rm.texture_key("texture3").unwrap();
+
+    let removed = rm.remove_many(ResourceKind::Texture, &["texture0", "texture1", "texture2", "missing"]);
+    assert_eq!(removed, 3);
+    assert_eq!(rm.texture_count(), 1);
+    assert!(rm.texture_by_name("texture1").is_none());
+    assert!(rm.texture(kept).is_some());
+
+    // GPU objects stay alive until released
+    assert_eq!(rm.retired_resource_count(), 3);
+    assert_eq!(rm.release_retired_resources(), 3);
+    assert_eq!(rm.retired_resource_count(), 0);
+}
+
+#[test]
+fn test_remove_many_materials_frees_slots() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+    let (_vk, fk) = create_test_shaders(&mut rm, &graphics_device);
+    for i in 0..2 {
+        rm.create_material(format!("material{}", i), create_test_material_desc(fk),
+            &*graphics_device.lock().unwrap()).unwrap();
+    }
+    assert_eq!(rm.remove_many(ResourceKind::Material, &["material0", "material1"]), 2);
+    assert_eq!(rm.material_count(), 0);
+
+    // Freed slot ids are reused
+    let key = rm.create_material("again".to_string(), create_test_material_desc(fk),
+        &*graphics_device.lock().unwrap()).unwrap();
+    assert!(rm.material(key).unwrap().slot_id() < 2);
+}
+
+// ============================================================================
+// Tests: Dependencies
+// ============================================================================
+
+#[test]
+fn test_referenced_geometry_and_material_removal_is_deferred() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+    let (geometry, material) = create_mesh_prerequisites(&mut rm, &graphics_device, "dep");
+    rm.create_mesh("mesh".to_string(), create_test_mesh_desc(geometry, material)).unwrap();
+    assert_eq!(rm.dependent_count(ResourceKind::Geometry, "geom_dep"), 1);
+    assert_eq!(rm.dependent_count(ResourceKind::Material, "mat_dep"), 1);
+    assert_eq!(rm.dependent_count(ResourceKind::Mesh, "mesh"), 0);
+
+    // Unregistered at once, still alive for the mesh
+    assert!(rm.remove_geometry("geom_dep"));
+    assert!(rm.remove_material("mat_dep"));
+    assert!(rm.geometry_key("geom_dep").is_none());
+    assert!(rm.geometry(geometry).is_some());
+    assert!(rm.material(material).is_some());
+    assert_eq!(rm.material_slot_count(), 1);
+    assert_eq!(rm.stats().pending_removals, 2);
+
+    // The last dependent retires them
+    assert!(rm.remove_mesh("mesh"));
+    assert!(rm.geometry(geometry).is_none());
+    assert!(rm.material(material).is_none());
+    assert_eq!(rm.material_slot_count(), 0);
+    assert_eq!(rm.pending_removal_count(), 0);
+    assert_eq!(rm.retired_resource_count(), 2);
+}
+
+#[test]
+fn test_material_texture_dependencies_follow_slot_changes() {
+    let mut rm = ResourceManager::new();
+    let (used, unused, material) = create_texture_usage_setup(&mut rm);
+    assert_eq!(rm.dependent_count(ResourceKind::Texture, "used"), 1);
+
+    assert!(rm.remove_texture(used));
+    assert!(!rm.remove_texture(used));
+    assert!(rm.texture(used).is_some());
+
+    rm.set_material_texture_slot(material, MaterialTextureSlotDesc {
+        name: "albedo".to_string(),
+        texture: unused,
+        layer: None,
+        region: None,
+        sampler_type: graphics_device::SamplerType::LinearRepeat,
+        flipbook: None,
+    }).unwrap();
+    assert!(rm.texture(used).is_none());
+    assert_eq!(rm.dependent_count(ResourceKind::Texture, "unused"), 1);
+}
+
+#[test]
+fn test_remove_many_defers_referenced_resources() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+    let (vk, fk) = create_test_shaders(&mut rm, &graphics_device);
+    rm.create_pipeline("pipe".to_string(), create_test_pipeline_desc(vk, fk),
+        &mut *graphics_device.lock().unwrap()).unwrap();
+    let vertex_name = rm.shaders().find(|entry| entry.key == vk).unwrap().name.to_string();
+    assert_eq!(rm.dependent_count(ResourceKind::Shader, &vertex_name), 1);
+
+    assert_eq!(rm.remove_many(ResourceKind::Shader, &[vertex_name.as_str()]), 1);
+    assert!(rm.shader(vk).is_some());
+    assert_eq!(rm.retired_resource_count(), 0);
+
+    assert_eq!(rm.remove_many(ResourceKind::Pipeline, &["pipe"]), 1);
+    assert!(rm.shader(vk).is_none());
+    assert_eq!(rm.retired_resource_count(), 2);
+}
+
+#[test]
+fn test_clear_retires_everything() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+    let (vk, fk) = create_test_shaders(&mut rm, &graphics_device);
+    rm.create_texture("texture".to_string(),
+        create_test_texture_desc(graphics_device.clone(), "texture", 16, 16)).unwrap();
+    rm.create_pipeline("pipeline".to_string(), create_test_pipeline_desc(vk, fk),
+        &mut *graphics_device.lock().unwrap()).unwrap();
+    rm.create_material("material".to_string(), create_test_material_desc(fk),
+        &*graphics_device.lock().unwrap()).unwrap();
+
+    rm.clear();
+    assert_eq!(rm.texture_count(), 0);
+    assert_eq!(rm.shader_count(), 0);
+    assert_eq!(rm.pipeline_count(), 0);
+    assert_eq!(rm.material_count(), 0);
+    assert!(rm.shader(vk).is_none());
+    assert_eq!(rm.retired_resource_count(), 5);
+
+    // Names can be reused right away
+    let (_vk, fk) = create_test_shaders(&mut rm, &graphics_device);
+    rm.create_material("material".to_string(), create_test_material_desc(fk),
+        &*graphics_device.lock().unwrap()).unwrap();
+    assert_eq!(rm.release_retired_resources(), 5);
+}
+
 // ============================================================================
 // Tests: MockGraphicsDevice Verification
 // ============================================================================
@@ -935,6 +1187,27 @@ fn test_mock_graphics_device_tracks_pipelines() {
     assert_eq!(created_pipelines.len(), 1);
 }
 
+#[test]
+fn test_resource_names_become_device_debug_names() {
+    let mut rm = ResourceManager::new();
+    let mock = Arc::new(Mutex::new(graphics_device::mock_graphics_device::MockGraphicsDevice::new()));
+    let graphics_device: Arc<Mutex<dyn graphics_device::GraphicsDevice>> = mock.clone();
+
+    let desc = create_test_geometry_desc(graphics_device.clone(), "hero");
+    rm.create_geometry("hero".to_string(), desc).unwrap();
+    let desc = create_test_texture_desc(graphics_device.clone(), "albedo", 4, 4);
+    rm.create_texture("albedo".to_string(), desc).unwrap();
+    let desc = { let (vk, fk) = create_test_shaders(&mut rm, &graphics_device); create_test_pipeline_desc(vk, fk) };
+    rm.create_pipeline("opaque".to_string(), desc, &mut *graphics_device.lock().unwrap()).unwrap();
+
+    let mock = mock.lock().unwrap();
+    assert_eq!(mock.get_created_buffers(), vec!["hero vertices", "hero indices"]);
+    assert_eq!(mock.get_created_textures(), vec!["albedo"]);
+    assert_eq!(mock.get_created_pipelines(), vec!["opaque"]);
+    assert!(mock.get_created_shaders().iter().all(|name| name.starts_with("test_vert_") || name.starts_with("test_frag_")),
+        "{:?}", mock.get_created_shaders());
+}
+
 // ============================================================================
 // Tests: Resource Modification (add_* methods)
 // ============================================================================
@@ -950,6 +1223,7 @@ fn test_add_texture_layer() {
         texture: graphics_device::TextureDesc {
             width: 256,
             height: 256,
+            depth: 1,
             format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
             usage: graphics_device::TextureUsage::Sampled,
             array_layers: 4, // Indexed texture with 4 slots
@@ -957,6 +1231,7 @@ fn test_add_texture_layer() {
             mipmap: graphics_device::MipmapMode::None,
             texture_type: graphics_device::TextureType::Array2D,
             sample_count: graphics_device::SampleCount::S1,
+            debug_name: None,
         },
         layers: vec![
             LayerDesc {
@@ -1004,7 +1279,7 @@ fn test_add_texture_layer_to_nonexistent_texture() {
     };
 
     let result = rm.add_texture_layer(TextureKey::default(), new_layer);
-    assert!(result.is_err());
+    assert!(matches!(result, Err(crate::error::Error::ResourceNotFound { kind: "Texture", .. })));
 }
 
 #[test]
@@ -1057,6 +1332,22 @@ fn test_add_texture_region() {
     assert_eq!(layer.region_count(), 1);
 }
 
+#[test]
+fn test_allocate_and_free_texture_region() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+    let desc = create_test_texture_desc(graphics_device.clone(), "atlas", 256, 256);
+    let tex_key = rm.create_texture("atlas".to_string(), desc).unwrap();
+
+    let region = rm.allocate_texture_region(tex_key, "default", 64, 32).unwrap();
+    assert_eq!((region.width, region.height), (64, 32));
+    let stats = rm.texture(tex_key).unwrap().layer_by_name("default").unwrap().atlas_stats();
+    assert_eq!(stats.used_area, 64 * 32);
+
+    assert!(rm.free_texture_region(tex_key, "default", &region).unwrap());
+    assert!(rm.allocate_texture_region(TextureKey::default(), "default", 8, 8).is_err());
+}
+
 #[test]
 fn test_add_texture_region_to_nonexistent_texture() {
     let mut rm = ResourceManager::new();
@@ -1079,8 +1370,8 @@ fn test_add_geometry_mesh_to_existing_geometry() {
     let desc = GeometryDesc {
         name: "test_geom".to_string(),
         graphics_device: graphics_device.clone(),
-        vertex_data: vec![0u8; 32], // 4 vertices * 8 bytes
-        index_data: Some(vec![0u8; 12]), // 6 indices * 2 bytes
+        vertex_data: vec![0u8; 32].into(), // 4 vertices * 8 bytes
+        index_data: Some(vec![0u8; 12].into()), // 6 indices * 2 bytes
         vertex_layout: graphics_device::VertexLayout {
             bindings: vec![graphics_device::VertexBinding {
                 binding: 0,
@@ -1095,6 +1386,7 @@ fn test_add_geometry_mesh_to_existing_geometry() {
             }],
         },
         index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![], // No initial meshes
     };
 
@@ -1148,8 +1440,8 @@ fn test_add_geometry_submesh_lod() {
     let desc = GeometryDesc {
         name: "geom".to_string(),
         graphics_device: graphics_device.clone(),
-        vertex_data: vec![0u8; 64], // 8 vertices * 8 bytes
-        index_data: Some(vec![0u8; 24]), // 12 indices * 2 bytes
+        vertex_data: vec![0u8; 64].into(), // 8 vertices * 8 bytes
+        index_data: Some(vec![0u8; 24].into()), // 12 indices * 2 bytes
         vertex_layout: graphics_device::VertexLayout {
             bindings: vec![graphics_device::VertexBinding {
                 binding: 0,
@@ -1164,6 +1456,7 @@ fn test_add_geometry_submesh_lod() {
             }],
         },
         index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![
             GeometryMeshDesc {
                 name: "hero".to_string(),
@@ -1221,6 +1514,59 @@ fn test_add_geometry_submesh_lod_to_nonexistent_geometry() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_build_geometry_meshlets() {
+    let mut rm = ResourceManager::new();
+    let config = crate::resource::MeshletConfig::default();
+    assert!(rm.build_geometry_meshlets(GeometryKey::default(), &config).is_err());
+
+    let desc = GeometryDesc {
+        name: "geom".to_string(),
+        graphics_device: create_mock_graphics_device(),
+        vertex_data: [[0.0f32, 0.0], [1.0, 0.0], [0.0, 1.0]].iter()
+            .flat_map(|v| v.iter().flat_map(|f| f.to_le_bytes()))
+            .collect::<Vec<u8>>()
+            .into(),
+        index_data: None,
+        vertex_layout: graphics_device::VertexLayout {
+            bindings: vec![graphics_device::VertexBinding {
+                binding: 0,
+                stride: 8,
+                input_rate: graphics_device::VertexInputRate::Vertex,
+            }],
+            attributes: vec![graphics_device::VertexAttribute {
+                location: 0,
+                binding: 0,
+                format: graphics_device::BufferFormat::R32G32_SFLOAT,
+                offset: 0,
+            }],
+        },
+        index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
+        meshes: vec![GeometryMeshDesc {
+            name: "tri".to_string(),
+            submeshes: vec![GeometrySubMeshDesc {
+                name: "tri".to_string(),
+                lods: vec![GeometrySubMeshLODDesc {
+                    vertex_offset: 0,
+                    vertex_count: 3,
+                    index_offset: 0,
+                    index_count: 0,
+                    topology: graphics_device::PrimitiveTopology::TriangleList,
+                }],
+                lod_thresholds: Vec::new(),
+            }],
+        }],
+    };
+    let geom_key = rm.create_geometry("geom".to_string(), desc).unwrap();
+    assert_eq!(rm.build_geometry_meshlets(geom_key, &config).unwrap(), 1);
+    assert_eq!(rm.geometry(geom_key).unwrap().meshlets().len(), 1);
+
+    // Shared geometries cannot be mutated
+    let _shared = rm.geometry(geom_key).unwrap().clone();
+    assert!(rm.build_geometry_meshlets(geom_key, &config).is_err());
+}
+
 #[test]
 fn test_add_geometry_submesh() {
     let mut rm = ResourceManager::new();
@@ -1230,8 +1576,8 @@ fn test_add_geometry_submesh() {
     let desc = GeometryDesc {
         name: "geom".to_string(),
         graphics_device: graphics_device.clone(),
-        vertex_data: vec![0u8; 64], // 8 vertices * 8 bytes
-        index_data: Some(vec![0u8; 24]), // 12 indices * 2 bytes
+        vertex_data: vec![0u8; 64].into(), // 8 vertices * 8 bytes
+        index_data: Some(vec![0u8; 24].into()), // 12 indices * 2 bytes
         vertex_layout: graphics_device::VertexLayout {
             bindings: vec![graphics_device::VertexBinding {
                 binding: 0,
@@ -1246,6 +1592,7 @@ fn test_add_geometry_submesh() {
             }],
         },
         index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![
             GeometryMeshDesc {
                 name: "hero".to_string(),
@@ -1405,6 +1752,8 @@ fn test_sync_materials_basic() {
                 ("color".to_string(), ParamValue::Vec4([1.0, 0.0, 0.0, 1.0])),
             ],
             render_state: None,
+            engine_features: Default::default(),
+            render_queue: None,
         }],
     };
     rm.create_material("body".to_string(), mat_desc, &*graphics_device.lock().unwrap()).unwrap();
@@ -1443,6 +1792,8 @@ fn test_sync_materials_type_mismatch_skips() {
                 ("roughness".to_string(), ParamValue::Float(0.8)),
             ],
             render_state: None,
+            engine_features: Default::default(),
+            render_queue: None,
         }],
     };
     rm.create_material("body".to_string(), mat_desc, &*graphics_device.lock().unwrap()).unwrap();
@@ -1482,6 +1833,8 @@ fn test_sync_materials_missing_field_skips() {
                 ("roughness".to_string(), ParamValue::Float(0.8)),
             ],
             render_state: None,
+            engine_features: Default::default(),
+            render_queue: None,
         }],
     };
     rm.create_material("body".to_string(), mat_desc, &*graphics_device.lock().unwrap()).unwrap();
@@ -1521,6 +1874,8 @@ fn test_sync_materials_bool_to_uint() {
                 ("is_metallic".to_string(), ParamValue::Bool(true)),
             ],
             render_state: None,
+            engine_features: Default::default(),
+            render_queue: None,
         }],
     };
     rm.create_material("body".to_string(), mat_desc, &*graphics_device.lock().unwrap()).unwrap();
@@ -1559,6 +1914,8 @@ fn test_sync_materials_vec3_padding() {
                 ("normal".to_string(), ParamValue::Vec3([1.0, 0.0, 0.0])),
             ],
             render_state: None,
+            engine_features: Default::default(),
+            render_queue: None,
         }],
     };
     rm.create_material("body".to_string(), mat_desc, &*graphics_device.lock().unwrap()).unwrap();
@@ -1600,6 +1957,8 @@ fn test_sync_materials_slot_exceeds_buffer() {
                     ("roughness".to_string(), ParamValue::Float(0.5)),
                 ],
                 render_state: None,
+                engine_features: Default::default(),
+                render_queue: None,
             }],
         };
         rm.create_material(format!("mat{}", i), mat_desc, &*graphics_device.lock().unwrap()).unwrap();
@@ -1620,6 +1979,117 @@ fn test_sync_materials_slot_exceeds_buffer() {
     assert!(result.is_ok());
 }
 
+// ============================================================================
+// Tests: sync_dirty_materials
+// ============================================================================
+
+/// Material with a "roughness" param and an "albedo" slot, plus a buffer
+/// holding both
+fn create_dirty_sync_context(rm: &mut ResourceManager) -> (MaterialKey, Arc<Buffer>) {
+    let graphics_device = create_mock_graphics_device();
+    let (_vk, fk) = create_test_shaders(rm, &graphics_device);
+    let texture = rm.create_texture("tex".to_string(), create_test_texture_desc(graphics_device.clone(), "tex", 4, 4)).unwrap();
+
+    let mut mat_desc = create_test_material_desc(fk);
+    mat_desc.passes[0].params.push(("roughness".to_string(), ParamValue::Float(0.5)));
+    mat_desc.passes[0].textures.push(MaterialTextureSlotDesc {
+        name: "albedo".to_string(),
+        texture,
+        layer: None,
+        region: None,
+        sampler_type: graphics_device::SamplerType::LinearRepeat,
+        flipbook: None,
+    });
+    let material = rm.create_material("mat".to_string(), mat_desc, &*graphics_device.lock().unwrap()).unwrap();
+
+    let buffer = rm.create_buffer("material_buffer".to_string(), BufferDesc {
+        graphics_device,
+        kind: BufferKind::Storage,
+        fields: vec![
+            FieldDesc { name: "roughness".to_string(), field_type: FieldType::Float },
+            FieldDesc { name: "albedoTexture".to_string(), field_type: FieldType::UInt },
+        ],
+        count: 4,
+    }).unwrap();
+    (material, rm.buffer(buffer).cloned().unwrap())
+}
+
+#[test]
+fn test_sync_dirty_materials_writes_new_then_changed_only() {
+    let mut rm = ResourceManager::new();
+    let (material, buffer) = create_dirty_sync_context(&mut rm);
+
+    assert_eq!(rm.dirty_material_count(), 1);
+    assert_eq!(rm.sync_dirty_materials(&buffer).unwrap(), 1);
+    assert!(!rm.material(material).unwrap().is_dirty());
+    assert_eq!(rm.sync_dirty_materials(&buffer).unwrap(), 0);
+
+    // Two changes, one write
+    rm.set_material_param(material, "roughness", ParamValue::Float(0.1)).unwrap();
+    rm.set_material_param(material, "roughness", ParamValue::Float(0.2)).unwrap();
+    assert_eq!(rm.dirty_material_count(), 1);
+    assert_eq!(rm.sync_dirty_materials(&buffer).unwrap(), 1);
+
+    let pass = rm.material(material).unwrap().pass(0).unwrap();
+    assert_eq!(pass.param_by_name("roughness").unwrap().as_float(), Some(0.2));
+}
+
+#[test]
+fn test_set_material_texture_slot_marks_dirty() {
+    let mut rm = ResourceManager::new();
+    let (material, buffer) = create_dirty_sync_context(&mut rm);
+    rm.sync_dirty_materials(&buffer).unwrap();
+
+    let graphics_device = create_mock_graphics_device();
+    let other = rm.create_texture("other".to_string(), create_test_texture_desc(graphics_device.clone(), "other", 4, 4)).unwrap();
+    rm.set_material_texture_slot(material, MaterialTextureSlotDesc {
+        name: "albedo".to_string(),
+        texture: other,
+        layer: None,
+        region: None,
+        sampler_type: graphics_device::SamplerType::NearestClamp,
+        flipbook: None,
+    }).unwrap();
+
+    let slot = rm.material(material).unwrap().iter_all_texture_slots().next().unwrap();
+    assert_eq!(slot.texture(), other);
+    assert_eq!(rm.sync_dirty_materials(&buffer).unwrap(), 1);
+
+    // Unknown slot
+    let result = rm.set_material_texture_slot(material, MaterialTextureSlotDesc {
+        name: "normal".to_string(),
+        texture: other,
+        layer: None,
+        region: None,
+        sampler_type: graphics_device::SamplerType::LinearRepeat,
+        flipbook: None,
+    });
+    assert!(result.is_err());
+    assert_eq!(rm.dirty_material_count(), 0);
+}
+
+#[test]
+fn test_set_material_param_requires_exclusive_material() {
+    let mut rm = ResourceManager::new();
+    let (material, buffer) = create_dirty_sync_context(&mut rm);
+    rm.sync_dirty_materials(&buffer).unwrap();
+
+    let shared = rm.material(material).cloned().unwrap();
+    assert!(rm.set_material_param(material, "roughness", ParamValue::Float(0.1)).is_err());
+    drop(shared);
+    assert!(rm.set_material_param(material, "roughness", ParamValue::Float(0.1)).is_ok());
+}
+
+#[test]
+fn test_sync_dirty_materials_skips_removed_materials() {
+    let mut rm = ResourceManager::new();
+    let (_material, buffer) = create_dirty_sync_context(&mut rm);
+
+    assert!(rm.remove_material("mat"));
+    assert_eq!(rm.sync_dirty_materials(&buffer).unwrap(), 0);
+    assert_eq!(rm.dirty_material_count(), 0);
+}
+
 // ============================================================================
 // Tests: Private Helpers (compatible_field_type, param_to_padded_bytes)
 // ============================================================================
@@ -1732,7 +2202,7 @@ fn test_default_material_buffer_fields() {
     ).unwrap();
     let buffer = rm.buffer(buffer_key).unwrap();
 
-    // All 14 fields must exist
+    // All 18 fields must exist
     assert!(buffer.field_id("baseColor").is_some());
     assert!(buffer.field_id("emissiveColor").is_some());
     assert!(buffer.field_id("metallic").is_some());
@@ -1747,6 +2217,10 @@ fn test_default_material_buffer_fields() {
     assert!(buffer.field_id("emissiveTexture").is_some());
     assert!(buffer.field_id("aoTexture").is_some());
     assert!(buffer.field_id("flags").is_some());
+    assert!(buffer.field_id("albedoUvRect").is_some());
+    assert!(buffer.field_id("emissiveUvRect").is_some());
+    assert!(buffer.field_id(UV_SCROLL_PARAM).is_some());
+    assert!(buffer.field_id(UV_ROTATION_SPEED_PARAM).is_some());
 }
 
 #[test]
@@ -1759,8 +2233,9 @@ fn test_default_material_buffer_stride() {
     ).unwrap();
     let buffer = rm.buffer(buffer_key).unwrap();
 
-    // 2×Vec4(16) + 6×Float(4) + 16×UInt(4) = 32 + 24 + 64 = 120, padded to 128 (std430 alignment)
-    assert_eq!(buffer.stride(), 128);
+    // 2×Vec4(16) + 6×Float(4) + 16×UInt(4) = 32 + 24 + 64 = 120, padded to 128 (Vec4 alignment)
+    // + 2×Vec4(16) + Vec2(8) + Float(4) = 128 + 44 = 172, padded to 176 (std430 alignment)
+    assert_eq!(buffer.stride(), 176);
 }
 
 #[test]
@@ -1791,7 +2266,7 @@ fn test_sync_materials_texture_slot_writes_layer() {
     let tex_desc = TextureDesc {
         graphics_device: graphics_device.clone(),
         texture: graphics_device::TextureDesc {
-            width: 64, height: 64,
+            width: 64, height: 64, depth: 1,
             format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
             usage: graphics_device::TextureUsage::Sampled,
             array_layers: 3,
@@ -1799,6 +2274,7 @@ fn test_sync_materials_texture_slot_writes_layer() {
             mipmap: graphics_device::MipmapMode::None,
             texture_type: graphics_device::TextureType::Array2D,
             sample_count: graphics_device::SampleCount::S1,
+            debug_name: None,
         },
         layers: vec![
             LayerDesc { name: "grass".to_string(), layer_index: 0, data: None, regions: vec![] },
@@ -1824,9 +2300,12 @@ fn test_sync_materials_texture_slot_writes_layer() {
                 layer: Some(LayerRef::Index(2)),
                 region: None,
                 sampler_type: graphics_device::SamplerType::LinearRepeat,
+                flipbook: None,
             }],
             params: vec![],
             render_state: None,
+            engine_features: Default::default(),
+            render_queue: None,
         }],
     };
     rm.create_material("ground".to_string(), mat_desc, &*graphics_device.lock().unwrap()).unwrap();
@@ -1871,9 +2350,12 @@ fn test_sync_materials_texture_slot_no_layer_writes_zero() {
                 layer: None,
                 region: None,
                 sampler_type: graphics_device::SamplerType::LinearRepeat,
+                flipbook: None,
             }],
             params: vec![],
             render_state: None,
+            engine_features: Default::default(),
+            render_queue: None,
         }],
     };
     rm.create_material("flat".to_string(), mat_desc, &*graphics_device.lock().unwrap()).unwrap();
@@ -1915,9 +2397,12 @@ fn test_sync_materials_texture_slot_missing_field_skips() {
                 layer: None,
                 region: None,
                 sampler_type: graphics_device::SamplerType::LinearRepeat,
+                flipbook: None,
             }],
             params: vec![],
             render_state: None,
+            engine_features: Default::default(),
+            render_queue: None,
         }],
     };
     rm.create_material("mat".to_string(), mat_desc, &*graphics_device.lock().unwrap()).unwrap();
@@ -1958,9 +2443,12 @@ fn test_sync_materials_texture_slot_wrong_type_skips() {
                 layer: None,
                 region: None,
                 sampler_type: graphics_device::SamplerType::LinearRepeat,
+                flipbook: None,
             }],
             params: vec![],
             render_state: None,
+            engine_features: Default::default(),
+            render_queue: None,
         }],
     };
     rm.create_material("mat".to_string(), mat_desc, &*graphics_device.lock().unwrap()).unwrap();
@@ -1978,6 +2466,60 @@ fn test_sync_materials_texture_slot_wrong_type_skips() {
     assert!(rm.sync_materials_to_buffer(rm.buffer(buffer).unwrap()).is_ok());
 }
 
+#[test]
+fn test_sync_material_animations_writes_flipbook_uv_rect() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+
+    let mut tex_desc = create_test_texture_desc(graphics_device.clone(), "sheet", 64, 64);
+    tex_desc.layers[0].regions = (0..4).map(|i| AtlasRegionDesc {
+        name: format!("frame{}", i),
+        region: AtlasRegion { x: (i % 2) * 32, y: (i / 2) * 32, width: 32, height: 32 },
+    }).collect();
+    let texture = rm.create_texture("sheet".to_string(), tex_desc).unwrap();
+
+    let (vk, fk) = create_test_shaders(&mut rm, &graphics_device); let pipe_desc = create_test_pipeline_desc(vk, fk);
+    let _pipeline = rm.create_pipeline("standard".to_string(), pipe_desc, &mut *graphics_device.lock().unwrap()).unwrap();
+
+    let mat_desc = MaterialDesc {
+        passes: vec![MaterialPassDesc {
+            pass_type: 0,
+            fragment_shader: fk,
+            color_blend: Default::default(),
+            polygon_mode: PolygonMode::Fill,
+            textures: vec![MaterialTextureSlotDesc {
+                name: "albedo".to_string(),
+                texture,
+                layer: Some(LayerRef::Index(0)),
+                region: None,
+                sampler_type: graphics_device::SamplerType::LinearRepeat,
+                flipbook: Some(FlipbookDesc {
+                    frames: (0..4).map(RegionRef::Index).collect(),
+                    frames_per_second: 12.0,
+                    looping: true,
+                }),
+            }],
+            params: vec![
+                (UV_SCROLL_PARAM.to_string(), ParamValue::Vec2([0.1, 0.0])),
+                (UV_ROTATION_SPEED_PARAM.to_string(), ParamValue::Float(0.5)),
+            ],
+            render_state: None,
+            engine_features: Default::default(),
+            render_queue: None,
+        }],
+    };
+    let mat_key = rm.create_material("fire".to_string(), mat_desc, &*graphics_device.lock().unwrap()).unwrap();
+    let slot = rm.material(mat_key).unwrap().pass(0).unwrap().texture_slot_by_name("albedo").unwrap();
+    assert_eq!(slot.region_at(0.3), Some(3));
+
+    let buffer_key = rm.create_default_material_buffer(
+        "material".to_string(), graphics_device.clone(), 4,
+    ).unwrap();
+    let buffer = rm.buffer(buffer_key).unwrap();
+    assert!(rm.sync_materials_to_buffer(buffer).is_ok());
+    assert!(rm.sync_material_animations(buffer, 0.3).is_ok());
+}
+
 // ============================================================================
 // Buffer accessor + remove paths
 // ============================================================================
@@ -2083,6 +2625,20 @@ mod buffer_api {
         let key = rm.create_default_instance_buffer("inst".to_string(), gd, 8).unwrap();
         assert!(rm.buffer(key).is_some());
     }
+
+    #[test]
+    fn test_create_default_light_cluster_buffers_are_packed() {
+        let gd = Arc::new(Mutex::new(graphics_device::mock_graphics_device::MockGraphicsDevice::new()));
+        let mut rm = ResourceManager::new();
+        let clusters = rm.create_default_light_cluster_buffer("clusters".to_string(), gd.clone(), 24).unwrap();
+        let indices = rm.create_default_light_index_buffer("indices".to_string(), gd, 96).unwrap();
+
+        // std430: `uvec2`-sized cluster ranges and a tight `uint` array
+        assert_eq!(rm.buffer(clusters).unwrap().stride(), 8);
+        assert_eq!(rm.buffer(clusters).unwrap().count(), 24);
+        assert_eq!(rm.buffer(indices).unwrap().stride(), 4);
+        assert_eq!(rm.buffer(indices).unwrap().size(), 96 * 4);
+    }
 }
 
 // ============================================================================
@@ -2098,7 +2654,7 @@ mod texture_accessors {
         rm.create_texture(name.to_string(), ResTextureDesc {
             graphics_device: gd,
             texture: graphics_device::TextureDesc {
-                width: 32, height: 32,
+                width: 32, height: 32, depth: 1,
                 format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
                 usage: graphics_device::TextureUsage::Sampled,
                 texture_type: graphics_device::TextureType::Tex2D,
@@ -2106,6 +2662,7 @@ mod texture_accessors {
                 array_layers: 1,
                 data: None,
                 mipmap: graphics_device::MipmapMode::None,
+                debug_name: None,
             },
             layers: vec![LayerDesc { name: "default".to_string(), layer_index: 0, data: None, regions: vec![] }],
         }).unwrap()
@@ -2137,3 +2694,637 @@ mod texture_accessors {
         assert!(!rm.remove_texture(key));
     }
 }
+
+// ============================================================================
+// Tests: external_references (leak report)
+// ============================================================================
+
+#[test]
+fn test_external_references_empty_when_unshared() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+    let desc = create_test_texture_desc(graphics_device.clone(), "tex", 64, 64);
+    rm.create_texture("tex".to_string(), desc).unwrap();
+
+    assert!(rm.external_references().is_empty());
+}
+
+#[test]
+fn test_external_references_reports_held_clones() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+    let desc = create_test_texture_desc(graphics_device.clone(), "held", 64, 64);
+    rm.create_texture("held".to_string(), desc).unwrap();
+    let desc = create_test_texture_desc(graphics_device.clone(), "free", 64, 64);
+    rm.create_texture("free".to_string(), desc).unwrap();
+
+    let _held = Arc::clone(rm.texture_by_name("held").unwrap());
+
+    let leaks = rm.external_references();
+    assert_eq!(leaks, vec![ResourceLeak {
+        kind: "Texture",
+        name: "held".to_string(),
+        ref_count: 2,
+    }]);
+}
+
+// ============================================================================
+// Tests: texture usage
+// ============================================================================
+
+/// Create "used" (layer 1 of a 2-layer array) and "unused" textures plus a
+/// material sampling "used". Returns (used, unused, material).
+fn create_texture_usage_setup(rm: &mut ResourceManager) -> (TextureKey, TextureKey, MaterialKey) {
+    let graphics_device = create_mock_graphics_device();
+    let mut used_desc = create_test_texture_desc(graphics_device.clone(), "used", 64, 64);
+    used_desc.texture.array_layers = 2;
+    used_desc.texture.data = None;
+    used_desc.texture.texture_type = graphics_device::TextureType::Array2D;
+    used_desc.layers.push(LayerDesc {
+        name: "second".to_string(), layer_index: 1, data: None, regions: vec![],
+    });
+    let used = rm.create_texture("used".to_string(), used_desc).unwrap();
+    let unused_desc = create_test_texture_desc(graphics_device.clone(), "unused", 64, 64);
+    let unused = rm.create_texture("unused".to_string(), unused_desc).unwrap();
+
+    let (_vk, fk) = create_test_shaders(rm, &graphics_device);
+    let material = rm.create_material("mat".to_string(), MaterialDesc {
+        passes: vec![MaterialPassDesc {
+            pass_type: 0,
+            fragment_shader: fk,
+            color_blend: Default::default(),
+            polygon_mode: PolygonMode::Fill,
+            textures: vec![MaterialTextureSlotDesc {
+                name: "albedo".to_string(),
+                texture: used,
+                layer: Some(LayerRef::Index(1)),
+                region: None,
+                sampler_type: graphics_device::SamplerType::LinearRepeat,
+                flipbook: None,
+            }],
+            params: vec![],
+            render_state: None,
+            engine_features: Default::default(),
+            render_queue: None,
+        }],
+    }, &*graphics_device.lock().unwrap()).unwrap();
+
+    (used, unused, material)
+}
+
+#[test]
+fn test_record_material_texture_usage() {
+    let mut rm = ResourceManager::new();
+    let (used, unused, material) = create_texture_usage_setup(&mut rm);
+
+    rm.begin_texture_usage_frame();
+    rm.record_material_texture_usage(material, 0);
+    rm.record_material_texture_usage(material, 0);
+
+    let stats = rm.texture_usage(used).unwrap();
+    assert_eq!(stats.last_used_frame, 1);
+    assert_eq!(stats.draw_count, 2);
+    assert_eq!(stats.layers, vec![1]);
+    assert!(rm.texture_usage(unused).is_none());
+}
+
+#[test]
+fn test_record_material_texture_usage_ignores_unknown_pass() {
+    let mut rm = ResourceManager::new();
+    let (used, _unused, material) = create_texture_usage_setup(&mut rm);
+
+    rm.record_material_texture_usage(material, 5);
+    rm.record_material_texture_usage(MaterialKey::default(), 0);
+    assert!(rm.texture_usage(used).is_none());
+}
+
+#[test]
+fn test_texture_usage_report_lists_every_texture() {
+    let mut rm = ResourceManager::new();
+    let (used, _unused, material) = create_texture_usage_setup(&mut rm);
+
+    rm.begin_texture_usage_frame();
+    rm.record_material_texture_usage(material, 0);
+    rm.begin_texture_usage_frame();
+
+    let report = rm.texture_usage_report();
+    assert_eq!(report.frame, 2);
+    let names: Vec<&str> = report.textures.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, vec!["unused", "used"]);
+    assert_eq!(report.textures[1].texture, used);
+    assert_eq!(report.textures[1].frames_idle, Some(1));
+    let never: Vec<&str> = report.never_used().map(|r| r.name.as_str()).collect();
+    assert_eq!(never, vec!["unused"]);
+}
+
+#[test]
+fn test_remove_texture_drops_usage() {
+    let mut rm = ResourceManager::new();
+    let (used, _unused, material) = create_texture_usage_setup(&mut rm);
+
+    rm.record_material_texture_usage(material, 0);
+    assert!(rm.texture_usage(used).is_some());
+    rm.remove_material("mat");
+    assert!(rm.remove_texture(used));
+    assert!(rm.texture_usage(used).is_none());
+}
+
+// ============================================================================
+// Engine Features Tests
+// ============================================================================
+
+#[test]
+fn test_set_engine_features_bumps_generation() {
+    let mut rm = ResourceManager::new();
+    assert_eq!(rm.engine_features(), graphics_device::EngineFeatures::NONE);
+    assert_eq!(rm.engine_features_generation(), 0);
+
+    assert!(rm.set_engine_features(graphics_device::EngineFeatures::SHADOWS));
+    assert_eq!(rm.engine_features(), graphics_device::EngineFeatures::SHADOWS);
+    assert_eq!(rm.engine_features_generation(), 1);
+
+    // Same mask: no change, no invalidation
+    assert!(!rm.set_engine_features(graphics_device::EngineFeatures::SHADOWS));
+    assert_eq!(rm.engine_features_generation(), 1);
+}
+
+#[test]
+fn test_set_debug_view_bumps_generation() {
+    let mut rm = ResourceManager::new();
+    assert_eq!(rm.debug_view(), graphics_device::DebugViewMode::None);
+
+    assert!(rm.set_debug_view(graphics_device::DebugViewMode::Wireframe));
+    assert_eq!(rm.debug_view(), graphics_device::DebugViewMode::Wireframe);
+    assert_eq!(rm.engine_features_generation(), 1);
+    assert!(!rm.set_debug_view(graphics_device::DebugViewMode::Wireframe));
+    assert_eq!(rm.engine_features_generation(), 1);
+
+    // The feature mask is independent of the debug view
+    assert_eq!(rm.engine_features(), graphics_device::EngineFeatures::NONE);
+    assert!(rm.set_debug_view(graphics_device::DebugViewMode::None));
+    assert_eq!(rm.engine_features_generation(), 2);
+}
+
+#[test]
+fn test_resolve_pipeline_wireframe_variant() {
+    let graphics_device = create_mock_graphics_device();
+    let mut rm = ResourceManager::new();
+    let (vk, fk) = create_test_shaders(&mut rm, &graphics_device);
+    let layout = Arc::new(create_test_pipeline_desc(vk, fk).vertex_layout);
+    let pass_info = PassInfo::new(
+        vec![graphics_device::TextureFormat::R8G8B8A8_UNORM], None, graphics_device::SampleCount::S1,
+    );
+    let resolve = |rm: &mut ResourceManager, mode: graphics_device::DebugViewMode| rm.resolve_pipeline(
+        vk, fk, layout.clone(), graphics_device::PrimitiveTopology::TriangleList,
+        &Default::default(), mode.polygon_mode(PolygonMode::Fill), &pass_info, mode.engine_features(),
+        &mut *graphics_device.lock().unwrap(),
+    ).unwrap();
+
+    let base = resolve(&mut rm, graphics_device::DebugViewMode::None);
+    let wireframe = resolve(&mut rm, graphics_device::DebugViewMode::Wireframe);
+    let normals = resolve(&mut rm, graphics_device::DebugViewMode::Normals);
+    assert_ne!(base, wireframe);
+    assert_ne!(base, normals);
+    assert_eq!(rm.pipeline_count(), 3);
+    assert_eq!(resolve(&mut rm, graphics_device::DebugViewMode::None), base);
+}
+
+#[test]
+fn test_resolve_pipeline_one_variant_per_feature_mask() {
+    let graphics_device = create_mock_graphics_device();
+    let mut rm = ResourceManager::new();
+    let (vk, fk) = create_test_shaders(&mut rm, &graphics_device);
+    let layout = Arc::new(create_test_pipeline_desc(vk, fk).vertex_layout);
+    let pass_info = PassInfo::new(
+        vec![graphics_device::TextureFormat::R8G8B8A8_UNORM], None, graphics_device::SampleCount::S1,
+    );
+    let resolve = |rm: &mut ResourceManager, features| rm.resolve_pipeline(
+        vk, fk, layout.clone(), graphics_device::PrimitiveTopology::TriangleList,
+        &Default::default(), PolygonMode::Fill, &pass_info, features,
+        &mut *graphics_device.lock().unwrap(),
+    ).unwrap();
+
+    let base = resolve(&mut rm, graphics_device::EngineFeatures::NONE);
+    let shadows = resolve(&mut rm, graphics_device::EngineFeatures::SHADOWS);
+    assert_ne!(base, shadows);
+    assert_eq!(rm.pipeline_count(), 2);
+
+    // Toggling back reuses the cached variant
+    assert_eq!(resolve(&mut rm, graphics_device::EngineFeatures::NONE), base);
+    assert_eq!(rm.pipeline_count(), 2);
+}
+
+// ============================================================================
+// Tests: enumeration and statistics
+// ============================================================================
+
+#[test]
+fn test_textures_iterates_name_key_and_resource() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+    let a = rm.create_texture("a".to_string(),
+        create_test_texture_desc(graphics_device.clone(), "a", 64, 64)).unwrap();
+    let b = rm.create_texture("b".to_string(),
+        create_test_texture_desc(graphics_device.clone(), "b", 32, 32)).unwrap();
+
+    let mut entries: Vec<_> = rm.textures().collect();
+    entries.sort_by_key(|entry| entry.name);
+
+    assert_eq!(entries.len(), 2);
+    assert_eq!((entries[0].name, entries[0].key), ("a", a));
+    assert_eq!((entries[1].name, entries[1].key), ("b", b));
+    assert!(Arc::ptr_eq(entries[1].resource, rm.texture(b).unwrap()));
+}
+
+#[test]
+fn test_enumeration_empty_manager() {
+    let rm = ResourceManager::new();
+
+    assert_eq!(rm.textures().count(), 0);
+    assert_eq!(rm.materials().count(), 0);
+    assert_eq!(rm.meshes().count(), 0);
+    assert_eq!(rm.stats(), ResourceStats::default());
+}
+
+#[test]
+fn test_enumeration_skips_removed_resources() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+    rm.create_texture("kept".to_string(),
+        create_test_texture_desc(graphics_device.clone(), "kept", 64, 64)).unwrap();
+    rm.create_texture("removed".to_string(),
+        create_test_texture_desc(graphics_device.clone(), "removed", 64, 64)).unwrap();
+
+    rm.remove_texture_by_name("removed");
+
+    let names: Vec<_> = rm.textures().map(|entry| entry.name).collect();
+    assert_eq!(names, vec!["kept"]);
+}
+
+#[test]
+fn test_stats_counts_resources() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+    rm.create_texture("tex".to_string(),
+        create_test_texture_desc(graphics_device.clone(), "tex", 64, 64)).unwrap();
+    rm.create_texture("held".to_string(),
+        create_test_texture_desc(graphics_device.clone(), "held", 64, 64)).unwrap();
+    let _held = Arc::clone(rm.texture_by_name("held").unwrap());
+    rm.create_default_frame_uniform_buffer("frame".to_string(), graphics_device.clone()).unwrap();
+
+    let stats = rm.stats();
+    assert_eq!(stats.textures, 2);
+    assert_eq!(stats.buffers, 1);
+    assert_eq!(stats.total(), 3);
+    assert_eq!(stats.buffer_bytes, rm.buffer_by_name("frame").unwrap().size());
+    assert_eq!(stats.externally_referenced, 1);
+    assert_eq!(stats.retired_resources, 0);
+}
+
+#[test]
+fn test_pin_texture_counts_pins() {
+    let mut rm = ResourceManager::new();
+    let (used, unused, _material) = create_texture_usage_setup(&mut rm);
+
+    assert_eq!(rm.pin_texture(unused).unwrap(), 1);
+    assert_eq!(rm.pin_texture(unused).unwrap(), 2);
+    assert!(rm.pin_texture(TextureKey::default()).is_err());
+
+    let report = rm.texture_usage_report();
+    let idle: Vec<TextureKey> = report.idle_for(0).iter().map(|r| r.texture).collect();
+    assert_eq!(idle, vec![used]);
+
+    assert!(rm.unpin_texture(unused));
+    assert_eq!(rm.texture_pin_count(unused), 1);
+    assert!(rm.unpin_texture(unused));
+    assert!(!rm.unpin_texture(unused));
+    assert_eq!(rm.texture_pin_count(unused), 0);
+    assert_eq!(rm.texture_usage_report().idle_for(0).len(), 2);
+}
+
+#[test]
+fn test_pin_buffer_reported_in_stats() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+    let key = rm.create_default_frame_uniform_buffer("frame".to_string(), graphics_device).unwrap();
+
+    rm.pin_buffer(key).unwrap();
+    assert_eq!(rm.buffer_pin_count(key), 1);
+    let stats = rm.stats();
+    assert_eq!(stats.pinned_buffers, 1);
+    assert_eq!(stats.pinned_textures, 0);
+    assert_eq!(stats.pinned_buffer_bytes, rm.buffer(key).unwrap().size());
+
+    assert!(rm.remove_buffer("frame"));
+    assert_eq!(rm.buffer_pin_count(key), 0);
+    assert_eq!(rm.stats().pinned_buffers, 0);
+    assert!(rm.pin_buffer(key).is_err());
+}
+
+// ============================================================================
+// Tests: default resources
+// ============================================================================
+
+/// Create the default resources with a one-pass error material
+fn create_test_default_resources(
+    rm: &mut ResourceManager,
+    graphics_device: &Arc<Mutex<dyn graphics_device::GraphicsDevice>>,
+) -> DefaultResources {
+    let (_, fk) = create_test_shaders(rm, graphics_device);
+    rm.create_default_resources(DefaultResourcesDesc {
+        graphics_device: graphics_device.clone(),
+        error_passes: vec![(0, fk)],
+    }).unwrap()
+}
+
+#[test]
+fn test_create_default_resources_registers_named_resources() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+    let defaults = create_test_default_resources(&mut rm, &graphics_device);
+
+    assert_eq!(rm.texture_key(DEFAULT_WHITE_TEXTURE), Some(defaults.white_texture));
+    assert_eq!(rm.texture_key(DEFAULT_BLACK_TEXTURE), Some(defaults.black_texture));
+    assert_eq!(rm.texture_key(DEFAULT_NORMAL_TEXTURE), Some(defaults.normal_texture));
+    assert_eq!(rm.material_key(ERROR_MATERIAL), Some(defaults.error_material));
+    assert_eq!(rm.mesh_key(ERROR_CUBE_MESH), Some(defaults.error_cube_mesh));
+    assert_eq!(rm.default_resources(), Some(&defaults));
+
+    let error_material = rm.material(defaults.error_material).unwrap();
+    let pass = error_material.pass(0).unwrap();
+    assert_eq!(pass.texture_slot_by_name(ERROR_ALBEDO_SLOT).unwrap().texture(), defaults.white_texture);
+    let geometry = rm.geometry(defaults.error_cube_geometry).unwrap();
+    assert_eq!(geometry.total_vertex_count(), 24);
+    assert_eq!(geometry.total_index_count(), 36);
+}
+
+#[test]
+fn test_create_default_resources_twice_fails() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+    create_test_default_resources(&mut rm, &graphics_device);
+
+    let (_, fk) = create_test_shaders(&mut rm, &graphics_device);
+    let result = rm.create_default_resources(DefaultResourcesDesc {
+        graphics_device: graphics_device.clone(),
+        error_passes: vec![(0, fk)],
+    });
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_missing_material_texture_uses_fallback_texture() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+    let defaults = create_test_default_resources(&mut rm, &graphics_device);
+    let (_, fk) = create_test_shaders(&mut rm, &graphics_device);
+
+    let mut desc = create_test_material_desc(fk);
+    desc.passes[0].textures = vec![
+        MaterialTextureSlotDesc {
+            name: "albedo".to_string(),
+            texture: TextureKey::default(),
+            layer: Some(LayerRef::Name("missing".to_string())),
+            region: None,
+            sampler_type: graphics_device::SamplerType::LinearRepeat,
+            flipbook: None,
+        },
+        MaterialTextureSlotDesc {
+            name: "normal".to_string(),
+            texture: TextureKey::default(),
+            layer: None,
+            region: None,
+            sampler_type: graphics_device::SamplerType::LinearRepeat,
+            flipbook: None,
+        },
+    ];
+    let key = rm.create_material("broken".to_string(), desc, &*graphics_device.lock().unwrap()).unwrap();
+
+    let pass = rm.material(key).unwrap().pass(0).unwrap();
+    assert_eq!(pass.texture_slot_by_name("albedo").unwrap().texture(), defaults.white_texture);
+    assert_eq!(pass.texture_slot_by_name("albedo").unwrap().layer(), None);
+    assert_eq!(pass.texture_slot_by_name("normal").unwrap().texture(), defaults.normal_texture);
+}
+
+#[test]
+fn test_missing_material_texture_fails_without_defaults() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+    let (_, fk) = create_test_shaders(&mut rm, &graphics_device);
+
+    let mut desc = create_test_material_desc(fk);
+    desc.passes[0].textures = vec![MaterialTextureSlotDesc {
+        name: "albedo".to_string(),
+        texture: TextureKey::default(),
+        layer: None,
+        region: None,
+        sampler_type: graphics_device::SamplerType::LinearRepeat,
+        flipbook: None,
+    }];
+
+    assert!(rm.create_material("broken".to_string(), desc, &*graphics_device.lock().unwrap()).is_err());
+}
+
+#[test]
+fn test_missing_mesh_material_uses_error_material() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+    let defaults = create_test_default_resources(&mut rm, &graphics_device);
+    let (geometry, _) = create_mesh_prerequisites(&mut rm, &graphics_device, "fallback");
+
+    let key = rm.create_mesh("broken".to_string(),
+        create_test_mesh_desc(geometry, MaterialKey::default())).unwrap();
+
+    assert_eq!(rm.mesh(key).unwrap().submesh(0).unwrap().material(), defaults.error_material);
+}
+
+#[test]
+fn test_clear_forgets_default_resources() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+    create_test_default_resources(&mut rm, &graphics_device);
+
+    rm.clear();
+
+    assert!(rm.default_resources().is_none());
+    create_test_default_resources(&mut rm, &graphics_device);
+}
+
+// ============================================================================
+// Tests: standard PBR
+// ============================================================================
+
+fn create_test_standard_pbr(
+    rm: &mut ResourceManager,
+    graphics_device: &Arc<Mutex<dyn graphics_device::GraphicsDevice>>,
+) -> StandardPbrShaders {
+    rm.create_standard_pbr_shaders(StandardPbrDesc::default(), &mut *graphics_device.lock().unwrap()).unwrap()
+}
+
+#[test]
+fn test_create_standard_pbr_shaders_registers_named_shaders() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+    let shaders = create_test_standard_pbr(&mut rm, &graphics_device);
+
+    assert_eq!(rm.shader_key(STANDARD_PBR_VERTEX_SHADER), Some(shaders.vertex_shader));
+    assert_eq!(rm.shader_key(STANDARD_PBR_FRAGMENT_SHADER), Some(shaders.fragment_shader));
+    assert_eq!(rm.shader_key("galaxy3d::standard_pbr_vs_morph3_normals"), Some(shaders.morph_vertex_shaders[2][1]));
+    assert_eq!(rm.shader_count(), 10);
+    assert_eq!(rm.standard_pbr_shaders(), Some(&shaders));
+
+    let again = rm.create_standard_pbr_shaders(StandardPbrDesc::default(), &mut *graphics_device.lock().unwrap());
+    assert!(again.is_err());
+
+    rm.clear();
+    assert!(rm.standard_pbr_shaders().is_none());
+}
+
+#[test]
+fn test_standard_pbr_vertex_shader_for_matches_morph_targets() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+    let shaders = create_test_standard_pbr(&mut rm, &graphics_device);
+
+    let plain = rm.create_geometry("plain".to_string(),
+        create_test_geometry_desc(graphics_device.clone(), "plain")).unwrap();
+    let mut desc = create_test_geometry_desc(graphics_device.clone(), "morphed");
+    desc.morph_targets = ["smile", "blink"].iter().map(|name| MorphTargetDesc {
+        name: name.to_string(),
+        position_deltas: vec![[0.0, 0.1, 0.0]; 4],
+        normal_deltas: Some(vec![[0.0, 0.0, 1.0]; 4]),
+    }).collect();
+    let morphed = rm.create_geometry("morphed".to_string(), desc).unwrap();
+
+    assert_eq!(shaders.vertex_shader_for(rm.geometry(plain).unwrap()), shaders.vertex_shader);
+    assert_eq!(shaders.vertex_shader_for(rm.geometry(morphed).unwrap()), shaders.morph_vertex_shaders[1][1]);
+    assert_eq!(rm.shader_key(&standard_pbr_morph_vertex_shader_name(2, true)), Some(shaders.morph_vertex_shaders[1][1]));
+}
+
+#[test]
+fn test_create_standard_pbr_material_writes_default_fields() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+    let shaders = create_test_standard_pbr(&mut rm, &graphics_device);
+    let albedo = rm.create_texture("albedo".to_string(),
+        create_test_texture_desc(graphics_device.clone(), "albedo", 4, 4)).unwrap();
+
+    let key = rm.create_standard_pbr_material("gold".to_string(), StandardPbrMaterialDesc {
+        base_color: [1.0, 0.8, 0.3, 1.0],
+        metallic: 1.0,
+        albedo_texture: Some(albedo),
+        ..Default::default()
+    }, &*graphics_device.lock().unwrap()).unwrap();
+    let buffer_key = rm.create_default_material_buffer("materials".to_string(), graphics_device.clone(), 4).unwrap();
+
+    let pass = rm.material(key).unwrap().pass(0).unwrap();
+    assert_eq!(pass.fragment_shader(), shaders.fragment_shader);
+    assert_eq!(pass.texture_slots().len(), 1);
+    assert_eq!(pass.texture_slot_by_name(PBR_ALBEDO_SLOT).unwrap().texture(), albedo);
+    assert!(matches!(pass.param_by_name(PBR_METALLIC_PARAM).unwrap().value(), ParamValue::Float(m) if *m == 1.0));
+
+    // Every param matches a default material buffer field and type
+    assert!(rm.sync_materials_to_buffer(rm.buffer(buffer_key).unwrap()).is_ok());
+    let buffer = rm.buffer(buffer_key).unwrap();
+    for param in pass.params() {
+        assert!(buffer.field_id(param.name()).is_some(), "{}", param.name());
+    }
+}
+
+#[test]
+fn test_create_standard_pbr_material_requires_shaders_and_2d_textures() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+    let desc = || StandardPbrMaterialDesc::default();
+    assert!(rm.create_standard_pbr_material("early".to_string(), desc(), &*graphics_device.lock().unwrap()).is_err());
+
+    create_test_standard_pbr(&mut rm, &graphics_device);
+    let mut array_desc = create_test_texture_desc(graphics_device.clone(), "array", 4, 4);
+    array_desc.texture.texture_type = graphics_device::TextureType::Array2D;
+    array_desc.texture.data = None;
+    let array = rm.create_texture("array".to_string(), array_desc).unwrap();
+
+    let result = rm.create_standard_pbr_material("layered".to_string(), StandardPbrMaterialDesc {
+        normal_texture: Some(array),
+        ..desc()
+    }, &*graphics_device.lock().unwrap());
+    assert!(result.is_err());
+    assert!(rm.create_standard_pbr_material("plain".to_string(), desc(), &*graphics_device.lock().unwrap()).is_ok());
+}
+
+// ============================================================================
+// Tests: shader hot-reload
+// ============================================================================
+
+#[test]
+fn test_reload_shader_recreates_dependent_pipelines_in_place() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+    let (vk, fk) = create_test_shaders(&mut rm, &graphics_device);
+    let (other_vk, other_fk) = create_test_shaders(&mut rm, &graphics_device);
+    let pipeline = rm.create_pipeline("b_pipe".to_string(),
+        create_test_pipeline_desc(vk, fk), &mut *graphics_device.lock().unwrap()).unwrap();
+    rm.create_pipeline("a_pipe".to_string(),
+        create_test_pipeline_desc(other_vk, fk), &mut *graphics_device.lock().unwrap()).unwrap();
+    let untouched = rm.create_pipeline("other".to_string(),
+        create_test_pipeline_desc(other_vk, other_fk), &mut *graphics_device.lock().unwrap()).unwrap();
+
+    let old_gpu_pipeline = Arc::clone(rm.pipeline(pipeline).unwrap().graphics_device_pipeline());
+    let old_untouched = Arc::clone(rm.pipeline(untouched).unwrap());
+    let sort_id = rm.pipeline(pipeline).unwrap().sort_id();
+
+    let report = rm.reload_shader(fk,
+        ShaderDesc { code: &[], stage: graphics_device::ShaderStage::Fragment, entry_point: "main".to_string() },
+        &mut *graphics_device.lock().unwrap(),
+    ).unwrap();
+
+    assert_eq!(rm.shader_key(&report.shader), Some(fk));
+    assert_eq!(report.recreated_pipelines, vec!["a_pipe".to_string(), "b_pipe".to_string()]);
+    let reloaded = rm.pipeline(pipeline).unwrap();
+    assert!(!Arc::ptr_eq(reloaded.graphics_device_pipeline(), &old_gpu_pipeline));
+    assert_eq!(reloaded.fragment_shader(), fk);
+    assert_eq!(reloaded.sort_id(), sort_id);
+    assert!(Arc::ptr_eq(rm.pipeline(untouched).unwrap(), &old_untouched));
+    assert_eq!(rm.pipeline_count(), 3);
+}
+
+#[test]
+fn test_reload_shader_stage_mismatch_keeps_previous_version() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+    let (vk, fk) = create_test_shaders(&mut rm, &graphics_device);
+    let old_shader = Arc::clone(rm.shader(fk).unwrap());
+
+    let err = rm.reload_shader(fk,
+        ShaderDesc { code: &[], stage: graphics_device::ShaderStage::Vertex, entry_point: "main".to_string() },
+        &mut *graphics_device.lock().unwrap(),
+    ).err().unwrap();
+
+    assert_eq!(err.code(), crate::error::ErrorCode::Incompatible);
+    assert!(Arc::ptr_eq(rm.shader(fk).unwrap(), &old_shader));
+    assert!(rm.shader(vk).is_some());
+}
+
+#[test]
+fn test_reload_unknown_shader_fails() {
+    let mut rm = ResourceManager::new();
+    let graphics_device = create_mock_graphics_device();
+
+    let err = rm.reload_shader(ShaderKey::default(),
+        ShaderDesc { code: &[], stage: graphics_device::ShaderStage::Fragment, entry_point: "main".to_string() },
+        &mut *graphics_device.lock().unwrap(),
+    ).err().unwrap();
+
+    assert_eq!(err.code(), crate::error::ErrorCode::ResourceNotFound);
+}
+
+#[test]
+fn test_shader_reload_report_display() {
+    let report = ShaderReloadReport {
+        shader: "lit_frag".to_string(),
+        recreated_pipelines: vec!["a".to_string(), "b".to_string()],
+        duration: std::time::Duration::from_micros(1500),
+    };
+
+    assert_eq!(report.to_string(), "Reloaded shader 'lit_frag' in 1.50 ms, recreated 2 pipeline(s): a, b");
+}
@@ -0,0 +1,250 @@
+/// Built-in PBR shaders and materials.
+///
+/// The engine ships a metallic-roughness PBR shader pair wired to the
+/// default buffers (`create_default_*_buffer()`), so scenes render with
+/// correct lighting without application shaders:
+///
+/// - Material inputs: `baseColor`, `metallic`, `roughness`, normal map
+///   (`normalScale`), `ao`, `emissiveColor`, and the matching texture slots.
+///   Metallic and roughness are read from the blue and green channels of
+///   `metallicRoughness` (glTF convention), occlusion from the red one.
+/// - Lighting: GGX specular, Lambert diffuse, the sun of the frame buffer,
+///   the point and spot lights, and the ambient term. Once a
+///   `LightClusterGrid` is uploaded the lights come from the fragment's
+///   cluster, otherwise from the instance's top-8 assignment. Fog applies with `EngineFeatures::FOG`. With
+///   `EngineFeatures::UNLIT` (`ForwardDrawerConfig::unlit`) the output is
+///   the base color plus the emission.
+/// - Flipbooks, UV scrolling/rotation, instance tints and the LOD
+///   cross-fade are honored.
+/// - Morph targets: geometries with morph targets use a vertex shader
+///   variant (`StandardPbrShaders::vertex_shader_for()`) blending
+///   `position + sum(morphWeights[t] * position_delta[t])`, and the same
+///   for normals when the targets have normal deltas.
+/// - The output is linear HDR: exposure and tone mapping are post effects.
+///
+/// The GLSL sources live in `galaxy_3d_engine/shaders/` and are embedded as
+/// `STANDARD_PBR_VERTEX_GLSL` and `STANDARD_PBR_FRAGMENT_GLSL`. Their
+/// SPIR-V, compiled by `shaders/compile.sh` into `shaders/spirv/`, is
+/// embedded too: `StandardPbrDesc::default()` passes it to
+/// `ResourceManager::create_standard_pbr_shaders()`.
+///
+/// Interface:
+///
+/// - Vertex inputs: position, normal and uv (`standard_pbr_vertex_layout()`,
+///   the error cube layout). The normal map frame is derived from screen
+///   derivatives, so no tangent attribute is needed. The morph variants
+///   also read the morph target stream (`MORPH_TARGET_BUFFER_BINDING`).
+/// - Set 0: the bindless table. Material textures must be `Tex2D`.
+/// - Set 1 (`ScenePassAction` bindings, in this order): frame UBO, instance
+///   SSBO, material SSBO, light SSBO, light cluster SSBO, light index SSBO.
+///   The last two are bound even when the grid is unused (one element
+///   each is enough).
+/// - Push constant: the draw slot. `ForwardDrawerConfig::instancing` and
+///   `motion_vectors` are not supported.
+///
+/// ```ignore
+/// let shaders = rm.create_standard_pbr_shaders(StandardPbrDesc::default(), &mut *gd.lock().unwrap())?;
+/// let gold = rm.create_standard_pbr_material("gold".to_string(), StandardPbrMaterialDesc {
+///     base_color: [1.0, 0.78, 0.34, 1.0],
+///     metallic: 1.0,
+///     roughness: 0.3,
+///     ..Default::default()
+/// }, &*gd.lock().unwrap())?;
+/// let vertex_shader = shaders.vertex_shader_for(rm.geometry(geometry).unwrap());
+/// scene.add_mesh_instance(mesh, world, vertex_shader, &rm)?;
+/// ```
+
+use crate::graphics_device::{self, DynamicRenderState, SamplerType};
+use crate::resource::resource_manager::{ShaderKey, TextureKey};
+use crate::resource::default_resources::{
+    self, ERROR_CUBE_NORMAL_LOCATION, ERROR_CUBE_UV_LOCATION,
+};
+use crate::resource::geometry::{Geometry, GEOMETRY_POSITION_LOCATION, MAX_MORPH_TARGETS};
+
+/// GLSL source of the standard PBR vertex shader
+pub const STANDARD_PBR_VERTEX_GLSL: &str = include_str!("../../shaders/standard_pbr.vert");
+/// GLSL source of the standard PBR fragment shader
+pub const STANDARD_PBR_FRAGMENT_GLSL: &str = include_str!("../../shaders/standard_pbr.frag");
+
+/// SPIR-V of the standard PBR vertex shader
+pub static STANDARD_PBR_VERTEX_SPIRV: &[u8] = include_spirv!("standard_pbr.vert.spv");
+/// SPIR-V of the standard PBR fragment shader
+pub static STANDARD_PBR_FRAGMENT_SPIRV: &[u8] = include_spirv!("standard_pbr.frag.spv");
+/// SPIR-V of the morph variants of the vertex shader, indexed by
+/// `[morph target count - 1][has normal deltas]`
+pub static STANDARD_PBR_MORPH_VERTEX_SPIRV: [[&[u8]; 2]; MAX_MORPH_TARGETS] = [
+    [include_spirv!("standard_pbr_morph1.vert.spv"), include_spirv!("standard_pbr_morph1_normals.vert.spv")],
+    [include_spirv!("standard_pbr_morph2.vert.spv"), include_spirv!("standard_pbr_morph2_normals.vert.spv")],
+    [include_spirv!("standard_pbr_morph3.vert.spv"), include_spirv!("standard_pbr_morph3_normals.vert.spv")],
+    [include_spirv!("standard_pbr_morph4.vert.spv"), include_spirv!("standard_pbr_morph4_normals.vert.spv")],
+];
+
+/// Name of the standard PBR vertex shader resource
+pub const STANDARD_PBR_VERTEX_SHADER: &str = "galaxy3d::standard_pbr_vs";
+/// Name of the standard PBR fragment shader resource
+pub const STANDARD_PBR_FRAGMENT_SHADER: &str = "galaxy3d::standard_pbr_fs";
+
+/// Name of the morph variant of the vertex shader for `target_count`
+/// targets, with or without normal deltas
+pub fn standard_pbr_morph_vertex_shader_name(target_count: usize, normals: bool) -> String {
+    let suffix = if normals { "_normals" } else { "" };
+    format!("{}_morph{}{}", STANDARD_PBR_VERTEX_SHADER, target_count, suffix)
+}
+
+/// Vertex attribute location of the position
+pub const STANDARD_PBR_POSITION_LOCATION: u32 = GEOMETRY_POSITION_LOCATION;
+/// Vertex attribute location of the normal
+pub const STANDARD_PBR_NORMAL_LOCATION: u32 = ERROR_CUBE_NORMAL_LOCATION;
+/// Vertex attribute location of the texture coordinates
+pub const STANDARD_PBR_UV_LOCATION: u32 = ERROR_CUBE_UV_LOCATION;
+
+/// Set 1 binding of the frame uniform buffer
+pub const STANDARD_PBR_FRAME_BINDING: u32 = 0;
+/// Set 1 binding of the instance storage buffer
+pub const STANDARD_PBR_INSTANCE_BINDING: u32 = 1;
+/// Set 1 binding of the material storage buffer
+pub const STANDARD_PBR_MATERIAL_BINDING: u32 = 2;
+/// Set 1 binding of the light storage buffer
+pub const STANDARD_PBR_LIGHT_BINDING: u32 = 3;
+/// Set 1 binding of the light cluster storage buffer (`LightClusterGrid::upload`)
+pub const STANDARD_PBR_LIGHT_CLUSTER_BINDING: u32 = 4;
+/// Set 1 binding of the light index storage buffer (`LightClusterGrid::upload`)
+pub const STANDARD_PBR_LIGHT_INDEX_BINDING: u32 = 5;
+
+/// Texture slot of the base color (RGBA)
+pub const PBR_ALBEDO_SLOT: &str = "albedo";
+/// Texture slot of the tangent-space normal map
+pub const PBR_NORMAL_SLOT: &str = "normal";
+/// Texture slot of the metallic (B) and roughness (G) map
+pub const PBR_METALLIC_ROUGHNESS_SLOT: &str = "metallicRoughness";
+/// Texture slot of the emissive color
+pub const PBR_EMISSIVE_SLOT: &str = "emissive";
+/// Texture slot of the ambient occlusion (R)
+pub const PBR_AO_SLOT: &str = "ao";
+
+/// Param (Vec4) multiplying the albedo texture
+pub const PBR_BASE_COLOR_PARAM: &str = "baseColor";
+/// Param (Vec4, rgb used) multiplying the emissive texture
+pub const PBR_EMISSIVE_COLOR_PARAM: &str = "emissiveColor";
+/// Param (Float) multiplying the metallic channel
+pub const PBR_METALLIC_PARAM: &str = "metallic";
+/// Param (Float) multiplying the roughness channel
+pub const PBR_ROUGHNESS_PARAM: &str = "roughness";
+/// Param (Float) scaling the normal map XY
+pub const PBR_NORMAL_SCALE_PARAM: &str = "normalScale";
+/// Param (Float) multiplying the occlusion channel
+pub const PBR_AO_PARAM: &str = "ao";
+
+/// SPIR-V of the standard shaders, compiled from the embedded GLSL. The
+/// default is the embedded SPIR-V.
+#[derive(Debug, Clone, Copy)]
+pub struct StandardPbrDesc<'a> {
+    pub vertex_spirv: &'a [u8],
+    /// Morph variants, indexed by `[morph target count - 1][has normal deltas]`
+    pub morph_vertex_spirv: [[&'a [u8]; 2]; MAX_MORPH_TARGETS],
+    pub fragment_spirv: &'a [u8],
+}
+
+impl Default for StandardPbrDesc<'static> {
+    fn default() -> Self {
+        Self {
+            vertex_spirv: STANDARD_PBR_VERTEX_SPIRV,
+            morph_vertex_spirv: STANDARD_PBR_MORPH_VERTEX_SPIRV,
+            fragment_spirv: STANDARD_PBR_FRAGMENT_SPIRV,
+        }
+    }
+}
+
+/// Keys of the standard shaders. Render instances use `vertex_shader_for()`
+/// their geometry, standard materials `fragment_shader`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StandardPbrShaders {
+    pub vertex_shader: ShaderKey,
+    /// Morph variants, indexed by `[morph target count - 1][has normal deltas]`
+    pub morph_vertex_shaders: [[ShaderKey; 2]; MAX_MORPH_TARGETS],
+    pub fragment_shader: ShaderKey,
+}
+
+impl StandardPbrShaders {
+    /// Vertex shader matching the morph targets of `geometry`
+    /// (`vertex_shader` without morph targets)
+    pub fn vertex_shader_for(&self, geometry: &Geometry) -> ShaderKey {
+        match geometry.morph_target_count() {
+            0 => self.vertex_shader,
+            count => self.morph_vertex_shaders[count - 1][geometry.morph_target_has_normals() as usize],
+        }
+    }
+}
+
+/// Descriptor of `ResourceManager::create_standard_pbr_material()`.
+///
+/// The defaults match the default material buffer: white, dielectric,
+/// roughness 0.5, no emission. A texture left to None is not sampled (its
+/// factor applies alone).
+#[derive(Debug, Clone)]
+pub struct StandardPbrMaterialDesc {
+    /// Pass type of the material pass
+    pub pass_type: u8,
+    /// Linear RGBA
+    pub base_color: [f32; 4],
+    pub metallic: f32,
+    pub roughness: f32,
+    /// Linear RGB
+    pub emissive_color: [f32; 3],
+    pub normal_scale: f32,
+    /// Occlusion strength
+    pub ao: f32,
+    pub albedo_texture: Option<TextureKey>,
+    pub normal_texture: Option<TextureKey>,
+    pub metallic_roughness_texture: Option<TextureKey>,
+    pub emissive_texture: Option<TextureKey>,
+    pub ao_texture: Option<TextureKey>,
+    /// Sampler of every texture slot
+    pub sampler_type: SamplerType,
+    /// Render state override (None: material default)
+    pub render_state: Option<DynamicRenderState>,
+}
+
+impl Default for StandardPbrMaterialDesc {
+    fn default() -> Self {
+        Self {
+            pass_type: 0,
+            base_color: [1.0, 1.0, 1.0, 1.0],
+            metallic: 0.0,
+            roughness: 0.5,
+            emissive_color: [0.0, 0.0, 0.0],
+            normal_scale: 1.0,
+            ao: 1.0,
+            albedo_texture: None,
+            normal_texture: None,
+            metallic_roughness_texture: None,
+            emissive_texture: None,
+            ao_texture: None,
+            sampler_type: SamplerType::LinearRepeat,
+            render_state: None,
+        }
+    }
+}
+
+impl StandardPbrMaterialDesc {
+    /// Bound texture slots, as `(slot name, texture)`
+    pub(crate) fn texture_slots(&self) -> impl Iterator<Item = (&'static str, TextureKey)> + '_ {
+        [
+            (PBR_ALBEDO_SLOT, self.albedo_texture),
+            (PBR_NORMAL_SLOT, self.normal_texture),
+            (PBR_METALLIC_ROUGHNESS_SLOT, self.metallic_roughness_texture),
+            (PBR_EMISSIVE_SLOT, self.emissive_texture),
+            (PBR_AO_SLOT, self.ao_texture),
+        ].into_iter().filter_map(|(slot, texture)| texture.map(|texture| (slot, texture)))
+    }
+}
+
+/// Vertex layout read by the standard vertex shader: position, normal and
+/// uv interleaved in binding 0 (32 bytes per vertex, as the error cube)
+pub fn standard_pbr_vertex_layout() -> graphics_device::VertexLayout {
+    default_resources::error_cube_vertex_layout()
+}
+
+#[cfg(test)]
+#[path = "standard_pbr_tests.rs"]
+mod tests;
@@ -0,0 +1,134 @@
+/// Tests for the standard PBR shader interface
+
+use super::*;
+use std::sync::{Arc, Mutex};
+use crate::resource::resource_manager::ResourceManager;
+use crate::resource::buffer::Buffer;
+use slotmap::SlotMap;
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Member names of the GLSL block starting at the line `header`
+fn glsl_block_fields(source: &str, header: &str) -> Vec<String> {
+    let start = source.find(header).unwrap_or_else(|| panic!("'{}' not found", header));
+    source[start + header.len()..]
+        .lines()
+        .map(str::trim)
+        .take_while(|line| !line.starts_with('}'))
+        .filter(|line| !line.is_empty())
+        .map(|line| line.trim_end_matches(';').split_whitespace().last().unwrap().to_string())
+        .collect()
+}
+
+fn buffer_fields(buffer: &Buffer) -> Vec<String> {
+    buffer.fields().iter().map(|field| field.name.clone()).collect()
+}
+
+/// The default buffers of the standard shaders
+fn create_default_buffers() -> [Vec<String>; 5] {
+    let gd: Arc<Mutex<dyn graphics_device::GraphicsDevice>> =
+        Arc::new(Mutex::new(graphics_device::mock_graphics_device::MockGraphicsDevice::new()));
+    let mut rm = ResourceManager::new();
+    let frame = rm.create_default_frame_uniform_buffer("frame".to_string(), gd.clone()).unwrap();
+    let instances = rm.create_default_instance_buffer("instances".to_string(), gd.clone(), 1).unwrap();
+    let materials = rm.create_default_material_buffer("materials".to_string(), gd.clone(), 1).unwrap();
+    let lights = rm.create_default_light_buffer("lights".to_string(), gd.clone(), 1).unwrap();
+    let clusters = rm.create_default_light_cluster_buffer("clusters".to_string(), gd, 1).unwrap();
+    [frame, instances, materials, lights, clusters].map(|key| buffer_fields(rm.buffer(key).unwrap()))
+}
+
+// ============================================================================
+// Tests: shader interface
+// ============================================================================
+
+#[test]
+fn test_glsl_blocks_match_default_buffers() {
+    let [frame, instances, materials, lights, clusters] = create_default_buffers();
+
+    for source in [STANDARD_PBR_VERTEX_GLSL, STANDARD_PBR_FRAGMENT_GLSL] {
+        assert_eq!(glsl_block_fields(source, "uniform Frame {"), frame);
+        assert_eq!(glsl_block_fields(source, "struct Instance {"), instances);
+    }
+    assert_eq!(glsl_block_fields(STANDARD_PBR_FRAGMENT_GLSL, "struct Material {"), materials);
+    assert_eq!(glsl_block_fields(STANDARD_PBR_FRAGMENT_GLSL, "struct Light {"), lights);
+    assert_eq!(glsl_block_fields(STANDARD_PBR_FRAGMENT_GLSL, "struct LightCluster {"), clusters);
+}
+
+#[test]
+fn test_glsl_bindings_match_constants() {
+    for (binding, block) in [
+        (STANDARD_PBR_FRAME_BINDING, "uniform Frame"),
+        (STANDARD_PBR_INSTANCE_BINDING, "readonly buffer Instances"),
+        (STANDARD_PBR_MATERIAL_BINDING, "readonly buffer Materials"),
+        (STANDARD_PBR_LIGHT_BINDING, "readonly buffer Lights"),
+        (STANDARD_PBR_LIGHT_CLUSTER_BINDING, "readonly buffer LightClusters"),
+        (STANDARD_PBR_LIGHT_INDEX_BINDING, "readonly buffer LightIndices"),
+    ] {
+        let declaration = format!("set = 1, binding = {}) {}", binding, block);
+        assert!(STANDARD_PBR_FRAGMENT_GLSL.contains(&declaration), "{}", declaration);
+    }
+    for (location, input) in [
+        (STANDARD_PBR_POSITION_LOCATION, "inPosition"),
+        (STANDARD_PBR_NORMAL_LOCATION, "inNormal"),
+        (STANDARD_PBR_UV_LOCATION, "inUv"),
+    ] {
+        let declaration = format!("layout(location = {}) in ", location);
+        let line = STANDARD_PBR_VERTEX_GLSL.lines().find(|line| line.starts_with(&declaration));
+        assert!(line.is_some_and(|line| line.ends_with(&format!("{};", input))), "{}", input);
+    }
+}
+
+#[test]
+fn test_glsl_morph_inputs_match_constants() {
+    use crate::resource::geometry::MORPH_TARGET_FIRST_LOCATION;
+
+    let normals = MORPH_TARGET_FIRST_LOCATION + MAX_MORPH_TARGETS as u32;
+    for declaration in [
+        format!("layout(location = {}) in vec3 inMorphPosition[MORPH_TARGET_COUNT];", MORPH_TARGET_FIRST_LOCATION),
+        format!("layout(location = {}) in vec3 inMorphNormal[MORPH_TARGET_COUNT];", normals),
+    ] {
+        assert!(STANDARD_PBR_VERTEX_GLSL.contains(&declaration), "{}", declaration);
+    }
+}
+
+#[test]
+fn test_embedded_spirv_is_aligned_spirv() {
+    let variants = STANDARD_PBR_MORPH_VERTEX_SPIRV.iter().flatten().copied();
+    for code in [STANDARD_PBR_VERTEX_SPIRV, STANDARD_PBR_FRAGMENT_SPIRV].into_iter().chain(variants) {
+        assert_eq!(code.as_ptr() as usize % 4, 0);
+        assert_eq!(code.len() % 4, 0);
+        assert_eq!(code[..4], 0x0723_0203u32.to_le_bytes());
+    }
+}
+
+#[test]
+fn test_vertex_layout_matches_locations() {
+    let layout = standard_pbr_vertex_layout();
+    let locations: Vec<u32> = layout.attributes.iter().map(|a| a.location).collect();
+
+    assert_eq!(locations, vec![
+        STANDARD_PBR_POSITION_LOCATION, STANDARD_PBR_NORMAL_LOCATION, STANDARD_PBR_UV_LOCATION,
+    ]);
+    assert_eq!(layout.bindings.len(), 1);
+}
+
+// ============================================================================
+// Tests: material descriptor
+// ============================================================================
+
+#[test]
+fn test_texture_slots_skip_unset_textures() {
+    let mut textures: SlotMap<TextureKey, ()> = SlotMap::with_key();
+    let (albedo, ao) = (textures.insert(()), textures.insert(()));
+    let desc = StandardPbrMaterialDesc {
+        albedo_texture: Some(albedo),
+        ao_texture: Some(ao),
+        ..Default::default()
+    };
+
+    let slots: Vec<_> = desc.texture_slots().collect();
+    assert_eq!(slots, vec![(PBR_ALBEDO_SLOT, albedo), (PBR_AO_SLOT, ao)]);
+    assert_eq!(StandardPbrMaterialDesc::default().texture_slots().count(), 0);
+}
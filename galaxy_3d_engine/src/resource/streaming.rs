@@ -0,0 +1,661 @@
+/// Asynchronous resource streaming.
+///
+/// `StreamingManager` loads textures and geometries in the background on
+/// top of the synchronous `ResourceManager`:
+///
+/// - **Requests** carry a name, a priority (higher first, FIFO among equal
+///   priorities) and a decode job. The job runs on a worker thread and
+///   returns the full descriptor (`StreamedAsset`): file reads, image and
+///   mesh decoding happen there, never on the main thread.
+/// - **Uploads** happen in `update()`, on the main thread: decoded assets
+///   are created in the `ResourceManager`, highest priority first, within
+///   the per-frame count and byte limits of `StreamingBudget`.
+/// - **Eviction**: when the streamed textures exceed
+///   `StreamingBudget::texture_bytes`, the least recently drawn ones (see
+///   `ResourceManager::texture_usage`) are removed. Pinned textures
+///   (`ResourceManager::pin_texture`) and textures drawn in the current
+///   usage frame are kept. Evicted textures can be requested again.
+/// - **Load states** (`load_state()`) let loading screens and gameplay code
+///   wait for what they need.
+/// - **Progress events** are published on `Engine::progress_bus()` (or the
+///   bus of `set_progress_bus()`) as each request is queued, started by a
+///   worker, completed or failed, with durations, so a loading screen can
+///   show real progress.
+///
+/// Only whole textures are evicted; geometries are never evicted.
+
+use std::collections::BinaryHeap;
+use std::cmp::Ordering;
+use std::sync::{mpsc, Arc, Condvar, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use rustc_hash::{FxHashMap, FxHashSet};
+use crate::error::Result;
+use crate::engine::Engine;
+use crate::engine_bail;
+use crate::event::{ProgressBus, ProgressEvent, ProgressState};
+use super::geometry::GeometryDesc;
+use super::resource_manager::{GeometryKey, ResourceManager, TextureKey};
+use super::texture::TextureDesc;
+
+/// Default number of uploads per `update()`
+pub const DEFAULT_MAX_UPLOADS_PER_FRAME: usize = 4;
+/// Default upload bytes per `update()`
+pub const DEFAULT_MAX_UPLOAD_BYTES_PER_FRAME: u64 = 64 * 1024 * 1024;
+
+// ===== PUBLIC TYPES =====
+
+/// Descriptor produced by a decode job, created in the `ResourceManager`
+/// at upload time.
+pub enum StreamedAsset {
+    Texture(TextureDesc),
+    Geometry(GeometryDesc<'static>),
+}
+
+/// Decode job of a request, run on a worker thread
+pub type StreamingJob = Box<dyn FnOnce() -> Result<StreamedAsset> + Send>;
+
+/// Load state of a streamed resource
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadState {
+    /// Waiting for a worker thread
+    Queued,
+    /// Being decoded by a worker thread
+    Decoding,
+    /// Decoded, waiting for its upload in `update()`
+    Decoded,
+    /// Created in the `ResourceManager`
+    Resident,
+    /// Removed by the memory budget (can be requested again)
+    Evicted,
+    /// The job or the resource creation failed (can be requested again)
+    Failed(String),
+}
+
+/// Memory and upload limits of a `StreamingManager`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamingBudget {
+    /// Maximum bytes of resident streamed textures (None = no eviction)
+    pub texture_bytes: Option<u64>,
+    /// Maximum resources created per `update()`
+    pub max_uploads_per_frame: usize,
+    /// Maximum bytes created per `update()`. The first upload of a frame
+    /// always goes through, so a large asset cannot stall the queue.
+    pub max_upload_bytes_per_frame: u64,
+}
+
+impl Default for StreamingBudget {
+    fn default() -> Self {
+        Self {
+            texture_bytes: None,
+            max_uploads_per_frame: DEFAULT_MAX_UPLOADS_PER_FRAME,
+            max_upload_bytes_per_frame: DEFAULT_MAX_UPLOAD_BYTES_PER_FRAME,
+        }
+    }
+}
+
+/// Source of the progress events of a `StreamingManager`
+pub const STREAMING_PROGRESS_SOURCE: &str = "galaxy3d::StreamingManager";
+
+/// Outcome of one `StreamingManager::update()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StreamingFrameStats {
+    /// Resources created this frame
+    pub uploaded: usize,
+    /// Bytes created this frame
+    pub uploaded_bytes: u64,
+    /// Textures evicted this frame
+    pub evicted: usize,
+    /// Jobs or creations that failed this frame
+    pub failed: usize,
+    /// Requests not resident yet (queued, decoding or decoded)
+    pub in_flight: usize,
+    /// Bytes of the resident streamed textures
+    pub resident_texture_bytes: u64,
+}
+
+// ===== INTERNAL TYPES =====
+
+/// A request waiting for a worker
+struct QueuedJob {
+    priority: i32,
+    seq: u64,
+    name: String,
+    job: StreamingJob,
+}
+
+impl PartialEq for QueuedJob {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for QueuedJob {}
+
+impl PartialOrd for QueuedJob {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QueuedJob {
+    /// Highest priority first, then oldest request first
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority.cmp(&other.priority).then(other.seq.cmp(&self.seq))
+    }
+}
+
+/// State shared with the worker threads
+#[derive(Default)]
+struct JobQueue {
+    jobs: BinaryHeap<QueuedJob>,
+    /// Sequence numbers of the jobs taken by a worker and not collected
+    /// yet by `update()`
+    started: FxHashSet<u64>,
+    shutdown: bool,
+}
+
+/// Message sent back by a worker
+enum WorkerMessage {
+    /// A job was taken from the queue
+    Started { name: String, seq: u64, at: Instant },
+    /// A job returned
+    Finished(JobResult),
+}
+
+/// Job result sent back by a worker
+struct JobResult {
+    name: String,
+    seq: u64,
+    result: Result<StreamedAsset>,
+    /// Time spent in the job
+    decode: Duration,
+}
+
+/// A decoded asset waiting for its upload
+struct DecodedAsset {
+    name: String,
+    seq: u64,
+    asset: StreamedAsset,
+}
+
+/// Resource created by the manager
+#[derive(Debug, Clone, Copy)]
+enum StreamedKey {
+    Texture(TextureKey),
+    Geometry(GeometryKey),
+}
+
+/// Bookkeeping of one requested name
+struct StreamEntry {
+    /// Sequence number of the current request
+    seq: u64,
+    priority: i32,
+    state: LoadState,
+    key: Option<StreamedKey>,
+    /// Estimated GPU bytes (set once decoded)
+    bytes: u64,
+    /// Texture usage frame of the upload (a texture never drawn with is
+    /// idle since then)
+    resident_since: u64,
+    requested_at: Instant,
+    /// Time spent in the job (set once decoded)
+    decode: Duration,
+}
+
+// ===== STREAMING MANAGER =====
+
+/// Background loader feeding the `ResourceManager` (see module docs).
+///
+/// Dropping it stops the workers after their current job; queued jobs are
+/// discarded.
+pub struct StreamingManager {
+    budget: StreamingBudget,
+    queue: Arc<(Mutex<JobQueue>, Condvar)>,
+    workers: Vec<JoinHandle<()>>,
+    results: mpsc::Receiver<WorkerMessage>,
+    entries: FxHashMap<String, StreamEntry>,
+    decoded: Vec<DecodedAsset>,
+    next_seq: u64,
+    /// Bus of the progress events
+    progress: ProgressBus,
+}
+
+impl StreamingManager {
+    /// Start `worker_count` decode threads.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `worker_count` is 0 or a thread cannot be spawned.
+    pub fn new(worker_count: usize, budget: StreamingBudget) -> Result<Self> {
+        if worker_count == 0 {
+            engine_bail!("galaxy3d::StreamingManager", "worker_count must not be 0");
+        }
+        let queue = Arc::new((Mutex::new(JobQueue::default()), Condvar::new()));
+        let (sender, results) = mpsc::channel();
+        let mut manager = Self {
+            budget,
+            queue,
+            workers: Vec::with_capacity(worker_count),
+            results,
+            entries: FxHashMap::default(),
+            decoded: Vec::new(),
+            next_seq: 0,
+            progress: Engine::progress_bus(),
+        };
+        for index in 0..worker_count {
+            let queue = Arc::clone(&manager.queue);
+            let sender = sender.clone();
+            let handle = std::thread::Builder::new()
+                .name(format!("galaxy3d-streaming-{}", index))
+                .spawn(move || worker_loop(&queue, &sender))
+                .map_err(|e| crate::engine_err!("galaxy3d::StreamingManager",
+                    "Cannot spawn streaming worker {}: {}", index, e))?;
+            manager.workers.push(handle);
+        }
+        Ok(manager)
+    }
+
+    pub fn budget(&self) -> &StreamingBudget {
+        &self.budget
+    }
+
+    pub fn set_budget(&mut self, budget: StreamingBudget) {
+        self.budget = budget;
+    }
+
+    // ===== REQUESTS =====
+
+    /// Queue the decode of `name`.
+    ///
+    /// Returns false (and drops `job`) if `name` is already queued,
+    /// loading or resident. Evicted and failed names are loaded again.
+    pub fn request<F>(&mut self, name: impl Into<String>, priority: i32, job: F) -> bool
+    where
+        F: FnOnce() -> Result<StreamedAsset> + Send + 'static,
+    {
+        let name = name.into();
+        if let Some(entry) = self.entries.get(&name) {
+            if !matches!(entry.state, LoadState::Evicted | LoadState::Failed(_)) {
+                return false;
+            }
+        }
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.entries.insert(name.clone(), StreamEntry {
+            seq,
+            priority,
+            state: LoadState::Queued,
+            key: None,
+            bytes: 0,
+            resident_since: 0,
+            requested_at: Instant::now(),
+            decode: Duration::ZERO,
+        });
+        self.publish(name.clone(), ProgressState::Queued);
+        let (lock, condvar) = &*self.queue;
+        lock.lock().unwrap().jobs.push(QueuedJob { priority, seq, name, job: Box::new(job) });
+        condvar.notify_one();
+        true
+    }
+
+    /// Queue the decode of `name` from the archive entry `entry`: the
+    /// worker reads (and decompresses) the entry, then runs `decode` on
+    /// its bytes. Same rules as `request()`.
+    #[cfg(feature = "archive")]
+    pub fn request_from_archive<F>(
+        &mut self,
+        name: impl Into<String>,
+        priority: i32,
+        archive: &Arc<super::asset_archive::AssetArchive>,
+        entry: impl Into<String>,
+        decode: F,
+    ) -> bool
+    where
+        F: FnOnce(&[u8]) -> Result<StreamedAsset> + Send + 'static,
+    {
+        let archive = Arc::clone(archive);
+        let entry = entry.into();
+        self.request(name, priority, move || decode(&archive.read(&entry)?))
+    }
+
+    /// Change the priority of a request not resident yet. A job already
+    /// taken by a worker keeps running; the priority then orders its upload.
+    /// Returns false if `name` is not in flight.
+    pub fn set_priority(&mut self, name: &str, priority: i32) -> bool {
+        let Some(entry) = self.entries.get_mut(name) else {
+            return false;
+        };
+        if !matches!(entry.state, LoadState::Queued | LoadState::Decoded) {
+            return false;
+        }
+        entry.priority = priority;
+        let seq = entry.seq;
+        let mut queue = self.queue.0.lock().unwrap();
+        let mut jobs = std::mem::take(&mut queue.jobs).into_vec();
+        for job in jobs.iter_mut().filter(|job| job.seq == seq) {
+            job.priority = priority;
+        }
+        queue.jobs = jobs.into();
+        true
+    }
+
+    /// Forget a request not resident yet. A job already taken by a worker
+    /// completes and its result is dropped. Returns false if `name` is not
+    /// in flight.
+    pub fn cancel(&mut self, name: &str) -> bool {
+        let in_flight = self.entries.get(name)
+            .is_some_and(|entry| matches!(entry.state, LoadState::Queued | LoadState::Decoded));
+        if !in_flight {
+            return false;
+        }
+        let entry = self.entries.remove(name).unwrap();
+        self.queue.0.lock().unwrap().jobs.retain(|job| job.seq != entry.seq);
+        self.decoded.retain(|asset| asset.seq != entry.seq);
+        self.publish(name.to_string(), ProgressState::Cancelled);
+        true
+    }
+
+    // ===== QUERIES =====
+
+    /// Load state of a requested name, None if never requested (or
+    /// cancelled)
+    pub fn load_state(&self, name: &str) -> Option<LoadState> {
+        let entry = self.entries.get(name)?;
+        if entry.state == LoadState::Queued && self.queue.0.lock().unwrap().started.contains(&entry.seq) {
+            return Some(LoadState::Decoding);
+        }
+        Some(entry.state.clone())
+    }
+
+    /// True if the name is resident
+    pub fn is_resident(&self, name: &str) -> bool {
+        self.entries.get(name).is_some_and(|entry| entry.state == LoadState::Resident)
+    }
+
+    /// Key of a resident streamed texture
+    pub fn texture_key(&self, name: &str) -> Option<TextureKey> {
+        match self.entries.get(name)?.key? {
+            StreamedKey::Texture(key) => Some(key),
+            StreamedKey::Geometry(_) => None,
+        }
+    }
+
+    /// Key of a resident streamed geometry
+    pub fn geometry_key(&self, name: &str) -> Option<GeometryKey> {
+        match self.entries.get(name)?.key? {
+            StreamedKey::Geometry(key) => Some(key),
+            StreamedKey::Texture(_) => None,
+        }
+    }
+
+    /// Requests not resident yet (queued, decoding or decoded)
+    pub fn in_flight_count(&self) -> usize {
+        self.entries.values()
+            .filter(|entry| matches!(entry.state, LoadState::Queued | LoadState::Decoded))
+            .count()
+    }
+
+    /// Estimated bytes of the resident streamed textures
+    pub fn resident_texture_bytes(&self) -> u64 {
+        self.entries.values()
+            .filter(|entry| entry.state == LoadState::Resident && matches!(entry.key, Some(StreamedKey::Texture(_))))
+            .map(|entry| entry.bytes)
+            .sum()
+    }
+
+    // ===== PROGRESS EVENTS =====
+
+    /// Publish the progress events on `bus` instead of
+    /// `Engine::progress_bus()`.
+    ///
+    /// Events are published on the main thread, by `request()`, `cancel()`
+    /// and `update()`. A name requested again after an eviction or a
+    /// failure reports a new `Queued` event.
+    pub fn set_progress_bus(&mut self, bus: ProgressBus) {
+        self.progress = bus;
+    }
+
+    // ===== FRAME UPDATE =====
+
+    /// Collect the decoded assets, upload the most urgent ones within the
+    /// per-frame limits, then evict textures over the memory budget.
+    ///
+    /// Call once per frame, on the thread owning the `ResourceManager`.
+    /// Failures are reported through `load_state()`, not returned.
+    pub fn update(&mut self, rm: &mut ResourceManager) -> StreamingFrameStats {
+        let mut stats = StreamingFrameStats::default();
+        self.collect_results(&mut stats);
+        self.upload(rm, &mut stats);
+        self.evict(rm, &mut stats);
+        stats.in_flight = self.in_flight_count();
+        stats.resident_texture_bytes = self.resident_texture_bytes();
+        stats
+    }
+
+    // ===== PRIVATE HELPERS =====
+
+    fn publish(&self, name: String, state: ProgressState) {
+        self.progress.publish(ProgressEvent { source: STREAMING_PROGRESS_SOURCE, name, state });
+    }
+
+    /// Move the finished jobs to the decoded list (or to the failed state)
+    fn collect_results(&mut self, stats: &mut StreamingFrameStats) {
+        let mut messages = Vec::new();
+        while let Ok(message) = self.results.try_recv() {
+            messages.push(message);
+        }
+        if messages.is_empty() {
+            return;
+        }
+        {
+            let mut queue = self.queue.0.lock().unwrap();
+            for message in &messages {
+                if let WorkerMessage::Finished(result) = message {
+                    queue.started.remove(&result.seq);
+                }
+            }
+        }
+        for message in messages {
+            let (name, seq) = match &message {
+                WorkerMessage::Started { name, seq, .. } => (name, *seq),
+                WorkerMessage::Finished(result) => (&result.name, result.seq),
+            };
+            // Cancelled, or requested again since
+            let Some(entry) = self.entries.get_mut(name).filter(|entry| entry.seq == seq) else {
+                continue;
+            };
+            let (name, state) = match message {
+                WorkerMessage::Started { name, at, .. } => {
+                    (name, ProgressState::Started { waited: at.saturating_duration_since(entry.requested_at) })
+                }
+                WorkerMessage::Finished(JobResult { name, seq, result, decode }) => match result {
+                    Ok(asset) => {
+                        entry.state = LoadState::Decoded;
+                        entry.bytes = asset_bytes(&asset);
+                        entry.decode = decode;
+                        self.decoded.push(DecodedAsset { name, seq, asset });
+                        continue;
+                    }
+                    Err(e) => {
+                        crate::engine_warn!("galaxy3d::StreamingManager", "Failed to decode '{}': {}", name, e);
+                        entry.state = LoadState::Failed(e.to_string());
+                        stats.failed += 1;
+                        let total = entry.requested_at.elapsed();
+                        (name, ProgressState::Failed { error: e.to_string(), total })
+                    }
+                },
+            };
+            self.publish(name, state);
+        }
+    }
+
+    /// Create the decoded assets, highest priority first
+    fn upload(&mut self, rm: &mut ResourceManager, stats: &mut StreamingFrameStats) {
+        let entries = &self.entries;
+        let priority = |asset: &DecodedAsset| entries.get(&asset.name).map_or(i32::MIN, |e| e.priority);
+        self.decoded.sort_by(|a, b| priority(b).cmp(&priority(a)).then(a.seq.cmp(&b.seq)));
+
+        let mut remaining = Vec::new();
+        for decoded in std::mem::take(&mut self.decoded) {
+            let bytes = self.entries.get(&decoded.name).map_or(0, |entry| entry.bytes);
+            let over_bytes = stats.uploaded > 0
+                && stats.uploaded_bytes + bytes > self.budget.max_upload_bytes_per_frame;
+            if stats.uploaded >= self.budget.max_uploads_per_frame || over_bytes {
+                remaining.push(decoded);
+                continue;
+            }
+            let DecodedAsset { name, asset, .. } = decoded;
+            let created = match asset {
+                StreamedAsset::Texture(desc) => rm.create_texture(name.clone(), desc).map(StreamedKey::Texture),
+                StreamedAsset::Geometry(desc) => rm.create_geometry(name.clone(), desc).map(StreamedKey::Geometry),
+            };
+            let entry = self.entries.get_mut(&name).unwrap();
+            let total = entry.requested_at.elapsed();
+            let state = match created {
+                Ok(key) => {
+                    entry.state = LoadState::Resident;
+                    entry.key = Some(key);
+                    entry.resident_since = rm.texture_usage_frame();
+                    stats.uploaded += 1;
+                    stats.uploaded_bytes += bytes;
+                    ProgressState::Completed { work: entry.decode, total }
+                }
+                Err(e) => {
+                    entry.state = LoadState::Failed(e.to_string());
+                    stats.failed += 1;
+                    ProgressState::Failed { error: e.to_string(), total }
+                }
+            };
+            self.publish(name, state);
+        }
+        self.decoded = remaining;
+    }
+
+    /// Remove the least recently drawn textures until the budget is met
+    fn evict(&mut self, rm: &mut ResourceManager, stats: &mut StreamingFrameStats) {
+        // Textures removed from the ResourceManager by the application
+        for entry in self.entries.values_mut() {
+            if let (LoadState::Resident, Some(StreamedKey::Texture(key))) = (&entry.state, entry.key) {
+                if rm.texture(key).is_none() {
+                    entry.state = LoadState::Evicted;
+                    entry.key = None;
+                }
+            }
+        }
+        let Some(limit) = self.budget.texture_bytes else {
+            return;
+        };
+        let mut resident = self.resident_texture_bytes();
+        if resident <= limit {
+            return;
+        }
+
+        let frame = rm.texture_usage_frame();
+        let mut candidates: Vec<(u64, &String, TextureKey)> = self.entries.iter()
+            .filter(|(_, entry)| entry.state == LoadState::Resident)
+            .filter_map(|(name, entry)| match entry.key {
+                Some(StreamedKey::Texture(key)) if rm.texture_pin_count(key) == 0 => {
+                    let last_used = rm.texture_usage(key)
+                        .map_or(entry.resident_since, |usage| usage.last_used_frame.max(entry.resident_since));
+                    Some((frame - last_used, name, key))
+                }
+                _ => None,
+            })
+            .filter(|(idle, _, _)| *idle > 0)
+            .collect();
+        candidates.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(b.1)));
+
+        let mut victims = Vec::new();
+        for (_, name, key) in candidates {
+            if resident <= limit {
+                break;
+            }
+            resident -= self.entries[name].bytes;
+            victims.push((name.clone(), key));
+        }
+        for (name, key) in victims {
+            rm.remove_texture(key);
+            let entry = self.entries.get_mut(&name).unwrap();
+            entry.state = LoadState::Evicted;
+            entry.key = None;
+            stats.evicted += 1;
+        }
+        if resident > limit {
+            crate::engine_warn!("galaxy3d::StreamingManager",
+                "Streamed textures use {} bytes for a {} bytes budget: the rest is pinned or in use",
+                resident, limit);
+        }
+    }
+}
+
+impl Drop for StreamingManager {
+    fn drop(&mut self) {
+        {
+            let (lock, condvar) = &*self.queue;
+            let mut queue = lock.lock().unwrap();
+            queue.shutdown = true;
+            queue.jobs.clear();
+            condvar.notify_all();
+        }
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// Body of a worker thread: run the most urgent job until shutdown
+fn worker_loop(queue: &(Mutex<JobQueue>, Condvar), sender: &mpsc::Sender<WorkerMessage>) {
+    let (lock, condvar) = queue;
+    loop {
+        let job = {
+            let mut queue = lock.lock().unwrap();
+            loop {
+                if queue.shutdown {
+                    return;
+                }
+                if let Some(job) = queue.jobs.pop() {
+                    queue.started.insert(job.seq);
+                    break job;
+                }
+                queue = condvar.wait(queue).unwrap();
+            }
+        };
+        let QueuedJob { name, seq, job, .. } = job;
+        let at = Instant::now();
+        if sender.send(WorkerMessage::Started { name: name.clone(), seq, at }).is_err() {
+            return;
+        }
+        let result = job();
+        let decode = at.elapsed();
+        if sender.send(WorkerMessage::Finished(JobResult { name, seq, result, decode })).is_err() {
+            return;
+        }
+    }
+}
+
+/// Estimated GPU bytes of a decoded asset (every mip and layer of a
+/// texture, vertex and index data of a geometry)
+fn asset_bytes(asset: &StreamedAsset) -> u64 {
+    match asset {
+        StreamedAsset::Texture(desc) => {
+            let texture = &desc.texture;
+            let mip_levels = texture.mipmap.mip_levels(texture.width, texture.height);
+            let texel_size = texture.format.bytes_per_pixel() as u64;
+            (0..mip_levels)
+                .map(|mip| {
+                    let width = (texture.width >> mip).max(1) as u64;
+                    let height = (texture.height >> mip).max(1) as u64;
+                    width * height * texel_size
+                })
+                .sum::<u64>() * texture.array_layers as u64
+        }
+        StreamedAsset::Geometry(desc) => {
+            (desc.vertex_data.len() + desc.index_data.as_ref().map_or(0, |data| data.len())) as u64
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "streaming_tests.rs"]
+mod tests;
@@ -0,0 +1,303 @@
+/// Unit tests for streaming.rs
+
+use super::*;
+use std::sync::mpsc::Receiver;
+use std::time::{Duration, Instant};
+use crate::event::LoadProgress;
+use crate::graphics_device::{self, GraphicsDevice};
+use crate::graphics_device::mock_graphics_device::MockGraphicsDevice;
+use crate::resource::texture::LayerDesc;
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+fn make_device() -> Arc<Mutex<dyn GraphicsDevice>> {
+    Arc::new(Mutex::new(MockGraphicsDevice::new()))
+}
+
+/// 8-bit RGBA texture descriptor of `size` x `size` (size * size * 4 bytes)
+fn texture_desc(gd: &Arc<Mutex<dyn GraphicsDevice>>, size: u32) -> TextureDesc {
+    TextureDesc {
+        graphics_device: gd.clone(),
+        texture: graphics_device::TextureDesc {
+            width: size,
+            height: size,
+            depth: 1,
+            format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
+            usage: graphics_device::TextureUsage::Sampled,
+            array_layers: 1,
+            data: None,
+            mipmap: graphics_device::MipmapMode::None,
+            texture_type: graphics_device::TextureType::Tex2D,
+            sample_count: graphics_device::SampleCount::S1,
+            debug_name: None,
+        },
+        layers: vec![LayerDesc { name: "default".to_string(), layer_index: 0, data: None, regions: vec![] }],
+    }
+}
+
+fn texture_job(gd: &Arc<Mutex<dyn GraphicsDevice>>, size: u32) -> impl FnOnce() -> Result<StreamedAsset> + Send + 'static {
+    let desc = texture_desc(gd, size);
+    move || Ok(StreamedAsset::Texture(desc))
+}
+
+/// Run `update()` until `done` holds (panics after 5 seconds)
+fn update_until(
+    streaming: &mut StreamingManager,
+    rm: &mut ResourceManager,
+    mut done: impl FnMut(&StreamingManager) -> bool,
+) -> StreamingFrameStats {
+    let mut total = StreamingFrameStats::default();
+    let start = Instant::now();
+    while !done(streaming) {
+        assert!(start.elapsed() < Duration::from_secs(5), "streaming did not settle");
+        let stats = streaming.update(rm);
+        total.uploaded += stats.uploaded;
+        total.evicted += stats.evicted;
+        total.failed += stats.failed;
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    total
+}
+
+/// Job blocked until the returned sender fires
+fn gated_job(
+    gd: &Arc<Mutex<dyn GraphicsDevice>>,
+) -> (mpsc::Sender<()>, impl FnOnce() -> Result<StreamedAsset> + Send + 'static) {
+    let (gate, wait): (mpsc::Sender<()>, Receiver<()>) = mpsc::channel();
+    let job = texture_job(gd, 4);
+    (gate, move || {
+        let _ = wait.recv();
+        job()
+    })
+}
+
+// ============================================================================
+// LOADING
+// ============================================================================
+
+#[test]
+fn test_new_rejects_zero_workers() {
+    assert!(StreamingManager::new(0, StreamingBudget::default()).is_err());
+}
+
+#[test]
+fn test_request_loads_texture_and_geometry() {
+    let gd = make_device();
+    let mut rm = ResourceManager::new();
+    let mut streaming = StreamingManager::new(2, StreamingBudget::default()).unwrap();
+
+    assert!(streaming.request("albedo", 0, texture_job(&gd, 16)));
+    assert!(!streaming.request("albedo", 5, texture_job(&gd, 16)));
+    let geometry_gd = gd.clone();
+    streaming.request("rock", 0, move || Ok(StreamedAsset::Geometry(GeometryDesc {
+        name: "rock".to_string(),
+        graphics_device: geometry_gd,
+        vertex_data: vec![0u8; 48].into(),
+        index_data: None,
+        vertex_layout: graphics_device::VertexLayout {
+            bindings: vec![graphics_device::VertexBinding {
+                binding: 0,
+                stride: 12,
+                input_rate: graphics_device::VertexInputRate::Vertex,
+            }],
+            attributes: vec![graphics_device::VertexAttribute {
+                location: 0,
+                binding: 0,
+                format: graphics_device::BufferFormat::R32G32B32_SFLOAT,
+                offset: 0,
+            }],
+        },
+        index_type: graphics_device::IndexType::U16,
+        morph_targets: Vec::new(),
+        meshes: Vec::new(),
+    })));
+    assert_eq!(streaming.load_state("missing"), None);
+
+    let stats = update_until(&mut streaming, &mut rm, |s| s.in_flight_count() == 0);
+    assert_eq!(stats.uploaded, 2);
+    assert_eq!(streaming.load_state("albedo"), Some(LoadState::Resident));
+    assert_eq!(rm.texture_key("albedo"), streaming.texture_key("albedo"));
+    assert_eq!(rm.geometry_key("rock"), streaming.geometry_key("rock"));
+    assert_eq!(streaming.texture_key("rock"), None);
+    assert_eq!(streaming.resident_texture_bytes(), 16 * 16 * 4);
+}
+
+#[test]
+fn test_failed_job_can_be_requested_again() {
+    let gd = make_device();
+    let mut rm = ResourceManager::new();
+    let mut streaming = StreamingManager::new(1, StreamingBudget::default()).unwrap();
+
+    streaming.request("broken", 0, || Err(crate::engine_err!("galaxy3d::test", "corrupt file")));
+    let stats = update_until(&mut streaming, &mut rm, |s| s.in_flight_count() == 0);
+    assert_eq!(stats.failed, 1);
+    assert!(matches!(streaming.load_state("broken"), Some(LoadState::Failed(msg)) if msg.contains("corrupt")));
+
+    assert!(streaming.request("broken", 0, texture_job(&gd, 4)));
+    update_until(&mut streaming, &mut rm, |s| s.is_resident("broken"));
+}
+
+#[test]
+fn test_upload_limits_and_priority_order() {
+    let gd = make_device();
+    let mut rm = ResourceManager::new();
+    let budget = StreamingBudget { max_uploads_per_frame: 1, ..Default::default() };
+    let mut streaming = StreamingManager::new(1, budget).unwrap();
+
+    // Hold the worker so that both requests are decoded before any upload
+    let (gate, blocker) = gated_job(&gd);
+    streaming.request("blocker", 10, blocker);
+    streaming.request("low", 0, texture_job(&gd, 4));
+    streaming.request("high", 1, texture_job(&gd, 4));
+    update_until(&mut streaming, &mut rm, |s| s.load_state("blocker") == Some(LoadState::Decoding));
+    assert_eq!(streaming.load_state("low"), Some(LoadState::Queued));
+    assert!(streaming.set_priority("low", 5));
+    gate.send(()).unwrap();
+
+    let mut order = Vec::new();
+    while order.len() < 3 {
+        let before: Vec<bool> = ["blocker", "low", "high"].iter().map(|n| streaming.is_resident(n)).collect();
+        let stats = streaming.update(&mut rm);
+        assert!(stats.uploaded <= 1);
+        for (i, name) in ["blocker", "low", "high"].iter().enumerate() {
+            if !before[i] && streaming.is_resident(name) {
+                order.push(*name);
+            }
+        }
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    assert_eq!(order, vec!["blocker", "low", "high"]);
+}
+
+#[test]
+fn test_cancel_drops_queued_request() {
+    let gd = make_device();
+    let mut rm = ResourceManager::new();
+    let mut streaming = StreamingManager::new(1, StreamingBudget::default()).unwrap();
+
+    let (gate, blocker) = gated_job(&gd);
+    streaming.request("blocker", 0, blocker);
+    streaming.request("later", 0, texture_job(&gd, 4));
+    assert!(streaming.cancel("later"));
+    assert!(!streaming.cancel("later"));
+    gate.send(()).unwrap();
+
+    update_until(&mut streaming, &mut rm, |s| s.in_flight_count() == 0);
+    assert_eq!(streaming.load_state("later"), None);
+    assert_eq!(rm.texture_key("later"), None);
+}
+
+// ============================================================================
+// EVICTION
+// ============================================================================
+
+#[test]
+fn test_budget_evicts_least_recently_used_unpinned_textures() {
+    let gd = make_device();
+    let mut rm = ResourceManager::new();
+    let budget = StreamingBudget { texture_bytes: Some(3 * 64), ..Default::default() };
+    let mut streaming = StreamingManager::new(1, budget).unwrap();
+
+    for name in ["a", "b", "c"] {
+        streaming.request(name, 0, texture_job(&gd, 4));
+    }
+    update_until(&mut streaming, &mut rm, |s| s.in_flight_count() == 0);
+    rm.pin_texture(streaming.texture_key("a").unwrap()).unwrap();
+
+    // Textures loaded in the current usage frame are not evicted
+    streaming.request("d", 0, texture_job(&gd, 4));
+    update_until(&mut streaming, &mut rm, |s| s.is_resident("d"));
+    assert_eq!(streaming.resident_texture_bytes(), 4 * 64);
+
+    rm.begin_texture_usage_frame();
+    let stats = streaming.update(&mut rm);
+    assert_eq!(stats.evicted, 1);
+    assert_eq!(stats.resident_texture_bytes, 3 * 64);
+    assert!(streaming.is_resident("a"));
+    assert_eq!(streaming.load_state("b"), Some(LoadState::Evicted));
+    assert_eq!(rm.texture_key("b"), None);
+
+    // Evicted textures can be requested again
+    assert!(streaming.request("b", 0, texture_job(&gd, 4)));
+}
+
+#[test]
+fn test_texture_removed_by_application_is_evicted() {
+    let gd = make_device();
+    let mut rm = ResourceManager::new();
+    let mut streaming = StreamingManager::new(1, StreamingBudget::default()).unwrap();
+
+    streaming.request("albedo", 0, texture_job(&gd, 4));
+    update_until(&mut streaming, &mut rm, |s| s.is_resident("albedo"));
+    rm.remove_texture_by_name("albedo");
+    streaming.update(&mut rm);
+    assert_eq!(streaming.load_state("albedo"), Some(LoadState::Evicted));
+}
+
+// ============================================================================
+// PROGRESS EVENTS
+// ============================================================================
+
+/// States of `name`, durations dropped
+fn states_of(events: &[ProgressEvent], name: &str) -> Vec<&'static str> {
+    events.iter()
+        .filter(|event| event.name == name)
+        .map(|event| match event.state {
+            ProgressState::Queued => "queued",
+            ProgressState::Started { .. } => "started",
+            ProgressState::Completed { .. } => "completed",
+            ProgressState::Failed { .. } => "failed",
+            ProgressState::Cancelled => "cancelled",
+        })
+        .collect()
+}
+
+#[test]
+fn test_events_report_each_request_in_order() {
+    let gd = make_device();
+    let mut rm = ResourceManager::new();
+    let mut streaming = StreamingManager::new(1, StreamingBudget::default()).unwrap();
+    let bus = ProgressBus::new();
+    let subscriber = bus.subscribe();
+    streaming.set_progress_bus(bus);
+
+    let (gate, blocker) = gated_job(&gd);
+    streaming.request("blocker", 0, blocker);
+    streaming.request("broken", 0, || Err(crate::engine_err!("galaxy3d::test", "corrupt file")));
+    streaming.request("later", 0, texture_job(&gd, 4));
+    assert!(streaming.cancel("later"));
+    assert!(!streaming.request("blocker", 0, texture_job(&gd, 4)));
+
+    let mut events = subscriber.drain();
+    assert_eq!(states_of(&events, "blocker"), vec!["queued"]);
+    assert_eq!(states_of(&events, "later"), vec!["queued", "cancelled"]);
+    assert!(events.iter().all(|event| event.source == STREAMING_PROGRESS_SOURCE));
+
+    // The blocker's decode lasts at least until the gate opens
+    update_until(&mut streaming, &mut rm, |s| s.load_state("blocker") == Some(LoadState::Decoding));
+    std::thread::sleep(Duration::from_millis(5));
+    gate.send(()).unwrap();
+    update_until(&mut streaming, &mut rm, |s| s.in_flight_count() == 0);
+    events.extend(subscriber.drain());
+
+    assert_eq!(states_of(&events, "blocker"), vec!["queued", "started", "completed"]);
+    assert_eq!(states_of(&events, "broken"), vec!["queued", "started", "failed"]);
+    for event in &events {
+        match &event.state {
+            ProgressState::Completed { work, total } => {
+                assert!(*work >= Duration::from_millis(5));
+                assert!(total >= work);
+            }
+            ProgressState::Failed { error, .. } => assert!(error.contains("corrupt")),
+            _ => {}
+        }
+    }
+
+    let mut progress = LoadProgress::new();
+    for event in &events {
+        progress.record(event);
+    }
+    assert_eq!((progress.total(), progress.completed(), progress.failed()), (2, 1, 1));
+}
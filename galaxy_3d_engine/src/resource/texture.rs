@@ -7,13 +7,16 @@
 /// - Simple texture: 1 layer (array_layers=1), optional atlas regions
 /// - Indexed texture: N layers (array_layers>1), each with optional atlas regions
 ///
-/// A layer can be an atlas texture if it has regions defined.
+/// A layer can be an atlas texture if it has regions defined. Regions are
+/// either declared (`AtlasRegionDesc`) or packed at runtime with
+/// `TextureLayer::allocate_region()` (see `atlas_packer.rs`).
 
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::sync::{Arc, Mutex};
 use crate::error::Result;
 use crate::{engine_bail, engine_err};
 use crate::graphics_device;
+use super::atlas_packer::{AtlasPacker, AtlasStats};
 
 // ===== TEXTURE =====
 
@@ -34,12 +37,18 @@ pub struct TextureLayer {
     layer_index: u32,
     regions: Vec<AtlasRegion>,
     region_names: FxHashMap<String, usize>,
+    /// Layer size (the texture size)
+    width: u32,
+    height: u32,
+    /// Runtime allocator, created by the first `allocate_region()`
+    packer: Option<AtlasPacker>,
 }
 
 /// Atlas region definition
 ///
 /// Defines a rectangular sub-region within a texture layer.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "manifest", derive(serde::Serialize, serde::Deserialize))]
 pub struct AtlasRegion {
     pub x: u32,
     pub y: u32,
@@ -47,6 +56,15 @@ pub struct AtlasRegion {
     pub height: u32,
 }
 
+impl AtlasRegion {
+    /// UV rectangle `(offset.x, offset.y, scale.x, scale.y)` of the region
+    /// in a texture of the given size
+    pub fn uv_rect(&self, texture_width: u32, texture_height: u32) -> [f32; 4] {
+        let (w, h) = (texture_width as f32, texture_height as f32);
+        [self.x as f32 / w, self.y as f32 / h, self.width as f32 / w, self.height as f32 / h]
+    }
+}
+
 // ===== DESCRIPTORS =====
 
 /// Texture creation descriptor
@@ -70,6 +88,41 @@ pub struct AtlasRegionDesc {
     pub region: AtlasRegion,
 }
 
+impl TextureDesc {
+    /// Whether the texture is loaded from data and only sampled, so it can
+    /// be downscaled by a platform profile
+    pub(crate) fn is_downscalable(&self) -> bool {
+        self.texture.usage == graphics_device::TextureUsage::Sampled
+            && self.texture.texture_type != graphics_device::TextureType::Tex3D
+            && (self.texture.data.is_some() || self.layers.iter().any(|l| l.data.is_some()))
+    }
+
+    /// Halve the texture `levels` times (see
+    /// `graphics_device::TextureDesc::drop_top_mips()`). Atlas regions are
+    /// scaled down with it.
+    pub(crate) fn drop_top_mips(&mut self, levels: u32) -> Result<()> {
+        if levels == 0 {
+            return Ok(());
+        }
+        // Layer data replaces the descriptor data at creation: move it
+        // there so that every image is downscaled together
+        if self.layers.iter().any(|l| l.data.is_some()) {
+            let layers = self.layers.iter_mut()
+                .filter_map(|l| l.data.take().map(|data| graphics_device::TextureLayerData { layer: l.layer_index, data }))
+                .collect();
+            self.texture.data = Some(graphics_device::TextureData::Layers(layers));
+        }
+        self.texture.drop_top_mips(levels)?;
+        for region in self.layers.iter_mut().flat_map(|l| l.regions.iter_mut()).map(|r| &mut r.region) {
+            region.x >>= levels;
+            region.y >>= levels;
+            region.width = (region.width >> levels).max(1);
+            region.height = (region.height >> levels).max(1);
+        }
+        Ok(())
+    }
+}
+
 // ===== TEXTURE IMPLEMENTATION =====
 
 impl Texture {
@@ -100,6 +153,8 @@ impl Texture {
                 "Tex2D texture cannot have array_layers > 1 (got {}). Use TextureType::Array2D instead.",
                 array_layers);
         }
+        Self::validate_cube(&desc.texture)?;
+        desc.texture.validate_volume()?;
 
         // ========== VALIDATION 2: Indexed texture constraints ==========
         if is_indexed && desc.layers.is_empty() {
@@ -189,7 +244,7 @@ impl Texture {
                     texture_width,
                     texture_height,
                     desc.texture.format
-                );
+                ) * desc.texture.depth.max(1) as usize;
 
                 if data.len() != expected_size {
                     engine_bail!("galaxy3d::Texture", "Layer '{}' data size mismatch: expected {} bytes, got {}",
@@ -227,6 +282,7 @@ impl Texture {
 
         let mut layers = Vec::new();
         let mut layer_names = FxHashMap::default();
+        let info = graphics_device_texture.info();
 
         for (vec_index, layer_desc) in desc.layers.into_iter().enumerate() {
             // Build regions for this layer
@@ -243,6 +299,9 @@ impl Texture {
                 layer_index: layer_desc.layer_index,
                 regions,
                 region_names,
+                width: info.width,
+                height: info.height,
+                packer: None,
             };
 
             layers.push(layer);
@@ -256,6 +315,29 @@ impl Texture {
         })
     }
 
+    /// Cube maps: square faces, `CUBE_FACE_COUNT` layers per cube
+    fn validate_cube(desc: &graphics_device::TextureDesc) -> Result<()> {
+        let faces = graphics_device::CUBE_FACE_COUNT;
+        match desc.texture_type {
+            graphics_device::TextureType::Cube if desc.array_layers != faces => {
+                engine_bail!("galaxy3d::Texture",
+                    "Cube texture must have array_layers = {} (got {})", faces, desc.array_layers);
+            }
+            graphics_device::TextureType::CubeArray
+                if desc.array_layers == 0 || !desc.array_layers.is_multiple_of(faces) =>
+            {
+                engine_bail!("galaxy3d::Texture",
+                    "CubeArray texture must have a multiple of {} array_layers (got {})",
+                    faces, desc.array_layers);
+            }
+            texture_type if texture_type.is_cube() && desc.width != desc.height => {
+                engine_bail!("galaxy3d::Texture",
+                    "Cube texture faces must be square (got {}x{})", desc.width, desc.height);
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Calculate expected data size for a layer
     fn calculate_layer_data_size(width: u32, height: u32, format: graphics_device::TextureFormat) -> usize {
         let bytes_per_pixel = match format {
@@ -351,11 +433,15 @@ impl Texture {
             region_names.insert(region_desc.name, region_index);
         }
 
+        let info = self.graphics_device_texture.info();
         let layer = TextureLayer {
             name: desc.name.clone(),
             layer_index: desc.layer_index,
             regions,
             region_names,
+            width: info.width,
+            height: info.height,
+            packer: None,
         };
 
         let vec_index = self.layers.len();
@@ -374,6 +460,24 @@ impl Texture {
         layer.add_region(desc, &self.graphics_device_texture.info())
     }
 
+    /// Pack a `width` x `height` region into a layer
+    /// (see `TextureLayer::allocate_region`)
+    pub fn allocate_region(&mut self, layer_name: &str, width: u32, height: u32) -> Result<AtlasRegion> {
+        self.layer_mut(layer_name)?.allocate_region(width, height)
+    }
+
+    /// Give back a region packed into a layer
+    /// (see `TextureLayer::free_region`)
+    pub fn free_region(&mut self, layer_name: &str, region: &AtlasRegion) -> Result<bool> {
+        Ok(self.layer_mut(layer_name)?.free_region(region))
+    }
+
+    fn layer_mut(&mut self, layer_name: &str) -> Result<&mut TextureLayer> {
+        let layer_vec_index = *self.layer_names.get(layer_name)
+            .ok_or_else(|| engine_err!("galaxy3d::Texture", "Layer '{}' not found", layer_name))?;
+        Ok(&mut self.layers[layer_vec_index])
+    }
+
     // ===== INFO =====
 
     /// Check if this is a simple texture (array_layers == 1)
@@ -436,6 +540,47 @@ impl TextureLayer {
         self.layer_index
     }
 
+    // ===== RUNTIME PACKING =====
+
+    /// Pack a `width` x `height` region into the free space of the layer,
+    /// around the declared regions and the regions allocated before.
+    ///
+    /// The region is not named: use its `uv_rect()` directly, and give it
+    /// back with `free_region()`. Leave a gutter around sprites sampled
+    /// with filtering by asking for a larger size.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a dimension is zero or if no free rectangle is
+    /// large enough (see `atlas_stats()` to decide when to repack).
+    pub fn allocate_region(&mut self, width: u32, height: u32) -> Result<AtlasRegion> {
+        if width == 0 || height == 0 {
+            engine_bail!("galaxy3d::Texture", "Cannot allocate a {}x{} region in layer '{}'",
+                width, height, self.name);
+        }
+        let packer = self.packer
+            .get_or_insert_with(|| AtlasPacker::new(self.width, self.height, &self.regions));
+        match packer.allocate(width, height) {
+            Some(region) => Ok(region),
+            None => crate::engine_bail_warn!("galaxy3d::Texture",
+                "Layer '{}' has no free {}x{} rectangle", self.name, width, height),
+        }
+    }
+
+    /// Give back a region returned by `allocate_region()`. Returns false if
+    /// the layer did not allocate it (declared regions cannot be freed).
+    pub fn free_region(&mut self, region: &AtlasRegion) -> bool {
+        self.packer.as_mut().is_some_and(|packer| packer.free(region))
+    }
+
+    /// Occupancy and fragmentation of the layer
+    pub fn atlas_stats(&self) -> AtlasStats {
+        match &self.packer {
+            Some(packer) => packer.stats(),
+            None => AtlasPacker::new(self.width, self.height, &self.regions).stats(),
+        }
+    }
+
     // ===== MODIFICATION (internal) =====
 
     pub(crate) fn add_region(&mut self, desc: AtlasRegionDesc, texture_info: &graphics_device::TextureInfo) -> Result<u32> {
@@ -460,6 +605,9 @@ impl TextureLayer {
             engine_bail!("galaxy3d::Texture", "Region '{}' has zero dimension", desc.name);
         }
 
+        if let Some(packer) = self.packer.as_mut() {
+            packer.reserve(&desc.region);
+        }
         let index = self.regions.len();
         self.regions.push(desc.region);
         self.region_names.insert(desc.name, index);
@@ -29,6 +29,7 @@ fn create_simple_texture_desc(graphics_device: Arc<Mutex<dyn graphics_device::Gr
         texture: graphics_device::TextureDesc {
             width: 256,
             height: 256,
+            depth: 1,
             format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
             texture_type: graphics_device::TextureType::Tex2D,
             sample_count: graphics_device::SampleCount::S1,
@@ -36,6 +37,7 @@ fn create_simple_texture_desc(graphics_device: Arc<Mutex<dyn graphics_device::Gr
             array_layers: 1,
             mipmap: graphics_device::MipmapMode::None,
             data: None,
+            debug_name: None,
         },
         layers: vec![
             LayerDesc {
@@ -55,6 +57,7 @@ fn create_indexed_texture_desc(graphics_device: Arc<Mutex<dyn graphics_device::G
         texture: graphics_device::TextureDesc {
             width: 256,
             height: 256,
+            depth: 1,
             format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
             texture_type: graphics_device::TextureType::Array2D,
             sample_count: graphics_device::SampleCount::S1,
@@ -62,6 +65,7 @@ fn create_indexed_texture_desc(graphics_device: Arc<Mutex<dyn graphics_device::G
             array_layers: 4,
             mipmap: graphics_device::MipmapMode::None,
             data: None,
+            debug_name: None,
         },
         layers: vec![
             LayerDesc {
@@ -104,6 +108,7 @@ fn test_simple_texture_requires_one_layer() {
         texture: graphics_device::TextureDesc {
             width: 256,
             height: 256,
+            depth: 1,
             format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
             texture_type: graphics_device::TextureType::Tex2D,
             sample_count: graphics_device::SampleCount::S1,
@@ -111,6 +116,7 @@ fn test_simple_texture_requires_one_layer() {
             array_layers: 1, // Simple texture
             mipmap: graphics_device::MipmapMode::None,
             data: None,
+            debug_name: None,
         },
         layers: vec![], // ERROR: must have exactly 1 layer
     };
@@ -127,6 +133,7 @@ fn test_simple_texture_layer_must_be_index_0() {
         texture: graphics_device::TextureDesc {
             width: 256,
             height: 256,
+            depth: 1,
             format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
             texture_type: graphics_device::TextureType::Tex2D,
             sample_count: graphics_device::SampleCount::S1,
@@ -134,6 +141,7 @@ fn test_simple_texture_layer_must_be_index_0() {
             array_layers: 1,
             mipmap: graphics_device::MipmapMode::None,
             data: None,
+            debug_name: None,
         },
         layers: vec![
             LayerDesc {
@@ -157,6 +165,7 @@ fn test_simple_texture_with_atlas_region() {
         texture: graphics_device::TextureDesc {
             width: 256,
             height: 256,
+            depth: 1,
             format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
             texture_type: graphics_device::TextureType::Tex2D,
             sample_count: graphics_device::SampleCount::S1,
@@ -164,6 +173,7 @@ fn test_simple_texture_with_atlas_region() {
             array_layers: 1,
             mipmap: graphics_device::MipmapMode::None,
             data: None,
+            debug_name: None,
         },
         layers: vec![
             LayerDesc {
@@ -224,6 +234,7 @@ fn test_indexed_texture_can_be_empty() {
         texture: graphics_device::TextureDesc {
             width: 256,
             height: 256,
+            depth: 1,
             format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
             texture_type: graphics_device::TextureType::Array2D,
             sample_count: graphics_device::SampleCount::S1,
@@ -231,6 +242,7 @@ fn test_indexed_texture_can_be_empty() {
             array_layers: 4, // Indexed
             mipmap: graphics_device::MipmapMode::None,
             data: None,
+            debug_name: None,
         },
         layers: vec![], // OK: indexed can start empty
     };
@@ -249,6 +261,7 @@ fn test_indexed_texture_layer_bounds() {
         texture: graphics_device::TextureDesc {
             width: 256,
             height: 256,
+            depth: 1,
             format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
             texture_type: graphics_device::TextureType::Array2D,
             sample_count: graphics_device::SampleCount::S1,
@@ -256,6 +269,7 @@ fn test_indexed_texture_layer_bounds() {
             array_layers: 2, // Only 2 layers
             mipmap: graphics_device::MipmapMode::None,
             data: None,
+            debug_name: None,
         },
         layers: vec![
             LayerDesc {
@@ -281,6 +295,49 @@ fn test_indexed_texture_layer_bounds() {
 // LAYER ACCESS TESTS
 // ============================================================================
 
+#[test]
+fn test_cube_texture_layer_rules() {
+    let cube = |texture_type, width, array_layers| {
+        let mut desc = create_indexed_texture_desc(create_mock_graphics_device());
+        desc.texture.texture_type = texture_type;
+        desc.texture.width = width;
+        desc.texture.array_layers = array_layers;
+        Texture::from_desc(desc)
+    };
+    use graphics_device::TextureType::{Cube, CubeArray};
+
+    let texture = cube(Cube, 256, graphics_device::CUBE_FACE_COUNT).unwrap();
+    assert_eq!(texture.graphics_device_texture().info().texture_type, Cube);
+    assert!(cube(CubeArray, 256, 12).is_ok());
+
+    assert!(cube(Cube, 256, 4).is_err());
+    assert!(cube(CubeArray, 256, 8).is_err());
+    // Faces must be square
+    assert!(cube(Cube, 128, 6).is_err());
+}
+
+#[test]
+fn test_volume_texture_layer_data() {
+    let volume = |texture_type, depth, data_size: usize| {
+        let mut desc = create_simple_texture_desc(create_mock_graphics_device());
+        desc.texture.width = 16;
+        desc.texture.height = 16;
+        desc.texture.depth = depth;
+        desc.texture.texture_type = texture_type;
+        desc.layers[0].data = Some(vec![0u8; data_size]);
+        Texture::from_desc(desc)
+    };
+    use graphics_device::TextureType::{Tex2D, Tex3D};
+
+    let texture = volume(Tex3D, 8, 16 * 16 * 8 * 4).unwrap();
+    assert_eq!(texture.graphics_device_texture().info().depth, 8);
+    assert_eq!(texture.graphics_device_texture().info().texture_type, Tex3D);
+
+    // The single layer holds every slice
+    assert!(volume(Tex3D, 8, 16 * 16 * 4).is_err());
+    assert!(volume(Tex2D, 8, 16 * 16 * 8 * 4).is_err());
+}
+
 #[test]
 fn test_layer_access_by_index() {
     let graphics_device = create_mock_graphics_device();
@@ -434,6 +491,53 @@ fn test_add_region_to_layer() {
     assert_eq!(layer.region_count(), 1);
 }
 
+#[test]
+fn test_allocate_region_packs_around_declared_regions() {
+    let graphics_device = create_mock_graphics_device();
+    let mut texture = Texture::from_desc(create_simple_texture_desc(graphics_device)).unwrap();
+    texture.add_region("main", AtlasRegionDesc {
+        name: "logo".to_string(),
+        region: AtlasRegion { x: 0, y: 0, width: 256, height: 128 },
+    }).unwrap();
+
+    let region = texture.allocate_region("main", 128, 128).unwrap();
+    assert!(region.y >= 128);
+    assert!(texture.allocate_region("main", 0, 4).is_err());
+    assert!(texture.allocate_region("missing", 4, 4).is_err());
+
+    let stats = texture.layer(0).unwrap().atlas_stats();
+    assert_eq!(stats.allocated_regions, 1);
+    assert_eq!(stats.free_area, 128 * 128);
+    assert!(texture.allocate_region("main", 256, 128).is_err());
+    // Allocated regions are not named
+    assert_eq!(texture.layer(0).unwrap().region_count(), 1);
+}
+
+#[test]
+fn test_free_region_returns_space() {
+    let graphics_device = create_mock_graphics_device();
+    let mut texture = Texture::from_desc(create_simple_texture_desc(graphics_device)).unwrap();
+    let region = texture.allocate_region("main", 256, 256).unwrap();
+
+    assert!(texture.allocate_region("main", 1, 1).is_err());
+    assert!(texture.free_region("main", &region).unwrap());
+    assert!(!texture.free_region("main", &region).unwrap());
+    assert_eq!(texture.allocate_region("main", 1, 1).unwrap(), AtlasRegion { x: 0, y: 0, width: 1, height: 1 });
+}
+
+#[test]
+fn test_declared_region_added_after_packing_is_reserved() {
+    let graphics_device = create_mock_graphics_device();
+    let mut texture = Texture::from_desc(create_simple_texture_desc(graphics_device)).unwrap();
+    texture.allocate_region("main", 128, 256).unwrap();
+    texture.add_region("main", AtlasRegionDesc {
+        name: "hud".to_string(),
+        region: AtlasRegion { x: 128, y: 0, width: 128, height: 256 },
+    }).unwrap();
+
+    assert!(texture.allocate_region("main", 1, 1).is_err());
+}
+
 #[test]
 fn test_region_bounds_validation() {
     let graphics_device = create_mock_graphics_device();
@@ -442,6 +546,7 @@ fn test_region_bounds_validation() {
         texture: graphics_device::TextureDesc {
             width: 256,
             height: 256,
+            depth: 1,
             format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
             texture_type: graphics_device::TextureType::Tex2D,
             sample_count: graphics_device::SampleCount::S1,
@@ -449,6 +554,7 @@ fn test_region_bounds_validation() {
             array_layers: 1,
             mipmap: graphics_device::MipmapMode::None,
             data: None,
+            debug_name: None,
         },
         layers: vec![
             LayerDesc {
@@ -482,6 +588,7 @@ fn test_region_zero_dimension() {
         texture: graphics_device::TextureDesc {
             width: 256,
             height: 256,
+            depth: 1,
             format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
             texture_type: graphics_device::TextureType::Tex2D,
             sample_count: graphics_device::SampleCount::S1,
@@ -489,6 +596,7 @@ fn test_region_zero_dimension() {
             array_layers: 1,
             mipmap: graphics_device::MipmapMode::None,
             data: None,
+            debug_name: None,
         },
         layers: vec![
             LayerDesc {
@@ -514,6 +622,13 @@ fn test_region_zero_dimension() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_region_uv_rect() {
+    let region = AtlasRegion { x: 64, y: 128, width: 32, height: 64 };
+    assert_eq!(region.uv_rect(256, 256), [0.25, 0.5, 0.125, 0.25]);
+    assert_eq!(region.uv_rect(128, 512), [0.5, 0.25, 0.25, 0.125]);
+}
+
 #[test]
 fn test_region_lookup() {
     let graphics_device = create_mock_graphics_device();
@@ -522,6 +637,7 @@ fn test_region_lookup() {
         texture: graphics_device::TextureDesc {
             width: 256,
             height: 256,
+            depth: 1,
             format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
             texture_type: graphics_device::TextureType::Tex2D,
             sample_count: graphics_device::SampleCount::S1,
@@ -529,6 +645,7 @@ fn test_region_lookup() {
             array_layers: 1,
             mipmap: graphics_device::MipmapMode::None,
             data: None,
+            debug_name: None,
         },
         layers: vec![
             LayerDesc {
@@ -580,6 +697,7 @@ fn test_duplicate_region_names() {
         texture: graphics_device::TextureDesc {
             width: 256,
             height: 256,
+            depth: 1,
             format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
             texture_type: graphics_device::TextureType::Tex2D,
             sample_count: graphics_device::SampleCount::S1,
@@ -587,6 +705,7 @@ fn test_duplicate_region_names() {
             array_layers: 1,
             mipmap: graphics_device::MipmapMode::None,
             data: None,
+            debug_name: None,
         },
         layers: vec![
             LayerDesc {
@@ -633,6 +752,7 @@ fn test_duplicate_layer_names() {
         texture: graphics_device::TextureDesc {
             width: 256,
             height: 256,
+            depth: 1,
             format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
             texture_type: graphics_device::TextureType::Array2D,
             sample_count: graphics_device::SampleCount::S1,
@@ -640,6 +760,7 @@ fn test_duplicate_layer_names() {
             array_layers: 4,
             mipmap: graphics_device::MipmapMode::None,
             data: None,
+            debug_name: None,
         },
         layers: vec![
             LayerDesc {
@@ -669,6 +790,7 @@ fn test_duplicate_layer_indices() {
         texture: graphics_device::TextureDesc {
             width: 256,
             height: 256,
+            depth: 1,
             format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
             texture_type: graphics_device::TextureType::Array2D,
             sample_count: graphics_device::SampleCount::S1,
@@ -676,6 +798,7 @@ fn test_duplicate_layer_indices() {
             array_layers: 4,
             mipmap: graphics_device::MipmapMode::None,
             data: None,
+            debug_name: None,
         },
         layers: vec![
             LayerDesc {
@@ -709,6 +832,7 @@ fn test_complex_indexed_texture_with_atlas() {
         texture: graphics_device::TextureDesc {
             width: 512,
             height: 512,
+            depth: 1,
             format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
             texture_type: graphics_device::TextureType::Array2D,
             sample_count: graphics_device::SampleCount::S1,
@@ -716,6 +840,7 @@ fn test_complex_indexed_texture_with_atlas() {
             array_layers: 3,
             mipmap: graphics_device::MipmapMode::None,
             data: None,
+            debug_name: None,
         },
         layers: vec![
             LayerDesc {
@@ -806,6 +931,7 @@ fn test_texture_layer_getters() {
         texture: graphics_device::TextureDesc {
             width: 256,
             height: 256,
+            depth: 1,
             format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
             texture_type: graphics_device::TextureType::Tex2D,
             sample_count: graphics_device::SampleCount::S1,
@@ -813,6 +939,7 @@ fn test_texture_layer_getters() {
             array_layers: 1,
             mipmap: graphics_device::MipmapMode::None,
             data: None,
+            debug_name: None,
         },
         layers: vec![
             LayerDesc {
@@ -870,6 +997,7 @@ fn test_layer_data_size_validation_rgba8() {
         texture: graphics_device::TextureDesc {
             width: 256,
             height: 256,
+            depth: 1,
             format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
             texture_type: graphics_device::TextureType::Tex2D,
             sample_count: graphics_device::SampleCount::S1,
@@ -877,6 +1005,7 @@ fn test_layer_data_size_validation_rgba8() {
             array_layers: 1,
             mipmap: graphics_device::MipmapMode::None,
             data: None,
+            debug_name: None,
         },
         layers: vec![
             LayerDesc {
@@ -898,6 +1027,7 @@ fn test_layer_data_size_validation_rgba8() {
         texture: graphics_device::TextureDesc {
             width: 256,
             height: 256,
+            depth: 1,
             format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
             texture_type: graphics_device::TextureType::Tex2D,
             sample_count: graphics_device::SampleCount::S1,
@@ -905,6 +1035,7 @@ fn test_layer_data_size_validation_rgba8() {
             array_layers: 1,
             mipmap: graphics_device::MipmapMode::None,
             data: None,
+            debug_name: None,
         },
         layers: vec![
             LayerDesc {
@@ -930,6 +1061,7 @@ fn test_layer_data_upload_multiple_layers() {
         texture: graphics_device::TextureDesc {
             width: 128,
             height: 128,
+            depth: 1,
             format: graphics_device::TextureFormat::B8G8R8A8_SRGB,
             texture_type: graphics_device::TextureType::Array2D,
             sample_count: graphics_device::SampleCount::S1,
@@ -937,6 +1069,7 @@ fn test_layer_data_upload_multiple_layers() {
             array_layers: 3,
             mipmap: graphics_device::MipmapMode::None,
             data: None,
+            debug_name: None,
         },
         layers: vec![
             LayerDesc {
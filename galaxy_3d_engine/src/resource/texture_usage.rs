@@ -0,0 +1,147 @@
+//! Texture usage statistics.
+//!
+//! Approximate, draw-level record of which textures (and which layers of
+//! texture arrays) were actually drawn with. The drawer records the texture
+//! slots of every material pass it emits; the application advances the
+//! frame counter once per frame with
+//! `ResourceManager::begin_texture_usage_frame()`.
+//!
+//! The report is meant to drive streaming eviction (textures idle for many
+//! frames) and to find dead assets (textures never drawn with). Textures
+//! pinned with `ResourceManager::pin_texture()` are never eviction
+//! candidates.
+
+use rustc_hash::FxHashMap;
+use super::resource_manager::TextureKey;
+
+// ===== PER-TEXTURE STATISTICS =====
+
+/// Usage statistics of one texture.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TextureUsageStats {
+    /// Last frame the texture was drawn with
+    pub last_used_frame: u64,
+    /// Number of draws using the texture during `last_used_frame`
+    pub draw_count: u32,
+    /// Number of draws using the texture since tracking started
+    pub total_draw_count: u64,
+    /// Array layers sampled during `last_used_frame` (sorted, unique).
+    /// Empty when only whole-texture slots referenced it.
+    pub layers: Vec<u32>,
+}
+
+// ===== TRACKER =====
+
+/// Per-frame texture usage tracker, owned by the `ResourceManager`.
+#[derive(Debug, Default)]
+pub struct TextureUsageTracker {
+    frame: u64,
+    stats: FxHashMap<TextureKey, TextureUsageStats>,
+}
+
+impl TextureUsageTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current frame index (0 before the first `begin_frame`)
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Advance to the next frame. Per-frame counters are reset lazily on
+    /// the first use of each texture in the new frame.
+    pub fn begin_frame(&mut self) {
+        self.frame += 1;
+    }
+
+    /// Record one draw using `texture` (optionally a specific array layer).
+    pub fn record(&mut self, texture: TextureKey, layer: Option<u32>) {
+        let frame = self.frame;
+        let stats = self.stats.entry(texture).or_default();
+        if stats.last_used_frame != frame {
+            stats.last_used_frame = frame;
+            stats.draw_count = 0;
+            stats.layers.clear();
+        }
+        stats.draw_count += 1;
+        stats.total_draw_count += 1;
+        if let Some(layer) = layer {
+            if let Err(pos) = stats.layers.binary_search(&layer) {
+                stats.layers.insert(pos, layer);
+            }
+        }
+    }
+
+    /// Statistics of a texture, or None if it was never drawn with
+    pub fn stats(&self, texture: TextureKey) -> Option<&TextureUsageStats> {
+        self.stats.get(&texture)
+    }
+
+    /// Number of frames since the texture was last drawn with, or None if
+    /// it was never drawn with.
+    pub fn frames_idle(&self, texture: TextureKey) -> Option<u64> {
+        self.stats.get(&texture).map(|s| self.frame - s.last_used_frame)
+    }
+
+    /// Forget a texture (called when it is removed from the manager)
+    pub fn remove(&mut self, texture: TextureKey) {
+        self.stats.remove(&texture);
+    }
+
+    /// Drop every statistic and restart at frame 0
+    pub fn clear(&mut self) {
+        self.frame = 0;
+        self.stats.clear();
+    }
+}
+
+// ===== REPORT =====
+
+/// One texture of a `TextureUsageReport`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextureUsageRecord {
+    /// Texture key
+    pub texture: TextureKey,
+    /// Texture name in the manager
+    pub name: String,
+    /// Statistics, None if the texture was never drawn with
+    pub stats: Option<TextureUsageStats>,
+    /// Frames since the last use, None if never drawn with
+    pub frames_idle: Option<u64>,
+    /// Residency pins held on the texture (0 = evictable)
+    pub pin_count: u32,
+}
+
+/// Usage snapshot of every texture registered in the `ResourceManager`,
+/// produced by `ResourceManager::texture_usage_report()`.
+#[derive(Debug, Clone)]
+pub struct TextureUsageReport {
+    /// Frame the report was taken at
+    pub frame: u64,
+    /// One record per texture, sorted by name
+    pub textures: Vec<TextureUsageRecord>,
+}
+
+impl TextureUsageReport {
+    /// Textures never drawn with since tracking started (dead asset
+    /// candidates).
+    pub fn never_used(&self) -> impl Iterator<Item = &TextureUsageRecord> {
+        self.textures.iter().filter(|r| r.stats.is_none())
+    }
+
+    /// Textures idle for at least `frames`, including never-used ones
+    /// (eviction candidates), most idle first. Pinned textures are skipped.
+    pub fn idle_for(&self, frames: u64) -> Vec<&TextureUsageRecord> {
+        let mut idle: Vec<&TextureUsageRecord> = self.textures.iter()
+            .filter(|r| r.pin_count == 0)
+            .filter(|r| !matches!(r.frames_idle, Some(f) if f < frames))
+            .collect();
+        idle.sort_by_key(|r| std::cmp::Reverse(r.frames_idle.unwrap_or(u64::MAX)));
+        idle
+    }
+}
+
+#[cfg(test)]
+#[path = "texture_usage_tests.rs"]
+mod tests;
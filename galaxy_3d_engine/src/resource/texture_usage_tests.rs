@@ -0,0 +1,156 @@
+use super::*;
+use slotmap::SlotMap;
+
+fn make_keys(count: usize) -> Vec<TextureKey> {
+    let mut map: SlotMap<TextureKey, ()> = SlotMap::with_key();
+    (0..count).map(|_| map.insert(())).collect()
+}
+
+fn make_record(name: &str, texture: TextureKey, frames_idle: Option<u64>) -> TextureUsageRecord {
+    TextureUsageRecord {
+        texture,
+        name: name.to_string(),
+        stats: frames_idle.map(|_| TextureUsageStats::default()),
+        frames_idle,
+        pin_count: 0,
+    }
+}
+
+// ============================================================================
+// TextureUsageTracker
+// ============================================================================
+
+#[test]
+fn test_tracker_starts_empty() {
+    let tracker = TextureUsageTracker::new();
+    let keys = make_keys(1);
+    assert_eq!(tracker.frame(), 0);
+    assert!(tracker.stats(keys[0]).is_none());
+    assert!(tracker.frames_idle(keys[0]).is_none());
+}
+
+#[test]
+fn test_record_counts_draws_in_frame() {
+    let mut tracker = TextureUsageTracker::new();
+    let keys = make_keys(1);
+    tracker.begin_frame();
+    tracker.record(keys[0], None);
+    tracker.record(keys[0], None);
+
+    let stats = tracker.stats(keys[0]).unwrap();
+    assert_eq!(stats.last_used_frame, 1);
+    assert_eq!(stats.draw_count, 2);
+    assert_eq!(stats.total_draw_count, 2);
+    assert!(stats.layers.is_empty());
+}
+
+#[test]
+fn test_record_resets_per_frame_counters() {
+    let mut tracker = TextureUsageTracker::new();
+    let keys = make_keys(1);
+    tracker.begin_frame();
+    tracker.record(keys[0], Some(3));
+    tracker.record(keys[0], Some(1));
+    tracker.begin_frame();
+    tracker.record(keys[0], Some(2));
+
+    let stats = tracker.stats(keys[0]).unwrap();
+    assert_eq!(stats.last_used_frame, 2);
+    assert_eq!(stats.draw_count, 1);
+    assert_eq!(stats.total_draw_count, 3);
+    assert_eq!(stats.layers, vec![2]);
+}
+
+#[test]
+fn test_record_layers_sorted_unique() {
+    let mut tracker = TextureUsageTracker::new();
+    let keys = make_keys(1);
+    for layer in [5, 1, 5, 3, 1] {
+        tracker.record(keys[0], Some(layer));
+    }
+    assert_eq!(tracker.stats(keys[0]).unwrap().layers, vec![1, 3, 5]);
+}
+
+#[test]
+fn test_frames_idle() {
+    let mut tracker = TextureUsageTracker::new();
+    let keys = make_keys(2);
+    tracker.begin_frame();
+    tracker.record(keys[0], None);
+    tracker.begin_frame();
+    tracker.begin_frame();
+    tracker.record(keys[1], None);
+
+    assert_eq!(tracker.frames_idle(keys[0]), Some(2));
+    assert_eq!(tracker.frames_idle(keys[1]), Some(0));
+}
+
+#[test]
+fn test_remove_and_clear() {
+    let mut tracker = TextureUsageTracker::new();
+    let keys = make_keys(2);
+    tracker.begin_frame();
+    tracker.record(keys[0], None);
+    tracker.record(keys[1], None);
+
+    tracker.remove(keys[0]);
+    assert!(tracker.stats(keys[0]).is_none());
+    assert!(tracker.stats(keys[1]).is_some());
+
+    tracker.clear();
+    assert_eq!(tracker.frame(), 0);
+    assert!(tracker.stats(keys[1]).is_none());
+}
+
+// ============================================================================
+// TextureUsageReport
+// ============================================================================
+
+#[test]
+fn test_report_never_used() {
+    let keys = make_keys(3);
+    let report = TextureUsageReport {
+        frame: 10,
+        textures: vec![
+            make_record("a", keys[0], Some(0)),
+            make_record("b", keys[1], None),
+            make_record("c", keys[2], Some(7)),
+        ],
+    };
+    let names: Vec<&str> = report.never_used().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, vec!["b"]);
+}
+
+#[test]
+fn test_report_idle_for_sorted_most_idle_first() {
+    let keys = make_keys(4);
+    let report = TextureUsageReport {
+        frame: 10,
+        textures: vec![
+            make_record("a", keys[0], Some(0)),
+            make_record("b", keys[1], Some(5)),
+            make_record("c", keys[2], None),
+            make_record("d", keys[3], Some(9)),
+        ],
+    };
+    let names: Vec<&str> = report.idle_for(5).iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, vec!["c", "d", "b"]);
+}
+
+#[test]
+fn test_report_idle_for_skips_pinned() {
+    let keys = make_keys(3);
+    let mut pinned = make_record("b", keys[1], None);
+    pinned.pin_count = 2;
+    let report = TextureUsageReport {
+        frame: 10,
+        textures: vec![
+            make_record("a", keys[0], Some(8)),
+            pinned,
+            make_record("c", keys[2], Some(9)),
+        ],
+    };
+    let names: Vec<&str> = report.idle_for(5).iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, vec!["c", "a"]);
+    assert_eq!(report.never_used().count(), 1);
+}
@@ -0,0 +1,640 @@
+/// Virtual texturing (sparse residency of very large textures).
+///
+/// A `VirtualTexture` is a texture too large to be resident, cut in square
+/// pages at every mip (`pages_x` x `pages_y` pages at mip 0, half as many
+/// per side at each following mip, down to a single root page). Only the
+/// pages the camera needs are kept on the GPU:
+///
+/// - **Physical texture** (`<name>_physical`): a `TextureType::Array2D`
+///   holding one resident page per layer, `page_size + 2 * border` texels per
+///   side. The border repeats the neighbouring texels so bilinear filtering
+///   does not bleed across pages.
+/// - **Page table** (`<name>_page_table`): an `R32_UINT` texture of
+///   `pages_x` x `pages_y` texels with a full mip chain. The texel of a
+///   virtual page holds the finest resident page covering it:
+///   `(resident_mip << 24) | layer`, or `VT_PAGE_TABLE_EMPTY` while not even
+///   the root page is loaded.
+/// - **Feedback pass**: the application renders the scene into an `R32_UINT`
+///   target (`VIRTUAL_TEXTURE_FEEDBACK_FORMAT`, usually at 1/8 resolution)
+///   cleared to `VT_FEEDBACK_NONE`. Its fragment shader writes the packed
+///   page it would sample (`VirtualPage::pack`). `VirtualTextureFeedback`
+///   reads the target back without stalling.
+/// - **Page loader**: `update()` streams the requested pages through the
+///   application `VirtualPageLoader` into free physical layers, evicting the
+///   least recently used pages, then uploads the page table.
+///
+/// Shader interface (the engine does not ship these shaders):
+///
+/// ```glsl
+/// // feedback pass, fragment stage
+/// vec2 texel = uv * vec2(pagesX, pagesY) * pageSize;
+/// float lod = log2(max(length(dFdx(texel)), length(dFdy(texel))));
+/// uint mip = uint(clamp(floor(lod), 0.0, float(mipCount - 1)));
+/// uvec2 page = uvec2(uv * vec2(max(uvec2(pagesX, pagesY) >> mip, uvec2(1))));
+/// outPage = (mip << 28) | (page.x << 14) | page.y;
+///
+/// // material pass, fragment stage
+/// uint entry = texelFetch(pageTable, ivec2(page), int(mip)).r;  // usampler2D
+/// uint resident = entry >> 24;
+/// vec2 pages = vec2(max(uvec2(pagesX, pagesY) >> resident, uvec2(1)));
+/// vec2 inPage = fract(uv * pages) * pageSize + border;
+/// vec4 color = texture(physical, vec3(inPage / (pageSize + 2 * border), entry & 0xFFFFFF));
+/// ```
+///
+/// The physical texture has no mips: sample it with trilinear filtering
+/// disabled or accept the aliasing of the page mip selection. Page uploads
+/// go through `Texture::update()`, which may be synchronous: bound them per
+/// frame with the `max_uploads` argument of `update()`.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use rustc_hash::FxHashMap;
+use crate::error::{Error, Result};
+use crate::engine_bail;
+use crate::graphics_device::{
+    self, CommandList, GraphicsDevice, ImageAccess, MipmapMode, Rect2D, SampleCount,
+    TextureFormat, TextureType, TextureUsage,
+};
+use crate::render_graph::{ReadbackManager, ReadbackTicket};
+use super::resource_manager::{ResourceManager, TextureKey};
+use super::texture::{LayerDesc, TextureDesc};
+
+/// Format of the feedback target rendered by the feedback pass
+pub const VIRTUAL_TEXTURE_FEEDBACK_FORMAT: TextureFormat = TextureFormat::R32_UINT;
+/// Format of the page table texture
+pub const VT_PAGE_TABLE_FORMAT: TextureFormat = TextureFormat::R32_UINT;
+/// Feedback of pixels sampling no virtual page (the target clear value)
+pub const VT_FEEDBACK_NONE: u32 = u32::MAX;
+/// Page table entry of a page with no resident page covering it
+pub const VT_PAGE_TABLE_EMPTY: u32 = u32::MAX;
+/// Maximum number of pages per side at mip 0 (14 bits per packed coordinate)
+pub const MAX_VIRTUAL_PAGES_PER_SIDE: u32 = 1 << 14;
+/// Default page size in texels, border excluded
+pub const DEFAULT_VT_PAGE_SIZE: u32 = 128;
+/// Default page border in texels
+pub const DEFAULT_VT_PAGE_BORDER: u32 = 4;
+/// Default number of physical pages (layers of the physical texture)
+pub const DEFAULT_VT_PHYSICAL_PAGES: u32 = 256;
+
+/// Bit layout of a packed `VirtualPage`
+const PAGE_MIP_SHIFT: u32 = 28;
+const PAGE_X_SHIFT: u32 = 14;
+const PAGE_COORD_MASK: u32 = MAX_VIRTUAL_PAGES_PER_SIDE - 1;
+/// Bit layout of a page table entry
+const PAGE_TABLE_MIP_SHIFT: u32 = 24;
+const PAGE_TABLE_LAYER_MASK: u32 = (1 << PAGE_TABLE_MIP_SHIFT) - 1;
+/// Size of one feedback texel in the readback data
+const FEEDBACK_TEXEL_SIZE: usize = 4;
+
+// ===== VIRTUAL PAGE =====
+
+/// A page of a virtual texture: `x`, `y` in pages of mip `mip`.
+///
+/// A page of mip `m` covers `page_size << m` virtual texels per side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct VirtualPage {
+    pub mip: u32,
+    pub x: u32,
+    pub y: u32,
+}
+
+impl VirtualPage {
+    pub fn new(mip: u32, x: u32, y: u32) -> Self {
+        Self { mip, x, y }
+    }
+
+    /// Value written by the feedback shader for this page
+    pub fn pack(self) -> u32 {
+        (self.mip << PAGE_MIP_SHIFT)
+            | ((self.x & PAGE_COORD_MASK) << PAGE_X_SHIFT)
+            | (self.y & PAGE_COORD_MASK)
+    }
+
+    /// Decode a feedback value, None for `VT_FEEDBACK_NONE`
+    pub fn unpack(value: u32) -> Option<Self> {
+        if value == VT_FEEDBACK_NONE {
+            return None;
+        }
+        Some(Self {
+            mip: value >> PAGE_MIP_SHIFT,
+            x: (value >> PAGE_X_SHIFT) & PAGE_COORD_MASK,
+            y: value & PAGE_COORD_MASK,
+        })
+    }
+
+    /// The page of the next mip covering this one
+    pub fn parent(self) -> Self {
+        Self { mip: self.mip + 1, x: self.x / 2, y: self.y / 2 }
+    }
+}
+
+// ===== DESC =====
+
+/// Layout of a virtual texture
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VirtualTextureDesc {
+    /// Pages per side at mip 0 (powers of two, at most
+    /// `MAX_VIRTUAL_PAGES_PER_SIDE`)
+    pub pages_x: u32,
+    pub pages_y: u32,
+    /// Page size in texels, border excluded
+    pub page_size: u32,
+    /// Texels repeated around each page for filtering
+    pub border: u32,
+    /// Number of physical pages (layers of the physical texture)
+    pub physical_pages: u32,
+    /// Texel format of the physical texture
+    pub format: TextureFormat,
+}
+
+impl Default for VirtualTextureDesc {
+    fn default() -> Self {
+        Self {
+            pages_x: 64,
+            pages_y: 64,
+            page_size: DEFAULT_VT_PAGE_SIZE,
+            border: DEFAULT_VT_PAGE_BORDER,
+            physical_pages: DEFAULT_VT_PHYSICAL_PAGES,
+            format: TextureFormat::R8G8B8A8_UNORM,
+        }
+    }
+}
+
+// ===== PAGE LOADER =====
+
+/// Source of the page texels (disk tiles, procedural terrain, ...).
+pub trait VirtualPageLoader {
+    /// Write the texels of `page`, border included, row after row into
+    /// `texels` (`VirtualTexture::page_byte_size()` bytes).
+    ///
+    /// Return `Ok(false)` when the page is not available yet (e.g. still
+    /// being read by an I/O thread): it stays requested and is asked again
+    /// by the next `update()`.
+    fn load_page(&mut self, page: VirtualPage, texels: &mut [u8]) -> Result<bool>;
+}
+
+/// Outcome of one `VirtualTexture::update()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct VirtualTextureUpdate {
+    /// Pages loaded and uploaded into the physical texture
+    pub uploaded: usize,
+    /// Resident pages evicted to make room
+    pub evicted: usize,
+    /// Requested pages still missing
+    pub pending: usize,
+}
+
+/// A resident page and the feedback frame it was last needed in
+#[derive(Debug, Clone, Copy)]
+struct PhysicalPage {
+    page: VirtualPage,
+    last_used: u64,
+}
+
+// ===== VIRTUAL TEXTURE =====
+
+/// Page residency of a virtual texture (see module docs).
+pub struct VirtualTexture {
+    desc: VirtualTextureDesc,
+    mip_levels: u32,
+    physical: TextureKey,
+    page_table: TextureKey,
+    physical_texture: Arc<dyn graphics_device::Texture>,
+    page_table_texture: Arc<dyn graphics_device::Texture>,
+    /// Resident page of each physical layer
+    layers: Vec<Option<PhysicalPage>>,
+    /// Physical layer of each resident page
+    resident: FxHashMap<VirtualPage, u32>,
+    /// Missing pages, in load order (coarsest mip first, then most seen)
+    requests: Vec<VirtualPage>,
+    /// Feedback frame counter
+    frame: u64,
+    /// CPU copy of the page table, one Vec per mip
+    table: Vec<Vec<u32>>,
+    table_dirty: bool,
+    /// Reused page texels of `update()`
+    scratch: Vec<u8>,
+}
+
+impl VirtualTexture {
+    /// Create the physical texture (`<name>_physical`) and page table
+    /// (`<name>_page_table`) of a virtual texture. The root page is
+    /// requested right away.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the layout is invalid (zero sizes, page counts
+    /// not powers of two or above `MAX_VIRTUAL_PAGES_PER_SIDE`, depth
+    /// format) or if a texture cannot be created.
+    pub fn new(
+        rm: &mut ResourceManager,
+        graphics_device: &Arc<Mutex<dyn GraphicsDevice>>,
+        name: &str,
+        desc: VirtualTextureDesc,
+    ) -> Result<Self> {
+        let valid_side = |pages: u32| pages.is_power_of_two() && pages <= MAX_VIRTUAL_PAGES_PER_SIDE;
+        if !valid_side(desc.pages_x) || !valid_side(desc.pages_y) {
+            engine_bail!("galaxy3d::VirtualTexture",
+                "Page counts must be powers of two up to {}: {}x{}",
+                MAX_VIRTUAL_PAGES_PER_SIDE, desc.pages_x, desc.pages_y);
+        }
+        if desc.page_size == 0 || desc.physical_pages == 0 || desc.physical_pages > PAGE_TABLE_LAYER_MASK {
+            engine_bail!("galaxy3d::VirtualTexture", "Invalid page size or physical page count: {:?}", desc);
+        }
+        if desc.format.is_depth() {
+            engine_bail!("galaxy3d::VirtualTexture", "Depth format {:?} cannot be virtual", desc.format);
+        }
+
+        let physical_size = desc.page_size + 2 * desc.border;
+        let physical = create_texture(rm, graphics_device, format!("{}_physical", name),
+            physical_size, physical_size, desc.format, TextureType::Array2D, desc.physical_pages,
+            MipmapMode::None)?;
+        let page_table = create_texture(rm, graphics_device, format!("{}_page_table", name),
+            desc.pages_x, desc.pages_y, VT_PAGE_TABLE_FORMAT, TextureType::Tex2D, 1,
+            MipmapMode::Generate { max_levels: None })?;
+        let physical_texture = rm.texture(physical).unwrap().graphics_device_texture().clone();
+        let page_table_texture = rm.texture(page_table).unwrap().graphics_device_texture().clone();
+
+        let mip_levels = MipmapMode::max_mip_levels(desc.pages_x, desc.pages_y);
+        let table = (0..mip_levels)
+            .map(|mip| {
+                let (width, height) = pages_at_mip(&desc, mip);
+                vec![VT_PAGE_TABLE_EMPTY; (width * height) as usize]
+            })
+            .collect();
+        let mut texture = Self {
+            desc,
+            mip_levels,
+            physical,
+            page_table,
+            physical_texture,
+            page_table_texture,
+            layers: vec![None; desc.physical_pages as usize],
+            resident: FxHashMap::default(),
+            requests: Vec::new(),
+            frame: 0,
+            table,
+            table_dirty: true,
+            scratch: Vec::new(),
+        };
+        let root = texture.root_page();
+        texture.requests.push(root);
+        texture.upload_page_table()?;
+        Ok(texture)
+    }
+
+    // ===== ACCESSORS =====
+
+    pub fn desc(&self) -> &VirtualTextureDesc {
+        &self.desc
+    }
+
+    /// Number of page mips (the last one is the root page)
+    pub fn mip_levels(&self) -> u32 {
+        self.mip_levels
+    }
+
+    /// Physical texture (one resident page per layer)
+    pub fn physical_texture(&self) -> TextureKey {
+        self.physical
+    }
+
+    /// Page table texture
+    pub fn page_table_texture(&self) -> TextureKey {
+        self.page_table
+    }
+
+    /// The single page of the last mip, never evicted once loaded
+    pub fn root_page(&self) -> VirtualPage {
+        VirtualPage::new(self.mip_levels - 1, 0, 0)
+    }
+
+    /// Bytes of one page, border included, as written by the loader
+    pub fn page_byte_size(&self) -> usize {
+        let side = (self.desc.page_size + 2 * self.desc.border) as usize;
+        side * side * self.desc.format.bytes_per_pixel() as usize
+    }
+
+    /// True if the page exists in the virtual texture
+    pub fn contains_page(&self, page: VirtualPage) -> bool {
+        if page.mip >= self.mip_levels {
+            return false;
+        }
+        let (width, height) = pages_at_mip(&self.desc, page.mip);
+        page.x < width && page.y < height
+    }
+
+    /// Physical layer of a resident page
+    pub fn physical_layer(&self, page: VirtualPage) -> Option<u32> {
+        self.resident.get(&page).copied()
+    }
+
+    /// Number of resident pages
+    pub fn resident_count(&self) -> usize {
+        self.resident.len()
+    }
+
+    /// Missing pages, in the order `update()` loads them
+    pub fn requested_pages(&self) -> &[VirtualPage] {
+        &self.requests
+    }
+
+    /// Page table entry of a page (see module docs), None outside the
+    /// virtual texture
+    pub fn page_table_entry(&self, page: VirtualPage) -> Option<u32> {
+        if !self.contains_page(page) {
+            return None;
+        }
+        let (width, _) = pages_at_mip(&self.desc, page.mip);
+        Some(self.table[page.mip as usize][(page.y * width + page.x) as usize])
+    }
+
+    // ===== FEEDBACK =====
+
+    /// Take the pages sampled by a feedback pass (packed `VirtualPage`
+    /// values, `VT_FEEDBACK_NONE` and pages outside the texture ignored).
+    ///
+    /// Starts a new residency frame: the resident pages needed (and the
+    /// resident ancestors they fall back to) are marked used, and the
+    /// missing ones, with their missing ancestors, replace the previous
+    /// requests.
+    pub fn process_feedback(&mut self, feedback: &[u32]) {
+        self.frame += 1;
+        let mut seen: FxHashMap<VirtualPage, u32> = FxHashMap::default();
+        for page in feedback.iter().filter_map(|&value| VirtualPage::unpack(value)) {
+            if self.contains_page(page) {
+                *seen.entry(page).or_insert(0) += 1;
+            }
+        }
+
+        let mut missing: FxHashMap<VirtualPage, u32> = FxHashMap::default();
+        for (&page, &count) in &seen {
+            let mut current = page;
+            loop {
+                match self.resident.get(&current) {
+                    Some(&layer) => {
+                        if let Some(slot) = self.layers[layer as usize].as_mut() {
+                            slot.last_used = self.frame;
+                        }
+                    }
+                    None => *missing.entry(current).or_insert(0) += count,
+                }
+                if current.mip + 1 >= self.mip_levels {
+                    break;
+                }
+                current = current.parent();
+            }
+        }
+        let root = self.root_page();
+        if !self.resident.contains_key(&root) {
+            missing.entry(root).or_insert(0);
+        }
+
+        let mut requests: Vec<(VirtualPage, u32)> = missing.into_iter().collect();
+        requests.sort_by(|(a, a_count), (b, b_count)| {
+            b.mip.cmp(&a.mip).then(b_count.cmp(a_count)).then(a.cmp(b))
+        });
+        self.requests = requests.into_iter().map(|(page, _)| page).collect();
+    }
+
+    // ===== STREAMING =====
+
+    /// Load up to `max_uploads` requested pages into the physical texture,
+    /// then upload the page table if residency changed.
+    ///
+    /// A page needs a free physical layer, or the least recently used page
+    /// not needed by the last feedback (the root page is never evicted).
+    /// When none is left, the remaining requests wait: the physical texture
+    /// is too small for the current view.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the loader or a texture upload fails. Pages
+    /// uploaded before the failure stay resident.
+    pub fn update(&mut self, loader: &mut dyn VirtualPageLoader, max_uploads: usize) -> Result<VirtualTextureUpdate> {
+        let mut result = VirtualTextureUpdate::default();
+        let mut waiting = Vec::new();
+        let mut outcome = Ok(());
+        let mut pages = std::mem::take(&mut self.requests).into_iter();
+        for page in pages.by_ref() {
+            if self.resident.contains_key(&page) {
+                continue;
+            }
+            if result.uploaded >= max_uploads {
+                waiting.push(page);
+                continue;
+            }
+            match self.stream_page(page, loader) {
+                Ok(Some(evicted)) => {
+                    result.uploaded += 1;
+                    result.evicted += evicted as usize;
+                }
+                Ok(None) => waiting.push(page),
+                Err(e) => {
+                    waiting.push(page);
+                    outcome = Err(e);
+                    break;
+                }
+            }
+        }
+
+        waiting.extend(pages);
+        self.requests = waiting;
+        result.pending = self.requests.len();
+        outcome?;
+        self.upload_page_table()?;
+        Ok(result)
+    }
+
+    /// Evict every page (the page table is uploaded by the next `update()`).
+    /// The root page is requested again.
+    pub fn clear(&mut self) {
+        self.layers.fill(None);
+        self.resident.clear();
+        self.requests = vec![self.root_page()];
+        self.table_dirty = true;
+    }
+
+    // ===== PRIVATE HELPERS =====
+
+    /// Load `page` into a physical layer. Returns None if no layer can be
+    /// freed or the loader is not ready, else whether a page was evicted.
+    fn stream_page(&mut self, page: VirtualPage, loader: &mut dyn VirtualPageLoader) -> Result<Option<bool>> {
+        let Some((layer, evicted)) = self.acquire_layer() else {
+            return Ok(None);
+        };
+        let size = self.page_byte_size();
+        self.scratch.resize(size, 0);
+        if !loader.load_page(page, &mut self.scratch)? {
+            return Ok(None);
+        }
+        if let Some(old) = evicted {
+            self.resident.remove(&old);
+            self.layers[layer as usize] = None;
+            self.table_dirty = true;
+        }
+        self.physical_texture.update(layer, 0, &self.scratch)?;
+        self.layers[layer as usize] = Some(PhysicalPage { page, last_used: self.frame });
+        self.resident.insert(page, layer);
+        self.table_dirty = true;
+        Ok(Some(evicted.is_some()))
+    }
+
+    /// A free layer, or the least recently used evictable one (finest mip
+    /// first on ties) with the page it holds
+    fn acquire_layer(&self) -> Option<(u32, Option<VirtualPage>)> {
+        if let Some(free) = self.layers.iter().position(Option::is_none) {
+            return Some((free as u32, None));
+        }
+        let root = self.root_page();
+        self.layers.iter().enumerate()
+            .filter_map(|(layer, slot)| slot.map(|slot| (layer, slot)))
+            .filter(|(_, slot)| slot.page != root && slot.last_used < self.frame)
+            .min_by_key(|(_, slot)| (slot.last_used, slot.page.mip))
+            .map(|(layer, slot)| (layer as u32, Some(slot.page)))
+    }
+
+    /// Recompute the page table from the resident pages and upload every
+    /// mip, if residency changed since the last upload
+    fn upload_page_table(&mut self) -> Result<()> {
+        if !self.table_dirty {
+            return Ok(());
+        }
+        for mip in 0..self.mip_levels {
+            let (width, height) = pages_at_mip(&self.desc, mip);
+            for y in 0..height {
+                for x in 0..width {
+                    let mut page = VirtualPage::new(mip, x, y);
+                    let entry = loop {
+                        if let Some(&layer) = self.resident.get(&page) {
+                            break (page.mip << PAGE_TABLE_MIP_SHIFT) | layer;
+                        }
+                        if page.mip + 1 >= self.mip_levels {
+                            break VT_PAGE_TABLE_EMPTY;
+                        }
+                        page = page.parent();
+                    };
+                    self.table[mip as usize][(y * width + x) as usize] = entry;
+                }
+            }
+            let bytes: Vec<u8> = self.table[mip as usize].iter().flat_map(|entry| entry.to_ne_bytes()).collect();
+            self.page_table_texture.update(0, mip, &bytes)?;
+        }
+        self.table_dirty = false;
+        Ok(())
+    }
+}
+
+/// Pages per side at a mip
+fn pages_at_mip(desc: &VirtualTextureDesc, mip: u32) -> (u32, u32) {
+    ((desc.pages_x >> mip).max(1), (desc.pages_y >> mip).max(1))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn create_texture(
+    rm: &mut ResourceManager,
+    graphics_device: &Arc<Mutex<dyn GraphicsDevice>>,
+    name: String,
+    width: u32,
+    height: u32,
+    format: TextureFormat,
+    texture_type: TextureType,
+    array_layers: u32,
+    mipmap: MipmapMode,
+) -> Result<TextureKey> {
+    rm.create_texture(name.clone(), TextureDesc {
+        graphics_device: graphics_device.clone(),
+        texture: graphics_device::TextureDesc {
+            width,
+            height,
+            depth: 1,
+            format,
+            usage: TextureUsage::Sampled,
+            texture_type,
+            sample_count: SampleCount::S1,
+            array_layers,
+            data: None,
+            mipmap,
+            debug_name: Some(name),
+        },
+        // A simple texture carries its single layer; array layers are
+        // filled page by page
+        layers: if array_layers == 1 {
+            vec![LayerDesc { name: "default".to_string(), layer_index: 0, data: None, regions: vec![] }]
+        } else {
+            Vec::new()
+        },
+    })
+}
+
+// ===== FEEDBACK READBACK =====
+
+/// Asynchronous readback of the feedback target.
+///
+/// Per frame, after the feedback pass:
+/// 1. `record(cmd, target)` from a pass action, outside a render pass;
+/// 2. `poll()` once after the render graph has executed, and hand the
+///    returned values to `VirtualTexture::process_feedback()`.
+pub struct VirtualTextureFeedback {
+    readbacks: ReadbackManager,
+    pending: VecDeque<ReadbackTicket>,
+}
+
+impl VirtualTextureFeedback {
+    /// Create a feedback reader for a render graph with `frames_in_flight`
+    /// command lists.
+    pub fn new(frames_in_flight: usize) -> Result<Self> {
+        Ok(Self {
+            readbacks: ReadbackManager::new(frames_in_flight)?,
+            pending: VecDeque::new(),
+        })
+    }
+
+    /// Number of recorded copies not yet returned by `poll()`
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Record the copy of the whole feedback target.
+    ///
+    /// `target.access_type` must be `AccessType::TransferRead`, with
+    /// `previous_access_type` set to how the feedback pass wrote it.
+    pub fn record(&mut self, cmd: &mut dyn CommandList, target: &ImageAccess) -> Result<()> {
+        let info = target.texture.info();
+        if info.format != VIRTUAL_TEXTURE_FEEDBACK_FORMAT {
+            crate::engine_error!("galaxy3d::VirtualTextureFeedback",
+                "Feedback target must be {:?}, got {:?}", VIRTUAL_TEXTURE_FEEDBACK_FORMAT, info.format);
+            return Err(Error::Incompatible {
+                expected: format!("{:?}", VIRTUAL_TEXTURE_FEEDBACK_FORMAT),
+                found: format!("{:?}", info.format),
+            });
+        }
+        let rect = Rect2D { x: 0, y: 0, width: info.width, height: info.height };
+        let ticket = self.readbacks.read_texture(cmd, target, &[rect])?;
+        self.pending.push_back(ticket);
+        Ok(())
+    }
+
+    /// Return the most recent completed feedback, None if no copy has
+    /// completed since the last call. Older completed copies are dropped.
+    pub fn poll(&mut self) -> Result<Option<Vec<u32>>> {
+        self.readbacks.poll()?;
+        let mut latest = None;
+        while let Some(&ticket) = self.pending.front() {
+            let Some(data) = self.readbacks.take(ticket) else {
+                break;
+            };
+            self.pending.pop_front();
+            latest = Some(data);
+        }
+        Ok(latest.map(|data| {
+            data.chunks_exact(FEEDBACK_TEXEL_SIZE)
+                .map(|bytes| u32::from_ne_bytes(bytes.try_into().unwrap()))
+                .collect()
+        }))
+    }
+}
+
+#[cfg(test)]
+#[path = "virtual_texture_tests.rs"]
+mod tests;
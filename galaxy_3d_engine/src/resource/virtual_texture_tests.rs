@@ -0,0 +1,253 @@
+/// Unit tests for virtual_texture.rs
+
+use super::*;
+use crate::graphics_device::AccessType;
+use crate::graphics_device::mock_graphics_device::{MockCommandList, MockGraphicsDevice, MockTexture};
+use serial_test::serial;
+use crate::render_graph::test_helpers::setup_engine;
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+fn make_device() -> Arc<Mutex<dyn GraphicsDevice>> {
+    Arc::new(Mutex::new(MockGraphicsDevice::new()))
+}
+
+/// 4x4 pages of 8 texels (3 mips), `physical_pages` layers
+fn small_desc(physical_pages: u32) -> VirtualTextureDesc {
+    VirtualTextureDesc {
+        pages_x: 4,
+        pages_y: 4,
+        page_size: 8,
+        border: 1,
+        physical_pages,
+        format: TextureFormat::R8G8B8A8_UNORM,
+    }
+}
+
+fn create(rm: &mut ResourceManager, physical_pages: u32) -> VirtualTexture {
+    VirtualTexture::new(rm, &make_device(), "terrain", small_desc(physical_pages)).unwrap()
+}
+
+/// Loader recording the pages it was asked for
+#[derive(Default)]
+struct TestLoader {
+    not_ready: bool,
+    loaded: Vec<VirtualPage>,
+}
+
+impl VirtualPageLoader for TestLoader {
+    fn load_page(&mut self, page: VirtualPage, texels: &mut [u8]) -> Result<bool> {
+        if self.not_ready {
+            return Ok(false);
+        }
+        texels.fill(page.mip as u8);
+        self.loaded.push(page);
+        Ok(true)
+    }
+}
+
+fn entry(mip: u32, layer: u32) -> u32 {
+    (mip << PAGE_TABLE_MIP_SHIFT) | layer
+}
+
+// ============================================================================
+// PAGES AND CREATION
+// ============================================================================
+
+#[test]
+fn test_page_pack_round_trip() {
+    let page = VirtualPage::new(3, 1234, 16383);
+    assert_eq!(VirtualPage::unpack(page.pack()), Some(page));
+    assert_eq!(VirtualPage::unpack(VT_FEEDBACK_NONE), None);
+    assert_eq!(VirtualPage::new(0, 5, 3).parent(), VirtualPage::new(1, 2, 1));
+}
+
+#[test]
+fn test_new_creates_physical_and_page_table() {
+    let mut rm = ResourceManager::new();
+    let vt = create(&mut rm, 4);
+
+    assert_eq!(rm.texture_key("terrain_physical"), Some(vt.physical_texture()));
+    assert_eq!(rm.texture_key("terrain_page_table"), Some(vt.page_table_texture()));
+    let physical = rm.texture(vt.physical_texture()).unwrap().graphics_device_texture().info().clone();
+    assert_eq!((physical.width, physical.array_layers), (10, 4));
+    assert_eq!(physical.texture_type, TextureType::Array2D);
+    let table = rm.texture(vt.page_table_texture()).unwrap().graphics_device_texture().info().clone();
+    assert_eq!((table.width, table.mip_levels, table.format), (4, 3, VT_PAGE_TABLE_FORMAT));
+
+    assert_eq!(vt.mip_levels(), 3);
+    assert_eq!(vt.page_byte_size(), 10 * 10 * 4);
+    assert_eq!(vt.requested_pages(), &[vt.root_page()]);
+    assert_eq!(vt.page_table_entry(VirtualPage::new(0, 3, 3)), Some(VT_PAGE_TABLE_EMPTY));
+    assert_eq!(vt.page_table_entry(VirtualPage::new(0, 4, 0)), None);
+}
+
+#[test]
+fn test_new_with_single_physical_page() {
+    let mut rm = ResourceManager::new();
+    let vt = VirtualTexture::new(&mut rm, &make_device(), "single", small_desc(1)).unwrap();
+
+    let physical = rm.texture(vt.physical_texture()).unwrap();
+    assert_eq!(physical.graphics_device_texture().info().array_layers, 1);
+    assert_eq!(physical.layer_count(), 1);
+    assert_eq!(rm.texture(vt.page_table_texture()).unwrap().layer_count(), 1);
+}
+
+#[test]
+fn test_new_rejects_invalid_layouts() {
+    let mut rm = ResourceManager::new();
+    let gd = make_device();
+    let invalid = [
+        VirtualTextureDesc { pages_x: 3, ..small_desc(4) },
+        VirtualTextureDesc { pages_y: MAX_VIRTUAL_PAGES_PER_SIDE * 2, ..small_desc(4) },
+        VirtualTextureDesc { page_size: 0, ..small_desc(4) },
+        small_desc(0),
+        VirtualTextureDesc { format: TextureFormat::D32_FLOAT, ..small_desc(4) },
+    ];
+    for desc in invalid {
+        assert!(VirtualTexture::new(&mut rm, &gd, "bad", desc).is_err(), "{:?}", desc);
+    }
+}
+
+// ============================================================================
+// FEEDBACK AND STREAMING
+// ============================================================================
+
+#[test]
+fn test_root_page_fills_page_table() {
+    let mut rm = ResourceManager::new();
+    let mut vt = create(&mut rm, 4);
+    let mut loader = TestLoader::default();
+
+    let result = vt.update(&mut loader, 8).unwrap();
+    assert_eq!(result, VirtualTextureUpdate { uploaded: 1, evicted: 0, pending: 0 });
+    let layer = vt.physical_layer(vt.root_page()).unwrap();
+    for page in [VirtualPage::new(0, 0, 0), VirtualPage::new(0, 3, 2), VirtualPage::new(1, 1, 1)] {
+        assert_eq!(vt.page_table_entry(page), Some(entry(2, layer)));
+    }
+}
+
+#[test]
+fn test_feedback_requests_coarse_ancestors_first() {
+    let mut rm = ResourceManager::new();
+    let mut vt = create(&mut rm, 4);
+    let mut loader = TestLoader::default();
+    vt.update(&mut loader, 8).unwrap();
+
+    let fine = VirtualPage::new(0, 3, 1);
+    vt.process_feedback(&[VT_FEEDBACK_NONE, fine.pack(), fine.pack(), VirtualPage::new(0, 9, 9).pack()]);
+    assert_eq!(vt.requested_pages(), &[VirtualPage::new(1, 1, 0), fine]);
+
+    vt.update(&mut loader, 8).unwrap();
+    assert_eq!(vt.resident_count(), 3);
+    let fine_layer = vt.physical_layer(fine).unwrap();
+    let parent_layer = vt.physical_layer(fine.parent()).unwrap();
+    assert_eq!(vt.page_table_entry(fine), Some(entry(0, fine_layer)));
+    // Sibling falls back to the shared parent
+    assert_eq!(vt.page_table_entry(VirtualPage::new(0, 2, 0)), Some(entry(1, parent_layer)));
+}
+
+#[test]
+fn test_not_ready_and_upload_budget_keep_requests() {
+    let mut rm = ResourceManager::new();
+    let mut vt = create(&mut rm, 8);
+    let mut loader = TestLoader { not_ready: true, ..Default::default() };
+
+    assert_eq!(vt.update(&mut loader, 8).unwrap().pending, 1);
+    assert_eq!(vt.resident_count(), 0);
+
+    loader.not_ready = false;
+    vt.process_feedback(&[VirtualPage::new(0, 0, 0).pack(), VirtualPage::new(0, 3, 3).pack()]);
+    assert_eq!(vt.requested_pages().len(), 5);
+    let result = vt.update(&mut loader, 2).unwrap();
+    assert_eq!((result.uploaded, result.pending), (2, 3));
+    assert_eq!(loader.loaded[0], vt.root_page());
+    assert_eq!(vt.update(&mut loader, 8).unwrap().pending, 0);
+}
+
+#[test]
+fn test_least_recently_used_page_is_evicted() {
+    let mut rm = ResourceManager::new();
+    let mut vt = create(&mut rm, 2);
+    let mut loader = TestLoader::default();
+    vt.update(&mut loader, 8).unwrap();
+
+    let first = VirtualPage::new(2, 0, 0).pack();
+    let a = VirtualPage::new(1, 0, 0);
+    let b = VirtualPage::new(1, 1, 1);
+    vt.process_feedback(&[first, a.pack()]);
+    vt.update(&mut loader, 8).unwrap();
+    assert!(vt.physical_layer(a).is_some());
+
+    // `a` was not needed by the last feedback: its layer goes to `b`
+    vt.process_feedback(&[b.pack()]);
+    let result = vt.update(&mut loader, 8).unwrap();
+    assert_eq!(result, VirtualTextureUpdate { uploaded: 1, evicted: 1, pending: 0 });
+    assert_eq!(vt.physical_layer(a), None);
+    let root_layer = vt.physical_layer(vt.root_page()).unwrap();
+    assert_eq!(vt.page_table_entry(a), Some(entry(2, root_layer)));
+
+    // Pages needed by the current feedback and the root are never evicted
+    vt.process_feedback(&[a.pack(), b.pack()]);
+    assert_eq!(vt.update(&mut loader, 8).unwrap().pending, 1);
+    assert!(vt.physical_layer(b).is_some());
+}
+
+#[test]
+fn test_clear_evicts_everything() {
+    let mut rm = ResourceManager::new();
+    let mut vt = create(&mut rm, 4);
+    let mut loader = TestLoader::default();
+    vt.update(&mut loader, 8).unwrap();
+
+    vt.clear();
+    assert_eq!(vt.resident_count(), 0);
+    assert_eq!(vt.requested_pages(), &[vt.root_page()]);
+    vt.update(&mut TestLoader { not_ready: true, ..Default::default() }, 8).unwrap();
+    assert_eq!(vt.page_table_entry(vt.root_page()), Some(VT_PAGE_TABLE_EMPTY));
+}
+
+// ============================================================================
+// FEEDBACK READBACK
+// ============================================================================
+
+fn feedback_target(format: TextureFormat) -> ImageAccess {
+    let mut texture = MockTexture::new(8, 4, 1, TextureType::Tex2D, "feedback".to_string());
+    texture.info.format = format;
+    ImageAccess {
+        texture: Arc::new(texture),
+        access_type: AccessType::TransferRead,
+        previous_access_type: Some(AccessType::ColorAttachmentWrite),
+    }
+}
+
+#[test]
+#[serial]
+fn test_feedback_rejects_wrong_target_format() {
+    setup_engine();
+    let mut feedback = VirtualTextureFeedback::new(1).unwrap();
+    let mut cmd = MockCommandList::new();
+    let result = feedback.record(&mut cmd, &feedback_target(TextureFormat::R8G8B8A8_UNORM));
+    assert_eq!(result.unwrap_err().code(), crate::error::ErrorCode::Incompatible);
+}
+
+#[test]
+#[serial]
+fn test_feedback_returns_latest_readback() {
+    setup_engine();
+    let mut feedback = VirtualTextureFeedback::new(1).unwrap();
+    let mut cmd = MockCommandList::new();
+    feedback.record(&mut cmd, &feedback_target(VIRTUAL_TEXTURE_FEEDBACK_FORMAT)).unwrap();
+    feedback.record(&mut cmd, &feedback_target(VIRTUAL_TEXTURE_FEEDBACK_FORMAT)).unwrap();
+    assert_eq!(cmd.commands.len(), 2);
+    assert_eq!(feedback.pending_count(), 2);
+
+    assert_eq!(feedback.poll().unwrap(), None);
+    // Mock readback is zeroed: every texel requests page (0, 0, 0)
+    let values = feedback.poll().unwrap().unwrap();
+    assert_eq!(values.len(), 32);
+    assert_eq!(VirtualPage::unpack(values[0]), Some(VirtualPage::new(0, 0, 0)));
+    assert_eq!(feedback.pending_count(), 0);
+}
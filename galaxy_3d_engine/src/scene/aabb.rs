@@ -0,0 +1,79 @@
+/// Axis-aligned bounding box.
+///
+/// Pure math (glam only): part of the core built without the `renderer`
+/// feature, shared by culling code outside the renderer.
+
+use glam::{Mat4, Vec3};
+
+/// Axis-Aligned Bounding Box in local space
+///
+/// Used for frustum culling. Stored in local space and transformed
+/// by the world_matrix at culling time.
+#[derive(Debug, Clone, Copy)]
+pub struct AABB {
+    /// Minimum corner (x, y, z)
+    pub min: Vec3,
+    /// Maximum corner (x, y, z)
+    pub max: Vec3,
+}
+
+impl AABB {
+    /// Transform this local-space AABB by a matrix, returning a new AABB.
+    ///
+    /// Uses the Arvo method: projects each matrix axis onto the AABB extents
+    /// for an exact (tight) result without transforming all 8 corners.
+    pub fn transformed(&self, matrix: &Mat4) -> AABB {
+        let center = (self.min + self.max) * 0.5;
+        let extents = (self.max - self.min) * 0.5;
+
+        let new_center = matrix.transform_point3(center);
+
+        let abs_x = matrix.x_axis.truncate().abs();
+        let abs_y = matrix.y_axis.truncate().abs();
+        let abs_z = matrix.z_axis.truncate().abs();
+
+        let new_extents =
+            abs_x * extents.x +
+            abs_y * extents.y +
+            abs_z * extents.z;
+
+        AABB {
+            min: new_center - new_extents,
+            max: new_center + new_extents,
+        }
+    }
+
+    /// Check that both corners are finite (no NaN or infinity)
+    pub fn is_finite(&self) -> bool {
+        self.min.is_finite() && self.max.is_finite()
+    }
+
+    /// Check if this AABB contains another AABB entirely
+    pub fn contains(&self, other: &AABB) -> bool {
+        other.min.x >= self.min.x && other.max.x <= self.max.x &&
+        other.min.y >= self.min.y && other.max.y <= self.max.y &&
+        other.min.z >= self.min.z && other.max.z <= self.max.z
+    }
+
+    /// Check if this AABB intersects another AABB
+    pub fn intersects(&self, other: &AABB) -> bool {
+        self.min.x <= other.max.x && self.max.x >= other.min.x &&
+        self.min.y <= other.max.y && self.max.y >= other.min.y &&
+        self.min.z <= other.max.z && self.max.z >= other.min.z
+    }
+
+    /// Get the center of the AABB
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) * 0.5
+    }
+
+    /// Return the point inside (or on the surface of) this AABB closest
+    /// to the given point. Equivalent to clamping the point to the box.
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        Vec3::new(
+            point.x.clamp(self.min.x, self.max.x),
+            point.y.clamp(self.min.y, self.max.y),
+            point.z.clamp(self.min.z, self.max.z),
+        )
+    }
+}
@@ -0,0 +1,318 @@
+/// Billboards: textured quads facing the camera.
+///
+/// A Billboard is a position, a size and an atlas region. It bypasses the
+/// mesh / material / draw slot plumbing: `BillboardAction` packs the
+/// visible billboards of a scene into a per-instance vertex buffer every
+/// frame and draws them as one instanced quad (see `billboard_action.rs`
+/// for the shader interface).
+///
+/// `Billboard::corners()` is the CPU reference of the quad expansion the
+/// vertex shader must perform.
+
+use glam::{Vec2, Vec3, Vec4};
+use slotmap::new_key_type;
+use crate::error::Result;
+use crate::{engine_err, engine_not_found};
+use crate::graphics_device::{
+    BufferFormat, SamplerType, VertexAttribute, VertexBinding, VertexInputRate, VertexLayout,
+};
+use crate::resource::resource_manager::{ResourceManager, TextureKey};
+use crate::resource::material::{LayerRef, RegionRef, FULL_UV_RECT};
+use crate::resource::texture::Texture;
+use super::aabb::AABB;
+
+// ===== SLOT MAP KEY =====
+
+new_key_type! {
+    /// Stable key for a Billboard within a Scene.
+    pub struct BillboardKey;
+}
+
+// ===== INSTANCE LAYOUT =====
+
+/// Size in bytes of one billboard instance in the instance vertex buffer
+pub const BILLBOARD_INSTANCE_STRIDE: u32 = 80;
+
+/// Vertices per billboard (one triangle strip quad)
+pub const BILLBOARD_VERTEX_COUNT: u32 = 4;
+
+/// Vertex layout of the billboard instance buffer (one binding, per instance).
+///
+/// - location 0: position (vec3)
+/// - location 1: size (vec2)
+/// - location 2: lock axis (vec3, `AxisLocked` only)
+/// - location 3: UV rectangle (vec4, `offset.xy, scale.xy`)
+/// - location 4: color (vec4, linear RGBA)
+/// - location 5: mode, bindless texture index, sampler index, layer (uvec4)
+pub fn billboard_instance_layout() -> VertexLayout {
+    let attribute = |location: u32, format: BufferFormat, offset: u32| VertexAttribute {
+        location, binding: 0, format, offset,
+    };
+    VertexLayout {
+        bindings: vec![VertexBinding {
+            binding: 0,
+            stride: BILLBOARD_INSTANCE_STRIDE,
+            input_rate: VertexInputRate::Instance,
+        }],
+        attributes: vec![
+            attribute(0, BufferFormat::R32G32B32_SFLOAT, 0),
+            attribute(1, BufferFormat::R32G32_SFLOAT, 12),
+            attribute(2, BufferFormat::R32G32B32_SFLOAT, 20),
+            attribute(3, BufferFormat::R32G32B32A32_SFLOAT, 32),
+            attribute(4, BufferFormat::R32G32B32A32_SFLOAT, 48),
+            attribute(5, BufferFormat::R32G32B32A32_UINT, 64),
+        ],
+    }
+}
+
+// ===== BILLBOARD MODE =====
+
+/// How a billboard quad is oriented.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BillboardMode {
+    /// Parallel to the screen: the quad uses the camera right/up vectors.
+    /// All billboards share the same orientation (particles, labels).
+    ScreenAligned,
+    /// Faces the camera position (spherical billboard): the orientation
+    /// differs per billboard, so quads never look sheared at the screen edges.
+    CameraFacing,
+    /// Rotates around a world axis only, toward the camera (cylindrical
+    /// billboard: trees, grass, impostors). The axis is the quad's up.
+    AxisLocked(Vec3),
+}
+
+impl BillboardMode {
+    /// Mode id written to the instance buffer
+    pub fn id(&self) -> u32 {
+        match self {
+            BillboardMode::ScreenAligned => 0,
+            BillboardMode::CameraFacing => 1,
+            BillboardMode::AxisLocked(_) => 2,
+        }
+    }
+
+    /// Lock axis (zero for the other modes)
+    pub fn axis(&self) -> Vec3 {
+        match self {
+            BillboardMode::AxisLocked(axis) => *axis,
+            _ => Vec3::ZERO,
+        }
+    }
+}
+
+// ===== BILLBOARD DESC =====
+
+/// Descriptor for creating a Billboard.
+pub struct BillboardDesc {
+    /// World-space center of the quad
+    pub position: Vec3,
+    /// Width and height in world units
+    pub size: Vec2,
+    /// Orientation of the quad
+    pub mode: BillboardMode,
+    /// Color multiplied with the texture (linear RGBA)
+    pub color: Vec4,
+    /// Texture sampled by the quad
+    pub texture: TextureKey,
+    /// Texture layer (None = layer 0)
+    pub layer: Option<LayerRef>,
+    /// Atlas region of the layer (None = whole layer, requires `layer`)
+    pub region: Option<RegionRef>,
+    /// Sampler used for the texture
+    pub sampler_type: SamplerType,
+}
+
+// ===== BILLBOARD =====
+
+/// CPU-side billboard, stored in a SlotMap in the Scene.
+///
+/// The texture references are resolved at creation: the GPU sees only the
+/// bindless index, the layer and the UV rectangle of the region.
+pub struct Billboard {
+    position: Vec3,
+    size: Vec2,
+    mode: BillboardMode,
+    color: Vec4,
+    texture: TextureKey,
+    /// Index in the bindless texture table
+    bindless_index: u32,
+    /// Index in the bindless sampler table (= SamplerType as u32)
+    sampler_index: u32,
+    /// Resolved layer index (0 when none was given)
+    layer: u32,
+    /// UV rectangle of the region (`FULL_UV_RECT` without region)
+    uv_rect: [f32; 4],
+    visible: bool,
+}
+
+impl Billboard {
+    /// Create a Billboard from a descriptor, resolving its texture, layer
+    /// and region.
+    pub(crate) fn from_desc(desc: BillboardDesc, resource_manager: &ResourceManager) -> Result<Self> {
+        if !desc.position.is_finite() || !desc.size.is_finite() {
+            crate::engine_bail_warn!("galaxy3d::Billboard", "non-finite position or size");
+        }
+        let texture = resource_manager.texture(desc.texture)
+            .ok_or_else(|| engine_not_found!("galaxy3d::Billboard", "Texture", format!("{:?}", desc.texture)))?;
+        let (layer, uv_rect) = resolve_layer_region(
+            texture, desc.layer, desc.region, "galaxy3d::Billboard",
+        )?;
+
+        Ok(Self {
+            position: desc.position,
+            size: desc.size,
+            mode: normalized_mode(desc.mode),
+            color: desc.color,
+            texture: desc.texture,
+            bindless_index: texture.graphics_device_texture().bindless_index(),
+            sampler_index: desc.sampler_type as u32,
+            layer,
+            uv_rect,
+            visible: true,
+        })
+    }
+
+    // ===== ACCESSORS =====
+
+    /// Get the world-space center
+    pub fn position(&self) -> Vec3 { self.position }
+
+    /// Get the width and height in world units
+    pub fn size(&self) -> Vec2 { self.size }
+
+    /// Get the orientation mode
+    pub fn mode(&self) -> BillboardMode { self.mode }
+
+    /// Get the color (linear RGBA)
+    pub fn color(&self) -> Vec4 { self.color }
+
+    /// Get the texture key
+    pub fn texture(&self) -> TextureKey { self.texture }
+
+    /// Get the bindless texture index
+    pub fn bindless_index(&self) -> u32 { self.bindless_index }
+
+    /// Get the bindless sampler index
+    pub fn sampler_index(&self) -> u32 { self.sampler_index }
+
+    /// Get the resolved layer index
+    pub fn layer(&self) -> u32 { self.layer }
+
+    /// Get the UV rectangle `(offset.xy, scale.xy)` of the region
+    pub fn uv_rect(&self) -> [f32; 4] { self.uv_rect }
+
+    /// Whether the billboard is drawn
+    pub fn visible(&self) -> bool { self.visible }
+
+    /// World AABB enclosing the quad in any orientation
+    pub fn bounds(&self) -> AABB {
+        let radius = Vec3::splat(self.size.length() * 0.5);
+        AABB { min: self.position - radius, max: self.position + radius }
+    }
+
+    /// World-space corners of the quad seen from a camera, in triangle
+    /// strip order: bottom-left, bottom-right, top-left, top-right.
+    ///
+    /// Reference of the vertex shader expansion: `camera_right` and
+    /// `camera_up` are the (normalized) camera axes in world space.
+    pub fn corners(&self, camera_position: Vec3, camera_right: Vec3, camera_up: Vec3) -> [Vec3; 4] {
+        let (right, up) = match self.mode {
+            BillboardMode::ScreenAligned => (camera_right, camera_up),
+            BillboardMode::CameraFacing => {
+                let to_camera = (camera_position - self.position).normalize_or_zero();
+                let right = camera_up.cross(to_camera).try_normalize().unwrap_or(camera_right);
+                (right, to_camera.cross(right))
+            }
+            BillboardMode::AxisLocked(axis) => {
+                let to_camera = camera_position - self.position;
+                (axis.cross(to_camera).try_normalize().unwrap_or(camera_right), axis)
+            }
+        };
+        let half_right = right * (self.size.x * 0.5);
+        let half_up = up * (self.size.y * 0.5);
+        [
+            self.position - half_right - half_up,
+            self.position + half_right - half_up,
+            self.position - half_right + half_up,
+            self.position + half_right + half_up,
+        ]
+    }
+
+    /// Append the instance data (`billboard_instance_layout()`) to `out`
+    pub(crate) fn write_instance(&self, out: &mut Vec<u8>) {
+        let axis = self.mode.axis();
+        let floats = [
+            self.position.x, self.position.y, self.position.z,
+            self.size.x, self.size.y,
+            axis.x, axis.y, axis.z,
+            self.uv_rect[0], self.uv_rect[1], self.uv_rect[2], self.uv_rect[3],
+            self.color.x, self.color.y, self.color.z, self.color.w,
+        ];
+        out.extend_from_slice(bytemuck::cast_slice(&floats));
+        let ids = [self.mode.id(), self.bindless_index, self.sampler_index, self.layer];
+        out.extend_from_slice(bytemuck::cast_slice(&ids));
+    }
+
+    // ===== SETTERS (crate-internal, called by Scene setters) =====
+
+    pub(crate) fn set_position(&mut self, position: Vec3) { self.position = position; }
+    pub(crate) fn set_size(&mut self, size: Vec2) { self.size = size; }
+    pub(crate) fn set_mode(&mut self, mode: BillboardMode) { self.mode = normalized_mode(mode); }
+    pub(crate) fn set_color(&mut self, color: Vec4) { self.color = color; }
+    pub(crate) fn set_visible(&mut self, visible: bool) { self.visible = visible; }
+}
+
+/// Resolve a layer and an atlas region of a texture to the layer index
+/// (0 when none is given) and the UV rectangle `(offset.xy, scale.xy)` of
+/// the region (`FULL_UV_RECT` without region). `source` tags the errors.
+pub(crate) fn resolve_layer_region(
+    texture: &Texture,
+    layer: Option<LayerRef>,
+    region: Option<RegionRef>,
+    source: &str,
+) -> Result<(u32, [f32; 4])> {
+    let layer = match layer {
+        None => None,
+        Some(LayerRef::Index(i)) => {
+            if texture.layer(i).is_none() {
+                crate::engine_bail!(source, "layer index {} does not exist", i);
+            }
+            Some(i)
+        }
+        Some(LayerRef::Name(ref name)) => Some(texture.layer_index_by_name(name)
+            .ok_or_else(|| engine_err!(source, "layer '{}' not found", name))?),
+    };
+
+    let uv_rect = match region {
+        None => FULL_UV_RECT,
+        Some(region_ref) => {
+            let layer_idx = layer.ok_or_else(|| engine_err!(source,
+                "region specified without a layer"))?;
+            let texture_layer = texture.layer(layer_idx)
+                .ok_or_else(|| engine_err!(source, "layer {} not found", layer_idx))?;
+            let region = match region_ref {
+                RegionRef::Index(i) => texture_layer.region(i)
+                    .ok_or_else(|| engine_err!(source,
+                        "region index {} does not exist in layer {}", i, layer_idx))?,
+                RegionRef::Name(ref name) => texture_layer.region_by_name(name)
+                    .ok_or_else(|| engine_err!(source,
+                        "region '{}' not found in layer {}", name, layer_idx))?,
+            };
+            let info = texture.graphics_device_texture().info();
+            region.uv_rect(info.width, info.height)
+        }
+    };
+    Ok((layer.unwrap_or(0), uv_rect))
+}
+
+/// Normalize the lock axis of an `AxisLocked` mode
+fn normalized_mode(mode: BillboardMode) -> BillboardMode {
+    match mode {
+        BillboardMode::AxisLocked(axis) => BillboardMode::AxisLocked(axis.normalize_or_zero()),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+#[path = "billboard_tests.rs"]
+mod tests;
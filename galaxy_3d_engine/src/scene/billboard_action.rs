@@ -0,0 +1,161 @@
+/// Pass action rendering the billboards of a scene.
+///
+/// Shader interface of the billboard pipeline:
+/// - vertex input: `billboard_instance_layout()`, per instance; no vertex
+///   buffer, the shader builds the quad from `gl_VertexIndex` (0..4, see
+///   `Billboard::corners()` for the corner order and orientation)
+/// - push constants: vertex stage, offset 0,
+///   `{ mat4 viewProjection; vec4 cameraPosition; vec4 cameraRight; vec4 cameraUp; }`
+///   (112 bytes, `.w` unused)
+/// - set 0: bindless textures, sampled with the instance's texture and
+///   sampler indices, at the instance's layer and UV rectangle
+/// - topology: `PrimitiveTopology::TriangleStrip`
+///
+/// Billboards are depth-tested against the 3D geometry: run the pass after
+/// the opaque pass on the same depth target, with depth test on and depth
+/// write off. They are culled against the camera frustum and sorted back to
+/// front, so alpha blending between billboards is correct.
+///
+/// The instances are uploaded every frame into a host-visible vertex
+/// buffer, one per frame in flight, grown to the next power of two like
+/// `DebugDrawAction`.
+
+use std::sync::{Arc, Mutex};
+use crate::error::Result;
+use crate::engine::Engine;
+use crate::camera::Camera;
+use crate::graphics_device::{self, BufferDesc, BufferUsage, CommandList, ShaderStageFlags};
+use crate::render_graph::PassAction;
+use crate::resource::resource_manager::PassInfo;
+use super::billboard::{BillboardKey, BILLBOARD_INSTANCE_STRIDE, BILLBOARD_VERTEX_COUNT};
+use super::render_view::RenderView;
+use super::scene::Scene;
+
+/// Initial capacity of each instance buffer, in billboards
+const INITIAL_INSTANCE_CAPACITY: u32 = 256;
+
+/// Pass action drawing the billboards of a `Scene` seen from a `RenderView`.
+pub struct BillboardAction {
+    scene: Arc<Mutex<Scene>>,
+    render_view: Arc<Mutex<Option<RenderView>>>,
+    pipeline: Arc<dyn graphics_device::Pipeline>,
+    /// One instance buffer per frame in flight, with its capacity in billboards
+    instance_buffers: Vec<Option<(Arc<dyn graphics_device::Buffer>, u32)>>,
+    /// Index of the buffer used by the next frame
+    frame: usize,
+    /// Visible billboards with their view depth, reused across frames
+    order: Vec<(f32, BillboardKey)>,
+    /// Packed instance data, reused across frames
+    instance_data: Vec<u8>,
+}
+
+impl BillboardAction {
+    /// Create the action for a render graph with `frames_in_flight` command
+    /// lists. `pipeline` must follow the billboard shader interface (see
+    /// module docs). Instance buffers are created on first use.
+    pub fn new(
+        scene: Arc<Mutex<Scene>>,
+        render_view: Arc<Mutex<Option<RenderView>>>,
+        pipeline: Arc<dyn graphics_device::Pipeline>,
+        frames_in_flight: usize,
+    ) -> Result<Self> {
+        if frames_in_flight == 0 {
+            crate::engine_bail!("galaxy3d::BillboardAction", "frames_in_flight must be at least 1");
+        }
+        Ok(Self {
+            scene,
+            render_view,
+            pipeline,
+            instance_buffers: (0..frames_in_flight).map(|_| None).collect(),
+            frame: 0,
+            order: Vec::new(),
+            instance_data: Vec::new(),
+        })
+    }
+
+    /// Cull the visible billboards against the camera frustum, sort them
+    /// back to front and pack them into `instance_data`. Returns their count.
+    fn pack_instances(&mut self, scene: &Scene, camera: &Camera) -> u32 {
+        let world = camera.view_matrix().inverse();
+        let camera_pos = world.w_axis.truncate();
+        let camera_forward = -world.z_axis.truncate();
+
+        self.order.clear();
+        for (key, billboard) in scene.billboards() {
+            if billboard.visible() && camera.frustum().intersects_aabb(&billboard.bounds()) {
+                let depth = (billboard.position() - camera_pos).dot(camera_forward);
+                self.order.push((depth, key));
+            }
+        }
+        self.order.sort_unstable_by(|a, b| b.0.total_cmp(&a.0));
+
+        self.instance_data.clear();
+        for (_, key) in &self.order {
+            if let Some(billboard) = scene.billboard(*key) {
+                billboard.write_instance(&mut self.instance_data);
+            }
+        }
+        self.order.len() as u32
+    }
+
+    /// Return the instance buffer of the current frame, (re)created when
+    /// smaller than `instance_count`
+    fn frame_buffer(&mut self, instance_count: u32) -> Result<Arc<dyn graphics_device::Buffer>> {
+        let slot = &mut self.instance_buffers[self.frame];
+        if let Some((buffer, capacity)) = slot {
+            if *capacity >= instance_count {
+                return Ok(buffer.clone());
+            }
+        }
+        let capacity = instance_count.next_power_of_two().max(INITIAL_INSTANCE_CAPACITY);
+        let gd_arc = Engine::graphics_device("main")?;
+        let buffer = gd_arc.lock().unwrap().create_buffer(BufferDesc {
+            size: capacity as u64 * BILLBOARD_INSTANCE_STRIDE as u64,
+            usage: BufferUsage::Vertex,
+            debug_name: Some("Billboard instances".to_string()),
+        })?;
+        *slot = Some((buffer.clone(), capacity));
+        Ok(buffer)
+    }
+}
+
+impl PassAction for BillboardAction {
+    fn execute(&mut self, cmd: &mut dyn CommandList, _pass_info: &PassInfo) -> Result<()> {
+        let render_view = self.render_view.clone();
+        let view = render_view.lock().unwrap();
+        let Some(ref view) = *view else {
+            return Ok(());
+        };
+        let camera = view.camera();
+        let instance_count = {
+            let scene = self.scene.clone();
+            let scene = scene.lock().unwrap();
+            self.pack_instances(&scene, camera)
+        };
+        if instance_count == 0 {
+            return Ok(());
+        }
+
+        let buffer = self.frame_buffer(instance_count)?;
+        self.frame = (self.frame + 1) % self.instance_buffers.len();
+        buffer.update(0, &self.instance_data)?;
+
+        let world = camera.view_matrix().inverse();
+        let mut push_constants = camera.view_projection_matrix().to_cols_array().to_vec();
+        push_constants.extend_from_slice(&world.w_axis.to_array());
+        push_constants.extend_from_slice(&world.x_axis.truncate().normalize_or_zero().extend(0.0).to_array());
+        push_constants.extend_from_slice(&world.y_axis.truncate().normalize_or_zero().extend(0.0).to_array());
+
+        cmd.set_viewport(*camera.viewport())?;
+        cmd.set_scissor(camera.effective_scissor())?;
+        cmd.bind_pipeline(&self.pipeline)?;
+        cmd.bind_textures()?;
+        cmd.push_constants(ShaderStageFlags::VERTEX, 0, bytemuck::cast_slice(&push_constants))?;
+        cmd.bind_vertex_buffer(&buffer, 0)?;
+        cmd.draw_instanced(BILLBOARD_VERTEX_COUNT, 0, instance_count, 0)
+    }
+}
+
+#[cfg(test)]
+#[path = "billboard_action_tests.rs"]
+mod tests;
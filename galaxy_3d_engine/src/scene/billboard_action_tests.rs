@@ -0,0 +1,149 @@
+use super::*;
+use glam::{Mat4, Vec2, Vec3, Vec4};
+use serial_test::serial;
+use crate::camera::Frustum;
+use crate::graphics_device::{SampleCount, SamplerType, TextureFormat, Viewport};
+use crate::graphics_device::mock_graphics_device::{MockCommandList, MockPipeline};
+use crate::render_graph::test_helpers::setup_engine;
+use crate::resource::resource_manager::ResourceManager;
+use crate::scene::billboard::{BillboardDesc, BillboardMode};
+use crate::scene::scene_test_helpers::create_atlas_texture;
+
+fn make_pass_info() -> PassInfo {
+    PassInfo::new(vec![TextureFormat::R8G8B8A8_UNORM], None, SampleCount::S1)
+}
+
+/// Perspective camera at (0, 0, 10) looking down -Z
+fn make_camera() -> Camera {
+    let view = Mat4::look_at_rh(Vec3::new(0.0, 0.0, 10.0), Vec3::ZERO, Vec3::Y);
+    let projection = Mat4::perspective_rh(std::f32::consts::FRAC_PI_2, 1.0, 0.1, 100.0);
+    let viewport = Viewport { x: 0.0, y: 0.0, width: 512.0, height: 512.0, min_depth: 0.0, max_depth: 1.0 };
+    Camera::new(view, projection, Frustum::from_view_projection(&(projection * view)), viewport)
+}
+
+struct Fixture {
+    rm: ResourceManager,
+    scene: Arc<Mutex<Scene>>,
+    render_view: Arc<Mutex<Option<RenderView>>>,
+    action: BillboardAction,
+}
+
+fn make_fixture() -> Fixture {
+    setup_engine();
+    let scene = Arc::new(Mutex::new(Scene::new()));
+    let render_view = Arc::new(Mutex::new(Some(RenderView::new(make_camera(), 0))));
+    let pipeline: Arc<dyn graphics_device::Pipeline> =
+        Arc::new(MockPipeline::new("billboard_pipeline".to_string()));
+    let action = BillboardAction::new(scene.clone(), render_view.clone(), pipeline, 2).unwrap();
+    Fixture { rm: ResourceManager::new(), scene, render_view, action }
+}
+
+fn add_billboard(fixture: &mut Fixture, position: Vec3) -> BillboardKey {
+    let texture = fixture.rm.texture_key("atlas")
+        .unwrap_or_else(|| create_atlas_texture(&mut fixture.rm));
+    fixture.scene.lock().unwrap().create_billboard(BillboardDesc {
+        position,
+        size: Vec2::ONE,
+        mode: BillboardMode::CameraFacing,
+        color: Vec4::ONE,
+        texture,
+        layer: None,
+        region: None,
+        sampler_type: SamplerType::LinearClamp,
+    }, &fixture.rm).unwrap()
+}
+
+/// Z of each packed instance, in draw order
+fn packed_depths(action: &BillboardAction) -> Vec<f32> {
+    action.instance_data.chunks(BILLBOARD_INSTANCE_STRIDE as usize)
+        .map(|instance| bytemuck::cast_slice::<u8, f32>(&instance[..12])[2])
+        .collect()
+}
+
+#[test]
+fn test_new_rejects_zero_frames_in_flight() {
+    let scene = Arc::new(Mutex::new(Scene::new()));
+    let pipeline: Arc<dyn graphics_device::Pipeline> =
+        Arc::new(MockPipeline::new("billboard_pipeline".to_string()));
+    assert!(BillboardAction::new(scene, Arc::new(Mutex::new(None)), pipeline, 0).is_err());
+}
+
+#[test]
+#[serial]
+fn test_execute_without_billboards_or_view_records_nothing() {
+    let mut fixture = make_fixture();
+    let mut cmd = MockCommandList::new();
+    fixture.action.execute(&mut cmd, &make_pass_info()).unwrap();
+    assert!(cmd.commands.is_empty());
+
+    add_billboard(&mut fixture, Vec3::ZERO);
+    *fixture.render_view.lock().unwrap() = None;
+    fixture.action.execute(&mut cmd, &make_pass_info()).unwrap();
+    assert!(cmd.commands.is_empty());
+}
+
+#[test]
+#[serial]
+fn test_execute_draws_one_instanced_quad() {
+    let mut fixture = make_fixture();
+    add_billboard(&mut fixture, Vec3::ZERO);
+    add_billboard(&mut fixture, Vec3::new(1.0, 0.0, 0.0));
+    let mut cmd = MockCommandList::new();
+    fixture.action.execute(&mut cmd, &make_pass_info()).unwrap();
+    assert_eq!(cmd.commands, vec![
+        "set_viewport", "set_scissor", "bind_pipeline", "bind_textures",
+        "push_constants", "bind_vertex_buffer", "draw_instanced 2@0",
+    ]);
+}
+
+#[test]
+#[serial]
+fn test_billboards_sorted_back_to_front() {
+    let mut fixture = make_fixture();
+    add_billboard(&mut fixture, Vec3::new(0.0, 0.0, 5.0));
+    add_billboard(&mut fixture, Vec3::new(0.0, 0.0, -20.0));
+    add_billboard(&mut fixture, Vec3::new(0.5, 0.0, 0.0));
+    let mut cmd = MockCommandList::new();
+    fixture.action.execute(&mut cmd, &make_pass_info()).unwrap();
+    assert_eq!(packed_depths(&fixture.action), vec![-20.0, 0.0, 5.0]);
+}
+
+#[test]
+#[serial]
+fn test_hidden_and_culled_billboards_skipped() {
+    let mut fixture = make_fixture();
+    let hidden = add_billboard(&mut fixture, Vec3::new(0.0, 0.0, 1.0));
+    add_billboard(&mut fixture, Vec3::new(0.0, 0.0, 20.0)); // behind the camera
+    add_billboard(&mut fixture, Vec3::new(0.0, 0.0, -200.0)); // beyond the far plane
+    add_billboard(&mut fixture, Vec3::new(0.0, 0.0, -3.0));
+    fixture.scene.lock().unwrap().set_billboard_visible(hidden, false);
+
+    let mut cmd = MockCommandList::new();
+    fixture.action.execute(&mut cmd, &make_pass_info()).unwrap();
+    assert_eq!(packed_depths(&fixture.action), vec![-3.0]);
+    assert_eq!(cmd.commands.last().unwrap(), "draw_instanced 1@0");
+}
+
+#[test]
+#[serial]
+fn test_instance_buffers_rotate_and_grow() {
+    let mut fixture = make_fixture();
+    add_billboard(&mut fixture, Vec3::ZERO);
+    let mut cmd = MockCommandList::new();
+
+    fixture.action.execute(&mut cmd, &make_pass_info()).unwrap();
+    fixture.action.execute(&mut cmd, &make_pass_info()).unwrap();
+    let first = fixture.action.instance_buffers[0].as_ref().unwrap().0.clone();
+    assert!(!Arc::ptr_eq(&first, &fixture.action.instance_buffers[1].as_ref().unwrap().0));
+
+    // Same size: buffer reused
+    fixture.action.execute(&mut cmd, &make_pass_info()).unwrap();
+    assert!(Arc::ptr_eq(&first, &fixture.action.instance_buffers[0].as_ref().unwrap().0));
+
+    // Outgrown: buffer recreated at the next power of two
+    for i in 0..INITIAL_INSTANCE_CAPACITY {
+        add_billboard(&mut fixture, Vec3::new(0.0, 0.0, -(i as f32) * 0.1));
+    }
+    fixture.action.execute(&mut cmd, &make_pass_info()).unwrap();
+    assert_eq!(fixture.action.instance_buffers[1].as_ref().unwrap().1, 512);
+}
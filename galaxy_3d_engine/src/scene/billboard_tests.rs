@@ -0,0 +1,168 @@
+use super::*;
+use crate::scene::scene_test_helpers::create_atlas_texture;
+
+fn desc(texture: TextureKey, region: Option<RegionRef>) -> BillboardDesc {
+    BillboardDesc {
+        position: Vec3::new(1.0, 2.0, 3.0),
+        size: Vec2::new(2.0, 4.0),
+        mode: BillboardMode::ScreenAligned,
+        color: Vec4::ONE,
+        texture,
+        layer: region.as_ref().map(|_| LayerRef::Name("sprites".to_string())),
+        region,
+        sampler_type: SamplerType::LinearClamp,
+    }
+}
+
+fn setup() -> (ResourceManager, TextureKey) {
+    let mut rm = ResourceManager::new();
+    let texture = create_atlas_texture(&mut rm);
+    (rm, texture)
+}
+
+// ============================================================================
+// Creation
+// ============================================================================
+
+#[test]
+fn test_from_desc_without_region_uses_whole_layer() {
+    let (rm, texture) = setup();
+    let billboard = Billboard::from_desc(desc(texture, None), &rm).unwrap();
+    assert_eq!(billboard.uv_rect(), FULL_UV_RECT);
+    assert_eq!(billboard.layer(), 0);
+    assert_eq!(billboard.sampler_index(), SamplerType::LinearClamp as u32);
+    assert_eq!(billboard.texture(), texture);
+    assert!(billboard.visible());
+}
+
+#[test]
+fn test_from_desc_resolves_region_by_name_and_index() {
+    let (rm, texture) = setup();
+    let by_name = Billboard::from_desc(desc(texture, Some(RegionRef::Name("spark".to_string()))), &rm).unwrap();
+    assert_eq!(by_name.uv_rect(), [0.25, 0.0, 0.25, 0.5]);
+    let by_index = Billboard::from_desc(desc(texture, Some(RegionRef::Index(0))), &rm).unwrap();
+    assert_eq!(by_index.uv_rect(), [0.0, 0.0, 0.25, 0.25]);
+}
+
+#[test]
+fn test_from_desc_rejects_bad_references() {
+    let (rm, texture) = setup();
+    assert!(Billboard::from_desc(desc(texture, Some(RegionRef::Index(9))), &rm).is_err());
+    assert!(Billboard::from_desc(desc(texture, Some(RegionRef::Name("fire".to_string()))), &rm).is_err());
+
+    let mut no_layer = desc(texture, Some(RegionRef::Index(0)));
+    no_layer.layer = None;
+    assert!(Billboard::from_desc(no_layer, &rm).is_err());
+
+    let mut bad_layer = desc(texture, None);
+    bad_layer.layer = Some(LayerRef::Index(3));
+    assert!(Billboard::from_desc(bad_layer, &rm).is_err());
+
+    assert!(Billboard::from_desc(desc(TextureKey::default(), None), &rm).is_err());
+}
+
+#[test]
+fn test_from_desc_rejects_non_finite_values() {
+    let (rm, texture) = setup();
+    let mut bad = desc(texture, None);
+    bad.position.x = f32::NAN;
+    assert!(Billboard::from_desc(bad, &rm).is_err());
+    let mut bad = desc(texture, None);
+    bad.size.y = f32::INFINITY;
+    assert!(Billboard::from_desc(bad, &rm).is_err());
+}
+
+#[test]
+fn test_axis_locked_axis_is_normalized() {
+    let (rm, texture) = setup();
+    let mut d = desc(texture, None);
+    d.mode = BillboardMode::AxisLocked(Vec3::new(0.0, 5.0, 0.0));
+    let mut billboard = Billboard::from_desc(d, &rm).unwrap();
+    assert_eq!(billboard.mode(), BillboardMode::AxisLocked(Vec3::Y));
+    billboard.set_mode(BillboardMode::AxisLocked(Vec3::new(3.0, 0.0, 0.0)));
+    assert_eq!(billboard.mode().axis(), Vec3::X);
+}
+
+// ============================================================================
+// Orientation
+// ============================================================================
+
+fn oriented(mode: BillboardMode, position: Vec3) -> Billboard {
+    let (rm, texture) = setup();
+    let mut d = desc(texture, None);
+    d.mode = mode;
+    d.position = position;
+    Billboard::from_desc(d, &rm).unwrap()
+}
+
+/// Unit normal of the quad built from its corners
+fn normal(corners: &[Vec3; 4]) -> Vec3 {
+    (corners[1] - corners[0]).cross(corners[2] - corners[0]).normalize()
+}
+
+#[test]
+fn test_screen_aligned_uses_camera_axes() {
+    let billboard = oriented(BillboardMode::ScreenAligned, Vec3::new(5.0, 0.0, 0.0));
+    let corners = billboard.corners(Vec3::new(0.0, 0.0, 10.0), Vec3::X, Vec3::Y);
+    assert_eq!(corners[0], Vec3::new(4.0, -2.0, 0.0));
+    assert_eq!(corners[3], Vec3::new(6.0, 2.0, 0.0));
+}
+
+#[test]
+fn test_camera_facing_points_at_camera() {
+    let position = Vec3::new(5.0, 0.0, 0.0);
+    let camera = Vec3::new(0.0, 0.0, 10.0);
+    let billboard = oriented(BillboardMode::CameraFacing, position);
+    let corners = billboard.corners(camera, Vec3::X, Vec3::Y);
+    let to_camera = (camera - position).normalize();
+    assert!(normal(&corners).abs_diff_eq(to_camera, 1e-5));
+    // Width and height are kept
+    assert!(((corners[1] - corners[0]).length() - 2.0).abs() < 1e-5);
+    assert!(((corners[2] - corners[0]).length() - 4.0).abs() < 1e-5);
+}
+
+#[test]
+fn test_axis_locked_keeps_axis_as_up() {
+    let position = Vec3::ZERO;
+    let camera = Vec3::new(10.0, 8.0, 0.0);
+    let billboard = oriented(BillboardMode::AxisLocked(Vec3::Y), position);
+    let corners = billboard.corners(camera, Vec3::X, Vec3::Y);
+    assert!((corners[2] - corners[0]).normalize().abs_diff_eq(Vec3::Y, 1e-5));
+    // Faces the camera in the horizontal plane only
+    assert!(normal(&corners).abs_diff_eq(Vec3::X, 1e-5));
+}
+
+#[test]
+fn test_bounds_enclose_every_orientation() {
+    let billboard = oriented(BillboardMode::CameraFacing, Vec3::ZERO);
+    let bounds = billboard.bounds();
+    for camera in [Vec3::X, Vec3::Y * 3.0 + Vec3::Z, Vec3::new(-2.0, 1.0, -5.0)] {
+        for corner in billboard.corners(camera, Vec3::X, Vec3::Y) {
+            assert!(corner.cmpge(bounds.min - 1e-5).all() && corner.cmple(bounds.max + 1e-5).all());
+        }
+    }
+}
+
+// ============================================================================
+// Instance data
+// ============================================================================
+
+#[test]
+fn test_write_instance_matches_layout() {
+    let billboard = oriented(BillboardMode::AxisLocked(Vec3::Z), Vec3::new(1.0, 2.0, 3.0));
+    let mut out = Vec::new();
+    billboard.write_instance(&mut out);
+    assert_eq!(out.len(), BILLBOARD_INSTANCE_STRIDE as usize);
+
+    let floats: &[f32] = bytemuck::cast_slice(&out[..64]);
+    assert_eq!(&floats[0..3], &[1.0, 2.0, 3.0]);
+    assert_eq!(&floats[3..5], &[2.0, 4.0]);
+    assert_eq!(&floats[5..8], &[0.0, 0.0, 1.0]);
+    assert_eq!(&floats[8..12], &FULL_UV_RECT);
+    let ids: &[u32] = bytemuck::cast_slice(&out[64..]);
+    assert_eq!(ids, &[2, billboard.bindless_index(), billboard.sampler_index(), 0]);
+
+    let layout = billboard_instance_layout();
+    assert_eq!(layout.bindings[0].input_rate, VertexInputRate::Instance);
+    assert_eq!(layout.attributes.last().unwrap().offset + 16, BILLBOARD_INSTANCE_STRIDE);
+}
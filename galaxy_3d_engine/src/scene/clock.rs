@@ -0,0 +1,268 @@
+/// Simulation clock, decoupled from the wall clock.
+///
+/// The application feeds the real frame time to `EngineClock::advance()`
+/// once per frame; everything time-driven reads the clock instead:
+///
+/// - `DefaultUpdater::update_frame` writes `time`, `deltaTime` and
+///   `frameIndex` into the frame uniform buffer (shader animation, UV
+///   scroll, particles drawn as billboards).
+/// - Material flipbooks: `ResourceManager::sync_material_animations(buffer,
+///   scene.clock().time())`.
+/// - Procedural content draws its random numbers from `clock.rng(stream)`,
+///   which depends only on the clock seed.
+///
+/// Debugging controls: `pause()` freezes simulation time while frames keep
+/// rendering, `step(n)` advances a paused clock by `n` fixed steps (one per
+/// frame), and `set_time_scale()` gives slow motion. `set_fixed_delta()`
+/// ignores the wall clock, so with a fixed seed a capture replays the same
+/// frames on every run.
+
+use crate::error::Result;
+use crate::engine_bail;
+
+/// Step of `step()` while paused (seconds)
+pub const DEFAULT_STEP_DELTA: f32 = 1.0 / 60.0;
+
+/// Longest wall-clock frame taken into account (seconds). Longer frames
+/// (breakpoint, window drag) are clamped so the simulation does not jump.
+pub const MAX_FRAME_DELTA: f32 = 0.25;
+
+/// Seed of a new clock
+pub const DEFAULT_CLOCK_SEED: u64 = 0;
+
+/// Simulation clock of a scene (see module docs).
+#[derive(Debug, Clone, PartialEq)]
+pub struct EngineClock {
+    /// Simulation time (seconds)
+    time: f64,
+    /// Simulation time advanced by the last `advance()`
+    delta: f32,
+    /// Number of `advance()` calls since creation or `reset()`
+    frame_index: u32,
+    time_scale: f32,
+    paused: bool,
+    /// Steps left to run while paused
+    pending_steps: u32,
+    step_delta: f32,
+    /// Replaces the wall-clock delta when set (captures)
+    fixed_delta: Option<f32>,
+    seed: u64,
+}
+
+impl Default for EngineClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EngineClock {
+    /// Running clock at time 0, scale 1
+    pub fn new() -> Self {
+        Self {
+            time: 0.0,
+            delta: 0.0,
+            frame_index: 0,
+            time_scale: 1.0,
+            paused: false,
+            pending_steps: 0,
+            step_delta: DEFAULT_STEP_DELTA,
+            fixed_delta: None,
+            seed: DEFAULT_CLOCK_SEED,
+        }
+    }
+
+    /// Advance by one frame of `wall_delta` seconds of real time and
+    /// return the simulation delta:
+    ///
+    /// - the fixed delta if set, else `wall_delta` clamped to
+    ///   `[0, MAX_FRAME_DELTA]`, times the time scale;
+    /// - while paused, 0, or the step delta if a step is pending.
+    pub fn advance(&mut self, wall_delta: f32) -> f32 {
+        let delta = if self.paused {
+            if self.pending_steps > 0 {
+                self.pending_steps -= 1;
+                self.step_delta
+            } else {
+                0.0
+            }
+        } else {
+            let frame = match self.fixed_delta {
+                Some(fixed) => fixed,
+                None if wall_delta.is_finite() => wall_delta.clamp(0.0, MAX_FRAME_DELTA),
+                None => 0.0,
+            };
+            frame * self.time_scale
+        };
+        self.time += delta as f64;
+        self.delta = delta;
+        self.frame_index = self.frame_index.wrapping_add(1);
+        delta
+    }
+
+    /// Back to time 0 and frame 0. Settings and seed are kept.
+    pub fn reset(&mut self) {
+        self.time = 0.0;
+        self.delta = 0.0;
+        self.frame_index = 0;
+        self.pending_steps = 0;
+    }
+
+    // ===== READINGS =====
+
+    /// Simulation time in seconds (the frame uniform `time`)
+    pub fn time(&self) -> f32 {
+        self.time as f32
+    }
+
+    /// Simulation time in double precision, for long sessions
+    pub fn time_f64(&self) -> f64 {
+        self.time
+    }
+
+    /// Simulation delta of the last frame (the frame uniform `deltaTime`)
+    pub fn delta_time(&self) -> f32 {
+        self.delta
+    }
+
+    /// Frames advanced (the frame uniform `frameIndex`), counted while
+    /// paused too
+    pub fn frame_index(&self) -> u32 {
+        self.frame_index
+    }
+
+    // ===== CONTROLS =====
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Resume a paused clock, dropping the steps not run yet
+    pub fn resume(&mut self) {
+        self.paused = false;
+        self.pending_steps = 0;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Run `count` steps of `step_delta()` on the next frames of a paused
+    /// clock (one per frame). Ignored while running.
+    pub fn step(&mut self, count: u32) {
+        if self.paused {
+            self.pending_steps = self.pending_steps.saturating_add(count);
+        }
+    }
+
+    pub fn step_delta(&self) -> f32 {
+        self.step_delta
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if `delta` is not finite and positive.
+    pub fn set_step_delta(&mut self, delta: f32) -> Result<()> {
+        if !(delta.is_finite() && delta > 0.0) {
+            engine_bail!("galaxy3d::EngineClock", "Step delta must be positive, got {}", delta);
+        }
+        self.step_delta = delta;
+        Ok(())
+    }
+
+    pub fn time_scale(&self) -> f32 {
+        self.time_scale
+    }
+
+    /// Multiply the wall-clock delta (0.25 = slow motion, 0 = frozen).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `scale` is negative or not finite.
+    pub fn set_time_scale(&mut self, scale: f32) -> Result<()> {
+        if !(scale.is_finite() && scale >= 0.0) {
+            engine_bail!("galaxy3d::EngineClock", "Time scale must be >= 0, got {}", scale);
+        }
+        self.time_scale = scale;
+        Ok(())
+    }
+
+    pub fn fixed_delta(&self) -> Option<f32> {
+        self.fixed_delta
+    }
+
+    /// Advance by `delta` every frame, whatever the wall clock says (None
+    /// goes back to the wall clock). The time scale still applies.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `delta` is not finite and positive.
+    pub fn set_fixed_delta(&mut self, delta: Option<f32>) -> Result<()> {
+        if let Some(delta) = delta.filter(|d| !(d.is_finite() && *d > 0.0)) {
+            engine_bail!("galaxy3d::EngineClock", "Fixed delta must be positive, got {}", delta);
+        }
+        self.fixed_delta = delta;
+        Ok(())
+    }
+
+    // ===== SEEDS =====
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    /// Random generator of `stream` (one per system, e.g. an emitter id).
+    /// The sequence depends only on the clock seed and `stream`.
+    pub fn rng(&self, stream: u64) -> SeededRng {
+        let mut mixer = SeededRng::new(self.seed);
+        SeededRng::new(mixer.next_u64() ^ stream)
+    }
+}
+
+// ===== SEEDED RNG =====
+
+/// Small deterministic random generator (SplitMix64) for procedural
+/// content. Not for cryptography.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    /// SplitMix64 increment
+    const GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(Self::GAMMA);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// Uniform in [0, 1)
+    pub fn next_f32(&mut self) -> f32 {
+        // 24 random bits: every value is exactly representable
+        (self.next_u64() >> 40) as f32 / (1u32 << 24) as f32
+    }
+
+    /// Uniform in [min, max)
+    pub fn range_f32(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+}
+
+#[cfg(test)]
+#[path = "clock_tests.rs"]
+mod tests;
@@ -0,0 +1,129 @@
+use super::*;
+
+// ============================================================================
+// Advance
+// ============================================================================
+
+#[test]
+fn test_advance_follows_wall_clock_and_scale() {
+    let mut clock = EngineClock::new();
+    assert_eq!(clock.advance(0.1), 0.1);
+    clock.set_time_scale(0.5).unwrap();
+    assert_eq!(clock.advance(0.1), 0.05);
+
+    assert!((clock.time() - 0.15).abs() < 1e-6);
+    assert_eq!(clock.delta_time(), 0.05);
+    assert_eq!(clock.frame_index(), 2);
+}
+
+#[test]
+fn test_advance_clamps_long_and_invalid_frames() {
+    let mut clock = EngineClock::new();
+    assert_eq!(clock.advance(5.0), MAX_FRAME_DELTA);
+    assert_eq!(clock.advance(-1.0), 0.0);
+    assert_eq!(clock.advance(f32::NAN), 0.0);
+    assert_eq!(clock.time(), MAX_FRAME_DELTA);
+}
+
+#[test]
+fn test_fixed_delta_ignores_wall_clock() {
+    let mut clock = EngineClock::new();
+    clock.set_fixed_delta(Some(0.02)).unwrap();
+    assert_eq!(clock.advance(0.5), 0.02);
+    assert_eq!(clock.advance(0.0), 0.02);
+
+    clock.set_fixed_delta(None).unwrap();
+    assert_eq!(clock.advance(0.1), 0.1);
+}
+
+#[test]
+fn test_reset_keeps_settings() {
+    let mut clock = EngineClock::new();
+    clock.set_time_scale(2.0).unwrap();
+    clock.set_seed(7);
+    clock.advance(0.1);
+    clock.reset();
+
+    assert_eq!(clock.time(), 0.0);
+    assert_eq!(clock.frame_index(), 0);
+    assert_eq!(clock.time_scale(), 2.0);
+    assert_eq!(clock.seed(), 7);
+}
+
+// ============================================================================
+// Pause and step
+// ============================================================================
+
+#[test]
+fn test_pause_freezes_time_but_counts_frames() {
+    let mut clock = EngineClock::new();
+    clock.advance(0.1);
+    clock.pause();
+    assert_eq!(clock.advance(0.1), 0.0);
+    assert_eq!(clock.time(), 0.1);
+    assert_eq!(clock.frame_index(), 2);
+
+    clock.resume();
+    assert_eq!(clock.advance(0.1), 0.1);
+}
+
+#[test]
+fn test_step_runs_one_step_per_frame_while_paused() {
+    let mut clock = EngineClock::new();
+    clock.set_step_delta(0.5).unwrap();
+
+    // Ignored while running
+    clock.step(1);
+    assert_eq!(clock.advance(0.1), 0.1);
+
+    clock.pause();
+    clock.step(2);
+    assert_eq!(clock.advance(0.1), 0.5);
+    assert_eq!(clock.advance(0.1), 0.5);
+    assert_eq!(clock.advance(0.1), 0.0);
+    assert!((clock.time() - 1.1).abs() < 1e-6);
+}
+
+#[test]
+fn test_invalid_settings_are_rejected() {
+    let mut clock = EngineClock::new();
+    assert!(clock.set_time_scale(-1.0).is_err());
+    assert!(clock.set_step_delta(0.0).is_err());
+    assert!(clock.set_fixed_delta(Some(f32::INFINITY)).is_err());
+    assert_eq!(clock, EngineClock::new());
+
+    assert!(clock.set_time_scale(0.0).is_ok());
+}
+
+// ============================================================================
+// Seeds
+// ============================================================================
+
+#[test]
+fn test_rng_depends_only_on_seed_and_stream() {
+    let mut a = EngineClock::new();
+    let mut b = EngineClock::new();
+    a.set_seed(42);
+    b.set_seed(42);
+    b.advance(1.0);
+
+    let seq = |clock: &EngineClock, stream| {
+        let mut rng = clock.rng(stream);
+        (0..4).map(|_| rng.next_u64()).collect::<Vec<_>>()
+    };
+    assert_eq!(seq(&a, 1), seq(&b, 1));
+    assert_ne!(seq(&a, 1), seq(&a, 2));
+    b.set_seed(43);
+    assert_ne!(seq(&a, 1), seq(&b, 1));
+}
+
+#[test]
+fn test_rng_float_ranges() {
+    let mut rng = SeededRng::new(1);
+    for _ in 0..1000 {
+        let unit = rng.next_f32();
+        assert!((0.0..1.0).contains(&unit));
+        let ranged = rng.range_f32(-2.0, 3.0);
+        assert!((-2.0..3.0).contains(&ranged));
+    }
+}
@@ -2,10 +2,12 @@
 ///
 /// A CameraCuller determines which RenderInstances are visible
 /// from a given camera. Implementations range from brute-force
-/// (return all) to spatial structures (Octree, BVH).
+/// (return all) to spatial structures (Octree, BVH). `OcclusionCuller`
+/// composes with any of them to also drop instances hidden behind the
+/// previous frame's depth.
 
-use glam::Vec3;
-use crate::camera::{Camera, Frustum, VisibleInstances, VisibleInstance};
+use glam::{Mat4, Vec3};
+use crate::camera::{Camera, DepthPyramid, Frustum, VisibleInstances, VisibleInstance};
 use super::scene::Scene;
 use super::scene_index::SceneIndex;
 
@@ -64,6 +66,7 @@ impl CameraCuller for BruteForceCuller {
         _scene_index: Option<&dyn SceneIndex>,
         visible: &mut VisibleInstances,
     ) {
+        crate::profile_scope!("culling");
         visible.set_camera(camera.clone());
         visible.clear_instances();
 
@@ -80,7 +83,8 @@ impl CameraCuller for BruteForceCuller {
 ///
 /// With a SceneIndex: spatial query (O(log n) for Octree/BVH).
 /// Without: brute-force frustum test on all instances (O(n), still
-/// culls invisible objects unlike BruteForceCuller).
+/// culls invisible objects unlike BruteForceCuller). Instances whose world
+/// AABB is not finite (NaN/Inf transform) are skipped.
 pub struct FrustumCuller;
 
 impl FrustumCuller {
@@ -97,6 +101,7 @@ impl CameraCuller for FrustumCuller {
         scene_index: Option<&dyn SceneIndex>,
         visible: &mut VisibleInstances,
     ) {
+        crate::profile_scope!("culling");
         visible.set_camera(camera.clone());
         visible.clear_instances();
 
@@ -113,7 +118,7 @@ impl CameraCuller for FrustumCuller {
                 for (key, instance) in scene.render_instances() {
                     let world_aabb = instance.bounding_box()
                         .transformed(instance.world_matrix());
-                    if frustum.intersects_aabb(&world_aabb) {
+                    if world_aabb.is_finite() && frustum.intersects_aabb(&world_aabb) {
                         let inst_pos = instance.world_matrix().w_axis.truncate();
                         let depth = (inst_pos - camera_pos).dot(camera_forward);
                         visible.instances_mut().push(VisibleInstance { key, distance: depth });
@@ -124,6 +129,89 @@ impl CameraCuller for FrustumCuller {
     }
 }
 
+/// Hierarchical-Z occlusion culler — runs an inner culler (typically
+/// `FrustumCuller`), then removes the instances whose world AABB is hidden
+/// behind a `DepthPyramid`.
+///
+/// Each frame, the caller hands over the pyramid built from the previous
+/// frame's depth target with the view-projection matrix that frame was
+/// rendered with (`set_depth_pyramid`). Testing against last frame's depth
+/// is an approximation: an instance revealed by a moving occluder or a
+/// camera cut may be missing for a frame. Call `clear_depth_pyramid()` on
+/// camera cuts; without a pyramid only the inner culler runs.
+pub struct OcclusionCuller<C: CameraCuller = FrustumCuller> {
+    inner: C,
+    /// Pyramid and the view-projection matrix its depth was rendered with
+    occluder: Option<(DepthPyramid, Mat4)>,
+    /// Instances removed by the last `cull_into()`
+    occluded_count: usize,
+}
+
+impl<C: CameraCuller> OcclusionCuller<C> {
+    /// Wrap an inner culler
+    pub fn new(inner: C) -> Self {
+        Self { inner, occluder: None, occluded_count: 0 }
+    }
+
+    /// Set the depth pyramid tested by the next culls, with the
+    /// view-projection matrix its depth was rendered with
+    pub fn set_depth_pyramid(&mut self, pyramid: DepthPyramid, view_projection: Mat4) {
+        self.occluder = Some((pyramid, view_projection));
+    }
+
+    /// Drop the depth pyramid (e.g. on a camera cut): only the inner
+    /// culler runs until the next `set_depth_pyramid()`
+    pub fn clear_depth_pyramid(&mut self) {
+        self.occluder = None;
+    }
+
+    /// Current depth pyramid, if any
+    pub fn depth_pyramid(&self) -> Option<&DepthPyramid> {
+        self.occluder.as_ref().map(|(pyramid, _)| pyramid)
+    }
+
+    /// Number of instances removed by occlusion in the last `cull_into()`
+    pub fn occluded_count(&self) -> usize {
+        self.occluded_count
+    }
+
+    /// The wrapped culler
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// The wrapped culler (mutable)
+    pub fn inner_mut(&mut self) -> &mut C {
+        &mut self.inner
+    }
+}
+
+impl<C: CameraCuller> CameraCuller for OcclusionCuller<C> {
+    fn cull_into(
+        &mut self,
+        scene: &Scene,
+        camera: &Camera,
+        scene_index: Option<&dyn SceneIndex>,
+        visible: &mut VisibleInstances,
+    ) {
+        self.inner.cull_into(scene, camera, scene_index, visible);
+        self.occluded_count = 0;
+        let Some((pyramid, view_projection)) = &self.occluder else {
+            return;
+        };
+
+        crate::profile_scope!("occlusion_culling");
+        let before = visible.visible_count();
+        visible.instances_mut().retain(|vi| {
+            scene.render_instance(vi.key).is_none_or(|instance| {
+                let world_aabb = instance.bounding_box().transformed(instance.world_matrix());
+                !(world_aabb.is_finite() && pyramid.is_occluded(&world_aabb, view_projection))
+            })
+        });
+        self.occluded_count = before - visible.visible_count();
+    }
+}
+
 #[cfg(test)]
 #[path = "culler_tests.rs"]
 mod tests;
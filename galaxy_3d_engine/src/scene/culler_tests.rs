@@ -1,5 +1,5 @@
 use super::*;
-use crate::camera::VisibleInstances;
+use crate::camera::{DepthPyramid, VisibleInstances};
 use crate::scene::scene::Scene;
 use crate::scene::scene_test_helpers::{setup_resources, create_test_aabb, create_test_camera};
 use glam::{Mat4, Vec3};
@@ -109,6 +109,20 @@ fn test_frustum_cull_into_no_scene_index() {
     assert!(!visible.instances().is_empty());
 }
 
+#[test]
+fn test_frustum_cull_no_scene_index_skips_nan_transform() {
+    let mut culler = FrustumCuller::new();
+    let (mut scene, _rm) = build_scene_with_n_instances(1);
+    let key = scene.render_instance_keys().next().unwrap();
+    // Bypasses Scene::set_world_matrix validation
+    scene.render_instance_mut(key).unwrap()
+        .set_world_matrix(Mat4::from_translation(Vec3::new(f32::NAN, 0.0, 0.0)));
+
+    let mut visible = VisibleInstances::new_empty();
+    culler.cull_into(&scene, &create_test_camera(), None, &mut visible);
+    assert!(visible.instances().is_empty());
+}
+
 #[test]
 fn test_frustum_cull_clears_visible_instances() {
     let mut culler = FrustumCuller::new();
@@ -131,3 +145,66 @@ fn test_frustum_cull_snapshots_camera() {
     culler.cull_into(&scene, &camera, None, &mut visible);
     assert_eq!(visible.camera().viewport().width, 1920.0);
 }
+
+// ============================================================================
+// OcclusionCuller
+// ============================================================================
+
+/// Depth pyramid filled with the near-plane depth: hides every instance
+/// whose projected depth is positive
+fn near_wall_pyramid() -> DepthPyramid {
+    DepthPyramid::from_depth(8, 8, &[0.0; 64]).unwrap()
+}
+
+/// Occluder matrix pushing the test AABB (±1 at the origin) to depth [1, 3]
+fn pushed_back_view_projection() -> Mat4 {
+    Mat4::from_translation(Vec3::new(0.0, 0.0, 2.0))
+}
+
+#[test]
+fn test_occlusion_cull_without_pyramid_matches_inner_culler() {
+    let mut culler = OcclusionCuller::new(FrustumCuller::new());
+    let (scene, _rm) = build_scene_with_n_instances(1);
+    let mut visible = VisibleInstances::new_empty();
+    culler.cull_into(&scene, &create_test_camera(), None, &mut visible);
+    assert_eq!(visible.visible_count(), 1);
+    assert_eq!(culler.occluded_count(), 0);
+    assert!(culler.depth_pyramid().is_none());
+}
+
+#[test]
+fn test_occlusion_cull_removes_hidden_instances() {
+    let mut culler = OcclusionCuller::new(FrustumCuller::new());
+    let (scene, _rm) = build_scene_with_n_instances(1);
+    culler.set_depth_pyramid(near_wall_pyramid(), pushed_back_view_projection());
+
+    let mut visible = VisibleInstances::new_empty();
+    culler.cull_into(&scene, &create_test_camera(), None, &mut visible);
+    assert!(visible.is_empty());
+    assert_eq!(culler.occluded_count(), 1);
+}
+
+#[test]
+fn test_occlusion_cull_keeps_instances_in_front_of_depth() {
+    let mut culler = OcclusionCuller::new(BruteForceCuller::new());
+    let (scene, _rm) = build_scene_with_n_instances(1);
+    let far_depth = DepthPyramid::from_depth(8, 8, &[1.0; 64]).unwrap();
+    culler.set_depth_pyramid(far_depth, Mat4::IDENTITY);
+
+    let mut visible = VisibleInstances::new_empty();
+    culler.cull_into(&scene, &create_test_camera(), None, &mut visible);
+    assert_eq!(visible.visible_count(), 1);
+    assert_eq!(culler.occluded_count(), 0);
+}
+
+#[test]
+fn test_occlusion_cull_clear_pyramid_restores_inner_result() {
+    let mut culler = OcclusionCuller::new(FrustumCuller::new());
+    let (scene, _rm) = build_scene_with_n_instances(1);
+    culler.set_depth_pyramid(near_wall_pyramid(), pushed_back_view_projection());
+    culler.clear_depth_pyramid();
+
+    let mut visible = VisibleInstances::new_empty();
+    culler.cull_into(&scene, &create_test_camera(), None, &mut visible);
+    assert_eq!(visible.visible_count(), 1);
+}
@@ -6,16 +6,34 @@
 use std::sync::Arc;
 use crate::error::Result;
 use crate::engine::Engine;
-use crate::graphics_device::{CommandList, BindingGroup, ShaderStageFlags};
-use crate::resource::resource_manager::PassInfo;
+use crate::graphics_device::{
+    self, CommandList, BindingGroup, ShaderStageFlags, ColorBlendState,
+    CompareOp, DynamicRenderState, PrimitiveTopology, VertexLayout, VertexBinding,
+    VertexAttribute, VertexInputRate, BufferFormat, BufferDesc, BufferUsage, EngineFeatures,
+    DebugViewMode,
+};
+use crate::engine_bail;
+use crate::resource::resource_manager::{
+    ResourceManager, PassInfo, PipelineKey, ShaderKey, MaterialKey, GeometryKey,
+};
+use crate::resource::{RenderQueueClass, MORPH_TARGET_BUFFER_BINDING};
 use super::render_view::RenderView;
 use super::scene::Scene;
-use super::render_queue::{RenderQueue, DrawCall, build_sort_key};
+use super::render_queue::{RenderQueue, DrawCall, build_sort_key, build_transparent_sort_key};
+use super::lod::encode_debug_lod;
 
 /// Default preallocated capacity for the internal RenderQueue.
 /// Sized to cover typical scenes without any per-frame reallocation.
 const DEFAULT_DRAW_CALL_CAPACITY: usize = 4096;
 
+/// Vertex input binding of the per-instance buffer when instancing is on.
+pub const INSTANCE_BUFFER_BINDING: u32 = 1;
+/// Vertex attribute location of the per-instance draw slot (`uint`) when
+/// instancing is on.
+pub const INSTANCE_DRAW_SLOT_LOCATION: u32 = 15;
+/// Size of one instance buffer entry (a u32 draw slot)
+const INSTANCE_STRIDE: u32 = std::mem::size_of::<u32>() as u32;
+
 /// Strategy for drawing visible submeshes.
 ///
 /// Called within an active render pass. The Drawer issues draw commands
@@ -44,15 +62,91 @@ pub trait Drawer: Send + Sync {
     ) -> Result<()>;
 }
 
+/// ForwardDrawer options.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ForwardDrawerConfig {
+    /// Draw opaque geometry depth-only first, then shade it with depth test
+    /// `Equal` and depth write off, so each pixel is shaded once.
+    ///
+    /// Applies to non-transparent passes without blending whose render state
+    /// has depth test and depth write on; other passes are drawn normally after the
+    /// pre-pass. The depth-only variant keeps the material's shaders (color
+    /// writes masked) so its pipeline layout matches the shading pipeline
+    /// and alpha-tested materials still discard in the pre-pass. The vertex
+    /// shader must produce bit-identical positions in both pipelines
+    /// (`invariant gl_Position`).
+    pub depth_prepass: bool,
+    /// Merge consecutive draw calls of the same submesh (same pipeline,
+    /// geometry range and render state) into one instanced draw.
+    ///
+    /// The draw slot of every instance is written to an instance buffer bound
+    /// at `INSTANCE_BUFFER_BINDING` (instance rate); the shaders read it from
+    /// a `uint` attribute at `INSTANCE_DRAW_SLOT_LOCATION` and fetch the
+    /// transforms from the per-instance buffer as usual. Every pipeline
+    /// resolved by this drawer gets that extra binding, so all shaders drawn
+    /// with it must declare the attribute. Transparent draws keep their
+    /// back-to-front order and are drawn one instance at a time.
+    ///
+    /// Either way, the draw slot of a draw that cross-fades between LODs
+    /// holds its `LodFade` in its upper bits (`LodFade::encode`).
+    pub instancing: bool,
+    /// The pass has a motion-vector color attachment after the scene color
+    /// (added by `TemporalAa::build`).
+    ///
+    /// Every pipeline resolved by this drawer gets
+    /// `EngineFeatures::MOTION_VECTORS`, whatever the material pass
+    /// declares, so the same shaders only write motion in this pass. The
+    /// material color blend applies to the motion target too: blended
+    /// shaders write the motion with alpha 0 to keep the opaque motion
+    /// behind them. The depth pre-pass writes no color, the shading pass
+    /// writes the motion.
+    pub motion_vectors: bool,
+    /// Simplified shading profile (overhead maps, thumbnails, distant
+    /// reflections).
+    ///
+    /// Every pipeline resolved by this drawer gets `EngineFeatures::UNLIT`,
+    /// whatever the material pass declares, and none of the global features
+    /// (shadows, fog, IBL). Shaders honoring the flag output their base
+    /// color and emission (the standard PBR shaders do).
+    pub unlit: bool,
+}
+
+/// A run of consecutive sorted draw calls drawn as one instanced draw
+#[derive(Debug, Clone, Copy)]
+struct InstanceRun {
+    first_instance: u32,
+    count: u32,
+}
+
 /// Forward drawer — sorts visible submeshes by (signature, pipeline, geometry,
-/// distance), then emits draw calls with state-tracked rebinds so identical
+/// render state), then emits draw calls with state-tracked rebinds so identical
 /// pipelines and geometries are not rebound back-to-back.
 ///
-/// The internal `RenderQueue` is preallocated and reused frame-to-frame; no
+/// Draw calls are split by the material pass `RenderQueueClass` and emitted
+/// queue after queue: opaque, alpha-tested, then transparent sorted
+/// back-to-front by view depth so blending composites correctly.
+///
+/// The internal `RenderQueue`s are preallocated and reused frame-to-frame; no
 /// allocation happens during a normal draw (unless the queue grows past its
 /// initial capacity, in which case it grows once and stays at the new size).
 pub struct ForwardDrawer {
     queue: RenderQueue,
+    alpha_test_queue: RenderQueue,
+    transparent_queue: RenderQueue,
+    /// Depth pre-pass draw calls (empty unless `config.depth_prepass`)
+    prepass_queue: RenderQueue,
+    config: ForwardDrawerConfig,
+    /// Per-frame draw slots uploaded to the instance buffer (instancing only)
+    instance_slots: Vec<u32>,
+    /// Instance runs of each queue, in emission order (instancing only)
+    instance_runs: [Vec<InstanceRun>; 4],
+    instance_buffer: Option<Arc<dyn graphics_device::Buffer>>,
+    /// Capacity of `instance_buffer`, in instances
+    instance_capacity: usize,
+    /// Outgrown instance buffers. The GPU may still read them for frames in
+    /// flight, so they live as long as the drawer (growth is geometric, this
+    /// at most doubles the footprint).
+    retired_instance_buffers: Vec<Arc<dyn graphics_device::Buffer>>,
 }
 
 impl ForwardDrawer {
@@ -63,7 +157,144 @@ impl ForwardDrawer {
 
     /// Create a ForwardDrawer with a specific preallocated capacity.
     pub fn with_capacity(capacity: usize) -> Self {
-        Self { queue: RenderQueue::with_capacity(capacity) }
+        Self::with_config(capacity, ForwardDrawerConfig::default())
+    }
+
+    /// Create a ForwardDrawer with a specific capacity and options.
+    /// The pre-pass queue and instancing buffers are only preallocated when
+    /// the matching option is on.
+    pub fn with_config(capacity: usize, config: ForwardDrawerConfig) -> Self {
+        let prepass_capacity = if config.depth_prepass { capacity } else { 0 };
+        let instance_capacity = if config.instancing { capacity } else { 0 };
+        Self {
+            queue: RenderQueue::with_capacity(capacity),
+            alpha_test_queue: RenderQueue::with_capacity(capacity),
+            transparent_queue: RenderQueue::with_capacity(capacity),
+            prepass_queue: RenderQueue::with_capacity(prepass_capacity),
+            config,
+            instance_slots: Vec::with_capacity(instance_capacity),
+            instance_runs: Default::default(),
+            instance_buffer: None,
+            instance_capacity: 0,
+            retired_instance_buffers: Vec::new(),
+        }
+    }
+
+    /// Current options
+    pub fn config(&self) -> ForwardDrawerConfig {
+        self.config
+    }
+
+    /// Replace the options (takes effect on the next draw).
+    ///
+    /// `instancing`, `motion_vectors` and `unlit` change every pipeline the
+    /// drawer resolves (vertex layout, engine features), which the per-pass
+    /// pipeline cache does not track: they can only be chosen at
+    /// construction.
+    pub fn set_config(&mut self, config: ForwardDrawerConfig) -> Result<()> {
+        if config.instancing != self.config.instancing {
+            crate::engine_bail_warn!("galaxy3d::ForwardDrawer",
+                "instancing can only be chosen at construction (with_config)");
+        }
+        if config.motion_vectors != self.config.motion_vectors {
+            crate::engine_bail_warn!("galaxy3d::ForwardDrawer",
+                "motion_vectors can only be chosen at construction (with_config)");
+        }
+        if config.unlit != self.config.unlit {
+            crate::engine_bail_warn!("galaxy3d::ForwardDrawer",
+                "unlit can only be chosen at construction (with_config)");
+        }
+        self.config = config;
+        Ok(())
+    }
+
+    /// Upload the draw slots of this frame, growing the instance buffer if
+    /// needed, and return it.
+    fn upload_instance_slots(&mut self) -> Result<Arc<dyn graphics_device::Buffer>> {
+        let count = self.instance_slots.len().max(1);
+        if self.instance_buffer.is_none() || count > self.instance_capacity {
+            let capacity = count.next_power_of_two().max(self.instance_slots.capacity());
+            let gd_arc = Engine::graphics_device("main")?;
+            let buffer = gd_arc.lock().unwrap().create_buffer(BufferDesc {
+                size: capacity as u64 * INSTANCE_STRIDE as u64,
+                usage: BufferUsage::Vertex,
+                debug_name: Some("ForwardDrawer instances".to_string()),
+            })?;
+            if let Some(old) = self.instance_buffer.replace(buffer) {
+                self.retired_instance_buffers.push(old);
+            }
+            self.instance_capacity = capacity;
+        }
+        let buffer = self.instance_buffer.as_ref().unwrap();
+        if !self.instance_slots.is_empty() {
+            buffer.update(0, bytemuck::cast_slice(&self.instance_slots))?;
+        }
+        Ok(Arc::clone(buffer))
+    }
+}
+
+/// Geometry vertex layout plus the per-instance draw slot binding.
+fn with_instance_binding(layout: &VertexLayout) -> Result<VertexLayout> {
+    if layout.bindings.iter().any(|b| b.binding == INSTANCE_BUFFER_BINDING)
+        || layout.attributes.iter().any(|a| a.location == INSTANCE_DRAW_SLOT_LOCATION)
+    {
+        engine_bail!("galaxy3d::ForwardDrawer",
+            "Instancing: geometry vertex layout already uses binding {} or location {}",
+            INSTANCE_BUFFER_BINDING, INSTANCE_DRAW_SLOT_LOCATION);
+    }
+    let mut layout = layout.clone();
+    layout.bindings.push(VertexBinding {
+        binding: INSTANCE_BUFFER_BINDING,
+        stride: INSTANCE_STRIDE,
+        input_rate: VertexInputRate::Instance,
+    });
+    layout.attributes.push(VertexAttribute {
+        location: INSTANCE_DRAW_SLOT_LOCATION,
+        binding: INSTANCE_BUFFER_BINDING,
+        format: BufferFormat::R32_UINT,
+        offset: 0,
+    });
+    Ok(layout)
+}
+
+/// Whether two sorted draw calls can share one instanced draw
+fn same_instance_group(a: &DrawCall, b: &DrawCall) -> bool {
+    a.pipeline_key == b.pipeline_key
+        && a.geometry_key == b.geometry_key
+        && a.vertex_offset == b.vertex_offset
+        && a.vertex_count == b.vertex_count
+        && a.index_offset == b.index_offset
+        && a.index_count == b.index_count
+        && a.render_state_sig == b.render_state_sig
+}
+
+/// Append the draw slots of a sorted queue to `slots` and split the queue
+/// into instance runs (one run per draw call when `merge` is false).
+fn collect_instance_runs(
+    queue: &RenderQueue,
+    merge: bool,
+    slots: &mut Vec<u32>,
+    runs: &mut Vec<InstanceRun>,
+) {
+    runs.clear();
+    let mut prev: Option<&DrawCall> = None;
+    for dc in queue.iter_sorted() {
+        let first_instance = slots.len() as u32;
+        slots.push(dc.draw_slot);
+        match (prev, runs.last_mut()) {
+            (Some(p), Some(run)) if merge && same_instance_group(p, dc) => run.count += 1,
+            _ => runs.push(InstanceRun { first_instance, count: 1 }),
+        }
+        prev = Some(dc);
+    }
+}
+
+/// Render state used to shade geometry already laid down by the pre-pass.
+fn depth_equal_render_state(state: &DynamicRenderState) -> DynamicRenderState {
+    DynamicRenderState {
+        depth_write_enable: false,
+        depth_compare_op: CompareOp::Equal,
+        ..*state
     }
 }
 
@@ -77,6 +308,7 @@ impl Drawer for ForwardDrawer {
         binding_group: &Arc<dyn BindingGroup>,
         bind_textures: bool,
     ) -> Result<()> {
+        crate::profile_scope!("drawing");
         let pass_info_gen = pass_info.generation();
         let camera = view.camera();
 
@@ -87,11 +319,66 @@ impl Drawer for ForwardDrawer {
         // Acquire ResourceManager lock ONCE for the whole draw pass.
         let rm_arc = Engine::resource_manager()?;
         let mut rm = rm_arc.lock().unwrap();
+        let mut engine_features = if self.config.unlit {
+            EngineFeatures::UNLIT
+        } else {
+            rm.engine_features()
+        };
+        if self.config.motion_vectors {
+            engine_features = engine_features | EngineFeatures::MOTION_VECTORS;
+        }
+        // Debug views swap every pipeline for a variant (see DebugViewMode)
+        let debug_view = rm.debug_view();
+        engine_features = engine_features | debug_view.engine_features();
+        // Set by the configuration whatever the material pass declares
+        let drawer_features = EngineFeatures::MOTION_VECTORS | EngineFeatures::UNLIT
+            | EngineFeatures::DEBUG_VIEWS;
+        let features_gen = rm.engine_features_generation();
+        let instancing = self.config.instancing;
+
+        // Resolve (through the ResourceManager pipeline cache) the pipeline of
+        // a material pass, optionally overriding its color blend state.
+        let resolve = |
+            rm: &mut ResourceManager,
+            vertex_shader: ShaderKey,
+            material: MaterialKey,
+            mat_pass_idx: usize,
+            geometry_key: GeometryKey,
+            topology: PrimitiveTopology,
+            color_blend_override: Option<ColorBlendState>,
+        | -> Result<PipelineKey> {
+            let (frag_shader, color_blend, polygon_mode, pass_features, vertex_layout_arc) = {
+                let mat = rm.material(material).unwrap();
+                let pass = mat.pass(mat_pass_idx).unwrap();
+                let geo = rm.geometry(geometry_key).unwrap();
+                let vertex_layout = if instancing {
+                    Arc::new(with_instance_binding(geo.vertex_layout())?)
+                } else {
+                    Arc::clone(geo.vertex_layout())
+                };
+                let color_blend = color_blend_override
+                    .or_else(|| debug_view.color_blend())
+                    .unwrap_or(*pass.color_blend());
+                (pass.fragment_shader(), color_blend, debug_view.polygon_mode(pass.polygon_mode()),
+                 pass.engine_features(), vertex_layout)
+            };
+
+            let gd_arc = Engine::graphics_device("main")?;
+            let mut gd = gd_arc.lock().unwrap();
+            rm.resolve_pipeline(
+                vertex_shader, frag_shader, vertex_layout_arc, topology,
+                &color_blend, polygon_mode, pass_info,
+                engine_features & (pass_features | drawer_features), &mut *gd,
+            )
+        };
 
         // ===== PHASE 1: fill the queue =====
         // Resolve pipelines for stale/missing cache entries, collect per-submesh
         // draw data and its 64-bit sort key.
         self.queue.clear();
+        self.alpha_test_queue.clear();
+        self.transparent_queue.clear();
+        self.prepass_queue.clear();
 
         for item in view.items() {
             let key = item.key;
@@ -109,7 +396,7 @@ impl Drawer for ForwardDrawer {
                 vertex_shader, topology,
                 sm_pass_material, sm_pass_mat_pass_idx, draw_slot,
                 vertex_offset, vertex_count, index_offset, index_count,
-                cached_pipeline_key, mat_gen,
+                cached_pipeline_key, cached_prepass, mat_gen,
                 geometry_key, geo_sort_id,
             ) = {
                 let inst = match scene.render_instance(key) {
@@ -125,22 +412,29 @@ impl Drawer for ForwardDrawer {
                 let geo_sm_lod = match geo_sm.lod(lod_idx) { Some(l) => l, None => continue };
                 let mat = match rm.material(sm_pass.material()) { Some(m) => m, None => continue };
                 let mat_gen = mat.generation();
-                let cached = if sm_pass.is_pipeline_valid(pass_info_gen, mat_gen) {
-                    sm_pass.cached_pipeline_key()
+                let (cached, cached_prepass) = if sm_pass.is_pipeline_valid(pass_info_gen, mat_gen, features_gen) {
+                    (sm_pass.cached_pipeline_key(), sm_pass.cached_depth_prepass())
                 } else {
-                    None
+                    (None, None)
                 };
                 (
                     sm_pass.vertex_shader(),
                     geo_sm_lod.topology(),
                     sm_pass.material(),
                     sm_pass.material_pass_index(),
-                    render_sm.draw_slot(),
+                    // Cross-fading draws carry their fade in the upper bits
+                    // (the LOD index in the LOD coloring view)
+                    if debug_view == DebugViewMode::LodColoring {
+                        encode_debug_lod(render_sm.draw_slot(), item.lod_index)
+                    } else {
+                        item.lod_fade.encode(render_sm.draw_slot())
+                    },
                     geo_sm_lod.vertex_offset(),
                     geo_sm_lod.vertex_count(),
                     geo_sm_lod.index_offset(),
                     geo_sm_lod.index_count(),
                     cached,
+                    cached_prepass,
                     mat_gen,
                     geometry_key,
                     geo.sort_id(),
@@ -151,26 +445,15 @@ impl Drawer for ForwardDrawer {
             let pipeline_key = match cached_pipeline_key {
                 Some(k) => k,
                 None => {
-                    let (frag_shader, color_blend, polygon_mode, vertex_layout_arc) = {
-                        let mat = rm.material(sm_pass_material).unwrap();
-                        let pass = mat.pass(sm_pass_mat_pass_idx).unwrap();
-                        let geo = rm.geometry(geometry_key).unwrap();
-                        (pass.fragment_shader(), *pass.color_blend(), pass.polygon_mode(),
-                         Arc::clone(geo.vertex_layout()))
-                    };
-
-                    let gd_arc = Engine::graphics_device("main")?;
-                    let mut gd = gd_arc.lock().unwrap();
-                    let resolved = rm.resolve_pipeline(
-                        vertex_shader, frag_shader, vertex_layout_arc, topology,
-                        &color_blend, polygon_mode, pass_info, &mut *gd,
+                    let resolved = resolve(
+                        &mut rm, vertex_shader, sm_pass_material, sm_pass_mat_pass_idx,
+                        geometry_key, topology, None,
                     )?;
-                    drop(gd);
 
                     scene.render_instance_mut(key).unwrap()
                         .sub_mesh_mut(sm_idx).unwrap()
                         .pass_by_index_mut(pass_idx).unwrap()
-                        .set_cached_pipeline(resolved, pass_info_gen, mat_gen);
+                        .set_cached_pipeline(resolved, pass_info_gen, mat_gen, features_gen);
                     resolved
                 }
             };
@@ -190,17 +473,86 @@ impl Drawer for ForwardDrawer {
                 rm.material(sm_pass_material).unwrap_unchecked()
                     .pass(sm_pass_mat_pass_idx).unwrap_unchecked()
             };
-            let render_state = *mat_pass.render_state();
-            let render_state_sig = mat_pass.render_state_signature_id();
-
-            let sort_key = build_sort_key(
-                signature_id,
-                pipeline_sort_id,
-                geo_sort_id,
-                render_state_sig,
-            );
+            let mut render_state = *mat_pass.render_state();
+            let mut render_state_sig = mat_pass.render_state_signature_id();
+            let render_queue = mat_pass.render_queue();
+            let depth_prepass = self.config.depth_prepass
+                && debug_view == DebugViewMode::None
+                && render_queue != RenderQueueClass::Transparent
+                && !mat_pass.color_blend().blend_enable
+                && render_state.depth_test_enable
+                && render_state.depth_write_enable;
+
+            // Approximate texture usage: every texture slot of the material
+            // pass counts as drawn with, whatever the shader actually samples.
+            rm.record_material_texture_usage(sm_pass_material, sm_pass_mat_pass_idx);
+
+            // Overdraw counts every fragment: no depth test, no culling
+            if let Some(debug_state) = debug_view.render_state(&render_state) {
+                render_state = debug_state;
+                render_state_sig = rm.get_or_assign_material_render_state_signature_id(&render_state)?;
+            }
+
+            // Depth pre-pass: queue a depth-only draw with the material render
+            // state, then shade with depth test Equal and depth write off.
+            if depth_prepass {
+                let (depth_pipeline_key, equal_state_sig) = match cached_prepass {
+                    Some(c) => c,
+                    None => {
+                        let depth_key = resolve(
+                            &mut rm, vertex_shader, sm_pass_material, sm_pass_mat_pass_idx,
+                            geometry_key, topology, Some(ColorBlendState::depth_only()),
+                        )?;
+                        let equal_sig = rm.get_or_assign_material_render_state_signature_id(
+                            &depth_equal_render_state(&render_state),
+                        )?;
+                        scene.render_instance_mut(key).unwrap()
+                            .sub_mesh_mut(sm_idx).unwrap()
+                            .pass_by_index_mut(pass_idx).unwrap()
+                            .set_cached_depth_prepass(depth_key, equal_sig);
+                        (depth_key, equal_sig)
+                    }
+                };
+
+                // SAFETY: same rationale as the shading pipeline lookup above.
+                let depth_pipeline = unsafe { rm.pipeline(depth_pipeline_key).unwrap_unchecked() };
+                let depth_sort_key = build_sort_key(
+                    depth_pipeline.signature_id(),
+                    depth_pipeline.sort_id(),
+                    geo_sort_id,
+                    render_state_sig,
+                );
+                self.prepass_queue.push(
+                    DrawCall {
+                        pipeline_key: depth_pipeline_key,
+                        geometry_key,
+                        vertex_offset,
+                        vertex_count,
+                        index_offset,
+                        index_count,
+                        draw_slot,
+                        render_state,
+                        render_state_sig,
+                    },
+                    depth_sort_key,
+                );
 
-            self.queue.push(
+                render_state = depth_equal_render_state(&render_state);
+                render_state_sig = equal_state_sig;
+            }
+
+            let (queue, sort_key) = match render_queue {
+                RenderQueueClass::Opaque => (&mut self.queue, build_sort_key(
+                    signature_id, pipeline_sort_id, geo_sort_id, render_state_sig,
+                )),
+                RenderQueueClass::AlphaTest => (&mut self.alpha_test_queue, build_sort_key(
+                    signature_id, pipeline_sort_id, geo_sort_id, render_state_sig,
+                )),
+                RenderQueueClass::Transparent => (&mut self.transparent_queue, build_transparent_sort_key(
+                    item.distance, pipeline_sort_id, geo_sort_id, render_state_sig,
+                )),
+            };
+            queue.push(
                 DrawCall {
                     pipeline_key,
                     geometry_key,
@@ -217,97 +569,177 @@ impl Drawer for ForwardDrawer {
         }
 
         // ===== PHASE 2: sort =====
-        self.queue.sort();
-
-        // ===== PHASE 3: emit with state tracking =====
-        // Track the last bound pipeline/geometry/signature so identical values
-        // on consecutive draw calls don't re-issue Vulkan bind commands.
-        // `bg_set_index` is invariant for the pass, hoist out of the loop.
-        // `current_pc_flags` caches the push-constant stage flags of the
-        // currently bound pipeline so we don't re-query reflection per draw.
-        let bg_set_index = binding_group.set_index();
-        let mut last_pipeline_key = None;
-        let mut last_geometry_key = None;
-        let mut last_signature_id: Option<u16> = None;
-        let mut last_render_state_sig: Option<u16> = None;
-        let mut current_pc_flags: Option<ShaderStageFlags> = None;
-
-        for dc in self.queue.iter_sorted() {
-            // Pipeline rebind if different from previous draw call.
-            if last_pipeline_key != Some(dc.pipeline_key) {
-                // SAFETY: `dc.pipeline_key` was pushed into the queue during
-                // PHASE 1 after a successful lookup under the same `rm` lock
-                // that we still hold. Nothing can have removed the pipeline
-                // between PHASE 1 and PHASE 3.
-                let pipeline = unsafe { rm.pipeline(dc.pipeline_key).unwrap_unchecked() };
-                let gd_pipeline = pipeline.graphics_device_pipeline();
-                cmd.bind_pipeline(gd_pipeline)?;
-
-                // When the pipeline layout signature changes, Vulkan invalidates
-                // all previously bound descriptor sets. We must re-bind set 0
-                // (bindless) and set 1 (per-pass binding group) here.
-                let sig = pipeline.signature_id();
-                if last_signature_id != Some(sig) {
-                    if bind_textures {
-                        cmd.bind_textures()?;
-                    }
-                    cmd.bind_binding_group(
-                        gd_pipeline,
-                        bg_set_index,
-                        binding_group,
-                    )?;
-                    last_signature_id = Some(sig);
-                }
-
-                // Cache push-constant stage flags for this pipeline; the
-                // reflection is static per pipeline so we only query it on
-                // rebind, not on every drawcall.
-                current_pc_flags = gd_pipeline
-                    .reflection()
-                    .push_constants()
-                    .first()
-                    .map(|pc| pc.stage_flags);
+        // Depth pre-pass first, transparent last. With instancing, identical
+        // submeshes are made adjacent and split into instance runs.
+        if instancing {
+            self.prepass_queue.sort_grouping_draw_ranges();
+            self.queue.sort_grouping_draw_ranges();
+            self.alpha_test_queue.sort_grouping_draw_ranges();
+        } else {
+            self.prepass_queue.sort();
+            self.queue.sort();
+            self.alpha_test_queue.sort();
+        }
+        self.transparent_queue.sort();
+        let queues = [
+            (&self.prepass_queue, true),
+            (&self.queue, true),
+            (&self.alpha_test_queue, true),
+            (&self.transparent_queue, false),
+        ];
 
-                last_pipeline_key = Some(dc.pipeline_key);
+        if !instancing {
+            // ===== PHASE 3: emit =====
+            for (queue, _) in queues {
+                emit_queue(queue, None, &rm, cmd, binding_group, bind_textures)?;
             }
+            return Ok(());
+        }
 
-            // Geometry rebind if different from previous draw call.
-            if last_geometry_key != Some(dc.geometry_key) {
-                // SAFETY: same rationale as the pipeline lookup above —
-                // `dc.geometry_key` was validated under the still-held `rm` lock.
-                let geo = unsafe { rm.geometry(dc.geometry_key).unwrap_unchecked() };
-                cmd.bind_vertex_buffer(geo.vertex_buffer(), 0)?;
-                if let Some(ib) = geo.index_buffer() {
-                    cmd.bind_index_buffer(ib, 0, geo.index_type())?;
-                }
-                last_geometry_key = Some(dc.geometry_key);
-            }
+        self.instance_slots.clear();
+        for ((queue, merge), runs) in queues.into_iter().zip(self.instance_runs.iter_mut()) {
+            collect_instance_runs(queue, merge, &mut self.instance_slots, runs);
+        }
+        let instance_buffer = self.upload_instance_slots()?;
 
-            // Per-draw-call dynamic state (may differ between draw calls sharing
-            // the same pipeline, e.g. blend / cull overrides from the material).
-            // Skip re-emission when the render state signature is identical to
-            // the previous draw call — sort key groups identical signatures.
-            if last_render_state_sig != Some(dc.render_state_sig) {
-                cmd.set_dynamic_state(&dc.render_state)?;
-                last_render_state_sig = Some(dc.render_state_sig);
-            }
+        // ===== PHASE 3: emit instanced =====
+        // The instance binding is not touched by pipeline or geometry binds.
+        cmd.bind_vertex_buffer_at(INSTANCE_BUFFER_BINDING, &instance_buffer, 0)?;
+        let queues = [
+            &self.prepass_queue,
+            &self.queue,
+            &self.alpha_test_queue,
+            &self.transparent_queue,
+        ];
+        for (queue, runs) in queues.into_iter().zip(&self.instance_runs) {
+            emit_queue(queue, Some(runs), &rm, cmd, binding_group, bind_textures)?;
+        }
+        Ok(())
+    }
+}
+
+/// Emit the draw calls of a sorted queue with state tracking.
+///
+/// With `runs`, each run is drawn once, instanced, from its first draw call
+/// (the other draw calls of the run only contributed their draw slot).
+///
+/// Must be called under the same `rm` lock that was held while filling the
+/// queue: every pipeline and geometry key in it is assumed to be valid.
+fn emit_queue(
+    queue: &RenderQueue,
+    runs: Option<&[InstanceRun]>,
+    rm: &ResourceManager,
+    cmd: &mut dyn CommandList,
+    binding_group: &Arc<dyn BindingGroup>,
+    bind_textures: bool,
+) -> Result<()> {
+    // Track the last bound pipeline/geometry/signature so identical values
+    // on consecutive draw calls don't re-issue Vulkan bind commands.
+    // `bg_set_index` is invariant for the pass, hoist out of the loop.
+    // `current_pc_flags` caches the push-constant stage flags of the
+    // currently bound pipeline so we don't re-query reflection per draw.
+    let bg_set_index = binding_group.set_index();
+    let mut last_pipeline_key = None;
+    let mut last_geometry_key = None;
+    let mut last_signature_id: Option<u16> = None;
+    let mut last_render_state_sig: Option<u16> = None;
+    let mut current_pc_flags: Option<ShaderStageFlags> = None;
+    let mut runs = runs.map(|r| r.iter());
+    let mut skip = 0;
 
-            // Push constants (draw slot) and the draw command.
-            if let Some(flags) = current_pc_flags {
-                cmd.push_constants(
-                    flags, 0, bytemuck::bytes_of(&dc.draw_slot),
+    for dc in queue.iter_sorted() {
+        if skip > 0 {
+            skip -= 1;
+            continue;
+        }
+        let run = runs.as_mut().and_then(|r| r.next());
+        if let Some(run) = run {
+            skip = run.count - 1;
+        }
+
+        // Pipeline rebind if different from previous draw call.
+        if last_pipeline_key != Some(dc.pipeline_key) {
+            // SAFETY: `dc.pipeline_key` was pushed into the queue during
+            // PHASE 1 after a successful lookup under the same `rm` lock
+            // that the caller still holds. Nothing can have removed the
+            // pipeline between PHASE 1 and PHASE 3.
+            let pipeline = unsafe { rm.pipeline(dc.pipeline_key).unwrap_unchecked() };
+            let gd_pipeline = pipeline.graphics_device_pipeline();
+            cmd.bind_pipeline(gd_pipeline)?;
+
+            // When the pipeline layout signature changes, Vulkan invalidates
+            // all previously bound descriptor sets. We must re-bind set 0
+            // (bindless) and set 1 (per-pass binding group) here.
+            let sig = pipeline.signature_id();
+            if last_signature_id != Some(sig) {
+                if bind_textures {
+                    cmd.bind_textures()?;
+                }
+                cmd.bind_binding_group(
+                    gd_pipeline,
+                    bg_set_index,
+                    binding_group,
                 )?;
+                last_signature_id = Some(sig);
             }
 
-            if dc.index_count > 0 {
-                cmd.draw_indexed(dc.index_count, dc.index_offset, dc.vertex_offset as i32)?;
-            } else {
-                cmd.draw(dc.vertex_count, dc.vertex_offset)?;
+            // Cache push-constant stage flags for this pipeline; the
+            // reflection is static per pipeline so we only query it on
+            // rebind, not on every drawcall.
+            current_pc_flags = gd_pipeline
+                .reflection()
+                .push_constants()
+                .first()
+                .map(|pc| pc.stage_flags);
+
+            last_pipeline_key = Some(dc.pipeline_key);
+        }
+
+        // Geometry rebind if different from previous draw call.
+        if last_geometry_key != Some(dc.geometry_key) {
+            // SAFETY: same rationale as the pipeline lookup above —
+            // `dc.geometry_key` was validated under the still-held `rm` lock.
+            let geo = unsafe { rm.geometry(dc.geometry_key).unwrap_unchecked() };
+            cmd.bind_vertex_buffer(geo.vertex_buffer(), 0)?;
+            if let Some(ib) = geo.index_buffer() {
+                cmd.bind_index_buffer(ib, 0, geo.index_type())?;
             }
+            if let Some(mb) = geo.morph_target_buffer() {
+                cmd.bind_vertex_buffer_at(MORPH_TARGET_BUFFER_BINDING, mb, 0)?;
+            }
+            last_geometry_key = Some(dc.geometry_key);
         }
 
-        Ok(())
+        // Per-draw-call dynamic state (may differ between draw calls sharing
+        // the same pipeline, e.g. blend / cull overrides from the material).
+        // Skip re-emission when the render state signature is identical to
+        // the previous draw call — sort key groups identical signatures.
+        if last_render_state_sig != Some(dc.render_state_sig) {
+            cmd.set_dynamic_state(&dc.render_state)?;
+            last_render_state_sig = Some(dc.render_state_sig);
+        }
+
+        // Push constants (draw slot) and the draw command.
+        if let Some(flags) = current_pc_flags {
+            cmd.push_constants(
+                flags, 0, bytemuck::bytes_of(&dc.draw_slot),
+            )?;
+        }
+
+        match (run, dc.index_count > 0) {
+            (None, true) => cmd.draw_indexed(dc.index_count, dc.index_offset, dc.vertex_offset as i32)?,
+            (None, false) => cmd.draw(dc.vertex_count, dc.vertex_offset)?,
+            (Some(run), true) => cmd.draw_indexed_instanced(
+                dc.index_count, dc.index_offset, dc.vertex_offset as i32,
+                run.count, run.first_instance,
+            )?,
+            (Some(run), false) => cmd.draw_instanced(
+                dc.vertex_count, dc.vertex_offset, run.count, run.first_instance,
+            )?,
+        }
     }
+
+    Ok(())
 }
 
 #[cfg(test)]
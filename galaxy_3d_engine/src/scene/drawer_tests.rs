@@ -3,7 +3,7 @@ use crate::engine::Engine;
 use crate::camera::VisibleInstances;
 use crate::graphics_device::{TextureFormat, SampleCount, mock_graphics_device::{MockGraphicsDevice, MockCommandList, MockBindingGroup}};
 use crate::resource::resource_manager::PassInfo;
-use crate::scene::{Scene, BruteForceCuller, CameraCuller, RenderView};
+use crate::scene::{Scene, BruteForceCuller, CameraCuller, LodFade, RenderView, VisibleSubMesh};
 use crate::scene::scene_test_helpers::{create_test_aabb, create_test_camera};
 use crate::scene::view_dispatcher::ViewDispatcher;
 use serial_test::serial;
@@ -29,6 +29,69 @@ fn test_forward_drawer_with_capacity_large() {
     let _d = ForwardDrawer::with_capacity(65_536);
 }
 
+#[test]
+fn test_forward_drawer_config() {
+    assert!(!ForwardDrawer::new().config().depth_prepass);
+    let mut d = ForwardDrawer::with_config(16, ForwardDrawerConfig { depth_prepass: true, ..Default::default() });
+    assert!(d.config().depth_prepass);
+    d.set_config(ForwardDrawerConfig::default()).unwrap();
+    assert!(!d.config().depth_prepass);
+}
+
+#[test]
+fn test_forward_drawer_set_config_rejects_instancing_toggle() {
+    let mut d = ForwardDrawer::new();
+    let instanced = ForwardDrawerConfig { instancing: true, ..Default::default() };
+    assert!(d.set_config(instanced).is_err());
+    assert!(!d.config().instancing);
+
+    let mut d = ForwardDrawer::with_config(16, instanced);
+    assert!(d.set_config(ForwardDrawerConfig { depth_prepass: true, ..instanced }).is_ok());
+    assert!(d.set_config(ForwardDrawerConfig::default()).is_err());
+}
+
+#[test]
+fn test_forward_drawer_set_config_rejects_motion_vectors_toggle() {
+    let mut d = ForwardDrawer::new();
+    assert!(d.set_config(ForwardDrawerConfig { motion_vectors: true, ..Default::default() }).is_err());
+    assert!(!d.config().motion_vectors);
+}
+
+#[test]
+fn test_forward_drawer_set_config_rejects_unlit_toggle() {
+    let mut d = ForwardDrawer::with_config(16, ForwardDrawerConfig { unlit: true, ..Default::default() });
+    assert!(d.set_config(ForwardDrawerConfig::default()).is_err());
+    assert!(d.config().unlit);
+}
+
+#[test]
+fn test_with_instance_binding_appends_instance_rate_slot() {
+    use crate::graphics_device::VertexInputRate;
+
+    let layout = VertexLayout {
+        bindings: vec![VertexBinding { binding: 0, stride: 8, input_rate: VertexInputRate::Vertex }],
+        attributes: vec![VertexAttribute { location: 0, binding: 0, format: BufferFormat::R32G32_SFLOAT, offset: 0 }],
+    };
+    let augmented = with_instance_binding(&layout).unwrap();
+    assert_eq!(augmented.bindings.len(), 2);
+    assert_eq!(augmented.bindings[1].binding, INSTANCE_BUFFER_BINDING);
+    assert_eq!(augmented.bindings[1].input_rate, VertexInputRate::Instance);
+    assert_eq!(augmented.attributes[1].location, INSTANCE_DRAW_SLOT_LOCATION);
+    assert_eq!(augmented.attributes[1].format, BufferFormat::R32_UINT);
+
+    // Already instanced: conflicting binding
+    assert!(with_instance_binding(&augmented).is_err());
+}
+
+#[test]
+fn test_depth_equal_render_state() {
+    let state = depth_equal_render_state(&DynamicRenderState::default());
+    assert!(state.depth_test_enable);
+    assert!(!state.depth_write_enable);
+    assert_eq!(state.depth_compare_op, CompareOp::Equal);
+    assert_eq!(state.cull_mode, DynamicRenderState::default().cull_mode);
+}
+
 // ============================================================================
 // Engine-backed integration tests
 // ============================================================================
@@ -52,7 +115,8 @@ fn populate_resource_manager() -> (
         GeometryDesc, GeometryMeshDesc, GeometrySubMeshDesc, GeometrySubMeshLODDesc,
     };
     use crate::resource::pipeline::PipelineDesc;
-    use crate::resource::material::{MaterialDesc, MaterialPassDesc, ParamValue};
+    use crate::resource::material::{MaterialDesc, MaterialPassDesc, MaterialTextureSlotDesc, ParamValue};
+    use crate::resource::texture::{TextureDesc, LayerDesc};
     use crate::resource::mesh::{MeshDesc, MeshSubMeshDesc, GeometryMeshRef, GeometrySubMeshRef};
     use crate::resource::shader::ShaderDesc;
 
@@ -68,10 +132,11 @@ fn populate_resource_manager() -> (
     let geo_key = rm.create_geometry("geo".to_string(), GeometryDesc {
         name: "geo".to_string(),
         graphics_device: gd_arc.clone(),
-        vertex_data: vec![0u8; 48],
-        index_data: Some(vec![0u8; 12]),
+        vertex_data: vec![0u8; 48].into(),
+        index_data: Some(vec![0u8; 12].into()),
         vertex_layout: layout.clone(),
         index_type: IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![GeometryMeshDesc {
             name: "cube".to_string(),
             submeshes: vec![GeometrySubMeshDesc {
@@ -96,15 +161,39 @@ fn populate_resource_manager() -> (
         vertex_shader: vk, fragment_shader: fk,
         vertex_layout: layout, topology: PrimitiveTopology::TriangleList,
         rasterization: Default::default(), color_blend: Default::default(),
-        multisample: Default::default(), color_formats: vec![], depth_format: None,
+        multisample: Default::default(), color_formats: vec![], depth_format: None, engine_features: Default::default(),
     }, &mut *gd_arc.lock().unwrap()).unwrap();
+    let albedo = rm.create_texture("albedo".to_string(), TextureDesc {
+        graphics_device: gd_arc.clone(),
+        texture: crate::graphics_device::TextureDesc {
+            width: 4, height: 4, depth: 1,
+            format: TextureFormat::R8G8B8A8_UNORM,
+            usage: crate::graphics_device::TextureUsage::Sampled,
+            texture_type: crate::graphics_device::TextureType::Tex2D,
+            sample_count: SampleCount::S1,
+            array_layers: 1,
+            data: None,
+            mipmap: crate::graphics_device::MipmapMode::None,
+            debug_name: None,
+        },
+        layers: vec![LayerDesc { name: "default".to_string(), layer_index: 0, data: None, regions: vec![] }],
+    }).unwrap();
     let mk = rm.create_material("m".to_string(), MaterialDesc {
         passes: vec![MaterialPassDesc {
             pass_type: 0,
             fragment_shader: fk, color_blend: Default::default(), polygon_mode: PolygonMode::Fill,
-            textures: vec![],
+            textures: vec![MaterialTextureSlotDesc {
+                name: "albedo".to_string(),
+                texture: albedo,
+                layer: None,
+                region: None,
+                sampler_type: crate::graphics_device::SamplerType::LinearRepeat,
+                flipbook: None,
+            }],
             params: vec![("value".to_string(), ParamValue::Float(1.0))],
             render_state: None,
+            engine_features: Default::default(),
+            render_queue: None,
         }],
     }, &*gd_arc.lock().unwrap()).unwrap();
     let mesh_key = rm.create_mesh("mesh".to_string(), MeshDesc {
@@ -184,6 +273,49 @@ fn test_forward_drawer_draw_with_one_visible_submesh() {
     assert!(cmd.commands.iter().any(|c| c == "draw_indexed"));
 }
 
+#[test]
+#[serial]
+fn test_forward_drawer_draw_records_texture_usage() {
+    setup_engine_with_main_device();
+    let (mesh_key, vertex_shader_key) = populate_resource_manager();
+
+    let mut scene = Scene::new();
+    {
+        let rm_arc = Engine::resource_manager().unwrap();
+        let mut rm = rm_arc.lock().unwrap();
+        rm.begin_texture_usage_frame();
+        scene.create_render_instance(
+            mesh_key, Mat4::IDENTITY, create_test_aabb(),
+            vertex_shader_key, &[], &rm,
+        ).unwrap();
+    }
+
+    let camera = create_test_camera();
+    let mut culler = BruteForceCuller::new();
+    let mut visible = VisibleInstances::new_empty();
+    culler.cull_into(&scene, &camera, None, &mut visible);
+
+    let mut view = RenderView::new(camera.clone(), 0);
+    {
+        let rm_arc = Engine::resource_manager().unwrap();
+        let rm = rm_arc.lock().unwrap();
+        ViewDispatcher::dispatch(&visible, &mut scene, &rm, std::slice::from_mut(&mut view));
+    }
+
+    let mut drawer = ForwardDrawer::new();
+    let mut cmd = MockCommandList::new();
+    let bg: Arc<dyn crate::graphics_device::BindingGroup> =
+        Arc::new(MockBindingGroup::new("test_bg".to_string(), 1));
+    drawer.draw(&mut scene, &view, &mut cmd, &make_pass_info(), &bg, true).unwrap();
+
+    let rm_arc = Engine::resource_manager().unwrap();
+    let rm = rm_arc.lock().unwrap();
+    let albedo = rm.texture_key("albedo").unwrap();
+    let stats = rm.texture_usage(albedo).unwrap();
+    assert_eq!(stats.last_used_frame, 1);
+    assert_eq!(stats.draw_count, 1);
+}
+
 #[test]
 #[serial]
 fn test_forward_drawer_draw_skips_invalid_render_instance() {
@@ -264,3 +396,426 @@ fn test_forward_drawer_draw_skips_textures_when_disabled() {
     // bind_textures should NOT have been emitted.
     assert!(!cmd.commands.iter().any(|c| c == "bind_textures"));
 }
+
+#[test]
+#[serial]
+fn test_forward_drawer_ignored_engine_feature_reuses_pipeline() {
+    setup_engine_with_main_device();
+    let (mesh_key, vertex_shader_key) = populate_resource_manager();
+
+    let mut scene = Scene::new();
+    let key = {
+        let rm_arc = Engine::resource_manager().unwrap();
+        let rm = rm_arc.lock().unwrap();
+        scene.create_render_instance(
+            mesh_key, Mat4::IDENTITY, create_test_aabb(),
+            vertex_shader_key, &[], &rm,
+        ).unwrap()
+    };
+
+    let camera = create_test_camera();
+    let mut culler = BruteForceCuller::new();
+    let mut visible = VisibleInstances::new_empty();
+    culler.cull_into(&scene, &camera, None, &mut visible);
+
+    let mut view = RenderView::new(camera.clone(), 0);
+    {
+        let rm_arc = Engine::resource_manager().unwrap();
+        let rm = rm_arc.lock().unwrap();
+        ViewDispatcher::dispatch(&visible, &mut scene, &rm, std::slice::from_mut(&mut view));
+    }
+
+    let mut drawer = ForwardDrawer::new();
+    let bg: Arc<dyn crate::graphics_device::BindingGroup> =
+        Arc::new(MockBindingGroup::new("test_bg".to_string(), 1));
+    let info = make_pass_info();
+    drawer.draw(&mut scene, &view, &mut MockCommandList::new(), &info, &bg, true).unwrap();
+
+    let rm_arc = Engine::resource_manager().unwrap();
+    let (pipeline_count, features_gen) = {
+        let mut rm = rm_arc.lock().unwrap();
+        assert!(rm.set_engine_features(crate::graphics_device::EngineFeatures::FOG));
+        (rm.pipeline_count(), rm.engine_features_generation())
+    };
+    let cached = |scene: &Scene| scene.render_instance(key).unwrap()
+        .sub_mesh(0).unwrap().pass_by_index(0).unwrap()
+        .is_pipeline_valid(info.generation(), 0, features_gen);
+    assert!(!cached(&scene));
+
+    // The material pass does not declare FOG: the pipeline is re-resolved
+    // to the same variant, no new pipeline is created.
+    drawer.draw(&mut scene, &view, &mut MockCommandList::new(), &info, &bg, true).unwrap();
+    assert!(cached(&scene));
+    assert_eq!(rm_arc.lock().unwrap().pipeline_count(), pipeline_count);
+}
+
+#[test]
+#[serial]
+fn test_forward_drawer_depth_prepass_draws_opaque_twice() {
+    setup_engine_with_main_device();
+    let (mesh_key, vertex_shader_key) = populate_resource_manager();
+
+    let mut scene = Scene::new();
+    {
+        let rm_arc = Engine::resource_manager().unwrap();
+        let rm = rm_arc.lock().unwrap();
+        scene.create_render_instance(
+            mesh_key, Mat4::IDENTITY, create_test_aabb(),
+            vertex_shader_key, &[], &rm,
+        ).unwrap();
+    }
+
+    let camera = create_test_camera();
+    let mut culler = BruteForceCuller::new();
+    let mut visible = VisibleInstances::new_empty();
+    culler.cull_into(&scene, &camera, None, &mut visible);
+
+    let mut view = RenderView::new(camera.clone(), 0);
+    {
+        let rm_arc = Engine::resource_manager().unwrap();
+        let rm = rm_arc.lock().unwrap();
+        ViewDispatcher::dispatch(&visible, &mut scene, &rm, std::slice::from_mut(&mut view));
+    }
+
+    let mut drawer = ForwardDrawer::with_config(16, ForwardDrawerConfig { depth_prepass: true, ..Default::default() });
+    let bg: Arc<dyn crate::graphics_device::BindingGroup> =
+        Arc::new(MockBindingGroup::new("test_bg".to_string(), 1));
+    let info = make_pass_info();
+    let rm_arc = Engine::resource_manager().unwrap();
+    let initial_pipelines = rm_arc.lock().unwrap().pipeline_count();
+
+    // Depth-only draw, then the shading draw with its own pipeline and state
+    let mut cmd = MockCommandList::new();
+    drawer.draw(&mut scene, &view, &mut cmd, &info, &bg, true).unwrap();
+    let count = |cmd: &MockCommandList, name: &str| cmd.commands.iter().filter(|c| *c == name).count();
+    assert_eq!(count(&cmd, "draw_indexed"), 2);
+    assert_eq!(count(&cmd, "bind_pipeline"), 2);
+    assert_eq!(count(&cmd, "set_dynamic_state"), 2);
+    let after_first = rm_arc.lock().unwrap().pipeline_count();
+    assert_eq!(after_first, initial_pipelines + 2);
+
+    // Second frame: both pipelines come from the per-pass cache
+    let mut cmd = MockCommandList::new();
+    drawer.draw(&mut scene, &view, &mut cmd, &info, &bg, true).unwrap();
+    assert_eq!(count(&cmd, "draw_indexed"), 2);
+    assert_eq!(rm_arc.lock().unwrap().pipeline_count(), after_first);
+
+    // Disabled: a single draw
+    drawer.set_config(ForwardDrawerConfig::default()).unwrap();
+    let mut cmd = MockCommandList::new();
+    drawer.draw(&mut scene, &view, &mut cmd, &info, &bg, true).unwrap();
+    assert_eq!(count(&cmd, "draw_indexed"), 1);
+}
+
+#[test]
+#[serial]
+fn test_forward_drawer_transparent_skips_depth_prepass() {
+    use crate::graphics_device::{BlendPreset, PolygonMode};
+    use crate::resource::material::{MaterialDesc, MaterialPassDesc};
+    use crate::resource::mesh::{MeshDesc, MeshSubMeshDesc, GeometryMeshRef, GeometrySubMeshRef};
+
+    setup_engine_with_main_device();
+    let (opaque_mesh, vertex_shader_key) = populate_resource_manager();
+
+    let mut scene = Scene::new();
+    {
+        let rm_arc = Engine::resource_manager().unwrap();
+        let gd_arc = Engine::graphics_device("main").unwrap();
+        let mut rm = rm_arc.lock().unwrap();
+        let fk = rm.shader_key("frag").unwrap();
+        let glass = rm.create_material("glass".to_string(), MaterialDesc {
+            passes: vec![MaterialPassDesc {
+                pass_type: 0,
+                fragment_shader: fk,
+                color_blend: BlendPreset::PremultipliedAlpha.color_blend(),
+                polygon_mode: PolygonMode::Fill,
+                textures: vec![],
+                params: vec![],
+                render_state: None,
+                engine_features: Default::default(),
+                render_queue: None,
+            }],
+        }, &*gd_arc.lock().unwrap()).unwrap();
+        let geo_key = rm.geometry_key("geo").unwrap();
+        let glass_mesh = rm.create_mesh("glass_mesh".to_string(), MeshDesc {
+            geometry: geo_key,
+            geometry_mesh: GeometryMeshRef::Name("cube".to_string()),
+            submeshes: vec![MeshSubMeshDesc {
+                submesh: GeometrySubMeshRef::Name("main".to_string()),
+                material: glass,
+            }],
+        }).unwrap();
+        for mesh in [opaque_mesh, glass_mesh] {
+            scene.create_render_instance(
+                mesh, Mat4::IDENTITY, create_test_aabb(),
+                vertex_shader_key, &[], &rm,
+            ).unwrap();
+        }
+    }
+
+    let camera = create_test_camera();
+    let mut culler = BruteForceCuller::new();
+    let mut visible = VisibleInstances::new_empty();
+    culler.cull_into(&scene, &camera, None, &mut visible);
+
+    let mut view = RenderView::new(camera.clone(), 0);
+    {
+        let rm_arc = Engine::resource_manager().unwrap();
+        let rm = rm_arc.lock().unwrap();
+        ViewDispatcher::dispatch(&visible, &mut scene, &rm, std::slice::from_mut(&mut view));
+    }
+
+    let mut drawer = ForwardDrawer::with_config(16, ForwardDrawerConfig { depth_prepass: true, ..Default::default() });
+    let bg: Arc<dyn crate::graphics_device::BindingGroup> =
+        Arc::new(MockBindingGroup::new("test_bg".to_string(), 1));
+    let mut cmd = MockCommandList::new();
+    drawer.draw(&mut scene, &view, &mut cmd, &make_pass_info(), &bg, true).unwrap();
+
+    // Opaque: depth-only + shading draw. Transparent: shading draw only.
+    let draws = cmd.commands.iter().filter(|c| *c == "draw_indexed").count();
+    assert_eq!(draws, 3);
+    assert_eq!(cmd.commands.iter().filter(|c| *c == "bind_pipeline").count(), 3);
+}
+
+#[test]
+#[serial]
+fn test_forward_drawer_instancing_merges_identical_submeshes() {
+    setup_engine_with_main_device();
+    let (mesh_key, vertex_shader_key) = populate_resource_manager();
+
+    let mut scene = Scene::new();
+    {
+        let rm_arc = Engine::resource_manager().unwrap();
+        let rm = rm_arc.lock().unwrap();
+        for x in [0.0, 1.0, 2.0] {
+            scene.create_render_instance(
+                mesh_key, Mat4::from_translation(glam::Vec3::new(x, 0.0, 0.0)), create_test_aabb(),
+                vertex_shader_key, &[], &rm,
+            ).unwrap();
+        }
+    }
+
+    let camera = create_test_camera();
+    let mut culler = BruteForceCuller::new();
+    let mut visible = VisibleInstances::new_empty();
+    culler.cull_into(&scene, &camera, None, &mut visible);
+
+    let mut view = RenderView::new(camera.clone(), 0);
+    {
+        let rm_arc = Engine::resource_manager().unwrap();
+        let rm = rm_arc.lock().unwrap();
+        ViewDispatcher::dispatch(&visible, &mut scene, &rm, std::slice::from_mut(&mut view));
+    }
+
+    let bg: Arc<dyn crate::graphics_device::BindingGroup> =
+        Arc::new(MockBindingGroup::new("test_bg".to_string(), 1));
+    let count = |cmd: &MockCommandList, name: &str| cmd.commands.iter().filter(|c| *c == name).count();
+
+    // Three instances of the same submesh: one instanced draw
+    let mut drawer = ForwardDrawer::with_config(16, ForwardDrawerConfig { instancing: true, ..Default::default() });
+    let mut cmd = MockCommandList::new();
+    drawer.draw(&mut scene, &view, &mut cmd, &make_pass_info(), &bg, true).unwrap();
+    assert_eq!(count(&cmd, "bind_vertex_buffer_at 1"), 1);
+    assert_eq!(count(&cmd, "draw_indexed_instanced 3@0"), 1);
+    assert_eq!(count(&cmd, "draw_indexed"), 0);
+
+    // With the depth pre-pass, each queue gets its own instance range
+    let mut drawer = ForwardDrawer::with_config(16, ForwardDrawerConfig { depth_prepass: true, instancing: true, ..Default::default() });
+    let mut cmd = MockCommandList::new();
+    drawer.draw(&mut scene, &view, &mut cmd, &make_pass_info(), &bg, true).unwrap();
+    assert_eq!(count(&cmd, "draw_indexed_instanced 3@0"), 1);
+    assert_eq!(count(&cmd, "draw_indexed_instanced 3@3"), 1);
+}
+
+#[test]
+#[serial]
+fn test_forward_drawer_cross_fading_draws_encode_lod_fade() {
+    setup_engine_with_main_device();
+    let (mesh_key, vertex_shader_key) = populate_resource_manager();
+
+    let mut scene = Scene::new();
+    let key = {
+        let rm_arc = Engine::resource_manager().unwrap();
+        let rm = rm_arc.lock().unwrap();
+        scene.create_render_instance(
+            mesh_key, Mat4::IDENTITY, create_test_aabb(),
+            vertex_shader_key, &[], &rm,
+        ).unwrap()
+    };
+    let draw_slot = scene.render_instance(key).unwrap().sub_mesh(0).unwrap().draw_slot();
+
+    // The same submesh drawn fading in and fading out
+    let mut view = RenderView::new(create_test_camera(), 0);
+    for lod_fade in [LodFade::In(0.5), LodFade::Out(0.5)] {
+        view.push(VisibleSubMesh {
+            key, distance: 1.0, submesh_index: 0, pass_index: 0, lod_index: 0, lod_fade,
+        });
+    }
+
+    let bg: Arc<dyn crate::graphics_device::BindingGroup> =
+        Arc::new(MockBindingGroup::new("test_bg".to_string(), 1));
+    let mut drawer = ForwardDrawer::with_config(16, ForwardDrawerConfig { instancing: true, ..Default::default() });
+    let mut cmd = MockCommandList::new();
+    drawer.draw(&mut scene, &view, &mut cmd, &make_pass_info(), &bg, true).unwrap();
+
+    let mut slots = drawer.instance_slots.clone();
+    slots.sort_unstable();
+    assert_eq!(slots, vec![LodFade::In(0.5).encode(draw_slot), LodFade::Out(0.5).encode(draw_slot)]);
+    assert_eq!(LodFade::decode(slots[1]), (draw_slot, LodFade::Out(0.5)));
+}
+
+#[test]
+#[serial]
+fn test_forward_drawer_motion_vectors_resolve_feature_variant() {
+    setup_engine_with_main_device();
+    let (mesh_key, vertex_shader_key) = populate_resource_manager();
+    let bg: Arc<dyn crate::graphics_device::BindingGroup> =
+        Arc::new(MockBindingGroup::new("test_bg".to_string(), 1));
+    // Scene color + motion vectors
+    let motion_info = PassInfo::new(
+        vec![TextureFormat::R8G8B8A8_UNORM, TextureFormat::R16G16_SFLOAT], None, SampleCount::S1,
+    );
+    let cases = [
+        (true, motion_info, EngineFeatures::MOTION_VECTORS),
+        (false, make_pass_info(), EngineFeatures::NONE),
+    ];
+
+    for (motion_vectors, info, expected) in cases {
+        let mut scene = Scene::new();
+        let key = {
+            let rm_arc = Engine::resource_manager().unwrap();
+            let rm = rm_arc.lock().unwrap();
+            scene.create_render_instance(
+                mesh_key, Mat4::IDENTITY, create_test_aabb(),
+                vertex_shader_key, &[], &rm,
+            ).unwrap()
+        };
+        let mut view = RenderView::new(create_test_camera(), 0);
+        view.push(VisibleSubMesh {
+            key, distance: 1.0, submesh_index: 0, pass_index: 0, lod_index: 0, lod_fade: LodFade::None,
+        });
+
+        let config = ForwardDrawerConfig { motion_vectors, ..Default::default() };
+        let mut drawer = ForwardDrawer::with_config(16, config);
+        drawer.draw(&mut scene, &view, &mut MockCommandList::new(), &info, &bg, true).unwrap();
+
+        // Set without the material pass declaring it
+        let pipeline_key = scene.render_instance(key).unwrap()
+            .sub_mesh(0).unwrap().pass_by_index(0).unwrap()
+            .cached_pipeline_key().unwrap();
+        let rm_arc = Engine::resource_manager().unwrap();
+        let rm = rm_arc.lock().unwrap();
+        assert_eq!(rm.pipeline(pipeline_key).unwrap().desc().engine_features, expected);
+    }
+}
+
+#[test]
+#[serial]
+fn test_forward_drawer_unlit_drops_global_features() {
+    setup_engine_with_main_device();
+    let (mesh_key, vertex_shader_key) = populate_resource_manager();
+    let bg: Arc<dyn crate::graphics_device::BindingGroup> =
+        Arc::new(MockBindingGroup::new("test_bg".to_string(), 1));
+    Engine::resource_manager().unwrap().lock().unwrap().set_engine_features(EngineFeatures::ALL);
+
+    let mut scene = Scene::new();
+    let key = {
+        let rm_arc = Engine::resource_manager().unwrap();
+        let rm = rm_arc.lock().unwrap();
+        scene.create_render_instance(
+            mesh_key, Mat4::IDENTITY, create_test_aabb(),
+            vertex_shader_key, &[], &rm,
+        ).unwrap()
+    };
+    let mut view = RenderView::new(create_test_camera(), 0);
+    view.push(VisibleSubMesh {
+        key, distance: 1.0, submesh_index: 0, pass_index: 0, lod_index: 0, lod_fade: LodFade::None,
+    });
+
+    let config = ForwardDrawerConfig { unlit: true, ..Default::default() };
+    let mut drawer = ForwardDrawer::with_config(16, config);
+    drawer.draw(&mut scene, &view, &mut MockCommandList::new(), &make_pass_info(), &bg, true).unwrap();
+
+    let pipeline_key = scene.render_instance(key).unwrap()
+        .sub_mesh(0).unwrap().pass_by_index(0).unwrap()
+        .cached_pipeline_key().unwrap();
+    let rm_arc = Engine::resource_manager().unwrap();
+    let rm = rm_arc.lock().unwrap();
+    assert_eq!(rm.pipeline(pipeline_key).unwrap().desc().engine_features, EngineFeatures::UNLIT);
+}
+
+#[test]
+#[serial]
+fn test_forward_drawer_debug_views_resolve_variants() {
+    use crate::graphics_device::{BlendPreset, PolygonMode};
+
+    setup_engine_with_main_device();
+    let (mesh_key, vertex_shader_key) = populate_resource_manager();
+    let bg: Arc<dyn crate::graphics_device::BindingGroup> =
+        Arc::new(MockBindingGroup::new("test_bg".to_string(), 1));
+    let rm_arc = Engine::resource_manager().unwrap();
+
+    let mut scene = Scene::new();
+    let key = scene.create_render_instance(
+        mesh_key, Mat4::IDENTITY, create_test_aabb(),
+        vertex_shader_key, &[], &rm_arc.lock().unwrap(),
+    ).unwrap();
+    let mut view = RenderView::new(create_test_camera(), 0);
+    view.push(VisibleSubMesh {
+        key, distance: 1.0, submesh_index: 0, pass_index: 0, lod_index: 0, lod_fade: LodFade::None,
+    });
+    let mut drawer = ForwardDrawer::with_config(16, ForwardDrawerConfig { depth_prepass: true, ..Default::default() });
+
+    for mode in DebugViewMode::ALL {
+        rm_arc.lock().unwrap().set_debug_view(mode);
+        let mut cmd = MockCommandList::new();
+        drawer.draw(&mut scene, &view, &mut cmd, &make_pass_info(), &bg, true).unwrap();
+
+        let pipeline_key = scene.render_instance(key).unwrap()
+            .sub_mesh(0).unwrap().pass_by_index(0).unwrap()
+            .cached_pipeline_key().unwrap();
+        let rm = rm_arc.lock().unwrap();
+        let desc = rm.pipeline(pipeline_key).unwrap().desc();
+        assert_eq!(desc.engine_features, mode.engine_features(), "{:?}", mode);
+        assert_eq!(desc.rasterization.polygon_mode, mode.polygon_mode(PolygonMode::Fill), "{:?}", mode);
+        let expected_blend = if mode == DebugViewMode::Overdraw {
+            BlendPreset::Additive.color_blend()
+        } else {
+            Default::default()
+        };
+        assert_eq!(desc.color_blend, expected_blend, "{:?}", mode);
+
+        // Debug views draw without the depth pre-pass
+        let draws = cmd.commands.iter().filter(|c| *c == "draw_indexed").count();
+        assert_eq!(draws, if mode == DebugViewMode::None { 2 } else { 1 }, "{:?}", mode);
+    }
+}
+
+#[test]
+#[serial]
+fn test_forward_drawer_lod_coloring_encodes_lod_index() {
+    setup_engine_with_main_device();
+    let (mesh_key, vertex_shader_key) = populate_resource_manager();
+    let rm_arc = Engine::resource_manager().unwrap();
+
+    let mut scene = Scene::new();
+    let key = scene.create_render_instance(
+        mesh_key, Mat4::IDENTITY, create_test_aabb(),
+        vertex_shader_key, &[], &rm_arc.lock().unwrap(),
+    ).unwrap();
+    let draw_slot = scene.render_instance(key).unwrap().sub_mesh(0).unwrap().draw_slot();
+    let mut view = RenderView::new(create_test_camera(), 0);
+    view.push(VisibleSubMesh {
+        key, distance: 1.0, submesh_index: 0, pass_index: 0, lod_index: 0, lod_fade: LodFade::In(0.5),
+    });
+
+    rm_arc.lock().unwrap().set_debug_view(DebugViewMode::LodColoring);
+    let bg: Arc<dyn crate::graphics_device::BindingGroup> =
+        Arc::new(MockBindingGroup::new("test_bg".to_string(), 1));
+    let mut drawer = ForwardDrawer::with_config(16, ForwardDrawerConfig { instancing: true, ..Default::default() });
+    drawer.draw(&mut scene, &view, &mut MockCommandList::new(), &make_pass_info(), &bg, true).unwrap();
+
+    // The fade level is replaced by the LOD index (0 here)
+    assert_eq!(drawer.instance_slots, vec![encode_debug_lod(draw_slot, 0)]);
+    assert_eq!(drawer.instance_slots[0], draw_slot);
+}
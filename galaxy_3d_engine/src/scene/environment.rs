@@ -0,0 +1,111 @@
+/// Scene-level environment parameters (ambient light and fog).
+///
+/// Stored on the Scene and written every frame into the frame uniform
+/// buffer by `DefaultUpdater::update_frame`, so shaders and fullscreen
+/// passes read them from the same block as the camera data.
+
+use glam::Vec3;
+
+// ===== FOG MODE =====
+
+/// Distance fog falloff, written to the `fogMode` frame field.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FogMode {
+    /// No fog (shaders skip the fog term)
+    #[default]
+    None = 0,
+    /// Linear ramp from `start` (no fog) to `end` (full fog)
+    Linear = 1,
+    /// `1 - exp(-density * distance)`
+    Exponential = 2,
+    /// `1 - exp(-(density * distance)^2)`: clear near the camera, then a
+    /// sharper falloff than `Exponential`
+    ExponentialSquared = 3,
+}
+
+// ===== FOG =====
+
+/// Fog settings.
+///
+/// The height terms apply to every mode: the fog amount is scaled by
+/// `exp(-height_falloff * (y - base_height))` (clamped to 1 below
+/// `base_height`), so fog thins out with altitude. A `height_falloff` of 0
+/// gives uniform fog.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fog {
+    pub mode: FogMode,
+    /// Fog color (linear RGB)
+    pub color: Vec3,
+    /// Linear mode: distance where fog starts
+    pub start: f32,
+    /// Linear mode: distance of full fog
+    pub end: f32,
+    /// Exponential mode: density per world unit
+    pub density: f32,
+    /// Height attenuation rate (0 = uniform fog)
+    pub height_falloff: f32,
+    /// World-space height where the height attenuation starts
+    pub base_height: f32,
+}
+
+impl Fog {
+    /// Fog amount (0 = none, 1 = full fog color) of a point at `distance`
+    /// from the camera and world height `height`. CPU reference of the
+    /// shader fog term.
+    pub fn amount(&self, distance: f32, height: f32) -> f32 {
+        let amount = match self.mode {
+            FogMode::None => return 0.0,
+            FogMode::Linear => {
+                (distance - self.start) / (self.end - self.start).max(f32::EPSILON)
+            }
+            FogMode::Exponential => 1.0 - (-self.density * distance).exp(),
+            FogMode::ExponentialSquared => 1.0 - (-(self.density * distance).powi(2)).exp(),
+        };
+        let height_scale = (-self.height_falloff * (height - self.base_height)).exp().min(1.0);
+        (amount.clamp(0.0, 1.0) * height_scale).clamp(0.0, 1.0)
+    }
+}
+
+impl Default for Fog {
+    fn default() -> Self {
+        Self {
+            mode: FogMode::None,
+            color: Vec3::splat(0.5),
+            start: 10.0,
+            end: 100.0,
+            density: 0.01,
+            height_falloff: 0.0,
+            base_height: 0.0,
+        }
+    }
+}
+
+// ===== SCENE ENVIRONMENT =====
+
+/// Ambient light and fog of a Scene.
+///
+/// Defaults match the factory defaults of
+/// `ResourceManager::create_default_frame_uniform_buffer()`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SceneEnvironment {
+    /// Ambient light color (linear RGB)
+    pub ambient_color: Vec3,
+    /// Ambient light multiplier
+    pub ambient_intensity: f32,
+    pub fog: Fog,
+}
+
+impl Default for SceneEnvironment {
+    fn default() -> Self {
+        Self {
+            ambient_color: Vec3::splat(0.1),
+            ambient_intensity: 1.0,
+            fog: Fog::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "environment_tests.rs"]
+mod tests;
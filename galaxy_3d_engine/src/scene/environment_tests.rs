@@ -0,0 +1,51 @@
+use super::*;
+
+#[test]
+fn test_default_environment_has_no_fog() {
+    let env = SceneEnvironment::default();
+    assert_eq!(env.fog.mode, FogMode::None);
+    assert_eq!(env.ambient_color, Vec3::splat(0.1));
+    assert_eq!(env.ambient_intensity, 1.0);
+}
+
+#[test]
+fn test_fog_mode_gpu_values() {
+    assert_eq!(FogMode::None as u32, 0);
+    assert_eq!(FogMode::Linear as u32, 1);
+    assert_eq!(FogMode::Exponential as u32, 2);
+    assert_eq!(FogMode::ExponentialSquared as u32, 3);
+}
+
+#[test]
+fn test_fog_amount_per_mode() {
+    let fog = |mode| Fog { mode, start: 10.0, end: 20.0, density: 0.1, ..Fog::default() };
+    assert_eq!(fog(FogMode::None).amount(100.0, 0.0), 0.0);
+
+    let linear = fog(FogMode::Linear);
+    assert_eq!(linear.amount(5.0, 0.0), 0.0);
+    assert_eq!(linear.amount(15.0, 0.0), 0.5);
+    assert_eq!(linear.amount(50.0, 0.0), 1.0);
+
+    let exponential = fog(FogMode::Exponential);
+    let squared = fog(FogMode::ExponentialSquared);
+    assert!((exponential.amount(10.0, 0.0) - (1.0 - (-1.0f32).exp())).abs() < 1e-6);
+    assert!((squared.amount(10.0, 0.0) - (1.0 - (-1.0f32).exp())).abs() < 1e-6);
+    // Squared falloff is clearer near the camera and denser far away
+    assert!(squared.amount(5.0, 0.0) < exponential.amount(5.0, 0.0));
+    assert!(squared.amount(20.0, 0.0) > exponential.amount(20.0, 0.0));
+}
+
+#[test]
+fn test_fog_amount_thins_with_height() {
+    let fog = Fog {
+        mode: FogMode::Exponential, density: 0.1, height_falloff: 0.5, base_height: 2.0,
+        ..Fog::default()
+    };
+    let ground = fog.amount(10.0, 0.0);
+    assert_eq!(ground, fog.amount(10.0, 2.0));
+    assert!((fog.amount(10.0, 4.0) - ground * (-1.0f32).exp()).abs() < 1e-6);
+
+    // No falloff: uniform fog
+    let uniform = Fog { height_falloff: 0.0, ..fog };
+    assert_eq!(uniform.amount(10.0, 100.0), uniform.amount(10.0, 0.0));
+}
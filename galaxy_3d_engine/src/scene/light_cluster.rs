@@ -0,0 +1,369 @@
+//! Clustered light culling (CPU).
+//!
+//! Splits the camera view volume into a 3D grid of clusters: `tiles_x` ×
+//! `tiles_y` screen tiles and `slices_z` depth slices with exponential
+//! spacing between the near and far planes. Each enabled Point/Spot light is
+//! tested (as a bounding sphere of radius `range`) against the view-space
+//! AABB of every cluster it may overlap, and its GPU light slot is appended
+//! to the cluster's light list.
+//!
+//! The result is two flat arrays ready to be uploaded to storage buffers:
+//! - `cluster_ranges()`: one `[offset, count]` pair per cluster
+//! - `light_indices()`: the concatenated per-cluster light slot lists
+//!
+//! `upload()` writes them to the buffers of
+//! `ResourceManager::create_default_light_cluster_buffer` and
+//! `create_default_light_index_buffer`, and the grid dimensions and depth
+//! range to the frame uniform. Bound to the forward pass, the standard PBR
+//! fragment shader finds its cluster from its clip position and view-space
+//! depth (see `slice_for_depth`), then loops over
+//! `light_indices[offset .. offset + count]`. This replaces the
+//! per-instance top-8 assignment done by `Updater::assign_lights()` and
+//! scales to many more lights per object.
+//!
+//! All working buffers are reused across frames — zero allocation in steady
+//! state once the high-water mark has been reached.
+
+use glam::{Mat4, Vec3};
+use crate::error::Result;
+use crate::engine_bail;
+use crate::camera::Camera;
+use crate::resource::buffer::Buffer;
+use super::aabb::AABB;
+use super::scene::Scene;
+
+// ===== CONFIG =====
+
+/// Dimensions of the cluster grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LightClusterConfig {
+    /// Number of screen tiles along X
+    pub tiles_x: u32,
+    /// Number of screen tiles along Y
+    pub tiles_y: u32,
+    /// Number of exponential depth slices between near and far
+    pub slices_z: u32,
+    /// Maximum number of lights stored per cluster (extra lights are dropped)
+    pub max_lights_per_cluster: u32,
+}
+
+impl Default for LightClusterConfig {
+    fn default() -> Self {
+        Self {
+            tiles_x: 16,
+            tiles_y: 9,
+            slices_z: 24,
+            max_lights_per_cluster: 64,
+        }
+    }
+}
+
+// ===== LIGHT CLUSTER GRID =====
+
+/// CPU clustered light culling grid.
+///
+/// Created once by the caller and rebuilt every frame with `build()`.
+pub struct LightClusterGrid {
+    config: LightClusterConfig,
+    near: f32,
+    far: f32,
+    /// Projection matrix the cluster bounds were computed with
+    projection: Mat4,
+    /// `(projection, near, far)` used to build `cluster_bounds`. The bounds
+    /// only depend on these, so they are recomputed only when one changes.
+    bounds_key: Option<(Mat4, f32, f32)>,
+    /// View-space AABB of each cluster
+    cluster_bounds: Vec<AABB>,
+    /// Per-cluster light slot lists (scratch, flattened at the end of `build`)
+    per_cluster: Vec<Vec<u32>>,
+    /// `[offset, count]` into `light_indices`, one entry per cluster
+    cluster_ranges: Vec<[u32; 2]>,
+    /// Concatenated light slots of all clusters
+    light_indices: Vec<u32>,
+}
+
+impl LightClusterGrid {
+    /// Create an empty grid with the given dimensions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any dimension or `max_lights_per_cluster` is zero.
+    pub fn new(config: LightClusterConfig) -> Result<Self> {
+        if config.tiles_x == 0 || config.tiles_y == 0 || config.slices_z == 0 {
+            engine_bail!("galaxy3d::LightClusterGrid",
+                "Cluster grid dimensions must be non-zero (got {}x{}x{})",
+                config.tiles_x, config.tiles_y, config.slices_z);
+        }
+        if config.max_lights_per_cluster == 0 {
+            engine_bail!("galaxy3d::LightClusterGrid",
+                "max_lights_per_cluster must be non-zero");
+        }
+
+        let cluster_count =
+            (config.tiles_x * config.tiles_y * config.slices_z) as usize;
+
+        Ok(Self {
+            config,
+            near: 0.0,
+            far: 0.0,
+            projection: Mat4::IDENTITY,
+            bounds_key: None,
+            cluster_bounds: Vec::with_capacity(cluster_count),
+            per_cluster: vec![Vec::new(); cluster_count],
+            cluster_ranges: vec![[0, 0]; cluster_count],
+            light_indices: Vec::new(),
+        })
+    }
+
+    // ===== ACCESSORS =====
+
+    /// Grid dimensions
+    pub fn config(&self) -> &LightClusterConfig {
+        &self.config
+    }
+
+    /// Total number of clusters (`tiles_x * tiles_y * slices_z`)
+    pub fn cluster_count(&self) -> usize {
+        self.cluster_ranges.len()
+    }
+
+    /// Flat cluster index for a (tile x, tile y, slice z) triple
+    pub fn cluster_index(&self, x: u32, y: u32, z: u32) -> usize {
+        (x + y * self.config.tiles_x + z * self.config.tiles_x * self.config.tiles_y) as usize
+    }
+
+    /// `[offset, count]` into `light_indices()`, one entry per cluster
+    pub fn cluster_ranges(&self) -> &[[u32; 2]] {
+        &self.cluster_ranges
+    }
+
+    /// Concatenated light slot lists of all clusters
+    pub fn light_indices(&self) -> &[u32] {
+        &self.light_indices
+    }
+
+    /// Upper bound of `light_indices().len()`: every cluster full. A light
+    /// index buffer of that count never overflows.
+    pub fn max_light_index_count(&self) -> usize {
+        self.cluster_count() * self.config.max_lights_per_cluster as usize
+    }
+
+    /// Light slots affecting a given cluster
+    pub fn cluster_lights(&self, cluster: usize) -> &[u32] {
+        match self.cluster_ranges.get(cluster) {
+            Some(&[offset, count]) => {
+                &self.light_indices[offset as usize..(offset + count) as usize]
+            }
+            None => &[],
+        }
+    }
+
+    /// Depth slice containing a positive view-space depth, or None if the
+    /// depth lies outside `[near, far]` of the last `build()`.
+    pub fn slice_for_depth(&self, depth: f32) -> Option<u32> {
+        if self.far <= self.near || depth < self.near || depth > self.far {
+            return None;
+        }
+        let t = (depth / self.near).ln() / (self.far / self.near).ln();
+        let slice = (t * self.config.slices_z as f32) as u32;
+        Some(slice.min(self.config.slices_z - 1))
+    }
+
+    /// Cluster containing a view-space position, or None if it lies outside
+    /// the view volume of the last `build()`.
+    pub fn cluster_for_view_position(&self, position: Vec3) -> Option<usize> {
+        let z = self.slice_for_depth(-position.z)?;
+        let ndc = self.projection.project_point3(position);
+        if !(-1.0..=1.0).contains(&ndc.x) || !(-1.0..=1.0).contains(&ndc.y) {
+            return None;
+        }
+        let x = (((ndc.x + 1.0) * 0.5 * self.config.tiles_x as f32) as u32)
+            .min(self.config.tiles_x - 1);
+        let y = (((ndc.y + 1.0) * 0.5 * self.config.tiles_y as f32) as u32)
+            .min(self.config.tiles_y - 1);
+        Some(self.cluster_index(x, y, z))
+    }
+
+    // ===== BUILD =====
+
+    /// Assign all enabled lights of the scene to the clusters of `camera`.
+    ///
+    /// `near` and `far` are the view-space depths bounding the cluster grid
+    /// (usually the camera clip planes). Spot lights are tested with their
+    /// bounding sphere, which is conservative.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `near <= 0` or `far <= near`.
+    pub fn build(&mut self, scene: &Scene, camera: &Camera, near: f32, far: f32) -> Result<()> {
+        if near <= 0.0 || far <= near {
+            engine_bail!("galaxy3d::LightClusterGrid",
+                "Invalid cluster depth range (near = {}, far = {})", near, far);
+        }
+
+        let projection = *camera.projection_matrix();
+        if self.bounds_key != Some((projection, near, far)) {
+            self.near = near;
+            self.far = far;
+            self.projection = projection;
+            self.rebuild_cluster_bounds();
+            self.bounds_key = Some((projection, near, far));
+        }
+
+        for list in &mut self.per_cluster {
+            list.clear();
+        }
+
+        let view = camera.view_matrix();
+        let max_per_cluster = self.config.max_lights_per_cluster as usize;
+
+        for (_, light) in scene.lights() {
+            if !light.enabled() {
+                continue;
+            }
+            let center = view.transform_point3(light.position());
+            let radius = light.range();
+            let depth = -center.z;
+            if depth + radius < near || depth - radius > far {
+                continue;
+            }
+
+            // Both depths are clamped into [near, far], so the lookups succeed.
+            let z0 = self.slice_for_depth((depth - radius).max(near)).unwrap_or(0);
+            let z1 = self.slice_for_depth((depth + radius).min(far))
+                .unwrap_or(self.config.slices_z - 1);
+
+            for z in z0..=z1 {
+                for y in 0..self.config.tiles_y {
+                    for x in 0..self.config.tiles_x {
+                        let idx = self.cluster_index(x, y, z);
+                        let closest = self.cluster_bounds[idx].closest_point(center);
+                        if (closest - center).length_squared() > radius * radius {
+                            continue;
+                        }
+                        let list = &mut self.per_cluster[idx];
+                        if list.len() < max_per_cluster {
+                            list.push(light.light_slot());
+                        }
+                    }
+                }
+            }
+        }
+
+        // Flatten per-cluster lists into the GPU-ready arrays
+        self.light_indices.clear();
+        for (range, list) in self.cluster_ranges.iter_mut().zip(&self.per_cluster) {
+            *range = [self.light_indices.len() as u32, list.len() as u32];
+            self.light_indices.extend_from_slice(list);
+        }
+
+        Ok(())
+    }
+
+    // ===== UPLOAD =====
+
+    /// Write the last `build()` to the GPU buffers read by the standard
+    /// PBR shaders.
+    ///
+    /// - `frame`: the default frame uniform. `lightClusterGrid` gets the
+    ///   grid dimensions and `lightClusterDepth` its `[near, far]` range.
+    ///   Before the first `build()` the grid is written as 0, which keeps
+    ///   the shaders on the per-instance lights.
+    /// - `clusters`: `cluster_ranges()`, one element per cluster
+    /// - `light_indices`: `light_indices()`, one element per entry
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `frame` lacks the cluster fields, if `clusters`
+    /// does not hold `cluster_count()` 8-byte elements, or if
+    /// `light_indices` is too small for this frame's list.
+    pub fn upload(&self, frame: &Buffer, clusters: &Buffer, light_indices: &Buffer) -> Result<()> {
+        let (Some(grid_field), Some(depth_field)) =
+            (frame.field_id("lightClusterGrid"), frame.field_id("lightClusterDepth"))
+        else {
+            engine_bail!("galaxy3d::LightClusterGrid",
+                "Frame buffer has no lightClusterGrid / lightClusterDepth fields");
+        };
+        if clusters.count() as usize != self.cluster_count() || clusters.stride() != 8 {
+            engine_bail!("galaxy3d::LightClusterGrid",
+                "Cluster buffer must hold {} elements of 8 bytes (got {} of {})",
+                self.cluster_count(), clusters.count(), clusters.stride());
+        }
+        if light_indices.stride() != 4 || (light_indices.count() as usize) < self.light_indices.len() {
+            engine_bail!("galaxy3d::LightClusterGrid",
+                "Light index buffer must hold {} elements of 4 bytes (got {} of {})",
+                self.light_indices.len(), light_indices.count(), light_indices.stride());
+        }
+
+        clusters.update_raw(0, bytemuck::cast_slice(&self.cluster_ranges))?;
+        if !self.light_indices.is_empty() {
+            light_indices.update_raw(0, bytemuck::cast_slice(&self.light_indices))?;
+        }
+
+        let grid = match self.bounds_key {
+            Some(_) => [self.config.tiles_x, self.config.tiles_y, self.config.slices_z, 0],
+            None => [0; 4],
+        };
+        let depth = [self.near, self.far, 0.0, 0.0];
+        frame.update_field(0, grid_field, bytemuck::bytes_of(&grid))?;
+        frame.update_field(0, depth_field, bytemuck::bytes_of(&depth))
+    }
+
+    /// Recompute the view-space AABB of every cluster from the current
+    /// projection and depth range.
+    ///
+    /// Perspective projections: each tile corner is unprojected to a view
+    /// ray, then intersected with the slice near/far planes. Orthographic
+    /// projections: tile corners keep the same view-space X/Y at every depth.
+    fn rebuild_cluster_bounds(&mut self) {
+        let inv_projection = self.projection.inverse();
+        let is_perspective = self.projection.z_axis.w != 0.0;
+        let (tiles_x, tiles_y, slices_z) =
+            (self.config.tiles_x, self.config.tiles_y, self.config.slices_z);
+
+        self.cluster_bounds.clear();
+        for z in 0..slices_z {
+            let slice_near = self.slice_depth(z);
+            let slice_far = self.slice_depth(z + 1);
+            for y in 0..tiles_y {
+                for x in 0..tiles_x {
+                    let ndc_x = [
+                        -1.0 + 2.0 * x as f32 / tiles_x as f32,
+                        -1.0 + 2.0 * (x + 1) as f32 / tiles_x as f32,
+                    ];
+                    let ndc_y = [
+                        -1.0 + 2.0 * y as f32 / tiles_y as f32,
+                        -1.0 + 2.0 * (y + 1) as f32 / tiles_y as f32,
+                    ];
+
+                    let mut min = Vec3::splat(f32::MAX);
+                    let mut max = Vec3::splat(f32::MIN);
+                    for nx in ndc_x {
+                        for ny in ndc_y {
+                            let p = inv_projection.project_point3(Vec3::new(nx, ny, 0.5));
+                            for d in [slice_near, slice_far] {
+                                let corner = if is_perspective && p.z < 0.0 {
+                                    p * (d / -p.z)
+                                } else {
+                                    Vec3::new(p.x, p.y, -d)
+                                };
+                                min = min.min(corner);
+                                max = max.max(corner);
+                            }
+                        }
+                    }
+                    self.cluster_bounds.push(AABB { min, max });
+                }
+            }
+        }
+    }
+
+    /// View-space depth of the near boundary of slice `z` (exponential).
+    fn slice_depth(&self, z: u32) -> f32 {
+        self.near * (self.far / self.near).powf(z as f32 / self.config.slices_z as f32)
+    }
+}
+
+#[cfg(test)]
+#[path = "light_cluster_tests.rs"]
+mod tests;
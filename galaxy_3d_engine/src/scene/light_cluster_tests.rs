@@ -0,0 +1,237 @@
+use super::*;
+use crate::camera::{Camera, Frustum};
+use crate::graphics_device::Viewport;
+use crate::scene::LightDesc;
+use crate::scene::scene_test_helpers::{create_mock_graphics_device, make_frame_buffer};
+use crate::resource::resource_manager::ResourceManager;
+use std::sync::Arc;
+use glam::{Mat4, Vec3};
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+const NEAR: f32 = 0.1;
+const FAR: f32 = 100.0;
+
+/// Camera at the origin looking down -Z with a 90° vertical FOV.
+fn create_perspective_camera() -> Camera {
+    let view = Mat4::look_at_rh(Vec3::ZERO, Vec3::new(0.0, 0.0, -1.0), Vec3::Y);
+    let projection = Mat4::perspective_rh(std::f32::consts::FRAC_PI_2, 16.0 / 9.0, NEAR, FAR);
+    let frustum = Frustum::from_view_projection(&(projection * view));
+    let viewport = Viewport { x: 0.0, y: 0.0, width: 1920.0, height: 1080.0, min_depth: 0.0, max_depth: 1.0 };
+    Camera::new(view, projection, frustum, viewport)
+}
+
+/// Frame, cluster and light index buffers sized for `grid`
+fn create_cluster_buffers(grid: &LightClusterGrid, index_count: u32) -> [Arc<Buffer>; 3] {
+    let mut rm = ResourceManager::new();
+    let gd = create_mock_graphics_device();
+    let frame = make_frame_buffer(&mut rm);
+    let clusters = rm.create_default_light_cluster_buffer(
+        "clusters".to_string(), gd.clone(), grid.cluster_count() as u32).unwrap();
+    let indices = rm.create_default_light_index_buffer("indices".to_string(), gd, index_count).unwrap();
+    [frame, rm.buffer(clusters).unwrap().clone(), rm.buffer(indices).unwrap().clone()]
+}
+
+fn point_light(position: Vec3, range: f32) -> LightDesc {
+    LightDesc::Point {
+        position,
+        color: Vec3::ONE,
+        intensity: 1.0,
+        range,
+        attenuation_constant: 0.0,
+        attenuation_linear: 0.0,
+        attenuation_quadratic: 1.0,
+    }
+}
+
+// ============================================================================
+// Tests: creation / validation
+// ============================================================================
+
+#[test]
+fn test_new_default_config() {
+    let grid = LightClusterGrid::new(LightClusterConfig::default()).unwrap();
+    assert_eq!(grid.cluster_count(), 16 * 9 * 24);
+    assert!(grid.light_indices().is_empty());
+}
+
+#[test]
+fn test_new_rejects_zero_dimension() {
+    let config = LightClusterConfig { tiles_x: 0, ..Default::default() };
+    assert!(LightClusterGrid::new(config).is_err());
+}
+
+#[test]
+fn test_new_rejects_zero_max_lights() {
+    let config = LightClusterConfig { max_lights_per_cluster: 0, ..Default::default() };
+    assert!(LightClusterGrid::new(config).is_err());
+}
+
+#[test]
+fn test_build_rejects_invalid_depth_range() {
+    let mut grid = LightClusterGrid::new(LightClusterConfig::default()).unwrap();
+    let scene = Scene::new();
+    let camera = create_perspective_camera();
+    assert!(grid.build(&scene, &camera, 0.0, FAR).is_err());
+    assert!(grid.build(&scene, &camera, 10.0, 5.0).is_err());
+}
+
+// ============================================================================
+// Tests: slices
+// ============================================================================
+
+#[test]
+fn test_slice_for_depth_bounds() {
+    let mut grid = LightClusterGrid::new(LightClusterConfig::default()).unwrap();
+    let scene = Scene::new();
+    grid.build(&scene, &create_perspective_camera(), NEAR, FAR).unwrap();
+
+    assert_eq!(grid.slice_for_depth(NEAR), Some(0));
+    assert_eq!(grid.slice_for_depth(FAR), Some(23));
+    assert_eq!(grid.slice_for_depth(NEAR * 0.5), None);
+    assert_eq!(grid.slice_for_depth(FAR * 2.0), None);
+
+    let mut previous = 0;
+    for i in 1..100 {
+        let slice = grid.slice_for_depth(i as f32).unwrap();
+        assert!(slice >= previous);
+        previous = slice;
+    }
+}
+
+// ============================================================================
+// Tests: light assignment
+// ============================================================================
+
+#[test]
+fn test_light_in_front_lands_in_its_cluster() {
+    let mut grid = LightClusterGrid::new(LightClusterConfig::default()).unwrap();
+    let mut scene = Scene::new();
+    let key = scene.create_light(point_light(Vec3::new(0.0, 0.0, -10.0), 2.0));
+    let slot = scene.light(key).unwrap().light_slot();
+    let camera = create_perspective_camera();
+
+    grid.build(&scene, &camera, NEAR, FAR).unwrap();
+
+    let cluster = grid.cluster_for_view_position(Vec3::new(0.0, 0.0, -10.0)).unwrap();
+    assert_eq!(grid.cluster_lights(cluster), &[slot]);
+    // A small light must not touch every cluster
+    let touched = grid.cluster_ranges().iter().filter(|r| r[1] > 0).count();
+    assert!(touched > 0 && touched < grid.cluster_count());
+}
+
+#[test]
+fn test_light_behind_camera_is_culled() {
+    let mut grid = LightClusterGrid::new(LightClusterConfig::default()).unwrap();
+    let mut scene = Scene::new();
+    scene.create_light(point_light(Vec3::new(0.0, 0.0, 20.0), 2.0));
+
+    grid.build(&scene, &create_perspective_camera(), NEAR, FAR).unwrap();
+    assert!(grid.light_indices().is_empty());
+}
+
+#[test]
+fn test_disabled_light_is_skipped() {
+    let mut grid = LightClusterGrid::new(LightClusterConfig::default()).unwrap();
+    let mut scene = Scene::new();
+    let key = scene.create_light(point_light(Vec3::new(0.0, 0.0, -10.0), 2.0));
+    scene.set_light_enabled(key, false);
+
+    grid.build(&scene, &create_perspective_camera(), NEAR, FAR).unwrap();
+    assert!(grid.light_indices().is_empty());
+}
+
+#[test]
+fn test_max_lights_per_cluster_is_respected() {
+    let config = LightClusterConfig { max_lights_per_cluster: 2, ..Default::default() };
+    let mut grid = LightClusterGrid::new(config).unwrap();
+    let mut scene = Scene::new();
+    for _ in 0..5 {
+        scene.create_light(point_light(Vec3::new(0.0, 0.0, -10.0), 2.0));
+    }
+
+    grid.build(&scene, &create_perspective_camera(), NEAR, FAR).unwrap();
+    let cluster = grid.cluster_for_view_position(Vec3::new(0.0, 0.0, -10.0)).unwrap();
+    assert_eq!(grid.cluster_lights(cluster).len(), 2);
+}
+
+#[test]
+fn test_rebuild_clears_previous_frame() {
+    let mut grid = LightClusterGrid::new(LightClusterConfig::default()).unwrap();
+    let mut scene = Scene::new();
+    let key = scene.create_light(point_light(Vec3::new(0.0, 0.0, -10.0), 2.0));
+    let camera = create_perspective_camera();
+
+    grid.build(&scene, &camera, NEAR, FAR).unwrap();
+    assert!(!grid.light_indices().is_empty());
+
+    scene.remove_light(key);
+    let _ = scene.removed_lights();
+    grid.build(&scene, &camera, NEAR, FAR).unwrap();
+    assert!(grid.light_indices().is_empty());
+    assert!(grid.cluster_ranges().iter().all(|r| r[1] == 0));
+}
+
+#[test]
+fn test_cluster_ranges_are_contiguous() {
+    let mut grid = LightClusterGrid::new(LightClusterConfig::default()).unwrap();
+    let mut scene = Scene::new();
+    scene.create_light(point_light(Vec3::new(-3.0, 1.0, -8.0), 4.0));
+    scene.create_light(point_light(Vec3::new(2.0, -1.0, -15.0), 6.0));
+
+    grid.build(&scene, &create_perspective_camera(), NEAR, FAR).unwrap();
+
+    let mut expected_offset = 0;
+    for &[offset, count] in grid.cluster_ranges() {
+        assert_eq!(offset, expected_offset);
+        expected_offset += count;
+    }
+    assert_eq!(expected_offset as usize, grid.light_indices().len());
+}
+
+// ============================================================================
+// Tests: upload
+// ============================================================================
+
+#[test]
+fn test_upload_fits_max_light_index_count() {
+    let config = LightClusterConfig { max_lights_per_cluster: 4, ..Default::default() };
+    let mut grid = LightClusterGrid::new(config).unwrap();
+    let mut scene = Scene::new();
+    for _ in 0..8 {
+        scene.create_light(point_light(Vec3::new(0.0, 0.0, -10.0), 50.0));
+    }
+    grid.build(&scene, &create_perspective_camera(), NEAR, FAR).unwrap();
+    assert!(grid.light_indices().len() <= grid.max_light_index_count());
+
+    let [frame, clusters, indices] = create_cluster_buffers(&grid, grid.max_light_index_count() as u32);
+    assert!(grid.upload(&frame, &clusters, &indices).is_ok());
+}
+
+#[test]
+fn test_upload_before_build_is_accepted() {
+    let grid = LightClusterGrid::new(LightClusterConfig::default()).unwrap();
+    let [frame, clusters, indices] = create_cluster_buffers(&grid, 1);
+    assert!(grid.upload(&frame, &clusters, &indices).is_ok());
+}
+
+#[test]
+fn test_upload_rejects_mismatched_buffers() {
+    let mut grid = LightClusterGrid::new(LightClusterConfig::default()).unwrap();
+    let mut scene = Scene::new();
+    scene.create_light(point_light(Vec3::new(0.0, 0.0, -10.0), 2.0));
+    grid.build(&scene, &create_perspective_camera(), NEAR, FAR).unwrap();
+    let [frame, clusters, indices] = create_cluster_buffers(&grid, grid.light_indices().len() as u32);
+
+    // Too few light indices, wrong cluster count, frame without the grid fields
+    let [_, _, short_indices] = create_cluster_buffers(&grid, grid.light_indices().len() as u32 - 1);
+    assert!(grid.upload(&frame, &clusters, &short_indices).is_err());
+    let small = LightClusterGrid::new(LightClusterConfig { slices_z: 1, ..Default::default() }).unwrap();
+    let [_, small_clusters, _] = create_cluster_buffers(&small, 1);
+    assert!(grid.upload(&frame, &small_clusters, &indices).is_err());
+    assert!(grid.upload(&clusters, &clusters, &indices).is_err());
+
+    assert!(grid.upload(&frame, &clusters, &indices).is_ok());
+}
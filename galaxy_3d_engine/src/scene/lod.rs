@@ -10,6 +10,162 @@
 //! screen-size metric changes abruptly (camera cut, teleport, spawn).
 //! Residual popping is meant to be masked by dither + TAA at the shading
 //! stage, not by throttling the LOD selection temporally.
+//!
+//! `LodConfig` is the global selection policy applied by the
+//! `ViewDispatcher` on top of the per-submesh thresholds: the metric fed to
+//! the hysteresis (camera screen size or view distance), a LOD bias and the
+//! length of the dithered cross-fade.
+//!
+//! Cross-fade: after a LOD switch, the previous and the new LOD are both
+//! drawn for `cross_fade_frames` frames. Each draw carries a `LodFade`,
+//! packed into the upper bits of the draw slot the shader receives
+//! (`LodFade::encode`). The fragment shader compares a screen-space dither
+//! value against the fade progress and discards with complementary masks,
+//! so every pixel is covered by exactly one of the two LODs:
+//!
+//! ```glsl
+//! uint slot  = drawSlot & 0x00FFFFFFu;            // LOD_FADE_SLOT_MASK
+//! uint level = (drawSlot >> 24) & 0x7Fu;          // 0: no fade
+//! if (level != 0u) {
+//!     bool fadingOut = (drawSlot & 0x80000000u) != 0u;
+//!     bool covered = dither(gl_FragCoord.xy) < float(level) / 128.0;
+//!     if (covered == fadingOut) discard;
+//! }
+//! ```
+
+/// Default `LodConfig::bias` (thresholds used as authored)
+pub const DEFAULT_LOD_BIAS: f32 = 1.0;
+
+/// Default reference focal length of `LodMetric::Distance`, in pixels per
+/// world unit at a depth of one unit: a 1080-pixel-high viewport with a
+/// 60 degree vertical field of view (`1080 / (2 * tan(30 deg))`)
+pub const DEFAULT_LOD_PIXELS_PER_UNIT: f32 = 935.307;
+
+/// Default `LodConfig::cross_fade_frames` (LOD switches pop; the shaders
+/// need not decode `LodFade`)
+pub const DEFAULT_LOD_CROSS_FADE_FRAMES: u8 = 0;
+
+/// Bits of an encoded draw slot holding the draw slot itself. Draw slots
+/// must stay below `1 << 24` when the cross-fade is on.
+pub const LOD_FADE_SLOT_MASK: u32 = 0x00FF_FFFF;
+/// Shift of the 7-bit fade level in an encoded draw slot
+pub const LOD_FADE_LEVEL_SHIFT: u32 = 24;
+/// Number of fade steps: level `l` (1 to `LOD_FADE_LEVELS - 1`) covers a
+/// fraction `l / LOD_FADE_LEVELS` of the pixels with the incoming LOD
+pub const LOD_FADE_LEVELS: u32 = 128;
+/// Bit of an encoded draw slot set on the draws of the outgoing LOD
+pub const LOD_FADE_OUT_BIT: u32 = 1 << 31;
+
+/// Metric compared against the `(drop, raise)` thresholds, in pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LodMetric {
+    /// Projected diameter through the view's camera (projection and
+    /// viewport): zooming in or raising the resolution selects finer LODs.
+    ScreenSize,
+    /// Projected diameter through a fixed reference projection
+    /// (`camera::reference_sphere_diameter`): the LOD depends on the view
+    /// distance only.
+    Distance {
+        /// Reference focal length, in pixels per world unit at depth one
+        pixels_per_unit: f32,
+    },
+}
+
+/// Global LOD selection policy (see module docs)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LodConfig {
+    /// Metric fed to `apply_hysteresis`
+    pub metric: LodMetric,
+    /// Multiplier applied to the metric before the threshold comparison:
+    /// above 1 keeps finer LODs longer, below 1 switches to coarser LODs
+    /// sooner. Must be positive.
+    pub bias: f32,
+    /// Number of frames during which the previous LOD keeps being drawn,
+    /// dithered out, after a switch (0 = no cross-fade). Above 0, the
+    /// shaders must decode the draw slot (see module docs).
+    pub cross_fade_frames: u8,
+}
+
+impl Default for LodConfig {
+    fn default() -> Self {
+        Self {
+            metric: LodMetric::ScreenSize,
+            bias: DEFAULT_LOD_BIAS,
+            cross_fade_frames: DEFAULT_LOD_CROSS_FADE_FRAMES,
+        }
+    }
+}
+
+/// Dithered cross-fade state of one draw (see module docs)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LodFade {
+    /// Fully drawn
+    #[default]
+    None,
+    /// Incoming LOD, with the transition progress in `[0, 1]`
+    In(f32),
+    /// Outgoing LOD, with the transition progress in `[0, 1]` (the same
+    /// value as the incoming draw, so the dither masks are complementary)
+    Out(f32),
+}
+
+impl LodFade {
+    /// Transition progress after `elapsed` of `frames` cross-fade frames
+    /// (`elapsed` counted from 1)
+    pub fn progress(elapsed: u8, frames: u8) -> f32 {
+        elapsed as f32 / (frames as f32 + 1.0)
+    }
+
+    /// Draw slot value seen by the shader: `draw_slot` with the fade level
+    /// in bits 24..31 and `LOD_FADE_OUT_BIT` on outgoing draws. `None`
+    /// leaves the draw slot unchanged. The progress is quantized to a level
+    /// in `1..LOD_FADE_LEVELS`, so a fading draw is never fully covered
+    /// nor fully discarded.
+    pub fn encode(self, draw_slot: u32) -> u32 {
+        let (progress, out_bit) = match self {
+            LodFade::None => return draw_slot,
+            LodFade::In(progress) => (progress, 0),
+            LodFade::Out(progress) => (progress, LOD_FADE_OUT_BIT),
+        };
+        let scaled = progress * LOD_FADE_LEVELS as f32 + 0.5;
+        // `as` saturates (NaN to 0), the clamp keeps the level non-zero
+        let level = (scaled as u32).clamp(1, LOD_FADE_LEVELS - 1);
+        (draw_slot & LOD_FADE_SLOT_MASK) | (level << LOD_FADE_LEVEL_SHIFT) | out_bit
+    }
+
+    /// Split a value produced by `encode` into the draw slot and the fade
+    /// (progress quantized to the fade level)
+    pub fn decode(value: u32) -> (u32, LodFade) {
+        let level = (value >> LOD_FADE_LEVEL_SHIFT) & (LOD_FADE_LEVELS - 1);
+        if level == 0 {
+            return (value, LodFade::None);
+        }
+        let progress = level as f32 / LOD_FADE_LEVELS as f32;
+        let fade = if value & LOD_FADE_OUT_BIT != 0 {
+            LodFade::Out(progress)
+        } else {
+            LodFade::In(progress)
+        };
+        (value & LOD_FADE_SLOT_MASK, fade)
+    }
+
+    /// Fraction of the pixels this draw covers
+    pub fn coverage(self) -> f32 {
+        match self {
+            LodFade::None => 1.0,
+            LodFade::In(progress) => progress,
+            LodFade::Out(progress) => 1.0 - progress,
+        }
+    }
+}
+
+/// Draw slot value seen by the shader in the LOD coloring debug view
+/// (`DebugViewMode::LodColoring`): `draw_slot` with the LOD index (up to
+/// 127) in the bits of the fade level. No draw cross-fades in that view.
+pub fn encode_debug_lod(draw_slot: u32, lod_index: u8) -> u32 {
+    let lod = (lod_index as u32).min(LOD_FADE_LEVELS - 1);
+    (draw_slot & LOD_FADE_SLOT_MASK) | (lod << LOD_FADE_LEVEL_SHIFT)
+}
 
 /// Select the new LOD index from the previous one, the current screen-space
 /// size of the object, and the per-frontier thresholds.
@@ -84,3 +84,104 @@ fn test_single_frontier_two_lods() {
     assert_eq!(apply_hysteresis(1, 35.0, &t), 1);   // dead zone
     assert_eq!(apply_hysteresis(1, 41.0, &t), 0);
 }
+
+// ============================================================================
+// Reference-grid sweeps
+// ============================================================================
+
+#[test]
+fn test_sweep_result_in_range_and_idempotent() {
+    let t = standard_thresholds();
+    for current in 0..=5u8 {
+        for step in 0..=400 {
+            let size = step as f32 * 0.25;
+            let lod = apply_hysteresis(current, size, &t);
+            assert!(lod as usize <= t.len(), "current={} size={} lod={}", current, size, lod);
+            // A second frame at the same size never changes the LOD
+            assert_eq!(apply_hysteresis(lod, size, &t), lod, "current={} size={}", current, size);
+        }
+    }
+}
+
+#[test]
+fn test_sweep_larger_size_never_gives_coarser_lod() {
+    let t = standard_thresholds();
+    for current in 0..=3u8 {
+        let mut previous = u8::MAX;
+        for step in 0..=400 {
+            let lod = apply_hysteresis(current, step as f32 * 0.25, &t);
+            assert!(lod <= previous, "current={} step={}", current, step);
+            previous = lod;
+        }
+    }
+}
+
+#[test]
+fn test_sizes_exactly_on_thresholds() {
+    let t = standard_thresholds();
+    // Exactly on `drop`: not strictly below, so no drop
+    assert_eq!(apply_hysteresis(0, 50.0, &t), 0);
+    // Exactly on `raise`: not strictly above, so no rise
+    assert_eq!(apply_hysteresis(1, 60.0, &t), 1);
+}
+
+#[test]
+fn test_nan_screen_size_keeps_current_lod() {
+    let t = standard_thresholds();
+    for current in 0..=3u8 {
+        assert_eq!(apply_hysteresis(current, f32::NAN, &t), current);
+    }
+}
+
+// ============================================================================
+// Cross-fade
+// ============================================================================
+
+#[test]
+fn test_lod_fade_none_keeps_draw_slot() {
+    assert_eq!(LodFade::None.encode(0xFFFF_FFFF), 0xFFFF_FFFF);
+    assert_eq!(LodFade::decode(42), (42, LodFade::None));
+    assert_eq!(LodFade::default(), LodFade::None);
+}
+
+#[test]
+fn test_lod_fade_encode_decode_round_trip() {
+    let encoded = LodFade::In(0.25).encode(1234);
+    assert_eq!(encoded & LOD_FADE_SLOT_MASK, 1234);
+    assert_eq!(encoded >> LOD_FADE_LEVEL_SHIFT, 32);
+    assert_eq!(LodFade::decode(encoded), (1234, LodFade::In(0.25)));
+
+    let encoded = LodFade::Out(0.25).encode(1234);
+    assert_ne!(encoded & LOD_FADE_OUT_BIT, 0);
+    assert_eq!(LodFade::decode(encoded), (1234, LodFade::Out(0.25)));
+}
+
+#[test]
+fn test_lod_fade_level_never_reaches_bounds() {
+    for progress in [0.0, -1.0, f32::NAN] {
+        let (_, fade) = LodFade::decode(LodFade::In(progress).encode(0));
+        assert_eq!(fade, LodFade::In(1.0 / LOD_FADE_LEVELS as f32));
+    }
+    let (_, fade) = LodFade::decode(LodFade::Out(1.0).encode(0));
+    assert_eq!(fade, LodFade::Out((LOD_FADE_LEVELS - 1) as f32 / LOD_FADE_LEVELS as f32));
+}
+
+#[test]
+fn test_lod_fade_coverage_is_complementary() {
+    for elapsed in 1..=4 {
+        let progress = LodFade::progress(elapsed, 4);
+        assert!(progress > 0.0 && progress < 1.0);
+        let total = LodFade::In(progress).coverage() + LodFade::Out(progress).coverage();
+        assert!((total - 1.0).abs() < 1e-6);
+    }
+    assert_eq!(LodFade::None.coverage(), 1.0);
+}
+
+#[test]
+fn test_encode_debug_lod_replaces_fade_bits() {
+    let value = encode_debug_lod(0xABCDEF | LOD_FADE_OUT_BIT, 3);
+    assert_eq!(value & LOD_FADE_SLOT_MASK, 0xABCDEF);
+    assert_eq!(value >> LOD_FADE_LEVEL_SHIFT, 3);
+    assert_eq!(encode_debug_lod(7, 0), 7);
+    assert_eq!(encode_debug_lod(0, 200) >> LOD_FADE_LEVEL_SHIFT, LOD_FADE_LEVELS - 1);
+}
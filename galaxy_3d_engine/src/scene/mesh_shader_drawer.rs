@@ -0,0 +1,341 @@
+/// Mesh shader drawing.
+///
+/// `MeshShaderDrawer` is a `Drawer` that draws the meshlets of a geometry
+/// (`Geometry::build_meshlets`) with a mesh shader pipeline: a task shader
+/// culls the meshlets of a draw (frustum and normal cone), a mesh shader
+/// fetches their vertices from storage buffers (vertex pulling) and emits
+/// the triangles. Everything else goes through a `ForwardDrawer`:
+///
+/// - devices without mesh shaders (`GraphicsDevice::supports_mesh_shaders`),
+/// - LODs without meshlets, geometries with morph targets,
+/// - alpha-tested and transparent material passes,
+/// - debug views (they need per-material pipeline variants).
+///
+/// The mesh path draws every item with the same pipeline, so its fragment
+/// shader replaces the material fragment shader; it reads the material
+/// through the instance data (set 1) like the standard shaders. Meshlet
+/// draws are emitted before the fallback draws.
+///
+/// Shader contract (set 1 is the pass binding group, as for the
+/// `ForwardDrawer`):
+///
+/// ```glsl
+/// layout(push_constant) uniform MeshDraw {
+///     uint drawSlot;       // LOD fade in the upper bits (LodFade::encode)
+///     uint meshletOffset;  // first meshlet of the LOD
+///     uint meshletCount;
+/// };
+/// layout(std430, set = 2, binding = 0) readonly buffer Meshlets { Meshlet meshlets[]; };
+/// layout(std430, set = 2, binding = 1) readonly buffer MeshletVertices { uint meshletVertices[]; };
+/// layout(std430, set = 2, binding = 2) readonly buffer MeshletTriangles { uint meshletTriangles[]; };
+/// layout(std430, set = 2, binding = 3) readonly buffer Vertices { float vertexData[]; };
+///
+/// // task: one invocation per meshlet, MESHLET_TASK_GROUP_SIZE per group
+/// taskPayloadSharedEXT uint payload[MESHLET_TASK_GROUP_SIZE];
+/// uint i = gl_GlobalInvocationID.x;
+/// bool visible = false;
+/// if (i < meshletCount) {
+///     Meshlet m = meshlets[meshletOffset + i];
+///     Instance inst = instances[drawSlot & 0x00FFFFFFu];
+///     vec3 camera = (inst.inverseWorld * frame.cameraPosition).xyz;
+///     vec3 toCenter = m.center - camera;
+///     bool backfacing = dot(toCenter, m.coneAxis) >= m.coneCutoff * length(toCenter) + m.radius;
+///     visible = !backfacing && sphereInFrustum(frame.viewProjection * inst.world, m.center, m.radius);
+/// }
+/// uint slot = subgroupExclusiveAdd(visible ? 1u : 0u);  // one subgroup per group
+/// if (visible) payload[slot] = meshletOffset + i;
+/// EmitMeshTasksEXT(subgroupAdd(visible ? 1u : 0u), 1, 1);
+///
+/// // mesh: one group per surviving meshlet
+/// Meshlet m = meshlets[payload[gl_WorkGroupID.x]];
+/// uint vertex = meshletVertices[m.vertexOffset + v];   // vertex buffer index
+/// uint byte = m.triangleOffset + 3 * t + corner;       // local vertex index
+/// uint local = (meshletTriangles[byte / 4] >> (8 * (byte % 4))) & 0xFFu;
+/// ```
+///
+/// Without a task shader, the mesh shader runs one group per meshlet
+/// (`meshletOffset + gl_WorkGroupID.x`) and nothing is culled.
+
+use std::sync::Arc;
+use rustc_hash::FxHashMap;
+use crate::error::Result;
+use crate::engine::Engine;
+use crate::graphics_device::{
+    self, BindingGroup, BindingResource, CommandList, DebugViewMode, DynamicRenderState,
+    GraphicsDevice, PipelineDesc, Shader, ShaderStageFlags,
+};
+use crate::resource::resource_manager::{GeometryKey, PassInfo, ResourceManager};
+use crate::resource::RenderQueueClass;
+use super::drawer::{Drawer, ForwardDrawer};
+use super::render_view::{RenderView, VisibleSubMesh};
+use super::scene::Scene;
+
+/// Descriptor set of the meshlet buffers of the drawn geometry
+pub const MESHLET_BINDING_SET: u32 = 2;
+/// Meshlets handled by one task shader workgroup (`local_size_x`)
+pub const MESHLET_TASK_GROUP_SIZE: u32 = 32;
+
+/// One meshlet draw of the mesh path
+#[derive(Debug, Clone, Copy)]
+struct MeshletDraw {
+    geometry_key: GeometryKey,
+    draw_slot: u32,
+    meshlet_offset: u32,
+    meshlet_count: u32,
+    render_state: DynamicRenderState,
+    render_state_sig: u16,
+}
+
+/// Meshlet binding group of a geometry, with the meshlet buffer it was
+/// created for (rebuilt meshlets get new buffers)
+struct MeshletBindingGroup {
+    meshlets: Arc<dyn graphics_device::Buffer>,
+    binding_group: Arc<dyn BindingGroup>,
+}
+
+/// Draws meshlets with a mesh shader pipeline, other items with a
+/// `ForwardDrawer` (see module docs).
+pub struct MeshShaderDrawer {
+    /// None when the device has no mesh shaders
+    pipeline: Option<Arc<dyn graphics_device::Pipeline>>,
+    has_task_shader: bool,
+    fallback: ForwardDrawer,
+    /// Items left to the fallback, reused frame to frame
+    fallback_view: Option<RenderView>,
+    /// Reused meshlet draw list
+    draws: Vec<MeshletDraw>,
+    binding_groups: FxHashMap<GeometryKey, MeshletBindingGroup>,
+}
+
+impl MeshShaderDrawer {
+    /// Create the mesh pipeline from the shaders, or fall back to
+    /// `fallback` for everything when the device has no mesh shaders.
+    ///
+    /// `desc.vertex_layout` is ignored (see
+    /// `GraphicsDevice::create_mesh_pipeline`).
+    pub fn new(
+        graphics_device: &mut dyn GraphicsDevice,
+        desc: PipelineDesc,
+        task_shader: Option<&Arc<dyn Shader>>,
+        mesh_shader: &Arc<dyn Shader>,
+        fragment_shader: &Arc<dyn Shader>,
+        fallback: ForwardDrawer,
+    ) -> Result<Self> {
+        if !graphics_device.supports_mesh_shaders() {
+            crate::engine_info!("galaxy3d::MeshShaderDrawer",
+                "Mesh shaders not supported, drawing with the ForwardDrawer");
+            return Ok(Self::fallback_only(fallback));
+        }
+        let pipeline = graphics_device.create_mesh_pipeline(desc, task_shader, mesh_shader, fragment_shader)?;
+        Ok(Self {
+            pipeline: Some(pipeline),
+            has_task_shader: task_shader.is_some(),
+            ..Self::fallback_only(fallback)
+        })
+    }
+
+    /// Draw everything with `fallback`
+    pub fn fallback_only(fallback: ForwardDrawer) -> Self {
+        Self {
+            pipeline: None,
+            has_task_shader: false,
+            fallback,
+            fallback_view: None,
+            draws: Vec::new(),
+            binding_groups: FxHashMap::default(),
+        }
+    }
+
+    /// Whether meshlets are drawn with the mesh pipeline
+    pub fn is_mesh_path_active(&self) -> bool {
+        self.pipeline.is_some()
+    }
+
+    pub fn fallback(&self) -> &ForwardDrawer {
+        &self.fallback
+    }
+
+    pub fn fallback_mut(&mut self) -> &mut ForwardDrawer {
+        &mut self.fallback
+    }
+
+    /// Meshlet draw of an item, None when it must go through the fallback
+    fn meshlet_draw(
+        scene: &Scene,
+        rm: &mut ResourceManager,
+        item: &VisibleSubMesh,
+    ) -> Option<MeshletDraw> {
+        let inst = scene.render_instance(item.key)?;
+        let render_sm = inst.sub_mesh(item.submesh_index as usize)?;
+        let sm_pass = render_sm.pass_by_index(item.pass_index as usize)?;
+        let geo = rm.geometry(inst.geometry())?;
+        if geo.meshlet_buffers().is_none() || geo.morph_target_buffer().is_some() {
+            return None;
+        }
+        let lod = geo.mesh(inst.geometry_mesh_id())?
+            .submesh(render_sm.geometry_submesh_id())?
+            .lod(item.lod_index as usize)?;
+        if lod.meshlet_count() == 0 {
+            return None;
+        }
+        let (meshlet_offset, meshlet_count) = (lod.meshlet_offset(), lod.meshlet_count());
+        let mat_pass = rm.material(sm_pass.material())?
+            .pass(sm_pass.material_pass_index())?;
+        if mat_pass.render_queue() != RenderQueueClass::Opaque {
+            return None;
+        }
+        let render_state = *mat_pass.render_state();
+        let render_state_sig = mat_pass.render_state_signature_id();
+        rm.record_material_texture_usage(sm_pass.material(), sm_pass.material_pass_index());
+
+        Some(MeshletDraw {
+            geometry_key: inst.geometry(),
+            draw_slot: item.lod_fade.encode(render_sm.draw_slot()),
+            meshlet_offset,
+            meshlet_count,
+            render_state,
+            render_state_sig,
+        })
+    }
+
+    /// Meshlet binding group of a geometry, created on first use
+    fn binding_group(
+        &mut self,
+        rm: &ResourceManager,
+        pipeline: &Arc<dyn graphics_device::Pipeline>,
+        geometry_key: GeometryKey,
+    ) -> Result<Arc<dyn BindingGroup>> {
+        // The draw was built from this geometry, with meshlet buffers,
+        // under the same `rm` lock
+        let geo = rm.geometry(geometry_key).unwrap();
+        let buffers = geo.meshlet_buffers().unwrap();
+        if let Some(cached) = self.binding_groups.get(&geometry_key) {
+            if Arc::ptr_eq(&cached.meshlets, &buffers.meshlets) {
+                return Ok(Arc::clone(&cached.binding_group));
+            }
+        }
+        let gd_arc = Engine::graphics_device("main")?;
+        let binding_group = gd_arc.lock().unwrap().create_binding_group(pipeline, MESHLET_BINDING_SET, &[
+            BindingResource::StorageBuffer(&*buffers.meshlets),
+            BindingResource::StorageBuffer(&*buffers.vertices),
+            BindingResource::StorageBuffer(&*buffers.triangles),
+            BindingResource::StorageBuffer(&**geo.vertex_buffer()),
+        ])?;
+        self.binding_groups.insert(geometry_key, MeshletBindingGroup {
+            meshlets: Arc::clone(&buffers.meshlets),
+            binding_group: Arc::clone(&binding_group),
+        });
+        Ok(binding_group)
+    }
+
+    /// Mesh workgroups of a draw of `meshlet_count` meshlets
+    fn group_count(&self, meshlet_count: u32) -> u32 {
+        if self.has_task_shader {
+            meshlet_count.div_ceil(MESHLET_TASK_GROUP_SIZE)
+        } else {
+            meshlet_count
+        }
+    }
+}
+
+impl Drawer for MeshShaderDrawer {
+    fn draw(
+        &mut self,
+        scene: &mut Scene,
+        view: &RenderView,
+        cmd: &mut dyn CommandList,
+        pass_info: &PassInfo,
+        binding_group: &Arc<dyn BindingGroup>,
+        bind_textures: bool,
+    ) -> Result<()> {
+        let Some(pipeline) = self.pipeline.clone() else {
+            return self.fallback.draw(scene, view, cmd, pass_info, binding_group, bind_textures);
+        };
+
+        // Split the items between the mesh path and the fallback
+        if self.fallback_view.as_ref().is_none_or(|fallback_view| fallback_view.pass_type() != view.pass_type()) {
+            self.fallback_view = Some(RenderView::with_capacity(view.camera().clone(), view.pass_type(), view.len()));
+        }
+        let fallback_view = self.fallback_view.as_mut().unwrap();
+        fallback_view.clear();
+        fallback_view.set_camera(view.camera().clone());
+        fallback_view.set_foveation(view.foveation().copied())?;
+        self.draws.clear();
+        {
+            let rm_arc = Engine::resource_manager()?;
+            let mut rm = rm_arc.lock().unwrap();
+            let debug_view = rm.debug_view();
+            for item in view.items() {
+                let draw = if debug_view == DebugViewMode::None {
+                    Self::meshlet_draw(scene, &mut rm, item)
+                } else {
+                    None
+                };
+                match draw {
+                    Some(draw) => self.draws.push(draw),
+                    None => fallback_view.push(*item),
+                }
+            }
+
+            if !self.draws.is_empty() {
+                let mut draws = std::mem::take(&mut self.draws);
+                draws.sort_unstable_by_key(|draw| (draw.render_state_sig, draw.geometry_key));
+                let result = self.emit(&draws, &rm, &pipeline, cmd, view, binding_group, bind_textures);
+                self.draws = draws;
+                result?;
+            }
+        }
+
+        let fallback_view = self.fallback_view.as_ref().unwrap();
+        self.fallback.draw(scene, fallback_view, cmd, pass_info, binding_group, bind_textures)
+    }
+}
+
+impl MeshShaderDrawer {
+    /// Emit sorted meshlet draws with state tracking
+    #[allow(clippy::too_many_arguments)]
+    fn emit(
+        &mut self,
+        draws: &[MeshletDraw],
+        rm: &ResourceManager,
+        pipeline: &Arc<dyn graphics_device::Pipeline>,
+        cmd: &mut dyn CommandList,
+        view: &RenderView,
+        binding_group: &Arc<dyn BindingGroup>,
+        bind_textures: bool,
+    ) -> Result<()> {
+        let camera = view.camera();
+        cmd.set_viewport(*camera.viewport())?;
+        cmd.set_scissor(camera.effective_scissor())?;
+        cmd.bind_pipeline(pipeline)?;
+        if bind_textures {
+            cmd.bind_textures()?;
+        }
+        cmd.bind_binding_group(pipeline, binding_group.set_index(), binding_group)?;
+        let pc_flags = pipeline.reflection().push_constants().first()
+            .map(|pc| pc.stage_flags)
+            .unwrap_or(ShaderStageFlags::TASK_MESH_FRAGMENT);
+
+        let mut last_geometry_key = None;
+        let mut last_render_state_sig = None;
+        for draw in draws {
+            if last_geometry_key != Some(draw.geometry_key) {
+                let meshlet_group = self.binding_group(rm, pipeline, draw.geometry_key)?;
+                cmd.bind_binding_group(pipeline, MESHLET_BINDING_SET, &meshlet_group)?;
+                last_geometry_key = Some(draw.geometry_key);
+            }
+            if last_render_state_sig != Some(draw.render_state_sig) {
+                cmd.set_dynamic_state(&draw.render_state)?;
+                last_render_state_sig = Some(draw.render_state_sig);
+            }
+            let push_constants = [draw.draw_slot, draw.meshlet_offset, draw.meshlet_count, 0];
+            cmd.push_constants(pc_flags, 0, bytemuck::cast_slice(&push_constants))?;
+            cmd.draw_mesh_tasks(self.group_count(draw.meshlet_count), 1, 1)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "mesh_shader_drawer_tests.rs"]
+mod tests;
@@ -0,0 +1,225 @@
+use super::*;
+use serial_test::serial;
+use glam::Mat4;
+use crate::graphics_device::{
+    BufferFormat, IndexType, PolygonMode, PrimitiveTopology, SampleCount, ShaderStage,
+    TextureFormat, VertexAttribute, VertexBinding, VertexInputRate, VertexLayout,
+};
+use crate::graphics_device::mock_graphics_device::{MockBindingGroup, MockCommandList, MockGraphicsDevice};
+use crate::render_graph::test_helpers::setup_engine;
+use crate::resource::geometry::{GeometryDesc, GeometryMeshDesc, GeometrySubMeshDesc, GeometrySubMeshLODDesc};
+use crate::resource::material::{MaterialDesc, MaterialPassDesc};
+use crate::resource::mesh::{MeshDesc, MeshSubMeshDesc, GeometryMeshRef, GeometrySubMeshRef};
+use crate::resource::shader::ShaderDesc;
+use crate::resource::MeshletConfig;
+use crate::scene::{LodFade, VisibleSubMesh};
+use crate::scene::scene_test_helpers::{create_test_aabb, create_test_camera};
+
+/// Quad mesh drawn with a material of the given queue, with or without
+/// meshlets. Returns a scene holding one instance of it.
+fn make_scene(render_queue: RenderQueueClass, meshlets: bool) -> Scene {
+    let rm_arc = Engine::resource_manager().unwrap();
+    let gd_arc = Engine::graphics_device("main").unwrap();
+    let mut rm = rm_arc.lock().unwrap();
+
+    let layout = VertexLayout {
+        bindings: vec![VertexBinding { binding: 0, stride: 8, input_rate: VertexInputRate::Vertex }],
+        attributes: vec![VertexAttribute { location: 0, binding: 0, format: BufferFormat::R32G32_SFLOAT, offset: 0 }],
+    };
+    let positions = [[0.0f32, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+    let indices = [0u16, 1, 2, 2, 3, 0];
+    let geo_key = rm.create_geometry("geo".to_string(), GeometryDesc {
+        name: "geo".to_string(),
+        graphics_device: gd_arc.clone(),
+        vertex_data: positions.iter().flatten().flat_map(|v| v.to_ne_bytes()).collect::<Vec<u8>>().into(),
+        index_data: Some(indices.iter().flat_map(|i| i.to_ne_bytes()).collect::<Vec<u8>>().into()),
+        vertex_layout: layout,
+        index_type: IndexType::U16,
+        morph_targets: Vec::new(),
+        meshes: vec![GeometryMeshDesc {
+            name: "quad".to_string(),
+            submeshes: vec![GeometrySubMeshDesc {
+                name: "main".to_string(),
+                lods: vec![GeometrySubMeshLODDesc {
+                    vertex_offset: 0, vertex_count: 4,
+                    index_offset: 0, index_count: 6,
+                    topology: PrimitiveTopology::TriangleList,
+                }],
+                lod_thresholds: Vec::new(),
+            }],
+        }],
+    }).unwrap();
+    if meshlets {
+        rm.build_geometry_meshlets(geo_key, &MeshletConfig::default()).unwrap();
+    }
+
+    let vk = rm.create_shader("vert".to_string(),
+        ShaderDesc { code: &[], stage: ShaderStage::Vertex, entry_point: "main".to_string() },
+        &mut *gd_arc.lock().unwrap()).unwrap();
+    let fk = rm.create_shader("frag".to_string(),
+        ShaderDesc { code: &[], stage: ShaderStage::Fragment, entry_point: "main".to_string() },
+        &mut *gd_arc.lock().unwrap()).unwrap();
+    let mk = rm.create_material("m".to_string(), MaterialDesc {
+        passes: vec![MaterialPassDesc {
+            pass_type: 0,
+            fragment_shader: fk, color_blend: Default::default(), polygon_mode: PolygonMode::Fill,
+            textures: vec![],
+            params: vec![],
+            render_state: None,
+            engine_features: Default::default(),
+            render_queue: Some(render_queue),
+        }],
+    }, &*gd_arc.lock().unwrap()).unwrap();
+    let mesh_key = rm.create_mesh("mesh".to_string(), MeshDesc {
+        geometry: geo_key,
+        geometry_mesh: GeometryMeshRef::Name("quad".to_string()),
+        submeshes: vec![MeshSubMeshDesc {
+            submesh: GeometrySubMeshRef::Name("main".to_string()),
+            material: mk,
+        }],
+    }).unwrap();
+
+    let mut scene = Scene::new();
+    scene.create_render_instance(mesh_key, Mat4::IDENTITY, create_test_aabb(), vk, &[], &rm).unwrap();
+    scene
+}
+
+fn make_view(scene: &Scene) -> RenderView {
+    let mut view = RenderView::new(create_test_camera(), 0);
+    for key in scene.render_instance_keys() {
+        view.push(VisibleSubMesh {
+            key,
+            distance: 1.0,
+            submesh_index: 0,
+            pass_index: 0,
+            lod_index: 0,
+            lod_fade: LodFade::None,
+        });
+    }
+    view
+}
+
+/// Drawer with a mesh pipeline (task shader or not) on a mesh shader device
+fn make_drawer(task_shader: bool) -> MeshShaderDrawer {
+    let mut gd = MockGraphicsDevice::new();
+    gd.mesh_shaders = true;
+    let shader = |gd: &mut MockGraphicsDevice, stage| gd.create_shader(graphics_device::ShaderDesc {
+        code: &[], stage, entry_point: "main".to_string(), debug_name: None,
+    }).unwrap();
+    let task = shader(&mut gd, ShaderStage::Task);
+    let mesh = shader(&mut gd, ShaderStage::Mesh);
+    let fragment = shader(&mut gd, ShaderStage::Fragment);
+    let desc = PipelineDesc {
+        vertex_layout: VertexLayout { bindings: vec![], attributes: vec![] },
+        topology: PrimitiveTopology::TriangleList,
+        rasterization: Default::default(),
+        color_blend: Default::default(),
+        multisample: Default::default(),
+        color_formats: vec![TextureFormat::R8G8B8A8_UNORM],
+        depth_format: None,
+        engine_features: Default::default(),
+        debug_name: None,
+        dynamic_uniform_buffers: Vec::new(),
+    };
+    let task = task_shader.then_some(&task);
+    MeshShaderDrawer::new(&mut gd, desc, task, &mesh, &fragment, ForwardDrawer::new()).unwrap()
+}
+
+fn draw(drawer: &mut MeshShaderDrawer, scene: &mut Scene) -> Vec<String> {
+    let view = make_view(scene);
+    let pass_info = PassInfo::new(vec![TextureFormat::R8G8B8A8_UNORM], None, SampleCount::S1);
+    let binding_group: Arc<dyn BindingGroup> =
+        Arc::new(MockBindingGroup::new("pass_bg".to_string(), 1));
+    let mut cmd = MockCommandList::new();
+    drawer.draw(scene, &view, &mut cmd, &pass_info, &binding_group, true).unwrap();
+    cmd.commands
+}
+
+// ============================================================================
+// Construction
+// ============================================================================
+
+#[test]
+fn test_new_without_mesh_shader_support() {
+    let mut gd = MockGraphicsDevice::new();
+    let shader = gd.create_shader(graphics_device::ShaderDesc {
+        code: &[], stage: ShaderStage::Mesh, entry_point: "main".to_string(), debug_name: None,
+    }).unwrap();
+    let desc = PipelineDesc {
+        vertex_layout: VertexLayout { bindings: vec![], attributes: vec![] },
+        topology: PrimitiveTopology::TriangleList,
+        rasterization: Default::default(),
+        color_blend: Default::default(),
+        multisample: Default::default(),
+        color_formats: vec![],
+        depth_format: None,
+        engine_features: Default::default(),
+        debug_name: None,
+        dynamic_uniform_buffers: Vec::new(),
+    };
+    let drawer = MeshShaderDrawer::new(&mut gd, desc, None, &shader, &shader, ForwardDrawer::new()).unwrap();
+    assert!(!drawer.is_mesh_path_active());
+    assert!(make_drawer(true).is_mesh_path_active());
+}
+
+#[test]
+fn test_group_count() {
+    assert_eq!(make_drawer(true).group_count(1), 1);
+    assert_eq!(make_drawer(true).group_count(MESHLET_TASK_GROUP_SIZE + 1), 2);
+    // Without task shader: one mesh workgroup per meshlet
+    assert_eq!(make_drawer(false).group_count(40), 40);
+}
+
+// ============================================================================
+// Drawing
+// ============================================================================
+
+#[test]
+#[serial]
+fn test_draws_meshlets_with_mesh_pipeline() {
+    setup_engine();
+    let mut scene = make_scene(RenderQueueClass::Opaque, true);
+    let mut drawer = make_drawer(true);
+
+    let commands = draw(&mut drawer, &mut scene);
+    assert_eq!(&commands[..8], [
+        "set_viewport", "set_scissor", "bind_pipeline", "bind_textures",
+        "bind_binding_group", "bind_binding_group", "set_dynamic_state", "push_constants",
+    ]);
+    assert_eq!(commands[8], "draw_mesh_tasks 1");
+    assert!(!commands.iter().any(|c| c.starts_with("draw_indexed")));
+
+    // The meshlet binding group is created once per geometry
+    draw(&mut drawer, &mut scene);
+    assert_eq!(drawer.binding_groups.len(), 1);
+}
+
+#[test]
+#[serial]
+fn test_falls_back_without_meshlets() {
+    setup_engine();
+    let mut scene = make_scene(RenderQueueClass::Opaque, false);
+    let commands = draw(&mut make_drawer(true), &mut scene);
+    assert!(commands.iter().any(|c| c == "draw_indexed"));
+    assert!(!commands.iter().any(|c| c.starts_with("draw_mesh_tasks")));
+}
+
+#[test]
+#[serial]
+fn test_falls_back_for_transparent_materials() {
+    setup_engine();
+    let mut scene = make_scene(RenderQueueClass::Transparent, true);
+    let commands = draw(&mut make_drawer(true), &mut scene);
+    assert!(commands.iter().any(|c| c == "draw_indexed"));
+    assert!(!commands.iter().any(|c| c.starts_with("draw_mesh_tasks")));
+}
+
+#[test]
+#[serial]
+fn test_fallback_only_draws_classic_path() {
+    setup_engine();
+    let mut scene = make_scene(RenderQueueClass::Opaque, true);
+    let commands = draw(&mut MeshShaderDrawer::fallback_only(ForwardDrawer::new()), &mut scene);
+    assert!(commands.iter().any(|c| c == "draw_indexed"));
+    assert!(!commands.iter().any(|c| c.starts_with("draw_mesh_tasks")));
+}
@@ -2,38 +2,96 @@
 //!
 //! Provides scene, render instance management, and rendering strategies
 //! (culling, dispatching, drawing, updating).
+//!
+//! Without the `renderer` feature only `AABB`, `Ray` and the LOD
+//! hysteresis are built.
 
-mod render_instance;
-mod light;
+// Core modules (always built)
+mod aabb;
 mod lod;
-mod scene;
-mod scene_manager;
-mod scene_index;
-mod octree_scene_index;
-mod culler;
-mod drawer;
-mod updater;
-mod render_view;
-mod view_dispatcher;
-mod render_queue;
-
-#[cfg(test)]
-mod scene_test_helpers;
+mod ray;
 
-pub use render_instance::{
-    RenderInstance, RenderInstanceKey, RenderSubMesh, RenderSubMeshPass,
-    VertexShaderOverride,
-    AABB, FLAG_VISIBLE, FLAG_CAST_SHADOW, FLAG_RECEIVE_SHADOW,
+pub use aabb::AABB;
+pub use ray::Ray;
+pub use lod::{
+    apply_hysteresis, encode_debug_lod, LodConfig, LodFade, LodMetric, DEFAULT_LOD_BIAS,
+    DEFAULT_LOD_CROSS_FADE_FRAMES, DEFAULT_LOD_PIXELS_PER_UNIT, LOD_FADE_LEVELS,
+    LOD_FADE_LEVEL_SHIFT, LOD_FADE_OUT_BIT, LOD_FADE_SLOT_MASK,
 };
-pub use render_view::{RenderView, VisibleSubMesh};
-pub use view_dispatcher::ViewDispatcher;
-pub use light::{Light, LightKey, LightType, LightDesc};
-pub use scene::Scene;
-pub use scene_manager::SceneManager;
-pub use scene_index::SceneIndex;
-pub use octree_scene_index::OctreeSceneIndex;
-pub use culler::{CameraCuller, BruteForceCuller, FrustumCuller};
-pub use drawer::{Drawer, ForwardDrawer};
-pub use updater::{Updater, NoOpUpdater, DefaultUpdater};
-pub use render_queue::{RenderQueue, DrawCall, distance_to_u16, build_sort_key};
-pub use lod::apply_hysteresis;
+
+cfg_renderer! {
+    mod render_instance;
+    mod light;
+    mod billboard;
+    mod billboard_action;
+    mod light_cluster;
+    mod environment;
+    mod clock;
+    mod scene;
+    mod scene_node;
+    mod transform_components;
+    mod scene_manager;
+    mod scene_index;
+    mod octree_scene_index;
+    mod culler;
+    mod drawer;
+    mod skybox_drawer;
+    mod mesh_shader_drawer;
+    mod updater;
+    mod render_view;
+    mod view_dispatcher;
+    mod view_list;
+    mod render_queue;
+    mod picking;
+    mod ray_tracing_scene;
+    #[cfg(feature = "manifest")]
+    mod scene_file;
+
+    pub use render_instance::{
+        RenderInstance, RenderInstanceKey, RenderSubMesh, RenderSubMeshPass,
+        VertexShaderOverride,
+        FLAG_VISIBLE, FLAG_CAST_SHADOW, FLAG_RECEIVE_SHADOW,
+    };
+    pub use render_view::{
+        RenderView, VisibleSubMesh, FoveationMask, DEFAULT_RENDER_SCALE, MAX_RENDER_SCALE,
+    };
+    pub use view_dispatcher::ViewDispatcher;
+    pub use view_list::{ViewList, SceneView, instance_pass_name};
+    pub use light::{Light, LightKey, LightType, LightDesc};
+    pub use billboard::{
+        Billboard, BillboardKey, BillboardMode, BillboardDesc, billboard_instance_layout,
+        BILLBOARD_INSTANCE_STRIDE, BILLBOARD_VERTEX_COUNT,
+    };
+    pub(crate) use billboard::resolve_layer_region;
+    pub use billboard_action::BillboardAction;
+    pub use light_cluster::{LightClusterGrid, LightClusterConfig};
+    pub use environment::{SceneEnvironment, Fog, FogMode};
+    pub use clock::{
+        EngineClock, SeededRng, DEFAULT_STEP_DELTA, MAX_FRAME_DELTA, DEFAULT_CLOCK_SEED,
+    };
+    pub use scene::Scene;
+    pub use scene_node::{SceneNode, SceneNodeKey};
+    pub use scene_manager::SceneManager;
+    pub use scene_index::{SceneIndex, RayHit};
+    pub use octree_scene_index::{
+        OctreeSceneIndex, OctreeQueryStats, OCTREE_STRICT_LOOSENESS, OCTREE_MAX_LOOSENESS,
+    };
+    pub use culler::{CameraCuller, BruteForceCuller, FrustumCuller, OcclusionCuller};
+    pub use drawer::{Drawer, ForwardDrawer, ForwardDrawerConfig};
+    pub use skybox_drawer::{SkyboxDrawer, DEFAULT_SKYBOX_INTENSITY};
+    pub use mesh_shader_drawer::{MeshShaderDrawer, MESHLET_BINDING_SET, MESHLET_TASK_GROUP_SIZE};
+    pub use updater::{Updater, NoOpUpdater, DefaultUpdater, HierarchyUpdater};
+    pub use render_queue::{
+        RenderQueue, DrawCall, distance_to_u16, build_sort_key, build_transparent_sort_key,
+    };
+    pub use picking::{
+        Picker, PickResult, resolve_pick_id, pick_id_for_draw_slot,
+        PICKING_TARGET_FORMAT, PICK_ID_NONE, MAX_PICKS_PER_FRAME,
+    };
+    pub use ray_tracing_scene::{RayTracingScene, RT_MASK_VISIBLE, RT_MASK_SHADOW_CASTER};
+    #[cfg(feature = "manifest")]
+    pub use scene_file::{SceneFile, InstanceRecord, VertexShaderOverrideRecord};
+}
+
+#[cfg(test)]
+pub(crate) mod scene_test_helpers;
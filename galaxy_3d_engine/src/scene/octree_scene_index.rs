@@ -15,20 +15,84 @@
 /// Each object stores its world-space position alongside its key inside the
 /// node it belongs to. This allows `query_frustum` to compute view-space depth
 /// for each visible instance without a separate scene lookup.
+///
+/// The root bounds need not be cubic: each node splits its box at its center
+/// on all three axes, so cells keep the aspect ratio of the root (a flat
+/// 4000 x 200 x 4000 terrain gets flat cells). Objects outside the root are
+/// kept in the root node and tested individually on every query. To keep
+/// that set small, the bounds can be replaced (`set_bounds`), fitted to the
+/// indexed objects (`rebuild`), or grown automatically (`set_auto_grow`);
+/// a warning is logged when too many objects fall outside.
+///
+/// Dynamic scenes:
+/// - `update` moves an object to its new node in O(depth), without rebuild.
+/// - Loose octree (`set_looseness`): each node's query bounds are its cell
+///   expanded by a factor, and objects are placed by their center. Large
+///   moving objects then sit in deep nodes instead of piling up in the root.
+/// - Every node tracks the object count of its subtree: queries skip empty
+///   subtrees without classifying their nodes, so underpopulated branches
+///   cost as little as if they were merged into their parent.
+/// - `last_query_stats` reports the work done by the last `query_frustum`.
+///
+/// `query_ray` descends only into the nodes whose (loose) bounds the ray
+/// crosses. Root objects are always tested, so out-of-bounds objects are
+/// found too.
 
-use rustc_hash::FxHashMap;
+use std::sync::Mutex;
+use rustc_hash::{FxHashMap, FxHashSet};
 use glam::Vec3;
 use crate::camera::{Frustum, FrustumTest, VisibleInstance};
-use super::render_instance::{RenderInstanceKey, AABB};
+use super::aabb::AABB;
+use super::ray::Ray;
+use super::render_instance::RenderInstanceKey;
 use super::scene_index::SceneIndex;
 
 /// Index of the root node in the flat node array.
 const ROOT: usize = 0;
 
+/// Maximum number of root doublings for one out-of-bounds object when
+/// auto-growth is enabled (65536x the initial extent)
+const MAX_GROWTH_STEPS: u32 = 16;
+
+/// Out-of-bounds objects are reported once their count reaches this value...
+const OUT_OF_BOUNDS_WARN_MIN_COUNT: usize = 16;
+
+/// ...and this fraction of the indexed objects
+const OUT_OF_BOUNDS_WARN_RATIO: f32 = 0.25;
+
+/// Looseness of a regular (non-loose) octree
+pub const OCTREE_STRICT_LOOSENESS: f32 = 1.0;
+
+/// Largest accepted looseness (nodes as large as 4x their cell)
+pub const OCTREE_MAX_LOOSENESS: f32 = 4.0;
+
+/// Parent index of the root node
+const NO_PARENT: usize = usize::MAX;
+
+/// Work done by one `query_frustum` call
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OctreeQueryStats {
+    /// Nodes reached by the traversal (classified or collected)
+    pub nodes_visited: u32,
+    /// Child subtrees skipped because they hold no object
+    pub empty_subtrees_skipped: u32,
+    /// Objects tested individually against the frustum
+    pub objects_tested: u32,
+    /// Objects reported visible
+    pub objects_visible: u32,
+}
+
 /// A single node in the octree.
 struct OctreeNode {
-    /// World-space AABB of this node
+    /// World-space AABB of this node (its cell)
     aabb: AABB,
+    /// Cell expanded by the looseness: bounds of the objects it may hold,
+    /// used by queries (equal to `aabb` in a strict octree)
+    loose_aabb: AABB,
+    /// Index of the parent node (`NO_PARENT` for the root)
+    parent: usize,
+    /// Objects stored in this node and all its descendants
+    subtree_count: u32,
     /// Index of the first child in the flat array (0 = no children / leaf)
     first_child: usize,
     /// Objects stored in this node, paired with their world-space position
@@ -56,6 +120,16 @@ pub struct OctreeSceneIndex {
     /// Pre-computed subtree sizes indexed by remaining depth.
     /// subtree_sizes[d] = total node count for a subtree of depth d.
     subtree_sizes: Vec<usize>,
+    /// Objects whose AABB is not contained in the root bounds
+    out_of_bounds: FxHashSet<RenderInstanceKey>,
+    /// Grow the root bounds instead of keeping objects outside
+    auto_grow: bool,
+    /// Set once the out-of-bounds warning is logged, until the bounds change
+    out_of_bounds_warned: bool,
+    /// Node bounds expansion factor (`OCTREE_STRICT_LOOSENESS` = regular octree)
+    looseness: f32,
+    /// Statistics of the last query (a mutex: queries take `&self`)
+    last_query_stats: Mutex<OctreeQueryStats>,
 }
 
 impl OctreeSceneIndex {
@@ -67,22 +141,230 @@ impl OctreeSceneIndex {
     /// * `max_depth` - Maximum tree depth (root = 0). Total nodes = (8^(d+1) - 1) / 7.
     ///   Typical values: 4–6 for most scenes.
     pub fn new(world_aabb: AABB, max_depth: u32) -> Self {
+        let subtree_sizes: Vec<usize> = (0..=max_depth).map(Self::total_node_count).collect();
+
+        Self {
+            nodes: Self::build_nodes(&world_aabb, max_depth, OCTREE_STRICT_LOOSENESS),
+            max_depth,
+            object_locations: FxHashMap::default(),
+            subtree_sizes,
+            out_of_bounds: FxHashSet::default(),
+            auto_grow: false,
+            out_of_bounds_warned: false,
+            looseness: OCTREE_STRICT_LOOSENESS,
+            last_query_stats: Mutex::new(OctreeQueryStats::default()),
+        }
+    }
+
+    /// Allocate the node array of a tree covering `world_aabb`
+    fn build_nodes(world_aabb: &AABB, max_depth: u32, looseness: f32) -> Vec<OctreeNode> {
         // Pre-compute total node count: sum of 8^i for i=0..=max_depth
         let total_nodes = Self::total_node_count(max_depth);
         let mut nodes = Vec::with_capacity(total_nodes);
 
         // Build the tree level by level
-        Self::build_recursive(&mut nodes, &world_aabb, 0, max_depth);
+        Self::build_recursive(&mut nodes, world_aabb, NO_PARENT, 0, max_depth, looseness);
 
         debug_assert_eq!(nodes.len(), total_nodes);
+        nodes
+    }
 
-        let subtree_sizes: Vec<usize> = (0..=max_depth).map(Self::total_node_count).collect();
+    /// World-space bounds of the root node
+    pub fn bounds(&self) -> &AABB {
+        &self.nodes[ROOT].aabb
+    }
 
-        Self {
-            nodes,
-            max_depth,
-            object_locations: FxHashMap::default(),
-            subtree_sizes,
+    /// Maximum tree depth (root = 0)
+    pub fn max_depth(&self) -> u32 {
+        self.max_depth
+    }
+
+    /// Number of indexed objects
+    pub fn len(&self) -> usize {
+        self.object_locations.len()
+    }
+
+    /// True if no object is indexed
+    pub fn is_empty(&self) -> bool {
+        self.object_locations.is_empty()
+    }
+
+    /// Number of objects outside the root bounds. They are stored in the
+    /// root node and frustum-tested one by one on every query.
+    pub fn out_of_bounds_count(&self) -> usize {
+        self.out_of_bounds.len()
+    }
+
+    /// Whether an object leaving the bounds grows them (default: false).
+    ///
+    /// Growth doubles the root extent toward the object (keeping the aspect
+    /// ratio and `max_depth`, so cells get coarser) until it contains the
+    /// object, then re-inserts every object. Objects too far away (more
+    /// than `MAX_GROWTH_STEPS` doublings) or with non-finite bounds still
+    /// stay outside.
+    pub fn set_auto_grow(&mut self, auto_grow: bool) {
+        self.auto_grow = auto_grow;
+    }
+
+    /// Whether auto-growth is enabled
+    pub fn auto_grow(&self) -> bool {
+        self.auto_grow
+    }
+
+    /// Turn the tree into a loose octree and re-insert every object.
+    ///
+    /// Each node accepts objects within its cell expanded by `looseness`
+    /// (clamped to `[OCTREE_STRICT_LOOSENESS, OCTREE_MAX_LOOSENESS]`; 2.0
+    /// is the usual choice). Objects are routed by their center, so an
+    /// object smaller than `(looseness - 1) / 2` of a cell never straddles
+    /// its children. Same cost as `set_bounds`.
+    pub fn set_looseness(&mut self, looseness: f32) {
+        let looseness = if looseness.is_finite() {
+            looseness.clamp(OCTREE_STRICT_LOOSENESS, OCTREE_MAX_LOOSENESS)
+        } else {
+            OCTREE_STRICT_LOOSENESS
+        };
+        self.looseness = looseness;
+        let bounds = self.nodes[ROOT].aabb;
+        self.set_bounds(bounds);
+    }
+
+    /// Node bounds expansion factor (`OCTREE_STRICT_LOOSENESS` when not loose)
+    pub fn looseness(&self) -> f32 {
+        self.looseness
+    }
+
+    /// Statistics of the last `query_frustum` call
+    pub fn last_query_stats(&self) -> OctreeQueryStats {
+        *self.last_query_stats.lock().unwrap()
+    }
+
+    /// Replace the root bounds and re-insert every object.
+    ///
+    /// O(objects + nodes); meant for level loads or occasional resizes,
+    /// not per-frame use.
+    pub fn set_bounds(&mut self, world_aabb: AABB) {
+        let objects: Vec<(RenderInstanceKey, Vec3, AABB)> = self.nodes.iter_mut()
+            .flat_map(|node| node.objects.drain(..))
+            .collect();
+        self.nodes = Self::build_nodes(&world_aabb, self.max_depth, self.looseness);
+        self.object_locations.clear();
+        self.out_of_bounds.clear();
+        self.out_of_bounds_warned = false;
+        for (key, world_position, aabb) in objects {
+            self.insert_object(key, world_position, &aabb);
+        }
+    }
+
+    /// Fit the root bounds to the indexed objects and re-insert them.
+    ///
+    /// Objects with non-finite bounds are ignored for the fit. Does
+    /// nothing when no object has finite bounds. Same cost as `set_bounds`.
+    pub fn rebuild(&mut self) {
+        let fitted = self.nodes.iter()
+            .flat_map(|node| node.objects.iter())
+            .filter(|(_, _, aabb)| aabb.is_finite())
+            .fold(None, |acc: Option<AABB>, (_, _, aabb)| Some(match acc {
+                Some(b) => AABB { min: b.min.min(aabb.min), max: b.max.max(aabb.max) },
+                None => *aabb,
+            }));
+        if let Some(bounds) = fitted {
+            self.set_bounds(bounds);
+        }
+    }
+
+    /// Root bounds doubled toward `world_aabb` until they contain it, or
+    /// None if that takes more than `MAX_GROWTH_STEPS` doublings
+    fn grown_bounds(&self, world_aabb: &AABB) -> Option<AABB> {
+        if !world_aabb.is_finite() {
+            return None;
+        }
+        let mut bounds = self.nodes[ROOT].aabb;
+        let target = world_aabb.center();
+        for _ in 0..MAX_GROWTH_STEPS {
+            let size = bounds.max - bounds.min;
+            let center = bounds.center();
+            let toward_min = target.cmplt(center);
+            bounds.min = Vec3::select(toward_min, bounds.min - size, bounds.min);
+            bounds.max = Vec3::select(toward_min, bounds.max, bounds.max + size);
+            if bounds.contains(world_aabb) {
+                return Some(bounds);
+            }
+        }
+        None
+    }
+
+    /// Grow the bounds to index an object outside them, if enabled.
+    ///
+    /// Returns true if the bounds grew (the object is then indexed).
+    fn try_grow(&mut self, key: RenderInstanceKey, world_position: Vec3, world_aabb: &AABB) -> bool {
+        if !self.auto_grow {
+            return false;
+        }
+        match self.grown_bounds(world_aabb) {
+            Some(bounds) => {
+                self.set_bounds(bounds);
+                self.insert_object(key, world_position, world_aabb);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Warn (once per bounds) when many objects fall outside the root
+    fn check_out_of_bounds(&mut self) {
+        let count = self.out_of_bounds.len();
+        if self.out_of_bounds_warned
+            || count < OUT_OF_BOUNDS_WARN_MIN_COUNT
+            || (count as f32) < OUT_OF_BOUNDS_WARN_RATIO * self.object_locations.len() as f32
+        {
+            return;
+        }
+        self.out_of_bounds_warned = true;
+        let bounds = self.nodes[ROOT].aabb;
+        crate::engine_warn!("galaxy3d::OctreeSceneIndex",
+            "{} of {} objects are outside the octree bounds {:?}..{:?}; \
+             use set_bounds(), rebuild() or set_auto_grow(true)",
+            count, self.object_locations.len(), bounds.min, bounds.max);
+    }
+
+    /// Place an object in the tree (the key must not be indexed)
+    fn insert_object(&mut self, key: RenderInstanceKey, world_position: Vec3, world_aabb: &AABB) {
+        // If object is outside the octree bounds, store at root
+        if !self.nodes[ROOT].aabb.contains(world_aabb) {
+            self.push_object(ROOT, key, world_position, world_aabb);
+            self.out_of_bounds.insert(key);
+            return;
+        }
+
+        let node_idx = self.find_target_node(world_aabb);
+        self.push_object(node_idx, key, world_position, world_aabb);
+    }
+
+    /// Store an object in a node and count it in the node's ancestors
+    fn push_object(&mut self, node_idx: usize, key: RenderInstanceKey, world_position: Vec3, world_aabb: &AABB) {
+        self.nodes[node_idx].objects.push((key, world_position, *world_aabb));
+        self.object_locations.insert(key, node_idx);
+        let mut idx = node_idx;
+        while idx != NO_PARENT {
+            self.nodes[idx].subtree_count += 1;
+            idx = self.nodes[idx].parent;
+        }
+    }
+
+    /// Take an object out of its node and its ancestors' counts
+    fn take_object(&mut self, key: RenderInstanceKey) {
+        let Some(node_idx) = self.object_locations.remove(&key) else {
+            return;
+        };
+        let objects = &mut self.nodes[node_idx].objects;
+        if let Some(pos) = objects.iter().position(|(k, _, _)| *k == key) {
+            objects.swap_remove(pos);
+            let mut idx = node_idx;
+            while idx != NO_PARENT {
+                self.nodes[idx].subtree_count -= 1;
+                idx = self.nodes[idx].parent;
+            }
         }
     }
 
@@ -101,27 +383,27 @@ impl OctreeSceneIndex {
     fn build_recursive(
         nodes: &mut Vec<OctreeNode>,
         aabb: &AABB,
+        parent: usize,
         depth: u32,
         max_depth: u32,
+        looseness: f32,
     ) {
         let node_index = nodes.len();
+        let margin = (aabb.max - aabb.min) * ((looseness - 1.0) * 0.5);
+        let loose_aabb = AABB { min: aabb.min - margin, max: aabb.max + margin };
 
-        if depth >= max_depth {
-            // Leaf node: no children
-            nodes.push(OctreeNode {
-                aabb: *aabb,
-                first_child: 0,
-                objects: Vec::new(),
-            });
-            return;
-        }
-
-        // Internal node: reserve slot, then build 8 children
+        // Leaf nodes keep first_child = 0; internal nodes fill it below
         nodes.push(OctreeNode {
             aabb: *aabb,
-            first_child: 0, // will be filled below
+            loose_aabb,
+            parent,
+            subtree_count: 0,
+            first_child: 0,
             objects: Vec::new(),
         });
+        if depth >= max_depth {
+            return;
+        }
 
         let center = (*aabb).center();
         let first_child = nodes.len();
@@ -130,7 +412,7 @@ impl OctreeSceneIndex {
         // 8 children: enumerate all octants
         for octant in 0..8u8 {
             let child_aabb = Self::octant_aabb(aabb, &center, octant);
-            Self::build_recursive(nodes, &child_aabb, depth + 1, max_depth);
+            Self::build_recursive(nodes, &child_aabb, node_index, depth + 1, max_depth, looseness);
         }
     }
 
@@ -162,53 +444,15 @@ impl OctreeSceneIndex {
             | (((point.z >= center.z) as u8) << 2)
     }
 
-    /// Insert an object into the deepest node that fully contains it.
+    /// Find the deepest node that fully contains an AABB (read-only, no tree modification).
     ///
     /// Uses direct octant calculation instead of testing all 8 children:
     /// if both AABB corners (min, max) fall into the same octant, the object
     /// fits entirely in that child. Otherwise it straddles a boundary
-    /// and stays in the current node.
-    fn insert_iterative(
-        &mut self,
-        key: RenderInstanceKey,
-        world_position: Vec3,
-        world_aabb: &AABB,
-    ) -> usize {
-        let mut node_idx = ROOT;
-        let mut depth = 0;
-
-        loop {
-            if depth >= self.max_depth {
-                self.nodes[node_idx].objects.push((key, world_position, *world_aabb));
-                return node_idx;
-            }
-
-            let first_child = self.nodes[node_idx].first_child;
-            if first_child == 0 {
-                self.nodes[node_idx].objects.push((key, world_position, *world_aabb));
-                return node_idx;
-            }
-
-            let center = self.nodes[node_idx].aabb.center();
-            let min_oct = Self::point_octant(&center, &world_aabb.min);
-            let max_oct = Self::point_octant(&center, &world_aabb.max);
-
-            if min_oct != max_oct {
-                // Straddles boundary — stays in current node
-                self.nodes[node_idx].objects.push((key, world_position, *world_aabb));
-                return node_idx;
-            }
-
-            // Both corners in same octant — descend
-            node_idx = first_child + self.subtree_offset(min_oct, self.max_depth - depth - 1);
-            depth += 1;
-        }
-    }
-
-    /// Find the deepest node that fully contains an AABB (read-only, no tree modification).
+    /// and stays in the current node. In a loose octree the child is the
+    /// octant of the AABB center, entered if its loose bounds contain the AABB.
     ///
-    /// Same traversal logic as `insert_iterative` but without pushing into any node.
-    /// Used by `update` to check if an object needs to move.
+    /// Used by insertion, and by `update` to check if an object needs to move.
     fn find_target_node(&self, world_aabb: &AABB) -> usize {
         let mut node_idx = ROOT;
         let mut depth = 0;
@@ -224,14 +468,23 @@ impl OctreeSceneIndex {
             }
 
             let center = self.nodes[node_idx].aabb.center();
-            let min_oct = Self::point_octant(&center, &world_aabb.min);
-            let max_oct = Self::point_octant(&center, &world_aabb.max);
-
-            if min_oct != max_oct {
-                return node_idx;
-            }
+            let octant = if self.looseness > OCTREE_STRICT_LOOSENESS {
+                let octant = Self::point_octant(&center, &world_aabb.center());
+                let child = first_child + self.subtree_offset(octant, self.max_depth - depth - 1);
+                if !self.nodes[child].loose_aabb.contains(world_aabb) {
+                    return node_idx;
+                }
+                octant
+            } else {
+                let min_oct = Self::point_octant(&center, &world_aabb.min);
+                let max_oct = Self::point_octant(&center, &world_aabb.max);
+                if min_oct != max_oct {
+                    return node_idx;
+                }
+                min_oct
+            };
 
-            node_idx = first_child + self.subtree_offset(min_oct, self.max_depth - depth - 1);
+            node_idx = first_child + self.subtree_offset(octant, self.max_depth - depth - 1);
             depth += 1;
         }
     }
@@ -265,6 +518,7 @@ impl OctreeSceneIndex {
         camera_forward: Vec3,
         results: &mut Vec<VisibleInstance>,
         depth: u32,
+        stats: &mut OctreeQueryStats,
     ) {
         // SAFETY: `node_idx` is either ROOT (=0) or `first_child + octant*stride`,
         // both computed so they fall within the depth-first range pre-allocated
@@ -272,12 +526,14 @@ impl OctreeSceneIndex {
         // in `new()`).
         let node = unsafe { self.nodes.get_unchecked(node_idx) };
 
+        stats.nodes_visited += 1;
+
         match classification {
             FrustumTest::Outside => return,
 
             FrustumTest::Inside => {
                 // Everything in this subtree is visible
-                self.collect_all(node_idx, camera_pos, camera_forward, results, depth);
+                self.collect_all(node_idx, camera_pos, camera_forward, results, depth, stats);
                 return;
             }
 
@@ -288,10 +544,12 @@ impl OctreeSceneIndex {
                 // `(inst_pos - camera_pos).dot(camera_forward)` so it can be
                 // hoisted out of the inner loop: `inst_pos.dot(camera_forward) - c_dot`.
                 let c_dot = camera_pos.dot(camera_forward);
+                stats.objects_tested += node.objects.len() as u32;
                 for (key, inst_pos, world_aabb) in &node.objects {
                     if frustum.intersects_aabb(world_aabb) {
                         let view_depth = inst_pos.dot(camera_forward) - c_dot;
                         results.push(VisibleInstance { key: *key, distance: view_depth });
+                        stats.objects_visible += 1;
                     }
                 }
 
@@ -310,11 +568,15 @@ impl OctreeSceneIndex {
                             let child_idx = first_child + (octant as usize) * stride;
                             // SAFETY: `first_child` + 8 * `stride` stays within the
                             // depth-first range allocated for this subtree.
-                            let child_aabb = &unsafe { self.nodes.get_unchecked(child_idx) }.aabb;
-                            let child_class = frustum.classify_aabb(child_aabb);
+                            let child = unsafe { self.nodes.get_unchecked(child_idx) };
+                            if child.subtree_count == 0 {
+                                stats.empty_subtrees_skipped += 1;
+                                continue;
+                            }
+                            let child_class = frustum.classify_aabb(&child.loose_aabb);
                             self.query_recursive(
                                 child_idx, frustum, child_class,
-                                camera_pos, camera_forward, results, depth + 1,
+                                camera_pos, camera_forward, results, depth + 1, stats,
                             );
                         }
                     }
@@ -323,6 +585,35 @@ impl OctreeSceneIndex {
         }
     }
 
+    /// Ray query: test the objects of a node, then recurse into the
+    /// non-empty children whose loose bounds the ray crosses.
+    fn query_ray_recursive(
+        &self,
+        node_idx: usize,
+        ray: &Ray,
+        max_distance: f32,
+        results: &mut Vec<(RenderInstanceKey, f32)>,
+        depth: u32,
+    ) {
+        let node = &self.nodes[node_idx];
+        for (key, _, world_aabb) in &node.objects {
+            if let Some(distance) = ray.intersect_aabb(world_aabb, max_distance) {
+                results.push((*key, distance));
+            }
+        }
+
+        if depth < self.max_depth && node.first_child != 0 {
+            let stride = self.subtree_sizes[(self.max_depth - depth - 1) as usize];
+            for octant in 0..8 {
+                let child_idx = node.first_child + octant * stride;
+                let child = &self.nodes[child_idx];
+                if child.subtree_count != 0 && ray.intersect_aabb(&child.loose_aabb, max_distance).is_some() {
+                    self.query_ray_recursive(child_idx, ray, max_distance, results, depth + 1);
+                }
+            }
+        }
+    }
+
     /// Collect all objects from a node and its entire subtree (no frustum test).
     ///
     /// Used when the parent node's AABB is fully inside the frustum. Each object
@@ -334,10 +625,17 @@ impl OctreeSceneIndex {
         camera_forward: Vec3,
         results: &mut Vec<VisibleInstance>,
         depth: u32,
+        stats: &mut OctreeQueryStats,
     ) {
         // SAFETY: same invariant as `query_recursive` — `node_idx` is always
         // a valid index into the pre-allocated `nodes` vec.
         let node = unsafe { self.nodes.get_unchecked(node_idx) };
+        if node.subtree_count == 0 {
+            stats.empty_subtrees_skipped += 1;
+            return;
+        }
+        stats.nodes_visited += 1;
+        stats.objects_visible += node.objects.len() as u32;
         // Same hoisted invariant as `query_recursive` Partial branch.
         let c_dot = camera_pos.dot(camera_forward);
         for (key, inst_pos, _) in &node.objects {
@@ -354,7 +652,7 @@ impl OctreeSceneIndex {
                 };
                 for octant in 0..8u8 {
                     let child_idx = first_child + (octant as usize) * stride;
-                    self.collect_all(child_idx, camera_pos, camera_forward, results, depth + 1);
+                    self.collect_all(child_idx, camera_pos, camera_forward, results, depth + 1, stats);
                 }
             }
         }
@@ -365,29 +663,32 @@ impl OctreeSceneIndex {
 
 impl SceneIndex for OctreeSceneIndex {
     fn insert(&mut self, key: RenderInstanceKey, world_position: Vec3, world_aabb: &AABB) {
-        // If object is outside the octree bounds, store at root
-        if !self.nodes[ROOT].aabb.contains(world_aabb) {
-            self.nodes[ROOT].objects.push((key, world_position, *world_aabb));
-            self.object_locations.insert(key, ROOT);
+        if !self.nodes[ROOT].aabb.contains(world_aabb)
+            && self.try_grow(key, world_position, world_aabb)
+        {
             return;
         }
-
-        let node_idx = self.insert_iterative(key, world_position, world_aabb);
-        self.object_locations.insert(key, node_idx);
+        self.insert_object(key, world_position, world_aabb);
+        self.check_out_of_bounds();
     }
 
     fn remove(&mut self, key: RenderInstanceKey) {
-        if let Some(node_idx) = self.object_locations.remove(&key) {
-            let objects = &mut self.nodes[node_idx].objects;
-            if let Some(pos) = objects.iter().position(|(k, _, _)| *k == key) {
-                objects.swap_remove(pos);
-            }
-        }
+        self.out_of_bounds.remove(&key);
+        self.take_object(key);
     }
 
     fn update(&mut self, key: RenderInstanceKey, world_position: Vec3, world_aabb: &AABB) {
         let target = if self.nodes[ROOT].aabb.contains(world_aabb) {
+            self.out_of_bounds.remove(&key);
             self.find_target_node(world_aabb)
+        } else if !self.out_of_bounds.contains(&key) {
+            // Leaving the bounds
+            self.remove(key);
+            if !self.try_grow(key, world_position, world_aabb) {
+                self.insert_object(key, world_position, world_aabb);
+                self.check_out_of_bounds();
+            }
+            return;
         } else {
             ROOT
         };
@@ -405,9 +706,8 @@ impl SceneIndex for OctreeSceneIndex {
         }
 
         // Different node — remove from old, place directly in target
-        self.remove(key);
-        self.nodes[target].objects.push((key, world_position, *world_aabb));
-        self.object_locations.insert(key, target);
+        self.take_object(key);
+        self.push_object(target, key, world_position, world_aabb);
     }
 
     fn query_frustum(
@@ -421,15 +721,32 @@ impl SceneIndex for OctreeSceneIndex {
             return;
         }
 
-        let root_class = frustum.classify_aabb(&self.nodes[ROOT].aabb);
-        self.query_recursive(ROOT, frustum, root_class, camera_pos, camera_forward, results, 0);
+        let mut stats = OctreeQueryStats::default();
+        let root_class = frustum.classify_aabb(&self.nodes[ROOT].loose_aabb);
+        self.query_recursive(ROOT, frustum, root_class, camera_pos, camera_forward, results, 0, &mut stats);
+        *self.last_query_stats.lock().unwrap() = stats;
+    }
+
+    fn query_ray(
+        &self,
+        ray: &Ray,
+        max_distance: f32,
+        results: &mut Vec<(RenderInstanceKey, f32)>,
+    ) {
+        if self.nodes.is_empty() {
+            return;
+        }
+        self.query_ray_recursive(ROOT, ray, max_distance, results, 0);
     }
 
     fn clear(&mut self) {
         for node in &mut self.nodes {
             node.objects.clear();
+            node.subtree_count = 0;
         }
         self.object_locations.clear();
+        self.out_of_bounds.clear();
+        self.out_of_bounds_warned = false;
     }
 }
 
@@ -700,4 +1017,282 @@ mod tests {
         assert!(a.intersects(&b)); // overlapping
         assert!(!a.intersects(&c)); // disjoint
     }
+
+    // ===== Bounds management =====
+
+    #[test]
+    fn test_non_cubic_bounds_split_on_every_axis() {
+        let flat = make_aabb(Vec3::new(-400.0, -20.0, -400.0), Vec3::new(400.0, 20.0, 400.0));
+        let mut octree = OctreeSceneIndex::new(flat, 2);
+        let key = make_key(1);
+        let obj_aabb = make_aabb(Vec3::new(210.0, 1.0, 210.0), Vec3::new(220.0, 2.0, 220.0));
+        octree.insert(key, aabb_center(&obj_aabb), &obj_aabb);
+
+        // Leaf cells are 200 x 10 x 200
+        let node = &octree.nodes[octree.object_locations[&key]];
+        assert_eq!(node.aabb.max - node.aabb.min, Vec3::new(200.0, 10.0, 200.0));
+        assert_eq!(octree.out_of_bounds_count(), 0);
+    }
+
+    #[test]
+    fn test_out_of_bounds_objects_are_counted() {
+        let mut octree = OctreeSceneIndex::new(world_aabb(), 3);
+        let (a, b) = (make_key(1), make_key(2));
+        let outside = make_aabb(Vec3::splat(150.0), Vec3::splat(160.0));
+        let inside = make_aabb(Vec3::splat(1.0), Vec3::splat(2.0));
+        octree.insert(a, aabb_center(&outside), &outside);
+        octree.insert(b, aabb_center(&inside), &inside);
+        assert_eq!((octree.len(), octree.out_of_bounds_count()), (2, 1));
+
+        // Moving back inside or out again keeps the count exact
+        octree.update(a, aabb_center(&inside), &inside);
+        octree.update(b, aabb_center(&outside), &outside);
+        assert_eq!(octree.out_of_bounds_count(), 1);
+        assert_eq!(octree.object_locations[&b], ROOT);
+        octree.remove(b);
+        assert_eq!(octree.out_of_bounds_count(), 0);
+    }
+
+    #[test]
+    fn test_out_of_bounds_warning_needs_count_and_ratio() {
+        let mut octree = OctreeSceneIndex::new(world_aabb(), 1);
+        let inside = make_aabb(Vec3::splat(1.0), Vec3::splat(2.0));
+        for i in 0..100 {
+            octree.insert(make_key(i + 1), Vec3::ONE, &inside);
+        }
+        let outside = make_aabb(Vec3::splat(500.0), Vec3::splat(501.0));
+        for i in 0..OUT_OF_BOUNDS_WARN_MIN_COUNT as u32 {
+            octree.insert(make_key(200 + i), Vec3::splat(500.0), &outside);
+        }
+        // 16 of 116: below the ratio
+        assert!(!octree.out_of_bounds_warned);
+        for i in 0..30 {
+            octree.insert(make_key(300 + i), Vec3::splat(500.0), &outside);
+        }
+        assert!(octree.out_of_bounds_warned);
+        octree.rebuild();
+        assert!(!octree.out_of_bounds_warned);
+    }
+
+    #[test]
+    fn test_set_bounds_reinserts_objects() {
+        let mut octree = OctreeSceneIndex::new(world_aabb(), 3);
+        let key = make_key(1);
+        let far = make_aabb(Vec3::new(300.0, 0.0, 0.0), Vec3::new(310.0, 10.0, 10.0));
+        octree.insert(key, aabb_center(&far), &far);
+        assert_eq!(octree.out_of_bounds_count(), 1);
+
+        octree.set_bounds(make_aabb(Vec3::splat(-400.0), Vec3::splat(400.0)));
+        assert_eq!(octree.out_of_bounds_count(), 0);
+        assert_ne!(octree.object_locations[&key], ROOT);
+        assert_eq!(octree.len(), 1);
+        assert_eq!(octree.nodes.len(), 585);
+    }
+
+    #[test]
+    fn test_rebuild_fits_bounds_to_objects() {
+        let mut octree = OctreeSceneIndex::new(world_aabb(), 2);
+        let a = make_aabb(Vec3::new(-500.0, 0.0, 0.0), Vec3::new(-490.0, 5.0, 5.0));
+        let b = make_aabb(Vec3::new(10.0, -5.0, 0.0), Vec3::new(20.0, 0.0, 30.0));
+        let bad = make_aabb(Vec3::splat(f32::NEG_INFINITY), Vec3::splat(f32::INFINITY));
+        octree.insert(make_key(1), aabb_center(&a), &a);
+        octree.insert(make_key(2), aabb_center(&b), &b);
+        octree.insert(make_key(3), Vec3::ZERO, &bad);
+
+        octree.rebuild();
+        let bounds = octree.bounds();
+        assert_eq!((bounds.min, bounds.max), (Vec3::new(-500.0, -5.0, 0.0), Vec3::new(20.0, 5.0, 30.0)));
+        assert_eq!(octree.len(), 3);
+        assert_eq!(octree.out_of_bounds_count(), 1);
+
+        // Empty index: bounds unchanged
+        let mut empty = OctreeSceneIndex::new(world_aabb(), 1);
+        empty.rebuild();
+        assert_eq!(empty.bounds().max, world_aabb().max);
+    }
+
+    #[test]
+    fn test_auto_grow_doubles_root_toward_object() {
+        let mut octree = OctreeSceneIndex::new(world_aabb(), 2);
+        octree.set_auto_grow(true);
+        let inside = make_key(1);
+        let inside_aabb = make_aabb(Vec3::splat(1.0), Vec3::splat(2.0));
+        octree.insert(inside, aabb_center(&inside_aabb), &inside_aabb);
+
+        let key = make_key(2);
+        let far = make_aabb(Vec3::new(250.0, 0.0, -250.0), Vec3::new(260.0, 10.0, -240.0));
+        octree.insert(key, aabb_center(&far), &far);
+        // One doubling toward +X / +Y / -Z
+        let bounds = octree.bounds();
+        assert_eq!((bounds.min, bounds.max), (Vec3::new(-100.0, -100.0, -300.0), Vec3::new(300.0, 300.0, 100.0)));
+        assert_eq!(octree.out_of_bounds_count(), 0);
+        assert!(octree.object_locations.contains_key(&inside));
+
+        // Non-finite bounds cannot grow the root
+        let bad = make_aabb(Vec3::ZERO, Vec3::splat(f32::INFINITY));
+        octree.update(key, Vec3::ZERO, &bad);
+        assert_eq!(octree.out_of_bounds_count(), 1);
+    }
+
+    // ===== Reference grid =====
+
+    /// Grid of objects: small boxes, zero-size points, and boxes straddling
+    /// the octant boundaries (x/y/z = 0 and ±50) that stay in parent nodes.
+    fn reference_grid_objects() -> Vec<(RenderInstanceKey, Vec3, AABB)> {
+        let mut objects = Vec::new();
+        let mut idx = 1;
+        for xi in -4i32..=4 {
+            for yi in -2..=2 {
+                for zi in -4..=4 {
+                    let center = Vec3::new(xi as f32, yi as f32, zi as f32) * 25.0;
+                    let half = match (xi + yi + zi).rem_euclid(3) {
+                        0 => Vec3::ZERO,
+                        1 => Vec3::splat(2.0),
+                        _ => Vec3::new(13.0, 1.0, 7.0),
+                    };
+                    let aabb = make_aabb(center - half, center + half);
+                    objects.push((make_key(idx), center, aabb));
+                    idx += 1;
+                }
+            }
+        }
+        objects
+    }
+
+    /// Compare octree queries with brute-force frustum tests from several cameras
+    fn assert_queries_match_brute_force(octree: &OctreeSceneIndex, objects: &[(RenderInstanceKey, Vec3, AABB)]) {
+        let cameras = [
+            (Vec3::ZERO, Vec3::new(0.0, 0.0, -1.0)),
+            (Vec3::new(60.0, 10.0, 60.0), Vec3::ZERO),
+            (Vec3::new(-90.0, 40.0, 0.0), Vec3::new(50.0, 0.0, 0.0)),
+            (Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, 1.0)),
+        ];
+        for (eye, target) in cameras {
+            let proj = Mat4::perspective_rh(std::f32::consts::FRAC_PI_3, 1.0, 0.5, 120.0);
+            let view = Mat4::look_at_rh(eye, target, Vec3::Y);
+            let frustum = Frustum::from_view_projection(&(proj * view));
+            let forward = (target - eye).normalize();
+
+            let mut results = Vec::new();
+            octree.query_frustum(&frustum, eye, forward, &mut results);
+            let mut got = visible_keys(&results);
+            got.sort();
+            let mut want: Vec<_> = objects.iter()
+                .filter(|(_, _, aabb)| frustum.intersects_aabb(aabb))
+                .map(|(key, _, _)| *key)
+                .collect();
+            want.sort();
+
+            assert!(!want.is_empty());
+            assert_eq!(got, want, "camera at {:?} looking at {:?}", eye, target);
+        }
+    }
+
+    #[test]
+    fn test_query_matches_brute_force_on_reference_grid() {
+        let objects = reference_grid_objects();
+        let mut octree = OctreeSceneIndex::new(world_aabb(), 3);
+        for (key, pos, aabb) in &objects {
+            octree.insert(*key, *pos, aabb);
+        }
+        assert_queries_match_brute_force(&octree, &objects);
+    }
+
+    // ===== Loose octree =====
+
+    #[test]
+    fn test_loose_query_matches_brute_force_on_reference_grid() {
+        let objects = reference_grid_objects();
+        let mut octree = OctreeSceneIndex::new(world_aabb(), 3);
+        octree.set_looseness(2.0);
+        for (key, pos, aabb) in &objects {
+            octree.insert(*key, *pos, aabb);
+        }
+        assert_queries_match_brute_force(&octree, &objects);
+
+        // Move every object and query again
+        for (key, pos, aabb) in &objects {
+            let offset = Vec3::new(7.0, -3.0, 11.0);
+            octree.update(*key, *pos + offset, &make_aabb(aabb.min + offset, aabb.max + offset));
+        }
+        let moved: Vec<_> = objects.iter()
+            .map(|(key, pos, aabb)| {
+                let offset = Vec3::new(7.0, -3.0, 11.0);
+                (*key, *pos + offset, make_aabb(aabb.min + offset, aabb.max + offset))
+            })
+            .collect();
+        assert_queries_match_brute_force(&octree, &moved);
+    }
+
+    #[test]
+    fn test_loose_octree_keeps_straddling_objects_out_of_root() {
+        let mut octree = OctreeSceneIndex::new(world_aabb(), 3);
+        let key = make_key(1);
+        // Straddles the root center: strict placement keeps it in the root
+        let aabb = make_aabb(Vec3::splat(-1.0), Vec3::splat(3.0));
+        octree.insert(key, aabb_center(&aabb), &aabb);
+        assert_eq!(octree.object_locations[&key], ROOT);
+
+        octree.set_looseness(2.0);
+        assert_eq!(octree.looseness(), 2.0);
+        assert_ne!(octree.object_locations[&key], ROOT);
+        assert_eq!(octree.len(), 1);
+    }
+
+    #[test]
+    fn test_set_looseness_clamps_invalid_values() {
+        let mut octree = OctreeSceneIndex::new(world_aabb(), 1);
+        octree.set_looseness(0.5);
+        assert_eq!(octree.looseness(), OCTREE_STRICT_LOOSENESS);
+        octree.set_looseness(100.0);
+        assert_eq!(octree.looseness(), OCTREE_MAX_LOOSENESS);
+        octree.set_looseness(f32::NAN);
+        assert_eq!(octree.looseness(), OCTREE_STRICT_LOOSENESS);
+    }
+
+    // ===== Subtree counts and query statistics =====
+
+    #[test]
+    fn test_subtree_counts_follow_insert_update_remove() {
+        let mut octree = OctreeSceneIndex::new(world_aabb(), 2);
+        let (a, b) = (make_key(1), make_key(2));
+        let small = make_aabb(Vec3::splat(10.0), Vec3::splat(11.0));
+        octree.insert(a, aabb_center(&small), &small);
+        octree.insert(b, aabb_center(&small), &small);
+        assert_eq!(octree.nodes[ROOT].subtree_count, 2);
+
+        // Move `a` to the opposite octant: counts follow the object
+        let moved = make_aabb(Vec3::splat(-11.0), Vec3::splat(-10.0));
+        octree.update(a, aabb_center(&moved), &moved);
+        let a_node = octree.object_locations[&a];
+        let b_node = octree.object_locations[&b];
+        assert_eq!(octree.nodes[a_node].subtree_count, 1);
+        assert_eq!(octree.nodes[b_node].subtree_count, 1);
+        assert_eq!(octree.nodes[ROOT].subtree_count, 2);
+
+        octree.remove(a);
+        assert_eq!(octree.nodes[a_node].subtree_count, 0);
+        assert_eq!(octree.nodes[ROOT].subtree_count, 1);
+        octree.clear();
+        assert!(octree.nodes.iter().all(|node| node.subtree_count == 0));
+    }
+
+    #[test]
+    fn test_query_stats_skip_empty_subtrees() {
+        let mut octree = OctreeSceneIndex::new(world_aabb(), 3);
+        let key = make_key(1);
+        let aabb = make_aabb(Vec3::new(-2.0, -2.0, -12.0), Vec3::new(-1.0, -1.0, -11.0));
+        octree.insert(key, aabb_center(&aabb), &aabb);
+
+        let (pos, fwd) = forward_camera_pos_forward();
+        let mut results = Vec::new();
+        octree.query_frustum(&all_visible_frustum(), pos, fwd, &mut results);
+        let stats = octree.last_query_stats();
+        assert_eq!(stats.objects_visible, 1);
+        assert_eq!(results.len(), 1);
+        // Only the path down to the object is visited: root + 3 levels
+        assert!(stats.nodes_visited <= 4, "{:?}", stats);
+        assert!(stats.empty_subtrees_skipped >= 7 * 3, "{:?}", stats);
+        assert!(stats.objects_tested <= 1);
+    }
 }
@@ -0,0 +1,191 @@
+/// Entity picking through a GPU ID buffer.
+///
+/// A picking pass renders the scene into an `R32_UINT` color target
+/// (`PICKING_TARGET_FORMAT`) cleared to `PICK_ID_NONE`. Its fragment shader
+/// writes `drawSlot + 1` (see `pick_id_for_draw_slot`), with the draw slot
+/// read exactly as in the regular scene passes (masked with
+/// `LOD_FADE_SLOT_MASK` when LODs cross-fade). The engine does not ship
+/// that shader: any material whose fragment stage follows this contract
+/// works, typically through `VertexShaderOverride`s or a dedicated pass type.
+///
+/// `Picker` then reads back the pixels under the requested cursor positions
+/// without stalling, through its own `ReadbackManager`: `record()` copies
+/// them from inside the frame's command list, and `poll()` returns the
+/// results once that command list has completed, `frames_in_flight` frames
+/// later.
+
+use std::collections::VecDeque;
+use crate::error::{Error, Result};
+use crate::graphics_device::{CommandList, ImageAccess, Rect2D, TextureFormat};
+use crate::render_graph::{ReadbackManager, ReadbackTicket};
+use super::render_instance::RenderInstanceKey;
+use super::scene::Scene;
+
+/// Format of the ID target rendered by the picking pass
+pub const PICKING_TARGET_FORMAT: TextureFormat = TextureFormat::R32_UINT;
+
+/// ID of pixels not covered by any instance (the target clear value)
+pub const PICK_ID_NONE: u32 = 0;
+
+/// Maximum number of pixels read back per frame; extra requests wait for
+/// the next frame
+pub const MAX_PICKS_PER_FRAME: usize = 16;
+
+/// Size of one picked pixel in the readback data
+const PICK_ID_SIZE: usize = 4;
+
+/// Value written by the picking shader for a draw slot
+pub fn pick_id_for_draw_slot(draw_slot: u32) -> u32 {
+    draw_slot + 1
+}
+
+/// Find the render instance owning the submesh drawn with a pick ID.
+///
+/// Returns None for `PICK_ID_NONE` or when no current instance uses that
+/// draw slot (e.g. removed since the pick was recorded).
+pub fn resolve_pick_id(scene: &Scene, id: u32) -> Option<RenderInstanceKey> {
+    if id == PICK_ID_NONE {
+        return None;
+    }
+    let draw_slot = id - 1;
+    scene.render_instances()
+        .find(|(_, instance)| {
+            (0..instance.sub_mesh_count())
+                .any(|i| instance.sub_mesh(i).is_some_and(|sm| sm.draw_slot() == draw_slot))
+        })
+        .map(|(key, _)| key)
+}
+
+/// Result of a pick request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PickResult {
+    /// Requested pixel
+    pub x: u32,
+    pub y: u32,
+    /// Instance under the pixel, None for background
+    pub key: Option<RenderInstanceKey>,
+}
+
+/// Pixel requested in a recorded batch
+struct PendingPick {
+    x: u32,
+    y: u32,
+    /// Index of the pixel in the batch readback (None: pixel outside the
+    /// target)
+    index: Option<usize>,
+}
+
+/// Picks recorded in the same frame
+struct PendingBatch {
+    /// None when every pixel was outside the target
+    ticket: Option<ReadbackTicket>,
+    picks: Vec<PendingPick>,
+}
+
+/// Asynchronous readback of the picking ID target.
+///
+/// Per frame, after the ID target has been rendered:
+/// 1. `request(x, y)` whenever the user clicks (any time);
+/// 2. `record(cmd, id_target)` from a pass action, outside a render pass;
+/// 3. `poll(scene)` once after the render graph has executed.
+pub struct Picker {
+    readbacks: ReadbackManager,
+    requests: VecDeque<(u32, u32)>,
+    pending: VecDeque<PendingBatch>,
+    /// Reused scratch of `record()`
+    rects: Vec<Rect2D>,
+}
+
+impl Picker {
+    /// Create a picker for a render graph with `frames_in_flight` command
+    /// lists.
+    pub fn new(frames_in_flight: usize) -> Result<Self> {
+        Ok(Self {
+            readbacks: ReadbackManager::new(frames_in_flight)?,
+            requests: VecDeque::new(),
+            pending: VecDeque::new(),
+            rects: Vec::with_capacity(MAX_PICKS_PER_FRAME),
+        })
+    }
+
+    /// Queue a pick at a pixel of the ID target
+    pub fn request(&mut self, x: u32, y: u32) {
+        self.requests.push_back((x, y));
+    }
+
+    /// Number of requests not yet returned by `poll()`
+    pub fn pending_count(&self) -> usize {
+        self.requests.len() + self.pending.iter().map(|batch| batch.picks.len()).sum::<usize>()
+    }
+
+    /// Record the copies of up to `MAX_PICKS_PER_FRAME` queued pixels.
+    ///
+    /// `id_target.access_type` must be `AccessType::TransferRead`, with
+    /// `previous_access_type` set to how the picking pass wrote it.
+    /// Does nothing when no request is queued.
+    pub fn record(&mut self, cmd: &mut dyn CommandList, id_target: &ImageAccess) -> Result<()> {
+        if self.requests.is_empty() {
+            return Ok(());
+        }
+        let info = id_target.texture.info();
+        if info.format != PICKING_TARGET_FORMAT {
+            crate::engine_error!("galaxy3d::Picker",
+                "ID target must be {:?}, got {:?}", PICKING_TARGET_FORMAT, info.format);
+            return Err(Error::Incompatible {
+                expected: format!("{:?}", PICKING_TARGET_FORMAT),
+                found: format!("{:?}", info.format),
+            });
+        }
+
+        self.rects.clear();
+        let count = self.requests.len().min(MAX_PICKS_PER_FRAME);
+        let mut picks = Vec::with_capacity(count);
+        for &(x, y) in self.requests.iter().take(count) {
+            let index = (x < info.width && y < info.height).then(|| {
+                self.rects.push(Rect2D { x: x as i32, y: y as i32, width: 1, height: 1 });
+                self.rects.len() - 1
+            });
+            picks.push(PendingPick { x, y, index });
+        }
+        let ticket = if self.rects.is_empty() {
+            None
+        } else {
+            Some(self.readbacks.read_texture(cmd, id_target, &self.rects)?)
+        };
+        self.requests.drain(..count);
+        self.pending.push_back(PendingBatch { ticket, picks });
+        Ok(())
+    }
+
+    /// Return the picks whose copy has completed.
+    ///
+    /// Call once per frame, after the render graph has executed: a copy
+    /// recorded in frame N is read after frame N + `frames_in_flight`,
+    /// whose command list reuse waited for frame N's fence.
+    pub fn poll(&mut self, scene: &Scene) -> Result<Vec<PickResult>> {
+        self.readbacks.poll()?;
+        let mut results = Vec::new();
+        while let Some(batch) = self.pending.front() {
+            let data = match batch.ticket {
+                Some(ticket) => match self.readbacks.take(ticket) {
+                    Some(data) => data,
+                    None => break,
+                },
+                None => Vec::new(),
+            };
+            let batch = self.pending.pop_front().unwrap();
+            for pick in batch.picks {
+                let key = pick.index.and_then(|index| {
+                    let bytes = &data[index * PICK_ID_SIZE..(index + 1) * PICK_ID_SIZE];
+                    resolve_pick_id(scene, u32::from_ne_bytes(bytes.try_into().unwrap()))
+                });
+                results.push(PickResult { x: pick.x, y: pick.y, key });
+            }
+        }
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+#[path = "picking_tests.rs"]
+mod tests;
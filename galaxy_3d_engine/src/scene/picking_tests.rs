@@ -0,0 +1,130 @@
+use super::*;
+use std::sync::Arc;
+use crate::graphics_device::{AccessType, TextureType};
+use crate::graphics_device::mock_graphics_device::{MockCommandList, MockTexture};
+use crate::render_graph::test_helpers::setup_engine;
+use crate::scene::scene_test_helpers::{setup_resources, create_test_aabb};
+use glam::Mat4;
+use serial_test::serial;
+
+fn id_target(format: TextureFormat) -> ImageAccess {
+    let mut texture = MockTexture::new(64, 32, 1, TextureType::Tex2D, "ids".to_string());
+    texture.info.format = format;
+    ImageAccess {
+        texture: Arc::new(texture),
+        access_type: AccessType::TransferRead,
+        previous_access_type: Some(AccessType::ColorAttachmentWrite),
+    }
+}
+
+// ============================================================================
+// Pick IDs
+// ============================================================================
+
+#[test]
+fn test_pick_id_none_resolves_to_none() {
+    let scene = Scene::new();
+    assert_eq!(resolve_pick_id(&scene, PICK_ID_NONE), None);
+    assert_eq!(resolve_pick_id(&scene, pick_id_for_draw_slot(7)), None);
+}
+
+#[test]
+fn test_resolve_pick_id_finds_instance_of_draw_slot() {
+    let s = setup_resources();
+    let mut scene = Scene::new();
+    let a = scene.create_render_instance(s.mesh_key, Mat4::IDENTITY, create_test_aabb(), s.vertex_shader_key, &[], &s.rm).unwrap();
+    let b = scene.create_render_instance(s.mesh_key, Mat4::IDENTITY, create_test_aabb(), s.vertex_shader_key, &[], &s.rm).unwrap();
+
+    for key in [a, b] {
+        let slot = scene.render_instance(key).unwrap().sub_mesh(0).unwrap().draw_slot();
+        assert_eq!(resolve_pick_id(&scene, pick_id_for_draw_slot(slot)), Some(key));
+    }
+}
+
+// ============================================================================
+// Picker
+// ============================================================================
+
+#[test]
+#[serial]
+fn test_picker_rejects_zero_frames_in_flight() {
+    setup_engine();
+    assert!(Picker::new(0).is_err());
+}
+
+#[test]
+#[serial]
+fn test_record_without_request_emits_nothing() {
+    setup_engine();
+    let mut picker = Picker::new(2).unwrap();
+    let mut cmd = MockCommandList::new();
+    picker.record(&mut cmd, &id_target(PICKING_TARGET_FORMAT)).unwrap();
+    assert!(cmd.commands.is_empty());
+}
+
+#[test]
+#[serial]
+fn test_record_rejects_wrong_target_format() {
+    setup_engine();
+    let mut picker = Picker::new(2).unwrap();
+    picker.request(1, 1);
+    let mut cmd = MockCommandList::new();
+    let result = picker.record(&mut cmd, &id_target(TextureFormat::R8G8B8A8_UNORM));
+    assert_eq!(result.unwrap_err().code(), crate::error::ErrorCode::Incompatible);
+}
+
+#[test]
+#[serial]
+fn test_result_available_after_frames_in_flight() {
+    setup_engine();
+    let scene = Scene::new();
+    let mut picker = Picker::new(2).unwrap();
+    picker.request(3, 4);
+    let mut cmd = MockCommandList::new();
+    picker.record(&mut cmd, &id_target(PICKING_TARGET_FORMAT)).unwrap();
+    assert_eq!(cmd.commands, vec!["copy_texture_to_buffer 1".to_string()]);
+
+    // Frames N and N+1 may still be in flight
+    assert!(picker.poll(&scene).unwrap().is_empty());
+    assert!(picker.poll(&scene).unwrap().is_empty());
+    assert_eq!(picker.pending_count(), 1);
+    // Mock readback is zeroed: background
+    let results = picker.poll(&scene).unwrap();
+    assert_eq!(results, vec![PickResult { x: 3, y: 4, key: None }]);
+    assert_eq!(picker.pending_count(), 0);
+}
+
+#[test]
+#[serial]
+fn test_out_of_bounds_pick_is_not_copied() {
+    setup_engine();
+    let scene = Scene::new();
+    let mut picker = Picker::new(1).unwrap();
+    picker.request(64, 0);
+    picker.request(10, 10);
+    let mut cmd = MockCommandList::new();
+    picker.record(&mut cmd, &id_target(PICKING_TARGET_FORMAT)).unwrap();
+    assert_eq!(cmd.commands, vec!["copy_texture_to_buffer 1".to_string()]);
+
+    picker.poll(&scene).unwrap();
+    let results = picker.poll(&scene).unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0], PickResult { x: 64, y: 0, key: None });
+}
+
+#[test]
+#[serial]
+fn test_requests_beyond_frame_limit_wait_for_next_record() {
+    setup_engine();
+    let mut picker = Picker::new(2).unwrap();
+    for i in 0..(MAX_PICKS_PER_FRAME as u32 + 3) {
+        picker.request(i % 64, 0);
+    }
+    let mut cmd = MockCommandList::new();
+    picker.record(&mut cmd, &id_target(PICKING_TARGET_FORMAT)).unwrap();
+    picker.record(&mut cmd, &id_target(PICKING_TARGET_FORMAT)).unwrap();
+    assert_eq!(cmd.commands, vec![
+        format!("copy_texture_to_buffer {}", MAX_PICKS_PER_FRAME),
+        "copy_texture_to_buffer 3".to_string(),
+    ]);
+}
@@ -0,0 +1,90 @@
+/// Ray and ray intersection tests.
+///
+/// Pure math (glam only): part of the core built without the `renderer`
+/// feature. `SceneIndex::raycast` builds scene raycasts on top of it.
+
+use glam::{Mat4, Vec3};
+use super::aabb::AABB;
+
+/// Determinant under which a ray is considered parallel to a triangle
+const PARALLEL_EPSILON: f32 = 1e-12;
+
+/// Half-line `origin + t * direction`, `t >= 0`.
+///
+/// Distances returned by the intersection tests are values of `t`: world
+/// units when `direction` is normalized (as built by `Ray::new`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray {
+    /// Start point
+    pub origin: Vec3,
+    /// Direction (normalized by `new`, not by `transformed`)
+    pub direction: Vec3,
+}
+
+impl Ray {
+    /// Create a ray with a normalized direction (zero stays zero and hits
+    /// nothing)
+    pub fn new(origin: Vec3, direction: Vec3) -> Self {
+        Self { origin, direction: direction.normalize_or_zero() }
+    }
+
+    /// Point at distance `t` along the ray
+    pub fn at(&self, t: f32) -> Vec3 {
+        self.origin + self.direction * t
+    }
+
+    /// Transform the ray by a matrix (e.g. the inverse world matrix to
+    /// test local-space geometry).
+    ///
+    /// The direction is not renormalized, so `t` keeps its meaning: a hit
+    /// at `t` on the transformed ray is at `t` on the original one.
+    pub fn transformed(&self, matrix: &Mat4) -> Ray {
+        Ray {
+            origin: matrix.transform_point3(self.origin),
+            direction: matrix.transform_vector3(self.direction),
+        }
+    }
+
+    /// Distance at which the ray enters an AABB (0 when the origin is
+    /// inside), or None when it misses it within `max_distance`.
+    ///
+    /// Slab test: boxes touched on an edge or a face count as hit.
+    pub fn intersect_aabb(&self, aabb: &AABB, max_distance: f32) -> Option<f32> {
+        let inverse = self.direction.recip();
+        let t0 = (aabb.min - self.origin) * inverse;
+        let t1 = (aabb.max - self.origin) * inverse;
+        // min/max ignore the NaN of an origin lying on a slab of a zero axis
+        let t_enter = t0.min(t1).max_element().max(0.0);
+        let t_exit = t0.max(t1).min_element().min(max_distance);
+        (t_enter <= t_exit).then_some(t_enter)
+    }
+
+    /// Distance at which the ray hits a triangle, from either side, or
+    /// None when it misses it (Möller–Trumbore).
+    pub fn intersect_triangle(&self, a: Vec3, b: Vec3, c: Vec3) -> Option<f32> {
+        let edge1 = b - a;
+        let edge2 = c - a;
+        let p = self.direction.cross(edge2);
+        let det = edge1.dot(p);
+        if det.abs() < PARALLEL_EPSILON {
+            return None;
+        }
+        let inverse_det = 1.0 / det;
+        let s = self.origin - a;
+        let u = s.dot(p) * inverse_det;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        let q = s.cross(edge1);
+        let v = self.direction.dot(q) * inverse_det;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+        let t = edge2.dot(q) * inverse_det;
+        (t >= 0.0).then_some(t)
+    }
+}
+
+#[cfg(test)]
+#[path = "ray_tests.rs"]
+mod tests;
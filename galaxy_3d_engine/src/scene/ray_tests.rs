@@ -0,0 +1,105 @@
+use super::*;
+
+fn unit_box() -> AABB {
+    AABB { min: Vec3::splat(-1.0), max: Vec3::splat(1.0) }
+}
+
+// ============================================================================
+// Construction
+// ============================================================================
+
+#[test]
+fn test_new_normalizes_direction() {
+    let ray = Ray::new(Vec3::ZERO, Vec3::new(0.0, 0.0, -5.0));
+    assert_eq!(ray.direction, Vec3::NEG_Z);
+    assert_eq!(ray.at(2.0), Vec3::new(0.0, 0.0, -2.0));
+}
+
+#[test]
+fn test_transformed_keeps_distances() {
+    let ray = Ray::new(Vec3::new(0.0, 0.0, 10.0), Vec3::NEG_Z);
+    let world = Mat4::from_scale_rotation_translation(
+        Vec3::splat(2.0), glam::Quat::from_rotation_y(0.5), Vec3::new(0.0, 0.0, 3.0));
+    let local = ray.transformed(&world.inverse());
+    // The hit point on the local ray maps back to the world point at the same t
+    let t = 4.0;
+    assert!(world.transform_point3(local.at(t)).abs_diff_eq(ray.at(t), 1e-4));
+}
+
+// ============================================================================
+// AABB
+// ============================================================================
+
+#[test]
+fn test_aabb_hit_returns_entry_distance() {
+    let ray = Ray::new(Vec3::new(0.0, 0.0, 5.0), Vec3::NEG_Z);
+    assert_eq!(ray.intersect_aabb(&unit_box(), f32::MAX), Some(4.0));
+}
+
+#[test]
+fn test_aabb_origin_inside_returns_zero() {
+    let ray = Ray::new(Vec3::ZERO, Vec3::X);
+    assert_eq!(ray.intersect_aabb(&unit_box(), f32::MAX), Some(0.0));
+}
+
+#[test]
+fn test_aabb_miss_behind_and_beside() {
+    let behind = Ray::new(Vec3::new(0.0, 0.0, 5.0), Vec3::Z);
+    assert_eq!(behind.intersect_aabb(&unit_box(), f32::MAX), None);
+    let beside = Ray::new(Vec3::new(3.0, 0.0, 5.0), Vec3::NEG_Z);
+    assert_eq!(beside.intersect_aabb(&unit_box(), f32::MAX), None);
+}
+
+#[test]
+fn test_aabb_respects_max_distance() {
+    let ray = Ray::new(Vec3::new(0.0, 0.0, 5.0), Vec3::NEG_Z);
+    assert_eq!(ray.intersect_aabb(&unit_box(), 3.5), None);
+    assert_eq!(ray.intersect_aabb(&unit_box(), 4.5), Some(4.0));
+}
+
+#[test]
+fn test_aabb_axis_parallel_ray() {
+    // Zero direction components: inside the slab hits, outside misses
+    let inside = Ray::new(Vec3::new(0.5, 0.5, 5.0), Vec3::NEG_Z);
+    assert_eq!(inside.intersect_aabb(&unit_box(), f32::MAX), Some(4.0));
+    let outside = Ray::new(Vec3::new(0.5, 1.5, 5.0), Vec3::NEG_Z);
+    assert_eq!(outside.intersect_aabb(&unit_box(), f32::MAX), None);
+}
+
+// ============================================================================
+// Triangle
+// ============================================================================
+
+fn triangle() -> (Vec3, Vec3, Vec3) {
+    (Vec3::new(-1.0, -1.0, 0.0), Vec3::new(1.0, -1.0, 0.0), Vec3::new(0.0, 1.0, 0.0))
+}
+
+#[test]
+fn test_triangle_hit_from_both_sides() {
+    let (a, b, c) = triangle();
+    let front = Ray::new(Vec3::new(0.0, 0.0, 3.0), Vec3::NEG_Z);
+    assert_eq!(front.intersect_triangle(a, b, c), Some(3.0));
+    let back = Ray::new(Vec3::new(0.0, 0.0, -2.0), Vec3::Z);
+    assert_eq!(back.intersect_triangle(a, b, c), Some(2.0));
+}
+
+#[test]
+fn test_triangle_miss_outside_edges() {
+    let (a, b, c) = triangle();
+    let ray = Ray::new(Vec3::new(0.9, 0.9, 3.0), Vec3::NEG_Z);
+    assert_eq!(ray.intersect_triangle(a, b, c), None);
+}
+
+#[test]
+fn test_triangle_miss_behind_origin() {
+    let (a, b, c) = triangle();
+    let ray = Ray::new(Vec3::new(0.0, 0.0, 3.0), Vec3::Z);
+    assert_eq!(ray.intersect_triangle(a, b, c), None);
+}
+
+#[test]
+fn test_triangle_parallel_ray_misses() {
+    let (a, b, c) = triangle();
+    let ray = Ray::new(Vec3::new(-5.0, 0.0, 0.0), Vec3::X);
+    assert_eq!(ray.intersect_triangle(a, b, c), None);
+}
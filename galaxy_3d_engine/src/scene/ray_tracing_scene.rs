@@ -0,0 +1,187 @@
+/// Acceleration structures of a scene, for hardware ray tracing.
+///
+/// `RayTracingScene` keeps one bottom-level acceleration structure (BLAS)
+/// per geometry submesh drawn by the scene, built on first use from its
+/// LOD 0 (`Geometry::lod_blas_geometry`), and a top-level acceleration
+/// structure (TLAS) holding one instance per submesh of the visible render
+/// instances:
+///
+/// - `update()` gathers the instances (CPU side, once per frame);
+/// - `record_build()` rebuilds the TLAS from them on the GPU, before the
+///   ray tracing passes of the frame (`RayTracedShadowAction` does it).
+///
+/// Instance contract for hit shaders:
+///
+/// ```glsl
+/// uint drawSlot = gl_InstanceCustomIndexEXT;   // RenderSubMesh::draw_slot
+/// // cull masks of traceRayEXT
+/// const uint RT_MASK_VISIBLE       = 0x01;
+/// const uint RT_MASK_SHADOW_CASTER = 0x02;
+/// ```
+///
+/// Every triangle is opaque (alpha-tested materials cast solid shadows)
+/// and two-sided. Morph targets are ignored; LODs other than 0 are not
+/// traced. Only triangle-list LODs with a float position attribute get a
+/// BLAS; other submeshes are skipped.
+
+use std::sync::Arc;
+use rustc_hash::FxHashMap;
+use crate::error::Result;
+use crate::engine_warn;
+use crate::graphics_device::{
+    self, AccelerationStructure, BlasDesc, CommandList, GraphicsDevice, TlasDesc, TlasInstance,
+};
+use crate::resource::resource_manager::{GeometryKey, ResourceManager};
+use super::render_instance::FLAG_CAST_SHADOW;
+use super::scene::Scene;
+
+/// Instance mask bit set on every instance
+pub const RT_MASK_VISIBLE: u8 = 0x01;
+/// Instance mask bit set on instances with `FLAG_CAST_SHADOW`
+pub const RT_MASK_SHADOW_CASTER: u8 = 0x02;
+
+/// BLAS of a geometry submesh, with the vertex buffer it was built from
+/// (a geometry replaced under the same key gets a new buffer)
+struct CachedBlas {
+    vertex_buffer: Arc<dyn graphics_device::Buffer>,
+    blas: Arc<dyn AccelerationStructure>,
+}
+
+/// Acceleration structures of a scene (see module docs).
+pub struct RayTracingScene {
+    tlas: Arc<dyn AccelerationStructure>,
+    max_instances: u32,
+    /// BLAS per (geometry, geometry mesh id, geometry submesh id)
+    blas_cache: FxHashMap<(GeometryKey, usize, usize), CachedBlas>,
+    /// Instances gathered by the last `update()`, reused frame to frame
+    instances: Vec<TlasInstance>,
+}
+
+impl RayTracingScene {
+    /// Create the TLAS for up to `max_instances` instances.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if ray tracing is not enabled on the device
+    /// (`GraphicsDevice::supports_ray_tracing`) or the TLAS cannot be
+    /// created.
+    pub fn new(graphics_device: &mut dyn GraphicsDevice, max_instances: u32) -> Result<Self> {
+        let tlas = graphics_device.create_tlas(&TlasDesc {
+            max_instances,
+            debug_name: Some("scene_tlas".to_string()),
+        })?;
+        Ok(Self {
+            tlas,
+            max_instances,
+            blas_cache: FxHashMap::default(),
+            instances: Vec::new(),
+        })
+    }
+
+    /// The top-level acceleration structure, to bind as
+    /// `BindingResource::AccelerationStructure`
+    pub fn tlas(&self) -> &Arc<dyn AccelerationStructure> {
+        &self.tlas
+    }
+
+    /// Instances gathered by the last `update()`
+    pub fn instances(&self) -> &[TlasInstance] {
+        &self.instances
+    }
+
+    /// Number of cached bottom-level acceleration structures
+    pub fn blas_count(&self) -> usize {
+        self.blas_cache.len()
+    }
+
+    /// Gather one TLAS instance per submesh of the visible render
+    /// instances, building the missing BLAS.
+    ///
+    /// Instances beyond `max_instances` are dropped with a warning.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a BLAS cannot be built.
+    pub fn update(
+        &mut self,
+        scene: &Scene,
+        resource_manager: &ResourceManager,
+        graphics_device: &mut dyn GraphicsDevice,
+    ) -> Result<()> {
+        // Drop the BLAS of removed or replaced geometries
+        self.blas_cache.retain(|(geometry_key, _, _), cached| {
+            resource_manager.geometry(*geometry_key)
+                .is_some_and(|geo| Arc::ptr_eq(geo.vertex_buffer(), &cached.vertex_buffer))
+        });
+
+        self.instances.clear();
+        let mut dropped = 0usize;
+        for (_, inst) in scene.render_instances() {
+            if !inst.is_visible() {
+                continue;
+            }
+            let Some(geo) = resource_manager.geometry(inst.geometry()) else {
+                continue;
+            };
+            let mask = if inst.flags() & FLAG_CAST_SHADOW != 0 {
+                RT_MASK_VISIBLE | RT_MASK_SHADOW_CASTER
+            } else {
+                RT_MASK_VISIBLE
+            };
+            for index in 0..inst.sub_mesh_count() {
+                let Some(render_sm) = inst.sub_mesh(index) else {
+                    continue;
+                };
+                let cache_key = (inst.geometry(), inst.geometry_mesh_id(), render_sm.geometry_submesh_id());
+                let blas_address = match self.blas_cache.get(&cache_key) {
+                    Some(cached) => cached.blas.device_address(),
+                    None => {
+                        let Some(geometry) = geo.submesh_lod(cache_key.1, cache_key.2, 0)
+                            .and_then(|lod| geo.lod_blas_geometry(lod, true))
+                        else {
+                            continue;
+                        };
+                        let blas = graphics_device.create_blas(&BlasDesc {
+                            geometries: vec![geometry],
+                            debug_name: Some(format!("{}_blas", geo.name())),
+                        })?;
+                        let address = blas.device_address();
+                        self.blas_cache.insert(cache_key, CachedBlas {
+                            vertex_buffer: Arc::clone(geo.vertex_buffer()),
+                            blas,
+                        });
+                        address
+                    }
+                };
+                if self.instances.len() >= self.max_instances as usize {
+                    dropped += 1;
+                    continue;
+                }
+                self.instances.push(TlasInstance {
+                    transform: *inst.world_matrix(),
+                    custom_index: render_sm.draw_slot(),
+                    mask,
+                    hit_group_offset: 0,
+                    double_sided: true,
+                    force_opaque: true,
+                    blas_address,
+                });
+            }
+        }
+        if dropped > 0 {
+            engine_warn!("galaxy3d::RayTracingScene",
+                "{} instances dropped: the TLAS holds at most {}", dropped, self.max_instances);
+        }
+        Ok(())
+    }
+
+    /// Rebuild the TLAS from the instances of the last `update()`
+    /// (outside a render pass).
+    pub fn record_build(&self, cmd: &mut dyn CommandList) -> Result<()> {
+        cmd.build_tlas(&self.tlas, &self.instances)
+    }
+}
+
+#[cfg(test)]
+#[path = "ray_tracing_scene_tests.rs"]
+mod tests;
@@ -0,0 +1,127 @@
+use super::*;
+use glam::{Mat4, Vec3};
+use crate::graphics_device::mock_graphics_device::{MockCommandList, MockGraphicsDevice};
+use crate::resource::resource_manager::ResourceManager;
+use crate::scene::render_instance::FLAG_VISIBLE;
+use crate::scene::scene_test_helpers::{setup_quad_resources, TestSetup};
+
+fn ray_tracing_device() -> MockGraphicsDevice {
+    let mut gd = MockGraphicsDevice::new();
+    gd.ray_tracing = true;
+    gd
+}
+
+/// Scene with `n` quad instances along X
+fn build_scene(n: usize) -> (Scene, TestSetup) {
+    let setup = setup_quad_resources();
+    let mut scene = Scene::new();
+    for i in 0..n {
+        let world = Mat4::from_translation(Vec3::new(i as f32 * 10.0, 0.0, 0.0));
+        scene.add_mesh_instance(setup.mesh_key, world, setup.vertex_shader_key, &setup.rm).unwrap();
+    }
+    (scene, setup)
+}
+
+fn update(rt: &mut RayTracingScene, scene: &Scene, rm: &ResourceManager, gd: &mut MockGraphicsDevice) {
+    rt.update(scene, rm, gd).unwrap();
+}
+
+// ============================================================================
+// Creation
+// ============================================================================
+
+#[test]
+fn test_new_requires_ray_tracing() {
+    let mut gd = MockGraphicsDevice::new();
+    assert!(RayTracingScene::new(&mut gd, 16).is_err());
+}
+
+#[test]
+fn test_new_creates_tlas() {
+    let mut gd = ray_tracing_device();
+    let rt = RayTracingScene::new(&mut gd, 16).unwrap();
+    assert_eq!(rt.tlas().kind(), graphics_device::AccelerationStructureKind::TopLevel);
+    assert!(rt.instances().is_empty());
+    assert_eq!(rt.blas_count(), 0);
+}
+
+// ============================================================================
+// update
+// ============================================================================
+
+#[test]
+fn test_update_shares_blas_between_instances() {
+    let (scene, setup) = build_scene(3);
+    let mut gd = ray_tracing_device();
+    let mut rt = RayTracingScene::new(&mut gd, 16).unwrap();
+    update(&mut rt, &scene, &setup.rm, &mut gd);
+
+    assert_eq!(rt.instances().len(), 3);
+    assert_eq!(rt.blas_count(), 1);
+    let address = rt.instances()[0].blas_address;
+    assert!(rt.instances().iter().all(|i| i.blas_address == address));
+
+    // Cached: a second update builds nothing
+    let built = gd.acceleration_structure_count;
+    update(&mut rt, &scene, &setup.rm, &mut gd);
+    assert_eq!(gd.acceleration_structure_count, built);
+    assert_eq!(rt.instances().len(), 3);
+}
+
+#[test]
+fn test_update_fills_instances() {
+    let (scene, setup) = build_scene(2);
+    let mut gd = ray_tracing_device();
+    let mut rt = RayTracingScene::new(&mut gd, 16).unwrap();
+    update(&mut rt, &scene, &setup.rm, &mut gd);
+
+    for (key, inst) in scene.render_instances() {
+        let draw_slot = inst.sub_mesh(0).unwrap().draw_slot();
+        let tlas_inst = rt.instances().iter()
+            .find(|i| i.custom_index == draw_slot)
+            .unwrap_or_else(|| panic!("no TLAS instance for {:?}", key));
+        assert_eq!(tlas_inst.transform, *inst.world_matrix());
+        assert_eq!(tlas_inst.mask, RT_MASK_VISIBLE);
+        assert!(tlas_inst.force_opaque);
+    }
+}
+
+#[test]
+fn test_update_masks_and_visibility() {
+    let (mut scene, setup) = build_scene(2);
+    let keys: Vec<_> = scene.render_instance_keys().collect();
+    scene.render_instance_mut(keys[0]).unwrap().set_flags(FLAG_VISIBLE | FLAG_CAST_SHADOW);
+    scene.render_instance_mut(keys[1]).unwrap().set_visible(false);
+
+    let mut gd = ray_tracing_device();
+    let mut rt = RayTracingScene::new(&mut gd, 16).unwrap();
+    update(&mut rt, &scene, &setup.rm, &mut gd);
+
+    assert_eq!(rt.instances().len(), 1);
+    assert_eq!(rt.instances()[0].mask, RT_MASK_VISIBLE | RT_MASK_SHADOW_CASTER);
+}
+
+#[test]
+fn test_update_drops_instances_over_capacity() {
+    let (scene, setup) = build_scene(3);
+    let mut gd = ray_tracing_device();
+    let mut rt = RayTracingScene::new(&mut gd, 2).unwrap();
+    update(&mut rt, &scene, &setup.rm, &mut gd);
+    assert_eq!(rt.instances().len(), 2);
+}
+
+// ============================================================================
+// record_build
+// ============================================================================
+
+#[test]
+fn test_record_build_emits_tlas_build() {
+    let (scene, setup) = build_scene(2);
+    let mut gd = ray_tracing_device();
+    let mut rt = RayTracingScene::new(&mut gd, 16).unwrap();
+    update(&mut rt, &scene, &setup.rm, &mut gd);
+
+    let mut cmd = MockCommandList::new();
+    rt.record_build(&mut cmd).unwrap();
+    assert_eq!(cmd.commands, vec!["build_tlas 2"]);
+}
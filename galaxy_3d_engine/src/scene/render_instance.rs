@@ -5,15 +5,19 @@
 /// It does NOT duplicate the geometry data — it stores a GeometryKey + mesh
 /// id and queries the Geometry at draw time.
 
-use glam::{Vec3, Mat4};
+use glam::{Vec3, Vec4, Mat4};
 use slotmap::new_key_type;
 use crate::error::Result;
-use crate::engine_err;
+use crate::{engine_bail, engine_err, engine_not_found, engine_warn};
 use crate::resource::mesh::Mesh;
+use crate::resource::geometry::{Geometry, MAX_MORPH_TARGETS};
 use crate::resource::resource_manager::{
-    ResourceManager, GeometryKey, MaterialKey, ShaderKey, PipelineKey,
+    ResourceManager, GeometryKey, MaterialKey, MeshKey, ShaderKey, PipelineKey,
 };
 use crate::utils::SlotAllocator;
+use super::aabb::AABB;
+use super::lod::LodFade;
+use super::drawer::INSTANCE_DRAW_SLOT_LOCATION;
 
 // ===== SLOT MAP KEY =====
 
@@ -25,76 +29,6 @@ new_key_type! {
     pub struct RenderInstanceKey;
 }
 
-// ===== AABB =====
-
-/// Axis-Aligned Bounding Box in local space
-///
-/// Used for frustum culling. Stored in local space and transformed
-/// by the world_matrix at culling time.
-#[derive(Debug, Clone, Copy)]
-pub struct AABB {
-    /// Minimum corner (x, y, z)
-    pub min: Vec3,
-    /// Maximum corner (x, y, z)
-    pub max: Vec3,
-}
-
-impl AABB {
-    /// Transform this local-space AABB by a matrix, returning a new AABB.
-    ///
-    /// Uses the Arvo method: projects each matrix axis onto the AABB extents
-    /// for an exact (tight) result without transforming all 8 corners.
-    pub fn transformed(&self, matrix: &Mat4) -> AABB {
-        let center = (self.min + self.max) * 0.5;
-        let extents = (self.max - self.min) * 0.5;
-
-        let new_center = matrix.transform_point3(center);
-
-        let abs_x = matrix.x_axis.truncate().abs();
-        let abs_y = matrix.y_axis.truncate().abs();
-        let abs_z = matrix.z_axis.truncate().abs();
-
-        let new_extents =
-            abs_x * extents.x +
-            abs_y * extents.y +
-            abs_z * extents.z;
-
-        AABB {
-            min: new_center - new_extents,
-            max: new_center + new_extents,
-        }
-    }
-
-    /// Check if this AABB contains another AABB entirely
-    pub fn contains(&self, other: &AABB) -> bool {
-        other.min.x >= self.min.x && other.max.x <= self.max.x &&
-        other.min.y >= self.min.y && other.max.y <= self.max.y &&
-        other.min.z >= self.min.z && other.max.z <= self.max.z
-    }
-
-    /// Check if this AABB intersects another AABB
-    pub fn intersects(&self, other: &AABB) -> bool {
-        self.min.x <= other.max.x && self.max.x >= other.min.x &&
-        self.min.y <= other.max.y && self.max.y >= other.min.y &&
-        self.min.z <= other.max.z && self.max.z >= other.min.z
-    }
-
-    /// Get the center of the AABB
-    pub fn center(&self) -> Vec3 {
-        (self.min + self.max) * 0.5
-    }
-
-    /// Return the point inside (or on the surface of) this AABB closest
-    /// to the given point. Equivalent to clamping the point to the box.
-    pub fn closest_point(&self, point: Vec3) -> Vec3 {
-        Vec3::new(
-            point.x.clamp(self.min.x, self.max.x),
-            point.y.clamp(self.min.y, self.max.y),
-            point.z.clamp(self.min.z, self.max.z),
-        )
-    }
-}
-
 // ===== FLAGS =====
 
 /// Render instance flags (bitfield)
@@ -142,10 +76,22 @@ pub struct RenderSubMeshPass {
     cached_pass_info_gen: u64,
     /// Material generation at the time of pipeline resolution
     cached_material_gen: u64,
+    /// Engine features generation at the time of pipeline resolution
+    cached_features_gen: u64,
+    /// Depth pre-pass pipeline key and depth-Equal render state signature id,
+    /// valid alongside `cached_pipeline_key` (cleared when it is re-cached)
+    cached_depth_prepass: Option<(PipelineKey, u16)>,
     /// LOD index selected for this pass last frame. Used as the previous
     /// state for LOD hysteresis in the `ViewDispatcher`. Initial value 0
     /// (highest-detail LOD).
     current_lod: u8,
+    /// False until the `ViewDispatcher` selects a LOD for this pass: the
+    /// first selection never cross-fades.
+    lod_selected: bool,
+    /// LOD being faded out after a switch
+    fade_from_lod: u8,
+    /// Cross-fade frames elapsed since the switch (0 = no cross-fade)
+    fade_elapsed: u8,
 }
 
 // ===== RENDER SUBMESH =====
@@ -194,10 +140,24 @@ pub struct RenderInstance {
     sub_meshes: Vec<RenderSubMesh>,
     /// World transform matrix (pre-computed by game engine)
     world_matrix: Mat4,
+    /// World matrix of the previous GPU upload (motion vectors)
+    previous_world_matrix: Mat4,
     /// Bit flags (visibility, shadow casting, etc.)
     flags: u64,
     /// Axis-Aligned Bounding Box in local space
     bounding_box: AABB,
+    /// Morph target weights, in the Geometry's morph target order
+    morph_weights: [f32; MAX_MORPH_TARGETS],
+    /// Color multiplier applied on top of the material (RGBA, 1 = unchanged)
+    color_tint: Vec4,
+    /// Emissive color added on top of the material (RGB, 0 = unchanged)
+    emissive_tint: Vec3,
+    /// Mesh the instance was built from (None outside a Scene)
+    mesh: Option<MeshKey>,
+    /// Default vertex shader given at creation (before overrides)
+    vertex_shader: ShaderKey,
+    /// LOD drawn whatever the distance (None: hysteresis selection)
+    lod_override: Option<u8>,
 }
 
 // ===== RENDER INSTANCE IMPLEMENTATION =====
@@ -211,7 +171,8 @@ impl RenderInstance {
     ///
     /// `vertex_shader` is the default vertex shader for all passes of all
     /// submeshes. `vertex_shader_overrides` can override the VS for specific
-    /// (submesh index, pass_type) pairs.
+    /// (submesh index, pass_type) pairs. Every vertex shader is checked
+    /// against the geometry vertex layout (see `validate_vertex_inputs`).
     pub(crate) fn from_mesh(
         mesh: &Mesh,
         world_matrix: Mat4,
@@ -223,12 +184,18 @@ impl RenderInstance {
     ) -> Result<Self> {
         // ===== Validation: geometry + mesh exist =====
         let geometry_arc = resource_manager.geometry(mesh.geometry())
-            .ok_or_else(|| engine_err!("galaxy3d::RenderInstance",
-                "Geometry key not found in ResourceManager"))?;
+            .ok_or_else(|| engine_not_found!("galaxy3d::RenderInstance",
+                "Geometry", format!("{:?}", mesh.geometry())))?;
         let geom_mesh = geometry_arc.mesh(mesh.geometry_mesh_id())
             .ok_or_else(|| engine_err!("galaxy3d::RenderInstance",
                 "GeometryMesh id {} not found", mesh.geometry_mesh_id()))?;
 
+        // ===== Validation: vertex shaders read attributes the geometry has =====
+        validate_vertex_inputs(geometry_arc, vertex_shader, resource_manager)?;
+        for vs_override in vertex_shader_overrides {
+            validate_vertex_inputs(geometry_arc, vs_override.vertex_shader, resource_manager)?;
+        }
+
         // ===== Build RenderSubMeshes from mesh submeshes =====
         let mut sub_meshes = Vec::with_capacity(mesh.submesh_count());
 
@@ -244,10 +211,21 @@ impl RenderInstance {
                     submesh.submesh_id()));
             }
 
-            let material_key = submesh.material();
-            let material = resource_manager.material(material_key)
-                .ok_or_else(|| engine_err!("galaxy3d::RenderInstance",
-                    "Material key not found in ResourceManager"))?;
+            // A material removed after the mesh was created falls back to
+            // the error material when the default resources exist
+            let (material_key, material) = match resource_manager.material(submesh.material()) {
+                Some(material) => (submesh.material(), material),
+                None => {
+                    let error_material = resource_manager.default_resources()
+                        .and_then(|defaults| resource_manager.material(defaults.error_material)
+                            .map(|material| (defaults.error_material, material)))
+                        .ok_or_else(|| engine_not_found!("galaxy3d::RenderInstance",
+                            "Material", format!("{:?}", submesh.material())))?;
+                    engine_warn!("galaxy3d::RenderInstance",
+                        "Material {:?} not found, using the error material", submesh.material());
+                    error_material
+                }
+            };
 
             // Build per-pass data from the material's passes
             let mut pass_mask: u64 = 0;
@@ -272,7 +250,12 @@ impl RenderInstance {
                     cached_pipeline_key: None,
                     cached_pass_info_gen: 0,
                     cached_material_gen: 0,
+                    cached_features_gen: 0,
+                    cached_depth_prepass: None,
                     current_lod: 0,
+                    lod_selected: false,
+                    fade_from_lod: 0,
+                    fade_elapsed: 0,
                 });
 
                 pass_mask |= 1u64 << pt;
@@ -293,8 +276,15 @@ impl RenderInstance {
             geometry_mesh_id: mesh.geometry_mesh_id(),
             sub_meshes,
             world_matrix,
+            previous_world_matrix: world_matrix,
             flags: FLAG_VISIBLE,
             bounding_box,
+            morph_weights: [0.0; MAX_MORPH_TARGETS],
+            color_tint: Vec4::ONE,
+            emissive_tint: Vec3::ZERO,
+            mesh: None,
+            vertex_shader,
+            lod_override: None,
         })
     }
 
@@ -310,6 +300,33 @@ impl RenderInstance {
         self.geometry_mesh_id
     }
 
+    /// Mesh the instance was created from (None if built outside a Scene)
+    pub fn mesh(&self) -> Option<MeshKey> {
+        self.mesh
+    }
+
+    pub(crate) fn set_mesh(&mut self, mesh: MeshKey) {
+        self.mesh = Some(mesh);
+    }
+
+    /// Default vertex shader given at creation. Passes listed in the
+    /// creation's `VertexShaderOverride`s use their own (see
+    /// `RenderSubMeshPass::vertex_shader`).
+    pub fn vertex_shader(&self) -> ShaderKey {
+        self.vertex_shader
+    }
+
+    /// LOD forced on every submesh, if any
+    pub fn lod_override(&self) -> Option<u8> {
+        self.lod_override
+    }
+
+    /// Force a LOD on every submesh (clamped to each submesh's last LOD),
+    /// or None to go back to the distance-based selection
+    pub fn set_lod_override(&mut self, lod: Option<u8>) {
+        self.lod_override = lod;
+    }
+
     /// Get a submesh by index
     pub fn sub_mesh(&self, index: usize) -> Option<&RenderSubMesh> {
         self.sub_meshes.get(index)
@@ -338,6 +355,52 @@ impl RenderInstance {
         self.world_matrix = matrix;
     }
 
+    /// World matrix as of the previous GPU upload of the transform (equal
+    /// to the world matrix at creation). Written to `previousWorld` for
+    /// motion vectors.
+    pub fn previous_world_matrix(&self) -> &Mat4 {
+        &self.previous_world_matrix
+    }
+
+    /// Record the current world matrix as uploaded (called by the updater)
+    pub(crate) fn commit_previous_world_matrix(&mut self) {
+        self.previous_world_matrix = self.world_matrix;
+    }
+
+    /// Get the morph target weights
+    pub fn morph_weights(&self) -> &[f32; MAX_MORPH_TARGETS] {
+        &self.morph_weights
+    }
+
+    /// Set the morph target weights. Missing weights are set to 0, weights
+    /// beyond `MAX_MORPH_TARGETS` are ignored.
+    pub fn set_morph_weights(&mut self, weights: &[f32]) {
+        self.morph_weights = [0.0; MAX_MORPH_TARGETS];
+        for (dst, src) in self.morph_weights.iter_mut().zip(weights) {
+            *dst = *src;
+        }
+    }
+
+    /// Get the color multiplier
+    pub fn color_tint(&self) -> Vec4 {
+        self.color_tint
+    }
+
+    /// Set the color multiplier (RGBA, `Vec4::ONE` = no tint)
+    pub fn set_color_tint(&mut self, tint: Vec4) {
+        self.color_tint = tint;
+    }
+
+    /// Get the additive emissive color
+    pub fn emissive_tint(&self) -> Vec3 {
+        self.emissive_tint
+    }
+
+    /// Set the additive emissive color (`Vec3::ZERO` = no tint)
+    pub fn set_emissive_tint(&mut self, tint: Vec3) {
+        self.emissive_tint = tint;
+    }
+
     /// Get the flags
     pub fn flags(&self) -> u64 {
         self.flags
@@ -495,17 +558,38 @@ impl RenderSubMeshPass {
     }
 
     /// Check if the cached pipeline is still valid for the given generations
-    pub fn is_pipeline_valid(&self, pass_info_gen: u64, material_gen: u64) -> bool {
+    pub fn is_pipeline_valid(&self, pass_info_gen: u64, material_gen: u64, features_gen: u64) -> bool {
         self.cached_pipeline_key.is_some()
             && self.cached_pass_info_gen == pass_info_gen
             && self.cached_material_gen == material_gen
+            && self.cached_features_gen == features_gen
     }
 
     /// Cache a resolved pipeline key with the current generation counters
-    pub fn set_cached_pipeline(&mut self, key: PipelineKey, pass_info_gen: u64, material_gen: u64) {
+    pub fn set_cached_pipeline(
+        &mut self,
+        key: PipelineKey,
+        pass_info_gen: u64,
+        material_gen: u64,
+        features_gen: u64,
+    ) {
         self.cached_pipeline_key = Some(key);
         self.cached_pass_info_gen = pass_info_gen;
         self.cached_material_gen = material_gen;
+        self.cached_features_gen = features_gen;
+        self.cached_depth_prepass = None;
+    }
+
+    /// Get the cached depth pre-pass data: depth-only pipeline key and the
+    /// render state signature id of the depth-Equal shading state.
+    /// Only meaningful while `is_pipeline_valid` holds.
+    pub fn cached_depth_prepass(&self) -> Option<(PipelineKey, u16)> {
+        self.cached_depth_prepass
+    }
+
+    /// Cache the depth pre-pass data for the currently cached pipeline
+    pub fn set_cached_depth_prepass(&mut self, pipeline_key: PipelineKey, equal_state_sig: u16) {
+        self.cached_depth_prepass = Some((pipeline_key, equal_state_sig));
     }
 
     /// Get the last LOD index selected for this pass (hysteresis state).
@@ -513,10 +597,66 @@ impl RenderSubMeshPass {
         self.current_lod
     }
 
-    /// Update the current LOD (called by the `ViewDispatcher` each frame).
-    pub(crate) fn set_current_lod(&mut self, lod: u8) {
+    /// LOD being faded out and transition progress, while a cross-fade
+    /// started by `select_lod` is running.
+    pub fn lod_cross_fade(&self, cross_fade_frames: u8) -> Option<(u8, f32)> {
+        if self.fade_elapsed == 0 || self.fade_elapsed > cross_fade_frames {
+            return None;
+        }
+        Some((self.fade_from_lod, LodFade::progress(self.fade_elapsed, cross_fade_frames)))
+    }
+
+    /// Select the LOD of this frame and advance the cross-fade (called by
+    /// the `ViewDispatcher` each frame). A switch restarts the cross-fade
+    /// from the LOD selected last frame, even in the middle of another one.
+    pub(crate) fn select_lod(&mut self, lod: u8, cross_fade_frames: u8) {
+        if self.lod_selected && lod != self.current_lod && cross_fade_frames > 0 {
+            self.fade_from_lod = self.current_lod;
+            self.fade_elapsed = 1;
+        } else if self.fade_elapsed > 0 {
+            self.fade_elapsed = if self.fade_elapsed < cross_fade_frames { self.fade_elapsed + 1 } else { 0 };
+        }
         self.current_lod = lod;
+        self.lod_selected = true;
+    }
+}
+
+// ===== VERTEX INPUT VALIDATION =====
+
+/// Check that a geometry provides every input of a vertex shader.
+///
+/// Each input location needs a geometry attribute at that location with
+/// the same numeric type (float, int or uint). Component counts may differ:
+/// the GPU drops extra components and fills missing ones with (0, 0, 0, 1).
+/// The instance draw slot location is fed by the drawer, not the geometry.
+fn validate_vertex_inputs(
+    geometry: &Geometry,
+    vertex_shader: ShaderKey,
+    resource_manager: &ResourceManager,
+) -> Result<()> {
+    let shader = resource_manager.shader(vertex_shader)
+        .ok_or_else(|| engine_not_found!("galaxy3d::RenderInstance",
+            "Vertex shader", format!("{:?}", vertex_shader)))?;
+    let attributes = &geometry.vertex_layout().attributes;
+    for input in shader.graphics_device_shader().reflected_vertex_inputs() {
+        if input.location == INSTANCE_DRAW_SLOT_LOCATION {
+            continue;
+        }
+        let Some(attribute) = attributes.iter().find(|a| a.location == input.location) else {
+            engine_bail!("galaxy3d::RenderInstance",
+                "Geometry '{}' has no vertex attribute at location {} read by vertex shader input '{}' \
+                 ({:?} x{}): add the attribute to the geometry vertex layout or remove the input",
+                geometry.name(), input.location, input.name, input.scalar_kind, input.component_count);
+        };
+        if attribute.format.scalar_kind() != input.scalar_kind {
+            engine_bail!("galaxy3d::RenderInstance",
+                "Geometry '{}' attribute at location {} is {:?} ({:?}) but vertex shader input '{}' \
+                 reads {:?} x{}: change the attribute format or the shader input type",
+                geometry.name(), input.location, attribute.format, attribute.format.scalar_kind(),
+                input.name, input.scalar_kind, input.component_count);
+        }
     }
+    Ok(())
 }
 
 #[cfg(test)]
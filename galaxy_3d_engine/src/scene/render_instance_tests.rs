@@ -6,7 +6,7 @@ use crate::graphics_device::{
     PrimitiveTopology, BufferFormat, TextureFormat, TextureUsage,
     MipmapMode, TextureData, SamplerType, ShaderStage,
     VertexLayout, VertexBinding, VertexAttribute,
-    VertexInputRate, IndexType, PolygonMode,
+    VertexInputRate, IndexType, PolygonMode, ReflectedVertexInput, ScalarKind,
 };
 use crate::resource::geometry::{
     GeometryDesc, GeometryMeshDesc, GeometrySubMeshDesc, GeometrySubMeshLODDesc,
@@ -58,7 +58,7 @@ fn create_test_pipeline_desc(vk: ShaderKey, fk: ShaderKey) -> PipelineDesc {
         vertex_layout: create_vertex_layout(),
         topology: PrimitiveTopology::TriangleList,
         rasterization: Default::default(), color_blend: Default::default(),
-        multisample: Default::default(), color_formats: vec![], depth_format: None,
+        multisample: Default::default(), color_formats: vec![], depth_format: None, engine_features: Default::default(),
     }
 }
 
@@ -95,8 +95,8 @@ fn create_test_resources() -> TestResources {
     // Mesh "object" with 3 submeshes (body, head, legs), each with 1 LOD.
     let geo_key = rm.create_geometry("test_geo".to_string(), GeometryDesc {
         name: "test_geo".to_string(), graphics_device: gd.clone(),
-        vertex_data: vec![0u8; 120], index_data: Some(vec![0u8; 36]),
-        vertex_layout: create_vertex_layout(), index_type: IndexType::U16,
+        vertex_data: vec![0u8; 120].into(), index_data: Some(vec![0u8; 36].into()),
+        vertex_layout: create_vertex_layout(), index_type: IndexType::U16, morph_targets: Vec::new(),
         meshes: vec![GeometryMeshDesc {
             name: "object".to_string(),
             submeshes: vec![
@@ -127,6 +127,8 @@ fn create_test_resources() -> TestResources {
             fragment_shader: fk, color_blend: Default::default(), polygon_mode: PolygonMode::Fill, textures: vec![],
             params: vec![("color".to_string(), ParamValue::Vec4([1.0, 0.5, 0.2, 1.0]))],
             render_state: None,
+            engine_features: Default::default(),
+            render_queue: None,
         }],
     }, &*gd.lock().unwrap()).unwrap();
 
@@ -151,8 +153,8 @@ fn create_non_indexed_resources() -> (TestResources, MeshKey) {
 
     let geo_key = rm.create_geometry("ni_geo".to_string(), GeometryDesc {
         name: "ni_geo".to_string(), graphics_device: gd.clone(),
-        vertex_data: vec![0u8; 48], index_data: None,
-        vertex_layout: create_vertex_layout(), index_type: IndexType::U16,
+        vertex_data: vec![0u8; 48].into(), index_data: None,
+        vertex_layout: create_vertex_layout(), index_type: IndexType::U16, morph_targets: Vec::new(),
         meshes: vec![GeometryMeshDesc {
             name: "simple".to_string(),
             submeshes: vec![GeometrySubMeshDesc {
@@ -168,7 +170,7 @@ fn create_non_indexed_resources() -> (TestResources, MeshKey) {
     let mk = rm.create_material("m".to_string(), MaterialDesc {
         passes: vec![MaterialPassDesc {
             pass_type: 0,
-            fragment_shader: fk, color_blend: Default::default(), polygon_mode: PolygonMode::Fill, textures: vec![], params: vec![("color".to_string(), ParamValue::Vec4([1.0, 0.5, 0.2, 1.0]))], render_state: None,
+            fragment_shader: fk, color_blend: Default::default(), polygon_mode: PolygonMode::Fill, textures: vec![], params: vec![("color".to_string(), ParamValue::Vec4([1.0, 0.5, 0.2, 1.0]))], render_state: None, engine_features: Default::default(), render_queue: None,
         }],
     }, &*gd.lock().unwrap()).unwrap();
 
@@ -216,6 +218,64 @@ fn test_aabb_debug() {
     assert!(debug.contains("AABB"));
 }
 
+/// Reference AABB transform: bound the 8 transformed corners
+fn transformed_corners(aabb: &AABB, matrix: &Mat4) -> AABB {
+    let mut min = Vec3::splat(f32::INFINITY);
+    let mut max = Vec3::splat(f32::NEG_INFINITY);
+    for i in 0..8 {
+        let corner = Vec3::new(
+            if i & 1 == 0 { aabb.min.x } else { aabb.max.x },
+            if i & 2 == 0 { aabb.min.y } else { aabb.max.y },
+            if i & 4 == 0 { aabb.min.z } else { aabb.max.z },
+        );
+        let p = matrix.transform_point3(corner);
+        min = min.min(p);
+        max = max.max(p);
+    }
+    AABB { min, max }
+}
+
+#[test]
+fn test_aabb_transformed_matches_corner_reference_grid() {
+    let boxes = [
+        AABB { min: Vec3::new(-1.0, -2.0, -3.0), max: Vec3::new(1.0, 2.0, 3.0) },
+        AABB { min: Vec3::new(2.0, 0.5, -7.0), max: Vec3::new(4.0, 0.5, -1.0) }, // flat
+        AABB { min: Vec3::splat(3.0), max: Vec3::splat(3.0) },                    // zero-size
+    ];
+    let angles = [0.0, 0.3, std::f32::consts::FRAC_PI_2, 2.5, -1.2];
+    let scales = [Vec3::ONE, Vec3::new(2.0, 0.5, 3.0), Vec3::new(-1.0, 1.0, 0.0)];
+    let translations = [Vec3::ZERO, Vec3::new(10.0, -5.0, 100.0)];
+
+    for aabb in &boxes {
+        for &yaw in &angles {
+            for &pitch in &angles {
+                for &scale in &scales {
+                    for &translation in &translations {
+                        let rotation = glam::Quat::from_euler(glam::EulerRot::YXZ, yaw, pitch, 0.0);
+                        let m = Mat4::from_scale_rotation_translation(scale, rotation, translation);
+                        let got = aabb.transformed(&m);
+                        let want = transformed_corners(aabb, &m);
+                        let tolerance = 1e-4 * (1.0 + want.max.abs().max_element().max(want.min.abs().max_element()));
+                        assert!((got.min - want.min).abs().max_element() < tolerance
+                            && (got.max - want.max).abs().max_element() < tolerance,
+                            "{:?} * {:?}: {:?} != {:?}", aabb, m, got, want);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_aabb_is_finite() {
+    assert!(create_test_aabb().is_finite());
+    let nan = AABB { min: Vec3::new(f32::NAN, 0.0, 0.0), max: Vec3::ONE };
+    assert!(!nan.is_finite());
+    // A NaN transform propagates into the result, which callers can detect
+    let m = Mat4::from_translation(Vec3::new(0.0, f32::NAN, 0.0));
+    assert!(!create_test_aabb().transformed(&m).is_finite());
+}
+
 // ============================================================================
 // Tests: Flags
 // ============================================================================
@@ -330,8 +390,8 @@ fn test_from_mesh_material_with_texture() {
 
     let geo_key = rm.create_geometry("geo".to_string(), GeometryDesc {
         name: "geo".to_string(), graphics_device: gd.clone(),
-        vertex_data: vec![0u8; 120], index_data: Some(vec![0u8; 36]),
-        vertex_layout: create_vertex_layout(), index_type: IndexType::U16,
+        vertex_data: vec![0u8; 120].into(), index_data: Some(vec![0u8; 36].into()),
+        vertex_layout: create_vertex_layout(), index_type: IndexType::U16, morph_targets: Vec::new(),
         meshes: vec![GeometryMeshDesc {
             name: "object".to_string(),
             submeshes: vec![
@@ -352,11 +412,12 @@ fn test_from_mesh_material_with_texture() {
     let tex_key = rm.create_texture("tex".to_string(), TextureDesc {
         graphics_device: gd.clone(),
         texture: crate::graphics_device::TextureDesc {
-            width: 64, height: 64, format: TextureFormat::R8G8B8A8_UNORM,
+            width: 64, height: 64, depth: 1, format: TextureFormat::R8G8B8A8_UNORM,
             usage: TextureUsage::Sampled, array_layers: 1,
             data: Some(TextureData::Single(vec![255u8; 64*64*4])),
             mipmap: MipmapMode::None, texture_type: crate::graphics_device::TextureType::Tex2D,
             sample_count: crate::graphics_device::SampleCount::S1,
+            debug_name: None,
         },
         layers: vec![LayerDesc { name: "default".to_string(), layer_index: 0, data: None, regions: vec![] }],
     }).unwrap();
@@ -367,8 +428,8 @@ fn test_from_mesh_material_with_texture() {
         passes: vec![MaterialPassDesc {
             pass_type: 0,
             fragment_shader: fk, color_blend: Default::default(), polygon_mode: PolygonMode::Fill, textures: vec![MaterialTextureSlotDesc {
-                name: "diffuse".to_string(), texture: tex_key, layer: None, region: None, sampler_type: SamplerType::LinearRepeat,
-            }], params: vec![("roughness".to_string(), ParamValue::Float(0.8)), ("metallic".to_string(), ParamValue::Float(0.0))], render_state: None,
+                name: "diffuse".to_string(), texture: tex_key, layer: None, region: None, sampler_type: SamplerType::LinearRepeat, flipbook: None,
+            }], params: vec![("roughness".to_string(), ParamValue::Float(0.8)), ("metallic".to_string(), ParamValue::Float(0.0))], render_state: None, engine_features: Default::default(), render_queue: None,
         }],
     }, &*gd.lock().unwrap()).unwrap();
 
@@ -429,6 +490,75 @@ fn test_from_mesh_stores_bounding_box() {
     assert_eq!(instance.bounding_box().max, Vec3::new(5.0, 10.0, 5.0));
 }
 
+// ============================================================================
+// Tests: Vertex Input Validation
+// ============================================================================
+
+fn vertex_input(location: u32, scalar_kind: ScalarKind, component_count: u32) -> ReflectedVertexInput {
+    ReflectedVertexInput { name: format!("in{}", location), location, scalar_kind, component_count }
+}
+
+/// Create a vertex shader reflecting the given inputs
+fn create_vertex_shader_with_inputs(rm: &mut ResourceManager, name: &str, inputs: Vec<ReflectedVertexInput>) -> ShaderKey {
+    let mut gd = MockGraphicsDevice::new();
+    gd.vertex_shader_inputs = inputs;
+    rm.create_shader(name.to_string(), ShaderDesc { code: &[], stage: ShaderStage::Vertex, entry_point: "main".to_string() }, &mut gd).unwrap()
+}
+
+#[test]
+fn test_from_mesh_accepts_compatible_vertex_inputs() {
+    let mut res = create_test_resources();
+    let mk = create_simple_mesh_key(&mut res);
+    // vec3 read from a vec2 attribute (z filled by the GPU) + the drawer's instance slot
+    let vs = create_vertex_shader_with_inputs(&mut res.rm, "vs_ok", vec![
+        vertex_input(0, ScalarKind::Float32, 3),
+        vertex_input(INSTANCE_DRAW_SLOT_LOCATION, ScalarKind::UInt32, 1),
+    ]);
+    assert!(create_test_render_instance(mk, Mat4::IDENTITY, create_test_aabb(), vs, &res.rm).is_ok());
+}
+
+#[test]
+fn test_from_mesh_rejects_missing_vertex_attribute() {
+    let mut res = create_test_resources();
+    let mk = create_simple_mesh_key(&mut res);
+    let vs = create_vertex_shader_with_inputs(&mut res.rm, "vs_skinned", vec![
+        vertex_input(0, ScalarKind::Float32, 3),
+        vertex_input(4, ScalarKind::UInt32, 4),
+    ]);
+    let err = create_test_render_instance(mk, Mat4::IDENTITY, create_test_aabb(), vs, &res.rm).err().unwrap();
+    let message = err.to_string();
+    assert!(message.contains("no vertex attribute at location 4"), "{}", message);
+    assert!(message.contains("'in4'"), "{}", message);
+}
+
+#[test]
+fn test_from_mesh_rejects_vertex_format_mismatch() {
+    let mut res = create_test_resources();
+    let mk = create_simple_mesh_key(&mut res);
+    let vs = create_vertex_shader_with_inputs(&mut res.rm, "vs_uint", vec![
+        vertex_input(0, ScalarKind::UInt32, 2),
+    ]);
+    let err = create_test_render_instance(mk, Mat4::IDENTITY, create_test_aabb(), vs, &res.rm).err().unwrap();
+    let message = err.to_string();
+    assert!(message.contains("R32G32_SFLOAT"), "{}", message);
+    assert!(message.contains("UInt32"), "{}", message);
+}
+
+#[test]
+fn test_from_mesh_validates_vertex_shader_overrides() {
+    let mut res = create_test_resources();
+    let mk = create_simple_mesh_key(&mut res);
+    let bad_vs = create_vertex_shader_with_inputs(&mut res.rm, "vs_bad", vec![
+        vertex_input(3, ScalarKind::Float32, 4),
+    ]);
+    let overrides = [VertexShaderOverride { submesh: 1, pass_type: 0, vertex_shader: bad_vs }];
+    let mesh = res.rm.mesh(mk).unwrap();
+    let mut alloc = SlotAllocator::new();
+    let result = RenderInstance::from_mesh(
+        mesh, Mat4::IDENTITY, create_test_aabb(), res.vertex_shader_key, &overrides, &mut alloc, &res.rm);
+    assert!(result.is_err());
+}
+
 // ============================================================================
 // Tests: RenderInstance Accessors and Mutators
 // ============================================================================
@@ -858,11 +988,27 @@ mod flags_and_passes {
         let sm = inst.sub_mesh_mut(0).unwrap();
         let pass = sm.pass_by_index_mut(0).unwrap();
         let pk = PipelineKey::default();
-        pass.set_cached_pipeline(pk, 5, 7);
+        pass.set_cached_pipeline(pk, 5, 7, 2);
         assert_eq!(pass.cached_pipeline_key(), Some(pk));
-        assert!(pass.is_pipeline_valid(5, 7));
-        assert!(!pass.is_pipeline_valid(6, 7));
-        assert!(!pass.is_pipeline_valid(5, 8));
+        assert!(pass.is_pipeline_valid(5, 7, 2));
+        assert!(!pass.is_pipeline_valid(6, 7, 2));
+        assert!(!pass.is_pipeline_valid(5, 8, 2));
+        assert!(!pass.is_pipeline_valid(5, 7, 3));
+    }
+
+    #[test]
+    fn test_set_cached_pipeline_clears_depth_prepass() {
+        use crate::resource::resource_manager::PipelineKey;
+        let mut inst = make_instance();
+        let sm = inst.sub_mesh_mut(0).unwrap();
+        let pass = sm.pass_by_index_mut(0).unwrap();
+        let pk = PipelineKey::default();
+        assert_eq!(pass.cached_depth_prepass(), None);
+        pass.set_cached_pipeline(pk, 1, 1, 0);
+        pass.set_cached_depth_prepass(pk, 3);
+        assert_eq!(pass.cached_depth_prepass(), Some((pk, 3)));
+        pass.set_cached_pipeline(pk, 2, 1, 0);
+        assert_eq!(pass.cached_depth_prepass(), None);
     }
 
     #[test]
@@ -9,10 +9,12 @@
 //! - Indirect sort: we sort a small `SortEntry { sort_key, index }` array,
 //!   never the heavier `DrawCall` records themselves (cache-friendly).
 //! - Sort-key layout (MSB → LSB):
-//!     [63..48] pipeline signature id — preserves descriptor set binds
-//!     [47..32] pipeline sort id       — groups identical pipelines
-//!     [31..16] geometry sort id       — groups identical vertex/index buffers
-//!     [15..0]  distance               — front-to-back for opaque passes
+//!   [63..48] pipeline signature id — preserves descriptor set binds
+//!   [47..32] pipeline sort id       — groups identical pipelines
+//!   [31..16] geometry sort id       — groups identical vertex/index buffers
+//!   [15..0]  render state signature — skips redundant dynamic state
+//! - Transparent draw calls use `build_transparent_sort_key()` instead, with
+//!   the inverted view depth in the MSBs for back-to-front blending.
 
 use rdst::{RadixKey, RadixSort};
 use crate::graphics_device::DynamicRenderState;
@@ -51,6 +53,24 @@ pub fn build_sort_key(
   |  (render_state_sig as u64)
 }
 
+/// Build a packed 64-bit sort key for transparent draw-call sorting.
+///
+/// Farthest first: the view depth, inverted, takes the 16 MSBs so blending
+/// composites back-to-front. Pipeline, geometry and render state only break
+/// ties between draw calls at the same quantized depth.
+#[inline]
+pub fn build_transparent_sort_key(
+    distance: f32,
+    pipeline_sort_id: u16,
+    geometry_sort_id: u16,
+    render_state_sig: u16,
+) -> u64 {
+    ((!distance_to_u16(distance) as u64) << 48)
+  | ((pipeline_sort_id as u64)          << 32)
+  | ((geometry_sort_id as u64)          << 16)
+  |  (render_state_sig as u64)
+}
+
 /// One entry in the auxiliary sort array.
 /// 16 bytes with natural alignment (4 entries per 64-byte cache line).
 #[repr(C)]
@@ -79,6 +99,8 @@ pub struct DrawCall {
     pub vertex_count: u32,
     pub index_offset: u32,
     pub index_count: u32,
+    /// Draw slot passed to the shader, with the LOD cross-fade bits
+    /// (`LodFade::encode`)
     pub draw_slot: u32,
     pub render_state: DynamicRenderState,
     /// Stable u16 id identifying `render_state`. Draw calls with equal ids share
@@ -144,6 +166,21 @@ impl RenderQueue {
         self.sort_entries.radix_sort_unstable();
     }
 
+    /// Sort like `sort()`, then order draw calls sharing a sort key by draw
+    /// range, so identical submeshes end up adjacent and can be instanced.
+    pub fn sort_grouping_draw_ranges(&mut self) {
+        self.sort();
+        let draw_calls = &self.draw_calls;
+        for run in self.sort_entries.chunk_by_mut(|a, b| a.sort_key == b.sort_key) {
+            if run.len() > 1 {
+                run.sort_unstable_by_key(|e| {
+                    let dc = &draw_calls[e.draw_call_index as usize];
+                    (dc.index_offset, dc.index_count, dc.vertex_offset, dc.vertex_count)
+                });
+            }
+        }
+    }
+
     /// Iterate draw calls in sorted order. Each entry indexes into `draw_calls`.
     pub fn iter_sorted(&self) -> impl Iterator<Item = &DrawCall> + '_ {
         self.sort_entries
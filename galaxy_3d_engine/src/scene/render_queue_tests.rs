@@ -16,6 +16,25 @@ fn make_dc(draw_slot: u32) -> DrawCall {
     }
 }
 
+// ============================================================================
+// build_transparent_sort_key
+// ============================================================================
+
+#[test]
+fn test_transparent_sort_key_far_before_near() {
+    let far = build_transparent_sort_key(50.0, 0, 0, 0);
+    let near = build_transparent_sort_key(2.0, 0xFFFF, 0xFFFF, 0xFFFF);
+    assert!(far < near);
+}
+
+#[test]
+fn test_transparent_sort_key_state_breaks_ties() {
+    let a = build_transparent_sort_key(10.0, 1, 0, 0);
+    let b = build_transparent_sort_key(10.0, 2, 0, 0);
+    assert!(a < b);
+    assert_eq!(a >> 48, b >> 48);
+}
+
 // ============================================================================
 // build_sort_key
 // ============================================================================
@@ -159,6 +178,24 @@ fn test_sort_with_full_packed_keys() {
     assert_eq!(slots, vec![2, 1, 0]);
 }
 
+#[test]
+fn test_sort_grouping_draw_ranges_makes_equal_ranges_adjacent() {
+    let ranged = |draw_slot, index_offset| DrawCall { index_offset, ..make_dc(draw_slot) };
+    let mut q = RenderQueue::with_capacity(8);
+    q.push(ranged(0, 0), 5);
+    q.push(ranged(1, 6), 5);
+    q.push(ranged(2, 0), 5);
+    q.push(ranged(3, 0), 1);
+    q.sort_grouping_draw_ranges();
+    let slots: Vec<u32> = q.iter_sorted().map(|dc| dc.draw_slot).collect();
+    // Key order is kept; within key 5, both index_offset 0 calls come first
+    assert_eq!(slots[0], 3);
+    assert_eq!(slots[3], 1);
+    let mut same_range = slots[1..3].to_vec();
+    same_range.sort();
+    assert_eq!(same_range, vec![0, 2]);
+}
+
 #[test]
 fn test_clear_then_reuse() {
     let mut q = RenderQueue::with_capacity(4);
@@ -3,14 +3,32 @@
 /// Produced by the `ViewDispatcher` from a `CulledInstances` (the raw output
 /// of frustum culling). Each RenderView is bound to a single `pass_type` and
 /// contains a flat list of `VisibleSubMesh` entries — one per (instance,
-/// submesh) pair that participates in this pass.
+/// submesh) pair that participates in this pass, two while the pair
+/// cross-fades between LODs.
 ///
 /// The `VisibleSubMesh` entries are fully resolved: submesh index, pass index,
 /// LOD index, and distance are all pre-computed so the Drawer can iterate
 /// without any lookup or decision logic.
+///
+/// Render scale: a view can render at a fraction (or a multiple) of its
+/// output resolution. The camera snapshot then carries the scaled viewport
+/// and scissor, so drawers need no change; `output_viewport()` keeps the
+/// full-resolution rectangle the composite pass upscales into (see
+/// `post::ViewTargetManager` and `post::Upscale`). An optional
+/// `FoveationMask` describes where full density matters most; it is only
+/// forwarded to the composite shader for now (hook for VR foveation).
 
 use crate::camera::Camera;
+use crate::error::Result;
+use crate::engine_bail;
+use crate::graphics_device::viewport::{Rect2D, Viewport};
 use super::render_instance::RenderInstanceKey;
+use super::lod::LodFade;
+
+/// Render scale of a new view (native resolution)
+pub const DEFAULT_RENDER_SCALE: f32 = 1.0;
+/// Largest render scale (2x supersampling per axis)
+pub const MAX_RENDER_SCALE: f32 = 2.0;
 
 /// A single draw item — one submesh of one instance, ready for drawing.
 ///
@@ -28,6 +46,54 @@ pub struct VisibleSubMesh {
     pub pass_index: u8,
     /// LOD index to use for the GeometrySubMesh (V1: always 0)
     pub lod_index: u8,
+    /// Dithered cross-fade of this LOD (`LodFade::None` outside transitions)
+    pub lod_fade: LodFade,
+}
+
+/// Radial render density over a view, in output UV space (0..1).
+///
+/// Density is 1 inside `inner_radius` around `center`, falls linearly to
+/// `outer_density` at `outer_radius` and stays there beyond. Radii are
+/// measured in UV units on both axes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FoveationMask {
+    pub center: [f32; 2],
+    pub inner_radius: f32,
+    pub outer_radius: f32,
+    /// Density of the periphery, in (0, 1]
+    pub outer_density: f32,
+}
+
+impl FoveationMask {
+    /// Relative render density at an output UV
+    pub fn density_at(&self, uv: [f32; 2]) -> f32 {
+        let dx = uv[0] - self.center[0];
+        let dy = uv[1] - self.center[1];
+        let distance = (dx * dx + dy * dy).sqrt();
+        if distance <= self.inner_radius {
+            return 1.0;
+        }
+        if distance >= self.outer_radius {
+            return self.outer_density;
+        }
+        let t = (distance - self.inner_radius) / (self.outer_radius - self.inner_radius);
+        1.0 + (self.outer_density - 1.0) * t
+    }
+
+    fn validate(&self) -> Result<()> {
+        let finite = self.center.iter().chain([&self.inner_radius, &self.outer_radius])
+            .all(|v| v.is_finite());
+        if !finite || self.inner_radius < 0.0 || self.outer_radius <= self.inner_radius {
+            engine_bail!("galaxy3d::RenderView",
+                "Foveation radii must be finite with 0 <= inner < outer, got {} and {}",
+                self.inner_radius, self.outer_radius);
+        }
+        if !(self.outer_density > 0.0 && self.outer_density <= 1.0) {
+            engine_bail!("galaxy3d::RenderView",
+                "Foveation outer density must be in (0, 1], got {}", self.outer_density);
+        }
+        Ok(())
+    }
 }
 
 /// A per-pass draw list — produced by the ViewDispatcher, consumed by a Drawer.
@@ -37,7 +103,14 @@ pub struct VisibleSubMesh {
 /// state once the high-water mark has been reached.
 #[derive(Debug, Clone)]
 pub struct RenderView {
+    /// Camera snapshot with the scaled viewport and scissor
     camera: Camera,
+    /// Viewport of the snapshot before scaling
+    output_viewport: Viewport,
+    /// Scissor of the snapshot before scaling
+    output_scissor: Option<Rect2D>,
+    render_scale: f32,
+    foveation: Option<FoveationMask>,
     pass_type: u8,
     items: Vec<VisibleSubMesh>,
 }
@@ -45,17 +118,17 @@ pub struct RenderView {
 impl RenderView {
     /// Create an empty RenderView for a given pass type.
     pub fn new(camera: Camera, pass_type: u8) -> Self {
-        Self {
-            camera,
-            pass_type,
-            items: Vec::new(),
-        }
+        Self::with_capacity(camera, pass_type, 0)
     }
 
     /// Create an empty RenderView with a pre-allocated capacity.
     pub fn with_capacity(camera: Camera, pass_type: u8, capacity: usize) -> Self {
         Self {
+            output_viewport: *camera.viewport(),
+            output_scissor: camera.scissor().copied(),
             camera,
+            render_scale: DEFAULT_RENDER_SCALE,
+            foveation: None,
             pass_type,
             items: Vec::with_capacity(capacity),
         }
@@ -63,11 +136,34 @@ impl RenderView {
 
     // ===== ACCESSORS =====
 
-    /// Camera snapshot (copied from the CulledInstances at dispatch time).
+    /// Camera snapshot (copied from the CulledInstances at dispatch time),
+    /// with the viewport and scissor scaled by `render_scale()`.
     pub fn camera(&self) -> &Camera {
         &self.camera
     }
 
+    /// Viewport of the camera snapshot at output resolution
+    pub fn output_viewport(&self) -> &Viewport {
+        &self.output_viewport
+    }
+
+    /// Resolution multiplier applied to the output viewport (1.0 = native)
+    pub fn render_scale(&self) -> f32 {
+        self.render_scale
+    }
+
+    /// Radial density mask, if any
+    pub fn foveation(&self) -> Option<&FoveationMask> {
+        self.foveation.as_ref()
+    }
+
+    /// Render target size for an output of `width` x `height` pixels
+    /// (rounded, at least 1x1)
+    pub fn scaled_extent(&self, width: u32, height: u32) -> (u32, u32) {
+        let scale = |size: u32| ((size as f32 * self.render_scale).round() as u32).max(1);
+        (scale(width), scale(height))
+    }
+
     /// The pass type this view represents.
     pub fn pass_type(&self) -> u8 {
         self.pass_type
@@ -95,9 +191,34 @@ impl RenderView {
 
     // ===== MUTATION (used by ViewDispatcher) =====
 
-    /// Replace the camera snapshot.
+    /// Replace the camera snapshot. Its viewport and scissor are the output
+    /// ones: the stored camera gets them scaled by `render_scale()`.
     pub fn set_camera(&mut self, camera: Camera) {
+        self.output_viewport = *camera.viewport();
+        self.output_scissor = camera.scissor().copied();
         self.camera = camera;
+        self.apply_render_scale();
+    }
+
+    /// Set the resolution multiplier, in (0, MAX_RENDER_SCALE]. Render
+    /// targets sized with `scaled_extent()` must be reallocated to match.
+    pub fn set_render_scale(&mut self, scale: f32) -> Result<()> {
+        if !(scale > 0.0 && scale <= MAX_RENDER_SCALE) {
+            engine_bail!("galaxy3d::RenderView",
+                "Render scale must be in (0, {}], got {}", MAX_RENDER_SCALE, scale);
+        }
+        self.render_scale = scale;
+        self.apply_render_scale();
+        Ok(())
+    }
+
+    /// Set or remove the radial density mask.
+    pub fn set_foveation(&mut self, foveation: Option<FoveationMask>) -> Result<()> {
+        if let Some(mask) = &foveation {
+            mask.validate()?;
+        }
+        self.foveation = foveation;
+        Ok(())
     }
 
     /// Push a draw item.
@@ -109,6 +230,27 @@ impl RenderView {
     pub fn clear(&mut self) {
         self.items.clear();
     }
+
+    // ===== PRIVATE HELPERS =====
+
+    /// Scale the output viewport and scissor into the camera snapshot.
+    fn apply_render_scale(&mut self) {
+        let scale = self.render_scale;
+        let viewport = self.output_viewport;
+        self.camera.set_viewport(Viewport {
+            x: viewport.x * scale,
+            y: viewport.y * scale,
+            width: viewport.width * scale,
+            height: viewport.height * scale,
+            ..viewport
+        });
+        self.camera.set_scissor(self.output_scissor.map(|scissor| Rect2D {
+            x: (scissor.x as f32 * scale).round() as i32,
+            y: (scissor.y as f32 * scale).round() as i32,
+            width: (scissor.width as f32 * scale).round() as u32,
+            height: (scissor.height as f32 * scale).round() as u32,
+        }));
+    }
 }
 
 #[cfg(test)]
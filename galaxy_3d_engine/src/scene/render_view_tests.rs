@@ -18,6 +18,7 @@ fn make_item() -> VisibleSubMesh {
         submesh_index: 0,
         pass_index: 0,
         lod_index: 0,
+        lod_fade: LodFade::None,
     }
 }
 
@@ -91,6 +92,7 @@ fn test_visible_sub_mesh_clone_copy() {
         submesh_index: 1,
         pass_index: 2,
         lod_index: 4,
+        lod_fade: LodFade::Out(0.5),
     };
     let copy = item;
     let cloned = item.clone();
@@ -98,6 +100,7 @@ fn test_visible_sub_mesh_clone_copy() {
     assert_eq!(cloned.submesh_index, 1);
     assert_eq!(cloned.pass_index, 2);
     assert_eq!(cloned.lod_index, 4);
+    assert_eq!(cloned.lod_fade, LodFade::Out(0.5));
 }
 
 #[test]
@@ -120,3 +123,57 @@ fn test_clone_preserves_items_and_pass_type() {
     assert_eq!(cloned.len(), 2);
     assert_eq!(cloned.pass_type(), 7);
 }
+
+// ============================================================================
+// Render scale and foveation
+// ============================================================================
+
+#[test]
+fn test_render_scale_scales_camera_viewport_and_scissor() {
+    let mut camera = make_camera(1920.0);
+    camera.set_scissor(Some(crate::graphics_device::Rect2D { x: 100, y: 0, width: 800, height: 600 }));
+    let mut view = RenderView::new(camera, 0);
+    assert_eq!(view.render_scale(), DEFAULT_RENDER_SCALE);
+
+    view.set_render_scale(0.5).unwrap();
+    assert_eq!(view.camera().viewport().width, 960.0);
+    assert_eq!(view.camera().viewport().height, 540.0);
+    assert_eq!(view.camera().scissor().unwrap().x, 50);
+    assert_eq!(view.camera().scissor().unwrap().width, 400);
+    assert_eq!(view.output_viewport().width, 1920.0);
+    assert_eq!(view.scaled_extent(1920, 1080), (960, 540));
+
+    // A new snapshot (dispatch) is scaled too
+    view.set_camera(make_camera(1280.0));
+    assert_eq!(view.camera().viewport().width, 640.0);
+    assert_eq!(view.output_viewport().width, 1280.0);
+}
+
+#[test]
+fn test_invalid_render_scale_is_rejected() {
+    let mut view = RenderView::new(make_camera(1920.0), 0);
+    assert!(view.set_render_scale(0.0).is_err());
+    assert!(view.set_render_scale(MAX_RENDER_SCALE + 0.5).is_err());
+    assert!(view.set_render_scale(f32::NAN).is_err());
+    assert_eq!(view.render_scale(), DEFAULT_RENDER_SCALE);
+
+    view.set_render_scale(0.001).unwrap();
+    assert_eq!(view.scaled_extent(100, 100), (1, 1));
+}
+
+#[test]
+fn test_foveation_density_falls_off_radially() {
+    let mask = FoveationMask { center: [0.5, 0.5], inner_radius: 0.1, outer_radius: 0.3, outer_density: 0.5 };
+    assert_eq!(mask.density_at([0.5, 0.55]), 1.0);
+    assert!((mask.density_at([0.7, 0.5]) - 0.75).abs() < 1e-5);
+    assert_eq!(mask.density_at([1.0, 1.0]), 0.5);
+
+    let mut view = RenderView::new(make_camera(1920.0), 0);
+    view.set_foveation(Some(mask)).unwrap();
+    assert_eq!(view.foveation(), Some(&mask));
+    assert!(view.set_foveation(Some(FoveationMask { outer_radius: 0.05, ..mask })).is_err());
+    assert!(view.set_foveation(Some(FoveationMask { outer_density: 0.0, ..mask })).is_err());
+    assert_eq!(view.foveation(), Some(&mask));
+    view.set_foveation(None).unwrap();
+    assert!(view.foveation().is_none());
+}
@@ -2,18 +2,28 @@
 ///
 /// Uses SlotMaps for O(1) insert/remove with stable keys.
 /// Instances and lights are stored contiguously for cache-friendly iteration.
+/// An optional SceneNode hierarchy can drive their transforms.
 
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 use slotmap::SlotMap;
-use glam::{Mat4, Vec3};
+use glam::{Mat4, Quat, Vec2, Vec3, Vec4};
 use crate::error::Result;
-use crate::engine_err;
+use crate::{engine_err, engine_not_found};
+use crate::camera::{Camera, Frustum};
 use crate::resource::resource_manager::{ResourceManager, MeshKey, ShaderKey};
 use crate::utils::{SlotAllocator, SwapSet};
+use super::aabb::AABB;
 use super::render_instance::{
-    RenderInstance, RenderInstanceKey, VertexShaderOverride, AABB,
+    RenderInstance, RenderInstanceKey, VertexShaderOverride,
 };
 use super::light::{Light, LightKey, LightType, LightDesc};
+use super::billboard::{Billboard, BillboardKey, BillboardDesc, BillboardMode};
+use super::environment::{SceneEnvironment, Fog};
+use super::clock::EngineClock;
+use super::scene_node::{SceneNode, SceneNodeKey, NodeLight, NodeCamera};
+use super::transform_components::TransformComponents;
+#[cfg(feature = "manifest")]
+use super::scene_file::{InstanceRecord, SceneFile, VertexShaderOverrideRecord};
 
 /// A renderable scene containing RenderInstances and Lights.
 ///
@@ -32,6 +42,12 @@ pub struct Scene {
     draw_slot_allocator: SlotAllocator,
     /// Instances whose world matrix changed since last frame
     dirty_instance_transforms: SwapSet<RenderInstanceKey>,
+    /// Instances whose morph weights changed since last frame
+    dirty_instance_morph_weights: SwapSet<RenderInstanceKey>,
+    /// Instances whose color or emissive tint changed since last frame
+    dirty_instance_tints: SwapSet<RenderInstanceKey>,
+    /// TRS components of the instances driven by components (SoA)
+    transform_components: TransformComponents,
     /// Newly created instances pending full GPU buffer initialization
     new_instances: SwapSet<RenderInstanceKey>,
     /// Instances marked for deferred removal (processed by Updater)
@@ -51,6 +67,35 @@ pub struct Scene {
     dirty_light_data: SwapSet<LightKey>,
     /// Lights marked for deferred removal
     removed_lights: SwapSet<LightKey>,
+
+    // ----- Billboards -----
+
+    /// Billboards (no GPU slot: `BillboardAction` uploads them every frame)
+    billboards: SlotMap<BillboardKey, Billboard>,
+
+    // ----- Nodes -----
+
+    /// Scene node hierarchy
+    nodes: SlotMap<SceneNodeKey, SceneNode>,
+    /// Nodes whose local transform or parent changed since the last
+    /// `update_node_transforms()`
+    dirty_nodes: FxHashSet<SceneNodeKey>,
+    /// Node each attached render instance belongs to
+    instance_nodes: FxHashMap<RenderInstanceKey, SceneNodeKey>,
+    /// Node each attached light belongs to
+    light_nodes: FxHashMap<LightKey, SceneNodeKey>,
+    /// Traversal stack of `update_node_transforms()`, reused across frames
+    node_stack: Vec<SceneNodeKey>,
+
+    // ----- Environment -----
+
+    /// Ambient light and fog (written to the frame buffer every frame)
+    environment: SceneEnvironment,
+
+    // ----- Time -----
+
+    /// Simulation clock (written to the frame buffer every frame)
+    clock: EngineClock,
 }
 
 impl Scene {
@@ -60,6 +105,9 @@ impl Scene {
             render_instances: SlotMap::with_key(),
             draw_slot_allocator: SlotAllocator::new(),
             dirty_instance_transforms: SwapSet::new(),
+            dirty_instance_morph_weights: SwapSet::new(),
+            dirty_instance_tints: SwapSet::new(),
+            transform_components: TransformComponents::new(),
             new_instances: SwapSet::new(),
             removed_instances: SwapSet::new(),
             lights: SlotMap::with_key(),
@@ -68,6 +116,14 @@ impl Scene {
             dirty_light_transforms: SwapSet::new(),
             dirty_light_data: SwapSet::new(),
             removed_lights: SwapSet::new(),
+            billboards: SlotMap::with_key(),
+            nodes: SlotMap::with_key(),
+            dirty_nodes: FxHashSet::default(),
+            instance_nodes: FxHashMap::default(),
+            light_nodes: FxHashMap::default(),
+            node_stack: Vec::new(),
+            environment: SceneEnvironment::default(),
+            clock: EngineClock::new(),
         }
     }
 
@@ -88,6 +144,12 @@ impl Scene {
     /// * `bounding_box` - AABB in local space
     /// * `vertex_shader` - Vertex shader to use for pipeline resolution
     /// * `resource_manager` - ResourceManager for resolving keys
+    ///
+    /// # Errors
+    ///
+    /// Fails if the mesh key is unknown, or if `world_matrix` or
+    /// `bounding_box` contains NaN or infinite values (they would poison
+    /// the spatial index and culling).
     pub fn create_render_instance(
         &mut self,
         mesh_key: MeshKey,
@@ -97,20 +159,54 @@ impl Scene {
         vertex_shader_overrides: &[VertexShaderOverride],
         resource_manager: &ResourceManager,
     ) -> Result<RenderInstanceKey> {
+        if !world_matrix.is_finite() || !bounding_box.is_finite() {
+            crate::engine_bail_warn!("galaxy3d::Scene",
+                "create_render_instance: non-finite world matrix or bounding box");
+        }
         let mesh = resource_manager.mesh(mesh_key)
-            .ok_or_else(|| engine_err!("galaxy3d::Scene", "Mesh key not found in ResourceManager"))?;
+            .ok_or_else(|| engine_not_found!("galaxy3d::Scene", "Mesh", format!("{:?}", mesh_key)))?;
 
-        let instance = RenderInstance::from_mesh(
+        let mut instance = RenderInstance::from_mesh(
             mesh, world_matrix, bounding_box, vertex_shader,
             vertex_shader_overrides,
             &mut self.draw_slot_allocator,
             resource_manager,
         )?;
+        instance.set_mesh(mesh_key);
         let key = self.render_instances.insert(instance);
         self.new_instances.insert(key);
         Ok(key)
     }
 
+    /// Create a RenderInstance from a Mesh with default settings
+    ///
+    /// Shorthand for `create_render_instance()` with the mesh geometry's
+    /// `Geometry::bounds()` as bounding box and no vertex shader override.
+    /// Submeshes, LODs and per-pass data come from the mesh and its
+    /// materials; flags default to `FLAG_VISIBLE`.
+    ///
+    /// # Errors
+    ///
+    /// Same as `create_render_instance()`, plus when the geometry has no
+    /// bounds (no float position attribute at `GEOMETRY_POSITION_LOCATION`):
+    /// pass an explicit AABB to `create_render_instance()` instead.
+    pub fn add_mesh_instance(
+        &mut self,
+        mesh_key: MeshKey,
+        world_matrix: Mat4,
+        vertex_shader: ShaderKey,
+        resource_manager: &ResourceManager,
+    ) -> Result<RenderInstanceKey> {
+        let mesh = resource_manager.mesh(mesh_key)
+            .ok_or_else(|| engine_not_found!("galaxy3d::Scene", "Mesh", format!("{:?}", mesh_key)))?;
+        let geometry = resource_manager.geometry(mesh.geometry())
+            .ok_or_else(|| engine_not_found!("galaxy3d::Scene", "Geometry", format!("{:?}", mesh.geometry())))?;
+        let bounding_box = *geometry.bounds()
+            .ok_or_else(|| engine_err!("galaxy3d::Scene",
+                "add_mesh_instance: geometry '{}' has no position bounds", geometry.name()))?;
+        self.create_render_instance(mesh_key, world_matrix, bounding_box, vertex_shader, &[], resource_manager)
+    }
+
     /// Mark a RenderInstance for deferred removal.
     ///
     /// The instance stays in the scene until `removed_instances()` is called,
@@ -120,13 +216,42 @@ impl Scene {
         if self.render_instances.contains_key(key) {
             self.removed_instances.insert(key);
             self.dirty_instance_transforms.remove(&key);
+            self.dirty_instance_morph_weights.remove(&key);
+            self.dirty_instance_tints.remove(&key);
+            self.transform_components.remove(key);
             self.new_instances.remove(&key);
+            self.detach_render_instance(key);
             true
         } else {
             false
         }
     }
 
+    /// Mark several RenderInstances for deferred removal.
+    ///
+    /// Same semantics as `remove_render_instance()`; the instances are
+    /// deleted together by the next `removed_instances()` call.
+    /// Returns the number of valid keys marked.
+    pub fn remove_render_instances(
+        &mut self,
+        keys: impl IntoIterator<Item = RenderInstanceKey>,
+    ) -> usize {
+        let mut count = 0;
+        for key in keys {
+            if self.render_instances.contains_key(key) {
+                self.removed_instances.insert(key);
+                self.dirty_instance_transforms.remove(&key);
+                self.dirty_instance_morph_weights.remove(&key);
+                self.dirty_instance_tints.remove(&key);
+                self.transform_components.remove(key);
+                self.new_instances.remove(&key);
+                self.detach_render_instance(key);
+                count += 1;
+            }
+        }
+        count
+    }
+
     /// Get a RenderInstance by key
     pub fn render_instance(
         &self,
@@ -144,16 +269,246 @@ impl Scene {
     }
 
     /// Set the world matrix of a render instance. Returns false if key is invalid.
+    ///
+    /// A matrix containing NaN or infinite values is rejected with a warning
+    /// (the instance keeps its previous transform) and false is returned.
+    /// An instance driven by TRS components goes back to matrix mode (its
+    /// components are dropped).
     pub fn set_world_matrix(&mut self, key: RenderInstanceKey, matrix: Mat4) -> bool {
+        if !matrix.is_finite() {
+            crate::engine_warn!("galaxy3d::Scene",
+                "set_world_matrix: non-finite matrix rejected");
+            return false;
+        }
         if let Some(instance) = self.render_instances.get_mut(key) {
             instance.set_world_matrix(matrix);
             self.dirty_instance_transforms.insert(key);
+            self.transform_components.remove(key);
+            true
+        } else {
+            false
+        }
+    }
+
+    // ===== TRS TRANSFORM COMPONENTS =====
+
+    /// Drive a render instance by translation / rotation / scale.
+    ///
+    /// The world matrix is not composed here but by the next
+    /// `compose_transform_components()` (called by `DefaultUpdater`), so
+    /// several component changes in a frame cost one composition. The
+    /// rotation is normalized.
+    ///
+    /// Returns false (with a warning for NaN/Inf or a zero rotation) if the
+    /// key is invalid, a component is not finite, or the instance is
+    /// attached to a SceneNode (the node drives its matrix).
+    pub fn set_transform_components(
+        &mut self,
+        key: RenderInstanceKey,
+        translation: Vec3,
+        rotation: Quat,
+        scale: Vec3,
+    ) -> bool {
+        let Some(rotation) = self.check_components(key, translation, rotation, scale) else {
+            return false;
+        };
+        self.transform_components.set(key, translation, rotation, scale);
+        true
+    }
+
+    /// Set the translation of a render instance. An instance still in
+    /// matrix mode first takes the components of its current world matrix.
+    /// Same failure cases as `set_transform_components()`.
+    pub fn set_translation(&mut self, key: RenderInstanceKey, translation: Vec3) -> bool {
+        if self.check_components(key, translation, Quat::IDENTITY, Vec3::ONE).is_none() {
+            return false;
+        }
+        self.ensure_transform_components(key);
+        self.transform_components.set_translation(key, translation)
+    }
+
+    /// Set the rotation of a render instance (normalized). Same behavior as
+    /// `set_translation()`.
+    pub fn set_rotation(&mut self, key: RenderInstanceKey, rotation: Quat) -> bool {
+        let Some(rotation) = self.check_components(key, Vec3::ZERO, rotation, Vec3::ONE) else {
+            return false;
+        };
+        self.ensure_transform_components(key);
+        self.transform_components.set_rotation(key, rotation)
+    }
+
+    /// Set the scale of a render instance. Same behavior as
+    /// `set_translation()`.
+    pub fn set_scale(&mut self, key: RenderInstanceKey, scale: Vec3) -> bool {
+        if self.check_components(key, Vec3::ZERO, Quat::IDENTITY, scale).is_none() {
+            return false;
+        }
+        self.ensure_transform_components(key);
+        self.transform_components.set_scale(key, scale)
+    }
+
+    /// TRS components of a render instance as (translation, rotation,
+    /// scale), or None if it is in matrix mode
+    pub fn transform_components(&self, key: RenderInstanceKey) -> Option<(Vec3, Quat, Vec3)> {
+        self.transform_components.get(key)
+    }
+
+    /// Number of render instances driven by TRS components
+    pub fn transform_component_count(&self) -> usize {
+        self.transform_components.len()
+    }
+
+    // ----- Fixed-timestep interpolation -----
+
+    /// Start a fixed simulation step: the current TRS components of every
+    /// instance become its previous ones. Call before writing the step's
+    /// transforms.
+    ///
+    /// Typical loop with gameplay at a fixed rate and uncapped rendering:
+    /// ```text
+    /// while accumulator >= STEP { scene.begin_transform_step(); simulate(STEP); accumulator -= STEP; }
+    /// scene.set_transform_interpolation(accumulator / STEP);
+    /// render();
+    /// ```
+    pub fn begin_transform_step(&mut self) {
+        self.transform_components.begin_step();
+    }
+
+    /// Set the fraction of the current step elapsed at render time, in
+    /// [0, 1] (clamped). Instances are drawn at `lerp(previous, current,
+    /// alpha)` (slerp for rotations). The default, 1, draws the current
+    /// components, i.e. no interpolation.
+    pub fn set_transform_interpolation(&mut self, alpha: f32) {
+        if alpha.is_nan() {
+            crate::engine_warn!("galaxy3d::Scene",
+                "set_transform_interpolation: NaN alpha ignored");
+            return;
+        }
+        self.transform_components.set_alpha(alpha);
+    }
+
+    /// Current interpolation factor (see `set_transform_interpolation`)
+    pub fn transform_interpolation(&self) -> f32 {
+        self.transform_components.alpha()
+    }
+
+    /// TRS components at the start of the current step, or None if the
+    /// instance is in matrix mode
+    pub fn previous_transform_components(&self, key: RenderInstanceKey) -> Option<(Vec3, Quat, Vec3)> {
+        self.transform_components.get_previous(key)
+    }
+
+    /// Make the previous components of an instance equal to its current
+    /// ones, so it is not interpolated across a teleport. Returns false if
+    /// the instance is not driven by TRS components.
+    pub fn snap_transform_components(&mut self, key: RenderInstanceKey) -> bool {
+        self.transform_components.snap(key)
+    }
+
+    /// Compose the world matrix of every instance whose components changed
+    /// and mark it in `dirty_instance_transforms`. Returns the number of
+    /// matrices composed.
+    pub fn compose_transform_components(&mut self) -> usize {
+        let render_instances = &mut self.render_instances;
+        let dirty_instance_transforms = &mut self.dirty_instance_transforms;
+        self.transform_components.compose_dirty(|key, matrix| {
+            if let Some(instance) = render_instances.get_mut(key) {
+                instance.set_world_matrix(matrix);
+                dirty_instance_transforms.insert(key);
+            }
+        })
+    }
+
+    /// Validate component input; returns the normalized rotation
+    fn check_components(
+        &self,
+        key: RenderInstanceKey,
+        translation: Vec3,
+        rotation: Quat,
+        scale: Vec3,
+    ) -> Option<Quat> {
+        if !self.render_instances.contains_key(key) || self.instance_nodes.contains_key(&key) {
+            return None;
+        }
+        let rotation = rotation.normalize();
+        if !translation.is_finite() || !rotation.is_finite() || !scale.is_finite() {
+            crate::engine_warn!("galaxy3d::Scene",
+                "Transform components rejected: NaN/Inf or zero-length rotation");
+            return None;
+        }
+        Some(rotation)
+    }
+
+    /// Switch an instance in matrix mode to components decomposed from its
+    /// current world matrix
+    fn ensure_transform_components(&mut self, key: RenderInstanceKey) {
+        if !self.transform_components.contains(key) {
+            let (scale, rotation, translation) =
+                self.render_instances[key].world_matrix().to_scale_rotation_translation();
+            self.transform_components.set(key, translation, rotation, scale);
+        }
+    }
+
+    /// Set the morph target weights of a render instance (see
+    /// `RenderInstance::set_morph_weights`). Returns false if key is invalid.
+    pub fn set_morph_weights(&mut self, key: RenderInstanceKey, weights: &[f32]) -> bool {
+        if let Some(instance) = self.render_instances.get_mut(key) {
+            instance.set_morph_weights(weights);
+            self.dirty_instance_morph_weights.insert(key);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Flip and return the set of instances with pending morph weight changes.
+    pub fn dirty_instance_morph_weights(&self) -> &FxHashSet<RenderInstanceKey> {
+        self.dirty_instance_morph_weights.flip()
+    }
+
+    /// Set the color multiplier of a render instance (RGBA, `Vec4::ONE` =
+    /// no tint). Returns false if key is invalid.
+    ///
+    /// Only the instance buffer is written: no material is duplicated and
+    /// the draw batching is unchanged.
+    pub fn set_color_tint(&mut self, key: RenderInstanceKey, tint: Vec4) -> bool {
+        if let Some(instance) = self.render_instances.get_mut(key) {
+            instance.set_color_tint(tint);
+            self.dirty_instance_tints.insert(key);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Set the additive emissive color of a render instance (`Vec3::ZERO` =
+    /// no tint). Returns false if key is invalid.
+    pub fn set_emissive_tint(&mut self, key: RenderInstanceKey, tint: Vec3) -> bool {
+        if let Some(instance) = self.render_instances.get_mut(key) {
+            instance.set_emissive_tint(tint);
+            self.dirty_instance_tints.insert(key);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Force the LOD drawn for every submesh of a render instance, or None
+    /// for the distance-based selection. Returns false if key is invalid.
+    pub fn set_lod_override(&mut self, key: RenderInstanceKey, lod: Option<u8>) -> bool {
+        if let Some(instance) = self.render_instances.get_mut(key) {
+            instance.set_lod_override(lod);
             true
         } else {
             false
         }
     }
 
+    /// Flip and return the set of instances with pending tint changes.
+    pub fn dirty_instance_tints(&self) -> &FxHashSet<RenderInstanceKey> {
+        self.dirty_instance_tints.flip()
+    }
+
     /// Flip and return the set of instances with pending transform changes.
     ///
     /// Returns the dirty keys accumulated since the previous call.
@@ -237,6 +592,7 @@ impl Scene {
             self.dirty_light_transforms.remove(&key);
             self.dirty_light_data.remove(&key);
             self.new_lights.remove(&key);
+            self.detach_light(key);
             true
         } else {
             false
@@ -422,13 +778,568 @@ impl Scene {
         self.lights.len()
     }
 
+    // ===== BILLBOARDS =====
+
+    /// Create a Billboard from a descriptor and add it to the scene.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the texture key is unknown, if the layer or region does not
+    /// exist, or if the position or size is not finite.
+    pub fn create_billboard(
+        &mut self,
+        desc: BillboardDesc,
+        resource_manager: &ResourceManager,
+    ) -> Result<BillboardKey> {
+        let billboard = Billboard::from_desc(desc, resource_manager)?;
+        Ok(self.billboards.insert(billboard))
+    }
+
+    /// Remove a Billboard. Immediate: billboards hold no GPU slot.
+    ///
+    /// Returns false if the key is invalid.
+    pub fn remove_billboard(&mut self, key: BillboardKey) -> bool {
+        self.billboards.remove(key).is_some()
+    }
+
+    /// Get a Billboard by key.
+    pub fn billboard(&self, key: BillboardKey) -> Option<&Billboard> {
+        self.billboards.get(key)
+    }
+
+    /// Replace a Billboard entirely (texture references are resolved again).
+    ///
+    /// Returns Ok(false) if the key is invalid.
+    pub fn set_billboard(
+        &mut self,
+        key: BillboardKey,
+        desc: BillboardDesc,
+        resource_manager: &ResourceManager,
+    ) -> Result<bool> {
+        if !self.billboards.contains_key(key) {
+            return Ok(false);
+        }
+        self.billboards[key] = Billboard::from_desc(desc, resource_manager)?;
+        Ok(true)
+    }
+
+    /// Set a billboard's world-space center.
+    pub fn set_billboard_position(&mut self, key: BillboardKey, position: Vec3) -> bool {
+        if let Some(billboard) = self.billboards.get_mut(key) {
+            billboard.set_position(position);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Set a billboard's width and height in world units.
+    pub fn set_billboard_size(&mut self, key: BillboardKey, size: Vec2) -> bool {
+        if let Some(billboard) = self.billboards.get_mut(key) {
+            billboard.set_size(size);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Set a billboard's orientation mode.
+    pub fn set_billboard_mode(&mut self, key: BillboardKey, mode: BillboardMode) -> bool {
+        if let Some(billboard) = self.billboards.get_mut(key) {
+            billboard.set_mode(mode);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Set a billboard's color (linear RGBA).
+    pub fn set_billboard_color(&mut self, key: BillboardKey, color: Vec4) -> bool {
+        if let Some(billboard) = self.billboards.get_mut(key) {
+            billboard.set_color(color);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Show or hide a billboard.
+    pub fn set_billboard_visible(&mut self, key: BillboardKey, visible: bool) -> bool {
+        if let Some(billboard) = self.billboards.get_mut(key) {
+            billboard.set_visible(visible);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Iterate over all billboards (key, billboard).
+    pub fn billboards(&self) -> impl Iterator<Item = (BillboardKey, &Billboard)> {
+        self.billboards.iter()
+    }
+
+    /// Get the number of billboards.
+    pub fn billboard_count(&self) -> usize {
+        self.billboards.len()
+    }
+
+    // ===== ENVIRONMENT =====
+
+    /// Get the ambient light and fog settings.
+    pub fn environment(&self) -> &SceneEnvironment {
+        &self.environment
+    }
+
+    /// Replace the ambient light and fog settings.
+    pub fn set_environment(&mut self, environment: SceneEnvironment) {
+        self.environment = environment;
+    }
+
+    /// Set the ambient light color (linear RGB) and intensity.
+    pub fn set_ambient(&mut self, color: Vec3, intensity: f32) {
+        self.environment.ambient_color = color;
+        self.environment.ambient_intensity = intensity;
+    }
+
+    /// Set the fog settings.
+    pub fn set_fog(&mut self, fog: Fog) {
+        self.environment.fog = fog;
+    }
+
+    // ===== CLOCK =====
+
+    /// Get the simulation clock.
+    pub fn clock(&self) -> &EngineClock {
+        &self.clock
+    }
+
+    /// Get the simulation clock, to advance it once per frame or to pause,
+    /// step or scale it.
+    pub fn clock_mut(&mut self) -> &mut EngineClock {
+        &mut self.clock
+    }
+
+    // ===== NODES =====
+
+    /// Create a scene node under `parent` (None for a root node).
+    ///
+    /// The node is marked dirty: its world transform is final after the next
+    /// `update_node_transforms()`.
+    pub fn create_node(
+        &mut self,
+        parent: Option<SceneNodeKey>,
+        local_transform: Mat4,
+    ) -> Result<SceneNodeKey> {
+        let parent_world = match parent {
+            Some(p) => *self.nodes.get(p)
+                .ok_or_else(|| engine_err!("galaxy3d::Scene", "Parent node key not found"))?
+                .world_transform(),
+            None => Mat4::IDENTITY,
+        };
+        let key = self.nodes.insert(SceneNode::new(parent, local_transform, parent_world * local_transform));
+        if let Some(p) = parent {
+            self.nodes[p].children.push(key);
+        }
+        self.dirty_nodes.insert(key);
+        Ok(key)
+    }
+
+    /// Remove a node and its whole subtree.
+    ///
+    /// Render instances and lights attached to the removed nodes are marked
+    /// for removal too; owned cameras are dropped. Returns false if the key
+    /// is invalid.
+    pub fn remove_node(&mut self, key: SceneNodeKey) -> bool {
+        let parent = match self.nodes.get(key) {
+            Some(node) => node.parent,
+            None => return false,
+        };
+        if let Some(p) = parent {
+            self.nodes[p].children.retain(|c| *c != key);
+        }
+
+        let mut stack = vec![key];
+        while let Some(node_key) = stack.pop() {
+            let node = self.nodes.remove(node_key).unwrap();
+            self.dirty_nodes.remove(&node_key);
+            stack.extend(node.children);
+            for (instance, _) in node.render_instances {
+                self.instance_nodes.remove(&instance);
+                self.remove_render_instance(instance);
+            }
+            for light in node.lights {
+                self.light_nodes.remove(&light.key);
+                self.remove_light(light.key);
+            }
+        }
+        true
+    }
+
+    /// Get a SceneNode by key
+    pub fn node(&self, key: SceneNodeKey) -> Option<&SceneNode> {
+        self.nodes.get(key)
+    }
+
+    /// Number of scene nodes
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Set the local transform of a node. Marks the node (and so its
+    /// subtree) dirty. Returns false if the key is invalid.
+    pub fn set_node_transform(&mut self, key: SceneNodeKey, local_transform: Mat4) -> bool {
+        if let Some(node) = self.nodes.get_mut(key) {
+            node.local_transform = local_transform;
+            self.dirty_nodes.insert(key);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Move a node under another parent (None makes it a root node).
+    ///
+    /// The local transform is kept, so the world transform changes.
+    /// Returns false if a key is invalid or if `parent` is the node itself
+    /// or one of its descendants.
+    pub fn set_node_parent(&mut self, key: SceneNodeKey, parent: Option<SceneNodeKey>) -> bool {
+        if !self.nodes.contains_key(key) {
+            return false;
+        }
+        if let Some(p) = parent {
+            let mut ancestor = Some(p);
+            while let Some(a) = ancestor {
+                match self.nodes.get(a) {
+                    Some(_) if a == key => return false,
+                    Some(node) => ancestor = node.parent,
+                    None => return false,
+                }
+            }
+        }
+
+        if let Some(old) = self.nodes[key].parent {
+            self.nodes[old].children.retain(|c| *c != key);
+        }
+        if let Some(p) = parent {
+            self.nodes[p].children.push(key);
+        }
+        self.nodes[key].parent = parent;
+        self.dirty_nodes.insert(key);
+        true
+    }
+
+    /// Attach a render instance to a node.
+    ///
+    /// The instance keeps its current world placement as an offset from the
+    /// node, then follows the node. An instance attached elsewhere is moved.
+    /// An instance driven by TRS components goes back to matrix mode, its
+    /// latest components defining the placement.
+    /// Returns false if a key is invalid.
+    pub fn attach_render_instance(&mut self, node: SceneNodeKey, instance: RenderInstanceKey) -> bool {
+        if !self.nodes.contains_key(node) || !self.render_instances.contains_key(instance) {
+            return false;
+        }
+        if let Some((translation, rotation, scale)) = self.transform_components.get(instance) {
+            self.render_instances[instance].set_world_matrix(
+                Mat4::from_scale_rotation_translation(scale, rotation, translation));
+            self.dirty_instance_transforms.insert(instance);
+            self.transform_components.remove(instance);
+        }
+        let local = self.nodes[node].world_transform.inverse() * *self.render_instances[instance].world_matrix();
+        self.detach_render_instance(instance);
+        self.nodes[node].render_instances.push((instance, local));
+        self.instance_nodes.insert(instance, node);
+        true
+    }
+
+    /// Detach a render instance from its node. The instance keeps its
+    /// current world matrix. Returns false if it was not attached.
+    pub fn detach_render_instance(&mut self, instance: RenderInstanceKey) -> bool {
+        match self.instance_nodes.remove(&instance) {
+            Some(node) => {
+                if let Some(node) = self.nodes.get_mut(node) {
+                    node.render_instances.retain(|(k, _)| *k != instance);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Attach a light to a node (same semantics as `attach_render_instance`:
+    /// the current position and direction become node-space offsets).
+    pub fn attach_light(&mut self, node: SceneNodeKey, light: LightKey) -> bool {
+        let (Some(target), Some(l)) = (self.nodes.get(node), self.lights.get(light)) else {
+            return false;
+        };
+        let inverse = target.world_transform.inverse();
+        let node_light = NodeLight {
+            key: light,
+            local_position: inverse.transform_point3(l.position()),
+            local_direction: inverse.transform_vector3(l.direction()),
+        };
+        self.detach_light(light);
+        self.nodes[node].lights.push(node_light);
+        self.light_nodes.insert(light, node);
+        true
+    }
+
+    /// Detach a light from its node. Returns false if it was not attached.
+    pub fn detach_light(&mut self, light: LightKey) -> bool {
+        match self.light_nodes.remove(&light) {
+            Some(node) => {
+                if let Some(node) = self.nodes.get_mut(node) {
+                    node.lights.retain(|l| l.key != light);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Give a camera to a node. Its current view placement becomes an offset
+    /// from the node; from then on `update_node_transforms()` rewrites its
+    /// view matrix and frustum. Returns the camera index within the node,
+    /// or None if the key is invalid.
+    pub fn attach_camera(&mut self, node: SceneNodeKey, camera: Camera) -> Option<usize> {
+        let target = self.nodes.get_mut(node)?;
+        let local = target.world_transform.inverse() * camera.view_matrix().inverse();
+        target.cameras.push(NodeCamera { camera, local });
+        Some(target.cameras.len() - 1)
+    }
+
+    /// Mutable access to a node camera (projection, viewport, scissor).
+    ///
+    /// The view matrix and frustum are overwritten by the next update of
+    /// the node.
+    pub fn node_camera_mut(&mut self, node: SceneNodeKey, index: usize) -> Option<&mut Camera> {
+        self.nodes.get_mut(node)?.cameras.get_mut(index).map(|c| &mut c.camera)
+    }
+
+    /// Whether a node is waiting for `update_node_transforms()`
+    pub fn is_node_dirty(&self, key: SceneNodeKey) -> bool {
+        self.dirty_nodes.contains(&key)
+    }
+
+    /// Recompute the world transforms of dirty nodes and their subtrees and
+    /// push them to the attached objects.
+    ///
+    /// Attached render instances and lights go through the regular dirty
+    /// sets (`dirty_instance_transforms`, `dirty_light_transforms`), so the
+    /// Updater then writes them to the GPU and updates the SceneIndex.
+    /// Returns the number of nodes recomputed.
+    pub fn update_node_transforms(&mut self) -> usize {
+        if self.dirty_nodes.is_empty() {
+            return 0;
+        }
+
+        // Start from the topmost dirty nodes: the others are reached
+        // through their ancestor's subtree.
+        let mut stack = std::mem::take(&mut self.node_stack);
+        stack.clear();
+        for &key in &self.dirty_nodes {
+            let mut ancestor = self.nodes[key].parent;
+            let mut covered = false;
+            while let Some(a) = ancestor {
+                if self.dirty_nodes.contains(&a) {
+                    covered = true;
+                    break;
+                }
+                ancestor = self.nodes[a].parent;
+            }
+            if !covered {
+                stack.push(key);
+            }
+        }
+        self.dirty_nodes.clear();
+
+        let mut count = 0;
+        while let Some(key) = stack.pop() {
+            let parent_world = match self.nodes[key].parent {
+                Some(p) => self.nodes[p].world_transform,
+                None => Mat4::IDENTITY,
+            };
+            let node = &mut self.nodes[key];
+            let world = parent_world * node.local_transform;
+            node.world_transform = world;
+            stack.extend_from_slice(&node.children);
+            count += 1;
+
+            for (instance_key, local) in &node.render_instances {
+                if let Some(instance) = self.render_instances.get_mut(*instance_key) {
+                    instance.set_world_matrix(world * *local);
+                    self.dirty_instance_transforms.insert(*instance_key);
+                }
+            }
+            for node_light in &node.lights {
+                if let Some(light) = self.lights.get_mut(node_light.key) {
+                    light.set_position(world.transform_point3(node_light.local_position));
+                    light.set_direction(world.transform_vector3(node_light.local_direction));
+                    self.dirty_light_transforms.insert(node_light.key);
+                }
+            }
+            for node_camera in &mut node.cameras {
+                let view = (world * node_camera.local).inverse();
+                let camera = &mut node_camera.camera;
+                camera.set_view(view);
+                camera.set_frustum(Frustum::from_view_projection(&(*camera.projection_matrix() * view)));
+            }
+        }
+
+        self.node_stack = stack;
+        count
+    }
+
+    // ===== SERIALIZATION =====
+
+    /// Describe the render instances as a `SceneFile` (instances pending
+    /// removal are skipped).
+    ///
+    /// # Errors
+    ///
+    /// Fails if an instance was built outside the scene, or its mesh or a
+    /// vertex shader is no longer registered (it would have no name).
+    #[cfg(feature = "manifest")]
+    pub fn to_scene_file(&self, resource_manager: &ResourceManager) -> Result<SceneFile> {
+        let meshes: FxHashMap<MeshKey, &str> = resource_manager.meshes().map(|e| (e.key, e.name)).collect();
+        let shaders: FxHashMap<ShaderKey, &str> = resource_manager.shaders().map(|e| (e.key, e.name)).collect();
+        let shader_name = |key: ShaderKey| shaders.get(&key).map(|name| name.to_string())
+            .ok_or_else(|| engine_err!("galaxy3d::Scene", "to_scene_file: vertex shader {:?} has no name", key));
+
+        let mut instances = Vec::with_capacity(self.render_instances.len());
+        for (key, instance) in &self.render_instances {
+            if self.removed_instances.contains(&key) {
+                continue;
+            }
+            let mesh = instance.mesh().and_then(|mesh| meshes.get(&mesh))
+                .ok_or_else(|| engine_err!("galaxy3d::Scene", "to_scene_file: instance {:?} has no named mesh", key))?;
+            let mut vertex_shader_overrides = Vec::new();
+            for sm_idx in 0..instance.sub_mesh_count() {
+                let sub_mesh = instance.sub_mesh(sm_idx).unwrap();
+                for pass_type in 0..64u8 {
+                    let Some(pass) = sub_mesh.pass(pass_type) else { continue };
+                    if pass.vertex_shader() != instance.vertex_shader() {
+                        vertex_shader_overrides.push(VertexShaderOverrideRecord {
+                            submesh: sm_idx,
+                            pass_type,
+                            vertex_shader: shader_name(pass.vertex_shader())?,
+                        });
+                    }
+                }
+            }
+            let weights = instance.morph_weights();
+            let weight_count = weights.iter().rposition(|w| *w != 0.0).map_or(0, |last| last + 1);
+            let bounds = instance.bounding_box();
+            instances.push(InstanceRecord {
+                mesh: mesh.to_string(),
+                world_matrix: instance.world_matrix().to_cols_array(),
+                bounds_min: bounds.min.to_array(),
+                bounds_max: bounds.max.to_array(),
+                vertex_shader: shader_name(instance.vertex_shader())?,
+                vertex_shader_overrides,
+                flags: instance.flags(),
+                lod_override: instance.lod_override(),
+                color_tint: instance.color_tint().to_array(),
+                emissive_tint: instance.emissive_tint().to_array(),
+                morph_weights: weights[..weight_count].to_vec(),
+            });
+        }
+        Ok(SceneFile { instances })
+    }
+
+    /// Create the render instances of a `SceneFile`, resolving resources
+    /// by name. All or nothing: on error, the instances already created
+    /// are removed.
+    #[cfg(feature = "manifest")]
+    pub fn load_scene_file(
+        &mut self,
+        file: &SceneFile,
+        resource_manager: &ResourceManager,
+    ) -> Result<Vec<RenderInstanceKey>> {
+        let mut keys = Vec::with_capacity(file.instances.len());
+        for record in &file.instances {
+            match self.create_instance_from_record(record, resource_manager) {
+                Ok(key) => keys.push(key),
+                Err(e) => {
+                    self.remove_render_instances(keys);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(keys)
+    }
+
+    /// `to_scene_file()` printed as RON or JSON
+    #[cfg(feature = "manifest")]
+    pub fn serialize(
+        &self,
+        resource_manager: &ResourceManager,
+        format: crate::resource::manifest::ManifestFormat,
+    ) -> Result<String> {
+        self.to_scene_file(resource_manager)?.to_text(format)
+    }
+
+    /// Parse a RON or JSON scene and add its instances (see
+    /// `load_scene_file()`)
+    #[cfg(feature = "manifest")]
+    pub fn deserialize(
+        &mut self,
+        text: &str,
+        format: crate::resource::manifest::ManifestFormat,
+        resource_manager: &ResourceManager,
+    ) -> Result<Vec<RenderInstanceKey>> {
+        let file = SceneFile::parse(text, format)?;
+        self.load_scene_file(&file, resource_manager)
+    }
+
+    #[cfg(feature = "manifest")]
+    fn create_instance_from_record(
+        &mut self,
+        record: &InstanceRecord,
+        resource_manager: &ResourceManager,
+    ) -> Result<RenderInstanceKey> {
+        let shader_key = |name: &str| resource_manager.shader_key(name)
+            .ok_or_else(|| engine_not_found!("galaxy3d::Scene", "Shader", name));
+        let mesh = resource_manager.mesh_key(&record.mesh)
+            .ok_or_else(|| engine_not_found!("galaxy3d::Scene", "Mesh", &record.mesh))?;
+        let overrides = record.vertex_shader_overrides.iter()
+            .map(|o| Ok(VertexShaderOverride {
+                submesh: o.submesh,
+                pass_type: o.pass_type,
+                vertex_shader: shader_key(&o.vertex_shader)?,
+            }))
+            .collect::<Result<Vec<_>>>()?;
+        let key = self.create_render_instance(
+            mesh,
+            Mat4::from_cols_array(&record.world_matrix),
+            AABB { min: Vec3::from_array(record.bounds_min), max: Vec3::from_array(record.bounds_max) },
+            shader_key(&record.vertex_shader)?,
+            &overrides,
+            resource_manager,
+        )?;
+        let instance = self.render_instances.get_mut(key).unwrap();
+        instance.set_flags(record.flags);
+        instance.set_lod_override(record.lod_override);
+        instance.set_color_tint(Vec4::from_array(record.color_tint));
+        instance.set_emissive_tint(Vec3::from_array(record.emissive_tint));
+        instance.set_morph_weights(&record.morph_weights);
+        Ok(key)
+    }
+
     // ===== CLEAR =====
 
-    /// Remove all render instances, lights, and reset allocators.
+    /// Remove all render instances, lights, billboards, and reset allocators.
+    /// The environment settings and the clock are kept.
+    ///
+    /// Unlike `remove_render_instances()`, this is immediate: no removal is
+    /// reported to the Updater, so clear the SceneIndex alongside.
     pub fn clear(&mut self) {
         self.render_instances.clear();
         self.draw_slot_allocator = SlotAllocator::new();
         self.dirty_instance_transforms.clear();
+        self.dirty_instance_morph_weights.clear();
+        self.dirty_instance_tints.clear();
+        self.transform_components.clear();
         self.new_instances.clear();
         self.removed_instances.clear();
         self.lights.clear();
@@ -437,6 +1348,11 @@ impl Scene {
         self.dirty_light_transforms.clear();
         self.dirty_light_data.clear();
         self.removed_lights.clear();
+        self.billboards.clear();
+        self.nodes.clear();
+        self.dirty_nodes.clear();
+        self.instance_nodes.clear();
+        self.light_nodes.clear();
     }
 
     /// Minimum SSBO capacity needed (in number of slots)
@@ -0,0 +1,115 @@
+/// Scene files (`manifest` feature): the render instances of a `Scene` as
+/// RON or JSON.
+///
+/// Resources are referenced by name (mesh, vertex shaders), so a file
+/// loads in any `ResourceManager` holding resources of the same names,
+/// e.g. one filled by `ResourceManager::load_manifest()`. Each instance
+/// keeps its world matrix, local bounding box, flags, tints, morph weights
+/// and LOD override.
+///
+/// `Scene::serialize()` / `Scene::deserialize()` write and read the text;
+/// `SceneFile::save()` / `SceneFile::load()` the files. Lights, billboards,
+/// nodes and the environment are not stored.
+
+use std::path::Path;
+use serde::{Serialize, Deserialize};
+use crate::error::Result;
+use crate::{engine_bail, engine_err};
+use crate::resource::manifest::ManifestFormat;
+use crate::utils::write_atomic;
+
+/// Vertex shader override of one (submesh, pass type) pair
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VertexShaderOverrideRecord {
+    pub submesh: usize,
+    pub pass_type: u8,
+    pub vertex_shader: String,
+}
+
+/// One render instance of a scene file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstanceRecord {
+    /// Mesh name
+    pub mesh: String,
+    /// World matrix, column-major
+    pub world_matrix: [f32; 16],
+    /// Local bounding box corners
+    pub bounds_min: [f32; 3],
+    pub bounds_max: [f32; 3],
+    /// Default vertex shader name
+    pub vertex_shader: String,
+    #[serde(default)]
+    pub vertex_shader_overrides: Vec<VertexShaderOverrideRecord>,
+    #[serde(default = "default_flags")]
+    pub flags: u64,
+    #[serde(default)]
+    pub lod_override: Option<u8>,
+    #[serde(default = "default_color_tint")]
+    pub color_tint: [f32; 4],
+    #[serde(default)]
+    pub emissive_tint: [f32; 3],
+    /// Morph weights, trailing zeros omitted
+    #[serde(default)]
+    pub morph_weights: Vec<f32>,
+}
+
+fn default_flags() -> u64 {
+    super::render_instance::FLAG_VISIBLE
+}
+
+fn default_color_tint() -> [f32; 4] {
+    [1.0; 4]
+}
+
+/// Render instances of a scene (see module docs)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SceneFile {
+    #[serde(default)]
+    pub instances: Vec<InstanceRecord>,
+}
+
+impl SceneFile {
+    /// Parse a scene file text
+    pub fn parse(text: &str, format: ManifestFormat) -> Result<Self> {
+        match format {
+            ManifestFormat::Ron => ron::from_str(text)
+                .map_err(|e| engine_err!("galaxy3d::SceneFile", "Invalid RON scene: {}", e)),
+            ManifestFormat::Json => serde_json::from_str(text)
+                .map_err(|e| engine_err!("galaxy3d::SceneFile", "Invalid JSON scene: {}", e)),
+        }
+    }
+
+    /// Print the scene file (pretty-printed)
+    pub fn to_text(&self, format: ManifestFormat) -> Result<String> {
+        match format {
+            ManifestFormat::Ron => ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+                .map_err(|e| engine_err!("galaxy3d::SceneFile", "Cannot write RON scene: {}", e)),
+            ManifestFormat::Json => serde_json::to_string_pretty(self)
+                .map_err(|e| engine_err!("galaxy3d::SceneFile", "Cannot write JSON scene: {}", e)),
+        }
+    }
+
+    /// Read a `.ron` / `.json` scene file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let format = format_of(path)?;
+        let text = std::fs::read_to_string(path).map_err(|e| engine_err!("galaxy3d::SceneFile",
+            "Cannot read scene '{}': {}", path.display(), e))?;
+        Self::parse(&text, format)
+    }
+
+    /// Write the scene file to `path` (atomically, see `write_atomic`)
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let text = self.to_text(format_of(path)?)?;
+        write_atomic(path, text.as_bytes())
+    }
+}
+
+fn format_of(path: &Path) -> Result<ManifestFormat> {
+    match ManifestFormat::from_path(path) {
+        Some(format) => Ok(format),
+        None => engine_bail!("galaxy3d::SceneFile",
+            "Scene '{}' must have a .ron or .json extension", path.display()),
+    }
+}
@@ -6,10 +6,35 @@
 ///
 /// Ownership: the caller creates and owns the SceneIndex.
 /// It is passed by reference to Updater and CameraCuller.
+///
+/// Raycasts (`raycast`) run on the CPU without a physics engine: the index
+/// finds the instances whose AABB the ray crosses, then each candidate is
+/// tested against the triangles of its Geometry (LOD 0, base pose).
 
 use glam::Vec3;
 use crate::camera::{Frustum, VisibleInstance};
-use super::render_instance::{RenderInstanceKey, AABB};
+use crate::resource::ResourceManager;
+use super::aabb::AABB;
+use super::ray::Ray;
+use super::render_instance::{RenderInstance, RenderInstanceKey};
+use super::scene::Scene;
+
+/// Triangle of a render instance hit by a ray (see `SceneIndex::raycast`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit {
+    /// Instance hit
+    pub key: RenderInstanceKey,
+    /// Distance from the ray origin, along the ray
+    pub distance: f32,
+    /// World-space hit point
+    pub point: Vec3,
+    /// World-space unit normal of the triangle, facing the ray origin
+    pub normal: Vec3,
+    /// Index of the submesh hit in the instance
+    pub sub_mesh: usize,
+    /// Index of the triangle hit in the submesh LOD 0
+    pub triangle: u32,
+}
 
 /// Trait for spatial indexing of scene instances.
 ///
@@ -45,6 +70,90 @@ pub trait SceneIndex: Send + Sync {
         results: &mut Vec<VisibleInstance>,
     );
 
+    /// Query all instances whose world AABB the ray crosses within
+    /// `max_distance`.
+    ///
+    /// Pushes `(key, entry distance)` into `results`, unsorted (0 when the
+    /// ray starts inside the AABB). Results are appended to `results` (the
+    /// vec is NOT cleared).
+    fn query_ray(
+        &self,
+        ray: &Ray,
+        max_distance: f32,
+        results: &mut Vec<(RenderInstanceKey, f32)>,
+    );
+
     /// Remove all instances from the index.
     fn clear(&mut self);
+
+    /// Cast a ray against the triangles of the indexed instances.
+    ///
+    /// Returns the nearest hit of every instance hit within `max_distance`,
+    /// nearest first. Candidates come from `query_ray`; each one is tested
+    /// against the CPU positions of its Geometry (`Geometry::raycast_lod`,
+    /// LOD 0, morph targets ignored). Instances whose Geometry has no CPU
+    /// positions or whose world matrix is not invertible are never hit.
+    fn raycast(
+        &self,
+        ray: &Ray,
+        max_distance: f32,
+        scene: &Scene,
+        resource_manager: &ResourceManager,
+    ) -> Vec<RayHit> {
+        let mut candidates = Vec::new();
+        self.query_ray(ray, max_distance, &mut candidates);
+
+        let mut hits: Vec<RayHit> = candidates.into_iter()
+            .filter_map(|(key, _)| {
+                let instance = scene.render_instance(key)?;
+                raycast_instance(key, instance, ray, max_distance, resource_manager)
+            })
+            .collect();
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        hits
+    }
+}
+
+/// Nearest triangle of an instance hit by a world-space ray
+fn raycast_instance(
+    key: RenderInstanceKey,
+    instance: &RenderInstance,
+    ray: &Ray,
+    max_distance: f32,
+    resource_manager: &ResourceManager,
+) -> Option<RayHit> {
+    let geometry = resource_manager.geometry(instance.geometry())?;
+    let geometry_mesh = geometry.mesh(instance.geometry_mesh_id())?;
+    let world = *instance.world_matrix();
+    if world.determinant() == 0.0 {
+        return None;
+    }
+    // The local ray keeps the world distances (direction not renormalized)
+    let local_ray = ray.transformed(&world.inverse());
+
+    let mut nearest: Option<(f32, usize, u32)> = None;
+    for sub_mesh_index in 0..instance.sub_mesh_count() {
+        let sub_mesh = instance.sub_mesh(sub_mesh_index)?;
+        let Some(lod) = geometry_mesh.submesh(sub_mesh.geometry_submesh_id()).and_then(|s| s.lod(0)) else {
+            continue;
+        };
+        let limit = nearest.map_or(max_distance, |(distance, _, _)| distance);
+        if let Some((distance, triangle)) = geometry.raycast_lod(lod, &local_ray, limit) {
+            if nearest.is_none_or(|(best, _, _)| distance < best) {
+                nearest = Some((distance, sub_mesh_index, triangle));
+            }
+        }
+    }
+
+    let (distance, sub_mesh_index, triangle) = nearest?;
+    let geometry_submesh_id = instance.sub_mesh(sub_mesh_index)?.geometry_submesh_id();
+    let lod = geometry_mesh.submesh(geometry_submesh_id)?.lod(0)?;
+    let [a, b, c] = geometry.lod_triangle(lod, triangle)?.map(|corner| world.transform_point3(corner));
+    let normal = (b - a).cross(c - a).normalize_or_zero();
+    let normal = if normal.dot(ray.direction) > 0.0 { -normal } else { normal };
+    Some(RayHit { key, distance, point: ray.at(distance), normal, sub_mesh: sub_mesh_index, triangle })
 }
+
+#[cfg(test)]
+#[path = "scene_index_tests.rs"]
+mod tests;
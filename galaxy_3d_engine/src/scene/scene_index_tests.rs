@@ -0,0 +1,135 @@
+use super::*;
+use crate::scene::octree_scene_index::OctreeSceneIndex;
+use crate::scene::scene_test_helpers::{setup_quad_resources, create_test_aabb, TestSetup};
+use glam::{Mat4, Quat};
+
+/// Scene of 2x2 quads (XY plane) at the given world matrices, indexed by
+/// their world AABB
+fn build_quad_scene(worlds: &[Mat4]) -> (Scene, OctreeSceneIndex, TestSetup, Vec<RenderInstanceKey>) {
+    let setup = setup_quad_resources();
+    let mut scene = Scene::new();
+    let mut index = OctreeSceneIndex::new(
+        AABB { min: Vec3::splat(-100.0), max: Vec3::splat(100.0) }, 3);
+    let keys = worlds.iter().map(|world| {
+        let key = scene.create_render_instance(
+            setup.mesh_key, *world, create_test_aabb(),
+            setup.vertex_shader_key, &[], &setup.rm,
+        ).unwrap();
+        index.insert(key, world.w_axis.truncate(), &create_test_aabb().transformed(world));
+        key
+    }).collect();
+    (scene, index, setup, keys)
+}
+
+// ============================================================================
+// query_ray
+// ============================================================================
+
+#[test]
+fn test_query_ray_returns_crossed_aabbs() {
+    let worlds = [
+        Mat4::IDENTITY,
+        Mat4::from_translation(Vec3::new(0.0, 0.0, -5.0)),
+        Mat4::from_translation(Vec3::new(10.0, 0.0, 0.0)),
+    ];
+    let (_scene, index, _setup, keys) = build_quad_scene(&worlds);
+    let ray = Ray::new(Vec3::new(0.0, 0.0, 10.0), Vec3::NEG_Z);
+
+    let mut results = Vec::new();
+    index.query_ray(&ray, f32::MAX, &mut results);
+    results.sort_by(|a, b| a.1.total_cmp(&b.1));
+    assert_eq!(results, vec![(keys[0], 9.0), (keys[1], 14.0)]);
+}
+
+// ============================================================================
+// raycast
+// ============================================================================
+
+#[test]
+fn test_raycast_hits_triangles_nearest_first() {
+    let worlds = [
+        Mat4::from_translation(Vec3::new(0.0, 0.0, -5.0)),
+        Mat4::IDENTITY,
+    ];
+    let (scene, index, setup, keys) = build_quad_scene(&worlds);
+    let ray = Ray::new(Vec3::new(0.5, 0.5, 10.0), Vec3::NEG_Z);
+
+    let hits = index.raycast(&ray, f32::MAX, &scene, &setup.rm);
+    assert_eq!(hits.len(), 2);
+    assert_eq!(hits[0].key, keys[1]);
+    assert_eq!(hits[0].distance, 10.0);
+    assert_eq!(hits[0].point, Vec3::new(0.5, 0.5, 0.0));
+    assert_eq!(hits[0].normal, Vec3::Z);
+    assert_eq!(hits[0].sub_mesh, 0);
+    // (0.5, 0.5) lies in the second triangle (2, 3, 0)... or on its shared edge
+    assert!(hits[0].triangle <= 1);
+    assert_eq!(hits[1].key, keys[0]);
+    assert_eq!(hits[1].distance, 15.0);
+}
+
+#[test]
+fn test_raycast_is_precise_not_aabb_based() {
+    // The ray crosses the quad's AABB (z in [-1, 1]) but runs parallel to
+    // the quad, 0.5 above its plane
+    let (scene, index, setup, _keys) = build_quad_scene(&[Mat4::IDENTITY]);
+    let ray = Ray::new(Vec3::new(-10.0, 0.0, 0.5), Vec3::X);
+
+    let mut candidates = Vec::new();
+    index.query_ray(&ray, f32::MAX, &mut candidates);
+    assert_eq!(candidates.len(), 1);
+    assert!(index.raycast(&ray, f32::MAX, &scene, &setup.rm).is_empty());
+
+    // Beside the quad, inside its AABB
+    let corner = Ray::new(Vec3::new(0.0, 0.0, 10.0), Vec3::new(0.15, 0.0, -1.0));
+    assert!(index.raycast(&corner, f32::MAX, &scene, &setup.rm).is_empty());
+}
+
+#[test]
+fn test_raycast_respects_max_distance() {
+    let worlds = [
+        Mat4::IDENTITY,
+        Mat4::from_translation(Vec3::new(0.0, 0.0, -5.0)),
+    ];
+    let (scene, index, setup, keys) = build_quad_scene(&worlds);
+    let ray = Ray::new(Vec3::new(0.0, 0.5, 10.0), Vec3::NEG_Z);
+
+    let hits = index.raycast(&ray, 12.0, &scene, &setup.rm);
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].key, keys[0]);
+    assert!(index.raycast(&ray, 9.0, &scene, &setup.rm).is_empty());
+}
+
+#[test]
+fn test_raycast_uses_world_transform() {
+    // Quad scaled x2 and turned to face +X
+    let world = Mat4::from_scale_rotation_translation(
+        Vec3::splat(2.0), Quat::from_rotation_y(std::f32::consts::FRAC_PI_2), Vec3::new(3.0, 0.0, 0.0));
+    let (scene, index, setup, keys) = build_quad_scene(&[world]);
+
+    // 1.5 off-center: outside the unscaled quad, inside the scaled one
+    let ray = Ray::new(Vec3::new(10.0, 1.5, 0.0), Vec3::NEG_X);
+    let hits = index.raycast(&ray, f32::MAX, &scene, &setup.rm);
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].key, keys[0]);
+    assert!((hits[0].distance - 7.0).abs() < 1e-4);
+    assert!(hits[0].normal.abs_diff_eq(Vec3::X, 1e-4));
+}
+
+#[test]
+fn test_raycast_normal_faces_ray_from_behind() {
+    let (scene, index, setup, _keys) = build_quad_scene(&[Mat4::IDENTITY]);
+    let ray = Ray::new(Vec3::new(0.2, -0.3, -4.0), Vec3::Z);
+    let hits = index.raycast(&ray, f32::MAX, &scene, &setup.rm);
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].normal, Vec3::NEG_Z);
+}
+
+#[test]
+fn test_raycast_skips_instances_missing_from_scene() {
+    let (mut scene, index, setup, keys) = build_quad_scene(&[Mat4::IDENTITY]);
+    // Removed from the scene, not yet from the index
+    scene.remove_render_instance(keys[0]);
+    scene.removed_instances();
+    let ray = Ray::new(Vec3::new(0.0, 0.5, 10.0), Vec3::NEG_Z);
+    assert!(index.raycast(&ray, f32::MAX, &scene, &setup.rm).is_empty());
+}
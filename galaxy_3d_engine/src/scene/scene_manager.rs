@@ -2,12 +2,17 @@
 ///
 /// Manages named scenes. Scenes are stored as Arc<Mutex<Scene>>
 /// for thread-safe shared access.
+///
+/// Also holds the `ViewList` of the frame: the cameras rendered this frame,
+/// culled and dispatched in order by `prepare_views()`.
 
 use rustc_hash::FxHashMap;
 use std::sync::{Arc, Mutex};
 use crate::error::Result;
-use crate::{engine_bail};
+use crate::{engine_bail, engine_not_found};
+use crate::resource::ResourceManager;
 use super::scene::Scene;
+use super::view_list::ViewList;
 
 /// Scene manager singleton (managed by Engine)
 ///
@@ -15,6 +20,7 @@ use super::scene::Scene;
 /// (main scene, UI overlay, minimap, etc.).
 pub struct SceneManager {
     scenes: FxHashMap<String, Arc<Mutex<Scene>>>,
+    views: ViewList,
 }
 
 impl SceneManager {
@@ -22,6 +28,7 @@ impl SceneManager {
     pub(crate) fn new() -> Self {
         Self {
             scenes: FxHashMap::default(),
+            views: ViewList::new(),
         }
     }
 
@@ -68,9 +75,57 @@ impl SceneManager {
         self.scenes.keys().map(|k| k.as_str()).collect()
     }
 
-    /// Remove all scenes
+    /// Remove all scenes and views
     pub fn clear(&mut self) {
         self.scenes.clear();
+        self.views = ViewList::new();
+    }
+
+    // ===== VIEWS =====
+
+    /// Replace the views rendered each frame
+    ///
+    /// Returns the previous list.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a view renders a scene that does not exist; the
+    /// current list is kept.
+    pub fn submit_views(&mut self, views: ViewList) -> Result<ViewList> {
+        if let Some(view) = views.iter().find(|v| !self.scenes.contains_key(v.scene())) {
+            engine_bail!("galaxy3d::SceneManager",
+                "View '{}' renders unknown scene '{}'", view.name(), view.scene());
+        }
+        Ok(std::mem::replace(&mut self.views, views))
+    }
+
+    /// Views of the frame, in render order
+    pub fn views(&self) -> &ViewList {
+        &self.views
+    }
+
+    /// Views of the frame (e.g. to move cameras before `prepare_views()`)
+    pub fn views_mut(&mut self) -> &mut ViewList {
+        &mut self.views
+    }
+
+    /// Cull and dispatch every enabled view, in order, and write their
+    /// frame buffers. Each scene is locked once per view.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a view's scene was removed or its updater
+    /// fails; the views before it are already prepared.
+    pub fn prepare_views(&mut self, rm: &ResourceManager) -> Result<()> {
+        crate::profile_scope!("prepare_views");
+        for view in self.views.iter_mut().filter(|v| v.is_enabled()) {
+            let scene = self.scenes.get(view.scene()).ok_or_else(|| {
+                engine_not_found!("galaxy3d::SceneManager", "Scene", view.scene())
+            })?;
+            let mut scene = scene.lock().unwrap();
+            view.prepare(&mut scene, rm)?;
+        }
+        Ok(())
     }
 }
 
@@ -250,3 +250,58 @@ fn test_scene_arc_mutex_is_shareable() {
     // Both references access the same scene
     assert!(Arc::ptr_eq(&scene, &scene2));
 }
+
+// ============================================================================
+// Tests: Views
+// ============================================================================
+
+fn make_view(name: &str, scene: &str, order: i32) -> super::super::view_list::SceneView {
+    use crate::scene::scene_test_helpers::create_test_camera;
+    super::super::view_list::SceneView::new(name, scene, create_test_camera(), &[0])
+        .unwrap()
+        .with_order(order)
+}
+
+#[test]
+fn test_submit_views_rejects_unknown_scene() {
+    let mut sm = SceneManager::new();
+    create_scene_with_mock(&mut sm, "main").unwrap();
+
+    let mut views = ViewList::new();
+    views.add(make_view("main", "main", 0)).unwrap();
+    assert!(sm.submit_views(views).unwrap().is_empty());
+
+    let mut bad = ViewList::new();
+    bad.add(make_view("minimap", "missing", 0)).unwrap();
+    assert!(sm.submit_views(bad).is_err());
+    assert_eq!(sm.views().names(), ["main"]);
+
+    sm.clear();
+    assert!(sm.views().is_empty());
+}
+
+#[test]
+fn test_prepare_views_dispatches_enabled_views() {
+    use crate::scene::scene_test_helpers::{setup_resources, create_test_aabb};
+    let setup = setup_resources();
+    let mut sm = SceneManager::new();
+    let scene = create_scene_with_mock(&mut sm, "world").unwrap();
+    scene.lock().unwrap().create_render_instance(
+        setup.mesh_key, glam::Mat4::IDENTITY, create_test_aabb(),
+        setup.vertex_shader_key, &[], &setup.rm,
+    ).unwrap();
+
+    let mut views = ViewList::new();
+    views.add(make_view("main", "world", 0)).unwrap();
+    views.add(make_view("minimap", "world", 1)).unwrap();
+    sm.submit_views(views).unwrap();
+    sm.views_mut().view_mut("minimap").unwrap().set_enabled(false);
+
+    sm.prepare_views(&setup.rm).unwrap();
+    assert_eq!(sm.views().view("main").unwrap().visible().visible_count(), 1);
+    assert!(sm.views().view("minimap").unwrap().visible().is_empty());
+
+    // A removed scene fails the views that still render it
+    sm.remove_scene("world");
+    assert!(sm.prepare_views(&setup.rm).is_err());
+}
@@ -0,0 +1,118 @@
+/// Scene node hierarchy.
+///
+/// A SceneNode carries a local transform relative to its parent and the
+/// resulting world transform. Render instances, lights and cameras can be
+/// attached to a node: they then follow it, keeping the offset they had
+/// relative to the node when they were attached.
+///
+/// Nodes are stored in the Scene and edited through Scene methods, which
+/// track dirty nodes. `Scene::update_node_transforms()` (called each frame by
+/// `HierarchyUpdater`) recomputes world transforms top-down and pushes them
+/// to the attached objects through the regular dirty sets.
+
+use glam::{Mat4, Vec3};
+use slotmap::new_key_type;
+use crate::camera::Camera;
+use super::render_instance::RenderInstanceKey;
+use super::light::LightKey;
+
+// ===== SLOT MAP KEY =====
+
+new_key_type! {
+    /// Stable key for a SceneNode within a Scene.
+    ///
+    /// Keys remain valid even after other nodes are removed.
+    /// A key becomes invalid only when its own node is removed.
+    pub struct SceneNodeKey;
+}
+
+// ===== ATTACHMENTS =====
+
+/// Light attached to a node, with its position and direction in node space
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct NodeLight {
+    pub(crate) key: LightKey,
+    pub(crate) local_position: Vec3,
+    pub(crate) local_direction: Vec3,
+}
+
+/// Camera owned by a node, with its camera-to-node transform
+pub(crate) struct NodeCamera {
+    pub(crate) camera: Camera,
+    pub(crate) local: Mat4,
+}
+
+// ===== SCENE NODE =====
+
+/// Node of the scene hierarchy.
+pub struct SceneNode {
+    pub(crate) parent: Option<SceneNodeKey>,
+    pub(crate) children: Vec<SceneNodeKey>,
+    /// Transform relative to the parent (or to the world for root nodes)
+    pub(crate) local_transform: Mat4,
+    /// `parent.world_transform * local_transform`
+    pub(crate) world_transform: Mat4,
+    /// Attached render instances with their instance-to-node transform
+    pub(crate) render_instances: Vec<(RenderInstanceKey, Mat4)>,
+    pub(crate) lights: Vec<NodeLight>,
+    pub(crate) cameras: Vec<NodeCamera>,
+}
+
+impl SceneNode {
+    pub(crate) fn new(parent: Option<SceneNodeKey>, local_transform: Mat4, world_transform: Mat4) -> Self {
+        Self {
+            parent,
+            children: Vec::new(),
+            local_transform,
+            world_transform,
+            render_instances: Vec::new(),
+            lights: Vec::new(),
+            cameras: Vec::new(),
+        }
+    }
+
+    /// Parent node (None for a root node)
+    pub fn parent(&self) -> Option<SceneNodeKey> {
+        self.parent
+    }
+
+    /// Child nodes
+    pub fn children(&self) -> &[SceneNodeKey] {
+        &self.children
+    }
+
+    /// Transform relative to the parent
+    pub fn local_transform(&self) -> &Mat4 {
+        &self.local_transform
+    }
+
+    /// World transform, as of the last `Scene::update_node_transforms()`
+    pub fn world_transform(&self) -> &Mat4 {
+        &self.world_transform
+    }
+
+    /// Keys of the attached render instances
+    pub fn render_instances(&self) -> impl Iterator<Item = RenderInstanceKey> + '_ {
+        self.render_instances.iter().map(|(key, _)| *key)
+    }
+
+    /// Keys of the attached lights
+    pub fn lights(&self) -> impl Iterator<Item = LightKey> + '_ {
+        self.lights.iter().map(|light| light.key)
+    }
+
+    /// Number of cameras owned by this node
+    pub fn camera_count(&self) -> usize {
+        self.cameras.len()
+    }
+
+    /// Camera owned by this node, with its view matrix and frustum following
+    /// the node
+    pub fn camera(&self, index: usize) -> Option<&Camera> {
+        self.cameras.get(index).map(|c| &c.camera)
+    }
+}
+
+#[cfg(test)]
+#[path = "scene_node_tests.rs"]
+mod tests;
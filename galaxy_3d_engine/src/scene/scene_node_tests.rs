@@ -0,0 +1,208 @@
+use crate::scene::scene::Scene;
+use crate::scene::{LightDesc, SceneNodeKey};
+use crate::scene::scene_test_helpers::{setup_resources, create_test_aabb, create_test_camera};
+use glam::{Mat4, Vec3};
+
+fn translation(x: f32, y: f32, z: f32) -> Mat4 {
+    Mat4::from_translation(Vec3::new(x, y, z))
+}
+
+fn point_light(position: Vec3) -> LightDesc {
+    LightDesc::Point {
+        position, color: Vec3::ONE, intensity: 1.0, range: 10.0,
+        attenuation_constant: 0.0, attenuation_linear: 0.0, attenuation_quadratic: 1.0,
+    }
+}
+
+// ============================================================================
+// Hierarchy structure
+// ============================================================================
+
+#[test]
+fn test_create_node_links_parent_and_child() {
+    let mut scene = Scene::new();
+    let root = scene.create_node(None, Mat4::IDENTITY).unwrap();
+    let child = scene.create_node(Some(root), translation(1.0, 0.0, 0.0)).unwrap();
+
+    assert_eq!(scene.node(child).unwrap().parent(), Some(root));
+    assert_eq!(scene.node(root).unwrap().children(), &[child]);
+    assert_eq!(scene.node_count(), 2);
+}
+
+#[test]
+fn test_create_node_with_invalid_parent_fails() {
+    let mut scene = Scene::new();
+    assert!(scene.create_node(Some(SceneNodeKey::default()), Mat4::IDENTITY).is_err());
+}
+
+#[test]
+fn test_set_node_parent_rejects_cycles() {
+    let mut scene = Scene::new();
+    let a = scene.create_node(None, Mat4::IDENTITY).unwrap();
+    let b = scene.create_node(Some(a), Mat4::IDENTITY).unwrap();
+    let c = scene.create_node(Some(b), Mat4::IDENTITY).unwrap();
+
+    assert!(!scene.set_node_parent(a, Some(c)));
+    assert!(!scene.set_node_parent(a, Some(a)));
+    assert!(scene.set_node_parent(c, Some(a)));
+    assert_eq!(scene.node(a).unwrap().children(), &[b, c]);
+    assert!(scene.node(b).unwrap().children().is_empty());
+}
+
+#[test]
+fn test_remove_node_removes_subtree_and_attachments() {
+    let s = setup_resources();
+    let mut scene = Scene::new();
+    let root = scene.create_node(None, Mat4::IDENTITY).unwrap();
+    let child = scene.create_node(Some(root), Mat4::IDENTITY).unwrap();
+    let grandchild = scene.create_node(Some(child), Mat4::IDENTITY).unwrap();
+    let instance = scene.create_render_instance(s.mesh_key, Mat4::IDENTITY, create_test_aabb(), s.vertex_shader_key, &[], &s.rm).unwrap();
+    let light = scene.create_light(point_light(Vec3::ZERO));
+    scene.attach_render_instance(grandchild, instance);
+    scene.attach_light(child, light);
+
+    assert!(scene.remove_node(child));
+    assert!(scene.node(grandchild).is_none());
+    assert!(scene.node(root).unwrap().children().is_empty());
+    assert_eq!(scene.node_count(), 1);
+    // Attached objects are marked for deferred removal
+    assert!(scene.removed_instances().contains(&instance));
+    assert!(scene.removed_lights().contains(&light));
+}
+
+// ============================================================================
+// Transform propagation
+// ============================================================================
+
+#[test]
+fn test_update_propagates_world_transforms() {
+    let mut scene = Scene::new();
+    let root = scene.create_node(None, translation(10.0, 0.0, 0.0)).unwrap();
+    let child = scene.create_node(Some(root), translation(0.0, 5.0, 0.0)).unwrap();
+    assert_eq!(scene.update_node_transforms(), 2);
+    assert_eq!(*scene.node(child).unwrap().world_transform(), translation(10.0, 5.0, 0.0));
+
+    // Moving the root moves the child; nothing to do on the next call
+    scene.set_node_transform(root, translation(-1.0, 0.0, 0.0));
+    assert!(scene.is_node_dirty(root));
+    assert_eq!(scene.update_node_transforms(), 2);
+    assert_eq!(*scene.node(child).unwrap().world_transform(), translation(-1.0, 5.0, 0.0));
+    assert_eq!(scene.update_node_transforms(), 0);
+}
+
+#[test]
+fn test_dirty_child_of_dirty_parent_updated_once() {
+    let mut scene = Scene::new();
+    let root = scene.create_node(None, Mat4::IDENTITY).unwrap();
+    let child = scene.create_node(Some(root), Mat4::IDENTITY).unwrap();
+    scene.update_node_transforms();
+
+    scene.set_node_transform(child, translation(0.0, 0.0, 1.0));
+    scene.set_node_transform(root, translation(1.0, 0.0, 0.0));
+    assert_eq!(scene.update_node_transforms(), 2);
+    assert_eq!(*scene.node(child).unwrap().world_transform(), translation(1.0, 0.0, 1.0));
+}
+
+#[test]
+fn test_reparent_keeps_local_transform() {
+    let mut scene = Scene::new();
+    let a = scene.create_node(None, translation(1.0, 0.0, 0.0)).unwrap();
+    let b = scene.create_node(None, translation(0.0, 2.0, 0.0)).unwrap();
+    let child = scene.create_node(Some(a), translation(0.0, 0.0, 3.0)).unwrap();
+    scene.update_node_transforms();
+
+    scene.set_node_parent(child, Some(b));
+    scene.update_node_transforms();
+    assert_eq!(*scene.node(child).unwrap().world_transform(), translation(0.0, 2.0, 3.0));
+}
+
+// ============================================================================
+// Attachments
+// ============================================================================
+
+#[test]
+fn test_attached_instance_follows_node_and_is_marked_dirty() {
+    let s = setup_resources();
+    let mut scene = Scene::new();
+    let node = scene.create_node(None, translation(1.0, 0.0, 0.0)).unwrap();
+    scene.update_node_transforms();
+    // Instance placed 2 units right of the node: that offset is kept
+    let instance = scene.create_render_instance(s.mesh_key, translation(3.0, 0.0, 0.0), create_test_aabb(), s.vertex_shader_key, &[], &s.rm).unwrap();
+    assert!(scene.attach_render_instance(node, instance));
+    assert_eq!(scene.node(node).unwrap().render_instances().collect::<Vec<_>>(), vec![instance]);
+
+    scene.set_node_transform(node, translation(1.0, 0.0, -4.0));
+    scene.update_node_transforms();
+    assert_eq!(*scene.render_instance(instance).unwrap().world_matrix(), translation(3.0, 0.0, -4.0));
+    assert!(scene.has_dirty_instance_transform(instance));
+}
+
+#[test]
+fn test_detached_or_removed_instance_no_longer_follows() {
+    let s = setup_resources();
+    let mut scene = Scene::new();
+    let node = scene.create_node(None, Mat4::IDENTITY).unwrap();
+    let a = scene.create_render_instance(s.mesh_key, Mat4::IDENTITY, create_test_aabb(), s.vertex_shader_key, &[], &s.rm).unwrap();
+    let b = scene.create_render_instance(s.mesh_key, Mat4::IDENTITY, create_test_aabb(), s.vertex_shader_key, &[], &s.rm).unwrap();
+    scene.attach_render_instance(node, a);
+    scene.attach_render_instance(node, b);
+
+    assert!(scene.detach_render_instance(a));
+    assert!(!scene.detach_render_instance(a));
+    scene.remove_render_instance(b);
+    assert_eq!(scene.node(node).unwrap().render_instances().count(), 0);
+
+    scene.set_node_transform(node, translation(5.0, 0.0, 0.0));
+    scene.update_node_transforms();
+    assert_eq!(*scene.render_instance(a).unwrap().world_matrix(), Mat4::IDENTITY);
+}
+
+#[test]
+fn test_attach_moves_instance_between_nodes() {
+    let s = setup_resources();
+    let mut scene = Scene::new();
+    let n1 = scene.create_node(None, Mat4::IDENTITY).unwrap();
+    let n2 = scene.create_node(None, Mat4::IDENTITY).unwrap();
+    let instance = scene.create_render_instance(s.mesh_key, Mat4::IDENTITY, create_test_aabb(), s.vertex_shader_key, &[], &s.rm).unwrap();
+    scene.attach_render_instance(n1, instance);
+    scene.attach_render_instance(n2, instance);
+    assert_eq!(scene.node(n1).unwrap().render_instances().count(), 0);
+    assert_eq!(scene.node(n2).unwrap().render_instances().count(), 1);
+}
+
+#[test]
+fn test_attached_light_follows_rotation() {
+    let mut scene = Scene::new();
+    let node = scene.create_node(None, Mat4::IDENTITY).unwrap();
+    scene.update_node_transforms();
+    let light = scene.create_light(point_light(Vec3::new(1.0, 0.0, 0.0)));
+    assert!(scene.attach_light(node, light));
+    let _ = scene.dirty_light_transforms();
+
+    scene.set_node_transform(node, Mat4::from_rotation_y(std::f32::consts::FRAC_PI_2));
+    scene.update_node_transforms();
+    let position = scene.light(light).unwrap().position();
+    assert!((position - Vec3::new(0.0, 0.0, -1.0)).length() < 1e-5, "{:?}", position);
+    assert!(scene.dirty_light_transforms().contains(&light));
+}
+
+#[test]
+fn test_node_camera_view_and_frustum_follow_node() {
+    let mut scene = Scene::new();
+    let node = scene.create_node(None, Mat4::IDENTITY).unwrap();
+    scene.update_node_transforms();
+    let index = scene.attach_camera(node, create_test_camera()).unwrap();
+    assert_eq!(scene.node(node).unwrap().camera_count(), 1);
+
+    scene.set_node_transform(node, translation(3.0, 0.0, 0.0));
+    scene.update_node_transforms();
+    let camera = scene.node(node).unwrap().camera(index).unwrap();
+    assert_eq!(*camera.view_matrix(), translation(-3.0, 0.0, 0.0));
+    // Identity projection: the frustum is the unit cube around the camera,
+    // so the left plane moved from x = -1 to x = 2
+    let left_plane = camera.frustum().planes[crate::camera::PLANE_LEFT];
+    assert!((left_plane.w + 2.0).abs() < 1e-5, "{:?}", left_plane);
+
+    assert!(scene.node_camera_mut(node, index).is_some());
+    assert!(scene.node_camera_mut(node, index + 1).is_none());
+}
@@ -22,8 +22,9 @@ use crate::resource::material::{MaterialDesc, MaterialPassDesc, ParamValue};
 use crate::resource::mesh::{MeshDesc, MeshSubMeshDesc, GeometryMeshRef, GeometrySubMeshRef};
 use crate::resource::shader::ShaderDesc;
 use crate::resource::buffer::Buffer;
-use crate::resource::resource_manager::{ResourceManager, MeshKey, ShaderKey};
-use crate::scene::render_instance::AABB;
+use crate::resource::texture::{TextureDesc, LayerDesc, AtlasRegion, AtlasRegionDesc};
+use crate::resource::resource_manager::{ResourceManager, MeshKey, ShaderKey, TextureKey};
+use crate::scene::aabb::AABB;
 
 pub(crate) struct TestSetup {
     pub rm: ResourceManager,
@@ -76,26 +77,47 @@ fn create_test_shaders(
 /// pipeline, and default material. Returns the keys needed to instantiate
 /// it in a `Scene`.
 pub(crate) fn setup_resources() -> TestSetup {
+    setup_resources_with_lod_thresholds(Vec::new())
+}
+
+/// Same as `setup_resources()`, with one LOD per frontier of `lod_thresholds`
+/// plus LOD 0 (all LODs share the same vertex and index range).
+pub(crate) fn setup_resources_with_lod_thresholds(lod_thresholds: Vec<(f32, f32)>) -> TestSetup {
+    setup_resources_from(&[[0.0; 2]; 6], &[0; 6], lod_thresholds)
+}
+
+/// Same as `setup_resources()`, with a 2x2 quad in the XY plane centered on
+/// the origin (two triangles) as the mesh geometry.
+pub(crate) fn setup_quad_resources() -> TestSetup {
+    let corners = [[-1.0, -1.0], [1.0, -1.0], [1.0, 1.0], [-1.0, 1.0]];
+    setup_resources_from(&corners, &[0, 1, 2, 2, 3, 0], Vec::new())
+}
+
+/// Build the test resources around a geometry of vec2 positions
+fn setup_resources_from(positions: &[[f32; 2]], indices: &[u16], lod_thresholds: Vec<(f32, f32)>) -> TestSetup {
     let gd = create_mock_graphics_device();
     let mut rm = ResourceManager::new();
 
+    let vertex_data: Vec<u8> = positions.iter().flatten().flat_map(|v| v.to_ne_bytes()).collect();
+    let index_data: Vec<u8> = indices.iter().flat_map(|i| i.to_ne_bytes()).collect();
     let geo_key = rm.create_geometry("geo".to_string(), GeometryDesc {
         name: "geo".to_string(),
         graphics_device: gd.clone(),
-        vertex_data: vec![0u8; 48],
-        index_data: Some(vec![0u8; 12]),
+        vertex_data: vertex_data.into(),
+        index_data: Some(index_data.into()),
         vertex_layout: create_vertex_layout(),
         index_type: IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![GeometryMeshDesc {
             name: "cube".to_string(),
             submeshes: vec![GeometrySubMeshDesc {
                 name: "main".to_string(),
-                lods: vec![GeometrySubMeshLODDesc {
-                    vertex_offset: 0, vertex_count: 6,
-                    index_offset: 0, index_count: 6,
+                lods: (0..=lod_thresholds.len()).map(|_| GeometrySubMeshLODDesc {
+                    vertex_offset: 0, vertex_count: positions.len() as u32,
+                    index_offset: 0, index_count: indices.len() as u32,
                     topology: PrimitiveTopology::TriangleList,
-                }],
-                lod_thresholds: Vec::new(),
+                }).collect(),
+                lod_thresholds,
             }],
         }],
     }).unwrap();
@@ -111,6 +133,7 @@ pub(crate) fn setup_resources() -> TestSetup {
         multisample: Default::default(),
         color_formats: vec![],
         depth_format: None,
+        engine_features: Default::default(),
     }, &mut *gd.lock().unwrap()).unwrap();
 
     let mk = rm.create_material("m".to_string(), MaterialDesc {
@@ -122,6 +145,8 @@ pub(crate) fn setup_resources() -> TestSetup {
             textures: vec![],
             params: vec![("value".to_string(), ParamValue::Float(1.0))],
             render_state: None,
+            engine_features: Default::default(),
+            render_queue: None,
         }],
     }, &*gd.lock().unwrap()).unwrap();
 
@@ -137,6 +162,27 @@ pub(crate) fn setup_resources() -> TestSetup {
     TestSetup { rm, mesh_key, vertex_shader_key: vk }
 }
 
+/// Create a 256x256 atlas texture: layer "sprites" with regions "smoke"
+/// (0, 0, 64x64) and "spark" (64, 0, 64x128)
+pub(crate) fn create_atlas_texture(rm: &mut ResourceManager) -> TextureKey {
+    rm.create_texture("atlas".to_string(), TextureDesc {
+        graphics_device: create_mock_graphics_device(),
+        texture: graphics_device::TextureDesc {
+            width: 256, height: 256, depth: 1,
+            format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
+            usage: graphics_device::TextureUsage::Sampled,
+            texture_type: graphics_device::TextureType::Tex2D,
+            sample_count: graphics_device::SampleCount::S1,
+            array_layers: 1, data: None, mipmap: graphics_device::MipmapMode::None,
+            debug_name: None,
+        },
+        layers: vec![LayerDesc { name: "sprites".to_string(), layer_index: 0, data: None, regions: vec![
+            AtlasRegionDesc { name: "smoke".to_string(), region: AtlasRegion { x: 0, y: 0, width: 64, height: 64 } },
+            AtlasRegionDesc { name: "spark".to_string(), region: AtlasRegion { x: 64, y: 0, width: 64, height: 128 } },
+        ]}],
+    }).unwrap()
+}
+
 /// Build a frame uniform buffer detached from the global Engine, sized to
 /// match `DefaultUpdater::update_frame`'s field layout.
 pub(crate) fn make_frame_buffer(rm: &mut ResourceManager) -> Arc<Buffer> {
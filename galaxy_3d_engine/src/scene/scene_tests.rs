@@ -18,7 +18,8 @@ use crate::resource::mesh::{
 };
 use crate::resource::shader::ShaderDesc;
 use crate::resource::resource_manager::{ResourceManager, MeshKey, ShaderKey};
-use crate::scene::render_instance::{AABB, RenderInstanceKey};
+use crate::scene::aabb::AABB;
+use crate::scene::render_instance::RenderInstanceKey;
 use crate::scene::render_view::RenderView;
 use crate::scene::view_dispatcher::ViewDispatcher;
 use glam::{Vec3, Mat4};
@@ -66,8 +67,8 @@ fn setup_resources() -> TestSetup {
 
     let _geo_key = rm.create_geometry("geo".to_string(), GeometryDesc {
         name: "geo".to_string(), graphics_device: gd.clone(),
-        vertex_data: vec![0u8; 48], index_data: Some(vec![0u8; 12]),
-        vertex_layout: create_vertex_layout(), index_type: IndexType::U16,
+        vertex_data: vec![0u8; 48].into(), index_data: Some(vec![0u8; 12].into()),
+        vertex_layout: create_vertex_layout(), index_type: IndexType::U16, morph_targets: Vec::new(),
         meshes: vec![GeometryMeshDesc {
             name: "cube".to_string(),
             submeshes: vec![GeometrySubMeshDesc {
@@ -87,7 +88,7 @@ fn setup_resources() -> TestSetup {
         vertex_shader: vk, fragment_shader: fk,
         vertex_layout: create_vertex_layout(), topology: PrimitiveTopology::TriangleList,
         rasterization: Default::default(), color_blend: Default::default(),
-        multisample: Default::default(), color_formats: vec![], depth_format: None,
+        multisample: Default::default(), color_formats: vec![], depth_format: None, engine_features: Default::default(),
     }, &mut *gd.lock().unwrap()).unwrap();
 
     let mk = rm.create_material("m".to_string(), MaterialDesc {
@@ -96,6 +97,8 @@ fn setup_resources() -> TestSetup {
             fragment_shader: fk, color_blend: Default::default(), polygon_mode: PolygonMode::Fill, textures: vec![],
             params: vec![("value".to_string(), ParamValue::Float(1.0))],
             render_state: None,
+            engine_features: Default::default(),
+            render_queue: None,
         }],
     }, &*gd.lock().unwrap()).unwrap();
 
@@ -127,6 +130,31 @@ fn test_scene_new_is_empty() {
     assert_eq!(scene.render_instance_count(), 0);
 }
 
+// ============================================================================
+// Tests: Environment
+// ============================================================================
+
+#[test]
+fn test_scene_environment_setters() {
+    use crate::scene::{Fog, FogMode, SceneEnvironment};
+
+    let mut scene = Scene::new();
+    assert_eq!(*scene.environment(), SceneEnvironment::default());
+
+    scene.set_ambient(Vec3::new(0.2, 0.2, 0.3), 2.0);
+    scene.set_fog(Fog { mode: FogMode::Linear, start: 5.0, end: 50.0, ..Default::default() });
+    assert_eq!(scene.environment().ambient_color, Vec3::new(0.2, 0.2, 0.3));
+    assert_eq!(scene.environment().ambient_intensity, 2.0);
+    assert_eq!(scene.environment().fog.mode, FogMode::Linear);
+
+    // clear() keeps the environment
+    scene.clear();
+    assert_eq!(scene.environment().fog.end, 50.0);
+
+    scene.set_environment(SceneEnvironment::default());
+    assert_eq!(scene.environment().fog.mode, FogMode::None);
+}
+
 // ============================================================================
 // Tests: Create RenderInstance
 // ============================================================================
@@ -162,6 +190,24 @@ fn test_create_render_instance_returns_unique_keys() {
     assert_ne!(k1, k2);
 }
 
+#[test]
+fn test_add_mesh_instance_uses_geometry_bounds() {
+    let s = setup_resources();
+    let mut scene = Scene::new();
+    let key = scene.add_mesh_instance(s.mesh_key, Mat4::from_translation(Vec3::X), s.vertex_shader_key, &s.rm).unwrap();
+
+    let instance = scene.render_instance(key).unwrap();
+    let geometry_bounds = *s.rm.geometry(s.rm.mesh(s.mesh_key).unwrap().geometry()).unwrap().bounds().unwrap();
+    assert_eq!(instance.bounding_box().min, geometry_bounds.min);
+    assert_eq!(instance.bounding_box().max, geometry_bounds.max);
+    assert_eq!(*instance.world_matrix(), Mat4::from_translation(Vec3::X));
+    assert_eq!(instance.sub_mesh_count(), 1);
+    assert!(instance.is_visible());
+    assert!(scene.new_instances().contains(&key));
+
+    assert!(scene.add_mesh_instance(MeshKey::default(), Mat4::IDENTITY, s.vertex_shader_key, &s.rm).is_err());
+}
+
 // ============================================================================
 // Tests: Remove RenderInstance (Deferred)
 // ============================================================================
@@ -177,6 +223,162 @@ fn test_remove_render_instance_deferred() {
     assert_eq!(scene.render_instance_count(), 0);
 }
 
+#[test]
+fn test_remove_render_instances_batch() {
+    let s = setup_resources();
+    let mut scene = Scene::new();
+    let keys: Vec<_> = (0..4).map(|_| {
+        scene.create_render_instance(s.mesh_key, Mat4::IDENTITY, create_test_aabb(), s.vertex_shader_key, &[], &s.rm).unwrap()
+    }).collect();
+    remove_and_commit(&mut scene, keys[3]);
+
+    // keys[3] is already gone
+    assert_eq!(scene.remove_render_instances(keys.iter().copied()), 3);
+    assert_eq!(scene.render_instance_count(), 3); // deferred
+    assert_eq!(scene.removed_instances().len(), 3);
+    assert_eq!(scene.render_instance_count(), 0);
+    assert_eq!(scene.draw_slot_count(), 0);
+}
+
+#[test]
+fn test_set_morph_weights_marks_dirty() {
+    let s = setup_resources();
+    let mut scene = Scene::new();
+    let key = scene.create_render_instance(s.mesh_key, Mat4::IDENTITY, create_test_aabb(), s.vertex_shader_key, &[], &s.rm).unwrap();
+    assert_eq!(*scene.render_instance(key).unwrap().morph_weights(), [0.0; 4]);
+
+    assert!(scene.set_morph_weights(key, &[0.5, 1.0]));
+    assert_eq!(*scene.render_instance(key).unwrap().morph_weights(), [0.5, 1.0, 0.0, 0.0]);
+    assert!(scene.dirty_instance_morph_weights().contains(&key));
+
+    remove_and_commit(&mut scene, key);
+    assert!(!scene.set_morph_weights(key, &[1.0]));
+}
+
+#[test]
+fn test_set_tints_marks_dirty() {
+    let s = setup_resources();
+    let mut scene = Scene::new();
+    let key = scene.create_render_instance(s.mesh_key, Mat4::IDENTITY, create_test_aabb(), s.vertex_shader_key, &[], &s.rm).unwrap();
+    assert_eq!(scene.render_instance(key).unwrap().color_tint(), Vec4::ONE);
+    assert_eq!(scene.render_instance(key).unwrap().emissive_tint(), Vec3::ZERO);
+
+    assert!(scene.set_color_tint(key, Vec4::new(1.0, 0.0, 0.0, 1.0)));
+    assert!(scene.set_emissive_tint(key, Vec3::new(0.0, 0.5, 0.0)));
+    assert_eq!(scene.render_instance(key).unwrap().color_tint(), Vec4::new(1.0, 0.0, 0.0, 1.0));
+    assert_eq!(scene.render_instance(key).unwrap().emissive_tint(), Vec3::new(0.0, 0.5, 0.0));
+    assert_eq!(scene.dirty_instance_tints().len(), 1);
+
+    remove_and_commit(&mut scene, key);
+    assert!(!scene.set_color_tint(key, Vec4::ONE));
+    assert!(!scene.set_emissive_tint(key, Vec3::ZERO));
+}
+
+// ============================================================================
+// Tests: TRS transform components
+// ============================================================================
+
+#[test]
+fn test_transform_components_compose_lazily() {
+    let s = setup_resources();
+    let mut scene = Scene::new();
+    let key = scene.create_render_instance(s.mesh_key, Mat4::IDENTITY, create_test_aabb(), s.vertex_shader_key, &[], &s.rm).unwrap();
+    let _ = scene.dirty_instance_transforms();
+
+    let rotation = Quat::from_rotation_y(0.5);
+    assert!(scene.set_transform_components(key, Vec3::new(1.0, 2.0, 3.0), rotation, Vec3::splat(2.0)));
+    // Nothing composed yet
+    assert_eq!(*scene.render_instance(key).unwrap().world_matrix(), Mat4::IDENTITY);
+    assert_eq!(scene.dirty_instance_transform_count(), 0);
+
+    assert_eq!(scene.compose_transform_components(), 1);
+    assert_eq!(*scene.render_instance(key).unwrap().world_matrix(),
+        Mat4::from_scale_rotation_translation(Vec3::splat(2.0), rotation, Vec3::new(1.0, 2.0, 3.0)));
+    assert!(scene.has_dirty_instance_transform(key));
+    assert_eq!(scene.compose_transform_components(), 0);
+}
+
+#[test]
+fn test_single_component_setter_decomposes_world_matrix() {
+    let s = setup_resources();
+    let mut scene = Scene::new();
+    let world = Mat4::from_scale_rotation_translation(Vec3::splat(3.0), Quat::IDENTITY, Vec3::new(0.0, 4.0, 0.0));
+    let key = scene.create_render_instance(s.mesh_key, world, create_test_aabb(), s.vertex_shader_key, &[], &s.rm).unwrap();
+    assert!(scene.transform_components(key).is_none());
+
+    assert!(scene.set_translation(key, Vec3::new(1.0, 0.0, 0.0)));
+    let (translation, _, scale) = scene.transform_components(key).unwrap();
+    assert_eq!(translation, Vec3::new(1.0, 0.0, 0.0));
+    assert!((scale - Vec3::splat(3.0)).length() < 1e-5);
+
+    // An explicit matrix returns the instance to matrix mode
+    assert!(scene.set_world_matrix(key, Mat4::IDENTITY));
+    assert!(scene.transform_components(key).is_none());
+    assert_eq!(scene.compose_transform_components(), 0);
+    assert_eq!(*scene.render_instance(key).unwrap().world_matrix(), Mat4::IDENTITY);
+}
+
+#[test]
+fn test_transform_components_rejected_cases() {
+    let s = setup_resources();
+    let mut scene = Scene::new();
+    let key = scene.create_render_instance(s.mesh_key, Mat4::IDENTITY, create_test_aabb(), s.vertex_shader_key, &[], &s.rm).unwrap();
+
+    assert!(!scene.set_transform_components(key, Vec3::NAN, Quat::IDENTITY, Vec3::ONE));
+    assert!(!scene.set_rotation(key, Quat::from_xyzw(0.0, 0.0, 0.0, 0.0)));
+    assert!(scene.transform_components(key).is_none());
+
+    // Node-attached instances are driven by their node
+    let node = scene.create_node(None, Mat4::IDENTITY).unwrap();
+    scene.attach_render_instance(node, key);
+    assert!(!scene.set_translation(key, Vec3::X));
+
+    remove_and_commit(&mut scene, key);
+    assert!(!scene.set_scale(key, Vec3::ONE));
+}
+
+#[test]
+fn test_transform_interpolation_between_fixed_steps() {
+    let s = setup_resources();
+    let mut scene = Scene::new();
+    let key = scene.create_render_instance(s.mesh_key, Mat4::IDENTITY, create_test_aabb(), s.vertex_shader_key, &[], &s.rm).unwrap();
+    scene.set_transform_components(key, Vec3::ZERO, Quat::IDENTITY, Vec3::ONE);
+    assert_eq!(scene.transform_interpolation(), 1.0);
+
+    scene.begin_transform_step();
+    scene.set_translation(key, Vec3::new(0.0, 0.0, -8.0));
+    assert_eq!(scene.previous_transform_components(key).unwrap().0, Vec3::ZERO);
+    scene.set_transform_interpolation(0.75);
+    scene.set_transform_interpolation(f32::NAN);
+    assert_eq!(scene.transform_interpolation(), 0.75);
+
+    scene.compose_transform_components();
+    assert_eq!(*scene.render_instance(key).unwrap().world_matrix(), Mat4::from_translation(Vec3::new(0.0, 0.0, -6.0)));
+
+    assert!(scene.snap_transform_components(key));
+    scene.compose_transform_components();
+    assert_eq!(*scene.render_instance(key).unwrap().world_matrix(), Mat4::from_translation(Vec3::new(0.0, 0.0, -8.0)));
+}
+
+#[test]
+fn test_attach_uses_latest_components_and_removal_drops_them() {
+    let s = setup_resources();
+    let mut scene = Scene::new();
+    let a = scene.create_render_instance(s.mesh_key, Mat4::IDENTITY, create_test_aabb(), s.vertex_shader_key, &[], &s.rm).unwrap();
+    let b = scene.create_render_instance(s.mesh_key, Mat4::IDENTITY, create_test_aabb(), s.vertex_shader_key, &[], &s.rm).unwrap();
+    scene.set_translation(a, Vec3::new(5.0, 0.0, 0.0));
+    scene.set_translation(b, Vec3::new(6.0, 0.0, 0.0));
+
+    let node = scene.create_node(None, Mat4::IDENTITY).unwrap();
+    assert!(scene.attach_render_instance(node, a));
+    assert!(scene.transform_components(a).is_none());
+    assert_eq!(*scene.render_instance(a).unwrap().world_matrix(), Mat4::from_translation(Vec3::new(5.0, 0.0, 0.0)));
+
+    scene.remove_render_instance(b);
+    assert_eq!(scene.transform_component_count(), 0);
+    assert_eq!(scene.compose_transform_components(), 0);
+}
+
 #[test]
 fn test_remove_render_instance_key_becomes_invalid() {
     let s = setup_resources();
@@ -243,6 +445,28 @@ fn test_set_world_matrix() {
     assert_eq!(*scene.render_instance(key).unwrap().world_matrix(), m);
 }
 
+#[test]
+fn test_set_world_matrix_rejects_nan() {
+    let s = setup_resources();
+    let mut scene = Scene::new();
+    let key = scene.create_render_instance(s.mesh_key, Mat4::IDENTITY, create_test_aabb(), s.vertex_shader_key, &[], &s.rm).unwrap();
+    let nan = Mat4::from_translation(Vec3::new(f32::NAN, 0.0, 0.0));
+    assert!(!scene.set_world_matrix(key, nan));
+    assert_eq!(*scene.render_instance(key).unwrap().world_matrix(), Mat4::IDENTITY);
+    assert!(!scene.has_dirty_instance_transform(key));
+}
+
+#[test]
+fn test_create_render_instance_rejects_non_finite_input() {
+    let s = setup_resources();
+    let mut scene = Scene::new();
+    let nan = Mat4::from_scale(Vec3::splat(f32::NAN));
+    assert!(scene.create_render_instance(s.mesh_key, nan, create_test_aabb(), s.vertex_shader_key, &[], &s.rm).is_err());
+    let inf_aabb = AABB { min: Vec3::splat(f32::NEG_INFINITY), max: Vec3::ONE };
+    assert!(scene.create_render_instance(s.mesh_key, Mat4::IDENTITY, inf_aabb, s.vertex_shader_key, &[], &s.rm).is_err());
+    assert_eq!(scene.render_instance_count(), 0);
+}
+
 // ============================================================================
 // Tests: render_instance_keys
 // ============================================================================
@@ -373,15 +597,18 @@ fn setup_engine_draw_test() -> (Scene, MeshKey, ShaderKey, Arc<dyn crate::graphi
     Engine::create_resource_manager().unwrap();
 
     let rm_arc = Engine::resource_manager().unwrap();
-    let gd = create_mock_graphics_device();
+    // The drawer resolves pipelines through the "main" device
+    let gd = Engine::create_graphics_device(
+        "main", crate::graphics_device::mock_graphics_device::MockGraphicsDevice::new(),
+    ).unwrap();
 
     let (mesh_key, vertex_shader_key) = {
         let mut rm = rm_arc.lock().unwrap();
 
         let geo_key = rm.create_geometry("geo".to_string(), GeometryDesc {
             name: "geo".to_string(), graphics_device: gd.clone(),
-            vertex_data: vec![0u8; 48], index_data: Some(vec![0u8; 12]),
-            vertex_layout: create_vertex_layout(), index_type: IndexType::U16,
+            vertex_data: vec![0u8; 48].into(), index_data: Some(vec![0u8; 12].into()),
+            vertex_layout: create_vertex_layout(), index_type: IndexType::U16, morph_targets: Vec::new(),
             meshes: vec![GeometryMeshDesc {
                 name: "cube".to_string(),
                 submeshes: vec![GeometrySubMeshDesc {
@@ -401,7 +628,7 @@ fn setup_engine_draw_test() -> (Scene, MeshKey, ShaderKey, Arc<dyn crate::graphi
             vertex_shader: vk, fragment_shader: fk,
             vertex_layout: create_vertex_layout(), topology: PrimitiveTopology::TriangleList,
             rasterization: Default::default(), color_blend: Default::default(),
-            multisample: Default::default(), color_formats: vec![], depth_format: None,
+            multisample: Default::default(), color_formats: vec![], depth_format: None, engine_features: Default::default(),
         }, &mut *gd.lock().unwrap()).unwrap();
 
         let mk = rm.create_material("m".to_string(), MaterialDesc {
@@ -410,6 +637,8 @@ fn setup_engine_draw_test() -> (Scene, MeshKey, ShaderKey, Arc<dyn crate::graphi
                 fragment_shader: fk, color_blend: Default::default(), polygon_mode: PolygonMode::Fill, textures: vec![],
                 params: vec![("value".to_string(), ParamValue::Float(1.0))],
                 render_state: None,
+                engine_features: Default::default(),
+                render_queue: None,
             }],
         }, &*gd.lock().unwrap()).unwrap();
 
@@ -493,11 +722,11 @@ fn test_draw_single_instance() {
     assert_eq!(cmd.commands, vec![
         "set_viewport",
         "set_scissor",
+        "bind_pipeline",
+        "bind_binding_group",  // per-pass set, rebound on signature change
         "bind_vertex_buffer",
         "bind_index_buffer",
-        "bind_pipeline",
         "set_dynamic_state",
-        "bind_binding_group",  // global set 0
         // push_constants skipped: MockShader has no reflected push constants
         "draw_indexed",
     ]);
@@ -560,9 +789,11 @@ fn test_draw_multiple_instances() {
         vec![], None, crate::graphics_device::SampleCount::S1,
     );
     drawer.draw(&mut scene, &render_view, &mut cmd, &pass_info, &binding_group, false).unwrap();
-    // 2 instances: viewport + scissor + 2x (bind_vb, bind_ib, bind_pipeline, set_dynamic_state, bind_bg, draw_indexed)
+    // 2 instances sharing pipeline, geometry and render state: viewport + scissor
+    // + one (bind_pipeline, bind_bg, bind_vb, bind_ib, set_dynamic_state) + 2x draw_indexed
     // push_constants skipped: MockShader has no reflected push constants
-    assert_eq!(cmd.commands.len(), 2 + 2 * 6);
+    assert_eq!(cmd.commands.len(), 2 + 5 + 2);
+    assert_eq!(cmd.commands.iter().filter(|c| *c == "draw_indexed").count(), 2);
     Engine::reset_for_testing();
 }
 
@@ -1084,3 +1315,169 @@ mod lights {
         assert_eq!(scene.new_light_count(), 0);
     }
 }
+
+// ============================================================================
+// Tests: Billboards
+// ============================================================================
+
+mod billboards {
+    use super::super::*;
+    use crate::graphics_device::SamplerType;
+    use crate::scene::{BillboardDesc, BillboardMode};
+    use crate::scene::scene_test_helpers::create_atlas_texture;
+    use crate::resource::resource_manager::{ResourceManager, TextureKey};
+    use crate::resource::material::RegionRef;
+
+    fn billboard_desc(texture: TextureKey) -> BillboardDesc {
+        BillboardDesc {
+            position: Vec3::ZERO,
+            size: Vec2::ONE,
+            mode: BillboardMode::ScreenAligned,
+            color: Vec4::ONE,
+            texture,
+            layer: None,
+            region: None,
+            sampler_type: SamplerType::LinearRepeat,
+        }
+    }
+
+    #[test]
+    fn test_create_and_remove_billboard() {
+        let mut rm = ResourceManager::new();
+        let texture = create_atlas_texture(&mut rm);
+        let mut scene = Scene::new();
+        let key = scene.create_billboard(billboard_desc(texture), &rm).unwrap();
+        assert_eq!(scene.billboard_count(), 1);
+        assert!(scene.billboard(key).is_some());
+
+        // Removal is immediate
+        assert!(scene.remove_billboard(key));
+        assert_eq!(scene.billboard_count(), 0);
+        assert!(!scene.remove_billboard(key));
+    }
+
+    #[test]
+    fn test_create_billboard_with_bad_region_fails() {
+        let mut rm = ResourceManager::new();
+        let texture = create_atlas_texture(&mut rm);
+        let mut scene = Scene::new();
+        let mut desc = billboard_desc(texture);
+        desc.region = Some(RegionRef::Index(0));
+        assert!(scene.create_billboard(desc, &rm).is_err());
+        assert_eq!(scene.billboard_count(), 0);
+    }
+
+    #[test]
+    fn test_billboard_setters() {
+        let mut rm = ResourceManager::new();
+        let texture = create_atlas_texture(&mut rm);
+        let mut scene = Scene::new();
+        let key = scene.create_billboard(billboard_desc(texture), &rm).unwrap();
+
+        assert!(scene.set_billboard_position(key, Vec3::X));
+        assert!(scene.set_billboard_size(key, Vec2::new(2.0, 3.0)));
+        assert!(scene.set_billboard_mode(key, BillboardMode::AxisLocked(Vec3::Y * 2.0)));
+        assert!(scene.set_billboard_color(key, Vec4::new(1.0, 0.0, 0.0, 0.5)));
+        assert!(scene.set_billboard_visible(key, false));
+
+        let billboard = scene.billboard(key).unwrap();
+        assert_eq!(billboard.position(), Vec3::X);
+        assert_eq!(billboard.size(), Vec2::new(2.0, 3.0));
+        assert_eq!(billboard.mode(), BillboardMode::AxisLocked(Vec3::Y));
+        assert_eq!(billboard.color(), Vec4::new(1.0, 0.0, 0.0, 0.5));
+        assert!(!billboard.visible());
+
+        scene.remove_billboard(key);
+        assert!(!scene.set_billboard_position(key, Vec3::ZERO));
+        assert!(!scene.set_billboard_visible(key, true));
+    }
+
+    #[test]
+    fn test_set_billboard_replaces_it() {
+        let mut rm = ResourceManager::new();
+        let texture = create_atlas_texture(&mut rm);
+        let mut scene = Scene::new();
+        let key = scene.create_billboard(billboard_desc(texture), &rm).unwrap();
+        scene.set_billboard_visible(key, false);
+
+        let mut desc = billboard_desc(texture);
+        desc.layer = Some(crate::resource::material::LayerRef::Index(0));
+        desc.region = Some(RegionRef::Name("smoke".to_string()));
+        assert!(scene.set_billboard(key, desc, &rm).unwrap());
+
+        let billboard = scene.billboard(key).unwrap();
+        assert_eq!(billboard.uv_rect(), [0.0, 0.0, 0.25, 0.25]);
+        assert!(billboard.visible());
+    }
+
+    #[test]
+    fn test_clear_drops_billboards() {
+        let mut rm = ResourceManager::new();
+        let texture = create_atlas_texture(&mut rm);
+        let mut scene = Scene::new();
+        let key = scene.create_billboard(billboard_desc(texture), &rm).unwrap();
+        scene.clear();
+        assert_eq!(scene.billboard_count(), 0);
+        assert!(scene.billboard(key).is_none());
+    }
+}
+
+// ============================================================================
+// Tests: Serialization
+// ============================================================================
+
+#[cfg(feature = "manifest")]
+mod serialization {
+    use super::*;
+    use crate::resource::manifest::ManifestFormat;
+    use crate::scene::render_instance::FLAG_CAST_SHADOW;
+
+    #[test]
+    fn test_serialize_round_trip_keeps_instance_state() {
+        let s = setup_resources();
+        let mut scene = Scene::new();
+        let world = Mat4::from_translation(Vec3::new(3.0, -2.0, 7.5));
+        let key = scene.create_render_instance(s.mesh_key, world, create_test_aabb(), s.vertex_shader_key, &[], &s.rm).unwrap();
+        scene.render_instance_mut(key).unwrap().set_flags(FLAG_CAST_SHADOW);
+        scene.set_lod_override(key, Some(2));
+        scene.set_color_tint(key, glam::Vec4::new(1.0, 0.5, 0.5, 1.0));
+        scene.set_morph_weights(key, &[0.25]);
+        let removed = scene.create_render_instance(s.mesh_key, Mat4::IDENTITY, create_test_aabb(), s.vertex_shader_key, &[], &s.rm).unwrap();
+        scene.remove_render_instance(removed);
+
+        for format in [ManifestFormat::Ron, ManifestFormat::Json] {
+            let text = scene.serialize(&s.rm, format).unwrap();
+            let mut loaded = Scene::new();
+            let keys = loaded.deserialize(&text, format, &s.rm).unwrap();
+            assert_eq!(keys.len(), 1);
+
+            let instance = loaded.render_instance(keys[0]).unwrap();
+            assert_eq!(instance.mesh(), Some(s.mesh_key));
+            assert_eq!(*instance.world_matrix(), world);
+            assert_eq!(instance.bounding_box().min, create_test_aabb().min);
+            assert_eq!(instance.bounding_box().max, create_test_aabb().max);
+            assert_eq!(instance.vertex_shader(), s.vertex_shader_key);
+            assert_eq!(instance.flags(), FLAG_CAST_SHADOW);
+            assert_eq!(instance.lod_override(), Some(2));
+            assert_eq!(instance.color_tint(), glam::Vec4::new(1.0, 0.5, 0.5, 1.0));
+            assert_eq!(instance.morph_weights()[0], 0.25);
+            assert!(loaded.has_new_instance(keys[0]));
+        }
+    }
+
+    #[test]
+    fn test_deserialize_unknown_mesh_creates_nothing() {
+        let s = setup_resources();
+        let mut scene = Scene::new();
+        scene.create_render_instance(s.mesh_key, Mat4::IDENTITY, create_test_aabb(), s.vertex_shader_key, &[], &s.rm).unwrap();
+        let mut file = scene.to_scene_file(&s.rm).unwrap();
+        let mut missing = file.instances[0].clone();
+        missing.mesh = "missing".to_string();
+        file.instances.push(missing);
+
+        let mut loaded = Scene::new();
+        assert!(loaded.load_scene_file(&file, &s.rm).is_err());
+        loaded.removed_instances();
+        assert_eq!(loaded.render_instance_count(), 0);
+    }
+}
@@ -0,0 +1,141 @@
+/// Skybox drawing.
+///
+/// `SkyboxDrawer` is a `Drawer` that fills the background with a cube map.
+/// It draws one fullscreen triangle at the far plane, so it plugs into a
+/// `ScenePassAction` like the `ForwardDrawer` and ignores the visible
+/// submeshes of the view.
+///
+/// The pipeline is supplied by the application (no vertex input, triangle
+/// list). Its shaders follow this contract:
+///
+/// ```glsl
+/// layout(push_constant) uniform Skybox {
+///     mat4 inverseViewProjection;  // rotation-only view, jittered projection
+///     uint cubeIndex;              // bindless index, set 0 binding 2
+///     float intensity;
+/// };
+/// // vertex: fullscreen triangle from gl_VertexIndex, z at the far plane
+/// vec2 ndc = vec2((gl_VertexIndex << 1) & 2, gl_VertexIndex & 2) * 2.0 - 1.0;
+/// gl_Position = vec4(ndc, 1.0, 1.0);
+/// direction = (inverseViewProjection * vec4(ndc, 1.0, 1.0)).xyz;
+/// // fragment
+/// color = texture(cubemaps[cubeIndex], direction) * intensity;
+/// ```
+///
+/// The depth test is `LessOrEqual` without depth writes: the sky can run
+/// first on a cleared depth buffer, or after the opaque geometry where it
+/// only fills the empty pixels (cheaper).
+
+use std::sync::Arc;
+use glam::Vec4;
+use crate::error::Result;
+use crate::engine::Engine;
+use crate::engine_bail;
+use crate::graphics_device::{
+    self, BindingGroup, CommandList, CompareOp, CullMode, DynamicRenderState,
+    ShaderStageFlags, TextureType,
+};
+use crate::resource::resource_manager::{PassInfo, TextureKey};
+use super::drawer::Drawer;
+use super::render_view::RenderView;
+use super::scene::Scene;
+
+/// Vertices of the fullscreen triangle
+const SKYBOX_VERTEX_COUNT: u32 = 3;
+
+/// Default multiplier of the cube map color
+pub const DEFAULT_SKYBOX_INTENSITY: f32 = 1.0;
+
+/// Draws a cube map behind the scene (see module docs).
+pub struct SkyboxDrawer {
+    pipeline: Arc<dyn graphics_device::Pipeline>,
+    cubemap: TextureKey,
+    /// Multiplier of the cube map color (exposure of HDR skies)
+    pub intensity: f32,
+    /// Reused push constant block
+    push_constants: Vec<u8>,
+}
+
+impl SkyboxDrawer {
+    /// Create a skybox drawing `cubemap` (a `TextureType::Cube` texture)
+    /// with `pipeline`.
+    pub fn new(pipeline: Arc<dyn graphics_device::Pipeline>, cubemap: TextureKey) -> Self {
+        Self {
+            pipeline,
+            cubemap,
+            intensity: DEFAULT_SKYBOX_INTENSITY,
+            push_constants: Vec::new(),
+        }
+    }
+
+    pub fn cubemap(&self) -> TextureKey {
+        self.cubemap
+    }
+
+    /// Switch to another cube map (time of day, level change)
+    pub fn set_cubemap(&mut self, cubemap: TextureKey) {
+        self.cubemap = cubemap;
+    }
+
+    /// Render state of the sky: no culling, depth tested at the far plane
+    /// but never written.
+    pub fn render_state() -> DynamicRenderState {
+        DynamicRenderState {
+            cull_mode: CullMode::None,
+            depth_test_enable: true,
+            depth_write_enable: false,
+            depth_compare_op: CompareOp::LessOrEqual,
+            ..Default::default()
+        }
+    }
+}
+
+impl Drawer for SkyboxDrawer {
+    fn draw(
+        &mut self,
+        _scene: &mut Scene,
+        view: &RenderView,
+        cmd: &mut dyn CommandList,
+        _pass_info: &PassInfo,
+        _binding_group: &Arc<dyn BindingGroup>,
+        _bind_textures: bool,
+    ) -> Result<()> {
+        let camera = view.camera();
+        let cube_index = {
+            let rm_arc = Engine::resource_manager()?;
+            let rm = rm_arc.lock().unwrap();
+            let Some(texture) = rm.texture(self.cubemap) else {
+                engine_bail!("galaxy3d::SkyboxDrawer", "Cube map texture not found");
+            };
+            let texture = texture.graphics_device_texture();
+            if texture.info().texture_type != TextureType::Cube {
+                engine_bail!("galaxy3d::SkyboxDrawer",
+                    "Cube map texture has type {:?}, expected Cube", texture.info().texture_type);
+            }
+            texture.bindless_index()
+        };
+
+        // Directions only: drop the camera translation from the view
+        let mut rotation = *camera.view_matrix();
+        rotation.w_axis = Vec4::W;
+        let inverse_view_projection = (camera.jittered_projection_matrix() * rotation).inverse();
+
+        self.push_constants.clear();
+        self.push_constants.extend_from_slice(bytemuck::cast_slice(&inverse_view_projection.to_cols_array()));
+        self.push_constants.extend_from_slice(&cube_index.to_ne_bytes());
+        self.push_constants.extend_from_slice(&self.intensity.to_ne_bytes());
+
+        cmd.set_viewport(*camera.viewport())?;
+        cmd.set_scissor(camera.effective_scissor())?;
+        cmd.bind_pipeline(&self.pipeline)?;
+        // The cube map is sampled through the bindless set, whatever the pass asks
+        cmd.bind_textures()?;
+        cmd.set_dynamic_state(&Self::render_state())?;
+        cmd.push_constants(ShaderStageFlags::VERTEX_FRAGMENT, 0, &self.push_constants)?;
+        cmd.draw(SKYBOX_VERTEX_COUNT, 0)
+    }
+}
+
+#[cfg(test)]
+#[path = "skybox_drawer_tests.rs"]
+mod tests;
@@ -0,0 +1,83 @@
+use super::*;
+use serial_test::serial;
+use crate::graphics_device::{SampleCount, TextureFormat, CUBE_FACE_COUNT};
+use crate::graphics_device::mock_graphics_device::{
+    MockBindingGroup, MockCommandList, MockPipeline,
+};
+use crate::render_graph::test_helpers::setup_engine;
+use crate::resource::texture::TextureDesc;
+use crate::scene::scene_test_helpers::create_test_camera;
+
+fn create_texture(name: &str, texture_type: TextureType, array_layers: u32) -> TextureKey {
+    let rm_arc = Engine::resource_manager().unwrap();
+    let mut rm = rm_arc.lock().unwrap();
+    rm.create_texture(name.to_string(), TextureDesc {
+        graphics_device: Engine::graphics_device("main").unwrap(),
+        texture: graphics_device::TextureDesc {
+            width: 64, height: 64, depth: 1,
+            format: TextureFormat::R8G8B8A8_UNORM,
+            usage: graphics_device::TextureUsage::Sampled,
+            texture_type,
+            sample_count: SampleCount::S1,
+            array_layers, data: None, mipmap: graphics_device::MipmapMode::None,
+            debug_name: None,
+        },
+        layers: vec![],
+    }).unwrap()
+}
+
+fn draw(drawer: &mut SkyboxDrawer) -> Result<Vec<String>> {
+    let mut scene = Scene::new();
+    let view = RenderView::new(create_test_camera(), 0);
+    let pass_info = PassInfo::new(vec![TextureFormat::R8G8B8A8_UNORM], None, SampleCount::S1);
+    let binding_group: Arc<dyn BindingGroup> =
+        Arc::new(MockBindingGroup::new("pass_bg".to_string(), 1));
+    let mut cmd = MockCommandList::new();
+    drawer.draw(&mut scene, &view, &mut cmd, &pass_info, &binding_group, false)?;
+    Ok(cmd.commands)
+}
+
+fn make_drawer(cubemap: TextureKey) -> SkyboxDrawer {
+    SkyboxDrawer::new(Arc::new(MockPipeline::new("skybox".to_string())), cubemap)
+}
+
+// ============================================================================
+// Drawing
+// ============================================================================
+
+#[test]
+#[serial]
+fn test_skybox_draws_fullscreen_triangle() {
+    setup_engine();
+    let cubemap = create_texture("sky", TextureType::Cube, CUBE_FACE_COUNT);
+    let mut drawer = make_drawer(cubemap);
+    assert_eq!(drawer.intensity, DEFAULT_SKYBOX_INTENSITY);
+
+    // Textures are bound even when the pass skips them
+    assert_eq!(draw(&mut drawer).unwrap(), vec![
+        "set_viewport", "set_scissor", "bind_pipeline", "bind_textures",
+        "set_dynamic_state", "push_constants", "draw",
+    ]);
+}
+
+#[test]
+#[serial]
+fn test_skybox_rejects_non_cube_texture() {
+    setup_engine();
+    // Six layers are not enough, the texture must be a cube
+    let array = create_texture("array", TextureType::Array2D, CUBE_FACE_COUNT);
+    let mut drawer = make_drawer(array);
+    assert!(draw(&mut drawer).is_err());
+
+    drawer.set_cubemap(create_texture("sky", TextureType::Cube, CUBE_FACE_COUNT));
+    assert!(draw(&mut drawer).is_ok());
+}
+
+#[test]
+fn test_skybox_render_state() {
+    let state = SkyboxDrawer::render_state();
+    assert_eq!(state.cull_mode, CullMode::None);
+    assert!(state.depth_test_enable);
+    assert!(!state.depth_write_enable);
+    assert_eq!(state.depth_compare_op, CompareOp::LessOrEqual);
+}
@@ -0,0 +1,238 @@
+/// Per-instance TRS transform components.
+///
+/// Instances driven by translation / rotation / scale instead of a raw
+/// Mat4 keep their components here, in dense parallel arrays (SoA) so
+/// interpolation, animation blending or network snapshots can walk one
+/// component over all instances. Setting a component only marks the
+/// instance; matrices are composed lazily, once per frame, by
+/// `Scene::compose_transform_components()` (called by `DefaultUpdater`).
+///
+/// For fixed-timestep simulations, the components of the previous step are
+/// kept alongside the current ones (`begin_step()`), and the matrix is
+/// composed from `lerp(previous, current, alpha)` (slerp for rotations),
+/// with `alpha` the fraction of the step elapsed at render time.
+
+use glam::{Mat4, Quat, Vec3};
+use rustc_hash::FxHashMap;
+use super::render_instance::RenderInstanceKey;
+
+/// Dense TRS storage with O(1) swap-remove.
+pub(crate) struct TransformComponents {
+    keys: Vec<RenderInstanceKey>,
+    translations: Vec<Vec3>,
+    rotations: Vec<Quat>,
+    scales: Vec<Vec3>,
+    /// Components at the start of the current simulation step
+    previous_translations: Vec<Vec3>,
+    previous_rotations: Vec<Quat>,
+    previous_scales: Vec<Vec3>,
+    /// Whether the entry changed since the last `compose_dirty()`
+    dirty: Vec<bool>,
+    /// Dense index of each key
+    index: FxHashMap<RenderInstanceKey, usize>,
+    /// Keys of the dirty entries (may contain removed keys)
+    dirty_keys: Vec<RenderInstanceKey>,
+    /// Interpolation factor between previous (0) and current (1) components
+    alpha: f32,
+}
+
+impl TransformComponents {
+    pub(crate) fn new() -> Self {
+        Self {
+            keys: Vec::new(),
+            translations: Vec::new(),
+            rotations: Vec::new(),
+            scales: Vec::new(),
+            previous_translations: Vec::new(),
+            previous_rotations: Vec::new(),
+            previous_scales: Vec::new(),
+            dirty: Vec::new(),
+            index: FxHashMap::default(),
+            dirty_keys: Vec::new(),
+            alpha: 1.0,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub(crate) fn contains(&self, key: RenderInstanceKey) -> bool {
+        self.index.contains_key(&key)
+    }
+
+    /// Components of an instance as (translation, rotation, scale)
+    pub(crate) fn get(&self, key: RenderInstanceKey) -> Option<(Vec3, Quat, Vec3)> {
+        self.index.get(&key).map(|&i| (self.translations[i], self.rotations[i], self.scales[i]))
+    }
+
+    /// Previous-step components of an instance as (translation, rotation,
+    /// scale)
+    pub(crate) fn get_previous(&self, key: RenderInstanceKey) -> Option<(Vec3, Quat, Vec3)> {
+        self.index.get(&key).map(|&i| {
+            (self.previous_translations[i], self.previous_rotations[i], self.previous_scales[i])
+        })
+    }
+
+    /// Set all components of an instance, adding it if needed. A new
+    /// entry starts with previous = current (nothing to interpolate from).
+    pub(crate) fn set(&mut self, key: RenderInstanceKey, translation: Vec3, rotation: Quat, scale: Vec3) {
+        let i = match self.index.get(&key) {
+            Some(&i) => i,
+            None => {
+                let i = self.keys.len();
+                self.keys.push(key);
+                self.translations.push(translation);
+                self.rotations.push(rotation);
+                self.scales.push(scale);
+                self.previous_translations.push(translation);
+                self.previous_rotations.push(rotation);
+                self.previous_scales.push(scale);
+                self.dirty.push(false);
+                self.index.insert(key, i);
+                i
+            }
+        };
+        self.translations[i] = translation;
+        self.rotations[i] = rotation;
+        self.scales[i] = scale;
+        self.mark_dirty(i);
+    }
+
+    /// Set the translation of an existing entry. Returns false if absent.
+    pub(crate) fn set_translation(&mut self, key: RenderInstanceKey, translation: Vec3) -> bool {
+        let Some(&i) = self.index.get(&key) else { return false };
+        self.translations[i] = translation;
+        self.mark_dirty(i);
+        true
+    }
+
+    /// Set the rotation of an existing entry. Returns false if absent.
+    pub(crate) fn set_rotation(&mut self, key: RenderInstanceKey, rotation: Quat) -> bool {
+        let Some(&i) = self.index.get(&key) else { return false };
+        self.rotations[i] = rotation;
+        self.mark_dirty(i);
+        true
+    }
+
+    /// Set the scale of an existing entry. Returns false if absent.
+    pub(crate) fn set_scale(&mut self, key: RenderInstanceKey, scale: Vec3) -> bool {
+        let Some(&i) = self.index.get(&key) else { return false };
+        self.scales[i] = scale;
+        self.mark_dirty(i);
+        true
+    }
+
+    /// Copy the current components of an entry into its previous ones, so
+    /// it is not interpolated across a discontinuity (teleport, respawn).
+    /// Returns false if absent.
+    pub(crate) fn snap(&mut self, key: RenderInstanceKey) -> bool {
+        let Some(&i) = self.index.get(&key) else { return false };
+        self.previous_translations[i] = self.translations[i];
+        self.previous_rotations[i] = self.rotations[i];
+        self.previous_scales[i] = self.scales[i];
+        self.mark_dirty(i);
+        true
+    }
+
+    /// Start a simulation step: the current components become the previous
+    /// ones. Interpolated entries are marked for recomposition.
+    pub(crate) fn begin_step(&mut self) {
+        self.mark_interpolated_dirty();
+        self.previous_translations.copy_from_slice(&self.translations);
+        self.previous_rotations.copy_from_slice(&self.rotations);
+        self.previous_scales.copy_from_slice(&self.scales);
+    }
+
+    pub(crate) fn alpha(&self) -> f32 {
+        self.alpha
+    }
+
+    /// Set the interpolation factor (clamped to [0, 1]). Entries whose
+    /// previous and current components differ are marked for recomposition.
+    pub(crate) fn set_alpha(&mut self, alpha: f32) {
+        let alpha = alpha.clamp(0.0, 1.0);
+        if alpha != self.alpha {
+            self.alpha = alpha;
+            self.mark_interpolated_dirty();
+        }
+    }
+
+    fn mark_interpolated_dirty(&mut self) {
+        for i in 0..self.keys.len() {
+            if self.previous_translations[i] != self.translations[i]
+                || self.previous_rotations[i] != self.rotations[i]
+                || self.previous_scales[i] != self.scales[i]
+            {
+                self.mark_dirty(i);
+            }
+        }
+    }
+
+    fn mark_dirty(&mut self, i: usize) {
+        if !self.dirty[i] {
+            self.dirty[i] = true;
+            self.dirty_keys.push(self.keys[i]);
+        }
+    }
+
+    /// Remove an entry (swap-remove). Returns false if absent.
+    pub(crate) fn remove(&mut self, key: RenderInstanceKey) -> bool {
+        let Some(i) = self.index.remove(&key) else { return false };
+        self.keys.swap_remove(i);
+        self.translations.swap_remove(i);
+        self.rotations.swap_remove(i);
+        self.scales.swap_remove(i);
+        self.previous_translations.swap_remove(i);
+        self.previous_rotations.swap_remove(i);
+        self.previous_scales.swap_remove(i);
+        self.dirty.swap_remove(i);
+        if let Some(&moved) = self.keys.get(i) {
+            self.index.insert(moved, i);
+        }
+        true
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.keys.clear();
+        self.translations.clear();
+        self.rotations.clear();
+        self.scales.clear();
+        self.previous_translations.clear();
+        self.previous_rotations.clear();
+        self.previous_scales.clear();
+        self.dirty.clear();
+        self.index.clear();
+        self.dirty_keys.clear();
+    }
+
+    /// Compose the (interpolated) matrix of every dirty entry and clear the
+    /// dirty state. Returns the number of matrices composed.
+    pub(crate) fn compose_dirty(&mut self, mut f: impl FnMut(RenderInstanceKey, Mat4)) -> usize {
+        let mut count = 0;
+        for key in self.dirty_keys.drain(..) {
+            // Removed (and possibly re-added) since it was marked
+            let Some(&i) = self.index.get(&key) else { continue };
+            if !self.dirty[i] {
+                continue;
+            }
+            self.dirty[i] = false;
+            let matrix = if self.alpha >= 1.0 {
+                Mat4::from_scale_rotation_translation(self.scales[i], self.rotations[i], self.translations[i])
+            } else {
+                Mat4::from_scale_rotation_translation(
+                    self.previous_scales[i].lerp(self.scales[i], self.alpha),
+                    self.previous_rotations[i].slerp(self.rotations[i], self.alpha),
+                    self.previous_translations[i].lerp(self.translations[i], self.alpha),
+                )
+            };
+            f(key, matrix);
+            count += 1;
+        }
+        count
+    }
+}
+
+#[cfg(test)]
+#[path = "transform_components_tests.rs"]
+mod tests;
@@ -0,0 +1,129 @@
+use super::*;
+use slotmap::SlotMap;
+
+fn keys(n: usize) -> Vec<RenderInstanceKey> {
+    let mut map: SlotMap<RenderInstanceKey, ()> = SlotMap::with_key();
+    (0..n).map(|_| map.insert(())).collect()
+}
+
+#[test]
+fn test_set_and_get() {
+    let k = keys(1);
+    let mut tc = TransformComponents::new();
+    assert!(tc.get(k[0]).is_none());
+    tc.set(k[0], Vec3::X, Quat::IDENTITY, Vec3::ONE);
+    assert_eq!(tc.get(k[0]), Some((Vec3::X, Quat::IDENTITY, Vec3::ONE)));
+    assert!(tc.set_scale(k[0], Vec3::splat(2.0)));
+    assert_eq!(tc.get(k[0]).unwrap().2, Vec3::splat(2.0));
+    assert_eq!(tc.len(), 1);
+}
+
+#[test]
+fn test_single_component_setters_need_entry() {
+    let k = keys(1);
+    let mut tc = TransformComponents::new();
+    assert!(!tc.set_translation(k[0], Vec3::X));
+    assert!(!tc.set_rotation(k[0], Quat::IDENTITY));
+    assert!(!tc.set_scale(k[0], Vec3::ONE));
+}
+
+#[test]
+fn test_compose_dirty_once_per_change() {
+    let k = keys(2);
+    let mut tc = TransformComponents::new();
+    tc.set(k[0], Vec3::new(1.0, 2.0, 3.0), Quat::IDENTITY, Vec3::ONE);
+    tc.set(k[1], Vec3::ZERO, Quat::IDENTITY, Vec3::ONE);
+    // Several changes to the same entry compose once
+    tc.set_translation(k[0], Vec3::new(4.0, 5.0, 6.0));
+    tc.set_scale(k[0], Vec3::splat(2.0));
+
+    let mut composed = Vec::new();
+    assert_eq!(tc.compose_dirty(|key, m| composed.push((key, m))), 2);
+    assert_eq!(composed[0].0, k[0]);
+    assert_eq!(composed[0].1, Mat4::from_scale_rotation_translation(
+        Vec3::splat(2.0), Quat::IDENTITY, Vec3::new(4.0, 5.0, 6.0)));
+    assert_eq!(tc.compose_dirty(|_, _| {}), 0);
+}
+
+#[test]
+fn test_remove_keeps_other_entries_addressable() {
+    let k = keys(3);
+    let mut tc = TransformComponents::new();
+    for (i, key) in k.iter().enumerate() {
+        tc.set(*key, Vec3::splat(i as f32), Quat::IDENTITY, Vec3::ONE);
+    }
+    assert!(tc.remove(k[0]));
+    assert!(!tc.remove(k[0]));
+    assert_eq!(tc.get(k[2]).unwrap().0, Vec3::splat(2.0));
+    assert_eq!(tc.get(k[1]).unwrap().0, Vec3::splat(1.0));
+
+    // The removed entry is skipped by the pending composition
+    let mut composed = Vec::new();
+    tc.compose_dirty(|key, _| composed.push(key));
+    assert_eq!(composed, vec![k[1], k[2]]);
+}
+
+// ============================================================================
+// Interpolation
+// ============================================================================
+
+#[test]
+fn test_new_entry_has_no_previous_motion() {
+    let k = keys(1);
+    let mut tc = TransformComponents::new();
+    tc.set(k[0], Vec3::X, Quat::IDENTITY, Vec3::ONE);
+    assert_eq!(tc.get_previous(k[0]), tc.get(k[0]));
+}
+
+#[test]
+fn test_alpha_interpolates_between_steps() {
+    let k = keys(2);
+    let mut tc = TransformComponents::new();
+    tc.set(k[0], Vec3::ZERO, Quat::IDENTITY, Vec3::ONE);
+    tc.set(k[1], Vec3::ZERO, Quat::IDENTITY, Vec3::ONE);
+    tc.compose_dirty(|_, _| {});
+
+    tc.begin_step();
+    tc.set_translation(k[0], Vec3::new(10.0, 0.0, 0.0));
+    tc.set_rotation(k[0], Quat::from_rotation_y(std::f32::consts::FRAC_PI_2));
+    tc.set_alpha(0.25);
+
+    // Only the moving entry is recomposed
+    let mut composed = Vec::new();
+    tc.compose_dirty(|key, m| composed.push((key, m)));
+    assert_eq!(composed.len(), 1);
+    let expected = Mat4::from_rotation_translation(
+        Quat::from_rotation_y(std::f32::consts::FRAC_PI_8), Vec3::new(2.5, 0.0, 0.0));
+    assert!(composed[0].1.abs_diff_eq(expected, 1e-5), "{:?}", composed[0].1);
+
+    // Every alpha change recomposes it
+    tc.set_alpha(0.5);
+    assert_eq!(tc.compose_dirty(|_, _| {}), 1);
+    tc.set_alpha(0.5);
+    assert_eq!(tc.compose_dirty(|_, _| {}), 0);
+}
+
+#[test]
+fn test_begin_step_settles_and_snap_skips_interpolation() {
+    let k = keys(1);
+    let mut tc = TransformComponents::new();
+    tc.set(k[0], Vec3::ZERO, Quat::IDENTITY, Vec3::ONE);
+    tc.begin_step();
+    tc.set_translation(k[0], Vec3::new(4.0, 0.0, 0.0));
+    tc.set_alpha(0.5);
+    tc.compose_dirty(|_, _| {});
+
+    // Next step without movement: recomposed once at the current position
+    tc.begin_step();
+    let mut composed = Vec::new();
+    tc.compose_dirty(|_, m| composed.push(m));
+    assert_eq!(composed, vec![Mat4::from_translation(Vec3::new(4.0, 0.0, 0.0))]);
+
+    // Teleport: snapped, drawn at the new position whatever alpha
+    tc.set_translation(k[0], Vec3::new(100.0, 0.0, 0.0));
+    assert!(tc.snap(k[0]));
+    tc.set_alpha(0.1);
+    let mut composed = Vec::new();
+    tc.compose_dirty(|_, m| composed.push(m));
+    assert_eq!(composed, vec![Mat4::from_translation(Vec3::new(100.0, 0.0, 0.0))]);
+}
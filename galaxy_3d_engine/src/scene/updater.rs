@@ -1,16 +1,19 @@
 /// Update strategies.
 ///
 /// An Updater synchronizes scene data to GPU buffers each frame.
-/// Four phases: per-frame camera data, per-instance data, per-light data,
-/// and per-instance light assignment (post-culling).
+/// Four phases: per-frame camera and environment data, per-instance data,
+/// per-light data, and per-instance light assignment (post-culling).
 
-use glam::Vec3;
+use glam::{Mat4, Vec2, Vec3};
+use rustc_hash::FxHashSet;
 use crate::error::Result;
 use crate::camera::{Camera, VisibleInstances};
 use crate::resource::buffer::Buffer;
 use super::scene::Scene;
 use super::scene_index::SceneIndex;
-use super::render_instance::AABB;
+use super::aabb::AABB;
+use super::render_instance::{RenderInstance, RenderInstanceKey};
+use crate::resource::resource_manager::ResourceManager;
 use super::light::{LightType, LightKey};
 
 /// Strategy for synchronizing scene data to GPU buffers.
@@ -20,11 +23,12 @@ use super::light::{LightType, LightKey};
 ///
 /// `&mut self` allows stateful implementations to track dirty state.
 pub trait Updater: Send + Sync {
-    /// Update the per-frame uniform buffer from the camera state.
+    /// Update the per-frame uniform buffer from the camera state and the
+    /// scene environment.
     ///
-    /// Writes camera matrices (view, projection, view-projection)
-    /// and other per-frame data into `frame_buffer`.
-    fn update_frame(&mut self, camera: &Camera, frame_buffer: &Buffer) -> Result<()>;
+    /// Writes camera matrices (view, projection, view-projection),
+    /// ambient/fog settings and other per-frame data into `frame_buffer`.
+    fn update_frame(&mut self, scene: &Scene, camera: &Camera, frame_buffer: &Buffer) -> Result<()>;
 
     /// Update the per-instance storage buffer from dirty instances.
     ///
@@ -32,6 +36,8 @@ pub trait Updater: Send + Sync {
     /// - Removed: drains + deletes from Scene, then cleans up SceneIndex
     /// - New: writes all GPU fields + inserts into SceneIndex
     /// - Dirty: writes transform fields + updates SceneIndex
+    /// - Dirty morph weights: writes the morph weights field
+    /// - Dirty tints: writes the color and emissive tint fields
     fn update_instances(
         &mut self,
         scene: &mut Scene,
@@ -69,7 +75,7 @@ impl NoOpUpdater {
 }
 
 impl Updater for NoOpUpdater {
-    fn update_frame(&mut self, _camera: &Camera, _frame_buffer: &Buffer) -> Result<()> {
+    fn update_frame(&mut self, _scene: &Scene, _camera: &Camera, _frame_buffer: &Buffer) -> Result<()> {
         Ok(())
     }
 
@@ -93,17 +99,37 @@ impl Updater for NoOpUpdater {
 
 /// Default updater — synchronizes camera and instance data to GPU buffers.
 ///
-/// Writes per-frame camera data into the Scene's frame buffer,
+/// Writes per-frame camera and environment data into the Scene's frame buffer,
 /// and per-instance world matrices into the Scene's instance buffer.
 ///
 /// Assumes the Scene's frame buffer was created with
 /// `ResourceManager::create_default_frame_uniform_buffer()` whose layout is:
 ///   0: view (Mat4), 1: projection (Mat4), 2: viewProjection (Mat4), ...
+///   7: ambientColor (Vec4), 15: ambientIntensity (Float), 16-19: fog,
+///   20: previousViewProjection (Mat4), 21: jitter (Vec4)
 ///
 /// Assumes the Scene's instance buffer was created with
 /// `ResourceManager::create_default_instance_buffer()` whose layout is:
 ///   0: world (Mat4), 1: previousWorld (Mat4), 2: inverseWorld (Mat4),
-///   3: materialSlotId (UInt), 4: flags (UInt), 5: lightCount (UInt), ...
+///   3: materialSlotId (UInt), 4: flags (UInt), 5: lightCount (UInt), ...,
+///   9: morphWeights (Vec4), 10: colorTint (Vec4), 11: emissiveTint (Vec4)
+///
+/// # Transform validation
+///
+/// When enabled with `set_transform_validation(true)`, instances whose world
+/// matrix contains NaN/Inf or is not invertible are quarantined: their GPU
+/// fields are not written and they are kept out of the SceneIndex (so they
+/// are never culled in), and a warning lists the offending keys. A
+/// quarantined instance rejoins as soon as it receives a valid transform.
+///
+/// # Motion vectors
+///
+/// `previousViewProjection` is the unjittered view-projection of the
+/// previous `update_frame` call (the current one on the first call), and
+/// `jitter` holds the NDC jitter of the current frame in `.xy` and of the
+/// previous frame in `.zw`. `previousWorld` is the world matrix of the
+/// previous upload of a moved instance; the frame after it stops moving,
+/// it is set back to `world` (zero motion).
 pub struct DefaultUpdater {
     /// Pre-allocated buffer for the keys of currently enabled lights.
     /// Reused across frames via clear() + repush — zero allocation in steady
@@ -114,6 +140,27 @@ pub struct DefaultUpdater {
     /// instances within a frame via clear() + repush — zero allocation in
     /// steady state.
     candidates: Vec<(u32, f32)>,
+    /// Check world matrices before writing them (off by default)
+    validate_transforms: bool,
+    /// Instances currently held back because of an invalid transform
+    quarantined: FxHashSet<RenderInstanceKey>,
+    /// Keys quarantined during the current call, reported in one warning.
+    /// Reused across frames.
+    rejected_keys: Vec<RenderInstanceKey>,
+    /// Quarantined keys that received a valid transform this frame and are
+    /// re-processed as new instances. Reused across frames.
+    released_keys: Vec<RenderInstanceKey>,
+    /// Unjittered view-projection of the previous frame
+    previous_view_projection: Option<Mat4>,
+    /// NDC jitter of the previous frame
+    previous_jitter: Vec2,
+    /// Instances whose transform was uploaded this frame. Reused across frames.
+    moved_keys: Vec<RenderInstanceKey>,
+    /// Instances moved last frame, whose `previousWorld` goes back to
+    /// `world` unless they move again. Reused across frames.
+    settling_keys: Vec<RenderInstanceKey>,
+    /// New and released instances of this frame. Reused across frames.
+    added_keys: Vec<RenderInstanceKey>,
 }
 
 impl DefaultUpdater {
@@ -123,6 +170,17 @@ impl DefaultUpdater {
     const FRAME_FIELD_VIEW_PROJECTION: usize   = 2;
     const FRAME_FIELD_CAMERA_POSITION: usize   = 3;
     const FRAME_FIELD_CAMERA_DIRECTION: usize  = 4;
+    const FRAME_FIELD_AMBIENT_COLOR: usize     = 7;
+    const FRAME_FIELD_TIME: usize              = 8;
+    const FRAME_FIELD_DELTA_TIME: usize        = 9;
+    const FRAME_FIELD_FRAME_INDEX: usize       = 10;
+    const FRAME_FIELD_AMBIENT_INTENSITY: usize = 15;
+    const FRAME_FIELD_FOG_COLOR: usize         = 16;
+    const FRAME_FIELD_FOG_PARAMS: usize        = 17;
+    const FRAME_FIELD_FOG_BASE_HEIGHT: usize   = 18;
+    const FRAME_FIELD_FOG_MODE: usize          = 19;
+    const FRAME_FIELD_PREVIOUS_VIEW_PROJECTION: usize = 20;
+    const FRAME_FIELD_JITTER: usize            = 21;
 
     /// Field indices matching `create_default_instance_buffer()` layout
     const INSTANCE_FIELD_WORLD: usize            = 0;
@@ -133,6 +191,9 @@ impl DefaultUpdater {
     const INSTANCE_FIELD_LIGHT_COUNT: usize      = 5;
     const INSTANCE_FIELD_LIGHT_INDICES_0: usize = 7;
     const INSTANCE_FIELD_LIGHT_INDICES_1: usize = 8;
+    const INSTANCE_FIELD_MORPH_WEIGHTS: usize    = 9;
+    const INSTANCE_FIELD_COLOR_TINT: usize       = 10;
+    const INSTANCE_FIELD_EMISSIVE_TINT: usize    = 11;
 
     /// Maximum number of lights per instance (2 × UVec4 = 8 slots)
     const MAX_LIGHTS_PER_INSTANCE: usize = 8;
@@ -148,21 +209,117 @@ impl DefaultUpdater {
         Self {
             enabled_light_keys: Vec::new(),
             candidates: Vec::new(),
+            validate_transforms: false,
+            quarantined: FxHashSet::default(),
+            rejected_keys: Vec::new(),
+            released_keys: Vec::new(),
+            previous_view_projection: None,
+            previous_jitter: Vec2::ZERO,
+            moved_keys: Vec::new(),
+            settling_keys: Vec::new(),
+            added_keys: Vec::new(),
         }
     }
+
+    /// Enable or disable the transform validation pass (see type docs).
+    ///
+    /// Disabling it does not release instances already quarantined: they
+    /// rejoin on their next transform change.
+    pub fn set_transform_validation(&mut self, enabled: bool) {
+        self.validate_transforms = enabled;
+    }
+
+    /// Whether the transform validation pass is enabled
+    pub fn transform_validation(&self) -> bool {
+        self.validate_transforms
+    }
+
+    /// Instances currently quarantined because of an invalid transform
+    pub fn quarantined_instances(&self) -> &FxHashSet<RenderInstanceKey> {
+        &self.quarantined
+    }
+
+    /// Write every GPU field of a newly added (or released) instance.
+    fn write_new_instance(
+        instance: &RenderInstance,
+        inverse_world: &Mat4,
+        rm: &ResourceManager,
+        instance_buffer: &Buffer,
+    ) -> Result<()> {
+        let world = instance.world_matrix();
+        let flags = instance.flags() as u32;
+        let morph_weights = instance.morph_weights();
+        let color_tint = instance.color_tint();
+        let emissive_tint = instance.emissive_tint().extend(0.0);
+
+        for sm_idx in 0..instance.sub_mesh_count() {
+            let sub_mesh = instance.sub_mesh(sm_idx).unwrap();
+            let slot = sub_mesh.draw_slot();
+            // Look up the material slot id from the first pass of the
+            // submesh. When multiple passes reference different materials,
+            // the SSBO slot will need to change — for now V1 uses passes[0].
+            let material_slot_id = rm.material(
+                    sub_mesh.pass_by_index(0).unwrap().material()
+                )
+                .ok_or_else(|| crate::engine_not_found!("galaxy3d::DefaultUpdater",
+                    "Material", format!("{:?}", sub_mesh.pass_by_index(0).unwrap().material())))?
+                .slot_id();
+
+            instance_buffer.update_field(slot, Self::INSTANCE_FIELD_WORLD,
+                bytemuck::bytes_of(world))?;
+            instance_buffer.update_field(slot, Self::INSTANCE_FIELD_PREVIOUS_WORLD,
+                bytemuck::bytes_of(world))?;
+            instance_buffer.update_field(slot, Self::INSTANCE_FIELD_INVERSE_WORLD,
+                bytemuck::bytes_of(inverse_world))?;
+            instance_buffer.update_field(slot, Self::INSTANCE_FIELD_MATERIAL_SLOT_ID,
+                bytemuck::bytes_of(&material_slot_id))?;
+            instance_buffer.update_field(slot, Self::INSTANCE_FIELD_FLAGS,
+                bytemuck::bytes_of(&flags))?;
+            instance_buffer.update_field(slot, Self::INSTANCE_FIELD_MORPH_WEIGHTS,
+                bytemuck::bytes_of(morph_weights))?;
+            instance_buffer.update_field(slot, Self::INSTANCE_FIELD_COLOR_TINT,
+                bytemuck::bytes_of(&color_tint))?;
+            instance_buffer.update_field(slot, Self::INSTANCE_FIELD_EMISSIVE_TINT,
+                bytemuck::bytes_of(&emissive_tint))?;
+        }
+        Ok(())
+    }
+}
+
+/// Inverse of a world matrix, or None if the matrix contains NaN/Inf or is
+/// not invertible (the inverse would not be finite).
+fn checked_inverse(world: &Mat4) -> Option<Mat4> {
+    if !world.is_finite() || world.determinant() == 0.0 {
+        return None;
+    }
+    let inverse = world.inverse();
+    inverse.is_finite().then_some(inverse)
 }
 
 impl Updater for DefaultUpdater {
-    fn update_frame(&mut self, camera: &Camera, frame_buffer: &Buffer) -> Result<()> {
+    fn update_frame(&mut self, scene: &Scene, camera: &Camera, frame_buffer: &Buffer) -> Result<()> {
+        crate::profile_scope!("update_frame");
         let buf = frame_buffer;
         let view = camera.view_matrix();
-        let proj = camera.projection_matrix();
-        let view_proj = camera.view_projection_matrix();
+        // Jittered for TAA (identical to the plain matrices without jitter)
+        let proj = camera.jittered_projection_matrix();
+        let view_proj = camera.jittered_view_projection_matrix();
 
         buf.update_field(0, Self::FRAME_FIELD_VIEW,            bytemuck::bytes_of(view))?;
-        buf.update_field(0, Self::FRAME_FIELD_PROJECTION,      bytemuck::bytes_of(proj))?;
+        buf.update_field(0, Self::FRAME_FIELD_PROJECTION,      bytemuck::bytes_of(&proj))?;
         buf.update_field(0, Self::FRAME_FIELD_VIEW_PROJECTION, bytemuck::bytes_of(&view_proj))?;
 
+        // Motion vectors: previous unjittered matrix and both jitters
+        let unjittered_view_proj = camera.view_projection_matrix();
+        let previous_view_proj = self.previous_view_projection.unwrap_or(unjittered_view_proj);
+        let jitter = camera.jitter_ndc();
+        let jitters: [f32; 4] = [jitter.x, jitter.y, self.previous_jitter.x, self.previous_jitter.y];
+        buf.update_field(0, Self::FRAME_FIELD_PREVIOUS_VIEW_PROJECTION,
+            bytemuck::bytes_of(&previous_view_proj))?;
+        buf.update_field(0, Self::FRAME_FIELD_JITTER, bytemuck::bytes_of(&jitters))?;
+        self.previous_view_projection = Some(unjittered_view_proj);
+        self.previous_jitter = jitter;
+
         // Extract camera position & forward direction from view matrix inverse
         let inv_view = view.inverse();
         let pos = inv_view.col(3).truncate();
@@ -173,6 +330,26 @@ impl Updater for DefaultUpdater {
         let camera_dir: [f32; 4] = [fwd.x, fwd.y, fwd.z, 0.0];
         buf.update_field(0, Self::FRAME_FIELD_CAMERA_DIRECTION, bytemuck::bytes_of(&camera_dir))?;
 
+        // Scene environment (ambient + fog)
+        let env = scene.environment();
+        let ambient: [f32; 4] = [env.ambient_color.x, env.ambient_color.y, env.ambient_color.z, 1.0];
+        buf.update_field(0, Self::FRAME_FIELD_AMBIENT_COLOR, bytemuck::bytes_of(&ambient))?;
+        buf.update_field(0, Self::FRAME_FIELD_AMBIENT_INTENSITY, bytemuck::bytes_of(&env.ambient_intensity))?;
+
+        let fog = &env.fog;
+        let fog_color: [f32; 4] = [fog.color.x, fog.color.y, fog.color.z, 1.0];
+        let fog_params: [f32; 4] = [fog.start, fog.end, fog.density, fog.height_falloff];
+        buf.update_field(0, Self::FRAME_FIELD_FOG_COLOR, bytemuck::bytes_of(&fog_color))?;
+        buf.update_field(0, Self::FRAME_FIELD_FOG_PARAMS, bytemuck::bytes_of(&fog_params))?;
+        buf.update_field(0, Self::FRAME_FIELD_FOG_BASE_HEIGHT, bytemuck::bytes_of(&fog.base_height))?;
+        buf.update_field(0, Self::FRAME_FIELD_FOG_MODE, bytemuck::bytes_of(&(fog.mode as u32)))?;
+
+        // Simulation clock (not the wall clock: pause, step and time scale apply)
+        let clock = scene.clock();
+        buf.update_field(0, Self::FRAME_FIELD_TIME, bytemuck::bytes_of(&clock.time()))?;
+        buf.update_field(0, Self::FRAME_FIELD_DELTA_TIME, bytemuck::bytes_of(&clock.delta_time()))?;
+        buf.update_field(0, Self::FRAME_FIELD_FRAME_INDEX, bytemuck::bytes_of(&clock.frame_index()))?;
+
         Ok(())
     }
 
@@ -182,6 +359,11 @@ impl Updater for DefaultUpdater {
         mut scene_index: Option<&mut dyn SceneIndex>,
         instance_buffer: &Buffer,
     ) -> Result<()> {
+        crate::profile_scope!("update_instances");
+        // Lazy TRS composition: instances driven by transform components get
+        // their world matrix now and join the dirty transforms of Phase 2
+        scene.compose_transform_components();
+
         // Phase 0: removals — removed_instances() flips the SwapSet, frees draw
         // slots and removes from SlotMap, then we clean up the SceneIndex.
         {
@@ -191,7 +373,16 @@ impl Updater for DefaultUpdater {
                     idx.remove(*key);
                 }
             }
+            if !self.quarantined.is_empty() {
+                for key in removed_keys {
+                    self.quarantined.remove(key);
+                }
+            }
         }
+        self.rejected_keys.clear();
+        self.released_keys.clear();
+        self.moved_keys.clear();
+        self.added_keys.clear();
 
         // Phase 1: new instances — write ALL GPU fields + insert into SceneIndex.
         // Lock the ResourceManager once for the whole new-instances loop, only
@@ -208,33 +399,20 @@ impl Updater for DefaultUpdater {
                 };
 
                 let world = *instance.world_matrix();
-                let inverse_world = world.inverse();
-                let flags = instance.flags() as u32;
-
-                for sm_idx in 0..instance.sub_mesh_count() {
-                    let sub_mesh = instance.sub_mesh(sm_idx).unwrap();
-                    let slot = sub_mesh.draw_slot();
-                    // Look up the material slot id from the first pass of the
-                    // submesh. When multiple passes reference different materials,
-                    // the SSBO slot will need to change — for now V1 uses passes[0].
-                    let material_slot_id = rm.material(
-                            sub_mesh.pass_by_index(0).unwrap().material()
-                        )
-                        .ok_or_else(|| crate::engine_err!("galaxy3d::DefaultUpdater",
-                            "Material key not found in ResourceManager"))?
-                        .slot_id();
-
-                    instance_buffer.update_field(slot, Self::INSTANCE_FIELD_WORLD,
-                        bytemuck::bytes_of(&world))?;
-                    instance_buffer.update_field(slot, Self::INSTANCE_FIELD_PREVIOUS_WORLD,
-                        bytemuck::bytes_of(&world))?;
-                    instance_buffer.update_field(slot, Self::INSTANCE_FIELD_INVERSE_WORLD,
-                        bytemuck::bytes_of(&inverse_world))?;
-                    instance_buffer.update_field(slot, Self::INSTANCE_FIELD_MATERIAL_SLOT_ID,
-                        bytemuck::bytes_of(&material_slot_id))?;
-                    instance_buffer.update_field(slot, Self::INSTANCE_FIELD_FLAGS,
-                        bytemuck::bytes_of(&flags))?;
-                }
+                let inverse_world = if self.validate_transforms {
+                    match checked_inverse(&world) {
+                        Some(inverse) => inverse,
+                        None => {
+                            self.quarantined.insert(*key);
+                            self.rejected_keys.push(*key);
+                            continue;
+                        }
+                    }
+                } else {
+                    world.inverse()
+                };
+                Self::write_new_instance(instance, &inverse_world, &rm, instance_buffer)?;
+                self.added_keys.push(*key);
 
                 if let Some(ref mut idx) = scene_index {
                     let world_aabb = instance.bounding_box().transformed(&world);
@@ -244,6 +422,31 @@ impl Updater for DefaultUpdater {
             }
         }
 
+        // New instances start without motion, even if moved before their
+        // first upload (they are in the dirty transforms too)
+        for key in &self.added_keys {
+            if let Some(instance) = scene.render_instance_mut(*key) {
+                instance.commit_previous_world_matrix();
+            }
+        }
+
+        // Phase 2a: instances moved last frame stop moving — previousWorld
+        // goes back to world (overwritten below if they move again)
+        for key in &self.settling_keys {
+            let instance = match scene.render_instance(*key) {
+                Some(inst) => inst,
+                None => continue,
+            };
+            if self.quarantined.contains(key) {
+                continue;
+            }
+            for sm_idx in 0..instance.sub_mesh_count() {
+                let slot = instance.sub_mesh(sm_idx).unwrap().draw_slot();
+                instance_buffer.update_field(slot, Self::INSTANCE_FIELD_PREVIOUS_WORLD,
+                    bytemuck::bytes_of(instance.world_matrix()))?;
+            }
+        }
+
         // Phase 2: dirty instance transforms — write matrices + update SceneIndex
         let dirty_keys = scene.dirty_instance_transforms();
         for key in dirty_keys {
@@ -253,7 +456,28 @@ impl Updater for DefaultUpdater {
             };
 
             let world = *instance.world_matrix();
-            let inverse_world = world.inverse();
+            let inverse_world = if self.validate_transforms {
+                match checked_inverse(&world) {
+                    Some(inverse) => inverse,
+                    None => {
+                        if self.quarantined.insert(*key) {
+                            if let Some(ref mut idx) = scene_index {
+                                idx.remove(*key);
+                            }
+                            self.rejected_keys.push(*key);
+                        }
+                        continue;
+                    }
+                }
+            } else {
+                world.inverse()
+            };
+            // A quarantined instance never had its GPU fields written nor was
+            // indexed: handle it like a new instance below.
+            if self.quarantined.remove(key) {
+                self.released_keys.push(*key);
+                continue;
+            }
             for sm_idx in 0..instance.sub_mesh_count() {
                 let sub_mesh = instance.sub_mesh(sm_idx).unwrap();
                 let slot = sub_mesh.draw_slot();
@@ -262,10 +486,11 @@ impl Updater for DefaultUpdater {
                 buf.update_field(slot, Self::INSTANCE_FIELD_WORLD,
                     bytemuck::bytes_of(&world))?;
                 buf.update_field(slot, Self::INSTANCE_FIELD_PREVIOUS_WORLD,
-                    bytemuck::bytes_of(&world))?;
+                    bytemuck::bytes_of(instance.previous_world_matrix()))?;
                 buf.update_field(slot, Self::INSTANCE_FIELD_INVERSE_WORLD,
                     bytemuck::bytes_of(&inverse_world))?;
             }
+            self.moved_keys.push(*key);
 
             if let Some(ref mut idx) = scene_index {
                 let world_aabb = instance.bounding_box().transformed(&world);
@@ -274,10 +499,73 @@ impl Updater for DefaultUpdater {
             }
         }
 
+        // Phase 2b: released instances — full write + insert into SceneIndex
+        if !self.released_keys.is_empty() {
+            let rm_arc = crate::engine::Engine::resource_manager()?;
+            let rm = rm_arc.lock().unwrap();
+            for key in &self.released_keys {
+                let instance = scene.render_instance(*key).unwrap();
+                let world = *instance.world_matrix();
+                Self::write_new_instance(instance, &world.inverse(), &rm, instance_buffer)?;
+                self.added_keys.push(*key);
+                if let Some(ref mut idx) = scene_index {
+                    let world_aabb = instance.bounding_box().transformed(&world);
+                    idx.insert(*key, world.w_axis.truncate(), &world_aabb);
+                }
+            }
+        }
+
+        // The uploaded transforms become the previous ones of the next frame
+        for key in self.moved_keys.iter().chain(&self.added_keys) {
+            if let Some(instance) = scene.render_instance_mut(*key) {
+                instance.commit_previous_world_matrix();
+            }
+        }
+        std::mem::swap(&mut self.moved_keys, &mut self.settling_keys);
+
+        if !self.rejected_keys.is_empty() {
+            crate::engine_warn!("galaxy3d::DefaultUpdater",
+                "Quarantined {} instance(s) with a NaN/Inf or non-invertible world matrix: {:?}",
+                self.rejected_keys.len(), self.rejected_keys);
+        }
+
+        // Phase 3: dirty morph weights — write the weights of every submesh
+        let dirty_keys = scene.dirty_instance_morph_weights();
+        for key in dirty_keys {
+            let instance = match scene.render_instance(*key) {
+                Some(inst) => inst,
+                None => continue,
+            };
+            for sm_idx in 0..instance.sub_mesh_count() {
+                let slot = instance.sub_mesh(sm_idx).unwrap().draw_slot();
+                instance_buffer.update_field(slot, Self::INSTANCE_FIELD_MORPH_WEIGHTS,
+                    bytemuck::bytes_of(instance.morph_weights()))?;
+            }
+        }
+
+        // Phase 4: dirty tints — write the color and emissive tints of every submesh
+        let dirty_keys = scene.dirty_instance_tints();
+        for key in dirty_keys {
+            let instance = match scene.render_instance(*key) {
+                Some(inst) => inst,
+                None => continue,
+            };
+            let color_tint = instance.color_tint();
+            let emissive_tint = instance.emissive_tint().extend(0.0);
+            for sm_idx in 0..instance.sub_mesh_count() {
+                let slot = instance.sub_mesh(sm_idx).unwrap().draw_slot();
+                instance_buffer.update_field(slot, Self::INSTANCE_FIELD_COLOR_TINT,
+                    bytemuck::bytes_of(&color_tint))?;
+                instance_buffer.update_field(slot, Self::INSTANCE_FIELD_EMISSIVE_TINT,
+                    bytemuck::bytes_of(&emissive_tint))?;
+            }
+        }
+
         Ok(())
     }
 
     fn update_lights(&mut self, scene: &mut Scene, light_buffer: &Buffer) -> Result<()> {
+        crate::profile_scope!("update_lights");
         // Phase 0: removals — free light slots + remove from SlotMap
         let _ = scene.removed_lights();
 
@@ -463,6 +751,61 @@ impl Updater for DefaultUpdater {
     }
 }
 
+// ===== HIERARCHY UPDATER =====
+
+/// Updater for scenes driven by a SceneNode hierarchy.
+///
+/// Runs `Scene::update_node_transforms()` before delegating to a
+/// `DefaultUpdater`: node world transforms are recomputed, attached
+/// instances and lights are marked dirty, and the inner updater then
+/// writes them to the GPU buffers and moves them in the SceneIndex.
+pub struct HierarchyUpdater {
+    inner: DefaultUpdater,
+}
+
+impl Default for HierarchyUpdater {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HierarchyUpdater {
+    pub fn new() -> Self {
+        Self { inner: DefaultUpdater::new() }
+    }
+
+    /// The wrapped DefaultUpdater (e.g. to enable transform validation)
+    pub fn inner_mut(&mut self) -> &mut DefaultUpdater {
+        &mut self.inner
+    }
+}
+
+impl Updater for HierarchyUpdater {
+    fn update_frame(&mut self, scene: &Scene, camera: &Camera, frame_buffer: &Buffer) -> Result<()> {
+        self.inner.update_frame(scene, camera, frame_buffer)
+    }
+
+    fn update_instances(
+        &mut self,
+        scene: &mut Scene,
+        scene_index: Option<&mut dyn SceneIndex>,
+        instance_buffer: &Buffer,
+    ) -> Result<()> {
+        scene.update_node_transforms();
+        self.inner.update_instances(scene, scene_index, instance_buffer)
+    }
+
+    fn update_lights(&mut self, scene: &mut Scene, light_buffer: &Buffer) -> Result<()> {
+        // No-op when update_instances already propagated this frame
+        scene.update_node_transforms();
+        self.inner.update_lights(scene, light_buffer)
+    }
+
+    fn assign_lights(&mut self, scene: &Scene, visible: &VisibleInstances, instance_buffer: &Buffer) -> Result<()> {
+        self.inner.assign_lights(scene, visible, instance_buffer)
+    }
+}
+
 // ===== CONE-AABB INTERSECTION =====
 
 /// Test if a cone (defined by apex, direction, range, and tan of outer angle)
@@ -20,9 +20,10 @@ fn test_noop_updater_new() {
 fn test_noop_update_frame_returns_ok() {
     let mut setup = setup_resources();
     let buf = make_frame_buffer(&mut setup.rm);
+    let scene = Scene::new();
     let camera = create_test_camera();
     let mut updater = NoOpUpdater::new();
-    assert!(updater.update_frame(&camera, &buf).is_ok());
+    assert!(updater.update_frame(&scene, &camera, &buf).is_ok());
 }
 
 #[test]
@@ -66,20 +67,79 @@ fn test_default_updater_new() {
 fn test_default_update_frame_writes_camera_data() {
     let mut setup = setup_resources();
     let buf = make_frame_buffer(&mut setup.rm);
+    let scene = Scene::new();
     let camera = create_test_camera();
     let mut updater = DefaultUpdater::new();
-    assert!(updater.update_frame(&camera, &buf).is_ok());
+    assert!(updater.update_frame(&scene, &camera, &buf).is_ok());
 }
 
 #[test]
 fn test_default_update_frame_idempotent_on_repeated_call() {
     let mut setup = setup_resources();
     let buf = make_frame_buffer(&mut setup.rm);
+    let scene = Scene::new();
+    let camera = create_test_camera();
+    let mut updater = DefaultUpdater::new();
+    updater.update_frame(&scene, &camera, &buf).unwrap();
+    updater.update_frame(&scene, &camera, &buf).unwrap();
+    updater.update_frame(&scene, &camera, &buf).unwrap();
+}
+
+#[test]
+fn test_default_update_frame_writes_environment() {
+    use crate::scene::{Fog, FogMode};
+
+    let mut setup = setup_resources();
+    let buf = make_frame_buffer(&mut setup.rm);
+    // Field indices used by the updater must match the factory layout
+    assert_eq!(buf.field_id("ambientColor"), Some(DefaultUpdater::FRAME_FIELD_AMBIENT_COLOR));
+    assert_eq!(buf.field_id("ambientIntensity"), Some(DefaultUpdater::FRAME_FIELD_AMBIENT_INTENSITY));
+    assert_eq!(buf.field_id("fogColor"), Some(DefaultUpdater::FRAME_FIELD_FOG_COLOR));
+    assert_eq!(buf.field_id("fogParams"), Some(DefaultUpdater::FRAME_FIELD_FOG_PARAMS));
+    assert_eq!(buf.field_id("fogBaseHeight"), Some(DefaultUpdater::FRAME_FIELD_FOG_BASE_HEIGHT));
+    assert_eq!(buf.field_id("fogMode"), Some(DefaultUpdater::FRAME_FIELD_FOG_MODE));
+
+    let mut scene = Scene::new();
+    scene.set_ambient(Vec3::new(0.2, 0.3, 0.4), 0.5);
+    scene.set_fog(Fog { mode: FogMode::Exponential, density: 0.05, height_falloff: 0.1, ..Default::default() });
+    let camera = create_test_camera();
+    let mut updater = DefaultUpdater::new();
+    assert!(updater.update_frame(&scene, &camera, &buf).is_ok());
+}
+
+#[test]
+fn test_default_update_frame_writes_clock() {
+    let mut setup = setup_resources();
+    let buf = make_frame_buffer(&mut setup.rm);
+    assert_eq!(buf.field_id("time"), Some(DefaultUpdater::FRAME_FIELD_TIME));
+    assert_eq!(buf.field_id("deltaTime"), Some(DefaultUpdater::FRAME_FIELD_DELTA_TIME));
+    assert_eq!(buf.field_id("frameIndex"), Some(DefaultUpdater::FRAME_FIELD_FRAME_INDEX));
+
+    let mut scene = Scene::new();
+    scene.clock_mut().advance(0.1);
+    scene.clock_mut().pause();
+    scene.clock_mut().advance(0.1);
     let camera = create_test_camera();
     let mut updater = DefaultUpdater::new();
-    updater.update_frame(&camera, &buf).unwrap();
-    updater.update_frame(&camera, &buf).unwrap();
-    updater.update_frame(&camera, &buf).unwrap();
+    assert!(updater.update_frame(&scene, &camera, &buf).is_ok());
+}
+
+#[test]
+fn test_default_update_frame_tracks_previous_view_projection() {
+    let mut setup = setup_resources();
+    let buf = make_frame_buffer(&mut setup.rm);
+    assert_eq!(buf.field_id("previousViewProjection"),
+        Some(DefaultUpdater::FRAME_FIELD_PREVIOUS_VIEW_PROJECTION));
+    assert_eq!(buf.field_id("jitter"), Some(DefaultUpdater::FRAME_FIELD_JITTER));
+
+    let scene = Scene::new();
+    let mut camera = create_test_camera();
+    camera.set_jitter(glam::Vec2::new(0.25, -0.25));
+    let mut updater = DefaultUpdater::new();
+    updater.update_frame(&scene, &camera, &buf).unwrap();
+    // Unjittered, so motion vectors do not include the jitter
+    assert_eq!(updater.previous_view_projection, Some(camera.view_projection_matrix()));
+    assert_eq!(updater.previous_jitter, camera.jitter_ndc());
 }
 
 #[test]
@@ -306,7 +366,7 @@ mod engine_backed {
     use crate::resource::material::{MaterialDesc, MaterialPassDesc, ParamValue};
     use crate::resource::mesh::{MeshDesc, MeshSubMeshDesc, GeometryMeshRef, GeometrySubMeshRef};
     use crate::resource::shader::ShaderDesc;
-    use crate::scene::render_instance::AABB;
+    use crate::scene::aabb::AABB;
     use serial_test::serial;
     use glam::{Mat4, Vec3};
     use std::sync::Arc;
@@ -330,8 +390,8 @@ mod engine_backed {
         };
         let geo_key = rm.create_geometry("geo".to_string(), GeometryDesc {
             name: "geo".to_string(), graphics_device: gd_arc.clone(),
-            vertex_data: vec![0u8; 48], index_data: Some(vec![0u8; 12]),
-            vertex_layout: layout.clone(), index_type: IndexType::U16,
+            vertex_data: vec![0u8; 48].into(), index_data: Some(vec![0u8; 12].into()),
+            vertex_layout: layout.clone(), index_type: IndexType::U16, morph_targets: Vec::new(),
             meshes: vec![GeometryMeshDesc {
                 name: "cube".to_string(),
                 submeshes: vec![GeometrySubMeshDesc {
@@ -355,7 +415,7 @@ mod engine_backed {
             vertex_shader: vk, fragment_shader: fk,
             vertex_layout: layout, topology: PrimitiveTopology::TriangleList,
             rasterization: Default::default(), color_blend: Default::default(),
-            multisample: Default::default(), color_formats: vec![], depth_format: None,
+            multisample: Default::default(), color_formats: vec![], depth_format: None, engine_features: Default::default(),
         }, &mut *gd_arc.lock().unwrap()).unwrap();
         let mk = rm.create_material("m".to_string(), MaterialDesc {
             passes: vec![MaterialPassDesc {
@@ -363,6 +423,8 @@ mod engine_backed {
                 polygon_mode: PolygonMode::Fill, textures: vec![],
                 params: vec![("value".to_string(), ParamValue::Float(1.0))],
                 render_state: None,
+                engine_features: Default::default(),
+                render_queue: None,
             }],
         }, &*gd_arc.lock().unwrap()).unwrap();
         let mesh_key = rm.create_mesh("mesh".to_string(), MeshDesc {
@@ -419,6 +481,188 @@ mod engine_backed {
         assert!(updater.update_instances(&mut scene, None, &buf).is_ok());
     }
 
+    #[test]
+    #[serial]
+    fn test_default_update_instances_tracks_previous_world() {
+        let (buf, mesh_key, vk) = setup_engine();
+
+        let mut scene = Scene::new();
+        let key = {
+            let rm_arc = Engine::resource_manager().unwrap();
+            let rm = rm_arc.lock().unwrap();
+            scene.create_render_instance(mesh_key, Mat4::IDENTITY, make_aabb(), vk, &[], &rm).unwrap()
+        };
+        let moved = Mat4::from_translation(Vec3::new(5.0, 0.0, 0.0));
+        // Moved before its first upload: no motion from the creation matrix
+        scene.set_world_matrix(key, moved);
+
+        let mut updater = DefaultUpdater::new();
+        updater.update_instances(&mut scene, None, &buf).unwrap();
+        assert_eq!(*scene.render_instance(key).unwrap().previous_world_matrix(), moved);
+        updater.update_instances(&mut scene, None, &buf).unwrap();
+        assert!(updater.settling_keys.is_empty());
+
+        let moved_again = Mat4::from_translation(Vec3::new(6.0, 0.0, 0.0));
+        scene.set_world_matrix(key, moved_again);
+        updater.update_instances(&mut scene, None, &buf).unwrap();
+        assert_eq!(*scene.render_instance(key).unwrap().previous_world_matrix(), moved_again);
+        assert_eq!(updater.settling_keys, vec![key]);
+
+        // Still for one frame: previousWorld settles back to world
+        updater.update_instances(&mut scene, None, &buf).unwrap();
+        assert!(updater.settling_keys.is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn test_default_update_instances_dirty_tint_path() {
+        let (buf, mesh_key, vk) = setup_engine();
+
+        let mut scene = Scene::new();
+        let key = {
+            let rm_arc = Engine::resource_manager().unwrap();
+            let rm = rm_arc.lock().unwrap();
+            scene.create_render_instance(mesh_key, Mat4::IDENTITY, make_aabb(), vk, &[], &rm).unwrap()
+        };
+
+        let mut updater = DefaultUpdater::new();
+        updater.update_instances(&mut scene, None, &buf).unwrap();
+        scene.set_color_tint(key, glam::Vec4::new(1.0, 0.2, 0.2, 1.0));
+        scene.set_emissive_tint(key, Vec3::new(0.5, 0.0, 0.0));
+        assert!(updater.update_instances(&mut scene, None, &buf).is_ok());
+        // The tint set was drained by the update
+        assert!(scene.dirty_instance_tints().is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn test_default_update_instances_composes_transform_components() {
+        let (buf, mesh_key, vk) = setup_engine();
+
+        let mut scene = Scene::new();
+        let key = {
+            let rm_arc = Engine::resource_manager().unwrap();
+            let rm = rm_arc.lock().unwrap();
+            scene.create_render_instance(mesh_key, Mat4::IDENTITY, make_aabb(), vk, &[], &rm).unwrap()
+        };
+        let mut index = crate::scene::OctreeSceneIndex::new(
+            AABB { min: Vec3::splat(-100.0), max: Vec3::splat(100.0) }, 2);
+
+        let mut updater = DefaultUpdater::new();
+        updater.update_instances(&mut scene, Some(&mut index), &buf).unwrap();
+        assert_eq!(indexed_keys(&index), 1);
+
+        // Moved out of the NDC cube by its translation component
+        scene.set_translation(key, Vec3::new(50.0, 0.0, 0.0));
+        updater.update_instances(&mut scene, Some(&mut index), &buf).unwrap();
+        assert_eq!(*scene.render_instance(key).unwrap().world_matrix(),
+            Mat4::from_translation(Vec3::new(50.0, 0.0, 0.0)));
+        assert_eq!(indexed_keys(&index), 0);
+    }
+
+    fn indexed_keys(index: &crate::scene::OctreeSceneIndex) -> usize {
+        use crate::camera::Frustum;
+        use crate::scene::SceneIndex;
+        // Identity view-projection: the whole NDC cube around the origin
+        let mut results = Vec::new();
+        index.query_frustum(&Frustum::from_view_projection(&Mat4::IDENTITY),
+            Vec3::ZERO, Vec3::NEG_Z, &mut results);
+        results.len()
+    }
+
+    #[test]
+    #[serial]
+    fn test_transform_validation_quarantines_singular_new_instance() {
+        let (buf, mesh_key, vk) = setup_engine();
+        let mut scene = Scene::new();
+        let (good, bad) = {
+            let rm_arc = Engine::resource_manager().unwrap();
+            let rm = rm_arc.lock().unwrap();
+            (
+                scene.create_render_instance(mesh_key, Mat4::IDENTITY, make_aabb(), vk, &[], &rm).unwrap(),
+                scene.create_render_instance(mesh_key, Mat4::from_scale(Vec3::new(1.0, 0.0, 1.0)),
+                    make_aabb(), vk, &[], &rm).unwrap(),
+            )
+        };
+
+        let mut index = crate::scene::OctreeSceneIndex::new(
+            AABB { min: Vec3::splat(-10.0), max: Vec3::splat(10.0) }, 2);
+        let mut updater = DefaultUpdater::new();
+        assert!(!updater.transform_validation());
+        updater.set_transform_validation(true);
+        updater.update_instances(&mut scene, Some(&mut index), &buf).unwrap();
+
+        assert!(updater.quarantined_instances().contains(&bad));
+        assert!(!updater.quarantined_instances().contains(&good));
+        assert_eq!(indexed_keys(&index), 1);
+    }
+
+    #[test]
+    #[serial]
+    fn test_quarantined_instance_released_by_valid_transform() {
+        let (buf, mesh_key, vk) = setup_engine();
+        let mut scene = Scene::new();
+        let key = {
+            let rm_arc = Engine::resource_manager().unwrap();
+            let rm = rm_arc.lock().unwrap();
+            scene.create_render_instance(mesh_key, Mat4::IDENTITY, make_aabb(), vk, &[], &rm).unwrap()
+        };
+
+        let mut index = crate::scene::OctreeSceneIndex::new(
+            AABB { min: Vec3::splat(-10.0), max: Vec3::splat(10.0) }, 2);
+        let mut updater = DefaultUpdater::new();
+        updater.set_transform_validation(true);
+        updater.update_instances(&mut scene, Some(&mut index), &buf).unwrap();
+        assert_eq!(indexed_keys(&index), 1);
+
+        // Degenerate transform: pulled out of the index
+        scene.set_world_matrix(key, Mat4::ZERO);
+        updater.update_instances(&mut scene, Some(&mut index), &buf).unwrap();
+        assert!(updater.quarantined_instances().contains(&key));
+        assert_eq!(indexed_keys(&index), 0);
+
+        // Valid again: back in the index
+        scene.set_world_matrix(key, Mat4::from_translation(Vec3::new(0.5, 0.0, 0.0)));
+        updater.update_instances(&mut scene, Some(&mut index), &buf).unwrap();
+        assert!(updater.quarantined_instances().is_empty());
+        assert_eq!(indexed_keys(&index), 1);
+
+        // Removal also clears the quarantine
+        scene.set_world_matrix(key, Mat4::ZERO);
+        updater.update_instances(&mut scene, Some(&mut index), &buf).unwrap();
+        scene.remove_render_instance(key);
+        updater.update_instances(&mut scene, Some(&mut index), &buf).unwrap();
+        assert!(updater.quarantined_instances().is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn test_hierarchy_updater_moves_attached_instance_in_index() {
+        let (buf, mesh_key, vk) = setup_engine();
+        let mut scene = Scene::new();
+        let node = scene.create_node(None, Mat4::IDENTITY).unwrap();
+        let key = {
+            let rm_arc = Engine::resource_manager().unwrap();
+            let rm = rm_arc.lock().unwrap();
+            scene.create_render_instance(mesh_key, Mat4::IDENTITY, make_aabb(), vk, &[], &rm).unwrap()
+        };
+        scene.attach_render_instance(node, key);
+
+        let mut index = crate::scene::OctreeSceneIndex::new(
+            AABB { min: Vec3::splat(-100.0), max: Vec3::splat(100.0) }, 2);
+        let mut updater = HierarchyUpdater::new();
+        updater.update_instances(&mut scene, Some(&mut index), &buf).unwrap();
+        assert_eq!(indexed_keys(&index), 1);
+
+        // Move the node out of the identity frustum: the index follows
+        scene.set_node_transform(node, Mat4::from_translation(Vec3::new(50.0, 0.0, 0.0)));
+        updater.update_instances(&mut scene, Some(&mut index), &buf).unwrap();
+        assert!(!scene.is_node_dirty(node));
+        assert_eq!(*scene.render_instance(key).unwrap().world_matrix(),
+            Mat4::from_translation(Vec3::new(50.0, 0.0, 0.0)));
+        assert_eq!(indexed_keys(&index), 0);
+    }
+
     #[test]
     #[serial]
     fn test_default_update_instances_removed_path() {
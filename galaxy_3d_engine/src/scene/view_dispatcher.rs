@@ -12,11 +12,22 @@
 /// (instance, submesh, pass) tuple. Submeshes whose selected LOD has zero
 /// vertices and indices are silently dropped — this is the intended way to
 /// hide a submesh past a given distance.
+///
+/// `dispatch_with_lod()` applies a global `LodConfig` (distance metric, LOD
+/// bias, cross-fade); `dispatch()` uses the default one (camera screen size,
+/// no bias, no cross-fade). An instance with a `lod_override()` skips the
+/// selection and draws that LOD (clamped to the submesh's last one).
+///
+/// Cross-fade: while a pass fades between two LODs, two entries are pushed,
+/// the new LOD with `LodFade::In` and the previous one with `LodFade::Out`.
+/// An empty LOD is skipped as usual, so a submesh hidden past a distance
+/// dithers out instead of disappearing at once. The fade advances once per
+/// dispatch in which the pass is visible.
 
 use super::scene::Scene;
 use super::render_view::{RenderView, VisibleSubMesh};
-use super::lod::apply_hysteresis;
-use crate::camera::{VisibleInstances, project_sphere_diameter};
+use super::lod::{apply_hysteresis, LodConfig, LodFade, LodMetric};
+use crate::camera::{VisibleInstances, project_sphere_diameter, reference_sphere_diameter};
 use crate::resource::ResourceManager;
 
 /// Dispatches culled instances into per-pass RenderViews.
@@ -39,6 +50,17 @@ impl ViewDispatcher {
         scene: &mut Scene,
         rm: &ResourceManager,
         render_views: &mut [RenderView],
+    ) {
+        Self::dispatch_with_lod(visible, scene, rm, render_views, &LodConfig::default());
+    }
+
+    /// Same as `dispatch()`, selecting LODs with the given policy.
+    pub fn dispatch_with_lod(
+        visible: &VisibleInstances,
+        scene: &mut Scene,
+        rm: &ResourceManager,
+        render_views: &mut [RenderView],
+        lod: &LodConfig,
     ) {
         // Copy camera snapshot + clear all views
         let camera = visible.camera();
@@ -61,6 +83,7 @@ impl ViewDispatcher {
 
             let geom_key = instance.geometry();
             let mesh_id = instance.geometry_mesh_id();
+            let lod_override = instance.lod_override();
             let geo = match rm.geometry(geom_key) { Some(g) => g, None => continue };
             let geo_mesh = match geo.mesh(mesh_id) { Some(m) => m, None => continue };
 
@@ -83,7 +106,12 @@ impl ViewDispatcher {
                 // given submesh — compute it once here and reuse inside the
                 // view loop. When views gain per-view cameras, move this back
                 // inside the loop and cache by `camera_id` instead.
-                let screen_size = project_sphere_diameter(center, radius, camera);
+                let screen_size = lod.bias * match lod.metric {
+                    LodMetric::ScreenSize => project_sphere_diameter(center, radius, camera),
+                    LodMetric::Distance { pixels_per_unit } => {
+                        reference_sphere_diameter(center, radius, camera, pixels_per_unit)
+                    }
+                };
 
                 for view in render_views.iter_mut() {
                     let pt = view.pass_type();
@@ -97,25 +125,39 @@ impl ViewDispatcher {
                         Some(p) => p,
                         None => continue,
                     };
-                    let new_lod = apply_hysteresis(pass.current_lod(), screen_size, thresholds);
-                    pass.set_current_lod(new_lod);
+                    let new_lod = match lod_override {
+                        Some(forced) => forced.min(geo_sm.lod_count().saturating_sub(1) as u8),
+                        None => apply_hysteresis(pass.current_lod(), screen_size, thresholds),
+                    };
+                    pass.select_lod(new_lod, lod.cross_fade_frames);
 
-                    // Hide the submesh when its selected LOD has no geometry.
-                    let lod_data = match geo_sm.lod(new_lod as usize) {
-                        Some(l) => l,
-                        None => continue,
+                    let (fade_in, fade_out) = match pass.lod_cross_fade(lod.cross_fade_frames) {
+                        Some((from_lod, progress)) => {
+                            (LodFade::In(progress), Some((from_lod, LodFade::Out(progress))))
+                        }
+                        None => (LodFade::None, None),
                     };
-                    if lod_data.vertex_count() == 0 && lod_data.index_count() == 0 {
-                        continue;
-                    }
 
-                    view.push(VisibleSubMesh {
-                        key: vi.key,
-                        distance: vi.distance,
-                        submesh_index: sm_idx as u8,
-                        pass_index,
-                        lod_index: new_lod,
-                    });
+                    let draws = std::iter::once((new_lod, fade_in)).chain(fade_out);
+                    for (lod_index, lod_fade) in draws {
+                        // Hide the submesh when its selected LOD has no geometry.
+                        let lod_data = match geo_sm.lod(lod_index as usize) {
+                            Some(l) => l,
+                            None => continue,
+                        };
+                        if lod_data.vertex_count() == 0 && lod_data.index_count() == 0 {
+                            continue;
+                        }
+
+                        view.push(VisibleSubMesh {
+                            key: vi.key,
+                            distance: vi.distance,
+                            submesh_index: sm_idx as u8,
+                            pass_index,
+                            lod_index,
+                            lod_fade,
+                        });
+                    }
                 }
             }
         }
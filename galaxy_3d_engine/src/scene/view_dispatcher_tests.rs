@@ -1,7 +1,10 @@
 use super::*;
-use crate::camera::VisibleInstances;
+use crate::camera::{Camera, Frustum, VisibleInstances};
+use crate::graphics_device::Viewport;
 use crate::scene::{Scene, BruteForceCuller, CameraCuller, RenderView, VisibleSubMesh, RenderInstanceKey};
-use crate::scene::scene_test_helpers::{setup_resources, create_test_aabb, create_test_camera};
+use crate::scene::scene_test_helpers::{
+    setup_resources, setup_resources_with_lod_thresholds, create_test_aabb, create_test_camera, TestSetup,
+};
 use glam::{Mat4, Vec3};
 use slotmap::Key;
 
@@ -46,6 +49,7 @@ fn test_dispatch_clears_existing_view_items() {
         submesh_index: 0,
         pass_index: 0,
         lod_index: 0,
+        lod_fade: LodFade::None,
     });
     assert_eq!(view.len(), 1);
 
@@ -169,3 +173,121 @@ fn test_dispatch_with_translated_instance_emits_distance() {
     let item = views[0].items()[0];
     assert!(item.distance.is_finite());
 }
+
+// ============================================================================
+// LOD policy
+// ============================================================================
+
+/// One instance at view depth 10 (perspective camera, 60 degree FOV, 1080
+/// pixels high: ~324 px for the bounding sphere of the ±1 test AABB, radius
+/// sqrt(3)), with LOD0 -> LOD1 below 150 px and LOD1 -> LOD2 below 50 px
+struct LodFixture {
+    setup: TestSetup,
+    scene: Scene,
+    visible: VisibleInstances,
+}
+
+fn lod_fixture() -> LodFixture {
+    let setup = setup_resources_with_lod_thresholds(vec![(150.0, 160.0), (50.0, 60.0)]);
+    let mut scene = Scene::new();
+    scene.create_render_instance(
+        setup.mesh_key,
+        Mat4::from_translation(Vec3::new(0.0, 0.0, -10.0)),
+        create_test_aabb(),
+        setup.vertex_shader_key, &[], &setup.rm,
+    ).unwrap();
+
+    let projection = Mat4::perspective_rh(std::f32::consts::FRAC_PI_3, 16.0 / 9.0, 0.1, 100.0);
+    let viewport = Viewport { x: 0.0, y: 0.0, width: 1920.0, height: 1080.0, min_depth: 0.0, max_depth: 1.0 };
+    let camera = Camera::new(Mat4::IDENTITY, projection, Frustum::from_view_projection(&projection), viewport);
+    let mut visible = VisibleInstances::new_empty();
+    BruteForceCuller::new().cull_into(&scene, &camera, None, &mut visible);
+    LodFixture { setup, scene, visible }
+}
+
+/// `(lod_index, lod_fade)` of the items dispatched for one frame
+fn dispatch_frame(fixture: &mut LodFixture, lod: &LodConfig) -> Vec<(u8, LodFade)> {
+    let mut views = [RenderView::new(fixture.visible.camera().clone(), 0)];
+    ViewDispatcher::dispatch_with_lod(&fixture.visible, &mut fixture.scene, &fixture.setup.rm, &mut views, lod);
+    views[0].items().iter().map(|item| (item.lod_index, item.lod_fade)).collect()
+}
+
+/// LOD of the single submesh dispatched for the `lod_fixture` instance
+fn dispatched_lod(lod: &LodConfig) -> u8 {
+    dispatch_frame(&mut lod_fixture(), lod)[0].0
+}
+
+#[test]
+fn test_dispatch_default_lod_config_uses_screen_size() {
+    assert_eq!(dispatched_lod(&LodConfig::default()), 0);
+}
+
+#[test]
+fn test_dispatch_lod_bias_selects_coarser_lods() {
+    let lod = LodConfig { bias: 0.4, ..LodConfig::default() };
+    assert_eq!(dispatched_lod(&lod), 1);
+    let lod = LodConfig { bias: 0.1, ..LodConfig::default() };
+    assert_eq!(dispatched_lod(&lod), 2);
+}
+
+#[test]
+fn test_dispatch_distance_metric_ignores_camera_projection() {
+    // Reference focal length of 400 px: 2 * sqrt(3) * 400 / 10 = ~139 px -> LOD1
+    let lod = LodConfig {
+        metric: LodMetric::Distance { pixels_per_unit: 400.0 },
+        ..LodConfig::default()
+    };
+    assert_eq!(dispatched_lod(&lod), 1);
+}
+
+#[test]
+fn test_dispatch_lod_override_skips_selection() {
+    let mut fixture = lod_fixture();
+    let key = fixture.scene.render_instance_keys().next().unwrap();
+    assert!(fixture.scene.set_lod_override(key, Some(1)));
+    assert_eq!(dispatch_frame(&mut fixture, &LodConfig::default())[0].0, 1);
+
+    // Clamped to the last LOD, then back to the hysteresis
+    fixture.scene.set_lod_override(key, Some(9));
+    assert_eq!(dispatch_frame(&mut fixture, &LodConfig::default())[0].0, 2);
+    fixture.scene.set_lod_override(key, None);
+    assert_eq!(dispatch_frame(&mut fixture, &LodConfig::default())[0].0, 0);
+}
+
+#[test]
+fn test_dispatch_cross_fades_after_lod_switch() {
+    let mut fixture = lod_fixture();
+    let fine = LodConfig { cross_fade_frames: 2, ..LodConfig::default() };
+    let coarse = LodConfig { bias: 0.4, ..fine };
+
+    // First selection of the pass: no cross-fade
+    assert_eq!(dispatch_frame(&mut fixture, &coarse), vec![(1, LodFade::None)]);
+
+    // LOD1 -> LOD0: both drawn for two frames with the same progress
+    let third = LodFade::progress(1, 2);
+    let two_thirds = LodFade::progress(2, 2);
+    assert_eq!(dispatch_frame(&mut fixture, &fine), vec![(0, LodFade::In(third)), (1, LodFade::Out(third))]);
+    assert_eq!(dispatch_frame(&mut fixture, &fine), vec![(0, LodFade::In(two_thirds)), (1, LodFade::Out(two_thirds))]);
+    assert_eq!(dispatch_frame(&mut fixture, &fine), vec![(0, LodFade::None)]);
+}
+
+#[test]
+fn test_dispatch_switch_during_cross_fade_restarts_it() {
+    let mut fixture = lod_fixture();
+    let fine = LodConfig { cross_fade_frames: 3, ..LodConfig::default() };
+    dispatch_frame(&mut fixture, &fine);
+    dispatch_frame(&mut fixture, &LodConfig { bias: 0.4, ..fine });
+
+    // LOD0 -> LOD1 fading, then LOD1 -> LOD2: only the last two are drawn
+    let items = dispatch_frame(&mut fixture, &LodConfig { bias: 0.1, ..fine });
+    let first = LodFade::progress(1, 3);
+    assert_eq!(items, vec![(2, LodFade::In(first)), (1, LodFade::Out(first))]);
+}
+
+#[test]
+fn test_dispatch_without_cross_fade_pops() {
+    let mut fixture = lod_fixture();
+    dispatch_frame(&mut fixture, &LodConfig::default());
+    let items = dispatch_frame(&mut fixture, &LodConfig { bias: 0.4, ..LodConfig::default() });
+    assert_eq!(items, vec![(1, LodFade::None)]);
+}
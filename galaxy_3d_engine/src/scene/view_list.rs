@@ -0,0 +1,319 @@
+/// ViewList — the ordered cameras rendered in one frame.
+///
+/// A frame often draws the same scenes from several cameras: the main
+/// camera, a minimap, a mirror, shadow views. Each `SceneView` owns
+/// everything that differs between them:
+///
+/// - its camera and culler, and the `VisibleInstances` the culler fills
+///   (per-view culling results);
+/// - one `RenderView` per pass type, shared as
+///   `Arc<Mutex<Option<RenderView>>>` with the `ScenePassAction`s that draw
+///   it;
+/// - an optional frame uniform buffer, written from the view's camera by
+///   the view's own `Updater` (so motion-vector history stays per view).
+///
+/// Views are kept sorted by `order` (lower first, insertion order between
+/// equal orders). `SceneManager::submit_views()` takes the list and
+/// `SceneManager::prepare_views()` culls and dispatches every enabled view
+/// in that order. On the render-graph side,
+/// `RenderGraphManager::create_render_pass_instances()` creates one pass per
+/// view from a template, named with `instance_pass_name()`.
+///
+/// LOD hysteresis and cross-fade history live on the instance passes, so
+/// views drawing the same pass type of the same instance share them: a
+/// distant view (minimap) can hold back LOD changes of the main view by
+/// one hysteresis band. Give such views their own pass type, or force a
+/// LOD with `RenderInstance::set_lod_override()`.
+
+use std::sync::{Arc, Mutex};
+use crate::camera::{Camera, VisibleInstances};
+use crate::error::Result;
+use crate::engine_bail;
+use crate::resource::buffer::Buffer;
+use crate::resource::ResourceManager;
+use super::culler::{CameraCuller, FrustumCuller};
+use super::lod::LodConfig;
+use super::render_view::RenderView;
+use super::scene::Scene;
+use super::scene_index::SceneIndex;
+use super::updater::{DefaultUpdater, Updater};
+use super::view_dispatcher::ViewDispatcher;
+
+/// Name of the render pass instanced from `base` for the view `view`
+pub fn instance_pass_name(base: &str, view: &str) -> String {
+    format!("{}@{}", base, view)
+}
+
+/// One camera rendered this frame (see module docs)
+pub struct SceneView {
+    name: String,
+    /// Name of the scene in the `SceneManager`
+    scene: String,
+    camera: Camera,
+    order: i32,
+    enabled: bool,
+    lod: LodConfig,
+    culler: Box<dyn CameraCuller>,
+    scene_index: Option<Arc<Mutex<dyn SceneIndex>>>,
+    /// Culling result of the last `SceneManager::prepare_views()`
+    visible: VisibleInstances,
+    /// One shared RenderView per pass type, in `pass_types` order
+    render_views: Vec<(u8, Arc<Mutex<Option<RenderView>>>)>,
+    frame_buffer: Option<Arc<Buffer>>,
+    updater: Box<dyn Updater>,
+    /// Dispatch scratch, reused across frames
+    scratch: Vec<RenderView>,
+}
+
+impl SceneView {
+    /// Create an enabled view of `scene` drawing `pass_types`, with order
+    /// 0, a `FrustumCuller`, no scene index and no frame buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a pass type is repeated or is not below 64.
+    pub fn new(name: &str, scene: &str, camera: Camera, pass_types: &[u8]) -> Result<Self> {
+        for (i, &pass_type) in pass_types.iter().enumerate() {
+            if pass_type >= 64 {
+                engine_bail!("galaxy3d::SceneView",
+                    "View '{}': pass type {} must be below 64", name, pass_type);
+            }
+            if pass_types[..i].contains(&pass_type) {
+                engine_bail!("galaxy3d::SceneView",
+                    "View '{}': pass type {} is listed twice", name, pass_type);
+            }
+        }
+        let render_views = pass_types.iter()
+            .map(|&pass_type| {
+                let view = RenderView::new(camera.clone(), pass_type);
+                (pass_type, Arc::new(Mutex::new(Some(view))))
+            })
+            .collect();
+        Ok(Self {
+            name: name.to_string(),
+            scene: scene.to_string(),
+            camera,
+            order: 0,
+            enabled: true,
+            lod: LodConfig::default(),
+            culler: Box::new(FrustumCuller::new()),
+            scene_index: None,
+            visible: VisibleInstances::new_empty(),
+            render_views,
+            frame_buffer: None,
+            updater: Box::new(DefaultUpdater::new()),
+            scratch: Vec::with_capacity(pass_types.len()),
+        })
+    }
+
+    /// Same view with another order (lower renders first)
+    pub fn with_order(mut self, order: i32) -> Self {
+        self.order = order;
+        self
+    }
+
+    // ===== ACCESSORS =====
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Name of the rendered scene
+    pub fn scene(&self) -> &str {
+        &self.scene
+    }
+
+    pub fn camera(&self) -> &Camera {
+        &self.camera
+    }
+
+    pub fn order(&self) -> i32 {
+        self.order
+    }
+
+    /// Whether `SceneManager::prepare_views()` processes the view
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// LOD policy of the view's dispatch
+    pub fn lod_config(&self) -> &LodConfig {
+        &self.lod
+    }
+
+    /// Pass types drawn by the view
+    pub fn pass_types(&self) -> impl Iterator<Item = u8> + '_ {
+        self.render_views.iter().map(|(pass_type, _)| *pass_type)
+    }
+
+    /// Shared RenderView of a pass type, to build a `ScenePassAction`
+    pub fn render_view(&self, pass_type: u8) -> Option<Arc<Mutex<Option<RenderView>>>> {
+        self.render_views.iter()
+            .find(|(pt, _)| *pt == pass_type)
+            .map(|(_, view)| Arc::clone(view))
+    }
+
+    /// Instances culled in by the last `SceneManager::prepare_views()`
+    pub fn visible(&self) -> &VisibleInstances {
+        &self.visible
+    }
+
+    /// Frame uniform buffer written from the view's camera, if any
+    pub fn frame_buffer(&self) -> Option<&Arc<Buffer>> {
+        self.frame_buffer.as_ref()
+    }
+
+    // ===== MUTATION =====
+
+    /// Replace the camera used by the next cull
+    pub fn set_camera(&mut self, camera: Camera) {
+        self.camera = camera;
+    }
+
+    /// Enable or skip the view without removing it (its last results stay)
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn set_lod_config(&mut self, lod: LodConfig) {
+        self.lod = lod;
+    }
+
+    /// Replace the culler (e.g. an `OcclusionCuller` fed with this view's
+    /// depth pyramid)
+    pub fn set_culler(&mut self, culler: Box<dyn CameraCuller>) {
+        self.culler = culler;
+    }
+
+    /// Spatial index queried by the culler (may be shared between views)
+    pub fn set_scene_index(&mut self, scene_index: Option<Arc<Mutex<dyn SceneIndex>>>) {
+        self.scene_index = scene_index;
+    }
+
+    /// Set or remove the frame uniform buffer (layout of
+    /// `ResourceManager::create_default_frame_uniform_buffer()` for the
+    /// `DefaultUpdater`)
+    pub fn set_frame_buffer(&mut self, frame_buffer: Option<Arc<Buffer>>) {
+        self.frame_buffer = frame_buffer;
+    }
+
+    /// Replace the updater writing the frame buffer
+    pub fn set_updater(&mut self, updater: Box<dyn Updater>) {
+        self.updater = updater;
+    }
+
+    // ===== FRAME (used by SceneManager) =====
+
+    /// Cull `scene` with the view's camera, dispatch into its RenderViews
+    /// and write its frame buffer.
+    pub(crate) fn prepare(
+        &mut self,
+        scene: &mut Scene,
+        rm: &ResourceManager,
+    ) -> Result<()> {
+        {
+            let guard = self.scene_index.as_ref().map(|index| index.lock().unwrap());
+            self.culler.cull_into(scene, &self.camera, guard.as_deref(), &mut self.visible);
+        }
+
+        // Move the shared RenderViews into a contiguous slice for the
+        // dispatcher, then hand them back.
+        self.scratch.clear();
+        for (pass_type, shared) in &self.render_views {
+            let view = shared.lock().unwrap().take()
+                .unwrap_or_else(|| RenderView::new(self.camera.clone(), *pass_type));
+            self.scratch.push(view);
+        }
+        ViewDispatcher::dispatch_with_lod(
+            &self.visible, scene, rm, &mut self.scratch, &self.lod,
+        );
+        for ((_, shared), view) in self.render_views.iter().zip(self.scratch.drain(..)) {
+            *shared.lock().unwrap() = Some(view);
+        }
+
+        if let Some(frame_buffer) = &self.frame_buffer {
+            self.updater.update_frame(scene, &self.camera, frame_buffer)?;
+        }
+        Ok(())
+    }
+}
+
+/// Ordered list of the views of a frame (see module docs)
+#[derive(Default)]
+pub struct ViewList {
+    views: Vec<SceneView>,
+}
+
+impl ViewList {
+    pub fn new() -> Self {
+        Self { views: Vec::new() }
+    }
+
+    /// Insert a view after every view of lower or equal order
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a view with the same name already exists.
+    pub fn add(&mut self, view: SceneView) -> Result<()> {
+        if self.views.iter().any(|v| v.name == view.name) {
+            engine_bail!("galaxy3d::ViewList",
+                "View '{}' already exists", view.name);
+        }
+        let position = self.views.partition_point(|v| v.order <= view.order);
+        self.views.insert(position, view);
+        Ok(())
+    }
+
+    /// Remove a view by name
+    pub fn remove(&mut self, name: &str) -> Option<SceneView> {
+        let position = self.views.iter().position(|v| v.name == name)?;
+        Some(self.views.remove(position))
+    }
+
+    /// Move a view to another order (behind the views of that order).
+    /// Returns false if the view does not exist.
+    pub fn set_order(&mut self, name: &str, order: i32) -> bool {
+        let Some(mut view) = self.remove(name) else {
+            return false;
+        };
+        view.order = order;
+        let position = self.views.partition_point(|v| v.order <= order);
+        self.views.insert(position, view);
+        true
+    }
+
+    pub fn view(&self, name: &str) -> Option<&SceneView> {
+        self.views.iter().find(|v| v.name == name)
+    }
+
+    pub fn view_mut(&mut self, name: &str) -> Option<&mut SceneView> {
+        self.views.iter_mut().find(|v| v.name == name)
+    }
+
+    /// Views in render order
+    pub fn iter(&self) -> std::slice::Iter<'_, SceneView> {
+        self.views.iter()
+    }
+
+    /// Views in render order (mutable)
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, SceneView> {
+        self.views.iter_mut()
+    }
+
+    /// View names in render order
+    pub fn names(&self) -> Vec<&str> {
+        self.views.iter().map(|v| v.name.as_str()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.views.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.views.is_empty()
+    }
+}
+
+#[cfg(test)]
+#[path = "view_list_tests.rs"]
+mod tests;
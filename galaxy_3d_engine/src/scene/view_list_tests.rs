@@ -0,0 +1,112 @@
+/// Unit tests for view_list.rs
+
+use super::*;
+use glam::{Mat4, Vec3};
+use crate::camera::Frustum;
+use crate::graphics_device::Viewport;
+use crate::scene::scene_test_helpers::{
+    setup_resources, create_test_aabb, create_test_camera, make_frame_buffer,
+};
+
+/// Camera looking at the test scene from `offset` (identity projection)
+fn shifted_camera(offset: Vec3) -> Camera {
+    let view = Mat4::from_translation(-offset);
+    let viewport = Viewport { x: 0.0, y: 0.0, width: 256.0, height: 256.0, min_depth: 0.0, max_depth: 1.0 };
+    Camera::new(view, Mat4::IDENTITY, Frustum::from_view_projection(&view), viewport)
+}
+
+fn view(name: &str, order: i32) -> SceneView {
+    SceneView::new(name, "world", create_test_camera(), &[0]).unwrap().with_order(order)
+}
+
+// ============================================================================
+// List
+// ============================================================================
+
+#[test]
+fn test_views_sorted_by_order_then_insertion() {
+    let mut views = ViewList::new();
+    views.add(view("main", 0)).unwrap();
+    views.add(view("shadow", -10)).unwrap();
+    views.add(view("mirror", 0)).unwrap();
+    views.add(view("minimap", 5)).unwrap();
+    assert_eq!(views.names(), ["shadow", "main", "mirror", "minimap"]);
+
+    assert!(views.set_order("main", 1));
+    assert_eq!(views.names(), ["shadow", "mirror", "main", "minimap"]);
+    assert_eq!(views.view("main").unwrap().order(), 1);
+    assert!(!views.set_order("missing", 1));
+
+    assert_eq!(views.remove("mirror").unwrap().name(), "mirror");
+    assert!(views.remove("mirror").is_none());
+    assert_eq!(views.len(), 3);
+}
+
+#[test]
+fn test_add_rejects_duplicate_names() {
+    let mut views = ViewList::new();
+    views.add(view("main", 0)).unwrap();
+    assert!(views.add(view("main", 3)).is_err());
+    assert_eq!(views.len(), 1);
+}
+
+#[test]
+fn test_new_validates_pass_types() {
+    let camera = create_test_camera();
+    assert!(SceneView::new("v", "world", camera.clone(), &[0, 0]).is_err());
+    assert!(SceneView::new("v", "world", camera.clone(), &[64]).is_err());
+
+    let view = SceneView::new("v", "world", camera, &[3, 1]).unwrap();
+    assert_eq!(view.pass_types().collect::<Vec<_>>(), [3, 1]);
+    assert_eq!(view.render_view(1).unwrap().lock().unwrap().as_ref().unwrap().pass_type(), 1);
+    assert!(view.render_view(0).is_none());
+}
+
+// ============================================================================
+// Prepare
+// ============================================================================
+
+#[test]
+fn test_prepare_culls_each_view_with_its_camera() {
+    let mut setup = setup_resources();
+    let mut scene = Scene::new();
+    let key = scene.create_render_instance(
+        setup.mesh_key, Mat4::IDENTITY, create_test_aabb(),
+        setup.vertex_shader_key, &[], &setup.rm,
+    ).unwrap();
+
+    let mut main = SceneView::new("main", "world", shifted_camera(Vec3::ZERO), &[0]).unwrap();
+    main.set_frame_buffer(Some(make_frame_buffer(&mut setup.rm)));
+    let mut away = SceneView::new("away", "world", shifted_camera(Vec3::new(100.0, 0.0, 0.0)), &[0]).unwrap();
+
+    main.prepare(&mut scene, &setup.rm).unwrap();
+    away.prepare(&mut scene, &setup.rm).unwrap();
+
+    assert_eq!(main.visible().visible_count(), 1);
+    assert_eq!(main.visible().instances()[0].key, key);
+    assert!(away.visible().is_empty());
+
+    let shared = main.render_view(0).unwrap();
+    let render_view = shared.lock().unwrap();
+    let render_view = render_view.as_ref().unwrap();
+    assert_eq!(render_view.len(), 1);
+    assert_eq!(render_view.camera().viewport().width, 256.0);
+    assert!(away.render_view(0).unwrap().lock().unwrap().as_ref().unwrap().is_empty());
+}
+
+#[test]
+fn test_prepare_refills_a_taken_render_view() {
+    let setup = setup_resources();
+    let mut scene = Scene::new();
+    scene.create_render_instance(
+        setup.mesh_key, Mat4::IDENTITY, create_test_aabb(),
+        setup.vertex_shader_key, &[], &setup.rm,
+    ).unwrap();
+
+    let mut view = SceneView::new("main", "world", create_test_camera(), &[0]).unwrap();
+    let shared = view.render_view(0).unwrap();
+    assert!(shared.lock().unwrap().take().is_some());
+
+    view.prepare(&mut scene, &setup.rm).unwrap();
+    assert_eq!(shared.lock().unwrap().as_ref().unwrap().len(), 1);
+}
@@ -0,0 +1,123 @@
+/// Heightmap — a grid of height samples in terrain local space.
+///
+/// Sample `(x, z)` sits at `(x * cell_size, height * height_scale,
+/// z * cell_size)`: the grid lies on the local XZ plane with Y up (convert
+/// with `CoordinateSystem::conversion_to()` for other world conventions).
+/// Heights are stored normalized, usually in 0..1 for imported maps.
+
+use glam::Vec3;
+use crate::error::Result;
+use crate::engine_bail;
+
+/// Height samples of a terrain, row-major (`z * width + x`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Heightmap {
+    width: u32,
+    depth: u32,
+    heights: Vec<f32>,
+}
+
+impl Heightmap {
+    /// Create a heightmap of `width` x `depth` samples.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a side has fewer than 2 samples, if `heights`
+    /// does not hold `width * depth` values or if a height is not finite.
+    pub fn new(width: u32, depth: u32, heights: Vec<f32>) -> Result<Self> {
+        if width < 2 || depth < 2 {
+            engine_bail!("galaxy3d::Heightmap",
+                "Heightmap needs at least 2x2 samples, got {}x{}", width, depth);
+        }
+        let expected = width as usize * depth as usize;
+        if heights.len() != expected {
+            engine_bail!("galaxy3d::Heightmap",
+                "{}x{} heightmap needs {} heights, got {}", width, depth, expected, heights.len());
+        }
+        if let Some(index) = heights.iter().position(|h| !h.is_finite()) {
+            engine_bail!("galaxy3d::Heightmap",
+                "Height {} at ({}, {}) is not finite",
+                heights[index], index % width as usize, index / width as usize);
+        }
+        Ok(Self { width, depth, heights })
+    }
+
+    /// Heightmap from 16-bit little-endian samples (RAW/R16 exports),
+    /// normalized to 0..1.
+    pub fn from_r16(width: u32, depth: u32, bytes: &[u8]) -> Result<Self> {
+        if !bytes.len().is_multiple_of(2) {
+            engine_bail!("galaxy3d::Heightmap",
+                "R16 data must have an even length, got {} bytes", bytes.len());
+        }
+        let heights = bytes.chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]) as f32 / u16::MAX as f32)
+            .collect();
+        Self::new(width, depth, heights)
+    }
+
+    /// Heightmap filled by `height(x, z)`
+    pub fn from_fn(width: u32, depth: u32, mut height: impl FnMut(u32, u32) -> f32) -> Result<Self> {
+        let heights = (0..depth)
+            .flat_map(|z| (0..width).map(move |x| (x, z)))
+            .map(|(x, z)| height(x, z))
+            .collect();
+        Self::new(width, depth, heights)
+    }
+
+    /// Samples along X
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Samples along Z
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// All samples, row-major
+    pub fn heights(&self) -> &[f32] {
+        &self.heights
+    }
+
+    /// Sample at a grid position, clamped to the edges
+    pub fn height(&self, x: i64, z: i64) -> f32 {
+        let x = x.clamp(0, self.width as i64 - 1) as usize;
+        let z = z.clamp(0, self.depth as i64 - 1) as usize;
+        self.heights[z * self.width as usize + x]
+    }
+
+    /// Bilinear height at a fractional grid position, clamped to the edges
+    pub fn sample(&self, x: f32, z: f32) -> f32 {
+        let x = x.clamp(0.0, (self.width - 1) as f32);
+        let z = z.clamp(0.0, (self.depth - 1) as f32);
+        let (x0, z0) = (x.floor(), z.floor());
+        let (tx, tz) = (x - x0, z - z0);
+        let (x0, z0) = (x0 as i64, z0 as i64);
+        let top = self.height(x0, z0) * (1.0 - tx) + self.height(x0 + 1, z0) * tx;
+        let bottom = self.height(x0, z0 + 1) * (1.0 - tx) + self.height(x0 + 1, z0 + 1) * tx;
+        top * (1.0 - tz) + bottom * tz
+    }
+
+    /// Unit normal at a sample, from central differences of the scaled
+    /// heights (one-sided on the edges)
+    pub fn normal(&self, x: u32, z: u32, cell_size: f32, height_scale: f32) -> Vec3 {
+        let (x, z) = (x as i64, z as i64);
+        let span_x = ((x + 1).min(self.width as i64 - 1) - (x - 1).max(0)) as f32 * cell_size;
+        let span_z = ((z + 1).min(self.depth as i64 - 1) - (z - 1).max(0)) as f32 * cell_size;
+        let dx = (self.height(x + 1, z) - self.height(x - 1, z)) * height_scale / span_x;
+        let dz = (self.height(x, z + 1) - self.height(x, z - 1)) * height_scale / span_z;
+        Vec3::new(-dx, 1.0, -dz).normalize()
+    }
+
+    /// Normals of every sample, row-major (see `normal()`)
+    pub fn compute_normals(&self, cell_size: f32, height_scale: f32) -> Vec<Vec3> {
+        (0..self.depth)
+            .flat_map(|z| (0..self.width).map(move |x| (x, z)))
+            .map(|(x, z)| self.normal(x, z, cell_size, height_scale))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[path = "heightmap_tests.rs"]
+mod tests;
@@ -0,0 +1,81 @@
+/// Unit tests for heightmap.rs
+
+use super::*;
+
+// ============================================================================
+// Construction
+// ============================================================================
+
+#[test]
+fn test_new_validates_size_and_values() {
+    assert!(Heightmap::new(1, 4, vec![0.0; 4]).is_err());
+    assert!(Heightmap::new(2, 2, vec![0.0; 3]).is_err());
+    assert!(Heightmap::new(2, 2, vec![0.0, f32::NAN, 0.0, 0.0]).is_err());
+
+    let heightmap = Heightmap::new(3, 2, vec![0.0; 6]).unwrap();
+    assert_eq!((heightmap.width(), heightmap.depth()), (3, 2));
+}
+
+#[test]
+fn test_from_r16_normalizes_little_endian_samples() {
+    let bytes = [0x00, 0x00, 0xFF, 0xFF, 0x00, 0x80, 0x01, 0x00];
+    let heightmap = Heightmap::from_r16(2, 2, &bytes).unwrap();
+    assert_eq!(heightmap.heights()[0], 0.0);
+    assert_eq!(heightmap.heights()[1], 1.0);
+    assert!((heightmap.heights()[2] - 0x8000 as f32 / 65535.0).abs() < 1e-6);
+
+    assert!(Heightmap::from_r16(2, 2, &bytes[..7]).is_err());
+    assert!(Heightmap::from_r16(2, 2, &bytes[..6]).is_err());
+}
+
+#[test]
+fn test_from_fn_is_row_major() {
+    let heightmap = Heightmap::from_fn(3, 2, |x, z| (z * 10 + x) as f32).unwrap();
+    assert_eq!(heightmap.heights(), [0.0, 1.0, 2.0, 10.0, 11.0, 12.0]);
+    assert_eq!(heightmap.height(2, 1), 12.0);
+}
+
+// ============================================================================
+// Sampling
+// ============================================================================
+
+#[test]
+fn test_height_clamps_to_edges() {
+    let heightmap = Heightmap::from_fn(3, 2, |x, z| (z * 10 + x) as f32).unwrap();
+    assert_eq!(heightmap.height(-5, 0), 0.0);
+    assert_eq!(heightmap.height(9, 9), 12.0);
+}
+
+#[test]
+fn test_sample_is_bilinear() {
+    let heightmap = Heightmap::new(2, 2, vec![0.0, 1.0, 2.0, 3.0]).unwrap();
+    assert_eq!(heightmap.sample(0.5, 0.0), 0.5);
+    assert_eq!(heightmap.sample(0.0, 0.5), 1.0);
+    assert_eq!(heightmap.sample(0.5, 0.5), 1.5);
+    assert_eq!(heightmap.sample(4.0, -1.0), 1.0);
+}
+
+// ============================================================================
+// Normals
+// ============================================================================
+
+#[test]
+fn test_flat_heightmap_normals_point_up() {
+    let heightmap = Heightmap::new(3, 3, vec![0.5; 9]).unwrap();
+    for normal in heightmap.compute_normals(1.0, 10.0) {
+        assert!((normal - Vec3::Y).length() < 1e-6);
+    }
+}
+
+#[test]
+fn test_slope_normal_leans_downhill() {
+    // Height rises by 1 per sample along X: a 45 degree slope
+    let heightmap = Heightmap::from_fn(3, 3, |x, _| x as f32).unwrap();
+    let expected = Vec3::new(-1.0, 1.0, 0.0).normalize();
+    assert!((heightmap.normal(1, 1, 1.0, 1.0) - expected).length() < 1e-6);
+    assert!((heightmap.normal(0, 0, 1.0, 1.0) - expected).length() < 1e-6);
+
+    // Doubling the cell size halves the slope
+    let flatter = heightmap.normal(1, 1, 2.0, 1.0);
+    assert!((flatter - Vec3::new(-0.5, 1.0, 0.0).normalize()).length() < 1e-6);
+}
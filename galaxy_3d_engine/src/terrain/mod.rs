@@ -0,0 +1,16 @@
+//! Heightmap terrain rendering.
+//!
+//! `Terrain` splits a `Heightmap` into chunks drawn as regular render
+//! instances, each with geomipmapped LODs selected by the view dispatcher
+//! and skirts hiding the cracks between LODs. Ground layers are blended by
+//! a splat map generated from height and slope rules.
+
+mod heightmap;
+mod terrain;
+
+pub use heightmap::Heightmap;
+pub use terrain::{
+    Terrain, TerrainDesc, TerrainChunk, SplatRule, SplatMaterialDesc,
+    create_splat_material, terrain_vertex_layout,
+    TERRAIN_VERTEX_STRIDE, TERRAIN_MAX_CHUNK_CELLS, TERRAIN_MAX_SPLAT_LAYERS, TERRAIN_SUBMESH_NAME,
+};
@@ -0,0 +1,656 @@
+/// Chunked heightmap terrain with geomipmapped LODs.
+///
+/// The heightmap is split into square chunks of `chunk_cells` cells. Each
+/// chunk becomes a `GeometryMesh` whose single submesh ("surface") holds
+/// one LOD per mip level of the grid (LOD `i` keeps every `2^i`-th sample).
+/// LODs are selected by the `ViewDispatcher` like any mesh, from the
+/// projected size of the chunk and the thresholds of `lod_thresholds()`.
+/// Cracks between chunks at different LODs are hidden by skirts: a strip
+/// hanging `skirt_depth` below every chunk border.
+///
+/// All chunks share one `Geometry`: their vertices are stored chunk after
+/// chunk, relative to the chunk origin, and every chunk uses the same index
+/// ranges per LOD (indices are relative to the LOD's `vertex_offset`).
+///
+/// `spawn()` creates one `RenderInstance` per chunk, so chunks are culled
+/// per frame like other instances (through the `SceneIndex` once the
+/// updater has inserted them).
+///
+/// Splat layering: `generate_splat_map()` derives RGBA8 blend weights from
+/// height and slope rules, and `create_splat_material()` binds that map
+/// with a texture array of up to four ground layers.
+
+use std::borrow::Cow;
+use std::sync::{Arc, Mutex};
+use glam::{Mat4, Vec3};
+use crate::error::Result;
+use crate::{engine_bail, engine_not_found};
+use crate::graphics_device::{
+    self, BufferFormat, IndexType, PolygonMode, PrimitiveTopology, SamplerType, TextureType,
+    VertexAttribute, VertexBinding, VertexInputRate, VertexLayout,
+};
+use crate::resource::geometry::{
+    GeometryDesc, GeometryMeshDesc, GeometrySubMeshDesc, GeometrySubMeshLODDesc,
+};
+use crate::resource::material::{MaterialDesc, MaterialPassDesc, MaterialTextureSlotDesc, ParamValue};
+use crate::resource::mesh::{GeometryMeshRef, GeometrySubMeshRef, MeshDesc, MeshSubMeshDesc};
+use crate::resource::resource_manager::{
+    GeometryKey, MaterialKey, MeshKey, ResourceManager, ShaderKey, TextureKey,
+};
+use crate::scene::{RenderInstanceKey, Scene, AABB};
+use super::heightmap::Heightmap;
+
+/// Size in bytes of one terrain vertex
+pub const TERRAIN_VERTEX_STRIDE: u32 = 32;
+
+/// Largest chunk side, in cells (keeps every LOD under 16-bit indices)
+pub const TERRAIN_MAX_CHUNK_CELLS: u32 = 128;
+
+/// Ground layers blended by a splat map (one per RGBA channel)
+pub const TERRAIN_MAX_SPLAT_LAYERS: usize = 4;
+
+/// Name of the submesh of every chunk mesh
+pub const TERRAIN_SUBMESH_NAME: &str = "surface";
+
+/// Vertex layout of terrain geometries (one binding, per vertex).
+///
+/// - location 0: position (vec3, relative to the chunk origin)
+/// - location 1: normal (vec3)
+/// - location 2: UV over the whole terrain (vec2, for the splat map)
+pub fn terrain_vertex_layout() -> VertexLayout {
+    let attribute = |location: u32, format: BufferFormat, offset: u32| VertexAttribute {
+        location, binding: 0, format, offset,
+    };
+    VertexLayout {
+        bindings: vec![VertexBinding {
+            binding: 0,
+            stride: TERRAIN_VERTEX_STRIDE,
+            input_rate: VertexInputRate::Vertex,
+        }],
+        attributes: vec![
+            attribute(0, BufferFormat::R32G32B32_SFLOAT, 0),
+            attribute(1, BufferFormat::R32G32B32_SFLOAT, 12),
+            attribute(2, BufferFormat::R32G32_SFLOAT, 24),
+        ],
+    }
+}
+
+// ===== TERRAIN DESC =====
+
+/// Descriptor for creating a Terrain.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TerrainDesc {
+    /// Distance between two samples
+    pub cell_size: f32,
+    /// Height of a sample of 1.0
+    pub height_scale: f32,
+    /// Chunk side in cells: a power of two up to `TERRAIN_MAX_CHUNK_CELLS`
+    /// dividing both heightmap sides minus one
+    pub chunk_cells: u32,
+    /// LODs per chunk, at most `log2(chunk_cells) + 1`
+    pub lod_count: u8,
+    /// Projected chunk diameter (pixels) under which LOD 0 drops to LOD 1;
+    /// each next frontier is half the previous one
+    pub lod_screen_size: f32,
+    /// Depth of the crack-hiding skirts (0 = no skirts)
+    pub skirt_depth: f32,
+}
+
+// ===== SPLAT RULES =====
+
+/// Where a ground layer appears in a generated splat map.
+///
+/// The weight of a sample is the product of its height and slope
+/// memberships, each 1 inside its range and fading linearly to 0 over
+/// `*_blend` outside it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SplatRule {
+    /// Scaled height range
+    pub min_height: f32,
+    pub max_height: f32,
+    /// Slope range, in radians from flat
+    pub min_slope: f32,
+    pub max_slope: f32,
+    pub height_blend: f32,
+    pub slope_blend: f32,
+}
+
+impl SplatRule {
+    /// Weight of a sample of the given height and slope, in 0..1
+    pub fn weight(&self, height: f32, slope: f32) -> f32 {
+        fade(height, self.min_height, self.max_height, self.height_blend)
+            * fade(slope, self.min_slope, self.max_slope, self.slope_blend)
+    }
+}
+
+/// 1 inside `[low, high]`, fading linearly to 0 over `blend` outside
+fn fade(value: f32, low: f32, high: f32, blend: f32) -> f32 {
+    if value >= low && value <= high {
+        return 1.0;
+    }
+    if blend <= 0.0 {
+        return 0.0;
+    }
+    let distance = if value < low { low - value } else { value - high };
+    (1.0 - distance / blend).max(0.0)
+}
+
+/// Descriptor of a splat material (see `create_splat_material()`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SplatMaterialDesc {
+    /// Pass type of the terrain pass
+    pub pass_type: u8,
+    pub fragment_shader: ShaderKey,
+    /// RGBA blend weights over the whole terrain ("splatMap" slot)
+    pub splat_map: TextureKey,
+    /// `Array2D` texture of 1 to 4 ground layers ("splatLayers" slot)
+    pub layers: TextureKey,
+    /// Repetitions of the layer textures per terrain cell
+    /// ("layerTiling" param; "layerCount" holds the layer count)
+    pub layer_tiling: f32,
+}
+
+/// Create a material blending the layers of a texture array by a splat
+/// map, layer `i` weighted by channel `i`.
+///
+/// # Errors
+///
+/// Returns an error if a texture is missing, if `layers` is not an
+/// `Array2D` of 1 to 4 layers, or if material creation fails.
+pub fn create_splat_material(
+    rm: &mut ResourceManager,
+    graphics_device: &dyn graphics_device::GraphicsDevice,
+    name: &str,
+    desc: &SplatMaterialDesc,
+) -> Result<MaterialKey> {
+    if rm.texture(desc.splat_map).is_none() {
+        return Err(engine_not_found!("galaxy3d::Terrain", "Texture", format!("{:?}", desc.splat_map)));
+    }
+    let layers = rm.texture(desc.layers)
+        .ok_or_else(|| engine_not_found!("galaxy3d::Terrain", "Texture", format!("{:?}", desc.layers)))?;
+    let info = layers.graphics_device_texture().info();
+    if info.texture_type != TextureType::Array2D
+        || info.array_layers == 0
+        || info.array_layers as usize > TERRAIN_MAX_SPLAT_LAYERS
+    {
+        engine_bail!("galaxy3d::Terrain",
+            "Splat material '{}': layers must be an Array2D of 1 to {} layers, got {:?} with {}",
+            name, TERRAIN_MAX_SPLAT_LAYERS, info.texture_type, info.array_layers);
+    }
+    let layer_count = info.array_layers;
+
+    let slot = |slot_name: &str, texture: TextureKey, sampler_type: SamplerType| MaterialTextureSlotDesc {
+        name: slot_name.to_string(),
+        texture,
+        layer: None,
+        region: None,
+        sampler_type,
+        flipbook: None,
+    };
+    rm.create_material(name.to_string(), MaterialDesc {
+        passes: vec![MaterialPassDesc {
+            pass_type: desc.pass_type,
+            fragment_shader: desc.fragment_shader,
+            color_blend: Default::default(),
+            polygon_mode: PolygonMode::Fill,
+            textures: vec![
+                slot("splatMap", desc.splat_map, SamplerType::LinearClamp),
+                slot("splatLayers", desc.layers, SamplerType::LinearRepeat),
+            ],
+            params: vec![
+                ("layerTiling".to_string(), ParamValue::Float(desc.layer_tiling)),
+                ("layerCount".to_string(), ParamValue::UInt(layer_count)),
+            ],
+            render_state: None,
+            engine_features: Default::default(),
+            render_queue: None,
+        }],
+    }, graphics_device)
+}
+
+// ===== TERRAIN =====
+
+/// One square piece of the terrain
+#[derive(Debug, Clone, Copy)]
+pub struct TerrainChunk {
+    /// Chunk coordinates in the chunk grid
+    pub x: u32,
+    pub z: u32,
+    /// Position of the chunk's first sample in terrain space
+    pub origin: Vec3,
+    /// Bounds relative to `origin`, skirts included
+    pub bounds: AABB,
+}
+
+/// Chunked heightmap terrain (see module docs)
+pub struct Terrain {
+    name: String,
+    heightmap: Heightmap,
+    desc: TerrainDesc,
+    normals: Vec<Vec3>,
+    chunks_x: u32,
+    chunks_z: u32,
+    chunks: Vec<TerrainChunk>,
+    geometry: Option<GeometryKey>,
+    /// Mesh of each chunk, in `chunks` order (empty before `create_resources()`)
+    meshes: Vec<MeshKey>,
+}
+
+impl Terrain {
+    /// Split a heightmap into chunks and compute its normals. `name`
+    /// prefixes every resource the terrain creates.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a descriptor value is out of range or if
+    /// `chunk_cells` does not divide the heightmap sides minus one.
+    pub fn new(name: &str, heightmap: Heightmap, desc: TerrainDesc) -> Result<Self> {
+        if !(desc.cell_size > 0.0 && desc.cell_size.is_finite() && desc.height_scale.is_finite()) {
+            engine_bail!("galaxy3d::Terrain",
+                "Terrain '{}': cell_size must be positive and height_scale finite, got {} and {}",
+                name, desc.cell_size, desc.height_scale);
+        }
+        if !desc.chunk_cells.is_power_of_two() || !(2..=TERRAIN_MAX_CHUNK_CELLS).contains(&desc.chunk_cells) {
+            engine_bail!("galaxy3d::Terrain",
+                "Terrain '{}': chunk_cells must be a power of two in 2..={}, got {}",
+                name, TERRAIN_MAX_CHUNK_CELLS, desc.chunk_cells);
+        }
+        let max_lods = desc.chunk_cells.trailing_zeros() + 1;
+        if desc.lod_count == 0 || desc.lod_count as u32 > max_lods {
+            engine_bail!("galaxy3d::Terrain",
+                "Terrain '{}': lod_count must be in 1..={} for {} cells, got {}",
+                name, max_lods, desc.chunk_cells, desc.lod_count);
+        }
+        if !(desc.lod_screen_size > 0.0 && desc.lod_screen_size.is_finite()
+            && desc.skirt_depth >= 0.0 && desc.skirt_depth.is_finite())
+        {
+            engine_bail!("galaxy3d::Terrain",
+                "Terrain '{}': lod_screen_size must be positive and skirt_depth non-negative, got {} and {}",
+                name, desc.lod_screen_size, desc.skirt_depth);
+        }
+        let (cells_x, cells_z) = (heightmap.width() - 1, heightmap.depth() - 1);
+        if cells_x % desc.chunk_cells != 0 || cells_z % desc.chunk_cells != 0 {
+            engine_bail!("galaxy3d::Terrain",
+                "Terrain '{}': {}x{} cells are not a multiple of {}-cell chunks",
+                name, cells_x, cells_z, desc.chunk_cells);
+        }
+
+        let normals = heightmap.compute_normals(desc.cell_size, desc.height_scale);
+        let (chunks_x, chunks_z) = (cells_x / desc.chunk_cells, cells_z / desc.chunk_cells);
+        let mut terrain = Self {
+            name: name.to_string(),
+            heightmap,
+            desc,
+            normals,
+            chunks_x,
+            chunks_z,
+            chunks: Vec::with_capacity((chunks_x * chunks_z) as usize),
+            geometry: None,
+            meshes: Vec::new(),
+        };
+        for z in 0..chunks_z {
+            for x in 0..chunks_x {
+                let chunk = terrain.build_chunk(x, z);
+                terrain.chunks.push(chunk);
+            }
+        }
+        Ok(terrain)
+    }
+
+    // ===== ACCESSORS =====
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn heightmap(&self) -> &Heightmap {
+        &self.heightmap
+    }
+
+    pub fn desc(&self) -> &TerrainDesc {
+        &self.desc
+    }
+
+    /// Normal of every heightmap sample, row-major
+    pub fn normals(&self) -> &[Vec3] {
+        &self.normals
+    }
+
+    /// Chunk grid size (along X, along Z)
+    pub fn chunk_grid(&self) -> (u32, u32) {
+        (self.chunks_x, self.chunks_z)
+    }
+
+    /// Chunks, row-major
+    pub fn chunks(&self) -> &[TerrainChunk] {
+        &self.chunks
+    }
+
+    /// Shared geometry, once `create_resources()` ran
+    pub fn geometry(&self) -> Option<GeometryKey> {
+        self.geometry
+    }
+
+    /// Chunk meshes in `chunks()` order, once `create_resources()` ran
+    pub fn meshes(&self) -> &[MeshKey] {
+        &self.meshes
+    }
+
+    /// Terrain-space size (X, Z)
+    pub fn extent(&self) -> (f32, f32) {
+        let cells = |samples: u32| (samples - 1) as f32 * self.desc.cell_size;
+        (cells(self.heightmap.width()), cells(self.heightmap.depth()))
+    }
+
+    /// Scaled height under a terrain-space point (bilinear, clamped to the
+    /// edges)
+    pub fn height_at(&self, x: f32, z: f32) -> f32 {
+        self.heightmap.sample(x / self.desc.cell_size, z / self.desc.cell_size) * self.desc.height_scale
+    }
+
+    /// LOD thresholds `(drop, raise)` of the chunk submeshes: frontier `i`
+    /// drops at `lod_screen_size / 2^i` pixels and comes back 25% above
+    pub fn lod_thresholds(&self) -> Vec<(f32, f32)> {
+        (1..self.desc.lod_count)
+            .map(|lod| {
+                let drop = self.desc.lod_screen_size / (1u32 << (lod - 1)) as f32;
+                (drop, drop * 1.25)
+            })
+            .collect()
+    }
+
+    // ===== GEOMETRY =====
+
+    /// Geometry of every chunk and LOD (see module docs). Pure CPU: call
+    /// `create_resources()` to upload it.
+    pub fn build_geometry_desc(
+        &self,
+        graphics_device: Arc<Mutex<dyn graphics_device::GraphicsDevice>>,
+    ) -> GeometryDesc<'static> {
+        let mut vertex_data: Vec<u8> = Vec::new();
+        let mut index_data: Vec<u8> = Vec::new();
+
+        // Index ranges are shared: build them once per LOD
+        let mut index_ranges = Vec::with_capacity(self.desc.lod_count as usize);
+        for lod in 0..self.desc.lod_count {
+            let cells = self.desc.chunk_cells >> lod;
+            let indices = self.lod_indices(cells);
+            index_ranges.push(((index_data.len() / 2) as u32, indices.len() as u32));
+            index_data.extend(indices.iter().flat_map(|i| i.to_ne_bytes()));
+        }
+
+        let thresholds = self.lod_thresholds();
+        let vertex_count = |bytes: &Vec<u8>| (bytes.len() / TERRAIN_VERTEX_STRIDE as usize) as u32;
+        let meshes = self.chunks.iter().map(|chunk| {
+            let lods = (0..self.desc.lod_count).map(|lod| {
+                let vertex_offset = vertex_count(&vertex_data);
+                self.push_lod_vertices(chunk, lod, &mut vertex_data);
+                let (index_offset, index_count) = index_ranges[lod as usize];
+                GeometrySubMeshLODDesc {
+                    vertex_offset,
+                    vertex_count: vertex_count(&vertex_data) - vertex_offset,
+                    index_offset,
+                    index_count,
+                    topology: PrimitiveTopology::TriangleList,
+                }
+            }).collect();
+            GeometryMeshDesc {
+                name: Self::chunk_name(chunk),
+                submeshes: vec![GeometrySubMeshDesc {
+                    name: TERRAIN_SUBMESH_NAME.to_string(),
+                    lods,
+                    lod_thresholds: thresholds.clone(),
+                }],
+            }
+        }).collect();
+
+        GeometryDesc {
+            name: self.name.clone(),
+            graphics_device,
+            vertex_data: Cow::Owned(vertex_data),
+            index_data: Some(Cow::Owned(index_data)),
+            vertex_layout: terrain_vertex_layout(),
+            index_type: IndexType::U16,
+            morph_targets: Vec::new(),
+            meshes,
+        }
+    }
+
+    /// Upload the geometry and create one mesh per chunk drawn with
+    /// `material`. Resources are named `<name>` (geometry) and
+    /// `<name>/chunk_<x>_<z>` (meshes).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the resources already exist or a creation
+    /// fails; nothing is left behind.
+    pub fn create_resources(
+        &mut self,
+        rm: &mut ResourceManager,
+        graphics_device: Arc<Mutex<dyn graphics_device::GraphicsDevice>>,
+        material: MaterialKey,
+    ) -> Result<()> {
+        if self.geometry.is_some() {
+            engine_bail!("galaxy3d::Terrain",
+                "Terrain '{}' resources already exist", self.name);
+        }
+        let geometry = rm.create_geometry(self.name.clone(), self.build_geometry_desc(graphics_device))?;
+        let mut meshes = Vec::with_capacity(self.chunks.len());
+        for chunk in &self.chunks {
+            let chunk_name = Self::chunk_name(chunk);
+            let created = rm.create_mesh(format!("{}/{}", self.name, chunk_name), MeshDesc {
+                geometry,
+                geometry_mesh: GeometryMeshRef::Name(chunk_name),
+                submeshes: vec![MeshSubMeshDesc {
+                    submesh: GeometrySubMeshRef::Name(TERRAIN_SUBMESH_NAME.to_string()),
+                    material,
+                }],
+            });
+            match created {
+                Ok(key) => meshes.push(key),
+                Err(e) => {
+                    for chunk in &self.chunks[..meshes.len()] {
+                        rm.remove_mesh(&format!("{}/{}", self.name, Self::chunk_name(chunk)));
+                    }
+                    rm.remove_geometry(&self.name);
+                    return Err(e);
+                }
+            }
+        }
+        self.geometry = Some(geometry);
+        self.meshes = meshes;
+        Ok(())
+    }
+
+    /// Remove the meshes and geometry of `create_resources()`. Instances
+    /// spawned from them must be removed first.
+    pub fn remove_resources(&mut self, rm: &mut ResourceManager) {
+        if self.geometry.take().is_none() {
+            return;
+        }
+        for chunk in &self.chunks {
+            rm.remove_mesh(&format!("{}/{}", self.name, Self::chunk_name(chunk)));
+        }
+        self.meshes.clear();
+        rm.remove_geometry(&self.name);
+    }
+
+    /// Create one render instance per chunk, placed by `world_matrix`
+    /// (terrain space to world). Returns the keys in `chunks()` order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `create_resources()` did not run or an instance
+    /// creation fails (instances already created are removed).
+    pub fn spawn(
+        &self,
+        scene: &mut Scene,
+        world_matrix: Mat4,
+        vertex_shader: ShaderKey,
+        rm: &ResourceManager,
+    ) -> Result<Vec<RenderInstanceKey>> {
+        if self.geometry.is_none() {
+            engine_bail!("galaxy3d::Terrain",
+                "Terrain '{}': spawn() needs create_resources() first", self.name);
+        }
+        let mut keys = Vec::with_capacity(self.chunks.len());
+        for (chunk, mesh) in self.chunks.iter().zip(&self.meshes) {
+            let created = scene.create_render_instance(
+                *mesh,
+                world_matrix * Mat4::from_translation(chunk.origin),
+                chunk.bounds,
+                vertex_shader,
+                &[],
+                rm,
+            );
+            match created {
+                Ok(key) => keys.push(key),
+                Err(e) => {
+                    for key in keys {
+                        scene.remove_render_instance(key);
+                    }
+                    return Err(e);
+                }
+            }
+        }
+        Ok(keys)
+    }
+
+    // ===== SPLAT MAP =====
+
+    /// RGBA8 splat map with one texel per heightmap sample: channel `i`
+    /// holds the normalized weight of `rules[i]`. Samples matched by no
+    /// rule get layer 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are no rules or more than four.
+    pub fn generate_splat_map(&self, rules: &[SplatRule]) -> Result<Vec<u8>> {
+        if rules.is_empty() || rules.len() > TERRAIN_MAX_SPLAT_LAYERS {
+            engine_bail!("galaxy3d::Terrain",
+                "Terrain '{}': splat maps take 1 to {} rules, got {}",
+                self.name, TERRAIN_MAX_SPLAT_LAYERS, rules.len());
+        }
+        let mut texels = Vec::with_capacity(self.heightmap.heights().len() * 4);
+        for (height, normal) in self.heightmap.heights().iter().zip(&self.normals) {
+            let height = height * self.desc.height_scale;
+            let slope = normal.y.clamp(-1.0, 1.0).acos();
+            let mut weights = [0.0f32; TERRAIN_MAX_SPLAT_LAYERS];
+            for (weight, rule) in weights.iter_mut().zip(rules) {
+                *weight = rule.weight(height, slope);
+            }
+            let total: f32 = weights.iter().sum();
+            if total <= 0.0 {
+                weights = [1.0, 0.0, 0.0, 0.0];
+            } else {
+                weights.iter_mut().for_each(|w| *w /= total);
+            }
+            texels.extend(weights.map(|w| (w * 255.0).round() as u8));
+        }
+        Ok(texels)
+    }
+
+    // ===== PRIVATE HELPERS =====
+
+    fn chunk_name(chunk: &TerrainChunk) -> String {
+        format!("chunk_{}_{}", chunk.x, chunk.z)
+    }
+
+    /// Origin and bounds of a chunk
+    fn build_chunk(&self, x: u32, z: u32) -> TerrainChunk {
+        let cells = self.desc.chunk_cells;
+        let (first_x, first_z) = (x * cells, z * cells);
+        let (mut low, mut high) = (f32::INFINITY, f32::NEG_INFINITY);
+        for sz in first_z..=first_z + cells {
+            for sx in first_x..=first_x + cells {
+                let height = self.heightmap.height(sx as i64, sz as i64) * self.desc.height_scale;
+                low = low.min(height);
+                high = high.max(height);
+            }
+        }
+        let side = cells as f32 * self.desc.cell_size;
+        TerrainChunk {
+            x,
+            z,
+            origin: Vec3::new(first_x as f32 * self.desc.cell_size, 0.0, first_z as f32 * self.desc.cell_size),
+            bounds: AABB {
+                min: Vec3::new(0.0, low - self.desc.skirt_depth, 0.0),
+                max: Vec3::new(side, high, side),
+            },
+        }
+    }
+
+    /// Grid vertices of one chunk LOD, then its skirt vertices
+    fn push_lod_vertices(&self, chunk: &TerrainChunk, lod: u8, out: &mut Vec<u8>) {
+        let step = 1u32 << lod;
+        let cells = self.desc.chunk_cells >> lod;
+        let (first_x, first_z) = (chunk.x * self.desc.chunk_cells, chunk.z * self.desc.chunk_cells);
+        let uv_scale = [
+            1.0 / (self.heightmap.width() - 1) as f32,
+            1.0 / (self.heightmap.depth() - 1) as f32,
+        ];
+        let mut push = |gx: u32, gz: u32, drop: f32| {
+            let (sx, sz) = (first_x + gx * step, first_z + gz * step);
+            let height = self.heightmap.height(sx as i64, sz as i64) * self.desc.height_scale;
+            let normal = self.normals[(sz * self.heightmap.width() + sx) as usize];
+            let position = [
+                (gx * step) as f32 * self.desc.cell_size,
+                height - drop,
+                (gz * step) as f32 * self.desc.cell_size,
+            ];
+            let uv = [sx as f32 * uv_scale[0], sz as f32 * uv_scale[1]];
+            for value in position.iter().chain(normal.to_array().iter()).chain(uv.iter()) {
+                out.extend_from_slice(&value.to_ne_bytes());
+            }
+        };
+        for gz in 0..=cells {
+            for gx in 0..=cells {
+                push(gx, gz, 0.0);
+            }
+        }
+        if self.desc.skirt_depth > 0.0 {
+            for (gx, gz) in border_loop(cells) {
+                push(gx, gz, self.desc.skirt_depth);
+            }
+        }
+    }
+
+    /// Indices of a `cells` x `cells` LOD grid (counter-clockwise seen from
+    /// +Y), then its skirt quads (both windings)
+    fn lod_indices(&self, cells: u32) -> Vec<u16> {
+        let row = cells + 1;
+        let at = |gx: u32, gz: u32| (gz * row + gx) as u16;
+        let mut indices = Vec::with_capacity((cells * cells * 6 + cells * 4 * 12) as usize);
+        for gz in 0..cells {
+            for gx in 0..cells {
+                let (tl, tr) = (at(gx, gz), at(gx + 1, gz));
+                let (bl, br) = (at(gx, gz + 1), at(gx + 1, gz + 1));
+                indices.extend_from_slice(&[tl, bl, tr, tr, bl, br]);
+            }
+        }
+        if self.desc.skirt_depth > 0.0 {
+            let border: Vec<(u32, u32)> = border_loop(cells).collect();
+            let first_skirt = row * row;
+            for (k, &(gx, gz)) in border.iter().enumerate() {
+                let next = (k + 1) % border.len();
+                let (a, b) = (at(gx, gz), at(border[next].0, border[next].1));
+                let (sa, sb) = ((first_skirt + k as u32) as u16, (first_skirt + next as u32) as u16);
+                indices.extend_from_slice(&[a, sa, b, b, sa, sb, a, b, sa, b, sb, sa]);
+            }
+        }
+        indices
+    }
+}
+
+/// Border samples of a `cells` x `cells` grid, walked once around
+fn border_loop(cells: u32) -> impl Iterator<Item = (u32, u32)> {
+    let top = (0..cells).map(|gx| (gx, 0));
+    let right = (0..cells).map(move |gz| (cells, gz));
+    let bottom = (0..cells).map(move |i| (cells - i, cells));
+    let left = (0..cells).map(move |i| (0, cells - i));
+    top.chain(right).chain(bottom).chain(left)
+}
+
+#[cfg(test)]
+#[path = "terrain_tests.rs"]
+mod tests;
@@ -0,0 +1,250 @@
+/// Unit tests for terrain.rs
+
+use super::*;
+use crate::graphics_device::ShaderStage;
+use crate::resource::shader::ShaderDesc;
+use crate::resource::texture::{TextureDesc, LayerDesc};
+use crate::scene::scene_test_helpers::{setup_resources, create_mock_graphics_device};
+
+fn desc() -> TerrainDesc {
+    TerrainDesc {
+        cell_size: 2.0,
+        height_scale: 8.0,
+        chunk_cells: 4,
+        lod_count: 3,
+        lod_screen_size: 200.0,
+        skirt_depth: 1.0,
+    }
+}
+
+/// 9x9 samples (2x2 chunks of 4 cells) rising along X: scaled height = x
+fn ramp_terrain(desc: TerrainDesc) -> Terrain {
+    let heightmap = Heightmap::from_fn(9, 9, |x, _| x as f32 / 8.0).unwrap();
+    Terrain::new("ground", heightmap, desc).unwrap()
+}
+
+fn create_texture(rm: &mut ResourceManager, name: &str, texture_type: TextureType, array_layers: u32) -> TextureKey {
+    let layers = if array_layers == 1 {
+        vec![LayerDesc { name: "main".to_string(), layer_index: 0, data: None, regions: vec![] }]
+    } else {
+        vec![]
+    };
+    rm.create_texture(name.to_string(), TextureDesc {
+        graphics_device: create_mock_graphics_device(),
+        texture: graphics_device::TextureDesc {
+            width: 64, height: 64, depth: 1,
+            format: graphics_device::TextureFormat::R8G8B8A8_UNORM,
+            usage: graphics_device::TextureUsage::Sampled,
+            texture_type,
+            sample_count: graphics_device::SampleCount::S1,
+            array_layers, data: None, mipmap: graphics_device::MipmapMode::None,
+            debug_name: None,
+        },
+        layers,
+    }).unwrap()
+}
+
+// ============================================================================
+// Construction
+// ============================================================================
+
+#[test]
+fn test_new_validates_desc() {
+    let heightmap = || Heightmap::new(9, 9, vec![0.0; 81]).unwrap();
+    let invalid = [
+        TerrainDesc { cell_size: 0.0, ..desc() },
+        TerrainDesc { chunk_cells: 3, ..desc() },
+        TerrainDesc { chunk_cells: 256, ..desc() },
+        TerrainDesc { chunk_cells: 16, ..desc() },
+        TerrainDesc { lod_count: 0, ..desc() },
+        TerrainDesc { lod_count: 4, ..desc() },
+        TerrainDesc { lod_screen_size: 0.0, ..desc() },
+        TerrainDesc { skirt_depth: -1.0, ..desc() },
+    ];
+    for desc in invalid {
+        assert!(Terrain::new("ground", heightmap(), desc).is_err(), "{:?}", desc);
+    }
+    assert!(Terrain::new("ground", heightmap(), desc()).is_ok());
+}
+
+#[test]
+fn test_chunks_cover_the_heightmap() {
+    let terrain = ramp_terrain(desc());
+    assert_eq!(terrain.chunk_grid(), (2, 2));
+    assert_eq!(terrain.extent(), (16.0, 16.0));
+    assert_eq!(terrain.chunks().len(), 4);
+
+    let chunk = terrain.chunks()[1];
+    assert_eq!((chunk.x, chunk.z), (1, 0));
+    assert_eq!(chunk.origin, Vec3::new(8.0, 0.0, 0.0));
+    // Heights 4..8 under the chunk, skirt 1 below
+    assert_eq!(chunk.bounds.min, Vec3::new(0.0, 3.0, 0.0));
+    assert_eq!(chunk.bounds.max, Vec3::new(8.0, 8.0, 8.0));
+    assert_eq!(terrain.chunks()[2].origin, Vec3::new(0.0, 0.0, 8.0));
+}
+
+#[test]
+fn test_height_at_scales_and_interpolates() {
+    let terrain = ramp_terrain(desc());
+    assert_eq!(terrain.height_at(0.0, 0.0), 0.0);
+    assert_eq!(terrain.height_at(3.0, 5.0), 1.5);
+    assert_eq!(terrain.height_at(100.0, 0.0), 8.0);
+}
+
+#[test]
+fn test_lod_thresholds_halve_per_frontier() {
+    let terrain = ramp_terrain(desc());
+    assert_eq!(terrain.lod_thresholds(), [(200.0, 250.0), (100.0, 125.0)]);
+}
+
+// ============================================================================
+// Geometry
+// ============================================================================
+
+#[test]
+fn test_geometry_desc_lods_with_skirts() {
+    let terrain = ramp_terrain(desc());
+    let geometry = terrain.build_geometry_desc(create_mock_graphics_device());
+    assert_eq!(geometry.meshes.len(), 4);
+    assert_eq!(geometry.meshes[1].name, "chunk_1_0");
+
+    // Per LOD: (cells + 1)^2 grid vertices + 4 * cells skirt vertices,
+    // 6 indices per cell + 12 per skirt segment
+    let lods = &geometry.meshes[0].submeshes[0].lods;
+    let counts: Vec<_> = lods.iter().map(|lod| (lod.vertex_count, lod.index_count)).collect();
+    assert_eq!(counts, [(41, 288), (17, 120), (8, 54)]);
+    assert_eq!(lods.iter().map(|lod| lod.index_offset).collect::<Vec<_>>(), [0, 288, 408]);
+    assert_eq!(lods.iter().map(|lod| lod.vertex_offset).collect::<Vec<_>>(), [0, 41, 58]);
+
+    // Chunks share index ranges and follow each other in the vertex data
+    let second = &geometry.meshes[1].submeshes[0].lods;
+    assert_eq!(second[0].vertex_offset, 66);
+    assert_eq!(second[2].index_offset, 408);
+    assert_eq!(geometry.vertex_data.len(), 4 * 66 * TERRAIN_VERTEX_STRIDE as usize);
+    assert_eq!(geometry.index_data.as_ref().unwrap().len(), 462 * 2);
+}
+
+#[test]
+fn test_geometry_desc_without_skirts() {
+    let terrain = ramp_terrain(TerrainDesc { skirt_depth: 0.0, ..desc() });
+    let geometry = terrain.build_geometry_desc(create_mock_graphics_device());
+    let lods = &geometry.meshes[0].submeshes[0].lods;
+    let counts: Vec<_> = lods.iter().map(|lod| (lod.vertex_count, lod.index_count)).collect();
+    assert_eq!(counts, [(25, 96), (9, 24), (4, 6)]);
+}
+
+#[test]
+fn test_lod_grid_faces_up() {
+    let terrain = ramp_terrain(TerrainDesc { skirt_depth: 0.0, ..desc() });
+    let indices = terrain.lod_indices(1);
+    // Quad corners on the XZ plane: 0 (0,0), 1 (1,0), 2 (0,1), 3 (1,1)
+    let corner = |i: u16| Vec3::new((i % 2) as f32, 0.0, (i / 2) as f32);
+    for triangle in indices.chunks(3) {
+        let (a, b, c) = (corner(triangle[0]), corner(triangle[1]), corner(triangle[2]));
+        assert!((b - a).cross(c - a).y > 0.0);
+    }
+}
+
+// ============================================================================
+// Resources
+// ============================================================================
+
+#[test]
+fn test_create_resources_and_spawn() {
+    let mut setup = setup_resources();
+    let material = setup.rm.material_key("m").unwrap();
+    let mut terrain = ramp_terrain(desc());
+    let mut scene = Scene::new();
+
+    assert!(terrain.spawn(&mut scene, Mat4::IDENTITY, setup.vertex_shader_key, &setup.rm).is_err());
+
+    terrain.create_resources(&mut setup.rm, create_mock_graphics_device(), material).unwrap();
+    assert_eq!(terrain.meshes().len(), 4);
+    let geometry = setup.rm.geometry_by_name("ground").unwrap();
+    assert_eq!(geometry.submesh_by_name("chunk_1_1", TERRAIN_SUBMESH_NAME).unwrap().lod_count(), 3);
+    assert!(setup.rm.mesh_by_name("ground/chunk_0_1").is_some());
+    assert!(terrain.create_resources(&mut setup.rm, create_mock_graphics_device(), material).is_err());
+
+    let world = Mat4::from_translation(Vec3::new(0.0, -5.0, 0.0));
+    let keys = terrain.spawn(&mut scene, world, setup.vertex_shader_key, &setup.rm).unwrap();
+    assert_eq!(keys.len(), 4);
+    let instance = scene.render_instance(keys[3]).unwrap();
+    assert_eq!(*instance.world_matrix(), world * Mat4::from_translation(Vec3::new(8.0, 0.0, 8.0)));
+    assert_eq!(instance.mesh(), Some(terrain.meshes()[3]));
+
+    for key in keys {
+        scene.remove_render_instance(key);
+    }
+    let meshes_before = setup.rm.mesh_count();
+    terrain.remove_resources(&mut setup.rm);
+    assert_eq!(setup.rm.mesh_count(), meshes_before - 4);
+    assert!(setup.rm.geometry_by_name("ground").is_none());
+    assert!(terrain.geometry().is_none());
+}
+
+// ============================================================================
+// Splat
+// ============================================================================
+
+#[test]
+fn test_splat_rule_weight_fades_outside_range() {
+    let rule = SplatRule {
+        min_height: 2.0, max_height: 4.0,
+        min_slope: 0.0, max_slope: 1.0,
+        height_blend: 2.0, slope_blend: 0.0,
+    };
+    assert_eq!(rule.weight(3.0, 0.5), 1.0);
+    assert_eq!(rule.weight(5.0, 0.5), 0.5);
+    assert_eq!(rule.weight(7.0, 0.5), 0.0);
+    assert_eq!(rule.weight(3.0, 1.5), 0.0);
+}
+
+#[test]
+fn test_generate_splat_map_normalizes_weights() {
+    let terrain = ramp_terrain(desc());
+    let rule = |min_height: f32, max_height: f32| SplatRule {
+        min_height, max_height,
+        min_slope: 0.0, max_slope: std::f32::consts::PI,
+        height_blend: 0.0, slope_blend: 0.0,
+    };
+    let low = rule(0.0, 2.0);
+    let high = rule(2.0, 6.0);
+    let texels = terrain.generate_splat_map(&[low, high]).unwrap();
+    assert_eq!(texels.len(), 81 * 4);
+
+    let texel = |x: usize| &texels[x * 4..x * 4 + 4];
+    assert_eq!(texel(0), [255, 0, 0, 0]);
+    // Height 2 is in both ranges
+    assert_eq!(texel(2), [128, 128, 0, 0]);
+    assert_eq!(texel(4), [0, 255, 0, 0]);
+    // Height 8 matches no rule: layer 0
+    assert_eq!(texel(8), [255, 0, 0, 0]);
+
+    assert!(terrain.generate_splat_map(&[]).is_err());
+    assert!(terrain.generate_splat_map(&[low; 5]).is_err());
+}
+
+#[test]
+fn test_create_splat_material_requires_a_layer_array() {
+    let mut setup = setup_resources();
+    let gd = create_mock_graphics_device();
+    let fragment_shader = setup.rm.create_shader(
+        "terrain_frag".to_string(),
+        ShaderDesc { code: &[], stage: ShaderStage::Fragment, entry_point: "main".to_string() },
+        &mut *gd.lock().unwrap(),
+    ).unwrap();
+    let splat_map = create_texture(&mut setup.rm, "splat", TextureType::Tex2D, 1);
+    let flat = create_texture(&mut setup.rm, "flat", TextureType::Tex2D, 1);
+    let layers = create_texture(&mut setup.rm, "layers", TextureType::Array2D, 3);
+    let too_many = create_texture(&mut setup.rm, "too_many", TextureType::Array2D, 5);
+
+    let desc = |layers: TextureKey| SplatMaterialDesc {
+        pass_type: 0, fragment_shader, splat_map, layers, layer_tiling: 0.25,
+    };
+    assert!(create_splat_material(&mut setup.rm, &*gd.lock().unwrap(), "a", &desc(flat)).is_err());
+    assert!(create_splat_material(&mut setup.rm, &*gd.lock().unwrap(), "b", &desc(too_many)).is_err());
+
+    let key = create_splat_material(&mut setup.rm, &*gd.lock().unwrap(), "terrain", &desc(layers)).unwrap();
+    assert_eq!(setup.rm.material_key("terrain"), Some(key));
+    assert!(setup.rm.material_key("a").is_none());
+}
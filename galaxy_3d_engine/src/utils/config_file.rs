@@ -0,0 +1,218 @@
+/// Versioned key/value config file with migrations.
+///
+/// The text format is one `key = value` per line, `#` starting a comment
+/// line, and a reserved `version` key:
+///
+/// ```text
+/// version = 2
+/// graphics.preset = high
+/// window.width = 1920
+/// ```
+///
+/// Every format change bumps the version and appends a migration. The
+/// migration at index `i` upgrades a version `i` file to version `i + 1`,
+/// so the current version is the number of migrations:
+///
+/// ```ignore
+/// const MIGRATIONS: &[ConfigMigration] = &[
+///     |config| { config.rename("width", "window.width"); Ok(()) },  // 0 -> 1
+///     |config| { config.remove("vsync"); Ok(()) },                  // 1 -> 2
+/// ];
+/// let config = ConfigFile::load(dirs.config_file(), MIGRATIONS)?;
+/// ```
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::path::Path;
+use std::str::FromStr;
+use crate::error::Result;
+use crate::engine_bail;
+use super::platform::write_atomic;
+
+/// Reserved key holding the format version
+pub const CONFIG_VERSION_KEY: &str = "version";
+
+/// Upgrade of a config by one version (see module docs)
+pub type ConfigMigration = fn(&mut ConfigFile) -> Result<()>;
+
+/// In-memory config file (see module docs). Keys are kept sorted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigFile {
+    version: u32,
+    values: BTreeMap<String, String>,
+}
+
+impl ConfigFile {
+    /// Empty config at `version`
+    pub fn new(version: u32) -> Self {
+        Self { version, values: BTreeMap::new() }
+    }
+
+    /// Parse the text format. A file without a `version` line is version 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error on a line without `=`, an empty key, a duplicate
+    /// key or a version that is not an integer.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut config = Self::new(0);
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                engine_bail!("galaxy3d::ConfigFile", "Line {}: expected 'key = value'", index + 1);
+            };
+            let (key, value) = (key.trim(), value.trim());
+            if key.is_empty() {
+                engine_bail!("galaxy3d::ConfigFile", "Line {}: empty key", index + 1);
+            }
+            if key == CONFIG_VERSION_KEY {
+                config.version = value.parse().map_err(|_| {
+                    crate::engine_err!("galaxy3d::ConfigFile",
+                        "Line {}: invalid version '{}'", index + 1, value)
+                })?;
+            } else if config.values.insert(key.to_string(), value.to_string()).is_some() {
+                engine_bail!("galaxy3d::ConfigFile", "Line {}: duplicate key '{}'", index + 1, key);
+            }
+        }
+        Ok(config)
+    }
+
+    /// Load `path` and bring it to the current version (`migrations.len()`).
+    /// A missing file gives an empty config at the current version. A
+    /// migrated file is saved back, so each migration runs once.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed, if a
+    /// migration fails, or if the file is newer than the current version.
+    pub fn load(path: impl AsRef<Path>, migrations: &[ConfigMigration]) -> Result<Self> {
+        let path = path.as_ref();
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Self::new(migrations.len() as u32));
+            }
+            Err(e) => {
+                engine_bail!("galaxy3d::ConfigFile", "Failed to read '{}': {}", path.display(), e);
+            }
+        };
+        let mut config = Self::parse(&text)?;
+        if config.migrate(migrations)? {
+            crate::engine_info!("galaxy3d::ConfigFile",
+                "Migrated '{}' to version {}", path.display(), config.version);
+            config.save(path)?;
+        }
+        Ok(config)
+    }
+
+    /// Write the text format to `path` (atomically, see `write_atomic`).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        write_atomic(path, self.to_string().as_bytes())
+    }
+
+    /// Apply the migrations from the config version up to
+    /// `migrations.len()`. Returns whether any was applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a migration fails (the config is then left at
+    /// the version of the last successful one) or if the config is newer
+    /// than `migrations.len()`.
+    pub fn migrate(&mut self, migrations: &[ConfigMigration]) -> Result<bool> {
+        let current = migrations.len() as u32;
+        if self.version > current {
+            engine_bail!("galaxy3d::ConfigFile",
+                "Config version {} is newer than the supported version {}", self.version, current);
+        }
+        let from = self.version;
+        for migration in &migrations[from as usize..] {
+            migration(self)?;
+            self.version += 1;
+        }
+        Ok(self.version != from)
+    }
+
+    // ===== VALUES =====
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(String::as_str)
+    }
+
+    /// Value parsed as `T` (None if missing or not parsable)
+    pub fn get_parsed<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.get(key)?.parse().ok()
+    }
+
+    /// Set a value (its `Display` form).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key is empty, reserved or contains `=`, `#`
+    /// or a line break, or if the value contains a line break.
+    pub fn set(&mut self, key: &str, value: impl Display) -> Result<()> {
+        let value = value.to_string();
+        if key.trim() != key
+            || key.is_empty()
+            || key.starts_with('#')
+            || key.contains(['=', '\n', '\r'])
+        {
+            engine_bail!("galaxy3d::ConfigFile", "Invalid config key '{}'", key);
+        }
+        if key == CONFIG_VERSION_KEY {
+            engine_bail!("galaxy3d::ConfigFile", "'{}' is reserved", CONFIG_VERSION_KEY);
+        }
+        if value.contains(['\n', '\r']) {
+            engine_bail!("galaxy3d::ConfigFile", "Value of '{}' spans several lines", key);
+        }
+        self.values.insert(key.to_string(), value.trim().to_string());
+        Ok(())
+    }
+
+    /// Remove a value, returning it
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        self.values.remove(key)
+    }
+
+    /// Move a value to another key (migrations). Returns false if `from`
+    /// is missing; an existing `to` is replaced.
+    pub fn rename(&mut self, from: &str, to: &str) -> bool {
+        match self.values.remove(from) {
+            Some(value) => {
+                self.values.insert(to.to_string(), value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Keys in sorted order
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(String::as_str)
+    }
+}
+
+impl Display for ConfigFile {
+    /// Text format: the version first, then the keys in sorted order
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} = {}", CONFIG_VERSION_KEY, self.version)?;
+        for (key, value) in &self.values {
+            writeln!(f, "{} = {}", key, value)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "config_file_tests.rs"]
+mod tests;
@@ -0,0 +1,108 @@
+use super::*;
+use std::fs;
+use crate::utils::test_helpers::scratch_dir;
+
+/// 0 -> 1 renames `width`, 1 -> 2 drops `vsync`
+const MIGRATIONS: &[ConfigMigration] = &[
+    |config| {
+        config.rename("width", "window.width");
+        Ok(())
+    },
+    |config| {
+        config.remove("vsync");
+        Ok(())
+    },
+];
+
+// ============================================================================
+// Text format
+// ============================================================================
+
+#[test]
+fn test_parse_and_print_round_trip() {
+    let config = ConfigFile::parse("# settings\n\nversion = 3\nwindow.width=1920\n  name = a = b  \n").unwrap();
+    assert_eq!(config.version(), 3);
+    assert_eq!(config.get("window.width"), Some("1920"));
+    assert_eq!(config.get_parsed::<u32>("window.width"), Some(1920));
+    // Split on the first '='
+    assert_eq!(config.get("name"), Some("a = b"));
+    assert_eq!(config.get_parsed::<u32>("name"), None);
+    assert_eq!(config.keys().collect::<Vec<_>>(), vec!["name", "window.width"]);
+
+    let text = config.to_string();
+    assert!(text.starts_with("version = 3\n"));
+    assert_eq!(ConfigFile::parse(&text).unwrap(), config);
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!(ConfigFile::parse("no separator").is_err());
+    assert!(ConfigFile::parse(" = value").is_err());
+    assert!(ConfigFile::parse("a = 1\na = 2").is_err());
+    assert!(ConfigFile::parse("version = two").is_err());
+    // No version line: version 0
+    assert_eq!(ConfigFile::parse("a = 1").unwrap().version(), 0);
+}
+
+#[test]
+fn test_set_validates_keys_and_values() {
+    let mut config = ConfigFile::new(1);
+    config.set("graphics.preset", "high").unwrap();
+    config.set("scale", 1.5).unwrap();
+    assert_eq!(config.get_parsed::<f32>("scale"), Some(1.5));
+
+    for key in ["", "a=b", "#a", " a", "a\nb", CONFIG_VERSION_KEY] {
+        assert!(config.set(key, 1).is_err(), "key {:?}", key);
+    }
+    assert!(config.set("a", "x\ny").is_err());
+    assert_eq!(config.remove("scale"), Some("1.5".to_string()));
+    assert!(!config.rename("scale", "other"));
+}
+
+// ============================================================================
+// Migrations
+// ============================================================================
+
+#[test]
+fn test_migrate_from_old_version() {
+    let mut config = ConfigFile::parse("width = 800\nvsync = on").unwrap();
+    assert!(config.migrate(MIGRATIONS).unwrap());
+    assert_eq!(config.version(), 2);
+    assert_eq!(config.get("window.width"), Some("800"));
+    assert_eq!(config.get("vsync"), None);
+
+    // Already current
+    assert!(!config.migrate(MIGRATIONS).unwrap());
+    // Written by a newer build
+    assert!(ConfigFile::new(3).migrate(MIGRATIONS).is_err());
+}
+
+#[test]
+fn test_failed_migration_stops_at_last_version() {
+    let migrations: &[ConfigMigration] = &[
+        |_| Ok(()),
+        |_| crate::engine_bail!("galaxy3d::test", "bad"),
+    ];
+    let mut config = ConfigFile::new(0);
+    assert!(config.migrate(migrations).is_err());
+    assert_eq!(config.version(), 1);
+}
+
+#[test]
+fn test_load_missing_migrates_and_saves() {
+    let dir = scratch_dir("config_load");
+    let path = dir.join(crate::utils::CONFIG_FILE_NAME);
+
+    // Missing file: empty, current version
+    let config = ConfigFile::load(&path, MIGRATIONS).unwrap();
+    assert_eq!(config, ConfigFile::new(2));
+
+    // Old file: migrated and saved back
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(&path, "version = 0\nwidth = 640\n").unwrap();
+    let config = ConfigFile::load(&path, MIGRATIONS).unwrap();
+    assert_eq!(config.get("window.width"), Some("640"));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "version = 2\nwindow.width = 640\n");
+
+    let _ = fs::remove_dir_all(&dir);
+}
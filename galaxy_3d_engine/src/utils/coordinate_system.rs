@@ -0,0 +1,170 @@
+/// Coordinate system conventions and conversion helpers.
+///
+/// The engine world is right-handed; its up axis is Y by default and can be
+/// switched to Z with `Engine::set_world_coordinate_system()` (physics
+/// engines, GIS, Blender-centric pipelines). Assets authored in another
+/// convention are converted once, at import time, with
+/// `convert_vertex_attributes()` / `flip_triangle_winding()` /
+/// `convert_transform()`, and cameras are built from the world convention
+/// with `look_at()`. Nothing is converted at render time.
+
+use glam::{Mat3, Mat4, Vec3};
+use crate::error::Result;
+use crate::graphics_device::{BufferFormat, IndexType, VertexLayout};
+
+/// World up axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum UpAxis {
+    /// +Y up (glTF, Maya, Unity, the engine default)
+    #[default]
+    Y,
+    /// +Z up (Blender, 3ds Max, Unreal, most physics engines)
+    Z,
+}
+
+/// Handedness of the basis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Handedness {
+    #[default]
+    Right,
+    Left,
+}
+
+/// Axis convention of a space (engine world, asset file, physics engine).
+///
+/// Conversions keep the X axis and map the up axes onto each other. The
+/// remaining axis is then fixed by the handedness: for Y-up spaces it is
+/// the back axis (+Z towards the viewer when right-handed), for Z-up spaces
+/// the forward axis (+Y away from the viewer when right-handed).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CoordinateSystem {
+    pub up: UpAxis,
+    pub handedness: Handedness,
+}
+
+impl CoordinateSystem {
+    /// Engine default; glTF, Maya
+    pub const Y_UP_RIGHT_HANDED: Self = Self { up: UpAxis::Y, handedness: Handedness::Right };
+    /// Blender, 3ds Max, most physics engines
+    pub const Z_UP_RIGHT_HANDED: Self = Self { up: UpAxis::Z, handedness: Handedness::Right };
+    /// Unity, Direct3D samples
+    pub const Y_UP_LEFT_HANDED: Self = Self { up: UpAxis::Y, handedness: Handedness::Left };
+    /// Unreal Engine
+    pub const Z_UP_LEFT_HANDED: Self = Self { up: UpAxis::Z, handedness: Handedness::Left };
+
+    /// Unit up vector of this convention
+    pub fn up_vector(&self) -> Vec3 {
+        match self.up {
+            UpAxis::Y => Vec3::Y,
+            UpAxis::Z => Vec3::Z,
+        }
+    }
+
+    /// Basis change from this convention to right-handed Y-up
+    fn to_y_up_right_handed(self) -> Mat3 {
+        match (self.up, self.handedness) {
+            (UpAxis::Y, Handedness::Right) => Mat3::IDENTITY,
+            (UpAxis::Y, Handedness::Left) => Mat3::from_cols(Vec3::X, Vec3::Y, Vec3::NEG_Z),
+            (UpAxis::Z, Handedness::Right) => Mat3::from_cols(Vec3::X, Vec3::NEG_Z, Vec3::Y),
+            (UpAxis::Z, Handedness::Left) => Mat3::from_cols(Vec3::X, Vec3::Z, Vec3::Y),
+        }
+    }
+
+    /// Matrix converting positions and directions from this convention to
+    /// `target` (a rotation, or a reflection when the handedness differs)
+    pub fn conversion_to(&self, target: &CoordinateSystem) -> Mat4 {
+        // Basis changes are orthonormal: the inverse is the transpose
+        Mat4::from_mat3(target.to_y_up_right_handed().transpose() * self.to_y_up_right_handed())
+    }
+
+    /// Whether converting to `target` mirrors geometry, which reverses the
+    /// triangle winding (see `flip_triangle_winding`)
+    pub fn is_mirrored_to(&self, target: &CoordinateSystem) -> bool {
+        self.handedness != target.handedness
+    }
+
+    /// Express a transform of this convention (e.g. a node matrix read from
+    /// an asset) in `target`
+    pub fn convert_transform(&self, target: &CoordinateSystem, transform: &Mat4) -> Mat4 {
+        let conversion = self.conversion_to(target);
+        conversion * *transform * conversion.transpose()
+    }
+
+    /// Convert vec3 vertex attributes (positions, normals, tangent xyz) in
+    /// place, from this convention to `target`.
+    ///
+    /// `locations` lists the attributes to convert; each must be
+    /// `R32G32B32_SFLOAT` or `R32G32B32A32_SFLOAT` (w is left untouched,
+    /// including the tangent handedness sign, which stays valid because the
+    /// winding is flipped alongside).
+    pub fn convert_vertex_attributes(
+        &self,
+        target: &CoordinateSystem,
+        vertex_data: &mut [u8],
+        layout: &VertexLayout,
+        locations: &[u32],
+    ) -> Result<()> {
+        let conversion = Mat3::from_mat4(self.conversion_to(target));
+        if conversion == Mat3::IDENTITY {
+            return Ok(());
+        }
+        for &location in locations {
+            let attribute = layout.attributes.iter().find(|a| a.location == location)
+                .ok_or_else(|| crate::engine_err!("galaxy3d::CoordinateSystem",
+                    "No vertex attribute at location {}", location))?;
+            if !matches!(attribute.format, BufferFormat::R32G32B32_SFLOAT | BufferFormat::R32G32B32A32_SFLOAT) {
+                crate::engine_bail!("galaxy3d::CoordinateSystem",
+                    "Vertex attribute at location {} is {:?}, expected a float vec3/vec4",
+                    location, attribute.format);
+            }
+            let binding = layout.bindings.iter().find(|b| b.binding == attribute.binding)
+                .ok_or_else(|| crate::engine_err!("galaxy3d::CoordinateSystem",
+                    "No vertex binding {} for location {}", attribute.binding, location))?;
+            // Only the binding-0 stream is interleaved in `vertex_data`
+            if binding.binding != 0 {
+                crate::engine_bail!("galaxy3d::CoordinateSystem",
+                    "Vertex attribute at location {} is not in binding 0", location);
+            }
+
+            let stride = binding.stride as usize;
+            let offset = attribute.offset as usize;
+            for vertex in vertex_data.chunks_exact_mut(stride) {
+                let bytes = &mut vertex[offset..offset + 12];
+                let value = Vec3::from_array(bytemuck::pod_read_unaligned::<[f32; 3]>(bytes));
+                bytes.copy_from_slice(bytemuck::bytes_of(&(conversion * value).to_array()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Reverse the winding of every triangle of a triangle-list index
+    /// buffer (swaps the last two indices of each triangle).
+    pub fn flip_triangle_winding(index_data: &mut [u8], index_type: IndexType) -> Result<()> {
+        let index_size = match index_type {
+            IndexType::U16 => 2,
+            IndexType::U32 => 4,
+        };
+        if !index_data.len().is_multiple_of(3 * index_size) {
+            crate::engine_bail!("galaxy3d::CoordinateSystem",
+                "Index data ({} bytes) is not a whole number of triangles", index_data.len());
+        }
+        for triangle in index_data.chunks_exact_mut(3 * index_size) {
+            let (first, rest) = triangle[index_size..].split_at_mut(index_size);
+            first.swap_with_slice(rest);
+        }
+        Ok(())
+    }
+
+    /// View matrix of a camera at `eye` looking at `target`, with this
+    /// convention's up vector
+    pub fn look_at(&self, eye: Vec3, target: Vec3) -> Mat4 {
+        match self.handedness {
+            Handedness::Right => Mat4::look_at_rh(eye, target, self.up_vector()),
+            Handedness::Left => Mat4::look_at_lh(eye, target, self.up_vector()),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "coordinate_system_tests.rs"]
+mod tests;
@@ -0,0 +1,133 @@
+use super::*;
+use crate::graphics_device::{VertexAttribute, VertexBinding, VertexInputRate};
+
+const ALL: [CoordinateSystem; 4] = [
+    CoordinateSystem::Y_UP_RIGHT_HANDED,
+    CoordinateSystem::Z_UP_RIGHT_HANDED,
+    CoordinateSystem::Y_UP_LEFT_HANDED,
+    CoordinateSystem::Z_UP_LEFT_HANDED,
+];
+
+fn assert_vec3_eq(a: Vec3, b: Vec3) {
+    assert!((a - b).length() < 1e-6, "{:?} != {:?}", a, b);
+}
+
+/// Position (vec3) + normal (vec3) + uv (vec2), binding 0
+fn layout() -> VertexLayout {
+    VertexLayout {
+        bindings: vec![VertexBinding { binding: 0, stride: 32, input_rate: VertexInputRate::Vertex }],
+        attributes: vec![
+            VertexAttribute { location: 0, binding: 0, format: BufferFormat::R32G32B32_SFLOAT, offset: 0 },
+            VertexAttribute { location: 1, binding: 0, format: BufferFormat::R32G32B32_SFLOAT, offset: 12 },
+            VertexAttribute { location: 2, binding: 0, format: BufferFormat::R32G32_SFLOAT, offset: 24 },
+        ],
+    }
+}
+
+// ============================================================================
+// Conversions
+// ============================================================================
+
+#[test]
+fn test_default_is_y_up_right_handed() {
+    assert_eq!(CoordinateSystem::default(), CoordinateSystem::Y_UP_RIGHT_HANDED);
+}
+
+#[test]
+fn test_up_vectors_map_onto_each_other() {
+    for from in ALL {
+        for to in ALL {
+            let converted = from.conversion_to(&to).transform_vector3(from.up_vector());
+            assert_vec3_eq(converted, to.up_vector());
+            // X is kept by every conversion
+            assert_vec3_eq(from.conversion_to(&to).transform_vector3(Vec3::X), Vec3::X);
+        }
+    }
+}
+
+#[test]
+fn test_z_up_to_y_up_reference_values() {
+    let m = CoordinateSystem::Z_UP_RIGHT_HANDED.conversion_to(&CoordinateSystem::Y_UP_RIGHT_HANDED);
+    // Blender forward (+Y) becomes -Z, the forward direction of a Y-up RH camera
+    assert_vec3_eq(m.transform_point3(Vec3::new(1.0, 2.0, 3.0)), Vec3::new(1.0, 3.0, -2.0));
+}
+
+#[test]
+fn test_conversion_round_trip_and_mirroring() {
+    for from in ALL {
+        for to in ALL {
+            let there = from.conversion_to(&to);
+            let back = to.conversion_to(&from);
+            assert!((back * there).abs_diff_eq(Mat4::IDENTITY, 1e-6));
+            let determinant = there.determinant();
+            assert_eq!(determinant < 0.0, from.is_mirrored_to(&to), "{:?} -> {:?}", from, to);
+        }
+    }
+}
+
+#[test]
+fn test_convert_transform_matches_converted_points() {
+    let from = CoordinateSystem::Z_UP_LEFT_HANDED;
+    let to = CoordinateSystem::Y_UP_RIGHT_HANDED;
+    let transform = Mat4::from_rotation_z(0.7) * Mat4::from_translation(Vec3::new(1.0, -2.0, 4.0));
+    let converted = from.convert_transform(&to, &transform);
+
+    let conversion = from.conversion_to(&to);
+    let p = Vec3::new(0.5, 3.0, -1.0);
+    assert_vec3_eq(converted.transform_point3(conversion.transform_point3(p)),
+        conversion.transform_point3(transform.transform_point3(p)));
+}
+
+// ============================================================================
+// Import helpers
+// ============================================================================
+
+#[test]
+fn test_convert_vertex_attributes_converts_selected_locations() {
+    let vertex: [f32; 8] = [1.0, 2.0, 3.0, 0.0, 0.0, 1.0, 0.25, 0.75];
+    let mut data = bytemuck::cast_slice(&[vertex, vertex]).to_vec();
+    CoordinateSystem::Z_UP_RIGHT_HANDED.convert_vertex_attributes(
+        &CoordinateSystem::Y_UP_RIGHT_HANDED, &mut data, &layout(), &[0, 1]).unwrap();
+
+    let floats: &[f32] = bytemuck::cast_slice(&data);
+    for v in floats.chunks_exact(8) {
+        assert_eq!(v, &[1.0, 3.0, -2.0, 0.0, 1.0, 0.0, 0.25, 0.75]);
+    }
+}
+
+#[test]
+fn test_convert_vertex_attributes_rejects_non_vec3_attribute() {
+    let mut data = vec![0u8; 32];
+    let from = CoordinateSystem::Z_UP_RIGHT_HANDED;
+    let to = CoordinateSystem::Y_UP_RIGHT_HANDED;
+    assert!(from.convert_vertex_attributes(&to, &mut data, &layout(), &[2]).is_err());
+    assert!(from.convert_vertex_attributes(&to, &mut data, &layout(), &[5]).is_err());
+}
+
+#[test]
+fn test_flip_triangle_winding() {
+    let mut u16_data = bytemuck::cast_slice(&[0u16, 1, 2, 3, 4, 5]).to_vec();
+    CoordinateSystem::flip_triangle_winding(&mut u16_data, IndexType::U16).unwrap();
+    assert_eq!(bytemuck::cast_slice::<u8, u16>(&u16_data), &[0, 2, 1, 3, 5, 4]);
+
+    let mut u32_data = bytemuck::cast_slice(&[7u32, 8, 9]).to_vec();
+    CoordinateSystem::flip_triangle_winding(&mut u32_data, IndexType::U32).unwrap();
+    assert_eq!(bytemuck::cast_slice::<u8, u32>(&u32_data), &[7, 9, 8]);
+
+    let mut partial = vec![0u8; 8];
+    assert!(CoordinateSystem::flip_triangle_winding(&mut partial, IndexType::U16).is_err());
+}
+
+// ============================================================================
+// Camera helpers
+// ============================================================================
+
+#[test]
+fn test_look_at_uses_convention_up_axis() {
+    let cs = CoordinateSystem::Z_UP_RIGHT_HANDED;
+    let view = cs.look_at(Vec3::new(0.0, -10.0, 0.0), Vec3::ZERO);
+    // World up (+Z) is screen up (+Y in view space)
+    assert_vec3_eq(view.transform_vector3(Vec3::Z), Vec3::Y);
+    // The target is straight ahead (-Z in a right-handed view space)
+    assert_vec3_eq(view.transform_point3(Vec3::ZERO), Vec3::new(0.0, 0.0, -10.0));
+}
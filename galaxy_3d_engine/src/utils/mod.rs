@@ -1,7 +1,19 @@
 //! Utility types shared across the engine.
 
+mod config_file;
+mod coordinate_system;
+mod platform;
 mod slot_allocator;
 mod swap_set;
 
+#[cfg(test)]
+pub(crate) mod test_helpers;
+
+pub use config_file::{ConfigFile, ConfigMigration, CONFIG_VERSION_KEY};
+pub use coordinate_system::{CoordinateSystem, UpAxis, Handedness};
+pub use platform::{
+    write_atomic, AppDirs, PlatformFamily, CONFIG_FILE_NAME, PIPELINE_CACHE_FILE_NAME,
+    SCREENSHOTS_DIR_NAME,
+};
 pub use slot_allocator::SlotAllocator;
 pub(crate) use swap_set::SwapSet;
@@ -0,0 +1,229 @@
+/// Platform directories and atomic file writes.
+///
+/// `AppDirs` resolves where an application keeps its files, following each
+/// platform's convention:
+///
+/// | | Config | Data | Cache |
+/// |---|---|---|---|
+/// | Windows | `%APPDATA%\app` | `%APPDATA%\app` | `%LOCALAPPDATA%\app\cache` |
+/// | macOS | `~/Library/Application Support/app` | same | `~/Library/Caches/app` |
+/// | Linux / other Unix | `$XDG_CONFIG_HOME/app` (`~/.config/app`) | `$XDG_DATA_HOME/app` (`~/.local/share/app`) | `$XDG_CACHE_HOME/app` (`~/.cache/app`) |
+///
+/// The engine files live under these directories (`config_file()`,
+/// `pipeline_cache_file()`, `screenshots_dir()`). Nothing is created until
+/// `create_all()` or `write_atomic()` is called.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use crate::error::Result;
+use crate::engine_bail;
+
+/// File name of the application config, in the config directory
+pub const CONFIG_FILE_NAME: &str = "config.cfg";
+
+/// File name of the pipeline cache, in the cache directory
+pub const PIPELINE_CACHE_FILE_NAME: &str = "pipeline_cache.bin";
+
+/// Name of the screenshots directory, in the data directory
+pub const SCREENSHOTS_DIR_NAME: &str = "screenshots";
+
+/// Suffix of the temporary file written by `write_atomic()`
+const ATOMIC_TEMP_SUFFIX: &str = ".tmp";
+
+/// Operating system family, for the directory conventions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlatformFamily {
+    Windows,
+    MacOs,
+    /// Linux and the other Unix systems (XDG base directories)
+    Unix,
+}
+
+impl PlatformFamily {
+    /// Family of the running system
+    pub fn current() -> Self {
+        if cfg!(target_os = "windows") {
+            Self::Windows
+        } else if cfg!(target_os = "macos") {
+            Self::MacOs
+        } else {
+            Self::Unix
+        }
+    }
+}
+
+/// Per-application directories (see module docs).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppDirs {
+    config: PathBuf,
+    data: PathBuf,
+    cache: PathBuf,
+}
+
+impl AppDirs {
+    /// Directories of `app_name` on the running system, from the process
+    /// environment.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `app_name` is not a plain directory name, or if
+    /// the environment does not locate the user's home.
+    pub fn new(app_name: &str) -> Result<Self> {
+        Self::resolve(PlatformFamily::current(), app_name, |name| std::env::var_os(name).map(PathBuf::from))
+    }
+
+    /// Directories of `app_name` for `platform`, reading environment
+    /// variables through `env` (empty values count as unset).
+    ///
+    /// # Errors
+    ///
+    /// Same as `new()`.
+    pub fn resolve(
+        platform: PlatformFamily,
+        app_name: &str,
+        env: impl Fn(&str) -> Option<PathBuf>,
+    ) -> Result<Self> {
+        if app_name.is_empty()
+            || app_name == "."
+            || app_name == ".."
+            || app_name.contains(['/', '\\'])
+        {
+            engine_bail!("galaxy3d::AppDirs", "Invalid application name '{}'", app_name);
+        }
+        let var = |name: &str| env(name).filter(|path| !path.as_os_str().is_empty());
+        let home = || var("HOME").or_else(|| var("USERPROFILE"));
+
+        let (config, data, cache) = match platform {
+            PlatformFamily::Windows => {
+                let roaming = var("APPDATA")
+                    .or_else(|| home().map(|h| h.join("AppData").join("Roaming")));
+                let local = var("LOCALAPPDATA")
+                    .or_else(|| home().map(|h| h.join("AppData").join("Local")));
+                match (roaming, local) {
+                    (Some(roaming), Some(local)) => {
+                        let app = roaming.join(app_name);
+                        (app.clone(), app, local.join(app_name).join("cache"))
+                    }
+                    _ => engine_bail!("galaxy3d::AppDirs",
+                        "Neither APPDATA / LOCALAPPDATA nor a home directory is set"),
+                }
+            }
+            PlatformFamily::MacOs => {
+                let Some(home) = home() else {
+                    engine_bail!("galaxy3d::AppDirs", "HOME is not set");
+                };
+                let library = home.join("Library");
+                let app = library.join("Application Support").join(app_name);
+                (app.clone(), app, library.join("Caches").join(app_name))
+            }
+            PlatformFamily::Unix => {
+                let xdg = |name: &str, fallback: &[&str]| {
+                    var(name).or_else(|| home().map(|h| fallback.iter().fold(h, |p, c| p.join(c))))
+                };
+                match (
+                    xdg("XDG_CONFIG_HOME", &[".config"]),
+                    xdg("XDG_DATA_HOME", &[".local", "share"]),
+                    xdg("XDG_CACHE_HOME", &[".cache"]),
+                ) {
+                    (Some(config), Some(data), Some(cache)) => {
+                        (config.join(app_name), data.join(app_name), cache.join(app_name))
+                    }
+                    _ => engine_bail!("galaxy3d::AppDirs", "HOME is not set"),
+                }
+            }
+        };
+        Ok(Self { config, data, cache })
+    }
+
+    // ===== ACCESSORS =====
+
+    /// Settings written by the application
+    pub fn config_dir(&self) -> &Path {
+        &self.config
+    }
+
+    /// User data (saves, screenshots)
+    pub fn data_dir(&self) -> &Path {
+        &self.data
+    }
+
+    /// Files that can be rebuilt (pipeline cache, ...)
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache
+    }
+
+    /// Application config file (`ConfigFile`)
+    pub fn config_file(&self) -> PathBuf {
+        self.config.join(CONFIG_FILE_NAME)
+    }
+
+    /// Pipeline cache blob
+    pub fn pipeline_cache_file(&self) -> PathBuf {
+        self.cache.join(PIPELINE_CACHE_FILE_NAME)
+    }
+
+    /// Screenshots folder
+    pub fn screenshots_dir(&self) -> PathBuf {
+        self.data.join(SCREENSHOTS_DIR_NAME)
+    }
+
+    /// Create the config, data and cache directories (and their parents)
+    /// if missing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a directory cannot be created.
+    pub fn create_all(&self) -> Result<()> {
+        for dir in [&self.config, &self.data, &self.cache] {
+            create_dir(dir)?;
+        }
+        Ok(())
+    }
+}
+
+/// Replace the content of `path` with `bytes` so that readers see either
+/// the old or the new file, never a partial one (crash or power loss
+/// during the write).
+///
+/// The bytes go to a temporary file next to `path`, flushed to disk, then
+/// renamed over `path`. Missing parent directories are created.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written or renamed; the original
+/// file is left untouched and the temporary file removed.
+pub fn write_atomic(path: impl AsRef<Path>, bytes: &[u8]) -> Result<()> {
+    let path = path.as_ref();
+    let Some(file_name) = path.file_name() else {
+        engine_bail!("galaxy3d::write_atomic", "'{}' is not a file path", path.display());
+    };
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        create_dir(parent)?;
+    }
+    let mut temp_name = file_name.to_os_string();
+    temp_name.push(ATOMIC_TEMP_SUFFIX);
+    let temp = path.with_file_name(temp_name);
+
+    let written = fs::File::create(&temp)
+        .and_then(|mut file| {
+            file.write_all(bytes)?;
+            file.sync_all()
+        })
+        .and_then(|()| fs::rename(&temp, path));
+    if let Err(e) = written {
+        let _ = fs::remove_file(&temp);
+        engine_bail!("galaxy3d::write_atomic", "Failed to write '{}': {}", path.display(), e);
+    }
+    Ok(())
+}
+
+fn create_dir(dir: &Path) -> Result<()> {
+    fs::create_dir_all(dir).map_err(|e| {
+        crate::engine_err!("galaxy3d::AppDirs", "Failed to create '{}': {}", dir.display(), e)
+    })
+}
+
+#[cfg(test)]
+#[path = "platform_tests.rs"]
+mod tests;
@@ -0,0 +1,111 @@
+use super::*;
+use std::collections::HashMap;
+use crate::utils::test_helpers::scratch_dir;
+
+/// Environment lookup backed by a fixed set of variables
+fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<PathBuf> {
+    let vars: HashMap<String, PathBuf> = vars.iter()
+        .map(|(k, v)| (k.to_string(), PathBuf::from(v)))
+        .collect();
+    move |name| vars.get(name).cloned()
+}
+
+// ============================================================================
+// AppDirs
+// ============================================================================
+
+#[test]
+fn test_unix_dirs_fall_back_to_home() {
+    let dirs = AppDirs::resolve(PlatformFamily::Unix, "game", env(&[("HOME", "/home/u")])).unwrap();
+    assert_eq!(dirs.config_dir(), Path::new("/home/u/.config/game"));
+    assert_eq!(dirs.data_dir(), Path::new("/home/u/.local/share/game"));
+    assert_eq!(dirs.cache_dir(), Path::new("/home/u/.cache/game"));
+    assert_eq!(dirs.config_file(), Path::new("/home/u/.config/game").join(CONFIG_FILE_NAME));
+    assert_eq!(dirs.pipeline_cache_file(), Path::new("/home/u/.cache/game").join(PIPELINE_CACHE_FILE_NAME));
+    assert_eq!(dirs.screenshots_dir(), Path::new("/home/u/.local/share/game").join(SCREENSHOTS_DIR_NAME));
+}
+
+#[test]
+fn test_unix_dirs_use_xdg_variables() {
+    let dirs = AppDirs::resolve(PlatformFamily::Unix, "game", env(&[
+        ("HOME", "/home/u"),
+        ("XDG_CONFIG_HOME", "/cfg"),
+        ("XDG_DATA_HOME", ""),
+        ("XDG_CACHE_HOME", "/tmp/cache"),
+    ])).unwrap();
+    assert_eq!(dirs.config_dir(), Path::new("/cfg/game"));
+    // Empty counts as unset
+    assert_eq!(dirs.data_dir(), Path::new("/home/u/.local/share/game"));
+    assert_eq!(dirs.cache_dir(), Path::new("/tmp/cache/game"));
+}
+
+#[test]
+fn test_windows_and_macos_dirs() {
+    let dirs = AppDirs::resolve(PlatformFamily::Windows, "game", env(&[
+        ("APPDATA", "C:/Users/u/AppData/Roaming"),
+        ("LOCALAPPDATA", "C:/Users/u/AppData/Local"),
+    ])).unwrap();
+    assert_eq!(dirs.config_dir(), Path::new("C:/Users/u/AppData/Roaming/game"));
+    assert_eq!(dirs.data_dir(), dirs.config_dir());
+    assert_eq!(dirs.cache_dir(), Path::new("C:/Users/u/AppData/Local/game/cache"));
+
+    let dirs = AppDirs::resolve(PlatformFamily::MacOs, "game", env(&[("HOME", "/Users/u")])).unwrap();
+    assert_eq!(dirs.config_dir(), Path::new("/Users/u/Library/Application Support/game"));
+    assert_eq!(dirs.cache_dir(), Path::new("/Users/u/Library/Caches/game"));
+}
+
+#[test]
+fn test_resolve_errors() {
+    assert!(AppDirs::resolve(PlatformFamily::Unix, "game", env(&[])).is_err());
+    assert!(AppDirs::resolve(PlatformFamily::MacOs, "game", env(&[])).is_err());
+    for name in ["", "..", "a/b", "a\\b"] {
+        assert!(AppDirs::resolve(PlatformFamily::Unix, name, env(&[("HOME", "/home/u")])).is_err());
+    }
+}
+
+// ============================================================================
+// Atomic writes
+// ============================================================================
+
+#[test]
+fn test_write_atomic_creates_and_replaces() {
+    let dir = scratch_dir("platform_write_atomic");
+    let path = dir.join("nested").join("settings.cfg");
+
+    write_atomic(&path, b"first").unwrap();
+    assert_eq!(fs::read(&path).unwrap(), b"first");
+    write_atomic(&path, b"second").unwrap();
+    assert_eq!(fs::read(&path).unwrap(), b"second");
+    // No temporary file left behind
+    assert_eq!(fs::read_dir(path.parent().unwrap()).unwrap().count(), 1);
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_write_atomic_failure_keeps_original() {
+    let dir = scratch_dir("platform_write_atomic_failure");
+    // A directory in place of the file: the rename fails
+    let path = dir.join("taken");
+    fs::create_dir_all(path.join("child")).unwrap();
+
+    assert!(write_atomic(&path, b"data").is_err());
+    assert!(path.join("child").is_dir());
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_create_all() {
+    let dir = scratch_dir("platform_create_all");
+    let dirs = AppDirs::resolve(PlatformFamily::Unix, "game", env(&[
+        ("HOME", dir.to_str().unwrap()),
+    ])).unwrap();
+    dirs.create_all().unwrap();
+    assert!(dirs.config_dir().is_dir());
+    assert!(dirs.data_dir().is_dir());
+    assert!(dirs.cache_dir().is_dir());
+
+    let _ = fs::remove_dir_all(&dir);
+}
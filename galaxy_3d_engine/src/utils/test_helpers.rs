@@ -0,0 +1,15 @@
+//! Helpers shared by tests that touch the file system.
+
+use std::fs;
+use std::path::PathBuf;
+
+/// Fresh, empty directory under the system temp dir. `name` must be unique
+/// among the tests (e.g. "<module>_<test>"); the process id keeps
+/// concurrent test runs apart.
+pub(crate) fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir()
+        .join(format!("galaxy3d_{}_{}", std::process::id(), name));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
@@ -108,6 +108,7 @@ fn test_integration_create_pipeline() {
         multisample: Default::default(),
         color_formats: vec![],
         depth_format: None,
+        engine_features: Default::default(),
     };
 
     let result = rm.create_pipeline("test_pipeline".to_string(), desc, &mut *graphics_device_lock);
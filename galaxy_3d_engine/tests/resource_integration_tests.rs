@@ -39,6 +39,7 @@ fn test_integration_create_texture_with_vulkan() {
         texture: RenderTextureDesc {
             width: 256,
             height: 256,
+            depth: 1,
             format: TextureFormat::R8G8B8A8_UNORM,
             usage: TextureUsage::Sampled,
             array_layers: 1,
@@ -46,6 +47,7 @@ fn test_integration_create_texture_with_vulkan() {
             data: None,
             texture_type: TextureType::Tex2D,
             sample_count: SampleCount::S1,
+            debug_name: None,
         },
         layers: vec![
             LayerDesc {
@@ -118,10 +120,11 @@ fn test_integration_create_geometry_with_vulkan() {
     let desc = GeometryDesc {
         name: "test_geom".to_string(),
         graphics_device: graphics_device_arc.clone(),
-        vertex_data,
-        index_data: Some(index_data),
+        vertex_data: vertex_data.into(),
+        index_data: Some(index_data.into()),
         vertex_layout,
         index_type: IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![
             GeometryMeshDesc {
                 name: "quad".to_string(),
@@ -175,6 +178,7 @@ fn test_integration_multiple_resources() {
             texture: RenderTextureDesc {
                 width: 128,
                 height: 128,
+                depth: 1,
                 format: TextureFormat::R8G8B8A8_UNORM,
                 usage: TextureUsage::Sampled,
                 array_layers: 1,
@@ -182,6 +186,7 @@ fn test_integration_multiple_resources() {
                 data: None,
                 texture_type: TextureType::Tex2D,
                 sample_count: SampleCount::S1,
+                debug_name: None,
             },
             layers: vec![
                 LayerDesc {
@@ -268,10 +273,11 @@ fn test_integration_geometry_with_multiple_lods() {
     let desc = GeometryDesc {
         name: "lod_geom".to_string(),
         graphics_device: graphics_device_arc.clone(),
-        vertex_data,
-        index_data: Some(index_data),
+        vertex_data: vertex_data.into(),
+        index_data: Some(index_data.into()),
         vertex_layout,
         index_type: IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![
             GeometryMeshDesc {
                 name: "main_mesh".to_string(),
@@ -382,10 +388,11 @@ fn test_integration_geometry_with_multiple_submeshes() {
     let desc = GeometryDesc {
         name: "multi_submesh".to_string(),
         graphics_device: graphics_device_arc.clone(),
-        vertex_data,
-        index_data: Some(index_data),
+        vertex_data: vertex_data.into(),
+        index_data: Some(index_data.into()),
         vertex_layout,
         index_type: IndexType::U16,
+        morph_targets: Vec::new(),
         meshes: vec![
             GeometryMeshDesc {
                 name: "character".to_string(),
@@ -461,6 +468,7 @@ fn test_integration_many_resources_stress_test() {
             texture: RenderTextureDesc {
                 width: 64,
                 height: 64,
+                depth: 1,
                 format: TextureFormat::R8G8B8A8_UNORM,
                 usage: TextureUsage::Sampled,
                 array_layers: 1,
@@ -468,6 +476,7 @@ fn test_integration_many_resources_stress_test() {
                 data: None,
                 texture_type: TextureType::Tex2D,
                 sample_count: SampleCount::S1,
+                debug_name: None,
             },
             layers: vec![
                 LayerDesc {
@@ -517,10 +526,11 @@ fn test_integration_many_resources_stress_test() {
         let desc = GeometryDesc {
             name: format!("geom_{}", i),
             graphics_device: graphics_device_arc.clone(),
-            vertex_data,
-            index_data: Some(index_data),
+            vertex_data: vertex_data.into(),
+            index_data: Some(index_data.into()),
             vertex_layout,
             index_type: IndexType::U16,
+            morph_targets: Vec::new(),
             meshes: vec![
                 GeometryMeshDesc {
                     name: "quad".to_string(),
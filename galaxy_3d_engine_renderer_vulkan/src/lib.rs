@@ -19,17 +19,26 @@ mod vulkan_pipeline;
 #[cfg(feature = "vulkan-validation")]
 mod debug;
 mod vulkan_sync;
+mod vulkan_stats;
+mod vulkan_debug_names;
+mod vulkan_deletion_queue;
+mod vulkan_staging_belt;
+mod vulkan_queue_sharing;
+mod vulkan_resource_state;
 mod vulkan_command_list;
 mod vulkan_render_pass;
 mod vulkan_swapchain;
 mod vulkan_binding_group;
+mod vulkan_descriptor_pool;
 mod vulkan_sampler;
 mod vulkan_frame_buffer;
+mod vulkan_ray_tracing;
+mod vulkan_query;
 
 // Main galaxy3d namespace module
 pub mod galaxy3d {
     // VulkanGraphicsDevice at root of galaxy3d
-    pub use crate::vulkan::VulkanGraphicsDevice;
+    pub use crate::vulkan::{VulkanGraphicsDevice, VULKAN_BACKEND_NAME};
 
     // Vulkan sub-module with all implementations
     pub mod vulkan {
@@ -42,6 +51,8 @@ pub mod galaxy3d {
         pub use crate::vulkan_swapchain::Swapchain;
         pub use crate::vulkan_binding_group::BindingGroup;
         pub use crate::vulkan_frame_buffer::Framebuffer;
+        pub use crate::vulkan_ray_tracing::AccelerationStructure;
+        pub use crate::vulkan_query::QueryPool;
     }
 
     // Debug sub-module (validation layers / debug messenger)
@@ -51,8 +62,18 @@ pub mod galaxy3d {
     }
 }
 
-/// Register the Vulkan backend with the plugin system
+/// Register the Vulkan backend with the plugin system, under
+/// `VULKAN_BACKEND_NAME`.
+///
+/// Call it before `Engine::initialize_with_config()`.
 pub fn register() {
-    // TODO: Implement plugin registration
-    // galaxy_3d_engine::register_graphics_device_plugin("vulkan", create_vulkan_graphics_device);
+    galaxy_3d_engine::galaxy3d::render::register_graphics_device_plugin(
+        galaxy3d::VULKAN_BACKEND_NAME,
+        |window, config| {
+            let device = galaxy3d::VulkanGraphicsDevice::new(window, config)?;
+            let device: std::sync::Arc<std::sync::Mutex<dyn galaxy_3d_engine::galaxy3d::GraphicsDevice>> =
+                std::sync::Arc::new(std::sync::Mutex::new(device));
+            Ok(device)
+        },
+    );
 }
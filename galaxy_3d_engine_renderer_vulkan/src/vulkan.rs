@@ -8,18 +8,22 @@ use galaxy_3d_engine::galaxy3d::render::{
     Shader as RendererShader, Pipeline as RendererPipeline,
     BindingGroup as RendererBindingGroup,
     Framebuffer as RendererFramebuffer, FramebufferDesc, FramebufferAttachment,
-    RenderPassDesc,
-    TextureDesc, TextureData, TextureInfo, TextureType, BufferDesc, ShaderDesc, PipelineDesc,
-    BindingResource, BindingType, BindingGroupLayoutDesc, ShaderStageFlags,
-    ReflectedBinding, ReflectedPushConstant, ReflectedMember, ReflectedMemberType,
+    RenderPassDesc, AttachmentDesc,
+    TextureDesc, TextureData, TextureInfo, TextureType, CUBE_FACE_COUNT, BufferDesc, ShaderDesc, PipelineDesc,
+    BindingResource, BindingType, BindingGroupLayoutDesc, ShaderStageFlags, validate_depth_sampling,
+    validate_storage_images,
+    ReflectedBinding, ReflectedPushConstant, ReflectedVertexInput, ReflectedMember, ReflectedMemberType,
     ScalarKind, PipelineReflection,
     TextureFormat, BufferFormat, ShaderStage, BufferUsage, PrimitiveTopology,
     ImageLayout,
-    GraphicsDeviceStats, VertexInputRate,
+    GraphicsDeviceStats, DescriptorPoolUsage, VertexInputRate,
     Config, BindlessConfig, TextureUsage, SamplerType,
     MipmapMode, ManualMipmapData,
     PolygonMode,
-    BlendFactor, BlendOp, SampleCount,
+    BlendFactor, BlendOp, SampleCount, EngineFeatures,
+    AccelerationStructure as RendererAccelerationStructure,
+    BlasDesc, TlasDesc, RayTracingPipelineDesc, ShaderBindingTableLayout,
+    QueryPool as RendererQueryPool, QueryPoolDesc, QueryType,
 };
 #[cfg(feature = "vulkan-validation")]
 use galaxy_3d_engine::galaxy3d::render::DebugSeverity;
@@ -31,18 +35,21 @@ use std::mem::ManuallyDrop;
 use gpu_allocator::vulkan::{Allocator, AllocatorCreateDesc};
 use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
 use winit::window::Window;
-use galaxy_3d_engine::{engine_info, engine_error, engine_bail, engine_bail_warn, engine_err, engine_warn_err};
+use galaxy_3d_engine::{engine_info, engine_error, engine_bail, engine_bail_warn, engine_err, engine_backend_err};
 
 use crate::vulkan_texture::Texture;
 use crate::vulkan_buffer::Buffer;
 use crate::vulkan_shader::Shader;
 use crate::vulkan_pipeline::Pipeline;
 use crate::vulkan_command_list::{CommandList, color_write_mask_to_vk};
-use crate::vulkan_render_pass::RenderPass;
+use crate::vulkan_render_pass::{RenderPass, create_legacy_render_pass};
 use crate::vulkan_swapchain::Swapchain;
 use crate::vulkan_sampler::SamplerCache;
 use crate::vulkan_binding_group::BindingGroup;
+use crate::vulkan_descriptor_pool::DescriptorPoolSet;
 use crate::vulkan_context::GpuContext;
+use crate::vulkan_ray_tracing::{RayTracingFunctions, ShaderBindingTable};
+use crate::vulkan_query::QueryPool;
 
 /// Runtime capabilities for optional dynamic states (EXT_extended_dynamic_state3 / EXT_color_write_enable).
 ///
@@ -58,6 +65,36 @@ pub struct DynamicStateCaps {
     pub color_write_enable: bool,
 }
 
+/// Device extensions required below Vulkan 1.3, where the backend takes the
+/// legacy `VkRenderPass` path: they provide what 1.3 made core.
+const LEGACY_DEVICE_EXTENSIONS: [&std::ffi::CStr; 3] = [
+    ash::khr::synchronization2::NAME,
+    ash::ext::extended_dynamic_state::NAME,
+    ash::ext::extended_dynamic_state2::NAME,
+];
+
+/// Load the functions of a device older than Vulkan 1.3.
+///
+/// The 1.3 functions the backend calls (`vkCmdPipelineBarrier2`,
+/// `vkQueueSubmit2`, `vkCmdSetCullMode`, ...) then only exist under their
+/// `KHR`/`EXT` names: each core name falls back to its suffixed variants.
+unsafe fn load_legacy_device(instance: &ash::Instance, device: vk::Device) -> ash::Device {
+    ash::Device::load_with(
+        |name| {
+            let function = instance.get_device_proc_addr(device, name.as_ptr()).or_else(|| {
+                ["KHR", "EXT"].iter().find_map(|suffix| {
+                    let mut suffixed = name.to_bytes().to_vec();
+                    suffixed.extend_from_slice(suffix.as_bytes());
+                    let suffixed = CString::new(suffixed).ok()?;
+                    instance.get_device_proc_addr(device, suffixed.as_ptr())
+                })
+            });
+            function.map_or(std::ptr::null(), |f| f as *const std::ffi::c_void)
+        },
+        device,
+    )
+}
+
 // ============================================================================
 // Bindless State
 // ============================================================================
@@ -69,20 +106,21 @@ pub struct DynamicStateCaps {
 /// A single persistent descriptor set (set 0) contains all bindless tables:
 ///   binding 0: texture2D[]      (max 4096)
 ///   binding 1: texture2DArray[] (max 64)
-///   binding 2: textureCube[]    (max 64)   — future
-///   binding 3: texture3D[]      (max 16)   — future
+///   binding 2: textureCube[]    (max 64)
+///   binding 3: texture3D[]      (max 16)
 ///   binding 4: sampler[]        (6 fixed)
+///   binding 5: textureCubeArray[] (max 16)
 ///
 /// Textures are registered/unregistered via SlotAllocators.
 /// Samplers are filled at init and never modified.
 /// The descriptor set persists for the lifetime of the GraphicsDevice.
-#[allow(dead_code)] // Cube/3D allocators reserved for future texture types
 struct BindlessState {
     // Allocators (one per texture type, shared with textures for Drop)
-    texture_2d_allocator:    Arc<Mutex<SlotAllocator>>,
-    texture_cube_allocator:  Arc<Mutex<SlotAllocator>>,
-    texture_3d_allocator:    Arc<Mutex<SlotAllocator>>,
-    texture_array_allocator: Arc<Mutex<SlotAllocator>>,
+    texture_2d_allocator:         Arc<Mutex<SlotAllocator>>,
+    texture_cube_allocator:       Arc<Mutex<SlotAllocator>>,
+    texture_3d_allocator:         Arc<Mutex<SlotAllocator>>,
+    texture_array_allocator:      Arc<Mutex<SlotAllocator>>,
+    texture_cube_array_allocator: Arc<Mutex<SlotAllocator>>,
 
     // Single descriptor set containing all bindless bindings
     descriptor_set: vk::DescriptorSet,
@@ -92,12 +130,16 @@ struct BindlessState {
     pool: vk::DescriptorPool,
 }
 
+/// Backend name matched against `Config::backend`
+pub const VULKAN_BACKEND_NAME: &str = "vulkan";
+
 /// Binding indices within the bindless descriptor set (set 0)
 const BINDLESS_BINDING_TEXTURE_2D:    u32 = 0;
 const BINDLESS_BINDING_TEXTURE_ARRAY: u32 = 1;
 const BINDLESS_BINDING_TEXTURE_CUBE:  u32 = 2;
 const BINDLESS_BINDING_TEXTURE_3D:    u32 = 3;
 const BINDLESS_BINDING_SAMPLER:       u32 = 4;
+const BINDLESS_BINDING_TEXTURE_CUBE_ARRAY: u32 = 5;
 
 impl BindlessState {
     /// Create the bindless state: pool, layout, descriptor set, and fill sampler table.
@@ -110,10 +152,11 @@ impl BindlessState {
         let max_cube = config.max_texture_cube;
         let max_3d = config.max_texture_3d;
         let max_array = config.max_texture_2d_array;
+        let max_cube_array = config.max_texture_cube_array;
         let sampler_count = 6u32; // SamplerType variant count
 
         // --- Pool ---
-        let total_sampled_images = max_2d + max_cube + max_3d + max_array;
+        let total_sampled_images = max_2d + max_cube + max_3d + max_array + max_cube_array;
         let pool_sizes = [
             vk::DescriptorPoolSize {
                 ty: vk::DescriptorType::SAMPLED_IMAGE,
@@ -131,9 +174,9 @@ impl BindlessState {
             .pool_sizes(&pool_sizes);
 
         let pool = device.create_descriptor_pool(&pool_info, None)
-            .map_err(|e| engine_err!("galaxy3d::vulkan::bindless", "Failed to create bindless descriptor pool: {:?}", e))?;
+            .map_err(|e| engine_backend_err!("galaxy3d::vulkan::bindless", e.as_raw(), "Failed to create bindless descriptor pool: {:?}", e))?;
 
-        // --- Layout: 5 bindings in one descriptor set ---
+        // --- Layout: 6 bindings in one descriptor set ---
         let bindings = [
             // binding 0: texture2D[]
             vk::DescriptorSetLayoutBinding::default()
@@ -165,6 +208,12 @@ impl BindlessState {
                 .descriptor_type(vk::DescriptorType::SAMPLER)
                 .descriptor_count(sampler_count)
                 .stage_flags(vk::ShaderStageFlags::ALL),
+            // binding 5: textureCubeArray[]
+            vk::DescriptorSetLayoutBinding::default()
+                .binding(BINDLESS_BINDING_TEXTURE_CUBE_ARRAY)
+                .descriptor_type(vk::DescriptorType::SAMPLED_IMAGE)
+                .descriptor_count(max_cube_array)
+                .stage_flags(vk::ShaderStageFlags::ALL),
         ];
 
         // All bindings are PARTIALLY_BOUND + UPDATE_AFTER_BIND
@@ -174,6 +223,7 @@ impl BindlessState {
             vk::DescriptorBindingFlags::PARTIALLY_BOUND | vk::DescriptorBindingFlags::UPDATE_AFTER_BIND,
             vk::DescriptorBindingFlags::PARTIALLY_BOUND | vk::DescriptorBindingFlags::UPDATE_AFTER_BIND,
             vk::DescriptorBindingFlags::PARTIALLY_BOUND | vk::DescriptorBindingFlags::UPDATE_AFTER_BIND,
+            vk::DescriptorBindingFlags::PARTIALLY_BOUND | vk::DescriptorBindingFlags::UPDATE_AFTER_BIND,
         ];
 
         let mut flags_info = vk::DescriptorSetLayoutBindingFlagsCreateInfo::default()
@@ -185,7 +235,7 @@ impl BindlessState {
             .push_next(&mut flags_info);
 
         let layout = device.create_descriptor_set_layout(&layout_info, None)
-            .map_err(|e| engine_err!("galaxy3d::vulkan::bindless", "Failed to create bindless layout: {:?}", e))?;
+            .map_err(|e| engine_backend_err!("galaxy3d::vulkan::bindless", e.as_raw(), "Failed to create bindless layout: {:?}", e))?;
 
         // --- Allocate the single descriptor set ---
         let layouts = [layout];
@@ -194,7 +244,7 @@ impl BindlessState {
             .set_layouts(&layouts);
 
         let sets = device.allocate_descriptor_sets(&alloc_info)
-            .map_err(|e| engine_err!("galaxy3d::vulkan::bindless", "Failed to allocate bindless descriptor set: {:?}", e))?;
+            .map_err(|e| engine_backend_err!("galaxy3d::vulkan::bindless", e.as_raw(), "Failed to allocate bindless descriptor set: {:?}", e))?;
         let descriptor_set = sets[0];
 
         // --- Fill sampler table (binding 4) with all SamplerType variants ---
@@ -233,6 +283,7 @@ impl BindlessState {
             texture_cube_allocator: Arc::new(Mutex::new(SlotAllocator::new())),
             texture_3d_allocator: Arc::new(Mutex::new(SlotAllocator::new())),
             texture_array_allocator: Arc::new(Mutex::new(SlotAllocator::new())),
+            texture_cube_array_allocator: Arc::new(Mutex::new(SlotAllocator::new())),
             descriptor_set,
             layout,
             pool,
@@ -250,6 +301,11 @@ impl BindlessState {
         let (allocator, binding) = match texture_type {
             TextureType::Tex2D   => (&self.texture_2d_allocator, BINDLESS_BINDING_TEXTURE_2D),
             TextureType::Array2D => (&self.texture_array_allocator, BINDLESS_BINDING_TEXTURE_ARRAY),
+            TextureType::Cube => (&self.texture_cube_allocator, BINDLESS_BINDING_TEXTURE_CUBE),
+            TextureType::CubeArray => {
+                (&self.texture_cube_array_allocator, BINDLESS_BINDING_TEXTURE_CUBE_ARRAY)
+            }
+            TextureType::Tex3D => (&self.texture_3d_allocator, BINDLESS_BINDING_TEXTURE_3D),
         };
 
         let index = allocator.lock().unwrap().alloc();
@@ -297,7 +353,6 @@ pub struct VulkanGraphicsDevice {
     graphics_queue_family: u32,
     /// Present queue (may be same as graphics)
     present_queue: vk::Queue,
-    #[allow(dead_code)]
     present_queue_family: u32,
 
     /// GPU memory allocator reference (stored in GpuContext)
@@ -308,7 +363,10 @@ pub struct VulkanGraphicsDevice {
     current_submit_fence: usize,
 
     /// Descriptor pools for binding group allocation (grows dynamically when exhausted)
-    descriptor_pools: Mutex<Vec<vk::DescriptorPool>>,
+    descriptor_pools: Mutex<DescriptorPoolSet>,
+    /// Descriptor pools of the transient binding groups, reset by
+    /// `reset_transient_pools`
+    transient_descriptor_pools: Mutex<DescriptorPoolSet>,
     /// Internal sampler cache (creates VkSampler on first use, behind Mutex for &self access)
     sampler_cache: Mutex<SamplerCache>,
 
@@ -318,6 +376,25 @@ pub struct VulkanGraphicsDevice {
 
     /// Bindless texture and sampler tables
     bindless_state: BindlessState,
+    /// `imageCubeArray` feature enabled (TextureType::CubeArray)
+    image_cube_array: bool,
+    /// `pipelineStatisticsQuery` feature enabled (QueryType::PipelineStatistics)
+    pipeline_statistics_query: bool,
+    /// `occlusionQueryPrecise` feature enabled (exact occlusion sample counts)
+    occlusion_query_precise: bool,
+    /// VK_EXT_mesh_shader functions (None when the device lacks task or
+    /// mesh shaders)
+    mesh_shader: Option<ash::ext::mesh_shader::Device>,
+    /// Ray tracing functions and limits (None unless `Config::ray_tracing`
+    /// is set and the device supports it)
+    ray_tracing: Option<RayTracingFunctions>,
+    /// Dynamic rendering (Vulkan 1.3 device); false on the legacy
+    /// `VkRenderPass`/`VkFramebuffer` path
+    dynamic_rendering: bool,
+    /// `Config::validate_barriers`, passed to the command lists
+    validate_barriers: bool,
+    /// `minUniformBufferOffsetAlignment` (dynamic uniform buffer offsets)
+    uniform_buffer_alignment: u64,
 }
 
 impl VulkanGraphicsDevice {
@@ -342,12 +419,15 @@ impl VulkanGraphicsDevice {
                     true,
                     u64::MAX,
                 )
-                .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to wait for submit fence: {:?}", e))?;
+                .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to wait for submit fence: {:?}", e))?;
 
             // Reset fence
             self.device
                 .reset_fences(&[self.submit_fences[self.current_submit_fence]])
-                .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to reset submit fence: {:?}", e))?;
+                .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to reset submit fence: {:?}", e))?;
+
+            // The previous submission has completed: destroy what it kept alive
+            self.gpu_context.collect_released();
 
             // Collect command buffers into a stack-allocated fixed-capacity
             // array (no heap allocation per submit).
@@ -364,6 +444,9 @@ impl VulkanGraphicsDevice {
                 cmd_bufs[i] = (*vk_cmd).command_buffer();
             }
 
+            // Resources dropped from now on wait for this submission
+            self.gpu_context.deletion_queue.record_submit();
+
             // Submit with synchronization (vkQueueSubmit2 via helper).
             crate::vulkan_sync::submit_command_buffers(
                 &self.device,
@@ -373,11 +456,221 @@ impl VulkanGraphicsDevice {
                 &[(signal_semaphore, vk::PipelineStageFlags2::ALL_COMMANDS)],
                 self.submit_fences[self.current_submit_fence],
             )?;
+            self.gpu_context.counters.record_submit();
 
             Ok(())
         }
     }
 
+    /// Create a binding group whose descriptor set comes from `pools`
+    fn create_binding_group_in(
+        &self,
+        pools: &Mutex<DescriptorPoolSet>,
+        pipeline: &Arc<dyn RendererPipeline>,
+        set_index: u32,
+        resources: &[BindingResource],
+    ) -> Result<Arc<dyn RendererBindingGroup>> {
+        validate_depth_sampling(None, resources)?;
+        validate_storage_images(None, resources)?;
+        unsafe {
+            // Downcast pipeline to access stored descriptor set layouts
+            let vk_pipeline = pipeline.as_ref() as *const dyn RendererPipeline as *const Pipeline;
+            let vk_pipeline = &*vk_pipeline;
+
+            // Set 0 is the bindless set (owned by BindlessState, not the pipeline).
+            // Pipeline's descriptor_set_layouts stores only sets 1+, so we offset by 1.
+            if set_index == 0 {
+                engine_bail!("galaxy3d::vulkan",
+                    "create_binding_group: set 0 is reserved for bindless textures (managed by backend)");
+            }
+            let layout_index = (set_index - 1) as usize;
+            if layout_index >= vk_pipeline.descriptor_set_layouts.len() {
+                engine_bail!("galaxy3d::vulkan",
+                    "create_binding_group: set_index {} out of range (pipeline has sets 1..={})",
+                    set_index, vk_pipeline.descriptor_set_layouts.len());
+            }
+
+            let ds_layout = vk_pipeline.descriptor_set_layouts[layout_index];
+
+            // Allocate descriptor set from pool (grow dynamically if exhausted)
+            let descriptor_set = pools.lock().unwrap().allocate(&self.device, ds_layout)?;
+            self.gpu_context.counters.record_descriptor_set_allocation();
+
+            // Write resources into descriptor set
+            // We need to keep buffer_infos and image_infos alive for the duration of the write
+            let mut buffer_infos: Vec<vk::DescriptorBufferInfo> = Vec::new();
+            let mut image_infos: Vec<vk::DescriptorImageInfo> = Vec::new();
+            let mut acceleration_structures: Vec<vk::AccelerationStructureKHR> = Vec::new();
+            let mut writes: Vec<vk::WriteDescriptorSet> = Vec::new();
+
+            for (binding_index, resource) in resources.iter().enumerate() {
+                match resource {
+                    BindingResource::UniformBuffer(buffer) => {
+                        let vk_buffer = *buffer as *const dyn RendererBuffer as *const crate::vulkan_buffer::Buffer;
+                        let vk_buffer = &*vk_buffer;
+
+                        buffer_infos.push(
+                            vk::DescriptorBufferInfo::default()
+                                .buffer(vk_buffer.buffer)
+                                .offset(0)
+                                .range(vk::WHOLE_SIZE)
+                        );
+                    }
+                    BindingResource::SampledTexture(texture, sampler_type) => {
+                        let vk_texture = *texture as *const dyn RendererTexture as *const Texture;
+                        let vk_texture = &*vk_texture;
+                        let vk_sampler = self.sampler_cache.lock().unwrap().get(*sampler_type);
+
+                        image_infos.push(
+                            vk::DescriptorImageInfo::default()
+                                .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+                                .image_view(vk_texture.view)
+                                .sampler(vk_sampler)
+                        );
+                    }
+                    BindingResource::SampledTextureDesc(texture, sampler_desc) => {
+                        let vk_texture = *texture as *const dyn RendererTexture as *const Texture;
+                        let vk_texture = &*vk_texture;
+                        let vk_sampler = self.sampler_cache.lock().unwrap().get_desc(sampler_desc)?;
+                        image_infos.push(
+                            vk::DescriptorImageInfo::default()
+                                .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+                                .image_view(vk_texture.view)
+                                .sampler(vk_sampler)
+                        );
+                    }
+                    BindingResource::UniformBufferDynamic(buffer, range) => {
+                        let vk_buffer = *buffer as *const dyn RendererBuffer as *const crate::vulkan_buffer::Buffer;
+                        let vk_buffer = &*vk_buffer;
+                        buffer_infos.push(
+                            vk::DescriptorBufferInfo::default()
+                                .buffer(vk_buffer.buffer)
+                                .offset(0)
+                                .range(*range)
+                        );
+                    }
+                    BindingResource::StorageBuffer(buffer) => {
+                        let vk_buffer = *buffer as *const dyn RendererBuffer as *const crate::vulkan_buffer::Buffer;
+                        let vk_buffer = &*vk_buffer;
+
+                        buffer_infos.push(
+                            vk::DescriptorBufferInfo::default()
+                                .buffer(vk_buffer.buffer)
+                                .offset(0)
+                                .range(vk::WHOLE_SIZE)
+                        );
+                    }
+                    BindingResource::StorageImage(texture, mip_level) => {
+                        let vk_texture = *texture as *const dyn RendererTexture as *const Texture;
+                        let vk_texture = &*vk_texture;
+                        image_infos.push(
+                            vk::DescriptorImageInfo::default()
+                                .image_layout(vk::ImageLayout::GENERAL)
+                                .image_view(vk_texture.mip_views[*mip_level as usize])
+                        );
+                    }
+                    BindingResource::AccelerationStructure(acceleration_structure) => {
+                        let vk_as = *acceleration_structure as *const dyn RendererAccelerationStructure
+                            as *const crate::vulkan_ray_tracing::AccelerationStructure;
+                        acceleration_structures.push((*vk_as).handle);
+                    }
+                }
+                // Track binding index for write construction
+                let _ = binding_index;
+            }
+
+            // Acceleration structures are written through a chained struct
+            let mut acceleration_structure_writes: Vec<vk::WriteDescriptorSetAccelerationStructureKHR> =
+                acceleration_structures.iter()
+                    .map(|handle| vk::WriteDescriptorSetAccelerationStructureKHR::default()
+                        .acceleration_structures(std::slice::from_ref(handle)))
+                    .collect();
+            let mut acceleration_structure_writes = acceleration_structure_writes.iter_mut();
+
+            // Build write descriptor sets with correct pointers
+            let mut buffer_idx = 0usize;
+            let mut image_idx = 0usize;
+
+            for (binding_index, resource) in resources.iter().enumerate() {
+                match resource {
+                    BindingResource::UniformBuffer(_) => {
+                        writes.push(
+                            vk::WriteDescriptorSet::default()
+                                .dst_set(descriptor_set)
+                                .dst_binding(binding_index as u32)
+                                .dst_array_element(0)
+                                .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER)
+                                .buffer_info(std::slice::from_ref(&buffer_infos[buffer_idx]))
+                        );
+                        buffer_idx += 1;
+                    }
+                    BindingResource::SampledTexture(_, _) | BindingResource::SampledTextureDesc(_, _) => {
+                        writes.push(
+                            vk::WriteDescriptorSet::default()
+                                .dst_set(descriptor_set)
+                                .dst_binding(binding_index as u32)
+                                .dst_array_element(0)
+                                .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                                .image_info(std::slice::from_ref(&image_infos[image_idx]))
+                        );
+                        image_idx += 1;
+                    }
+                    BindingResource::UniformBufferDynamic(_, _) => {
+                        writes.push(
+                            vk::WriteDescriptorSet::default()
+                                .dst_set(descriptor_set)
+                                .dst_binding(binding_index as u32)
+                                .dst_array_element(0)
+                                .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER_DYNAMIC)
+                                .buffer_info(std::slice::from_ref(&buffer_infos[buffer_idx]))
+                        );
+                        buffer_idx += 1;
+                    }
+                    BindingResource::StorageBuffer(_) => {
+                        writes.push(
+                            vk::WriteDescriptorSet::default()
+                                .dst_set(descriptor_set)
+                                .dst_binding(binding_index as u32)
+                                .dst_array_element(0)
+                                .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+                                .buffer_info(std::slice::from_ref(&buffer_infos[buffer_idx]))
+                        );
+                        buffer_idx += 1;
+                    }
+                    BindingResource::StorageImage(_, _) => {
+                        writes.push(
+                            vk::WriteDescriptorSet::default()
+                                .dst_set(descriptor_set)
+                                .dst_binding(binding_index as u32)
+                                .dst_array_element(0)
+                                .descriptor_type(vk::DescriptorType::STORAGE_IMAGE)
+                                .image_info(std::slice::from_ref(&image_infos[image_idx]))
+                        );
+                        image_idx += 1;
+                    }
+                    BindingResource::AccelerationStructure(_) => {
+                        writes.push(
+                            vk::WriteDescriptorSet::default()
+                                .dst_set(descriptor_set)
+                                .dst_binding(binding_index as u32)
+                                .dst_array_element(0)
+                                .descriptor_type(vk::DescriptorType::ACCELERATION_STRUCTURE_KHR)
+                                .descriptor_count(1)
+                                .push_next(acceleration_structure_writes.next().unwrap())
+                        );
+                    }
+                }
+            }
+
+            self.device.update_descriptor_sets(&writes, &[]);
+
+            Ok(Arc::new(BindingGroup {
+                descriptor_set,
+                set_index,
+            }))
+        }
+    }
+
     /// Create a new Vulkan device
     ///
     /// # Arguments
@@ -385,40 +678,20 @@ impl VulkanGraphicsDevice {
     /// * `window` - Window for surface creation
     /// * `config` - GraphicsDevice configuration
     ///
-    /// Create a descriptor pool with fixed capacity (1024 sets).
-    /// Called during init and when the current pool is exhausted.
-    fn create_descriptor_pool(device: &ash::Device) -> Result<vk::DescriptorPool> {
-        let pool_sizes = [
-            vk::DescriptorPoolSize {
-                ty: vk::DescriptorType::COMBINED_IMAGE_SAMPLER,
-                descriptor_count: 2048,
-            },
-            vk::DescriptorPoolSize {
-                ty: vk::DescriptorType::UNIFORM_BUFFER,
-                descriptor_count: 1024,
-            },
-            vk::DescriptorPoolSize {
-                ty: vk::DescriptorType::STORAGE_BUFFER,
-                descriptor_count: 1024,
-            },
-        ];
-        let info = vk::DescriptorPoolCreateInfo::default()
-            .pool_sizes(&pool_sizes)
-            .max_sets(1024);
-
-        unsafe {
-            device.create_descriptor_pool(&info, None)
-                .map_err(|e| {
-                    engine_error!("galaxy3d::vulkan", "Failed to create descriptor pool: {:?}", e);
-                    Error::InitializationFailed(format!("Failed to create descriptor pool: {:?}", e))
-                })
-        }
-    }
-
     pub fn new<W: HasDisplayHandle + HasWindowHandle>(
         window: &W,
         config: Config,
     ) -> Result<Self> {
+        let other_backend = config.backend.as_deref()
+            .filter(|backend| !backend.eq_ignore_ascii_case(VULKAN_BACKEND_NAME));
+        if let Some(backend) = other_backend {
+            engine_error!("galaxy3d::vulkan", "Config requests the '{}' backend", backend);
+            return Err(Error::InitializationFailed(format!("Config requests the '{}' backend", backend)));
+        }
+        if config.descriptor_pools.max_sets == 0 || config.transient_descriptor_pools.max_sets == 0 {
+            engine_error!("galaxy3d::vulkan", "Descriptor pools need a max_sets greater than 0");
+            return Err(Error::InitializationFailed("Descriptor pools need a max_sets greater than 0".to_string()));
+        }
         unsafe {
             // Create Vulkan Entry
             let entry = ash::Entry::load()
@@ -441,7 +714,6 @@ impl VulkanGraphicsDevice {
                     engine_error!("galaxy3d::vulkan", "Failed to get display handle: {}", e);
                     Error::InitializationFailed(format!("Failed to get display handle: {}", e))
                 })?;
-            #[cfg_attr(not(feature = "vulkan-validation"), allow(unused_mut))]
             let mut extension_names = ash_window::enumerate_required_extensions(display_handle.as_raw())
                 .map_err(|e| {
                     engine_error!("galaxy3d::vulkan", "Failed to get required extensions: {}", e);
@@ -449,12 +721,35 @@ impl VulkanGraphicsDevice {
                 })?
                 .to_vec();
 
-            // Add debug utils extension if validation is enabled
+            // Add debug utils extension: required by validation, and used for
+            // object names / command labels whenever the loader exposes it
             #[cfg(feature = "vulkan-validation")]
-            if config.enable_validation {
+            let validation_enabled = config.enable_validation;
+            #[cfg(not(feature = "vulkan-validation"))]
+            let validation_enabled = false;
+            let instance_extensions = entry
+                .enumerate_instance_extension_properties(None)
+                .unwrap_or_default();
+            let has_instance_ext = |name: &std::ffi::CStr| -> bool {
+                instance_extensions.iter().any(|p| p.extension_name_as_c_str() == Ok(name))
+            };
+            let debug_utils_enabled = validation_enabled
+                || has_instance_ext(ash::ext::debug_utils::NAME);
+            if debug_utils_enabled {
                 extension_names.push(ash::ext::debug_utils::NAME.as_ptr());
             }
 
+            // Portability drivers (MoltenVK on macOS/iOS) are only listed by
+            // the loader when the application opts in to portability enumeration
+            let portability_enabled = has_instance_ext(ash::khr::portability_enumeration::NAME);
+            let instance_flags = if portability_enabled {
+                extension_names.push(ash::khr::portability_enumeration::NAME.as_ptr());
+                engine_info!("galaxy3d::vulkan", "Portability enumeration enabled (MoltenVK)");
+                vk::InstanceCreateFlags::ENUMERATE_PORTABILITY_KHR
+            } else {
+                vk::InstanceCreateFlags::empty()
+            };
+
             // Validation layers
             #[cfg(feature = "vulkan-validation")]
             let layer_names: Vec<*const i8> = if config.enable_validation {
@@ -466,6 +761,7 @@ impl VulkanGraphicsDevice {
             let layer_names: Vec<*const i8> = Vec::new();
 
             let create_info = vk::InstanceCreateInfo::default()
+                .flags(instance_flags)
                 .application_info(&app_info)
                 .enabled_layer_names(&layer_names)
                 .enabled_extension_names(&extension_names);
@@ -559,11 +855,13 @@ impl VulkanGraphicsDevice {
                     Error::InitializationFailed(format!("Failed to enumerate physical devices: {:?}", e))
                 })?;
 
+            let adapter_count = physical_devices.len();
             let physical_device = physical_devices
                 .into_iter()
-                .next()
+                .nth(config.adapter_index.unwrap_or(0))
                 .ok_or_else(|| {
-                    engine_error!("galaxy3d::vulkan", "No Vulkan-capable GPU found");
+                    engine_error!("galaxy3d::vulkan", "No Vulkan-capable GPU found at index {} ({} found)",
+                        config.adapter_index.unwrap_or(0), adapter_count);
                     Error::InitializationFailed("No Vulkan-capable GPU found".to_string())
                 })?;
 
@@ -627,9 +925,40 @@ impl VulkanGraphicsDevice {
                 })
             };
 
+            // --- Rendering path ---
+            // Dynamic rendering on Vulkan 1.3 devices; older ones (1.2 at
+            // least, for bindless) get legacy VkRenderPass/VkFramebuffer
+            // objects and the extensions providing the rest of 1.3.
+            let device_properties = instance.get_physical_device_properties(physical_device);
+            let device_api_version = device_properties.api_version;
+            if device_api_version < vk::API_VERSION_1_2 {
+                engine_error!("galaxy3d::vulkan", "Vulkan 1.2 required, the device supports {}.{}",
+                    vk::api_version_major(device_api_version), vk::api_version_minor(device_api_version));
+                return Err(Error::InitializationFailed("Vulkan 1.2 device required".to_string()));
+            }
+            let dynamic_rendering = device_api_version >= vk::API_VERSION_1_3;
+            if !dynamic_rendering {
+                let missing: Vec<&std::ffi::CStr> = LEGACY_DEVICE_EXTENSIONS
+                    .into_iter()
+                    .filter(|name| !has_ext(*name))
+                    .collect();
+                if !missing.is_empty() {
+                    engine_error!("galaxy3d::vulkan", "Missing device extensions for Vulkan {}.{}: {:?}",
+                        vk::api_version_major(device_api_version), vk::api_version_minor(device_api_version), missing);
+                    return Err(Error::InitializationFailed(format!("Missing device extensions: {:?}", missing)));
+                }
+            }
+            engine_info!("galaxy3d::vulkan", "Rendering path: {} (device Vulkan {}.{})",
+                if dynamic_rendering { "dynamic rendering" } else { "legacy render passes" },
+                vk::api_version_major(device_api_version), vk::api_version_minor(device_api_version));
+
             let has_state3 = has_ext(ash::ext::extended_dynamic_state3::NAME);
             let has_color_write = has_ext(ash::ext::color_write_enable::NAME);
             let has_depth_clip_ext = has_ext(vk::EXT_DEPTH_CLIP_ENABLE_NAME);
+            let has_mesh_shader_ext = has_ext(ash::ext::mesh_shader::NAME);
+            let has_ray_tracing_ext = has_ext(ash::khr::acceleration_structure::NAME)
+                && has_ext(ash::khr::ray_tracing_pipeline::NAME)
+                && has_ext(ash::khr::deferred_host_operations::NAME);
 
             // --- Query per-feature bits for EXT_extended_dynamic_state3 ---
             let mut dynamic_state_caps = DynamicStateCaps {
@@ -643,8 +972,13 @@ impl VulkanGraphicsDevice {
             let mut state3_features = vk::PhysicalDeviceExtendedDynamicState3FeaturesEXT::default();
             let mut depth_clip_features = vk::PhysicalDeviceDepthClipEnableFeaturesEXT::default();
             let mut color_write_features = vk::PhysicalDeviceColorWriteEnableFeaturesEXT::default();
+            let mut mesh_shader_features = vk::PhysicalDeviceMeshShaderFeaturesEXT::default();
+            let mut acceleration_structure_features = vk::PhysicalDeviceAccelerationStructureFeaturesKHR::default();
+            let mut ray_tracing_pipeline_features = vk::PhysicalDeviceRayTracingPipelineFeaturesKHR::default();
+            let mut buffer_device_address_features = vk::PhysicalDeviceBufferDeviceAddressFeatures::default();
+            let query_ray_tracing = config.ray_tracing && has_ray_tracing_ext;
 
-            if has_state3 || has_color_write || has_depth_clip_ext {
+            if has_state3 || has_color_write || has_depth_clip_ext || has_mesh_shader_ext || query_ray_tracing {
                 let mut features2 = vk::PhysicalDeviceFeatures2::default();
                 if has_state3 {
                     features2 = features2.push_next(&mut state3_features);
@@ -655,6 +989,15 @@ impl VulkanGraphicsDevice {
                 if has_color_write {
                     features2 = features2.push_next(&mut color_write_features);
                 }
+                if has_mesh_shader_ext {
+                    features2 = features2.push_next(&mut mesh_shader_features);
+                }
+                if query_ray_tracing {
+                    features2 = features2
+                        .push_next(&mut acceleration_structure_features)
+                        .push_next(&mut ray_tracing_pipeline_features)
+                        .push_next(&mut buffer_device_address_features);
+                }
                 instance.get_physical_device_features2(physical_device, &mut features2);
             }
 
@@ -676,6 +1019,22 @@ impl VulkanGraphicsDevice {
                     color_write_features.color_write_enable != 0;
             }
 
+            // Mesh shader path (MeshShaderDrawer): task and mesh stages both required
+            let mesh_shaders = has_mesh_shader_ext
+                && mesh_shader_features.task_shader != 0
+                && mesh_shader_features.mesh_shader != 0;
+            engine_info!("galaxy3d::vulkan", "Mesh shaders: {}", mesh_shaders);
+
+            // Ray tracing is opt-in (Config::ray_tracing): it makes vertex
+            // and index buffers addressable by the GPU
+            let ray_tracing = query_ray_tracing
+                && acceleration_structure_features.acceleration_structure != 0
+                && ray_tracing_pipeline_features.ray_tracing_pipeline != 0
+                && buffer_device_address_features.buffer_device_address != 0;
+            if config.ray_tracing {
+                engine_info!("galaxy3d::vulkan", "Ray tracing: {}", ray_tracing);
+            }
+
             // Log what we found
             engine_info!("galaxy3d::vulkan",
                 "Dynamic state caps: depth_clamp={}, depth_clip={}, color_write_mask={}, alpha_to_coverage={}, color_write={}",
@@ -688,6 +1047,9 @@ impl VulkanGraphicsDevice {
 
             // --- Build device extension list ---
             let mut device_extension_names = vec![ash::khr::swapchain::NAME.as_ptr()];
+            if !dynamic_rendering {
+                device_extension_names.extend(LEGACY_DEVICE_EXTENSIONS.map(|name| name.as_ptr()));
+            }
             if has_state3 {
                 device_extension_names.push(ash::ext::extended_dynamic_state3::NAME.as_ptr());
             }
@@ -697,9 +1059,33 @@ impl VulkanGraphicsDevice {
             if has_depth_clip_ext {
                 device_extension_names.push(vk::EXT_DEPTH_CLIP_ENABLE_NAME.as_ptr());
             }
+            if mesh_shaders {
+                device_extension_names.push(ash::ext::mesh_shader::NAME.as_ptr());
+            }
+            if ray_tracing {
+                device_extension_names.push(ash::khr::acceleration_structure::NAME.as_ptr());
+                device_extension_names.push(ash::khr::ray_tracing_pipeline::NAME.as_ptr());
+                device_extension_names.push(ash::khr::deferred_host_operations::NAME.as_ptr());
+            }
+            // A portability (non-conformant) device requires the subset
+            // extension to be enabled whenever it exposes it
+            if has_ext(ash::khr::portability_subset::NAME) {
+                device_extension_names.push(ash::khr::portability_subset::NAME.as_ptr());
+                engine_info!("galaxy3d::vulkan", "Portability subset device: enabling {:?}",
+                    ash::khr::portability_subset::NAME);
+            }
 
+            // Cube map arrays, pipeline statistics and precise occlusion
+            // queries are optional (missing on some mobile GPUs)
+            let supported_features = instance.get_physical_device_features(physical_device);
+            let image_cube_array = supported_features.image_cube_array == vk::TRUE;
+            let pipeline_statistics_query = supported_features.pipeline_statistics_query == vk::TRUE;
+            let occlusion_query_precise = supported_features.occlusion_query_precise == vk::TRUE;
             let device_features = vk::PhysicalDeviceFeatures::default()
                 .sampler_anisotropy(true)
+                .image_cube_array(image_cube_array)
+                .pipeline_statistics_query(pipeline_statistics_query)
+                .occlusion_query_precise(occlusion_query_precise)
                 .depth_clamp(dynamic_state_caps.depth_clamp_enable);
 
             let mut vulkan_11_features = vk::PhysicalDeviceVulkan11Features::default()
@@ -711,15 +1097,23 @@ impl VulkanGraphicsDevice {
                 .runtime_descriptor_array(true)
                 .descriptor_binding_variable_descriptor_count(true)
                 .descriptor_binding_sampled_image_update_after_bind(true)
-                .descriptor_binding_partially_bound(true);
+                .descriptor_binding_partially_bound(true)
+                .buffer_device_address(ray_tracing);
 
             let mut vulkan_13_features = vk::PhysicalDeviceVulkan13Features::default()
                 .synchronization2(true)
                 .dynamic_rendering(true);
 
+            // Legacy path: the 1.3 features above, from their extensions
+            let mut synchronization2 = vk::PhysicalDeviceSynchronization2Features::default()
+                .synchronization2(true);
+
             let mut extended_dynamic_state = vk::PhysicalDeviceExtendedDynamicStateFeaturesEXT::default()
                 .extended_dynamic_state(true);
 
+            let mut extended_dynamic_state2 = vk::PhysicalDeviceExtendedDynamicState2FeaturesEXT::default()
+                .extended_dynamic_state2(true);
+
             // Conditionally enable state3 feature bits we'll actually use
             let mut state3_enable = vk::PhysicalDeviceExtendedDynamicState3FeaturesEXT::default()
                 .extended_dynamic_state3_depth_clamp_enable(dynamic_state_caps.depth_clamp_enable)
@@ -733,15 +1127,31 @@ impl VulkanGraphicsDevice {
             let mut color_write_enable = vk::PhysicalDeviceColorWriteEnableFeaturesEXT::default()
                 .color_write_enable(dynamic_state_caps.color_write_enable);
 
+            let mut mesh_shader_enable = vk::PhysicalDeviceMeshShaderFeaturesEXT::default()
+                .task_shader(true)
+                .mesh_shader(true);
+
+            let mut acceleration_structure_enable = vk::PhysicalDeviceAccelerationStructureFeaturesKHR::default()
+                .acceleration_structure(true);
+
+            let mut ray_tracing_pipeline_enable = vk::PhysicalDeviceRayTracingPipelineFeaturesKHR::default()
+                .ray_tracing_pipeline(true);
+
             let mut device_create_info = vk::DeviceCreateInfo::default()
                 .queue_create_infos(&queue_create_infos)
                 .enabled_extension_names(&device_extension_names)
                 .enabled_features(&device_features)
                 .push_next(&mut vulkan_11_features)
                 .push_next(&mut vulkan_12_features)
-                .push_next(&mut vulkan_13_features)
                 .push_next(&mut extended_dynamic_state);
 
+            if dynamic_rendering {
+                device_create_info = device_create_info.push_next(&mut vulkan_13_features);
+            } else {
+                device_create_info = device_create_info
+                    .push_next(&mut synchronization2)
+                    .push_next(&mut extended_dynamic_state2);
+            }
             if has_state3 {
                 device_create_info = device_create_info.push_next(&mut state3_enable);
             }
@@ -751,17 +1161,30 @@ impl VulkanGraphicsDevice {
             if has_color_write {
                 device_create_info = device_create_info.push_next(&mut color_write_enable);
             }
+            if mesh_shaders {
+                device_create_info = device_create_info.push_next(&mut mesh_shader_enable);
+            }
+            if ray_tracing {
+                device_create_info = device_create_info
+                    .push_next(&mut acceleration_structure_enable)
+                    .push_next(&mut ray_tracing_pipeline_enable);
+            }
 
-            let device = Arc::new(
-                instance
-                    .create_device(physical_device, &device_create_info, None)
-                    .map_err(|e| {
-                        engine_error!("galaxy3d::vulkan", "Failed to create logical device: {:?}", e);
-                        Error::InitializationFailed(format!("Failed to create device: {:?}", e))
-                    })?,
-            );
+            let device = instance
+                .create_device(physical_device, &device_create_info, None)
+                .map_err(|e| {
+                    engine_error!("galaxy3d::vulkan", "Failed to create logical device: {:?}", e);
+                    Error::InitializationFailed(format!("Failed to create device: {:?}", e))
+                })?;
+            let device = Arc::new(if dynamic_rendering {
+                device
+            } else {
+                load_legacy_device(&instance, device.handle())
+            });
 
             let graphics_queue = device.get_device_queue(graphics_family_index, 0);
+            let mesh_shader = mesh_shaders.then(|| ash::ext::mesh_shader::Device::new(&instance, &device));
+            let ray_tracing = ray_tracing.then(|| RayTracingFunctions::new(&instance, physical_device, &device));
             let present_queue = device.get_device_queue(present_family_index, 0);
 
             // Create GPU allocator
@@ -770,7 +1193,7 @@ impl VulkanGraphicsDevice {
                 device: (*device).clone(),
                 physical_device,
                 debug_settings: Default::default(),
-                buffer_device_address: false,
+                buffer_device_address: ray_tracing.is_some(),
                 allocation_sizes: Default::default(),
             })
             .map_err(|e| {
@@ -794,8 +1217,11 @@ impl VulkanGraphicsDevice {
                 );
             }
 
-            // Create initial descriptor pool for binding group allocation
-            let descriptor_pool = Self::create_descriptor_pool(&device)?;
+            // Create initial descriptor pools for binding group allocation
+            let descriptor_pools = DescriptorPoolSet::new(
+                &device, config.descriptor_pools, ray_tracing.is_some(), false)?;
+            let transient_descriptor_pools = DescriptorPoolSet::new(
+                &device, config.transient_descriptor_pools, ray_tracing.is_some(), true)?;
 
             // Create upload command pool (TRANSIENT + RESET for reusable one-shot uploads)
             let upload_pool_create_info = vk::CommandPoolCreateInfo::default()
@@ -818,13 +1244,14 @@ impl VulkanGraphicsDevice {
                 graphics_family_index,
                 upload_command_pool,
                 instance.clone(),
+                debug_utils_enabled,
                 #[cfg(feature = "vulkan-validation")]
                 debug_utils_loader,
                 #[cfg(feature = "vulkan-validation")]
                 debug_messenger,
             ));
 
-            let mut sampler_cache = SamplerCache::new(Arc::clone(&gpu_context));
+            let mut sampler_cache = SamplerCache::new(Arc::clone(&gpu_context), &config.platform_profile.limits());
             let bindless_state = BindlessState::new(&device, &mut sampler_cache, &config.bindless)?;
 
             Ok(Self {
@@ -839,10 +1266,19 @@ impl VulkanGraphicsDevice {
                 allocator: ManuallyDrop::new(allocator_arc),
                 submit_fences,
                 current_submit_fence: 0,
-                descriptor_pools: Mutex::new(vec![descriptor_pool]),
+                descriptor_pools: Mutex::new(descriptor_pools),
+                transient_descriptor_pools: Mutex::new(transient_descriptor_pools),
                 sampler_cache: Mutex::new(sampler_cache),
                 gpu_context,
                 bindless_state,
+                image_cube_array,
+                pipeline_statistics_query,
+                occlusion_query_precise,
+                mesh_shader,
+                ray_tracing,
+                dynamic_rendering,
+                validate_barriers: config.validate_barriers,
+                uniform_buffer_alignment: device_properties.limits.min_uniform_buffer_offset_alignment,
             })
         }
     }
@@ -945,6 +1381,9 @@ impl VulkanGraphicsDevice {
             TextureFormat::B8G8R8A8_SRGB => vk::Format::B8G8R8A8_SRGB,
             TextureFormat::B8G8R8A8_UNORM => vk::Format::B8G8R8A8_UNORM,
             TextureFormat::R16G16B16A16_SFLOAT => vk::Format::R16G16B16A16_SFLOAT,
+            TextureFormat::R11G11B10_UFLOAT => vk::Format::B10G11R11_UFLOAT_PACK32,
+            TextureFormat::R16G16_SFLOAT => vk::Format::R16G16_SFLOAT,
+            TextureFormat::R32_UINT => vk::Format::R32_UINT,
             TextureFormat::D16_UNORM => vk::Format::D16_UNORM,
             TextureFormat::D32_FLOAT => vk::Format::D32_SFLOAT,
             TextureFormat::D24_UNORM_S8_UINT => vk::Format::D24_UNORM_S8_UINT,
@@ -986,6 +1425,27 @@ impl VulkanGraphicsDevice {
             BufferFormat::R8_UINT => vk::Format::R8_UINT,
             BufferFormat::R8G8_UINT => vk::Format::R8G8_UINT,
             BufferFormat::R8G8B8A8_UINT => vk::Format::R8G8B8A8_UINT,
+            // Half float formats
+            BufferFormat::R16_SFLOAT => vk::Format::R16_SFLOAT,
+            BufferFormat::R16G16_SFLOAT => vk::Format::R16G16_SFLOAT,
+            BufferFormat::R16G16B16A16_SFLOAT => vk::Format::R16G16B16A16_SFLOAT,
+            // Normalized short formats
+            BufferFormat::R16_UNORM => vk::Format::R16_UNORM,
+            BufferFormat::R16G16_UNORM => vk::Format::R16G16_UNORM,
+            BufferFormat::R16G16B16A16_UNORM => vk::Format::R16G16B16A16_UNORM,
+            BufferFormat::R16_SNORM => vk::Format::R16_SNORM,
+            BufferFormat::R16G16_SNORM => vk::Format::R16G16_SNORM,
+            BufferFormat::R16G16B16A16_SNORM => vk::Format::R16G16B16A16_SNORM,
+            // Normalized byte formats
+            BufferFormat::R8_UNORM => vk::Format::R8_UNORM,
+            BufferFormat::R8G8_UNORM => vk::Format::R8G8_UNORM,
+            BufferFormat::R8G8B8A8_UNORM => vk::Format::R8G8B8A8_UNORM,
+            BufferFormat::R8_SNORM => vk::Format::R8_SNORM,
+            BufferFormat::R8G8_SNORM => vk::Format::R8G8_SNORM,
+            BufferFormat::R8G8B8A8_SNORM => vk::Format::R8G8B8A8_SNORM,
+            // Packed formats
+            BufferFormat::A2B10G10R10_UNORM_PACK32 => vk::Format::A2B10G10R10_UNORM_PACK32,
+            BufferFormat::A2B10G10R10_SNORM_PACK32 => vk::Format::A2B10G10R10_SNORM_PACK32,
         }
     }
 
@@ -995,6 +1455,12 @@ impl VulkanGraphicsDevice {
             ShaderStage::Vertex => vk::ShaderStageFlags::VERTEX,
             ShaderStage::Fragment => vk::ShaderStageFlags::FRAGMENT,
             ShaderStage::Compute => vk::ShaderStageFlags::COMPUTE,
+            ShaderStage::Task => vk::ShaderStageFlags::TASK_EXT,
+            ShaderStage::Mesh => vk::ShaderStageFlags::MESH_EXT,
+            ShaderStage::RayGen => vk::ShaderStageFlags::RAYGEN_KHR,
+            ShaderStage::Miss => vk::ShaderStageFlags::MISS_KHR,
+            ShaderStage::ClosestHit => vk::ShaderStageFlags::CLOSEST_HIT_KHR,
+            ShaderStage::AnyHit => vk::ShaderStageFlags::ANY_HIT_KHR,
         }
     }
 
@@ -1004,12 +1470,19 @@ impl VulkanGraphicsDevice {
             ShaderStage::Vertex => ShaderStageFlags::VERTEX,
             ShaderStage::Fragment => ShaderStageFlags::FRAGMENT,
             ShaderStage::Compute => ShaderStageFlags::COMPUTE,
+            ShaderStage::Task => ShaderStageFlags::TASK,
+            ShaderStage::Mesh => ShaderStageFlags::MESH,
+            ShaderStage::RayGen => ShaderStageFlags::RAYGEN,
+            ShaderStage::Miss => ShaderStageFlags::MISS,
+            ShaderStage::ClosestHit => ShaderStageFlags::CLOSEST_HIT,
+            ShaderStage::AnyHit => ShaderStageFlags::ANY_HIT,
         }
     }
 
-    /// Parse SPIR-V bytecode and extract reflected bindings and push constants using spirq
+    /// Parse SPIR-V bytecode and extract reflected bindings, push constants
+    /// and (vertex stage only) vertex inputs using spirq
     fn reflect_shader(code: &[u32], stage_flags: ShaderStageFlags)
-        -> Result<(Vec<ReflectedBinding>, Vec<ReflectedPushConstant>)>
+        -> Result<(Vec<ReflectedBinding>, Vec<ReflectedPushConstant>, Vec<ReflectedVertexInput>)>
     {
         let entry_points = spirq::ReflectConfig::new()
             .spv(code)
@@ -1020,6 +1493,7 @@ impl VulkanGraphicsDevice {
 
         let mut bindings = Vec::new();
         let mut push_constants = Vec::new();
+        let mut vertex_inputs = Vec::new();
 
         for entry_point in &entry_points {
             for var in entry_point.vars.iter() {
@@ -1047,12 +1521,43 @@ impl VulkanGraphicsDevice {
                             members,
                         });
                     }
+                    spirq::var::Variable::Input { name, location, ty }
+                        if stage_flags == ShaderStageFlags::VERTEX =>
+                    {
+                        Self::push_vertex_inputs(
+                            &mut vertex_inputs, name.as_deref().unwrap_or_default(), location.loc(), ty);
+                    }
                     _ => {}
                 }
             }
         }
 
-        Ok((bindings, push_constants))
+        Ok((bindings, push_constants, vertex_inputs))
+    }
+
+    /// Convert a reflected vertex input variable (a matrix takes one
+    /// location per column)
+    fn push_vertex_inputs(
+        inputs: &mut Vec<ReflectedVertexInput>,
+        name: &str,
+        location: u32,
+        ty: &spirq::ty::Type,
+    ) {
+        use spirq::ty::Type;
+        let (scalar_ty, component_count, locations) = match ty {
+            Type::Scalar(s) => (s, 1, 1),
+            Type::Vector(v) => (&v.scalar_ty, v.nscalar, 1),
+            Type::Matrix(m) => (&m.vector_ty.scalar_ty, m.vector_ty.nscalar, m.nvector),
+            _ => return,
+        };
+        for column in 0..locations {
+            inputs.push(ReflectedVertexInput {
+                name: name.to_string(),
+                location: location + column,
+                scalar_kind: Self::spirq_scalar_to_kind(scalar_ty),
+                component_count,
+            });
+        }
     }
 
     /// Convert spirq descriptor type to graphics_device BindingType
@@ -1064,6 +1569,8 @@ impl VulkanGraphicsDevice {
             DescriptorType::CombinedImageSampler() => Ok(BindingType::CombinedImageSampler),
             DescriptorType::SampledImage() => Ok(BindingType::CombinedImageSampler),
             DescriptorType::Sampler() => Ok(BindingType::CombinedImageSampler),
+            DescriptorType::StorageImage(..) => Ok(BindingType::StorageImage),
+            DescriptorType::AccelStruct() => Ok(BindingType::AccelerationStructure),
             other => {
                 engine_bail!("galaxy3d::vulkan",
                     "Unsupported SPIR-V descriptor type: {:?}", other);
@@ -1153,7 +1660,7 @@ impl VulkanGraphicsDevice {
             {
                 if existing.binding_type != fs_binding.binding_type {
                     engine_bail!("galaxy3d::vulkan",
-                        "Binding '{}' (set={}, binding={}) has different types in vertex ({:?}) and fragment ({:?})",
+                        "Binding '{}' (set={}, binding={}) has different types in two stages ({:?} and {:?})",
                         existing.name, existing.set, existing.binding,
                         existing.binding_type, fs_binding.binding_type);
                 }
@@ -1199,10 +1706,13 @@ impl VulkanGraphicsDevice {
     ///
     /// Set 0 is reserved for the bindless descriptor set (managed by BindlessState).
     /// This function only builds layouts for sets 1+. Bindings declared at set 0
-    /// in the shader are skipped (they use the bindless layout).
+    /// in the shader are skipped (they use the bindless layout). Every binding
+    /// is visible to `stage_flags`, so that the layouts of the pipelines of a
+    /// kind (graphics, ray tracing) stay compatible.
     fn build_descriptor_set_layouts(
         &self,
         merged_bindings: &[ReflectedBinding],
+        stage_flags: vk::ShaderStageFlags,
     ) -> Result<Vec<vk::DescriptorSetLayout>> {
         // Only consider bindings for sets 1+ (set 0 = bindless, handled separately)
         let non_bindless_bindings: Vec<&ReflectedBinding> = merged_bindings.iter()
@@ -1220,7 +1730,7 @@ impl VulkanGraphicsDevice {
                         .binding(b.binding)
                         .descriptor_type(Self::binding_type_to_vk(b.binding_type))
                         .descriptor_count(1)
-                        .stage_flags(vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT)
+                        .stage_flags(stage_flags)
                 })
                 .collect();
 
@@ -1257,19 +1767,15 @@ impl VulkanGraphicsDevice {
             .collect()
     }
 
-    /// Merge shader reflections into a PipelineReflection (used after pipeline creation)
-    fn merge_shader_reflections(
-        vertex_shader: &Arc<dyn RendererShader>,
-        fragment_shader: &Arc<dyn RendererShader>,
-    ) -> Result<PipelineReflection> {
-        let merged_bindings = Self::merge_reflected_bindings(
-            vertex_shader.reflected_bindings(),
-            fragment_shader.reflected_bindings(),
-        )?;
-        let push_constants = Self::merge_reflected_push_constants(
-            vertex_shader.reflected_push_constants(),
-            fragment_shader.reflected_push_constants(),
-        );
+    /// Merge the shader reflections of all pipeline stages, in order, into
+    /// a PipelineReflection
+    fn merge_shader_reflections(shaders: &[&Arc<dyn RendererShader>]) -> Result<PipelineReflection> {
+        let mut merged_bindings = Vec::new();
+        let mut push_constants = Vec::new();
+        for shader in shaders {
+            merged_bindings = Self::merge_reflected_bindings(&merged_bindings, shader.reflected_bindings())?;
+            push_constants = Self::merge_reflected_push_constants(&push_constants, shader.reflected_push_constants());
+        }
         Ok(PipelineReflection::new(merged_bindings, push_constants))
     }
 
@@ -1332,6 +1838,18 @@ impl VulkanGraphicsDevice {
         }
     }
 
+    /// Convert an AttachmentDesc to a legacy VkAttachmentDescription
+    /// (layouts are set by `create_legacy_render_pass`)
+    fn attachment_desc_to_vk(&self, attachment: &AttachmentDesc) -> vk::AttachmentDescription {
+        vk::AttachmentDescription::default()
+            .format(self.format_to_vk(attachment.format))
+            .samples(self.sample_count_to_vk(attachment.samples))
+            .load_op(CommandList::load_op_to_vk(attachment.load_op))
+            .store_op(CommandList::store_op_to_vk(attachment.store_op))
+            .stencil_load_op(CommandList::load_op_to_vk(attachment.stencil_load_op))
+            .stencil_store_op(CommandList::store_op_to_vk(attachment.stencil_store_op))
+    }
+
     /// Convert LoadOp to Vulkan
     /// Convert ImageLayout to Vulkan (used by future dynamic barrier tracking)
     #[allow(dead_code)]
@@ -1395,6 +1913,7 @@ impl VulkanGraphicsDevice {
             surface,
             surface_loader,
             self.present_queue,
+            &[self.graphics_queue_family, self.present_queue_family],
             width,
             height,
         )
@@ -1404,8 +1923,12 @@ impl VulkanGraphicsDevice {
     fn binding_type_to_vk(binding_type: BindingType) -> vk::DescriptorType {
         match binding_type {
             BindingType::UniformBuffer => vk::DescriptorType::UNIFORM_BUFFER,
-            BindingType::CombinedImageSampler => vk::DescriptorType::COMBINED_IMAGE_SAMPLER,
+            BindingType::UniformBufferDynamic => vk::DescriptorType::UNIFORM_BUFFER_DYNAMIC,
+            BindingType::CombinedImageSampler | BindingType::SampledDepthTexture =>
+                vk::DescriptorType::COMBINED_IMAGE_SAMPLER,
             BindingType::StorageBuffer => vk::DescriptorType::STORAGE_BUFFER,
+            BindingType::StorageImage => vk::DescriptorType::STORAGE_IMAGE,
+            BindingType::AccelerationStructure => vk::DescriptorType::ACCELERATION_STRUCTURE_KHR,
         }
     }
 
@@ -1415,17 +1938,524 @@ impl VulkanGraphicsDevice {
         if flags.contains_vertex() { vk_flags |= vk::ShaderStageFlags::VERTEX; }
         if flags.contains_fragment() { vk_flags |= vk::ShaderStageFlags::FRAGMENT; }
         if flags.contains_compute() { vk_flags |= vk::ShaderStageFlags::COMPUTE; }
+        if flags.contains_task() { vk_flags |= vk::ShaderStageFlags::TASK_EXT; }
+        if flags.contains_mesh() { vk_flags |= vk::ShaderStageFlags::MESH_EXT; }
+        if flags.contains_raygen() { vk_flags |= vk::ShaderStageFlags::RAYGEN_KHR; }
+        if flags.contains_miss() { vk_flags |= vk::ShaderStageFlags::MISS_KHR; }
+        if flags.contains_closest_hit() { vk_flags |= vk::ShaderStageFlags::CLOSEST_HIT_KHR; }
+        if flags.contains_any_hit() { vk_flags |= vk::ShaderStageFlags::ANY_HIT_KHR; }
         vk_flags
     }
 
-}
-
-impl GraphicsDevice for VulkanGraphicsDevice {
+    /// Create the pipeline layout deduced from the SPIR-V reflections of
+    /// the pipeline stages (graphics, mesh and ray tracing pipelines).
+    ///
+    /// The uniform buffers listed in `dynamic_uniform_buffers` (set, binding)
+    /// become `BindingType::UniformBufferDynamic`.
+    ///
+    /// Returns the merged reflection, the descriptor set layouts owned by
+    /// the pipeline (sets 1+) and the pipeline layout.
+    fn create_reflected_pipeline_layout(
+        &self,
+        shaders: &[&Arc<dyn RendererShader>],
+        binding_stages: vk::ShaderStageFlags,
+        dynamic_uniform_buffers: &[(u32, u32)],
+    ) -> Result<(PipelineReflection, Vec<vk::DescriptorSetLayout>, vk::PipelineLayout)> {
+        unsafe {
+            let reflection = Self::merge_shader_reflections(shaders)?;
+            let reflection = Self::apply_dynamic_uniform_buffers(reflection, dynamic_uniform_buffers)?;
+            let merged_bindings = reflection.bindings();
+
+            // Build VkDescriptorSetLayouts from merged reflected bindings (sets 1+)
+            let reflected_set_layouts = self.build_descriptor_set_layouts(merged_bindings, binding_stages)?;
+
+            // Inject bindless layout at set 0 only if the shader actually declares bindings there.
+            // Set 0 is reserved for the bindless descriptor set; pipelines that don't use textures
+            // don't need it in their pipeline layout.
+            let uses_bindless = merged_bindings.iter().any(|b| b.set == 0);
+            let descriptor_set_layouts: Vec<vk::DescriptorSetLayout> = if uses_bindless {
+                std::iter::once(self.bindless_state.layout)
+                    .chain(reflected_set_layouts.iter().copied())
+                    .collect()
+            } else {
+                reflected_set_layouts.clone()
+            };
+
+            // Build VkPushConstantRanges from merged reflected push constants
+            let push_constant_ranges = Self::build_push_constant_ranges(reflection.push_constants(), &[]);
+
+            let mut layout_create_info = vk::PipelineLayoutCreateInfo::default();
+
+            // Add descriptor set layouts (set 0 = bindless, sets 1+ = reflected)
+            if !descriptor_set_layouts.is_empty() {
+                layout_create_info = layout_create_info.set_layouts(&descriptor_set_layouts);
+            }
+
+            // Add push constant ranges if present
+            if !push_constant_ranges.is_empty() {
+                layout_create_info = layout_create_info.push_constant_ranges(&push_constant_ranges);
+            }
+
+            let layout = self.device.create_pipeline_layout(&layout_create_info, None)
+                .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to create pipeline layout: {:?}", e))?;
+
+            Ok((reflection, reflected_set_layouts, layout))
+        }
+    }
+
+    /// Turn the reflected uniform buffers at the given (set, binding) into
+    /// dynamic-offset uniform buffers.
+    fn apply_dynamic_uniform_buffers(
+        reflection: PipelineReflection,
+        dynamic_uniform_buffers: &[(u32, u32)],
+    ) -> Result<PipelineReflection> {
+        if dynamic_uniform_buffers.is_empty() {
+            return Ok(reflection);
+        }
+        let mut bindings = reflection.bindings().to_vec();
+        for &(set, binding) in dynamic_uniform_buffers {
+            let Some(reflected) = bindings.iter_mut().find(|b| b.set == set && b.binding == binding) else {
+                engine_bail!("galaxy3d::vulkan",
+                    "Dynamic uniform buffer (set {}, binding {}) not declared by the shaders", set, binding);
+            };
+            if reflected.binding_type != BindingType::UniformBuffer {
+                engine_bail!("galaxy3d::vulkan",
+                    "Dynamic uniform buffer (set {}, binding {}): '{}' is a {:?}",
+                    set, binding, reflected.name, reflected.binding_type);
+            }
+            reflected.binding_type = BindingType::UniformBufferDynamic;
+        }
+        Ok(PipelineReflection::new(bindings, reflection.push_constants().to_vec()))
+    }
+
+    /// Create a graphics pipeline from its stages, in order. Classic
+    /// pipelines (`vertex_input`) take a vertex and a fragment shader; mesh
+    /// shader pipelines an optional task, a mesh and a fragment shader, and
+    /// no vertex input or input assembly state.
+    fn build_graphics_pipeline(
+        &self,
+        desc: PipelineDesc,
+        shaders: &[&Arc<dyn RendererShader>],
+        vertex_input: bool,
+    ) -> Result<Arc<dyn RendererPipeline>> {
+        unsafe {
+            // Dynamic rendering: describe the attachment formats inline via
+            // `VkPipelineRenderingCreateInfo` instead of building a temporary
+            // VkRenderPass. The pipeline is not tied to a concrete render pass
+            // object; it is only compiled for this specific attachment layout.
+            // The legacy path builds that temporary VkRenderPass from the
+            // same formats (see below).
+            let color_formats: Vec<vk::Format> = desc
+                .color_formats
+                .iter()
+                .map(|&f| self.format_to_vk(f))
+                .collect();
+
+            // Depth/stencil formats are derived from `desc.depth_format`.
+            // When the depth format also contains stencil (D24_UNORM_S8_UINT,
+            // D32_SFLOAT_S8_UINT), `stencil_attachment_format` is set to the
+            // same value so the pipeline remains compatible with both aspects.
+            let (depth_format_vk, stencil_format_vk) = match desc.depth_format {
+                Some(fmt) => {
+                    let vk_fmt = self.format_to_vk(fmt);
+                    let stencil_fmt = if matches!(
+                        fmt,
+                        TextureFormat::D24_UNORM_S8_UINT | TextureFormat::D32_FLOAT_S8_UINT,
+                    ) {
+                        vk_fmt
+                    } else {
+                        vk::Format::UNDEFINED
+                    };
+                    (vk_fmt, stencil_fmt)
+                }
+                None => (vk::Format::UNDEFINED, vk::Format::UNDEFINED),
+            };
+
+            let mut pipeline_rendering_info = vk::PipelineRenderingCreateInfo::default()
+                .color_attachment_formats(&color_formats)
+                .depth_attachment_format(depth_format_vk)
+                .stencil_attachment_format(stencil_format_vk);
+
+            // Downcast shaders to Vulkan types
+            let shaders_vk: Vec<&Shader> = shaders
+                .iter()
+                .map(|shader| &*(shader.as_ref() as *const dyn RendererShader as *const Shader))
+                .collect();
+
+            // Create shader stage infos
+            let entry_points: Vec<CString> = shaders_vk
+                .iter()
+                .map(|shader| CString::new(shader.entry_point.as_str()).unwrap())
+                .collect();
+
+            // Engine features as a u32 specialization constant on both stages.
+            // Map entries whose id is not declared by a shader are ignored.
+            let feature_bytes = desc.engine_features.bits().to_ne_bytes();
+            let specialization_entries = [vk::SpecializationMapEntry {
+                constant_id: EngineFeatures::SPECIALIZATION_CONSTANT_ID,
+                offset: 0,
+                size: std::mem::size_of::<u32>(),
+            }];
+            let specialization_info = vk::SpecializationInfo::default()
+                .map_entries(&specialization_entries)
+                .data(&feature_bytes);
+
+            let shader_stages: Vec<vk::PipelineShaderStageCreateInfo> = shaders_vk
+                .iter()
+                .zip(&entry_points)
+                .map(|(shader, entry_point)| vk::PipelineShaderStageCreateInfo::default()
+                    .stage(shader.stage)
+                    .module(shader.module)
+                    .name(entry_point)
+                    .specialization_info(&specialization_info))
+                .collect();
+
+            // Vertex input state
+            let vertex_bindings: Vec<vk::VertexInputBindingDescription> = desc.vertex_layout.bindings
+                .iter()
+                .map(|binding| vk::VertexInputBindingDescription {
+                    binding: binding.binding,
+                    stride: binding.stride,
+                    input_rate: match binding.input_rate {
+                        VertexInputRate::Vertex => vk::VertexInputRate::VERTEX,
+                        VertexInputRate::Instance => vk::VertexInputRate::INSTANCE,
+                    },
+                })
+                .collect();
+
+            let vertex_attributes: Vec<vk::VertexInputAttributeDescription> = desc.vertex_layout.attributes
+                .iter()
+                .map(|attribute| vk::VertexInputAttributeDescription {
+                    location: attribute.location,
+                    binding: attribute.binding,
+                    format: self.buffer_format_to_vk(attribute.format),
+                    offset: attribute.offset,
+                })
+                .collect();
+
+            let vertex_input_state = vk::PipelineVertexInputStateCreateInfo::default()
+                .vertex_binding_descriptions(&vertex_bindings)
+                .vertex_attribute_descriptions(&vertex_attributes);
+
+            // Input assembly state
+            let input_assembly_state = vk::PipelineInputAssemblyStateCreateInfo::default()
+                .topology(self.topology_to_vk(desc.topology))
+                .primitive_restart_enable(false);
+
+            // Viewport state (dynamic)
+            let viewports = [vk::Viewport::default()];
+            let scissors = [vk::Rect2D::default()];
+            let viewport_state = vk::PipelineViewportStateCreateInfo::default()
+                .viewports(&viewports)
+                .scissors(&scissors);
+
+            // Rasterization state (cull_mode, front_face, depth_bias are dynamic)
+            let rasterization_state = vk::PipelineRasterizationStateCreateInfo::default()
+                .depth_clamp_enable(desc.rasterization.depth_clamp_enable)
+                .rasterizer_discard_enable(false)
+                .polygon_mode(self.polygon_mode_to_vk(desc.rasterization.polygon_mode))
+                .line_width(1.0)
+                .cull_mode(vk::CullModeFlags::BACK)
+                .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
+                .depth_bias_enable(false);
+
+            // Depth/stencil state (all fields are dynamic)
+            let depth_stencil_state = vk::PipelineDepthStencilStateCreateInfo::default()
+                .depth_test_enable(false)
+                .depth_write_enable(false)
+                .depth_compare_op(vk::CompareOp::LESS)
+                .depth_bounds_test_enable(false)
+                .stencil_test_enable(false);
+
+            // Multisample state
+            let multisample_state = vk::PipelineMultisampleStateCreateInfo::default()
+                .sample_shading_enable(false)
+                .rasterization_samples(self.sample_count_to_vk(desc.multisample.sample_count))
+                .alpha_to_coverage_enable(desc.multisample.alpha_to_coverage_enable);
+
+            // Color blend state
+            let color_blend_attachment = {
+                let mut attachment = vk::PipelineColorBlendAttachmentState::default()
+                    .color_write_mask(color_write_mask_to_vk(desc.color_blend.color_write_mask))
+                    .blend_enable(desc.color_blend.blend_enable);
+                if desc.color_blend.blend_enable {
+                    attachment = attachment
+                        .src_color_blend_factor(self.blend_factor_to_vk(desc.color_blend.src_color_factor))
+                        .dst_color_blend_factor(self.blend_factor_to_vk(desc.color_blend.dst_color_factor))
+                        .color_blend_op(self.blend_op_to_vk(desc.color_blend.color_blend_op))
+                        .src_alpha_blend_factor(self.blend_factor_to_vk(desc.color_blend.src_alpha_factor))
+                        .dst_alpha_blend_factor(self.blend_factor_to_vk(desc.color_blend.dst_alpha_factor))
+                        .alpha_blend_op(self.blend_op_to_vk(desc.color_blend.alpha_blend_op));
+                }
+                attachment
+            };
+
+            // One state per color attachment (same blend on every target,
+            // e.g. the scene color and the motion vectors)
+            let color_blend_attachments = vec![color_blend_attachment; color_formats.len()];
+            let color_blend_state = vk::PipelineColorBlendStateCreateInfo::default()
+                .logic_op_enable(false)
+                .attachments(&color_blend_attachments);
+
+            // Dynamic state — all per-draw states are set via set_dynamic_state()
+            let dynamic_states = vec![
+                // Vulkan 1.0 core
+                vk::DynamicState::VIEWPORT,
+                vk::DynamicState::SCISSOR,
+                vk::DynamicState::DEPTH_BIAS,
+                vk::DynamicState::DEPTH_BOUNDS,
+                vk::DynamicState::BLEND_CONSTANTS,
+                vk::DynamicState::STENCIL_COMPARE_MASK,
+                vk::DynamicState::STENCIL_WRITE_MASK,
+                vk::DynamicState::STENCIL_REFERENCE,
+                // Vulkan 1.3 / VK_EXT_extended_dynamic_state
+                vk::DynamicState::CULL_MODE,
+                vk::DynamicState::FRONT_FACE,
+                vk::DynamicState::DEPTH_TEST_ENABLE,
+                vk::DynamicState::DEPTH_WRITE_ENABLE,
+                vk::DynamicState::DEPTH_COMPARE_OP,
+                vk::DynamicState::DEPTH_BIAS_ENABLE,
+                vk::DynamicState::DEPTH_BOUNDS_TEST_ENABLE,
+                vk::DynamicState::STENCIL_TEST_ENABLE,
+                vk::DynamicState::STENCIL_OP,
+            ];
+            let dynamic_state = vk::PipelineDynamicStateCreateInfo::default()
+                .dynamic_states(&dynamic_states);
+
+            // Deduce pipeline layout from the SPIR-V reflections of all stages
+            let (reflection, reflected_set_layouts, layout) = self.create_reflected_pipeline_layout(
+                shaders, vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT,
+                &desc.dynamic_uniform_buffers)?;
+
+            // Legacy path: a temporary single-subpass render pass, compatible
+            // with every render pass of the same formats and sample count
+            // (load/store ops do not matter). Destroyed once the pipeline
+            // is created.
+            let legacy_render_pass = if self.dynamic_rendering {
+                vk::RenderPass::null()
+            } else {
+                let samples = self.sample_count_to_vk(desc.multisample.sample_count);
+                let attachment = |format: vk::Format| vk::AttachmentDescription::default()
+                    .format(format)
+                    .samples(samples);
+                let color_attachments: Vec<vk::AttachmentDescription> = color_formats
+                    .iter()
+                    .map(|&format| attachment(format))
+                    .collect();
+                create_legacy_render_pass(
+                    &self.device,
+                    &color_attachments,
+                    desc.depth_format.map(|_| attachment(depth_format_vk)),
+                    &[],
+                )?
+            };
+
+            // Create pipeline. With dynamic rendering, no VkRenderPass is
+            // bound — the attachment formats are carried by
+            // `VkPipelineRenderingCreateInfo` chained via `pNext`.
+            let mut pipeline_create_info = vk::GraphicsPipelineCreateInfo::default()
+                .stages(&shader_stages)
+                .viewport_state(&viewport_state)
+                .rasterization_state(&rasterization_state)
+                .depth_stencil_state(&depth_stencil_state)
+                .multisample_state(&multisample_state)
+                .color_blend_state(&color_blend_state)
+                .dynamic_state(&dynamic_state)
+                .layout(layout);
+            if self.dynamic_rendering {
+                pipeline_create_info = pipeline_create_info.push_next(&mut pipeline_rendering_info);
+            } else {
+                pipeline_create_info = pipeline_create_info
+                    .render_pass(legacy_render_pass)
+                    .subpass(0);
+            }
+            // Mesh shader pipelines generate their primitives: no vertex input
+            if vertex_input {
+                pipeline_create_info = pipeline_create_info
+                    .vertex_input_state(&vertex_input_state)
+                    .input_assembly_state(&input_assembly_state);
+            }
+
+            let pipelines = self.device.create_graphics_pipelines(
+                vk::PipelineCache::null(),
+                &[pipeline_create_info],
+                None,
+            );
+            if legacy_render_pass != vk::RenderPass::null() {
+                self.device.destroy_render_pass(legacy_render_pass, None);
+            }
+            let pipelines = pipelines
+                .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to create graphics pipeline: {:?}", e.1))?;
+
+            let pipeline = pipelines[0];
+            if let Some(name) = &desc.debug_name {
+                self.gpu_context.debug_names.set_object_name(pipeline, name);
+            }
+
+            Ok(Arc::new(Pipeline {
+                pipeline,
+                bind_point: vk::PipelineBindPoint::GRAPHICS,
+                pipeline_layout: layout,
+                // Only store layouts owned by this pipeline (sets 1+).
+                // Set 0 (bindless) is owned by BindlessState and must not be destroyed here.
+                descriptor_set_layouts: reflected_set_layouts,
+                device: (*self.device).clone(),
+                reflection,
+                topology: desc.topology,
+                shader_binding_table: None,
+            }))
+        }
+    }
+
+    /// Create a ray tracing pipeline and its shader binding table.
+    ///
+    /// Shader groups, in SBT order: the ray generation shader, one general
+    /// group per miss shader, then one triangle hit group per
+    /// `RayTracingHitGroup`.
+    fn build_ray_tracing_pipeline(
+        &self,
+        ray_tracing: &RayTracingFunctions,
+        desc: &RayTracingPipelineDesc,
+    ) -> Result<Arc<dyn RendererPipeline>> {
+        unsafe {
+            let properties = ray_tracing.properties;
+            if desc.max_recursion_depth == 0 || desc.max_recursion_depth > properties.max_recursion_depth {
+                engine_bail!("galaxy3d::vulkan",
+                    "create_ray_tracing_pipeline: recursion depth {} out of range 1..={}",
+                    desc.max_recursion_depth, properties.max_recursion_depth);
+            }
+
+            // Stages with the Vulkan stage they must have
+            let mut shaders: Vec<(&Arc<dyn RendererShader>, vk::ShaderStageFlags)> =
+                vec![(&desc.raygen, vk::ShaderStageFlags::RAYGEN_KHR)];
+            shaders.extend(desc.miss.iter().map(|shader| (shader, vk::ShaderStageFlags::MISS_KHR)));
+
+            let general_group = |index: usize| vk::RayTracingShaderGroupCreateInfoKHR::default()
+                .ty(vk::RayTracingShaderGroupTypeKHR::GENERAL)
+                .general_shader(index as u32)
+                .closest_hit_shader(vk::SHADER_UNUSED_KHR)
+                .any_hit_shader(vk::SHADER_UNUSED_KHR)
+                .intersection_shader(vk::SHADER_UNUSED_KHR);
+            let mut groups: Vec<vk::RayTracingShaderGroupCreateInfoKHR> =
+                (0..shaders.len()).map(general_group).collect();
+
+            for hit_group in &desc.hit_groups {
+                // Stage indices of the closest hit and any hit shaders
+                let mut indices = [vk::SHADER_UNUSED_KHR; 2];
+                let hit_shaders = [
+                    (&hit_group.closest_hit, vk::ShaderStageFlags::CLOSEST_HIT_KHR),
+                    (&hit_group.any_hit, vk::ShaderStageFlags::ANY_HIT_KHR),
+                ];
+                for (index, (shader, stage)) in indices.iter_mut().zip(hit_shaders) {
+                    if let Some(shader) = shader {
+                        shaders.push((shader, stage));
+                        *index = shaders.len() as u32 - 1;
+                    }
+                }
+                let [closest_hit, any_hit] = indices;
+                groups.push(vk::RayTracingShaderGroupCreateInfoKHR::default()
+                    .ty(vk::RayTracingShaderGroupTypeKHR::TRIANGLES_HIT_GROUP)
+                    .general_shader(vk::SHADER_UNUSED_KHR)
+                    .closest_hit_shader(closest_hit)
+                    .any_hit_shader(any_hit)
+                    .intersection_shader(vk::SHADER_UNUSED_KHR));
+            }
+
+            // Downcast shaders to Vulkan types
+            let shaders_vk: Vec<&Shader> = shaders
+                .iter()
+                .map(|(shader, _)| &*(shader.as_ref() as *const dyn RendererShader as *const Shader))
+                .collect();
+            for (shader, (_, stage)) in shaders_vk.iter().zip(&shaders) {
+                if shader.stage != *stage {
+                    engine_bail!("galaxy3d::vulkan",
+                        "create_ray_tracing_pipeline: {:?} shader given for a {:?} slot", shader.stage, stage);
+                }
+            }
+
+            let entry_points: Vec<CString> = shaders_vk
+                .iter()
+                .map(|shader| CString::new(shader.entry_point.as_str()).unwrap())
+                .collect();
+            let shader_stages: Vec<vk::PipelineShaderStageCreateInfo> = shaders_vk
+                .iter()
+                .zip(&entry_points)
+                .map(|(shader, entry_point)| vk::PipelineShaderStageCreateInfo::default()
+                    .stage(shader.stage)
+                    .module(shader.module)
+                    .name(entry_point))
+                .collect();
+
+            let stage_shaders: Vec<&Arc<dyn RendererShader>> = shaders.iter().map(|(shader, _)| *shader).collect();
+            let (reflection, reflected_set_layouts, layout) = self.create_reflected_pipeline_layout(
+                &stage_shaders,
+                vk::ShaderStageFlags::RAYGEN_KHR | vk::ShaderStageFlags::MISS_KHR
+                    | vk::ShaderStageFlags::CLOSEST_HIT_KHR | vk::ShaderStageFlags::ANY_HIT_KHR,
+                &[],
+            )?;
+
+            let create_info = vk::RayTracingPipelineCreateInfoKHR::default()
+                .stages(&shader_stages)
+                .groups(&groups)
+                .max_pipeline_ray_recursion_depth(desc.max_recursion_depth)
+                .layout(layout);
+            let pipelines = ray_tracing.pipeline.create_ray_tracing_pipelines(
+                vk::DeferredOperationKHR::null(),
+                vk::PipelineCache::null(),
+                &[create_info],
+                None,
+            )
+            .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to create ray tracing pipeline: {:?}", e.1))?;
+            let pipeline = pipelines[0];
+            if let Some(name) = &desc.debug_name {
+                self.gpu_context.debug_names.set_object_name(pipeline, name);
+            }
+
+            // Shader binding table: one record per group, in group order
+            let sbt_layout = ShaderBindingTableLayout::new(
+                properties.shader_group_handle_size,
+                properties.shader_group_handle_alignment,
+                properties.shader_group_base_alignment,
+                desc.miss.len() as u32,
+                desc.hit_groups.len() as u32,
+            )?;
+            let handles = ray_tracing.pipeline.get_ray_tracing_shader_group_handles(
+                pipeline,
+                0,
+                groups.len() as u32,
+                groups.len() * properties.shader_group_handle_size as usize,
+            )
+            .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to get shader group handles: {:?}", e))?;
+            let shader_binding_table = ShaderBindingTable::new(&self.gpu_context, &sbt_layout, &handles)?;
+
+            Ok(Arc::new(Pipeline {
+                pipeline,
+                bind_point: vk::PipelineBindPoint::RAY_TRACING_KHR,
+                pipeline_layout: layout,
+                descriptor_set_layouts: reflected_set_layouts,
+                device: (*self.device).clone(),
+                reflection,
+                // Unused: ray tracing pipelines do not draw
+                topology: PrimitiveTopology::TriangleList,
+                shader_binding_table: Some(shader_binding_table),
+            }))
+        }
+    }
+
+}
+
+impl GraphicsDevice for VulkanGraphicsDevice {
     fn create_command_list(&self) -> Result<Box<dyn RendererCommandList>> {
         let cmd_list = CommandList::new(
             self.device.clone(),
             self.graphics_queue_family,
             self.bindless_state.descriptor_set,
+            Arc::clone(&self.gpu_context.counters),
+            Arc::clone(&self.gpu_context.debug_names),
+            self.mesh_shader.clone(),
+            self.ray_tracing.clone(),
+            self.dynamic_rendering,
+            self.validate_barriers,
         )?;
         Ok(Box::new(cmd_list))
     }
@@ -1451,26 +2481,73 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                 resolve_image_views.push(self.create_attachment_image_view(att)?);
             }
 
-            Ok(Arc::new(crate::vulkan_frame_buffer::Framebuffer::new(
+            let mut framebuffer = crate::vulkan_frame_buffer::Framebuffer::new(
                 color_image_views,
                 depth_image_view,
                 resolve_image_views,
                 desc.width,
                 desc.height,
                 (*self.device).clone(),
-            )))
+            );
+
+            // Legacy path: a `VkFramebuffer` over the same views, in the
+            // attachment order of the render pass (colors, depth, resolves).
+            // On failure, dropping `framebuffer` destroys the views.
+            if !self.dynamic_rendering {
+                let vk_render_pass = desc.render_pass.as_ref()
+                    as *const dyn RendererRenderPass
+                    as *const RenderPass;
+                let attachments: Vec<vk::ImageView> = framebuffer.color_image_views.iter().copied()
+                    .chain(framebuffer.depth_image_view)
+                    .chain(framebuffer.resolve_image_views.iter().copied())
+                    .collect();
+                let create_info = vk::FramebufferCreateInfo::default()
+                    .render_pass((*vk_render_pass).legacy_render_pass)
+                    .attachments(&attachments)
+                    .width(desc.width)
+                    .height(desc.height)
+                    .layers(1);
+                framebuffer.legacy_framebuffer = self.device.create_framebuffer(&create_info, None)
+                    .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to create framebuffer: {:?}", e))?;
+            }
+
+            Ok(Arc::new(framebuffer))
         }
     }
 
     fn create_render_pass(&self, desc: &RenderPassDesc) -> Result<Arc<dyn RendererRenderPass>> {
         // With dynamic rendering, no `VkRenderPass` is created. We just clone
         // the attachment descriptors so that `begin_render_pass` can build a
-        // `VkRenderingInfo` inline at record time.
-        Ok(Arc::new(RenderPass {
-            color_attachments: desc.color_attachments.clone(),
-            depth_stencil_attachment: desc.depth_stencil_attachment.clone(),
-            color_resolve_attachments: desc.color_resolve_attachments.clone(),
-        }))
+        // `VkRenderingInfo` inline at record time. The legacy path also
+        // creates the `VkRenderPass` that `begin_render_pass` begins.
+        let legacy_render_pass = if self.dynamic_rendering {
+            vk::RenderPass::null()
+        } else {
+            let color_attachments: Vec<vk::AttachmentDescription> = desc.color_attachments
+                .iter()
+                .map(|att| self.attachment_desc_to_vk(att))
+                .collect();
+            let color_resolve_attachments: Vec<vk::AttachmentDescription> = desc.color_resolve_attachments
+                .iter()
+                .map(|att| self.attachment_desc_to_vk(att))
+                .collect();
+            unsafe {
+                create_legacy_render_pass(
+                    &self.device,
+                    &color_attachments,
+                    desc.depth_stencil_attachment.as_ref().map(|att| self.attachment_desc_to_vk(att)),
+                    &color_resolve_attachments,
+                )?
+            }
+        };
+
+        Ok(Arc::new(RenderPass::new(
+            desc.color_attachments.clone(),
+            desc.depth_stencil_attachment.clone(),
+            desc.color_resolve_attachments.clone(),
+            legacy_render_pass,
+            (*self.device).clone(),
+        )))
     }
 
     fn create_binding_group(
@@ -1479,154 +2556,16 @@ impl GraphicsDevice for VulkanGraphicsDevice {
         set_index: u32,
         resources: &[BindingResource],
     ) -> Result<Arc<dyn RendererBindingGroup>> {
-        unsafe {
-            // Downcast pipeline to access stored descriptor set layouts
-            let vk_pipeline = pipeline.as_ref() as *const dyn RendererPipeline as *const Pipeline;
-            let vk_pipeline = &*vk_pipeline;
-
-            // Set 0 is the bindless set (owned by BindlessState, not the pipeline).
-            // Pipeline's descriptor_set_layouts stores only sets 1+, so we offset by 1.
-            if set_index == 0 {
-                engine_bail!("galaxy3d::vulkan",
-                    "create_binding_group: set 0 is reserved for bindless textures (managed by backend)");
-            }
-            let layout_index = (set_index - 1) as usize;
-            if layout_index >= vk_pipeline.descriptor_set_layouts.len() {
-                engine_bail!("galaxy3d::vulkan",
-                    "create_binding_group: set_index {} out of range (pipeline has sets 1..={})",
-                    set_index, vk_pipeline.descriptor_set_layouts.len());
-            }
-
-            let ds_layout = vk_pipeline.descriptor_set_layouts[layout_index];
-
-            // Allocate descriptor set from pool (grow dynamically if exhausted)
-            let layouts = [ds_layout];
-            let descriptor_sets = {
-                let mut pools = self.descriptor_pools.lock().unwrap();
-                let current_pool = *pools.last().unwrap();
-                let allocate_info = vk::DescriptorSetAllocateInfo::default()
-                    .descriptor_pool(current_pool)
-                    .set_layouts(&layouts);
-
-                match self.device.allocate_descriptor_sets(&allocate_info) {
-                    Ok(sets) => sets,
-                    Err(vk::Result::ERROR_OUT_OF_POOL_MEMORY) => {
-                        let new_pool = Self::create_descriptor_pool(&self.device)?;
-                        pools.push(new_pool);
-                        engine_info!("galaxy3d::vulkan",
-                            "Descriptor pool exhausted, created new pool (total: {})",
-                            pools.len()
-                        );
-                        let retry_info = vk::DescriptorSetAllocateInfo::default()
-                            .descriptor_pool(new_pool)
-                            .set_layouts(&layouts);
-                        self.device.allocate_descriptor_sets(&retry_info)
-                            .map_err(|e| engine_err!("galaxy3d::vulkan",
-                                "Failed to allocate descriptor set after pool growth: {:?}", e))?
-                    }
-                    Err(e) => return Err(engine_err!("galaxy3d::vulkan",
-                        "Failed to allocate descriptor set: {:?}", e)),
-                }
-            };
-
-            let descriptor_set = descriptor_sets[0];
-
-            // Write resources into descriptor set
-            // We need to keep buffer_infos and image_infos alive for the duration of the write
-            let mut buffer_infos: Vec<vk::DescriptorBufferInfo> = Vec::new();
-            let mut image_infos: Vec<vk::DescriptorImageInfo> = Vec::new();
-            let mut writes: Vec<vk::WriteDescriptorSet> = Vec::new();
-
-            for (binding_index, resource) in resources.iter().enumerate() {
-                match resource {
-                    BindingResource::UniformBuffer(buffer) => {
-                        let vk_buffer = *buffer as *const dyn RendererBuffer as *const crate::vulkan_buffer::Buffer;
-                        let vk_buffer = &*vk_buffer;
-
-                        buffer_infos.push(
-                            vk::DescriptorBufferInfo::default()
-                                .buffer(vk_buffer.buffer)
-                                .offset(0)
-                                .range(vk::WHOLE_SIZE)
-                        );
-                    }
-                    BindingResource::SampledTexture(texture, sampler_type) => {
-                        let vk_texture = *texture as *const dyn RendererTexture as *const Texture;
-                        let vk_texture = &*vk_texture;
-                        let vk_sampler = self.sampler_cache.lock().unwrap().get(*sampler_type);
-
-                        image_infos.push(
-                            vk::DescriptorImageInfo::default()
-                                .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
-                                .image_view(vk_texture.view)
-                                .sampler(vk_sampler)
-                        );
-                    }
-                    BindingResource::StorageBuffer(buffer) => {
-                        let vk_buffer = *buffer as *const dyn RendererBuffer as *const crate::vulkan_buffer::Buffer;
-                        let vk_buffer = &*vk_buffer;
-
-                        buffer_infos.push(
-                            vk::DescriptorBufferInfo::default()
-                                .buffer(vk_buffer.buffer)
-                                .offset(0)
-                                .range(vk::WHOLE_SIZE)
-                        );
-                    }
-                }
-                // Track binding index for write construction
-                let _ = binding_index;
-            }
-
-            // Build write descriptor sets with correct pointers
-            let mut buffer_idx = 0usize;
-            let mut image_idx = 0usize;
-
-            for (binding_index, resource) in resources.iter().enumerate() {
-                match resource {
-                    BindingResource::UniformBuffer(_) => {
-                        writes.push(
-                            vk::WriteDescriptorSet::default()
-                                .dst_set(descriptor_set)
-                                .dst_binding(binding_index as u32)
-                                .dst_array_element(0)
-                                .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER)
-                                .buffer_info(std::slice::from_ref(&buffer_infos[buffer_idx]))
-                        );
-                        buffer_idx += 1;
-                    }
-                    BindingResource::SampledTexture(_, _) => {
-                        writes.push(
-                            vk::WriteDescriptorSet::default()
-                                .dst_set(descriptor_set)
-                                .dst_binding(binding_index as u32)
-                                .dst_array_element(0)
-                                .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
-                                .image_info(std::slice::from_ref(&image_infos[image_idx]))
-                        );
-                        image_idx += 1;
-                    }
-                    BindingResource::StorageBuffer(_) => {
-                        writes.push(
-                            vk::WriteDescriptorSet::default()
-                                .dst_set(descriptor_set)
-                                .dst_binding(binding_index as u32)
-                                .dst_array_element(0)
-                                .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
-                                .buffer_info(std::slice::from_ref(&buffer_infos[buffer_idx]))
-                        );
-                        buffer_idx += 1;
-                    }
-                }
-            }
-
-            self.device.update_descriptor_sets(&writes, &[]);
+        self.create_binding_group_in(&self.descriptor_pools, pipeline, set_index, resources)
+    }
 
-            Ok(Arc::new(BindingGroup {
-                descriptor_set,
-                set_index,
-            }))
-        }
+    fn create_transient_binding_group(
+        &self,
+        pipeline: &Arc<dyn RendererPipeline>,
+        set_index: u32,
+        resources: &[BindingResource],
+    ) -> Result<Arc<dyn RendererBindingGroup>> {
+        self.create_binding_group_in(&self.transient_descriptor_pools, pipeline, set_index, resources)
     }
 
     fn create_binding_group_from_layout(
@@ -1635,6 +2574,8 @@ impl GraphicsDevice for VulkanGraphicsDevice {
         set_index: u32,
         resources: &[BindingResource],
     ) -> Result<Arc<dyn RendererBindingGroup>> {
+        validate_depth_sampling(Some(layout), resources)?;
+        validate_storage_images(Some(layout), resources)?;
         unsafe {
             // Build VkDescriptorSetLayout from the explicit layout description
             let vk_bindings: Vec<vk::DescriptorSetLayoutBinding> = layout.entries.iter()
@@ -1654,40 +2595,13 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                     "Failed to create descriptor set layout from explicit layout: {:?}", e))?;
 
             // Allocate descriptor set from pool (grow dynamically if exhausted)
-            let layouts = [ds_layout];
-            let descriptor_sets = {
-                let mut pools = self.descriptor_pools.lock().unwrap();
-                let current_pool = *pools.last().unwrap();
-                let allocate_info = vk::DescriptorSetAllocateInfo::default()
-                    .descriptor_pool(current_pool)
-                    .set_layouts(&layouts);
-
-                match self.device.allocate_descriptor_sets(&allocate_info) {
-                    Ok(sets) => sets,
-                    Err(vk::Result::ERROR_OUT_OF_POOL_MEMORY) => {
-                        let new_pool = Self::create_descriptor_pool(&self.device)?;
-                        pools.push(new_pool);
-                        engine_info!("galaxy3d::vulkan",
-                            "Descriptor pool exhausted, created new pool (total: {})",
-                            pools.len()
-                        );
-                        let retry_info = vk::DescriptorSetAllocateInfo::default()
-                            .descriptor_pool(new_pool)
-                            .set_layouts(&layouts);
-                        self.device.allocate_descriptor_sets(&retry_info)
-                            .map_err(|e| engine_err!("galaxy3d::vulkan",
-                                "Failed to allocate descriptor set after pool growth: {:?}", e))?
-                    }
-                    Err(e) => return Err(engine_err!("galaxy3d::vulkan",
-                        "Failed to allocate descriptor set: {:?}", e)),
-                }
-            };
-
-            let descriptor_set = descriptor_sets[0];
+            let descriptor_set = self.descriptor_pools.lock().unwrap().allocate(&self.device, ds_layout)?;
+            self.gpu_context.counters.record_descriptor_set_allocation();
 
             // Write resources into descriptor set (same logic as create_binding_group)
             let mut buffer_infos: Vec<vk::DescriptorBufferInfo> = Vec::new();
             let mut image_infos: Vec<vk::DescriptorImageInfo> = Vec::new();
+            let mut acceleration_structures: Vec<vk::AccelerationStructureKHR> = Vec::new();
 
             for resource in resources.iter() {
                 match resource {
@@ -1712,6 +2626,27 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                                 .sampler(vk_sampler)
                         );
                     }
+                    BindingResource::SampledTextureDesc(texture, sampler_desc) => {
+                        let vk_texture = *texture as *const dyn RendererTexture as *const Texture;
+                        let vk_texture = &*vk_texture;
+                        let vk_sampler = self.sampler_cache.lock().unwrap().get_desc(sampler_desc)?;
+                        image_infos.push(
+                            vk::DescriptorImageInfo::default()
+                                .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+                                .image_view(vk_texture.view)
+                                .sampler(vk_sampler)
+                        );
+                    }
+                    BindingResource::UniformBufferDynamic(buffer, range) => {
+                        let vk_buffer = *buffer as *const dyn RendererBuffer as *const crate::vulkan_buffer::Buffer;
+                        let vk_buffer = &*vk_buffer;
+                        buffer_infos.push(
+                            vk::DescriptorBufferInfo::default()
+                                .buffer(vk_buffer.buffer)
+                                .offset(0)
+                                .range(*range)
+                        );
+                    }
                     BindingResource::StorageBuffer(buffer) => {
                         let vk_buffer = *buffer as *const dyn RendererBuffer as *const crate::vulkan_buffer::Buffer;
                         let vk_buffer = &*vk_buffer;
@@ -1722,9 +2657,31 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                                 .range(vk::WHOLE_SIZE)
                         );
                     }
+                    BindingResource::StorageImage(texture, mip_level) => {
+                        let vk_texture = *texture as *const dyn RendererTexture as *const Texture;
+                        let vk_texture = &*vk_texture;
+                        image_infos.push(
+                            vk::DescriptorImageInfo::default()
+                                .image_layout(vk::ImageLayout::GENERAL)
+                                .image_view(vk_texture.mip_views[*mip_level as usize])
+                        );
+                    }
+                    BindingResource::AccelerationStructure(acceleration_structure) => {
+                        let vk_as = *acceleration_structure as *const dyn RendererAccelerationStructure
+                            as *const crate::vulkan_ray_tracing::AccelerationStructure;
+                        acceleration_structures.push((*vk_as).handle);
+                    }
                 }
             }
 
+            // Acceleration structures are written through a chained struct
+            let mut acceleration_structure_writes: Vec<vk::WriteDescriptorSetAccelerationStructureKHR> =
+                acceleration_structures.iter()
+                    .map(|handle| vk::WriteDescriptorSetAccelerationStructureKHR::default()
+                        .acceleration_structures(std::slice::from_ref(handle)))
+                    .collect();
+            let mut acceleration_structure_writes = acceleration_structure_writes.iter_mut();
+
             let mut buffer_idx = 0usize;
             let mut image_idx = 0usize;
             let mut writes: Vec<vk::WriteDescriptorSet> = Vec::new();
@@ -1742,7 +2699,7 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                         );
                         buffer_idx += 1;
                     }
-                    BindingResource::SampledTexture(_, _) => {
+                    BindingResource::SampledTexture(_, _) | BindingResource::SampledTextureDesc(_, _) => {
                         writes.push(
                             vk::WriteDescriptorSet::default()
                                 .dst_set(descriptor_set)
@@ -1753,6 +2710,17 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                         );
                         image_idx += 1;
                     }
+                    BindingResource::UniformBufferDynamic(_, _) => {
+                        writes.push(
+                            vk::WriteDescriptorSet::default()
+                                .dst_set(descriptor_set)
+                                .dst_binding(binding_index as u32)
+                                .dst_array_element(0)
+                                .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER_DYNAMIC)
+                                .buffer_info(std::slice::from_ref(&buffer_infos[buffer_idx]))
+                        );
+                        buffer_idx += 1;
+                    }
                     BindingResource::StorageBuffer(_) => {
                         writes.push(
                             vk::WriteDescriptorSet::default()
@@ -1764,6 +2732,28 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                         );
                         buffer_idx += 1;
                     }
+                    BindingResource::StorageImage(_, _) => {
+                        writes.push(
+                            vk::WriteDescriptorSet::default()
+                                .dst_set(descriptor_set)
+                                .dst_binding(binding_index as u32)
+                                .dst_array_element(0)
+                                .descriptor_type(vk::DescriptorType::STORAGE_IMAGE)
+                                .image_info(std::slice::from_ref(&image_infos[image_idx]))
+                        );
+                        image_idx += 1;
+                    }
+                    BindingResource::AccelerationStructure(_) => {
+                        writes.push(
+                            vk::WriteDescriptorSet::default()
+                                .dst_set(descriptor_set)
+                                .dst_binding(binding_index as u32)
+                                .dst_array_element(0)
+                                .descriptor_type(vk::DescriptorType::ACCELERATION_STRUCTURE_KHR)
+                                .descriptor_count(1)
+                                .push_next(acceleration_structure_writes.next().unwrap())
+                        );
+                    }
                 }
             }
 
@@ -1785,9 +2775,12 @@ impl GraphicsDevice for VulkanGraphicsDevice {
     }
 
     fn create_texture(&mut self, desc: TextureDesc) -> Result<Arc<dyn RendererTexture>> {
+        // CPU-generated mipmaps become regular manual mip uploads
+        let desc = desc.resolve_cpu_mipmaps()?;
         unsafe {
             let format = self.format_to_vk(desc.format);
             let array_layers = desc.array_layers.max(1);
+            let depth = desc.depth.max(1);
 
             // Validate TextureType vs array_layers coherence
             if desc.texture_type == TextureType::Tex2D && array_layers > 1 {
@@ -1795,6 +2788,7 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                     "Tex2D texture cannot have array_layers > 1 (got {}). Use TextureType::Array2D instead.",
                     array_layers);
             }
+            desc.validate_volume()?;
 
             // Calculate mip levels from MipmapMode
             let mip_levels = desc.mipmap.mip_levels(desc.width, desc.height);
@@ -1810,6 +2804,45 @@ impl GraphicsDevice for VulkanGraphicsDevice {
             let view_type = match desc.texture_type {
                 TextureType::Array2D => vk::ImageViewType::TYPE_2D_ARRAY,
                 TextureType::Tex2D => vk::ImageViewType::TYPE_2D,
+                TextureType::Cube => vk::ImageViewType::CUBE,
+                TextureType::CubeArray => vk::ImageViewType::CUBE_ARRAY,
+                TextureType::Tex3D => vk::ImageViewType::TYPE_3D,
+            };
+            if desc.texture_type.is_cube() {
+                let faces = CUBE_FACE_COUNT;
+                let layers_ok = match desc.texture_type {
+                    TextureType::Cube => array_layers == faces,
+                    _ => array_layers.is_multiple_of(faces),
+                };
+                if !layers_ok || desc.width != desc.height {
+                    engine_bail!("galaxy3d::vulkan",
+                        "{:?} texture needs square faces and {} layers per cube (got {}x{}, {} layers)",
+                        desc.texture_type, faces, desc.width, desc.height, array_layers);
+                }
+                if desc.texture_type == TextureType::CubeArray && !self.image_cube_array {
+                    engine_bail!("galaxy3d::vulkan", "Cube map arrays are not supported by this device");
+                }
+            }
+
+            // Storage images need a format with storage support
+            if desc.usage == TextureUsage::Storage {
+                let features = self._instance
+                    .get_physical_device_format_properties(self.physical_device, format)
+                    .optimal_tiling_features;
+                if !features.contains(vk::FormatFeatureFlags::STORAGE_IMAGE)
+                    || desc.sample_count != SampleCount::S1
+                {
+                    engine_bail!("galaxy3d::vulkan",
+                        "{:?} ({:?}) cannot be used as a storage image on this device",
+                        desc.format, desc.sample_count);
+                }
+            }
+
+            // Cube views need a cube-compatible image
+            let image_flags = if desc.texture_type.is_cube() {
+                vk::ImageCreateFlags::CUBE_COMPATIBLE
+            } else {
+                vk::ImageCreateFlags::empty()
             };
 
             // Image usage flags based on declared TextureUsage
@@ -1832,6 +2865,12 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                     vk::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT
                         | vk::ImageUsageFlags::TRANSFER_DST
                 }
+                TextureUsage::Storage => {
+                    vk::ImageUsageFlags::SAMPLED
+                        | vk::ImageUsageFlags::STORAGE
+                        | vk::ImageUsageFlags::TRANSFER_SRC
+                        | vk::ImageUsageFlags::TRANSFER_DST
+                }
             };
             // All textures need SAMPLED for bindless (they are all registered in the bindless set 0)
             usage_flags |= vk::ImageUsageFlags::SAMPLED;
@@ -1847,24 +2886,29 @@ impl GraphicsDevice for VulkanGraphicsDevice {
             };
 
             // Create image
-            let image_create_info = vk::ImageCreateInfo::default()
-                .image_type(vk::ImageType::TYPE_2D)
+            let image_type = if desc.texture_type == TextureType::Tex3D {
+                vk::ImageType::TYPE_3D
+            } else {
+                vk::ImageType::TYPE_2D
+            };
+            let image_create_info = self.gpu_context.resource_sharing.image_info(vk::ImageCreateInfo::default()
+                .flags(image_flags)
+                .image_type(image_type)
                 .format(format)
                 .extent(vk::Extent3D {
                     width: desc.width,
                     height: desc.height,
-                    depth: 1,
+                    depth,
                 })
                 .mip_levels(mip_levels)
                 .array_layers(array_layers)
                 .samples(self.sample_count_to_vk(desc.sample_count))
                 .tiling(vk::ImageTiling::OPTIMAL)
                 .usage(usage_flags)
-                .sharing_mode(vk::SharingMode::EXCLUSIVE)
-                .initial_layout(vk::ImageLayout::UNDEFINED);
+                .initial_layout(vk::ImageLayout::UNDEFINED));
 
             let image = self.device.create_image(&image_create_info, None)
-                .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to create texture image: {:?}", e))?;
+                .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to create texture image: {:?}", e))?;
 
             // Allocate memory
             let requirements = self.device.get_image_memory_requirements(image);
@@ -1884,7 +2928,7 @@ impl GraphicsDevice for VulkanGraphicsDevice {
 
             // Bind memory
             self.device.bind_image_memory(image, allocation.memory(), allocation.offset())
-                .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to bind texture image memory: {:?}", e))?;
+                .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to bind texture image memory: {:?}", e))?;
 
             // Create image view
             let view_create_info = vk::ImageViewCreateInfo::default()
@@ -1906,7 +2950,34 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                 });
 
             let view = self.device.create_image_view(&view_create_info, None)
-                .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to create texture image view: {:?}", e))?;
+                .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to create texture image view: {:?}", e))?;
+
+            // Storage textures get one view per mip level for storage image
+            // bindings (cube faces are written as 2D array layers)
+            let mut mip_views = Vec::new();
+            if desc.usage == TextureUsage::Storage {
+                let storage_view_type = match desc.texture_type {
+                    TextureType::Tex2D => vk::ImageViewType::TYPE_2D,
+                    TextureType::Tex3D => vk::ImageViewType::TYPE_3D,
+                    TextureType::Array2D | TextureType::Cube | TextureType::CubeArray => {
+                        vk::ImageViewType::TYPE_2D_ARRAY
+                    }
+                };
+                for mip_level in 0..mip_levels {
+                    let mip_view_info = view_create_info
+                        .view_type(storage_view_type)
+                        .subresource_range(vk::ImageSubresourceRange {
+                            aspect_mask,
+                            base_mip_level: mip_level,
+                            level_count: 1,
+                            base_array_layer: 0,
+                            layer_count: array_layers,
+                        });
+                    mip_views.push(self.device.create_image_view(&mip_view_info, None)
+                        .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(),
+                            "Failed to create storage view of mip level {}: {:?}", mip_level, e))?);
+                }
+            }
 
             // Collect upload items: Vec<(layer_index, &[u8])>
             let upload_items: Vec<(u32, &[u8])> = match &desc.data {
@@ -1929,29 +3000,13 @@ impl GraphicsDevice for VulkanGraphicsDevice {
 
             let has_data = !upload_items.is_empty();
 
-            if has_data {
-                // Create a one-time command buffer for upload
-                let command_pool_create_info = vk::CommandPoolCreateInfo::default()
-                    .queue_family_index(self.graphics_queue_family)
-                    .flags(vk::CommandPoolCreateFlags::TRANSIENT);
-
-                let command_pool = self.device.create_command_pool(&command_pool_create_info, None)
-                    .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to create command pool for texture upload: {:?}", e))?;
-
-                let command_buffer_allocate_info = vk::CommandBufferAllocateInfo::default()
-                    .command_pool(command_pool)
-                    .level(vk::CommandBufferLevel::PRIMARY)
-                    .command_buffer_count(1);
-
-                let command_buffers = self.device.allocate_command_buffers(&command_buffer_allocate_info)
-                    .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to allocate command buffer for texture upload: {:?}", e))?;
-                let command_buffer = command_buffers[0];
+            // Staging offsets of image copies: multiple of 4 and of the texel size
+            let staging_alignment = (desc.format.bytes_per_pixel() as u64).max(4);
 
-                let begin_info = vk::CommandBufferBeginInfo::default()
-                    .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
-
-                self.device.begin_command_buffer(command_buffer, &begin_info)
-                    .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to begin command buffer for texture upload: {:?}", e))?;
+            if has_data {
+                // Record the copies into an upload of the staging belt
+                let mut upload = crate::vulkan_staging_belt::Upload::begin(&self.gpu_context)?;
+                let command_buffer = upload.command_buffer();
 
                 // Transition all layers: UNDEFINED → TRANSFER_DST_OPTIMAL
                 let barrier_to_transfer = vk::ImageMemoryBarrier2::default()
@@ -1978,48 +3033,14 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                     &[barrier_to_transfer],
                 );
 
-                // Upload each layer with its own staging buffer
-                let mut staging_buffers: Vec<(vk::Buffer, gpu_allocator::vulkan::Allocation)> = Vec::new();
-
+                // Upload each layer through the staging belt
                 for (layer_index, data) in &upload_items {
-                    let buffer_size = data.len() as u64;
-
-                    // Create staging buffer
-                    let staging_buffer_create_info = vk::BufferCreateInfo::default()
-                        .size(buffer_size)
-                        .usage(vk::BufferUsageFlags::TRANSFER_SRC)
-                        .sharing_mode(vk::SharingMode::EXCLUSIVE);
-
-                    let staging_buffer = self.device.create_buffer(&staging_buffer_create_info, None)
-                        .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to create staging buffer for layer {}: {:?}", layer_index, e))?;
-
-                    let staging_requirements = self.device.get_buffer_memory_requirements(staging_buffer);
-
-                    let staging_allocation = self.allocator.lock().unwrap().allocate(&gpu_allocator::vulkan::AllocationCreateDesc {
-                        name: "texture_staging_buffer",
-                        requirements: staging_requirements,
-                        location: gpu_allocator::MemoryLocation::CpuToGpu,
-                        linear: true,
-                        allocation_scheme: gpu_allocator::vulkan::AllocationScheme::GpuAllocatorManaged,
-                    })
-                    .map_err(|_e| {
-                        let size_mb = staging_requirements.size as f64 / (1024.0 * 1024.0);
-                        engine_error!("galaxy3d::vulkan", "Out of GPU memory for texture staging buffer layer {} ({:.2} MB)", layer_index, size_mb);
-                        Error::OutOfMemory
-                    })?;
-
-                    self.device.bind_buffer_memory(staging_buffer, staging_allocation.memory(), staging_allocation.offset())
-                        .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to bind staging buffer memory for layer {}: {:?}", layer_index, e))?;
-
-                    // Copy data to staging buffer
-                    let mapped_ptr = staging_allocation.mapped_ptr()
-                        .ok_or_else(|| engine_err!("galaxy3d::vulkan", "Staging buffer is not mapped for layer {}", layer_index))?
-                        .as_ptr() as *mut u8;
-                    std::ptr::copy_nonoverlapping(data.as_ptr(), mapped_ptr, data.len());
+                    // Copy data to the staging belt
+                    let (staging_buffer, staging_offset) = upload.stage(data, staging_alignment)?;
 
                     // Record copy command for this layer
                     let region = vk::BufferImageCopy::default()
-                        .buffer_offset(0)
+                        .buffer_offset(staging_offset)
                         .buffer_row_length(0)
                         .buffer_image_height(0)
                         .image_subresource(vk::ImageSubresourceLayers {
@@ -2032,7 +3053,7 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                         .image_extent(vk::Extent3D {
                             width: desc.width,
                             height: desc.height,
-                            depth: 1,
+                            depth,
                         });
 
                     self.device.cmd_copy_buffer_to_image(
@@ -2043,7 +3064,6 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                         &[region],
                     );
 
-                    staging_buffers.push((staging_buffer, staging_allocation));
                 }
 
                 // Generate or upload mipmaps (levels 1+)
@@ -2127,7 +3147,7 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                             let barrier_src_final = vk::ImageMemoryBarrier2::default()
                                 .src_stage_mask(vk::PipelineStageFlags2::BLIT)
                                 .src_access_mask(vk::AccessFlags2::TRANSFER_READ)
-                                .dst_stage_mask(vk::PipelineStageFlags2::FRAGMENT_SHADER)
+                                .dst_stage_mask(vk::PipelineStageFlags2::ALL_COMMANDS)
                                 .dst_access_mask(vk::AccessFlags2::SHADER_READ)
                                 .old_layout(vk::ImageLayout::TRANSFER_SRC_OPTIMAL)
                                 .new_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
@@ -2153,7 +3173,7 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                         let barrier_last_mip = vk::ImageMemoryBarrier2::default()
                             .src_stage_mask(vk::PipelineStageFlags2::COPY)
                             .src_access_mask(vk::AccessFlags2::TRANSFER_WRITE)
-                            .dst_stage_mask(vk::PipelineStageFlags2::FRAGMENT_SHADER)
+                            .dst_stage_mask(vk::PipelineStageFlags2::ALL_COMMANDS)
                             .dst_access_mask(vk::AccessFlags2::SHADER_READ)
                             .old_layout(vk::ImageLayout::TRANSFER_DST_OPTIMAL)
                             .new_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
@@ -2188,43 +3208,12 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                                     let mip_width = (desc.width >> mip_level).max(1);
                                     let mip_height = (desc.height >> mip_level).max(1);
 
-                                    // Create staging buffer for this mip level
-                                    let buffer_size = mip_data.len() as u64;
-                                    let staging_buffer_create_info = vk::BufferCreateInfo::default()
-                                        .size(buffer_size)
-                                        .usage(vk::BufferUsageFlags::TRANSFER_SRC)
-                                        .sharing_mode(vk::SharingMode::EXCLUSIVE);
-
-                                    let staging_buffer = self.device.create_buffer(&staging_buffer_create_info, None)
-                                        .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to create staging buffer for mip level {}: {:?}", mip_level, e))?;
-
-                                    let staging_requirements = self.device.get_buffer_memory_requirements(staging_buffer);
-
-                                    let staging_allocation = self.allocator.lock().unwrap().allocate(&gpu_allocator::vulkan::AllocationCreateDesc {
-                                        name: "mipmap_staging_buffer",
-                                        requirements: staging_requirements,
-                                        location: gpu_allocator::MemoryLocation::CpuToGpu,
-                                        linear: true,
-                                        allocation_scheme: gpu_allocator::vulkan::AllocationScheme::GpuAllocatorManaged,
-                                    })
-                                    .map_err(|_e| {
-                                        let size_mb = staging_requirements.size as f64 / (1024.0 * 1024.0);
-                                        engine_error!("galaxy3d::vulkan", "Out of GPU memory for mipmap staging buffer level {} ({:.2} MB)", mip_level, size_mb);
-                                        Error::OutOfMemory
-                                    })?;
-
-                                    self.device.bind_buffer_memory(staging_buffer, staging_allocation.memory(), staging_allocation.offset())
-                                        .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to bind staging buffer memory for mip level {}: {:?}", mip_level, e))?;
-
-                                    // Copy data to staging buffer
-                                    let mapped_ptr = staging_allocation.mapped_ptr()
-                                        .ok_or_else(|| engine_err!("galaxy3d::vulkan", "Staging buffer is not mapped for mip level {}", mip_level))?
-                                        .as_ptr() as *mut u8;
-                                    std::ptr::copy_nonoverlapping(mip_data.as_ptr(), mapped_ptr, mip_data.len());
+                                    // Copy data to the staging belt
+                                    let (staging_buffer, staging_offset) = upload.stage(mip_data, staging_alignment)?;
 
                                     // Copy to all array layers at this mip level
                                     let region = vk::BufferImageCopy::default()
-                                        .buffer_offset(0)
+                                        .buffer_offset(staging_offset)
                                         .buffer_row_length(0)
                                         .buffer_image_height(0)
                                         .image_subresource(vk::ImageSubresourceLayers {
@@ -2248,7 +3237,6 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                                         &[region],
                                     );
 
-                                    staging_buffers.push((staging_buffer, staging_allocation));
                                 }
                             }
                             ManualMipmapData::Layers(layers) => {
@@ -2270,43 +3258,12 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                                         let mip_width = (desc.width >> mip_level).max(1);
                                         let mip_height = (desc.height >> mip_level).max(1);
 
-                                        // Create staging buffer for this layer's mip level
-                                        let buffer_size = mip_data.len() as u64;
-                                        let staging_buffer_create_info = vk::BufferCreateInfo::default()
-                                            .size(buffer_size)
-                                            .usage(vk::BufferUsageFlags::TRANSFER_SRC)
-                                            .sharing_mode(vk::SharingMode::EXCLUSIVE);
-
-                                        let staging_buffer = self.device.create_buffer(&staging_buffer_create_info, None)
-                                            .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to create staging buffer for layer {} mip {}: {:?}", layer_data.layer, mip_level, e))?;
-
-                                        let staging_requirements = self.device.get_buffer_memory_requirements(staging_buffer);
-
-                                        let staging_allocation = self.allocator.lock().unwrap().allocate(&gpu_allocator::vulkan::AllocationCreateDesc {
-                                            name: "layer_mipmap_staging_buffer",
-                                            requirements: staging_requirements,
-                                            location: gpu_allocator::MemoryLocation::CpuToGpu,
-                                            linear: true,
-                                            allocation_scheme: gpu_allocator::vulkan::AllocationScheme::GpuAllocatorManaged,
-                                        })
-                                        .map_err(|_e| {
-                                            let size_mb = staging_requirements.size as f64 / (1024.0 * 1024.0);
-                                            engine_error!("galaxy3d::vulkan", "Out of GPU memory for layer {} mip {} staging buffer ({:.2} MB)", layer_data.layer, mip_level, size_mb);
-                                            Error::OutOfMemory
-                                        })?;
-
-                                        self.device.bind_buffer_memory(staging_buffer, staging_allocation.memory(), staging_allocation.offset())
-                                            .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to bind staging buffer memory for layer {} mip {}: {:?}", layer_data.layer, mip_level, e))?;
-
-                                        // Copy data to staging buffer
-                                        let mapped_ptr = staging_allocation.mapped_ptr()
-                                            .ok_or_else(|| engine_err!("galaxy3d::vulkan", "Staging buffer is not mapped for layer {} mip {}", layer_data.layer, mip_level))?
-                                            .as_ptr() as *mut u8;
-                                        std::ptr::copy_nonoverlapping(mip_data.as_ptr(), mapped_ptr, mip_data.len());
+                                        // Copy data to the staging belt
+                                        let (staging_buffer, staging_offset) = upload.stage(mip_data, staging_alignment)?;
 
                                         // Copy to specific layer at this mip level
                                         let region = vk::BufferImageCopy::default()
-                                            .buffer_offset(0)
+                                            .buffer_offset(staging_offset)
                                             .buffer_row_length(0)
                                             .buffer_image_height(0)
                                             .image_subresource(vk::ImageSubresourceLayers {
@@ -2330,7 +3287,6 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                                             &[region],
                                         );
 
-                                        staging_buffers.push((staging_buffer, staging_allocation));
                                     }
                                 }
                             }
@@ -2340,7 +3296,7 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                         let barrier_all_mips = vk::ImageMemoryBarrier2::default()
                             .src_stage_mask(vk::PipelineStageFlags2::COPY)
                             .src_access_mask(vk::AccessFlags2::TRANSFER_WRITE)
-                            .dst_stage_mask(vk::PipelineStageFlags2::FRAGMENT_SHADER)
+                            .dst_stage_mask(vk::PipelineStageFlags2::ALL_COMMANDS)
                             .dst_access_mask(vk::AccessFlags2::SHADER_READ)
                             .old_layout(vk::ImageLayout::TRANSFER_DST_OPTIMAL)
                             .new_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
@@ -2366,7 +3322,7 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                         let barrier_to_shader = vk::ImageMemoryBarrier2::default()
                             .src_stage_mask(vk::PipelineStageFlags2::COPY)
                             .src_access_mask(vk::AccessFlags2::TRANSFER_WRITE)
-                            .dst_stage_mask(vk::PipelineStageFlags2::FRAGMENT_SHADER)
+                            .dst_stage_mask(vk::PipelineStageFlags2::ALL_COMMANDS)
                             .dst_access_mask(vk::AccessFlags2::SHADER_READ)
                             .old_layout(vk::ImageLayout::TRANSFER_DST_OPTIMAL)
                             .new_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
@@ -2389,59 +3345,22 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                     }
                 }
 
-                // End recording, submit, and wait
-                self.device.end_command_buffer(command_buffer)
-                    .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to end command buffer for texture upload: {:?}", e))?;
-
-                crate::vulkan_sync::submit_command_buffers(
-                    &self.device,
-                    self.graphics_queue,
-                    &[command_buffer],
-                    &[],
-                    &[],
-                    vk::Fence::null(),
-                )?;
-
-                self.device.queue_wait_idle(self.graphics_queue)
-                    .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to wait for texture upload completion: {:?}", e))?;
-
-                // Clean up staging buffers and command pool
-                self.device.destroy_command_pool(command_pool, None);
-                for (staging_buf, staging_alloc) in staging_buffers {
-                    self.device.destroy_buffer(staging_buf, None);
-                    self.allocator.lock().unwrap().free(staging_alloc)
-                        .map_err(|_e| engine_warn_err!("galaxy3d::vulkan", "Failed to free staging buffer allocation during texture upload"))?;
-                }
-            } else if matches!(desc.usage, TextureUsage::Sampled | TextureUsage::SampledAndRenderTarget) {
-                // No data to upload — transition to SHADER_READ_ONLY_OPTIMAL
-                // (only for sampled textures; RenderTarget/DepthStencil stay UNDEFINED
-                // and the render pass handles the initial layout transition)
-                let command_pool_create_info = vk::CommandPoolCreateInfo::default()
-                    .queue_family_index(self.graphics_queue_family)
-                    .flags(vk::CommandPoolCreateFlags::TRANSIENT);
-
-                let command_pool = self.device.create_command_pool(&command_pool_create_info, None)
-                    .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to create command pool for layout transition: {:?}", e))?;
-
-                let command_buffer_allocate_info = vk::CommandBufferAllocateInfo::default()
-                    .command_pool(command_pool)
-                    .level(vk::CommandBufferLevel::PRIMARY)
-                    .command_buffer_count(1);
-
-                let command_buffers = self.device.allocate_command_buffers(&command_buffer_allocate_info)
-                    .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to allocate command buffer for layout transition: {:?}", e))?;
-                let command_buffer = command_buffers[0];
-
-                let begin_info = vk::CommandBufferBeginInfo::default()
-                    .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
-
-                self.device.begin_command_buffer(command_buffer, &begin_info)
-                    .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to begin command buffer for layout transition: {:?}", e))?;
+                // Submit without waiting: later submissions execute after
+                // the copies, and the chunks are recycled by its fence
+                upload.submit()?;
+            } else if matches!(desc.usage,
+                TextureUsage::Sampled | TextureUsage::SampledAndRenderTarget | TextureUsage::Storage)
+            {
+                // No data to upload — transition to SHADER_READ_ONLY_OPTIMAL
+                // (only for sampled and storage textures; RenderTarget/DepthStencil stay UNDEFINED
+                // and the render pass handles the initial layout transition)
+                let upload = crate::vulkan_staging_belt::Upload::begin(&self.gpu_context)?;
+                let command_buffer = upload.command_buffer();
 
                 let barrier = vk::ImageMemoryBarrier2::default()
                     .src_stage_mask(vk::PipelineStageFlags2::NONE)
                     .src_access_mask(vk::AccessFlags2::NONE)
-                    .dst_stage_mask(vk::PipelineStageFlags2::FRAGMENT_SHADER)
+                    .dst_stage_mask(vk::PipelineStageFlags2::ALL_COMMANDS)
                     .dst_access_mask(vk::AccessFlags2::SHADER_READ)
                     .old_layout(vk::ImageLayout::UNDEFINED)
                     .new_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
@@ -2462,28 +3381,14 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                     &[barrier],
                 );
 
-                self.device.end_command_buffer(command_buffer)
-                    .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to end command buffer for layout transition: {:?}", e))?;
-
-                crate::vulkan_sync::submit_command_buffers(
-                    &self.device,
-                    self.graphics_queue,
-                    &[command_buffer],
-                    &[],
-                    &[],
-                    vk::Fence::null(),
-                )?;
-
-                self.device.queue_wait_idle(self.graphics_queue)
-                    .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to wait for layout transition: {:?}", e))?;
-
-                self.device.destroy_command_pool(command_pool, None);
+                upload.submit()?;
             }
 
             // Build TextureInfo from the descriptor
             let info = TextureInfo::new(
                 desc.width,
                 desc.height,
+                depth,
                 desc.format,
                 desc.usage,
                 array_layers,
@@ -2497,6 +3402,11 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                 &self.device, desc.texture_type, view,
             );
 
+            if let Some(name) = &desc.debug_name {
+                self.gpu_context.debug_names.set_object_name(image, name);
+                self.gpu_context.debug_names.set_object_name(view, name);
+            }
+
             let mut texture = Texture::new(
                 Arc::clone(&self.gpu_context),
                 image,
@@ -2506,6 +3416,7 @@ impl GraphicsDevice for VulkanGraphicsDevice {
             );
             texture.bindless_index = bindless_index;
             texture.bindless_allocator = Some(bindless_allocator);
+            texture.mip_views = mip_views;
 
             Ok(Arc::new(texture))
         }
@@ -2514,20 +3425,30 @@ impl GraphicsDevice for VulkanGraphicsDevice {
     fn create_buffer(&mut self, desc: BufferDesc) -> Result<Arc<dyn RendererBuffer>> {
         unsafe {
             let usage = match desc.usage {
-                BufferUsage::Vertex => vk::BufferUsageFlags::VERTEX_BUFFER,
+                // Mesh shaders read the vertices as a storage buffer
+                BufferUsage::Vertex => vk::BufferUsageFlags::VERTEX_BUFFER | vk::BufferUsageFlags::STORAGE_BUFFER,
                 BufferUsage::Index => vk::BufferUsageFlags::INDEX_BUFFER,
                 BufferUsage::Uniform => vk::BufferUsageFlags::UNIFORM_BUFFER,
                 BufferUsage::Storage => vk::BufferUsageFlags::STORAGE_BUFFER,
+                // TRANSFER_SRC / TRANSFER_DST are added to every buffer below
+                BufferUsage::Readback => vk::BufferUsageFlags::empty(),
+            };
+            // Acceleration structure builds read vertices and indices by address
+            let usage = match desc.usage {
+                BufferUsage::Vertex | BufferUsage::Index if self.ray_tracing.is_some() => usage
+                    | vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS
+                    | vk::BufferUsageFlags::ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY_KHR,
+                _ => usage,
             };
 
-            // Create buffer
-            let buffer_create_info = vk::BufferCreateInfo::default()
+            // Create buffer (any buffer can be the source or destination of
+            // a copy, e.g. a storage buffer read back by `copy_buffer_to_buffer`)
+            let buffer_create_info = self.gpu_context.resource_sharing.buffer_info(vk::BufferCreateInfo::default()
                 .size(desc.size)
-                .usage(usage | vk::BufferUsageFlags::TRANSFER_DST)
-                .sharing_mode(vk::SharingMode::EXCLUSIVE);
+                .usage(usage | vk::BufferUsageFlags::TRANSFER_SRC | vk::BufferUsageFlags::TRANSFER_DST));
 
             let buffer = self.device.create_buffer(&buffer_create_info, None)
-                .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to create buffer of size {} bytes: {:?}", desc.size, e))?;
+                .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to create buffer of size {} bytes: {:?}", desc.size, e))?;
 
             // Allocate memory
             let requirements = self.device.get_buffer_memory_requirements(buffer);
@@ -2547,7 +3468,11 @@ impl GraphicsDevice for VulkanGraphicsDevice {
 
             // Bind memory
             self.device.bind_buffer_memory(buffer, allocation.memory(), allocation.offset())
-                .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to bind buffer memory: {:?}", e))?;
+                .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to bind buffer memory: {:?}", e))?;
+
+            if let Some(name) = &desc.debug_name {
+                self.gpu_context.debug_names.set_object_name(buffer, name);
+            }
 
             Ok(Arc::new(Buffer::new(
                 Arc::clone(&self.gpu_context),
@@ -2576,13 +3501,17 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                 .code(code_u32);
 
             let module = self.device.create_shader_module(&create_info, None)
-                .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to create shader module: {:?}", e))?;
+                .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to create shader module: {:?}", e))?;
 
             // SPIR-V reflection via spirq
             let stage_flags = Self::shader_stage_to_flags(desc.stage);
-            let (reflected_bindings, reflected_push_constants) =
+            let (reflected_bindings, reflected_push_constants, reflected_vertex_inputs) =
                 Self::reflect_shader(code_u32, stage_flags)?;
 
+            if let Some(name) = &desc.debug_name {
+                self.gpu_context.debug_names.set_object_name(module, name);
+            }
+
             Ok(Arc::new(Shader {
                 module,
                 stage: self.shader_stage_to_vk(desc.stage),
@@ -2590,6 +3519,7 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                 device: (*self.device).clone(),
                 reflected_bindings,
                 reflected_push_constants,
+                reflected_vertex_inputs,
             }))
         }
     }
@@ -2600,255 +3530,75 @@ impl GraphicsDevice for VulkanGraphicsDevice {
         vertex_shader: &Arc<dyn RendererShader>,
         fragment_shader: &Arc<dyn RendererShader>,
     ) -> Result<Arc<dyn RendererPipeline>> {
-        unsafe {
-            // Dynamic rendering: describe the attachment formats inline via
-            // `VkPipelineRenderingCreateInfo` instead of building a temporary
-            // VkRenderPass. The pipeline is not tied to a concrete render pass
-            // object; it is only compiled for this specific attachment layout.
-            let color_formats: Vec<vk::Format> = desc
-                .color_formats
-                .iter()
-                .map(|&f| self.format_to_vk(f))
-                .collect();
-
-            // Depth/stencil formats are derived from `desc.depth_format`.
-            // When the depth format also contains stencil (D24_UNORM_S8_UINT,
-            // D32_SFLOAT_S8_UINT), `stencil_attachment_format` is set to the
-            // same value so the pipeline remains compatible with both aspects.
-            let (depth_format_vk, stencil_format_vk) = match desc.depth_format {
-                Some(fmt) => {
-                    let vk_fmt = self.format_to_vk(fmt);
-                    let stencil_fmt = if matches!(
-                        fmt,
-                        TextureFormat::D24_UNORM_S8_UINT | TextureFormat::D32_FLOAT_S8_UINT,
-                    ) {
-                        vk_fmt
-                    } else {
-                        vk::Format::UNDEFINED
-                    };
-                    (vk_fmt, stencil_fmt)
-                }
-                None => (vk::Format::UNDEFINED, vk::Format::UNDEFINED),
-            };
-
-            let mut pipeline_rendering_info = vk::PipelineRenderingCreateInfo::default()
-                .color_attachment_formats(&color_formats)
-                .depth_attachment_format(depth_format_vk)
-                .stencil_attachment_format(stencil_format_vk);
-
-            // Downcast shaders to Vulkan types
-            let vertex_shader_vk = vertex_shader
-                .as_ref() as *const dyn RendererShader as *const Shader;
-            let vertex_shader_vk = &*vertex_shader_vk;
-
-            let fragment_shader_vk = fragment_shader
-                .as_ref() as *const dyn RendererShader as *const Shader;
-            let fragment_shader_vk = &*fragment_shader_vk;
-
-            // Create shader stage infos
-            let entry_point_vert = CString::new(vertex_shader_vk.entry_point.as_str()).unwrap();
-            let entry_point_frag = CString::new(fragment_shader_vk.entry_point.as_str()).unwrap();
-
-            let shader_stages = [
-                vk::PipelineShaderStageCreateInfo::default()
-                    .stage(vertex_shader_vk.stage)
-                    .module(vertex_shader_vk.module)
-                    .name(&entry_point_vert),
-                vk::PipelineShaderStageCreateInfo::default()
-                    .stage(fragment_shader_vk.stage)
-                    .module(fragment_shader_vk.module)
-                    .name(&entry_point_frag),
-            ];
-
-            // Vertex input state
-            let vertex_bindings: Vec<vk::VertexInputBindingDescription> = desc.vertex_layout.bindings
-                .iter()
-                .map(|binding| vk::VertexInputBindingDescription {
-                    binding: binding.binding,
-                    stride: binding.stride,
-                    input_rate: match binding.input_rate {
-                        VertexInputRate::Vertex => vk::VertexInputRate::VERTEX,
-                        VertexInputRate::Instance => vk::VertexInputRate::INSTANCE,
-                    },
-                })
-                .collect();
-
-            let vertex_attributes: Vec<vk::VertexInputAttributeDescription> = desc.vertex_layout.attributes
-                .iter()
-                .map(|attribute| vk::VertexInputAttributeDescription {
-                    location: attribute.location,
-                    binding: attribute.binding,
-                    format: self.buffer_format_to_vk(attribute.format),
-                    offset: attribute.offset,
-                })
-                .collect();
-
-            let vertex_input_state = vk::PipelineVertexInputStateCreateInfo::default()
-                .vertex_binding_descriptions(&vertex_bindings)
-                .vertex_attribute_descriptions(&vertex_attributes);
-
-            // Input assembly state
-            let input_assembly_state = vk::PipelineInputAssemblyStateCreateInfo::default()
-                .topology(self.topology_to_vk(desc.topology))
-                .primitive_restart_enable(false);
-
-            // Viewport state (dynamic)
-            let viewports = [vk::Viewport::default()];
-            let scissors = [vk::Rect2D::default()];
-            let viewport_state = vk::PipelineViewportStateCreateInfo::default()
-                .viewports(&viewports)
-                .scissors(&scissors);
-
-            // Rasterization state (cull_mode, front_face, depth_bias are dynamic)
-            let rasterization_state = vk::PipelineRasterizationStateCreateInfo::default()
-                .depth_clamp_enable(desc.rasterization.depth_clamp_enable)
-                .rasterizer_discard_enable(false)
-                .polygon_mode(self.polygon_mode_to_vk(desc.rasterization.polygon_mode))
-                .line_width(1.0)
-                .cull_mode(vk::CullModeFlags::BACK)
-                .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
-                .depth_bias_enable(false);
-
-            // Depth/stencil state (all fields are dynamic)
-            let depth_stencil_state = vk::PipelineDepthStencilStateCreateInfo::default()
-                .depth_test_enable(false)
-                .depth_write_enable(false)
-                .depth_compare_op(vk::CompareOp::LESS)
-                .depth_bounds_test_enable(false)
-                .stencil_test_enable(false);
-
-            // Multisample state
-            let multisample_state = vk::PipelineMultisampleStateCreateInfo::default()
-                .sample_shading_enable(false)
-                .rasterization_samples(self.sample_count_to_vk(desc.multisample.sample_count))
-                .alpha_to_coverage_enable(desc.multisample.alpha_to_coverage_enable);
-
-            // Color blend state
-            let color_blend_attachment = {
-                let mut attachment = vk::PipelineColorBlendAttachmentState::default()
-                    .color_write_mask(color_write_mask_to_vk(desc.color_blend.color_write_mask))
-                    .blend_enable(desc.color_blend.blend_enable);
-                if desc.color_blend.blend_enable {
-                    attachment = attachment
-                        .src_color_blend_factor(self.blend_factor_to_vk(desc.color_blend.src_color_factor))
-                        .dst_color_blend_factor(self.blend_factor_to_vk(desc.color_blend.dst_color_factor))
-                        .color_blend_op(self.blend_op_to_vk(desc.color_blend.color_blend_op))
-                        .src_alpha_blend_factor(self.blend_factor_to_vk(desc.color_blend.src_alpha_factor))
-                        .dst_alpha_blend_factor(self.blend_factor_to_vk(desc.color_blend.dst_alpha_factor))
-                        .alpha_blend_op(self.blend_op_to_vk(desc.color_blend.alpha_blend_op));
-                }
-                attachment
-            };
-
-            let color_blend_state = vk::PipelineColorBlendStateCreateInfo::default()
-                .logic_op_enable(false)
-                .attachments(std::slice::from_ref(&color_blend_attachment));
-
-            // Dynamic state — all per-draw states are set via set_dynamic_state()
-            let dynamic_states = vec![
-                // Vulkan 1.0 core
-                vk::DynamicState::VIEWPORT,
-                vk::DynamicState::SCISSOR,
-                vk::DynamicState::DEPTH_BIAS,
-                vk::DynamicState::DEPTH_BOUNDS,
-                vk::DynamicState::BLEND_CONSTANTS,
-                vk::DynamicState::STENCIL_COMPARE_MASK,
-                vk::DynamicState::STENCIL_WRITE_MASK,
-                vk::DynamicState::STENCIL_REFERENCE,
-                // Vulkan 1.3 / VK_EXT_extended_dynamic_state
-                vk::DynamicState::CULL_MODE,
-                vk::DynamicState::FRONT_FACE,
-                vk::DynamicState::DEPTH_TEST_ENABLE,
-                vk::DynamicState::DEPTH_WRITE_ENABLE,
-                vk::DynamicState::DEPTH_COMPARE_OP,
-                vk::DynamicState::DEPTH_BIAS_ENABLE,
-                vk::DynamicState::DEPTH_BOUNDS_TEST_ENABLE,
-                vk::DynamicState::STENCIL_TEST_ENABLE,
-                vk::DynamicState::STENCIL_OP,
-            ];
-            let dynamic_state = vk::PipelineDynamicStateCreateInfo::default()
-                .dynamic_states(&dynamic_states);
-
-            // Deduce pipeline layout from shader reflection
-            let merged_bindings = Self::merge_reflected_bindings(
-                vertex_shader.reflected_bindings(),
-                fragment_shader.reflected_bindings(),
-            )?;
-
-            // Build VkDescriptorSetLayouts from merged reflected bindings (sets 1+)
-            let reflected_set_layouts = self.build_descriptor_set_layouts(&merged_bindings)?;
-
-            // Inject bindless layout at set 0 only if the shader actually declares bindings there.
-            // Set 0 is reserved for the bindless descriptor set; pipelines that don't use textures
-            // don't need it in their pipeline layout.
-            let uses_bindless = merged_bindings.iter().any(|b| b.set == 0);
-            let descriptor_set_layouts: Vec<vk::DescriptorSetLayout> = if uses_bindless {
-                std::iter::once(self.bindless_state.layout)
-                    .chain(reflected_set_layouts.iter().copied())
-                    .collect()
-            } else {
-                reflected_set_layouts.clone()
-            };
-
-            // Build VkPushConstantRanges from merged reflected push constants
-            let push_constant_ranges = Self::build_push_constant_ranges(
-                vertex_shader.reflected_push_constants(),
-                fragment_shader.reflected_push_constants(),
-            );
+        self.build_graphics_pipeline(desc, &[vertex_shader, fragment_shader], true)
+    }
 
-            let mut layout_create_info = vk::PipelineLayoutCreateInfo::default();
+    fn supports_mesh_shaders(&self) -> bool {
+        self.mesh_shader.is_some()
+    }
 
-            // Add descriptor set layouts (set 0 = bindless, sets 1+ = reflected)
-            if !descriptor_set_layouts.is_empty() {
-                layout_create_info = layout_create_info.set_layouts(&descriptor_set_layouts);
-            }
+    fn create_mesh_pipeline(
+        &mut self,
+        desc: PipelineDesc,
+        task_shader: Option<&Arc<dyn RendererShader>>,
+        mesh_shader: &Arc<dyn RendererShader>,
+        fragment_shader: &Arc<dyn RendererShader>,
+    ) -> Result<Arc<dyn RendererPipeline>> {
+        if self.mesh_shader.is_none() {
+            engine_bail_warn!("galaxy3d::vulkan",
+                "create_mesh_pipeline: VK_EXT_mesh_shader is not supported by this device");
+        }
+        let shaders: Vec<&Arc<dyn RendererShader>> = task_shader.into_iter()
+            .chain([mesh_shader, fragment_shader])
+            .collect();
+        self.build_graphics_pipeline(desc, &shaders, false)
+    }
 
-            // Add push constant ranges if present
-            if !push_constant_ranges.is_empty() {
-                layout_create_info = layout_create_info.push_constant_ranges(&push_constant_ranges);
-            }
+    fn supports_ray_tracing(&self) -> bool {
+        self.ray_tracing.is_some()
+    }
 
-            let layout = self.device.create_pipeline_layout(&layout_create_info, None)
-                .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to create pipeline layout: {:?}", e))?;
+    fn uniform_buffer_alignment(&self) -> u64 {
+        self.uniform_buffer_alignment
+    }
 
-            // Create pipeline. With dynamic rendering, no VkRenderPass is
-            // bound — the attachment formats are carried by
-            // `VkPipelineRenderingCreateInfo` chained via `pNext`.
-            let pipeline_create_info = vk::GraphicsPipelineCreateInfo::default()
-                .push_next(&mut pipeline_rendering_info)
-                .stages(&shader_stages)
-                .vertex_input_state(&vertex_input_state)
-                .input_assembly_state(&input_assembly_state)
-                .viewport_state(&viewport_state)
-                .rasterization_state(&rasterization_state)
-                .depth_stencil_state(&depth_stencil_state)
-                .multisample_state(&multisample_state)
-                .color_blend_state(&color_blend_state)
-                .dynamic_state(&dynamic_state)
-                .layout(layout);
+    fn supports_pipeline_statistics_queries(&self) -> bool {
+        self.pipeline_statistics_query
+    }
 
-            let pipelines = self.device.create_graphics_pipelines(
-                vk::PipelineCache::null(),
-                &[pipeline_create_info],
-                None,
-            )
-            .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to create graphics pipeline: {:?}", e.1))?;
+    fn create_query_pool(&mut self, desc: &QueryPoolDesc) -> Result<Arc<dyn RendererQueryPool>> {
+        desc.validate()?;
+        if desc.query_type == QueryType::PipelineStatistics && !self.pipeline_statistics_query {
+            engine_bail_warn!("galaxy3d::vulkan", "create_query_pool: pipeline statistics queries are not supported");
+        }
+        let pool = unsafe { QueryPool::new(&self.gpu_context, desc, self.occlusion_query_precise)? };
+        Ok(Arc::new(pool))
+    }
 
-            let pipeline = pipelines[0];
+    fn create_blas(&mut self, desc: &BlasDesc) -> Result<Arc<dyn RendererAccelerationStructure>> {
+        let Some(ray_tracing) = &self.ray_tracing else {
+            engine_bail_warn!("galaxy3d::vulkan", "create_blas: ray tracing is not enabled");
+        };
+        let blas = unsafe { crate::vulkan_ray_tracing::create_blas(&self.gpu_context, ray_tracing, desc)? };
+        Ok(Arc::new(blas))
+    }
 
-            // Merge SPIR-V reflections from vertex + fragment shaders
-            let reflection = Self::merge_shader_reflections(vertex_shader, fragment_shader)?;
+    fn create_tlas(&mut self, desc: &TlasDesc) -> Result<Arc<dyn RendererAccelerationStructure>> {
+        let Some(ray_tracing) = &self.ray_tracing else {
+            engine_bail_warn!("galaxy3d::vulkan", "create_tlas: ray tracing is not enabled");
+        };
+        let tlas = unsafe { crate::vulkan_ray_tracing::create_tlas(&self.gpu_context, ray_tracing, desc)? };
+        Ok(Arc::new(tlas))
+    }
 
-            Ok(Arc::new(Pipeline {
-                pipeline,
-                pipeline_layout: layout,
-                // Only store layouts owned by this pipeline (sets 1+).
-                // Set 0 (bindless) is owned by BindlessState and must not be destroyed here.
-                descriptor_set_layouts: reflected_set_layouts,
-                device: (*self.device).clone(),
-                reflection,
-            }))
-        }
+    fn create_ray_tracing_pipeline(
+        &mut self,
+        desc: &RayTracingPipelineDesc,
+    ) -> Result<Arc<dyn RendererPipeline>> {
+        let Some(ray_tracing) = &self.ray_tracing else {
+            engine_bail_warn!("galaxy3d::vulkan", "create_ray_tracing_pipeline: ray tracing is not enabled");
+        };
+        self.build_ray_tracing_pipeline(ray_tracing, desc)
     }
 
     fn submit(&self, commands: &[&dyn RendererCommandList]) -> Result<()> {
@@ -2860,12 +3610,15 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                     true,
                     u64::MAX,
                 )
-                .map_err(|e| engine_err!("galaxy3d::vulkan", "submit: failed to wait for fence: {:?}", e))?;
+                .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "submit: failed to wait for fence: {:?}", e))?;
 
             // Reset fence
             self.device
                 .reset_fences(&[self.submit_fences[self.current_submit_fence]])
-                .map_err(|e| engine_err!("galaxy3d::vulkan", "submit: failed to reset fence: {:?}", e))?;
+                .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "submit: failed to reset fence: {:?}", e))?;
+
+            // The previous submission has completed: destroy what it kept alive
+            self.gpu_context.collect_released();
 
             // Collect command buffers into a stack-allocated fixed-capacity
             // array (no heap allocation per submit).
@@ -2882,6 +3635,9 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                 cmd_bufs[i] = (*vk_cmd).command_buffer();
             }
 
+            // Resources dropped from now on wait for this submission
+            self.gpu_context.deletion_queue.record_submit();
+
             // Submit (vkQueueSubmit2 via helper, no semaphores).
             crate::vulkan_sync::submit_command_buffers(
                 &self.device,
@@ -2891,6 +3647,7 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                 &[],
                 self.submit_fences[self.current_submit_fence],
             )?;
+            self.gpu_context.counters.record_submit();
 
             Ok(())
         }
@@ -2917,12 +3674,15 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                     true,
                     u64::MAX,
                 )
-                .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to wait for submit fence (swapchain): {:?}", e))?;
+                .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to wait for submit fence (swapchain): {:?}", e))?;
 
             // Reset fence
             self.device
                 .reset_fences(&[self.submit_fences[self.current_submit_fence]])
-                .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to reset submit fence (swapchain): {:?}", e))?;
+                .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to reset submit fence (swapchain): {:?}", e))?;
+
+            // The previous submission has completed: destroy what it kept alive
+            self.gpu_context.collect_released();
 
             // Collect command buffers into a stack-allocated fixed-capacity
             // array (no heap allocation per submit).
@@ -2939,6 +3699,9 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                 cmd_bufs[i] = (*vk_cmd).command_buffer();
             }
 
+            // Resources dropped from now on wait for this submission
+            self.gpu_context.deletion_queue.record_submit();
+
             // Submit with synchronization (vkQueueSubmit2 via helper).
             crate::vulkan_sync::submit_command_buffers(
                 &self.device,
@@ -2948,6 +3711,7 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                 &[(signal_semaphore, vk::PipelineStageFlags2::ALL_COMMANDS)],
                 self.submit_fences[self.current_submit_fence],
             )?;
+            self.gpu_context.counters.record_submit();
 
             Ok(())
         }
@@ -2957,8 +3721,10 @@ impl GraphicsDevice for VulkanGraphicsDevice {
         unsafe {
             self.device
                 .device_wait_idle()
-                .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to wait idle: {:?}", e))
+                .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to wait idle: {:?}", e))?;
         }
+        self.gpu_context.collect_released();
+        Ok(())
     }
 
     fn wait_for_previous_submit(&self) -> Result<()> {
@@ -2974,11 +3740,35 @@ impl GraphicsDevice for VulkanGraphicsDevice {
                     "wait_for_previous_submit: failed to wait for fence: {:?}", e
                 ))?;
         }
+        self.gpu_context.collect_released();
         Ok(())
     }
 
+    fn reset_transient_pools(&self) -> Result<()> {
+        // The sets may still be read by the last submit
+        self.wait_for_previous_submit()?;
+        self.transient_descriptor_pools.lock().unwrap().reset(&self.device)
+    }
+
+    fn descriptor_pool_usage(&self) -> Vec<DescriptorPoolUsage> {
+        let mut usage: Vec<DescriptorPoolUsage> = self.descriptor_pools.lock().unwrap().usage().collect();
+        usage.extend(self.transient_descriptor_pools.lock().unwrap().usage());
+        usage
+    }
+
     fn stats(&self) -> GraphicsDeviceStats {
-        GraphicsDeviceStats::default()
+        let mut stats = self.gpu_context.counters.snapshot();
+        // The report walks every live allocation: fine for a per-frame
+        // overlay, not for hot loops
+        let report = self.allocator.lock().unwrap().generate_report();
+        stats.gpu_memory_used = report.total_allocated_bytes;
+        stats.gpu_memory_reserved = report.total_reserved_bytes;
+        stats.set_descriptor_pool_usage(&self.descriptor_pool_usage());
+        stats
+    }
+
+    fn reset_stats(&self) {
+        self.gpu_context.counters.reset_frame();
     }
 
     fn resize(&mut self, _width: u32, _height: u32) {
@@ -2992,6 +3782,10 @@ impl Drop for VulkanGraphicsDevice {
             // Wait for device to finish
             self.device.device_wait_idle().ok();
 
+            // 0. Destroy the resources dropped during the last submission
+            //    (their allocations must go before the allocator)
+            self.gpu_context.collect_released();
+
             // 1. Shutdown sampler cache: destroy VkSamplers + release Arc<GpuContext>
             //    Must happen first while device is alive.
             //    After this, self.gpu_context is the sole Arc<GpuContext> owner.
@@ -3004,11 +3798,11 @@ impl Drop for VulkanGraphicsDevice {
             for &fence in &self.submit_fences {
                 self.device.destroy_fence(fence, None);
             }
-            for &pool in self.descriptor_pools.get_mut().unwrap().iter() {
-                self.device.destroy_descriptor_pool(pool, None);
-            }
+            self.descriptor_pools.get_mut().unwrap().destroy(&self.device);
+            self.transient_descriptor_pools.get_mut().unwrap().destroy(&self.device);
 
-            // 3. Destroy upload command pool from GpuContext
+            // 3. Destroy the staging belt, then the upload command pool from GpuContext
+            self.gpu_context.staging_belt.lock().unwrap().destroy(&self.gpu_context);
             {
                 let mut pool = self.gpu_context.upload_command_pool.lock().unwrap();
                 if *pool != vk::CommandPool::null() {
@@ -10,6 +10,8 @@ use gpu_allocator::vulkan::Allocation;
 use std::sync::Arc;
 
 use crate::vulkan_context::GpuContext;
+use crate::vulkan_deletion_queue::PendingDestroy;
+use crate::vulkan_staging_belt::Upload;
 
 /// Vulkan buffer implementation
 pub struct Buffer {
@@ -20,7 +22,6 @@ pub struct Buffer {
     /// GPU memory allocation
     pub(crate) allocation: Option<Allocation>,
     /// Buffer size
-    #[allow(dead_code)]
     pub(crate) size: u64,
 }
 
@@ -32,6 +33,7 @@ impl Buffer {
         allocation: Allocation,
         size: u64,
     ) -> Self {
+        ctx.counters.add_buffer_memory(allocation.size());
         Self {
             ctx,
             buffer,
@@ -39,32 +41,87 @@ impl Buffer {
             size,
         }
     }
+
+    /// Copy `data` at `offset` with a staged transfer, ordered after every
+    /// previous use of the buffer and before every later one
+    unsafe fn upload(&self, offset: u64, data: &[u8]) -> Result<()> {
+        let mut upload = Upload::begin(&self.ctx)?;
+        let command_buffer = upload.command_buffer();
+        let (staging_buffer, staging_offset) = upload.stage(data, 4)?;
+
+        let device = &self.ctx.device;
+        let before = crate::vulkan_sync::buffer_barrier2(
+            self.buffer,
+            vk::PipelineStageFlags2::ALL_COMMANDS,
+            vk::AccessFlags2::MEMORY_READ | vk::AccessFlags2::MEMORY_WRITE,
+            vk::PipelineStageFlags2::COPY,
+            vk::AccessFlags2::TRANSFER_WRITE,
+        );
+        crate::vulkan_sync::emit_barriers2(device, command_buffer, &[], &[before]);
+
+        let region = vk::BufferCopy::default()
+            .src_offset(staging_offset)
+            .dst_offset(offset)
+            .size(data.len() as u64);
+        device.cmd_copy_buffer(command_buffer, staging_buffer, self.buffer, &[region]);
+
+        let after = crate::vulkan_sync::buffer_barrier2(
+            self.buffer,
+            vk::PipelineStageFlags2::COPY,
+            vk::AccessFlags2::TRANSFER_WRITE,
+            vk::PipelineStageFlags2::ALL_COMMANDS,
+            vk::AccessFlags2::MEMORY_READ | vk::AccessFlags2::MEMORY_WRITE,
+        );
+        crate::vulkan_sync::emit_barriers2(device, command_buffer, &[], &[after]);
+
+        upload.submit()
+    }
 }
 
 impl RendererBuffer for Buffer {
     fn update(&self, offset: u64, data: &[u8]) -> Result<()> {
+        if offset + data.len() as u64 > self.size {
+            engine_bail!("galaxy3d::vulkan",
+                "Buffer update failed: range {}..{} exceeds size {}", offset, offset + data.len() as u64, self.size);
+        }
         unsafe {
             if let Some(allocation) = &self.allocation {
-                // Map memory and copy data
-                let mapped_ptr = allocation
-                    .mapped_ptr()
-                    .ok_or_else(|| engine_err!("galaxy3d::vulkan", "Buffer update failed: buffer is not CPU-accessible"))?
-                    .as_ptr() as *mut u8;
-
-                // Copy data
-                std::ptr::copy_nonoverlapping(
-                    data.as_ptr(),
-                    mapped_ptr.offset(offset as isize),
-                    data.len(),
-                );
-
-                Ok(())
+                match allocation.mapped_ptr() {
+                    // Map memory and copy data
+                    Some(mapped_ptr) => {
+                        std::ptr::copy_nonoverlapping(
+                            data.as_ptr(),
+                            (mapped_ptr.as_ptr() as *mut u8).offset(offset as isize),
+                            data.len(),
+                        );
+                        Ok(())
+                    }
+                    // Not CPU-accessible: copy through the staging belt
+                    None => self.upload(offset, data),
+                }
             } else {
                 engine_bail!("galaxy3d::vulkan", "Buffer update failed: no GPU allocation");
             }
         }
     }
 
+    fn read(&self, offset: u64, data: &mut [u8]) -> Result<()> {
+        if offset + data.len() as u64 > self.size {
+            engine_bail!("galaxy3d::vulkan",
+                "Buffer read failed: range {}..{} exceeds size {}", offset, offset + data.len() as u64, self.size);
+        }
+        let mapped_ptr = self.mapped_ptr()
+            .ok_or_else(|| engine_err!("galaxy3d::vulkan", "Buffer read failed: buffer is not CPU-accessible"))?;
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                mapped_ptr.add(offset as usize),
+                data.as_mut_ptr(),
+                data.len(),
+            );
+        }
+        Ok(())
+    }
+
     fn mapped_ptr(&self) -> Option<*mut u8> {
         self.allocation.as_ref()
             .and_then(|alloc| alloc.mapped_ptr())
@@ -74,17 +131,10 @@ impl RendererBuffer for Buffer {
 
 impl Drop for Buffer {
     fn drop(&mut self) {
-        unsafe {
-            // Free GPU memory
-            if let Some(allocation) = self.allocation.take() {
-                // Don't panic if lock fails - we still need to destroy the buffer
-                if let Ok(mut allocator) = self.ctx.allocator.lock() {
-                    allocator.free(allocation).ok();
-                }
-            }
-
-            // Destroy buffer
-            self.ctx.device.destroy_buffer(self.buffer, None);
-        }
+        // Deferred while a submitted command buffer may still read it
+        self.ctx.release(PendingDestroy::Buffer {
+            buffer: self.buffer,
+            allocation: self.allocation.take(),
+        });
     }
 }
\ No newline at end of file
@@ -10,11 +10,13 @@ use galaxy_3d_engine::galaxy3d::render::{
     BindingGroup as RendererBindingGroup,
     Texture as RendererTexture,
     Viewport, Rect2D, ClearValue, IndexType, ShaderStageFlags,
-    ImageAccess, BufferAccess, AccessType, TextureFormat,
-    DynamicRenderState, LoadOp, StoreOp,
+    ImageAccess, BufferAccess, AccessType, TextureFormat, TextureCopyRegion,
+    DynamicRenderState, LoadOp, StoreOp, PrimitiveTopology,
     CullMode, FrontFace, CompareOp, StencilOp, ColorWriteMask,
+    AccelerationStructure as RendererAccelerationStructure, TlasInstance,
+    QueryPool as RendererQueryPool, QueryType, validate_query_range,
 };
-use galaxy_3d_engine::{engine_bail, engine_err};
+use galaxy_3d_engine::{engine_bail, engine_err, engine_backend_err};
 use ash::vk;
 use std::sync::Arc;
 
@@ -23,7 +25,12 @@ use crate::vulkan_frame_buffer::Framebuffer;
 use crate::vulkan_pipeline::Pipeline;
 use crate::vulkan_buffer::Buffer;
 use crate::vulkan_binding_group::BindingGroup;
+use crate::vulkan_ray_tracing::{AccelerationStructure, RayTracingFunctions, tlas_geometry};
+use crate::vulkan_query::QueryPool;
 use crate::vulkan_texture::Texture as VulkanTexture;
+use crate::vulkan_resource_state::{ResourceStateTracker, Transition};
+use crate::vulkan_stats::DeviceCounters;
+use crate::vulkan_debug_names::DebugNames;
 
 impl CommandList {
     fn stage_flags_to_vk(flags: ShaderStageFlags) -> vk::ShaderStageFlags {
@@ -31,6 +38,12 @@ impl CommandList {
         if flags.contains_vertex() { vk_flags |= vk::ShaderStageFlags::VERTEX; }
         if flags.contains_fragment() { vk_flags |= vk::ShaderStageFlags::FRAGMENT; }
         if flags.contains_compute() { vk_flags |= vk::ShaderStageFlags::COMPUTE; }
+        if flags.contains_task() { vk_flags |= vk::ShaderStageFlags::TASK_EXT; }
+        if flags.contains_mesh() { vk_flags |= vk::ShaderStageFlags::MESH_EXT; }
+        if flags.contains_raygen() { vk_flags |= vk::ShaderStageFlags::RAYGEN_KHR; }
+        if flags.contains_miss() { vk_flags |= vk::ShaderStageFlags::MISS_KHR; }
+        if flags.contains_closest_hit() { vk_flags |= vk::ShaderStageFlags::CLOSEST_HIT_KHR; }
+        if flags.contains_any_hit() { vk_flags |= vk::ShaderStageFlags::ANY_HIT_KHR; }
         vk_flags
     }
 }
@@ -41,6 +54,15 @@ impl CommandList {
 /// for all subsequent frames (no per-frame allocation in steady state).
 const SCRATCH_CAPACITY: usize = 8;
 
+/// Query begun by `begin_query` and not ended yet
+struct ActiveQuery {
+    pool: vk::QueryPool,
+    query: u32,
+    query_type: QueryType,
+    /// Begun inside a render pass (must end inside it)
+    in_render_pass: bool,
+}
+
 /// Vulkan command list implementation
 ///
 /// Records rendering commands for later submission to the GPU.
@@ -59,6 +81,29 @@ pub struct CommandList {
     bound_pipeline_layout: Option<vk::PipelineLayout>,
     /// Bindless descriptor set (set 0) — bound on every bind_pipeline
     bindless_descriptor_set: vk::DescriptorSet,
+    /// Topology of the bound pipeline (for triangle statistics)
+    bound_topology: PrimitiveTopology,
+    /// Bind point of the bound pipeline (graphics or ray tracing)
+    bound_bind_point: vk::PipelineBindPoint,
+    /// Shader binding table regions of the bound ray tracing pipeline
+    bound_sbt: Option<[vk::StridedDeviceAddressRegionKHR; 4]>,
+    /// Device statistics counters
+    counters: Arc<DeviceCounters>,
+    /// Debug labels of pass regions
+    debug_names: Arc<DebugNames>,
+    /// VK_EXT_mesh_shader functions (None when unsupported)
+    mesh_shader: Option<ash::ext::mesh_shader::Device>,
+    /// Ray tracing functions (None unless enabled)
+    ray_tracing: Option<RayTracingFunctions>,
+    /// Dynamic rendering; false on the legacy `VkRenderPass` path
+    dynamic_rendering: bool,
+    /// Last access of each texture and buffer in this recording
+    resource_states: ResourceStateTracker,
+    /// Queries begun and not ended yet (at most one per `QueryType`)
+    active_queries: Vec<ActiveQuery>,
+    /// Scratch buffer reused every `build_tlas` to pack the instances. Same
+    /// zero-alloc policy as `barriers_scratch`.
+    instances_scratch: Vec<u8>,
     /// Scratch buffer reused every `begin_render_pass` to collect image
     /// barriers. Cleared before use; capacity grows to fit the largest
     /// render pass seen so far, then stays allocated — no heap
@@ -71,6 +116,10 @@ pub struct CommandList {
     /// per-color-attachment `VkRenderingAttachmentInfo`. Same policy as
     /// `barriers_scratch`.
     color_infos_scratch: Vec<vk::RenderingAttachmentInfo<'static>>,
+    /// Scratch buffer reused every legacy `begin_render_pass` to collect
+    /// the clear values, in render pass attachment order. Same policy as
+    /// `barriers_scratch`.
+    clear_values_scratch: Vec<vk::ClearValue>,
 }
 
 impl CommandList {
@@ -80,10 +129,23 @@ impl CommandList {
     ///
     /// * `device` - Vulkan logical device
     /// * `graphics_queue_family` - Graphics queue family index
-    pub fn new(
+    /// * `bindless_descriptor_set` - Bindless descriptor set (set 0)
+    /// * `counters` - Device statistics counters fed by this command list
+    /// * `debug_names` - Debug utils functions for command labels
+    /// * `mesh_shader` - VK_EXT_mesh_shader functions, if enabled
+    /// * `ray_tracing` - Ray tracing functions, if enabled
+    /// * `dynamic_rendering` - Dynamic rendering (false: legacy render passes)
+    /// * `validate_barriers` - Report redundant barriers and stale declared accesses
+    pub(crate) fn new(
         device: Arc<ash::Device>,
         graphics_queue_family: u32,
         bindless_descriptor_set: vk::DescriptorSet,
+        counters: Arc<DeviceCounters>,
+        debug_names: Arc<DebugNames>,
+        mesh_shader: Option<ash::ext::mesh_shader::Device>,
+        ray_tracing: Option<RayTracingFunctions>,
+        dynamic_rendering: bool,
+        validate_barriers: bool,
     ) -> Result<Self> {
         unsafe {
             // Create command pool
@@ -92,7 +154,7 @@ impl CommandList {
                 .flags(vk::CommandPoolCreateFlags::RESET_COMMAND_BUFFER);
 
             let command_pool = device.create_command_pool(&command_pool_create_info, None)
-                .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to create command pool: {:?}", e))?;
+                .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to create command pool: {:?}", e))?;
 
             // Allocate command buffer
             let command_buffer_allocate_info = vk::CommandBufferAllocateInfo::default()
@@ -101,7 +163,7 @@ impl CommandList {
                 .command_buffer_count(1);
 
             let command_buffers = device.allocate_command_buffers(&command_buffer_allocate_info)
-                .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to allocate command buffer: {:?}", e))?;
+                .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to allocate command buffer: {:?}", e))?;
 
             Ok(Self {
                 device,
@@ -111,9 +173,21 @@ impl CommandList {
                 in_render_pass: false,
                 bound_pipeline_layout: None,
                 bindless_descriptor_set,
+                bound_topology: PrimitiveTopology::TriangleList,
+                bound_bind_point: vk::PipelineBindPoint::GRAPHICS,
+                bound_sbt: None,
+                counters,
+                debug_names,
+                mesh_shader,
+                ray_tracing,
+                dynamic_rendering,
+                resource_states: ResourceStateTracker::new(validate_barriers),
+                active_queries: Vec::new(),
+                instances_scratch: Vec::new(),
                 barriers_scratch: Vec::with_capacity(SCRATCH_CAPACITY),
                 buffer_barriers_scratch: Vec::with_capacity(SCRATCH_CAPACITY),
                 color_infos_scratch: Vec::with_capacity(SCRATCH_CAPACITY),
+                clear_values_scratch: Vec::with_capacity(SCRATCH_CAPACITY),
             })
         }
     }
@@ -123,29 +197,8 @@ impl CommandList {
         self.command_buffer
     }
 
-    /// Map an AccessType to the corresponding Vulkan image layout.
-    fn access_type_to_layout(access: AccessType) -> vk::ImageLayout {
-        match access {
-            AccessType::ColorAttachmentWrite | AccessType::ColorAttachmentRead
-                => vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL,
-            AccessType::DepthStencilWrite
-                => vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL,
-            AccessType::DepthStencilReadOnly
-                => vk::ImageLayout::DEPTH_STENCIL_READ_ONLY_OPTIMAL,
-            AccessType::FragmentShaderRead | AccessType::VertexShaderRead
-            | AccessType::ComputeRead | AccessType::RayTracingRead
-                => vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
-            AccessType::ComputeWrite
-                => vk::ImageLayout::GENERAL,
-            AccessType::TransferRead
-                => vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
-            AccessType::TransferWrite
-                => vk::ImageLayout::TRANSFER_DST_OPTIMAL,
-        }
-    }
-
     /// Returns true if the texture format is a depth or depth/stencil format.
-    fn is_depth_format(format: TextureFormat) -> bool {
+    pub(crate) fn is_depth_format(format: TextureFormat) -> bool {
         matches!(format,
             TextureFormat::D16_UNORM
             | TextureFormat::D32_FLOAT
@@ -154,8 +207,121 @@ impl CommandList {
         )
     }
 
+    /// Emit the barriers of `image_accesses` and `buffer_accesses` from
+    /// their previous accesses (`begin_render_pass`, `declare_accesses`).
+    unsafe fn emit_access_barriers(
+        &mut self,
+        image_accesses: &[ImageAccess],
+        buffer_accesses: &[BufferAccess],
+    ) {
+        // Previous accesses come from the resource state tracker, then
+        // from `previous_access_type`. When both are None (first use of
+        // the texture in this frame) we transition from UNDEFINED.
+        //
+        // All barriers are batched into a single `vkCmdPipelineBarrier2`
+        // call (synchronization2) so the driver can combine stages and
+        // accesses optimally. Image and buffer barriers go into the
+        // same `VkDependencyInfo`.
+        // Reuse the persistent scratch buffers from `self`: `clear()`
+        // resets the length to 0 while keeping the already-allocated
+        // capacity, so no heap allocation happens per frame in steady
+        // state.
+        self.barriers_scratch.clear();
+        self.buffer_barriers_scratch.clear();
+
+        for access in image_accesses {
+            self.push_image_barrier(access);
+        }
+
+        for access in buffer_accesses {
+            let vk_buffer = access.buffer.as_ref()
+                as *const dyn RendererBuffer
+                as *const Buffer;
+            let vk_buffer = &*vk_buffer;
+
+            // Skip when there is nothing to synchronise on (first use,
+            // or a read after a read).
+            let transition = self.resource_states.buffer_transition(
+                vk_buffer.buffer, access.access_type, access.previous_access_type);
+            let Transition::From(prev) = transition else {
+                continue;
+            };
+            let (src_stage, src_access) = crate::vulkan_sync::access_type_to_stage_access_2(prev);
+            let (dst_stage, dst_access) =
+                crate::vulkan_sync::access_type_to_stage_access_2(access.access_type);
+
+            self.buffer_barriers_scratch.push(crate::vulkan_sync::buffer_barrier2(
+                vk_buffer.buffer,
+                src_stage,
+                src_access,
+                dst_stage,
+                dst_access,
+            ));
+        }
+
+        crate::vulkan_sync::emit_barriers2(
+            &self.device,
+            self.command_buffer,
+            &self.barriers_scratch,
+            &self.buffer_barriers_scratch,
+        );
+    }
+
+    /// Push the layout transition + synchronization barrier of one image
+    /// access into `barriers_scratch`, from the tracked access of the
+    /// texture, else `previous_access_type`, else UNDEFINED. No-op when
+    /// nothing needs to change (see `ResourceStateTracker`).
+    ///
+    /// # Safety
+    ///
+    /// `access.texture` must be a Vulkan `Texture`.
+    unsafe fn push_image_barrier(&mut self, access: &ImageAccess) {
+        let vk_texture = access.texture.as_ref()
+            as *const dyn RendererTexture
+            as *const VulkanTexture;
+        let vk_texture = &*vk_texture;
+
+        let new_layout = crate::vulkan_sync::access_type_to_layout(access.access_type);
+        let (dst_stage, dst_access) =
+            crate::vulkan_sync::access_type_to_stage_access_2(access.access_type);
+
+        let transition = self.resource_states.image_transition(
+            vk_texture.image, access.access_type, access.previous_access_type);
+        let (old_layout, src_stage, src_access) = match transition {
+            Transition::None => return,
+            Transition::From(prev) => {
+                let layout = crate::vulkan_sync::access_type_to_layout(prev);
+                let (stage, acc) =
+                    crate::vulkan_sync::access_type_to_stage_access_2(prev);
+                (layout, stage, acc)
+            }
+            Transition::Initial => (
+                vk::ImageLayout::UNDEFINED,
+                vk::PipelineStageFlags2::NONE,
+                vk::AccessFlags2::NONE,
+            ),
+        };
+
+        let aspect_mask = if Self::is_depth_format(vk_texture.info.format) {
+            vk::ImageAspectFlags::DEPTH
+        } else {
+            vk::ImageAspectFlags::COLOR
+        };
+
+        self.barriers_scratch.push(crate::vulkan_sync::image_barrier2(
+            vk_texture.image,
+            aspect_mask,
+            old_layout,
+            new_layout,
+            src_stage,
+            src_access,
+            dst_stage,
+            dst_access,
+        ));
+    }
+
     /// Map an engine `LoadOp` to the Vulkan attachment load op.
-    fn load_op_to_vk(op: LoadOp) -> vk::AttachmentLoadOp {
+    pub(crate) fn load_op_to_vk(op: LoadOp) -> vk::AttachmentLoadOp {
         match op {
             LoadOp::Load => vk::AttachmentLoadOp::LOAD,
             LoadOp::Clear => vk::AttachmentLoadOp::CLEAR,
@@ -164,7 +330,7 @@ impl CommandList {
     }
 
     /// Map an engine `StoreOp` to the Vulkan attachment store op.
-    fn store_op_to_vk(op: StoreOp) -> vk::AttachmentStoreOp {
+    pub(crate) fn store_op_to_vk(op: StoreOp) -> vk::AttachmentStoreOp {
         match op {
             StoreOp::Store => vk::AttachmentStoreOp::STORE,
             StoreOp::DontCare => vk::AttachmentStoreOp::DONT_CARE,
@@ -191,7 +357,7 @@ impl CommandList {
         unsafe {
             self.device.cmd_bind_descriptor_sets(
                 self.command_buffer,
-                vk::PipelineBindPoint::GRAPHICS,
+                self.bound_bind_point,
                 pipeline_layout,
                 first_set,
                 &[descriptor_set],
@@ -216,7 +382,7 @@ impl RendererCommandList for CommandList {
                     self.command_buffer,
                     vk::CommandBufferResetFlags::empty(),
                 )
-                .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to reset command buffer: {:?}", e))?;
+                .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to reset command buffer: {:?}", e))?;
 
             // Begin command buffer
             let begin_info = vk::CommandBufferBeginInfo::default()
@@ -224,11 +390,13 @@ impl RendererCommandList for CommandList {
 
             self.device
                 .begin_command_buffer(self.command_buffer, &begin_info)
-                .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to begin command buffer: {:?}", e))?;
+                .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to begin command buffer: {:?}", e))?;
 
             self.is_recording = true;
             self.in_render_pass = false;
             self.bound_pipeline_layout = None;
+            self.resource_states.reset();
+            self.active_queries.clear();
 
             Ok(())
         }
@@ -243,10 +411,14 @@ impl RendererCommandList for CommandList {
             engine_bail!("galaxy3d::vulkan", "end: render pass not ended before ending command list");
         }
 
+        if let Some(active) = self.active_queries.first() {
+            engine_bail!("galaxy3d::vulkan", "end: {:?} query {} not ended", active.query_type, active.query);
+        }
+
         unsafe {
             self.device
                 .end_command_buffer(self.command_buffer)
-                .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to end command buffer: {:?}", e))?;
+                .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to end command buffer: {:?}", e))?;
 
             self.is_recording = false;
 
@@ -274,103 +446,10 @@ impl RendererCommandList for CommandList {
             // Dynamic rendering: with no VkRenderPass, layout transitions that
             // used to be carried by subpass dependencies / initialLayout must
             // now be emitted explicitly here, for ALL accesses (attachments
-            // included). When `previous_access_type` is None (first use of
-            // the texture in this frame) we transition from UNDEFINED.
-            //
-            // All barriers are batched into a single `vkCmdPipelineBarrier2`
-            // call (synchronization2) so the driver can combine stages and
-            // accesses optimally. Image and buffer barriers go into the
-            // same `VkDependencyInfo`.
-            // Reuse the persistent scratch buffers from `self`: `clear()`
-            // resets the length to 0 while keeping the already-allocated
-            // capacity, so no heap allocation happens per frame in steady
-            // state.
-            self.barriers_scratch.clear();
-            self.buffer_barriers_scratch.clear();
-
-            for access in image_accesses {
-                let new_layout = Self::access_type_to_layout(access.access_type);
-                let (dst_stage, dst_access) =
-                    crate::vulkan_sync::access_type_to_stage_access_2(access.access_type);
-
-                let (old_layout, src_stage, src_access) = match access.previous_access_type {
-                    Some(prev) => {
-                        let layout = Self::access_type_to_layout(prev);
-                        let (stage, acc) =
-                            crate::vulkan_sync::access_type_to_stage_access_2(prev);
-                        (layout, stage, acc)
-                    }
-                    None => (
-                        vk::ImageLayout::UNDEFINED,
-                        vk::PipelineStageFlags2::NONE,
-                        vk::AccessFlags2::NONE,
-                    ),
-                };
-
-                // Skip emission when neither a layout transition nor a
-                // synchronization between two accesses is required.
-                if old_layout == new_layout && access.previous_access_type.is_none() {
-                    continue;
-                }
-
-                let vk_texture = access.texture.as_ref()
-                    as *const dyn RendererTexture
-                    as *const VulkanTexture;
-                let vk_texture = &*vk_texture;
-
-                let aspect_mask = if Self::is_depth_format(vk_texture.info.format) {
-                    vk::ImageAspectFlags::DEPTH
-                } else {
-                    vk::ImageAspectFlags::COLOR
-                };
-
-                self.barriers_scratch.push(crate::vulkan_sync::image_barrier2(
-                    vk_texture.image,
-                    aspect_mask,
-                    old_layout,
-                    new_layout,
-                    src_stage,
-                    src_access,
-                    dst_stage,
-                    dst_access,
-                ));
-            }
-
-            for access in buffer_accesses {
-                let (dst_stage, dst_access) =
-                    crate::vulkan_sync::access_type_to_stage_access_2(access.access_type);
-                let (src_stage, src_access) = match access.previous_access_type {
-                    Some(prev) => crate::vulkan_sync::access_type_to_stage_access_2(prev),
-                    // First use this frame: nothing to wait on.
-                    None => (vk::PipelineStageFlags2::NONE, vk::AccessFlags2::NONE),
-                };
+            // included). Legacy render passes keep their attachment layouts
+            // (initialLayout == finalLayout) and rely on the same barriers.
+            self.emit_access_barriers(image_accesses, buffer_accesses);
 
-                // Skip when there is nothing to synchronise on (first use
-                // and no previous access).
-                if access.previous_access_type.is_none() {
-                    continue;
-                }
-
-                let vk_buffer = access.buffer.as_ref()
-                    as *const dyn RendererBuffer
-                    as *const Buffer;
-                let vk_buffer = &*vk_buffer;
-
-                self.buffer_barriers_scratch.push(crate::vulkan_sync::buffer_barrier2(
-                    vk_buffer.buffer,
-                    src_stage,
-                    src_access,
-                    dst_stage,
-                    dst_access,
-                ));
-            }
-
-            crate::vulkan_sync::emit_barriers2(
-                &self.device,
-                self.command_buffer,
-                &self.barriers_scratch,
-                &self.buffer_barriers_scratch,
-            );
             // Downcast to Vulkan types
             let vk_render_pass = render_pass.as_ref()
                 as *const dyn RendererRenderPass
@@ -458,7 +537,25 @@ impl RendererCommandList for CommandList {
             // Silence unused-var warning in the no-depth branch.
             let _ = has_depth;
 
-            self.device.cmd_begin_rendering(self.command_buffer, &rendering_info);
+            if self.dynamic_rendering {
+                self.device.cmd_begin_rendering(self.command_buffer, &rendering_info);
+            } else {
+                // Legacy path: same attachments through the VkRenderPass /
+                // VkFramebuffer pair. Clear values follow its attachment
+                // order: colors, depth, then resolves (never cleared).
+                self.clear_values_scratch.clear();
+                self.clear_values_scratch.extend(self.color_infos_scratch.iter().map(|info| info.clear_value));
+                self.clear_values_scratch.extend(depth_rendering_info.iter().map(|info| info.clear_value));
+                self.clear_values_scratch.extend(
+                    vk_render_pass.color_resolve_attachments.iter().map(|_| vk::ClearValue::default()));
+
+                let begin_info = vk::RenderPassBeginInfo::default()
+                    .render_pass(vk_render_pass.legacy_render_pass)
+                    .framebuffer(vk_framebuffer.legacy_framebuffer)
+                    .render_area(render_area)
+                    .clear_values(&self.clear_values_scratch);
+                self.device.cmd_begin_render_pass(self.command_buffer, &begin_info, vk::SubpassContents::INLINE);
+            }
 
             self.in_render_pass = true;
 
@@ -475,14 +572,40 @@ impl RendererCommandList for CommandList {
             engine_bail!("galaxy3d::vulkan", "end_render_pass: not inside a render pass");
         }
 
+        if let Some(active) = self.active_queries.iter().find(|a| a.in_render_pass) {
+            engine_bail!("galaxy3d::vulkan",
+                "end_render_pass: {:?} query {} begun in the pass not ended", active.query_type, active.query);
+        }
+
         unsafe {
-            self.device.cmd_end_rendering(self.command_buffer);
+            if self.dynamic_rendering {
+                self.device.cmd_end_rendering(self.command_buffer);
+            } else {
+                self.device.cmd_end_render_pass(self.command_buffer);
+            }
             self.in_render_pass = false;
 
             Ok(())
         }
     }
 
+    fn declare_accesses(
+        &mut self,
+        image_accesses: &[ImageAccess],
+        buffer_accesses: &[BufferAccess],
+    ) -> Result<()> {
+        if !self.is_recording {
+            engine_bail!("galaxy3d::vulkan", "declare_accesses: command list not recording");
+        }
+        if self.in_render_pass {
+            engine_bail!("galaxy3d::vulkan", "declare_accesses: cannot emit barriers inside a render pass");
+        }
+        unsafe {
+            self.emit_access_barriers(image_accesses, buffer_accesses);
+        }
+        Ok(())
+    }
+
     fn set_viewport(&mut self, viewport: Viewport) -> Result<()> {
         if !self.is_recording {
             engine_bail!("galaxy3d::vulkan", "set_viewport: command list not recording");
@@ -574,6 +697,229 @@ impl RendererCommandList for CommandList {
         Ok(())
     }
 
+    fn resolve_texture(&mut self, src: &ImageAccess, dst: &ImageAccess) -> Result<()> {
+        if !self.is_recording {
+            engine_bail!("galaxy3d::vulkan", "resolve_texture: command list not recording");
+        }
+        if self.in_render_pass {
+            engine_bail!("galaxy3d::vulkan", "resolve_texture: cannot resolve inside a render pass");
+        }
+        if src.access_type != AccessType::TransferRead || dst.access_type != AccessType::TransferWrite {
+            engine_bail!("galaxy3d::vulkan",
+                "resolve_texture: expected TransferRead -> TransferWrite accesses, got {:?} -> {:?}",
+                src.access_type, dst.access_type);
+        }
+        let src_info = src.texture.info();
+        src_info.validate_resolve_into(dst.texture.info())?;
+
+        unsafe {
+            self.barriers_scratch.clear();
+            self.buffer_barriers_scratch.clear();
+            self.push_image_barrier(src);
+            self.push_image_barrier(dst);
+            crate::vulkan_sync::emit_barriers2(
+                &self.device,
+                self.command_buffer,
+                &self.barriers_scratch,
+                &self.buffer_barriers_scratch,
+            );
+
+            let src_vk = &*(src.texture.as_ref() as *const dyn RendererTexture as *const VulkanTexture);
+            let dst_vk = &*(dst.texture.as_ref() as *const dyn RendererTexture as *const VulkanTexture);
+
+            let subresource = vk::ImageSubresourceLayers {
+                aspect_mask: vk::ImageAspectFlags::COLOR,
+                mip_level: 0,
+                base_array_layer: 0,
+                layer_count: src_info.array_layers,
+            };
+            let region = vk::ImageResolve {
+                src_subresource: subresource,
+                src_offset: vk::Offset3D::default(),
+                dst_subresource: subresource,
+                dst_offset: vk::Offset3D::default(),
+                extent: vk::Extent3D { width: src_info.width, height: src_info.height, depth: 1 },
+            };
+            self.device.cmd_resolve_image(
+                self.command_buffer,
+                src_vk.image,
+                vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                dst_vk.image,
+                vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                &[region],
+            );
+        }
+
+        Ok(())
+    }
+
+    fn copy_texture_to_buffer(
+        &mut self,
+        src: &ImageAccess,
+        dst: &Arc<dyn RendererBuffer>,
+        regions: &[TextureCopyRegion],
+    ) -> Result<()> {
+        if !self.is_recording {
+            engine_bail!("galaxy3d::vulkan", "copy_texture_to_buffer: command list not recording");
+        }
+        if self.in_render_pass {
+            engine_bail!("galaxy3d::vulkan", "copy_texture_to_buffer: cannot copy inside a render pass");
+        }
+        if src.access_type != AccessType::TransferRead {
+            engine_bail!("galaxy3d::vulkan",
+                "copy_texture_to_buffer: expected TransferRead source access, got {:?}", src.access_type);
+        }
+        if regions.is_empty() {
+            return Ok(());
+        }
+
+        unsafe {
+            self.barriers_scratch.clear();
+            self.buffer_barriers_scratch.clear();
+            self.push_image_barrier(src);
+            crate::vulkan_sync::emit_barriers2(
+                &self.device,
+                self.command_buffer,
+                &self.barriers_scratch,
+                &self.buffer_barriers_scratch,
+            );
+
+            let src_vk = &*(src.texture.as_ref() as *const dyn RendererTexture as *const VulkanTexture);
+            let dst_vk = &*(dst.as_ref() as *const dyn RendererBuffer as *const Buffer);
+
+            let copies: Vec<vk::BufferImageCopy> = regions.iter().map(|r| vk::BufferImageCopy {
+                buffer_offset: r.buffer_offset,
+                buffer_row_length: 0,
+                buffer_image_height: 0,
+                image_subresource: vk::ImageSubresourceLayers {
+                    aspect_mask: vk::ImageAspectFlags::COLOR,
+                    mip_level: 0,
+                    base_array_layer: 0,
+                    layer_count: 1,
+                },
+                image_offset: vk::Offset3D { x: r.rect.x, y: r.rect.y, z: 0 },
+                image_extent: vk::Extent3D { width: r.rect.width, height: r.rect.height, depth: 1 },
+            }).collect();
+            self.device.cmd_copy_image_to_buffer(
+                self.command_buffer,
+                src_vk.image,
+                vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                dst_vk.buffer,
+                &copies,
+            );
+            self.resource_states.record_buffer(dst_vk.buffer, AccessType::TransferWrite);
+
+            // Make the copied data visible to host reads after the fence wait
+            self.buffer_barriers_scratch.clear();
+            self.buffer_barriers_scratch.push(crate::vulkan_sync::buffer_barrier2(
+                dst_vk.buffer,
+                vk::PipelineStageFlags2::COPY,
+                vk::AccessFlags2::TRANSFER_WRITE,
+                vk::PipelineStageFlags2::HOST,
+                vk::AccessFlags2::HOST_READ,
+            ));
+            crate::vulkan_sync::emit_barriers2(
+                &self.device,
+                self.command_buffer,
+                &[],
+                &self.buffer_barriers_scratch,
+            );
+        }
+
+        Ok(())
+    }
+
+    fn copy_buffer_to_buffer(
+        &mut self,
+        src: &BufferAccess,
+        src_offset: u64,
+        dst: &Arc<dyn RendererBuffer>,
+        dst_offset: u64,
+        size: u64,
+    ) -> Result<()> {
+        if !self.is_recording {
+            engine_bail!("galaxy3d::vulkan", "copy_buffer_to_buffer: command list not recording");
+        }
+        if self.in_render_pass {
+            engine_bail!("galaxy3d::vulkan", "copy_buffer_to_buffer: cannot copy inside a render pass");
+        }
+        if src.access_type != AccessType::TransferRead {
+            engine_bail!("galaxy3d::vulkan",
+                "copy_buffer_to_buffer: expected TransferRead source access, got {:?}", src.access_type);
+        }
+        if size == 0 {
+            return Ok(());
+        }
+
+        unsafe {
+            let src_vk = &*(src.buffer.as_ref() as *const dyn RendererBuffer as *const Buffer);
+            let dst_vk = &*(dst.as_ref() as *const dyn RendererBuffer as *const Buffer);
+
+            self.buffer_barriers_scratch.clear();
+            let transition = self.resource_states.buffer_transition(
+                src_vk.buffer, AccessType::TransferRead, src.previous_access_type);
+            if let Transition::From(prev) = transition {
+                let (src_stage, src_access) =
+                    crate::vulkan_sync::access_type_to_stage_access_2(prev);
+                self.buffer_barriers_scratch.push(crate::vulkan_sync::buffer_barrier2(
+                    src_vk.buffer,
+                    src_stage,
+                    src_access,
+                    vk::PipelineStageFlags2::COPY,
+                    vk::AccessFlags2::TRANSFER_READ,
+                ));
+                crate::vulkan_sync::emit_barriers2(
+                    &self.device,
+                    self.command_buffer,
+                    &[],
+                    &self.buffer_barriers_scratch,
+                );
+            }
+
+            self.device.cmd_copy_buffer(
+                self.command_buffer,
+                src_vk.buffer,
+                dst_vk.buffer,
+                &[vk::BufferCopy { src_offset, dst_offset, size }],
+            );
+            self.resource_states.record_buffer(dst_vk.buffer, AccessType::TransferWrite);
+
+            // Make the copied data visible to host reads after the fence wait
+            self.buffer_barriers_scratch.clear();
+            self.buffer_barriers_scratch.push(crate::vulkan_sync::buffer_barrier2(
+                dst_vk.buffer,
+                vk::PipelineStageFlags2::COPY,
+                vk::AccessFlags2::TRANSFER_WRITE,
+                vk::PipelineStageFlags2::HOST,
+                vk::AccessFlags2::HOST_READ,
+            ));
+            crate::vulkan_sync::emit_barriers2(
+                &self.device,
+                self.command_buffer,
+                &[],
+                &self.buffer_barriers_scratch,
+            );
+        }
+
+        Ok(())
+    }
+
+    fn begin_debug_label(&mut self, name: &str) -> Result<()> {
+        if !self.is_recording {
+            engine_bail!("galaxy3d::vulkan", "begin_debug_label: command list not recording");
+        }
+        self.debug_names.begin_label(self.command_buffer, name);
+        Ok(())
+    }
+
+    fn end_debug_label(&mut self) -> Result<()> {
+        if !self.is_recording {
+            engine_bail!("galaxy3d::vulkan", "end_debug_label: command list not recording");
+        }
+        self.debug_names.end_label(self.command_buffer);
+        Ok(())
+    }
+
     fn bind_pipeline(&mut self, pipeline: &Arc<dyn RendererPipeline>) -> Result<()> {
         if !self.is_recording {
             engine_bail!("galaxy3d::vulkan", "bind_pipeline: command list not recording");
@@ -586,12 +932,16 @@ impl RendererCommandList for CommandList {
 
             self.device.cmd_bind_pipeline(
                 self.command_buffer,
-                vk::PipelineBindPoint::GRAPHICS,
+                vk_pipeline.bind_point,
                 vk_pipeline.pipeline,
             );
 
             // Save pipeline layout for push constants and bind_textures
             self.bound_pipeline_layout = Some(vk_pipeline.pipeline_layout);
+            self.bound_topology = vk_pipeline.topology;
+            self.bound_bind_point = vk_pipeline.bind_point;
+            self.bound_sbt = vk_pipeline.shader_binding_table.as_ref().map(|sbt| sbt.regions);
+            self.counters.record_pipeline_bind();
 
             Ok(())
         }
@@ -605,7 +955,7 @@ impl RendererCommandList for CommandList {
 
             self.device.cmd_bind_descriptor_sets(
                 self.command_buffer,
-                vk::PipelineBindPoint::GRAPHICS,
+                self.bound_bind_point,
                 pipeline_layout,
                 0, // firstSet = 0 (bindless textures)
                 &[self.bindless_descriptor_set],
@@ -659,6 +1009,27 @@ impl RendererCommandList for CommandList {
         }
     }
 
+    fn bind_vertex_buffer_at(&mut self, binding: u32, buffer: &Arc<dyn RendererBuffer>, offset: u64) -> Result<()> {
+        if !self.is_recording {
+            engine_bail!("galaxy3d::vulkan", "bind_vertex_buffer_at: command list not recording");
+        }
+
+        unsafe {
+            // Downcast to Vulkan type
+            let vk_buffer = buffer.as_ref() as *const dyn RendererBuffer as *const Buffer;
+            let vk_buffer = &*vk_buffer;
+
+            self.device.cmd_bind_vertex_buffers(
+                self.command_buffer,
+                binding,
+                &[vk_buffer.buffer],
+                &[offset],
+            );
+
+            Ok(())
+        }
+    }
+
     fn bind_index_buffer(&mut self, buffer: &Arc<dyn RendererBuffer>, offset: u64, index_type: IndexType) -> Result<()> {
         if !self.is_recording {
             engine_bail!("galaxy3d::vulkan", "bind_index_buffer: command list not recording");
@@ -703,6 +1074,7 @@ impl RendererCommandList for CommandList {
                 first_vertex,
                 0, // first_instance
             );
+            self.counters.record_draw(self.bound_topology.triangle_count(vertex_count));
 
             Ok(())
         }
@@ -726,6 +1098,289 @@ impl RendererCommandList for CommandList {
                 vertex_offset,
                 0, // first_instance
             );
+            self.counters.record_draw(self.bound_topology.triangle_count(index_count));
+
+            Ok(())
+        }
+    }
+
+    fn draw_instanced(
+        &mut self,
+        vertex_count: u32,
+        first_vertex: u32,
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<()> {
+        if !self.is_recording {
+            engine_bail!("galaxy3d::vulkan", "draw_instanced: command list not recording");
+        }
+
+        if !self.in_render_pass {
+            engine_bail!("galaxy3d::vulkan", "draw_instanced: not inside a render pass");
+        }
+
+        unsafe {
+            self.device.cmd_draw(
+                self.command_buffer,
+                vertex_count,
+                instance_count,
+                first_vertex,
+                first_instance,
+            );
+            self.counters.record_draw(
+                self.bound_topology.triangle_count(vertex_count).saturating_mul(instance_count));
+
+            Ok(())
+        }
+    }
+
+    fn draw_indexed_instanced(
+        &mut self,
+        index_count: u32,
+        first_index: u32,
+        vertex_offset: i32,
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<()> {
+        if !self.is_recording {
+            engine_bail!("galaxy3d::vulkan", "draw_indexed_instanced: command list not recording");
+        }
+
+        if !self.in_render_pass {
+            engine_bail!("galaxy3d::vulkan", "draw_indexed_instanced: not inside a render pass");
+        }
+
+        unsafe {
+            self.device.cmd_draw_indexed(
+                self.command_buffer,
+                index_count,
+                instance_count,
+                first_index,
+                vertex_offset,
+                first_instance,
+            );
+            self.counters.record_draw(
+                self.bound_topology.triangle_count(index_count).saturating_mul(instance_count));
+
+            Ok(())
+        }
+    }
+
+    fn draw_mesh_tasks(&mut self, group_count_x: u32, group_count_y: u32, group_count_z: u32) -> Result<()> {
+        if !self.is_recording {
+            engine_bail!("galaxy3d::vulkan", "draw_mesh_tasks: command list not recording");
+        }
+
+        if !self.in_render_pass {
+            engine_bail!("galaxy3d::vulkan", "draw_mesh_tasks: not inside a render pass");
+        }
+
+        let Some(mesh_shader) = &self.mesh_shader else {
+            engine_bail!("galaxy3d::vulkan", "draw_mesh_tasks: VK_EXT_mesh_shader is not enabled");
+        };
+
+        unsafe {
+            mesh_shader.cmd_draw_mesh_tasks(self.command_buffer, group_count_x, group_count_y, group_count_z);
+            // The triangles are generated on the GPU: only the draw is counted
+            self.counters.record_draw(0);
+
+            Ok(())
+        }
+    }
+
+    fn build_tlas(
+        &mut self,
+        tlas: &Arc<dyn RendererAccelerationStructure>,
+        instances: &[TlasInstance],
+    ) -> Result<()> {
+        if !self.is_recording {
+            engine_bail!("galaxy3d::vulkan", "build_tlas: command list not recording");
+        }
+
+        if self.in_render_pass {
+            engine_bail!("galaxy3d::vulkan", "build_tlas: must be recorded outside a render pass");
+        }
+
+        let Some(ray_tracing) = &self.ray_tracing else {
+            engine_bail!("galaxy3d::vulkan", "build_tlas: ray tracing is not enabled");
+        };
+
+        unsafe {
+            // Downcast to Vulkan type
+            let vk_tlas = tlas.as_ref() as *const dyn RendererAccelerationStructure as *const AccelerationStructure;
+            let vk_tlas = &*vk_tlas;
+            let Some(buffers) = &vk_tlas.tlas else {
+                engine_bail!("galaxy3d::vulkan", "build_tlas: not a top-level acceleration structure");
+            };
+            if instances.len() > buffers.max_instances as usize {
+                engine_bail!("galaxy3d::vulkan",
+                    "build_tlas: {} instances exceed the TLAS capacity ({})", instances.len(), buffers.max_instances);
+            }
+
+            // Host writes are visible to the build once the command list is submitted
+            self.instances_scratch.clear();
+            for instance in instances {
+                instance.write_bytes(&mut self.instances_scratch);
+            }
+            buffers.instances.update(0, &self.instances_scratch)?;
+
+            // The previous build may still be read by ray tracing shaders
+            // (earlier in this command list or a previous submit)
+            let before = vk::MemoryBarrier2::default()
+                .src_stage_mask(vk::PipelineStageFlags2::RAY_TRACING_SHADER_KHR
+                    | vk::PipelineStageFlags2::FRAGMENT_SHADER
+                    | vk::PipelineStageFlags2::COMPUTE_SHADER
+                    | vk::PipelineStageFlags2::ACCELERATION_STRUCTURE_BUILD_KHR)
+                .src_access_mask(vk::AccessFlags2::ACCELERATION_STRUCTURE_READ_KHR
+                    | vk::AccessFlags2::ACCELERATION_STRUCTURE_WRITE_KHR)
+                .dst_stage_mask(vk::PipelineStageFlags2::ACCELERATION_STRUCTURE_BUILD_KHR)
+                .dst_access_mask(vk::AccessFlags2::ACCELERATION_STRUCTURE_WRITE_KHR);
+            self.device.cmd_pipeline_barrier2(
+                self.command_buffer,
+                &vk::DependencyInfo::default().memory_barriers(std::slice::from_ref(&before)),
+            );
+
+            let geometries = [tlas_geometry(buffers.instances_address)];
+            let build_info = vk::AccelerationStructureBuildGeometryInfoKHR::default()
+                .ty(vk::AccelerationStructureTypeKHR::TOP_LEVEL)
+                .flags(vk::BuildAccelerationStructureFlagsKHR::PREFER_FAST_TRACE)
+                .mode(vk::BuildAccelerationStructureModeKHR::BUILD)
+                .geometries(&geometries)
+                .dst_acceleration_structure(vk_tlas.handle)
+                .scratch_data(vk::DeviceOrHostAddressKHR { device_address: buffers.scratch_address });
+            let range = vk::AccelerationStructureBuildRangeInfoKHR {
+                primitive_count: instances.len() as u32,
+                primitive_offset: 0,
+                first_vertex: 0,
+                transform_offset: 0,
+            };
+            ray_tracing.acceleration_structure.cmd_build_acceleration_structures(
+                self.command_buffer,
+                &[build_info],
+                &[std::slice::from_ref(&range)],
+            );
+
+            // Ray queries and ray tracing shaders read the new structure
+            let after = vk::MemoryBarrier2::default()
+                .src_stage_mask(vk::PipelineStageFlags2::ACCELERATION_STRUCTURE_BUILD_KHR)
+                .src_access_mask(vk::AccessFlags2::ACCELERATION_STRUCTURE_WRITE_KHR)
+                .dst_stage_mask(vk::PipelineStageFlags2::RAY_TRACING_SHADER_KHR
+                    | vk::PipelineStageFlags2::FRAGMENT_SHADER
+                    | vk::PipelineStageFlags2::COMPUTE_SHADER)
+                .dst_access_mask(vk::AccessFlags2::ACCELERATION_STRUCTURE_READ_KHR);
+            self.device.cmd_pipeline_barrier2(
+                self.command_buffer,
+                &vk::DependencyInfo::default().memory_barriers(std::slice::from_ref(&after)),
+            );
+
+            Ok(())
+        }
+    }
+
+    fn trace_rays(&mut self, width: u32, height: u32, depth: u32) -> Result<()> {
+        if !self.is_recording {
+            engine_bail!("galaxy3d::vulkan", "trace_rays: command list not recording");
+        }
+
+        if self.in_render_pass {
+            engine_bail!("galaxy3d::vulkan", "trace_rays: must be recorded outside a render pass");
+        }
+
+        let Some(ray_tracing) = &self.ray_tracing else {
+            engine_bail!("galaxy3d::vulkan", "trace_rays: ray tracing is not enabled");
+        };
+        let Some([raygen, miss, hit, callable]) = &self.bound_sbt else {
+            engine_bail!("galaxy3d::vulkan", "trace_rays: no ray tracing pipeline bound");
+        };
+
+        unsafe {
+            ray_tracing.pipeline.cmd_trace_rays(
+                self.command_buffer, raygen, miss, hit, callable, width, height, depth);
+
+            Ok(())
+        }
+    }
+
+    fn reset_queries(
+        &mut self,
+        pool: &Arc<dyn RendererQueryPool>,
+        first_query: u32,
+        query_count: u32,
+    ) -> Result<()> {
+        if !self.is_recording {
+            engine_bail!("galaxy3d::vulkan", "reset_queries: command list not recording");
+        }
+
+        if self.in_render_pass {
+            engine_bail!("galaxy3d::vulkan", "reset_queries: must be recorded outside a render pass");
+        }
+
+        validate_query_range(pool.as_ref(), pool.query_type(), first_query, query_count)?;
+
+        unsafe {
+            let vk_pool = pool.as_ref() as *const dyn RendererQueryPool as *const QueryPool;
+            let vk_pool = &*vk_pool;
+
+            self.device.cmd_reset_query_pool(self.command_buffer, vk_pool.pool, first_query, query_count);
+
+            Ok(())
+        }
+    }
+
+    fn begin_query(&mut self, pool: &Arc<dyn RendererQueryPool>, query: u32) -> Result<()> {
+        if !self.is_recording {
+            engine_bail!("galaxy3d::vulkan", "begin_query: command list not recording");
+        }
+
+        let query_type = pool.query_type();
+        validate_query_range(pool.as_ref(), query_type, query, 1)?;
+        if self.active_queries.iter().any(|a| a.query_type == query_type) {
+            engine_bail!("galaxy3d::vulkan", "begin_query: a {:?} query is already active", query_type);
+        }
+
+        unsafe {
+            let vk_pool = pool.as_ref() as *const dyn RendererQueryPool as *const QueryPool;
+            let vk_pool = &*vk_pool;
+
+            let flags = if query_type == QueryType::Occlusion && vk_pool.precise {
+                vk::QueryControlFlags::PRECISE
+            } else {
+                vk::QueryControlFlags::empty()
+            };
+            self.device.cmd_begin_query(self.command_buffer, vk_pool.pool, query, flags);
+
+            self.active_queries.push(ActiveQuery {
+                pool: vk_pool.pool,
+                query,
+                query_type,
+                in_render_pass: self.in_render_pass,
+            });
+
+            Ok(())
+        }
+    }
+
+    fn end_query(&mut self, pool: &Arc<dyn RendererQueryPool>, query: u32) -> Result<()> {
+        if !self.is_recording {
+            engine_bail!("galaxy3d::vulkan", "end_query: command list not recording");
+        }
+
+        unsafe {
+            let vk_pool = pool.as_ref() as *const dyn RendererQueryPool as *const QueryPool;
+            let vk_pool = &*vk_pool;
+
+            let Some(index) = self.active_queries.iter()
+                .position(|a| a.pool == vk_pool.pool && a.query == query)
+            else {
+                engine_bail!("galaxy3d::vulkan", "end_query: query {} was not begun", query);
+            };
+            if self.active_queries[index].in_render_pass != self.in_render_pass {
+                engine_bail!("galaxy3d::vulkan",
+                    "end_query: query {} must end inside the render pass it began in, or outside any", query);
+            }
+            self.active_queries.swap_remove(index);
+
+            self.device.cmd_end_query(self.command_buffer, vk_pool.pool, query);
 
             Ok(())
         }
@@ -736,6 +1391,16 @@ impl RendererCommandList for CommandList {
         pipeline: &Arc<dyn RendererPipeline>,
         set_index: u32,
         binding_group: &Arc<dyn RendererBindingGroup>,
+    ) -> Result<()> {
+        self.bind_binding_group_dynamic(pipeline, set_index, binding_group, &[])
+    }
+
+    fn bind_binding_group_dynamic(
+        &mut self,
+        pipeline: &Arc<dyn RendererPipeline>,
+        set_index: u32,
+        binding_group: &Arc<dyn RendererBindingGroup>,
+        dynamic_offsets: &[u32],
     ) -> Result<()> {
         if !self.is_recording {
             engine_bail!("galaxy3d::vulkan", "bind_binding_group: command list not recording");
@@ -754,11 +1419,11 @@ impl RendererCommandList for CommandList {
             // Bind single descriptor set at the given set index
             self.device.cmd_bind_descriptor_sets(
                 self.command_buffer,
-                vk::PipelineBindPoint::GRAPHICS,
+                vk_pipeline.bind_point,
                 pipeline_layout,
                 set_index,
                 &[vk_bg.descriptor_set],
-                &[], // dynamic_offsets
+                dynamic_offsets,
             );
 
             Ok(())
@@ -799,7 +1464,7 @@ fn front_face_to_vk(face: FrontFace) -> vk::FrontFace {
 }
 
 #[inline(always)]
-fn compare_op_to_vk(op: CompareOp) -> vk::CompareOp {
+pub(crate) fn compare_op_to_vk(op: CompareOp) -> vk::CompareOp {
     match op {
         CompareOp::Never => vk::CompareOp::NEVER,
         CompareOp::Less => vk::CompareOp::LESS,
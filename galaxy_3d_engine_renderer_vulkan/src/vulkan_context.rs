@@ -4,12 +4,17 @@
 /// - Device for Vulkan API calls
 /// - Allocator for memory management
 /// - Queue for command submission
-/// - Command pool for one-shot upload operations
+/// - Command pool and staging belt for upload operations
 
 use ash::vk;
 use gpu_allocator::vulkan::Allocator;
 use std::mem::ManuallyDrop;
 use std::sync::{Arc, Mutex};
+use crate::vulkan_stats::DeviceCounters;
+use crate::vulkan_debug_names::DebugNames;
+use crate::vulkan_deletion_queue::{DeletionQueue, PendingDestroy};
+use crate::vulkan_queue_sharing::QueueSharing;
+use crate::vulkan_staging_belt::{StagingBelt, STAGING_CHUNK_SIZE};
 
 /// Shared GPU context for all Vulkan resources.
 ///
@@ -36,6 +41,21 @@ pub struct GpuContext {
     /// (created with TRANSIENT + RESET_COMMAND_BUFFER flags)
     pub upload_command_pool: Mutex<vk::CommandPool>,
 
+    /// Staging memory of texture and buffer uploads
+    pub(crate) staging_belt: Mutex<StagingBelt>,
+
+    /// Statistics counters, also shared with every command list
+    pub(crate) counters: Arc<DeviceCounters>,
+
+    /// Object names and command labels, also shared with every command list
+    pub(crate) debug_names: Arc<DebugNames>,
+
+    /// Buffers and textures dropped while a submission was in flight
+    pub(crate) deletion_queue: DeletionQueue<PendingDestroy>,
+
+    /// Sharing mode of buffers and textures (every queue family using them)
+    pub(crate) resource_sharing: QueueSharing,
+
     /// Vulkan instance (kept for reference, destroyed by VulkanGraphicsDevice)
     #[allow(dead_code)]
     instance: ash::Instance,
@@ -60,6 +80,7 @@ impl GpuContext {
     /// * `graphics_queue_family` - Graphics queue family index
     /// * `upload_command_pool` - Command pool for upload operations
     /// * `instance` - Vulkan instance
+    /// * `debug_utils_enabled` - Whether the instance enabled VK_EXT_debug_utils
     /// * `debug_utils_loader` - Debug utils loader (if validation enabled)
     /// * `debug_messenger` - Debug messenger handle (if validation enabled)
     pub fn new(
@@ -69,17 +90,24 @@ impl GpuContext {
         graphics_queue_family: u32,
         upload_command_pool: vk::CommandPool,
         instance: ash::Instance,
+        debug_utils_enabled: bool,
         #[cfg(feature = "vulkan-validation")]
         debug_utils_loader: Option<ash::ext::debug_utils::Instance>,
         #[cfg(feature = "vulkan-validation")]
         debug_messenger: Option<vk::DebugUtilsMessengerEXT>,
     ) -> Self {
+        let debug_names = Arc::new(DebugNames::new(&instance, &device, debug_utils_enabled));
         Self {
             device,
             allocator: ManuallyDrop::new(allocator),
             graphics_queue,
             graphics_queue_family,
             upload_command_pool: Mutex::new(upload_command_pool),
+            staging_belt: Mutex::new(StagingBelt::new(STAGING_CHUNK_SIZE)),
+            counters: Arc::new(DeviceCounters::default()),
+            debug_names,
+            deletion_queue: DeletionQueue::new(),
+            resource_sharing: QueueSharing::new(&[graphics_queue_family]),
             instance,
             #[cfg(feature = "vulkan-validation")]
             debug_utils_loader,
@@ -89,6 +117,25 @@ impl GpuContext {
     }
 }
 
+impl GpuContext {
+    /// Destroy a dropped resource once the GPU can no longer read it
+    pub(crate) fn release(&self, resource: PendingDestroy) {
+        if let Some(resource) = self.deletion_queue.defer(resource) {
+            resource.destroy(self);
+        }
+    }
+
+    /// Every frame submission issued so far has completed: wait for the
+    /// uploads, then destroy the resources they were all keeping alive
+    pub(crate) fn collect_released(&self) {
+        // Uploads submitted after the awaited frame may still be running
+        unsafe { self.staging_belt.lock().unwrap().recycle(self, true).ok(); }
+        for resource in self.deletion_queue.complete_all() {
+            resource.destroy(self);
+        }
+    }
+}
+
 impl Drop for GpuContext {
     fn drop(&mut self) {
         // NOTE: Device and instance destruction is handled by VulkanGraphicsDevice::drop()
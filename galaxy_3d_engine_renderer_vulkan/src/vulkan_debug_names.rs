@@ -0,0 +1,62 @@
+/// DebugNames - object names and command buffer labels (VK_EXT_debug_utils)
+///
+/// Named objects and labelled command regions show up in RenderDoc, Nsight
+/// and validation messages instead of anonymous handles. The extension is
+/// enabled on the instance whenever the loader exposes it; without it,
+/// every call is a no-op.
+
+use ash::vk;
+use std::ffi::CString;
+
+/// Device-level debug utils functions (None when the extension is not enabled)
+pub(crate) struct DebugNames {
+    loader: Option<ash::ext::debug_utils::Device>,
+}
+
+impl DebugNames {
+    /// Load the device functions when the instance enabled the extension
+    pub(crate) fn new(instance: &ash::Instance, device: &ash::Device, enabled: bool) -> Self {
+        Self {
+            loader: enabled.then(|| ash::ext::debug_utils::Device::new(instance, device)),
+        }
+    }
+
+    /// Name a Vulkan object
+    pub(crate) fn set_object_name<H: vk::Handle>(&self, handle: H, name: &str) {
+        let (Some(loader), Some(name)) = (&self.loader, to_c_string(name)) else {
+            return;
+        };
+        let info = vk::DebugUtilsObjectNameInfoEXT::default()
+            .object_handle(handle)
+            .object_name(&name);
+        // Naming is best effort: a failure only loses the debugger label
+        unsafe {
+            let _ = loader.set_debug_utils_object_name(&info);
+        }
+    }
+
+    /// Open a label region in a command buffer
+    pub(crate) fn begin_label(&self, command_buffer: vk::CommandBuffer, name: &str) {
+        let (Some(loader), Some(name)) = (&self.loader, to_c_string(name)) else {
+            return;
+        };
+        let label = vk::DebugUtilsLabelEXT::default().label_name(&name);
+        unsafe {
+            loader.cmd_begin_debug_utils_label(command_buffer, &label);
+        }
+    }
+
+    /// Close the last label region opened in a command buffer
+    pub(crate) fn end_label(&self, command_buffer: vk::CommandBuffer) {
+        if let Some(loader) = &self.loader {
+            unsafe {
+                loader.cmd_end_debug_utils_label(command_buffer);
+            }
+        }
+    }
+}
+
+/// Names with an interior NUL are dropped rather than truncated
+fn to_c_string(name: &str) -> Option<CString> {
+    CString::new(name).ok()
+}
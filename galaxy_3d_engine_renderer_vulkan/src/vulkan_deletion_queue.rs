@@ -0,0 +1,146 @@
+/// DeletionQueue - deferred destruction of GPU objects
+///
+/// A buffer or texture dropped by the resource layer may still be read by
+/// a command buffer the GPU is executing. Each release is tagged with the
+/// number of submissions issued so far and destroyed only once those
+/// submissions have completed, which the device learns when it waits on
+/// its submit fence (before the next submit) or idles. Releases made while
+/// no submission is in flight are destroyed at once.
+
+use ash::vk;
+use gpu_allocator::vulkan::Allocation;
+use galaxy_3d_engine::galaxy3d::utils::SlotAllocator;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use crate::vulkan_context::GpuContext;
+
+/// Releases waiting for submissions to complete
+pub(crate) struct DeletionQueue<T> {
+    state: Mutex<QueueState<T>>,
+}
+
+struct QueueState<T> {
+    /// Number of submissions issued
+    submitted: u64,
+    /// Number of submissions known to have completed
+    completed: u64,
+    /// Releases with the submission count they wait for, oldest first
+    pending: VecDeque<(u64, T)>,
+}
+
+impl<T> DeletionQueue<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            state: Mutex::new(QueueState { submitted: 0, completed: 0, pending: VecDeque::new() }),
+        }
+    }
+
+    /// Queue a release until the submissions issued so far complete.
+    ///
+    /// Returns the item back when no submission is in flight: the caller
+    /// destroys it immediately.
+    pub(crate) fn defer(&self, item: T) -> Option<T> {
+        let mut state = self.state.lock().unwrap();
+        if state.completed == state.submitted {
+            return Some(item);
+        }
+        let tag = state.submitted;
+        state.pending.push_back((tag, item));
+        None
+    }
+
+    /// Record one more submission
+    pub(crate) fn record_submit(&self) {
+        self.state.lock().unwrap().submitted += 1;
+    }
+
+    /// Every submission issued so far has completed: return the releases
+    /// that can now be destroyed.
+    pub(crate) fn complete_all(&self) -> Vec<T> {
+        let mut state = self.state.lock().unwrap();
+        state.completed = state.submitted;
+        let completed = state.completed;
+        let mut ready = Vec::new();
+        while state.pending.front().is_some_and(|(tag, _)| *tag <= completed) {
+            ready.push(state.pending.pop_front().unwrap().1);
+        }
+        ready
+    }
+
+    /// Number of releases waiting for the GPU
+    #[cfg(test)]
+    pub(crate) fn pending_count(&self) -> usize {
+        self.state.lock().unwrap().pending.len()
+    }
+}
+
+/// Vulkan objects of a dropped resource
+pub(crate) enum PendingDestroy {
+    Buffer {
+        buffer: vk::Buffer,
+        allocation: Option<Allocation>,
+    },
+    Texture {
+        image: vk::Image,
+        view: vk::ImageView,
+        /// Per-mip storage views
+        mip_views: Vec<vk::ImageView>,
+        allocation: Option<Allocation>,
+        /// Bindless slot to free (reusing it earlier would rewrite a
+        /// descriptor still read by the GPU)
+        bindless: Option<(Arc<Mutex<SlotAllocator>>, u32)>,
+    },
+    AccelerationStructure {
+        handle: vk::AccelerationStructureKHR,
+        loader: ash::khr::acceleration_structure::Device,
+    },
+    QueryPool {
+        pool: vk::QueryPool,
+    },
+}
+
+impl PendingDestroy {
+    /// Destroy the objects and free their memory
+    pub(crate) fn destroy(self, ctx: &GpuContext) {
+        unsafe {
+            match self {
+                PendingDestroy::Buffer { buffer, allocation } => {
+                    if let Some(allocation) = allocation {
+                        ctx.counters.remove_buffer_memory(allocation.size());
+                        // Don't panic if lock fails - we still need to destroy the buffer
+                        if let Ok(mut allocator) = ctx.allocator.lock() {
+                            allocator.free(allocation).ok();
+                        }
+                    }
+                    ctx.device.destroy_buffer(buffer, None);
+                }
+                PendingDestroy::Texture { image, view, mip_views, allocation, bindless } => {
+                    if let Some((allocator, index)) = bindless {
+                        allocator.lock().unwrap().free(index);
+                    }
+                    for mip_view in mip_views {
+                        ctx.device.destroy_image_view(mip_view, None);
+                    }
+                    ctx.device.destroy_image_view(view, None);
+                    if let Some(allocation) = allocation {
+                        ctx.counters.remove_texture_memory(allocation.size());
+                        ctx.allocator.lock().unwrap().free(allocation).ok();
+                    }
+                    ctx.device.destroy_image(image, None);
+                }
+                PendingDestroy::AccelerationStructure { handle, loader } => {
+                    // Its storage buffer is released right after it
+                    loader.destroy_acceleration_structure(handle, None);
+                }
+                PendingDestroy::QueryPool { pool } => {
+                    ctx.device.destroy_query_pool(pool, None);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "vulkan_deletion_queue_tests.rs"]
+mod tests;
@@ -0,0 +1,26 @@
+use super::*;
+
+#[test]
+fn test_release_without_submission_in_flight_is_immediate() {
+    let queue = DeletionQueue::new();
+    assert_eq!(queue.defer("a"), Some("a"));
+
+    queue.record_submit();
+    assert!(queue.complete_all().is_empty());
+    assert_eq!(queue.defer("b"), Some("b"));
+    assert_eq!(queue.pending_count(), 0);
+}
+
+#[test]
+fn test_release_waits_for_submissions_in_flight() {
+    let queue = DeletionQueue::new();
+    queue.record_submit();
+    assert_eq!(queue.defer("a"), None);
+    queue.record_submit();
+    assert_eq!(queue.defer("b"), None);
+    assert_eq!(queue.pending_count(), 2);
+
+    assert_eq!(queue.complete_all(), vec!["a", "b"]);
+    assert_eq!(queue.pending_count(), 0);
+    assert!(queue.complete_all().is_empty());
+}
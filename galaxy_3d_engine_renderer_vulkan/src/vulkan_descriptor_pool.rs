@@ -0,0 +1,152 @@
+/// DescriptorPoolSet - growable set of descriptor pools
+///
+/// Binding groups allocate their descriptor set from the current pool. When
+/// it is exhausted, allocation moves on to the next pool, created on demand
+/// with the sizes of the `DescriptorPoolConfig`. Sets are never freed one by
+/// one: persistent pools keep them until the device is destroyed, transient
+/// pools are reset as a whole by `reset()` and then refilled from the first.
+
+use ash::vk;
+use galaxy_3d_engine::galaxy3d::{Result, Error};
+use galaxy_3d_engine::galaxy3d::render::{DescriptorPoolConfig, DescriptorPoolUsage};
+use galaxy_3d_engine::{engine_info, engine_error, engine_err};
+
+/// Pool sizes of a `DescriptorPoolConfig`, leaving out the zero counts
+/// (Vulkan requires a non-zero `descriptorCount`). `ray_tracing` adds
+/// acceleration structure descriptors.
+pub(crate) fn pool_sizes(config: &DescriptorPoolConfig, ray_tracing: bool) -> Vec<vk::DescriptorPoolSize> {
+    let mut sizes = vec![
+        (vk::DescriptorType::COMBINED_IMAGE_SAMPLER, config.combined_image_samplers),
+        (vk::DescriptorType::UNIFORM_BUFFER, config.uniform_buffers),
+        (vk::DescriptorType::UNIFORM_BUFFER_DYNAMIC, config.uniform_buffers_dynamic),
+        (vk::DescriptorType::STORAGE_BUFFER, config.storage_buffers),
+        (vk::DescriptorType::STORAGE_IMAGE, config.storage_images),
+    ];
+    if ray_tracing {
+        sizes.push((vk::DescriptorType::ACCELERATION_STRUCTURE_KHR, config.acceleration_structures));
+    }
+    sizes.into_iter()
+        .filter(|&(_, count)| count > 0)
+        .map(|(ty, descriptor_count)| vk::DescriptorPoolSize { ty, descriptor_count })
+        .collect()
+}
+
+/// Descriptor pools of one kind (persistent or transient)
+pub(crate) struct DescriptorPoolSet {
+    config: DescriptorPoolConfig,
+    ray_tracing: bool,
+    transient: bool,
+    /// Pools with the number of sets allocated from each
+    pools: Vec<(vk::DescriptorPool, u32)>,
+    /// Pool the next allocation tries first; the pools after it are empty
+    current: usize,
+}
+
+impl DescriptorPoolSet {
+    /// Create the set and its first pool. `config.max_sets` must be greater
+    /// than 0 (checked by `VulkanGraphicsDevice::new`).
+    pub(crate) fn new(
+        device: &ash::Device,
+        config: DescriptorPoolConfig,
+        ray_tracing: bool,
+        transient: bool,
+    ) -> Result<Self> {
+        let mut set = Self { config, ray_tracing, transient, pools: Vec::new(), current: 0 };
+        let pool = set.create_pool(device)?;
+        set.pools.push((pool, 0));
+        Ok(set)
+    }
+
+    fn create_pool(&self, device: &ash::Device) -> Result<vk::DescriptorPool> {
+        let sizes = pool_sizes(&self.config, self.ray_tracing);
+        let info = vk::DescriptorPoolCreateInfo::default()
+            .pool_sizes(&sizes)
+            .max_sets(self.config.max_sets);
+
+        unsafe {
+            device.create_descriptor_pool(&info, None)
+                .map_err(|e| {
+                    engine_error!("galaxy3d::vulkan", "Failed to create descriptor pool: {:?}", e);
+                    Error::InitializationFailed(format!("Failed to create descriptor pool: {:?}", e))
+                })
+        }
+    }
+
+    /// Allocate a descriptor set, moving to the next pool (created if
+    /// needed) when the current one is exhausted
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if pool creation fails, or if the set does not fit
+    /// in an empty pool (the layout needs more descriptors of a type than
+    /// the `DescriptorPoolConfig` gives a pool).
+    pub(crate) fn allocate(
+        &mut self,
+        device: &ash::Device,
+        layout: vk::DescriptorSetLayout,
+    ) -> Result<vk::DescriptorSet> {
+        let layouts = [layout];
+        loop {
+            if self.current == self.pools.len() {
+                let pool = self.create_pool(device)?;
+                self.pools.push((pool, 0));
+                engine_info!("galaxy3d::vulkan",
+                    "{} descriptor pool exhausted, created new pool (total: {})",
+                    if self.transient { "Transient" } else { "Persistent" },
+                    self.pools.len()
+                );
+            }
+            let (pool, allocated) = &mut self.pools[self.current];
+            let allocate_info = vk::DescriptorSetAllocateInfo::default()
+                .descriptor_pool(*pool)
+                .set_layouts(&layouts);
+
+            match unsafe { device.allocate_descriptor_sets(&allocate_info) } {
+                Ok(sets) => {
+                    *allocated += 1;
+                    return Ok(sets[0]);
+                }
+                Err(vk::Result::ERROR_OUT_OF_POOL_MEMORY | vk::Result::ERROR_FRAGMENTED_POOL) if *allocated > 0 => {
+                    self.current += 1;
+                }
+                Err(e) => return Err(engine_err!("galaxy3d::vulkan",
+                    "Failed to allocate descriptor set: {:?}", e)),
+            }
+        }
+    }
+
+    /// Return every set to its pool. The GPU must no longer use them.
+    pub(crate) fn reset(&mut self, device: &ash::Device) -> Result<()> {
+        for (pool, allocated) in self.pools.iter_mut().filter(|(_, allocated)| *allocated > 0) {
+            unsafe {
+                device.reset_descriptor_pool(*pool, vk::DescriptorPoolResetFlags::empty())
+                    .map_err(|e| engine_err!("galaxy3d::vulkan",
+                        "Failed to reset descriptor pool: {:?}", e))?;
+            }
+            *allocated = 0;
+        }
+        self.current = 0;
+        Ok(())
+    }
+
+    /// Usage of each pool
+    pub(crate) fn usage(&self) -> impl Iterator<Item = DescriptorPoolUsage> + '_ {
+        self.pools.iter().map(|&(_, allocated_sets)| DescriptorPoolUsage {
+            transient: self.transient,
+            allocated_sets,
+            max_sets: self.config.max_sets,
+        })
+    }
+
+    /// Destroy the pools (and their sets)
+    pub(crate) fn destroy(&mut self, device: &ash::Device) {
+        for (pool, _) in self.pools.drain(..) {
+            unsafe { device.destroy_descriptor_pool(pool, None); }
+        }
+        self.current = 0;
+    }
+}
+
+#[cfg(test)]
+#[path = "vulkan_descriptor_pool_tests.rs"]
+mod tests;
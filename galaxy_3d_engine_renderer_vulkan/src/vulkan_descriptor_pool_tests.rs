@@ -0,0 +1,37 @@
+use super::*;
+
+fn count_of(sizes: &[vk::DescriptorPoolSize], ty: vk::DescriptorType) -> Option<u32> {
+    sizes.iter().find(|size| size.ty == ty).map(|size| size.descriptor_count)
+}
+
+#[test]
+fn test_pool_sizes_follow_config() {
+    let config = DescriptorPoolConfig::default();
+    let sizes = pool_sizes(&config, false);
+    assert_eq!(sizes.len(), 5);
+    assert_eq!(count_of(&sizes, vk::DescriptorType::COMBINED_IMAGE_SAMPLER), Some(2048));
+    assert_eq!(count_of(&sizes, vk::DescriptorType::UNIFORM_BUFFER), Some(1024));
+    assert_eq!(count_of(&sizes, vk::DescriptorType::UNIFORM_BUFFER_DYNAMIC), Some(256));
+    assert_eq!(count_of(&sizes, vk::DescriptorType::STORAGE_BUFFER), Some(1024));
+    assert_eq!(count_of(&sizes, vk::DescriptorType::STORAGE_IMAGE), Some(256));
+    assert_eq!(count_of(&sizes, vk::DescriptorType::ACCELERATION_STRUCTURE_KHR), None);
+}
+
+#[test]
+fn test_pool_sizes_add_acceleration_structures_with_ray_tracing() {
+    let config = DescriptorPoolConfig { acceleration_structures: 32, ..Default::default() };
+    let sizes = pool_sizes(&config, true);
+    assert_eq!(count_of(&sizes, vk::DescriptorType::ACCELERATION_STRUCTURE_KHR), Some(32));
+}
+
+#[test]
+fn test_pool_sizes_skip_zero_counts() {
+    let config = DescriptorPoolConfig {
+        storage_images: 0,
+        acceleration_structures: 0,
+        ..Default::default()
+    };
+    let sizes = pool_sizes(&config, true);
+    assert_eq!(sizes.len(), 4);
+    assert_eq!(count_of(&sizes, vk::DescriptorType::STORAGE_IMAGE), None);
+}
@@ -145,6 +145,35 @@ fn test_buffer_format_to_vk_byte_formats() {
     );
 }
 
+#[test]
+fn test_buffer_format_to_vk_compressed_formats() {
+    // Half float, normalized and packed formats
+    assert_eq!(
+        buffer_format_mapping(BufferFormat::R16G16_SFLOAT),
+        vk::Format::R16G16_SFLOAT
+    );
+    assert_eq!(
+        buffer_format_mapping(BufferFormat::R16G16B16A16_SFLOAT),
+        vk::Format::R16G16B16A16_SFLOAT
+    );
+    assert_eq!(
+        buffer_format_mapping(BufferFormat::R16G16_UNORM),
+        vk::Format::R16G16_UNORM
+    );
+    assert_eq!(
+        buffer_format_mapping(BufferFormat::R8G8B8A8_SNORM),
+        vk::Format::R8G8B8A8_SNORM
+    );
+    assert_eq!(
+        buffer_format_mapping(BufferFormat::A2B10G10R10_UNORM_PACK32),
+        vk::Format::A2B10G10R10_UNORM_PACK32
+    );
+    assert_eq!(
+        buffer_format_mapping(BufferFormat::A2B10G10R10_SNORM_PACK32),
+        vk::Format::A2B10G10R10_SNORM_PACK32
+    );
+}
+
 // ============================================================================
 // TEXTURE FORMAT CONVERSION TESTS
 // ============================================================================
@@ -172,6 +201,22 @@ fn test_texture_format_to_vk_color_formats() {
         texture_format_mapping(TextureFormat::R16G16B16A16_SFLOAT),
         vk::Format::R16G16B16A16_SFLOAT
     );
+    assert_eq!(
+        texture_format_mapping(TextureFormat::R11G11B10_UFLOAT),
+        vk::Format::B10G11R11_UFLOAT_PACK32  // Note: Vulkan names packed formats MSB-first
+    );
+    assert_eq!(
+        texture_format_mapping(TextureFormat::R16G16_SFLOAT),
+        vk::Format::R16G16_SFLOAT
+    );
+}
+
+#[test]
+fn test_texture_format_to_vk_integer_color_format() {
+    assert_eq!(
+        texture_format_mapping(TextureFormat::R32_UINT),
+        vk::Format::R32_UINT
+    );
 }
 
 #[test]
@@ -235,6 +280,27 @@ fn buffer_format_mapping(format: BufferFormat) -> vk::Format {
         BufferFormat::R8_UINT => vk::Format::R8_UINT,
         BufferFormat::R8G8_UINT => vk::Format::R8G8_UINT,
         BufferFormat::R8G8B8A8_UINT => vk::Format::R8G8B8A8_UINT,
+        // Half float formats
+        BufferFormat::R16_SFLOAT => vk::Format::R16_SFLOAT,
+        BufferFormat::R16G16_SFLOAT => vk::Format::R16G16_SFLOAT,
+        BufferFormat::R16G16B16A16_SFLOAT => vk::Format::R16G16B16A16_SFLOAT,
+        // Normalized short formats
+        BufferFormat::R16_UNORM => vk::Format::R16_UNORM,
+        BufferFormat::R16G16_UNORM => vk::Format::R16G16_UNORM,
+        BufferFormat::R16G16B16A16_UNORM => vk::Format::R16G16B16A16_UNORM,
+        BufferFormat::R16_SNORM => vk::Format::R16_SNORM,
+        BufferFormat::R16G16_SNORM => vk::Format::R16G16_SNORM,
+        BufferFormat::R16G16B16A16_SNORM => vk::Format::R16G16B16A16_SNORM,
+        // Normalized byte formats
+        BufferFormat::R8_UNORM => vk::Format::R8_UNORM,
+        BufferFormat::R8G8_UNORM => vk::Format::R8G8_UNORM,
+        BufferFormat::R8G8B8A8_UNORM => vk::Format::R8G8B8A8_UNORM,
+        BufferFormat::R8_SNORM => vk::Format::R8_SNORM,
+        BufferFormat::R8G8_SNORM => vk::Format::R8G8_SNORM,
+        BufferFormat::R8G8B8A8_SNORM => vk::Format::R8G8B8A8_SNORM,
+        // Packed formats
+        BufferFormat::A2B10G10R10_UNORM_PACK32 => vk::Format::A2B10G10R10_UNORM_PACK32,
+        BufferFormat::A2B10G10R10_SNORM_PACK32 => vk::Format::A2B10G10R10_SNORM_PACK32,
     }
 }
 
@@ -246,6 +312,9 @@ fn texture_format_mapping(format: TextureFormat) -> vk::Format {
         TextureFormat::B8G8R8A8_SRGB => vk::Format::B8G8R8A8_SRGB,
         TextureFormat::B8G8R8A8_UNORM => vk::Format::B8G8R8A8_UNORM,
         TextureFormat::R16G16B16A16_SFLOAT => vk::Format::R16G16B16A16_SFLOAT,
+        TextureFormat::R11G11B10_UFLOAT => vk::Format::B10G11R11_UFLOAT_PACK32,
+        TextureFormat::R16G16_SFLOAT => vk::Format::R16G16_SFLOAT,
+        TextureFormat::R32_UINT => vk::Format::R32_UINT,
         TextureFormat::D16_UNORM => vk::Format::D16_UNORM,
         TextureFormat::D32_FLOAT => vk::Format::D32_SFLOAT,
         TextureFormat::D24_UNORM_S8_UINT => vk::Format::D24_UNORM_S8_UINT,
@@ -1,9 +1,11 @@
 /// Framebuffer - Vulkan implementation of RendererFramebuffer trait
 ///
-/// With `VK_KHR_dynamic_rendering`, there is no `VkFramebuffer` object. The
+/// With dynamic rendering, there is no `VkFramebuffer` object. The
 /// `Framebuffer` becomes a pure Rust descriptor that owns the per-attachment
 /// `VkImageView` handles the command list needs when building a
-/// `VkRenderingInfo` at `begin_render_pass` time.
+/// `VkRenderingInfo` at `begin_render_pass` time. On the legacy path it
+/// also owns a `VkFramebuffer` over these views, for the `VkRenderPass` of
+/// its render pass.
 ///
 /// The image views are created from `FramebufferAttachment`s in
 /// `VulkanGraphicsDevice::create_framebuffer()` and destroyed by this
@@ -12,10 +14,11 @@
 use galaxy_3d_engine::galaxy3d::render::Framebuffer as RendererFramebuffer;
 use ash::vk;
 
-/// Vulkan framebuffer descriptor (data-only, no GPU framebuffer object).
+/// Vulkan framebuffer descriptor (no GPU framebuffer object with dynamic
+/// rendering).
 ///
-/// Owns the `VkImageView` handles created for each attachment and
-/// destroys them on Drop.
+/// Owns the `VkImageView` handles created for each attachment, and the
+/// legacy `VkFramebuffer`, and destroys them on Drop.
 pub struct Framebuffer {
     /// Image views for the color attachments, in render-pass order.
     pub(crate) color_image_views: Vec<vk::ImageView>,
@@ -24,11 +27,14 @@ pub struct Framebuffer {
     /// Image views for the MSAA resolve targets (same length as
     /// `color_image_views` when resolving, empty otherwise).
     pub(crate) resolve_image_views: Vec<vk::ImageView>,
+    /// Legacy `VkFramebuffer` (null with dynamic rendering), set by
+    /// `VulkanGraphicsDevice::create_framebuffer()` once the views exist.
+    pub(crate) legacy_framebuffer: vk::Framebuffer,
     /// Width in pixels
     width: u32,
     /// Height in pixels
     height: u32,
-    /// Device used to destroy the owned image views on Drop.
+    /// Device used to destroy the owned image views and framebuffer on Drop.
     device: ash::Device,
 }
 
@@ -45,6 +51,7 @@ impl Framebuffer {
             color_image_views,
             depth_image_view,
             resolve_image_views,
+            legacy_framebuffer: vk::Framebuffer::null(),
             width,
             height,
             device,
@@ -65,6 +72,9 @@ impl RendererFramebuffer for Framebuffer {
 impl Drop for Framebuffer {
     fn drop(&mut self) {
         unsafe {
+            if self.legacy_framebuffer != vk::Framebuffer::null() {
+                self.device.destroy_framebuffer(self.legacy_framebuffer, None);
+            }
             for view in &self.color_image_views {
                 self.device.destroy_image_view(*view, None);
             }
@@ -2,26 +2,34 @@
 
 use galaxy_3d_engine::galaxy3d::render::{
     Pipeline as RendererPipeline,
-    PipelineReflection,
+    PipelineReflection, PrimitiveTopology,
 };
 use ash::vk;
 
+use crate::vulkan_ray_tracing::ShaderBindingTable;
+
 /// Vulkan pipeline implementation
 ///
 /// Stores the descriptor set layouts internally (Option B design).
 /// The layouts are created from BindingGroupLayoutDesc at pipeline creation time
 /// and used by VulkanGraphicsDevice::create_binding_group() to allocate descriptor sets.
 pub struct Pipeline {
-    /// Vulkan graphics pipeline
+    /// Vulkan graphics or ray tracing pipeline
     pub(crate) pipeline: vk::Pipeline,
+    /// GRAPHICS or RAY_TRACING_KHR (binding groups are bound at this point)
+    pub(crate) bind_point: vk::PipelineBindPoint,
     /// Pipeline layout (crate-private, accessed internally for binding group binding)
     pub(crate) pipeline_layout: vk::PipelineLayout,
     /// Descriptor set layouts created for this pipeline (one per set index)
     pub(crate) descriptor_set_layouts: Vec<vk::DescriptorSetLayout>,
     /// Vulkan device (for cleanup)
     pub(crate) device: ash::Device,
-    /// SPIR-V reflection data (merged from vertex + fragment shaders)
+    /// SPIR-V reflection data (merged from all stages)
     pub(crate) reflection: PipelineReflection,
+    /// Primitive topology (for the triangle statistics of draws)
+    pub(crate) topology: PrimitiveTopology,
+    /// Shader binding table (ray tracing pipelines only)
+    pub(crate) shader_binding_table: Option<ShaderBindingTable>,
 }
 
 impl RendererPipeline for Pipeline {
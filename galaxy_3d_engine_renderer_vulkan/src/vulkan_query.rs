@@ -0,0 +1,124 @@
+/// QueryPool - Vulkan implementation of RendererQueryPool
+///
+/// Results are read with `vkGetQueryPoolResults` as 64-bit values with
+/// availability and without waiting: unavailable queries read `None`.
+
+use galaxy_3d_engine::galaxy3d::Result;
+use galaxy_3d_engine::galaxy3d::render::{
+    QueryPool as RendererQueryPool, QueryPoolDesc, QueryType,
+    PipelineStatistics, PIPELINE_STATISTICS_COUNTER_COUNT, validate_query_range,
+};
+use galaxy_3d_engine::engine_backend_err;
+use ash::vk;
+use std::sync::Arc;
+
+use crate::vulkan_context::GpuContext;
+use crate::vulkan_deletion_queue::PendingDestroy;
+
+/// Counters of a pipeline statistics query, in `PipelineStatistics` field
+/// order (Vulkan writes them by increasing bit)
+const PIPELINE_STATISTICS_FLAGS: vk::QueryPipelineStatisticFlags = vk::QueryPipelineStatisticFlags::from_raw(
+    vk::QueryPipelineStatisticFlags::INPUT_ASSEMBLY_VERTICES.as_raw()
+        | vk::QueryPipelineStatisticFlags::INPUT_ASSEMBLY_PRIMITIVES.as_raw()
+        | vk::QueryPipelineStatisticFlags::VERTEX_SHADER_INVOCATIONS.as_raw()
+        | vk::QueryPipelineStatisticFlags::CLIPPING_INVOCATIONS.as_raw()
+        | vk::QueryPipelineStatisticFlags::CLIPPING_PRIMITIVES.as_raw()
+        | vk::QueryPipelineStatisticFlags::FRAGMENT_SHADER_INVOCATIONS.as_raw()
+        | vk::QueryPipelineStatisticFlags::COMPUTE_SHADER_INVOCATIONS.as_raw(),
+);
+
+/// Vulkan query pool
+pub struct QueryPool {
+    /// Shared GPU context (deferred destruction)
+    ctx: Arc<GpuContext>,
+    /// Vulkan query pool
+    pub(crate) pool: vk::QueryPool,
+    /// Kind of the queries
+    query_type: QueryType,
+    /// Number of queries
+    count: u32,
+    /// Occlusion queries count exact samples (`occlusionQueryPrecise`)
+    pub(crate) precise: bool,
+}
+
+impl QueryPool {
+    /// Create a query pool (`desc` validated by the caller)
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - Shared GPU context
+    /// * `desc` - Query pool descriptor
+    /// * `precise` - `occlusionQueryPrecise` is enabled
+    pub(crate) unsafe fn new(ctx: &Arc<GpuContext>, desc: &QueryPoolDesc, precise: bool) -> Result<Self> {
+        let create_info = match desc.query_type {
+            QueryType::Occlusion => vk::QueryPoolCreateInfo::default()
+                .query_type(vk::QueryType::OCCLUSION),
+            QueryType::PipelineStatistics => vk::QueryPoolCreateInfo::default()
+                .query_type(vk::QueryType::PIPELINE_STATISTICS)
+                .pipeline_statistics(PIPELINE_STATISTICS_FLAGS),
+        }
+        .query_count(desc.count);
+
+        let pool = ctx.device.create_query_pool(&create_info, None)
+            .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(),
+                "Failed to create query pool of {} {:?} queries: {:?}", desc.count, desc.query_type, e))?;
+        if let Some(name) = &desc.debug_name {
+            ctx.debug_names.set_object_name(pool, name);
+        }
+
+        Ok(Self {
+            ctx: Arc::clone(ctx),
+            pool,
+            query_type: desc.query_type,
+            count: desc.count,
+            precise,
+        })
+    }
+
+    /// Read `N - 1` counters and the availability of each query
+    fn read<const N: usize>(&self, first_query: u32, query_count: u32) -> Result<Vec<[u64; N]>> {
+        let mut data = vec![[0u64; N]; query_count as usize];
+        let flags = vk::QueryResultFlags::TYPE_64 | vk::QueryResultFlags::WITH_AVAILABILITY;
+        let result = unsafe {
+            self.ctx.device.get_query_pool_results(self.pool, first_query, &mut data, flags)
+        };
+        match result {
+            // NOT_READY: some queries are unavailable, their availability is 0
+            Ok(()) | Err(vk::Result::NOT_READY) => Ok(data),
+            Err(e) => Err(engine_backend_err!("galaxy3d::vulkan", e.as_raw(),
+                "Failed to read query pool results: {:?}", e)),
+        }
+    }
+}
+
+impl RendererQueryPool for QueryPool {
+    fn query_type(&self) -> QueryType {
+        self.query_type
+    }
+
+    fn count(&self) -> u32 {
+        self.count
+    }
+
+    fn occlusion_results(&self, first_query: u32, query_count: u32) -> Result<Vec<Option<u64>>> {
+        validate_query_range(self, QueryType::Occlusion, first_query, query_count)?;
+        let data = self.read::<2>(first_query, query_count)?;
+        Ok(data.iter().map(|[samples, available]| (*available != 0).then_some(*samples)).collect())
+    }
+
+    fn pipeline_statistics(&self, first_query: u32, query_count: u32) -> Result<Vec<Option<PipelineStatistics>>> {
+        validate_query_range(self, QueryType::PipelineStatistics, first_query, query_count)?;
+        let data = self.read::<{ PIPELINE_STATISTICS_COUNTER_COUNT + 1 }>(first_query, query_count)?;
+        Ok(data.iter().map(|values| {
+            let (counters, available) = values.split_at(PIPELINE_STATISTICS_COUNTER_COUNT);
+            (available[0] != 0).then(|| PipelineStatistics::from_counters(counters.try_into().unwrap()))
+        }).collect())
+    }
+}
+
+impl Drop for QueryPool {
+    fn drop(&mut self) {
+        // Deferred while a submitted command buffer may still write queries
+        self.ctx.release(PendingDestroy::QueryPool { pool: self.pool });
+    }
+}
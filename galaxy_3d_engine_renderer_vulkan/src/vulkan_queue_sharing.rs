@@ -0,0 +1,72 @@
+/// QueueSharing - sharing mode of resources used by several queue families
+///
+/// A Vulkan image or buffer created `EXCLUSIVE` belongs to one queue family
+/// at a time: using it from another family needs a release/acquire barrier
+/// pair. `QueueSharing` is built from the families that use a resource and
+/// picks the mode for it: `EXCLUSIVE` when they are all the same family,
+/// `CONCURRENT` over the distinct families otherwise, so no ownership
+/// transfer is ever recorded by hand.
+///
+/// Used for the swapchain images (graphics + present families) and, through
+/// `GpuContext::resource_sharing`, for every buffer and texture. A queue
+/// added later (async compute, transfer) only needs its family listed there.
+
+use ash::vk;
+
+/// Queue families sharing a resource (see module docs)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct QueueSharing {
+    /// Distinct families, sorted
+    families: Vec<u32>,
+}
+
+impl QueueSharing {
+    /// Sharing between the given families (duplicates are ignored)
+    pub(crate) fn new(families: &[u32]) -> Self {
+        let mut families = families.to_vec();
+        families.sort_unstable();
+        families.dedup();
+        Self { families }
+    }
+
+    /// Whether several families use the resource
+    pub(crate) fn is_concurrent(&self) -> bool {
+        self.families.len() > 1
+    }
+
+    /// Sharing mode to create the resource with
+    pub(crate) fn mode(&self) -> vk::SharingMode {
+        if self.is_concurrent() {
+            vk::SharingMode::CONCURRENT
+        } else {
+            vk::SharingMode::EXCLUSIVE
+        }
+    }
+
+    /// Queue family indices to pass with `mode()` (empty when exclusive)
+    pub(crate) fn queue_family_indices(&self) -> &[u32] {
+        if self.is_concurrent() { &self.families } else { &[] }
+    }
+
+    /// Apply the sharing mode to a buffer create info
+    pub(crate) fn buffer_info<'a>(&'a self, info: vk::BufferCreateInfo<'a>) -> vk::BufferCreateInfo<'a> {
+        info.sharing_mode(self.mode()).queue_family_indices(self.queue_family_indices())
+    }
+
+    /// Apply the sharing mode to an image create info
+    pub(crate) fn image_info<'a>(&'a self, info: vk::ImageCreateInfo<'a>) -> vk::ImageCreateInfo<'a> {
+        info.sharing_mode(self.mode()).queue_family_indices(self.queue_family_indices())
+    }
+
+    /// Apply the sharing mode to a swapchain create info
+    pub(crate) fn swapchain_info<'a>(
+        &'a self,
+        info: vk::SwapchainCreateInfoKHR<'a>,
+    ) -> vk::SwapchainCreateInfoKHR<'a> {
+        info.image_sharing_mode(self.mode()).queue_family_indices(self.queue_family_indices())
+    }
+}
+
+#[cfg(test)]
+#[path = "vulkan_queue_sharing_tests.rs"]
+mod tests;
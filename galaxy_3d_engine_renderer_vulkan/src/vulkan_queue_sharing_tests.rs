@@ -0,0 +1,30 @@
+use super::*;
+
+#[test]
+fn test_single_family_is_exclusive() {
+    let sharing = QueueSharing::new(&[0, 0]);
+    assert!(!sharing.is_concurrent());
+    assert_eq!(sharing.mode(), vk::SharingMode::EXCLUSIVE);
+    assert!(sharing.queue_family_indices().is_empty());
+}
+
+#[test]
+fn test_distinct_families_are_concurrent() {
+    let sharing = QueueSharing::new(&[2, 0, 2]);
+    assert!(sharing.is_concurrent());
+    assert_eq!(sharing.mode(), vk::SharingMode::CONCURRENT);
+    assert_eq!(sharing.queue_family_indices(), &[0, 2]);
+}
+
+#[test]
+fn test_create_infos_receive_sharing_mode() {
+    let sharing = QueueSharing::new(&[0, 1]);
+    let buffer = sharing.buffer_info(vk::BufferCreateInfo::default());
+    assert_eq!(buffer.sharing_mode, vk::SharingMode::CONCURRENT);
+    assert_eq!(buffer.queue_family_index_count, 2);
+
+    let exclusive = QueueSharing::new(&[1]);
+    let image = exclusive.image_info(vk::ImageCreateInfo::default());
+    assert_eq!(image.sharing_mode, vk::SharingMode::EXCLUSIVE);
+    assert_eq!(image.queue_family_index_count, 0);
+}
@@ -0,0 +1,459 @@
+/// Ray tracing - Vulkan implementation of RendererAccelerationStructure
+/// (VK_KHR_acceleration_structure) and the shader binding tables of
+/// VK_KHR_ray_tracing_pipeline pipelines
+///
+/// Acceleration structures and shader binding tables live in buffers
+/// addressed by the GPU (`bufferDeviceAddress`), which the device only
+/// enables with ray tracing.
+
+use galaxy_3d_engine::galaxy3d::{
+    Error, Result,
+    render::{
+        AccelerationStructure as RendererAccelerationStructure,
+        AccelerationStructureKind, BlasDesc, Buffer as RendererBuffer,
+        BufferFormat, IndexType, ShaderBindingTableLayout, ShaderBindingTableRegion,
+        TlasDesc, TLAS_INSTANCE_SIZE,
+    },
+};
+use galaxy_3d_engine::{engine_bail, engine_error, engine_backend_err};
+use ash::vk;
+use std::sync::Arc;
+
+use crate::vulkan_buffer::Buffer;
+use crate::vulkan_context::GpuContext;
+use crate::vulkan_deletion_queue::PendingDestroy;
+
+/// Ray tracing limits of the physical device
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RayTracingProperties {
+    /// Size of a shader group handle
+    pub(crate) shader_group_handle_size: u32,
+    /// Alignment of a shader binding table record
+    pub(crate) shader_group_handle_alignment: u32,
+    /// Alignment of a shader binding table region
+    pub(crate) shader_group_base_alignment: u32,
+    /// Deepest `traceRayEXT` nesting
+    pub(crate) max_recursion_depth: u32,
+    /// Alignment of the scratch buffer address of a build
+    pub(crate) scratch_alignment: u64,
+}
+
+/// Ray tracing device functions and limits (cloned into every command list)
+#[derive(Clone)]
+pub(crate) struct RayTracingFunctions {
+    pub(crate) acceleration_structure: ash::khr::acceleration_structure::Device,
+    pub(crate) pipeline: ash::khr::ray_tracing_pipeline::Device,
+    pub(crate) properties: RayTracingProperties,
+}
+
+impl RayTracingFunctions {
+    /// Load the functions of a device created with the ray tracing
+    /// extensions and query its limits
+    pub(crate) unsafe fn new(
+        instance: &ash::Instance,
+        physical_device: vk::PhysicalDevice,
+        device: &ash::Device,
+    ) -> Self {
+        let mut pipeline_properties = vk::PhysicalDeviceRayTracingPipelinePropertiesKHR::default();
+        let mut acceleration_structure_properties = vk::PhysicalDeviceAccelerationStructurePropertiesKHR::default();
+        let mut properties2 = vk::PhysicalDeviceProperties2::default()
+            .push_next(&mut pipeline_properties)
+            .push_next(&mut acceleration_structure_properties);
+        instance.get_physical_device_properties2(physical_device, &mut properties2);
+
+        Self {
+            acceleration_structure: ash::khr::acceleration_structure::Device::new(instance, device),
+            pipeline: ash::khr::ray_tracing_pipeline::Device::new(instance, device),
+            properties: RayTracingProperties {
+                shader_group_handle_size: pipeline_properties.shader_group_handle_size,
+                shader_group_handle_alignment: pipeline_properties.shader_group_handle_alignment,
+                shader_group_base_alignment: pipeline_properties.shader_group_base_alignment,
+                max_recursion_depth: pipeline_properties.max_ray_recursion_depth,
+                scratch_alignment: acceleration_structure_properties
+                    .min_acceleration_structure_scratch_offset_alignment as u64,
+            },
+        }
+    }
+}
+
+/// Buffers of a top-level acceleration structure, reused by every build
+pub(crate) struct TlasBuffers {
+    /// Host-visible `VkAccelerationStructureInstanceKHR` array
+    pub(crate) instances: Buffer,
+    /// GPU address of `instances`
+    pub(crate) instances_address: u64,
+    /// Build scratch memory
+    _scratch: Buffer,
+    /// GPU address of the scratch memory (aligned)
+    pub(crate) scratch_address: u64,
+    /// Capacity of `instances`
+    pub(crate) max_instances: u32,
+}
+
+/// Vulkan acceleration structure implementation
+pub struct AccelerationStructure {
+    /// Shared GPU context (deferred destruction)
+    ctx: Arc<GpuContext>,
+    /// Functions destroying the handle
+    loader: ash::khr::acceleration_structure::Device,
+    /// Vulkan acceleration structure
+    pub(crate) handle: vk::AccelerationStructureKHR,
+    /// Bottom or top level
+    kind: AccelerationStructureKind,
+    /// Size of the storage
+    size: u64,
+    /// GPU address
+    address: u64,
+    /// Storage of the structure (destroyed after the handle)
+    _storage: Buffer,
+    /// Instance and scratch buffers (top level only)
+    pub(crate) tlas: Option<TlasBuffers>,
+}
+
+impl RendererAccelerationStructure for AccelerationStructure {
+    fn kind(&self) -> AccelerationStructureKind {
+        self.kind
+    }
+
+    fn size(&self) -> u64 {
+        self.size
+    }
+
+    fn device_address(&self) -> u64 {
+        self.address
+    }
+}
+
+impl Drop for AccelerationStructure {
+    fn drop(&mut self) {
+        // Deferred while a submitted command buffer may still trace rays
+        // against it. Queued before the storage buffer (dropped next).
+        self.ctx.release(PendingDestroy::AccelerationStructure {
+            handle: self.handle,
+            loader: self.loader.clone(),
+        });
+    }
+}
+
+/// Shader binding table of a ray tracing pipeline
+pub(crate) struct ShaderBindingTable {
+    /// Table storage (kept alive with the pipeline)
+    _buffer: Buffer,
+    /// Ray generation, miss, hit and callable regions (`vkCmdTraceRaysKHR`)
+    pub(crate) regions: [vk::StridedDeviceAddressRegionKHR; 4],
+}
+
+impl ShaderBindingTable {
+    /// Upload the shader group handles of a pipeline into a new table
+    pub(crate) unsafe fn new(
+        ctx: &Arc<GpuContext>,
+        layout: &ShaderBindingTableLayout,
+        handles: &[u8],
+    ) -> Result<Self> {
+        let table = layout.write_table(handles)?;
+        let buffer = create_addressable_buffer(
+            ctx,
+            table.len() as u64,
+            vk::BufferUsageFlags::SHADER_BINDING_TABLE_KHR,
+            gpu_allocator::MemoryLocation::CpuToGpu,
+            "shader_binding_table",
+        )?;
+        buffer.update(0, &table)?;
+        let address = buffer_address(&ctx.device, buffer.buffer);
+        let region = |region: &ShaderBindingTableRegion| {
+            if region.size == 0 {
+                vk::StridedDeviceAddressRegionKHR::default()
+            } else {
+                vk::StridedDeviceAddressRegionKHR {
+                    device_address: address + region.offset,
+                    stride: region.stride,
+                    size: region.size,
+                }
+            }
+        };
+        let regions = [
+            region(&layout.raygen),
+            region(&layout.miss),
+            region(&layout.hit),
+            vk::StridedDeviceAddressRegionKHR::default(),
+        ];
+        Ok(Self { _buffer: buffer, regions })
+    }
+}
+
+/// GPU address of a buffer created with `SHADER_DEVICE_ADDRESS`
+pub(crate) unsafe fn buffer_address(device: &ash::Device, buffer: vk::Buffer) -> u64 {
+    device.get_buffer_device_address(&vk::BufferDeviceAddressInfo::default().buffer(buffer))
+}
+
+/// Create a buffer addressed by the GPU (acceleration structure storage,
+/// scratch, instances, shader binding table)
+unsafe fn create_addressable_buffer(
+    ctx: &Arc<GpuContext>,
+    size: u64,
+    usage: vk::BufferUsageFlags,
+    location: gpu_allocator::MemoryLocation,
+    name: &'static str,
+) -> Result<Buffer> {
+    let device = &ctx.device;
+    let buffer_create_info = ctx.resource_sharing.buffer_info(vk::BufferCreateInfo::default()
+        .size(size)
+        .usage(usage | vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS));
+    let buffer = device.create_buffer(&buffer_create_info, None)
+        .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to create {} buffer: {:?}", name, e))?;
+
+    let requirements = device.get_buffer_memory_requirements(buffer);
+    let allocation = ctx.allocator.lock().unwrap().allocate(&gpu_allocator::vulkan::AllocationCreateDesc {
+        name,
+        requirements,
+        location,
+        linear: true,
+        allocation_scheme: gpu_allocator::vulkan::AllocationScheme::GpuAllocatorManaged,
+    })
+    .map_err(|_e| {
+        engine_error!("galaxy3d::vulkan", "Out of GPU memory for {} buffer ({} bytes)", name, size);
+        Error::OutOfMemory
+    })?;
+
+    device.bind_buffer_memory(buffer, allocation.memory(), allocation.offset())
+        .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to bind {} buffer memory: {:?}", name, e))?;
+
+    Ok(Buffer::new(Arc::clone(ctx), buffer, allocation, size))
+}
+
+/// Scratch buffer of a build and its aligned address
+unsafe fn create_scratch_buffer(
+    ctx: &Arc<GpuContext>,
+    rt: &RayTracingFunctions,
+    size: u64,
+) -> Result<(Buffer, u64)> {
+    let alignment = rt.properties.scratch_alignment.max(1);
+    let buffer = create_addressable_buffer(
+        ctx,
+        size + alignment,
+        vk::BufferUsageFlags::STORAGE_BUFFER,
+        gpu_allocator::MemoryLocation::GpuOnly,
+        "acceleration_structure_scratch",
+    )?;
+    let address = buffer_address(&ctx.device, buffer.buffer).next_multiple_of(alignment);
+    Ok((buffer, address))
+}
+
+/// Create an (unbuilt) acceleration structure and its storage
+unsafe fn create_acceleration_structure(
+    ctx: &Arc<GpuContext>,
+    rt: &RayTracingFunctions,
+    kind: AccelerationStructureKind,
+    size: u64,
+    debug_name: Option<&str>,
+) -> Result<AccelerationStructure> {
+    let storage = create_addressable_buffer(
+        ctx,
+        size,
+        vk::BufferUsageFlags::ACCELERATION_STRUCTURE_STORAGE_KHR,
+        gpu_allocator::MemoryLocation::GpuOnly,
+        "acceleration_structure",
+    )?;
+    let create_info = vk::AccelerationStructureCreateInfoKHR::default()
+        .buffer(storage.buffer)
+        .size(size)
+        .ty(kind_to_vk(kind));
+    let handle = rt.acceleration_structure.create_acceleration_structure(&create_info, None)
+        .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to create acceleration structure: {:?}", e))?;
+    let address = rt.acceleration_structure.get_acceleration_structure_device_address(
+        &vk::AccelerationStructureDeviceAddressInfoKHR::default().acceleration_structure(handle));
+    if let Some(name) = debug_name {
+        ctx.debug_names.set_object_name(handle, name);
+    }
+
+    Ok(AccelerationStructure {
+        ctx: Arc::clone(ctx),
+        loader: rt.acceleration_structure.clone(),
+        handle,
+        kind,
+        size,
+        address,
+        _storage: storage,
+        tlas: None,
+    })
+}
+
+fn kind_to_vk(kind: AccelerationStructureKind) -> vk::AccelerationStructureTypeKHR {
+    match kind {
+        AccelerationStructureKind::BottomLevel => vk::AccelerationStructureTypeKHR::BOTTOM_LEVEL,
+        AccelerationStructureKind::TopLevel => vk::AccelerationStructureTypeKHR::TOP_LEVEL,
+    }
+}
+
+/// Vertex position formats accepted by acceleration structure builds
+fn position_format_to_vk(format: BufferFormat) -> Option<vk::Format> {
+    match format {
+        BufferFormat::R32G32B32_SFLOAT => Some(vk::Format::R32G32B32_SFLOAT),
+        BufferFormat::R32G32_SFLOAT => Some(vk::Format::R32G32_SFLOAT),
+        BufferFormat::R16G16B16A16_SFLOAT => Some(vk::Format::R16G16B16A16_SFLOAT),
+        BufferFormat::R16G16_SFLOAT => Some(vk::Format::R16G16_SFLOAT),
+        _ => None,
+    }
+}
+
+/// Geometry of a TLAS build, reading the instances at `instances_address`
+pub(crate) fn tlas_geometry(instances_address: u64) -> vk::AccelerationStructureGeometryKHR<'static> {
+    let instances = vk::AccelerationStructureGeometryInstancesDataKHR::default()
+        .array_of_pointers(false)
+        .data(vk::DeviceOrHostAddressConstKHR { device_address: instances_address });
+    vk::AccelerationStructureGeometryKHR::default()
+        .geometry_type(vk::GeometryTypeKHR::INSTANCES)
+        .geometry(vk::AccelerationStructureGeometryDataKHR { instances })
+}
+
+/// Create a BLAS and build it with a one-shot submit (waited for)
+pub(crate) unsafe fn create_blas(
+    ctx: &Arc<GpuContext>,
+    rt: &RayTracingFunctions,
+    desc: &BlasDesc,
+) -> Result<AccelerationStructure> {
+    if desc.geometries.is_empty() {
+        engine_bail!("galaxy3d::vulkan", "create_blas: no geometry");
+    }
+    let device = &ctx.device;
+
+    let mut geometries = Vec::with_capacity(desc.geometries.len());
+    let mut ranges = Vec::with_capacity(desc.geometries.len());
+    for geometry in &desc.geometries {
+        let Some(vertex_format) = position_format_to_vk(geometry.vertex_format) else {
+            engine_bail!("galaxy3d::vulkan",
+                "create_blas: unsupported vertex position format {:?}", geometry.vertex_format);
+        };
+        if geometry.vertex_count == 0 || geometry.triangle_count == 0 {
+            engine_bail!("galaxy3d::vulkan", "create_blas: empty geometry");
+        }
+        let vertex_buffer = &*geometry.vertex_buffer as *const dyn RendererBuffer as *const Buffer;
+        let vertex_address = buffer_address(device, (*vertex_buffer).buffer) + geometry.position_offset as u64;
+
+        let mut triangles = vk::AccelerationStructureGeometryTrianglesDataKHR::default()
+            .vertex_format(vertex_format)
+            .vertex_data(vk::DeviceOrHostAddressConstKHR { device_address: vertex_address })
+            .vertex_stride(geometry.vertex_stride as u64)
+            .max_vertex(geometry.first_vertex + geometry.vertex_count - 1)
+            .index_type(vk::IndexType::NONE_KHR);
+        let mut primitive_offset = 0;
+        if let Some(index_buffer) = &geometry.index_buffer {
+            let index_buffer = &**index_buffer as *const dyn RendererBuffer as *const Buffer;
+            let index_type = match geometry.index_type {
+                IndexType::U16 => vk::IndexType::UINT16,
+                IndexType::U32 => vk::IndexType::UINT32,
+            };
+            triangles = triangles
+                .index_type(index_type)
+                .index_data(vk::DeviceOrHostAddressConstKHR {
+                    device_address: buffer_address(device, (*index_buffer).buffer),
+                });
+            primitive_offset = geometry.first_index * geometry.index_type.size_bytes();
+        }
+
+        let flags = if geometry.opaque {
+            vk::GeometryFlagsKHR::OPAQUE
+        } else {
+            vk::GeometryFlagsKHR::empty()
+        };
+        geometries.push(vk::AccelerationStructureGeometryKHR::default()
+            .geometry_type(vk::GeometryTypeKHR::TRIANGLES)
+            .geometry(vk::AccelerationStructureGeometryDataKHR { triangles })
+            .flags(flags));
+        ranges.push(vk::AccelerationStructureBuildRangeInfoKHR {
+            primitive_count: geometry.triangle_count,
+            primitive_offset,
+            first_vertex: geometry.first_vertex,
+            transform_offset: 0,
+        });
+    }
+
+    let mut build_info = vk::AccelerationStructureBuildGeometryInfoKHR::default()
+        .ty(vk::AccelerationStructureTypeKHR::BOTTOM_LEVEL)
+        .flags(vk::BuildAccelerationStructureFlagsKHR::PREFER_FAST_TRACE)
+        .mode(vk::BuildAccelerationStructureModeKHR::BUILD)
+        .geometries(&geometries);
+    let primitive_counts: Vec<u32> = ranges.iter().map(|range| range.primitive_count).collect();
+    let mut sizes = vk::AccelerationStructureBuildSizesInfoKHR::default();
+    rt.acceleration_structure.get_acceleration_structure_build_sizes(
+        vk::AccelerationStructureBuildTypeKHR::DEVICE, &build_info, &primitive_counts, &mut sizes);
+
+    let blas = create_acceleration_structure(
+        ctx, rt, AccelerationStructureKind::BottomLevel,
+        sizes.acceleration_structure_size, desc.debug_name.as_deref())?;
+    let (_scratch, scratch_address) = create_scratch_buffer(ctx, rt, sizes.build_scratch_size)?;
+    build_info = build_info
+        .dst_acceleration_structure(blas.handle)
+        .scratch_data(vk::DeviceOrHostAddressKHR { device_address: scratch_address });
+
+    // One-shot build, as texture uploads
+    let command_pool = *ctx.upload_command_pool.lock().unwrap();
+    let command_buffer_allocate_info = vk::CommandBufferAllocateInfo::default()
+        .command_pool(command_pool)
+        .level(vk::CommandBufferLevel::PRIMARY)
+        .command_buffer_count(1);
+    let command_buffer = device.allocate_command_buffers(&command_buffer_allocate_info)
+        .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "create_blas: failed to allocate command buffer: {:?}", e))?[0];
+
+    let begin_info = vk::CommandBufferBeginInfo::default()
+        .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+    device.begin_command_buffer(command_buffer, &begin_info)
+        .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "create_blas: failed to begin command buffer: {:?}", e))?;
+    rt.acceleration_structure.cmd_build_acceleration_structures(command_buffer, &[build_info], &[&ranges]);
+    device.end_command_buffer(command_buffer)
+        .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "create_blas: failed to end command buffer: {:?}", e))?;
+
+    crate::vulkan_sync::submit_command_buffers(
+        device,
+        ctx.graphics_queue,
+        &[command_buffer],
+        &[],
+        &[],
+        vk::Fence::null(),
+    )?;
+    device.queue_wait_idle(ctx.graphics_queue)
+        .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "create_blas: failed to wait for completion: {:?}", e))?;
+    device.free_command_buffers(command_pool, &[command_buffer]);
+
+    Ok(blas)
+}
+
+/// Create a TLAS sized for `desc.max_instances`, with its instance and
+/// scratch buffers (built by `CommandList::build_tlas`)
+pub(crate) unsafe fn create_tlas(
+    ctx: &Arc<GpuContext>,
+    rt: &RayTracingFunctions,
+    desc: &TlasDesc,
+) -> Result<AccelerationStructure> {
+    let max_instances = desc.max_instances.max(1);
+    let geometries = [tlas_geometry(0)];
+    let build_info = vk::AccelerationStructureBuildGeometryInfoKHR::default()
+        .ty(vk::AccelerationStructureTypeKHR::TOP_LEVEL)
+        .flags(vk::BuildAccelerationStructureFlagsKHR::PREFER_FAST_TRACE)
+        .mode(vk::BuildAccelerationStructureModeKHR::BUILD)
+        .geometries(&geometries);
+    let mut sizes = vk::AccelerationStructureBuildSizesInfoKHR::default();
+    rt.acceleration_structure.get_acceleration_structure_build_sizes(
+        vk::AccelerationStructureBuildTypeKHR::DEVICE, &build_info, &[max_instances], &mut sizes);
+
+    let mut tlas = create_acceleration_structure(
+        ctx, rt, AccelerationStructureKind::TopLevel,
+        sizes.acceleration_structure_size, desc.debug_name.as_deref())?;
+    let instances = create_addressable_buffer(
+        ctx,
+        max_instances as u64 * TLAS_INSTANCE_SIZE as u64,
+        vk::BufferUsageFlags::ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY_KHR,
+        gpu_allocator::MemoryLocation::CpuToGpu,
+        "tlas_instances",
+    )?;
+    let instances_address = buffer_address(&ctx.device, instances.buffer);
+    let (scratch, scratch_address) = create_scratch_buffer(ctx, rt, sizes.build_scratch_size)?;
+    tlas.tlas = Some(TlasBuffers {
+        instances,
+        instances_address,
+        _scratch: scratch,
+        scratch_address,
+        max_instances,
+    });
+    Ok(tlas)
+}
@@ -1,15 +1,26 @@
 /// RenderPass - Vulkan implementation of RendererRenderPass trait
 ///
-/// With `VK_KHR_dynamic_rendering`, there is no `VkRenderPass` object. The
-/// `RenderPass` becomes a pure Rust descriptor that records attachment
-/// formats and load/store ops. The command list reads this descriptor at
-/// `begin_render_pass` time and builds a `VkRenderingInfo` inline.
+/// The backend has two rendering paths, chosen once per device:
+///
+/// - dynamic rendering (Vulkan 1.3 devices): there is no `VkRenderPass`
+///   object. The `RenderPass` is a pure Rust descriptor that records
+///   attachment formats and load/store ops; the command list reads it at
+///   `begin_render_pass` time and builds a `VkRenderingInfo` inline.
+/// - legacy (older devices): the same descriptor also owns a single-subpass
+///   `VkRenderPass`, begun with `vkCmdBeginRenderPass`.
+///
+/// In both paths, layout transitions are emitted by the command list
+/// barriers: legacy attachments keep the same layout from start to end
+/// (`initialLayout == finalLayout`).
 
+use galaxy_3d_engine::galaxy3d::Result;
 use galaxy_3d_engine::galaxy3d::render::{
     AttachmentDesc, RenderPass as RendererRenderPass,
 };
+use galaxy_3d_engine::engine_backend_err;
+use ash::vk;
 
-/// Vulkan render pass descriptor (data-only, no GPU object).
+/// Vulkan render pass descriptor, with its `VkRenderPass` on the legacy path.
 pub struct RenderPass {
     /// Color attachment descriptors (format, samples, load/store ops).
     pub(crate) color_attachments: Vec<AttachmentDesc>,
@@ -18,8 +29,104 @@ pub struct RenderPass {
     /// Resolve attachment descriptors (same length as `color_attachments`
     /// when MSAA resolve is active, empty otherwise).
     pub(crate) color_resolve_attachments: Vec<AttachmentDesc>,
+    /// Legacy `VkRenderPass` (null with dynamic rendering).
+    pub(crate) legacy_render_pass: vk::RenderPass,
+    /// Device used to destroy the legacy render pass on Drop.
+    device: ash::Device,
+}
+
+impl RenderPass {
+    pub(crate) fn new(
+        color_attachments: Vec<AttachmentDesc>,
+        depth_stencil_attachment: Option<AttachmentDesc>,
+        color_resolve_attachments: Vec<AttachmentDesc>,
+        legacy_render_pass: vk::RenderPass,
+        device: ash::Device,
+    ) -> Self {
+        Self {
+            color_attachments,
+            depth_stencil_attachment,
+            color_resolve_attachments,
+            legacy_render_pass,
+            device,
+        }
+    }
 }
 
 impl RendererRenderPass for RenderPass {
     // No methods needed for now - just a type-safe wrapper
 }
+
+impl Drop for RenderPass {
+    fn drop(&mut self) {
+        if self.legacy_render_pass != vk::RenderPass::null() {
+            unsafe {
+                self.device.destroy_render_pass(self.legacy_render_pass, None);
+            }
+        }
+    }
+}
+
+/// Create a single-subpass `VkRenderPass` for the legacy path.
+///
+/// Attachments are numbered colors first, then depth/stencil, then resolve
+/// targets: framebuffers and clear values follow the same order. Layouts
+/// are left to the command list barriers (attachment layout from start to
+/// end), so the only dependencies are the implicit external ones.
+///
+/// Pipelines use it too, as a temporary compatible render pass (formats
+/// and sample counts matter, load/store ops do not).
+pub(crate) unsafe fn create_legacy_render_pass(
+    device: &ash::Device,
+    color_attachments: &[vk::AttachmentDescription],
+    depth_stencil_attachment: Option<vk::AttachmentDescription>,
+    color_resolve_attachments: &[vk::AttachmentDescription],
+) -> Result<vk::RenderPass> {
+    let color_layout = vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL;
+    let depth_layout = vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL;
+
+    let mut attachments = Vec::with_capacity(
+        color_attachments.len() * 2 + usize::from(depth_stencil_attachment.is_some()));
+    let mut color_refs = Vec::with_capacity(color_attachments.len());
+    for att in color_attachments {
+        color_refs.push(vk::AttachmentReference {
+            attachment: attachments.len() as u32,
+            layout: color_layout,
+        });
+        attachments.push(att.initial_layout(color_layout).final_layout(color_layout));
+    }
+    let depth_ref = depth_stencil_attachment.map(|att| {
+        let reference = vk::AttachmentReference {
+            attachment: attachments.len() as u32,
+            layout: depth_layout,
+        };
+        attachments.push(att.initial_layout(depth_layout).final_layout(depth_layout));
+        reference
+    });
+    let mut resolve_refs = Vec::with_capacity(color_resolve_attachments.len());
+    for att in color_resolve_attachments {
+        resolve_refs.push(vk::AttachmentReference {
+            attachment: attachments.len() as u32,
+            layout: color_layout,
+        });
+        attachments.push(att.initial_layout(color_layout).final_layout(color_layout));
+    }
+
+    let mut subpass = vk::SubpassDescription::default()
+        .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
+        .color_attachments(&color_refs);
+    if !resolve_refs.is_empty() {
+        subpass = subpass.resolve_attachments(&resolve_refs);
+    }
+    if let Some(ref depth_ref) = depth_ref {
+        subpass = subpass.depth_stencil_attachment(depth_ref);
+    }
+    let subpasses = [subpass];
+
+    let create_info = vk::RenderPassCreateInfo::default()
+        .attachments(&attachments)
+        .subpasses(&subpasses);
+
+    device.create_render_pass(&create_info, None)
+        .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to create render pass: {:?}", e))
+}
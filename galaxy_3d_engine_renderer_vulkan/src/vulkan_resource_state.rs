@@ -0,0 +1,142 @@
+/// ResourceStateTracker - last access of each texture and buffer in a
+/// command list recording
+///
+/// Callers declare each access with the access they believe came before
+/// (`ImageAccess::previous_access_type`, precalculated by the render graph).
+/// Once the command list has accessed a resource, the tracked access takes
+/// over: layouts stay right even when a declaration is stale or missing.
+///
+/// Barriers are minimal: none between two reads in the same layout (e.g.
+/// fragment sampling then compute sampling), a layout transition or a
+/// memory dependency otherwise. Color attachment reads count as writes,
+/// as blending writes the attachment too.
+///
+/// In validation mode, declared barriers that turn out useless and
+/// declarations that disagree with the tracked state are reported.
+
+use std::fmt::Debug;
+use std::hash::Hash;
+use ash::vk;
+use rustc_hash::FxHashMap;
+use galaxy_3d_engine::galaxy3d::render::AccessType;
+use galaxy_3d_engine::engine_warn;
+
+/// Barrier needed before an access
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Transition {
+    /// Nothing to wait on, no layout change
+    None,
+    /// First use of an image: transition from UNDEFINED
+    Initial,
+    /// Wait on the previous access (and change layout if needed)
+    From(AccessType),
+}
+
+/// Tracked state of the textures and buffers of a command list (see
+/// module docs). Reset at the start of each recording.
+pub(crate) struct ResourceStateTracker {
+    images: FxHashMap<vk::Image, AccessType>,
+    buffers: FxHashMap<vk::Buffer, AccessType>,
+    /// Report redundant barriers and stale declarations
+    validation: bool,
+}
+
+impl ResourceStateTracker {
+    pub(crate) fn new(validation: bool) -> Self {
+        Self {
+            images: FxHashMap::default(),
+            buffers: FxHashMap::default(),
+            validation,
+        }
+    }
+
+    /// Forget every state (new recording)
+    pub(crate) fn reset(&mut self) {
+        self.images.clear();
+        self.buffers.clear();
+    }
+
+    /// Record an image access and return the barrier it needs.
+    pub(crate) fn image_transition(
+        &mut self,
+        image: vk::Image,
+        access: AccessType,
+        declared_previous: Option<AccessType>,
+    ) -> Transition {
+        let previous = self.previous(&self.images, image, declared_previous);
+        self.images.insert(image, access);
+        match previous {
+            None => Transition::Initial,
+            Some(previous) => {
+                let same_layout = crate::vulkan_sync::access_type_to_layout(previous)
+                    == crate::vulkan_sync::access_type_to_layout(access);
+                self.dependency(image, previous, access, same_layout, declared_previous)
+            }
+        }
+    }
+
+    /// Record a buffer access and return the barrier it needs (none on
+    /// first use: buffers have no layout).
+    pub(crate) fn buffer_transition(
+        &mut self,
+        buffer: vk::Buffer,
+        access: AccessType,
+        declared_previous: Option<AccessType>,
+    ) -> Transition {
+        let previous = self.previous(&self.buffers, buffer, declared_previous);
+        self.buffers.insert(buffer, access);
+        match previous {
+            None => Transition::None,
+            Some(previous) => self.dependency(buffer, previous, access, true, declared_previous),
+        }
+    }
+
+    /// Record a buffer access synchronized by the caller (e.g. a copy
+    /// destination followed by its own host barrier)
+    pub(crate) fn record_buffer(&mut self, buffer: vk::Buffer, access: AccessType) {
+        self.buffers.insert(buffer, access);
+    }
+
+    /// Tracked access, else the declared one
+    fn previous<K: Hash + Eq + Debug>(
+        &self,
+        states: &FxHashMap<K, AccessType>,
+        key: K,
+        declared_previous: Option<AccessType>,
+    ) -> Option<AccessType> {
+        let tracked = states.get(&key).copied();
+        if self.validation && tracked.is_some() && tracked != declared_previous {
+            engine_warn!("galaxy3d::vulkan",
+                "{:?}: declared previous access {:?}, tracked {:?}", key, declared_previous, tracked);
+        }
+        tracked.or(declared_previous)
+    }
+
+    /// Barrier between two accesses of a resource
+    fn dependency<K: Debug>(
+        &self,
+        key: K,
+        previous: AccessType,
+        access: AccessType,
+        same_layout: bool,
+        declared_previous: Option<AccessType>,
+    ) -> Transition {
+        if !same_layout || may_write(previous) || may_write(access) {
+            return Transition::From(previous);
+        }
+        if self.validation && declared_previous.is_some() {
+            engine_warn!("galaxy3d::vulkan",
+                "{:?}: redundant barrier {:?} -> {:?} skipped", key, previous, access);
+        }
+        Transition::None
+    }
+}
+
+/// Accesses that may write the resource (blending writes color attachments)
+fn may_write(access: AccessType) -> bool {
+    access.is_write() || access == AccessType::ColorAttachmentRead
+}
+
+#[cfg(test)]
+#[path = "vulkan_resource_state_tests.rs"]
+mod tests;
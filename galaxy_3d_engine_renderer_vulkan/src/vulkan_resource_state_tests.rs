@@ -0,0 +1,122 @@
+use super::*;
+use ash::vk::Handle;
+
+fn image(raw: u64) -> vk::Image {
+    vk::Image::from_raw(raw)
+}
+
+fn buffer(raw: u64) -> vk::Buffer {
+    vk::Buffer::from_raw(raw)
+}
+
+// ============================================================================
+// Images
+// ============================================================================
+
+#[test]
+fn test_image_first_use_starts_from_undefined() {
+    let mut tracker = ResourceStateTracker::new(false);
+    assert_eq!(tracker.image_transition(image(1), AccessType::ColorAttachmentWrite, None), Transition::Initial);
+}
+
+#[test]
+fn test_image_first_use_follows_declared_previous() {
+    let mut tracker = ResourceStateTracker::new(false);
+    let transition = tracker.image_transition(
+        image(1), AccessType::FragmentShaderRead, Some(AccessType::ColorAttachmentWrite));
+    assert_eq!(transition, Transition::From(AccessType::ColorAttachmentWrite));
+}
+
+#[test]
+fn test_image_tracked_state_overrides_declaration() {
+    let mut tracker = ResourceStateTracker::new(false);
+    tracker.image_transition(image(1), AccessType::ColorAttachmentWrite, None);
+    // Stale declaration: the texture was written in this recording
+    let transition = tracker.image_transition(image(1), AccessType::FragmentShaderRead, None);
+    assert_eq!(transition, Transition::From(AccessType::ColorAttachmentWrite));
+}
+
+#[test]
+fn test_image_read_after_read_in_same_layout_is_skipped() {
+    let mut tracker = ResourceStateTracker::new(false);
+    tracker.image_transition(image(1), AccessType::FragmentShaderRead, Some(AccessType::ColorAttachmentWrite));
+    let transition = tracker.image_transition(
+        image(1), AccessType::ComputeRead, Some(AccessType::FragmentShaderRead));
+    assert_eq!(transition, Transition::None);
+}
+
+#[test]
+fn test_image_read_after_read_with_layout_change_is_kept() {
+    let mut tracker = ResourceStateTracker::new(false);
+    tracker.image_transition(image(1), AccessType::DepthStencilReadOnly, Some(AccessType::DepthStencilWrite));
+    let transition = tracker.image_transition(image(1), AccessType::FragmentShaderRead, None);
+    assert_eq!(transition, Transition::From(AccessType::DepthStencilReadOnly));
+}
+
+#[test]
+fn test_image_write_after_write_is_kept() {
+    let mut tracker = ResourceStateTracker::new(false);
+    tracker.image_transition(image(1), AccessType::ColorAttachmentWrite, None);
+    let transition = tracker.image_transition(image(1), AccessType::ColorAttachmentWrite, None);
+    assert_eq!(transition, Transition::From(AccessType::ColorAttachmentWrite));
+}
+
+#[test]
+fn test_image_color_attachment_read_counts_as_write() {
+    let mut tracker = ResourceStateTracker::new(false);
+    tracker.image_transition(image(1), AccessType::ColorAttachmentRead, None);
+    let transition = tracker.image_transition(image(1), AccessType::ColorAttachmentRead, None);
+    assert_eq!(transition, Transition::From(AccessType::ColorAttachmentRead));
+}
+
+#[test]
+fn test_images_are_tracked_separately() {
+    let mut tracker = ResourceStateTracker::new(false);
+    tracker.image_transition(image(1), AccessType::ColorAttachmentWrite, None);
+    assert_eq!(tracker.image_transition(image(2), AccessType::FragmentShaderRead, None), Transition::Initial);
+}
+
+#[test]
+fn test_reset_forgets_states() {
+    let mut tracker = ResourceStateTracker::new(false);
+    tracker.image_transition(image(1), AccessType::ColorAttachmentWrite, None);
+    tracker.buffer_transition(buffer(1), AccessType::ComputeWrite, None);
+    tracker.reset();
+    assert_eq!(tracker.image_transition(image(1), AccessType::FragmentShaderRead, None), Transition::Initial);
+    assert_eq!(tracker.buffer_transition(buffer(1), AccessType::ComputeRead, None), Transition::None);
+}
+
+// ============================================================================
+// Buffers
+// ============================================================================
+
+#[test]
+fn test_buffer_first_use_needs_no_barrier() {
+    let mut tracker = ResourceStateTracker::new(false);
+    assert_eq!(tracker.buffer_transition(buffer(1), AccessType::ComputeRead, None), Transition::None);
+}
+
+#[test]
+fn test_buffer_read_after_write_is_kept() {
+    let mut tracker = ResourceStateTracker::new(false);
+    tracker.buffer_transition(buffer(1), AccessType::ComputeWrite, None);
+    let transition = tracker.buffer_transition(buffer(1), AccessType::VertexShaderRead, None);
+    assert_eq!(transition, Transition::From(AccessType::ComputeWrite));
+}
+
+#[test]
+fn test_buffer_read_after_read_is_skipped() {
+    // Validation mode only adds warnings
+    let mut tracker = ResourceStateTracker::new(true);
+    let transition = tracker.buffer_transition(
+        buffer(1), AccessType::FragmentShaderRead, Some(AccessType::ComputeRead));
+    assert_eq!(transition, Transition::None);
+}
+
+#[test]
+fn test_recorded_copy_destination_is_waited_on() {
+    let mut tracker = ResourceStateTracker::new(false);
+    tracker.record_buffer(buffer(1), AccessType::TransferWrite);
+    let transition = tracker.buffer_transition(buffer(1), AccessType::ComputeRead, None);
+    assert_eq!(transition, Transition::From(AccessType::TransferWrite));
+}
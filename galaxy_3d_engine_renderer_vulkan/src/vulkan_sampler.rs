@@ -1,38 +1,82 @@
 /// SamplerCache — internal VkSampler management for the Vulkan backend
 ///
-/// Creates and caches VkSampler objects on first use. Typical engines only
-/// need 5-6 samplers total, so this is extremely lightweight.
+/// Creates and caches VkSampler objects on first use, one per distinct
+/// `SamplerDesc` (the `SamplerType` presets included). Typical engines only
+/// need a handful of samplers, so this is extremely lightweight.
 
-use galaxy_3d_engine::galaxy3d::render::SamplerType;
+use galaxy_3d_engine::galaxy3d::Result;
+use galaxy_3d_engine::galaxy3d::render::{
+    AddressMode, BorderColor, CompareOp, Filter, PlatformLimits, SamplerDesc, SamplerMipmapMode,
+    SamplerType,
+};
+use galaxy_3d_engine::engine_backend_err;
 use crate::vulkan_context::GpuContext;
+use crate::vulkan_command_list::compare_op_to_vk;
 use ash::vk;
 use rustc_hash::FxHashMap;
 use std::sync::Arc;
 
+/// Hashable form of a `SamplerDesc` (floats compared by bits)
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct SamplerKey {
+    filters: (Filter, Filter, SamplerMipmapMode),
+    address_modes: [AddressMode; 3],
+    max_anisotropy: Option<u32>,
+    compare: Option<CompareOp>,
+    border_color: BorderColor,
+    lods: [u32; 2],
+    max_lod: Option<u32>,
+}
+
+impl SamplerKey {
+    fn new(desc: &SamplerDesc) -> Self {
+        Self {
+            filters: (desc.mag_filter, desc.min_filter, desc.mipmap_mode),
+            address_modes: [desc.address_mode_u, desc.address_mode_v, desc.address_mode_w],
+            max_anisotropy: desc.max_anisotropy.map(f32::to_bits),
+            compare: desc.compare,
+            border_color: desc.border_color,
+            lods: [desc.mip_lod_bias.to_bits(), desc.min_lod.to_bits()],
+            max_lod: desc.max_lod.map(f32::to_bits),
+        }
+    }
+}
+
 /// Internal sampler cache — creates VkSampler on first use, destroys on shutdown/drop
 pub(crate) struct SamplerCache {
     ctx: Option<Arc<GpuContext>>,
-    cache: FxHashMap<SamplerType, vk::Sampler>,
+    cache: FxHashMap<SamplerKey, vk::Sampler>,
+    /// Platform profile clamps (anisotropy ceiling)
+    limits: PlatformLimits,
 }
 
 impl SamplerCache {
-    pub(crate) fn new(ctx: Arc<GpuContext>) -> Self {
+    pub(crate) fn new(ctx: Arc<GpuContext>, limits: &PlatformLimits) -> Self {
         Self {
             ctx: Some(ctx),
             cache: FxHashMap::default(),
+            limits: *limits,
         }
     }
 
-    /// Get or create a VkSampler for the given type
+    /// Get or create a VkSampler for the given preset
     pub(crate) fn get(&mut self, sampler_type: SamplerType) -> vk::Sampler {
-        if let Some(&sampler) = self.cache.get(&sampler_type) {
-            return sampler;
+        self.get_desc(&SamplerDesc::from(sampler_type))
+            .expect("Failed to create VkSampler")
+    }
+
+    /// Get or create a VkSampler for the given description
+    pub(crate) fn get_desc(&mut self, desc: &SamplerDesc) -> Result<vk::Sampler> {
+        let key = SamplerKey::new(desc);
+        if let Some(&sampler) = self.cache.get(&key) {
+            return Ok(sampler);
         }
 
+        desc.validate()?;
         let ctx = self.ctx.as_ref().expect("SamplerCache used after shutdown");
-        let sampler = Self::create_vk_sampler(ctx, sampler_type);
-        self.cache.insert(sampler_type, sampler);
-        sampler
+        let sampler = Self::create_vk_sampler(ctx, desc, &self.limits)?;
+        self.cache.insert(key, sampler);
+        Ok(sampler)
     }
 
     /// Destroy all cached VkSamplers and release the GpuContext reference.
@@ -46,88 +90,39 @@ impl SamplerCache {
         self.ctx = None;
     }
 
-    fn create_vk_sampler(ctx: &GpuContext, sampler_type: SamplerType) -> vk::Sampler {
-        let (mag, min, mipmap, address, anisotropy, border, compare) = match sampler_type {
-            SamplerType::LinearRepeat => (
-                vk::Filter::LINEAR,
-                vk::Filter::LINEAR,
-                vk::SamplerMipmapMode::LINEAR,
-                vk::SamplerAddressMode::REPEAT,
-                Some(16.0),
-                vk::BorderColor::FLOAT_OPAQUE_BLACK,
-                false,
-            ),
-            SamplerType::LinearClamp => (
-                vk::Filter::LINEAR,
-                vk::Filter::LINEAR,
-                vk::SamplerMipmapMode::LINEAR,
-                vk::SamplerAddressMode::CLAMP_TO_EDGE,
-                Some(16.0),
-                vk::BorderColor::FLOAT_OPAQUE_BLACK,
-                false,
-            ),
-            SamplerType::NearestRepeat => (
-                vk::Filter::NEAREST,
-                vk::Filter::NEAREST,
-                vk::SamplerMipmapMode::NEAREST,
-                vk::SamplerAddressMode::REPEAT,
-                None,
-                vk::BorderColor::FLOAT_OPAQUE_BLACK,
-                false,
-            ),
-            SamplerType::NearestClamp => (
-                vk::Filter::NEAREST,
-                vk::Filter::NEAREST,
-                vk::SamplerMipmapMode::NEAREST,
-                vk::SamplerAddressMode::CLAMP_TO_EDGE,
-                None,
-                vk::BorderColor::FLOAT_OPAQUE_BLACK,
-                false,
-            ),
-            SamplerType::Shadow => (
-                vk::Filter::LINEAR,
-                vk::Filter::LINEAR,
-                vk::SamplerMipmapMode::NEAREST,
-                vk::SamplerAddressMode::CLAMP_TO_BORDER,
-                None,
-                vk::BorderColor::FLOAT_OPAQUE_WHITE,
-                true,
-            ),
-            SamplerType::Anisotropic => (
-                vk::Filter::LINEAR,
-                vk::Filter::LINEAR,
-                vk::SamplerMipmapMode::LINEAR,
-                vk::SamplerAddressMode::REPEAT,
-                Some(16.0),
-                vk::BorderColor::FLOAT_OPAQUE_BLACK,
-                false,
-            ),
-        };
-
+    fn create_vk_sampler(ctx: &GpuContext, desc: &SamplerDesc, limits: &PlatformLimits) -> Result<vk::Sampler> {
         let mut create_info = vk::SamplerCreateInfo::default()
-            .mag_filter(mag)
-            .min_filter(min)
-            .mipmap_mode(mipmap)
-            .address_mode_u(address)
-            .address_mode_v(address)
-            .address_mode_w(address)
-            .mip_lod_bias(0.0)
-            .min_lod(0.0)
-            .max_lod(vk::LOD_CLAMP_NONE)
-            .border_color(border)
+            .mag_filter(filter_to_vk(desc.mag_filter))
+            .min_filter(filter_to_vk(desc.min_filter))
+            .mipmap_mode(match desc.mipmap_mode {
+                SamplerMipmapMode::Nearest => vk::SamplerMipmapMode::NEAREST,
+                SamplerMipmapMode::Linear => vk::SamplerMipmapMode::LINEAR,
+            })
+            .address_mode_u(address_mode_to_vk(desc.address_mode_u))
+            .address_mode_v(address_mode_to_vk(desc.address_mode_v))
+            .address_mode_w(address_mode_to_vk(desc.address_mode_w))
+            .mip_lod_bias(desc.mip_lod_bias)
+            .min_lod(desc.min_lod)
+            .max_lod(desc.max_lod.unwrap_or(vk::LOD_CLAMP_NONE))
+            .border_color(match desc.border_color {
+                BorderColor::TransparentBlack => vk::BorderColor::FLOAT_TRANSPARENT_BLACK,
+                BorderColor::OpaqueBlack => vk::BorderColor::FLOAT_OPAQUE_BLACK,
+                BorderColor::OpaqueWhite => vk::BorderColor::FLOAT_OPAQUE_WHITE,
+            })
             .unnormalized_coordinates(false);
 
-        if compare {
+        if let Some(compare) = desc.compare {
             create_info = create_info
                 .compare_enable(true)
-                .compare_op(vk::CompareOp::LESS_OR_EQUAL);
+                .compare_op(compare_op_to_vk(compare));
         } else {
             create_info = create_info
                 .compare_enable(false)
                 .compare_op(vk::CompareOp::ALWAYS);
         }
 
-        if let Some(max_aniso) = anisotropy {
+        // A ceiling of 1 disables anisotropic filtering
+        if let Some(max_aniso) = desc.max_anisotropy.map(|a| limits.clamp_anisotropy(a)).filter(|&a| a > 1.0) {
             create_info = create_info
                 .anisotropy_enable(true)
                 .max_anisotropy(max_aniso);
@@ -139,11 +134,27 @@ impl SamplerCache {
 
         unsafe {
             ctx.device.create_sampler(&create_info, None)
-                .expect("Failed to create VkSampler")
+                .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to create VkSampler: {:?}", e))
         }
     }
 }
 
+fn filter_to_vk(filter: Filter) -> vk::Filter {
+    match filter {
+        Filter::Nearest => vk::Filter::NEAREST,
+        Filter::Linear => vk::Filter::LINEAR,
+    }
+}
+
+fn address_mode_to_vk(mode: AddressMode) -> vk::SamplerAddressMode {
+    match mode {
+        AddressMode::Repeat => vk::SamplerAddressMode::REPEAT,
+        AddressMode::MirroredRepeat => vk::SamplerAddressMode::MIRRORED_REPEAT,
+        AddressMode::ClampToEdge => vk::SamplerAddressMode::CLAMP_TO_EDGE,
+        AddressMode::ClampToBorder => vk::SamplerAddressMode::CLAMP_TO_BORDER,
+    }
+}
+
 impl Drop for SamplerCache {
     fn drop(&mut self) {
         // If shutdown() was called, ctx is None and cache is empty — nothing to do.
@@ -4,6 +4,7 @@ use galaxy_3d_engine::galaxy3d::render::{
     Shader as RendererShader,
     ReflectedBinding,
     ReflectedPushConstant,
+    ReflectedVertexInput,
 };
 use ash::vk;
 
@@ -21,6 +22,8 @@ pub struct Shader {
     pub(crate) reflected_bindings: Vec<ReflectedBinding>,
     /// SPIR-V reflected push constants (parsed at shader creation, used at pipeline creation)
     pub(crate) reflected_push_constants: Vec<ReflectedPushConstant>,
+    /// SPIR-V reflected vertex inputs (vertex stage only, checked against geometry layouts)
+    pub(crate) reflected_vertex_inputs: Vec<ReflectedVertexInput>,
 }
 
 impl RendererShader for Shader {
@@ -30,6 +33,9 @@ impl RendererShader for Shader {
     fn reflected_push_constants(&self) -> &[ReflectedPushConstant] {
         &self.reflected_push_constants
     }
+    fn reflected_vertex_inputs(&self) -> &[ReflectedVertexInput] {
+        &self.reflected_vertex_inputs
+    }
 }
 
 impl Drop for Shader {
@@ -0,0 +1,326 @@
+/// StagingBelt - reusable staging memory for texture and buffer uploads
+///
+/// Uploads copy their data into large persistently mapped chunks instead of
+/// creating a staging buffer each. An upload records its copies into a
+/// command buffer of the upload command pool and is submitted with a fence,
+/// without waiting for the queue to idle: its chunks return to the belt once
+/// the fence has signaled. Fences are polled when the next upload begins and
+/// waited for by `GpuContext::collect_released`, before the resources
+/// dropped during an upload are destroyed.
+///
+/// Chunks are filled linearly. Data larger than a chunk gets a dedicated
+/// chunk, destroyed instead of recycled.
+
+use ash::vk;
+use gpu_allocator::vulkan::{Allocation, AllocationCreateDesc, AllocationScheme};
+use galaxy_3d_engine::galaxy3d::{Result, Error};
+use galaxy_3d_engine::{engine_error, engine_err, engine_backend_err, engine_warn};
+use std::collections::VecDeque;
+use std::sync::MutexGuard;
+
+use crate::vulkan_context::GpuContext;
+
+/// Size of a recycled staging chunk
+pub(crate) const STAGING_CHUNK_SIZE: u64 = 8 * 1024 * 1024;
+
+/// Idle chunks kept for reuse; chunks recycled beyond this are destroyed
+const MAX_FREE_CHUNKS: usize = 4;
+
+/// Offset at which `len` bytes aligned to `alignment` fit in a chunk of
+/// `capacity` bytes whose first free byte is `cursor`
+pub(crate) fn chunk_offset(cursor: u64, capacity: u64, len: u64, alignment: u64) -> Option<u64> {
+    let offset = cursor.next_multiple_of(alignment.max(1));
+    (offset.checked_add(len)? <= capacity).then_some(offset)
+}
+
+/// Size of the chunk created for `len` bytes
+pub(crate) fn chunk_size_for(len: u64, chunk_size: u64) -> u64 {
+    len.max(chunk_size)
+}
+
+/// Mapped staging buffer
+struct StagingChunk {
+    buffer: vk::Buffer,
+    allocation: Allocation,
+    size: u64,
+    /// First free byte
+    cursor: u64,
+}
+
+/// Submitted upload and the chunks it reads
+struct InFlightUpload {
+    fence: vk::Fence,
+    command_buffer: vk::CommandBuffer,
+    chunks: Vec<StagingChunk>,
+}
+
+/// Staging chunks and upload fences (see module docs)
+pub(crate) struct StagingBelt {
+    chunk_size: u64,
+    /// Idle chunks, cursor at 0
+    free_chunks: Vec<StagingChunk>,
+    /// Chunks written by the upload being recorded
+    recording: Vec<StagingChunk>,
+    /// Submitted uploads, oldest first
+    in_flight: VecDeque<InFlightUpload>,
+    /// Unsignaled fences ready for the next upload
+    free_fences: Vec<vk::Fence>,
+}
+
+impl StagingBelt {
+    pub(crate) fn new(chunk_size: u64) -> Self {
+        Self {
+            chunk_size,
+            free_chunks: Vec::new(),
+            recording: Vec::new(),
+            in_flight: VecDeque::new(),
+            free_fences: Vec::new(),
+        }
+    }
+
+    /// Return the chunks of completed uploads to the belt.
+    ///
+    /// With `wait`, blocks until every submitted upload has completed;
+    /// otherwise stops at the first one still running (uploads complete in
+    /// submission order).
+    pub(crate) unsafe fn recycle(&mut self, ctx: &GpuContext, wait: bool) -> Result<()> {
+        while let Some(upload) = self.in_flight.front() {
+            let fence = upload.fence;
+            if wait {
+                ctx.device.wait_for_fences(&[fence], true, u64::MAX)
+                    .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to wait for upload fence: {:?}", e))?;
+            } else {
+                let signaled = ctx.device.get_fence_status(fence)
+                    .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to query upload fence: {:?}", e))?;
+                if !signaled {
+                    break;
+                }
+            }
+            ctx.device.reset_fences(&[fence])
+                .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to reset upload fence: {:?}", e))?;
+
+            let upload = self.in_flight.pop_front().unwrap();
+            self.free_fences.push(upload.fence);
+            free_command_buffer(ctx, upload.command_buffer);
+            for chunk in upload.chunks {
+                self.return_chunk(ctx, chunk);
+            }
+        }
+        Ok(())
+    }
+
+    /// Copy `data` into the chunk being filled.
+    ///
+    /// Returns the staging buffer and the offset of the copy in it.
+    unsafe fn stage(&mut self, ctx: &GpuContext, data: &[u8], alignment: u64) -> Result<(vk::Buffer, u64)> {
+        let len = data.len() as u64;
+        let placed = self.recording.last()
+            .and_then(|chunk| chunk_offset(chunk.cursor, chunk.size, len, alignment));
+        let offset = match placed {
+            Some(offset) => offset,
+            None => {
+                let chunk = match self.free_chunks.iter().position(|chunk| chunk.size >= len) {
+                    Some(index) => self.free_chunks.swap_remove(index),
+                    None => create_chunk(ctx, chunk_size_for(len, self.chunk_size))?,
+                };
+                self.recording.push(chunk);
+                0
+            }
+        };
+
+        let chunk = self.recording.last_mut().unwrap();
+        let mapped_ptr = chunk.allocation.mapped_ptr()
+            .ok_or_else(|| engine_err!("galaxy3d::vulkan", "Staging chunk is not mapped"))?
+            .as_ptr() as *mut u8;
+        std::ptr::copy_nonoverlapping(data.as_ptr(), mapped_ptr.add(offset as usize), data.len());
+        chunk.cursor = offset + len;
+        Ok((chunk.buffer, offset))
+    }
+
+    /// Keep a chunk no longer read by the GPU, or destroy it
+    unsafe fn return_chunk(&mut self, ctx: &GpuContext, mut chunk: StagingChunk) {
+        if chunk.size == self.chunk_size && self.free_chunks.len() < MAX_FREE_CHUNKS {
+            chunk.cursor = 0;
+            self.free_chunks.push(chunk);
+        } else {
+            destroy_chunk(ctx, chunk);
+        }
+    }
+
+    /// Destroy every chunk and fence. The device must be idle and
+    /// `recycle` called, so that no upload is in flight.
+    pub(crate) unsafe fn destroy(&mut self, ctx: &GpuContext) {
+        if !self.in_flight.is_empty() {
+            engine_warn!("galaxy3d::vulkan", "StagingBelt destroyed with {} upload(s) in flight", self.in_flight.len());
+        }
+        for upload in self.in_flight.drain(..) {
+            ctx.device.destroy_fence(upload.fence, None);
+            free_command_buffer(ctx, upload.command_buffer);
+            for chunk in upload.chunks {
+                destroy_chunk(ctx, chunk);
+            }
+        }
+        for chunk in self.free_chunks.drain(..).chain(self.recording.drain(..)) {
+            destroy_chunk(ctx, chunk);
+        }
+        for fence in self.free_fences.drain(..) {
+            ctx.device.destroy_fence(fence, None);
+        }
+    }
+}
+
+/// Upload being recorded: holds the belt locked until submitted or dropped.
+///
+/// Dropping it without `submit()` discards the recorded commands and
+/// returns the chunks it filled.
+pub(crate) struct Upload<'a> {
+    ctx: &'a GpuContext,
+    belt: MutexGuard<'a, StagingBelt>,
+    command_buffer: vk::CommandBuffer,
+    submitted: bool,
+}
+
+impl<'a> Upload<'a> {
+    /// Recycle completed uploads and begin a command buffer from the
+    /// upload command pool
+    pub(crate) unsafe fn begin(ctx: &'a GpuContext) -> Result<Self> {
+        let mut belt = ctx.staging_belt.lock().unwrap();
+        belt.recycle(ctx, false)?;
+
+        let command_buffer = {
+            let command_pool = ctx.upload_command_pool.lock().unwrap();
+            let allocate_info = vk::CommandBufferAllocateInfo::default()
+                .command_pool(*command_pool)
+                .level(vk::CommandBufferLevel::PRIMARY)
+                .command_buffer_count(1);
+            ctx.device.allocate_command_buffers(&allocate_info)
+                .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to allocate upload command buffer: {:?}", e))?[0]
+        };
+
+        // Owns the command buffer from here: freed on error by Drop
+        let upload = Self { ctx, belt, command_buffer, submitted: false };
+
+        let begin_info = vk::CommandBufferBeginInfo::default()
+            .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+        ctx.device.begin_command_buffer(command_buffer, &begin_info)
+            .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to begin upload command buffer: {:?}", e))?;
+
+        Ok(upload)
+    }
+
+    /// Command buffer the copies and barriers are recorded into
+    pub(crate) fn command_buffer(&self) -> vk::CommandBuffer {
+        self.command_buffer
+    }
+
+    /// Copy `data` into staging memory.
+    ///
+    /// Returns the staging buffer and offset to copy from. Image copies
+    /// need an `alignment` multiple of both 4 and the texel size.
+    pub(crate) unsafe fn stage(&mut self, data: &[u8], alignment: u64) -> Result<(vk::Buffer, u64)> {
+        self.belt.stage(self.ctx, data, alignment)
+    }
+
+    /// Submit the upload with its fence, without waiting for it.
+    ///
+    /// Later submissions on the graphics queue execute after it; resources
+    /// dropped from now on wait for it through the deletion queue.
+    pub(crate) unsafe fn submit(mut self) -> Result<()> {
+        let device = &self.ctx.device;
+        device.end_command_buffer(self.command_buffer)
+            .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to end upload command buffer: {:?}", e))?;
+
+        let fence = match self.belt.free_fences.pop() {
+            Some(fence) => fence,
+            None => device.create_fence(&vk::FenceCreateInfo::default(), None)
+                .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to create upload fence: {:?}", e))?,
+        };
+
+        self.ctx.deletion_queue.record_submit();
+        if let Err(e) = crate::vulkan_sync::submit_command_buffers(
+            device,
+            self.ctx.graphics_queue,
+            &[self.command_buffer],
+            &[],
+            &[],
+            fence,
+        ) {
+            self.belt.free_fences.push(fence);
+            return Err(e);
+        }
+
+        let chunks = std::mem::take(&mut self.belt.recording);
+        self.belt.in_flight.push_back(InFlightUpload {
+            fence,
+            command_buffer: self.command_buffer,
+            chunks,
+        });
+        self.submitted = true;
+        Ok(())
+    }
+}
+
+impl Drop for Upload<'_> {
+    fn drop(&mut self) {
+        if self.submitted {
+            return;
+        }
+        unsafe {
+            free_command_buffer(self.ctx, self.command_buffer);
+            for chunk in std::mem::take(&mut self.belt.recording) {
+                self.belt.return_chunk(self.ctx, chunk);
+            }
+        }
+    }
+}
+
+unsafe fn free_command_buffer(ctx: &GpuContext, command_buffer: vk::CommandBuffer) {
+    let command_pool = ctx.upload_command_pool.lock().unwrap();
+    ctx.device.free_command_buffers(*command_pool, &[command_buffer]);
+}
+
+unsafe fn create_chunk(ctx: &GpuContext, size: u64) -> Result<StagingChunk> {
+    let buffer_create_info = vk::BufferCreateInfo::default()
+        .size(size)
+        .usage(vk::BufferUsageFlags::TRANSFER_SRC)
+        .sharing_mode(vk::SharingMode::EXCLUSIVE);
+
+    let buffer = ctx.device.create_buffer(&buffer_create_info, None)
+        .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to create staging chunk: {:?}", e))?;
+
+    let requirements = ctx.device.get_buffer_memory_requirements(buffer);
+
+    let allocation = match ctx.allocator.lock().unwrap().allocate(&AllocationCreateDesc {
+        name: "staging_chunk",
+        requirements,
+        location: gpu_allocator::MemoryLocation::CpuToGpu,
+        linear: true,
+        allocation_scheme: AllocationScheme::GpuAllocatorManaged,
+    }) {
+        Ok(allocation) => allocation,
+        Err(_) => {
+            ctx.device.destroy_buffer(buffer, None);
+            let size_mb = size as f64 / (1024.0 * 1024.0);
+            engine_error!("galaxy3d::vulkan", "Out of GPU memory for staging chunk ({:.2} MB)", size_mb);
+            return Err(Error::OutOfMemory);
+        }
+    };
+
+    if let Err(e) = ctx.device.bind_buffer_memory(buffer, allocation.memory(), allocation.offset()) {
+        destroy_chunk(ctx, StagingChunk { buffer, allocation, size, cursor: 0 });
+        return Err(engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to bind staging chunk memory: {:?}", e));
+    }
+
+    Ok(StagingChunk { buffer, allocation, size, cursor: 0 })
+}
+
+unsafe fn destroy_chunk(ctx: &GpuContext, chunk: StagingChunk) {
+    ctx.device.destroy_buffer(chunk.buffer, None);
+    if ctx.allocator.lock().unwrap().free(chunk.allocation).is_err() {
+        engine_warn!("galaxy3d::vulkan", "Failed to free staging chunk allocation");
+    }
+}
+
+#[cfg(test)]
+#[path = "vulkan_staging_belt_tests.rs"]
+mod tests;
@@ -0,0 +1,23 @@
+use super::*;
+
+#[test]
+fn test_chunk_offset_aligns_cursor() {
+    assert_eq!(chunk_offset(0, 1024, 16, 4), Some(0));
+    assert_eq!(chunk_offset(10, 1024, 16, 4), Some(12));
+    assert_eq!(chunk_offset(10, 1024, 16, 8), Some(16));
+    assert_eq!(chunk_offset(10, 1024, 16, 0), Some(10));
+}
+
+#[test]
+fn test_chunk_offset_rejects_overflow() {
+    assert_eq!(chunk_offset(1000, 1024, 24, 4), Some(1000));
+    assert_eq!(chunk_offset(1001, 1024, 24, 4), None);
+    assert_eq!(chunk_offset(0, 1024, 2048, 4), None);
+    assert_eq!(chunk_offset(u64::MAX - 3, u64::MAX, 8, 4), None);
+}
+
+#[test]
+fn test_oversized_data_gets_dedicated_chunk() {
+    assert_eq!(chunk_size_for(100, STAGING_CHUNK_SIZE), STAGING_CHUNK_SIZE);
+    assert_eq!(chunk_size_for(STAGING_CHUNK_SIZE + 1, STAGING_CHUNK_SIZE), STAGING_CHUNK_SIZE + 1);
+}
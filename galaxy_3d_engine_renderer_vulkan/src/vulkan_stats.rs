@@ -0,0 +1,86 @@
+/// DeviceCounters - statistics shared by the Vulkan device, its command
+/// lists and its resources
+///
+/// Command lists count draws, triangles and pipeline binds while recording;
+/// the device counts submits and descriptor set allocations; buffers and
+/// textures add their allocation size on creation and remove it on drop.
+/// All counters are relaxed atomics: they are only read for display and
+/// never order other memory accesses.
+
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use galaxy_3d_engine::galaxy3d::render::GraphicsDeviceStats;
+
+#[derive(Default)]
+pub(crate) struct DeviceCounters {
+    draw_calls: AtomicU32,
+    triangles: AtomicU32,
+    pipeline_binds: AtomicU32,
+    descriptor_sets_allocated: AtomicU32,
+    submits: AtomicU32,
+    buffer_memory: AtomicU64,
+    texture_memory: AtomicU64,
+}
+
+impl DeviceCounters {
+    /// Count one draw call assembling `triangles` triangles
+    pub(crate) fn record_draw(&self, triangles: u32) {
+        self.draw_calls.fetch_add(1, Ordering::Relaxed);
+        self.triangles.fetch_add(triangles, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_pipeline_bind(&self) {
+        self.pipeline_binds.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_descriptor_set_allocation(&self) {
+        self.descriptor_sets_allocated.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_submit(&self) {
+        self.submits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_buffer_memory(&self, bytes: u64) {
+        self.buffer_memory.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub(crate) fn remove_buffer_memory(&self, bytes: u64) {
+        self.buffer_memory.fetch_sub(bytes, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_texture_memory(&self, bytes: u64) {
+        self.texture_memory.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub(crate) fn remove_texture_memory(&self, bytes: u64) {
+        self.texture_memory.fetch_sub(bytes, Ordering::Relaxed);
+    }
+
+    /// Reset the per-frame counters (memory figures are kept)
+    pub(crate) fn reset_frame(&self) {
+        self.draw_calls.store(0, Ordering::Relaxed);
+        self.triangles.store(0, Ordering::Relaxed);
+        self.pipeline_binds.store(0, Ordering::Relaxed);
+        self.descriptor_sets_allocated.store(0, Ordering::Relaxed);
+        self.submits.store(0, Ordering::Relaxed);
+    }
+
+    /// Snapshot of the counters; allocator-wide figures are left to the
+    /// caller
+    pub(crate) fn snapshot(&self) -> GraphicsDeviceStats {
+        GraphicsDeviceStats {
+            draw_calls: self.draw_calls.load(Ordering::Relaxed),
+            triangles: self.triangles.load(Ordering::Relaxed),
+            pipeline_binds: self.pipeline_binds.load(Ordering::Relaxed),
+            descriptor_sets_allocated: self.descriptor_sets_allocated.load(Ordering::Relaxed),
+            submits: self.submits.load(Ordering::Relaxed),
+            buffer_memory: self.buffer_memory.load(Ordering::Relaxed),
+            texture_memory: self.texture_memory.load(Ordering::Relaxed),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "vulkan_stats_tests.rs"]
+mod tests;
@@ -0,0 +1,25 @@
+use super::*;
+
+#[test]
+fn test_counters_accumulate_until_frame_reset() {
+    let counters = DeviceCounters::default();
+    counters.record_draw(12);
+    counters.record_draw(3);
+    counters.record_pipeline_bind();
+    counters.record_descriptor_set_allocation();
+    counters.record_submit();
+    counters.add_buffer_memory(1024);
+    counters.add_texture_memory(4096);
+    counters.remove_texture_memory(1024);
+
+    let stats = counters.snapshot();
+    assert_eq!((stats.draw_calls, stats.triangles), (2, 15));
+    assert_eq!((stats.pipeline_binds, stats.descriptor_sets_allocated, stats.submits), (1, 1, 1));
+    assert_eq!((stats.buffer_memory, stats.texture_memory), (1024, 3072));
+
+    // Memory describes live resources: not reset per frame
+    counters.reset_frame();
+    let stats = counters.snapshot();
+    assert_eq!((stats.draw_calls, stats.triangles, stats.submits), (0, 0, 0));
+    assert_eq!((stats.buffer_memory, stats.texture_memory), (1024, 3072));
+}
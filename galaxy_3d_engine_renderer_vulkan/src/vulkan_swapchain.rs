@@ -7,12 +7,13 @@ use galaxy_3d_engine::galaxy3d::render::{
     Texture as RendererTexture,
     TextureFormat,
 };
-use galaxy_3d_engine::{engine_error, engine_err, engine_bail};
+use galaxy_3d_engine::{engine_error, engine_err, engine_bail, engine_backend_err};
 use ash::vk;
 use std::sync::Arc;
 
 use crate::vulkan_command_list::CommandList as VulkanCommandList;
 use crate::vulkan_texture::Texture as VulkanTexture;
+use crate::vulkan_queue_sharing::QueueSharing;
 
 /// Vulkan swapchain implementation
 ///
@@ -26,6 +27,8 @@ pub struct Swapchain {
 
     /// Present queue
     present_queue: vk::Queue,
+    /// Sharing mode of the swapchain images (graphics + present families)
+    sharing: QueueSharing,
 
     /// Surface
     surface: vk::SurfaceKHR,
@@ -63,6 +66,8 @@ impl Swapchain {
     /// * `surface` - Window surface
     /// * `surface_loader` - Surface loader
     /// * `present_queue` - Queue for presenting
+    /// * `queue_families` - Families using the images (graphics and present):
+    ///   the images are shared concurrently when they differ
     /// * `width` - Initial width
     /// * `height` - Initial height
     pub fn new(
@@ -72,6 +77,7 @@ impl Swapchain {
         surface: vk::SurfaceKHR,
         surface_loader: ash::khr::surface::Instance,
         present_queue: vk::Queue,
+        queue_families: &[u32],
         _width: u32,
         _height: u32,
     ) -> Result<Self> {
@@ -100,7 +106,8 @@ impl Swapchain {
             let swapchain_extent = surface_capabilities.current_extent;
 
             // Create swapchain
-            let swapchain_create_info = vk::SwapchainCreateInfoKHR::default()
+            let sharing = QueueSharing::new(queue_families);
+            let swapchain_create_info = sharing.swapchain_info(vk::SwapchainCreateInfoKHR::default()
                 .surface(surface)
                 .min_image_count(3.min(surface_capabilities.max_image_count))
                 .image_format(surface_format.format)
@@ -108,10 +115,9 @@ impl Swapchain {
                 .image_extent(swapchain_extent)
                 .image_array_layers(1)
                 .image_usage(vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::TRANSFER_DST)
-                .image_sharing_mode(vk::SharingMode::EXCLUSIVE)
                 .pre_transform(surface_capabilities.current_transform)
                 .composite_alpha(vk::CompositeAlphaFlagsKHR::OPAQUE)
-                .present_mode(vk::PresentModeKHR::FIFO);
+                .present_mode(vk::PresentModeKHR::FIFO));
 
             let swapchain_loader = ash::khr::swapchain::Device::new(instance, &device);
             let swapchain = swapchain_loader
@@ -191,6 +197,7 @@ impl Swapchain {
                 device,
                 physical_device,
                 present_queue,
+                sharing,
                 surface,
                 surface_loader,
                 swapchain,
@@ -307,6 +314,21 @@ impl RendererSwapchain for Swapchain {
             let dst_width = self.swapchain_extent.width;
             let dst_height = self.swapchain_extent.height;
 
+            if VulkanCommandList::is_depth_format(src_info.format) {
+                engine_bail!("galaxy3d::vulkan",
+                    "record_present_blit: source format {:?} is not a color format",
+                    src_info.format);
+            }
+
+            // vkCmdBlitImage converts HDR float sources to the surface
+            // format (clamping to [0, 1]). Same-size blits are plain copies:
+            // NEAREST avoids filtering, LINEAR is only needed when scaling.
+            let filter = if src_info.width == dst_width && src_info.height == dst_height {
+                vk::Filter::NEAREST
+            } else {
+                vk::Filter::LINEAR
+            };
+
             // Transition src: COLOR_ATTACHMENT_OPTIMAL → TRANSFER_SRC_OPTIMAL
             // Transition dst: UNDEFINED → TRANSFER_DST_OPTIMAL
             // Both batched into a single `vkCmdPipelineBarrier2` call.
@@ -374,7 +396,7 @@ impl RendererSwapchain for Swapchain {
                 dst_image,
                 vk::ImageLayout::TRANSFER_DST_OPTIMAL,
                 &[region],
-                vk::Filter::LINEAR,
+                filter,
             );
 
             // Transition dst: TRANSFER_DST_OPTIMAL → PRESENT_SRC_KHR
@@ -429,7 +451,7 @@ impl RendererSwapchain for Swapchain {
         unsafe {
             // Wait for device to be idle
             self.device.device_wait_idle()
-                .map_err(|e| engine_err!("galaxy3d::vulkan", "Failed to wait idle before swapchain recreate: {:?}", e))?;
+                .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "Failed to wait idle before swapchain recreate: {:?}", e))?;
 
             // Query surface capabilities to get the real extent
             let surface_capabilities = self.surface_loader
@@ -475,7 +497,7 @@ impl RendererSwapchain for Swapchain {
 
             // Recreate swapchain
             let old_swapchain = self.swapchain;
-            let swapchain_create_info = vk::SwapchainCreateInfoKHR::default()
+            let swapchain_create_info = self.sharing.swapchain_info(vk::SwapchainCreateInfoKHR::default()
                 .surface(self.surface)
                 .min_image_count(image_count as u32)
                 .image_format(self.swapchain_format)
@@ -483,12 +505,11 @@ impl RendererSwapchain for Swapchain {
                 .image_extent(extent)
                 .image_array_layers(1)
                 .image_usage(vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::TRANSFER_DST)
-                .image_sharing_mode(vk::SharingMode::EXCLUSIVE)
                 .pre_transform(surface_capabilities.current_transform)
                 .composite_alpha(vk::CompositeAlphaFlagsKHR::OPAQUE)
                 .present_mode(vk::PresentModeKHR::FIFO)
                 .clipped(true)
-                .old_swapchain(old_swapchain);
+                .old_swapchain(old_swapchain));
 
             let swapchain = self.swapchain_loader
                 .create_swapchain(&swapchain_create_info, None)
@@ -8,7 +8,7 @@
 
 use galaxy_3d_engine::galaxy3d::Result;
 use galaxy_3d_engine::galaxy3d::render::AccessType;
-use galaxy_3d_engine::engine_err;
+use galaxy_3d_engine::{engine_err, engine_backend_err};
 use ash::vk;
 
 /// Map an engine `AccessType` to the matching `VkPipelineStageFlags2` +
@@ -63,9 +63,34 @@ pub(crate) fn access_type_to_stage_access_2(
             vk::AccessFlags2::TRANSFER_WRITE,
         ),
         AccessType::RayTracingRead => (
-            vk::PipelineStageFlags2::FRAGMENT_SHADER,
+            vk::PipelineStageFlags2::RAY_TRACING_SHADER_KHR,
             vk::AccessFlags2::SHADER_READ,
         ),
+        AccessType::RayTracingWrite => (
+            vk::PipelineStageFlags2::RAY_TRACING_SHADER_KHR,
+            vk::AccessFlags2::SHADER_WRITE,
+        ),
+    }
+}
+
+/// Map an AccessType to the corresponding Vulkan image layout.
+pub(crate) fn access_type_to_layout(access: AccessType) -> vk::ImageLayout {
+    match access {
+        AccessType::ColorAttachmentWrite | AccessType::ColorAttachmentRead
+            => vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL,
+        AccessType::DepthStencilWrite
+            => vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL,
+        AccessType::DepthStencilReadOnly
+            => vk::ImageLayout::DEPTH_STENCIL_READ_ONLY_OPTIMAL,
+        AccessType::FragmentShaderRead | AccessType::VertexShaderRead
+        | AccessType::ComputeRead | AccessType::RayTracingRead
+            => vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+        AccessType::ComputeWrite | AccessType::RayTracingWrite
+            => vk::ImageLayout::GENERAL,
+        AccessType::TransferRead
+            => vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+        AccessType::TransferWrite
+            => vk::ImageLayout::TRANSFER_DST_OPTIMAL,
     }
 }
 
@@ -251,5 +276,5 @@ pub(crate) unsafe fn submit_command_buffers(
 
     device
         .queue_submit2(queue, &[submit_info], fence)
-        .map_err(|e| engine_err!("galaxy3d::vulkan", "queue_submit2 failed: {:?}", e))
+        .map_err(|e| engine_backend_err!("galaxy3d::vulkan", e.as_raw(), "queue_submit2 failed: {:?}", e))
 }
@@ -1,15 +1,17 @@
 /// Texture - Vulkan implementation of RendererTexture trait
 
-use galaxy_3d_engine::galaxy3d::{Result, Error};
+use galaxy_3d_engine::galaxy3d::Result;
 use galaxy_3d_engine::galaxy3d::render::Texture as RendererTexture;
 use galaxy_3d_engine::galaxy3d::render::TextureInfo;
 use galaxy_3d_engine::galaxy3d::utils::SlotAllocator;
-use galaxy_3d_engine::{engine_error, engine_bail, engine_err, engine_warn_err};
+use galaxy_3d_engine::engine_bail;
 use ash::vk;
 use gpu_allocator::vulkan::Allocation;
 use std::sync::{Arc, Mutex};
 
 use crate::vulkan_context::GpuContext;
+use crate::vulkan_deletion_queue::PendingDestroy;
+use crate::vulkan_staging_belt::Upload;
 
 /// Vulkan texture implementation
 pub struct Texture {
@@ -19,6 +21,8 @@ pub struct Texture {
     pub(crate) image: vk::Image,
     /// Vulkan image view
     pub(crate) view: vk::ImageView,
+    /// One storage view per mip level (`TextureUsage::Storage` only)
+    pub(crate) mip_views: Vec<vk::ImageView>,
     /// GPU memory allocation
     pub(crate) allocation: Option<Allocation>,
     /// Read-only texture properties
@@ -38,10 +42,12 @@ impl Texture {
         allocation: Allocation,
         info: TextureInfo,
     ) -> Self {
+        ctx.counters.add_texture_memory(allocation.size());
         Self {
             ctx,
             image,
             view,
+            mip_views: Vec::new(), // Set after creation for storage textures
             allocation: Some(allocation),
             info,
             bindless_index: 0, // Set by BindlessState after creation
@@ -73,67 +79,22 @@ impl RendererTexture for Texture {
         // Calculate expected size for this mip level
         let mip_width = (self.info.width >> mip_level).max(1);
         let mip_height = (self.info.height >> mip_level).max(1);
-        let expected_size = (mip_width * mip_height * self.info.format.bytes_per_pixel()) as usize;
+        let mip_depth = (self.info.depth >> mip_level).max(1);
+        let expected_size = (mip_width * mip_height * mip_depth * self.info.format.bytes_per_pixel()) as usize;
 
         if data.len() != expected_size {
-            engine_bail!("galaxy3d::vulkan", "update: data size {} doesn't match expected {} for mip level {} ({}x{})",
-                data.len(), expected_size, mip_level, mip_width, mip_height);
+            engine_bail!("galaxy3d::vulkan", "update: data size {} doesn't match expected {} for mip level {} ({}x{}x{})",
+                data.len(), expected_size, mip_level, mip_width, mip_height, mip_depth);
         }
 
         unsafe {
             let device = &self.ctx.device;
-            let buffer_size = data.len() as u64;
 
-            // Create staging buffer
-            let staging_buffer_create_info = vk::BufferCreateInfo::default()
-                .size(buffer_size)
-                .usage(vk::BufferUsageFlags::TRANSFER_SRC)
-                .sharing_mode(vk::SharingMode::EXCLUSIVE);
-
-            let staging_buffer = device.create_buffer(&staging_buffer_create_info, None)
-                .map_err(|e| engine_err!("galaxy3d::vulkan", "update: failed to create staging buffer: {:?}", e))?;
-
-            let staging_requirements = device.get_buffer_memory_requirements(staging_buffer);
-
-            let staging_allocation = self.ctx.allocator.lock().unwrap()
-                .allocate(&gpu_allocator::vulkan::AllocationCreateDesc {
-                    name: "texture_layer_staging",
-                    requirements: staging_requirements,
-                    location: gpu_allocator::MemoryLocation::CpuToGpu,
-                    linear: true,
-                    allocation_scheme: gpu_allocator::vulkan::AllocationScheme::GpuAllocatorManaged,
-                })
-                .map_err(|_e| {
-                    engine_error!("galaxy3d::vulkan", "update: out of GPU memory for staging buffer");
-                    Error::OutOfMemory
-                })?;
-
-            device.bind_buffer_memory(staging_buffer, staging_allocation.memory(), staging_allocation.offset())
-                .map_err(|e| engine_err!("galaxy3d::vulkan", "update: failed to bind staging buffer memory: {:?}", e))?;
-
-            // Copy data to staging buffer
-            let mapped_ptr = staging_allocation.mapped_ptr()
-                .ok_or_else(|| engine_err!("galaxy3d::vulkan", "Texture update: staging buffer is not mapped"))?
-                .as_ptr() as *mut u8;
-            std::ptr::copy_nonoverlapping(data.as_ptr(), mapped_ptr, data.len());
-
-            // Get command pool and allocate command buffer
-            let command_pool = *self.ctx.upload_command_pool.lock().unwrap();
-
-            let command_buffer_allocate_info = vk::CommandBufferAllocateInfo::default()
-                .command_pool(command_pool)
-                .level(vk::CommandBufferLevel::PRIMARY)
-                .command_buffer_count(1);
-
-            let command_buffers = device.allocate_command_buffers(&command_buffer_allocate_info)
-                .map_err(|e| engine_err!("galaxy3d::vulkan", "update: failed to allocate command buffer: {:?}", e))?;
-            let command_buffer = command_buffers[0];
-
-            let begin_info = vk::CommandBufferBeginInfo::default()
-                .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
-
-            device.begin_command_buffer(command_buffer, &begin_info)
-                .map_err(|e| engine_err!("galaxy3d::vulkan", "update: failed to begin command buffer: {:?}", e))?;
+            // Copy data to the staging belt (offset: multiple of 4 and of the texel size)
+            let mut upload = Upload::begin(&self.ctx)?;
+            let command_buffer = upload.command_buffer();
+            let staging_alignment = (self.info.format.bytes_per_pixel() as u64).max(4);
+            let (staging_buffer, staging_offset) = upload.stage(data, staging_alignment)?;
 
             // Transition single layer/mip: SHADER_READ_ONLY → TRANSFER_DST
             let sub_range = vk::ImageSubresourceRange {
@@ -145,7 +106,7 @@ impl RendererTexture for Texture {
             };
 
             let barrier_to_transfer = vk::ImageMemoryBarrier2::default()
-                .src_stage_mask(vk::PipelineStageFlags2::FRAGMENT_SHADER)
+                .src_stage_mask(vk::PipelineStageFlags2::ALL_COMMANDS)
                 .src_access_mask(vk::AccessFlags2::SHADER_READ)
                 .dst_stage_mask(vk::PipelineStageFlags2::COPY)
                 .dst_access_mask(vk::AccessFlags2::TRANSFER_WRITE)
@@ -164,7 +125,7 @@ impl RendererTexture for Texture {
 
             // Copy buffer to image layer/mip
             let region = vk::BufferImageCopy::default()
-                .buffer_offset(0)
+                .buffer_offset(staging_offset)
                 .buffer_row_length(0)
                 .buffer_image_height(0)
                 .image_subresource(vk::ImageSubresourceLayers {
@@ -177,7 +138,7 @@ impl RendererTexture for Texture {
                 .image_extent(vk::Extent3D {
                     width: mip_width,
                     height: mip_height,
-                    depth: 1,
+                    depth: mip_depth,
                 });
 
             device.cmd_copy_buffer_to_image(
@@ -192,7 +153,7 @@ impl RendererTexture for Texture {
             let barrier_to_shader = vk::ImageMemoryBarrier2::default()
                 .src_stage_mask(vk::PipelineStageFlags2::COPY)
                 .src_access_mask(vk::AccessFlags2::TRANSFER_WRITE)
-                .dst_stage_mask(vk::PipelineStageFlags2::FRAGMENT_SHADER)
+                .dst_stage_mask(vk::PipelineStageFlags2::ALL_COMMANDS)
                 .dst_access_mask(vk::AccessFlags2::SHADER_READ)
                 .old_layout(vk::ImageLayout::TRANSFER_DST_OPTIMAL)
                 .new_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
@@ -207,51 +168,22 @@ impl RendererTexture for Texture {
                 &[barrier_to_shader],
             );
 
-            // End recording, submit, and wait
-            device.end_command_buffer(command_buffer)
-                .map_err(|e| engine_err!("galaxy3d::vulkan", "update: failed to end command buffer: {:?}", e))?;
-
-            crate::vulkan_sync::submit_command_buffers(
-                device,
-                self.ctx.graphics_queue,
-                &[command_buffer],
-                &[],
-                &[],
-                vk::Fence::null(),
-            )?;
-
-            device.queue_wait_idle(self.ctx.graphics_queue)
-                .map_err(|e| engine_err!("galaxy3d::vulkan", "update: failed to wait for completion: {:?}", e))?;
-
-            // Clean up staging buffer (command buffer will be reset automatically)
-            device.free_command_buffers(command_pool, &[command_buffer]);
-            device.destroy_buffer(staging_buffer, None);
-            self.ctx.allocator.lock().unwrap().free(staging_allocation)
-                .map_err(|_e| engine_warn_err!("galaxy3d::vulkan", "Texture update: failed to free staging allocation"))?;
-
-            Ok(())
+            // Submit without waiting: later submissions execute after the copy
+            upload.submit()
         }
     }
 }
 
 impl Drop for Texture {
     fn drop(&mut self) {
-        // Free the bindless index back to the allocator
-        if let Some(allocator) = self.bindless_allocator.take() {
-            allocator.lock().unwrap().free(self.bindless_index);
-        }
-
-        unsafe {
-            // Destroy image view
-            self.ctx.device.destroy_image_view(self.view, None);
-
-            // Free GPU memory
-            if let Some(allocation) = self.allocation.take() {
-                self.ctx.allocator.lock().unwrap().free(allocation).ok();
-            }
-
-            // Destroy image
-            self.ctx.device.destroy_image(self.image, None);
-        }
+        // Deferred (bindless index included) while a submitted command
+        // buffer may still read it
+        self.ctx.release(PendingDestroy::Texture {
+            image: self.image,
+            view: self.view,
+            mip_views: std::mem::take(&mut self.mip_views),
+            allocation: self.allocation.take(),
+            bindless: self.bindless_allocator.take().map(|allocator| (allocator, self.bindless_index)),
+        });
     }
 }
\ No newline at end of file
@@ -9,7 +9,9 @@ use galaxy_3d_engine::galaxy3d::GraphicsDevice;
 use galaxy_3d_engine::galaxy3d::render::{
     TextureDesc, TextureFormat, TextureUsage, TextureType, MipmapMode, TextureData, SampleCount,
     BufferDesc, BufferUsage, ShaderDesc, ShaderStage,
-    Config,
+    BindingGroupLayoutDesc, BindingSlotDesc, BindingType, BindingResource, ShaderStageFlags,
+    QueryPoolDesc, QueryType,
+    Config, DescriptorPoolConfig,
 };
 use galaxy_3d_engine_renderer_vulkan::galaxy3d::VulkanGraphicsDevice;
 use winit::event_loop::EventLoop;
@@ -40,6 +42,7 @@ fn test_vulkan_create_simple_texture() {
     let desc = TextureDesc {
         width: 256,
         height: 256,
+        depth: 1,
         format: TextureFormat::R8G8B8A8_UNORM,
         usage: TextureUsage::Sampled,
         array_layers: 1,
@@ -47,6 +50,7 @@ fn test_vulkan_create_simple_texture() {
         texture_type: TextureType::Tex2D,
         sample_count: SampleCount::S1,
         data: None,
+        debug_name: None,
     };
 
     let texture = graphics_device.create_texture(desc).unwrap();
@@ -70,6 +74,7 @@ fn test_vulkan_create_texture_with_data() {
     let desc = TextureDesc {
         width: 4,
         height: 4,
+        depth: 1,
         format: TextureFormat::R8G8B8A8_UNORM,
         usage: TextureUsage::Sampled,
         array_layers: 1,
@@ -77,6 +82,7 @@ fn test_vulkan_create_texture_with_data() {
         texture_type: TextureType::Tex2D,
         sample_count: SampleCount::S1,
         data: Some(TextureData::Single(data)),
+        debug_name: None,
     };
 
     let texture = graphics_device.create_texture(desc).unwrap();
@@ -95,6 +101,7 @@ fn test_vulkan_create_texture_array() {
     let desc = TextureDesc {
         width: 128,
         height: 128,
+        depth: 1,
         format: TextureFormat::R8G8B8A8_UNORM,
         usage: TextureUsage::Sampled,
         array_layers: 4, // 4 layers
@@ -102,6 +109,7 @@ fn test_vulkan_create_texture_array() {
         texture_type: TextureType::Array2D,
         sample_count: SampleCount::S1,
         data: None,
+        debug_name: None,
     };
 
     let texture = graphics_device.create_texture(desc).unwrap();
@@ -119,6 +127,7 @@ fn test_vulkan_create_depth_texture() {
     let desc = TextureDesc {
         width: 512,
         height: 512,
+        depth: 1,
         format: TextureFormat::D32_FLOAT,
         usage: TextureUsage::DepthStencil,
         array_layers: 1,
@@ -126,6 +135,7 @@ fn test_vulkan_create_depth_texture() {
         texture_type: TextureType::Tex2D,
         sample_count: SampleCount::S1,
         data: None,
+        debug_name: None,
     };
 
     let texture = graphics_device.create_texture(desc).unwrap();
@@ -134,6 +144,72 @@ fn test_vulkan_create_depth_texture() {
     assert_eq!(info.format, TextureFormat::D32_FLOAT);
 }
 
+#[test]
+#[ignore] // Requires GPU
+fn test_vulkan_create_3d_texture_with_data() {
+    let (window, _event_loop) = create_test_window();
+    let mut graphics_device = VulkanGraphicsDevice::new(&window, Config::default()).unwrap();
+
+    // 8x8x4 RGBA volume, slice after slice
+    let data = vec![128u8; 8 * 8 * 4 * 4];
+
+    let desc = TextureDesc {
+        width: 8,
+        height: 8,
+        depth: 4,
+        format: TextureFormat::R8G8B8A8_UNORM,
+        usage: TextureUsage::Sampled,
+        array_layers: 1,
+        mipmap: MipmapMode::None,
+        texture_type: TextureType::Tex3D,
+        sample_count: SampleCount::S1,
+        data: Some(TextureData::Single(data.clone())),
+        debug_name: None,
+    };
+
+    let texture = graphics_device.create_texture(desc).unwrap();
+    assert_eq!(texture.info().depth, 4);
+    assert_eq!(texture.info().texture_type, TextureType::Tex3D);
+    texture.update(0, 0, &data).unwrap();
+}
+
+#[test]
+#[ignore] // Requires GPU
+fn test_vulkan_storage_texture_mip_binding() {
+    let (window, _event_loop) = create_test_window();
+    let mut graphics_device = VulkanGraphicsDevice::new(&window, Config::default()).unwrap();
+
+    let desc = TextureDesc {
+        width: 64,
+        height: 64,
+        depth: 1,
+        format: TextureFormat::R16G16B16A16_SFLOAT,
+        usage: TextureUsage::Storage,
+        array_layers: 1,
+        mipmap: MipmapMode::Generate { max_levels: Some(4) },
+        texture_type: TextureType::Tex2D,
+        sample_count: SampleCount::S1,
+        data: None,
+        debug_name: None,
+    };
+    let texture = graphics_device.create_texture(desc).unwrap();
+
+    let layout = BindingGroupLayoutDesc {
+        entries: vec![BindingSlotDesc {
+            binding: 0,
+            binding_type: BindingType::StorageImage,
+            count: 1,
+            stage_flags: ShaderStageFlags::COMPUTE,
+        }],
+    };
+    graphics_device.create_binding_group_from_layout(
+        &layout, 0, &[BindingResource::StorageImage(&*texture, 3)],
+    ).unwrap();
+    assert!(graphics_device.create_binding_group_from_layout(
+        &layout, 0, &[BindingResource::StorageImage(&*texture, 4)],
+    ).is_err());
+}
+
 // ============================================================================
 // BUFFER TESTS
 // ============================================================================
@@ -147,6 +223,7 @@ fn test_vulkan_create_vertex_buffer() {
     let desc = BufferDesc {
         size: 1024,
         usage: BufferUsage::Vertex,
+        debug_name: None,
     };
 
     let buffer = graphics_device.create_buffer(desc).unwrap();
@@ -165,6 +242,7 @@ fn test_vulkan_create_index_buffer() {
     let desc = BufferDesc {
         size: 512,
         usage: BufferUsage::Index,
+        debug_name: None,
     };
 
     let buffer = graphics_device.create_buffer(desc).unwrap();
@@ -187,6 +265,7 @@ fn test_vulkan_create_uniform_buffer() {
     let desc = BufferDesc {
         size: 256,
         usage: BufferUsage::Uniform,
+        debug_name: None,
     };
 
     let buffer = graphics_device.create_buffer(desc).unwrap();
@@ -214,6 +293,7 @@ fn test_vulkan_create_vertex_shader() {
         stage: ShaderStage::Vertex,
         entry_point: "main".to_string(),
         code: &spirv_code,
+        debug_name: None,
     };
 
     // Note: This will likely fail with invalid SPIR-V, but tests the API
@@ -235,6 +315,7 @@ fn test_vulkan_create_fragment_shader() {
         stage: ShaderStage::Fragment,
         entry_point: "main".to_string(),
         code: &spirv_code,
+        debug_name: None,
     };
 
     let _result = graphics_device.create_shader(desc);
@@ -278,6 +359,35 @@ fn test_vulkan_multiple_command_lists() {
     cmd3.end().unwrap();
 }
 
+#[test]
+#[ignore] // Requires GPU
+fn test_vulkan_occlusion_query_results() {
+    let (window, _event_loop) = create_test_window();
+    let mut graphics_device = VulkanGraphicsDevice::new(&window, Config::default()).unwrap();
+
+    let pool = graphics_device.create_query_pool(&QueryPoolDesc {
+        query_type: QueryType::Occlusion,
+        count: 2,
+        debug_name: None,
+    }).unwrap();
+
+    // No draw between begin and end: no sample passes
+    let mut cmd_list = graphics_device.create_command_list().unwrap();
+    cmd_list.begin().unwrap();
+    cmd_list.reset_queries(&pool, 0, 2).unwrap();
+    cmd_list.begin_query(&pool, 0).unwrap();
+    assert!(cmd_list.begin_query(&pool, 1).is_err());
+    cmd_list.end_query(&pool, 0).unwrap();
+    cmd_list.end().unwrap();
+
+    graphics_device.submit(&[&*cmd_list]).unwrap();
+    graphics_device.wait_idle().unwrap();
+
+    assert_eq!(pool.occlusion_results(0, 1).unwrap(), vec![Some(0)]);
+    assert_eq!(pool.occlusion_results(1, 1).unwrap(), vec![None]);
+    assert!(pool.pipeline_statistics(0, 1).is_err());
+}
+
 // ============================================================================
 // RENDERER LIFECYCLE TESTS
 // ============================================================================
@@ -304,6 +414,31 @@ fn test_vulkan_get_stats() {
     assert_eq!(stats.triangles, 0);
 }
 
+#[test]
+#[ignore] // Requires GPU
+fn test_vulkan_descriptor_pool_config_and_usage() {
+    let (window, _event_loop) = create_test_window();
+    let mut config = Config::default();
+    config.descriptor_pools.max_sets = 64;
+    config.transient_descriptor_pools = DescriptorPoolConfig { max_sets: 16, ..Default::default() };
+    let graphics_device = VulkanGraphicsDevice::new(&window, config).unwrap();
+
+    let usage = graphics_device.descriptor_pool_usage();
+    assert_eq!(usage.len(), 2);
+    assert!(!usage[0].transient);
+    assert_eq!(usage[0].max_sets, 64);
+    assert!(usage[1].transient);
+    assert_eq!(usage[1].max_sets, 16);
+    assert_eq!(graphics_device.stats().descriptor_set_capacity, 80);
+
+    graphics_device.reset_transient_pools().unwrap();
+    assert_eq!(graphics_device.stats().transient_descriptor_sets, 0);
+
+    let mut config = Config::default();
+    config.transient_descriptor_pools.max_sets = 0;
+    assert!(VulkanGraphicsDevice::new(&window, config).is_err());
+}
+
 #[test]
 #[ignore] // Requires GPU
 fn test_vulkan_resize() {